@@ -0,0 +1,195 @@
+//! Branch restrictions
+//!
+//! Bitbucket protects branches with one restriction per kind, e.g. `force`
+//! to prevent force pushes. Required builds are a count rather than named
+//! checks, so required checks aren't enforced here.
+
+use anyhow::Result;
+use orchestrate_core::{BranchProtection, ProtectionSetting};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::client::BitbucketClient;
+
+/// Settings Bitbucket branch restrictions can enforce
+pub const SUPPORTED_SETTINGS: [ProtectionSetting; 4] = [
+    ProtectionSetting::RequiredApprovals,
+    ProtectionSetting::DismissStaleReviews,
+    ProtectionSetting::AllowForcePushes,
+    ProtectionSetting::AllowDeletions,
+];
+
+/// Restriction kinds orchestrate manages
+const MANAGED_KINDS: [&str; 4] = [
+    "require_approvals_to_merge",
+    "reset_pullrequest_approvals_on_change",
+    "force",
+    "delete",
+];
+
+#[derive(Debug, Clone, Deserialize)]
+struct BranchRestriction {
+    id: i64,
+    kind: String,
+    pattern: Option<String>,
+    value: Option<u32>,
+}
+
+/// Restriction kinds and values enforcing `protection`
+fn restrictions_for(protection: &BranchProtection) -> Vec<(&'static str, Option<u32>)> {
+    let mut restrictions = Vec::new();
+    if protection.required_approvals > 0 {
+        restrictions.push((
+            "require_approvals_to_merge",
+            Some(protection.required_approvals),
+        ));
+    }
+    if protection.dismiss_stale_reviews {
+        restrictions.push(("reset_pullrequest_approvals_on_change", None));
+    }
+    if !protection.allow_force_pushes {
+        restrictions.push(("force", None));
+    }
+    if !protection.allow_deletions {
+        restrictions.push(("delete", None));
+    }
+    restrictions
+}
+
+fn protection_from(restrictions: &[BranchRestriction]) -> BranchProtection {
+    let has = |kind: &str| restrictions.iter().any(|r| r.kind == kind);
+    BranchProtection {
+        required_approvals: restrictions
+            .iter()
+            .find(|r| r.kind == "require_approvals_to_merge")
+            .and_then(|r| r.value)
+            .unwrap_or_default(),
+        dismiss_stale_reviews: has("reset_pullrequest_approvals_on_change"),
+        allow_force_pushes: !has("force"),
+        allow_deletions: !has("delete"),
+        ..BranchProtection::default()
+    }
+}
+
+impl BitbucketClient {
+    async fn branch_restrictions(&self, branch: &str) -> Result<Vec<BranchRestriction>> {
+        let restrictions: Vec<BranchRestriction> = self
+            .get_all(
+                &format!("branch-restrictions?pattern={}", branch),
+                "list branch restrictions",
+            )
+            .await?;
+        Ok(restrictions
+            .into_iter()
+            .filter(|r| r.pattern.as_deref() == Some(branch))
+            .filter(|r| MANAGED_KINDS.contains(&r.kind.as_str()))
+            .collect())
+    }
+
+    /// Current protection of a branch, or `None` if it has no restrictions
+    pub async fn get_branch_protection(&self, branch: &str) -> Result<Option<BranchProtection>> {
+        let restrictions = self.branch_restrictions(branch).await?;
+        if restrictions.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(protection_from(&restrictions)))
+    }
+
+    /// Create, update, or delete restrictions so a branch matches `protection`
+    pub async fn set_branch_protection(
+        &self,
+        branch: &str,
+        protection: &BranchProtection,
+    ) -> Result<()> {
+        let existing = self.branch_restrictions(branch).await?;
+        let desired = restrictions_for(protection);
+
+        for restriction in &existing {
+            match desired.iter().find(|(kind, _)| *kind == restriction.kind) {
+                None => {
+                    self.delete(
+                        &format!("branch-restrictions/{}", restriction.id),
+                        "delete branch restriction",
+                    )
+                    .await?
+                }
+                Some((kind, value)) if *value != restriction.value => {
+                    let _: serde_json::Value = self
+                        .put(
+                            &format!("branch-restrictions/{}", restriction.id),
+                            &json!({ "kind": kind, "value": value }),
+                            "update branch restriction",
+                        )
+                        .await?;
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (kind, value) in desired {
+            if existing.iter().any(|r| r.kind == kind) {
+                continue;
+            }
+            let _: serde_json::Value = self
+                .post(
+                    "branch-restrictions",
+                    &json!({
+                        "kind": kind,
+                        "pattern": branch,
+                        "branch_match_kind": "glob",
+                        "value": value,
+                    }),
+                    "create branch restriction",
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restrictions_round_trip() {
+        let protection = BranchProtection {
+            required_approvals: 2,
+            dismiss_stale_reviews: true,
+            ..BranchProtection::default()
+        };
+        let kinds = restrictions_for(&protection);
+        assert_eq!(
+            kinds,
+            vec![
+                ("require_approvals_to_merge", Some(2)),
+                ("reset_pullrequest_approvals_on_change", None),
+                ("force", None),
+                ("delete", None),
+            ]
+        );
+
+        let restrictions: Vec<BranchRestriction> = kinds
+            .iter()
+            .enumerate()
+            .map(|(id, (kind, value))| BranchRestriction {
+                id: id as i64,
+                kind: kind.to_string(),
+                pattern: Some("main".to_string()),
+                value: *value,
+            })
+            .collect();
+        let actual = protection_from(&restrictions);
+        assert!(protection
+            .drift(Some(&actual), &SUPPORTED_SETTINGS)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_unrestricted_branch_allows_force_pushes() {
+        let protection = protection_from(&[]);
+        assert!(protection.allow_force_pushes);
+        assert!(protection.allow_deletions);
+        assert_eq!(restrictions_for(&BranchProtection::unprotected()), vec![]);
+    }
+}
@@ -0,0 +1,280 @@
+//! Bitbucket Cloud REST API client
+//!
+//! Authenticates with a repository or workspace access token, or with a
+//! username and app password.
+
+use anyhow::Result;
+use orchestrate_core::{RepoProvider, Repository};
+use secrecy::{ExposeSecret, SecretString};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Base URL of the Bitbucket Cloud API
+pub const DEFAULT_BASE_URL: &str = "https://api.bitbucket.org/2.0";
+
+/// Default timeout for API requests
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Bitbucket credentials
+#[derive(Clone)]
+pub enum BitbucketAuth {
+    /// Repository, project, or workspace access token
+    Token(SecretString),
+    /// Username and app password
+    AppPassword {
+        username: String,
+        password: SecretString,
+    },
+}
+
+impl BitbucketAuth {
+    /// Credentials from `BITBUCKET_ACCESS_TOKEN`, or from
+    /// `BITBUCKET_USERNAME` and `BITBUCKET_APP_PASSWORD`
+    pub fn from_env() -> Result<Self> {
+        if let Ok(token) = std::env::var("BITBUCKET_ACCESS_TOKEN") {
+            return Ok(Self::Token(SecretString::new(token)));
+        }
+        match (
+            std::env::var("BITBUCKET_USERNAME"),
+            std::env::var("BITBUCKET_APP_PASSWORD"),
+        ) {
+            (Ok(username), Ok(password)) => Ok(Self::AppPassword {
+                username,
+                password: SecretString::new(password),
+            }),
+            _ => anyhow::bail!(
+                "Bitbucket credentials not set: set BITBUCKET_ACCESS_TOKEN, or BITBUCKET_USERNAME and BITBUCKET_APP_PASSWORD"
+            ),
+        }
+    }
+}
+
+/// Bitbucket Cloud client for one repository
+#[derive(Clone)]
+pub struct BitbucketClient {
+    /// Workspace ID
+    pub workspace: String,
+    /// Repository slug
+    pub repo_slug: String,
+    base_url: String,
+    auth: BitbucketAuth,
+    client: reqwest::Client,
+}
+
+impl BitbucketClient {
+    /// Create a client for `workspace/repo_slug`
+    pub fn new(workspace: &str, repo_slug: &str, auth: BitbucketAuth) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            workspace: workspace.to_string(),
+            repo_slug: repo_slug.to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            auth,
+            client,
+        }
+    }
+
+    /// Use a different API base URL
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Create a client for a repository declared in multi-repo configuration
+    pub fn for_repository(repository: &Repository, auth: BitbucketAuth) -> Result<Self> {
+        if repository.provider != RepoProvider::Bitbucket {
+            anyhow::bail!(
+                "Repository {} is hosted on {}, not Bitbucket",
+                repository.name,
+                repository.provider.as_str()
+            );
+        }
+        let (workspace, repo_slug) = parse_repo_url(&repository.url)
+            .ok_or_else(|| anyhow::anyhow!("Invalid Bitbucket URL: {}", repository.url))?;
+        Ok(Self::new(&workspace, &repo_slug, auth))
+    }
+
+    /// `workspace/repo_slug`
+    pub fn full_name(&self) -> String {
+        format!("{}/{}", self.workspace, self.repo_slug)
+    }
+
+    /// URL of a path under `repositories/{workspace}/{repo_slug}`
+    pub(crate) fn repo_url(&self, path: &str) -> String {
+        format!(
+            "{}/repositories/{}/{}/{}",
+            self.base_url, self.workspace, self.repo_slug, path
+        )
+    }
+
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        let builder = self.client.request(method, url);
+        match &self.auth {
+            BitbucketAuth::Token(token) => builder.bearer_auth(token.expose_secret()),
+            BitbucketAuth::AppPassword { username, password } => {
+                builder.basic_auth(username, Some(password.expose_secret()))
+            }
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        builder: reqwest::RequestBuilder,
+        action: &str,
+    ) -> Result<T> {
+        let response = builder.send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error = response.text().await?;
+            anyhow::bail!("Failed to {} ({}): {}", action, status, error);
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// GET a repository path and parse the JSON response
+    pub(crate) async fn get<T: DeserializeOwned>(&self, path: &str, action: &str) -> Result<T> {
+        self.send(
+            self.request(reqwest::Method::GET, &self.repo_url(path)),
+            action,
+        )
+        .await
+    }
+
+    /// GET every page of a paginated repository path
+    pub(crate) async fn get_all<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        action: &str,
+    ) -> Result<Vec<T>> {
+        let mut values = Vec::new();
+        let mut url = Some(self.repo_url(path));
+        while let Some(next) = url {
+            let page: Page<T> = self
+                .send(self.request(reqwest::Method::GET, &next), action)
+                .await?;
+            values.extend(page.values);
+            url = page.next;
+        }
+        Ok(values)
+    }
+
+    /// POST JSON to a repository path and parse the JSON response
+    pub(crate) async fn post<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &B,
+        action: &str,
+    ) -> Result<T> {
+        self.send(
+            self.request(reqwest::Method::POST, &self.repo_url(path))
+                .json(body),
+            action,
+        )
+        .await
+    }
+
+    /// PUT JSON to a repository path and parse the JSON response
+    pub(crate) async fn put<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &B,
+        action: &str,
+    ) -> Result<T> {
+        self.send(
+            self.request(reqwest::Method::PUT, &self.repo_url(path))
+                .json(body),
+            action,
+        )
+        .await
+    }
+
+    /// DELETE a repository path
+    pub(crate) async fn delete(&self, path: &str, action: &str) -> Result<()> {
+        let response = self
+            .request(reqwest::Method::DELETE, &self.repo_url(path))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error = response.text().await?;
+            anyhow::bail!("Failed to {} ({}): {}", action, status, error);
+        }
+
+        Ok(())
+    }
+}
+
+/// One page of a paginated response
+#[derive(Deserialize)]
+struct Page<T> {
+    values: Vec<T>,
+    next: Option<String>,
+}
+
+/// Workspace and repository slug of a Bitbucket clone or web URL
+///
+/// Accepts `https://[user@]bitbucket.org/workspace/repo(.git)` and
+/// `git@bitbucket.org:workspace/repo.git`.
+pub fn parse_repo_url(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => url.split_once(':')?.1,
+    };
+
+    let mut segments = path.trim_end_matches('/').split('/');
+    let workspace = segments.next().filter(|s| !s.is_empty())?;
+    let repo_slug = segments
+        .next()
+        .map(|s| s.trim_end_matches(".git"))
+        .filter(|s| !s.is_empty())?;
+    Some((workspace.to_string(), repo_slug.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth() -> BitbucketAuth {
+        BitbucketAuth::Token(SecretString::new("token".to_string()))
+    }
+
+    #[test]
+    fn test_parse_repo_url() {
+        assert_eq!(
+            parse_repo_url("https://alice@bitbucket.org/team/app.git"),
+            Some(("team".to_string(), "app".to_string()))
+        );
+        assert_eq!(
+            parse_repo_url("git@bitbucket.org:team/app.git"),
+            Some(("team".to_string(), "app".to_string()))
+        );
+        assert_eq!(
+            parse_repo_url("https://bitbucket.org/team/app/pull-requests/3"),
+            Some(("team".to_string(), "app".to_string()))
+        );
+        assert_eq!(parse_repo_url("https://bitbucket.org/team"), None);
+    }
+
+    #[test]
+    fn test_for_repository() {
+        let repo = Repository::new("app", "https://bitbucket.org/team/app");
+        let client = BitbucketClient::for_repository(&repo, auth()).unwrap();
+        assert_eq!(client.full_name(), "team/app");
+        assert_eq!(
+            client.repo_url("pullrequests"),
+            "https://api.bitbucket.org/2.0/repositories/team/app/pullrequests"
+        );
+
+        let repo = Repository::new("app", "https://gitlab.com/team/app");
+        assert!(BitbucketClient::for_repository(&repo, auth()).is_err());
+    }
+}
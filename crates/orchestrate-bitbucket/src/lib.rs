@@ -0,0 +1,19 @@
+//! Orchestrate Bitbucket - Bitbucket Cloud API integration
+//!
+//! This crate provides pull request management for Bitbucket repositories
+//! declared in multi-repo configuration:
+//! - PR creation, approval, and merging
+//! - Merge conflict detection
+//! - Build status as CI check results
+//! - Branch restrictions
+//! - All of the above as a PR workflow provider
+
+pub mod branch_restriction;
+pub mod client;
+pub mod pipeline;
+pub mod provider;
+pub mod pull_request;
+
+pub use client::{BitbucketAuth, BitbucketClient};
+pub use pipeline::BuildStatus;
+pub use pull_request::PullRequest;
@@ -0,0 +1,94 @@
+//! Pull request build statuses
+//!
+//! Bitbucket Pipelines and external CI report build statuses on the source
+//! commit; a pull request lists the statuses of its latest commit.
+
+use anyhow::Result;
+use orchestrate_core::{CiAggregateStatus, CiCheckResult, CiStatus};
+use serde::Deserialize;
+
+use crate::client::BitbucketClient;
+
+/// A build status reported on a commit
+#[derive(Debug, Clone, Deserialize)]
+pub struct BuildStatus {
+    pub key: String,
+    pub name: Option<String>,
+    /// `SUCCESSFUL`, `FAILED`, `INPROGRESS`, or `STOPPED`
+    pub state: String,
+    pub url: Option<String>,
+    pub description: Option<String>,
+}
+
+impl BuildStatus {
+    pub fn ci_status(&self) -> CiStatus {
+        match self.state.as_str() {
+            "SUCCESSFUL" => CiStatus::Passed,
+            "FAILED" => CiStatus::Failed,
+            "INPROGRESS" => CiStatus::Running,
+            "STOPPED" => CiStatus::Cancelled,
+            _ => CiStatus::Pending,
+        }
+    }
+
+    /// The build status as a CI check result
+    pub fn to_check(&self) -> CiCheckResult {
+        let status = self.ci_status();
+        let mut check = CiCheckResult::new(self.name.as_deref().unwrap_or(&self.key), status);
+        if let Some(url) = &self.url {
+            check = check.with_url(url);
+        }
+        if status == CiStatus::Failed {
+            if let Some(description) = &self.description {
+                check = check.with_failure(description);
+            }
+        }
+        check
+    }
+}
+
+impl BitbucketClient {
+    /// Build statuses of a PR as CI check results
+    pub async fn get_pr_checks(&self, id: i64) -> Result<Vec<CiCheckResult>> {
+        let statuses: Vec<BuildStatus> = self
+            .get_all(
+                &format!("pullrequests/{}/statuses", id),
+                "get build statuses",
+            )
+            .await?;
+        Ok(statuses.iter().map(BuildStatus::to_check).collect())
+    }
+
+    /// Overall CI status of a PR
+    pub async fn get_pr_ci_status(&self, id: i64) -> Result<CiAggregateStatus> {
+        Ok(CiAggregateStatus::from_checks(
+            &self.get_pr_checks(id).await?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_status_checks() {
+        let statuses: Vec<BuildStatus> = serde_json::from_value(serde_json::json!([
+            { "key": "pipeline-1", "name": "Pipeline #1", "state": "SUCCESSFUL" },
+            { "key": "lint", "state": "FAILED", "description": "3 errors",
+              "url": "https://ci.example.com/lint/1" },
+            { "key": "e2e", "state": "INPROGRESS" }
+        ]))
+        .unwrap();
+        let checks: Vec<CiCheckResult> = statuses.iter().map(BuildStatus::to_check).collect();
+
+        assert_eq!(checks[0].name, "Pipeline #1");
+        assert_eq!(checks[1].name, "lint");
+        assert_eq!(checks[1].failure_details.as_deref(), Some("3 errors"));
+        assert_eq!(checks[2].status, CiStatus::Running);
+        assert_eq!(
+            CiAggregateStatus::from_checks(&checks).overall,
+            CiStatus::Failed
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Bitbucket as a PR workflow provider
+
+use async_trait::async_trait;
+use orchestrate_core::{CiCheckResult, MergeMethod, OpenedPr, PrApprovals, PrProvider};
+
+use crate::client::BitbucketClient;
+
+fn to_core(e: anyhow::Error) -> orchestrate_core::Error {
+    orchestrate_core::Error::Other(e.to_string())
+}
+
+#[async_trait]
+impl PrProvider for BitbucketClient {
+    fn name(&self) -> &str {
+        "bitbucket"
+    }
+
+    async fn create_pr(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> orchestrate_core::Result<OpenedPr> {
+        let pr = BitbucketClient::create_pr(self, head, base, title, body)
+            .await
+            .map_err(to_core)?;
+        Ok(OpenedPr {
+            number: pr.id as u64,
+            url: pr.url().map(str::to_string),
+        })
+    }
+
+    /// Bitbucket has no required approval count, so one approval approves
+    async fn get_approvals(&self, number: u64) -> orchestrate_core::Result<PrApprovals> {
+        let pr = self.get_pr(number as i64).await.map_err(to_core)?;
+        let approvers: Vec<String> = pr.approvers().into_iter().map(str::to_string).collect();
+        Ok(PrApprovals {
+            approved: !approvers.is_empty(),
+            changes_requested: pr.changes_requested(),
+            approvers,
+        })
+    }
+
+    async fn get_ci_checks(&self, number: u64) -> orchestrate_core::Result<Vec<CiCheckResult>> {
+        self.get_pr_checks(number as i64).await.map_err(to_core)
+    }
+
+    async fn has_conflicts(&self, number: u64) -> orchestrate_core::Result<bool> {
+        BitbucketClient::has_conflicts(self, number as i64)
+            .await
+            .map_err(to_core)
+    }
+
+    async fn merge_pr(&self, number: u64, method: MergeMethod) -> orchestrate_core::Result<()> {
+        BitbucketClient::merge_pr(self, number as i64, method.as_str())
+            .await
+            .map_err(to_core)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+//! Pull requests and approvals
+
+use anyhow::Result;
+use orchestrate_core::PrMergeStatus;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::client::BitbucketClient;
+
+/// A Bitbucket pull request
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequest {
+    pub id: i64,
+    pub title: String,
+    /// `OPEN`, `MERGED`, `DECLINED`, or `SUPERSEDED`
+    pub state: String,
+    #[serde(default)]
+    pub draft: bool,
+    pub source: Endpoint,
+    pub destination: Endpoint,
+    #[serde(default)]
+    pub participants: Vec<Participant>,
+    pub links: Option<Links>,
+}
+
+/// Source or destination of a pull request
+#[derive(Debug, Clone, Deserialize)]
+pub struct Endpoint {
+    pub branch: Branch,
+    pub commit: Option<Commit>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Branch {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Commit {
+    pub hash: String,
+}
+
+/// A reviewer or other participant in a pull request
+#[derive(Debug, Clone, Deserialize)]
+pub struct Participant {
+    pub user: User,
+    /// `REVIEWER` or `PARTICIPANT`
+    pub role: String,
+    #[serde(default)]
+    pub approved: bool,
+    /// `approved`, `changes_requested`, or none
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct User {
+    pub display_name: String,
+    pub nickname: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Links {
+    pub html: Option<Link>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Link {
+    pub href: String,
+}
+
+impl PullRequest {
+    /// Web URL of the pull request
+    pub fn url(&self) -> Option<&str> {
+        self.links
+            .as_ref()
+            .and_then(|l| l.html.as_ref())
+            .map(|h| h.href.as_str())
+    }
+
+    /// Display names of participants who approved
+    pub fn approvers(&self) -> Vec<&str> {
+        self.participants
+            .iter()
+            .filter(|p| p.approved)
+            .map(|p| p.user.display_name.as_str())
+            .collect()
+    }
+
+    /// Whether a participant requested changes
+    pub fn changes_requested(&self) -> bool {
+        self.participants
+            .iter()
+            .any(|p| p.state.as_deref() == Some("changes_requested"))
+    }
+
+    /// Merge status in the same terms as GitHub PRs
+    ///
+    /// Bitbucket doesn't report mergeability on the pull request itself, so
+    /// conflicts come from the diffstat.
+    pub fn merge_status(&self, has_conflicts: bool) -> PrMergeStatus {
+        match self.state.as_str() {
+            "MERGED" => PrMergeStatus::Merged,
+            "DECLINED" | "SUPERSEDED" => PrMergeStatus::Closed,
+            _ if self.draft => PrMergeStatus::Draft,
+            _ if has_conflicts => PrMergeStatus::Conflicts,
+            _ => PrMergeStatus::Mergeable,
+        }
+    }
+}
+
+/// Bitbucket merge strategy for a `squash`, `rebase`, or `merge` strategy name
+///
+/// `rebase` fast-forwards, so it fails unless the source branch is up to date.
+pub fn merge_strategy(strategy: &str) -> &'static str {
+    match strategy {
+        "merge" => "merge_commit",
+        "rebase" => "fast_forward",
+        _ => "squash",
+    }
+}
+
+#[derive(Deserialize)]
+struct DiffStat {
+    status: String,
+}
+
+impl BitbucketClient {
+    /// Create a PR from `source` into `destination`
+    pub async fn create_pr(
+        &self,
+        source: &str,
+        destination: &str,
+        title: &str,
+        description: &str,
+    ) -> Result<PullRequest> {
+        let body = json!({
+            "title": title,
+            "description": description,
+            "source": { "branch": { "name": source } },
+            "destination": { "branch": { "name": destination } },
+            "close_source_branch": true,
+        });
+        self.post("pullrequests", &body, "create pull request")
+            .await
+    }
+
+    /// Get a PR by ID
+    pub async fn get_pr(&self, id: i64) -> Result<PullRequest> {
+        self.get(&format!("pullrequests/{}", id), "get pull request")
+            .await
+    }
+
+    /// Whether a PR's source branch conflicts with its destination
+    pub async fn has_conflicts(&self, id: i64) -> Result<bool> {
+        let stats: Vec<DiffStat> = self
+            .get_all(&format!("pullrequests/{}/diffstat", id), "get diffstat")
+            .await?;
+        Ok(stats.iter().any(|s| s.status == "merge conflict"))
+    }
+
+    /// Merge status of a PR, including conflicts
+    pub async fn get_merge_status(&self, id: i64) -> Result<PrMergeStatus> {
+        let pr = self.get_pr(id).await?;
+        let has_conflicts = pr.state == "OPEN" && self.has_conflicts(id).await?;
+        Ok(pr.merge_status(has_conflicts))
+    }
+
+    /// Approve a PR as the authenticated user
+    pub async fn approve_pr(&self, id: i64) -> Result<Participant> {
+        self.post(
+            &format!("pullrequests/{}/approve", id),
+            &json!({}),
+            "approve pull request",
+        )
+        .await
+    }
+
+    /// Merge a PR with the `squash`, `rebase`, or `merge` strategy
+    pub async fn merge_pr(&self, id: i64, strategy: &str) -> Result<PullRequest> {
+        let body = json!({
+            "merge_strategy": merge_strategy(strategy),
+            "close_source_branch": true,
+        });
+        self.post(
+            &format!("pullrequests/{}/merge", id),
+            &body,
+            "merge pull request",
+        )
+        .await
+    }
+
+    /// Post a comment on a PR
+    pub async fn post_pr_comment(&self, id: i64, body: &str) -> Result<()> {
+        let _: serde_json::Value = self
+            .post(
+                &format!("pullrequests/{}/comments", id),
+                &json!({ "content": { "raw": body } }),
+                "post pull request comment",
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(state: &str, draft: bool) -> PullRequest {
+        serde_json::from_value(json!({
+            "id": 3,
+            "title": "Add export",
+            "state": state,
+            "draft": draft,
+            "source": { "branch": { "name": "feature/export" }, "commit": { "hash": "abc123" } },
+            "destination": { "branch": { "name": "main" } },
+            "participants": [
+                { "user": { "display_name": "Alice" }, "role": "REVIEWER", "approved": true },
+                { "user": { "display_name": "Bob" }, "role": "REVIEWER", "approved": false, "state": "changes_requested" }
+            ],
+            "links": { "html": { "href": "https://bitbucket.org/team/app/pull-requests/3" } }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge_status() {
+        assert_eq!(
+            pr("OPEN", false).merge_status(false),
+            PrMergeStatus::Mergeable
+        );
+        assert_eq!(
+            pr("OPEN", false).merge_status(true),
+            PrMergeStatus::Conflicts
+        );
+        assert_eq!(pr("OPEN", true).merge_status(false), PrMergeStatus::Draft);
+        assert_eq!(
+            pr("MERGED", false).merge_status(false),
+            PrMergeStatus::Merged
+        );
+        assert_eq!(
+            pr("DECLINED", false).merge_status(false),
+            PrMergeStatus::Closed
+        );
+    }
+
+    #[test]
+    fn test_pull_request_fields() {
+        let pr = pr("OPEN", false);
+        assert_eq!(pr.approvers(), vec!["Alice"]);
+        assert!(pr.changes_requested());
+        assert_eq!(
+            pr.url(),
+            Some("https://bitbucket.org/team/app/pull-requests/3")
+        );
+        assert_eq!(merge_strategy("squash"), "squash");
+        assert_eq!(merge_strategy("merge"), "merge_commit");
+    }
+}
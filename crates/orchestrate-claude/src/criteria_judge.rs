@@ -0,0 +1,139 @@
+//! LLM-backed acceptance criteria judge
+//!
+//! Implements [`CriteriaJudge`] for the work evaluator's
+//! `acceptance_criteria_llm` check by asking Claude whether the work summary
+//! satisfies each criterion.
+
+use async_trait::async_trait;
+use orchestrate_core::{CriteriaJudge, CriterionCheck};
+use serde::Deserialize;
+
+use crate::client::{ClaudeClient, ContentBlock, CreateMessageRequest, MessageContent};
+
+const JUDGE_SYSTEM_PROMPT: &str = "You review software work against acceptance criteria. \
+Given one criterion and a summary of the work done, decide whether the criterion is met. \
+Respond with only a JSON object: {\"met\": bool, \"confidence\": number between 0 and 1, \
+\"evidence\": short string}.";
+
+/// Judges acceptance criteria with Claude
+#[derive(Clone)]
+pub struct ClaudeCriteriaJudge {
+    client: ClaudeClient,
+    model: String,
+}
+
+impl ClaudeCriteriaJudge {
+    pub fn new(client: ClaudeClient) -> Self {
+        Self {
+            client,
+            model: "claude-sonnet-4-20250514".to_string(),
+        }
+    }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Verdict {
+    met: bool,
+    #[serde(default = "default_confidence")]
+    confidence: f64,
+    #[serde(default)]
+    evidence: Option<String>,
+}
+
+fn default_confidence() -> f64 {
+    1.0
+}
+
+/// Parse the model's JSON verdict, tolerating surrounding prose or code fences
+fn parse_verdict(criterion: &str, text: &str) -> orchestrate_core::Result<CriterionCheck> {
+    let json = match (text.find('{'), text.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &text[start..=end],
+        _ => {
+            return Err(orchestrate_core::Error::Other(format!(
+                "No verdict in judge response: {}",
+                text
+            )))
+        }
+    };
+    let verdict: Verdict = serde_json::from_str(json)?;
+
+    let check = if verdict.met {
+        CriterionCheck::met(criterion)
+    } else {
+        CriterionCheck::unmet(criterion)
+    }
+    .with_confidence(verdict.confidence);
+
+    Ok(match verdict.evidence {
+        Some(evidence) => check.with_evidence(evidence),
+        None => check,
+    })
+}
+
+#[async_trait]
+impl CriteriaJudge for ClaudeCriteriaJudge {
+    async fn judge(
+        &self,
+        criterion: &str,
+        work_summary: &str,
+    ) -> orchestrate_core::Result<CriterionCheck> {
+        let prompt = format!(
+            "Acceptance criterion:\n{}\n\nWork summary:\n{}",
+            criterion, work_summary
+        );
+        let request = CreateMessageRequest::new(
+            self.model.clone(),
+            512,
+            vec![MessageContent {
+                role: "user".to_string(),
+                content: serde_json::json!(prompt),
+            }],
+        )
+        .with_system(JUDGE_SYSTEM_PROMPT);
+
+        let response = self
+            .client
+            .create_message(request)
+            .await
+            .map_err(|e| orchestrate_core::Error::Other(format!("Criteria judge failed: {}", e)))?;
+
+        let text: String = response
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        parse_verdict(criterion, &text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verdict() {
+        let check = parse_verdict(
+            "User can login",
+            "```json\n{\"met\": true, \"confidence\": 0.8, \"evidence\": \"login route added\"}\n```",
+        )
+        .unwrap();
+        assert!(check.is_met);
+        assert_eq!(check.confidence, 0.8);
+        assert_eq!(check.evidence.as_deref(), Some("login route added"));
+
+        let check = parse_verdict("User can logout", "{\"met\": false}").unwrap();
+        assert!(!check.is_met);
+        assert_eq!(check.confidence, 1.0);
+
+        assert!(parse_verdict("User can logout", "I cannot tell").is_err());
+    }
+}
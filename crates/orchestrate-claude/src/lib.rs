@@ -9,10 +9,12 @@
 //! - Session management
 
 pub mod client;
+pub mod criteria_judge;
 pub mod loop_runner;
 pub mod token;
 pub mod tools;
 
 pub use client::{ClaudeCliClient, ClaudeClient};
+pub use criteria_judge::ClaudeCriteriaJudge;
 pub use loop_runner::AgentLoop;
 pub use token::{ContextManager, TokenConfig, TokenEstimator};
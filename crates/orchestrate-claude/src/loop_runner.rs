@@ -5,14 +5,18 @@
 //! - Prompt caching for reduced costs
 //! - Session management for continuity
 //! - Dynamic output token allocation
+//! - Sentry reporting of loop errors, failed agents, and tool failures
 
 use anyhow::Result;
 use orchestrate_core::{
-    Agent, AgentState, AgentType, CustomInstruction, Database, LearningEngine, Message, Session,
+    scrub_secrets, Agent, AgentEvent, AgentState, AgentType, AgentTypeDefinition,
+    CustomInstruction, Database, LearningEngine, Message, MessageRole, OutputSummarizer,
+    RelatedSummary, SentryEvent, SentryLevel, Session,
 };
+use std::collections::HashMap;
 use std::path::Path;
 use std::time::Instant;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
 use crate::client::{ClaudeClient, ContentBlock, CreateMessageRequest, MessageContent};
 use crate::token::{ContextManager, TokenEstimator};
@@ -34,6 +38,10 @@ pub struct LoopConfig {
     pub enable_token_optimization: bool,
     /// Enable session tracking
     pub enable_sessions: bool,
+    /// Store a summary of each run and give new agents related prior work
+    pub enable_context_summaries: bool,
+    /// Max related prior work summaries injected into a new agent's prompt
+    pub related_summaries_limit: usize,
 }
 
 impl Default for LoopConfig {
@@ -47,6 +55,8 @@ impl Default for LoopConfig {
             max_consecutive_errors: 3,
             enable_token_optimization: true,
             enable_sessions: true,
+            enable_context_summaries: true,
+            related_summaries_limit: 3,
         }
     }
 }
@@ -60,6 +70,7 @@ pub struct AgentLoop {
     learning_engine: LearningEngine,
     context_manager: ContextManager,
     token_estimator: TokenEstimator,
+    secrets: HashMap<String, String>,
 }
 
 impl AgentLoop {
@@ -74,6 +85,7 @@ impl AgentLoop {
             token_estimator: TokenEstimator::new(),
             config,
             learning_engine: LearningEngine::new(),
+            secrets: HashMap::new(),
         }
     }
 
@@ -93,18 +105,45 @@ impl AgentLoop {
             token_estimator: TokenEstimator::new(),
             config,
             learning_engine,
+            secrets: HashMap::new(),
         }
     }
 
+    /// Expose secrets to the agent's tools as environment variables
+    ///
+    /// Secret values are scrubbed from tool output and stored messages.
+    pub fn with_secrets(mut self, secrets: HashMap<String, String>) -> Self {
+        self.tool_executor = std::mem::take(&mut self.tool_executor).with_secrets(secrets.clone());
+        self.secrets = secrets;
+        self
+    }
+
     /// Run the agent loop
     #[tracing::instrument(skip(self, agent), fields(agent_id = %agent.id, agent_type = ?agent.agent_type))]
     pub async fn run(&self, agent: &mut Agent) -> Result<()> {
+        let result = self.run_loop(agent).await;
+        if let Err(e) = &result {
+            let event = SentryEvent::new(SentryLevel::Error, "AgentLoopError", e.to_string())
+                .with_agent(agent);
+            self.report_error(agent, event).await;
+        }
+        result
+    }
+
+    async fn run_loop(&self, agent: &mut Agent) -> Result<()> {
         let start_time = Instant::now();
         info!("Starting agent loop for agent {}", agent.id);
 
+        // Link this run to its exported trace
+        if let Some(trace_id) = orchestrate_core::telemetry::current_trace_id() {
+            agent.trace_id = Some(trace_id);
+        }
+
         // Transition to initializing
+        let mut last_state = agent.state;
         agent.transition_to(AgentState::Initializing)?;
         self.db.update_agent(agent).await?;
+        self.record_state_change(agent, &mut last_state).await;
 
         // Load custom instructions for this agent type
         let (instructions, instruction_ids) = if self.config.enable_instructions {
@@ -133,9 +172,50 @@ impl AgentLoop {
             agent.id
         );
 
+        // Resolve user-defined agent type, if the agent was spawned as one
+        let type_definition = match agent.custom_type {
+            Some(ref name) => {
+                let def = self.db.get_agent_type_definition(name).await?;
+                if def.is_none() {
+                    warn!(
+                        "Custom agent type '{}' not found, using base type {:?}",
+                        name, agent.agent_type
+                    );
+                }
+                def
+            }
+            None => None,
+        };
+
         // Load message history
         let mut messages = self.db.get_messages(agent.id).await?;
 
+        // Give newly spawned agents summaries of related prior work
+        let related_summaries = if self.config.enable_context_summaries
+            && self.config.related_summaries_limit > 0
+            && messages.is_empty()
+        {
+            match self
+                .db
+                .find_related_summaries(&agent.task, self.config.related_summaries_limit)
+                .await
+            {
+                Ok(related) => related,
+                Err(e) => {
+                    warn!("Failed to find related summaries: {}", e);
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
+        debug!(
+            "Found {} related summaries for agent {}",
+            related_summaries.len(),
+            agent.id
+        );
+
         // Add initial task as user message if no messages
         if messages.is_empty() {
             let user_msg = Message::user(agent.id, &agent.task);
@@ -146,6 +226,7 @@ impl AgentLoop {
         // Transition to running
         agent.transition_to(AgentState::Running)?;
         self.db.update_agent(agent).await?;
+        self.record_state_change(agent, &mut last_state).await;
 
         let mut turn = 0;
         let mut was_blocked = false;
@@ -183,6 +264,15 @@ impl AgentLoop {
             turn += 1;
             let turn_start = Instant::now();
 
+            // Span covering the turn's model request and tool executions
+            let turn_span = info_span!(
+                "agent_turn",
+                agent_id = %agent.id,
+                turn,
+                input_tokens = tracing::field::Empty,
+                output_tokens = tracing::field::Empty,
+            );
+
             info!(
                 "[AGENT {}] Turn {}/{} | Idle: {}/{} | Errors: {}/{} | Messages: {}",
                 agent.id,
@@ -290,8 +380,18 @@ impl AgentLoop {
             };
 
             // Create request with prompt caching
-            let (base_prompt, dynamic_suffix) = self.get_system_prompt_parts(agent, &instructions);
-            let tools = self.tool_executor.get_tool_definitions(&agent.agent_type);
+            let (base_prompt, dynamic_suffix) = self.get_system_prompt_parts(
+                agent,
+                type_definition.as_ref(),
+                &instructions,
+                &related_summaries,
+            );
+            let tools = match type_definition {
+                Some(ref def) => self
+                    .tool_executor
+                    .get_tool_definitions_for(&def.effective_allowed_tools()),
+                None => self.tool_executor.get_tool_definitions(&agent.agent_type),
+            };
 
             // Build request - use caching if client supports it
             let request = if self.client.caching_enabled() && self.config.enable_token_optimization
@@ -311,7 +411,12 @@ impl AgentLoop {
             };
 
             // Call Claude API with error handling
-            let response = match self.client.create_message(request).await {
+            let response = match self
+                .client
+                .create_message(request)
+                .instrument(turn_span.clone())
+                .await
+            {
                 Ok(resp) => {
                     consecutive_errors = 0; // Reset on success
                     resp
@@ -327,6 +432,9 @@ impl AgentLoop {
                 }
             };
 
+            turn_span.record("input_tokens", response.usage.input_tokens);
+            turn_span.record("output_tokens", response.usage.output_tokens);
+
             // Track token usage (convert i32 from API to i64 for database)
             total_input_tokens += response.usage.input_tokens as i64;
             total_output_tokens += response.usage.output_tokens as i64;
@@ -389,6 +497,22 @@ impl AgentLoop {
                 warn!("Failed to update daily token usage: {}", e);
             }
 
+            // Attribute spend to the epic/story being worked on
+            if let Err(e) = self
+                .db
+                .attribute_token_usage(
+                    &agent.context,
+                    &self.config.model,
+                    response.usage.input_tokens as i64,
+                    response.usage.output_tokens as i64,
+                    response.usage.cache_read_input_tokens as i64,
+                    response.usage.cache_creation_input_tokens as i64,
+                )
+                .await
+            {
+                warn!("Failed to attribute token usage: {}", e);
+            }
+
             // Extract text and tool calls
             let mut text_content = String::new();
             let mut tool_calls = Vec::new();
@@ -417,6 +541,7 @@ impl AgentLoop {
             );
 
             // Store assistant message
+            let text_content = scrub_secrets(&text_content, &self.secrets);
             let assistant_msg = Message::assistant(agent.id, &text_content)
                 .with_tool_calls(tool_calls.clone())
                 .with_tokens(response.usage.input_tokens, response.usage.output_tokens);
@@ -474,12 +599,24 @@ impl AgentLoop {
                             .collect::<String>()
                     );
 
+                    let tool_start = Instant::now();
                     let result = self
                         .tool_executor
                         .execute(&tool_call.name, &tool_call.input, agent)
+                        .instrument(turn_span.clone())
                         .await;
 
                     let is_error = result.starts_with("Error:");
+                    let mut event = AgentEvent::tool_call(
+                        agent.id.to_string(),
+                        &tool_call.name,
+                        is_error,
+                        tool_start.elapsed().as_millis() as u64,
+                    );
+                    if let Some(ref sid) = session_id {
+                        event = event.with_session(sid);
+                    }
+                    self.record_event(&event).await;
                     if is_error {
                         had_error = true;
                         last_tool_error = Some(result.clone());
@@ -489,6 +626,8 @@ impl AgentLoop {
                             tool_call.name,
                             result.chars().take(200).collect::<String>()
                         );
+                        let event = SentryEvent::tool_failure(agent, &tool_call.name, &result);
+                        self.report_error(agent, event).await;
                     } else {
                         debug!(
                             "[AGENT {}] Tool '{}' success: {} chars",
@@ -533,6 +672,17 @@ impl AgentLoop {
         }
 
         let total_elapsed = start_time.elapsed();
+        self.record_state_change(agent, &mut last_state).await;
+
+        if agent.state == AgentState::Failed {
+            self.report_error(agent, SentryEvent::agent_failure(agent))
+                .await;
+        }
+        if matches!(agent.state, AgentState::Completed | AgentState::Failed) {
+            if let Some(telegram) = orchestrate_core::telegram::client() {
+                telegram.notify_agent_finished(agent).await;
+            }
+        }
 
         // Calculate cache savings
         let cache_hit_rate = if total_input_tokens > 0 {
@@ -563,6 +713,52 @@ impl AgentLoop {
             }
         }
 
+        // Record the override's effectiveness against the agent type's other prompt versions
+        if agent.system_prompt.is_some() {
+            let success = agent.state == AgentState::Completed;
+            match self.db.record_agent_prompt_override(agent).await {
+                Ok(Some(version)) => {
+                    if let Err(e) = self
+                        .db
+                        .record_prompt_version_outcome(
+                            version.id,
+                            success,
+                            total_input_tokens + total_output_tokens,
+                            start_time.elapsed().as_secs_f64(),
+                        )
+                        .await
+                    {
+                        warn!("Failed to record prompt version outcome: {}", e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Failed to record prompt version: {}", e),
+            }
+        }
+
+        // Index a summary of this run for related-work search
+        let output = messages
+            .iter()
+            .filter(|m| m.role == MessageRole::Assistant)
+            .map(|m| m.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if self.config.enable_context_summaries && !output.trim().is_empty() {
+            let mut summary = OutputSummarizer::new()
+                .summarize_output(&output)
+                .with_agent(agent.id.to_string());
+            if let Some(ref sid) = session_id {
+                summary = summary.with_session(sid);
+            }
+            if let Err(e) = self
+                .db
+                .store_context_summary(&summary, agent.context.story_id.as_deref())
+                .await
+            {
+                warn!("Failed to store context summary: {}", e);
+            }
+        }
+
         // Record instruction outcomes and apply learning
         let success = agent.state == AgentState::Completed;
         let completion_time = start_time.elapsed().as_secs_f64();
@@ -622,6 +818,38 @@ impl AgentLoop {
         Ok(())
     }
 
+    /// Report an error to Sentry when configured, with the agent's event log as
+    /// breadcrumbs (failures are logged, not fatal)
+    async fn report_error(&self, agent: &Agent, event: SentryEvent) {
+        let Some(client) = orchestrate_core::sentry::client() else {
+            return;
+        };
+        if let Err(e) = client.capture_for_agent(&self.db, agent, event).await {
+            warn!("Failed to report error to Sentry: {}", e);
+        }
+    }
+
+    /// Append an event to the agent's event log (failures are logged, not fatal)
+    async fn record_event(&self, event: &AgentEvent) {
+        if let Err(e) = self.db.insert_agent_event(event).await {
+            warn!("Failed to record agent event: {}", e);
+        }
+        orchestrate_core::event_bus::emit(event.into());
+    }
+
+    /// Record a state transition event if the agent's state changed since `last_state`
+    async fn record_state_change(&self, agent: &Agent, last_state: &mut AgentState) {
+        if agent.state == *last_state {
+            return;
+        }
+        let mut event = AgentEvent::state_transition(agent.id.to_string(), *last_state, agent.state);
+        if let Some(ref sid) = agent.session_id {
+            event = event.with_session(sid);
+        }
+        self.record_event(&event).await;
+        *last_state = agent.state;
+    }
+
     fn messages_to_api(&self, messages: &[Message]) -> Vec<MessageContent> {
         messages
             .iter()
@@ -659,7 +887,7 @@ impl AgentLoop {
 
     #[allow(dead_code)]
     fn get_system_prompt(&self, agent: &Agent) -> String {
-        let (base, suffix) = self.get_system_prompt_parts(agent, &[]);
+        let (base, suffix) = self.get_system_prompt_parts(agent, None, &[], &[]);
         if suffix.is_empty() {
             base
         } else {
@@ -670,14 +898,21 @@ impl AgentLoop {
     /// Get system prompt split into cacheable base and dynamic suffix
     ///
     /// The base prompt (agent identity, tools, status signals) is static and cacheable.
-    /// The suffix (current task, custom instructions) changes per run.
+    /// The suffix (current task, custom instructions, related work) changes per run.
     fn get_system_prompt_parts(
         &self,
         agent: &Agent,
+        type_definition: Option<&AgentTypeDefinition>,
         instructions: &[CustomInstruction],
+        related_summaries: &[RelatedSummary],
     ) -> (String, String) {
-        // Try to load agent prompt from .claude/agents/ file
-        let agent_prompt = self.load_agent_prompt(&agent.agent_type);
+        // Prefer the agent's own override, then the custom type's prompt,
+        // then the .claude/agents/ file
+        let agent_prompt = agent
+            .system_prompt
+            .clone()
+            .or_else(|| type_definition.and_then(|def| def.system_prompt.clone()))
+            .or_else(|| self.load_agent_prompt(&agent.agent_type));
 
         // Base prompt - static, cacheable
         let base_prompt = if let Some(ref custom_prompt) = agent_prompt {
@@ -693,7 +928,7 @@ If you encounter an error you cannot resolve, respond with "STATUS: BLOCKED: <re
             )
         } else {
             format!(
-                r#"You are an autonomous agent of type: {:?}
+                r#"You are an autonomous agent of type: {}
 
 You have access to these tools: {:?}
 
@@ -702,8 +937,13 @@ You have access to these tools: {:?}
 When you complete your task, respond with "STATUS: COMPLETE" in your message.
 If you need to wait for an external event (like PR review or CI), respond with "STATUS: WAITING".
 If you encounter an error you cannot resolve, respond with "STATUS: BLOCKED: <reason>"."#,
-                agent.agent_type,
-                agent.agent_type.allowed_tools()
+                agent
+                    .custom_type
+                    .clone()
+                    .unwrap_or_else(|| format!("{:?}", agent.agent_type)),
+                type_definition
+                    .map(|def| def.effective_allowed_tools())
+                    .unwrap_or_else(|| agent.agent_type.allowed_tools())
             )
         };
 
@@ -730,6 +970,20 @@ If you encounter an error you cannot resolve, respond with "STATUS: BLOCKED: <re
             ));
         }
 
+        // Add summaries of related prior work if any
+        if !related_summaries.is_empty() {
+            let related_block = related_summaries
+                .iter()
+                .map(|r| format!("- {}", r.summary.to_brief()))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            suffix_parts.push(format!(
+                "## Related Prior Work\n\nOther agents have done work related to this task. Reuse their decisions where they apply:\n\n{}",
+                related_block
+            ));
+        }
+
         (base_prompt, suffix_parts.join("\n\n"))
     }
 
@@ -4,11 +4,14 @@
 //! - All file paths are validated against allowed directories
 //! - Bash commands are sandboxed within the working directory
 //! - Dangerous commands are blocked by default
+//! - Secrets are exposed to bash commands as environment variables and
+//!   scrubbed from tool output
 
 use anyhow::{anyhow, Result};
 use glob::glob;
-use orchestrate_core::{Agent, AgentType};
+use orchestrate_core::{scrub_secrets, Agent, AgentType};
 use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use tracing::{debug, warn};
@@ -52,6 +55,7 @@ impl Default for SecurityConfig {
 pub struct ToolExecutor {
     working_dir: Option<PathBuf>,
     security: SecurityConfig,
+    secrets: HashMap<String, String>,
 }
 
 impl ToolExecutor {
@@ -60,6 +64,7 @@ impl ToolExecutor {
         Self {
             working_dir: None,
             security: SecurityConfig::default(),
+            secrets: HashMap::new(),
         }
     }
 
@@ -80,6 +85,15 @@ impl ToolExecutor {
         self
     }
 
+    /// Expose secrets to bash commands as environment variables
+    ///
+    /// Secret values are replaced in every tool's output, so they never reach
+    /// the model or stored messages.
+    pub fn with_secrets(mut self, secrets: HashMap<String, String>) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
     /// Add an allowed directory
     pub fn allow_directory(mut self, dir: impl Into<PathBuf>) -> Self {
         self.security.allowed_directories.push(dir.into());
@@ -190,7 +204,11 @@ impl ToolExecutor {
 
     /// Get tool definitions for an agent type
     pub fn get_tool_definitions(&self, agent_type: &AgentType) -> Vec<crate::client::Tool> {
-        let allowed = agent_type.allowed_tools();
+        self.get_tool_definitions_for(&agent_type.allowed_tools())
+    }
+
+    /// Get tool definitions for an explicit tool policy (e.g. a custom agent type)
+    pub fn get_tool_definitions_for(&self, allowed: &[&str]) -> Vec<crate::client::Tool> {
         let mut tools = Vec::new();
 
         if allowed.contains(&"Bash") {
@@ -349,6 +367,11 @@ impl ToolExecutor {
     }
 
     /// Execute a tool
+    #[tracing::instrument(
+        name = "tool_execution",
+        skip(self, name, input, agent),
+        fields(tool = name, agent_id = %agent.id)
+    )]
     pub async fn execute(&self, name: &str, input: &Value, agent: &Agent) -> String {
         debug!("Executing tool {} with input {:?}", name, input);
 
@@ -363,13 +386,15 @@ impl ToolExecutor {
             _ => Err(anyhow!("Unknown tool: {}", name)),
         };
 
-        match result {
+        let output = match result {
             Ok(output) => output,
             Err(e) => {
-                warn!("Tool {} failed: {}", name, e);
-                format!("Error: {}", e)
+                let error = scrub_secrets(&e.to_string(), &self.secrets);
+                warn!("Tool {} failed: {}", name, error);
+                format!("Error: {}", error)
             }
-        }
+        };
+        scrub_secrets(&output, &self.secrets)
     }
 
     async fn execute_bash(&self, input: &Value, agent: &Agent) -> Result<String> {
@@ -400,6 +425,7 @@ impl ToolExecutor {
             .current_dir(&canonical_wd)
             .env("HOME", &canonical_wd) // Restrict HOME
             .env("PATH", "/usr/local/bin:/usr/bin:/bin") // Restricted PATH
+            .envs(&self.secrets)
             .output()?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -629,4 +655,23 @@ mod tests {
         // Path traversal should be caught after canonicalization
         // Note: This test would need a real filesystem to work properly
     }
+
+    #[tokio::test]
+    async fn test_bash_secrets_exposed_and_scrubbed() {
+        let mut secrets = HashMap::new();
+        secrets.insert("DEPLOY_TOKEN".to_string(), "s3cr3t-value".to_string());
+        let executor = ToolExecutor::new()
+            .with_working_dir(std::env::temp_dir())
+            .with_secrets(secrets);
+        let agent = Agent::new(AgentType::StoryDeveloper, "Deploy");
+
+        let output = executor
+            .execute(
+                "bash",
+                &json!({"command": "test \"$DEPLOY_TOKEN\" = s3cr3t-value && echo token=$DEPLOY_TOKEN"}),
+                &agent,
+            )
+            .await;
+        assert_eq!(output, "token=[REDACTED]\n");
+    }
 }
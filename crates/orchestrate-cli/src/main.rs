@@ -4,8 +4,9 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use orchestrate_claude::{AgentLoop, ClaudeCliClient, ClaudeClient};
 use orchestrate_core::{
-    Agent, AgentState, AgentType, CustomInstruction, Database, Epic, EpicStatus,
-    LearningEngine, PatternStatus, Schedule, ScheduleRun, ShellState, Story, StoryStatus, Worktree,
+    Agent, AgentContext, AgentState, AgentType, CustomInstruction, Database, Epic, EpicStatus,
+    LearningEngine, PatternStatus, Schedule, ScheduleRun, ScheduleRunStatus, ShellState, Story,
+    StoryStatus, Worktree,
 };
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -13,7 +14,10 @@ use std::sync::Arc;
 use uuid::Uuid;
 use tokio::sync::Semaphore;
 use tracing::{error, info, warn, Level};
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
 
 /// Initialize logging with the specified verbosity level
 fn init_logging(verbose: u8, quiet: bool, json: bool) -> Result<()> {
@@ -31,17 +35,24 @@ fn init_logging(verbose: u8, quiet: bool, json: bool) -> Result<()> {
     let filter =
         EnvFilter::from_default_env().add_directive(format!("orchestrate={}", level).parse()?);
 
-    let builder = tracing_subscriber::fmt()
-        .with_env_filter(filter)
+    let fmt_layer = tracing_subscriber::fmt::layer()
         .with_target(verbose >= 2) // Show module path at debug+
         .with_file(verbose >= 3) // Show file:line at trace
         .with_line_number(verbose >= 3);
-
-    if json {
-        builder.json().init();
+    let (text_layer, json_layer) = if json {
+        (None, Some(fmt_layer.json()))
     } else {
-        builder.init();
-    }
+        (Some(fmt_layer), None)
+    };
+
+    // Export spans over OTLP when an endpoint is configured, regardless of log verbosity
+    let otel_layer = orchestrate_core::telemetry::otlp_layer()?
+        .map(|layer| layer.with_filter(Targets::new().with_target("orchestrate", Level::DEBUG)));
+
+    tracing_subscriber::registry()
+        .with(Layer::and_then(text_layer, json_layer).with_filter(filter))
+        .with(otel_layer)
+        .init();
 
     Ok(())
 }
@@ -111,11 +122,26 @@ enum Commands {
     Web {
         #[arg(short, long, default_value = "8080")]
         port: u16,
+        /// Serve HTTPS with this PEM certificate chain
+        #[arg(long, requires = "tls_key")]
+        tls_cert: Option<PathBuf>,
+        /// PEM private key of the TLS certificate
+        #[arg(long, requires = "tls_cert")]
+        tls_key: Option<PathBuf>,
     },
     /// Show system status
     Status {
         #[arg(long)]
         json: bool,
+        /// Stream events from a running web server instead
+        #[arg(long)]
+        watch: bool,
+        /// Web server to watch
+        #[arg(long, default_value = "http://localhost:8080")]
+        server: String,
+        /// Only watch these event kinds (comma-separated prefixes, e.g. agent,pipeline)
+        #[arg(long)]
+        kind: Option<String>,
     },
     /// Debug utilities
     Debug {
@@ -162,6 +188,11 @@ enum Commands {
         #[command(subcommand)]
         action: ApprovalAction,
     },
+    /// Secrets vault for pipeline stages
+    Secret {
+        #[command(subcommand)]
+        action: VaultAction,
+    },
     /// Feedback collection
     Feedback {
         #[command(subcommand)]
@@ -246,11 +277,26 @@ enum Commands {
         #[command(subcommand)]
         action: SlackAction,
     },
+    /// Jira issue synchronization
+    Jira {
+        #[command(subcommand)]
+        action: JiraAction,
+    },
+    /// Linear issue synchronization
+    Linear {
+        #[command(subcommand)]
+        action: LinearAction,
+    },
     /// Security scanning
     Security {
         #[command(subcommand)]
         action: SecurityAction,
     },
+    /// Reports and agent artifacts in object storage
+    Artifact {
+        #[command(subcommand)]
+        action: ArtifactAction,
+    },
     /// Epic autonomous processing
     Epic {
         #[command(subcommand)]
@@ -277,6 +323,9 @@ enum DaemonAction {
         /// Use claude CLI instead of direct API (uses OAuth auth)
         #[arg(long)]
         use_cli: bool,
+        /// Decision policy YAML file (reloaded when it changes)
+        #[arg(long)]
+        policy: Option<PathBuf>,
     },
     /// Stop the daemon
     Stop,
@@ -294,6 +343,12 @@ enum AgentAction {
         task: String,
         #[arg(short, long)]
         worktree: Option<String>,
+        /// System prompt overriding the agent type's template for this run
+        #[arg(long, conflicts_with = "system_prompt_file")]
+        system_prompt: Option<String>,
+        /// Read the system prompt override from a file
+        #[arg(long)]
+        system_prompt_file: Option<PathBuf>,
     },
     /// List agents
     List {
@@ -308,6 +363,32 @@ enum AgentAction {
     Resume { id: String },
     /// Terminate an agent
     Terminate { id: String },
+    /// Manage user-defined agent types
+    Types {
+        #[command(subcommand)]
+        action: AgentTypeAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum AgentTypeAction {
+    /// Import agent type definitions from a YAML file
+    Import {
+        /// Path to YAML file with an `agent_types:` list
+        file: PathBuf,
+    },
+    /// List user-defined agent types
+    List,
+    /// Show an agent type definition
+    Show {
+        /// Agent type name
+        name: String,
+    },
+    /// Remove a user-defined agent type
+    Remove {
+        /// Agent type name
+        name: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -329,9 +410,22 @@ enum PrAction {
         number: i32,
         #[arg(short, long, default_value = "squash")]
         strategy: String,
+        /// Clone URL of the repository (default: the origin remote)
+        #[arg(long)]
+        repo: Option<String>,
     },
     /// Show PR queue
     Queue,
+    /// Publish review and security results as the orchestrate/review check run
+    Check {
+        number: i32,
+        /// File with code reviewer output to take the verdict and issues from
+        #[arg(long)]
+        review: Option<String>,
+        /// Security scan JSON, as printed by `security scan --json`
+        #[arg(long)]
+        scan: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -691,6 +785,35 @@ enum TokensAction {
         #[arg(short, long, default_value = "30")]
         days: i32,
     },
+    /// Show spend per epic
+    Epics {
+        /// Only include the last N days (default: all time)
+        #[arg(short, long)]
+        days: Option<i32>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show spend per story
+    Stories {
+        /// Only include stories of this epic
+        #[arg(short, long)]
+        epic: Option<String>,
+        /// Only include the last N days (default: all time)
+        #[arg(short, long)]
+        days: Option<i32>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show token stats for a specific story
+    Story {
+        /// Story ID
+        story_id: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -709,6 +832,47 @@ enum ScheduleAction {
         /// Task description
         #[arg(short, long)]
         task: String,
+        /// IANA timezone the cron expression is read in (e.g. America/New_York)
+        #[arg(long, default_value = orchestrate_core::DEFAULT_TIMEZONE)]
+        timezone: String,
+        /// Maximum random delay in seconds added to each run
+        #[arg(long, default_value = "0")]
+        jitter: u32,
+        /// What to do when the previous run is still in progress (skip, queue, run-parallel)
+        #[arg(long, default_value = "run-parallel")]
+        overlap: String,
+        /// Only run after this schedule's latest run succeeded
+        #[arg(long)]
+        depends_on: Option<String>,
+        /// Pause the schedule once its runs use this many tokens in a cap period
+        #[arg(long)]
+        max_tokens: Option<u64>,
+        /// Pause the schedule once its runs cost this many USD in a cap period
+        #[arg(long)]
+        max_cost: Option<f64>,
+        /// Period the spend caps apply to (daily, weekly, monthly)
+        #[arg(long, default_value = "monthly")]
+        cap_period: String,
+        /// Alert when the schedule misses this many runs in a row
+        #[arg(long)]
+        max_missed: Option<u32>,
+    },
+    /// Schedule a single run at a future time
+    At {
+        /// Run time, e.g. 2025-01-10T02:00 (local to --timezone) or RFC 3339
+        time: String,
+        /// Agent type
+        #[arg(short, long)]
+        agent: String,
+        /// Task description
+        #[arg(short, long)]
+        task: String,
+        /// Schedule name (defaults to <agent>-at-<time>)
+        #[arg(short, long)]
+        name: Option<String>,
+        /// IANA timezone a run time without a UTC offset is read in
+        #[arg(long, default_value = orchestrate_core::DEFAULT_TIMEZONE)]
+        timezone: String,
     },
     /// List all schedules
     List,
@@ -737,6 +901,14 @@ enum ScheduleAction {
         /// Schedule name
         name: String,
     },
+    /// Show run metrics (success rate, duration, missed runs) for one or all schedules
+    Stats {
+        /// Schedule name (all schedules if omitted)
+        name: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Show schedule execution history
     History {
         /// Schedule name
@@ -752,9 +924,73 @@ enum ScheduleAction {
         /// Optional custom schedule name (defaults to template name)
         #[arg(short, long)]
         name: Option<String>,
+        /// IANA timezone the template's cron expression is read in
+        #[arg(long, default_value = orchestrate_core::DEFAULT_TIMEZONE)]
+        timezone: String,
+        /// Maximum random delay in seconds added to each run
+        #[arg(long, default_value = "0")]
+        jitter: u32,
+        /// What to do when the previous run is still in progress (skip, queue, run-parallel)
+        #[arg(long, default_value = "run-parallel")]
+        overlap: String,
+        /// Only run after this schedule's latest run succeeded
+        #[arg(long)]
+        depends_on: Option<String>,
+        /// Pause the schedule once its runs use this many tokens in a cap period
+        #[arg(long)]
+        max_tokens: Option<u64>,
+        /// Pause the schedule once its runs cost this many USD in a cap period
+        #[arg(long)]
+        max_cost: Option<f64>,
+        /// Period the spend caps apply to (daily, weekly, monthly)
+        #[arg(long, default_value = "monthly")]
+        cap_period: String,
+        /// Alert when the schedule misses this many runs in a row
+        #[arg(long)]
+        max_missed: Option<u32>,
     },
     /// List available schedule templates
     ListTemplates,
+    /// Manage blackout windows during which scheduled runs are skipped
+    Freeze {
+        #[command(subcommand)]
+        action: ScheduleFreezeAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScheduleFreezeAction {
+    /// Add a blackout window (holiday, change freeze)
+    Add {
+        /// Window name
+        name: String,
+        /// Start time, e.g. 2025-12-24T00:00 (local to --timezone) or RFC 3339
+        #[arg(long)]
+        from: String,
+        /// End time, e.g. 2025-12-27T00:00 (local to --timezone) or RFC 3339
+        #[arg(long)]
+        until: String,
+        /// Only freeze this schedule (defaults to every schedule)
+        #[arg(short, long)]
+        schedule: Option<String>,
+        /// Why runs are frozen
+        #[arg(short, long)]
+        reason: Option<String>,
+        /// IANA timezone times without a UTC offset are read in
+        #[arg(long, default_value = orchestrate_core::DEFAULT_TIMEZONE)]
+        timezone: String,
+    },
+    /// List blackout windows
+    List {
+        /// Include windows that have already ended
+        #[arg(long)]
+        all: bool,
+    },
+    /// Remove a blackout window
+    Remove {
+        /// Window name
+        name: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -792,6 +1028,21 @@ enum WebhookAction {
         #[command(subcommand)]
         action: SecretAction,
     },
+    /// Manage custom webhooks that map arbitrary payloads to events
+    Custom {
+        #[command(subcommand)]
+        action: CustomWebhookAction,
+    },
+    /// Manage outbound webhooks posting events to subscribers
+    Outbound {
+        #[command(subcommand)]
+        action: OutboundWebhookAction,
+    },
+    /// Manage per-source signature schemes and secrets
+    Source {
+        #[command(subcommand)]
+        action: SourceWebhookAction,
+    },
 }
 
 #[derive(Subcommand)]
@@ -802,12 +1053,112 @@ enum SecretAction {
     Show,
 }
 
+#[derive(Subcommand)]
+enum CustomWebhookAction {
+    /// Add a custom webhook from a YAML definition, replacing one with the same name
+    Add {
+        /// Path to YAML file
+        file: PathBuf,
+    },
+    /// List custom webhooks
+    List,
+    /// Show a custom webhook's definition
+    Show {
+        /// Webhook name
+        name: String,
+    },
+    /// Remove a custom webhook
+    Remove {
+        /// Webhook name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum OutboundWebhookAction {
+    /// Register a URL to receive events
+    Add {
+        /// URL payloads are posted to
+        url: String,
+        /// Events to send (agent.completed, agent.failed, pr.merged,
+        /// pipeline.succeeded, pipeline.failed, a prefix such as pipeline.*, or *)
+        #[arg(short, long, value_delimiter = ',', required = true)]
+        events: Vec<String>,
+        /// Signing secret (generated if not given)
+        #[arg(short, long)]
+        secret: Option<String>,
+        /// Description
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+    /// List outbound webhooks
+    List,
+    /// Remove an outbound webhook and its delivery log
+    Remove {
+        /// Webhook ID
+        id: i64,
+    },
+    /// Show recent deliveries
+    Deliveries {
+        /// Only show deliveries to this webhook
+        #[arg(short, long)]
+        webhook: Option<i64>,
+        /// Maximum number of deliveries to show
+        #[arg(short, long, default_value = "20")]
+        limit: i64,
+    },
+    /// Send a test delivery to an outbound webhook
+    Test {
+        /// Webhook ID
+        id: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum SourceWebhookAction {
+    /// Configure signature verification for a source, replacing any existing one
+    Add {
+        /// Source name (github, gitlab, or custom:<name>)
+        name: String,
+        /// Signature scheme (hmac-sha256, hmac-sha512, ed25519, token)
+        #[arg(short, long, default_value = "hmac-sha256")]
+        scheme: String,
+        /// Header carrying the signature (defaults per scheme)
+        #[arg(long)]
+        header: Option<String>,
+        /// Secret, token, or Ed25519 public key (generated for HMAC and token schemes)
+        #[arg(long)]
+        secret: Option<String>,
+    },
+    /// Rotate a source's secret, keeping the old one valid for an overlap window
+    Rotate {
+        /// Source name
+        name: String,
+        /// New secret or public key (generated for HMAC and token schemes)
+        #[arg(long)]
+        secret: Option<String>,
+        /// Hours the previous secret stays valid
+        #[arg(long, default_value = "24")]
+        overlap_hours: i64,
+    },
+    /// List configured sources
+    List,
+    /// Remove a source's signature configuration
+    Remove {
+        /// Source name
+        name: String,
+    },
+}
+
 #[derive(Subcommand)]
 enum PipelineAction {
     /// Create pipeline from YAML file
     Create {
         /// Path to YAML file
         file: PathBuf,
+        /// Accept stage agents that are neither built-in nor registered agent types
+        #[arg(long)]
+        allow_unknown_agents: bool,
     },
     /// List all pipelines
     List {
@@ -819,6 +1170,9 @@ enum PipelineAction {
     Show {
         /// Pipeline name
         name: String,
+        /// Print the stage graph instead (mermaid, dot)
+        #[arg(long, num_args = 0..=1, default_missing_value = "mermaid")]
+        graph: Option<String>,
     },
     /// Update pipeline from YAML file
     Update {
@@ -826,7 +1180,12 @@ enum PipelineAction {
         name: String,
         /// Path to YAML file
         file: PathBuf,
+        /// Accept stage agents that are neither built-in nor registered agent types
+        #[arg(long)]
+        allow_unknown_agents: bool,
     },
+    /// Print the JSON Schema for pipeline YAML files
+    Schema,
     /// Delete pipeline
     Delete {
         /// Pipeline name
@@ -846,9 +1205,18 @@ enum PipelineAction {
     Run {
         /// Pipeline name
         name: String,
-        /// Dry run - show what would be done without executing
+        /// Dry run - show the resolved plan (stages, agents, prompts, estimated cost) without executing
         #[arg(long)]
         dry_run: bool,
+        /// Output the dry-run plan as JSON
+        #[arg(long, requires = "dry_run")]
+        json: bool,
+        /// Commit SHA the run builds; stages with unchanged inputs reuse cached results
+        #[arg(long)]
+        commit: Option<String>,
+        /// Execute every stage, ignoring cached stage results
+        #[arg(long)]
+        no_cache: bool,
     },
     /// Show pipeline run status
     Status {
@@ -860,6 +1228,11 @@ enum PipelineAction {
         /// Run ID
         run_id: i64,
     },
+    /// Resume a failed run from its failed stages, reusing completed stages
+    Resume {
+        /// Run ID
+        run_id: i64,
+    },
     /// Show pipeline run history
     History {
         /// Pipeline name
@@ -882,6 +1255,37 @@ enum PipelineAction {
         #[arg(short, long)]
         force: bool,
     },
+    /// Manage reusable templates for extends: and include:
+    Template {
+        #[command(subcommand)]
+        action: PipelineTemplateAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum PipelineTemplateAction {
+    /// Store a parameterized pipeline template from a YAML file
+    Import {
+        /// Template name
+        name: String,
+        /// Path to YAML file
+        file: PathBuf,
+        /// Template description (defaults to the YAML description)
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// List built-in and stored templates
+    List,
+    /// Show a template definition
+    Show {
+        /// Template name
+        name: String,
+    },
+    /// Remove a stored template
+    Remove {
+        /// Template name
+        name: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -918,6 +1322,25 @@ enum ApprovalAction {
     },
 }
 
+#[derive(Subcommand)]
+enum VaultAction {
+    /// Store a secret, replacing any existing value
+    Set {
+        /// Secret name, exposed to stages as an environment variable of the same name
+        name: String,
+        /// Secret value (read from stdin if not given)
+        #[arg(long)]
+        value: Option<String>,
+    },
+    /// List secret names
+    List,
+    /// Delete a secret
+    Delete {
+        /// Secret name
+        name: String,
+    },
+}
+
 #[derive(Subcommand)]
 enum FeedbackAction {
     /// Add feedback for an agent
@@ -1247,6 +1670,29 @@ enum RepoAction {
         #[arg(short, long)]
         repo: Option<String>,
     },
+    /// Manage branch protection policy across repositories
+    Policy {
+        #[command(subcommand)]
+        action: RepoPolicyAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum RepoPolicyAction {
+    /// Apply a branch protection spec to every repository and report drift
+    Apply {
+        /// Branch policy spec (YAML)
+        spec: PathBuf,
+        /// Specific repository to apply to
+        #[arg(short, long)]
+        repo: Option<String>,
+        /// Only report drift; exit with an error if any branch drifted
+        #[arg(long)]
+        dry_run: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -1364,6 +1810,17 @@ enum IncidentAction {
         #[arg(short, long)]
         resolution: String,
     },
+    /// Page the on-call in PagerDuty and Opsgenie for an incident
+    Page {
+        /// Incident ID
+        id: String,
+        /// Routing key of the PagerDuty service (default: PAGERDUTY_ROUTING_KEY)
+        #[arg(long)]
+        routing_key: Option<String>,
+        /// Opsgenie team to page (requires OPSGENIE_API_KEY)
+        #[arg(long)]
+        opsgenie_team: Option<String>,
+    },
     /// Generate post-mortem
     Postmortem {
         /// Incident ID
@@ -1371,6 +1828,9 @@ enum IncidentAction {
         /// Output file
         #[arg(short, long)]
         output: Option<String>,
+        /// Upload the post-mortem to object storage
+        #[arg(long)]
+        store: bool,
     },
     /// Playbook management
     Playbook {
@@ -1429,6 +1889,9 @@ enum TestAction {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+        /// Upload the coverage report to object storage
+        #[arg(long)]
+        store: bool,
     },
     /// Run tests
     Run {
@@ -1587,6 +2050,18 @@ enum ReleaseAction {
         /// Draft release (don't make public)
         #[arg(long)]
         draft: bool,
+        /// Mark as a pre-release (implied by versions like 1.3.0-rc.1)
+        #[arg(long)]
+        prerelease: bool,
+        /// File to upload as a release asset (repeatable)
+        #[arg(long = "asset")]
+        assets: Vec<std::path::PathBuf>,
+        /// Commit or branch to tag (defaults to the default branch)
+        #[arg(long)]
+        target: Option<String>,
+        /// Previous tag to build release notes from (defaults to the latest tag)
+        #[arg(long)]
+        from: Option<String>,
     },
     /// List releases
     List {
@@ -1649,6 +2124,11 @@ enum MonitorAlertAction {
         /// Alert ID
         id: String,
     },
+    /// Resolve an alert, closing its on-call pages
+    Resolve {
+        /// Alert ID
+        id: String,
+    },
     /// Silence an alert rule
     Silence {
         /// Rule name
@@ -1690,6 +2170,12 @@ enum CostAction {
     ByAgent,
     /// Show cost breakdown by model
     ByModel,
+    /// Email the daily cost summary to users who opted in
+    EmailSummary {
+        /// Day to summarize (YYYY-MM-DD, default: yesterday)
+        #[arg(long)]
+        date: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -1728,33 +2214,148 @@ enum MonitorAuditAction {
 }
 
 #[derive(Subcommand)]
-enum SlackAction {
-    /// Connect to Slack workspace
-    Connect {
-        /// Bot token (xoxb-...)
-        #[arg(short, long, env = "SLACK_BOT_TOKEN")]
-        token: String,
+enum JiraAction {
+    /// Manage per-project Jira configuration
+    Project {
+        #[command(subcommand)]
+        action: JiraProjectAction,
     },
-    /// Disconnect from Slack
-    Disconnect,
-    /// Show Slack connection status
-    Status,
-    /// List available channels
-    Channels,
-    /// Set channel for notification type
-    Channel {
-        /// Notification type (agent_completed, pr_created, etc.)
-        #[arg(short = 't', long)]
-        notification_type: String,
-        /// Channel name
+    /// Create and transition Jira issues for stories and pull their comments
+    Sync {
+        /// Only sync stories of this epic
+        #[arg(short, long, conflicts_with = "story")]
+        epic: Option<String>,
+        /// Only sync this story
         #[arg(short, long)]
-        channel: String,
+        story: Option<String>,
     },
-    /// Send test message
-    Test {
-        /// Channel to send to
-        #[arg(short, long, default_value = "#orchestrate")]
-        channel: String,
+    /// Show the Jira comments pulled for a story
+    Comments {
+        /// Story ID
+        story: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum JiraProjectAction {
+    /// Configure a Jira project, replacing one with the same name
+    Add {
+        /// Configuration name
+        name: String,
+        /// Jira base URL (e.g., https://company.atlassian.net)
+        #[arg(long)]
+        url: String,
+        /// Jira project key
+        #[arg(short, long)]
+        key: String,
+        /// Only sync epics whose ID starts with this prefix
+        #[arg(long, default_value = "")]
+        epic_prefix: String,
+        /// Issue type created for epics
+        #[arg(long, default_value = "Epic")]
+        epic_type: String,
+        /// Issue type created for stories
+        #[arg(long, default_value = "Story")]
+        story_type: String,
+        /// Jira status for a story status, as <status>=<Jira status>
+        /// (e.g., completed=Closed)
+        #[arg(long = "status")]
+        statuses: Vec<String>,
+        /// Jira field for a story field, as <field>=<Jira field ID>
+        /// (e.g., epic_key=customfield_10014)
+        #[arg(long = "field")]
+        fields: Vec<String>,
+    },
+    /// List Jira projects
+    List,
+    /// Remove a Jira project and its issue links
+    Remove {
+        /// Configuration name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum LinearAction {
+    /// Manage per-team Linear configuration
+    Team {
+        #[command(subcommand)]
+        action: LinearTeamAction,
+    },
+    /// Create Linear issues for stories and move them to the stories' statuses
+    Sync {
+        /// Only sync stories of this epic
+        #[arg(short, long, conflicts_with = "story")]
+        epic: Option<String>,
+        /// Only sync this story
+        #[arg(short, long)]
+        story: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum LinearTeamAction {
+    /// Configure a Linear team, replacing one with the same name
+    Add {
+        /// Configuration name
+        name: String,
+        /// Linear team ID (UUID)
+        #[arg(long)]
+        team_id: String,
+        /// Only sync epics whose ID starts with this prefix
+        #[arg(long, default_value = "")]
+        epic_prefix: String,
+        /// Workflow state for a story status, as <status>=<state name>
+        /// (e.g., completed=Merged)
+        #[arg(long = "status")]
+        statuses: Vec<String>,
+        /// Label that spawns an agent for an issue
+        #[arg(long, default_value = orchestrate_core::linear::DEFAULT_TRIGGER_LABEL)]
+        trigger_label: String,
+        /// Type of the agents spawned from issues
+        #[arg(long, default_value = "story_developer")]
+        agent_type: String,
+    },
+    /// List Linear teams
+    List,
+    /// Remove a Linear team and its issue links
+    Remove {
+        /// Configuration name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum SlackAction {
+    /// Connect to Slack workspace
+    Connect {
+        /// Bot token (xoxb-...)
+        #[arg(short, long, env = "SLACK_BOT_TOKEN")]
+        token: String,
+    },
+    /// Disconnect from Slack
+    Disconnect,
+    /// Show Slack connection status
+    Status,
+    /// List available channels
+    Channels,
+    /// Set channel for notification type
+    Channel {
+        /// Notification type (agent_completed, pr_created, etc.)
+        #[arg(short = 't', long)]
+        notification_type: String,
+        /// Channel name
+        #[arg(short, long, required_unless_present = "google_chat")]
+        channel: Option<String>,
+        /// Google Chat incoming webhook URL that also receives this type
+        #[arg(long)]
+        google_chat: Option<String>,
+    },
+    /// Send test message
+    Test {
+        /// Channel to send to
+        #[arg(short, long, default_value = "#orchestrate")]
+        channel: String,
     },
     /// Map GitHub user to Slack user
     MapUser {
@@ -1762,11 +2363,32 @@ enum SlackAction {
         #[arg(long)]
         github: String,
         /// Slack user ID
+        #[arg(long, required_unless_present = "email", conflicts_with = "email")]
+        slack: Option<String>,
+        /// Look up the Slack user by email address
         #[arg(long)]
-        slack: String,
+        email: Option<String>,
     },
     /// List user mappings
     Users,
+    /// Configure email notifications for a mapped user
+    Email {
+        /// GitHub username
+        #[arg(long)]
+        github: String,
+        /// Email address
+        #[arg(long)]
+        address: Option<String>,
+        /// Email approval requests (true/false)
+        #[arg(long)]
+        approvals: Option<bool>,
+        /// Email incident escalations (true/false)
+        #[arg(long)]
+        incidents: Option<bool>,
+        /// Email the daily cost summary (true/false)
+        #[arg(long)]
+        cost_summary: Option<bool>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -1791,6 +2413,9 @@ enum SecurityAction {
         /// Output file
         #[arg(short, long)]
         output: Option<String>,
+        /// Upload the report to object storage
+        #[arg(long)]
+        store: bool,
     },
     /// Fix a specific vulnerability
     Fix {
@@ -1809,6 +2434,47 @@ enum SecurityAction {
     Baseline,
 }
 
+#[derive(Subcommand)]
+enum ArtifactAction {
+    /// Upload a file produced by an agent
+    Upload {
+        /// File to upload
+        file: String,
+        /// Agent that produced the file
+        #[arg(short, long)]
+        agent: String,
+        /// Content type (default: guessed from the file extension)
+        #[arg(long)]
+        content_type: Option<String>,
+    },
+    /// List stored artifacts
+    List {
+        /// Only artifacts of this kind (security_report, coverage_report,
+        /// postmortem, agent_artifact)
+        #[arg(short, long)]
+        kind: Option<String>,
+        /// Only artifacts of this scan, project, incident, or agent
+        #[arg(short, long)]
+        owner: Option<String>,
+        /// Maximum number of artifacts
+        #[arg(short, long, default_value = "20")]
+        limit: i64,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print a signed download URL
+    Url {
+        /// Artifact ID
+        id: i64,
+    },
+    /// Delete an artifact
+    Delete {
+        /// Artifact ID
+        id: i64,
+    },
+}
+
 #[derive(Subcommand)]
 enum EpicAction {
     /// Start autonomous epic processing
@@ -1844,6 +2510,11 @@ enum EpicAction {
     },
     /// List stuck agents
     StuckAgents,
+    /// Configure stuck detectors per agent type
+    StuckConfig {
+        #[command(subcommand)]
+        action: StuckConfigAction,
+    },
     /// Unblock a blocked epic
     Unblock {
         /// Epic ID to unblock
@@ -1862,6 +2533,46 @@ enum EpicAction {
     },
 }
 
+#[derive(Subcommand)]
+enum StuckConfigAction {
+    /// List agent types with stored stuck detection config
+    List,
+    /// Show the stuck detection config that applies to an agent type
+    Show {
+        /// Agent type ("default" applies to types without their own config)
+        #[arg(default_value = "default")]
+        agent_type: String,
+    },
+    /// Set a detector threshold (e.g. token_burn_threshold 50000)
+    Set {
+        /// Agent type
+        agent_type: String,
+        /// Parameter name
+        param: String,
+        /// New value
+        value: String,
+    },
+    /// Enable a detector (e.g. no_file_changes, error_loop, token_burn, rate_limit)
+    Enable {
+        /// Agent type
+        agent_type: String,
+        /// Detector name
+        detector: String,
+    },
+    /// Disable a detector
+    Disable {
+        /// Agent type
+        agent_type: String,
+        /// Detector name
+        detector: String,
+    },
+    /// Remove the stored config for an agent type
+    Reset {
+        /// Agent type
+        agent_type: String,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -1869,6 +2580,21 @@ async fn main() -> Result<()> {
     // Initialize logging with CLI options
     init_logging(cli.verbose, cli.quiet, cli.log_json)?;
 
+    // Report panics and agent errors to Sentry when a DSN is configured
+    if let Err(e) = orchestrate_core::sentry::init_from_env() {
+        warn!("Sentry reporting disabled: {}", e);
+    }
+
+    // Message the developer on Telegram when agents finish, if a bot is configured
+    if let Err(e) = orchestrate_core::telegram::init_from_env() {
+        warn!("Telegram notifications disabled: {}", e);
+    }
+
+    // Publish agent lifecycle events to NATS or Kafka when a bus is configured
+    if let Err(e) = orchestrate_core::event_bus::init_from_env().await {
+        warn!("Event bus publishing disabled: {}", e);
+    }
+
     // Expand home directory
     let db_path = shellexpand::tilde(&cli.db_path).to_string();
     let db_path = PathBuf::from(db_path);
@@ -1888,8 +2614,9 @@ async fn main() -> Result<()> {
                 poll_interval,
                 model,
                 use_cli,
+                policy,
             } => {
-                run_daemon(db, port, max_concurrent, poll_interval, model, use_cli).await?;
+                run_daemon(db, port, max_concurrent, poll_interval, model, use_cli, policy).await?;
             }
             DaemonAction::Stop => {
                 println!("Stopping daemon...");
@@ -1907,16 +2634,37 @@ async fn main() -> Result<()> {
                 agent_type,
                 task,
                 worktree,
+                system_prompt,
+                system_prompt_file,
             } => {
-                let agent_type = parse_agent_type(&agent_type)?;
-                let mut agent = Agent::new(agent_type, task);
+                let mut agent = match parse_agent_type(&agent_type) {
+                    Ok(agent_type) => Agent::new(agent_type, task),
+                    Err(e) => match db.get_agent_type_definition(&agent_type).await? {
+                        Some(def) => Agent::new(def.base_type, task).with_custom_type(def.name),
+                        None => return Err(e),
+                    },
+                };
 
                 if let Some(wt) = worktree {
                     agent = agent.with_worktree(wt);
                 }
 
+                let system_prompt = match system_prompt_file {
+                    Some(path) => Some(std::fs::read_to_string(&path)?),
+                    None => system_prompt,
+                };
+                if let Some(prompt) = system_prompt {
+                    agent = agent.with_system_prompt(prompt);
+                }
+
                 db.insert_agent(&agent).await?;
                 println!("Agent spawned: {}", agent.id);
+                if let Some(version) = db.record_agent_prompt_override(&agent).await? {
+                    println!(
+                        "System prompt override recorded as {} prompt version {}",
+                        version.agent_type, version.version
+                    );
+                }
             }
             AgentAction::List { state: _ } => {
                 let agents = db.list_agents().await?;
@@ -1936,9 +2684,20 @@ async fn main() -> Result<()> {
                 let uuid = uuid::Uuid::parse_str(&id)?;
                 if let Some(agent) = db.get_agent(uuid).await? {
                     println!("Agent: {}", agent.id);
-                    println!("Type: {:?}", agent.agent_type);
+                    match agent.custom_type {
+                        Some(ref custom) => {
+                            println!("Type: {} (base: {:?})", custom, agent.agent_type)
+                        }
+                        None => println!("Type: {:?}", agent.agent_type),
+                    }
                     println!("State: {:?}", agent.state);
                     println!("Task: {}", agent.task);
+                    if agent.system_prompt.is_some() {
+                        println!("System Prompt: overridden");
+                    }
+                    if let Some(ref trace_id) = agent.trace_id {
+                        println!("Trace: {}", trace_id);
+                    }
                     println!("Created: {}", agent.created_at);
                     println!("Updated: {}", agent.updated_at);
                 } else {
@@ -1975,6 +2734,61 @@ async fn main() -> Result<()> {
                     println!("Agent not found: {}", id);
                 }
             }
+            AgentAction::Types { action } => match action {
+                AgentTypeAction::Import { file } => {
+                    let types = orchestrate_core::AgentTypesFile::load(&file)?;
+                    for def in &types.agent_types {
+                        db.upsert_agent_type_definition(def).await?;
+                        println!("Imported agent type: {} (base: {})", def.name, def.base_type.as_str());
+                    }
+                    println!("{} agent type(s) imported", types.agent_types.len());
+                }
+                AgentTypeAction::List => {
+                    let defs = db.list_agent_type_definitions().await?;
+                    if defs.is_empty() {
+                        println!("No custom agent types defined");
+                    } else {
+                        println!("{:<28} {:<24} {:<8} DESCRIPTION", "NAME", "BASE TYPE", "TURNS");
+                        println!("{}", "-".repeat(90));
+                        for def in defs {
+                            println!(
+                                "{:<28} {:<24} {:<8} {}",
+                                def.name,
+                                def.base_type.as_str(),
+                                def.effective_max_turns(),
+                                def.description.as_deref().unwrap_or("-")
+                            );
+                        }
+                    }
+                }
+                AgentTypeAction::Show { name } => {
+                    if let Some(def) = db.get_agent_type_definition(&name).await? {
+                        println!("Name: {}", def.name);
+                        println!("Base type: {}", def.base_type.as_str());
+                        if let Some(ref desc) = def.description {
+                            println!("Description: {}", desc);
+                        }
+                        println!("Model: {}", def.effective_model());
+                        println!("Max turns: {}", def.effective_max_turns());
+                        println!("Tools: {}", def.effective_allowed_tools().join(", "));
+                        if !def.skills.is_empty() {
+                            println!("Skills: {}", def.skills.join(", "));
+                        }
+                        if let Some(ref prompt) = def.system_prompt {
+                            println!("\nSystem prompt:\n{}", prompt);
+                        }
+                    } else {
+                        println!("Agent type not found: {}", name);
+                    }
+                }
+                AgentTypeAction::Remove { name } => {
+                    if db.delete_agent_type_definition(&name).await? {
+                        println!("Agent type removed: {}", name);
+                    } else {
+                        println!("Agent type not found: {}", name);
+                    }
+                }
+            },
         },
 
         Commands::Pr { action } => match action {
@@ -1998,9 +2812,62 @@ async fn main() -> Result<()> {
             } => {
                 println!("Creating PR... (not implemented)");
             }
-            PrAction::Merge { number, strategy } => {
-                println!("Merging PR #{} with {} strategy...", number, strategy);
-                // TODO: Implement merge
+            PrAction::Merge {
+                number,
+                strategy,
+                repo,
+            } => {
+                use orchestrate_core::{PrWorkflowContext, PrWorkflowManager};
+
+                let repository = pr_repository(repo)?;
+                let provider = pr_provider(&repository)?;
+                let mut context = PrWorkflowContext::new(number as u64, "", "cli", "", "");
+                context.merge_method = strategy.parse()?;
+
+                println!(
+                    "Merging PR #{} on {} with {} strategy...",
+                    number,
+                    provider.name(),
+                    strategy
+                );
+                let manager = PrWorkflowManager::new();
+                manager.sync_pr(provider.as_ref(), &mut context).await?;
+                manager.merge_pr(provider.as_ref(), &mut context).await?;
+                println!("Merged PR #{}", number);
+            }
+            PrAction::Check {
+                number,
+                review,
+                scan,
+            } => {
+                use orchestrate_core::{CodeReviewCoordinator, SecurityPolicy, SecurityScan};
+                use orchestrate_github::{CheckRunReport, GitHubClient};
+
+                if review.is_none() && scan.is_none() {
+                    anyhow::bail!("Nothing to publish: pass --review and/or --scan");
+                }
+
+                let mut report = CheckRunReport::new();
+                if let Some(path) = review {
+                    let output = std::fs::read_to_string(&path)?;
+                    let result = CodeReviewCoordinator::new().parse_review_output(&output);
+                    report = report.with_review(&result);
+                }
+                if let Some(path) = scan {
+                    let scan: SecurityScan = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+                    report = report.with_security_scan(&scan, &SecurityPolicy::default());
+                }
+
+                let client = GitHubClient::new()?;
+                let head_sha = client.get_pr_status(number)?.head_sha;
+                let id = client.publish_review_check(&head_sha, &report)?;
+                println!(
+                    "Published check run {} on {}: {:?} ({})",
+                    id,
+                    &head_sha[..head_sha.len().min(7)],
+                    report.conclusion,
+                    report.title
+                );
             }
             PrAction::Queue => {
                 // Read from shell state file for compatibility
@@ -2051,7 +2918,13 @@ async fn main() -> Result<()> {
                     println!();
                     println!("=== Database PRs ===");
                     for pr in db_prs {
-                        println!("  - {} ({:?})", pr.branch_name, pr.status);
+                        match pr.merge_queue_position {
+                            Some(position) => println!(
+                                "  - {} ({:?}, merge queue position {})",
+                                pr.branch_name, pr.status, position
+                            ),
+                            None => println!("  - {} ({:?})", pr.branch_name, pr.status),
+                        }
                     }
                 }
             }
@@ -2151,14 +3024,34 @@ async fn main() -> Result<()> {
                 if let Some(ref desc) = description {
                     println!("  Description: {}", desc);
                 }
+
+                if let Some(jira) = orchestrate_core::JiraSyncService::from_env(db.clone()) {
+                    match jira.sync_story(&story).await {
+                        Ok(Some(action)) => println!("  Jira issue: {}", action.issue_key()),
+                        Ok(None) => {}
+                        Err(e) => eprintln!("  Failed to create Jira issue: {}", e),
+                    }
+                }
+
+                let linear = orchestrate_core::LinearSyncService::from_env(db.clone());
+                if linear.has_client() {
+                    match linear.sync_story(&story).await {
+                        Ok(Some(action)) => println!("  Linear issue: {}", action.identifier()),
+                        Ok(None) => {}
+                        Err(e) => eprintln!("  Failed to create Linear issue: {}", e),
+                    }
+                }
             }
         },
 
-        Commands::Web { port } => {
+        Commands::Web { port, tls_cert, tls_key } => {
             use orchestrate_web::{api::AppState, create_router};
             use std::sync::Arc;
 
-            println!("Starting web server on http://localhost:{}", port);
+            let scheme = if tls_cert.is_some() { "https" } else { "http" };
+            println!("Starting web server on {}://localhost:{}", scheme, port);
+            #[cfg(feature = "graphql")]
+            println!("GraphQL API at {}://localhost:{}/api/graphql", scheme, port);
 
             // Get API key from environment if set
             let api_key = std::env::var("ORCHESTRATE_API_KEY").ok();
@@ -2166,14 +3059,70 @@ async fn main() -> Result<()> {
                 println!("API key authentication enabled");
             }
 
-            let state = Arc::new(AppState::new(db, api_key));
+            // OIDC login issues session tokens signed with ORCHESTRATE_JWT_SECRET
+            let auth = orchestrate_web::AuthConfig::from_env();
+            if let Some(auth) = &auth {
+                let providers: Vec<&str> = auth.providers().map(|p| p.as_str()).collect();
+                if providers.is_empty() {
+                    println!("Session tokens enabled, no identity providers configured");
+                } else {
+                    println!("OIDC login enabled: {}", providers.join(", "));
+                }
+            }
+
+            // Datadog exports the same metrics served on /metrics
+            let metrics = Arc::new(orchestrate_web::MetricsCollector::default());
+            spawn_datadog_exporter(Arc::new(db.clone()), metrics.clone());
+
+            let mut state = AppState::new(db.clone(), api_key)
+                .with_metrics(metrics)
+                .with_rbac(orchestrate_web::RbacPolicy::from_env())
+                .with_probes(orchestrate_web::ProbeConfig::from_env());
+            if let Some(auth) = auth {
+                state = state.with_auth(auth);
+            }
+            if let Some(limits) = orchestrate_web::RateLimitConfig::from_env() {
+                println!("API rate limits enabled");
+                state = state.with_rate_limit(limits);
+            }
+            if let Some(cors) = orchestrate_web::CorsConfig::from_env() {
+                if cors.allows_any_origin() {
+                    println!("CORS enabled for any origin");
+                } else {
+                    println!("CORS enabled for {} origin(s)", cors.allowed_origins.len());
+                }
+                state = state.with_cors(cors);
+            }
+            let state = Arc::new(with_artifact_storage(state, &db)?);
+            publish_network_events(&state);
             let app = create_router(state);
 
-            let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
-            axum::serve(listener, app).await?;
+            // Peer addresses identify anonymous clients for rate limits
+            let app = app.into_make_service_with_connect_info::<std::net::SocketAddr>();
+            if let (Some(cert), Some(key)) = (tls_cert, tls_key) {
+                let tls = axum_server::tls_rustls::RustlsConfig::from_pem_file(&cert, &key)
+                    .await
+                    .map_err(|e| {
+                        anyhow::anyhow!(
+                            "Failed to load TLS certificate {} and key {}: {}",
+                            cert.display(),
+                            key.display(),
+                            e
+                        )
+                    })?;
+                let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
+                axum_server::bind_rustls(addr, tls).serve(app).await?;
+            } else {
+                let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
+                axum::serve(listener, app).await?;
+            }
+        }
+
+        Commands::Status { json, watch: true, server, kind } => {
+            watch_events(&server, kind.as_deref(), json).await?;
         }
 
-        Commands::Status { json } => {
+        Commands::Status { json, .. } => {
             let agents = db.list_agents().await?;
             let running = agents
                 .iter()
@@ -3542,6 +4491,50 @@ async fn main() -> Result<()> {
                 );
                 println!("╚══════════════════════════════════════════════════════════════╝");
             }
+            TokensAction::Epics { days, json } => {
+                let costs = db.list_epic_costs(days).await?;
+                print_entity_costs("EPIC", &costs, json)?;
+            }
+            TokensAction::Stories { epic, days, json } => {
+                let costs = db.list_story_costs(epic.as_deref(), days).await?;
+                print_entity_costs("STORY", &costs, json)?;
+            }
+            TokensAction::Story { story_id, json } => {
+                let stats = db.get_story_token_stats(&story_id).await?;
+                let cost: f64 = db
+                    .list_story_costs(None, None)
+                    .await?
+                    .iter()
+                    .filter(|c| c.entity_id == story_id)
+                    .map(|c| c.estimated_cost_usd)
+                    .sum();
+
+                if json {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&serde_json::json!({
+                            "story_id": story_id,
+                            "stats": stats,
+                            "estimated_cost_usd": cost,
+                        }))?
+                    );
+                    return Ok(());
+                }
+
+                println!("Token Statistics for Story {}", story_id);
+                println!("{}", "=".repeat(50));
+                println!("Turns:                  {:>12}", stats.turn_count);
+                println!(
+                    "Input tokens:           {:>12}",
+                    format_tokens(stats.total_input_tokens)
+                );
+                println!(
+                    "Output tokens:          {:>12}",
+                    format_tokens(stats.total_output_tokens)
+                );
+                println!("Cache hit rate:         {:>11.1}%", stats.cache_hit_rate);
+                println!("Est. cost to date:      {:>12}", format!("${:.4}", cost));
+            }
         },
 
         Commands::Schedule { action } => match action {
@@ -3550,14 +4543,37 @@ async fn main() -> Result<()> {
                 cron,
                 agent,
                 task,
+                timezone,
+                jitter,
+                overlap,
+                depends_on,
+                max_tokens,
+                max_cost,
+                cap_period,
+                max_missed,
             } => {
+                let cap_period: orchestrate_core::BudgetPeriod =
+                    cap_period.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+
                 // Create and validate schedule
-                let mut schedule = Schedule::new(name.clone(), cron.clone(), agent.clone(), task.clone());
+                let mut schedule = Schedule::new(name.clone(), cron.clone(), agent.clone(), task.clone())
+                    .with_timezone(timezone)
+                    .with_jitter(jitter)
+                    .with_overlap_policy(overlap.parse()?)
+                    .with_spend_cap_period(cap_period);
+                if let Some(depends_on) = depends_on {
+                    db.validate_schedule_dependency(&name, &depends_on).await?;
+                    schedule = schedule.with_depends_on(depends_on);
+                }
+                schedule.max_tokens = max_tokens;
+                schedule.max_cost_usd = max_cost;
+                schedule.max_consecutive_misses = max_missed;
 
                 // Validate cron expression
                 if let Err(e) = schedule.validate_cron() {
                     anyhow::bail!("Invalid cron expression: {}", e);
                 }
+                schedule.validate_timezone()?;
 
                 // Check if schedule with this name already exists
                 if db.get_schedule_by_name(&name).await?.is_some() {
@@ -3572,8 +4588,35 @@ async fn main() -> Result<()> {
 
                 println!("Schedule '{}' added successfully (ID: {})", name, id);
                 if let Some(next_run) = schedule.next_run {
-                    println!("Next run: {}", next_run.format("%Y-%m-%d %H:%M:%S UTC"));
+                    println!("Next run: {}", schedule.format_local(&next_run));
+                }
+            }
+
+            ScheduleAction::At {
+                time,
+                agent,
+                task,
+                name,
+                timezone,
+            } => {
+                let run_at = orchestrate_core::parse_run_at(&time, &timezone)?;
+                if run_at <= chrono::Utc::now() {
+                    anyhow::bail!("Run time {} is in the past", run_at.format("%Y-%m-%d %H:%M:%S UTC"));
+                }
+
+                let name = name.unwrap_or_else(|| format!("{}-at-{}", agent, run_at.format("%Y%m%d%H%M")));
+                if db.get_schedule_by_name(&name).await?.is_some() {
+                    anyhow::bail!("Schedule '{}' already exists", name);
                 }
+
+                let mut schedule = Schedule::once(name.clone(), run_at, agent, task)
+                    .with_timezone(timezone);
+                schedule.update_next_run()?;
+
+                let id = db.insert_schedule(&schedule).await?;
+
+                println!("One-shot schedule '{}' added successfully (ID: {})", name, id);
+                println!("Runs at: {}", schedule.format_local(&run_at));
             }
 
             ScheduleAction::List => {
@@ -3584,19 +4627,21 @@ async fn main() -> Result<()> {
                     return Ok(());
                 }
 
-                println!("{:<20} {:<15} {:<20} {:<10} {:<25}", "NAME", "CRON", "AGENT", "STATUS", "NEXT RUN");
-                println!("{}", "-".repeat(100));
+                println!("{:<20} {:<15} {:<20} {:<20} {:<10} {:<25}", "NAME", "CRON", "TIMEZONE", "AGENT", "STATUS", "NEXT RUN");
+                println!("{}", "-".repeat(120));
 
                 for schedule in schedules {
                     let status = if schedule.enabled { "enabled" } else { "disabled" };
                     let next_run = schedule.next_run
-                        .map(|nr| nr.format("%Y-%m-%d %H:%M:%S").to_string())
+                        .map(|nr| schedule.format_local(&nr))
                         .unwrap_or_else(|| "-".to_string());
+                    let cron = if schedule.is_one_shot() { "once" } else { schedule.cron_expression.as_str() };
 
                     println!(
-                        "{:<20} {:<15} {:<20} {:<10} {:<25}",
+                        "{:<20} {:<15} {:<20} {:<20} {:<10} {:<25}",
                         schedule.name,
-                        schedule.cron_expression,
+                        cron,
+                        schedule.timezone,
                         schedule.agent_type,
                         status,
                         next_run
@@ -3609,18 +4654,48 @@ async fn main() -> Result<()> {
                     .ok_or_else(|| anyhow::anyhow!("Schedule not found: {}", name))?;
 
                 println!("Schedule: {}", schedule.name);
-                println!("Cron: {}", schedule.cron_expression);
+                match schedule.run_at {
+                    Some(run_at) => println!("Runs once at: {}", schedule.format_local(&run_at)),
+                    None => println!("Cron: {}", schedule.cron_expression),
+                }
+                println!("Timezone: {}", schedule.timezone);
+                println!("Jitter: {}s", schedule.jitter_secs);
+                println!("Overlap policy: {}", schedule.overlap_policy.as_str());
+                if let Some(depends_on) = &schedule.depends_on {
+                    println!("Depends on: {}", depends_on);
+                }
+                if schedule.has_spend_cap() {
+                    let spend = db
+                        .get_schedule_spend(
+                            schedule.id,
+                            schedule.spend_period_start(&chrono::Utc::now()),
+                        )
+                        .await?;
+                    println!("Spend cap ({}):", schedule.spend_cap_period);
+                    if let Some(max_tokens) = schedule.max_tokens {
+                        println!("  Tokens: {} of {}", spend.tokens, max_tokens);
+                    }
+                    if let Some(max_cost) = schedule.max_cost_usd {
+                        println!("  Cost: ${:.2} of ${:.2}", spend.cost_usd, max_cost);
+                    }
+                }
+                if let Some(max_misses) = schedule.max_consecutive_misses {
+                    println!(
+                        "Missed runs: {} in a row (alert at {})",
+                        schedule.consecutive_missed_runs, max_misses
+                    );
+                }
                 println!("Agent: {}", schedule.agent_type);
                 println!("Task: {}", schedule.task);
                 println!("Enabled: {}", schedule.enabled);
                 println!("Created: {}", schedule.created_at.format("%Y-%m-%d %H:%M:%S UTC"));
 
                 if let Some(last_run) = schedule.last_run {
-                    println!("Last run: {}", last_run.format("%Y-%m-%d %H:%M:%S UTC"));
+                    println!("Last run: {}", schedule.format_local(&last_run));
                 }
 
                 if let Some(next_run) = schedule.next_run {
-                    println!("Next run: {}", next_run.format("%Y-%m-%d %H:%M:%S UTC"));
+                    println!("Next run: {}", schedule.format_local(&next_run));
                 }
 
                 // Show recent runs
@@ -3662,7 +4737,7 @@ async fn main() -> Result<()> {
 
                 println!("Schedule '{}' resumed", name);
                 if let Some(next_run) = schedule.next_run {
-                    println!("Next run: {}", next_run.format("%Y-%m-%d %H:%M:%S UTC"));
+                    println!("Next run: {}", schedule.format_local(&next_run));
                 }
             }
 
@@ -3699,18 +4774,86 @@ async fn main() -> Result<()> {
                 println!("Triggered schedule '{}' (run ID: {}, agent ID: {})", name, run_id, agent.id);
             }
 
-            ScheduleAction::History { name, limit } => {
-                let schedule = db.get_schedule_by_name(&name).await?
-                    .ok_or_else(|| anyhow::anyhow!("Schedule not found: {}", name))?;
-
-                let runs = db.get_schedule_runs(schedule.id, limit).await?;
+            ScheduleAction::Stats { name, json } => {
+                let schedules = match name {
+                    Some(name) => vec![db
+                        .get_schedule_by_name(&name)
+                        .await?
+                        .ok_or_else(|| anyhow::anyhow!("Schedule not found: {}", name))?],
+                    None => db.list_schedules(false).await?,
+                };
 
-                if runs.is_empty() {
-                    println!("No execution history for schedule '{}'", name);
-                    return Ok(());
+                let mut all_stats = Vec::new();
+                for schedule in schedules {
+                    if let Some(stats) = db.get_schedule_stats(schedule.id).await? {
+                        all_stats.push((schedule, stats));
+                    }
                 }
 
-                println!("Execution history for '{}' (last {} runs)", name, limit);
+                if json {
+                    let output: Vec<_> = all_stats
+                        .iter()
+                        .map(|(schedule, stats)| {
+                            serde_json::json!({
+                                "name": schedule.name,
+                                "stats": stats,
+                                "success_rate": stats.success_rate(),
+                                "max_consecutive_misses": schedule.max_consecutive_misses,
+                            })
+                        })
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&output)?);
+                    return Ok(());
+                }
+
+                if all_stats.is_empty() {
+                    println!("No schedules found");
+                    return Ok(());
+                }
+
+                println!(
+                    "{:<20} {:>6} {:>6} {:>6} {:>8} {:>9} {:>12} {:>7} {:>7}",
+                    "NAME", "RUNS", "OK", "FAILED", "SKIPPED", "SUCCESS", "AVG DURATION", "MISSED", "STREAK"
+                );
+                println!("{}", "-".repeat(92));
+
+                for (schedule, stats) in &all_stats {
+                    let success = stats
+                        .success_rate()
+                        .map(|rate| format!("{:.1}%", rate))
+                        .unwrap_or_else(|| "-".to_string());
+                    let duration = stats
+                        .avg_duration_secs
+                        .map(|secs| format!("{:.1}s", secs))
+                        .unwrap_or_else(|| "-".to_string());
+
+                    println!(
+                        "{:<20} {:>6} {:>6} {:>6} {:>8} {:>9} {:>12} {:>7} {:>7}",
+                        schedule.name,
+                        stats.total_runs,
+                        stats.succeeded_runs,
+                        stats.failed_runs,
+                        stats.skipped_runs,
+                        success,
+                        duration,
+                        stats.missed_runs,
+                        stats.consecutive_missed_runs
+                    );
+                }
+            }
+
+            ScheduleAction::History { name, limit } => {
+                let schedule = db.get_schedule_by_name(&name).await?
+                    .ok_or_else(|| anyhow::anyhow!("Schedule not found: {}", name))?;
+
+                let runs = db.get_schedule_runs(schedule.id, limit).await?;
+
+                if runs.is_empty() {
+                    println!("No execution history for schedule '{}'", name);
+                    return Ok(());
+                }
+
+                println!("Execution history for '{}' (last {} runs)", name, limit);
                 println!("{:<20} {:<15} {:<38} {:<10}", "STARTED", "STATUS", "AGENT", "DURATION");
                 println!("{}", "-".repeat(90));
 
@@ -3730,12 +4873,27 @@ async fn main() -> Result<()> {
                     );
 
                     if let Some(error) = &run.error_message {
-                        println!("  Error: {}", error);
+                        if run.status == ScheduleRunStatus::Skipped {
+                            println!("  Reason: {}", error);
+                        } else {
+                            println!("  Error: {}", error);
+                        }
                     }
                 }
             }
 
-            ScheduleAction::AddTemplate { template_name, name } => {
+            ScheduleAction::AddTemplate {
+                template_name,
+                name,
+                timezone,
+                jitter,
+                overlap,
+                depends_on,
+                max_tokens,
+                max_cost,
+                cap_period,
+                max_missed,
+            } => {
                 // Get the template
                 let template = orchestrate_core::schedule_template::get_template(&template_name)
                     .ok_or_else(|| anyhow::anyhow!("Template '{}' not found. Use 'orchestrate schedule list-templates' to see available templates", template_name))?;
@@ -3748,13 +4906,28 @@ async fn main() -> Result<()> {
                     anyhow::bail!("Schedule '{}' already exists", schedule_name);
                 }
 
+                let cap_period: orchestrate_core::BudgetPeriod =
+                    cap_period.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+
                 // Create schedule from template
                 let mut schedule = Schedule::new(
                     schedule_name.clone(),
                     template.cron.clone(),
                     template.agent.clone(),
                     template.task.clone(),
-                );
+                )
+                .with_timezone(timezone)
+                .with_jitter(jitter)
+                .with_overlap_policy(overlap.parse()?)
+                .with_spend_cap_period(cap_period);
+                schedule.validate_timezone()?;
+                if let Some(depends_on) = depends_on {
+                    db.validate_schedule_dependency(&schedule_name, &depends_on).await?;
+                    schedule = schedule.with_depends_on(depends_on);
+                }
+                schedule.max_tokens = max_tokens;
+                schedule.max_cost_usd = max_cost;
+                schedule.max_consecutive_misses = max_missed;
 
                 // Calculate next run
                 schedule.update_next_run()?;
@@ -3767,7 +4940,7 @@ async fn main() -> Result<()> {
                 println!("Cron: {}", template.cron);
                 println!("Agent: {}", template.agent);
                 if let Some(next_run) = schedule.next_run {
-                    println!("Next run: {}", next_run.format("%Y-%m-%d %H:%M:%S UTC"));
+                    println!("Next run: {}", schedule.format_local(&next_run));
                 }
                 println!("Schedule ID: {}", id);
             }
@@ -3800,6 +4973,82 @@ async fn main() -> Result<()> {
 
                 println!("To add a template, use: orchestrate schedule add-template <template-name>");
             }
+
+            ScheduleAction::Freeze { action } => match action {
+                ScheduleFreezeAction::Add {
+                    name,
+                    from,
+                    until,
+                    schedule,
+                    reason,
+                    timezone,
+                } => {
+                    let starts_at = orchestrate_core::parse_run_at(&from, &timezone)?;
+                    let ends_at = orchestrate_core::parse_run_at(&until, &timezone)?;
+                    let mut blackout = orchestrate_core::ScheduleBlackout::new(name.clone(), starts_at, ends_at);
+
+                    if let Some(schedule_name) = &schedule {
+                        let schedule = db.get_schedule_by_name(schedule_name).await?
+                            .ok_or_else(|| anyhow::anyhow!("Schedule not found: {}", schedule_name))?;
+                        blackout = blackout.for_schedule(schedule.id);
+                    }
+                    if let Some(reason) = reason {
+                        blackout = blackout.with_reason(reason);
+                    }
+                    blackout.validate()?;
+
+                    let id = db.insert_schedule_blackout(&blackout).await?;
+
+                    println!("Blackout window '{}' added (ID: {})", name, id);
+                    println!("Applies to: {}", schedule.as_deref().unwrap_or("all schedules"));
+                    println!(
+                        "From {} until {}",
+                        starts_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                        ends_at.format("%Y-%m-%d %H:%M:%S UTC")
+                    );
+                }
+
+                ScheduleFreezeAction::List { all } => {
+                    let blackouts = db.list_schedule_blackouts(all).await?;
+
+                    if blackouts.is_empty() {
+                        println!("No blackout windows found");
+                        return Ok(());
+                    }
+
+                    let schedule_names: std::collections::HashMap<i64, String> = db.list_schedules(false).await?
+                        .into_iter()
+                        .map(|schedule| (schedule.id, schedule.name))
+                        .collect();
+
+                    println!("{:<20} {:<20} {:<25} {:<25} {:<30}", "NAME", "SCHEDULE", "FROM", "UNTIL", "REASON");
+                    println!("{}", "-".repeat(120));
+
+                    for blackout in blackouts {
+                        let schedule = match blackout.schedule_id {
+                            Some(id) => schedule_names.get(&id).cloned().unwrap_or_else(|| id.to_string()),
+                            None => "all".to_string(),
+                        };
+
+                        println!(
+                            "{:<20} {:<20} {:<25} {:<25} {:<30}",
+                            blackout.name,
+                            schedule,
+                            blackout.starts_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                            blackout.ends_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                            blackout.reason.as_deref().unwrap_or("-")
+                        );
+                    }
+                }
+
+                ScheduleFreezeAction::Remove { name } => {
+                    if db.delete_schedule_blackout(&name).await? {
+                        println!("Blackout window '{}' removed", name);
+                    } else {
+                        anyhow::bail!("Blackout window not found: {}", name);
+                    }
+                }
+            },
         },
 
         Commands::Webhook { action } => match action {
@@ -3826,20 +5075,89 @@ async fn main() -> Result<()> {
                     handle_webhook_secret_show().await?;
                 }
             },
+            WebhookAction::Custom { action } => match action {
+                CustomWebhookAction::Add { file } => {
+                    handle_custom_webhook_add(&db, &file).await?;
+                }
+                CustomWebhookAction::List => {
+                    handle_custom_webhook_list(&db).await?;
+                }
+                CustomWebhookAction::Show { name } => {
+                    handle_custom_webhook_show(&db, &name).await?;
+                }
+                CustomWebhookAction::Remove { name } => {
+                    handle_custom_webhook_remove(&db, &name).await?;
+                }
+            },
+            WebhookAction::Outbound { action } => match action {
+                OutboundWebhookAction::Add {
+                    url,
+                    events,
+                    secret,
+                    description,
+                } => {
+                    handle_outbound_webhook_add(&db, url, events, secret, description).await?;
+                }
+                OutboundWebhookAction::List => {
+                    handle_outbound_webhook_list(&db).await?;
+                }
+                OutboundWebhookAction::Remove { id } => {
+                    handle_outbound_webhook_remove(&db, id).await?;
+                }
+                OutboundWebhookAction::Deliveries { webhook, limit } => {
+                    handle_outbound_webhook_deliveries(&db, webhook, limit).await?;
+                }
+                OutboundWebhookAction::Test { id } => {
+                    handle_outbound_webhook_test(db, id).await?;
+                }
+            },
+            WebhookAction::Source { action } => match action {
+                SourceWebhookAction::Add {
+                    name,
+                    scheme,
+                    header,
+                    secret,
+                } => {
+                    handle_webhook_source_add(&db, name, &scheme, header, secret).await?;
+                }
+                SourceWebhookAction::Rotate {
+                    name,
+                    secret,
+                    overlap_hours,
+                } => {
+                    handle_webhook_source_rotate(&db, &name, secret, overlap_hours).await?;
+                }
+                SourceWebhookAction::List => {
+                    handle_webhook_source_list(&db).await?;
+                }
+                SourceWebhookAction::Remove { name } => {
+                    handle_webhook_source_remove(&db, &name).await?;
+                }
+            },
         },
 
         Commands::Pipeline { action } => match action {
-            PipelineAction::Create { file } => {
-                handle_pipeline_create(&db, &file).await?;
+            PipelineAction::Create {
+                file,
+                allow_unknown_agents,
+            } => {
+                handle_pipeline_create(&db, &file, allow_unknown_agents).await?;
             }
             PipelineAction::List { enabled_only } => {
                 handle_pipeline_list(&db, enabled_only).await?;
             }
-            PipelineAction::Show { name } => {
-                handle_pipeline_show(&db, &name).await?;
+            PipelineAction::Show { name, graph } => {
+                handle_pipeline_show(&db, &name, graph.as_deref()).await?;
+            }
+            PipelineAction::Update {
+                name,
+                file,
+                allow_unknown_agents,
+            } => {
+                handle_pipeline_update(&db, &name, &file, allow_unknown_agents).await?;
             }
-            PipelineAction::Update { name, file } => {
-                handle_pipeline_update(&db, &name, &file).await?;
+            PipelineAction::Schema => {
+                println!("{}", orchestrate_core::PIPELINE_SCHEMA);
             }
             PipelineAction::Delete { name } => {
                 handle_pipeline_delete(&db, &name).await?;
@@ -3850,8 +5168,14 @@ async fn main() -> Result<()> {
             PipelineAction::Disable { name } => {
                 handle_pipeline_disable(&db, &name).await?;
             }
-            PipelineAction::Run { name, dry_run } => {
-                handle_pipeline_run(&db, &name, dry_run).await?;
+            PipelineAction::Run {
+                name,
+                dry_run,
+                json,
+                commit,
+                no_cache,
+            } => {
+                handle_pipeline_run(&db, &name, dry_run, json, commit, no_cache).await?;
             }
             PipelineAction::Status { run_id } => {
                 handle_pipeline_status(&db, run_id).await?;
@@ -3859,12 +5183,37 @@ async fn main() -> Result<()> {
             PipelineAction::Cancel { run_id } => {
                 handle_pipeline_cancel(&db, run_id).await?;
             }
+            PipelineAction::Resume { run_id } => {
+                handle_pipeline_resume(&db, run_id).await?;
+            }
             PipelineAction::History { name, limit } => {
                 handle_pipeline_history(&db, &name, limit).await?;
             }
             PipelineAction::Init { template, output, list, force } => {
                 handle_pipeline_init(template.as_deref(), output.as_ref(), list, force)?;
             }
+            PipelineAction::Template { action } => match action {
+                PipelineTemplateAction::Import {
+                    name,
+                    file,
+                    description,
+                } => {
+                    handle_pipeline_template_import(&db, &name, &file, description).await?;
+                }
+                PipelineTemplateAction::List => {
+                    handle_pipeline_template_list(&db).await?;
+                }
+                PipelineTemplateAction::Show { name } => {
+                    handle_pipeline_template_show(&db, &name).await?;
+                }
+                PipelineTemplateAction::Remove { name } => {
+                    if db.delete_pipeline_template(&name).await? {
+                        println!("Pipeline template removed: {}", name);
+                    } else {
+                        println!("Pipeline template not found: {}", name);
+                    }
+                }
+            },
         },
 
         Commands::Approval { action } => match action {
@@ -3881,6 +5230,17 @@ async fn main() -> Result<()> {
                 handle_approval_delegate(&db, id, &to).await?;
             }
         },
+        Commands::Secret { action } => match action {
+            VaultAction::Set { name, value } => {
+                handle_secret_set(&db, &name, value).await?;
+            }
+            VaultAction::List => {
+                handle_secret_list(&db).await?;
+            }
+            VaultAction::Delete { name } => {
+                handle_secret_delete(&db, &name).await?;
+            }
+        },
         Commands::Feedback { action } => match action {
             FeedbackAction::Add {
                 agent_id,
@@ -4244,7 +5604,7 @@ async fn main() -> Result<()> {
         },
         Commands::Docs { action } => match action {
             DocsAction::Generate { doc_type, output, format } => {
-                use orchestrate_core::{ApiDocumentation, ApiEndpoint, DocType};
+                use orchestrate_core::DocType;
 
                 let doc_type_parsed = match doc_type.to_lowercase().as_str() {
                     "api" => DocType::Api,
@@ -4256,47 +5616,12 @@ async fn main() -> Result<()> {
 
                 match doc_type_parsed {
                     DocType::Api => {
-                        // Generate API documentation from the codebase
-                        let mut api_doc = ApiDocumentation::new(
-                            "Orchestrate API",
-                            "1.0.0",
-                            Some("Agent orchestration and automation API"),
-                        );
-                        api_doc.add_server("http://localhost:8080", Some("Development server"));
-
-                        // Add sample endpoints from the known API
-                        api_doc.add_endpoint(
-                            ApiEndpoint::new("GET", "/api/agents")
-                                .with_summary("List all agents")
-                                .with_tag("agents")
-                                .with_query_param("status", false, Some("Filter by status"))
-                                .with_query_param("type", false, Some("Filter by agent type")),
-                        );
-                        api_doc.add_endpoint(
-                            ApiEndpoint::new("GET", "/api/agents/{id}")
-                                .with_summary("Get agent by ID")
-                                .with_tag("agents")
-                                .with_path_param("id", Some("Agent UUID")),
-                        );
-                        api_doc.add_endpoint(
-                            ApiEndpoint::new("POST", "/api/agents")
-                                .with_summary("Create a new agent")
-                                .with_tag("agents"),
-                        );
-                        api_doc.add_endpoint(
-                            ApiEndpoint::new("GET", "/api/sessions")
-                                .with_summary("List sessions")
-                                .with_tag("sessions"),
-                        );
-                        api_doc.add_endpoint(
-                            ApiEndpoint::new("GET", "/api/prs")
-                                .with_summary("List pull requests")
-                                .with_tag("pull-requests"),
-                        );
+                        // The spec the web server serves at /api/openapi.json
+                        let spec = orchestrate_web::openapi_spec();
 
                         let content = match format.to_lowercase().as_str() {
-                            "yaml" | "yml" => api_doc.to_openapi_yaml(),
-                            "json" => serde_json::to_string_pretty(&api_doc.to_openapi_json())?,
+                            "yaml" | "yml" => serde_yaml::to_string(&spec)?,
+                            "json" => serde_json::to_string_pretty(&spec)?,
                             _ => anyhow::bail!("Unknown format: {}. Valid: yaml, json", format),
                         };
 
@@ -5034,6 +6359,11 @@ async fn main() -> Result<()> {
                     }
                 }
             }
+            RepoAction::Policy { action } => match action {
+                RepoPolicyAction::Apply { spec, repo, dry_run, json } => {
+                    handle_repo_policy_apply(&db, &spec, repo.as_deref(), dry_run, json).await?;
+                }
+            },
         },
         Commands::Ci { action } => match action {
             CiAction::Config { provider, api_url, token } => {
@@ -5243,11 +6573,19 @@ async fn main() -> Result<()> {
                 }));
 
                 std::fs::write(incidents_file, serde_yaml::to_string(&incidents)?)?;
+                db.create_incident(&incident).await?;
 
                 println!("Created incident: {}", incident.id);
                 println!("  Title: {}", incident.title);
                 println!("  Severity: {}", incident.severity.as_str());
                 println!("  Status: {}", incident.status.as_str());
+
+                let escalation = orchestrate_core::IncidentEscalationService::from_env(db.clone())?;
+                match escalation.page_incident(&incident).await {
+                    Ok(true) => println!("  Paged the on-call"),
+                    Ok(false) => {}
+                    Err(e) => eprintln!("  Failed to page the on-call: {}", e),
+                }
                 println!();
                 println!("Next steps:");
                 println!("  orchestrate incident investigate {}", incident.id);
@@ -5277,13 +6615,78 @@ async fn main() -> Result<()> {
                 println!("(In production, would execute remediation actions)");
             }
             IncidentAction::Resolve { id, resolution } => {
+                use orchestrate_core::IncidentEscalationService;
+
                 println!("Resolving incident: {}", id);
                 println!("  Resolution: {}", resolution);
                 println!();
+
+                if let Some(mut incident) = db.get_incident(&id).await? {
+                    let escalation = IncidentEscalationService::from_env(db.clone())?;
+                    match escalation
+                        .resolve_incident(&mut incident, &resolution, None)
+                        .await
+                    {
+                        Ok(0) => {}
+                        Ok(n) => println!("Resolved {} on-call page(s)", n),
+                        Err(e) => eprintln!("Failed to resolve on-call pages: {}", e),
+                    }
+                }
+
                 println!("Incident marked as resolved.");
                 println!("  Generate post-mortem with: orchestrate incident postmortem {}", id);
             }
-            IncidentAction::Postmortem { id, output } => {
+            IncidentAction::Page {
+                id,
+                routing_key,
+                opsgenie_team,
+            } => {
+                use orchestrate_core::{
+                    EscalationTarget, EscalationTargetType, IncidentEscalationService,
+                };
+
+                let mut incident = db
+                    .get_incident(&id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Incident not found: {}", id))?;
+
+                let mut escalation = IncidentEscalationService::from_env(db.clone())?;
+                if let Some(routing_key) = routing_key {
+                    escalation = escalation.with_routing_key(routing_key);
+                }
+
+                let mut targets = Vec::new();
+                if let Some(routing_key) = escalation.routing_key() {
+                    targets.push(EscalationTarget {
+                        target_type: EscalationTargetType::PagerDuty,
+                        destination: routing_key.to_string(),
+                    });
+                }
+                if opsgenie_team.is_some() || escalation.has_opsgenie() {
+                    targets.push(EscalationTarget {
+                        target_type: EscalationTargetType::Opsgenie,
+                        destination: opsgenie_team.unwrap_or_default(),
+                    });
+                }
+                if targets.is_empty() {
+                    anyhow::bail!(
+                        "No on-call provider is configured. Set PAGERDUTY_ROUTING_KEY or OPSGENIE_API_KEY, or pass --routing-key."
+                    );
+                }
+
+                let rule = orchestrate_core::EscalationRule {
+                    name: "manual".to_string(),
+                    condition: orchestrate_core::EscalationCondition::Custom {
+                        expression: "manual".to_string(),
+                    },
+                    targets,
+                    delay_seconds: 0,
+                    repeat_interval_seconds: None,
+                };
+                escalation.escalate(&mut incident, &rule).await?;
+                println!("Paged the on-call for incident: {}", id);
+            }
+            IncidentAction::Postmortem { id, output, store } => {
                 use orchestrate_core::{Incident, IncidentSeverity, PostMortem, ActionItemPriority};
 
                 // Create sample incident for post-mortem
@@ -5298,10 +6701,15 @@ async fn main() -> Result<()> {
 
                 let content = pm.to_markdown();
 
+                if store {
+                    let artifact = require_artifact_store(&db)?.put_postmortem(&pm).await?;
+                    print_stored_artifact(&db, &artifact)?;
+                }
+
                 if let Some(path) = output {
                     std::fs::write(&path, &content)?;
                     println!("Post-mortem saved to: {}", path);
-                } else {
+                } else if !store {
                     println!("{}", content);
                 }
             }
@@ -5410,7 +6818,7 @@ async fn main() -> Result<()> {
                 println!();
                 println!("(In production, would analyze code and generate comprehensive tests)");
             }
-            TestAction::Coverage { threshold, diff, json } => {
+            TestAction::Coverage { threshold, diff, json, store } => {
                 use orchestrate_core::{CoverageReport, ModuleCoverage, FileCoverage};
 
                 println!("Analyzing test coverage...");
@@ -5443,6 +6851,13 @@ async fn main() -> Result<()> {
                         println!("Run 'orchestrate test generate' to add tests");
                     }
                 }
+
+                if store {
+                    let artifact = require_artifact_store(&db)?
+                        .put_coverage_report(&report)
+                        .await?;
+                    print_stored_artifact(&db, &artifact)?;
+                }
             }
             TestAction::Run { changed, test_type, verbose } => {
                 use orchestrate_core::{TestRun, TestResult, TestResultStatus, TestRunStatus};
@@ -5603,6 +7018,15 @@ async fn main() -> Result<()> {
 
                 println!("Deployment started: {}", deployment.id);
                 println!("Status: {}", deployment.status);
+
+                let db = Database::new(&db_path).await?;
+                let card = orchestrate_core::GoogleChatMessage::deployment(&deployment);
+                if let Err(e) = orchestrate_core::SlackService::new(db)
+                    .send_google_chat(&orchestrate_core::slack::NotificationType::DeploymentStarted, &card)
+                    .await
+                {
+                    tracing::warn!(error = %e, "Failed to post deployment to Google Chat");
+                }
                 println!();
                 println!("(In production, would execute deployment to {} provider)", env);
             }
@@ -5832,14 +7256,53 @@ async fn main() -> Result<()> {
                 println!("Run 'orchestrate release publish' to make it public");
                 println!("(In production, would create GitHub release)");
             }
-            ReleaseAction::Publish { version, draft } => {
+            ReleaseAction::Publish {
+                version,
+                draft,
+                prerelease,
+                assets,
+                target,
+                from,
+            } => {
+                use orchestrate_core::{Release, ReleaseType};
+                use orchestrate_github::{GitHubClient, PublishOptions};
+
+                let release_type = if prerelease {
+                    ReleaseType::PreRelease
+                } else {
+                    ReleaseType::Patch
+                };
+                let mut release = Release::new(&version, release_type, "cli-user");
+                let options = PublishOptions {
+                    assets,
+                    target,
+                    draft,
+                    previous_tag: from,
+                };
+
                 println!("Publishing release: {}", version);
                 if draft {
                     println!("  (as draft)");
                 }
+
+                let client = GitHubClient::new()?;
+                client.publish_release(&mut release, &options)?;
+
                 println!();
-                println!("Release published!");
-                println!("(In production, would publish to GitHub)");
+                println!(
+                    "Release {} {}",
+                    release.tag.as_deref().unwrap_or(&version),
+                    if draft { "created as draft" } else { "published!" }
+                );
+                if let Some(previous) = &release.previous_version {
+                    println!("  Notes cover changes since {}", previous);
+                }
+                if !release.assets.is_empty() {
+                    println!("  Assets:");
+                    for asset in &release.assets {
+                        println!("    {} ({} bytes)", asset.name, asset.size_bytes);
+                    }
+                }
             }
             ReleaseAction::List { limit } => {
                 println!("Releases (last {}):", limit);
@@ -5916,9 +7379,40 @@ async fn main() -> Result<()> {
                 println!("(In production, would fetch alerts from database)");
             }
             MonitorAlertAction::Ack { id } => {
+                let alert_id: i64 = id
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid alert ID: {}", id))?;
+                let mut alert = db
+                    .get_alert(alert_id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Alert not found: {}", id))?;
+
                 println!("Acknowledging alert: {}", id);
+                let by = std::env::var("USER").unwrap_or_else(|_| "unknown".to_string());
+                let escalation = orchestrate_core::IncidentEscalationService::from_env(db.clone())?;
+                let pages = escalation.acknowledge_alert(&mut alert, &by).await?;
+                if pages > 0 {
+                    println!("Acknowledged {} on-call page(s)", pages);
+                }
                 println!("Alert acknowledged.");
             }
+            MonitorAlertAction::Resolve { id } => {
+                let alert_id: i64 = id
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid alert ID: {}", id))?;
+                let mut alert = db
+                    .get_alert(alert_id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Alert not found: {}", id))?;
+
+                println!("Resolving alert: {}", id);
+                let escalation = orchestrate_core::IncidentEscalationService::from_env(db.clone())?;
+                let pages = escalation.resolve_alert(&mut alert).await?;
+                if pages > 0 {
+                    println!("Resolved {} on-call page(s)", pages);
+                }
+                println!("Alert resolved.");
+            }
             MonitorAlertAction::Silence { name, duration } => {
                 println!("Silencing rule: {} for {}", name, duration);
                 println!("Rule silenced.");
@@ -5962,8 +7456,12 @@ async fn main() -> Result<()> {
             }
             CostAction::Budget { amount } => {
                 println!("Setting monthly budget: ${:.2}", amount);
+                let budget = orchestrate_core::CostBudget::new(
+                    orchestrate_core::BudgetPeriod::Monthly,
+                    amount,
+                );
+                db.insert_cost_budget(&budget).await?;
                 println!("Budget updated.");
-                println!("(In production, would save to database)");
             }
             CostAction::Forecast { days } => {
                 println!("Cost Forecast ({} days)", days);
@@ -5992,6 +7490,22 @@ async fn main() -> Result<()> {
                 println!();
                 println!("(In production, would aggregate from database)");
             }
+            CostAction::EmailSummary { date } => {
+                use orchestrate_core::EmailNotificationService;
+
+                let date = match date {
+                    Some(date) => chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                        .map_err(|e| anyhow::anyhow!("Invalid date {}: {}", date, e))?,
+                    None => chrono::Utc::now().date_naive() - chrono::Duration::days(1),
+                };
+
+                let db = Database::new(&db_path).await?;
+                let service = EmailNotificationService::from_env(db)?.ok_or_else(|| {
+                    anyhow::anyhow!("SMTP is not configured. Set ORCHESTRATE_SMTP_HOST.")
+                })?;
+                let sent = service.send_cost_summary(date).await?;
+                println!("Sent {} cost summary email(s) for {}", sent, date);
+            }
         },
         Commands::Audit { action } => match action {
             MonitorAuditAction::Search { actor, action: action_filter, limit } => {
@@ -6031,12 +7545,90 @@ async fn main() -> Result<()> {
                 println!("(In production, would export actual audit logs)");
             }
         },
+        Commands::Jira { action } => match action {
+            JiraAction::Project { action } => match action {
+                JiraProjectAction::Add {
+                    name,
+                    url,
+                    key,
+                    epic_prefix,
+                    epic_type,
+                    story_type,
+                    statuses,
+                    fields,
+                } => {
+                    let mut project = orchestrate_core::JiraProject::new(name, url, key)
+                        .with_epic_prefix(epic_prefix);
+                    project.epic_issue_type = epic_type;
+                    project.story_issue_type = story_type;
+                    for mapping in statuses {
+                        let (status, jira_status) = parse_jira_mapping(&mapping)?;
+                        project = project.with_status(StoryStatus::from_str(status)?, jira_status);
+                    }
+                    for mapping in fields {
+                        let (source, field_id) = parse_jira_mapping(&mapping)?;
+                        project = project.with_field(source, field_id);
+                    }
+                    handle_jira_project_add(&db, project).await?;
+                }
+                JiraProjectAction::List => {
+                    handle_jira_project_list(&db).await?;
+                }
+                JiraProjectAction::Remove { name } => {
+                    if !db.delete_jira_project(&name).await? {
+                        anyhow::bail!("Jira project not found: {}", name);
+                    }
+                    println!("Jira project removed: {}", name);
+                }
+            },
+            JiraAction::Sync { epic, story } => {
+                handle_jira_sync(&db, epic.as_deref(), story.as_deref()).await?;
+            }
+            JiraAction::Comments { story } => {
+                handle_jira_comments(&db, &story).await?;
+            }
+        },
+        Commands::Linear { action } => match action {
+            LinearAction::Team { action } => match action {
+                LinearTeamAction::Add {
+                    name,
+                    team_id,
+                    epic_prefix,
+                    statuses,
+                    trigger_label,
+                    agent_type,
+                } => {
+                    let mut team = orchestrate_core::LinearTeam::new(name, team_id)
+                        .with_epic_prefix(epic_prefix)
+                        .with_trigger_label(trigger_label)
+                        .with_agent_type(AgentType::from_str(&agent_type)?);
+                    for mapping in statuses {
+                        let (status, state_name) = parse_jira_mapping(&mapping)?;
+                        team = team.with_status(StoryStatus::from_str(status)?, state_name);
+                    }
+                    handle_linear_team_add(&db, team).await?;
+                }
+                LinearTeamAction::List => {
+                    handle_linear_team_list(&db).await?;
+                }
+                LinearTeamAction::Remove { name } => {
+                    if !db.delete_linear_team(&name).await? {
+                        anyhow::bail!("Linear team not found: {}", name);
+                    }
+                    println!("Linear team removed: {}", name);
+                }
+            },
+            LinearAction::Sync { epic, story } => {
+                handle_linear_sync(&db, epic.as_deref(), story.as_deref()).await?;
+            }
+        },
+
         Commands::Slack { action } => {
-            use orchestrate_core::{SlackConnection, SlackService, SlackUserService, ChannelConfig, NotificationType, SlackMessage};
+            use orchestrate_core::{SlackApiClient, SlackConnection, SlackService, SlackUserService, ChannelConfig, NotificationType, SlackMessage};
 
-            let _db = Database::new(&db_path).await?;
-            let slack_service = SlackService::new();
-            let user_service = SlackUserService::new();
+            let db = Database::new(&db_path).await?;
+            let slack_service = SlackService::new(db.clone());
+            let user_service = SlackUserService::new(db.clone(), SlackService::new(db));
 
             match action {
                 SlackAction::Connect { token } => {
@@ -6045,42 +7637,47 @@ async fn main() -> Result<()> {
                         anyhow::bail!("Invalid token format. Bot tokens should start with 'xoxb-'");
                     }
 
-                    // In production, would call Slack API to get team info
-                    // For now, use placeholder values
-                    let conn = SlackConnection::new("T_WORKSPACE", "Workspace", &token)
+                    println!("Connecting to Slack...");
+                    let auth = SlackApiClient::new(&token).auth_test().await?;
+
+                    let mut conn = SlackConnection::new(&auth.team_id, &auth.team, &token)
                         .with_scopes(vec![
                             "chat:write".to_string(),
                             "chat:write.public".to_string(),
                             "commands".to_string(),
                             "users:read".to_string(),
+                            "users:read.email".to_string(),
                             "channels:read".to_string(),
                         ]);
+                    conn.bot_user_id = auth.user_id;
+                    conn.app_id = auth.bot_id.unwrap_or_default();
 
                     slack_service.save_connection(&conn).await?;
+                    let conn = slack_service.get_active_connection().await?.unwrap_or(conn);
 
-                    println!("Connecting to Slack...");
                     println!("  Team: {}", conn.team_name);
                     println!("  Team ID: {}", conn.team_id);
+                    println!("  Bot User ID: {}", conn.bot_user_id);
                     println!("  Scopes: {}", conn.scopes.join(", "));
                     println!();
                     println!("Connected successfully!");
                     println!("Connection ID: {}", conn.id);
                 }
                 SlackAction::Disconnect => {
-                    match slack_service.get_active_connection().await {
-                        Ok(mut conn) => {
+                    match slack_service.get_active_connection().await? {
+                        Some(mut conn) => {
                             conn.is_active = false;
                             slack_service.save_connection(&conn).await?;
                             println!("Disconnected from Slack workspace: {}", conn.team_name);
                         }
-                        Err(_) => {
+                        None => {
                             println!("No active Slack connection found.");
                         }
                     }
                 }
                 SlackAction::Status => {
-                    match slack_service.get_active_connection().await {
-                        Ok(conn) => {
+                    match slack_service.get_active_connection().await? {
+                        Some(conn) => {
                             println!("Slack Connection Status:");
                             println!();
                             println!("  Status: Connected ✓");
@@ -6095,7 +7692,7 @@ async fn main() -> Result<()> {
                             }
 
                             // Show channel config if exists
-                            if let Ok(config) = slack_service.get_channel_config(&conn.id).await {
+                            if let Some(config) = slack_service.get_channel_config(&conn.id).await? {
                                 println!();
                                 println!("Channel Configuration:");
                                 println!("  Default: {}", config.default_channel);
@@ -6105,9 +7702,15 @@ async fn main() -> Result<()> {
                                         println!("    {} -> {}", notif_type, channel);
                                     }
                                 }
+                                if !config.google_chat_webhooks.is_empty() {
+                                    println!("  Google Chat:");
+                                    for notif_type in config.google_chat_webhooks.keys() {
+                                        println!("    {} -> webhook", notif_type);
+                                    }
+                                }
                             }
                         }
-                        Err(_) => {
+                        None => {
                             println!("No active Slack connection.");
                             println!("Run 'orchestrate slack connect --token <TOKEN>' to connect.");
                         }
@@ -6126,9 +7729,9 @@ async fn main() -> Result<()> {
                     println!("  #alerts          - Alert and failure notifications");
                     println!("  #pr-reviews      - PR and review notifications");
                 }
-                SlackAction::Channel { notification_type, channel } => {
-                    let conn = slack_service.get_active_connection().await
-                        .map_err(|_| anyhow::anyhow!("No active Slack connection. Connect first."))?;
+                SlackAction::Channel { notification_type, channel, google_chat } => {
+                    let conn = slack_service.get_active_connection().await?
+                        .ok_or_else(|| anyhow::anyhow!("No active Slack connection. Connect first."))?;
 
                     // Parse notification type
                     let notif_type = match notification_type.as_str() {
@@ -6138,23 +7741,45 @@ async fn main() -> Result<()> {
                         "pr_created" => NotificationType::PrCreated,
                         "pr_merged" => NotificationType::PrMerged,
                         "ci_failed" => NotificationType::CiFailed,
+                        "deployment_started" => NotificationType::DeploymentStarted,
+                        "deployment_completed" => NotificationType::DeploymentCompleted,
                         "deployment_failed" => NotificationType::DeploymentFailed,
                         "approval_required" => NotificationType::ApprovalRequired,
+                        "alert_fired" => NotificationType::AlertFired,
                         _ => anyhow::bail!("Invalid notification type: {}", notification_type),
                     };
 
+                    if let Some(url) = &google_chat {
+                        if !orchestrate_core::google_chat::is_webhook_url(url) {
+                            anyhow::bail!(
+                                "Invalid Google Chat webhook URL. Expected {}...?key=...&token=...",
+                                orchestrate_core::google_chat::GOOGLE_CHAT_WEBHOOK_PREFIX
+                            );
+                        }
+                    }
+
                     // Get or create channel config
-                    let mut config = slack_service.get_channel_config(&conn.id).await
-                        .unwrap_or_else(|_| ChannelConfig::new("#orchestrate"));
+                    let mut config = slack_service.get_channel_config(&conn.id).await?
+                        .unwrap_or_else(|| ChannelConfig::new("#orchestrate"));
 
-                    config.channel_mappings.insert(notif_type.clone(), channel.clone());
-                    slack_service.update_channel_config(&config).await?;
+                    if let Some(channel) = &channel {
+                        config.channel_mappings.insert(notif_type.clone(), channel.clone());
+                    }
+                    if let Some(url) = &google_chat {
+                        config.google_chat_webhooks.insert(notif_type.clone(), url.clone());
+                    }
+                    slack_service.save_channel_config(&conn.id, &config).await?;
 
                     println!("Channel mapping updated:");
-                    println!("  {} -> {}", notification_type, channel);
+                    if let Some(channel) = &channel {
+                        println!("  {} -> {}", notification_type, channel);
+                    }
+                    if google_chat.is_some() {
+                        println!("  {} -> Google Chat webhook", notification_type);
+                    }
                 }
                 SlackAction::Test { channel } => {
-                    let _message = SlackMessage::new(&channel, "Test message from Orchestrate")
+                    let message = SlackMessage::new(&channel, "Test message from Orchestrate")
                         .with_blocks(vec![
                             orchestrate_core::SlackBlock::Section {
                                 text: orchestrate_core::SlackText::mrkdwn("🧪 *Test Message*\n\nThis is a test notification from Orchestrate."),
@@ -6163,22 +7788,44 @@ async fn main() -> Result<()> {
                             },
                         ]);
 
-                    // Slack notification sending not yet implemented
-                    println!("Slack notification feature not yet implemented.");
-                    println!("Would send test message to: {}", channel);
+                    let sent = slack_service.send_message(message).await?;
+                    println!("Sent test message to {} (ts {})", sent.channel, sent.ts);
                 }
-                SlackAction::MapUser { github, slack } => {
-                    // User mapping not yet implemented
-                    let slack_username = if slack.starts_with('@') {
-                        slack.trim_start_matches('@').to_string()
-                    } else {
-                        slack.clone()
+                SlackAction::MapUser { github, slack, email } => {
+                    if slack_service.get_active_connection().await?.is_none() {
+                        anyhow::bail!("No active Slack connection. Connect first.");
+                    }
+
+                    let (slack_user_id, slack_username) = match (slack, &email) {
+                        (_, Some(email)) => {
+                            let user = slack_service.lookup_user_by_email(email).await?
+                                .ok_or_else(|| anyhow::anyhow!("No Slack user with email {}", email))?;
+                            (user.id, user.name)
+                        }
+                        (Some(slack), None) => {
+                            let slack = slack.trim_start_matches('@').to_string();
+                            (slack.clone(), slack)
+                        }
+                        (None, None) => anyhow::bail!("Either --slack or --email is required"),
                     };
 
-                    println!("User mapping feature not yet implemented.");
-                    println!("Would map:");
-                    println!("  GitHub: {}", github);
-                    println!("  Slack: {} ({})", slack_username, slack);
+                    let mut mapping = user_service.map_user(&github, &slack_user_id, &slack_username).await?;
+
+                    // Keep the address the user was found by for email notifications
+                    if let Some(email) = email {
+                        if let Some(saved) = slack_service.get_user_mapping(&github).await? {
+                            mapping = saved;
+                        }
+                        mapping.email = Some(email);
+                        slack_service.save_user_mapping(&mapping).await?;
+                    }
+
+                    println!("User mapped:");
+                    println!("  GitHub: {}", mapping.github_username);
+                    println!("  Slack: {} (@{})", mapping.slack_user_id, mapping.slack_username);
+                    if let Some(email) = &mapping.email {
+                        println!("  Email: {}", email);
+                    }
                 }
                 SlackAction::Users => {
                     let mappings = user_service.list_user_mappings().await?;
@@ -6200,9 +7847,42 @@ async fn main() -> Result<()> {
                             if mapping.notify_on_mention { print!(" Mention"); }
                             if mapping.notify_on_failure { print!(" Failure"); }
                             println!();
+                            if let Some(email) = &mapping.email {
+                                print!("    Email ({}):", email);
+                                if mapping.email_on_approval { print!(" Approval"); }
+                                if mapping.email_on_incident { print!(" Incident"); }
+                                if mapping.email_cost_summary { print!(" CostSummary"); }
+                                println!();
+                            }
                         }
                     }
                 }
+                SlackAction::Email { github, address, approvals, incidents, cost_summary } => {
+                    let mut mapping = slack_service.get_user_mapping(&github).await?
+                        .ok_or_else(|| anyhow::anyhow!(
+                            "No user mapping for {}. Create one with: orchestrate slack map-user", github
+                        ))?;
+
+                    if let Some(address) = address {
+                        mapping.email = Some(address).filter(|a| !a.is_empty());
+                    }
+                    if let Some(approvals) = approvals {
+                        mapping.email_on_approval = approvals;
+                    }
+                    if let Some(incidents) = incidents {
+                        mapping.email_on_incident = incidents;
+                    }
+                    if let Some(cost_summary) = cost_summary {
+                        mapping.email_cost_summary = cost_summary;
+                    }
+                    slack_service.save_user_mapping(&mapping).await?;
+
+                    println!("Email notifications for {}:", mapping.github_username);
+                    println!("  Address: {}", mapping.email.as_deref().unwrap_or("(none)"));
+                    println!("  Approval requests: {}", mapping.email_on_approval);
+                    println!("  Incident escalations: {}", mapping.email_on_incident);
+                    println!("  Daily cost summary: {}", mapping.email_cost_summary);
+                }
             }
         },
         Commands::Security { action } => match action {
@@ -6278,7 +7958,7 @@ async fn main() -> Result<()> {
                     }
                 }
             }
-            SecurityAction::Report { format, output } => {
+            SecurityAction::Report { format, output, store } => {
                 use orchestrate_core::{SecurityScan, ScanType, Vulnerability, Severity, ReportFormat};
                 use std::str::FromStr;
 
@@ -6299,10 +7979,17 @@ async fn main() -> Result<()> {
                 let content = orchestrate_core::security_report::generate_report(&scan, report_format)
                     .map_err(|e| anyhow::anyhow!(e))?;
 
+                if store {
+                    let artifact = require_artifact_store(&db)?
+                        .put_security_report(&scan, report_format)
+                        .await?;
+                    print_stored_artifact(&db, &artifact)?;
+                }
+
                 if let Some(path) = output {
                     std::fs::write(&path, &content)?;
                     println!("Report written to: {}", path);
-                } else {
+                } else if !store {
                     if report_format == ReportFormat::Html {
                         println!("\n(HTML report generated, use --output to save to file)");
                     } else {
@@ -6371,6 +8058,38 @@ async fn main() -> Result<()> {
                 println!("Note: New vulnerabilities will still be flagged.");
             }
         },
+        Commands::Artifact { action } => match action {
+            ArtifactAction::Upload {
+                file,
+                agent,
+                content_type,
+            } => {
+                handle_artifact_upload(&db, &file, &agent, content_type.as_deref()).await?;
+            }
+            ArtifactAction::List {
+                kind,
+                owner,
+                limit,
+                json,
+            } => {
+                handle_artifact_list(&db, kind.as_deref(), owner.as_deref(), limit, json).await?;
+            }
+            ArtifactAction::Url { id } => {
+                let artifact = db
+                    .get_artifact(id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Artifact not found: {}", id))?;
+                println!("{}", require_artifact_store(&db)?.signed_url(&artifact)?);
+            }
+            ArtifactAction::Delete { id } => {
+                let artifact = db
+                    .get_artifact(id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Artifact not found: {}", id))?;
+                require_artifact_store(&db)?.delete(&artifact).await?;
+                println!("Artifact deleted: {}", artifact.key);
+            }
+        },
         Commands::Epic { action } => match action {
             EpicAction::AutoProcess { pattern, max_agents, model, dry_run } => {
                 handle_epic_auto_process(&db, pattern.as_deref(), max_agents, &model, dry_run).await?;
@@ -6390,6 +8109,9 @@ async fn main() -> Result<()> {
             EpicAction::StuckAgents => {
                 handle_epic_stuck_agents(&db).await?;
             }
+            EpicAction::StuckConfig { action } => {
+                handle_epic_stuck_config(&db, action).await?;
+            }
             EpicAction::Unblock { epic_id } => {
                 handle_epic_unblock(&db, &epic_id).await?;
             }
@@ -6402,6 +8124,9 @@ async fn main() -> Result<()> {
         },
     }
 
+    // Flush spans still queued for export
+    orchestrate_core::telemetry::shutdown();
+
     Ok(())
 }
 
@@ -6485,23 +8210,64 @@ fn format_tokens(tokens: i64) -> String {
     }
 }
 
-/// Client type for daemon
-#[derive(Clone)]
-enum DaemonClient {
-    Api(ClaudeClient),
-    Cli(ClaudeCliClient),
-}
-
-/// Run the daemon to execute agents
-async fn run_daemon(
-    db: Database,
-    port: u16,
-    max_concurrent: usize,
-    poll_interval: u64,
-    model: String,
-    use_cli: bool,
+/// Print per-epic or per-story spend as a table
+fn print_entity_costs(
+    label: &str,
+    costs: &[orchestrate_core::EntityCostSummary],
+    json: bool,
 ) -> Result<()> {
-    // Create client based on mode
+    if json {
+        println!("{}", serde_json::to_string_pretty(costs)?);
+        return Ok(());
+    }
+
+    if costs.is_empty() {
+        println!("No attributed token usage found");
+        return Ok(());
+    }
+
+    println!(
+        "{:<30} {:>12} {:>12} {:>10} {:>12}",
+        label, "INPUT", "OUTPUT", "REQUESTS", "EST. COST"
+    );
+    println!("{}", "-".repeat(80));
+
+    for cost in costs {
+        println!(
+            "{:<30} {:>12} {:>12} {:>10} {:>12}",
+            &cost.entity_id[..cost.entity_id.len().min(30)],
+            format_tokens(cost.total_input_tokens),
+            format_tokens(cost.total_output_tokens),
+            cost.request_count,
+            format!("${:.4}", cost.estimated_cost_usd)
+        );
+    }
+
+    let total: f64 = costs.iter().map(|c| c.estimated_cost_usd).sum();
+    println!("{}", "-".repeat(80));
+    println!("{:>67} ${:.4}", "TOTAL:", total);
+
+    Ok(())
+}
+
+/// Client type for daemon
+#[derive(Clone)]
+enum DaemonClient {
+    Api(ClaudeClient),
+    Cli(ClaudeCliClient),
+}
+
+/// Run the daemon to execute agents
+async fn run_daemon(
+    db: Database,
+    port: u16,
+    max_concurrent: usize,
+    poll_interval: u64,
+    model: String,
+    use_cli: bool,
+    policy: Option<PathBuf>,
+) -> Result<()> {
+    // Create client based on mode
     let client = if use_cli {
         // Check if claude CLI is available
         let output = std::process::Command::new("claude")
@@ -6549,6 +8315,13 @@ async fn run_daemon(
     } else {
         println!("║  Web API:         {:<42} ║", "disabled");
     }
+    let mut policy_reloader = policy
+        .map(orchestrate_core::PolicyReloader::new)
+        .transpose()?;
+    if let Some(reloader) = &policy_reloader {
+        let path = reloader.path().display().to_string();
+        println!("║  Policy:          {:<42} ║", &path[..path.len().min(42)]);
+    }
     println!("╚══════════════════════════════════════════════════════════════╝");
     println!();
     println!("Press Ctrl+C to stop the daemon");
@@ -6567,11 +8340,16 @@ async fn run_daemon(
     // Create semaphore for concurrency control
     let semaphore = Arc::new(Semaphore::new(max_concurrent));
 
+    let metrics = Arc::new(orchestrate_web::MetricsCollector::default());
+
     // Start web server (API + UI) if port > 0
     if port > 0 {
-        let db_clone = db.clone();
+        let state = Arc::new(with_artifact_storage(
+            orchestrate_web::api::AppState::new(db.clone(), None).with_metrics(metrics.clone()),
+            &db,
+        )?);
+        publish_network_events(&state);
         tokio::spawn(async move {
-            let state = Arc::new(orchestrate_web::api::AppState::new(db_clone, None));
             let router = orchestrate_web::create_router(state);
             let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port))
                 .await
@@ -6581,10 +8359,40 @@ async fn run_daemon(
         });
     }
 
+    // Deliver outbound webhooks for finished agents, merged PRs, and pipeline runs
+    let dispatcher = orchestrate_core::OutboundWebhookDispatcher::new(Arc::new(db.clone()));
+    tokio::spawn(async move {
+        dispatcher.run(std::time::Duration::from_secs(5)).await;
+    });
+
+    spawn_datadog_exporter(Arc::new(db.clone()), metrics);
+
+    // Applies approval timeouts and resumes the runs they unblock
+    let pipeline_executor =
+        orchestrate_core::PipelineExecutor::new(Arc::new(db.clone())).with_notifications_from_env();
+
     // Main polling loop
     let mut active_agents: std::collections::HashSet<uuid::Uuid> = std::collections::HashSet::new();
 
     while !shutdown.load(Ordering::SeqCst) {
+        // Pick up decision policy edits
+        if let Some(reloader) = policy_reloader.as_mut() {
+            match reloader.reload_if_changed() {
+                Ok(true) => info!("Reloaded decision policy from {}", reloader.path().display()),
+                Ok(false) => {}
+                Err(e) => warn!("Keeping previous decision policy: {}", e),
+            }
+        }
+
+        // Escalate or decide pipeline approvals that have timed out
+        match pipeline_executor.process_approval_timeouts().await {
+            Ok(processed) if !processed.is_empty() => {
+                info!("Processed {} timed out approval(s)", processed.len())
+            }
+            Ok(_) => {}
+            Err(e) => error!("Failed to process approval timeouts: {}", e),
+        }
+
         // Get pending agents (Created state)
         let pending = match db.list_agents_by_state(AgentState::Created).await {
             Ok(agents) => agents,
@@ -6708,6 +8516,8 @@ async fn run_agent_with_api(
         max_consecutive_errors: 3,
         enable_token_optimization: true,
         enable_sessions: true,
+        enable_context_summaries: true,
+        related_summaries_limit: 3,
     };
 
     let agent_loop = AgentLoop::new(client, db.clone(), config);
@@ -6869,6 +8679,16 @@ async fn run_agent_with_cli(
                         )
                         .await
                         .ok();
+                        db.attribute_token_usage(
+                            &agent.context,
+                            &model,
+                            input,
+                            output_tokens,
+                            cache_read,
+                            cache_write,
+                        )
+                        .await
+                        .ok();
                     }
 
                     agent.transition_to(AgentState::Completed)?;
@@ -6961,6 +8781,9 @@ async fn show_story(db: &Database, id: &str) -> Result<()> {
         if let Ok(Some(agent)) = db.get_agent(agent_id).await {
             println!("  Type: {:?}", agent.agent_type);
             println!("  State: {:?}", agent.state);
+            if let Some(ref trace_id) = agent.trace_id {
+                println!("  Trace: {}", trace_id);
+            }
         }
         println!();
     }
@@ -7088,6 +8911,27 @@ async fn process_bmad_epics(
         }
         println!("   ✓ {} stories saved", stories.len());
 
+        if let Some(jira) = orchestrate_core::JiraSyncService::from_env(db.clone()) {
+            let report = jira.sync_stories(&stories).await;
+            if report.created > 0 {
+                println!("   ✓ {} Jira issues created", report.created);
+            }
+            for error in &report.errors {
+                println!("   ⚠ Jira sync failed for {}", error);
+            }
+        }
+
+        let linear = orchestrate_core::LinearSyncService::from_env(db.clone());
+        if linear.has_client() {
+            let report = linear.sync_stories(&stories).await;
+            if report.created > 0 {
+                println!("   ✓ {} Linear issues created", report.created);
+            }
+            for error in &report.errors {
+                println!("   ⚠ Linear sync failed for {}", error);
+            }
+        }
+
         // Create worktree for the epic
         let worktree_name = format!("epic-{}", epic.id.replace("epic-", ""));
         let worktree_path = format!(".worktrees/{}", worktree_name);
@@ -7141,7 +8985,7 @@ async fn process_bmad_epics(
                 }
 
                 // Create story-developer agent
-                let task = format!(
+                let mut task = format!(
                     "Implement story {}: {}\n\n{}",
                     story.id,
                     story.title,
@@ -7150,8 +8994,16 @@ async fn process_bmad_epics(
                         .as_deref()
                         .unwrap_or("No description provided.")
                 );
+                let comments = db.list_jira_comments(&story.id).await?;
+                if let Some(context) = orchestrate_core::jira::comments_context(&comments) {
+                    task.push_str(&format!("\n\n{}", context));
+                }
 
-                let agent = Agent::new(AgentType::StoryDeveloper, &task);
+                let agent = Agent::new(AgentType::StoryDeveloper, &task).with_context(AgentContext {
+                    epic_id: Some(story.epic_id.clone()),
+                    story_id: Some(story.id.clone()),
+                    ..Default::default()
+                });
                 db.insert_agent(&agent).await?;
 
                 // Link story to agent
@@ -7307,6 +9159,12 @@ async fn show_bmad_status(db: &Database) -> Result<()> {
 
     for epic in &pending_epics {
         let stories = db.get_stories_for_epic(&epic.id).await?;
+        let story_costs: std::collections::HashMap<_, _> = db
+            .list_story_costs(Some(&epic.id), None)
+            .await?
+            .into_iter()
+            .map(|c| (c.entity_id, c.estimated_cost_usd))
+            .collect();
         let completed = stories
             .iter()
             .filter(|s| s.status == StoryStatus::Completed)
@@ -7341,6 +9199,9 @@ async fn show_bmad_status(db: &Database) -> Result<()> {
         println!("{} Epic: {} - {}", status_icon, epic.id, epic.title);
         println!("   Phase: {}", phase_str);
         println!("   Stories: {}/{} complete", completed, stories.len());
+        if !story_costs.is_empty() {
+            println!("   Cost to date: ${:.4}", story_costs.values().sum::<f64>());
+        }
 
         if in_progress > 0 || pending > 0 || blocked > 0 {
             println!(
@@ -7364,7 +9225,14 @@ async fn show_bmad_status(db: &Database) -> Result<()> {
                     .agent_id
                     .map(|id| format!(" [agent: {}]", &id.to_string()[..8]))
                     .unwrap_or_default();
-                println!("      {} {}: {}{}", icon, story.id, story.title, agent_str);
+                let cost_str = story_costs
+                    .get(&story.id)
+                    .map(|c| format!(" (${:.4})", c))
+                    .unwrap_or_default();
+                println!(
+                    "      {} {}: {}{}{}",
+                    icon, story.id, story.title, agent_str, cost_str
+                );
             }
         }
         println!();
@@ -7401,6 +9269,284 @@ async fn reset_bmad_state(db: &Database, force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Export metrics to Datadog in background when `DD_API_KEY` is set
+fn spawn_datadog_exporter(
+    db: std::sync::Arc<Database>,
+    metrics: std::sync::Arc<orchestrate_web::MetricsCollector>,
+) {
+    if let Some(config) = orchestrate_web::DatadogConfig::from_env() {
+        let exporter = orchestrate_web::DatadogExporter::new(metrics, db, config);
+        tokio::spawn(async move {
+            exporter.run().await;
+        });
+    }
+}
+
+/// Keep reports and artifacts served by the web UI in object storage, if configured
+fn with_artifact_storage(
+    state: orchestrate_web::api::AppState,
+    db: &Database,
+) -> Result<orchestrate_web::api::AppState> {
+    let artifacts = orchestrate_core::ArtifactStore::from_env(db.clone())?;
+    Ok(match artifacts {
+        Some(artifacts) => state.with_artifacts(artifacts),
+        None => state,
+    })
+}
+
+/// Print events from a web server's `/api/events` stream until it closes
+///
+/// Authenticates with `ORCHESTRATE_TOKEN` (a login session token) or
+/// `ORCHESTRATE_API_KEY`.
+async fn watch_events(server: &str, kind: Option<&str>, json: bool) -> Result<()> {
+    let mut request = reqwest::Client::new()
+        .get(format!("{}/api/events", server.trim_end_matches('/')))
+        .header("Accept", "text/event-stream");
+    if let Some(kind) = kind {
+        request = request.query(&[("kind", kind)]);
+    }
+    if let Ok(token) =
+        std::env::var("ORCHESTRATE_TOKEN").or_else(|_| std::env::var("ORCHESTRATE_API_KEY"))
+    {
+        request = request.bearer_auth(token);
+    }
+
+    let mut response = request.send().await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Event stream request failed with HTTP {}", response.status());
+    }
+    if !json {
+        println!("Watching events from {} (Ctrl+C to stop)", server);
+    }
+
+    let mut buffer = String::new();
+    while let Some(chunk) = response.chunk().await? {
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+        while let Some(end) = buffer.find("\n\n") {
+            let message: String = buffer.drain(..end + 2).collect();
+            let data: Vec<&str> = message
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(str::trim_start)
+                .collect();
+            if data.is_empty() {
+                continue;
+            }
+            let data = data.join("\n");
+            if json {
+                println!("{}", data);
+                continue;
+            }
+            match serde_json::from_str::<orchestrate_core::BusEvent>(&data) {
+                Ok(event) => println!(
+                    "{}  {:<28} {}{}",
+                    event.occurred_at.format("%H:%M:%S"),
+                    event.kind,
+                    event
+                        .agent_id
+                        .map(|id| format!("agent={} ", id))
+                        .unwrap_or_default(),
+                    event.data
+                ),
+                Err(_) => println!("{}", data),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Emit the web server's network events to the event feed and bus
+fn publish_network_events(state: &orchestrate_web::api::AppState) {
+    orchestrate_core::event_bus::forward_network_events(state.network.subscribe());
+}
+
+/// Apply a branch protection policy to the configured repositories
+async fn handle_repo_policy_apply(
+    db: &Database,
+    spec_path: &PathBuf,
+    repo_filter: Option<&str>,
+    dry_run: bool,
+    json: bool,
+) -> Result<()> {
+    use orchestrate_core::{BranchPolicyResult, BranchPolicySpec, Repository};
+
+    let spec = BranchPolicySpec::load(spec_path)?;
+
+    let mut repos = db.list_repositories().await?;
+    for url in &spec.repositories {
+        if repos.iter().any(|r| r.url == *url) {
+            continue;
+        }
+        let name = url
+            .trim_end_matches('/')
+            .rsplit(['/', ':'])
+            .next()
+            .unwrap_or(url)
+            .trim_end_matches(".git");
+        repos.push(Repository::new(name, url));
+    }
+    if let Some(name) = repo_filter {
+        repos.retain(|r| r.name == name);
+        if repos.is_empty() {
+            anyhow::bail!("Repository not found: {}", name);
+        }
+    }
+    if repos.is_empty() {
+        println!("No repositories configured. Use 'orchestrate repo add' or list them under 'repositories' in the spec.");
+        return Ok(());
+    }
+
+    let mut results = Vec::new();
+    for repo in &repos {
+        for rule in &spec.rules {
+            let mut result = BranchPolicyResult::new(&repo.name, &rule.branch);
+            if let Err(e) = apply_branch_rule(repo, rule, dry_run, &mut result).await {
+                result.error = Some(e.to_string());
+            }
+            results.push(result);
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        println!("Branch Policy{}", if dry_run { " (dry run)" } else { "" });
+        println!("{}", "=".repeat(60));
+        for result in &results {
+            let status = if let Some(error) = &result.error {
+                format!("✗ error: {}", error)
+            } else if result.drift.is_empty() {
+                "✓ in sync".to_string()
+            } else if result.applied {
+                format!("✓ applied ({} settings drifted)", result.drift.len())
+            } else {
+                format!("✗ {} settings drifted", result.drift.len())
+            };
+            println!("{} [{}]: {}", result.repository, result.branch, status);
+            for drift in &result.drift {
+                println!(
+                    "  {}: expected {}, found {}",
+                    drift.setting.as_str(),
+                    drift.expected,
+                    drift.actual
+                );
+            }
+            if !result.unsupported.is_empty() {
+                let names: Vec<&str> = result.unsupported.iter().map(|s| s.as_str()).collect();
+                println!("  not enforceable on this provider: {}", names.join(", "));
+            }
+        }
+    }
+
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    if failed > 0 {
+        anyhow::bail!("Failed to apply branch policy to {} branches", failed);
+    }
+    let drifted = results.iter().filter(|r| !r.drift.is_empty()).count();
+    if dry_run && drifted > 0 {
+        anyhow::bail!("{} branches drifted from the branch policy", drifted);
+    }
+    Ok(())
+}
+
+/// Compare one repository branch with a rule, writing the rule unless `dry_run`
+async fn apply_branch_rule(
+    repo: &orchestrate_core::Repository,
+    rule: &orchestrate_core::BranchRule,
+    dry_run: bool,
+    result: &mut orchestrate_core::BranchPolicyResult,
+) -> Result<()> {
+    use orchestrate_core::RepoProvider;
+
+    let desired = &rule.protection;
+    match repo.provider {
+        RepoProvider::GitHub => {
+            use orchestrate_github::{branch_protection::SUPPORTED_SETTINGS, GitHubClient};
+
+            let client = GitHubClient::from_url(&repo.url)?;
+            let current = client.get_branch_protection(&rule.branch)?;
+            result.unsupported = desired.unsupported(&SUPPORTED_SETTINGS);
+            result.drift = desired.drift(current.as_ref(), &SUPPORTED_SETTINGS);
+            if !dry_run && !result.drift.is_empty() {
+                client.set_branch_protection(&rule.branch, desired)?;
+                result.applied = true;
+            }
+        }
+        RepoProvider::GitLab => {
+            use orchestrate_gitlab::{protected_branch::SUPPORTED_SETTINGS, GitLabClient};
+
+            let client = GitLabClient::for_repository(repo)?;
+            let current = client.get_branch_protection(&rule.branch)?;
+            result.unsupported = desired.unsupported(&SUPPORTED_SETTINGS);
+            result.drift = desired.drift(current.as_ref(), &SUPPORTED_SETTINGS);
+            if !dry_run && !result.drift.is_empty() {
+                client.set_branch_protection(&rule.branch, desired)?;
+                result.applied = true;
+            }
+        }
+        RepoProvider::Bitbucket => {
+            use orchestrate_bitbucket::{
+                branch_restriction::SUPPORTED_SETTINGS, BitbucketAuth, BitbucketClient,
+            };
+
+            let client = BitbucketClient::for_repository(repo, BitbucketAuth::from_env()?)?;
+            let current = client.get_branch_protection(&rule.branch).await?;
+            result.unsupported = desired.unsupported(&SUPPORTED_SETTINGS);
+            result.drift = desired.drift(current.as_ref(), &SUPPORTED_SETTINGS);
+            if !dry_run && !result.drift.is_empty() {
+                client.set_branch_protection(&rule.branch, desired).await?;
+                result.applied = true;
+            }
+        }
+        RepoProvider::Other => anyhow::bail!("Unsupported provider for {}", repo.url),
+    }
+    Ok(())
+}
+
+/// Repository a PR command targets, given its clone URL or the origin remote
+fn pr_repository(url: Option<String>) -> Result<orchestrate_core::Repository> {
+    let url = match url {
+        Some(url) => url,
+        None => {
+            let output = std::process::Command::new("git")
+                .args(["remote", "get-url", "origin"])
+                .output()?;
+            if !output.status.success() {
+                anyhow::bail!("No origin remote; pass --repo");
+            }
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+    };
+    let name = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or(&url)
+        .trim_end_matches(".git")
+        .to_string();
+    Ok(orchestrate_core::Repository::new(&name, &url))
+}
+
+/// Provider client the PR workflow drives a repository's PRs through
+fn pr_provider(
+    repo: &orchestrate_core::Repository,
+) -> Result<std::sync::Arc<dyn orchestrate_core::PrProvider>> {
+    use orchestrate_core::RepoProvider;
+    use std::sync::Arc;
+
+    Ok(match repo.provider {
+        RepoProvider::GitHub => Arc::new(orchestrate_github::GitHubClient::from_url(&repo.url)?),
+        RepoProvider::GitLab => Arc::new(orchestrate_gitlab::GitLabClient::for_repository(repo)?),
+        RepoProvider::Bitbucket => {
+            use orchestrate_bitbucket::{BitbucketAuth, BitbucketClient};
+
+            let auth = BitbucketAuth::from_env()?;
+            Arc::new(BitbucketClient::for_repository(repo, auth)?)
+        }
+        RepoProvider::Other => anyhow::bail!("Unsupported provider for {}", repo.url),
+    })
+}
+
 /// Handle webhook start command
 async fn handle_webhook_start(
     db: Database,
@@ -7421,17 +9567,33 @@ async fn handle_webhook_start(
 
     let db_arc = Arc::new(db);
 
+    let metrics = Arc::new(orchestrate_web::MetricsCollector::default());
+
     // Start webhook processor in background
-    let processor = WebhookProcessor::new(db_arc.clone(), WebhookProcessorConfig::default());
+    let processor = WebhookProcessor::new(db_arc.clone(), WebhookProcessorConfig::default())
+        .with_metrics(metrics.clone());
     tokio::spawn(async move {
         processor.run().await;
     });
 
+    // Deliver outbound webhooks in background
+    let dispatcher = orchestrate_core::OutboundWebhookDispatcher::new(db_arc.clone());
+    tokio::spawn(async move {
+        dispatcher.run(std::time::Duration::from_secs(5)).await;
+    });
+
+    spawn_datadog_exporter(db_arc.clone(), metrics.clone());
+
     // Create AppState for the router
-    let app_state = Arc::new(orchestrate_web::api::AppState::new(
-        db_arc.as_ref().clone(),
-        None, // No API key for webhook-only server
-    ));
+    let app_state = Arc::new(with_artifact_storage(
+        orchestrate_web::api::AppState::new(
+            db_arc.as_ref().clone(),
+            None, // No API key for webhook-only server
+        )
+        .with_metrics(metrics),
+        db_arc.as_ref(),
+    )?);
+    publish_network_events(&app_state);
 
     // Create router with webhook endpoint
     let app = create_router_with_webhook(app_state, webhook_secret.clone());
@@ -7444,6 +9606,7 @@ async fn handle_webhook_start(
     println!("╠══════════════════════════════════════════════════════════════╣");
     println!("║  Listening on: {:<46} ║", addr);
     println!("║  Webhook URL:  {:<46} ║", format!("http://{}:{}/webhooks/github", "localhost", port));
+    println!("║  GitLab URL:   {:<46} ║", format!("http://{}:{}/webhooks/gitlab", "localhost", port));
     println!("║  Secret configured: {:<39} ║", if webhook_secret.is_some() { "Yes" } else { "No" });
     println!("╚══════════════════════════════════════════════════════════════╝");
     println!();
@@ -7607,102 +9770,839 @@ async fn handle_webhook_secret_show() -> Result<()> {
     Ok(())
 }
 
-/// Generate a minimal test payload for simulation
-fn generate_test_payload(event_type: &str) -> String {
-    match event_type {
-        "pull_request.opened" | "pull_request" => {
-            r#"{"action":"opened","number":1,"pull_request":{"number":1,"title":"Test PR","head":{"ref":"test-branch"},"base":{"ref":"main"}}}"#.to_string()
-        }
-        "check_run.completed" | "check_run" => {
-            r#"{"action":"completed","check_run":{"id":1,"name":"test","conclusion":"failure","head_sha":"abc123","html_url":"https://github.com/test/test/runs/1"}}"#.to_string()
-        }
-        "check_suite.completed" | "check_suite" => {
-            r#"{"action":"completed","check_suite":{"id":1,"conclusion":"failure","head_sha":"abc123","head_branch":"main"}}"#.to_string()
-        }
-        "push" => {
-            r#"{"ref":"refs/heads/main","before":"abc123","after":"def456","commits":[]}"#.to_string()
-        }
-        "pull_request_review.submitted" | "pull_request_review" => {
-            r#"{"action":"submitted","pull_request":{"number":1},"review":{"state":"changes_requested","body":"Please fix"}}"#.to_string()
-        }
-        "issues.opened" | "issues" => {
-            r#"{"action":"opened","issue":{"number":1,"title":"Test Issue","body":"Test body"}}"#.to_string()
+async fn handle_custom_webhook_add(db: &Database, file: &PathBuf) -> Result<()> {
+    use orchestrate_core::{CustomWebhook, CustomWebhookDefinition};
+
+    let yaml = std::fs::read_to_string(file)?;
+    let definition = CustomWebhookDefinition::from_yaml_str(&yaml)
+        .map_err(|e| anyhow::anyhow!("{}: {}", file.display(), e))?;
+
+    // Store the YAML as written so ${VAR} tokens resolve on the server
+    match db.get_custom_webhook_by_name(&definition.name).await? {
+        Some(mut webhook) => {
+            webhook.definition = yaml;
+            db.update_custom_webhook(&webhook).await?;
+            println!("Custom webhook updated: {}", definition.name);
         }
-        _ => {
-            format!(r#"{{"action":"test","event_type":"{}"}}"#, event_type)
+        None => {
+            let webhook = CustomWebhook::new(definition.name.clone(), yaml);
+            db.insert_custom_webhook(&webhook).await?;
+            println!("Custom webhook created: {}", definition.name);
         }
     }
+    println!("  URL: /webhooks/custom/{}", definition.name);
+    println!("  Event: {}", definition.event);
+    if definition.token.is_none() {
+        println!("  Warning: no token configured; any caller can post events");
+    }
+
+    Ok(())
 }
 
-// ==================== Pipeline Command Handlers ====================
+async fn handle_custom_webhook_list(db: &Database) -> Result<()> {
+    use orchestrate_core::CustomWebhookDefinition;
 
-async fn handle_pipeline_create(db: &Database, file: &PathBuf) -> Result<()> {
-    use orchestrate_core::Pipeline;
-    use std::fs;
+    let webhooks = db.list_custom_webhooks().await?;
+    if webhooks.is_empty() {
+        println!("No custom webhooks found");
+        return Ok(());
+    }
 
-    // Read YAML file
-    let yaml = fs::read_to_string(file)?;
+    println!(
+        "{:<20} {:<25} {:<8} {:<6} URL",
+        "NAME", "EVENT", "ENABLED", "TOKEN"
+    );
+    println!("{}", "-".repeat(90));
+    for webhook in webhooks {
+        let (event, token) = match CustomWebhookDefinition::from_yaml_str(&webhook.definition) {
+            Ok(definition) => (definition.event, definition.token.is_some()),
+            Err(_) => ("(invalid)".to_string(), false),
+        };
+        println!(
+            "{:<20} {:<25} {:<8} {:<6} /webhooks/custom/{}",
+            webhook.name,
+            event,
+            if webhook.enabled { "yes" } else { "no" },
+            if token { "yes" } else { "no" },
+            webhook.name
+        );
+    }
 
-    // Try to parse pipeline name from YAML (simple approach - look for "name:" line)
-    let name = yaml
-        .lines()
-        .find(|line| line.trim_start().starts_with("name:"))
-        .and_then(|line| line.split(':').nth(1))
-        .map(|s| s.trim().trim_matches('"').to_string())
-        .ok_or_else(|| anyhow::anyhow!("Pipeline YAML must contain 'name' field"))?;
+    Ok(())
+}
 
-    // Create pipeline (validation will happen when the executor parses it)
-    let pipeline = Pipeline::new(name.clone(), yaml);
-    db.insert_pipeline(&pipeline).await?;
+async fn handle_custom_webhook_show(db: &Database, name: &str) -> Result<()> {
+    let webhook = db
+        .get_custom_webhook_by_name(name)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Custom webhook not found: {}", name))?;
 
-    println!("Pipeline created: {}", name);
-    println!("  File: {:?}", file);
-    println!("  Note: Pipeline definition will be validated on first run");
+    println!("Custom webhook: {}", webhook.name);
+    println!("URL: /webhooks/custom/{}", webhook.name);
+    println!("Enabled: {}", webhook.enabled);
+    println!("\nDefinition:\n{}", webhook.definition);
 
     Ok(())
 }
 
-async fn handle_pipeline_list(db: &Database, enabled_only: bool) -> Result<()> {
-    let pipelines = if enabled_only {
-        db.list_enabled_pipelines().await?
-    } else {
-        db.list_pipelines().await?
-    };
-
-    if pipelines.is_empty() {
-        println!("No pipelines found");
-        return Ok(());
+async fn handle_custom_webhook_remove(db: &Database, name: &str) -> Result<()> {
+    if !db.delete_custom_webhook(name).await? {
+        anyhow::bail!("Custom webhook not found: {}", name);
     }
+    println!("Custom webhook removed: {}", name);
 
-    println!("{:<30} {:<10} {:<20}", "NAME", "ENABLED", "CREATED");
-    println!("{}", "-".repeat(70));
+    Ok(())
+}
 
-    for pipeline in pipelines {
-        let enabled_str = if pipeline.enabled { "yes" } else { "no" };
-        let created = pipeline.created_at.format("%Y-%m-%d %H:%M:%S");
-        println!("{:<30} {:<10} {:<20}", pipeline.name, enabled_str, created);
+async fn handle_outbound_webhook_add(
+    db: &Database,
+    url: String,
+    events: Vec<String>,
+    secret: Option<String>,
+    description: Option<String>,
+) -> Result<()> {
+    use orchestrate_core::OutboundWebhook;
+    use rand::Rng;
+
+    let generated = secret.is_none();
+    let secret = secret.unwrap_or_else(|| {
+        rand::thread_rng()
+            .sample_iter(&rand::distributions::Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect()
+    });
+
+    let mut webhook = OutboundWebhook::new(url, events).with_secret(secret.clone());
+    if let Some(description) = description {
+        webhook = webhook.with_description(description);
+    }
+    webhook.validate()?;
+    let id = db.insert_outbound_webhook(&webhook).await?;
+
+    println!("Outbound webhook created: {}", id);
+    println!("  URL: {}", webhook.url);
+    println!("  Events: {}", webhook.events.join(", "));
+    if generated {
+        println!("  Secret: {}", secret);
+        println!("  Payloads are signed in X-Orchestrate-Signature-256; store the secret now.");
     }
 
     Ok(())
 }
 
-async fn handle_pipeline_show(db: &Database, name: &str) -> Result<()> {
-    let pipeline = db
-        .get_pipeline_by_name(name)
-        .await?
-        .ok_or_else(|| anyhow::anyhow!("Pipeline not found: {}", name))?;
+async fn handle_outbound_webhook_list(db: &Database) -> Result<()> {
+    let webhooks = db.list_outbound_webhooks().await?;
+    if webhooks.is_empty() {
+        println!("No outbound webhooks found");
+        return Ok(());
+    }
 
-    println!("Pipeline: {}", pipeline.name);
-    println!("Enabled: {}", if pipeline.enabled { "yes" } else { "no" });
-    println!("Created: {}", pipeline.created_at.format("%Y-%m-%d %H:%M:%S"));
-    println!("\nDefinition:");
-    println!("{}", pipeline.definition);
+    println!("{:<6} {:<8} {:<35} URL", "ID", "ENABLED", "EVENTS");
+    println!("{}", "-".repeat(90));
+    for webhook in webhooks {
+        println!(
+            "{:<6} {:<8} {:<35} {}",
+            webhook.id.unwrap_or_default(),
+            if webhook.enabled { "yes" } else { "no" },
+            webhook.events.join(","),
+            webhook.url
+        );
+    }
 
     Ok(())
 }
 
-async fn handle_pipeline_update(db: &Database, name: &str, file: &PathBuf) -> Result<()> {
-    use std::fs;
+async fn handle_outbound_webhook_remove(db: &Database, id: i64) -> Result<()> {
+    if !db.delete_outbound_webhook(id).await? {
+        anyhow::bail!("Outbound webhook not found: {}", id);
+    }
+    println!("Outbound webhook removed: {}", id);
+
+    Ok(())
+}
+
+async fn handle_outbound_webhook_deliveries(
+    db: &Database,
+    webhook: Option<i64>,
+    limit: i64,
+) -> Result<()> {
+    let deliveries = db.list_outbound_deliveries(webhook, limit).await?;
+    if deliveries.is_empty() {
+        println!("No outbound deliveries found");
+        return Ok(());
+    }
+
+    println!(
+        "{:<8} {:<8} {:<20} {:<10} {:<9} {:<8} {:<20} ERROR",
+        "ID", "WEBHOOK", "EVENT", "STATUS", "ATTEMPTS", "HTTP", "CREATED"
+    );
+    println!("{}", "-".repeat(110));
+    for delivery in deliveries {
+        println!(
+            "{:<8} {:<8} {:<20} {:<10} {:<9} {:<8} {:<20} {}",
+            delivery.id.unwrap_or_default(),
+            delivery.webhook_id,
+            delivery.event_type,
+            delivery.status.as_str(),
+            format!("{}/{}", delivery.attempts, delivery.max_attempts),
+            delivery
+                .response_status
+                .map(|status| status.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            delivery.created_at.format("%Y-%m-%d %H:%M:%S"),
+            delivery.error_message.as_deref().unwrap_or("")
+        );
+    }
+
+    Ok(())
+}
+
+async fn handle_outbound_webhook_test(db: Database, id: i64) -> Result<()> {
+    use orchestrate_core::{OutboundDeliveryStatus, OutboundWebhookDispatcher};
+
+    let webhook = db
+        .get_outbound_webhook(id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Outbound webhook not found: {}", id))?;
+
+    println!("Sending test delivery to {}...", webhook.url);
+    let dispatcher = OutboundWebhookDispatcher::new(std::sync::Arc::new(db));
+    let delivery = dispatcher.send_test(id).await?;
+
+    match delivery.status {
+        OutboundDeliveryStatus::Succeeded => println!(
+            "Delivered (HTTP {})",
+            delivery.response_status.unwrap_or_default()
+        ),
+        _ => anyhow::bail!(
+            "Test delivery failed: {}",
+            delivery.error_message.unwrap_or_default()
+        ),
+    }
+
+    Ok(())
+}
+
+/// Resolve a source secret, generating one for schemes that allow it
+fn webhook_source_secret(
+    scheme: orchestrate_core::SignatureScheme,
+    secret: Option<String>,
+) -> Result<(String, bool)> {
+    use orchestrate_core::SignatureScheme;
+    use rand::Rng;
+
+    match secret {
+        Some(secret) => Ok((secret, false)),
+        None if scheme == SignatureScheme::Ed25519 => {
+            anyhow::bail!("--secret is required for ed25519 (the sender's public key)")
+        }
+        None => Ok((
+            rand::thread_rng()
+                .sample_iter(&rand::distributions::Alphanumeric)
+                .take(64)
+                .map(char::from)
+                .collect(),
+            true,
+        )),
+    }
+}
+
+async fn handle_webhook_source_add(
+    db: &Database,
+    name: String,
+    scheme: &str,
+    header: Option<String>,
+    secret: Option<String>,
+) -> Result<()> {
+    use orchestrate_core::{SignatureScheme, WebhookSource};
+
+    let scheme: SignatureScheme = scheme.parse()?;
+    let (secret, generated) = webhook_source_secret(scheme, secret)?;
+
+    let mut source = WebhookSource::new(name, scheme, secret.clone());
+    if let Some(header) = header {
+        source = source.with_header(header);
+    }
+    source.validate()?;
+
+    if db.get_webhook_source(&source.name).await?.is_some() {
+        db.delete_webhook_source(&source.name).await?;
+    }
+    db.insert_webhook_source(&source).await?;
+
+    println!("Webhook source configured: {}", source.name);
+    println!("  Scheme: {}", source.scheme.as_str());
+    println!("  Header: {}", source.header);
+    if generated {
+        println!("  Secret: {}", secret);
+        println!("  Configure the sender with this secret now.");
+    }
+
+    Ok(())
+}
+
+async fn handle_webhook_source_rotate(
+    db: &Database,
+    name: &str,
+    secret: Option<String>,
+    overlap_hours: i64,
+) -> Result<()> {
+    if overlap_hours < 0 {
+        anyhow::bail!("--overlap-hours must not be negative");
+    }
+
+    let mut source = db
+        .get_webhook_source(name)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Webhook source not found: {}", name))?;
+
+    let (secret, generated) = webhook_source_secret(source.scheme, secret)?;
+    source.rotate(secret.clone(), chrono::Duration::hours(overlap_hours));
+    source.validate()?;
+    db.update_webhook_source(&source).await?;
+
+    println!("Rotated secret for {}", source.name);
+    println!(
+        "  Previous secrets remain valid for {} hour(s)",
+        overlap_hours
+    );
+    if generated {
+        println!("  Secret: {}", secret);
+    }
+
+    Ok(())
+}
+
+async fn handle_webhook_source_list(db: &Database) -> Result<()> {
+    let sources = db.list_webhook_sources().await?;
+    if sources.is_empty() {
+        println!("No webhook sources configured");
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now();
+    println!("{:<25} {:<13} {:<25} SECRETS", "SOURCE", "SCHEME", "HEADER");
+    println!("{}", "-".repeat(80));
+    for source in sources {
+        println!(
+            "{:<25} {:<13} {:<25} {}/{} active",
+            source.name,
+            source.scheme.as_str(),
+            source.header,
+            source.active_secrets(now).count(),
+            source.secrets.len()
+        );
+    }
+
+    Ok(())
+}
+
+async fn handle_webhook_source_remove(db: &Database, name: &str) -> Result<()> {
+    if !db.delete_webhook_source(name).await? {
+        anyhow::bail!("Webhook source not found: {}", name);
+    }
+    println!("Webhook source removed: {}", name);
+
+    Ok(())
+}
+
+/// Split a `<key>=<value>` Jira or Linear mapping
+fn parse_jira_mapping(mapping: &str) -> Result<(&str, &str)> {
+    mapping
+        .split_once('=')
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Invalid mapping '{}', expected <key>=<value>", mapping))
+}
+
+async fn handle_jira_project_add(
+    db: &Database,
+    project: orchestrate_core::JiraProject,
+) -> Result<()> {
+    project.validate()?;
+
+    if db.get_jira_project(&project.name).await?.is_some() {
+        db.delete_jira_project(&project.name).await?;
+    }
+    db.insert_jira_project(&project).await?;
+
+    println!("Jira project configured: {}", project.name);
+    println!("  URL: {}", project.base_url);
+    println!("  Project key: {}", project.project_key);
+    if !project.epic_prefix.is_empty() {
+        println!("  Epics: {}*", project.epic_prefix);
+    }
+    for (status, jira_status) in &project.status_mapping {
+        println!("  Status {} -> {}", status, jira_status);
+    }
+    for (source, field_id) in &project.field_mapping {
+        println!("  Field {} -> {}", source, field_id);
+    }
+    if orchestrate_core::JiraAuth::from_env().is_none() {
+        println!("  Set JIRA_API_TOKEN (and JIRA_EMAIL for Jira Cloud) to enable syncing.");
+    }
+
+    Ok(())
+}
+
+async fn handle_jira_project_list(db: &Database) -> Result<()> {
+    let projects = db.list_jira_projects().await?;
+    if projects.is_empty() {
+        println!("No Jira projects configured");
+        return Ok(());
+    }
+
+    println!(
+        "{:<20} {:<10} {:<15} {:<8} URL",
+        "NAME", "KEY", "EPIC PREFIX", "ENABLED"
+    );
+    println!("{}", "-".repeat(90));
+    for project in projects {
+        println!(
+            "{:<20} {:<10} {:<15} {:<8} {}",
+            project.name,
+            project.project_key,
+            if project.epic_prefix.is_empty() {
+                "*"
+            } else {
+                project.epic_prefix.as_str()
+            },
+            if project.enabled { "yes" } else { "no" },
+            project.base_url
+        );
+    }
+
+    Ok(())
+}
+
+async fn handle_jira_sync(db: &Database, epic: Option<&str>, story: Option<&str>) -> Result<()> {
+    let jira = orchestrate_core::JiraSyncService::from_env(db.clone()).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Jira is not configured. Set JIRA_API_TOKEN (and JIRA_EMAIL for Jira Cloud)."
+        )
+    })?;
+
+    let report = match (epic, story) {
+        (_, Some(id)) => {
+            let story = db
+                .get_story(id)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Story not found: {}", id))?;
+            jira.sync_stories(&[story]).await
+        }
+        (Some(epic_id), None) => {
+            let stories = db.get_stories_for_epic(epic_id).await?;
+            jira.sync_stories(&stories).await
+        }
+        (None, None) => jira.sync_all().await?,
+    };
+
+    println!("Jira sync complete");
+    println!("  Issues created: {}", report.created);
+    println!("  Issues transitioned: {}", report.transitioned);
+    println!("  Comments pulled: {}", report.comments);
+    if !report.errors.is_empty() {
+        println!("  Errors:");
+        for error in &report.errors {
+            println!("    {}", error);
+        }
+        anyhow::bail!("{} Jira sync error(s)", report.errors.len());
+    }
+
+    Ok(())
+}
+
+async fn handle_jira_comments(db: &Database, story_id: &str) -> Result<()> {
+    let comments = db.list_jira_comments(story_id).await?;
+    match orchestrate_core::jira::comments_context(&comments) {
+        Some(context) => println!("{}", context),
+        None => println!("No Jira comments pulled for story {}", story_id),
+    }
+
+    Ok(())
+}
+
+async fn handle_linear_team_add(db: &Database, team: orchestrate_core::LinearTeam) -> Result<()> {
+    team.validate()?;
+
+    if db.get_linear_team(&team.name).await?.is_some() {
+        db.delete_linear_team(&team.name).await?;
+    }
+    db.insert_linear_team(&team).await?;
+
+    println!("Linear team configured: {}", team.name);
+    println!("  Team ID: {}", team.linear_team_id);
+    if !team.epic_prefix.is_empty() {
+        println!("  Epics: {}*", team.epic_prefix);
+    }
+    for (status, state_name) in &team.status_mapping {
+        println!("  Status {} -> {}", status, state_name);
+    }
+    println!(
+        "  Issues labelled '{}' spawn a {} agent",
+        team.trigger_label,
+        team.agent_type.as_str()
+    );
+    if orchestrate_core::LinearClient::from_env().is_none() {
+        println!("  Set LINEAR_API_KEY to enable syncing stories to Linear.");
+    }
+    println!(
+        "  Send Linear issue webhooks to /api/linear/webhook, signed with LINEAR_WEBHOOK_SECRET."
+    );
+
+    Ok(())
+}
+
+async fn handle_linear_team_list(db: &Database) -> Result<()> {
+    let teams = db.list_linear_teams().await?;
+    if teams.is_empty() {
+        println!("No Linear teams configured");
+        return Ok(());
+    }
+
+    println!(
+        "{:<20} {:<38} {:<15} {:<15} {:<8}",
+        "NAME", "TEAM ID", "EPIC PREFIX", "TRIGGER LABEL", "ENABLED"
+    );
+    println!("{}", "-".repeat(100));
+    for team in teams {
+        println!(
+            "{:<20} {:<38} {:<15} {:<15} {:<8}",
+            team.name,
+            team.linear_team_id,
+            if team.epic_prefix.is_empty() {
+                "*"
+            } else {
+                team.epic_prefix.as_str()
+            },
+            team.trigger_label,
+            if team.enabled { "yes" } else { "no" }
+        );
+    }
+
+    Ok(())
+}
+
+async fn handle_linear_sync(db: &Database, epic: Option<&str>, story: Option<&str>) -> Result<()> {
+    let linear = orchestrate_core::LinearSyncService::from_env(db.clone());
+    if !linear.has_client() {
+        anyhow::bail!("Linear is not configured. Set LINEAR_API_KEY.");
+    }
+
+    let report = match (epic, story) {
+        (_, Some(id)) => {
+            let story = db
+                .get_story(id)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Story not found: {}", id))?;
+            linear.sync_stories(&[story]).await
+        }
+        (Some(epic_id), None) => {
+            let stories = db.get_stories_for_epic(epic_id).await?;
+            linear.sync_stories(&stories).await
+        }
+        (None, None) => linear.sync_all().await?,
+    };
+
+    println!("Linear sync complete");
+    println!("  Issues created: {}", report.created);
+    println!("  Issues moved: {}", report.moved);
+    if !report.errors.is_empty() {
+        println!("  Errors:");
+        for error in &report.errors {
+            println!("    {}", error);
+        }
+        anyhow::bail!("{} Linear sync error(s)", report.errors.len());
+    }
+
+    Ok(())
+}
+
+/// Artifact store configured by the environment
+fn require_artifact_store(db: &Database) -> Result<orchestrate_core::ArtifactStore> {
+    orchestrate_core::ArtifactStore::from_env(db.clone())?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Artifact storage is not configured; set ORCHESTRATE_STORAGE to s3, gcs, or local"
+        )
+    })
+}
+
+fn print_stored_artifact(db: &Database, artifact: &orchestrate_core::Artifact) -> Result<()> {
+    let url = require_artifact_store(db)?.signed_url(artifact)?;
+    println!(
+        "Stored as artifact {} ({})",
+        artifact.id.unwrap_or_default(),
+        artifact.key
+    );
+    println!("  URL: {}", url);
+    Ok(())
+}
+
+/// Content type of a file, by extension
+fn guess_content_type(path: &std::path::Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    match extension {
+        "json" => "application/json",
+        "sarif" => "application/sarif+json",
+        "html" | "htm" => "text/html",
+        "md" => "text/markdown",
+        "txt" | "log" => "text/plain",
+        "diff" | "patch" => "text/x-diff",
+        "csv" => "text/csv",
+        "png" => "image/png",
+        "svg" => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+async fn handle_artifact_upload(
+    db: &Database,
+    file: &str,
+    agent: &str,
+    content_type: Option<&str>,
+) -> Result<()> {
+    let agent_id = Uuid::parse_str(agent)?;
+    if db.get_agent(agent_id).await?.is_none() {
+        anyhow::bail!("Agent not found: {}", agent);
+    }
+
+    let path = std::path::Path::new(file);
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow::anyhow!("Invalid file name: {}", file))?;
+    let body = std::fs::read(path)?;
+
+    let artifact = require_artifact_store(db)?
+        .put_agent_artifact(
+            agent_id,
+            name,
+            content_type.unwrap_or_else(|| guess_content_type(path)),
+            body,
+        )
+        .await?;
+    print_stored_artifact(db, &artifact)
+}
+
+async fn handle_artifact_list(
+    db: &Database,
+    kind: Option<&str>,
+    owner: Option<&str>,
+    limit: i64,
+    json: bool,
+) -> Result<()> {
+    use std::str::FromStr;
+
+    let kind = kind
+        .map(orchestrate_core::ArtifactKind::from_str)
+        .transpose()?;
+    let artifacts = db.list_artifacts(kind, owner, limit).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&artifacts)?);
+        return Ok(());
+    }
+    if artifacts.is_empty() {
+        println!("No artifacts stored");
+        return Ok(());
+    }
+
+    println!(
+        "{:<6} {:<16} {:<38} {:<24} {:>10} {:<20}",
+        "ID", "KIND", "OWNER", "NAME", "SIZE", "CREATED"
+    );
+    println!("{}", "-".repeat(118));
+    for artifact in artifacts {
+        println!(
+            "{:<6} {:<16} {:<38} {:<24} {:>10} {:<20}",
+            artifact.id.unwrap_or_default(),
+            artifact.kind.as_str(),
+            artifact.owner_id.as_deref().unwrap_or("-"),
+            artifact.name,
+            artifact.size_bytes,
+            artifact.created_at.format("%Y-%m-%d %H:%M:%S")
+        );
+    }
+
+    Ok(())
+}
+
+/// Generate a minimal test payload for simulation
+fn generate_test_payload(event_type: &str) -> String {
+    match event_type {
+        "pull_request.opened" | "pull_request" => {
+            r#"{"action":"opened","number":1,"pull_request":{"number":1,"title":"Test PR","head":{"ref":"test-branch"},"base":{"ref":"main"}}}"#.to_string()
+        }
+        "check_run.completed" | "check_run" => {
+            r#"{"action":"completed","check_run":{"id":1,"name":"test","conclusion":"failure","head_sha":"abc123","html_url":"https://github.com/test/test/runs/1"}}"#.to_string()
+        }
+        "check_suite.completed" | "check_suite" => {
+            r#"{"action":"completed","check_suite":{"id":1,"conclusion":"failure","head_sha":"abc123","head_branch":"main"}}"#.to_string()
+        }
+        "push" => {
+            r#"{"ref":"refs/heads/main","before":"abc123","after":"def456","commits":[]}"#.to_string()
+        }
+        "pull_request_review.submitted" | "pull_request_review" => {
+            r#"{"action":"submitted","pull_request":{"number":1},"review":{"state":"changes_requested","body":"Please fix"}}"#.to_string()
+        }
+        "issues.opened" | "issues" => {
+            r#"{"action":"opened","issue":{"number":1,"title":"Test Issue","body":"Test body"}}"#.to_string()
+        }
+        "issue_comment.created" | "issue_comment" => {
+            r#"{"action":"created","issue":{"number":1,"pull_request":{}},"comment":{"body":"/orchestrate rerun-review","author_association":"OWNER","user":{"login":"test","type":"User"}},"repository":{"full_name":"test/test","default_branch":"main"}}"#.to_string()
+        }
+        _ => {
+            format!(r#"{{"action":"test","event_type":"{}"}}"#, event_type)
+        }
+    }
+}
+
+// ==================== Pipeline Command Handlers ====================
+
+async fn handle_pipeline_create(
+    db: &Database,
+    file: &PathBuf,
+    allow_unknown_agents: bool,
+) -> Result<()> {
+    use orchestrate_core::Pipeline;
+    use std::fs;
+
+    // Read YAML file
+    let yaml = fs::read_to_string(file)?;
+    let (definition, yaml) = check_pipeline_file(db, file, &yaml, allow_unknown_agents).await?;
+
+    let pipeline = Pipeline::new(definition.name.clone(), yaml);
+    db.insert_pipeline(&pipeline).await?;
+
+    println!("Pipeline created: {}", definition.name);
+    println!("  File: {:?}", file);
+    println!("  Stages: {}", definition.stages.len());
+
+    Ok(())
+}
+
+/// Validate a pipeline YAML file, printing each problem with its location
+///
+/// Returns the definition along with the YAML to store, which has any
+/// `extends:` and `include:` templates expanded.
+async fn check_pipeline_file(
+    db: &Database,
+    file: &std::path::Path,
+    source: &str,
+    allow_unknown_agents: bool,
+) -> Result<(orchestrate_core::PipelineDefinition, String)> {
+    use orchestrate_core::{AgentType, PipelineDefinition, TemplateLibrary};
+
+    let yaml = TemplateLibrary::load(db)
+        .await?
+        .expand(source)
+        .map_err(|e| anyhow::anyhow!("{}: {}", file.display(), e))?;
+    // Locations refer to the expanded definition rather than the file
+    let location = if yaml == source {
+        file.display().to_string()
+    } else {
+        format!("{} (expanded)", file.display())
+    };
+
+    let mut issues = match PipelineDefinition::check_yaml(&yaml) {
+        Ok(definition) => {
+            let issues = if allow_unknown_agents {
+                Vec::new()
+            } else {
+                let custom_types: std::collections::HashSet<String> = db
+                    .list_agent_type_definitions()
+                    .await?
+                    .into_iter()
+                    .map(|definition| definition.name)
+                    .collect();
+                definition.check_agents(&yaml, |agent| {
+                    AgentType::from_str(agent).is_ok() || custom_types.contains(agent)
+                })
+            };
+            if issues.is_empty() {
+                return Ok((definition, yaml));
+            }
+            issues
+        }
+        Err(issues) => issues,
+    };
+
+    issues.sort_by_key(|issue| (issue.line.is_none(), issue.line, issue.column));
+    for issue in &issues {
+        match issue.line {
+            Some(_) => eprintln!("{}:{}", location, issue),
+            None => eprintln!("{}: {}", location, issue),
+        }
+    }
+    anyhow::bail!(
+        "Pipeline definition has {} error(s); run `orchestrate pipeline schema` for the accepted format",
+        issues.len()
+    )
+}
+
+async fn handle_pipeline_list(db: &Database, enabled_only: bool) -> Result<()> {
+    let pipelines = if enabled_only {
+        db.list_enabled_pipelines().await?
+    } else {
+        db.list_pipelines().await?
+    };
+
+    if pipelines.is_empty() {
+        println!("No pipelines found");
+        return Ok(());
+    }
+
+    println!("{:<30} {:<10} {:<20}", "NAME", "ENABLED", "CREATED");
+    println!("{}", "-".repeat(70));
+
+    for pipeline in pipelines {
+        let enabled_str = if pipeline.enabled { "yes" } else { "no" };
+        let created = pipeline.created_at.format("%Y-%m-%d %H:%M:%S");
+        println!("{:<30} {:<10} {:<20}", pipeline.name, enabled_str, created);
+    }
+
+    Ok(())
+}
+
+async fn handle_pipeline_show(db: &Database, name: &str, graph: Option<&str>) -> Result<()> {
+    use orchestrate_core::{PipelineDefinition, PipelineGraph};
+
+    let pipeline = db
+        .get_pipeline_by_name(name)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Pipeline not found: {}", name))?;
+
+    if let Some(format) = graph {
+        let definition = PipelineDefinition::from_yaml_str(&pipeline.definition)?;
+        let graph = PipelineGraph::from_definition(&definition);
+        match format {
+            "mermaid" => print!("{}", graph.to_mermaid()),
+            "dot" => print!("{}", graph.to_dot()),
+            other => anyhow::bail!("Unknown graph format '{}' (expected mermaid or dot)", other),
+        }
+        return Ok(());
+    }
+
+    println!("Pipeline: {}", pipeline.name);
+    println!("Enabled: {}", if pipeline.enabled { "yes" } else { "no" });
+    println!("Created: {}", pipeline.created_at.format("%Y-%m-%d %H:%M:%S"));
+    println!("\nDefinition:");
+    println!("{}", pipeline.definition);
+
+    Ok(())
+}
+
+async fn handle_pipeline_update(
+    db: &Database,
+    name: &str,
+    file: &PathBuf,
+    allow_unknown_agents: bool,
+) -> Result<()> {
+    use std::fs;
 
     // Get existing pipeline
     let mut pipeline = db
@@ -7712,13 +10612,13 @@ async fn handle_pipeline_update(db: &Database, name: &str, file: &PathBuf) -> Re
 
     // Read new YAML file
     let yaml = fs::read_to_string(file)?;
+    let (definition, yaml) = check_pipeline_file(db, file, &yaml, allow_unknown_agents).await?;
 
-    // Update pipeline (validation will happen when the executor parses it)
     pipeline.definition = yaml;
     db.update_pipeline(&pipeline).await?;
 
     println!("Pipeline updated: {}", name);
-    println!("  Note: Pipeline definition will be validated on first run");
+    println!("  Stages: {}", definition.stages.len());
 
     Ok(())
 }
@@ -7761,34 +10661,158 @@ async fn handle_pipeline_disable(db: &Database, name: &str) -> Result<()> {
     Ok(())
 }
 
-async fn handle_pipeline_run(db: &Database, name: &str, dry_run: bool) -> Result<()> {
-    use orchestrate_core::PipelineRun;
+async fn handle_pipeline_run(
+    db: &Database,
+    name: &str,
+    dry_run: bool,
+    json: bool,
+    commit: Option<String>,
+    no_cache: bool,
+) -> Result<()> {
+    use orchestrate_core::{PipelineDefinition, PipelineExecutor, PipelineRun, RunAdmission};
 
     let pipeline = db
         .get_pipeline_by_name(name)
         .await?
         .ok_or_else(|| anyhow::anyhow!("Pipeline not found: {}", name))?;
 
+    // Create pipeline run
+    let mut run = PipelineRun::new(pipeline.id.unwrap(), Some("manual".to_string()));
+    if let Some(commit) = commit {
+        run = run.with_commit_sha(commit);
+    }
+    if no_cache {
+        run = run.without_cache();
+    }
+
     if dry_run {
-        println!("Dry run: Would trigger pipeline '{}'", name);
-        println!("Pipeline definition:");
-        println!("{}", pipeline.definition);
+        let definition = PipelineDefinition::from_yaml_str(&pipeline.definition)?;
+        let executor = PipelineExecutor::new(Arc::new(db.clone()));
+        let plan = executor.plan_run(&definition, &run).await?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&plan)?);
+        } else {
+            print_pipeline_plan(&plan, &run);
+        }
         return Ok(());
     }
-
-    // Create pipeline run
-    let run = PipelineRun::new(pipeline.id.unwrap(), Some("manual".to_string()));
-    let run_id = db.insert_pipeline_run(&run).await?;
-
-    println!("Pipeline run started: {}", run_id);
+    let concurrency = PipelineDefinition::from_yaml_str(&pipeline.definition)
+        .ok()
+        .and_then(|definition| definition.concurrency);
+    let admission = db.admit_pipeline_run(&run, concurrency.as_ref()).await?;
+
+    match &admission {
+        RunAdmission::Admitted { run_id, cancelled } => {
+            println!("Pipeline run started: {}", run_id);
+            if !cancelled.is_empty() {
+                let cancelled: Vec<String> = cancelled.iter().map(|id| id.to_string()).collect();
+                println!("  Cancelled in-progress runs: {}", cancelled.join(", "));
+            }
+        }
+        RunAdmission::Queued { run_id, position } => {
+            println!("Pipeline run queued: {}", run_id);
+            println!("  Waiting behind {} earlier run(s)", position);
+        }
+        RunAdmission::Rejected {
+            run_id,
+            in_progress,
+        } => {
+            anyhow::bail!(
+                "Pipeline '{}' is at its concurrency limit with {} run(s) in progress; run {} was rejected",
+                name,
+                in_progress,
+                run_id
+            );
+        }
+    }
     println!("  Pipeline: {}", name);
     println!("  Trigger: manual");
+    if let Some(commit) = &run.commit_sha {
+        println!("  Commit: {}", commit);
+    }
+    println!(
+        "  Stage cache: {}",
+        if run.use_cache { "enabled" } else { "disabled" }
+    );
     println!("\nNote: Pipeline execution requires the daemon to be running.");
-    println!("Use 'orchestrate pipeline status {}' to check progress", run_id);
+    println!(
+        "Use 'orchestrate pipeline status {}' to check progress",
+        admission.run_id()
+    );
 
     Ok(())
 }
 
+/// Print a dry-run plan: stages in start order with their agents and prompts
+fn print_pipeline_plan(plan: &orchestrate_core::PipelinePlan, run: &orchestrate_core::PipelineRun) {
+    use orchestrate_core::PlannedStageAction;
+
+    println!(
+        "Dry run: pipeline '{}' (nothing will be executed)",
+        plan.pipeline
+    );
+    println!("  Trigger: {}", plan.trigger.as_deref().unwrap_or("manual"));
+    if let Some(commit) = &run.commit_sha {
+        println!("  Commit: {}", commit);
+    }
+    if !plan.variables.is_empty() {
+        println!("  Variables:");
+        for (name, value) in &plan.variables {
+            println!("    {}={}", name, value);
+        }
+    }
+
+    println!("\nStages:");
+    for (i, stage) in plan.stages.iter().enumerate() {
+        let action = match &stage.action {
+            PlannedStageAction::Run if !stage.approvers.is_empty() => {
+                format!("run after approval by {}", stage.approvers.join(", "))
+            }
+            PlannedStageAction::Run => "run".to_string(),
+            action => format!(
+                "{}: {}",
+                action.as_str(),
+                action.reason().unwrap_or_default()
+            ),
+        };
+        println!("  {}. {} - {}", i + 1, stage.name, action);
+        if !stage.depends_on.is_empty() {
+            println!("     Depends on: {}", stage.depends_on.join(", "));
+        }
+        if let Some(condition) = &stage.condition {
+            println!("     When: {}", condition);
+        }
+        for agent in &stage.agents {
+            let basis = match agent.estimate.sampled_agents {
+                0 => "default estimate".to_string(),
+                n => format!("average of {} agent(s)", n),
+            };
+            if agent.instance == stage.name {
+                println!("     Agent: {}", agent.agent);
+            } else {
+                println!("     Agent: {} for {}", agent.agent, agent.instance);
+            }
+            println!("       Prompt: {}", agent.prompt);
+            println!(
+                "       Estimate: ~{} tokens, ${:.2} ({})",
+                format_tokens(agent.estimate.tokens as i64),
+                agent.estimate.cost_usd,
+                basis
+            );
+        }
+    }
+
+    println!(
+        "\nEstimated total: ~{} tokens, ${:.2} across {} agent(s)",
+        format_tokens(plan.estimated_tokens() as i64),
+        plan.estimated_cost_usd(),
+        plan.agents().count()
+    );
+    if plan.fails() {
+        println!("Warning: the run would fail (see stages marked fail or blocked)");
+    }
+}
+
 async fn handle_pipeline_status(db: &Database, run_id: i64) -> Result<()> {
     use orchestrate_core::PipelineRunStatus;
 
@@ -7805,6 +10829,14 @@ async fn handle_pipeline_status(db: &Database, run_id: i64) -> Result<()> {
     println!("  Pipeline: {}", pipeline.name);
     println!("  Status: {:?}", run.status);
     println!("  Trigger: {}", run.trigger_event.as_deref().unwrap_or("unknown"));
+    if !run.variables.is_empty() {
+        let mut variables: Vec<_> = run.variables.iter().collect();
+        variables.sort();
+        println!("  Variables:");
+        for (name, value) in variables {
+            println!("    {}={}", name, value);
+        }
+    }
 
     if let Some(started) = run.started_at {
         println!("  Started: {}", started.format("%Y-%m-%d %H:%M:%S"));
@@ -7823,9 +10855,46 @@ async fn handle_pipeline_status(db: &Database, run_id: i64) -> Result<()> {
     if !stages.is_empty() {
         println!("\nStages:");
         for stage in stages {
-            let status_str = format!("{:?}", stage.status);
+            let mut status_str = format!("{:?}", stage.status);
+            if stage.failed_after_retries() {
+                status_str = format!("{} after {} attempts", status_str, stage.attempts);
+            }
             let agent_str = stage.agent_id.as_deref().unwrap_or("N/A");
-            println!("  - {}: {} (agent: {})", stage.stage_name, status_str, agent_str);
+            match stage.cached_from_run_id {
+                Some(from_run_id) => println!(
+                    "  - {}: {} (cached from run {})",
+                    stage.stage_name, status_str, from_run_id
+                ),
+                None => println!(
+                    "  - {}: {} (agent: {})",
+                    stage.stage_name, status_str, agent_str
+                ),
+            }
+        }
+    }
+
+    // Show saga compensation, if the pipeline declared any
+    if let Some(saga) = db
+        .get_saga_for_workflow(orchestrate_core::SagaWorkflowType::PipelineRun, &run_id.to_string())
+        .await?
+    {
+        println!("\nSaga: {}", saga.status);
+        if let Some(step) = &saga.failed_step {
+            println!("  Failed step: {}", step);
+        }
+        for compensation in db.list_saga_compensations(saga.id).await? {
+            print!(
+                "  - #{} {} ({}): {} (agent: {})",
+                compensation.sequence,
+                compensation.step_name,
+                compensation.action,
+                compensation.status,
+                compensation.agent
+            );
+            match &compensation.error_message {
+                Some(err) => println!(" - {}", err),
+                None => println!(),
+            }
         }
     }
 
@@ -7846,6 +10915,53 @@ async fn handle_pipeline_cancel(db: &Database, run_id: i64) -> Result<()> {
     Ok(())
 }
 
+async fn handle_pipeline_resume(db: &Database, run_id: i64) -> Result<()> {
+    use orchestrate_core::{PipelineExecutor, PipelineRunStatus, PipelineStageStatus};
+    use std::sync::Arc;
+
+    let run = db
+        .get_pipeline_run(run_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Pipeline run not found: {}", run_id))?;
+    if run.status != PipelineRunStatus::Failed {
+        anyhow::bail!(
+            "Only failed runs can be resumed; run {} is {}",
+            run_id,
+            run.status.as_str()
+        );
+    }
+
+    let stages = db.list_pipeline_stages(run_id).await?;
+    let names = |statuses: &[PipelineStageStatus]| {
+        stages
+            .iter()
+            .filter(|stage| statuses.contains(&stage.status))
+            .map(|stage| stage.stage_name.as_str())
+            .collect::<Vec<_>>()
+    };
+    let reused = names(&[PipelineStageStatus::Succeeded, PipelineStageStatus::Skipped]);
+    let restarted = names(&[PipelineStageStatus::Failed, PipelineStageStatus::Cancelled]);
+
+    println!("Resuming pipeline run {}...", run_id);
+    if !reused.is_empty() {
+        println!("  Reusing: {}", reused.join(", "));
+    }
+    if !restarted.is_empty() {
+        println!("  Restarting: {}", restarted.join(", "));
+    }
+
+    let executor = PipelineExecutor::new(Arc::new(db.clone())).with_notifications_from_env();
+    if let Err(e) = executor.resume_failed_run(run_id).await {
+        println!("  {}", e);
+    }
+
+    if let Some(run) = db.get_pipeline_run(run_id).await? {
+        println!("  Run status: {}", run.status.as_str());
+    }
+
+    Ok(())
+}
+
 async fn handle_pipeline_history(db: &Database, name: &str, limit: usize) -> Result<()> {
     let pipeline = db
         .get_pipeline_by_name(name)
@@ -7963,6 +11079,83 @@ fn handle_pipeline_init(
     Ok(())
 }
 
+async fn handle_pipeline_template_import(
+    db: &Database,
+    name: &str,
+    file: &PathBuf,
+    description: Option<String>,
+) -> Result<()> {
+    use orchestrate_core::PipelineTemplate;
+
+    let yaml = std::fs::read_to_string(file)?;
+    let description = description.unwrap_or_else(|| {
+        serde_yaml::from_str::<serde_yaml::Value>(&yaml)
+            .ok()
+            .and_then(|doc| doc.get("description")?.as_str().map(str::to_string))
+            .unwrap_or_default()
+    });
+
+    let template = PipelineTemplate::new(name, description, yaml);
+    db.upsert_pipeline_template(&template).await?;
+
+    println!("Pipeline template imported: {}", name);
+    for (parameter, default) in template.parameters()? {
+        match default {
+            Some(default) => println!("  {} (default: {})", parameter, default),
+            None => println!("  {} (required)", parameter),
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_pipeline_template_list(db: &Database) -> Result<()> {
+    use orchestrate_core::pipeline_template;
+
+    let stored = db.list_pipeline_templates().await?;
+    let mut templates: Vec<_> = pipeline_template::get_templates()
+        .into_values()
+        .filter(|builtin| !stored.iter().any(|t| t.name == builtin.name))
+        .map(|template| (template, "built-in"))
+        .collect();
+    templates.extend(stored.into_iter().map(|template| (template, "stored")));
+    templates.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+
+    println!(
+        "{:<20} {:<10} {:<30} DESCRIPTION",
+        "NAME", "SOURCE", "PARAMETERS"
+    );
+    println!("{}", "-".repeat(90));
+    for (template, source) in templates {
+        let parameters: Vec<_> = template.parameters()?.into_keys().collect();
+        println!(
+            "{:<20} {:<10} {:<30} {}",
+            template.name,
+            source,
+            parameters.join(", "),
+            template.description
+        );
+    }
+
+    Ok(())
+}
+
+async fn handle_pipeline_template_show(db: &Database, name: &str) -> Result<()> {
+    use orchestrate_core::TemplateLibrary;
+
+    let library = TemplateLibrary::load(db).await?;
+    let template = library
+        .get(name)
+        .ok_or_else(|| anyhow::anyhow!("Pipeline template not found: {}", name))?;
+
+    println!("Template: {}", template.name);
+    println!("Description: {}", template.description);
+    println!();
+    println!("{}", template.yaml);
+
+    Ok(())
+}
+
 // ==================== Approval Command Handlers ====================
 
 async fn handle_approval_list(db: &Database, pending_only: bool) -> Result<()> {
@@ -8031,6 +11224,7 @@ async fn handle_approval_approve(db: &Database, id: i64, comment: Option<&str>)
     }
 
     db.update_approval_request(&approval).await?;
+    resume_approved_run(db, &approval).await?;
 
     Ok(())
 }
@@ -8067,6 +11261,32 @@ async fn handle_approval_reject(db: &Database, id: i64, reason: Option<&str>) ->
     }
 
     db.update_approval_request(&approval).await?;
+    resume_approved_run(db, &approval).await?;
+
+    Ok(())
+}
+
+/// Resume the pipeline run behind a decided approval and report how it ended
+async fn resume_approved_run(
+    db: &Database,
+    approval: &orchestrate_core::ApprovalRequest,
+) -> Result<()> {
+    use orchestrate_core::PipelineExecutor;
+    use std::sync::Arc;
+
+    if !approval.status.is_terminal() {
+        return Ok(());
+    }
+
+    println!("Resuming pipeline run {}...", approval.run_id);
+    let executor = PipelineExecutor::new(Arc::new(db.clone())).with_notifications_from_env();
+    if let Err(e) = executor.resume_after_approval(approval).await {
+        println!("  {}", e);
+    }
+
+    if let Some(run) = db.get_pipeline_run(approval.run_id).await? {
+        println!("  Run status: {}", run.status.as_str());
+    }
 
     Ok(())
 }
@@ -8089,6 +11309,53 @@ async fn handle_approval_delegate(db: &Database, id: i64, to: &str) -> Result<()
     Ok(())
 }
 
+// ==================== Secret Handlers ====================
+
+async fn handle_secret_set(db: &Database, name: &str, value: Option<String>) -> Result<()> {
+    use orchestrate_core::SecretVault;
+
+    let value = match value {
+        Some(value) => value,
+        None => {
+            let mut input = String::new();
+            std::io::stdin().read_line(&mut input)?;
+            input.trim_end_matches(['\r', '\n']).to_string()
+        }
+    };
+    if value.is_empty() {
+        anyhow::bail!("Secret value cannot be empty");
+    }
+
+    SecretVault::from_env(db.clone()).set(name, &value).await?;
+    println!("Secret '{}' stored", name);
+
+    Ok(())
+}
+
+async fn handle_secret_list(db: &Database) -> Result<()> {
+    let names = db.list_secret_names().await?;
+
+    if names.is_empty() {
+        println!("No secrets found");
+        return Ok(());
+    }
+
+    for name in names {
+        println!("{}", name);
+    }
+
+    Ok(())
+}
+
+async fn handle_secret_delete(db: &Database, name: &str) -> Result<()> {
+    if !db.delete_secret(name).await? {
+        anyhow::bail!("Secret not found: {}", name);
+    }
+    println!("Secret '{}' deleted", name);
+
+    Ok(())
+}
+
 // ==================== Feedback Handlers ====================
 
 async fn handle_feedback_add(
@@ -8365,7 +11632,23 @@ async fn handle_epic_auto_process(
         model: Some(model.to_string()),
         ..Default::default()
     };
-    let session = AutonomousSession::new().with_config(config);
+    let mut session = AutonomousSession::new().with_config(config);
+    for (i, item) in plan.work_queue.iter().enumerate() {
+        session.add_work_item(orchestrate_core::WorkItem {
+            id: item.full_id.clone(),
+            work_type: orchestrate_core::WorkItemType::Story,
+            epic_id: item.epic_id.clone(),
+            story_id: Some(item.story_id.clone()),
+            priority: i as u32,
+            dependencies: item.dependencies.clone(),
+            metadata: serde_json::Value::Null,
+        });
+    }
+    session.set_goal(format!(
+        "Complete {} stories across {} epic(s)",
+        plan.total_stories,
+        plan.epics.len()
+    ));
 
     // Save session
     db.create_autonomous_session(&session).await?;
@@ -8404,6 +11687,14 @@ async fn handle_epic_auto_status(db: &Database, detailed: bool) -> Result<()> {
         if let Some(story) = &session.current_story_id {
             println!("  Current Story: {}", story);
         }
+        if let Some(plan) = &session.plan {
+            println!("  Goal: {}", plan.goal);
+            println!(
+                "  Plan: revision {}, {:.0}% complete",
+                plan.revision,
+                plan.progress() * 100.0
+            );
+        }
 
         if detailed {
             println!();
@@ -8419,6 +11710,13 @@ async fn handle_epic_auto_status(db: &Database, detailed: bool) -> Result<()> {
                 let status = if item.success { "success" } else { "failed" };
                 println!("    - {} ({})", item.id, status);
             }
+            if let Some(plan) = &session.plan {
+                println!();
+                println!("  Plan Steps:");
+                for step in &plan.steps {
+                    println!("    - {} ({})", step.work_item_id, step.status);
+                }
+            }
         }
         println!();
     }
@@ -8506,6 +11804,96 @@ async fn handle_epic_auto_stop(db: &Database, force: bool) -> Result<()> {
     Ok(())
 }
 
+async fn handle_epic_stuck_config(db: &Database, action: StuckConfigAction) -> Result<()> {
+    use orchestrate_core::StuckType;
+
+    match action {
+        StuckConfigAction::List => {
+            let configs = db.list_stuck_detection_configs().await?;
+            if configs.is_empty() {
+                println!("No stuck detection configs stored (built-in defaults apply)");
+                return Ok(());
+            }
+            println!("{:<28} DISABLED DETECTORS", "AGENT TYPE");
+            println!("{}", "-".repeat(60));
+            for (agent_type, config) in configs {
+                let disabled: Vec<&str> =
+                    config.disabled_detectors.iter().map(|d| d.as_str()).collect();
+                println!(
+                    "{:<28} {}",
+                    agent_type,
+                    if disabled.is_empty() { "-".to_string() } else { disabled.join(", ") }
+                );
+            }
+        }
+        StuckConfigAction::Show { agent_type } => {
+            let stored = db.get_stuck_detection_config(&agent_type).await?.is_some();
+            let config = db.resolve_stuck_detection_config(&agent_type).await?;
+            println!("Agent type: {}{}", agent_type, if stored { "" } else { " (inherited)" });
+            println!();
+            println!("Detectors:");
+            for detector in StuckType::all() {
+                println!(
+                    "  {:<18} {}",
+                    detector.as_str(),
+                    if config.is_enabled(*detector) { "enabled" } else { "disabled" }
+                );
+            }
+            println!();
+            println!("Thresholds:");
+            println!("  turn_warning_threshold:         {}", config.turn_warning_threshold);
+            println!("  token_warning_threshold:        {}", config.token_warning_threshold);
+            println!("  no_progress_turn_threshold:     {}", config.no_progress_turn_threshold);
+            println!("  no_file_changes_turn_threshold: {}", config.no_file_changes_turn_threshold);
+            println!("  token_burn_threshold:           {}", config.token_burn_threshold);
+            println!("  error_loop_threshold:           {}", config.error_loop_threshold);
+            println!("  rate_limit_stall_minutes:       {}", config.rate_limit_stall_minutes);
+            println!("  ci_timeout_minutes:             {}", config.ci_timeout_minutes);
+            println!("  review_delay_minutes:           {}", config.review_delay_minutes);
+        }
+        StuckConfigAction::Set { agent_type, param, value } => {
+            let mut config = db.resolve_stuck_detection_config(&agent_type).await?;
+            config.set_param(&param, &value)?;
+            db.upsert_stuck_detection_config(&agent_type, &config).await?;
+            println!("Set {} = {} for {}", param, value, agent_type);
+        }
+        StuckConfigAction::Enable { agent_type, detector } => {
+            set_stuck_detector_enabled(db, &agent_type, &detector, true).await?;
+        }
+        StuckConfigAction::Disable { agent_type, detector } => {
+            set_stuck_detector_enabled(db, &agent_type, &detector, false).await?;
+        }
+        StuckConfigAction::Reset { agent_type } => {
+            if db.delete_stuck_detection_config(&agent_type).await? {
+                println!("Stuck detection config reset for {}", agent_type);
+            } else {
+                println!("No stored stuck detection config for {}", agent_type);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn set_stuck_detector_enabled(
+    db: &Database,
+    agent_type: &str,
+    detector: &str,
+    enabled: bool,
+) -> Result<()> {
+    let detector: orchestrate_core::StuckType = detector.parse()?;
+    let mut config = db.resolve_stuck_detection_config(agent_type).await?;
+    config.set_enabled(detector, enabled);
+    db.upsert_stuck_detection_config(agent_type, &config).await?;
+    println!(
+        "{} detector {} for {}",
+        if enabled { "Enabled" } else { "Disabled" },
+        detector,
+        agent_type
+    );
+    Ok(())
+}
+
 async fn handle_epic_stuck_agents(db: &Database) -> Result<()> {
     use orchestrate_core::AgentState;
 
@@ -0,0 +1,24 @@
+//! Tests for PR CLI commands
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_pr_merge_rejects_unsupported_provider() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+
+    let mut cmd = Command::cargo_bin("orchestrate").unwrap();
+    cmd.arg("--db-path").arg(db_path.to_str().unwrap()).args([
+        "pr",
+        "merge",
+        "42",
+        "--repo",
+        "https://git.example.com/team/app.git",
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsupported provider"));
+}
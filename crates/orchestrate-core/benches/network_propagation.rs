@@ -0,0 +1,144 @@
+//! Benchmarks for state propagation through the agent network
+//!
+//! Each iteration builds a fresh network of 100+ agents and completes the
+//! root agent, measuring how long it takes for the change to reach every
+//! dependent. The handler simulates per-agent work so that concurrent
+//! delivery across independent subgraphs shows up as throughput.
+//!
+//! The `network_propagation_sequential` group runs the same networks with a
+//! handler that only lets one propagation through at a time, as a baseline
+//! for delivering to every dependent in turn.
+
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use orchestrate_core::network::{
+    AgentId, NetworkCoordinator, PropagationEvent, PropagationHandler, StateGraph,
+    StateGraphBuilder, StatePropagation, StateTransition,
+};
+use orchestrate_core::{AgentState, AgentType};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Simulates an agent reacting to a propagated state change
+#[derive(Default)]
+struct SimulatedWork {
+    /// Held while reacting when set, so propagations are handled one at a time
+    sequential: Option<tokio::sync::Mutex<()>>,
+}
+
+impl SimulatedWork {
+    fn sequential() -> Self {
+        Self {
+            sequential: Some(tokio::sync::Mutex::new(())),
+        }
+    }
+}
+
+#[async_trait]
+impl PropagationHandler for SimulatedWork {
+    async fn on_propagation(&self, _source: AgentId, _target: AgentId, _event: &PropagationEvent) {
+        let _turn = match &self.sequential {
+            Some(lock) => Some(lock.lock().await),
+            None => None,
+        };
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+}
+
+fn propagating_state_graph() -> StateGraph {
+    StateGraphBuilder::new(AgentState::Created)
+        .transition(AgentState::Created, AgentState::Initializing)
+        .transition(AgentState::Initializing, AgentState::Running)
+        .conditional_transition(
+            StateTransition::new(AgentState::Running, AgentState::Completed).propagate(
+                StatePropagation::signal(
+                    AgentType::CodeReviewer,
+                    PropagationEvent::DependencyCompleted,
+                ),
+            ),
+        )
+        .terminal(AgentState::Completed)
+        .build()
+}
+
+/// Build a network of `subgraphs` independent chains of `depth` reviewers,
+/// each hanging off a single running root agent
+async fn build_network(
+    subgraphs: usize,
+    depth: usize,
+    handler: Arc<SimulatedWork>,
+) -> (NetworkCoordinator, AgentId) {
+    let coordinator = NetworkCoordinator::with_defaults()
+        .with_state_graph(propagating_state_graph())
+        .with_propagation_handler(handler);
+
+    let root = AgentId::new();
+    coordinator
+        .register_agent(root, AgentType::StoryDeveloper, AgentState::Created)
+        .await
+        .unwrap();
+    for state in [AgentState::Initializing, AgentState::Running] {
+        coordinator.transition_state(root, state, None).await.unwrap();
+    }
+
+    for _ in 0..subgraphs {
+        let mut parent = root;
+        for _ in 0..depth {
+            let id = AgentId::new();
+            coordinator
+                .register_agent(id, AgentType::CodeReviewer, AgentState::Created)
+                .await
+                .unwrap();
+            coordinator.add_dependency(id, parent).await.unwrap();
+            parent = id;
+        }
+    }
+
+    (coordinator, root)
+}
+
+fn bench_propagation(c: &mut Criterion) {
+    bench_group(c, "network_propagation", SimulatedWork::default);
+}
+
+fn bench_sequential_propagation(c: &mut Criterion) {
+    bench_group(
+        c,
+        "network_propagation_sequential",
+        SimulatedWork::sequential,
+    );
+}
+
+fn bench_group(c: &mut Criterion, name: &str, handler: fn() -> SimulatedWork) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+
+    // (independent subgraphs, chain depth): 100 and 500 dependents
+    for (subgraphs, depth) in [(100, 1), (20, 5), (10, 10), (50, 10)] {
+        let nodes = subgraphs * depth;
+        group.bench_with_input(
+            BenchmarkId::new(format!("{}x{}", subgraphs, depth), nodes),
+            &(subgraphs, depth),
+            |b, &(subgraphs, depth)| {
+                b.iter_batched(
+                    || runtime.block_on(build_network(subgraphs, depth, Arc::new(handler()))),
+                    |(coordinator, root)| {
+                        runtime.block_on(async {
+                            coordinator
+                                .transition_state(root, AgentState::Completed, None)
+                                .await
+                                .unwrap();
+                        })
+                    },
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_propagation, bench_sequential_propagation);
+criterion_main!(benches);
@@ -2,8 +2,13 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
 use uuid::Uuid;
 
+/// Longest label key or value
+const MAX_LABEL_LENGTH: usize = 63;
+
 /// Agent states in the lifecycle
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT", rename_all = "snake_case")]
@@ -307,6 +312,13 @@ pub struct Agent {
     pub id: Uuid,
     /// Type of agent
     pub agent_type: AgentType,
+    /// Name of the user-defined agent type this agent was spawned as, if any
+    /// (`agent_type` then holds the definition's base type)
+    #[serde(default)]
+    pub custom_type: Option<String>,
+    /// System prompt that supersedes the agent type's template for this run
+    #[serde(default)]
+    pub system_prompt: Option<String>,
     /// Current state
     pub state: AgentState,
     /// Task description
@@ -319,8 +331,14 @@ pub struct Agent {
     pub parent_agent_id: Option<Uuid>,
     /// Associated worktree ID
     pub worktree_id: Option<String>,
+    /// Labels for selecting groups of agents, e.g. `team=web`
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
     /// Error message if failed
     pub error_message: Option<String>,
+    /// OpenTelemetry trace ID of the latest run, when spans are exported
+    #[serde(default)]
+    pub trace_id: Option<String>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -336,13 +354,17 @@ impl Agent {
         Self {
             id: Uuid::new_v4(),
             agent_type,
+            custom_type: None,
+            system_prompt: None,
             state: AgentState::Created,
             task: task.into(),
             context: AgentContext::default(),
             session_id: None,
             parent_agent_id: None,
             worktree_id: None,
+            labels: BTreeMap::new(),
             error_message: None,
+            trace_id: None,
             created_at: now,
             updated_at: now,
             completed_at: None,
@@ -355,6 +377,26 @@ impl Agent {
         self
     }
 
+    /// Spawn as a user-defined agent type built on `agent_type`
+    pub fn with_custom_type(mut self, name: impl Into<String>) -> Self {
+        self.custom_type = Some(name.into());
+        self
+    }
+
+    /// Override the system prompt template for this agent
+    pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Name the agent's prompts are versioned under: the custom type if any,
+    /// otherwise the base type
+    pub fn prompt_type_name(&self) -> String {
+        self.custom_type
+            .clone()
+            .unwrap_or_else(|| self.agent_type.as_str().to_string())
+    }
+
     /// Set parent agent (for forking)
     pub fn with_parent(mut self, parent_id: Uuid) -> Self {
         self.parent_agent_id = Some(parent_id);
@@ -367,6 +409,12 @@ impl Agent {
         self
     }
 
+    /// Add a label, e.g. `team=web`
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
     /// Transition to a new state
     pub fn transition_to(&mut self, new_state: AgentState) -> crate::Result<()> {
         if !self.state.can_transition_to(new_state) {
@@ -393,6 +441,113 @@ impl Agent {
     }
 }
 
+/// Check label keys and values: 1-63 characters of letters, digits, `-`,
+/// `_`, `.`, or `/` (values may also be empty)
+pub fn validate_labels(labels: &BTreeMap<String, String>) -> crate::Result<()> {
+    let valid = |s: &str| {
+        s.len() <= MAX_LABEL_LENGTH
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+    };
+    for (key, value) in labels {
+        if key.is_empty() || !valid(key) {
+            return Err(crate::Error::Other(format!("Invalid label key: '{}'", key)));
+        }
+        if !valid(value) {
+            return Err(crate::Error::Other(format!(
+                "Invalid value of label '{}': '{}'",
+                key, value
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// A requirement of a [`LabelSelector`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelRequirement {
+    Equals(String, String),
+    NotEquals(String, String),
+    Exists(String),
+    NotExists(String),
+}
+
+impl LabelRequirement {
+    fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        match self {
+            Self::Equals(key, value) => labels.get(key) == Some(value),
+            Self::NotEquals(key, value) => labels.get(key) != Some(value),
+            Self::Exists(key) => labels.contains_key(key),
+            Self::NotExists(key) => !labels.contains_key(key),
+        }
+    }
+}
+
+/// Kubernetes-style equality selector over agent labels, e.g.
+/// `team=web,env!=prod,canary,!pinned`
+///
+/// Every requirement must match. An empty selector matches every agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelSelector {
+    pub requirements: Vec<LabelRequirement>,
+}
+
+impl LabelSelector {
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.requirements.iter().all(|r| r.matches(labels))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requirements.is_empty()
+    }
+}
+
+impl FromStr for LabelSelector {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let requirements = s
+            .split(',')
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .map(|requirement| {
+                let (parsed, key, value) = if let Some((key, value)) = requirement.split_once("!=")
+                {
+                    let (key, value) = (key.trim(), value.trim());
+                    (
+                        LabelRequirement::NotEquals(key.into(), value.into()),
+                        key,
+                        value,
+                    )
+                } else if let Some((key, value)) = requirement
+                    .split_once("==")
+                    .or_else(|| requirement.split_once('='))
+                {
+                    let (key, value) = (key.trim(), value.trim());
+                    (
+                        LabelRequirement::Equals(key.into(), value.into()),
+                        key,
+                        value,
+                    )
+                } else if let Some(key) = requirement.strip_prefix('!') {
+                    let key = key.trim();
+                    (LabelRequirement::NotExists(key.into()), key, "")
+                } else {
+                    (
+                        LabelRequirement::Exists(requirement.into()),
+                        requirement,
+                        "",
+                    )
+                };
+                validate_labels(&BTreeMap::from([(key.to_string(), value.to_string())]))?;
+                Ok(parsed)
+            })
+            .collect::<crate::Result<Vec<_>>>()
+            .map_err(|e| crate::Error::Other(format!("Invalid label selector '{}': {}", s, e)))?;
+        Ok(Self { requirements })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -565,6 +720,26 @@ mod tests {
         assert_eq!(agent.context.pr_number, Some(42));
     }
 
+    #[test]
+    fn test_agent_with_custom_type() {
+        let agent = Agent::new(AgentType::CodeReviewer, "Review plan")
+            .with_custom_type("terraform-reviewer");
+        assert_eq!(agent.agent_type, AgentType::CodeReviewer);
+        assert_eq!(agent.custom_type.as_deref(), Some("terraform-reviewer"));
+        assert_eq!(agent.prompt_type_name(), "terraform-reviewer");
+    }
+
+    #[test]
+    fn test_agent_with_system_prompt() {
+        let agent = Agent::new(AgentType::StoryDeveloper, "Task")
+            .with_system_prompt("You are a careful Rust developer.");
+        assert_eq!(
+            agent.system_prompt.as_deref(),
+            Some("You are a careful Rust developer.")
+        );
+        assert_eq!(agent.prompt_type_name(), "story_developer");
+    }
+
     #[test]
     fn test_agent_with_parent() {
         let parent_id = Uuid::new_v4();
@@ -646,4 +821,37 @@ mod tests {
         agent.transition_to(AgentState::Initializing).unwrap();
         assert!(agent.updated_at > initial_updated_at);
     }
+
+    // ==================== Label Tests ====================
+
+    #[test]
+    fn test_label_selector_matches() {
+        let agent = Agent::new(AgentType::StoryDeveloper, "Implement login")
+            .with_label("team", "web")
+            .with_label("env", "staging");
+
+        let matches = |selector: &str| {
+            selector
+                .parse::<LabelSelector>()
+                .unwrap()
+                .matches(&agent.labels)
+        };
+        assert!(matches("team=web"));
+        assert!(matches("team==web, env!=prod"));
+        assert!(matches("env,!pinned"));
+        assert!(matches(""));
+        assert!(!matches("team=web,env=prod"));
+        assert!(!matches("pinned"));
+        assert!(!matches("!team"));
+    }
+
+    #[test]
+    fn test_label_selector_rejects_invalid_labels() {
+        assert!("=web".parse::<LabelSelector>().is_err());
+        assert!("team=web app".parse::<LabelSelector>().is_err());
+        assert!("!".parse::<LabelSelector>().is_err());
+
+        let labels = BTreeMap::from([("team".to_string(), "a".repeat(64))]);
+        assert!(validate_labels(&labels).is_err());
+    }
 }
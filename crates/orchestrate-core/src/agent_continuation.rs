@@ -5,6 +5,10 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Key in a continuation's `context` holding compressed prior-continuation context
+pub const PRIOR_CONTEXT_KEY: &str = "prior_context";
 
 /// Reasons for continuing an agent
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -192,9 +196,64 @@ impl AgentContinuation {
         self
     }
 
+    /// Attach compressed context from earlier continuations of the same agent
+    ///
+    /// Only the prior continuations' own messages and outcomes are used, so
+    /// context does not compound across handoffs.
+    pub fn with_prior_context(
+        mut self,
+        prior: &[AgentContinuation],
+        compressor: &ContinuationCompressor,
+    ) -> Self {
+        let compressed = compressor.compress(prior, &self.message);
+        if compressed.is_empty() {
+            return self;
+        }
+
+        let value = match serde_json::to_value(&compressed) {
+            Ok(value) => value,
+            Err(_) => return self,
+        };
+        match &mut self.context {
+            serde_json::Value::Object(map) => {
+                map.insert(PRIOR_CONTEXT_KEY.to_string(), value);
+            }
+            serde_json::Value::Null => {
+                self.context = serde_json::json!({ PRIOR_CONTEXT_KEY: value });
+            }
+            other => {
+                self.context = serde_json::json!({ "data": other.take(), PRIOR_CONTEXT_KEY: value });
+            }
+        }
+        self
+    }
+
+    /// Get the compressed prior-continuation context, if attached
+    pub fn prior_context(&self) -> Option<CompressedContinuationContext> {
+        self.context
+            .get(PRIOR_CONTEXT_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
     /// Get the full message to send to the agent (including prefix)
     pub fn full_message(&self) -> String {
-        format!("{}{}", self.reason.message_prefix(), self.message)
+        match self.prior_context() {
+            Some(prior) if !prior.is_empty() => format!(
+                "{}{}\n\n{}",
+                self.reason.message_prefix(),
+                self.message,
+                prior.to_prompt()
+            ),
+            _ => format!("{}{}", self.reason.message_prefix(), self.message),
+        }
+    }
+
+    /// Whether this continuation ended without resolving its request
+    fn is_unresolved(&self) -> bool {
+        match &self.result {
+            Some(result) => !result.success,
+            None => self.status == ContinuationStatus::Failed,
+        }
     }
 
     /// Mark as executing
@@ -378,6 +437,194 @@ impl ContinuationBuilder {
     }
 }
 
+/// One piece of context retained from an earlier continuation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriorContextEntry {
+    /// Continuation the entry was taken from
+    pub continuation_id: i64,
+    /// Reason of that continuation
+    pub reason: ContinuationReason,
+    /// Retained text
+    pub text: String,
+    /// Relevance score used for ranking
+    pub relevance: f64,
+}
+
+/// Compact, relevance-ranked context carried from earlier continuations
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompressedContinuationContext {
+    /// Retained entries in chronological order
+    pub entries: Vec<PriorContextEntry>,
+    /// Number of earlier continuations considered
+    pub continuations_seen: usize,
+    /// Number of lower-relevance items left out to fit the budget
+    pub items_dropped: usize,
+    /// Estimated token count of the retained entries
+    pub estimated_tokens: u64,
+}
+
+impl CompressedContinuationContext {
+    /// Whether any context was retained
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Render the context for inclusion in the agent prompt
+    pub fn to_prompt(&self) -> String {
+        let mut prompt = format!(
+            "Context from {} previous continuation(s)",
+            self.continuations_seen
+        );
+        if self.items_dropped > 0 {
+            prompt.push_str(&format!(
+                " ({} lower-relevance item(s) omitted)",
+                self.items_dropped
+            ));
+        }
+        prompt.push_str(":\n");
+        for entry in &self.entries {
+            prompt.push_str(&format!("- [{}] {}\n", entry.reason.as_str(), entry.text));
+        }
+        prompt
+    }
+}
+
+/// Compresses a chain of continuations into a bounded context
+///
+/// Prior messages are split into items, de-duplicated, scored by keyword
+/// overlap with the new message (weighted highest), recency and whether they
+/// were left unresolved, then kept greedily until the token budget is spent.
+#[derive(Debug, Clone)]
+pub struct ContinuationCompressor {
+    /// Token budget for retained context
+    max_tokens: u64,
+    /// Per-step decay applied to older continuations (0.0 - 1.0)
+    recency_decay: f64,
+}
+
+impl Default for ContinuationCompressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContinuationCompressor {
+    /// Create a compressor with the default 1500 token budget
+    pub fn new() -> Self {
+        Self {
+            max_tokens: 1500,
+            recency_decay: 0.7,
+        }
+    }
+
+    /// Set the token budget
+    pub fn with_max_tokens(mut self, max_tokens: u64) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    /// Set the recency decay
+    pub fn with_recency_decay(mut self, decay: f64) -> Self {
+        self.recency_decay = decay.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Compress `prior` continuations relative to the next message
+    pub fn compress(
+        &self,
+        prior: &[AgentContinuation],
+        next_message: &str,
+    ) -> CompressedContinuationContext {
+        let mut chain: Vec<&AgentContinuation> = prior.iter().collect();
+        chain.sort_by_key(|c| (c.created_at, c.id));
+
+        let query = keywords(next_message);
+        let newest = chain.len().saturating_sub(1);
+
+        // Walk newest first so duplicates keep their most recent occurrence
+        let mut seen = HashSet::new();
+        let mut candidates = Vec::new();
+        for (position, continuation) in chain.iter().enumerate().rev() {
+            let recency = self.recency_decay.powi((newest - position) as i32);
+            let unresolved = if continuation.is_unresolved() { 0.25 } else { 0.0 };
+
+            for text in continuation_items(continuation) {
+                if !seen.insert(text.to_lowercase()) {
+                    continue;
+                }
+                let words = keywords(&text);
+                let overlap = if words.is_empty() {
+                    0.0
+                } else {
+                    words.intersection(&query).count() as f64 / words.len() as f64
+                };
+                candidates.push((
+                    position,
+                    PriorContextEntry {
+                        continuation_id: continuation.id,
+                        reason: continuation.reason,
+                        text,
+                        relevance: 2.0 * overlap + recency + unresolved,
+                    },
+                ));
+            }
+        }
+
+        candidates.sort_by(|a, b| b.1.relevance.total_cmp(&a.1.relevance));
+
+        let total = candidates.len();
+        let mut estimated_tokens = 0;
+        let mut kept = Vec::new();
+        for (position, entry) in candidates {
+            let tokens = estimate_tokens(&entry.text);
+            if estimated_tokens + tokens > self.max_tokens {
+                continue;
+            }
+            estimated_tokens += tokens;
+            kept.push((position, entry));
+        }
+        kept.sort_by_key(|(position, _)| *position);
+
+        CompressedContinuationContext {
+            items_dropped: total - kept.len(),
+            entries: kept.into_iter().map(|(_, entry)| entry).collect(),
+            continuations_seen: chain.len(),
+            estimated_tokens,
+        }
+    }
+}
+
+/// Split a continuation into individually rankable context items
+fn continuation_items(continuation: &AgentContinuation) -> Vec<String> {
+    let mut items: Vec<String> = continuation
+        .message
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    if let Some(summary) = continuation.result.as_ref().and_then(|r| r.summary.as_ref()) {
+        items.push(format!("Outcome: {}", summary));
+    }
+    if let Some(error) = &continuation.error_message {
+        items.push(format!("Error: {}", error));
+    }
+    items
+}
+
+fn keywords(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| word.len() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn estimate_tokens(text: &str) -> u64 {
+    // Rough estimate: ~4 characters per token for English text
+    (text.len() as f64 / 4.0).ceil() as u64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -609,6 +856,91 @@ mod tests {
         assert!(cont.duration().is_some());
     }
 
+    // ==================== ContinuationCompressor Tests ====================
+
+    fn prior(id: i64, reason: ContinuationReason, message: &str) -> AgentContinuation {
+        let mut cont = AgentContinuation::new("agent-123", reason, message);
+        cont.id = id;
+        cont.created_at = Utc::now() - chrono::Duration::minutes(100 - id);
+        cont
+    }
+
+    #[test]
+    fn test_compress_deduplicates_and_orders_chronologically() {
+        let chain = vec![
+            prior(1, ContinuationReason::ReviewFeedback, "- Rename parse_config\n- Add docs"),
+            prior(2, ContinuationReason::ReviewFeedback, "- Add docs\n- Handle empty input"),
+        ];
+
+        let compressed = ContinuationCompressor::new().compress(&chain, "Fix remaining review items");
+
+        assert_eq!(compressed.continuations_seen, 2);
+        assert_eq!(compressed.items_dropped, 0);
+        let texts: Vec<&str> = compressed.entries.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, vec!["- Rename parse_config", "- Add docs", "- Handle empty input"]);
+        // Duplicate is attributed to the most recent occurrence
+        assert_eq!(compressed.entries[1].continuation_id, 2);
+    }
+
+    #[test]
+    fn test_compress_respects_budget_and_ranks_by_relevance() {
+        let mut chain: Vec<AgentContinuation> = (1..=20)
+            .map(|i| {
+                prior(
+                    i,
+                    ContinuationReason::FixRequest,
+                    &format!("Unrelated cleanup item number {} in module_{}", i, i),
+                )
+            })
+            .collect();
+        chain[0].message = "Database migration test_rollback fails on sqlite".to_string();
+
+        let compressor = ContinuationCompressor::new().with_max_tokens(40);
+        let compressed =
+            compressor.compress(&chain, "test_rollback still fails after the migration change");
+
+        assert!(compressed.estimated_tokens <= 40);
+        assert!(compressed.items_dropped > 0);
+        assert!(compressed
+            .entries
+            .iter()
+            .any(|e| e.text.contains("test_rollback")));
+    }
+
+    #[test]
+    fn test_with_prior_context_does_not_compound() {
+        let first = prior(1, ContinuationReason::TestFailures, "- Test 'a' failed: panic");
+        let second = AgentContinuation::new("agent-123", ContinuationReason::Retry, "Try again")
+            .with_prior_context(std::slice::from_ref(&first), &ContinuationCompressor::new());
+        assert_eq!(second.prior_context().unwrap().entries.len(), 1);
+
+        let mut second = second;
+        second.id = 2;
+        let third = AgentContinuation::new("agent-123", ContinuationReason::Retry, "Once more")
+            .with_context(serde_json::json!({"pr": 42}))
+            .with_prior_context(&[first, second], &ContinuationCompressor::new());
+
+        assert_eq!(third.context["pr"], 42);
+        let context = third.prior_context().unwrap();
+        assert_eq!(context.continuations_seen, 2);
+        assert_eq!(context.entries.len(), 2);
+        assert!(third.full_message().contains("Context from 2 previous continuation(s)"));
+    }
+
+    #[test]
+    fn test_compress_includes_unresolved_outcomes() {
+        let mut failed = prior(1, ContinuationReason::FixRequest, "Fix flaky login test");
+        failed.fail("Timed out waiting for CI");
+
+        let compressed = ContinuationCompressor::new().compress(&[failed], "Fix CI");
+
+        assert!(compressed
+            .entries
+            .iter()
+            .any(|e| e.text == "Error: Timed out waiting for CI"));
+        assert!(ContinuationCompressor::new().compress(&[], "anything").is_empty());
+    }
+
     // ==================== ContinuationBuilder Tests ====================
 
     #[test]
@@ -0,0 +1,205 @@
+//! Structured Agent Event Log
+//!
+//! Typed per-agent event stream (state transitions, tool calls, recoveries,
+//! evaluations) kept separate from the message transcript so timelines can be
+//! built without parsing conversation content.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::AgentState;
+
+/// Types of agent events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentEventType {
+    /// Agent moved from one state to another
+    StateTransition,
+    /// Agent invoked a tool
+    ToolCall,
+    /// A recovery action was taken for the agent
+    Recovery,
+    /// The agent's work was evaluated
+    Evaluation,
+}
+
+impl AgentEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::StateTransition => "state_transition",
+            Self::ToolCall => "tool_call",
+            Self::Recovery => "recovery",
+            Self::Evaluation => "evaluation",
+        }
+    }
+}
+
+impl std::str::FromStr for AgentEventType {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "state_transition" => Ok(Self::StateTransition),
+            "tool_call" => Ok(Self::ToolCall),
+            "recovery" => Ok(Self::Recovery),
+            "evaluation" => Ok(Self::Evaluation),
+            _ => Err(crate::Error::Other(format!(
+                "Invalid agent event type: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for AgentEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A single entry in an agent's event log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentEvent {
+    pub id: i64,
+    pub agent_id: String,
+    pub session_id: Option<String>,
+    pub event_type: AgentEventType,
+    /// Short human-readable summary (e.g. "running -> completed", "Bash")
+    pub summary: String,
+    /// Event-specific payload
+    pub data: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AgentEvent {
+    pub fn new(
+        agent_id: impl Into<String>,
+        event_type: AgentEventType,
+        summary: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: 0,
+            agent_id: agent_id.into(),
+            session_id: None,
+            event_type,
+            summary: summary.into(),
+            data: serde_json::json!({}),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Event for a state machine transition
+    pub fn state_transition(agent_id: impl Into<String>, from: AgentState, to: AgentState) -> Self {
+        Self::new(
+            agent_id,
+            AgentEventType::StateTransition,
+            format!("{} -> {}", from.as_str(), to.as_str()),
+        )
+        .with_data(serde_json::json!({
+            "from": from.as_str(),
+            "to": to.as_str(),
+        }))
+    }
+
+    /// Event for a tool invocation and its outcome
+    pub fn tool_call(
+        agent_id: impl Into<String>,
+        tool_name: impl Into<String>,
+        is_error: bool,
+        duration_ms: u64,
+    ) -> Self {
+        let tool_name = tool_name.into();
+        Self::new(agent_id, AgentEventType::ToolCall, tool_name.clone()).with_data(
+            serde_json::json!({
+                "tool": tool_name,
+                "is_error": is_error,
+                "duration_ms": duration_ms,
+            }),
+        )
+    }
+
+    /// Event for a recovery attempt
+    pub fn recovery(agent_id: impl Into<String>, attempt: &crate::recovery::RecoveryAttempt) -> Self {
+        Self::new(
+            agent_id,
+            AgentEventType::Recovery,
+            format!("{} ({})", attempt.action_type, attempt.outcome),
+        )
+        .with_data(serde_json::json!({
+            "recovery_attempt_id": attempt.id,
+            "action_type": attempt.action_type.as_str(),
+            "outcome": attempt.outcome.as_str(),
+            "attempt_number": attempt.attempt_number,
+        }))
+    }
+
+    /// Event for a work evaluation
+    pub fn evaluation(
+        agent_id: impl Into<String>,
+        evaluation: &crate::stuck_detection::WorkEvaluation,
+    ) -> Self {
+        Self::new(
+            agent_id,
+            AgentEventType::Evaluation,
+            format!(
+                "{} ({})",
+                evaluation.evaluation_type.as_str(),
+                evaluation.status.as_str()
+            ),
+        )
+        .with_data(serde_json::json!({
+            "work_evaluation_id": evaluation.id,
+            "evaluation_type": evaluation.evaluation_type.as_str(),
+            "status": evaluation.status.as_str(),
+        }))
+    }
+
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = data;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_event_type_roundtrip() {
+        for t in [
+            AgentEventType::StateTransition,
+            AgentEventType::ToolCall,
+            AgentEventType::Recovery,
+            AgentEventType::Evaluation,
+        ] {
+            assert_eq!(AgentEventType::from_str(t.as_str()).unwrap(), t);
+        }
+        assert!(AgentEventType::from_str("bogus").is_err());
+    }
+
+    #[test]
+    fn test_state_transition_event() {
+        let event =
+            AgentEvent::state_transition("agent-1", AgentState::Running, AgentState::Completed);
+        assert_eq!(event.event_type, AgentEventType::StateTransition);
+        assert_eq!(event.summary, "running -> completed");
+        assert_eq!(event.data["from"], "running");
+        assert_eq!(event.data["to"], "completed");
+    }
+
+    #[test]
+    fn test_tool_call_event() {
+        let event = AgentEvent::tool_call("agent-1", "Bash", true, 42).with_session("sess-1");
+        assert_eq!(event.event_type, AgentEventType::ToolCall);
+        assert_eq!(event.summary, "Bash");
+        assert_eq!(event.data["is_error"], true);
+        assert_eq!(event.data["duration_ms"], 42);
+        assert_eq!(event.session_id.as_deref(), Some("sess-1"));
+    }
+}
@@ -0,0 +1,261 @@
+//! User-defined agent types
+//!
+//! Teams can declare their own agent types (e.g. `terraform-reviewer`) in a
+//! YAML file or the database instead of patching the built-in [`AgentType`]
+//! enum. Each definition is layered on top of a built-in base type, which
+//! supplies runtime behavior and defaults for anything left unspecified.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::{AgentType, Error, Result};
+
+/// Tools that may appear in a custom agent type's tool policy
+pub const KNOWN_TOOLS: &[&str] = &["Bash", "Read", "Write", "Edit", "Glob", "Grep", "Task"];
+
+/// A custom agent type definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentTypeDefinition {
+    #[serde(default)]
+    pub id: i64,
+    /// Unique name, e.g. "terraform-reviewer"
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Built-in type this definition extends
+    pub base_type: AgentType,
+    /// Default system prompt (replaces the base type's prompt file)
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Skills this agent type provides to the agent network
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Tool policy; empty means inherit the base type's tools
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub max_turns: Option<u32>,
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AgentTypeDefinition {
+    pub fn new(name: impl Into<String>, base_type: AgentType) -> Self {
+        let now = Utc::now();
+        Self {
+            id: 0,
+            name: name.into(),
+            description: None,
+            base_type,
+            system_prompt: None,
+            skills: Vec::new(),
+            allowed_tools: Vec::new(),
+            model: None,
+            max_turns: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    pub fn with_skills(mut self, skills: Vec<String>) -> Self {
+        self.skills = skills;
+        self
+    }
+
+    pub fn with_allowed_tools(mut self, tools: Vec<String>) -> Self {
+        self.allowed_tools = tools;
+        self
+    }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn with_max_turns(mut self, max_turns: u32) -> Self {
+        self.max_turns = Some(max_turns);
+        self
+    }
+
+    /// Validate the definition
+    pub fn validate(&self) -> Result<()> {
+        let valid_name = !self.name.is_empty()
+            && self.name.len() <= 64
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+            && self.name.starts_with(|c: char| c.is_ascii_lowercase());
+        if !valid_name {
+            return Err(Error::Config(format!(
+                "Invalid agent type name '{}': use lowercase letters, digits, '-' or '_'",
+                self.name
+            )));
+        }
+
+        if AgentType::from_str(&self.name.replace('-', "_")).is_ok() {
+            return Err(Error::Config(format!(
+                "Agent type name '{}' conflicts with a built-in agent type",
+                self.name
+            )));
+        }
+
+        if let Some(tool) = self
+            .allowed_tools
+            .iter()
+            .find(|t| !KNOWN_TOOLS.contains(&t.as_str()))
+        {
+            return Err(Error::Config(format!(
+                "Unknown tool '{}' in agent type '{}'",
+                tool, self.name
+            )));
+        }
+
+        if self.max_turns == Some(0) {
+            return Err(Error::Config(format!(
+                "max_turns for agent type '{}' must be greater than 0",
+                self.name
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Tools this agent type may use, falling back to the base type's tools
+    pub fn effective_allowed_tools(&self) -> Vec<&str> {
+        if self.allowed_tools.is_empty() {
+            self.base_type.allowed_tools()
+        } else {
+            self.allowed_tools.iter().map(String::as_str).collect()
+        }
+    }
+
+    pub fn effective_model(&self) -> &str {
+        self.model
+            .as_deref()
+            .unwrap_or_else(|| self.base_type.default_model())
+    }
+
+    pub fn effective_max_turns(&self) -> u32 {
+        self.max_turns
+            .unwrap_or_else(|| self.base_type.default_max_turns())
+    }
+}
+
+/// Top-level structure of an agent types YAML file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentTypesFile {
+    #[serde(default)]
+    pub agent_types: Vec<AgentTypeDefinition>,
+}
+
+impl AgentTypesFile {
+    /// Parse and validate agent type definitions from YAML
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let file: Self = serde_yaml::from_str(content)
+            .map_err(|e| Error::Config(format!("Invalid agent types file: {}", e)))?;
+
+        let mut seen = std::collections::HashSet::new();
+        for def in &file.agent_types {
+            def.validate()?;
+            if !seen.insert(def.name.as_str()) {
+                return Err(Error::Config(format!(
+                    "Duplicate agent type '{}'",
+                    def.name
+                )));
+            }
+        }
+
+        Ok(file)
+    }
+
+    /// Load agent type definitions from a YAML file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_yaml(&content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_agent_types_file() {
+        let yaml = r#"
+agent_types:
+  - name: terraform-reviewer
+    description: Reviews Terraform plans
+    base_type: code_reviewer
+    system_prompt: You review Terraform changes for drift and security issues.
+    skills: [terraform, security-review]
+    allowed_tools: [Read, Grep, Bash]
+    max_turns: 25
+"#;
+        let file = AgentTypesFile::from_yaml(yaml).unwrap();
+        assert_eq!(file.agent_types.len(), 1);
+
+        let def = &file.agent_types[0];
+        assert_eq!(def.name, "terraform-reviewer");
+        assert_eq!(def.base_type, AgentType::CodeReviewer);
+        assert_eq!(def.skills, vec!["terraform", "security-review"]);
+        assert_eq!(def.effective_allowed_tools(), vec!["Read", "Grep", "Bash"]);
+        assert_eq!(def.effective_max_turns(), 25);
+    }
+
+    #[test]
+    fn test_defaults_inherit_from_base_type() {
+        let def = AgentTypeDefinition::new("docs-checker", AgentType::Explorer);
+        assert_eq!(def.effective_allowed_tools(), AgentType::Explorer.allowed_tools());
+        assert_eq!(def.effective_model(), AgentType::Explorer.default_model());
+        assert_eq!(def.effective_max_turns(), 20);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_definitions() {
+        assert!(AgentTypeDefinition::new("Bad Name", AgentType::Explorer)
+            .validate()
+            .is_err());
+        assert!(AgentTypeDefinition::new("code-reviewer", AgentType::Explorer)
+            .validate()
+            .is_err());
+        assert!(AgentTypeDefinition::new("reviewer", AgentType::Explorer)
+            .with_allowed_tools(vec!["Deploy".to_string()])
+            .validate()
+            .is_err());
+        assert!(AgentTypeDefinition::new("reviewer", AgentType::Explorer)
+            .with_max_turns(0)
+            .validate()
+            .is_err());
+        assert!(AgentTypeDefinition::new("reviewer", AgentType::Explorer)
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_names_rejected() {
+        let yaml = r#"
+agent_types:
+  - name: dup
+    base_type: explorer
+  - name: dup
+    base_type: explorer
+"#;
+        assert!(AgentTypesFile::from_yaml(yaml).is_err());
+    }
+}
@@ -82,6 +82,10 @@ pub struct ApprovalRequest {
     pub timeout_action: Option<String>,
     /// When the approval times out
     pub timeout_at: Option<DateTime<Utc>>,
+    /// Approvers added on the first timeout (comma-separated list)
+    pub escalate_to: Option<String>,
+    /// When the approval was escalated
+    pub escalated_at: Option<DateTime<Utc>>,
     /// When the approval was resolved
     pub resolved_at: Option<DateTime<Utc>>,
     /// Created timestamp
@@ -115,11 +119,45 @@ impl ApprovalRequest {
             timeout_seconds,
             timeout_action,
             timeout_at,
+            escalate_to: None,
+            escalated_at: None,
             resolved_at: None,
             created_at,
         }
     }
 
+    /// Escalate to additional approvers instead of applying the timeout action
+    pub fn with_escalation(mut self, approvers: Vec<String>) -> Self {
+        self.escalate_to = (!approvers.is_empty()).then(|| approvers.join(","));
+        self
+    }
+
+    /// Check if a timeout should escalate rather than resolve the request
+    pub fn can_escalate(&self) -> bool {
+        self.escalated_at.is_none() && self.escalate_to.as_deref().is_some_and(|a| !a.is_empty())
+    }
+
+    /// Add the escalation approvers and restart the timeout
+    pub fn escalate(&mut self) {
+        let Some(escalate_to) = self.escalate_to.take() else {
+            return;
+        };
+
+        let mut approvers: Vec<&str> = self.required_approvers.split(',').collect();
+        for approver in escalate_to.split(',') {
+            if !approvers.contains(&approver) {
+                approvers.push(approver);
+            }
+        }
+        self.required_approvers = approvers.join(",");
+
+        let now = Utc::now();
+        self.escalated_at = Some(now);
+        self.timeout_at = self
+            .timeout_seconds
+            .map(|seconds| now + chrono::Duration::seconds(seconds));
+    }
+
     /// Check if the request has timed out
     pub fn has_timed_out(&self) -> bool {
         if let Some(timeout_at) = self.timeout_at {
@@ -407,6 +445,42 @@ mod tests {
         assert!(request.resolved_at.is_none()); // Delegation doesn't resolve
     }
 
+    #[test]
+    fn test_approval_request_escalate() {
+        let mut request = ApprovalRequest::new(
+            1,
+            2,
+            "user1@example.com".to_string(),
+            1,
+            Some(3600),
+            Some("reject".to_string()),
+        )
+        .with_escalation(vec![
+            "user1@example.com".to_string(),
+            "lead@example.com".to_string(),
+        ]);
+        assert!(request.can_escalate());
+
+        request.escalate();
+        assert_eq!(
+            request.required_approvers,
+            "user1@example.com,lead@example.com"
+        );
+        assert_eq!(request.status, ApprovalStatus::Pending);
+        assert!(request.escalated_at.is_some());
+        assert!(!request.has_timed_out());
+        assert!(!request.can_escalate());
+    }
+
+    #[test]
+    fn test_approval_request_without_escalation() {
+        let request = ApprovalRequest::new(1, 2, "user1@example.com".to_string(), 1, None, None)
+            .with_escalation(vec![]);
+
+        assert!(request.escalate_to.is_none());
+        assert!(!request.can_escalate());
+    }
+
     #[test]
     fn test_approval_decision_new() {
         let decision = ApprovalDecision::new(
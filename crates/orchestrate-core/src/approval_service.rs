@@ -205,11 +205,21 @@ impl ApprovalService {
     }
 
     /// Process timed out approvals
+    ///
+    /// A request with escalation approvers is escalated on its first timeout
+    /// and stays pending; otherwise the timeout action resolves it.
     pub async fn process_timeouts(&self) -> Result<Vec<ApprovalRequest>> {
         let timed_out = self.db.list_timed_out_approvals().await?;
         let mut processed = Vec::new();
 
         for mut request in timed_out {
+            if request.can_escalate() {
+                request.escalate();
+                self.db.update_approval_request(&request).await?;
+                processed.push(request);
+                continue;
+            }
+
             // Apply timeout action
             match request.timeout_action.as_deref() {
                 Some("approve") => {
@@ -568,6 +578,39 @@ mod tests {
         assert_eq!(processed[0].status, ApprovalStatus::Approved);
     }
 
+    #[tokio::test]
+    async fn test_process_timeouts_escalates_first() {
+        let db = Database::in_memory().await.unwrap();
+        let service = ApprovalService::new(db.clone());
+
+        let (_, run_id, stage_id) = setup_test_approval(&db).await;
+
+        // Expired timeout that stays expired after the escalation restarts it
+        let request = ApprovalRequest::new(
+            stage_id,
+            run_id,
+            "user@example.com".to_string(),
+            1,
+            Some(-10),
+            Some("reject".to_string()),
+        )
+        .with_escalation(vec!["lead@example.com".to_string()]);
+        db.create_approval_request(request).await.unwrap();
+
+        let processed = service.process_timeouts().await.unwrap();
+        assert_eq!(processed.len(), 1);
+        assert_eq!(processed[0].status, ApprovalStatus::Pending);
+        assert_eq!(
+            processed[0].required_approvers,
+            "user@example.com,lead@example.com"
+        );
+        assert!(processed[0].escalated_at.is_some());
+
+        let processed = service.process_timeouts().await.unwrap();
+        assert_eq!(processed.len(), 1);
+        assert_eq!(processed[0].status, ApprovalStatus::Rejected);
+    }
+
     #[tokio::test]
     async fn test_list_pending() {
         let db = Database::in_memory().await.unwrap();
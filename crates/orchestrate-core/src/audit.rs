@@ -229,7 +229,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "Audit log query not yet implemented - returns stub empty list"]
     async fn test_insert_and_query_audit_entry() {
         let db = Database::in_memory().await.unwrap();
 
@@ -256,7 +255,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "Audit log query not yet implemented - returns stub empty list"]
     async fn test_query_by_timerange() {
         let db = Database::in_memory().await.unwrap();
 
@@ -300,7 +298,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "Audit log query not yet implemented - returns stub empty list"]
     async fn test_query_by_actor_type() {
         let db = Database::in_memory().await.unwrap();
 
@@ -331,7 +328,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "Audit log query not yet implemented - returns stub empty list"]
     async fn test_query_by_resource() {
         let db = Database::in_memory().await.unwrap();
 
@@ -361,7 +357,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "Audit log query not yet implemented - returns stub empty list"]
     async fn test_query_with_pagination() {
         let db = Database::in_memory().await.unwrap();
 
@@ -393,7 +388,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "Audit log query not yet implemented - returns stub empty list"]
     async fn test_query_success_failure() {
         let db = Database::in_memory().await.unwrap();
 
@@ -469,7 +463,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "Audit log export depends on query not yet implemented"]
     async fn test_export_json() {
         let db = Database::in_memory().await.unwrap();
 
@@ -515,7 +508,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "Audit log query not yet implemented - retention test needs it"]
     async fn test_apply_retention_policy() {
         let db = Database::in_memory().await.unwrap();
 
@@ -555,7 +547,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "Audit log count not yet implemented - returns stub"]
     async fn test_count_audit_entries() {
         let db = Database::in_memory().await.unwrap();
 
@@ -581,7 +572,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "Audit log query not yet implemented - returns stub empty list"]
     async fn test_combined_filters() {
         let db = Database::in_memory().await.unwrap();
 
@@ -77,6 +77,9 @@ impl AutonomousSessionState {
             (Planning, Executing) | (Planning, Blocked) => true,
             (Executing, Reviewing) | (Executing, Blocked) => true,
             (Reviewing, PrCreation) | (Reviewing, Executing) | (Reviewing, Blocked) => true,
+
+            // Re-planning when progress diverges from the plan
+            (Executing, Planning) | (Reviewing, Planning) => true,
             (PrCreation, PrMonitoring) | (PrCreation, Blocked) => true,
             (PrMonitoring, PrFixing) | (PrMonitoring, PrMerging) | (PrMonitoring, Blocked) => true,
             (PrFixing, PrMonitoring) | (PrFixing, Blocked) => true,
@@ -332,6 +335,223 @@ pub struct CompletedItem {
     pub agent_id: Option<String>,
 }
 
+/// Status of a step in the session plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanStepStatus {
+    /// Not started yet
+    Pending,
+    /// Work item has been dequeued and is being worked on
+    InProgress,
+    /// Work item completed successfully
+    Completed,
+    /// Work item failed
+    Failed,
+}
+
+impl PlanStepStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::InProgress => "in_progress",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::fmt::Display for PlanStepStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A planned unit of work, tied to a work item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanStep {
+    /// Work item ID this step tracks
+    pub work_item_id: String,
+    /// Type of work
+    pub work_type: WorkItemType,
+    /// Current status
+    pub status: PlanStepStatus,
+}
+
+/// Default number of failed steps before a re-plan is triggered
+const DEFAULT_REPLAN_FAILURE_THRESHOLD: u32 = 2;
+
+fn default_replan_failure_threshold() -> u32 {
+    DEFAULT_REPLAN_FAILURE_THRESHOLD
+}
+
+/// The session's goal and the plan for reaching it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionPlan {
+    /// What the session is trying to achieve
+    pub goal: String,
+    /// Planned steps in execution order
+    pub steps: Vec<PlanStep>,
+    /// Plan revision, incremented on every re-plan
+    pub revision: u32,
+    /// Failed steps tolerated before the plan is considered diverged
+    #[serde(default = "default_replan_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Work items completed that were not part of the plan
+    #[serde(default)]
+    pub unplanned_items: Vec<String>,
+    /// Plan creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SessionPlan {
+    /// Create a plan for a goal covering the given work items
+    pub fn new(goal: impl Into<String>, items: &[WorkItem]) -> Self {
+        let now = Utc::now();
+        let mut items: Vec<&WorkItem> = items.iter().collect();
+        items.sort_by_key(|item| item.priority);
+
+        Self {
+            goal: goal.into(),
+            steps: items
+                .into_iter()
+                .map(|item| PlanStep {
+                    work_item_id: item.id.clone(),
+                    work_type: item.work_type,
+                    status: PlanStepStatus::Pending,
+                })
+                .collect(),
+            revision: 1,
+            failure_threshold: DEFAULT_REPLAN_FAILURE_THRESHOLD,
+            unplanned_items: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Set the number of failed steps tolerated before re-planning
+    pub fn with_failure_threshold(mut self, threshold: u32) -> Self {
+        self.failure_threshold = threshold.max(1);
+        self
+    }
+
+    /// Get a step by work item ID
+    pub fn step(&self, work_item_id: &str) -> Option<&PlanStep> {
+        self.steps.iter().find(|s| s.work_item_id == work_item_id)
+    }
+
+    /// Update a step's status, returning false if the item is not planned
+    pub fn update_step(&mut self, work_item_id: &str, status: PlanStepStatus) -> bool {
+        match self.steps.iter_mut().find(|s| s.work_item_id == work_item_id) {
+            Some(step) => {
+                step.status = status;
+                self.updated_at = Utc::now();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Count steps with the given status
+    pub fn count(&self, status: PlanStepStatus) -> usize {
+        self.steps.iter().filter(|s| s.status == status).count()
+    }
+
+    /// Fraction of planned steps completed (0.0 - 1.0)
+    pub fn progress(&self) -> f64 {
+        if self.steps.is_empty() {
+            return 0.0;
+        }
+        self.count(PlanStepStatus::Completed) as f64 / self.steps.len() as f64
+    }
+
+    /// Whether every planned step has completed
+    pub fn is_complete(&self) -> bool {
+        !self.steps.is_empty() && self.count(PlanStepStatus::Completed) == self.steps.len()
+    }
+
+    /// Compare actual progress against the plan
+    ///
+    /// `queued` holds the IDs of work items still in the session queue, so
+    /// planned steps that are neither queued nor started can be detected.
+    pub fn divergence(&self, queued: &[&str]) -> Option<PlanDivergence> {
+        let failed = self.count(PlanStepStatus::Failed) as u32;
+        if failed >= self.failure_threshold {
+            return Some(PlanDivergence::StepsFailed {
+                failed,
+                threshold: self.failure_threshold,
+            });
+        }
+
+        if !self.unplanned_items.is_empty() {
+            return Some(PlanDivergence::UnplannedWork {
+                items: self.unplanned_items.clone(),
+            });
+        }
+
+        let missing: Vec<String> = self
+            .steps
+            .iter()
+            .filter(|s| s.status == PlanStepStatus::Pending)
+            .filter(|s| !queued.contains(&s.work_item_id.as_str()))
+            .map(|s| s.work_item_id.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Some(PlanDivergence::StepsDropped { items: missing });
+        }
+
+        None
+    }
+
+    /// Build the next revision of the plan for the remaining work
+    ///
+    /// Completed steps are carried over; everything else is replaced by the
+    /// given work items.
+    pub fn revise(&self, items: &[WorkItem]) -> Self {
+        let mut next = Self::new(self.goal.clone(), items)
+            .with_failure_threshold(self.failure_threshold);
+        let completed = self
+            .steps
+            .iter()
+            .filter(|s| s.status == PlanStepStatus::Completed)
+            .cloned();
+        next.steps.splice(0..0, completed);
+        next.revision = self.revision + 1;
+        next.created_at = self.created_at;
+        next
+    }
+}
+
+/// Why actual progress no longer matches the session plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PlanDivergence {
+    /// Too many planned steps failed
+    StepsFailed { failed: u32, threshold: u32 },
+    /// Work was completed that the plan did not include
+    UnplannedWork { items: Vec<String> },
+    /// Pending steps are no longer in the work queue
+    StepsDropped { items: Vec<String> },
+}
+
+impl PlanDivergence {
+    /// Human-readable reason, recorded with the re-planning transition
+    pub fn reason(&self) -> String {
+        match self {
+            Self::StepsFailed { failed, threshold } => {
+                format!("{} planned steps failed (threshold {})", failed, threshold)
+            }
+            Self::UnplannedWork { items } => {
+                format!("Unplanned work completed: {}", items.join(", "))
+            }
+            Self::StepsDropped { items } => {
+                format!("Planned steps no longer queued: {}", items.join(", "))
+            }
+        }
+    }
+}
+
 /// Session configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionConfig {
@@ -411,6 +631,9 @@ pub struct AutonomousSession {
     pub blocked_reason: Option<String>,
     /// Reason for being paused
     pub pause_reason: Option<String>,
+    /// Goal and plan the session is working towards
+    #[serde(default)]
+    pub plan: Option<SessionPlan>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
 }
@@ -435,6 +658,7 @@ impl AutonomousSession {
             error_message: None,
             blocked_reason: None,
             pause_reason: None,
+            plan: None,
             created_at: now,
         }
     }
@@ -544,7 +768,19 @@ impl AutonomousSession {
     }
 
     /// Add a work item to the queue
+    ///
+    /// Items queued while planning become steps of the current plan.
     pub fn add_work_item(&mut self, item: WorkItem) {
+        if let Some(plan) = &mut self.plan {
+            if self.state == AutonomousSessionState::Planning && plan.step(&item.id).is_none() {
+                plan.steps.push(PlanStep {
+                    work_item_id: item.id.clone(),
+                    work_type: item.work_type,
+                    status: PlanStepStatus::Pending,
+                });
+                plan.updated_at = Utc::now();
+            }
+        }
         self.work_queue.push(item);
         self.updated_at = Utc::now();
     }
@@ -567,12 +803,27 @@ impl AutonomousSession {
 
         // swap_remove is O(1) - swaps with last element and pops
         let item = self.work_queue.swap_remove(min_idx);
+        if let Some(plan) = &mut self.plan {
+            plan.update_step(&item.id, PlanStepStatus::InProgress);
+        }
         self.updated_at = Utc::now();
         Some(item)
     }
 
     /// Record a completed work item
+    ///
+    /// Also updates the matching plan step, or notes the item as unplanned.
     pub fn record_completed(&mut self, item: CompletedItem) {
+        if let Some(plan) = &mut self.plan {
+            let status = if item.success {
+                PlanStepStatus::Completed
+            } else {
+                PlanStepStatus::Failed
+            };
+            if !plan.update_step(&item.id, status) {
+                plan.unplanned_items.push(item.id.clone());
+            }
+        }
         if item.success {
             if matches!(item.work_type, WorkItemType::Story) {
                 self.metrics.record_story_completed();
@@ -598,6 +849,38 @@ impl AutonomousSession {
     pub fn has_pending_work(&self) -> bool {
         !self.work_queue.is_empty()
     }
+
+    /// Set the session goal, planning the currently queued work towards it
+    pub fn set_goal(&mut self, goal: impl Into<String>) {
+        self.plan = Some(SessionPlan::new(goal, &self.work_queue));
+        self.updated_at = Utc::now();
+    }
+
+    /// Get the session goal, if one has been set
+    pub fn goal(&self) -> Option<&str> {
+        self.plan.as_ref().map(|p| p.goal.as_str())
+    }
+
+    /// Check whether actual progress has diverged from the plan
+    pub fn plan_divergence(&self) -> Option<PlanDivergence> {
+        let plan = self.plan.as_ref()?;
+        let queued: Vec<&str> = self.work_queue.iter().map(|i| i.id.as_str()).collect();
+        plan.divergence(&queued)
+    }
+
+    /// Start a re-planning turn
+    ///
+    /// Moves the session back to Planning and replaces the plan with a new
+    /// revision covering the work still queued. Returns the superseded plan.
+    pub fn replan(&mut self) -> crate::Result<SessionPlan> {
+        let previous = self
+            .plan
+            .clone()
+            .ok_or_else(|| crate::Error::Other("Session has no plan to revise".to_string()))?;
+        self.transition_to(AutonomousSessionState::Planning)?;
+        self.plan = Some(previous.revise(&self.work_queue));
+        Ok(previous)
+    }
 }
 
 impl Default for AutonomousSession {
@@ -627,6 +910,20 @@ pub struct SessionStateHistory {
     pub created_at: DateTime<Utc>,
 }
 
+impl SessionStateHistory {
+    /// Plan recorded with a re-planning transition, if any
+    pub fn plan(&self) -> Option<SessionPlan> {
+        self.metadata
+            .get("plan")
+            .and_then(|plan| serde_json::from_value(plan.clone()).ok())
+    }
+
+    /// Whether this entry records a re-planning turn
+    pub fn is_replan(&self) -> bool {
+        self.metadata.get("replan").and_then(|v| v.as_bool()) == Some(true)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1143,6 +1440,153 @@ mod tests {
         assert_eq!(WorkItemType::MergePr.as_str(), "merge_pr");
     }
 
+    // ==================== SessionPlan Tests ====================
+
+    fn story_item(id: &str, priority: u32) -> WorkItem {
+        WorkItem {
+            id: id.to_string(),
+            work_type: WorkItemType::Story,
+            epic_id: "epic-1".to_string(),
+            story_id: Some(id.to_string()),
+            priority,
+            dependencies: vec![],
+            metadata: serde_json::Value::Null,
+        }
+    }
+
+    fn completed(id: &str, success: bool) -> CompletedItem {
+        CompletedItem {
+            id: id.to_string(),
+            work_type: WorkItemType::Story,
+            epic_id: "epic-1".to_string(),
+            story_id: Some(id.to_string()),
+            success,
+            completed_at: Utc::now(),
+            error: None,
+            agent_id: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_tracks_work_items() {
+        let mut session = AutonomousSession::new();
+        session.add_work_item(story_item("story-2", 2));
+        session.add_work_item(story_item("story-1", 1));
+        session.set_goal("Ship epic-1");
+
+        let plan = session.plan.as_ref().unwrap();
+        assert_eq!(session.goal(), Some("Ship epic-1"));
+        assert_eq!(plan.revision, 1);
+        assert_eq!(plan.steps[0].work_item_id, "story-1");
+        assert_eq!(plan.steps[1].work_item_id, "story-2");
+
+        let item = session.pop_work_item().unwrap();
+        assert_eq!(
+            session.plan.as_ref().unwrap().step(&item.id).unwrap().status,
+            PlanStepStatus::InProgress
+        );
+
+        session.record_completed(completed(&item.id, true));
+        let plan = session.plan.as_ref().unwrap();
+        assert_eq!(plan.step("story-1").unwrap().status, PlanStepStatus::Completed);
+        assert_eq!(plan.progress(), 0.5);
+        assert!(!plan.is_complete());
+        assert!(session.plan_divergence().is_none());
+    }
+
+    #[test]
+    fn test_plan_divergence_on_failures() {
+        let mut session = AutonomousSession::new();
+        session.add_work_item(story_item("story-1", 1));
+        session.add_work_item(story_item("story-2", 2));
+        session.add_work_item(story_item("story-3", 3));
+        session.set_goal("Ship epic-1");
+
+        session.pop_work_item();
+        session.record_completed(completed("story-1", false));
+        assert!(session.plan_divergence().is_none());
+
+        session.pop_work_item();
+        session.record_completed(completed("story-2", false));
+        assert_eq!(
+            session.plan_divergence(),
+            Some(PlanDivergence::StepsFailed {
+                failed: 2,
+                threshold: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_plan_divergence_on_unplanned_and_dropped_work() {
+        let mut session = AutonomousSession::new();
+        session.add_work_item(story_item("story-1", 1));
+        session.set_goal("Ship epic-1");
+
+        session.record_completed(completed("hotfix", true));
+        let divergence = session.plan_divergence().unwrap();
+        assert_eq!(
+            divergence,
+            PlanDivergence::UnplannedWork {
+                items: vec!["hotfix".to_string()]
+            }
+        );
+        assert!(divergence.reason().contains("hotfix"));
+
+        let mut session = AutonomousSession::new();
+        session.add_work_item(story_item("story-1", 1));
+        session.set_goal("Ship epic-1");
+        session.work_queue.clear();
+        assert_eq!(
+            session.plan_divergence(),
+            Some(PlanDivergence::StepsDropped {
+                items: vec!["story-1".to_string()]
+            })
+        );
+    }
+
+    #[test]
+    fn test_session_replan() {
+        let mut session = AutonomousSession::new();
+        session.start().unwrap();
+        session.transition_to(AutonomousSessionState::Discovering).unwrap();
+        session.transition_to(AutonomousSessionState::Planning).unwrap();
+        session.add_work_item(story_item("story-1", 1));
+        session.add_work_item(story_item("story-2", 2));
+        session.add_work_item(story_item("story-3", 3));
+        session.set_goal("Ship epic-1");
+        session.transition_to(AutonomousSessionState::Executing).unwrap();
+
+        session.pop_work_item();
+        session.record_completed(completed("story-1", true));
+        session.pop_work_item();
+        session.record_completed(completed("story-2", false));
+        session.pop_work_item();
+        session.record_completed(completed("story-3", false));
+        assert!(session.plan_divergence().is_some());
+
+        let previous = session.replan().unwrap();
+        assert_eq!(previous.revision, 1);
+        assert_eq!(session.state, AutonomousSessionState::Planning);
+
+        // Work queued during the re-planning turn joins the new plan
+        session.add_work_item(story_item("story-2b", 1));
+        let plan = session.plan.as_ref().unwrap();
+        assert_eq!(plan.revision, 2);
+        assert_eq!(plan.goal, "Ship epic-1");
+        assert_eq!(plan.steps.len(), 2);
+        assert_eq!(plan.step("story-1").unwrap().status, PlanStepStatus::Completed);
+        assert_eq!(plan.step("story-2b").unwrap().status, PlanStepStatus::Pending);
+        assert!(session.plan_divergence().is_none());
+    }
+
+    #[test]
+    fn test_replan_without_plan_fails() {
+        let mut session = AutonomousSession::new();
+        assert!(session.replan().is_err());
+        assert_eq!(session.state, AutonomousSessionState::Idle);
+    }
+
     // ==================== Full Workflow Test ====================
 
     #[test]
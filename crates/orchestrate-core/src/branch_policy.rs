@@ -0,0 +1,324 @@
+//! Branch protection policy
+//!
+//! A declarative spec of branch protection and required checks, applied to
+//! every repository in multi-repo configuration. Comparing the spec with a
+//! branch's current protection reports drift setting by setting.
+//!
+//! ```yaml
+//! repositories:
+//!   - https://gitlab.com/team/worker
+//! rules:
+//!   - branch: main
+//!     required_checks: [build, test]
+//!     required_approvals: 1
+//!     dismiss_stale_reviews: true
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::{Error, Result};
+
+/// Branch protection policy for a set of repositories
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BranchPolicySpec {
+    /// Repository URLs to apply the policy to, in addition to the
+    /// repositories in multi-repo configuration
+    #[serde(default)]
+    pub repositories: Vec<String>,
+    pub rules: Vec<BranchRule>,
+}
+
+impl BranchPolicySpec {
+    /// Parse and validate a spec from YAML
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let spec: Self = serde_yaml::from_str(content)
+            .map_err(|e| Error::Config(format!("Invalid branch policy: {}", e)))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Load a spec from a YAML file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_yaml(&content)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.rules.is_empty() {
+            return Err(Error::Config(
+                "Branch policy must have at least one rule".to_string(),
+            ));
+        }
+        let mut seen = std::collections::HashSet::new();
+        for rule in &self.rules {
+            if rule.branch.trim().is_empty() {
+                return Err(Error::Config(
+                    "Branch rule branch cannot be empty".to_string(),
+                ));
+            }
+            if !seen.insert(rule.branch.as_str()) {
+                return Err(Error::Config(format!(
+                    "Duplicate branch rule '{}'",
+                    rule.branch
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Protection required on one branch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchRule {
+    pub branch: String,
+    #[serde(flatten)]
+    pub protection: BranchProtection,
+}
+
+/// Protection settings of a branch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BranchProtection {
+    /// Status checks that must pass before merging
+    pub required_checks: Vec<String>,
+    /// Require the branch to be up to date before merging
+    pub strict_checks: bool,
+    /// Approving reviews required before merging
+    pub required_approvals: u32,
+    /// Dismiss approvals when new commits are pushed
+    pub dismiss_stale_reviews: bool,
+    /// Require approval from code owners
+    pub require_code_owner_reviews: bool,
+    /// Apply the rules to administrators too
+    pub enforce_admins: bool,
+    pub allow_force_pushes: bool,
+    pub allow_deletions: bool,
+    pub require_linear_history: bool,
+}
+
+/// A single branch protection setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectionSetting {
+    RequiredChecks,
+    StrictChecks,
+    RequiredApprovals,
+    DismissStaleReviews,
+    RequireCodeOwnerReviews,
+    EnforceAdmins,
+    AllowForcePushes,
+    AllowDeletions,
+    RequireLinearHistory,
+}
+
+impl ProtectionSetting {
+    pub const ALL: [ProtectionSetting; 9] = [
+        Self::RequiredChecks,
+        Self::StrictChecks,
+        Self::RequiredApprovals,
+        Self::DismissStaleReviews,
+        Self::RequireCodeOwnerReviews,
+        Self::EnforceAdmins,
+        Self::AllowForcePushes,
+        Self::AllowDeletions,
+        Self::RequireLinearHistory,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RequiredChecks => "required_checks",
+            Self::StrictChecks => "strict_checks",
+            Self::RequiredApprovals => "required_approvals",
+            Self::DismissStaleReviews => "dismiss_stale_reviews",
+            Self::RequireCodeOwnerReviews => "require_code_owner_reviews",
+            Self::EnforceAdmins => "enforce_admins",
+            Self::AllowForcePushes => "allow_force_pushes",
+            Self::AllowDeletions => "allow_deletions",
+            Self::RequireLinearHistory => "require_linear_history",
+        }
+    }
+}
+
+/// A setting whose current value differs from the policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyDrift {
+    pub setting: ProtectionSetting,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl BranchProtection {
+    /// Settings of a branch without any protection
+    pub fn unprotected() -> Self {
+        Self {
+            allow_force_pushes: true,
+            allow_deletions: true,
+            ..Self::default()
+        }
+    }
+
+    /// A setting's value, for display
+    pub fn value(&self, setting: ProtectionSetting) -> String {
+        match setting {
+            ProtectionSetting::RequiredChecks => {
+                let mut checks = self.required_checks.clone();
+                checks.sort();
+                checks.dedup();
+                format!("[{}]", checks.join(", "))
+            }
+            ProtectionSetting::StrictChecks => self.strict_checks.to_string(),
+            ProtectionSetting::RequiredApprovals => self.required_approvals.to_string(),
+            ProtectionSetting::DismissStaleReviews => self.dismiss_stale_reviews.to_string(),
+            ProtectionSetting::RequireCodeOwnerReviews => {
+                self.require_code_owner_reviews.to_string()
+            }
+            ProtectionSetting::EnforceAdmins => self.enforce_admins.to_string(),
+            ProtectionSetting::AllowForcePushes => self.allow_force_pushes.to_string(),
+            ProtectionSetting::AllowDeletions => self.allow_deletions.to_string(),
+            ProtectionSetting::RequireLinearHistory => self.require_linear_history.to_string(),
+        }
+    }
+
+    /// Settings of `actual` (`None` if unprotected) that differ from this
+    /// policy, among the settings a provider supports
+    pub fn drift(
+        &self,
+        actual: Option<&BranchProtection>,
+        supported: &[ProtectionSetting],
+    ) -> Vec<PolicyDrift> {
+        let unprotected = Self::unprotected();
+        let actual = actual.unwrap_or(&unprotected);
+        supported
+            .iter()
+            .filter_map(|&setting| {
+                let expected = self.value(setting);
+                let current = actual.value(setting);
+                (expected != current).then_some(PolicyDrift {
+                    setting,
+                    expected,
+                    actual: current,
+                })
+            })
+            .collect()
+    }
+
+    /// Settings this policy requires that a provider can't enforce
+    pub fn unsupported(&self, supported: &[ProtectionSetting]) -> Vec<ProtectionSetting> {
+        let unprotected = Self::unprotected();
+        ProtectionSetting::ALL
+            .into_iter()
+            .filter(|s| !supported.contains(s) && self.value(*s) != unprotected.value(*s))
+            .collect()
+    }
+}
+
+/// Outcome of applying a branch rule to one repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchPolicyResult {
+    pub repository: String,
+    pub branch: String,
+    /// Settings that differed from the policy before applying it
+    pub drift: Vec<PolicyDrift>,
+    /// Required settings the repository's provider can't enforce
+    pub unsupported: Vec<ProtectionSetting>,
+    /// Whether the policy was written to the provider
+    pub applied: bool,
+    pub error: Option<String>,
+}
+
+impl BranchPolicyResult {
+    pub fn new(repository: impl Into<String>, branch: impl Into<String>) -> Self {
+        Self {
+            repository: repository.into(),
+            branch: branch.into(),
+            drift: Vec::new(),
+            unsupported: Vec::new(),
+            applied: false,
+            error: None,
+        }
+    }
+
+    pub fn in_sync(&self) -> bool {
+        self.drift.is_empty() && self.error.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+repositories:
+  - https://gitlab.com/team/worker
+rules:
+  - branch: main
+    required_checks: [test, build]
+    required_approvals: 2
+    dismiss_stale_reviews: true
+"#;
+
+    #[test]
+    fn test_parse_spec() {
+        let spec = BranchPolicySpec::from_yaml(SPEC).unwrap();
+        assert_eq!(spec.repositories.len(), 1);
+        let rule = &spec.rules[0];
+        assert_eq!(rule.branch, "main");
+        assert_eq!(rule.protection.required_approvals, 2);
+        assert!(!rule.protection.allow_force_pushes);
+
+        assert!(BranchPolicySpec::from_yaml("rules: []").is_err());
+        assert!(
+            BranchPolicySpec::from_yaml("rules:\n  - branch: main\n  - branch: main\n").is_err()
+        );
+    }
+
+    #[test]
+    fn test_drift() {
+        let policy = BranchPolicySpec::from_yaml(SPEC).unwrap().rules[0]
+            .protection
+            .clone();
+
+        let actual = BranchProtection {
+            required_checks: vec!["build".to_string(), "test".to_string()],
+            required_approvals: 1,
+            dismiss_stale_reviews: true,
+            ..BranchProtection::default()
+        };
+        let drift = policy.drift(Some(&actual), &ProtectionSetting::ALL);
+        assert_eq!(
+            drift,
+            vec![PolicyDrift {
+                setting: ProtectionSetting::RequiredApprovals,
+                expected: "2".to_string(),
+                actual: "1".to_string(),
+            }]
+        );
+
+        let drift = policy.drift(None, &[ProtectionSetting::AllowForcePushes]);
+        assert_eq!(drift[0].actual, "true");
+        assert!(policy
+            .drift(Some(&policy), &ProtectionSetting::ALL)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_unsupported_settings() {
+        let policy = BranchPolicySpec::from_yaml(SPEC).unwrap().rules[0]
+            .protection
+            .clone();
+        let supported = [
+            ProtectionSetting::AllowForcePushes,
+            ProtectionSetting::AllowDeletions,
+            ProtectionSetting::RequireCodeOwnerReviews,
+        ];
+        assert_eq!(
+            policy.unsupported(&supported),
+            vec![
+                ProtectionSetting::RequiredChecks,
+                ProtectionSetting::RequiredApprovals,
+                ProtectionSetting::DismissStaleReviews,
+            ]
+        );
+    }
+}
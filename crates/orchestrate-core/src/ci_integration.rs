@@ -1,11 +1,20 @@
 //! CI/CD Platform Integration Module
 //!
 //! Types and utilities for integrating with CI/CD platforms like
-//! GitHub Actions, GitLab CI, and CircleCI.
+//! GitHub Actions, GitLab CI, and CircleCI, and a per-commit cache of
+//! check results kept current by webhooks.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+use crate::event_bus::BusEvent;
+use crate::pr_workflow::CiAggregateStatus;
+use crate::work_evaluation::{CiCheckResult, CiStatus};
 
 /// CI Provider type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -338,6 +347,184 @@ pub struct CiTriggerRequest {
     pub inputs: HashMap<String, String>,
 }
 
+/// Default time a cached commit status is trusted without a webhook
+pub const DEFAULT_CI_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Commits kept in the CI status cache before the oldest are evicted
+const MAX_CACHED_COMMITS: usize = 1000;
+
+static CI_STATUS_CACHE: OnceLock<CiStatusCache> = OnceLock::new();
+
+/// Status of a GitHub check from its `status` and `conclusion`
+pub fn check_status(status: &str, conclusion: Option<&str>) -> CiStatus {
+    match (status, conclusion) {
+        ("completed", Some("success" | "neutral" | "skipped")) => CiStatus::Passed,
+        ("completed", Some("timed_out")) => CiStatus::Timeout,
+        ("completed", Some("cancelled")) => CiStatus::Cancelled,
+        ("completed", Some("stale")) => CiStatus::Pending,
+        ("completed", _) => CiStatus::Failed,
+        ("in_progress", _) => CiStatus::Running,
+        _ => CiStatus::Pending,
+    }
+}
+
+/// Change in a commit's overall CI status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CiStatusTransition {
+    pub sha: String,
+    /// Previous overall status; `None` when first fetched
+    pub from: Option<CiStatus>,
+    pub to: CiStatus,
+    pub status: CiAggregateStatus,
+}
+
+struct CachedStatus {
+    checks: Vec<CiCheckResult>,
+    status: CiAggregateStatus,
+    fetched_at: Instant,
+    /// Set by a webhook saying the cached checks are out of date
+    invalidated: bool,
+}
+
+/// Check results per commit SHA
+///
+/// The PR workflow reads a commit's status from here instead of asking the
+/// CI provider on every loop. Check webhooks update cached checks in place
+/// or invalidate the commit, and entries older than the TTL are refetched
+/// in case a webhook was missed. Changes in overall status are broadcast
+/// to subscribers and published to the event bus as `ci.status_changed`.
+pub struct CiStatusCache {
+    entries: Mutex<HashMap<String, CachedStatus>>,
+    ttl: Duration,
+    transitions: broadcast::Sender<CiStatusTransition>,
+}
+
+impl CiStatusCache {
+    pub fn new(ttl: Duration) -> Self {
+        let (transitions, _) = broadcast::channel(256);
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl,
+            transitions,
+        }
+    }
+
+    /// Cached status of a commit, if still fresh
+    pub fn get(&self, sha: &str) -> Option<CiAggregateStatus> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(sha)
+            .filter(|e| !e.invalidated && e.fetched_at.elapsed() < self.ttl)
+            .map(|e| e.status.clone())
+    }
+
+    /// Cached status of a commit, fetching its checks on a miss
+    pub async fn get_or_fetch<F, Fut>(&self, sha: &str, fetch: F) -> crate::Result<CiAggregateStatus>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = crate::Result<Vec<CiCheckResult>>>,
+    {
+        if let Some(status) = self.get(sha) {
+            return Ok(status);
+        }
+        let checks = fetch().await?;
+        self.store(sha, checks);
+        Ok(self.get(sha).unwrap_or_else(|| CiAggregateStatus::from_checks(&[])))
+    }
+
+    /// Replace a commit's checks with freshly fetched results
+    pub fn store(&self, sha: &str, checks: Vec<CiCheckResult>) -> Option<CiStatusTransition> {
+        let status = CiAggregateStatus::from_checks(&checks);
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let from = entries.get(sha).map(|e| e.status.overall);
+        entries.insert(
+            sha.to_string(),
+            CachedStatus {
+                checks,
+                status: status.clone(),
+                fetched_at: Instant::now(),
+                invalidated: false,
+            },
+        );
+        if entries.len() > MAX_CACHED_COMMITS {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, e)| e.fetched_at)
+                .map(|(sha, _)| sha.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        drop(entries);
+        self.transition(sha, from, status)
+    }
+
+    /// Update one check of a cached commit from a webhook
+    ///
+    /// Commits that aren't cached are ignored; their checks are fetched in
+    /// full when the workflow next asks for them.
+    pub fn record_check(&self, sha: &str, check: CiCheckResult) -> Option<CiStatusTransition> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = entries.get_mut(sha)?;
+        match entry.checks.iter_mut().find(|c| c.name == check.name) {
+            Some(existing) => *existing = check,
+            None => entry.checks.push(check),
+        }
+        let from = entry.status.overall;
+        entry.status = CiAggregateStatus::from_checks(&entry.checks);
+        let status = entry.status.clone();
+        drop(entries);
+        self.transition(sha, Some(from), status)
+    }
+
+    /// Mark a commit's checks out of date, e.g. when a check suite is rerun
+    pub fn invalidate(&self, sha: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = entries.get_mut(sha) {
+            entry.invalidated = true;
+        }
+    }
+
+    /// Overall status changes, as they happen
+    pub fn subscribe(&self) -> broadcast::Receiver<CiStatusTransition> {
+        self.transitions.subscribe()
+    }
+
+    fn transition(
+        &self,
+        sha: &str,
+        from: Option<CiStatus>,
+        status: CiAggregateStatus,
+    ) -> Option<CiStatusTransition> {
+        if from == Some(status.overall) {
+            return None;
+        }
+        let transition = CiStatusTransition {
+            sha: sha.to_string(),
+            from,
+            to: status.overall,
+            status,
+        };
+        let _ = self.transitions.send(transition.clone());
+        crate::event_bus::emit(BusEvent::new(
+            "ci.status_changed",
+            serde_json::to_value(&transition).unwrap_or_default(),
+        ));
+        Some(transition)
+    }
+}
+
+impl Default for CiStatusCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CI_CACHE_TTL)
+    }
+}
+
+/// Process-wide CI status cache shared by the PR workflow and webhooks
+pub fn ci_status_cache() -> &'static CiStatusCache {
+    CI_STATUS_CACHE.get_or_init(CiStatusCache::default)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -425,4 +612,78 @@ mod tests {
         // Should not auto-fix flaky tests with high confidence
         assert!(!analysis.should_auto_fix());
     }
+
+    fn check(name: &str, status: CiStatus) -> CiCheckResult {
+        CiCheckResult::new(name, status)
+    }
+
+    #[test]
+    fn test_check_status() {
+        assert_eq!(check_status("completed", Some("success")), CiStatus::Passed);
+        assert_eq!(check_status("completed", Some("action_required")), CiStatus::Failed);
+        assert_eq!(check_status("completed", Some("timed_out")), CiStatus::Timeout);
+        assert_eq!(check_status("in_progress", None), CiStatus::Running);
+        assert_eq!(check_status("queued", None), CiStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_ci_status_cache_fetches_once() {
+        let cache = CiStatusCache::default();
+        let fetches = std::sync::atomic::AtomicU32::new(0);
+        let fetch = || async {
+            fetches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(vec![check("build", CiStatus::Running)])
+        };
+
+        let status = cache.get_or_fetch("abc123", fetch).await.unwrap();
+        assert_eq!(status.overall, CiStatus::Running);
+        let status = cache.get_or_fetch("abc123", fetch).await.unwrap();
+        assert_eq!(status.overall, CiStatus::Running);
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        cache.invalidate("abc123");
+        assert!(cache.get("abc123").is_none());
+        cache.get_or_fetch("abc123", fetch).await.unwrap();
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_ci_status_cache_ttl() {
+        let cache = CiStatusCache::new(Duration::ZERO);
+        cache.store("abc123", vec![check("build", CiStatus::Passed)]);
+        assert!(cache.get("abc123").is_none());
+    }
+
+    #[test]
+    fn test_ci_status_cache_transitions() {
+        let cache = CiStatusCache::default();
+        let mut transitions = cache.subscribe();
+
+        let first = cache
+            .store(
+                "abc123",
+                vec![check("build", CiStatus::Running), check("test", CiStatus::Passed)],
+            )
+            .unwrap();
+        assert_eq!(first.from, None);
+        assert_eq!(first.to, CiStatus::Running);
+
+        // Same overall status: no transition
+        assert!(cache
+            .record_check("abc123", check("test", CiStatus::Passed))
+            .is_none());
+
+        let done = cache
+            .record_check("abc123", check("build", CiStatus::Failed))
+            .unwrap();
+        assert_eq!(done.from, Some(CiStatus::Running));
+        assert_eq!(done.to, CiStatus::Failed);
+        assert_eq!(cache.get("abc123").unwrap().total, 2);
+
+        assert!(cache
+            .record_check("unknown", check("build", CiStatus::Passed))
+            .is_none());
+        assert_eq!(transitions.try_recv().unwrap().to, CiStatus::Running);
+        assert_eq!(transitions.try_recv().unwrap().to, CiStatus::Failed);
+    }
 }
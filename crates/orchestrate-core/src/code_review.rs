@@ -7,6 +7,7 @@
 //! - Parse review output for machine-readable verdict
 //! - Generate continuation messages from review feedback
 //! - Track review iterations and handle escalation
+//! - Reply to the PR review threads feedback came from
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -532,6 +533,49 @@ impl CodeReviewCoordinator {
         // Default to first preference
         self.config.preferred_reviewers.first().copied().unwrap_or(ReviewerType::Automated)
     }
+
+    /// Generate replies to the review threads behind a previous iteration's issues
+    ///
+    /// Issues the latest review no longer reports are answered as addressed and
+    /// their threads resolved; the rest get a reply noting they are still open.
+    /// Issues that did not come from a review thread are skipped.
+    pub fn generate_thread_replies(
+        &self,
+        previous_issues: &[ReviewIssue],
+        result: &ReviewResult,
+        commit_sha: Option<&str>,
+    ) -> Vec<ThreadReply> {
+        previous_issues
+            .iter()
+            .filter_map(|issue| {
+                let thread_id = issue.thread_id.clone()?;
+                let still_open = result.issues.iter().any(|i| i.same_issue(issue));
+                let body = if still_open {
+                    "Not addressed yet - this is still open in the latest review.".to_string()
+                } else if let Some(sha) = commit_sha {
+                    format!("Addressed in {sha}.")
+                } else {
+                    "Addressed.".to_string()
+                };
+                Some(ThreadReply {
+                    thread_id,
+                    body,
+                    resolve: !still_open,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reply to a reviewer's comment thread on the PR
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadReply {
+    /// Review thread to reply in
+    pub thread_id: String,
+    /// Reply text
+    pub body: String,
+    /// Resolve the thread after replying
+    pub resolve: bool,
 }
 
 impl Default for CodeReviewCoordinator {
@@ -891,4 +935,30 @@ mod tests {
         // Should not auto-approve when nitpick auto-approve is disabled
         assert!(!coordinator.can_auto_approve(&result));
     }
+
+    #[test]
+    fn test_generate_thread_replies() {
+        let coordinator = CodeReviewCoordinator::new();
+        let previous = vec![
+            ReviewIssue::new(ReviewIssueSeverity::High, "Missing validation")
+                .with_location("src/api.rs", 10)
+                .with_thread("thread-1"),
+            ReviewIssue::new(ReviewIssueSeverity::Medium, "Add tests")
+                .with_location("src/lib.rs", 3)
+                .with_thread("thread-2"),
+            ReviewIssue::new(ReviewIssueSeverity::Low, "Not from a thread"),
+        ];
+        let result = ReviewResult::new(ReviewVerdict::ChangesRequested).with_issues(vec![
+            ReviewIssue::new(ReviewIssueSeverity::Medium, "Tests still missing")
+                .with_location("src/lib.rs", 3),
+        ]);
+
+        let replies = coordinator.generate_thread_replies(&previous, &result, Some("abc1234"));
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies[0].thread_id, "thread-1");
+        assert_eq!(replies[0].body, "Addressed in abc1234.");
+        assert!(replies[0].resolve);
+        assert_eq!(replies[1].thread_id, "thread-2");
+        assert!(!replies[1].resolve);
+    }
 }
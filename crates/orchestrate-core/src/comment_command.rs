@@ -0,0 +1,201 @@
+//! Comment commands
+//!
+//! Maintainers can drive orchestrate from PR comments with a line such as
+//! `/orchestrate fix-ci`. Each command requires a minimum repository role,
+//! checked against the commenter before anything runs.
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// Prefix of a command line in a comment
+pub const COMMAND_PREFIX: &str = "/orchestrate";
+
+/// A command given in a PR comment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommentCommand {
+    /// Spawn an issue-fixer for the PR's failing CI checks
+    FixCi,
+    /// Rebase the PR onto its base branch, resolving conflicts
+    Rebase,
+    /// Run the code reviewer on the PR again
+    RerunReview,
+    /// Anything else after the prefix, answered with the command list
+    Unknown(String),
+}
+
+impl CommentCommand {
+    /// Every known command
+    pub const ALL: [CommentCommand; 3] = [Self::FixCi, Self::Rebase, Self::RerunReview];
+
+    /// The first command in a comment body, if any
+    ///
+    /// Commands must start a line; quoted lines (`> /orchestrate ...`) are
+    /// ignored so replies quoting a command don't run it again.
+    pub fn from_comment(body: &str) -> Option<Self> {
+        body.lines().find_map(|line| {
+            let rest = line.trim().strip_prefix(COMMAND_PREFIX)?;
+            if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+                return None;
+            }
+            let name = rest.split_whitespace().next().unwrap_or_default();
+            Some(
+                name.parse()
+                    .unwrap_or_else(|_| Self::Unknown(name.to_string())),
+            )
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::FixCi => "fix-ci",
+            Self::Rebase => "rebase",
+            Self::RerunReview => "rerun-review",
+            Self::Unknown(name) => name,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::FixCi => "Fix the failing CI checks",
+            Self::Rebase => "Rebase onto the base branch and resolve conflicts",
+            Self::RerunReview => "Review the PR again",
+            Self::Unknown(_) => "Unknown command",
+        }
+    }
+
+    /// Least repository role allowed to run the command
+    pub fn required_role(&self) -> RepoRole {
+        match self {
+            // Both push to the PR branch
+            Self::FixCi | Self::Rebase => RepoRole::Write,
+            Self::RerunReview => RepoRole::Triage,
+            Self::Unknown(_) => RepoRole::Read,
+        }
+    }
+
+    /// Markdown list of the commands, for help replies
+    pub fn help() -> String {
+        Self::ALL
+            .iter()
+            .map(|c| format!("- `{} {}`: {}", COMMAND_PREFIX, c.as_str(), c.description()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl FromStr for CommentCommand {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "fix-ci" => Ok(Self::FixCi),
+            "rebase" => Ok(Self::Rebase),
+            "rerun-review" => Ok(Self::RerunReview),
+            _ => Err(Error::Other(format!("Unknown comment command: {}", s))),
+        }
+    }
+}
+
+/// A user's role in a repository, from least to most privileged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepoRole {
+    None,
+    Read,
+    Triage,
+    Write,
+    Maintain,
+    Admin,
+}
+
+impl RepoRole {
+    /// Role from GitHub's collaborator permission (`role_name`)
+    pub fn from_permission(permission: &str) -> Self {
+        match permission.to_ascii_lowercase().as_str() {
+            "admin" => Self::Admin,
+            "maintain" => Self::Maintain,
+            "write" | "push" => Self::Write,
+            "triage" => Self::Triage,
+            "read" | "pull" => Self::Read,
+            _ => Self::None,
+        }
+    }
+
+    /// Role implied by a comment's `author_association`
+    ///
+    /// Only owners are known to be admins; members and collaborators may
+    /// have any role, so they are treated as readers unless the permission
+    /// API says more.
+    pub fn from_author_association(association: &str) -> Self {
+        match association {
+            "OWNER" => Self::Admin,
+            "MEMBER" | "COLLABORATOR" => Self::Read,
+            _ => Self::None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Read => "read",
+            Self::Triage => "triage",
+            Self::Write => "write",
+            Self::Maintain => "maintain",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Whether the role may run a command
+    pub fn allows(&self, command: &CommentCommand) -> bool {
+        *self >= command.required_role()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            CommentCommand::from_comment("/orchestrate fix-ci"),
+            Some(CommentCommand::FixCi)
+        );
+        assert_eq!(
+            CommentCommand::from_comment("Conflicts again.\r\n  /orchestrate rebase please\r\n"),
+            Some(CommentCommand::Rebase)
+        );
+        assert_eq!(
+            CommentCommand::from_comment("/orchestrate deploy"),
+            Some(CommentCommand::Unknown("deploy".to_string()))
+        );
+        assert_eq!(
+            CommentCommand::from_comment("/orchestrate"),
+            Some(CommentCommand::Unknown(String::new()))
+        );
+        assert_eq!(CommentCommand::from_comment("/orchestrated rebase"), None);
+        assert_eq!(CommentCommand::from_comment("> /orchestrate rebase"), None);
+        assert_eq!(CommentCommand::from_comment("Looks good"), None);
+    }
+
+    #[test]
+    fn test_role_permissions() {
+        assert!(RepoRole::from_permission("maintain").allows(&CommentCommand::Rebase));
+        assert!(!RepoRole::from_permission("triage").allows(&CommentCommand::FixCi));
+        assert!(RepoRole::from_permission("triage").allows(&CommentCommand::RerunReview));
+        assert!(
+            !RepoRole::from_author_association("CONTRIBUTOR").allows(&CommentCommand::RerunReview)
+        );
+        assert!(RepoRole::from_author_association("OWNER").allows(&CommentCommand::Rebase));
+    }
+
+    #[test]
+    fn test_help_lists_commands() {
+        let help = CommentCommand::help();
+        assert!(help.contains("`/orchestrate fix-ci`"));
+        assert!(help.contains("`/orchestrate rerun-review`"));
+    }
+}
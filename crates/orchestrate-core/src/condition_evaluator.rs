@@ -2,9 +2,12 @@
 //!
 //! This module provides condition evaluation for pipeline stages.
 //! Conditions determine whether a stage should be executed based on
-//! runtime context such as branch, paths, labels, and variables.
+//! runtime context such as branch, paths, labels, and variables, or on an
+//! expression over that context, the trigger payload, and earlier stages'
+//! results (see [`crate::condition_expression`]).
 
-use crate::{pipeline_parser::StageCondition, Result};
+use crate::{pipeline_parser::StageCondition, ConditionExpression, Result};
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use tracing::{debug, info};
 
@@ -19,6 +22,12 @@ pub struct ConditionContext {
     pub labels: Vec<String>,
     /// Runtime variables
     pub variables: HashMap<String, String>,
+    /// Event that triggered the run
+    pub trigger_event: Option<String>,
+    /// Payload of the triggering event
+    pub trigger_payload: Value,
+    /// Results of the run's stages (status, attempts), keyed by stage name
+    pub stage_results: HashMap<String, Value>,
 }
 
 impl ConditionContext {
@@ -50,6 +59,34 @@ impl ConditionContext {
         self.variables = variables;
         self
     }
+
+    /// Set the trigger event and its payload
+    pub fn with_trigger(mut self, event: Option<String>, payload: Value) -> Self {
+        self.trigger_event = event;
+        self.trigger_payload = payload;
+        self
+    }
+
+    /// Set the results of the run's stages
+    pub fn with_stage_results(mut self, stage_results: HashMap<String, Value>) -> Self {
+        self.stage_results = stage_results;
+        self
+    }
+
+    /// The context as seen by condition expressions
+    pub fn to_value(&self) -> Value {
+        json!({
+            "branch": self.branch,
+            "labels": self.labels,
+            "paths": self.changed_paths,
+            "variables": self.variables,
+            "trigger": {
+                "event": self.trigger_event,
+                "payload": self.trigger_payload,
+            },
+            "stages": self.stage_results,
+        })
+    }
 }
 
 /// Reason why a stage was skipped
@@ -65,6 +102,8 @@ pub enum SkipReason {
     VariableMismatch(String),
     /// Complex condition (and/or) not met
     ComplexCondition(String),
+    /// Condition expression evaluated to false
+    ExpressionFalse(String),
 }
 
 impl std::fmt::Display for SkipReason {
@@ -75,6 +114,7 @@ impl std::fmt::Display for SkipReason {
             SkipReason::LabelMismatch(msg) => write!(f, "Label condition not met: {}", msg),
             SkipReason::VariableMismatch(msg) => write!(f, "Variable condition not met: {}", msg),
             SkipReason::ComplexCondition(msg) => write!(f, "Complex condition not met: {}", msg),
+            SkipReason::ExpressionFalse(expr) => write!(f, "Expression is false: {}", expr),
         }
     }
 }
@@ -159,6 +199,16 @@ impl ConditionEvaluator {
             }
         }
 
+        // Check expression condition
+        if all_conditions_met {
+            if let Some(ref expr) = condition.expr {
+                if !ConditionExpression::parse(expr)?.evaluate(&context.to_value())? {
+                    all_conditions_met = false;
+                    skip_reason = Some(SkipReason::ExpressionFalse(expr.clone()));
+                }
+            }
+        }
+
         // Check OR condition (alternative)
         if !all_conditions_met {
             if let Some(ref or_condition) = condition.or {
@@ -234,7 +284,7 @@ impl ConditionEvaluator {
     }
 
     /// Simple glob pattern matching
-    fn matches_glob(&self, path: &str, pattern: &str) -> bool {
+    pub(crate) fn matches_glob(&self, path: &str, pattern: &str) -> bool {
         // Support simple glob patterns
         // ** matches any number of directories
         // * matches any characters in a path segment
@@ -325,6 +375,7 @@ mod tests {
             paths: None,
             labels: None,
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -342,6 +393,7 @@ mod tests {
             paths: None,
             labels: None,
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -359,6 +411,7 @@ mod tests {
             paths: None,
             labels: None,
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -376,6 +429,7 @@ mod tests {
             paths: None,
             labels: None,
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -396,6 +450,7 @@ mod tests {
             paths: Some(vec!["docs/README.md".to_string()]),
             labels: None,
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -416,6 +471,7 @@ mod tests {
             paths: Some(vec!["docs/**".to_string()]),
             labels: None,
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -433,6 +489,7 @@ mod tests {
             paths: Some(vec!["*.md".to_string()]),
             labels: None,
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -450,6 +507,7 @@ mod tests {
             paths: Some(vec!["docs/**".to_string()]),
             labels: None,
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -470,6 +528,7 @@ mod tests {
             paths: None,
             labels: Some(vec!["needs-full-test".to_string()]),
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -487,6 +546,7 @@ mod tests {
             paths: None,
             labels: Some(vec!["needs-full-test".to_string()]),
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -511,6 +571,7 @@ mod tests {
             paths: None,
             labels: None,
             variable: Some(required_vars),
+            expr: None,
             or: None,
         };
 
@@ -534,6 +595,7 @@ mod tests {
             paths: None,
             labels: None,
             variable: Some(required_vars),
+            expr: None,
             or: None,
         };
 
@@ -554,6 +616,7 @@ mod tests {
             paths: Some(vec!["docs/**".to_string()]),
             labels: Some(vec!["needs-docs-deploy".to_string()]),
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -574,6 +637,7 @@ mod tests {
             paths: Some(vec!["docs/**".to_string()]),
             labels: Some(vec!["needs-docs-deploy".to_string()]),
             variable: None,
+            expr: None,
             or: None,
         };
 
@@ -591,11 +655,13 @@ mod tests {
             paths: None,
             labels: Some(vec!["needs-full-test".to_string()]),
             variable: None,
+            expr: None,
             or: Some(Box::new(StageCondition {
                 branch: None,
                 paths: Some(vec!["src/core/**".to_string()]),
                 labels: None,
                 variable: None,
+                expr: None,
                 or: None,
             })),
         };
@@ -614,11 +680,13 @@ mod tests {
             paths: None,
             labels: Some(vec!["needs-full-test".to_string()]), // This will fail
             variable: None,
+            expr: None,
             or: Some(Box::new(StageCondition {
                 branch: None,
                 paths: Some(vec!["src/core/**".to_string()]), // This will succeed
                 labels: None,
                 variable: None,
+                expr: None,
                 or: None,
             })),
         };
@@ -637,11 +705,13 @@ mod tests {
             paths: None,
             labels: Some(vec!["needs-full-test".to_string()]), // Fails
             variable: None,
+            expr: None,
             or: Some(Box::new(StageCondition {
                 branch: None,
                 paths: Some(vec!["src/core/**".to_string()]), // Also fails
                 labels: None,
                 variable: None,
+                expr: None,
                 or: None,
             })),
         };
@@ -692,4 +762,34 @@ mod tests {
         let reason = SkipReason::PathMismatch("test".to_string());
         assert_eq!(reason.to_string(), "Path condition not met: test");
     }
+
+    #[test]
+    fn test_evaluate_expression() {
+        let evaluator = ConditionEvaluator::new();
+        let mut results = HashMap::new();
+        results.insert(
+            "test".to_string(),
+            json!({ "status": "failed", "attempts": 1 }),
+        );
+        let context = ConditionContext::new()
+            .with_branch("main".to_string())
+            .with_stage_results(results);
+
+        let condition = StageCondition {
+            branch: Some(vec!["main".to_string()]),
+            paths: None,
+            labels: None,
+            variable: None,
+            expr: Some("stages.test.status == 'succeeded'".to_string()),
+            or: None,
+        };
+
+        let result = evaluator.evaluate(&condition, &context).unwrap();
+        assert_eq!(
+            result,
+            EvaluationResult::Skip(SkipReason::ExpressionFalse(
+                "stages.test.status == 'succeeded'".to_string()
+            ))
+        );
+    }
 }
@@ -0,0 +1,762 @@
+//! Condition Expression Language
+//!
+//! This module parses and evaluates the `when.expr` expressions of pipeline
+//! stages, e.g.
+//!
+//! ```text
+//! branch == 'main' && stages.test.status == 'succeeded'
+//!     && (variables.replicas >= 2 || 'deploy' in labels)
+//! ```
+//!
+//! Expressions read from a context with these names:
+//!
+//! - `branch`, `labels`, `paths`: the triggering branch, labels, and changed paths
+//! - `variables.NAME`: pipeline and trigger variables
+//! - `trigger.event`, `trigger.payload.FIELD`: the trigger event and its payload
+//! - `stages.NAME.status`, `stages.NAME.attempts`: results of earlier stages
+//!
+//! Supported operators are `==`, `!=`, `<`, `<=`, `>`, `>=`, `in`, `&&` (`and`),
+//! `||` (`or`), and `!` (`not`), along with the functions `contains(a, b)`,
+//! `startsWith(s, prefix)`, `endsWith(s, suffix)`, and `changed(glob)`.
+
+use serde_json::Value;
+use std::fmt;
+
+use crate::{ConditionEvaluator, Error, Result};
+
+/// Names an expression may start a lookup from
+const ROOTS: [&str; 6] = [
+    "branch",
+    "labels",
+    "paths",
+    "variables",
+    "trigger",
+    "stages",
+];
+
+/// Functions with their number of arguments
+const FUNCTIONS: [(&str, usize); 4] = [
+    ("contains", 2),
+    ("startsWith", 2),
+    ("endsWith", 2),
+    ("changed", 1),
+];
+
+/// A parsed stage condition expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionExpression {
+    source: String,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Path(Vec<Segment>),
+    List(Vec<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, CompareOp, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+}
+
+impl CompareOp {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::In => "in",
+        }
+    }
+}
+
+impl ConditionExpression {
+    /// Parse an expression, reporting the column of the first syntax error
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source).map_err(|e| e.into_error(source))?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            end: source.chars().count() + 1,
+        };
+        let expr = parser.parse().map_err(|e| e.into_error(source))?;
+        Ok(Self {
+            source: source.to_string(),
+            expr,
+        })
+    }
+
+    /// The expression as written
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Names of the stages whose results the expression reads
+    pub fn stage_references(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        self.expr.visit(&mut |expr| {
+            if let Expr::Path(segments) = expr {
+                if let [Segment::Key(root), Segment::Key(name), ..] = segments.as_slice() {
+                    if root == "stages" && !names.contains(name) {
+                        names.push(name.clone());
+                    }
+                }
+            }
+        });
+        names
+    }
+
+    /// Evaluate the expression against a context built by
+    /// [`ConditionContext::to_value`](crate::ConditionContext::to_value)
+    pub fn evaluate(&self, context: &Value) -> Result<bool> {
+        let value = self.expr.eval(context).map_err(|e| {
+            Error::Other(format!(
+                "Condition '{}' could not be evaluated: {}",
+                self.source, e
+            ))
+        })?;
+        Ok(truthy(&value))
+    }
+}
+
+impl fmt::Display for ConditionExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+// ==================== Tokenizer ====================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Symbol(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "'{}'", name),
+            Token::Str(text) => write!(f, "string '{}'", text),
+            Token::Num(n) => write!(f, "number {}", n),
+            Token::Symbol(symbol) => write!(f, "'{}'", symbol),
+        }
+    }
+}
+
+/// A syntax error at a 1-based column
+struct SyntaxError {
+    column: usize,
+    message: String,
+}
+
+impl SyntaxError {
+    fn new(column: usize, message: impl Into<String>) -> Self {
+        Self {
+            column,
+            message: message.into(),
+        }
+    }
+
+    fn into_error(self, source: &str) -> Error {
+        Error::Other(format!(
+            "Invalid condition '{}': {} at column {}",
+            source, self.message, self.column
+        ))
+    }
+}
+
+const SYMBOLS: [&str; 15] = [
+    "==", "!=", "<=", ">=", "&&", "||", "<", ">", "!", "(", ")", "[", "]", ",", ".",
+];
+
+fn tokenize(source: &str) -> std::result::Result<Vec<(Token, usize)>, SyntaxError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '\'' || c == '"' {
+            let mut text = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return Err(SyntaxError::new(column, "unterminated string")),
+                    Some('\\') if chars.get(i + 1).is_some() => {
+                        text.push(chars[i + 1]);
+                        i += 2;
+                    }
+                    Some(&quote) if quote == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&other) => {
+                        text.push(other);
+                        i += 1;
+                    }
+                }
+            }
+            tokens.push((Token::Str(text), column));
+        } else if c.is_ascii_digit()
+            || (c == '-' && chars.get(i + 1).is_some_and(|next| next.is_ascii_digit()))
+        {
+            let start = i;
+            i += 1;
+            while i < chars.len()
+                && (chars[i].is_ascii_digit()
+                    || (chars[i] == '.'
+                        && chars.get(i + 1).is_some_and(|next| next.is_ascii_digit())))
+            {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let number = text
+                .parse()
+                .map_err(|_| SyntaxError::new(column, format!("invalid number '{}'", text)))?;
+            tokens.push((Token::Num(number), column));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-')
+            {
+                i += 1;
+            }
+            tokens.push((Token::Ident(chars[start..i].iter().collect()), column));
+        } else {
+            let rest: String = chars[i..].iter().take(2).collect();
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| rest.starts_with(**symbol))
+                .ok_or_else(|| SyntaxError::new(column, format!("unexpected character '{}'", c)))?;
+            i += symbol.len();
+            tokens.push((Token::Symbol(symbol), column));
+        }
+    }
+
+    Ok(tokens)
+}
+
+// ==================== Parser ====================
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    /// Column reported for errors at the end of the input
+    end: usize,
+}
+
+type ParseResult<T> = std::result::Result<T, SyntaxError>;
+
+impl Parser {
+    fn parse(&mut self) -> ParseResult<Expr> {
+        if self.tokens.is_empty() {
+            return Err(SyntaxError::new(1, "expression is empty"));
+        }
+        let expr = self.parse_or()?;
+        match self.tokens.get(self.position) {
+            None => Ok(expr),
+            Some((token, column)) => Err(SyntaxError::new(
+                *column,
+                format!("unexpected {} after the expression", token),
+            )),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn column(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map(|(_, column)| *column)
+            .unwrap_or(self.end)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .map(|(token, _)| token.clone());
+        self.position += 1;
+        token
+    }
+
+    /// Consume the next token if it is one of the given symbols or keywords
+    fn eat(&mut self, options: &[&'static str]) -> Option<&'static str> {
+        let text = match self.peek()? {
+            Token::Symbol(symbol) => *symbol,
+            Token::Ident(word) => word.as_str(),
+            _ => return None,
+        };
+        let matched = options.iter().copied().find(|option| *option == text)?;
+        self.position += 1;
+        Some(matched)
+    }
+
+    fn expect(&mut self, symbol: &'static str) -> ParseResult<()> {
+        if self.eat(&[symbol]).is_some() {
+            return Ok(());
+        }
+        let found = match self.peek() {
+            Some(token) => token.to_string(),
+            None => "end of input".to_string(),
+        };
+        Err(SyntaxError::new(
+            self.column(),
+            format!("expected '{}' but found {}", symbol, found),
+        ))
+    }
+
+    fn parse_or(&mut self) -> ParseResult<Expr> {
+        let mut left = self.parse_and()?;
+        while self.eat(&["||", "or"]).is_some() {
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> ParseResult<Expr> {
+        let mut left = self.parse_not()?;
+        while self.eat(&["&&", "and"]).is_some() {
+            let right = self.parse_not()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> ParseResult<Expr> {
+        if self.eat(&["!", "not"]).is_some() {
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> ParseResult<Expr> {
+        let left = self.parse_primary()?;
+        let op = match self.eat(&["==", "!=", "<=", ">=", "<", ">", "in"]) {
+            Some("==") => CompareOp::Eq,
+            Some("!=") => CompareOp::Ne,
+            Some("<=") => CompareOp::Le,
+            Some(">=") => CompareOp::Ge,
+            Some("<") => CompareOp::Lt,
+            Some(">") => CompareOp::Gt,
+            Some(_) => CompareOp::In,
+            None => return Ok(left),
+        };
+        let right = self.parse_primary()?;
+        Ok(Expr::Compare(Box::new(left), op, Box::new(right)))
+    }
+
+    fn parse_primary(&mut self) -> ParseResult<Expr> {
+        let column = self.column();
+        match self.next() {
+            Some(Token::Str(text)) => Ok(Expr::Literal(Value::String(text))),
+            Some(Token::Num(number)) => Ok(Expr::Literal(Value::from(number))),
+            Some(Token::Symbol("(")) => {
+                let expr = self.parse_or()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Some(Token::Symbol("[")) => {
+                let mut items = Vec::new();
+                if self.eat(&["]"]).is_none() {
+                    loop {
+                        items.push(self.parse_primary()?);
+                        if self.eat(&[","]).is_none() {
+                            break;
+                        }
+                    }
+                    self.expect("]")?;
+                }
+                Ok(Expr::List(items))
+            }
+            Some(Token::Ident(word)) => match word.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                _ if self.peek() == Some(&Token::Symbol("(")) => self.parse_call(word, column),
+                _ => self.parse_path(word, column),
+            },
+            Some(token) => Err(SyntaxError::new(
+                column,
+                format!("expected a value but found {}", token),
+            )),
+            None => Err(SyntaxError::new(
+                column,
+                "expected a value but found end of input",
+            )),
+        }
+    }
+
+    fn parse_call(&mut self, name: String, column: usize) -> ParseResult<Expr> {
+        let arity = FUNCTIONS
+            .iter()
+            .find(|(function, _)| *function == name)
+            .map(|(_, arity)| *arity)
+            .ok_or_else(|| SyntaxError::new(column, format!("unknown function '{}'", name)))?;
+
+        self.expect("(")?;
+        let mut args = Vec::new();
+        if self.eat(&[")"]).is_none() {
+            loop {
+                args.push(self.parse_or()?);
+                if self.eat(&[","]).is_none() {
+                    break;
+                }
+            }
+            self.expect(")")?;
+        }
+
+        if args.len() != arity {
+            return Err(SyntaxError::new(
+                column,
+                format!(
+                    "{}() takes {} argument(s) but was given {}",
+                    name,
+                    arity,
+                    args.len()
+                ),
+            ));
+        }
+        Ok(Expr::Call(name, args))
+    }
+
+    fn parse_path(&mut self, root: String, column: usize) -> ParseResult<Expr> {
+        if !ROOTS.contains(&root.as_str()) {
+            return Err(SyntaxError::new(
+                column,
+                format!(
+                    "unknown name '{}' (expected one of {})",
+                    root,
+                    ROOTS.join(", ")
+                ),
+            ));
+        }
+
+        let mut segments = vec![Segment::Key(root)];
+        loop {
+            if self.eat(&["."]).is_some() {
+                let column = self.column();
+                match self.next() {
+                    Some(Token::Ident(key)) => segments.push(Segment::Key(key)),
+                    Some(Token::Num(index)) if index >= 0.0 && index.fract() == 0.0 => {
+                        segments.push(Segment::Index(index as usize))
+                    }
+                    _ => return Err(SyntaxError::new(column, "expected a field name after '.'")),
+                }
+            } else if self.eat(&["["]).is_some() {
+                let column = self.column();
+                match self.next() {
+                    Some(Token::Str(key)) => segments.push(Segment::Key(key)),
+                    Some(Token::Num(index)) if index >= 0.0 && index.fract() == 0.0 => {
+                        segments.push(Segment::Index(index as usize))
+                    }
+                    _ => return Err(SyntaxError::new(column, "expected an index or quoted key")),
+                }
+                self.expect("]")?;
+            } else {
+                return Ok(Expr::Path(segments));
+            }
+        }
+    }
+}
+
+// ==================== Evaluation ====================
+
+impl Expr {
+    fn visit(&self, f: &mut impl FnMut(&Expr)) {
+        f(self);
+        match self {
+            Expr::Not(inner) => inner.visit(f),
+            Expr::And(left, right) | Expr::Or(left, right) | Expr::Compare(left, _, right) => {
+                left.visit(f);
+                right.visit(f);
+            }
+            Expr::List(items) | Expr::Call(_, items) => items.iter().for_each(|item| item.visit(f)),
+            Expr::Literal(_) | Expr::Path(_) => {}
+        }
+    }
+
+    fn eval(&self, context: &Value) -> std::result::Result<Value, String> {
+        match self {
+            Expr::Literal(value) => Ok(value.clone()),
+            Expr::Path(segments) => Ok(segments
+                .iter()
+                .try_fold(context, |value, segment| match segment {
+                    Segment::Key(key) => value.get(key),
+                    Segment::Index(index) => value.get(index),
+                })
+                .cloned()
+                .unwrap_or(Value::Null)),
+            Expr::List(items) => Ok(Value::Array(
+                items
+                    .iter()
+                    .map(|item| item.eval(context))
+                    .collect::<std::result::Result<_, _>>()?,
+            )),
+            Expr::Not(inner) => Ok(Value::Bool(!truthy(&inner.eval(context)?))),
+            Expr::And(left, right) => Ok(Value::Bool(
+                truthy(&left.eval(context)?) && truthy(&right.eval(context)?),
+            )),
+            Expr::Or(left, right) => Ok(Value::Bool(
+                truthy(&left.eval(context)?) || truthy(&right.eval(context)?),
+            )),
+            Expr::Compare(left, op, right) => {
+                let left = left.eval(context)?;
+                let right = right.eval(context)?;
+                compare(&left, *op, &right).map(Value::Bool)
+            }
+            Expr::Call(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.eval(context))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                call(name, &args, context).map(Value::Bool)
+            }
+        }
+    }
+}
+
+/// Whether a value counts as true; strings are true unless empty or "false"
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty() && s != "false",
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(fields) => !fields.is_empty(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Numeric value of numbers and numeric strings (variables are always strings)
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn loose_eq(left: &Value, right: &Value) -> bool {
+    if matches!(left, Value::Number(_)) || matches!(right, Value::Number(_)) {
+        if let (Some(l), Some(r)) = (as_number(left), as_number(right)) {
+            return l == r;
+        }
+    }
+    left == right
+}
+
+fn compare(left: &Value, op: CompareOp, right: &Value) -> std::result::Result<bool, String> {
+    let ordering = |left: &Value, right: &Value| {
+        if let (Some(l), Some(r)) = (as_number(left), as_number(right)) {
+            return l.partial_cmp(&r);
+        }
+        match (left, right) {
+            (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+            _ => None,
+        }
+    };
+
+    match op {
+        CompareOp::Eq => Ok(loose_eq(left, right)),
+        CompareOp::Ne => Ok(!loose_eq(left, right)),
+        CompareOp::In => contains(right, left),
+        _ => {
+            let ordering = ordering(left, right).ok_or_else(|| {
+                format!(
+                    "cannot compare {} with {} using '{}'",
+                    type_name(left),
+                    type_name(right),
+                    op.as_str()
+                )
+            })?;
+            Ok(match op {
+                CompareOp::Lt => ordering.is_lt(),
+                CompareOp::Le => ordering.is_le(),
+                CompareOp::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            })
+        }
+    }
+}
+
+fn contains(haystack: &Value, needle: &Value) -> std::result::Result<bool, String> {
+    match (haystack, needle) {
+        (Value::Null, _) => Ok(false),
+        (Value::Array(items), _) => Ok(items.iter().any(|item| loose_eq(item, needle))),
+        (Value::Object(fields), Value::String(key)) => Ok(fields.contains_key(key)),
+        (Value::String(text), Value::String(part)) => Ok(text.contains(part.as_str())),
+        _ => Err(format!(
+            "cannot look for {} in {}",
+            type_name(needle),
+            type_name(haystack)
+        )),
+    }
+}
+
+fn call(name: &str, args: &[Value], context: &Value) -> std::result::Result<bool, String> {
+    match (name, args) {
+        ("contains", [haystack, needle]) => contains(haystack, needle),
+        ("startsWith" | "endsWith", [Value::Null, _]) => Ok(false),
+        ("startsWith", [Value::String(text), Value::String(prefix)]) => {
+            Ok(text.starts_with(prefix.as_str()))
+        }
+        ("endsWith", [Value::String(text), Value::String(suffix)]) => {
+            Ok(text.ends_with(suffix.as_str()))
+        }
+        ("changed", [Value::String(pattern)]) => {
+            let evaluator = ConditionEvaluator::new();
+            Ok(context["paths"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .any(|path| evaluator.matches_glob(path, pattern)))
+        }
+        _ => Err(format!(
+            "{}() does not accept {}",
+            name,
+            args.iter().map(type_name).collect::<Vec<_>>().join(" and ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context() -> Value {
+        json!({
+            "branch": "release/1.2",
+            "labels": ["deploy", "security"],
+            "paths": ["docs/guide.md", "src/main.rs"],
+            "variables": { "environment": "staging", "replicas": "3", "dry_run": "false" },
+            "trigger": {
+                "event": "pull_request.merged",
+                "payload": { "pull_request": { "number": 42, "draft": false } }
+            },
+            "stages": { "test": { "status": "succeeded", "attempts": 2 } }
+        })
+    }
+
+    fn eval(source: &str) -> bool {
+        ConditionExpression::parse(source)
+            .unwrap()
+            .evaluate(&context())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_comparisons_and_boolean_operators() {
+        assert!(eval("variables.environment == 'staging'"));
+        assert!(eval("variables.environment != \"production\""));
+        assert!(eval("variables.replicas >= 2 && variables.replicas < 10"));
+        assert!(!eval("variables.replicas > 3"));
+        assert!(eval("branch == 'main' || startsWith(branch, 'release/')"));
+        assert!(eval("not (branch == 'main') and !variables.dry_run"));
+        assert!(eval("'deploy' in labels && !('hotfix' in labels)"));
+        assert!(eval("variables.environment in ['staging', 'production']"));
+    }
+
+    #[test]
+    fn test_stage_results_and_trigger_payload() {
+        assert!(eval(
+            "stages.test.status == 'succeeded' && stages.test.attempts > 1"
+        ));
+        assert!(!eval("stages.build.status == 'succeeded'"));
+        assert!(eval("trigger.payload.pull_request.number == 42"));
+        assert!(eval("!trigger.payload.pull_request.draft"));
+        assert!(eval("trigger.event == 'pull_request.merged'"));
+        assert!(eval("labels[1] == 'security' && labels.0 == 'deploy'"));
+    }
+
+    #[test]
+    fn test_functions() {
+        assert!(eval("changed('docs/**')"));
+        assert!(!eval("changed('infra/**')"));
+        assert!(eval("contains(labels, 'security')"));
+        assert!(eval("endsWith(branch, '.2')"));
+    }
+
+    #[test]
+    fn test_stage_references() {
+        let expr = ConditionExpression::parse(
+            "stages.build.status == 'succeeded' || stages['lint'].attempts > 1 || stages.build.attempts > 2",
+        )
+        .unwrap();
+        assert_eq!(expr.stage_references(), vec!["build", "lint"]);
+    }
+
+    #[test]
+    fn test_parse_errors_report_column() {
+        let error = |source: &str| ConditionExpression::parse(source).unwrap_err().to_string();
+
+        assert_eq!(
+            error("branch == 'main' &&"),
+            "Invalid condition 'branch == 'main' &&': expected a value but found end of input at column 20"
+        );
+        assert!(error("(branch == 'main'").contains("expected ')' but found end of input"));
+        assert!(error("brnch == 'main'").contains("unknown name 'brnch'"));
+        assert!(error("branch = 'main'").contains("unexpected character '=' at column 8"));
+        assert!(error("startsWith(branch)").contains("takes 2 argument(s) but was given 1"));
+        assert!(error("size(labels)").contains("unknown function 'size'"));
+        assert!(error("'unterminated").contains("unterminated string at column 1"));
+        assert!(error("").contains("expression is empty"));
+    }
+
+    #[test]
+    fn test_evaluation_errors() {
+        let error = ConditionExpression::parse("labels > 2")
+            .unwrap()
+            .evaluate(&context())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Condition 'labels > 2' could not be evaluated: cannot compare a list with a number using '>'"
+        );
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! Token-efficient context handoffs between agents. Provides structured
 //! summaries of agent work that can be passed to controllers or successor
-//! agents without consuming excessive context tokens.
+//! agents without consuming excessive context tokens. Summaries can be
+//! embedded and indexed so new agents receive related prior work.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -223,6 +224,27 @@ impl ContextSummary {
     pub fn test_names(&self) -> Vec<&str> {
         self.tests_added.iter().map(|t| t.name.as_str()).collect()
     }
+
+    /// Text used to embed the summary for semantic search
+    pub fn embedding_text(&self) -> String {
+        let mut parts = vec![self.summary_text.clone()];
+        parts.extend(self.key_decisions.iter().map(|d| d.description.clone()));
+        parts.extend(self.files_changed.iter().map(|f| f.path.clone()));
+        parts.extend(self.tests_added.iter().map(|t| t.name.clone()));
+        parts.extend(self.blockers.iter().map(|b| b.description.clone()));
+        parts.retain(|p| !p.is_empty());
+        parts.join("\n")
+    }
+
+    /// One-line rendering for injecting into another agent's prompt
+    pub fn to_brief(&self) -> String {
+        let mut brief = format!("[{}] {}", self.status.as_str(), self.summary_text);
+        let files = self.changed_file_paths();
+        if !files.is_empty() {
+            brief.push_str(&format!(" (files: {})", files.join(", ")));
+        }
+        brief
+    }
 }
 
 /// Status of the work
@@ -814,6 +836,104 @@ impl OutputSummarizer {
     }
 }
 
+/// Produces embedding vectors for context summary search
+pub trait SummaryEmbedder: Send + Sync {
+    /// Identifier stored with each embedding; only vectors from the same
+    /// model are compared
+    fn model(&self) -> &str;
+
+    /// Embed text into a vector
+    fn embed(&self, text: &str) -> Vec<f32>;
+}
+
+/// Local embedder using feature hashing over words and path segments
+///
+/// Deterministic and dependency-free, so indexing works offline. Vectors are
+/// L2-normalized, making cosine similarity a dot product.
+#[derive(Debug, Clone)]
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HashingEmbedder {
+    /// Create an embedder with 256 dimensions
+    pub fn new() -> Self {
+        Self { dimensions: 256 }
+    }
+
+    /// Set the number of dimensions
+    pub fn with_dimensions(mut self, dimensions: usize) -> Self {
+        self.dimensions = dimensions.max(1);
+        self
+    }
+
+    fn bucket(&self, token: &str) -> usize {
+        // FNV-1a, stable across runs and platforms
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in token.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        (hash % self.dimensions as u64) as usize
+    }
+}
+
+impl SummaryEmbedder for HashingEmbedder {
+    fn model(&self) -> &str {
+        "hashing-v1"
+    }
+
+    fn embed(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+        let words = text
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|w| w.len() >= 3)
+            .map(str::to_lowercase);
+        for word in words {
+            vector[self.bucket(&word)] += 1.0;
+        }
+
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|v| *v /= norm);
+        }
+        vector
+    }
+}
+
+/// Cosine similarity between two vectors (0.0 when either is empty or zero)
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    (dot / (norm_a * norm_b)) as f64
+}
+
+/// A stored summary matched against a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedSummary {
+    /// Stored summary ID
+    pub id: i64,
+    /// Story the summarized work belonged to
+    pub story_id: Option<String>,
+    /// The summary
+    pub summary: ContextSummary,
+    /// Relevance score (semantic similarity plus file overlap bonus)
+    pub score: f64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1100,4 +1220,37 @@ STATUS: COMPLETE
         assert!(!summary.tests_added.is_empty());
         assert!(!summary.summary_text.is_empty());
     }
+
+    // ==================== Embedding Tests ====================
+
+    #[test]
+    fn test_hashing_embedder_similarity() {
+        let embedder = HashingEmbedder::new();
+        let auth = embedder.embed("Added login handler with session token validation");
+        let auth_task = embedder.embed("Fix session token validation in the login flow");
+        let docs = embedder.embed("Rewrote README installation section");
+
+        assert_eq!(auth.len(), 256);
+        assert!((cosine_similarity(&auth, &auth) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&auth, &auth_task) > cosine_similarity(&docs, &auth_task));
+        assert_eq!(cosine_similarity(&auth, &[]), 0.0);
+        assert!(embedder.embed("").iter().all(|v| *v == 0.0));
+    }
+
+    #[test]
+    fn test_summary_embedding_text_and_brief() {
+        let mut summary = ContextSummary::new().with_status(WorkStatus::Completed);
+        summary.set_summary("Implemented token refresh");
+        summary.add_file_change(FileChange::new("src/auth.rs", FileChangeType::Modified));
+        summary.add_test(TestAdded::new("test_refresh"));
+
+        let text = summary.embedding_text();
+        assert!(text.contains("Implemented token refresh"));
+        assert!(text.contains("src/auth.rs"));
+        assert!(text.contains("test_refresh"));
+        assert_eq!(
+            summary.to_brief(),
+            "[Completed] Implemented token refresh (files: src/auth.rs)"
+        );
+    }
 }
@@ -3,6 +3,7 @@
 //! Provides cost tracking, budgeting, and optimization recommendations
 //! for multi-agent system operations.
 
+use chrono::{Datelike, Duration, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -70,6 +71,28 @@ pub enum BudgetPeriod {
     Monthly,
 }
 
+impl BudgetPeriod {
+    /// First and last day of the period containing `day`; weeks start on Monday
+    pub fn bounds(&self, day: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            BudgetPeriod::Daily => (day, day),
+            BudgetPeriod::Weekly => {
+                let start = day - Duration::days(day.weekday().num_days_from_monday() as i64);
+                (start, start + Duration::days(6))
+            }
+            BudgetPeriod::Monthly => {
+                let start = day.with_day(1).unwrap_or(day);
+                let next = if start.month() == 12 {
+                    NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+                } else {
+                    NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+                };
+                (start, next.map_or(day, |next| next - Duration::days(1)))
+            }
+        }
+    }
+}
+
 impl std::fmt::Display for BudgetPeriod {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -194,6 +217,16 @@ impl CostRecord {
     }
 }
 
+/// Cost-to-date for a single epic or story, summed across models and days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityCostSummary {
+    pub entity_id: String,
+    pub total_input_tokens: i64,
+    pub total_output_tokens: i64,
+    pub request_count: i64,
+    pub estimated_cost_usd: f64,
+}
+
 /// Daily cost summary
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DailyCost {
@@ -280,6 +313,29 @@ pub struct BudgetStatus {
     pub is_alert_triggered: bool,
 }
 
+/// Spend against a budget over its current period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetBurnDown {
+    pub budget: CostBudget,
+    pub period_start: String,
+    pub period_end: String,
+    pub spent: f64,
+    pub remaining: f64,
+    /// Spend by the end of the period at the average daily rate so far
+    pub projected: f64,
+    pub points: Vec<BurnDownPoint>,
+}
+
+/// Budget left at the end of one day of a period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BurnDownPoint {
+    pub date: String,
+    pub spent: f64,
+    pub remaining: f64,
+    /// Budget left when spending evenly over the period
+    pub ideal_remaining: f64,
+}
+
 /// Cost optimization recommendation type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -523,6 +579,47 @@ impl CostAnalytics {
         recommendations
     }
 
+    /// Burn-down of `budget` over its period containing `today`, from spend
+    /// per `YYYY-MM-DD` date; days after `today` are left out
+    pub fn burn_down(
+        budget: &CostBudget,
+        daily_spend: &HashMap<String, f64>,
+        today: NaiveDate,
+    ) -> BudgetBurnDown {
+        let (start, end) = budget.period_type.bounds(today);
+        let period_days = (end - start).num_days() + 1;
+
+        let mut spent = 0.0;
+        let mut points = Vec::new();
+        for (elapsed, day) in start.iter_days().take_while(|day| *day <= today).enumerate() {
+            let date = day.format("%Y-%m-%d").to_string();
+            spent += daily_spend.get(&date).copied().unwrap_or(0.0);
+            let ideal_spent = budget.amount_usd * (elapsed + 1) as f64 / period_days as f64;
+            points.push(BurnDownPoint {
+                date,
+                spent,
+                remaining: budget.amount_usd - spent,
+                ideal_remaining: budget.amount_usd - ideal_spent,
+            });
+        }
+
+        let projected = if points.is_empty() {
+            0.0
+        } else {
+            spent / points.len() as f64 * period_days as f64
+        };
+
+        BudgetBurnDown {
+            budget: budget.clone(),
+            period_start: start.format("%Y-%m-%d").to_string(),
+            period_end: end.format("%Y-%m-%d").to_string(),
+            spent,
+            remaining: budget.amount_usd - spent,
+            projected,
+            points,
+        }
+    }
+
     /// Aggregate costs by model
     pub fn aggregate_by_model(cost_records: &[CostRecord]) -> HashMap<String, f64> {
         let mut by_model = HashMap::new();
@@ -907,4 +1004,54 @@ mod tests {
         assert_eq!(most_expensive.entity_id, "agent-2");
         assert_eq!(most_expensive.estimated_cost_usd, 15.0);
     }
+
+    #[test]
+    fn test_budget_period_bounds() {
+        let day = NaiveDate::from_ymd_opt(2025, 12, 17).unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(BudgetPeriod::Daily.bounds(day), (day, day));
+        assert_eq!(
+            BudgetPeriod::Weekly.bounds(day),
+            (date(2025, 12, 15), date(2025, 12, 21))
+        );
+        assert_eq!(
+            BudgetPeriod::Monthly.bounds(day),
+            (date(2025, 12, 1), date(2025, 12, 31))
+        );
+        assert_eq!(
+            BudgetPeriod::Monthly.bounds(date(2024, 2, 10)),
+            (date(2024, 2, 1), date(2024, 2, 29))
+        );
+    }
+
+    #[test]
+    fn test_budget_burn_down() {
+        let budget = CostBudget::new(BudgetPeriod::Weekly, 70.0);
+        let spend: HashMap<String, f64> = [
+            ("2025-12-14".to_string(), 100.0), // previous week
+            ("2025-12-15".to_string(), 5.0),
+            ("2025-12-17".to_string(), 25.0),
+        ]
+        .into_iter()
+        .collect();
+
+        let today = NaiveDate::from_ymd_opt(2025, 12, 17).unwrap();
+        let burn_down = CostAnalytics::burn_down(&budget, &spend, today);
+
+        assert_eq!(burn_down.period_start, "2025-12-15");
+        assert_eq!(burn_down.period_end, "2025-12-21");
+        assert_eq!(burn_down.spent, 30.0);
+        assert_eq!(burn_down.remaining, 40.0);
+        assert_eq!(burn_down.projected, 70.0);
+        assert_eq!(burn_down.points.len(), 3);
+        assert_eq!(
+            burn_down.points[1],
+            BurnDownPoint {
+                date: "2025-12-16".to_string(),
+                spent: 5.0,
+                remaining: 65.0,
+                ideal_remaining: 50.0,
+            }
+        );
+    }
 }
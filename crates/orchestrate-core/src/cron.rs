@@ -1,9 +1,10 @@
 //! Cron expression parsing and scheduling
 //!
 //! This module provides functionality to parse cron expressions and calculate
-//! next run times for scheduled tasks.
+//! next run times for scheduled tasks, in UTC or in a named timezone.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, LocalResult, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
 use cron::Schedule as CronLib;
 use crate::Error;
 use std::str::FromStr;
@@ -57,6 +58,49 @@ impl CronSchedule {
             .ok_or_else(|| Error::Other("Failed to calculate next run time".to_string()))
     }
 
+    /// Calculate the next run time from a given time, reading the expression
+    /// as wall-clock time in `timezone`
+    ///
+    /// Across daylight saving changes, a time skipped when clocks go forward
+    /// runs as far after the change as it would have been into the skipped
+    /// hour (02:30 becomes 03:30), and a time repeated when clocks go back
+    /// runs once, at its first occurrence.
+    pub fn next_after_in(
+        &self,
+        from: &DateTime<Utc>,
+        timezone: Tz,
+    ) -> Result<DateTime<Utc>, Error> {
+        // Walk wall-clock times as if they were UTC, then place each one in
+        // the timezone
+        let wall_from = Utc.from_utc_datetime(&from.with_timezone(&timezone).naive_local());
+
+        for wall in self.schedule.after(&wall_from) {
+            let wall = wall.naive_utc();
+            let next = match timezone.from_local_datetime(&wall) {
+                LocalResult::Single(next) => next.with_timezone(&Utc),
+                LocalResult::Ambiguous(earliest, _) => earliest.with_timezone(&Utc),
+                LocalResult::None => {
+                    // Use the offset in effect before the clocks went forward
+                    let offset = timezone
+                        .offset_from_utc_datetime(&(wall - Duration::days(1)))
+                        .fix();
+                    Utc.from_utc_datetime(
+                        &(wall - Duration::seconds(offset.local_minus_utc() as i64)),
+                    )
+                }
+            };
+
+            // The second pass through a repeated hour maps back to the first
+            if next > *from {
+                return Ok(next);
+            }
+        }
+
+        Err(Error::Other(
+            "Failed to calculate next run time".to_string(),
+        ))
+    }
+
     /// Get the raw cron expression
     pub fn expression(&self) -> &str {
         &self.expression
@@ -69,6 +113,12 @@ impl CronSchedule {
     }
 }
 
+/// Parse an IANA timezone name such as "America/New_York" or "UTC"
+pub fn parse_timezone(name: &str) -> Result<Tz, Error> {
+    name.parse::<Tz>()
+        .map_err(|_| Error::Other(format!("Unknown timezone '{}'", name)))
+}
+
 /// Expand extended cron syntax (@daily, @weekly, etc.) to standard cron
 ///
 /// The cron library expects 6 fields: sec min hour day month weekday
@@ -333,4 +383,50 @@ mod tests {
         let schedule = CronSchedule::new("0 2 * * *").unwrap();
         assert_eq!(schedule.expression(), "0 2 * * *");
     }
+
+    #[test]
+    fn test_next_run_in_timezone_follows_daylight_saving() {
+        let schedule = CronSchedule::new("0 9 * * 1-5").unwrap();
+        let new_york = parse_timezone("America/New_York").unwrap();
+
+        // 9am EST is 14:00 UTC
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+        let next = schedule.next_after_in(&now, new_york).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 1, 15, 14, 0, 0).unwrap());
+
+        // After clocks go forward on Sunday 2025-03-09, 9am EDT is 13:00 UTC
+        let friday = Utc.with_ymd_and_hms(2025, 3, 7, 15, 0, 0).unwrap();
+        let next = schedule.next_after_in(&friday, new_york).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 3, 10, 13, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_next_run_in_timezone_skipped_and_repeated_times() {
+        let new_york = parse_timezone("America/New_York").unwrap();
+
+        // 02:30 does not exist on 2025-03-09 and runs at 03:30 EDT instead
+        let schedule = CronSchedule::new("30 2 * * *").unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 3, 8, 12, 0, 0).unwrap();
+        let next = schedule.next_after_in(&now, new_york).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 3, 9, 7, 30, 0).unwrap());
+        let next = schedule.next_after_in(&next, new_york).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 3, 10, 6, 30, 0).unwrap());
+
+        // 01:30 happens twice on 2025-11-02 and runs only the first time
+        let schedule = CronSchedule::new("30 1 * * *").unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 11, 1, 12, 0, 0).unwrap();
+        let next = schedule.next_after_in(&now, new_york).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 11, 2, 5, 30, 0).unwrap());
+        let next = schedule.next_after_in(&next, new_york).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 11, 3, 6, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!(parse_timezone("UTC").unwrap(), Tz::UTC);
+        assert!(parse_timezone("Europe/Prague").is_ok());
+
+        let err = parse_timezone("Mars/Olympus").unwrap_err();
+        assert!(err.to_string().contains("Unknown timezone 'Mars/Olympus'"));
+    }
 }
@@ -0,0 +1,331 @@
+//! Custom Inbound Webhooks
+//!
+//! Custom webhooks accept payloads from tools without a dedicated receiver
+//! (Sentry, Grafana, internal services) at `/webhooks/custom/<name>`. Each
+//! webhook is defined in YAML with a mapping that extracts the event type,
+//! action, and fields from the payload using JSONPath-style paths:
+//!
+//! ```yaml
+//! name: sentry
+//! description: Sentry issue alerts
+//! token: ${SENTRY_WEBHOOK_TOKEN}
+//! event: sentry_issue
+//! action: $.action
+//! fields:
+//!   issue_id: $.data.issue.id
+//!   title: $.data.issue.title
+//!   first_tag: $.data.issue.tags[0].value
+//! ```
+//!
+//! Mapped events are queued like any other webhook event, so pipelines
+//! trigger on them (`event: sentry_issue` or `event: sentry_issue.created`)
+//! and read the mapped fields as trigger variables.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::{Error, Result};
+
+/// Payload keys the mapping writes itself
+const RESERVED_FIELDS: &[&str] = &["action", "payload"];
+
+/// A stored custom webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomWebhook {
+    /// Database ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    /// Webhook name, the last segment of its URL
+    pub name: String,
+    /// YAML definition
+    pub definition: String,
+    /// Whether the webhook accepts events
+    pub enabled: bool,
+    /// Created timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+impl CustomWebhook {
+    /// Create a new custom webhook
+    pub fn new(name: String, definition: String) -> Self {
+        Self {
+            id: None,
+            name,
+            definition,
+            enabled: true,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Definition of a custom webhook and its payload mapping
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomWebhookDefinition {
+    /// Webhook name, used in `/webhooks/custom/<name>`
+    pub name: String,
+    /// Description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Token callers must send in the `X-Webhook-Token` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Event type of queued events, or a path (starting with `$`) to read it from
+    pub event: String,
+    /// Path to the event's action, appended to the event type for triggers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    /// Fields of the queued event, mapped to the path they are read from
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl CustomWebhookDefinition {
+    /// Parse and validate a definition from YAML
+    ///
+    /// `${VAR}` references are replaced with environment variables, so
+    /// tokens need not be stored in the definition.
+    pub fn from_yaml_str(yaml: &str) -> Result<Self> {
+        let yaml = crate::webhook_config::substitute_env_vars(yaml);
+        let definition: Self = serde_yaml::from_str(&yaml)
+            .map_err(|e| Error::Other(format!("Failed to parse custom webhook: {}", e)))?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    /// Check the name, event type, and paths
+    pub fn validate(&self) -> Result<()> {
+        let valid_name = !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_name {
+            return Err(Error::Other(format!(
+                "Custom webhook name '{}' may only contain letters, digits, '-' and '_'",
+                self.name
+            )));
+        }
+
+        if self.event.starts_with('$') {
+            path_segments(&self.event)?;
+        } else if self.event.is_empty() || self.event.contains(['.', ' ']) {
+            return Err(Error::Other(format!(
+                "Custom webhook event '{}' must be a non-empty name without '.' or spaces",
+                self.event
+            )));
+        }
+
+        if let Some(action) = &self.action {
+            path_segments(action)?;
+        }
+        for (field, path) in &self.fields {
+            if RESERVED_FIELDS.contains(&field.as_str()) {
+                return Err(Error::Other(format!(
+                    "Custom webhook field '{}' is reserved",
+                    field
+                )));
+            }
+            path_segments(path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Map a received payload to the event type and payload to queue
+    ///
+    /// The queued payload holds the mapped fields, the `action` when one is
+    /// mapped, and the original payload under `payload`. Fields missing from
+    /// the payload are left out.
+    pub fn map_payload(&self, payload: &Value) -> Result<(String, Value)> {
+        let event_type = if self.event.starts_with('$') {
+            resolve_path(payload, &self.event)
+                .and_then(|value| value.as_str())
+                .filter(|event| !event.is_empty())
+                .ok_or_else(|| {
+                    Error::Other(format!("Payload has no event type at {}", self.event))
+                })?
+                .replace(['.', ' '], "_")
+        } else {
+            self.event.clone()
+        };
+
+        let mut mapped = serde_json::Map::new();
+        if let Some(action) = self
+            .action
+            .as_deref()
+            .and_then(|path| resolve_path(payload, path))
+            .filter(|action| !action.is_null())
+        {
+            mapped.insert("action".to_string(), action.clone());
+        }
+        for (field, path) in &self.fields {
+            if let Some(value) = resolve_path(payload, path).filter(|value| !value.is_null()) {
+                mapped.insert(field.clone(), value.clone());
+            }
+        }
+        mapped.insert("payload".to_string(), payload.clone());
+
+        Ok((event_type, Value::Object(mapped)))
+    }
+}
+
+/// One step of a payload path
+#[derive(Debug, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Split a path such as `$.data.tags[0].value` into its segments
+///
+/// The leading `$` is optional and `$` alone refers to the whole payload.
+fn path_segments(path: &str) -> Result<Vec<PathSegment>> {
+    let invalid = || Error::Other(format!("Invalid payload path '{}'", path));
+
+    let rest = path.strip_prefix('$').unwrap_or(path);
+    let rest = rest.strip_prefix('.').unwrap_or(rest);
+    if rest.is_empty() {
+        return if path.starts_with('$') {
+            Ok(Vec::new())
+        } else {
+            Err(invalid())
+        };
+    }
+
+    let mut segments = Vec::new();
+    for part in rest.split('.') {
+        let (key, mut indexes) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if key.is_empty() && indexes.is_empty() {
+            return Err(invalid());
+        }
+        if !key.is_empty() {
+            segments.push(PathSegment::Key(key.to_string()));
+        }
+        while let Some(index) = indexes.strip_prefix('[') {
+            let close = index.find(']').ok_or_else(invalid)?;
+            let index_value = index[..close].parse().map_err(|_| invalid())?;
+            segments.push(PathSegment::Index(index_value));
+            indexes = &index[close + 1..];
+        }
+        if !indexes.is_empty() {
+            return Err(invalid());
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Value at a JSONPath-style path (e.g. `$.data.issue.id` or `tags[0]`)
+///
+/// Returns `None` when the path is invalid or the payload has no value there.
+pub fn resolve_path<'a>(payload: &'a Value, path: &str) -> Option<&'a Value> {
+    path_segments(path)
+        .ok()?
+        .iter()
+        .try_fold(payload, |value, segment| match segment {
+            PathSegment::Key(key) => value.get(key),
+            PathSegment::Index(index) => value.get(index),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SENTRY_YAML: &str = r#"
+name: sentry
+description: Sentry issue alerts
+token: secret-token
+event: sentry_issue
+action: $.action
+fields:
+  issue_id: $.data.issue.id
+  title: $.data.issue.title
+  first_tag: $.data.issue.tags[0].value
+  culprit: $.data.issue.culprit
+"#;
+
+    fn sentry_payload() -> Value {
+        json!({
+            "action": "created",
+            "data": {
+                "issue": {
+                    "id": "1170820242",
+                    "title": "TypeError: x is undefined",
+                    "tags": [{ "key": "env", "value": "production" }]
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_definition() {
+        let definition = CustomWebhookDefinition::from_yaml_str(SENTRY_YAML).unwrap();
+        assert_eq!(definition.name, "sentry");
+        assert_eq!(definition.token.as_deref(), Some("secret-token"));
+        assert_eq!(definition.fields.len(), 4);
+    }
+
+    #[test]
+    fn test_map_payload() {
+        let definition = CustomWebhookDefinition::from_yaml_str(SENTRY_YAML).unwrap();
+        let (event_type, mapped) = definition.map_payload(&sentry_payload()).unwrap();
+
+        assert_eq!(event_type, "sentry_issue");
+        assert_eq!(mapped["action"], "created");
+        assert_eq!(mapped["issue_id"], "1170820242");
+        assert_eq!(mapped["first_tag"], "production");
+        assert!(mapped.get("culprit").is_none());
+        assert_eq!(mapped["payload"], sentry_payload());
+    }
+
+    #[test]
+    fn test_event_type_from_payload() {
+        let definition = CustomWebhookDefinition::from_yaml_str(
+            "name: grafana\nevent: $.status\nfields:\n  rule: $.alerts[0].labels.alertname\n",
+        )
+        .unwrap();
+
+        let payload =
+            json!({ "status": "firing", "alerts": [{ "labels": { "alertname": "HighCPU" } }] });
+        let (event_type, mapped) = definition.map_payload(&payload).unwrap();
+        assert_eq!(event_type, "firing");
+        assert_eq!(mapped["rule"], "HighCPU");
+        assert!(mapped.get("action").is_none());
+
+        let err = definition.map_payload(&json!({})).unwrap_err();
+        assert!(err.to_string().contains("no event type"));
+    }
+
+    #[test]
+    fn test_invalid_definitions() {
+        let cases = [
+            ("name: bad/name\nevent: x\n", "may only contain"),
+            ("name: ok\nevent: a.b\n", "without '.'"),
+            (
+                "name: ok\nevent: x\naction: $.a[x]\n",
+                "Invalid payload path",
+            ),
+            ("name: ok\nevent: x\nfields:\n  payload: $.a\n", "reserved"),
+            ("name: ok\nevent: x\nunknown: 1\n", "Failed to parse"),
+        ];
+        for (yaml, message) in cases {
+            let err = CustomWebhookDefinition::from_yaml_str(yaml).unwrap_err();
+            assert!(err.to_string().contains(message), "{}: {}", yaml, err);
+        }
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let payload = json!({ "a": { "b": [1, { "c": "deep" }] } });
+        assert_eq!(resolve_path(&payload, "$.a.b[1].c"), Some(&json!("deep")));
+        assert_eq!(resolve_path(&payload, "a.b[0]"), Some(&json!(1)));
+        assert_eq!(resolve_path(&payload, "$"), Some(&payload));
+        assert_eq!(resolve_path(&payload, "$.a.missing"), None);
+        assert_eq!(resolve_path(&payload, "$.a..b"), None);
+    }
+}
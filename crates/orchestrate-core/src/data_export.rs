@@ -0,0 +1,576 @@
+//! Data Export
+//!
+//! Exports token usage, costs, the audit log, and agent history as CSV or
+//! Parquet for analysis outside the orchestrator:
+//! - [`ExportDataset`]: what to export, with the columns of each dataset
+//! - [`ExportTable`]: rows read by `Database::export_rows`, encoded as CSV
+//!   or Parquet
+//! - [`ExportJob`]: an export run in the background, stored as an
+//!   [`ArtifactKind::Export`](crate::ArtifactKind::Export) artifact to
+//!   download through a signed URL
+//!
+//! Timestamps are exported as ISO 8601 UTC text, so both formats read the
+//! same way in spreadsheets and dataframes.
+
+use chrono::{DateTime, Utc};
+use parquet::basic::{Compression, LogicalType, Repetition, Type as PhysicalType};
+use parquet::data_type::{BoolType, ByteArray, ByteArrayType, DoubleType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::types::Type as SchemaType;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::{Artifact, ArtifactStore, Database, Error, Result};
+
+/// Rows per Parquet row group
+const ROW_GROUP_ROWS: usize = 50_000;
+
+/// Data that can be exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportDataset {
+    /// Tokens of each agent session turn
+    TokenUsage,
+    /// Daily spend and tokens per model
+    Costs,
+    AuditLog,
+    /// Agent state transitions, tool calls, recoveries, and evaluations
+    AgentHistory,
+}
+
+impl ExportDataset {
+    pub const ALL: [Self; 4] = [
+        Self::TokenUsage,
+        Self::Costs,
+        Self::AuditLog,
+        Self::AgentHistory,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TokenUsage => "token_usage",
+            Self::Costs => "costs",
+            Self::AuditLog => "audit_log",
+            Self::AgentHistory => "agent_history",
+        }
+    }
+
+    /// Exported columns, in the order `Database::export_rows` selects them
+    pub fn columns(&self) -> &'static [(&'static str, ColumnType)] {
+        use ColumnType::*;
+        match self {
+            Self::TokenUsage => &[
+                ("timestamp", Text),
+                ("agent_id", Text),
+                ("session_id", Text),
+                ("turn_number", Integer),
+                ("input_tokens", Integer),
+                ("output_tokens", Integer),
+                ("cache_read_tokens", Integer),
+                ("cache_write_tokens", Integer),
+                ("epic_id", Text),
+                ("story_id", Text),
+            ],
+            Self::Costs => &[
+                ("date", Text),
+                ("model", Text),
+                ("requests", Integer),
+                ("input_tokens", Integer),
+                ("output_tokens", Integer),
+                ("cache_read_tokens", Integer),
+                ("cache_write_tokens", Integer),
+                ("cost_usd", Real),
+            ],
+            Self::AuditLog => &[
+                ("timestamp", Text),
+                ("actor", Text),
+                ("actor_type", Text),
+                ("action", Text),
+                ("resource_type", Text),
+                ("resource_id", Text),
+                ("success", Boolean),
+                ("error_message", Text),
+                ("details", Text),
+            ],
+            Self::AgentHistory => &[
+                ("timestamp", Text),
+                ("agent_id", Text),
+                ("agent_type", Text),
+                ("session_id", Text),
+                ("event_type", Text),
+                ("summary", Text),
+                ("data", Text),
+            ],
+        }
+    }
+}
+
+impl FromStr for ExportDataset {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|dataset| dataset.as_str() == s)
+            .ok_or_else(|| Error::Other(format!("Unknown export dataset: {}", s)))
+    }
+}
+
+/// File format of an export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportFormat {
+    #[default]
+    Csv,
+    Parquet,
+}
+
+impl DataExportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+impl FromStr for DataExportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "parquet" => Ok(Self::Parquet),
+            _ => Err(Error::Other(format!("Unknown export format: {}", s))),
+        }
+    }
+}
+
+/// What to export: a dataset between two times, as a file format
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportRequest {
+    pub dataset: ExportDataset,
+    #[serde(default)]
+    pub format: DataExportFormat,
+    /// Rows at or after this time
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// Rows before this time
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl ExportRequest {
+    pub fn new(dataset: ExportDataset, format: DataExportFormat) -> Self {
+        Self {
+            dataset,
+            format,
+            from: None,
+            to: None,
+        }
+    }
+
+    pub fn between(mut self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> Self {
+        self.from = from;
+        self.to = to;
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match (self.from, self.to) {
+            (Some(from), Some(to)) if from >= to => Err(Error::Other(
+                "Export range must start before it ends".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// File name, e.g. `audit_log-2026-10-01-2026-10-18.csv`
+    pub fn file_name(&self) -> String {
+        let mut name = self.dataset.as_str().to_string();
+        for time in [self.from, self.to].into_iter().flatten() {
+            name.push('-');
+            name.push_str(&time.format("%Y-%m-%d").to_string());
+        }
+        format!("{}.{}", name, self.format.as_str())
+    }
+}
+
+/// Type of an exported column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Integer,
+    Real,
+    Text,
+    Boolean,
+}
+
+/// A value of an exported row
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Boolean(bool),
+}
+
+impl ExportValue {
+    fn to_csv_field(&self) -> String {
+        match self {
+            Self::Null => String::new(),
+            Self::Integer(v) => v.to_string(),
+            Self::Real(v) => v.to_string(),
+            Self::Boolean(v) => v.to_string(),
+            Self::Text(v) if v.contains([',', '"', '\n', '\r']) => {
+                format!("\"{}\"", v.replace('"', "\"\""))
+            }
+            Self::Text(v) => v.clone(),
+        }
+    }
+}
+
+/// Rows of a dataset
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportTable {
+    pub dataset: ExportDataset,
+    pub rows: Vec<Vec<ExportValue>>,
+}
+
+impl ExportTable {
+    pub fn new(dataset: ExportDataset, rows: Vec<Vec<ExportValue>>) -> Self {
+        Self { dataset, rows }
+    }
+
+    pub fn encode(&self, format: DataExportFormat) -> Result<Vec<u8>> {
+        match format {
+            DataExportFormat::Csv => Ok(self.to_csv().into_bytes()),
+            DataExportFormat::Parquet => self.to_parquet(),
+        }
+    }
+
+    /// CSV with a header row
+    pub fn to_csv(&self) -> String {
+        let columns = self.dataset.columns();
+        let mut csv = columns
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(",");
+        csv.push('\n');
+        for row in &self.rows {
+            let fields: Vec<String> = row.iter().map(ExportValue::to_csv_field).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Snappy-compressed Parquet with one optional column per dataset column
+    pub fn to_parquet(&self) -> Result<Vec<u8>> {
+        let parquet_error = |e: parquet::errors::ParquetError| Error::Other(e.to_string());
+        let columns = self.dataset.columns();
+
+        let fields = columns
+            .iter()
+            .map(|(name, column_type)| {
+                let (physical, logical) = match column_type {
+                    ColumnType::Integer => (PhysicalType::INT64, None),
+                    ColumnType::Real => (PhysicalType::DOUBLE, None),
+                    ColumnType::Text => (PhysicalType::BYTE_ARRAY, Some(LogicalType::String)),
+                    ColumnType::Boolean => (PhysicalType::BOOLEAN, None),
+                };
+                SchemaType::primitive_type_builder(name, physical)
+                    .with_repetition(Repetition::OPTIONAL)
+                    .with_logical_type(logical)
+                    .build()
+                    .map(Arc::new)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(parquet_error)?;
+        let schema = SchemaType::group_type_builder(self.dataset.as_str())
+            .with_fields(fields)
+            .build()
+            .map_err(parquet_error)?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+
+        let mut writer =
+            SerializedFileWriter::new(Vec::new(), Arc::new(schema), Arc::new(properties))
+                .map_err(parquet_error)?;
+        for rows in self.rows.chunks(ROW_GROUP_ROWS) {
+            let mut row_group = writer.next_row_group().map_err(parquet_error)?;
+            for (index, (_, column_type)) in columns.iter().enumerate() {
+                let Some(mut column) = row_group.next_column().map_err(parquet_error)? else {
+                    break;
+                };
+                let values = rows.iter().map(|row| &row[index]);
+                // Nulls are left out of the values and marked by definition level 0
+                let levels: Vec<i16> = values
+                    .clone()
+                    .map(|v| i16::from(*v != ExportValue::Null))
+                    .collect();
+                match column_type {
+                    ColumnType::Integer => {
+                        let values: Vec<i64> = values
+                            .filter_map(|v| match v {
+                                ExportValue::Integer(v) => Some(*v),
+                                _ => None,
+                            })
+                            .collect();
+                        column
+                            .typed::<Int64Type>()
+                            .write_batch(&values, Some(&levels), None)
+                    }
+                    ColumnType::Real => {
+                        let values: Vec<f64> = values
+                            .filter_map(|v| match v {
+                                ExportValue::Real(v) => Some(*v),
+                                _ => None,
+                            })
+                            .collect();
+                        column
+                            .typed::<DoubleType>()
+                            .write_batch(&values, Some(&levels), None)
+                    }
+                    ColumnType::Text => {
+                        let values: Vec<ByteArray> = values
+                            .filter_map(|v| match v {
+                                ExportValue::Text(v) => Some(ByteArray::from(v.as_str())),
+                                _ => None,
+                            })
+                            .collect();
+                        column
+                            .typed::<ByteArrayType>()
+                            .write_batch(&values, Some(&levels), None)
+                    }
+                    ColumnType::Boolean => {
+                        let values: Vec<bool> = values
+                            .filter_map(|v| match v {
+                                ExportValue::Boolean(v) => Some(*v),
+                                _ => None,
+                            })
+                            .collect();
+                        column
+                            .typed::<BoolType>()
+                            .write_batch(&values, Some(&levels), None)
+                    }
+                }
+                .map_err(parquet_error)?;
+                column.close().map_err(parquet_error)?;
+            }
+            row_group.close().map_err(parquet_error)?;
+        }
+        writer.into_inner().map_err(parquet_error)
+    }
+}
+
+/// Status of an export job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportJobStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+impl ExportJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for ExportJobStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "running" => Ok(Self::Running),
+            "completed" => Ok(Self::Completed),
+            "failed" => Ok(Self::Failed),
+            _ => Err(Error::Other(format!("Invalid export job status: {}", s))),
+        }
+    }
+}
+
+/// An export run in the background
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportJob {
+    pub id: String,
+    #[serde(flatten)]
+    pub request: ExportRequest,
+    pub status: ExportJobStatus,
+    /// Rows exported, once completed
+    pub row_count: Option<i64>,
+    /// Artifact holding the file, once completed
+    pub artifact_id: Option<i64>,
+    pub error_message: Option<String>,
+    /// User or API key that requested the export
+    pub requested_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ExportJob {
+    pub fn new(request: ExportRequest) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            request,
+            status: ExportJobStatus::Running,
+            row_count: None,
+            artifact_id: None,
+            error_message: None,
+            requested_by: None,
+            created_at: Utc::now(),
+            completed_at: None,
+        }
+    }
+
+    pub fn with_requested_by(mut self, requested_by: impl Into<String>) -> Self {
+        self.requested_by = Some(requested_by.into());
+        self
+    }
+
+    pub fn mark_completed(&mut self, row_count: i64, artifact: &Artifact) {
+        self.status = ExportJobStatus::Completed;
+        self.row_count = Some(row_count);
+        self.artifact_id = artifact.id;
+        self.completed_at = Some(Utc::now());
+    }
+
+    pub fn mark_failed(&mut self, error: impl Into<String>) {
+        self.status = ExportJobStatus::Failed;
+        self.error_message = Some(error.into());
+        self.completed_at = Some(Utc::now());
+    }
+
+    /// Export the rows, store the file, and record the outcome
+    pub async fn run(&mut self, db: &Database, artifacts: &ArtifactStore) -> Result<()> {
+        let outcome = async {
+            let table = db.export_rows(&self.request, None).await?;
+            let body = table.encode(self.request.format)?;
+            let artifact = artifacts.put_export(&self.id, &self.request, body).await?;
+            Ok::<_, Error>((table.rows.len() as i64, artifact))
+        }
+        .await;
+
+        match outcome {
+            Ok((row_count, artifact)) => self.mark_completed(row_count, &artifact),
+            Err(e) => self.mark_failed(e.to_string()),
+        }
+        db.update_export_job(self).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    fn table() -> ExportTable {
+        ExportTable::new(
+            ExportDataset::Costs,
+            vec![
+                vec![
+                    ExportValue::Text("2026-10-17".to_string()),
+                    ExportValue::Text("claude-sonnet-4".to_string()),
+                    ExportValue::Integer(12),
+                    ExportValue::Integer(4000),
+                    ExportValue::Integer(800),
+                    ExportValue::Integer(2000),
+                    ExportValue::Integer(0),
+                    ExportValue::Real(0.042),
+                ],
+                vec![
+                    ExportValue::Text("2026-10-18".to_string()),
+                    ExportValue::Text("model, \"custom\"".to_string()),
+                    ExportValue::Integer(1),
+                    ExportValue::Integer(10),
+                    ExportValue::Integer(5),
+                    ExportValue::Integer(0),
+                    ExportValue::Integer(0),
+                    ExportValue::Null,
+                ],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_dataset_and_format_parsing() {
+        for dataset in ExportDataset::ALL {
+            assert_eq!(dataset.as_str().parse::<ExportDataset>().unwrap(), dataset);
+        }
+        assert!("messages".parse::<ExportDataset>().is_err());
+        assert_eq!(
+            "parquet".parse::<DataExportFormat>().unwrap(),
+            DataExportFormat::Parquet
+        );
+        assert!("xlsx".parse::<DataExportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_export_request_file_name_and_range() {
+        let from = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap();
+        let request = ExportRequest::new(ExportDataset::AuditLog, DataExportFormat::Csv)
+            .between(Some(from), Some(to));
+        assert_eq!(request.file_name(), "audit_log-2026-10-01-2026-10-18.csv");
+        assert!(request.validate().is_ok());
+
+        let request = ExportRequest::new(ExportDataset::Costs, DataExportFormat::Parquet);
+        assert_eq!(request.file_name(), "costs.parquet");
+
+        let reversed = request.between(Some(to), Some(from));
+        assert!(reversed.validate().is_err());
+    }
+
+    #[test]
+    fn test_table_to_csv() {
+        let csv = table().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "date,model,requests,input_tokens,output_tokens,cache_read_tokens,cache_write_tokens,cost_usd"
+        );
+        assert_eq!(
+            lines[1],
+            "2026-10-17,claude-sonnet-4,12,4000,800,2000,0,0.042"
+        );
+        assert_eq!(lines[2], "2026-10-18,\"model, \"\"custom\"\"\",1,10,5,0,0,");
+    }
+
+    #[test]
+    fn test_table_to_parquet() {
+        let bytes = table().to_parquet().unwrap();
+        assert_eq!(&bytes[..4], b"PAR1");
+
+        let mut file = tempfile::tempfile().unwrap();
+        std::io::Write::write_all(&mut file, &bytes).unwrap();
+        let reader = SerializedFileReader::new(file).unwrap();
+        let metadata = reader.metadata().file_metadata();
+        assert_eq!(metadata.num_rows(), 2);
+        let columns: Vec<&str> = metadata
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|c| c.name())
+            .collect();
+        assert_eq!(columns[0], "date");
+        assert_eq!(columns[7], "cost_usd");
+    }
+}
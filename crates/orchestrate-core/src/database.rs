@@ -1,7 +1,8 @@
 //! Database layer for SQLite
 
 use serde::{Deserialize, Serialize};
-use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use sqlx::sqlite::{SqliteExecutor, SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::Row;
 use std::path::Path;
 use std::time::Duration;
 use uuid::Uuid;
@@ -9,7 +10,7 @@ use uuid::Uuid;
 use crate::approval::{ApprovalDecision, ApprovalRequest, ApprovalStatus};
 use crate::experiment::{
     Experiment, ExperimentMetric, ExperimentStatus, ExperimentType, ExperimentVariant,
-    VariantResults,
+    VariantDailyResults, VariantResults,
 };
 use crate::model_selection::{
     ModelPerformance, ModelSelectionConfig, ModelSelectionRule, OptimizationGoal, TaskComplexity,
@@ -20,8 +21,13 @@ use crate::instruction::{
     LearningPattern, PatternStatus, PatternType, SuccessPattern, SuccessPatternType,
 };
 use crate::network::{AgentId, StepOutput, StepOutputType};
-use crate::schedule::{Schedule, ScheduleRun, ScheduleRunStatus};
-use crate::webhook::{WebhookEvent, WebhookEventStatus};
+use crate::pagination::{KeyValue, PageQuery, RowPage, SortKey};
+use crate::pipeline_plan::{
+    AgentTokenEstimate, DEFAULT_AGENT_INPUT_TOKENS, DEFAULT_AGENT_OUTPUT_TOKENS,
+};
+use crate::schedule::{Schedule, ScheduleRun, ScheduleRunStatus, ScheduleSpend, ScheduleStats};
+use crate::schedule_blackout::ScheduleBlackout;
+use crate::webhook::{WebhookEvent, WebhookEventStatus, WebhookEventSummary};
 use crate::{
     Agent, AgentState, AgentType, Epic, EpicStatus, MergeStrategy, Message, MessageRole, PrStatus,
     PullRequest, Result, Story, StoryStatus,
@@ -106,8 +112,19 @@ impl Database {
         Ok(db)
     }
 
+    /// Connection pool, for services that run their own queries
+    pub(crate) fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// Check that the database answers queries
+    pub async fn ping(&self) -> Result<()> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
     /// Run database migrations
-    async fn run_migrations(&self) -> Result<()> {
+    pub(crate) async fn run_migrations(&self) -> Result<()> {
         sqlx::query(include_str!("../../../migrations/001_initial.sql"))
             .execute(&self.pool)
             .await?;
@@ -170,6 +187,12 @@ impl Database {
         sqlx::query(include_str!("../../../migrations/016_incidents.sql"))
             .execute(&self.pool)
             .await?;
+        // Slack integration migration
+        sqlx::query(include_str!(
+            "../../../migrations/016_slack_integration.sql"
+        ))
+        .execute(&self.pool)
+        .await?;
         // Feature flags migration
         sqlx::query(include_str!("../../../migrations/017_feature_flags.sql"))
             .execute(&self.pool)
@@ -224,33 +247,335 @@ impl Database {
         ))
         .execute(&self.pool)
         .await?;
+        // Agent event log migration
+        sqlx::query(include_str!("../../../migrations/028_agent_events.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Custom agent types migration
+        sqlx::query(include_str!("../../../migrations/029_custom_agent_types.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Agent custom type column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!("../../../migrations/030_agent_custom_type.sql"))
+            .execute(&self.pool)
+            .await;
+        // Saga compensation migration
+        sqlx::query(include_str!("../../../migrations/031_sagas.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Token attribution columns - uses ALTER TABLE which fails if the columns exist
+        let _ = sqlx::query(include_str!("../../../migrations/032_token_attribution.sql"))
+            .execute(&self.pool)
+            .await;
+        // Stuck detection config migration
+        sqlx::query(include_str!("../../../migrations/033_stuck_detection_configs.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Stuck detection types - rebuilds the table, so only run while the old CHECK is in place
+        if self.table_needs_rebuild("stuck_agent_detections", "'token_burn'").await? {
+            sqlx::query(include_str!("../../../migrations/034_stuck_detection_types.sql"))
+                .execute(&self.pool)
+                .await?;
+        }
+        // Script recovery action - rebuilds the table, so only run while the old CHECK is in place
+        if self.table_needs_rebuild("recovery_attempts", "'script'").await? {
+            sqlx::query(include_str!("../../../migrations/035_recovery_script_action.sql"))
+                .execute(&self.pool)
+                .await?;
+        }
+        // Session plan column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!("../../../migrations/036_session_plans.sql"))
+            .execute(&self.pool)
+            .await;
+        // Context summary index migration
+        sqlx::query(include_str!("../../../migrations/037_context_summaries.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Agent system prompt column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!("../../../migrations/038_agent_system_prompt.sql"))
+            .execute(&self.pool)
+            .await;
+        // Pipeline stage cache migration
+        sqlx::query(include_str!("../../../migrations/039_pipeline_stage_cache.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Pipeline run cache columns - uses ALTER TABLE which fails if the columns exist
+        let _ = sqlx::query(include_str!("../../../migrations/040_pipeline_run_cache.sql"))
+            .execute(&self.pool)
+            .await;
+        // Approval escalation columns - uses ALTER TABLE which fails if the columns exist
+        let _ = sqlx::query(include_str!("../../../migrations/041_approval_escalation.sql"))
+            .execute(&self.pool)
+            .await;
+        // Pipeline stage attempts column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!("../../../migrations/042_pipeline_stage_attempts.sql"))
+            .execute(&self.pool)
+            .await;
+        // Pipeline run variables column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!("../../../migrations/043_pipeline_run_variables.sql"))
+            .execute(&self.pool)
+            .await;
+        // Secrets vault migration
+        sqlx::query(include_str!("../../../migrations/044_secrets.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Pipeline templates migration
+        sqlx::query(include_str!(
+            "../../../migrations/045_pipeline_templates.sql"
+        ))
+        .execute(&self.pool)
+        .await?;
+        // Pipeline run trigger payload column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!(
+            "../../../migrations/046_pipeline_run_trigger_payload.sql"
+        ))
+        .execute(&self.pool)
+        .await;
+        // Schedule timezone column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!(
+            "../../../migrations/047_schedule_timezone.sql"
+        ))
+        .execute(&self.pool)
+        .await;
+        // Schedule jitter and overlap columns - uses ALTER TABLE which fails if the columns exist
+        let _ = sqlx::query(include_str!("../../../migrations/048_schedule_overlap.sql"))
+            .execute(&self.pool)
+            .await;
+        // Schedule dependency column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!(
+            "../../../migrations/049_schedule_dependencies.sql"
+        ))
+        .execute(&self.pool)
+        .await;
+        // Skipped schedule runs - rebuilds the table, so only run while the old CHECK is in place
+        if self
+            .table_needs_rebuild("schedule_runs", "'skipped'")
+            .await?
+        {
+            sqlx::query(include_str!(
+                "../../../migrations/050_schedule_run_skipped.sql"
+            ))
+            .execute(&self.pool)
+            .await?;
+        }
+        // One-shot schedule column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!("../../../migrations/051_schedule_run_at.sql"))
+            .execute(&self.pool)
+            .await;
+        // Schedule blackout windows migration
+        sqlx::query(include_str!(
+            "../../../migrations/052_schedule_blackouts.sql"
+        ))
+        .execute(&self.pool)
+        .await?;
+        // Schedule spend cap columns - uses ALTER TABLE which fails if the columns exist
+        let _ = sqlx::query(include_str!(
+            "../../../migrations/053_schedule_spend_caps.sql"
+        ))
+        .execute(&self.pool)
+        .await;
+        // Schedule missed-run columns - uses ALTER TABLE which fails if the columns exist
+        let _ = sqlx::query(include_str!("../../../migrations/054_schedule_sla.sql"))
+            .execute(&self.pool)
+            .await;
+        // Custom inbound webhooks migration
+        sqlx::query(include_str!("../../../migrations/055_custom_webhooks.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Outbound webhooks migration
+        sqlx::query(include_str!(
+            "../../../migrations/056_outbound_webhooks.sql"
+        ))
+        .execute(&self.pool)
+        .await?;
+        // Webhook source signatures migration
+        sqlx::query(include_str!("../../../migrations/057_webhook_sources.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Email notification columns - uses ALTER TABLE which fails if the columns exist
+        let _ = sqlx::query(include_str!(
+            "../../../migrations/058_email_notifications.sql"
+        ))
+        .execute(&self.pool)
+        .await;
+        // On-call pages migration
+        sqlx::query(include_str!("../../../migrations/059_oncall_pages.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Jira issue synchronization migration
+        sqlx::query(include_str!("../../../migrations/060_jira_sync.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Linear issue synchronization migration
+        sqlx::query(include_str!("../../../migrations/061_linear_sync.sql"))
+            .execute(&self.pool)
+            .await?;
+        // GitHub issue triage migration
+        sqlx::query(include_str!("../../../migrations/062_issue_triage.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Agent trace ID column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!("../../../migrations/063_agent_trace_id.sql"))
+            .execute(&self.pool)
+            .await;
+        // Object storage artifacts migration
+        sqlx::query(include_str!("../../../migrations/064_artifacts.sql"))
+            .execute(&self.pool)
+            .await?;
+        // Google Chat webhook column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!("../../../migrations/065_google_chat_webhooks.sql"))
+            .execute(&self.pool)
+            .await;
+        // Merge queue position column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!("../../../migrations/066_pr_merge_queue.sql"))
+            .execute(&self.pool)
+            .await;
+        // Webhook event source column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!("../../../migrations/067_webhook_event_source.sql"))
+            .execute(&self.pool)
+            .await;
+        // Agent labels column - uses ALTER TABLE which fails if the column exists
+        let _ = sqlx::query(include_str!("../../../migrations/068_agent_labels.sql"))
+            .execute(&self.pool)
+            .await;
+        // Export artifact kind - rebuilds the table, so only run while the old CHECK is in place
+        if self.table_needs_rebuild("artifacts", "'export'").await? {
+            sqlx::query(include_str!("../../../migrations/069_artifact_exports.sql"))
+                .execute(&self.pool)
+                .await?;
+        }
+        // Data export jobs migration
+        sqlx::query(include_str!("../../../migrations/070_export_jobs.sql"))
+            .execute(&self.pool)
+            .await?;
         Ok(())
     }
 
+    /// Whether an existing table's definition lacks `marker` (e.g. a newly allowed CHECK value)
+    async fn table_needs_rebuild(&self, table: &str, marker: &str) -> Result<bool> {
+        let sql: Option<String> = sqlx::query_scalar(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?",
+        )
+        .bind(table)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(sql.is_some_and(|sql| !sql.contains(marker)))
+    }
+
     /// Begin a transaction
     pub async fn begin(&self) -> Result<sqlx::Transaction<'_, sqlx::Sqlite>> {
         Ok(self.pool.begin().await?)
     }
 
+    /// One page of the rows of `select`, see [`crate::pagination`]
+    ///
+    /// `filter` appends `AND` conditions to the query, and is applied again to
+    /// count the rows when the page asks for a total.
+    async fn fetch_page<R, F>(
+        &self,
+        select: &str,
+        filter: F,
+        sort: SortKey,
+        page: &PageQuery,
+    ) -> Result<RowPage<R>>
+    where
+        R: for<'r> sqlx::FromRow<'r, SqliteRow>,
+        F: for<'q> Fn(&mut sqlx::QueryBuilder<'q, sqlx::Sqlite>),
+    {
+        let mut query = sqlx::QueryBuilder::new(select);
+        query.push(" WHERE 1=1");
+        filter(&mut query);
+        if let Some(after) = &page.after {
+            if after.len() != sort.columns.len() {
+                return Err(crate::Error::Other("Invalid page cursor".to_string()));
+            }
+            let comparison = if sort.descending { "<" } else { ">" };
+            query.push(format!(" AND ({}) {} (", sort.columns.join(", "), comparison));
+            let mut values = query.separated(", ");
+            for value in after {
+                match value {
+                    KeyValue::Integer(value) => values.push_bind(*value),
+                    KeyValue::Text(value) => values.push_bind(value.clone()),
+                };
+            }
+            query.push(")");
+        }
+        let direction = if sort.descending { " DESC" } else { " ASC" };
+        let order: Vec<String> = sort
+            .columns
+            .iter()
+            .map(|column| format!("{}{}", column, direction))
+            .collect();
+        query.push(format!(" ORDER BY {} LIMIT ", order.join(", ")));
+        // One row past the page tells whether another page follows
+        query.push_bind(page.limit as i64 + 1);
+
+        let rows = query.build().fetch_all(&self.pool).await?;
+        let mut items = Vec::with_capacity(rows.len().min(page.limit));
+        for row in rows.iter().take(page.limit) {
+            items.push(R::from_row(row)?);
+        }
+        let next = match rows.get(page.limit) {
+            Some(_) => Some(Self::page_key(&rows[page.limit - 1], sort)?),
+            None => None,
+        };
+
+        let total = if page.include_total {
+            let mut count = sqlx::QueryBuilder::new("SELECT COUNT(*) FROM (");
+            count.push(select).push(" WHERE 1=1");
+            filter(&mut count);
+            count.push(")");
+            Some(
+                count
+                    .build_query_scalar::<i64>()
+                    .fetch_one(&self.pool)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        Ok(RowPage { items, next, total })
+    }
+
+    /// Sort key of a row, read from its (unqualified) key columns
+    fn page_key(row: &SqliteRow, sort: SortKey) -> Result<Vec<KeyValue>> {
+        sort.columns
+            .iter()
+            .map(|column| {
+                let name = column.rsplit('.').next().unwrap_or(column);
+                match row.try_get::<i64, _>(name) {
+                    Ok(value) => Ok(KeyValue::Integer(value)),
+                    Err(_) => Ok(KeyValue::Text(row.try_get::<String, _>(name)?)),
+                }
+            })
+            .collect()
+    }
+
     // ==================== Agent Operations ====================
 
     /// Insert a new agent
     pub async fn insert_agent(&self, agent: &Agent) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO agents (id, agent_type, state, task, context, session_id, parent_agent_id, worktree_id, error_message, created_at, updated_at, completed_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO agents (id, agent_type, custom_type, system_prompt, state, task, context, session_id, parent_agent_id, worktree_id, labels, error_message, trace_id, created_at, updated_at, completed_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(agent.id.to_string())
         .bind(agent.agent_type.as_str())
+        .bind(&agent.custom_type)
+        .bind(&agent.system_prompt)
         .bind(agent.state.as_str())
         .bind(&agent.task)
         .bind(serde_json::to_string(&agent.context)?)
         .bind(&agent.session_id)
         .bind(agent.parent_agent_id.map(|id| id.to_string()))
         .bind(&agent.worktree_id)
+        .bind(serde_json::to_string(&agent.labels)?)
         .bind(&agent.error_message)
+        .bind(&agent.trace_id)
         .bind(agent.created_at.to_rfc3339())
         .bind(agent.updated_at.to_rfc3339())
         .bind(agent.completed_at.map(|dt| dt.to_rfc3339()))
@@ -270,12 +595,16 @@ impl Database {
     }
 
     /// Update an agent
+    ///
+    /// Logs outbound webhook deliveries when the update completes or fails
+    /// the agent.
     pub async fn update_agent(&self, agent: &Agent) -> Result<()> {
+        let previous_state = self.stored_state_if_finishing(agent).await?;
         sqlx::query(
             r#"
             UPDATE agents SET
-                state = ?, task = ?, context = ?, session_id = ?, worktree_id = ?,
-                error_message = ?, updated_at = ?, completed_at = ?
+                state = ?, task = ?, context = ?, session_id = ?, worktree_id = ?, labels = ?,
+                error_message = ?, trace_id = ?, updated_at = ?, completed_at = ?
             WHERE id = ?
             "#,
         )
@@ -284,25 +613,48 @@ impl Database {
         .bind(serde_json::to_string(&agent.context)?)
         .bind(&agent.session_id)
         .bind(&agent.worktree_id)
+        .bind(serde_json::to_string(&agent.labels)?)
         .bind(&agent.error_message)
+        .bind(&agent.trace_id)
         .bind(agent.updated_at.to_rfc3339())
         .bind(agent.completed_at.map(|dt| dt.to_rfc3339()))
         .bind(agent.id.to_string())
         .execute(&self.pool)
         .await?;
+
+        if previous_state.is_some_and(|state| state != agent.state.as_str()) {
+            self.enqueue_outbound_event_logged(crate::OutboundEvent::for_agent(agent))
+                .await;
+        }
         Ok(())
     }
 
+    /// Stored state of an agent the update would complete or fail
+    ///
+    /// `None` when the update does not finish the agent, so most updates
+    /// skip the extra read.
+    async fn stored_state_if_finishing(&self, agent: &Agent) -> Result<Option<String>> {
+        if !matches!(agent.state, AgentState::Completed | AgentState::Failed) {
+            return Ok(None);
+        }
+        let state = sqlx::query_scalar::<_, String>("SELECT state FROM agents WHERE id = ?")
+            .bind(agent.id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(state)
+    }
+
     /// Update an agent with optimistic locking (returns true if updated)
     pub async fn update_agent_with_version(
         &self,
         agent: &Agent,
         expected_updated_at: &str,
     ) -> Result<bool> {
+        let previous_state = self.stored_state_if_finishing(agent).await?;
         let result = sqlx::query(
             r#"
             UPDATE agents SET
-                state = ?, task = ?, context = ?, session_id = ?, worktree_id = ?,
+                state = ?, task = ?, context = ?, session_id = ?, worktree_id = ?, labels = ?,
                 error_message = ?, updated_at = ?, completed_at = ?
             WHERE id = ? AND updated_at = ?
             "#,
@@ -312,6 +664,7 @@ impl Database {
         .bind(serde_json::to_string(&agent.context)?)
         .bind(&agent.session_id)
         .bind(&agent.worktree_id)
+        .bind(serde_json::to_string(&agent.labels)?)
         .bind(&agent.error_message)
         .bind(agent.updated_at.to_rfc3339())
         .bind(agent.completed_at.map(|dt| dt.to_rfc3339()))
@@ -320,7 +673,12 @@ impl Database {
         .execute(&self.pool)
         .await?;
 
-        Ok(result.rows_affected() > 0)
+        let updated = result.rows_affected() > 0;
+        if updated && previous_state.is_some_and(|state| state != agent.state.as_str()) {
+            self.enqueue_outbound_event_logged(crate::OutboundEvent::for_agent(agent))
+                .await;
+        }
+        Ok(updated)
     }
 
     /// List agents by state
@@ -344,6 +702,18 @@ impl Database {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// One page of agents, newest first
+    pub async fn list_agents_page(&self, page: &PageQuery) -> Result<RowPage<Agent>> {
+        self.fetch_page::<AgentRow, _>(
+            "SELECT * FROM agents",
+            |_| {},
+            SortKey::desc(&["created_at", "id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
     // ==================== Worktree Operations ====================
 
     /// Get worktree path by ID
@@ -420,6 +790,25 @@ impl Database {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// One page of an agent's messages, oldest first
+    pub async fn get_messages_page(
+        &self,
+        agent_id: Uuid,
+        page: &PageQuery,
+    ) -> Result<RowPage<Message>> {
+        let agent_id = agent_id.to_string();
+        self.fetch_page::<MessageRow, _>(
+            "SELECT * FROM agent_messages",
+            |q| {
+                q.push(" AND agent_id = ").push_bind(agent_id.clone());
+            },
+            SortKey::asc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
     /// Get messages for an agent with pagination
     pub async fn get_messages_paginated(
         &self,
@@ -704,6 +1093,50 @@ impl Database {
         Ok(())
     }
 
+    /// Get the latest PR queue entry for a GitHub PR number
+    pub async fn get_pr_by_number(&self, pr_number: i32) -> Result<Option<PullRequest>> {
+        let row = sqlx::query_as::<_, PrRow>(
+            r#"
+            SELECT * FROM pr_queue
+            WHERE pr_number = ?
+            ORDER BY id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(pr_number)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Update a PR's merge queue state
+    ///
+    /// Sets the status along with the queue position (cleared when the PR
+    /// leaves the queue) and the error message explaining a dequeue.
+    pub async fn update_pr_merge_queue(
+        &self,
+        id: i64,
+        status: PrStatus,
+        position: Option<i32>,
+        error_message: Option<&str>,
+    ) -> Result<()> {
+        self.update_pr_status(id, status).await?;
+        sqlx::query(
+            r#"
+            UPDATE pr_queue SET merge_queue_position = ?, error_message = COALESCE(?, error_message)
+            WHERE id = ?
+            "#,
+        )
+        .bind(position)
+        .bind(error_message)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     // ==================== Epic Operations ====================
 
     /// Upsert an epic
@@ -742,6 +1175,16 @@ impl Database {
         Ok(())
     }
 
+    /// Get an epic by ID
+    pub async fn get_epic(&self, id: &str) -> Result<Option<Epic>> {
+        let row = sqlx::query_as::<_, EpicRow>("SELECT * FROM epics WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
     /// Get pending epics
     pub async fn get_pending_epics(&self) -> Result<Vec<Epic>> {
         let rows = sqlx::query_as::<_, EpicRow>(
@@ -753,6 +1196,15 @@ impl Database {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// List all epics, oldest first
+    pub async fn list_epics(&self) -> Result<Vec<Epic>> {
+        let rows = sqlx::query_as::<_, EpicRow>("SELECT * FROM epics ORDER BY created_at ASC")
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
     // ==================== Story Operations ====================
 
     /// Upsert a story
@@ -1184,6 +1636,34 @@ impl Database {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// One page of instructions, in creation order
+    pub async fn list_instructions_page(
+        &self,
+        enabled_only: bool,
+        scope: Option<InstructionScope>,
+        source: Option<InstructionSource>,
+        page: &PageQuery,
+    ) -> Result<RowPage<CustomInstruction>> {
+        self.fetch_page::<InstructionRow, _>(
+            "SELECT * FROM custom_instructions",
+            |q| {
+                if enabled_only {
+                    q.push(" AND enabled = 1");
+                }
+                if let Some(scope) = &scope {
+                    q.push(" AND scope = ").push_bind(scope.as_str());
+                }
+                if let Some(source) = &source {
+                    q.push(" AND source = ").push_bind(source.as_str());
+                }
+            },
+            SortKey::asc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
     /// Update an instruction
     #[tracing::instrument(skip(self, instruction), level = "debug", fields(id = instruction.id))]
     pub async fn update_instruction(&self, instruction: &CustomInstruction) -> Result<()> {
@@ -1596,6 +2076,26 @@ impl Database {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// One page of learning patterns, in detection order
+    pub async fn list_patterns_page(
+        &self,
+        status: Option<PatternStatus>,
+        page: &PageQuery,
+    ) -> Result<RowPage<LearningPattern>> {
+        self.fetch_page::<PatternRow, _>(
+            "SELECT * FROM learning_patterns",
+            |q| {
+                if let Some(status) = status {
+                    q.push(" AND status = ").push_bind(status.as_str());
+                }
+            },
+            SortKey::asc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
     /// Update pattern status
     #[tracing::instrument(skip(self), level = "debug")]
     pub async fn update_pattern_status(
@@ -1913,6 +2413,34 @@ impl Database {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// One page of feedback, optionally of one agent, newest first
+    pub async fn list_feedback_page(
+        &self,
+        agent_id: Option<Uuid>,
+        rating: Option<FeedbackRating>,
+        source: Option<FeedbackSource>,
+        page: &PageQuery,
+    ) -> Result<RowPage<Feedback>> {
+        self.fetch_page::<FeedbackRow, _>(
+            "SELECT * FROM feedback",
+            |q| {
+                if let Some(agent_id) = agent_id {
+                    q.push(" AND agent_id = ").push_bind(agent_id.to_string());
+                }
+                if let Some(rating) = &rating {
+                    q.push(" AND rating = ").push_bind(rating.as_str());
+                }
+                if let Some(source) = &source {
+                    q.push(" AND source = ").push_bind(source.as_str());
+                }
+            },
+            SortKey::desc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
     /// Get feedback statistics for an agent
     #[tracing::instrument(skip(self), level = "debug")]
     pub async fn get_feedback_stats_for_agent(&self, agent_id: Uuid) -> Result<FeedbackStats> {
@@ -2342,6 +2870,26 @@ impl Database {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// One page of experiments, newest first
+    pub async fn list_experiments_page(
+        &self,
+        status: Option<ExperimentStatus>,
+        page: &PageQuery,
+    ) -> Result<RowPage<Experiment>> {
+        self.fetch_page::<ExperimentRow, _>(
+            "SELECT * FROM experiments",
+            |q| {
+                if let Some(status) = status {
+                    q.push(" AND status = ").push_bind(status.as_str());
+                }
+            },
+            SortKey::desc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
     /// Update experiment status
     #[tracing::instrument(skip(self), level = "debug")]
     pub async fn update_experiment_status(
@@ -2532,13 +3080,17 @@ impl Database {
                 v.name as variant_name,
                 v.is_control,
                 COUNT(o.id) as sample_count,
-                COALESCE(AVG(o.metric_value), 0) as mean,
+                -- REAL defaults, as variants without observations would
+                -- otherwise return INTEGER zeros
+                COALESCE(AVG(o.metric_value), 0.0) as mean,
+                -- SQLite lacks SQRT without its math extension, so the
+                -- standard deviation is taken in Rust
                 COALESCE(
-                    SQRT(AVG(o.metric_value * o.metric_value) - AVG(o.metric_value) * AVG(o.metric_value)),
-                    0
-                ) as std_dev,
-                COALESCE(MIN(o.metric_value), 0) as min_value,
-                COALESCE(MAX(o.metric_value), 0) as max_value,
+                    AVG(o.metric_value * o.metric_value) - AVG(o.metric_value) * AVG(o.metric_value),
+                    0.0
+                ) as variance,
+                COALESCE(MIN(o.metric_value), 0.0) as min_value,
+                COALESCE(MAX(o.metric_value), 0.0) as max_value,
                 SUM(CASE WHEN o.metric_value >= 1.0 THEN 1 ELSE 0 END) as success_count
             FROM experiment_variants v
             LEFT JOIN experiment_assignments a ON v.id = a.variant_id
@@ -2555,6 +3107,36 @@ impl Database {
         Ok(rows.into_iter().map(|r| r.into()).collect())
     }
 
+    /// Get per-day results of each variant of an experiment, oldest first
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_experiment_daily_results(
+        &self,
+        experiment_id: i64,
+    ) -> Result<Vec<VariantDailyResults>> {
+        let rows = sqlx::query_as::<_, VariantDailyResultsRow>(
+            r#"
+            SELECT
+                v.id as variant_id,
+                v.name as variant_name,
+                DATE(o.recorded_at) as date,
+                COUNT(o.id) as sample_count,
+                AVG(o.metric_value) as mean,
+                SUM(CASE WHEN o.metric_value >= 1.0 THEN 1 ELSE 0 END) as success_count
+            FROM experiment_observations o
+            JOIN experiment_assignments a ON a.id = o.assignment_id
+            JOIN experiment_variants v ON v.id = a.variant_id
+            WHERE v.experiment_id = ?
+            GROUP BY v.id, v.name, DATE(o.recorded_at)
+            ORDER BY date, v.is_control DESC, v.id
+            "#,
+        )
+        .bind(experiment_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
     /// Get count of running experiments for an agent type
     #[tracing::instrument(skip(self), level = "debug")]
     pub async fn get_running_experiments_for_agent_type(
@@ -2587,14 +3169,178 @@ impl Database {
         Ok(result.rows_affected() > 0)
     }
 
-    // ==================== Model Selection Operations ====================
+    // ==================== Prompt Version Operations ====================
 
-    /// Record or update model performance for a task type
-    #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn record_model_performance(
+    /// Create a prompt version
+    pub async fn create_prompt_version(
         &self,
-        model: &str,
-        task_type: &str,
+        version: &crate::prompt_optimization::PromptVersion,
+    ) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO prompt_versions (agent_type, version, content, description, parent_version_id, is_active, created_at, activated_at, deactivated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&version.agent_type)
+        .bind(version.version)
+        .bind(&version.content)
+        .bind(&version.description)
+        .bind(version.parent_version_id)
+        .bind(version.is_active)
+        .bind(version.created_at.to_rfc3339())
+        .bind(version.activated_at.map(|dt| dt.to_rfc3339()))
+        .bind(version.deactivated_at.map(|dt| dt.to_rfc3339()))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get a prompt version by ID
+    pub async fn get_prompt_version(
+        &self,
+        id: i64,
+    ) -> Result<Option<crate::prompt_optimization::PromptVersion>> {
+        let row = sqlx::query_as::<_, PromptVersionRow>("SELECT * FROM prompt_versions WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// List prompt versions for an agent type, oldest first
+    pub async fn list_prompt_versions(
+        &self,
+        agent_type: &str,
+    ) -> Result<Vec<crate::prompt_optimization::PromptVersion>> {
+        let rows = sqlx::query_as::<_, PromptVersionRow>(
+            "SELECT * FROM prompt_versions WHERE agent_type = ? ORDER BY version ASC",
+        )
+        .bind(agent_type)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Get the prompt version with this exact content, creating the next version if none exists
+    ///
+    /// New versions are inactive and parented on the agent type's active version, if any.
+    pub async fn get_or_create_prompt_version(
+        &self,
+        agent_type: &str,
+        content: &str,
+        description: Option<&str>,
+    ) -> Result<crate::prompt_optimization::PromptVersion> {
+        let existing = sqlx::query_as::<_, PromptVersionRow>(
+            "SELECT * FROM prompt_versions WHERE agent_type = ? AND content = ? LIMIT 1",
+        )
+        .bind(agent_type)
+        .bind(content)
+        .fetch_optional(&self.pool)
+        .await?;
+        if let Some(row) = existing {
+            return row.try_into();
+        }
+
+        let latest: Option<i32> =
+            sqlx::query_scalar("SELECT MAX(version) FROM prompt_versions WHERE agent_type = ?")
+                .bind(agent_type)
+                .fetch_one(&self.pool)
+                .await?;
+        let active: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM prompt_versions WHERE agent_type = ? AND is_active = 1 LIMIT 1",
+        )
+        .bind(agent_type)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let mut version = crate::prompt_optimization::PromptVersion::new(
+            agent_type.to_string(),
+            latest.unwrap_or(0) + 1,
+            content.to_string(),
+        );
+        if let Some(description) = description {
+            version = version.with_description(description.to_string());
+        }
+        if let Some(parent_id) = active {
+            version = version.with_parent(parent_id);
+        }
+        version.id = self.create_prompt_version(&version).await?;
+        Ok(version)
+    }
+
+    /// Record a prompt version for an agent's system prompt override, if it has one
+    pub async fn record_agent_prompt_override(
+        &self,
+        agent: &Agent,
+    ) -> Result<Option<crate::prompt_optimization::PromptVersion>> {
+        let Some(ref prompt) = agent.system_prompt else {
+            return Ok(None);
+        };
+        let description = format!("System prompt override for agent {}", agent.id);
+        self.get_or_create_prompt_version(&agent.prompt_type_name(), prompt, Some(&description))
+            .await
+            .map(Some)
+    }
+
+    /// Record the outcome of a run that used a prompt version
+    pub async fn record_prompt_version_outcome(
+        &self,
+        prompt_version_id: i64,
+        success: bool,
+        tokens: i64,
+        duration_secs: f64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO prompt_effectiveness (prompt_version_id, usage_count, success_count, failure_count, total_tokens, total_duration_secs, updated_at)
+            VALUES (?, 1, ?, ?, ?, ?, datetime('now'))
+            ON CONFLICT(prompt_version_id) DO UPDATE SET
+                usage_count = usage_count + 1,
+                success_count = success_count + excluded.success_count,
+                failure_count = failure_count + excluded.failure_count,
+                total_tokens = total_tokens + excluded.total_tokens,
+                total_duration_secs = total_duration_secs + excluded.total_duration_secs,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(prompt_version_id)
+        .bind(success as i64)
+        .bind(!success as i64)
+        .bind(tokens)
+        .bind(duration_secs)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get effectiveness metrics for a prompt version
+    pub async fn get_prompt_effectiveness(
+        &self,
+        prompt_version_id: i64,
+    ) -> Result<Option<crate::prompt_optimization::PromptEffectiveness>> {
+        let row = sqlx::query_as::<_, PromptEffectivenessRow>(
+            "SELECT * FROM prompt_effectiveness WHERE prompt_version_id = ?",
+        )
+        .bind(prompt_version_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    // ==================== Model Selection Operations ====================
+
+    /// Record or update model performance for a task type
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn record_model_performance(
+        &self,
+        model: &str,
+        task_type: &str,
         agent_type: Option<&str>,
         success: bool,
         tokens: i64,
@@ -2877,14 +3623,18 @@ impl Database {
         messages_included: i32,
         messages_summarized: i32,
     ) -> Result<()> {
+        // Epic/story attribution is taken from the agent's context
         sqlx::query(
             r#"
             INSERT INTO session_token_stats (
                 session_id, agent_id, turn_number,
                 input_tokens, output_tokens,
                 cache_read_tokens, cache_write_tokens,
-                context_window_used, messages_included, messages_summarized
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                context_window_used, messages_included, messages_summarized,
+                epic_id, story_id
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?,
+                (SELECT json_extract(context, '$.epic_id') FROM agents WHERE id = ?),
+                (SELECT json_extract(context, '$.story_id') FROM agents WHERE id = ?))
             "#,
         )
         .bind(session_id)
@@ -2897,6 +3647,8 @@ impl Database {
         .bind(context_window_used)
         .bind(messages_included)
         .bind(messages_summarized)
+        .bind(agent_id.to_string())
+        .bind(agent_id.to_string())
         .execute(&self.pool)
         .await?;
 
@@ -3103,438 +3855,515 @@ impl Database {
         Ok(row.into())
     }
 
-    // ==================== Schedule Operations ====================
-
-    /// Insert a new schedule
-    #[tracing::instrument(skip(self, schedule), level = "debug", fields(name = %schedule.name))]
-    pub async fn insert_schedule(&self, schedule: &Schedule) -> Result<i64> {
-        let result = sqlx::query(
+    /// Get token stats for all turns attributed to a story
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_story_token_stats(&self, story_id: &str) -> Result<TokenStats> {
+        let row = sqlx::query_as::<_, TokenStatsRow>(
             r#"
-            INSERT INTO schedules (name, cron_expression, agent_type, task, enabled, last_run, next_run, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            SELECT
+                COUNT(*) as turn_count,
+                COALESCE(SUM(input_tokens), 0) as total_input_tokens,
+                COALESCE(SUM(output_tokens), 0) as total_output_tokens,
+                COALESCE(SUM(cache_read_tokens), 0) as total_cache_read_tokens,
+                COALESCE(SUM(cache_write_tokens), 0) as total_cache_write_tokens,
+                CAST(COALESCE(AVG(context_window_used), 0) AS REAL) as avg_context_used,
+                CAST(COALESCE(AVG(messages_included), 0) AS REAL) as avg_messages_included,
+                COALESCE(SUM(messages_summarized), 0) as total_messages_summarized
+            FROM session_token_stats
+            WHERE story_id = ?
             "#,
         )
-        .bind(&schedule.name)
-        .bind(&schedule.cron_expression)
-        .bind(&schedule.agent_type)
-        .bind(&schedule.task)
-        .bind(schedule.enabled)
-        .bind(schedule.last_run.map(|dt| dt.to_rfc3339()))
-        .bind(schedule.next_run.map(|dt| dt.to_rfc3339()))
-        .bind(schedule.created_at.to_rfc3339())
-        .execute(&self.pool)
+        .bind(story_id)
+        .fetch_one(&self.pool)
         .await?;
 
-        Ok(result.last_insert_rowid())
-    }
-
-    /// Get a schedule by ID
-    #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn get_schedule(&self, id: i64) -> Result<Option<Schedule>> {
-        let row = sqlx::query_as::<_, ScheduleRow>("SELECT * FROM schedules WHERE id = ?")
-            .bind(id)
-            .fetch_optional(&self.pool)
-            .await?;
-
-        row.map(|r| r.try_into()).transpose()
-    }
-
-    /// Get a schedule by name
-    #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn get_schedule_by_name(&self, name: &str) -> Result<Option<Schedule>> {
-        let row = sqlx::query_as::<_, ScheduleRow>("SELECT * FROM schedules WHERE name = ?")
-            .bind(name)
-            .fetch_optional(&self.pool)
-            .await?;
-
-        row.map(|r| r.try_into()).transpose()
+        Ok(row.into())
     }
 
-    /// List all schedules with optional enabled filter
+    /// Estimated tokens and cost of one agent of `agent_type`
+    ///
+    /// Averages the recorded usage of earlier agents of the type, matching
+    /// pipeline agent names such as `code-reviewer` against `code_reviewer`.
+    /// Types without recorded usage get a default estimate.
     #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn list_schedules(&self, enabled_only: bool) -> Result<Vec<Schedule>> {
-        let rows = if enabled_only {
-            sqlx::query_as::<_, ScheduleRow>(
-                "SELECT * FROM schedules WHERE enabled = 1 ORDER BY created_at DESC",
-            )
-            .fetch_all(&self.pool)
-            .await?
-        } else {
-            sqlx::query_as::<_, ScheduleRow>("SELECT * FROM schedules ORDER BY created_at DESC")
-                .fetch_all(&self.pool)
-                .await?
-        };
-
-        rows.into_iter().map(|r| r.try_into()).collect()
-    }
-
-    /// Update a schedule
-    #[tracing::instrument(skip(self, schedule), level = "debug", fields(id = schedule.id))]
-    pub async fn update_schedule(&self, schedule: &Schedule) -> Result<()> {
-        sqlx::query(
+    pub async fn estimate_agent_tokens(&self, agent_type: &str) -> Result<AgentTokenEstimate> {
+        let (agents, input_tokens, output_tokens, cache_read_tokens, cache_write_tokens): (
+            i64,
+            i64,
+            i64,
+            i64,
+            i64,
+        ) = sqlx::query_as(
             r#"
-            UPDATE schedules SET
-                name = ?, cron_expression = ?, agent_type = ?, task = ?,
-                enabled = ?, last_run = ?, next_run = ?
-            WHERE id = ?
+            SELECT
+                COUNT(DISTINCT s.agent_id),
+                COALESCE(SUM(s.input_tokens), 0),
+                COALESCE(SUM(s.output_tokens), 0),
+                COALESCE(SUM(s.cache_read_tokens), 0),
+                COALESCE(SUM(s.cache_write_tokens), 0)
+            FROM session_token_stats s
+            JOIN agents a ON a.id = s.agent_id
+            WHERE a.agent_type = ?
             "#,
         )
-        .bind(&schedule.name)
-        .bind(&schedule.cron_expression)
-        .bind(&schedule.agent_type)
-        .bind(&schedule.task)
-        .bind(schedule.enabled)
-        .bind(schedule.last_run.map(|dt| dt.to_rfc3339()))
-        .bind(schedule.next_run.map(|dt| dt.to_rfc3339()))
-        .bind(schedule.id)
-        .execute(&self.pool)
+        .bind(agent_type.replace('-', "_"))
+        .fetch_one(&self.pool)
         .await?;
 
-        Ok(())
+        if agents == 0 {
+            let (input, output) = (
+                DEFAULT_AGENT_INPUT_TOKENS as i64,
+                DEFAULT_AGENT_OUTPUT_TOKENS as i64,
+            );
+            return Ok(AgentTokenEstimate {
+                tokens: (input + output) as u64,
+                cost_usd: Self::calculate_token_cost("sonnet", input, output, 0, 0),
+                sampled_agents: 0,
+            });
+        }
+
+        Ok(AgentTokenEstimate {
+            tokens: ((input_tokens + output_tokens) / agents).max(0) as u64,
+            cost_usd: Self::calculate_token_cost(
+                "sonnet",
+                input_tokens,
+                output_tokens,
+                cache_read_tokens,
+                cache_write_tokens,
+            ) / agents as f64,
+            sampled_agents: agents as u64,
+        })
     }
 
-    /// Delete a schedule
-    #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn delete_schedule(&self, id: i64) -> Result<bool> {
-        let result = sqlx::query("DELETE FROM schedules WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+    // ==================== Cost Attribution Operations ====================
 
-        Ok(result.rows_affected() > 0)
+    /// Attribute a request's token usage to the epic and story in an agent's context
+    #[tracing::instrument(skip(self, context), level = "debug")]
+    pub async fn attribute_token_usage(
+        &self,
+        context: &crate::AgentContext,
+        model: &str,
+        input_tokens: i64,
+        output_tokens: i64,
+        cache_read_tokens: i64,
+        cache_write_tokens: i64,
+    ) -> Result<()> {
+        if let Some(epic_id) = &context.epic_id {
+            self.update_cost_by_epic(
+                epic_id,
+                model,
+                input_tokens,
+                output_tokens,
+                cache_read_tokens,
+                cache_write_tokens,
+            )
+            .await?;
+        }
+        if let Some(story_id) = &context.story_id {
+            self.update_cost_by_story(
+                story_id,
+                model,
+                input_tokens,
+                output_tokens,
+                cache_read_tokens,
+                cache_write_tokens,
+            )
+            .await?;
+        }
+        Ok(())
     }
 
-    /// Get schedules that are due for execution
+    /// Add a request's token usage to today's cost for an epic
     #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn get_due_schedules(&self) -> Result<Vec<Schedule>> {
-        let now = chrono::Utc::now().to_rfc3339();
-        let rows = sqlx::query_as::<_, ScheduleRow>(
-            "SELECT * FROM schedules WHERE enabled = 1 AND next_run <= ? ORDER BY next_run ASC",
+    pub async fn update_cost_by_epic(
+        &self,
+        epic_id: &str,
+        model: &str,
+        input_tokens: i64,
+        output_tokens: i64,
+        cache_read_tokens: i64,
+        cache_write_tokens: i64,
+    ) -> Result<()> {
+        self.upsert_attributed_cost(
+            "cost_by_epic",
+            "epic_id",
+            epic_id,
+            model,
+            [input_tokens, output_tokens, cache_read_tokens, cache_write_tokens],
         )
-        .bind(now)
-        .fetch_all(&self.pool)
-        .await?;
-
-        rows.into_iter().map(|r| r.try_into()).collect()
+        .await
     }
 
-    /// Try to acquire a lock for schedule execution
-    ///
-    /// Returns true if lock was acquired, false if schedule is already locked.
-    /// Locks expire after 5 minutes to prevent deadlocks.
+    /// Add a request's token usage to today's cost for a story
     #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn try_lock_schedule(&self, schedule_id: i64) -> Result<bool> {
-        let now = chrono::Utc::now();
-        let lock_expiry = now - chrono::Duration::minutes(5);
-
-        // Try to acquire lock using UPDATE with WHERE clause
-        // This will only update if the schedule is not locked or lock has expired
-        let result = sqlx::query(
-            r#"
-            UPDATE schedules
-            SET locked_at = ?
-            WHERE id = ?
-            AND (locked_at IS NULL OR locked_at < ?)
-            "#,
+    pub async fn update_cost_by_story(
+        &self,
+        story_id: &str,
+        model: &str,
+        input_tokens: i64,
+        output_tokens: i64,
+        cache_read_tokens: i64,
+        cache_write_tokens: i64,
+    ) -> Result<()> {
+        self.upsert_attributed_cost(
+            "cost_by_story",
+            "story_id",
+            story_id,
+            model,
+            [input_tokens, output_tokens, cache_read_tokens, cache_write_tokens],
         )
-        .bind(now.to_rfc3339())
-        .bind(schedule_id)
-        .bind(lock_expiry.to_rfc3339())
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.rows_affected() > 0)
+        .await
     }
 
-    /// Release a schedule lock
-    #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn unlock_schedule(&self, schedule_id: i64) -> Result<()> {
-        sqlx::query("UPDATE schedules SET locked_at = NULL WHERE id = ?")
-            .bind(schedule_id)
+    /// Upsert into one of the per-entity cost tables (`tokens` is input, output,
+    /// cache read, cache write)
+    async fn upsert_attributed_cost(
+        &self,
+        table: &str,
+        id_column: &str,
+        entity_id: &str,
+        model: &str,
+        tokens: [i64; 4],
+    ) -> Result<()> {
+        let [input_tokens, output_tokens, cache_read_tokens, cache_write_tokens] = tokens;
+        let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        let estimated_cost = Self::calculate_token_cost(
+            model,
+            input_tokens,
+            output_tokens,
+            cache_read_tokens,
+            cache_write_tokens,
+        );
+
+        // Table and column names come from the fixed callers above, never from input
+        let query = format!(
+            r#"
+            INSERT INTO {table} (
+                date, {id_column}, model,
+                total_input_tokens, total_output_tokens,
+                total_cache_read_tokens, total_cache_write_tokens,
+                request_count, estimated_cost_usd, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?)
+            ON CONFLICT(date, {id_column}, model) DO UPDATE SET
+                total_input_tokens = total_input_tokens + excluded.total_input_tokens,
+                total_output_tokens = total_output_tokens + excluded.total_output_tokens,
+                total_cache_read_tokens = total_cache_read_tokens + excluded.total_cache_read_tokens,
+                total_cache_write_tokens = total_cache_write_tokens + excluded.total_cache_write_tokens,
+                request_count = request_count + 1,
+                estimated_cost_usd = estimated_cost_usd + excluded.estimated_cost_usd,
+                updated_at = excluded.updated_at
+            "#
+        );
+
+        sqlx::query(&query)
+            .bind(&date)
+            .bind(entity_id)
+            .bind(model)
+            .bind(input_tokens)
+            .bind(output_tokens)
+            .bind(cache_read_tokens)
+            .bind(cache_write_tokens)
+            .bind(estimated_cost)
+            .bind(&now)
+            .bind(&now)
             .execute(&self.pool)
             .await?;
 
         Ok(())
     }
 
-    /// Insert a schedule run record
-    #[tracing::instrument(skip(self, run), level = "debug", fields(schedule_id = run.schedule_id))]
-    pub async fn insert_schedule_run(&self, run: &ScheduleRun) -> Result<i64> {
-        let result = sqlx::query(
-            r#"
-            INSERT INTO schedule_runs (schedule_id, agent_id, started_at, completed_at, status, error_message)
-            VALUES (?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(run.schedule_id)
-        .bind(&run.agent_id)
-        .bind(run.started_at.to_rfc3339())
-        .bind(run.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(run.status.as_str())
-        .bind(&run.error_message)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_rowid())
-    }
-
-    /// Update a schedule run
-    #[tracing::instrument(skip(self, run), level = "debug", fields(id = run.id))]
-    pub async fn update_schedule_run(&self, run: &ScheduleRun) -> Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE schedule_runs SET
-                agent_id = ?, completed_at = ?, status = ?, error_message = ?
-            WHERE id = ?
+    /// Get daily per-model costs for an epic over the last `days` days
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_costs_by_epic(
+        &self,
+        epic_id: &str,
+        days: i32,
+    ) -> Result<Vec<crate::cost_analytics::CostRecord>> {
+        let rows = sqlx::query_as::<_, CostRecordRow>(
+            r#"
+            SELECT date, epic_id as entity_id, model,
+                   total_input_tokens, total_output_tokens,
+                   total_cache_read_tokens, total_cache_write_tokens,
+                   request_count, estimated_cost_usd
+            FROM cost_by_epic
+            WHERE epic_id = ? AND date >= date('now', '-' || ? || ' days')
+            ORDER BY date DESC, model
             "#,
         )
-        .bind(&run.agent_id)
-        .bind(run.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(run.status.as_str())
-        .bind(&run.error_message)
-        .bind(run.id)
-        .execute(&self.pool)
+        .bind(epic_id)
+        .bind(days)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(rows.into_iter().map(Into::into).collect())
     }
 
-    /// Get schedule runs for a schedule
+    /// Get daily per-model costs for a story over the last `days` days
     #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn get_schedule_runs(&self, schedule_id: i64, limit: i64) -> Result<Vec<ScheduleRun>> {
-        let rows = sqlx::query_as::<_, ScheduleRunRow>(
-            "SELECT * FROM schedule_runs WHERE schedule_id = ? ORDER BY started_at DESC LIMIT ?",
+    pub async fn get_costs_by_story(
+        &self,
+        story_id: &str,
+        days: i32,
+    ) -> Result<Vec<crate::cost_analytics::CostRecord>> {
+        let rows = sqlx::query_as::<_, CostRecordRow>(
+            r#"
+            SELECT date, story_id as entity_id, model,
+                   total_input_tokens, total_output_tokens,
+                   total_cache_read_tokens, total_cache_write_tokens,
+                   request_count, estimated_cost_usd
+            FROM cost_by_story
+            WHERE story_id = ? AND date >= date('now', '-' || ? || ' days')
+            ORDER BY date DESC, model
+            "#,
         )
-        .bind(schedule_id)
-        .bind(limit)
+        .bind(story_id)
+        .bind(days)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.try_into()).collect()
+        Ok(rows.into_iter().map(Into::into).collect())
     }
 
-    // ==================== Webhook Event Operations ====================
-
-    /// Insert a new webhook event (idempotent by delivery_id)
-    #[tracing::instrument(skip(self, event), level = "debug", fields(delivery_id = %event.delivery_id))]
-    pub async fn insert_webhook_event(&self, event: &WebhookEvent) -> Result<i64> {
-        let result = sqlx::query(
-            r#"
-            INSERT INTO webhook_events (
-                delivery_id, event_type, payload, status, retry_count, max_retries,
-                error_message, next_retry_at, received_at, processed_at, created_at, updated_at
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            ON CONFLICT(delivery_id) DO NOTHING
+    /// Total cost per epic, most expensive first (`days: None` for all time)
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn list_epic_costs(
+        &self,
+        days: Option<i32>,
+    ) -> Result<Vec<crate::cost_analytics::EntityCostSummary>> {
+        let rows = sqlx::query_as::<_, EntityCostSummaryRow>(
+            r#"
+            SELECT epic_id as entity_id,
+                   SUM(total_input_tokens) as total_input_tokens,
+                   SUM(total_output_tokens) as total_output_tokens,
+                   SUM(request_count) as request_count,
+                   SUM(estimated_cost_usd) as estimated_cost_usd
+            FROM cost_by_epic
+            WHERE (? IS NULL OR date >= date('now', '-' || ? || ' days'))
+            GROUP BY epic_id
+            ORDER BY estimated_cost_usd DESC, epic_id
             "#,
         )
-        .bind(&event.delivery_id)
-        .bind(&event.event_type)
-        .bind(&event.payload)
-        .bind(event.status.as_str())
-        .bind(event.retry_count)
-        .bind(event.max_retries)
-        .bind(&event.error_message)
-        .bind(event.next_retry_at.map(|dt| dt.to_rfc3339()))
-        .bind(event.received_at.to_rfc3339())
-        .bind(event.processed_at.map(|dt| dt.to_rfc3339()))
-        .bind(event.created_at.to_rfc3339())
-        .bind(event.updated_at.to_rfc3339())
-        .execute(&self.pool)
+        .bind(days)
+        .bind(days)
+        .fetch_all(&self.pool)
         .await?;
 
-        // If insert was ignored due to conflict, fetch the existing ID
-        if result.rows_affected() == 0 {
-            let id = sqlx::query_scalar::<_, i64>(
-                "SELECT id FROM webhook_events WHERE delivery_id = ?",
-            )
-            .bind(&event.delivery_id)
-            .fetch_one(&self.pool)
-            .await?;
-            Ok(id)
-        } else {
-            Ok(result.last_insert_rowid())
-        }
+        Ok(rows.into_iter().map(Into::into).collect())
     }
 
-    /// Get webhook event by ID
+    /// Total cost per story, most expensive first, optionally limited to one epic
+    /// (`days: None` for all time)
     #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn get_webhook_event(&self, id: i64) -> Result<Option<WebhookEvent>> {
-        let row = sqlx::query_as::<_, WebhookEventRow>(
-            "SELECT * FROM webhook_events WHERE id = ?",
+    pub async fn list_story_costs(
+        &self,
+        epic_id: Option<&str>,
+        days: Option<i32>,
+    ) -> Result<Vec<crate::cost_analytics::EntityCostSummary>> {
+        let rows = sqlx::query_as::<_, EntityCostSummaryRow>(
+            r#"
+            SELECT c.story_id as entity_id,
+                   SUM(c.total_input_tokens) as total_input_tokens,
+                   SUM(c.total_output_tokens) as total_output_tokens,
+                   SUM(c.request_count) as request_count,
+                   SUM(c.estimated_cost_usd) as estimated_cost_usd
+            FROM cost_by_story c
+            JOIN stories s ON s.id = c.story_id
+            WHERE (? IS NULL OR s.epic_id = ?)
+              AND (? IS NULL OR c.date >= date('now', '-' || ? || ' days'))
+            GROUP BY c.story_id
+            ORDER BY estimated_cost_usd DESC, c.story_id
+            "#,
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(epic_id)
+        .bind(epic_id)
+        .bind(days)
+        .bind(days)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.try_into()).transpose()
+        Ok(rows.into_iter().map(Into::into).collect())
     }
 
-    /// Get webhook event by delivery ID
+    /// Total cost per model, most expensive first (`days: None` for all time)
     #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn get_webhook_event_by_delivery_id(
+    pub async fn list_model_costs(
         &self,
-        delivery_id: &str,
-    ) -> Result<Option<WebhookEvent>> {
-        let row = sqlx::query_as::<_, WebhookEventRow>(
-            "SELECT * FROM webhook_events WHERE delivery_id = ?",
+        days: Option<i32>,
+    ) -> Result<Vec<crate::cost_analytics::EntityCostSummary>> {
+        let rows = sqlx::query_as::<_, EntityCostSummaryRow>(
+            r#"
+            SELECT model as entity_id,
+                   SUM(total_input_tokens) as total_input_tokens,
+                   SUM(total_output_tokens) as total_output_tokens,
+                   SUM(request_count) as request_count,
+                   COALESCE(SUM(estimated_cost_usd), 0.0) as estimated_cost_usd
+            FROM daily_token_usage
+            WHERE (? IS NULL OR date >= date('now', '-' || ? || ' days'))
+            GROUP BY model
+            ORDER BY estimated_cost_usd DESC, model
+            "#,
         )
-        .bind(delivery_id)
-        .fetch_optional(&self.pool)
+        .bind(days)
+        .bind(days)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.try_into()).transpose()
+        Ok(rows.into_iter().map(Into::into).collect())
     }
 
-    /// Get pending webhook events ready for processing
+    /// Total cost per agent type, most expensive first (`days: None` for all time)
+    ///
+    /// Session stats don't record the model, so usage is priced as Sonnet like
+    /// [`Self::estimate_agent_tokens`].
     #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn get_pending_webhook_events(&self, limit: i64) -> Result<Vec<WebhookEvent>> {
-        let now = chrono::Utc::now().to_rfc3339();
-        let rows = sqlx::query_as::<_, WebhookEventRow>(
-            r#"
-            SELECT * FROM webhook_events
-            WHERE status = 'pending'
-            AND (next_retry_at IS NULL OR next_retry_at <= ?)
-            ORDER BY received_at ASC
-            LIMIT ?
+    pub async fn list_agent_type_costs(
+        &self,
+        days: Option<i32>,
+    ) -> Result<Vec<crate::cost_analytics::EntityCostSummary>> {
+        let rows: Vec<(String, i64, i64, i64, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT a.agent_type,
+                   COALESCE(SUM(s.input_tokens), 0),
+                   COALESCE(SUM(s.output_tokens), 0),
+                   COALESCE(SUM(s.cache_read_tokens), 0),
+                   COALESCE(SUM(s.cache_write_tokens), 0),
+                   COUNT(*)
+            FROM session_token_stats s
+            JOIN agents a ON a.id = s.agent_id
+            WHERE (? IS NULL OR s.created_at >= datetime('now', '-' || ? || ' days'))
+            GROUP BY a.agent_type
             "#,
         )
-        .bind(&now)
-        .bind(limit)
+        .bind(days)
+        .bind(days)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.try_into()).collect()
+        let mut costs: Vec<crate::cost_analytics::EntityCostSummary> = rows
+            .into_iter()
+            .map(
+                |(agent_type, input, output, cache_read, cache_write, requests)| {
+                    crate::cost_analytics::EntityCostSummary {
+                        entity_id: agent_type,
+                        total_input_tokens: input,
+                        total_output_tokens: output,
+                        request_count: requests,
+                        estimated_cost_usd: Self::calculate_token_cost(
+                            "sonnet",
+                            input,
+                            output,
+                            cache_read,
+                            cache_write,
+                        ),
+                    }
+                },
+            )
+            .collect();
+        costs.sort_by(|a, b| {
+            b.estimated_cost_usd
+                .total_cmp(&a.estimated_cost_usd)
+                .then_with(|| a.entity_id.cmp(&b.entity_id))
+        });
+        Ok(costs)
     }
 
-    /// Update webhook event status and metadata
-    #[tracing::instrument(skip(self, event), level = "debug", fields(id = event.id))]
-    pub async fn update_webhook_event(&self, event: &WebhookEvent) -> Result<()> {
-        let id = event.id.ok_or_else(|| {
-            crate::Error::Other("Cannot update webhook event without ID".to_string())
-        })?;
-
-        sqlx::query(
+    /// Store a cost budget, replacing earlier ones from its start date
+    #[tracing::instrument(skip(self, budget), level = "debug")]
+    pub async fn insert_cost_budget(
+        &self,
+        budget: &crate::cost_analytics::CostBudget,
+    ) -> Result<i64> {
+        let result = sqlx::query(
             r#"
-            UPDATE webhook_events SET
-                status = ?,
-                retry_count = ?,
-                error_message = ?,
-                next_retry_at = ?,
-                processed_at = ?,
-                updated_at = ?
-            WHERE id = ?
+            INSERT INTO cost_budgets (period_type, amount_usd, alert_threshold_percent, start_date)
+            VALUES (?, ?, ?, ?)
             "#,
         )
-        .bind(event.status.as_str())
-        .bind(event.retry_count)
-        .bind(&event.error_message)
-        .bind(event.next_retry_at.map(|dt| dt.to_rfc3339()))
-        .bind(event.processed_at.map(|dt| dt.to_rfc3339()))
-        .bind(event.updated_at.to_rfc3339())
-        .bind(id)
+        .bind(budget.period_type.to_string())
+        .bind(budget.amount_usd)
+        .bind(budget.alert_threshold_percent)
+        .bind(&budget.start_date)
         .execute(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(result.last_insert_rowid())
     }
 
-    /// Get webhook events by status
+    /// The budget in effect today, if any
     #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn get_webhook_events_by_status(
+    pub async fn get_current_cost_budget(
         &self,
-        status: WebhookEventStatus,
-        limit: i64,
-    ) -> Result<Vec<WebhookEvent>> {
-        let rows = sqlx::query_as::<_, WebhookEventRow>(
+    ) -> Result<Option<crate::cost_analytics::CostBudget>> {
+        let row: Option<(i64, String, f64, i32, String, String, String)> = sqlx::query_as(
             r#"
-            SELECT * FROM webhook_events
-            WHERE status = ?
-            ORDER BY received_at DESC
-            LIMIT ?
+            SELECT id, period_type, amount_usd, alert_threshold_percent, start_date,
+                   created_at, updated_at
+            FROM cost_budgets
+            WHERE start_date <= date('now')
+            ORDER BY start_date DESC, id DESC
+            LIMIT 1
             "#,
         )
-        .bind(status.as_str())
-        .bind(limit)
-        .fetch_all(&self.pool)
+        .fetch_optional(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.try_into()).collect()
+        row.map(
+            |(id, period_type, amount_usd, alert_threshold_percent, start_date, created_at, updated_at)| {
+                Ok(crate::cost_analytics::CostBudget {
+                    id: Some(id),
+                    period_type: period_type.parse().map_err(crate::Error::Other)?,
+                    amount_usd,
+                    alert_threshold_percent,
+                    start_date,
+                    created_at: Some(created_at),
+                    updated_at: Some(updated_at),
+                })
+            },
+        )
+        .transpose()
     }
 
-    /// Count webhook events by status
-    #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn count_webhook_events_by_status(
-        &self,
-        status: WebhookEventStatus,
-    ) -> Result<i64> {
-        let count = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM webhook_events WHERE status = ?",
-        )
-        .bind(status.as_str())
-        .fetch_one(&self.pool)
-        .await?;
-
-        Ok(count)
-    }
-
-    /// Delete old completed webhook events (for cleanup)
-    #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn delete_old_webhook_events(&self, days: i64) -> Result<u64> {
-        let result = sqlx::query(
-            r#"
-            DELETE FROM webhook_events
-            WHERE status IN ('completed', 'dead_letter')
-            AND received_at < datetime('now', '-' || ? || ' days')
-            "#,
-        )
-        .bind(days)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.rows_affected())
-    }
-
-    /// Get recent webhook events (all statuses)
-    #[tracing::instrument(skip(self), level = "debug")]
-    pub async fn get_recent_webhook_events(&self, limit: i64) -> Result<Vec<WebhookEvent>> {
-        let rows = sqlx::query_as::<_, WebhookEventRow>(
-            r#"
-            SELECT * FROM webhook_events
-            ORDER BY received_at DESC
-            LIMIT ?
-            "#,
-        )
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
-
-        rows.into_iter().map(|r| r.try_into()).collect()
-    }
-
-    // ==================== Pipeline Operations ====================
+    // ==================== Schedule Operations ====================
 
-    /// Insert a new pipeline
-    pub async fn insert_pipeline(&self, pipeline: &crate::Pipeline) -> Result<i64> {
+    /// Insert a new schedule
+    #[tracing::instrument(skip(self, schedule), level = "debug", fields(name = %schedule.name))]
+    pub async fn insert_schedule(&self, schedule: &Schedule) -> Result<i64> {
         let result = sqlx::query(
             r#"
-            INSERT INTO pipelines (name, definition, enabled, created_at)
-            VALUES (?, ?, ?, ?)
+            INSERT INTO schedules (name, cron_expression, run_at, timezone, agent_type, task, enabled, jitter_secs, overlap_policy, depends_on, max_tokens, max_cost_usd, spend_cap_period, missed_runs, consecutive_missed_runs, max_consecutive_misses, last_run, next_run, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(&pipeline.name)
-        .bind(&pipeline.definition)
-        .bind(pipeline.enabled as i32)
-        .bind(pipeline.created_at.to_rfc3339())
+        .bind(&schedule.name)
+        .bind(&schedule.cron_expression)
+        .bind(schedule.run_at.map(|dt| dt.to_rfc3339()))
+        .bind(&schedule.timezone)
+        .bind(&schedule.agent_type)
+        .bind(&schedule.task)
+        .bind(schedule.enabled)
+        .bind(schedule.jitter_secs as i64)
+        .bind(schedule.overlap_policy.as_str())
+        .bind(&schedule.depends_on)
+        .bind(schedule.max_tokens.map(|tokens| tokens as i64))
+        .bind(schedule.max_cost_usd)
+        .bind(schedule.spend_cap_period.to_string())
+        .bind(schedule.missed_runs as i64)
+        .bind(schedule.consecutive_missed_runs as i64)
+        .bind(schedule.max_consecutive_misses.map(|misses| misses as i64))
+        .bind(schedule.last_run.map(|dt| dt.to_rfc3339()))
+        .bind(schedule.next_run.map(|dt| dt.to_rfc3339()))
+        .bind(schedule.created_at.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
         Ok(result.last_insert_rowid())
     }
 
-    /// Get pipeline by ID
-    pub async fn get_pipeline(&self, id: i64) -> Result<Option<crate::Pipeline>> {
-        let row = sqlx::query_as::<_, PipelineRow>("SELECT * FROM pipelines WHERE id = ?")
+    /// Get a schedule by ID
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_schedule(&self, id: i64) -> Result<Option<Schedule>> {
+        let row = sqlx::query_as::<_, ScheduleRow>("SELECT * FROM schedules WHERE id = ?")
             .bind(id)
             .fetch_optional(&self.pool)
             .await?;
@@ -3542,9 +4371,10 @@ impl Database {
         row.map(|r| r.try_into()).transpose()
     }
 
-    /// Get pipeline by name
-    pub async fn get_pipeline_by_name(&self, name: &str) -> Result<Option<crate::Pipeline>> {
-        let row = sqlx::query_as::<_, PipelineRow>("SELECT * FROM pipelines WHERE name = ?")
+    /// Get a schedule by name
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_schedule_by_name(&self, name: &str) -> Result<Option<Schedule>> {
+        let row = sqlx::query_as::<_, ScheduleRow>("SELECT * FROM schedules WHERE name = ?")
             .bind(name)
             .fetch_optional(&self.pool)
             .await?;
@@ -3552,287 +4382,467 @@ impl Database {
         row.map(|r| r.try_into()).transpose()
     }
 
-    /// Update pipeline
-    pub async fn update_pipeline(&self, pipeline: &crate::Pipeline) -> Result<()> {
-        let id = pipeline
-            .id
-            .ok_or_else(|| crate::Error::Other("Cannot update pipeline without ID".to_string()))?;
+    /// List all schedules with optional enabled filter
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn list_schedules(&self, enabled_only: bool) -> Result<Vec<Schedule>> {
+        let rows = if enabled_only {
+            sqlx::query_as::<_, ScheduleRow>(
+                "SELECT * FROM schedules WHERE enabled = 1 ORDER BY created_at DESC",
+            )
+            .fetch_all(&self.pool)
+            .await?
+        } else {
+            sqlx::query_as::<_, ScheduleRow>("SELECT * FROM schedules ORDER BY created_at DESC")
+                .fetch_all(&self.pool)
+                .await?
+        };
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// One page of schedules, in creation order
+    pub async fn list_schedules_page(&self, page: &PageQuery) -> Result<RowPage<Schedule>> {
+        self.fetch_page::<ScheduleRow, _>(
+            "SELECT * FROM schedules",
+            |_| {},
+            SortKey::asc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
 
+    /// Update a schedule
+    #[tracing::instrument(skip(self, schedule), level = "debug", fields(id = schedule.id))]
+    pub async fn update_schedule(&self, schedule: &Schedule) -> Result<()> {
         sqlx::query(
             r#"
-            UPDATE pipelines SET
-                name = ?,
-                definition = ?,
-                enabled = ?
+            UPDATE schedules SET
+                name = ?, cron_expression = ?, run_at = ?, timezone = ?, agent_type = ?, task = ?,
+                enabled = ?, jitter_secs = ?, overlap_policy = ?, depends_on = ?, max_tokens = ?,
+                max_cost_usd = ?, spend_cap_period = ?, missed_runs = ?, consecutive_missed_runs = ?,
+                max_consecutive_misses = ?, last_run = ?, next_run = ?
             WHERE id = ?
             "#,
         )
-        .bind(&pipeline.name)
-        .bind(&pipeline.definition)
-        .bind(pipeline.enabled as i32)
-        .bind(id)
+        .bind(&schedule.name)
+        .bind(&schedule.cron_expression)
+        .bind(schedule.run_at.map(|dt| dt.to_rfc3339()))
+        .bind(&schedule.timezone)
+        .bind(&schedule.agent_type)
+        .bind(&schedule.task)
+        .bind(schedule.enabled)
+        .bind(schedule.jitter_secs as i64)
+        .bind(schedule.overlap_policy.as_str())
+        .bind(&schedule.depends_on)
+        .bind(schedule.max_tokens.map(|tokens| tokens as i64))
+        .bind(schedule.max_cost_usd)
+        .bind(schedule.spend_cap_period.to_string())
+        .bind(schedule.missed_runs as i64)
+        .bind(schedule.consecutive_missed_runs as i64)
+        .bind(schedule.max_consecutive_misses.map(|misses| misses as i64))
+        .bind(schedule.last_run.map(|dt| dt.to_rfc3339()))
+        .bind(schedule.next_run.map(|dt| dt.to_rfc3339()))
+        .bind(schedule.id)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
 
-    /// List all pipelines
-    pub async fn list_pipelines(&self) -> Result<Vec<crate::Pipeline>> {
-        let rows = sqlx::query_as::<_, PipelineRow>("SELECT * FROM pipelines ORDER BY name ASC")
-            .fetch_all(&self.pool)
+    /// Check that `schedule_name` may depend on the schedule named `depends_on`
+    ///
+    /// The dependency must exist, and following its own dependencies must not
+    /// lead back to `schedule_name`.
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn validate_schedule_dependency(
+        &self,
+        schedule_name: &str,
+        depends_on: &str,
+    ) -> Result<()> {
+        if depends_on == schedule_name {
+            return Err(crate::Error::Other(format!(
+                "Schedule '{}' cannot depend on itself",
+                schedule_name
+            )));
+        }
+
+        let mut current = self
+            .get_schedule_by_name(depends_on)
+            .await?
+            .ok_or_else(|| {
+                crate::Error::Other(format!("Dependency schedule '{}' not found", depends_on))
+            })?;
+        let mut visited = std::collections::HashSet::new();
+        while let Some(next) = current.depends_on.take() {
+            if next == schedule_name {
+                return Err(crate::Error::Other(format!(
+                    "Schedule dependency cycle: '{}' already depends on '{}'",
+                    depends_on, schedule_name
+                )));
+            }
+            if !visited.insert(next.clone()) {
+                break;
+            }
+            match self.get_schedule_by_name(&next).await? {
+                Some(schedule) => current = schedule,
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Delete a schedule
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn delete_schedule(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM schedules WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
             .await?;
 
-        rows.into_iter().map(|r| r.try_into()).collect()
+        Ok(result.rows_affected() > 0)
     }
 
-    /// List enabled pipelines
-    pub async fn list_enabled_pipelines(&self) -> Result<Vec<crate::Pipeline>> {
-        let rows = sqlx::query_as::<_, PipelineRow>(
-            "SELECT * FROM pipelines WHERE enabled = 1 ORDER BY name ASC",
+    /// Get schedules that are due for execution
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_due_schedules(&self) -> Result<Vec<Schedule>> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let rows = sqlx::query_as::<_, ScheduleRow>(
+            "SELECT * FROM schedules WHERE enabled = 1 AND next_run <= ? ORDER BY next_run ASC",
         )
+        .bind(now)
         .fetch_all(&self.pool)
         .await?;
 
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
-    /// Delete pipeline
-    pub async fn delete_pipeline(&self, id: i64) -> Result<()> {
-        sqlx::query("DELETE FROM pipelines WHERE id = ?")
-            .bind(id)
+    /// Try to acquire a lock for schedule execution
+    ///
+    /// Returns true if lock was acquired, false if schedule is already locked.
+    /// Locks expire after 5 minutes to prevent deadlocks.
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn try_lock_schedule(&self, schedule_id: i64) -> Result<bool> {
+        let now = chrono::Utc::now();
+        let lock_expiry = now - chrono::Duration::minutes(5);
+
+        // Try to acquire lock using UPDATE with WHERE clause
+        // This will only update if the schedule is not locked or lock has expired
+        let result = sqlx::query(
+            r#"
+            UPDATE schedules
+            SET locked_at = ?
+            WHERE id = ?
+            AND (locked_at IS NULL OR locked_at < ?)
+            "#,
+        )
+        .bind(now.to_rfc3339())
+        .bind(schedule_id)
+        .bind(lock_expiry.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Release a schedule lock
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn unlock_schedule(&self, schedule_id: i64) -> Result<()> {
+        sqlx::query("UPDATE schedules SET locked_at = NULL WHERE id = ?")
+            .bind(schedule_id)
             .execute(&self.pool)
             .await?;
 
         Ok(())
     }
 
-    // ==================== Pipeline Run Operations ====================
-
-    /// Insert a new pipeline run
-    pub async fn insert_pipeline_run(&self, run: &crate::PipelineRun) -> Result<i64> {
+    /// Insert a schedule run record
+    #[tracing::instrument(skip(self, run), level = "debug", fields(schedule_id = run.schedule_id))]
+    pub async fn insert_schedule_run(&self, run: &ScheduleRun) -> Result<i64> {
         let result = sqlx::query(
             r#"
-            INSERT INTO pipeline_runs (
-                pipeline_id, status, trigger_event, started_at, completed_at, created_at
-            )
+            INSERT INTO schedule_runs (schedule_id, agent_id, started_at, completed_at, status, error_message)
             VALUES (?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(run.pipeline_id)
-        .bind(run.status.as_str())
-        .bind(&run.trigger_event)
-        .bind(run.started_at.map(|dt| dt.to_rfc3339()))
+        .bind(run.schedule_id)
+        .bind(&run.agent_id)
+        .bind(run.started_at.to_rfc3339())
         .bind(run.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(run.created_at.to_rfc3339())
+        .bind(run.status.as_str())
+        .bind(&run.error_message)
         .execute(&self.pool)
         .await?;
 
         Ok(result.last_insert_rowid())
     }
 
-    /// Get pipeline run by ID
-    pub async fn get_pipeline_run(&self, id: i64) -> Result<Option<crate::PipelineRun>> {
-        let row = sqlx::query_as::<_, PipelineRunRow>("SELECT * FROM pipeline_runs WHERE id = ?")
-            .bind(id)
-            .fetch_optional(&self.pool)
-            .await?;
-
-        row.map(|r| r.try_into()).transpose()
-    }
-
-    /// Update pipeline run
-    pub async fn update_pipeline_run(&self, run: &crate::PipelineRun) -> Result<()> {
-        let id = run
-            .id
-            .ok_or_else(|| crate::Error::Other("Cannot update pipeline run without ID".to_string()))?;
-
+    /// Update a schedule run
+    #[tracing::instrument(skip(self, run), level = "debug", fields(id = run.id))]
+    pub async fn update_schedule_run(&self, run: &ScheduleRun) -> Result<()> {
         sqlx::query(
             r#"
-            UPDATE pipeline_runs SET
-                status = ?,
-                started_at = ?,
-                completed_at = ?
+            UPDATE schedule_runs SET
+                agent_id = ?, completed_at = ?, status = ?, error_message = ?
             WHERE id = ?
             "#,
         )
-        .bind(run.status.as_str())
-        .bind(run.started_at.map(|dt| dt.to_rfc3339()))
+        .bind(&run.agent_id)
         .bind(run.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(id)
+        .bind(run.status.as_str())
+        .bind(&run.error_message)
+        .bind(run.id)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
 
-    /// List pipeline runs for a pipeline
-    pub async fn list_pipeline_runs(&self, pipeline_id: i64) -> Result<Vec<crate::PipelineRun>> {
-        let rows = sqlx::query_as::<_, PipelineRunRow>(
-            "SELECT * FROM pipeline_runs WHERE pipeline_id = ? ORDER BY created_at DESC",
-        )
-        .bind(pipeline_id)
+    /// Get schedule runs for a schedule
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_schedule_runs(&self, schedule_id: i64, limit: i64) -> Result<Vec<ScheduleRun>> {
+        let rows = sqlx::query_as::<_, ScheduleRunRow>(
+            "SELECT * FROM schedule_runs WHERE schedule_id = ? ORDER BY started_at DESC LIMIT ?",
+        )
+        .bind(schedule_id)
+        .bind(limit)
         .fetch_all(&self.pool)
         .await?;
 
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
-    /// List pipeline runs by status
-    pub async fn list_pipeline_runs_by_status(
+    /// One page of a schedule's runs, newest first
+    pub async fn get_schedule_runs_page(
         &self,
-        status: crate::PipelineRunStatus,
-    ) -> Result<Vec<crate::PipelineRun>> {
-        let rows = sqlx::query_as::<_, PipelineRunRow>(
-            "SELECT * FROM pipeline_runs WHERE status = ? ORDER BY created_at DESC",
+        schedule_id: i64,
+        page: &PageQuery,
+    ) -> Result<RowPage<ScheduleRun>> {
+        self.fetch_page::<ScheduleRunRow, _>(
+            "SELECT * FROM schedule_runs",
+            |q| {
+                q.push(" AND schedule_id = ").push_bind(schedule_id);
+            },
+            SortKey::desc(&["id"]),
+            page,
         )
-        .bind(status.as_str())
-        .fetch_all(&self.pool)
-        .await?;
-
-        rows.into_iter().map(|r| r.try_into()).collect()
+        .await?
+        .try_map(TryInto::try_into)
     }
 
-    // ==================== Pipeline Stage Operations ====================
-
-    /// Insert a new pipeline stage
-    pub async fn insert_pipeline_stage(&self, stage: &crate::PipelineStage) -> Result<i64> {
-        let result = sqlx::query(
+    /// Tokens used and estimated cost of a schedule's runs since `since`
+    ///
+    /// Turns don't record their model, so cost is estimated at the default
+    /// (Sonnet) pricing.
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_schedule_spend(
+        &self,
+        schedule_id: i64,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<ScheduleSpend> {
+        let (input_tokens, output_tokens, cache_read_tokens, cache_write_tokens): (
+            i64,
+            i64,
+            i64,
+            i64,
+        ) = sqlx::query_as(
             r#"
-            INSERT INTO pipeline_stages (
-                run_id, stage_name, status, agent_id, started_at, completed_at, created_at
+            SELECT
+                COALESCE(SUM(input_tokens), 0),
+                COALESCE(SUM(output_tokens), 0),
+                COALESCE(SUM(cache_read_tokens), 0),
+                COALESCE(SUM(cache_write_tokens), 0)
+            FROM session_token_stats
+            WHERE agent_id IN (
+                SELECT agent_id FROM schedule_runs
+                WHERE schedule_id = ? AND agent_id IS NOT NULL
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?)
+            AND created_at >= ?
             "#,
         )
-        .bind(stage.run_id)
-        .bind(&stage.stage_name)
-        .bind(stage.status.as_str())
-        .bind(&stage.agent_id)
-        .bind(stage.started_at.map(|dt| dt.to_rfc3339()))
-        .bind(stage.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(stage.created_at.to_rfc3339())
-        .execute(&self.pool)
+        .bind(schedule_id)
+        .bind(since.format("%Y-%m-%d %H:%M:%S").to_string())
+        .fetch_one(&self.pool)
         .await?;
 
-        Ok(result.last_insert_rowid())
-    }
-
-    /// Get pipeline stage by ID
-    pub async fn get_pipeline_stage(&self, id: i64) -> Result<Option<crate::PipelineStage>> {
-        let row =
-            sqlx::query_as::<_, PipelineStageRow>("SELECT * FROM pipeline_stages WHERE id = ?")
-                .bind(id)
-                .fetch_optional(&self.pool)
-                .await?;
-
-        row.map(|r| r.try_into()).transpose()
+        Ok(ScheduleSpend {
+            tokens: (input_tokens + output_tokens).max(0) as u64,
+            cost_usd: Self::calculate_token_cost(
+                "sonnet",
+                input_tokens,
+                output_tokens,
+                cache_read_tokens,
+                cache_write_tokens,
+            ),
+        })
     }
 
-    /// Get pipeline stage by name within a run
-    pub async fn get_pipeline_stage_by_name(
-        &self,
-        run_id: i64,
-        stage_name: &str,
-    ) -> Result<Option<crate::PipelineStage>> {
-        let row = sqlx::query_as::<_, PipelineStageRow>(
-            "SELECT * FROM pipeline_stages WHERE run_id = ? AND stage_name = ?",
+    /// Run metrics of a schedule, or `None` if it doesn't exist
+    ///
+    /// A run succeeded when its agent completed, and failed when no agent was
+    /// spawned or the agent failed or was terminated. Durations run from the
+    /// run's start until its agent finished.
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_schedule_stats(&self, schedule_id: i64) -> Result<Option<ScheduleStats>> {
+        let row = sqlx::query_as::<_, ScheduleStatsRow>(
+            r#"
+            SELECT
+                s.missed_runs,
+                s.consecutive_missed_runs,
+                COUNT(r.id) as total_runs,
+                COALESCE(SUM(CASE WHEN a.state = 'completed' THEN 1 ELSE 0 END), 0) as succeeded_runs,
+                COALESCE(SUM(CASE
+                    WHEN r.status = 'failed' OR a.state IN ('failed', 'terminated') THEN 1
+                    ELSE 0
+                END), 0) as failed_runs,
+                COALESCE(SUM(CASE WHEN r.status = 'skipped' THEN 1 ELSE 0 END), 0) as skipped_runs,
+                AVG(CASE
+                    WHEN a.state IN ('completed', 'failed', 'terminated')
+                    THEN (julianday(a.updated_at) - julianday(r.started_at)) * 86400.0
+                END) as avg_duration_secs
+            FROM schedules s
+            LEFT JOIN schedule_runs r ON r.schedule_id = s.id
+            LEFT JOIN agents a ON a.id = r.agent_id
+            WHERE s.id = ?
+            GROUP BY s.id
+            "#,
         )
-        .bind(run_id)
-        .bind(stage_name)
+        .bind(schedule_id)
         .fetch_optional(&self.pool)
         .await?;
 
-        row.map(|r| r.try_into()).transpose()
+        Ok(row.map(Into::into))
     }
 
-    /// Update pipeline stage
-    pub async fn update_pipeline_stage(&self, stage: &crate::PipelineStage) -> Result<()> {
-        let id = stage.id.ok_or_else(|| {
-            crate::Error::Other("Cannot update pipeline stage without ID".to_string())
-        })?;
+    // ==================== Schedule Blackout Operations ====================
 
-        sqlx::query(
+    /// Insert a blackout window
+    #[tracing::instrument(skip(self, blackout), level = "debug", fields(name = %blackout.name))]
+    pub async fn insert_schedule_blackout(&self, blackout: &ScheduleBlackout) -> Result<i64> {
+        let result = sqlx::query(
             r#"
-            UPDATE pipeline_stages SET
-                status = ?,
-                agent_id = ?,
-                started_at = ?,
-                completed_at = ?
-            WHERE id = ?
+            INSERT INTO schedule_blackouts (name, schedule_id, starts_at, ends_at, reason, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(stage.status.as_str())
-        .bind(&stage.agent_id)
-        .bind(stage.started_at.map(|dt| dt.to_rfc3339()))
-        .bind(stage.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(id)
+        .bind(&blackout.name)
+        .bind(blackout.schedule_id)
+        .bind(blackout.starts_at.to_rfc3339())
+        .bind(blackout.ends_at.to_rfc3339())
+        .bind(&blackout.reason)
+        .bind(blackout.created_at.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(result.last_insert_rowid())
     }
 
-    /// List pipeline stages for a run
-    pub async fn list_pipeline_stages(&self, run_id: i64) -> Result<Vec<crate::PipelineStage>> {
-        let rows = sqlx::query_as::<_, PipelineStageRow>(
-            "SELECT * FROM pipeline_stages WHERE run_id = ? ORDER BY created_at ASC",
+    /// List blackout windows by start time, skipping ended ones unless `include_ended`
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn list_schedule_blackouts(
+        &self,
+        include_ended: bool,
+    ) -> Result<Vec<ScheduleBlackout>> {
+        let rows = sqlx::query_as::<_, ScheduleBlackoutRow>(
+            "SELECT * FROM schedule_blackouts WHERE ? OR ends_at > ? ORDER BY starts_at ASC",
         )
-        .bind(run_id)
+        .bind(include_ended)
+        .bind(chrono::Utc::now().to_rfc3339())
         .fetch_all(&self.pool)
         .await?;
 
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
-    /// List pipeline stages by status within a run
-    pub async fn list_pipeline_stages_by_status(
+    /// Delete a blackout window by name
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn delete_schedule_blackout(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM schedule_blackouts WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Get the blackout window covering `at` for a schedule, global or its own
+    ///
+    /// When several windows overlap, the one ending last is returned.
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_active_schedule_blackout(
         &self,
-        run_id: i64,
-        status: crate::PipelineStageStatus,
-    ) -> Result<Vec<crate::PipelineStage>> {
-        let rows = sqlx::query_as::<_, PipelineStageRow>(
-            "SELECT * FROM pipeline_stages WHERE run_id = ? AND status = ? ORDER BY created_at ASC",
+        schedule_id: i64,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Option<ScheduleBlackout>> {
+        let at = at.to_rfc3339();
+        let row = sqlx::query_as::<_, ScheduleBlackoutRow>(
+            r#"
+            SELECT * FROM schedule_blackouts
+            WHERE (schedule_id IS NULL OR schedule_id = ?) AND starts_at <= ? AND ends_at > ?
+            ORDER BY ends_at DESC
+            LIMIT 1
+            "#,
         )
-        .bind(run_id)
-        .bind(status.as_str())
-        .fetch_all(&self.pool)
+        .bind(schedule_id)
+        .bind(&at)
+        .bind(&at)
+        .fetch_optional(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.try_into()).collect()
+        row.map(|r| r.try_into()).transpose()
     }
 
-    // Rollback event operations
+    // ==================== Webhook Event Operations ====================
 
-    /// Insert a rollback event
-    pub async fn insert_rollback_event(&self, event: &crate::RollbackEvent) -> Result<i64> {
+    /// Insert a new webhook event (idempotent by delivery_id)
+    #[tracing::instrument(skip(self, event), level = "debug", fields(delivery_id = %event.delivery_id))]
+    pub async fn insert_webhook_event(&self, event: &WebhookEvent) -> Result<i64> {
         let result = sqlx::query(
             r#"
-            INSERT INTO rollback_events
-                (run_id, failed_stage_name, rollback_to_stage, trigger_type, status,
-                 error_message, started_at, completed_at, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO webhook_events (
+                delivery_id, event_type, source, payload, status, retry_count, max_retries,
+                error_message, next_retry_at, received_at, processed_at, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(delivery_id) DO NOTHING
             "#,
         )
-        .bind(event.run_id)
-        .bind(&event.failed_stage_name)
-        .bind(&event.rollback_to_stage)
-        .bind(event.trigger_type.as_str())
+        .bind(&event.delivery_id)
+        .bind(&event.event_type)
+        .bind(&event.source)
+        .bind(&event.payload)
         .bind(event.status.as_str())
+        .bind(event.retry_count)
+        .bind(event.max_retries)
         .bind(&event.error_message)
-        .bind(event.started_at.map(|t| t.to_rfc3339()))
-        .bind(event.completed_at.map(|t| t.to_rfc3339()))
-        .bind(event.created_at.map(|t| t.to_rfc3339()))
+        .bind(event.next_retry_at.map(|dt| dt.to_rfc3339()))
+        .bind(event.received_at.to_rfc3339())
+        .bind(event.processed_at.map(|dt| dt.to_rfc3339()))
+        .bind(event.created_at.to_rfc3339())
+        .bind(event.updated_at.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        Ok(result.last_insert_rowid())
+        // If insert was ignored due to conflict, fetch the existing ID
+        if result.rows_affected() == 0 {
+            let id = sqlx::query_scalar::<_, i64>(
+                "SELECT id FROM webhook_events WHERE delivery_id = ?",
+            )
+            .bind(&event.delivery_id)
+            .fetch_one(&self.pool)
+            .await?;
+            Ok(id)
+        } else {
+            Ok(result.last_insert_rowid())
+        }
     }
 
-    /// Get a rollback event by ID
-    pub async fn get_rollback_event(&self, id: i64) -> Result<Option<crate::RollbackEvent>> {
-        let row = sqlx::query_as::<_, RollbackEventRow>(
-            "SELECT * FROM rollback_events WHERE id = ?",
+    /// Get webhook event by ID
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_webhook_event(&self, id: i64) -> Result<Option<WebhookEvent>> {
+        let row = sqlx::query_as::<_, WebhookEventRow>(
+            "SELECT * FROM webhook_events WHERE id = ?",
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -3841,1664 +4851,5299 @@ impl Database {
         row.map(|r| r.try_into()).transpose()
     }
 
-    /// Update a rollback event
-    pub async fn update_rollback_event(&self, event: &crate::RollbackEvent) -> Result<()> {
-        let id = event.id.ok_or_else(|| {
-            crate::Error::Other("Cannot update rollback event without an ID".to_string())
-        })?;
-
-        sqlx::query(
-            r#"
-            UPDATE rollback_events
-            SET status = ?, error_message = ?, started_at = ?, completed_at = ?
-            WHERE id = ?
-            "#,
+    /// Get webhook event by delivery ID
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_webhook_event_by_delivery_id(
+        &self,
+        delivery_id: &str,
+    ) -> Result<Option<WebhookEvent>> {
+        let row = sqlx::query_as::<_, WebhookEventRow>(
+            "SELECT * FROM webhook_events WHERE delivery_id = ?",
         )
-        .bind(event.status.as_str())
-        .bind(&event.error_message)
-        .bind(event.started_at.map(|t| t.to_rfc3339()))
-        .bind(event.completed_at.map(|t| t.to_rfc3339()))
-        .bind(id)
-        .execute(&self.pool)
+        .bind(delivery_id)
+        .fetch_optional(&self.pool)
         .await?;
 
-        Ok(())
+        row.map(|r| r.try_into()).transpose()
     }
 
-    /// List all rollback events for a pipeline run
-    pub async fn list_rollback_events(&self, run_id: i64) -> Result<Vec<crate::RollbackEvent>> {
-        let rows = sqlx::query_as::<_, RollbackEventRow>(
-            "SELECT * FROM rollback_events WHERE run_id = ? ORDER BY created_at ASC",
+    /// Get pending webhook events ready for processing
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_pending_webhook_events(&self, limit: i64) -> Result<Vec<WebhookEvent>> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let rows = sqlx::query_as::<_, WebhookEventRow>(
+            r#"
+            SELECT * FROM webhook_events
+            WHERE status = 'pending'
+            AND (next_retry_at IS NULL OR next_retry_at <= ?)
+            ORDER BY received_at ASC
+            LIMIT ?
+            "#,
         )
-        .bind(run_id)
+        .bind(&now)
+        .bind(limit)
         .fetch_all(&self.pool)
         .await?;
 
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
-    /// Count rollback events for a specific stage in a run (for loop prevention)
-    pub async fn count_rollback_events_for_stage(
+    /// Claim pending webhook events ready for processing, oldest first
+    ///
+    /// Claimed events are marked processing so later polls skip them. Events
+    /// of the excluded types stay pending, letting other types through while
+    /// one type is at its concurrency limit.
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn claim_pending_webhook_events(
         &self,
-        run_id: i64,
-        stage_name: &str,
-    ) -> Result<i64> {
-        let result = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM rollback_events WHERE run_id = ? AND rollback_to_stage = ?",
-        )
-        .bind(run_id)
-        .bind(stage_name)
-        .fetch_one(&self.pool)
-        .await?;
+        limit: i64,
+        excluded_event_types: &[String],
+    ) -> Result<Vec<WebhookEvent>> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let placeholders = vec!["?"; excluded_event_types.len()].join(", ");
+        let sql = format!(
+            r#"
+            UPDATE webhook_events SET status = 'processing', updated_at = ?
+            WHERE id IN (
+                SELECT id FROM webhook_events
+                WHERE status = 'pending'
+                AND (next_retry_at IS NULL OR next_retry_at <= ?)
+                AND event_type NOT IN ({})
+                ORDER BY received_at ASC
+                LIMIT ?
+            )
+            RETURNING *
+            "#,
+            placeholders
+        );
 
-        Ok(result)
-    }
-}
-
-/// Token usage statistics
-#[derive(Debug, Clone, serde::Serialize)]
-pub struct TokenStats {
-    pub turn_count: i64,
-    pub total_input_tokens: i64,
-    pub total_output_tokens: i64,
-    pub total_cache_read_tokens: i64,
-    pub total_cache_write_tokens: i64,
-    pub avg_context_used: f64,
-    pub avg_messages_included: f64,
-    pub total_messages_summarized: i64,
-    pub cache_hit_rate: f64,
-}
+        let mut query = sqlx::query_as::<_, WebhookEventRow>(&sql)
+            .bind(&now)
+            .bind(&now);
+        for event_type in excluded_event_types {
+            query = query.bind(event_type);
+        }
+        let rows = query.bind(limit).fetch_all(&self.pool).await?;
 
-#[derive(sqlx::FromRow)]
-struct TokenStatsRow {
-    turn_count: i64,
-    total_input_tokens: i64,
-    total_output_tokens: i64,
-    total_cache_read_tokens: i64,
-    total_cache_write_tokens: i64,
-    avg_context_used: f64,
-    avg_messages_included: f64,
-    total_messages_summarized: i64,
-}
+        let mut events = rows
+            .into_iter()
+            .map(|r| r.try_into())
+            .collect::<Result<Vec<WebhookEvent>>>()?;
+        events.sort_by_key(|event| event.received_at);
+        Ok(events)
+    }
 
-impl From<TokenStatsRow> for TokenStats {
-    fn from(row: TokenStatsRow) -> Self {
-        let total_input = row.total_input_tokens;
-        let cache_read = row.total_cache_read_tokens;
-        let cache_hit_rate = if total_input > 0 {
-            (cache_read as f64 / total_input as f64) * 100.0
-        } else {
-            0.0
-        };
+    /// Return events left processing by a stopped processor to the queue
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn requeue_processing_webhook_events(&self) -> Result<u64> {
+        let result = sqlx::query(
+            "UPDATE webhook_events SET status = 'pending', updated_at = ? WHERE status = 'processing'",
+        )
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
 
-        Self {
-            turn_count: row.turn_count,
-            total_input_tokens: row.total_input_tokens,
-            total_output_tokens: row.total_output_tokens,
-            total_cache_read_tokens: row.total_cache_read_tokens,
-            total_cache_write_tokens: row.total_cache_write_tokens,
-            avg_context_used: row.avg_context_used,
-            avg_messages_included: row.avg_messages_included,
-            total_messages_summarized: row.total_messages_summarized,
-            cache_hit_rate,
-        }
+        Ok(result.rows_affected())
     }
-}
 
-/// Daily token usage for cost tracking
-#[derive(Debug, Clone, serde::Serialize)]
-pub struct DailyTokenUsage {
-    pub date: String,
-    pub model: String,
-    pub total_input_tokens: i64,
-    pub total_output_tokens: i64,
-    pub total_cache_read_tokens: i64,
-    pub total_cache_write_tokens: i64,
-    pub request_count: i64,
-    pub agent_count: i64,
-    pub estimated_cost_usd: Option<f64>,
-}
+    /// Update webhook event status and metadata
+    #[tracing::instrument(skip(self, event), level = "debug", fields(id = event.id))]
+    pub async fn update_webhook_event(&self, event: &WebhookEvent) -> Result<()> {
+        let id = event.id.ok_or_else(|| {
+            crate::Error::Other("Cannot update webhook event without ID".to_string())
+        })?;
 
-#[derive(sqlx::FromRow)]
-struct DailyTokenUsageRow {
-    date: String,
-    model: String,
-    total_input_tokens: i64,
-    total_output_tokens: i64,
-    total_cache_read_tokens: i64,
-    total_cache_write_tokens: i64,
-    request_count: i64,
-    agent_count: i64,
-    estimated_cost_usd: Option<f64>,
-}
+        sqlx::query(
+            r#"
+            UPDATE webhook_events SET
+                status = ?,
+                retry_count = ?,
+                error_message = ?,
+                next_retry_at = ?,
+                processed_at = ?,
+                updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(event.status.as_str())
+        .bind(event.retry_count)
+        .bind(&event.error_message)
+        .bind(event.next_retry_at.map(|dt| dt.to_rfc3339()))
+        .bind(event.processed_at.map(|dt| dt.to_rfc3339()))
+        .bind(event.updated_at.to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
 
-impl From<DailyTokenUsageRow> for DailyTokenUsage {
-    fn from(row: DailyTokenUsageRow) -> Self {
-        Self {
-            date: row.date,
-            model: row.model,
-            total_input_tokens: row.total_input_tokens,
-            total_output_tokens: row.total_output_tokens,
-            total_cache_read_tokens: row.total_cache_read_tokens,
-            total_cache_write_tokens: row.total_cache_write_tokens,
-            request_count: row.request_count,
-            agent_count: row.agent_count,
-            estimated_cost_usd: row.estimated_cost_usd,
-        }
+        Ok(())
     }
-}
 
-// ==================== Row Types for SQLx ====================
+    /// Get webhook events by status
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_webhook_events_by_status(
+        &self,
+        status: WebhookEventStatus,
+        limit: i64,
+    ) -> Result<Vec<WebhookEvent>> {
+        let rows = sqlx::query_as::<_, WebhookEventRow>(
+            r#"
+            SELECT * FROM webhook_events
+            WHERE status = ?
+            ORDER BY received_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
 
-#[derive(sqlx::FromRow)]
-struct AgentRow {
-    id: String,
-    agent_type: String,
-    state: String,
-    task: String,
-    context: String,
-    session_id: Option<String>,
-    parent_agent_id: Option<String>,
-    worktree_id: Option<String>,
-    error_message: Option<String>,
-    created_at: String,
-    updated_at: String,
-    completed_at: Option<String>,
-}
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
 
-impl TryFrom<AgentRow> for Agent {
-    type Error = crate::Error;
+    /// Count webhook events by status
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn count_webhook_events_by_status(
+        &self,
+        status: WebhookEventStatus,
+    ) -> Result<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM webhook_events WHERE status = ?",
+        )
+        .bind(status.as_str())
+        .fetch_one(&self.pool)
+        .await?;
 
-    fn try_from(row: AgentRow) -> Result<Self> {
-        Ok(Agent {
-            id: Uuid::parse_str(&row.id).map_err(|e| crate::Error::Other(e.to_string()))?,
-            agent_type: AgentType::from_str(&row.agent_type)?,
-            state: AgentState::from_str(&row.state)?,
-            task: row.task,
-            context: serde_json::from_str(&row.context)?,
-            session_id: row.session_id,
-            parent_agent_id: row
-                .parent_agent_id
-                .map(|s| Uuid::parse_str(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?,
-            worktree_id: row.worktree_id,
-            error_message: row.error_message,
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            completed_at: row
-                .completed_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-        })
+        Ok(count)
     }
-}
 
-/// Statistics for an agent's message history
-#[derive(Debug, Clone, serde::Serialize)]
-pub struct AgentStats {
-    pub message_count: i64,
-    pub total_input_tokens: i64,
-    pub total_output_tokens: i64,
-    pub total_tokens: i64,
-    pub tool_call_count: i64,
-    pub error_count: i64,
-    pub first_message_at: Option<String>,
-    pub last_message_at: Option<String>,
-}
+    /// Delete old completed webhook events (for cleanup)
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn delete_old_webhook_events(&self, days: i64) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM webhook_events
+            WHERE status IN ('completed', 'dead_letter')
+            AND received_at < datetime('now', '-' || ? || ' days')
+            "#,
+        )
+        .bind(days)
+        .execute(&self.pool)
+        .await?;
 
-#[derive(sqlx::FromRow)]
-struct AgentStatsRow {
-    message_count: i64,
-    total_input_tokens: Option<i64>,
-    total_output_tokens: Option<i64>,
-    tool_call_count: i64,
-    error_count: i64,
-    first_message_at: Option<String>,
-    last_message_at: Option<String>,
-}
+        Ok(result.rows_affected())
+    }
 
-impl From<AgentStatsRow> for AgentStats {
-    fn from(row: AgentStatsRow) -> Self {
-        let input = row.total_input_tokens.unwrap_or(0);
-        let output = row.total_output_tokens.unwrap_or(0);
-        Self {
-            message_count: row.message_count,
-            total_input_tokens: input,
-            total_output_tokens: output,
-            total_tokens: input + output,
-            tool_call_count: row.tool_call_count,
-            error_count: row.error_count,
-            first_message_at: row.first_message_at,
-            last_message_at: row.last_message_at,
-        }
+    /// List webhook events without their payloads, newest first, optionally
+    /// by status, event type, and source
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn list_webhook_events_page(
+        &self,
+        status: Option<WebhookEventStatus>,
+        event_type: Option<&str>,
+        source: Option<&str>,
+        page: &PageQuery,
+    ) -> Result<RowPage<WebhookEventSummary>> {
+        self.fetch_page::<WebhookEventSummaryRow, _>(
+            r#"
+            SELECT id, delivery_id, event_type, source, status, retry_count,
+                max_retries, error_message, next_retry_at, received_at,
+                processed_at,
+                CASE WHEN json_valid(payload) THEN
+                    CASE json_type(payload, '$.action')
+                        WHEN 'text' THEN json_extract(payload, '$.action')
+                    END
+                END AS action,
+                LENGTH(CAST(payload AS BLOB)) AS payload_bytes
+            FROM webhook_events
+            "#,
+            |q| {
+                if let Some(status) = status {
+                    q.push(" AND status = ").push_bind(status.as_str());
+                }
+                if let Some(event_type) = event_type {
+                    q.push(" AND event_type = ")
+                        .push_bind(event_type.to_string());
+                }
+                if let Some(source) = source {
+                    q.push(" AND source = ").push_bind(source.to_string());
+                }
+            },
+            SortKey::desc(&["received_at", "id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
     }
-}
-
-#[derive(sqlx::FromRow)]
-struct SessionRow {
-    id: String,
-    agent_id: String,
-    parent_id: Option<String>,
-    api_session_id: Option<String>,
-    total_tokens: i64,
-    is_forked: bool,
-    forked_at: Option<String>,
-    created_at: String,
-    closed_at: Option<String>,
-}
 
-impl TryFrom<SessionRow> for crate::Session {
-    type Error = crate::Error;
+    /// Delete every event in the dead letter queue
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn purge_dead_letter_webhook_events(&self) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM webhook_events WHERE status = 'dead_letter'")
+            .execute(&self.pool)
+            .await?;
 
-    fn try_from(row: SessionRow) -> Result<Self> {
-        Ok(crate::Session {
-            id: row.id,
-            agent_id: Uuid::parse_str(&row.agent_id)
-                .map_err(|e| crate::Error::Other(e.to_string()))?,
-            parent_id: row.parent_id,
-            api_session_id: row.api_session_id,
-            total_tokens: row.total_tokens,
-            is_forked: row.is_forked,
-            forked_at: row
-                .forked_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            closed_at: row
-                .closed_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-        })
+        Ok(result.rows_affected())
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct MessageRow {
-    id: i64,
-    agent_id: String,
-    role: String,
-    content: String,
-    tool_calls: Option<String>,
-    tool_results: Option<String>,
-    input_tokens: i32,
-    output_tokens: i32,
-    created_at: String,
-}
+    /// Count webhook events of each source by status
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_webhook_source_stats(&self) -> Result<Vec<WebhookSourceStats>> {
+        let rows = sqlx::query_as::<_, WebhookSourceStatsRow>(
+            r#"
+            SELECT
+                source,
+                COUNT(*) AS total,
+                SUM(CASE WHEN status = 'pending' THEN 1 ELSE 0 END) AS pending,
+                SUM(CASE WHEN status = 'processing' THEN 1 ELSE 0 END) AS processing,
+                SUM(CASE WHEN status = 'completed' THEN 1 ELSE 0 END) AS completed,
+                SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END) AS failed,
+                SUM(CASE WHEN status = 'dead_letter' THEN 1 ELSE 0 END) AS dead_letter,
+                MAX(received_at) AS last_received_at
+            FROM webhook_events
+            GROUP BY source
+            ORDER BY source
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-impl TryFrom<MessageRow> for Message {
-    type Error = crate::Error;
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
 
-    fn try_from(row: MessageRow) -> Result<Self> {
-        Ok(Message {
-            id: row.id,
-            agent_id: Uuid::parse_str(&row.agent_id)
-                .map_err(|e| crate::Error::Other(e.to_string()))?,
-            role: MessageRole::from_str(&row.role)?,
-            content: row.content,
-            tool_calls: row
-                .tool_calls
-                .map(|s| serde_json::from_str(&s))
-                .transpose()?,
-            tool_results: row
-                .tool_results
-                .map(|s| serde_json::from_str(&s))
-                .transpose()?,
-            input_tokens: row.input_tokens,
-            output_tokens: row.output_tokens,
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-        })
+    /// Get recent webhook events (all statuses)
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub async fn get_recent_webhook_events(&self, limit: i64) -> Result<Vec<WebhookEvent>> {
+        let rows = sqlx::query_as::<_, WebhookEventRow>(
+            r#"
+            SELECT * FROM webhook_events
+            ORDER BY received_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct PrRow {
-    id: i64,
-    epic_id: Option<String>,
-    worktree_id: Option<String>,
-    branch_name: String,
-    title: Option<String>,
-    body: Option<String>,
-    pr_number: Option<i32>,
-    status: String,
-    merge_strategy: String,
-    agent_id: Option<String>,
-    error_message: Option<String>,
-    created_at: String,
-    updated_at: String,
-    merged_at: Option<String>,
-}
+    // ==================== Custom Webhook Operations ====================
 
-impl TryFrom<PrRow> for PullRequest {
-    type Error = crate::Error;
+    /// Insert a new custom webhook
+    pub async fn insert_custom_webhook(&self, webhook: &crate::CustomWebhook) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO custom_webhooks (name, definition, enabled, created_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(&webhook.name)
+        .bind(&webhook.definition)
+        .bind(webhook.enabled as i32)
+        .bind(webhook.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
 
-    fn try_from(row: PrRow) -> Result<Self> {
-        Ok(PullRequest {
-            id: row.id,
-            epic_id: row.epic_id,
-            worktree_id: row.worktree_id,
-            branch_name: row.branch_name,
-            title: row.title,
-            body: row.body,
-            pr_number: row.pr_number,
-            status: PrStatus::from_str(&row.status)?,
-            merge_strategy: MergeStrategy::from_str(&row.merge_strategy)?,
-            agent_id: row
-                .agent_id
-                .map(|s| Uuid::parse_str(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?,
-            error_message: row.error_message,
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            merged_at: row
-                .merged_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-        })
+        Ok(result.last_insert_rowid())
     }
-}
-
-#[derive(sqlx::FromRow)]
-struct EpicRow {
-    id: String,
-    title: String,
-    source_file: Option<String>,
-    pattern: Option<String>,
-    status: String,
-    current_phase: Option<String>,
-    agent_id: Option<String>,
-    pr_id: Option<i64>,
-    error_message: Option<String>,
-    created_at: String,
-    updated_at: String,
-    completed_at: Option<String>,
-}
 
-impl TryFrom<EpicRow> for Epic {
-    type Error = crate::Error;
+    /// Get custom webhook by name
+    pub async fn get_custom_webhook_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<crate::CustomWebhook>> {
+        let row =
+            sqlx::query_as::<_, CustomWebhookRow>("SELECT * FROM custom_webhooks WHERE name = ?")
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await?;
 
-    fn try_from(row: EpicRow) -> Result<Self> {
-        Ok(Epic {
-            id: row.id,
-            title: row.title,
-            source_file: row.source_file,
-            pattern: row.pattern,
-            status: EpicStatus::from_str(&row.status)?,
-            current_phase: row
-                .current_phase
-                .map(|p| serde_json::from_str(&format!("\"{}\"", p)))
-                .transpose()?,
-            agent_id: row
-                .agent_id
-                .map(|s| Uuid::parse_str(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?,
-            pr_id: row.pr_id,
-            error_message: row.error_message,
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            completed_at: row
-                .completed_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-        })
+        row.map(|r| r.try_into()).transpose()
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct StoryRow {
-    id: String,
-    epic_id: String,
-    title: String,
-    description: Option<String>,
-    acceptance_criteria: Option<String>,
-    status: String,
-    agent_id: Option<String>,
-    created_at: String,
-    updated_at: String,
-    completed_at: Option<String>,
-}
+    /// Update custom webhook
+    pub async fn update_custom_webhook(&self, webhook: &crate::CustomWebhook) -> Result<()> {
+        let id = webhook.id.ok_or_else(|| {
+            crate::Error::Other("Cannot update custom webhook without ID".to_string())
+        })?;
 
-impl TryFrom<StoryRow> for Story {
-    type Error = crate::Error;
+        sqlx::query(
+            r#"
+            UPDATE custom_webhooks SET
+                name = ?,
+                definition = ?,
+                enabled = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&webhook.name)
+        .bind(&webhook.definition)
+        .bind(webhook.enabled as i32)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
 
-    fn try_from(row: StoryRow) -> Result<Self> {
-        Ok(Story {
+        Ok(())
+    }
+
+    /// List all custom webhooks
+    pub async fn list_custom_webhooks(&self) -> Result<Vec<crate::CustomWebhook>> {
+        let rows = sqlx::query_as::<_, CustomWebhookRow>(
+            "SELECT * FROM custom_webhooks ORDER BY name ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Delete custom webhook by name, returning whether it existed
+    pub async fn delete_custom_webhook(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM custom_webhooks WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ==================== Webhook Source Operations ====================
+
+    /// Insert a new webhook source
+    pub async fn insert_webhook_source(&self, source: &crate::WebhookSource) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO webhook_sources (name, scheme, header, secrets, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&source.name)
+        .bind(source.scheme.as_str())
+        .bind(&source.header)
+        .bind(serde_json::to_string(&source.secrets)?)
+        .bind(source.created_at.to_rfc3339())
+        .bind(source.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get webhook source by name
+    pub async fn get_webhook_source(&self, name: &str) -> Result<Option<crate::WebhookSource>> {
+        let row =
+            sqlx::query_as::<_, WebhookSourceRow>("SELECT * FROM webhook_sources WHERE name = ?")
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Update webhook source
+    pub async fn update_webhook_source(&self, source: &crate::WebhookSource) -> Result<()> {
+        let id = source.id.ok_or_else(|| {
+            crate::Error::Other("Cannot update webhook source without ID".to_string())
+        })?;
+
+        sqlx::query(
+            r#"
+            UPDATE webhook_sources SET
+                scheme = ?,
+                header = ?,
+                secrets = ?,
+                updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(source.scheme.as_str())
+        .bind(&source.header)
+        .bind(serde_json::to_string(&source.secrets)?)
+        .bind(source.updated_at.to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List all webhook sources
+    pub async fn list_webhook_sources(&self) -> Result<Vec<crate::WebhookSource>> {
+        let rows = sqlx::query_as::<_, WebhookSourceRow>(
+            "SELECT * FROM webhook_sources ORDER BY name ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Delete webhook source by name, returning whether it existed
+    pub async fn delete_webhook_source(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM webhook_sources WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ==================== Outbound Webhook Operations ====================
+
+    /// Insert a new outbound webhook
+    pub async fn insert_outbound_webhook(&self, webhook: &crate::OutboundWebhook) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO outbound_webhooks (url, secret, events, description, enabled, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(serde_json::to_string(&webhook.events)?)
+        .bind(&webhook.description)
+        .bind(webhook.enabled as i32)
+        .bind(webhook.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get outbound webhook by ID
+    pub async fn get_outbound_webhook(&self, id: i64) -> Result<Option<crate::OutboundWebhook>> {
+        let row =
+            sqlx::query_as::<_, OutboundWebhookRow>("SELECT * FROM outbound_webhooks WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Update outbound webhook
+    pub async fn update_outbound_webhook(&self, webhook: &crate::OutboundWebhook) -> Result<()> {
+        let id = webhook.id.ok_or_else(|| {
+            crate::Error::Other("Cannot update outbound webhook without ID".to_string())
+        })?;
+
+        sqlx::query(
+            r#"
+            UPDATE outbound_webhooks SET
+                url = ?,
+                secret = ?,
+                events = ?,
+                description = ?,
+                enabled = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(serde_json::to_string(&webhook.events)?)
+        .bind(&webhook.description)
+        .bind(webhook.enabled as i32)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List all outbound webhooks
+    pub async fn list_outbound_webhooks(&self) -> Result<Vec<crate::OutboundWebhook>> {
+        let rows = sqlx::query_as::<_, OutboundWebhookRow>(
+            "SELECT * FROM outbound_webhooks ORDER BY id ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Delete outbound webhook and its delivery log, returning whether it existed
+    pub async fn delete_outbound_webhook(&self, id: i64) -> Result<bool> {
+        sqlx::query("DELETE FROM outbound_webhook_deliveries WHERE webhook_id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        let result = sqlx::query("DELETE FROM outbound_webhooks WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Log a delivery of the event for every enabled webhook subscribed to it,
+    /// returning how many were logged
+    pub async fn enqueue_outbound_event(&self, event: &crate::OutboundEvent) -> Result<usize> {
+        let mut enqueued = 0;
+        for webhook in self.list_outbound_webhooks().await? {
+            let Some(id) = webhook.id.filter(|_| webhook.enabled) else {
+                continue;
+            };
+            if webhook.matches(event.event_type) {
+                self.insert_outbound_delivery(&crate::OutboundWebhookDelivery::new(id, event))
+                    .await?;
+                enqueued += 1;
+            }
+        }
+
+        Ok(enqueued)
+    }
+
+    /// Log an outbound delivery without failing the caller
+    ///
+    /// Used where events occur as a side effect of another update, which
+    /// must not fail because a delivery could not be logged.
+    async fn enqueue_outbound_event_logged(&self, event: Option<crate::OutboundEvent>) {
+        let Some(event) = event else {
+            return;
+        };
+        if let Err(e) = self.enqueue_outbound_event(&event).await {
+            tracing::warn!(
+                event_type = event.event_type.as_str(),
+                error = %e,
+                "Failed to enqueue outbound webhook deliveries"
+            );
+        }
+    }
+
+    /// Insert a new outbound delivery
+    pub async fn insert_outbound_delivery(
+        &self,
+        delivery: &crate::OutboundWebhookDelivery,
+    ) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO outbound_webhook_deliveries (
+                webhook_id, event_type, payload, status, attempts, max_attempts,
+                response_status, error_message, next_attempt_at, created_at, delivered_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(delivery.webhook_id)
+        .bind(&delivery.event_type)
+        .bind(&delivery.payload)
+        .bind(delivery.status.as_str())
+        .bind(delivery.attempts)
+        .bind(delivery.max_attempts)
+        .bind(delivery.response_status)
+        .bind(&delivery.error_message)
+        .bind(delivery.next_attempt_at.to_rfc3339())
+        .bind(delivery.created_at.to_rfc3339())
+        .bind(delivery.delivered_at.map(|dt| dt.to_rfc3339()))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get outbound delivery by ID
+    pub async fn get_outbound_delivery(
+        &self,
+        id: i64,
+    ) -> Result<Option<crate::OutboundWebhookDelivery>> {
+        let row = sqlx::query_as::<_, OutboundWebhookDeliveryRow>(
+            "SELECT * FROM outbound_webhook_deliveries WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Update outbound delivery
+    pub async fn update_outbound_delivery(
+        &self,
+        delivery: &crate::OutboundWebhookDelivery,
+    ) -> Result<()> {
+        let id = delivery.id.ok_or_else(|| {
+            crate::Error::Other("Cannot update outbound delivery without ID".to_string())
+        })?;
+
+        sqlx::query(
+            r#"
+            UPDATE outbound_webhook_deliveries SET
+                status = ?,
+                attempts = ?,
+                max_attempts = ?,
+                response_status = ?,
+                error_message = ?,
+                next_attempt_at = ?,
+                delivered_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(delivery.status.as_str())
+        .bind(delivery.attempts)
+        .bind(delivery.max_attempts)
+        .bind(delivery.response_status)
+        .bind(&delivery.error_message)
+        .bind(delivery.next_attempt_at.to_rfc3339())
+        .bind(delivery.delivered_at.map(|dt| dt.to_rfc3339()))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List pending outbound deliveries whose next attempt is due, oldest first
+    pub async fn list_due_outbound_deliveries(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<crate::OutboundWebhookDelivery>> {
+        let rows = sqlx::query_as::<_, OutboundWebhookDeliveryRow>(
+            r#"
+            SELECT * FROM outbound_webhook_deliveries
+            WHERE status = 'pending' AND next_attempt_at <= ?
+            ORDER BY next_attempt_at ASC, id ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// List outbound deliveries, newest first, optionally for one webhook
+    pub async fn list_outbound_deliveries(
+        &self,
+        webhook_id: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<crate::OutboundWebhookDelivery>> {
+        let rows = sqlx::query_as::<_, OutboundWebhookDeliveryRow>(
+            r#"
+            SELECT * FROM outbound_webhook_deliveries
+            WHERE ? IS NULL OR webhook_id = ?
+            ORDER BY id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(webhook_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    // ==================== Pipeline Operations ====================
+
+    /// Insert a new pipeline
+    pub async fn insert_pipeline(&self, pipeline: &crate::Pipeline) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO pipelines (name, definition, enabled, created_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(&pipeline.name)
+        .bind(&pipeline.definition)
+        .bind(pipeline.enabled as i32)
+        .bind(pipeline.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get pipeline by ID
+    pub async fn get_pipeline(&self, id: i64) -> Result<Option<crate::Pipeline>> {
+        let row = sqlx::query_as::<_, PipelineRow>("SELECT * FROM pipelines WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Get pipeline by name
+    pub async fn get_pipeline_by_name(&self, name: &str) -> Result<Option<crate::Pipeline>> {
+        let row = sqlx::query_as::<_, PipelineRow>("SELECT * FROM pipelines WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Update pipeline
+    pub async fn update_pipeline(&self, pipeline: &crate::Pipeline) -> Result<()> {
+        let id = pipeline
+            .id
+            .ok_or_else(|| crate::Error::Other("Cannot update pipeline without ID".to_string()))?;
+
+        sqlx::query(
+            r#"
+            UPDATE pipelines SET
+                name = ?,
+                definition = ?,
+                enabled = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&pipeline.name)
+        .bind(&pipeline.definition)
+        .bind(pipeline.enabled as i32)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List all pipelines
+    pub async fn list_pipelines(&self) -> Result<Vec<crate::Pipeline>> {
+        let rows = sqlx::query_as::<_, PipelineRow>("SELECT * FROM pipelines ORDER BY name ASC")
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// One page of pipelines, in creation order
+    pub async fn list_pipelines_page(&self, page: &PageQuery) -> Result<RowPage<crate::Pipeline>> {
+        self.fetch_page::<PipelineRow, _>(
+            "SELECT * FROM pipelines",
+            |_| {},
+            SortKey::asc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
+    /// List enabled pipelines
+    pub async fn list_enabled_pipelines(&self) -> Result<Vec<crate::Pipeline>> {
+        let rows = sqlx::query_as::<_, PipelineRow>(
+            "SELECT * FROM pipelines WHERE enabled = 1 ORDER BY name ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Delete pipeline
+    pub async fn delete_pipeline(&self, id: i64) -> Result<()> {
+        sqlx::query("DELETE FROM pipelines WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // ==================== Pipeline Run Operations ====================
+
+    /// Insert a new pipeline run
+    pub async fn insert_pipeline_run(&self, run: &crate::PipelineRun) -> Result<i64> {
+        Self::insert_pipeline_run_with(&self.pool, run).await
+    }
+
+    async fn insert_pipeline_run_with<'e>(
+        executor: impl SqliteExecutor<'e>,
+        run: &crate::PipelineRun,
+    ) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO pipeline_runs (
+                pipeline_id, status, trigger_event, commit_sha, use_cache, variables,
+                trigger_payload, started_at, completed_at, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(run.pipeline_id)
+        .bind(run.status.as_str())
+        .bind(&run.trigger_event)
+        .bind(&run.commit_sha)
+        .bind(run.use_cache as i32)
+        .bind(serde_json::to_string(&run.variables)?)
+        .bind(
+            run.trigger_payload
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()?,
+        )
+        .bind(run.started_at.map(|dt| dt.to_rfc3339()))
+        .bind(run.completed_at.map(|dt| dt.to_rfc3339()))
+        .bind(run.created_at.to_rfc3339())
+        .execute(executor)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get pipeline run by ID
+    pub async fn get_pipeline_run(&self, id: i64) -> Result<Option<crate::PipelineRun>> {
+        let row = sqlx::query_as::<_, PipelineRunRow>("SELECT * FROM pipeline_runs WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Update pipeline run
+    pub async fn update_pipeline_run(&self, run: &crate::PipelineRun) -> Result<()> {
+        Self::update_pipeline_run_with(&self.pool, run).await
+    }
+
+    async fn update_pipeline_run_with<'e>(
+        executor: impl SqliteExecutor<'e>,
+        run: &crate::PipelineRun,
+    ) -> Result<()> {
+        let id = run
+            .id
+            .ok_or_else(|| crate::Error::Other("Cannot update pipeline run without ID".to_string()))?;
+
+        sqlx::query(
+            r#"
+            UPDATE pipeline_runs SET
+                status = ?,
+                started_at = ?,
+                completed_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(run.status.as_str())
+        .bind(run.started_at.map(|dt| dt.to_rfc3339()))
+        .bind(run.completed_at.map(|dt| dt.to_rfc3339()))
+        .bind(id)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List pipeline runs for a pipeline
+    pub async fn list_pipeline_runs(&self, pipeline_id: i64) -> Result<Vec<crate::PipelineRun>> {
+        let rows = sqlx::query_as::<_, PipelineRunRow>(
+            "SELECT * FROM pipeline_runs WHERE pipeline_id = ? ORDER BY created_at DESC",
+        )
+        .bind(pipeline_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// One page of a pipeline's runs, newest first
+    pub async fn list_pipeline_runs_page(
+        &self,
+        pipeline_id: i64,
+        page: &PageQuery,
+    ) -> Result<RowPage<crate::PipelineRun>> {
+        self.fetch_page::<PipelineRunRow, _>(
+            "SELECT * FROM pipeline_runs",
+            |q| {
+                q.push(" AND pipeline_id = ").push_bind(pipeline_id);
+            },
+            SortKey::desc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
+    /// List pipeline runs by status
+    pub async fn list_pipeline_runs_by_status(
+        &self,
+        status: crate::PipelineRunStatus,
+    ) -> Result<Vec<crate::PipelineRun>> {
+        let rows = sqlx::query_as::<_, PipelineRunRow>(
+            "SELECT * FROM pipeline_runs WHERE status = ? ORDER BY created_at DESC",
+        )
+        .bind(status.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// List a pipeline's runs that count against its concurrency limit
+    ///
+    /// Pending, running, and waiting-for-approval runs are listed oldest first.
+    pub async fn list_active_pipeline_runs(
+        &self,
+        pipeline_id: i64,
+    ) -> Result<Vec<crate::PipelineRun>> {
+        Self::list_active_pipeline_runs_with(&self.pool, pipeline_id).await
+    }
+
+    async fn list_active_pipeline_runs_with<'e>(
+        executor: impl SqliteExecutor<'e>,
+        pipeline_id: i64,
+    ) -> Result<Vec<crate::PipelineRun>> {
+        let rows = sqlx::query_as::<_, PipelineRunRow>(
+            r#"
+            SELECT * FROM pipeline_runs
+            WHERE pipeline_id = ? AND status IN ('pending', 'running', 'waiting_approval')
+            ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .bind(pipeline_id)
+        .fetch_all(executor)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Insert a triggered pipeline run, applying the pipeline's concurrency limit
+    ///
+    /// Without a limit the run is always admitted. At the limit, the run is
+    /// queued, the oldest active runs are cancelled to make room for it, or
+    /// it is rejected and recorded as cancelled so it never starts.
+    pub async fn admit_pipeline_run(
+        &self,
+        run: &crate::PipelineRun,
+        concurrency: Option<&crate::PipelineConcurrency>,
+    ) -> Result<crate::RunAdmission> {
+        use crate::{ConcurrencyAction, RunAdmission};
+
+        let Some(concurrency) = concurrency else {
+            let run_id = self.insert_pipeline_run(run).await?;
+            return Ok(RunAdmission::Admitted {
+                run_id,
+                cancelled: Vec::new(),
+            });
+        };
+
+        // Inserting first takes the database's write lock, so concurrent
+        // admissions wait for this one to commit before counting active runs
+        let mut tx = self.pool.begin().await?;
+        let run_id = Self::insert_pipeline_run_with(&mut *tx, run).await?;
+        let active: Vec<crate::PipelineRun> =
+            Self::list_active_pipeline_runs_with(&mut *tx, run.pipeline_id)
+                .await?
+                .into_iter()
+                .filter(|active| active.id != Some(run_id))
+                .collect();
+
+        let limit = concurrency.limit.max(1) as usize;
+        let excess = (active.len() + 1).saturating_sub(limit);
+        let admission = if excess == 0 {
+            RunAdmission::Admitted {
+                run_id,
+                cancelled: Vec::new(),
+            }
+        } else {
+            match concurrency.on_limit {
+                ConcurrencyAction::Queue => RunAdmission::Queued {
+                    run_id,
+                    position: excess,
+                },
+                ConcurrencyAction::CancelInProgress => {
+                    let mut cancelled = Vec::new();
+                    for mut previous in active.into_iter().take(excess) {
+                        previous.mark_cancelled();
+                        Self::update_pipeline_run_with(&mut *tx, &previous).await?;
+                        cancelled.extend(previous.id);
+                    }
+                    RunAdmission::Admitted { run_id, cancelled }
+                }
+                ConcurrencyAction::Reject => {
+                    let mut rejected = run.clone();
+                    rejected.id = Some(run_id);
+                    rejected.mark_cancelled();
+                    Self::update_pipeline_run_with(&mut *tx, &rejected).await?;
+                    RunAdmission::Rejected {
+                        run_id,
+                        in_progress: active.len(),
+                    }
+                }
+            }
+        };
+        tx.commit().await?;
+
+        Ok(admission)
+    }
+
+    /// Get the most recent run of a pipeline started by the given trigger event
+    pub async fn get_latest_pipeline_run_by_trigger(
+        &self,
+        pipeline_id: i64,
+        trigger_event: &str,
+    ) -> Result<Option<crate::PipelineRun>> {
+        let row = sqlx::query_as::<_, PipelineRunRow>(
+            r#"
+            SELECT * FROM pipeline_runs
+            WHERE pipeline_id = ? AND trigger_event = ?
+            ORDER BY created_at DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(pipeline_id)
+        .bind(trigger_event)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    // ==================== Pipeline Stage Operations ====================
+
+    /// Insert a new pipeline stage
+    pub async fn insert_pipeline_stage(&self, stage: &crate::PipelineStage) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO pipeline_stages (
+                run_id, stage_name, status, agent_id, cached_from_run_id, attempts,
+                started_at, completed_at, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(stage.run_id)
+        .bind(&stage.stage_name)
+        .bind(stage.status.as_str())
+        .bind(&stage.agent_id)
+        .bind(stage.cached_from_run_id)
+        .bind(stage.attempts)
+        .bind(stage.started_at.map(|dt| dt.to_rfc3339()))
+        .bind(stage.completed_at.map(|dt| dt.to_rfc3339()))
+        .bind(stage.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get pipeline stage by ID
+    pub async fn get_pipeline_stage(&self, id: i64) -> Result<Option<crate::PipelineStage>> {
+        let row =
+            sqlx::query_as::<_, PipelineStageRow>("SELECT * FROM pipeline_stages WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Get pipeline stage by name within a run
+    pub async fn get_pipeline_stage_by_name(
+        &self,
+        run_id: i64,
+        stage_name: &str,
+    ) -> Result<Option<crate::PipelineStage>> {
+        let row = sqlx::query_as::<_, PipelineStageRow>(
+            "SELECT * FROM pipeline_stages WHERE run_id = ? AND stage_name = ?",
+        )
+        .bind(run_id)
+        .bind(stage_name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Update pipeline stage
+    pub async fn update_pipeline_stage(&self, stage: &crate::PipelineStage) -> Result<()> {
+        let id = stage.id.ok_or_else(|| {
+            crate::Error::Other("Cannot update pipeline stage without ID".to_string())
+        })?;
+
+        sqlx::query(
+            r#"
+            UPDATE pipeline_stages SET
+                status = ?,
+                agent_id = ?,
+                cached_from_run_id = ?,
+                attempts = ?,
+                started_at = ?,
+                completed_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(stage.status.as_str())
+        .bind(&stage.agent_id)
+        .bind(stage.cached_from_run_id)
+        .bind(stage.attempts)
+        .bind(stage.started_at.map(|dt| dt.to_rfc3339()))
+        .bind(stage.completed_at.map(|dt| dt.to_rfc3339()))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List pipeline stages for a run
+    pub async fn list_pipeline_stages(&self, run_id: i64) -> Result<Vec<crate::PipelineStage>> {
+        let rows = sqlx::query_as::<_, PipelineStageRow>(
+            "SELECT * FROM pipeline_stages WHERE run_id = ? ORDER BY created_at ASC",
+        )
+        .bind(run_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// One page of a run's stages, in creation order
+    pub async fn list_pipeline_stages_page(
+        &self,
+        run_id: i64,
+        page: &PageQuery,
+    ) -> Result<RowPage<crate::PipelineStage>> {
+        self.fetch_page::<PipelineStageRow, _>(
+            "SELECT * FROM pipeline_stages",
+            |q| {
+                q.push(" AND run_id = ").push_bind(run_id);
+            },
+            SortKey::asc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
+    /// List pipeline stages by status within a run
+    pub async fn list_pipeline_stages_by_status(
+        &self,
+        run_id: i64,
+        status: crate::PipelineStageStatus,
+    ) -> Result<Vec<crate::PipelineStage>> {
+        let rows = sqlx::query_as::<_, PipelineStageRow>(
+            "SELECT * FROM pipeline_stages WHERE run_id = ? AND status = ? ORDER BY created_at ASC",
+        )
+        .bind(run_id)
+        .bind(status.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    // ==================== Pipeline Stage Cache Operations ====================
+
+    /// Record a succeeded stage's outcome under its cache key
+    ///
+    /// A later success with the same key replaces the earlier entry.
+    pub async fn insert_stage_cache_entry(
+        &self,
+        pipeline_id: i64,
+        stage_name: &str,
+        cache_key: &str,
+        commit_sha: &str,
+        run_id: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO pipeline_stage_cache (
+                pipeline_id, stage_name, cache_key, commit_sha, run_id, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(pipeline_id, stage_name, cache_key) DO UPDATE SET
+                commit_sha = excluded.commit_sha,
+                run_id = excluded.run_id,
+                created_at = excluded.created_at
+            "#,
+        )
+        .bind(pipeline_id)
+        .bind(stage_name)
+        .bind(cache_key)
+        .bind(commit_sha)
+        .bind(run_id)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Find the run whose cached outcome matches a stage's cache key
+    pub async fn find_stage_cache_run(
+        &self,
+        pipeline_id: i64,
+        stage_name: &str,
+        cache_key: &str,
+    ) -> Result<Option<i64>> {
+        let run_id: Option<i64> = sqlx::query_scalar(
+            r#"
+            SELECT run_id FROM pipeline_stage_cache
+            WHERE pipeline_id = ? AND stage_name = ? AND cache_key = ?
+            "#,
+        )
+        .bind(pipeline_id)
+        .bind(stage_name)
+        .bind(cache_key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(run_id)
+    }
+
+    /// Remove every cached stage outcome for a pipeline, returning how many were removed
+    pub async fn clear_stage_cache(&self, pipeline_id: i64) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM pipeline_stage_cache WHERE pipeline_id = ?")
+            .bind(pipeline_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // Rollback event operations
+
+    /// Insert a rollback event
+    pub async fn insert_rollback_event(&self, event: &crate::RollbackEvent) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO rollback_events
+                (run_id, failed_stage_name, rollback_to_stage, trigger_type, status,
+                 error_message, started_at, completed_at, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(event.run_id)
+        .bind(&event.failed_stage_name)
+        .bind(&event.rollback_to_stage)
+        .bind(event.trigger_type.as_str())
+        .bind(event.status.as_str())
+        .bind(&event.error_message)
+        .bind(event.started_at.map(|t| t.to_rfc3339()))
+        .bind(event.completed_at.map(|t| t.to_rfc3339()))
+        .bind(event.created_at.map(|t| t.to_rfc3339()))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get a rollback event by ID
+    pub async fn get_rollback_event(&self, id: i64) -> Result<Option<crate::RollbackEvent>> {
+        let row = sqlx::query_as::<_, RollbackEventRow>(
+            "SELECT * FROM rollback_events WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Update a rollback event
+    pub async fn update_rollback_event(&self, event: &crate::RollbackEvent) -> Result<()> {
+        let id = event.id.ok_or_else(|| {
+            crate::Error::Other("Cannot update rollback event without an ID".to_string())
+        })?;
+
+        sqlx::query(
+            r#"
+            UPDATE rollback_events
+            SET status = ?, error_message = ?, started_at = ?, completed_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(event.status.as_str())
+        .bind(&event.error_message)
+        .bind(event.started_at.map(|t| t.to_rfc3339()))
+        .bind(event.completed_at.map(|t| t.to_rfc3339()))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List all rollback events for a pipeline run
+    pub async fn list_rollback_events(&self, run_id: i64) -> Result<Vec<crate::RollbackEvent>> {
+        let rows = sqlx::query_as::<_, RollbackEventRow>(
+            "SELECT * FROM rollback_events WHERE run_id = ? ORDER BY created_at ASC",
+        )
+        .bind(run_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Count rollback events for a specific stage in a run (for loop prevention)
+    pub async fn count_rollback_events_for_stage(
+        &self,
+        run_id: i64,
+        stage_name: &str,
+    ) -> Result<i64> {
+        let result = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM rollback_events WHERE run_id = ? AND rollback_to_stage = ?",
+        )
+        .bind(run_id)
+        .bind(stage_name)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+}
+
+/// Token usage statistics
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TokenStats {
+    pub turn_count: i64,
+    pub total_input_tokens: i64,
+    pub total_output_tokens: i64,
+    pub total_cache_read_tokens: i64,
+    pub total_cache_write_tokens: i64,
+    pub avg_context_used: f64,
+    pub avg_messages_included: f64,
+    pub total_messages_summarized: i64,
+    pub cache_hit_rate: f64,
+}
+
+#[derive(sqlx::FromRow)]
+struct TokenStatsRow {
+    turn_count: i64,
+    total_input_tokens: i64,
+    total_output_tokens: i64,
+    total_cache_read_tokens: i64,
+    total_cache_write_tokens: i64,
+    avg_context_used: f64,
+    avg_messages_included: f64,
+    total_messages_summarized: i64,
+}
+
+impl From<TokenStatsRow> for TokenStats {
+    fn from(row: TokenStatsRow) -> Self {
+        let total_input = row.total_input_tokens;
+        let cache_read = row.total_cache_read_tokens;
+        let cache_hit_rate = if total_input > 0 {
+            (cache_read as f64 / total_input as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        Self {
+            turn_count: row.turn_count,
+            total_input_tokens: row.total_input_tokens,
+            total_output_tokens: row.total_output_tokens,
+            total_cache_read_tokens: row.total_cache_read_tokens,
+            total_cache_write_tokens: row.total_cache_write_tokens,
+            avg_context_used: row.avg_context_used,
+            avg_messages_included: row.avg_messages_included,
+            total_messages_summarized: row.total_messages_summarized,
+            cache_hit_rate,
+        }
+    }
+}
+
+/// Daily token usage for cost tracking
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DailyTokenUsage {
+    pub date: String,
+    pub model: String,
+    pub total_input_tokens: i64,
+    pub total_output_tokens: i64,
+    pub total_cache_read_tokens: i64,
+    pub total_cache_write_tokens: i64,
+    pub request_count: i64,
+    pub agent_count: i64,
+    pub estimated_cost_usd: Option<f64>,
+}
+
+#[derive(sqlx::FromRow)]
+struct DailyTokenUsageRow {
+    date: String,
+    model: String,
+    total_input_tokens: i64,
+    total_output_tokens: i64,
+    total_cache_read_tokens: i64,
+    total_cache_write_tokens: i64,
+    request_count: i64,
+    agent_count: i64,
+    estimated_cost_usd: Option<f64>,
+}
+
+impl From<DailyTokenUsageRow> for DailyTokenUsage {
+    fn from(row: DailyTokenUsageRow) -> Self {
+        Self {
+            date: row.date,
+            model: row.model,
+            total_input_tokens: row.total_input_tokens,
+            total_output_tokens: row.total_output_tokens,
+            total_cache_read_tokens: row.total_cache_read_tokens,
+            total_cache_write_tokens: row.total_cache_write_tokens,
+            request_count: row.request_count,
+            agent_count: row.agent_count,
+            estimated_cost_usd: row.estimated_cost_usd,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CostRecordRow {
+    date: String,
+    entity_id: String,
+    model: String,
+    total_input_tokens: i64,
+    total_output_tokens: i64,
+    total_cache_read_tokens: i64,
+    total_cache_write_tokens: i64,
+    request_count: i64,
+    estimated_cost_usd: f64,
+}
+
+impl From<CostRecordRow> for crate::cost_analytics::CostRecord {
+    fn from(row: CostRecordRow) -> Self {
+        Self {
+            date: row.date,
+            entity_id: row.entity_id,
+            model: row.model,
+            total_input_tokens: row.total_input_tokens,
+            total_output_tokens: row.total_output_tokens,
+            total_cache_read_tokens: row.total_cache_read_tokens,
+            total_cache_write_tokens: row.total_cache_write_tokens,
+            request_count: row.request_count,
+            estimated_cost_usd: row.estimated_cost_usd,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct EntityCostSummaryRow {
+    entity_id: String,
+    total_input_tokens: i64,
+    total_output_tokens: i64,
+    request_count: i64,
+    estimated_cost_usd: f64,
+}
+
+impl From<EntityCostSummaryRow> for crate::cost_analytics::EntityCostSummary {
+    fn from(row: EntityCostSummaryRow) -> Self {
+        Self {
+            entity_id: row.entity_id,
+            total_input_tokens: row.total_input_tokens,
+            total_output_tokens: row.total_output_tokens,
+            request_count: row.request_count,
+            estimated_cost_usd: row.estimated_cost_usd,
+        }
+    }
+}
+
+// ==================== Row Types for SQLx ====================
+
+#[derive(sqlx::FromRow)]
+struct AgentRow {
+    id: String,
+    agent_type: String,
+    custom_type: Option<String>,
+    system_prompt: Option<String>,
+    state: String,
+    task: String,
+    context: String,
+    session_id: Option<String>,
+    parent_agent_id: Option<String>,
+    worktree_id: Option<String>,
+    labels: String,
+    error_message: Option<String>,
+    trace_id: Option<String>,
+    created_at: String,
+    updated_at: String,
+    completed_at: Option<String>,
+}
+
+impl TryFrom<AgentRow> for Agent {
+    type Error = crate::Error;
+
+    fn try_from(row: AgentRow) -> Result<Self> {
+        Ok(Agent {
+            id: Uuid::parse_str(&row.id).map_err(|e| crate::Error::Other(e.to_string()))?,
+            agent_type: AgentType::from_str(&row.agent_type)?,
+            custom_type: row.custom_type,
+            system_prompt: row.system_prompt,
+            state: AgentState::from_str(&row.state)?,
+            task: row.task,
+            context: serde_json::from_str(&row.context)?,
+            session_id: row.session_id,
+            parent_agent_id: row
+                .parent_agent_id
+                .map(|s| Uuid::parse_str(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            worktree_id: row.worktree_id,
+            labels: serde_json::from_str(&row.labels)?,
+            error_message: row.error_message,
+            trace_id: row.trace_id,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            completed_at: row
+                .completed_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PromptVersionRow {
+    id: i64,
+    agent_type: String,
+    version: i32,
+    content: String,
+    description: Option<String>,
+    parent_version_id: Option<i64>,
+    is_active: bool,
+    created_at: String,
+    activated_at: Option<String>,
+    deactivated_at: Option<String>,
+}
+
+impl TryFrom<PromptVersionRow> for crate::prompt_optimization::PromptVersion {
+    type Error = crate::Error;
+
+    fn try_from(row: PromptVersionRow) -> Result<Self> {
+        Ok(Self {
+            id: row.id,
+            agent_type: row.agent_type,
+            version: row.version,
+            content: row.content,
+            description: row.description,
+            parent_version_id: row.parent_version_id,
+            is_active: row.is_active,
+            created_at: parse_datetime(&row.created_at)?,
+            activated_at: row.activated_at.map(|s| parse_datetime(&s)).transpose()?,
+            deactivated_at: row.deactivated_at.map(|s| parse_datetime(&s)).transpose()?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PromptEffectivenessRow {
+    prompt_version_id: i64,
+    usage_count: i64,
+    success_count: i64,
+    failure_count: i64,
+    total_tokens: i64,
+    total_duration_secs: f64,
+    total_feedback_score: f64,
+    feedback_count: i64,
+    updated_at: String,
+}
+
+impl TryFrom<PromptEffectivenessRow> for crate::prompt_optimization::PromptEffectiveness {
+    type Error = crate::Error;
+
+    fn try_from(row: PromptEffectivenessRow) -> Result<Self> {
+        let per_use = |total: f64| {
+            if row.usage_count > 0 {
+                total / row.usage_count as f64
+            } else {
+                0.0
+            }
+        };
+        Ok(Self {
+            prompt_version_id: row.prompt_version_id,
+            usage_count: row.usage_count,
+            success_count: row.success_count,
+            failure_count: row.failure_count,
+            success_rate: per_use(row.success_count as f64),
+            avg_tokens: per_use(row.total_tokens as f64),
+            avg_duration_secs: per_use(row.total_duration_secs),
+            avg_feedback_score: if row.feedback_count > 0 {
+                row.total_feedback_score / row.feedback_count as f64
+            } else {
+                0.0
+            },
+            updated_at: parse_datetime(&row.updated_at)?,
+        })
+    }
+}
+
+/// Statistics for an agent's message history
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AgentStats {
+    pub message_count: i64,
+    pub total_input_tokens: i64,
+    pub total_output_tokens: i64,
+    pub total_tokens: i64,
+    pub tool_call_count: i64,
+    pub error_count: i64,
+    pub first_message_at: Option<String>,
+    pub last_message_at: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AgentStatsRow {
+    message_count: i64,
+    total_input_tokens: Option<i64>,
+    total_output_tokens: Option<i64>,
+    tool_call_count: i64,
+    error_count: i64,
+    first_message_at: Option<String>,
+    last_message_at: Option<String>,
+}
+
+impl From<AgentStatsRow> for AgentStats {
+    fn from(row: AgentStatsRow) -> Self {
+        let input = row.total_input_tokens.unwrap_or(0);
+        let output = row.total_output_tokens.unwrap_or(0);
+        Self {
+            message_count: row.message_count,
+            total_input_tokens: input,
+            total_output_tokens: output,
+            total_tokens: input + output,
+            tool_call_count: row.tool_call_count,
+            error_count: row.error_count,
+            first_message_at: row.first_message_at,
+            last_message_at: row.last_message_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SessionRow {
+    id: String,
+    agent_id: String,
+    parent_id: Option<String>,
+    api_session_id: Option<String>,
+    total_tokens: i64,
+    is_forked: bool,
+    forked_at: Option<String>,
+    created_at: String,
+    closed_at: Option<String>,
+}
+
+impl TryFrom<SessionRow> for crate::Session {
+    type Error = crate::Error;
+
+    fn try_from(row: SessionRow) -> Result<Self> {
+        Ok(crate::Session {
+            id: row.id,
+            agent_id: Uuid::parse_str(&row.agent_id)
+                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            parent_id: row.parent_id,
+            api_session_id: row.api_session_id,
+            total_tokens: row.total_tokens,
+            is_forked: row.is_forked,
+            forked_at: row
+                .forked_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            closed_at: row
+                .closed_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MessageRow {
+    id: i64,
+    agent_id: String,
+    role: String,
+    content: String,
+    tool_calls: Option<String>,
+    tool_results: Option<String>,
+    input_tokens: i32,
+    output_tokens: i32,
+    created_at: String,
+}
+
+impl TryFrom<MessageRow> for Message {
+    type Error = crate::Error;
+
+    fn try_from(row: MessageRow) -> Result<Self> {
+        Ok(Message {
+            id: row.id,
+            agent_id: Uuid::parse_str(&row.agent_id)
+                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            role: MessageRole::from_str(&row.role)?,
+            content: row.content,
+            tool_calls: row
+                .tool_calls
+                .map(|s| serde_json::from_str(&s))
+                .transpose()?,
+            tool_results: row
+                .tool_results
+                .map(|s| serde_json::from_str(&s))
+                .transpose()?,
+            input_tokens: row.input_tokens,
+            output_tokens: row.output_tokens,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PrRow {
+    id: i64,
+    epic_id: Option<String>,
+    worktree_id: Option<String>,
+    branch_name: String,
+    title: Option<String>,
+    body: Option<String>,
+    pr_number: Option<i32>,
+    status: String,
+    merge_strategy: String,
+    agent_id: Option<String>,
+    error_message: Option<String>,
+    merge_queue_position: Option<i32>,
+    created_at: String,
+    updated_at: String,
+    merged_at: Option<String>,
+}
+
+impl TryFrom<PrRow> for PullRequest {
+    type Error = crate::Error;
+
+    fn try_from(row: PrRow) -> Result<Self> {
+        Ok(PullRequest {
+            id: row.id,
+            epic_id: row.epic_id,
+            worktree_id: row.worktree_id,
+            branch_name: row.branch_name,
+            title: row.title,
+            body: row.body,
+            pr_number: row.pr_number,
+            status: PrStatus::from_str(&row.status)?,
+            merge_strategy: MergeStrategy::from_str(&row.merge_strategy)?,
+            agent_id: row
+                .agent_id
+                .map(|s| Uuid::parse_str(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            error_message: row.error_message,
+            merge_queue_position: row.merge_queue_position,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            merged_at: row
+                .merged_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct EpicRow {
+    id: String,
+    title: String,
+    source_file: Option<String>,
+    pattern: Option<String>,
+    status: String,
+    current_phase: Option<String>,
+    agent_id: Option<String>,
+    pr_id: Option<i64>,
+    error_message: Option<String>,
+    created_at: String,
+    updated_at: String,
+    completed_at: Option<String>,
+}
+
+impl TryFrom<EpicRow> for Epic {
+    type Error = crate::Error;
+
+    fn try_from(row: EpicRow) -> Result<Self> {
+        Ok(Epic {
+            id: row.id,
+            title: row.title,
+            source_file: row.source_file,
+            pattern: row.pattern,
+            status: EpicStatus::from_str(&row.status)?,
+            // Phases are stored in their display form, e.g. CREATE_BRANCH
+            current_phase: row
+                .current_phase
+                .map(|p| serde_json::from_str(&format!("\"{}\"", p.to_lowercase())))
+                .transpose()?,
+            agent_id: row
+                .agent_id
+                .map(|s| Uuid::parse_str(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            pr_id: row.pr_id,
+            error_message: row.error_message,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            completed_at: row
+                .completed_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct StoryRow {
+    id: String,
+    epic_id: String,
+    title: String,
+    description: Option<String>,
+    acceptance_criteria: Option<String>,
+    status: String,
+    agent_id: Option<String>,
+    created_at: String,
+    updated_at: String,
+    completed_at: Option<String>,
+}
+
+impl TryFrom<StoryRow> for Story {
+    type Error = crate::Error;
+
+    fn try_from(row: StoryRow) -> Result<Self> {
+        Ok(Story {
+            id: row.id,
+            epic_id: row.epic_id,
+            title: row.title,
+            description: row.description,
+            acceptance_criteria: row
+                .acceptance_criteria
+                .map(|s| serde_json::from_str(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            status: StoryStatus::from_str(&row.status)?,
+            agent_id: row
+                .agent_id
+                .map(|s| Uuid::parse_str(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            completed_at: row
+                .completed_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct StepOutputRow {
+    id: i64,
+    agent_id: String,
+    skill_name: String,
+    output_type: String,
+    data: String,
+    consumed: bool,
+    consumed_by: Option<String>,
+    consumed_at: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<StepOutputRow> for StepOutput {
+    type Error = crate::Error;
+
+    fn try_from(row: StepOutputRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        let agent_uuid =
+            Uuid::parse_str(&row.agent_id).map_err(|e| crate::Error::Other(e.to_string()))?;
+
+        let consumed_by = row
+            .consumed_by
+            .map(|s| Uuid::parse_str(&s))
+            .transpose()
+            .map_err(|e| crate::Error::Other(e.to_string()))?
+            .map(AgentId::from_uuid);
+
+        let consumed_at = row
+            .consumed_at
+            .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+            .transpose()
+            .map_err(|e| crate::Error::Other(e.to_string()))?
+            .map(Into::into);
+
+        let output_type = StepOutputType::from_str(&row.output_type)?;
+
+        Ok(StepOutput {
+            id: Some(row.id),
+            agent_id: AgentId::from_uuid(agent_uuid),
+            skill_name: row.skill_name,
+            output_type,
+            data: serde_json::from_str(&row.data)?,
+            consumed: row.consumed,
+            consumed_by,
+            consumed_at,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct InstructionRow {
+    id: i64,
+    name: String,
+    content: String,
+    scope: String,
+    agent_type: Option<String>,
+    priority: i32,
+    enabled: bool,
+    source: String,
+    confidence: f64,
+    tags: Option<String>,
+    created_at: String,
+    updated_at: String,
+    created_by: Option<String>,
+}
+
+impl TryFrom<InstructionRow> for CustomInstruction {
+    type Error = crate::Error;
+
+    fn try_from(row: InstructionRow) -> Result<Self> {
+        let agent_type = row
+            .agent_type
+            .map(|s| AgentType::from_str(&s))
+            .transpose()?;
+
+        let tags: Vec<String> = row
+            .tags
+            .map(|s| serde_json::from_str(&s))
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(CustomInstruction {
+            id: row.id,
+            name: row.name,
+            content: row.content,
+            scope: InstructionScope::from_str(&row.scope)?,
+            agent_type,
+            priority: row.priority,
+            enabled: row.enabled,
+            source: InstructionSource::from_str(&row.source)?,
+            confidence: row.confidence,
+            tags,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            created_by: row.created_by,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct EffectivenessRow {
+    instruction_id: i64,
+    usage_count: i64,
+    success_count: i64,
+    failure_count: i64,
+    penalty_score: f64,
+    avg_completion_time: Option<f64>,
+    last_success_at: Option<String>,
+    last_failure_at: Option<String>,
+    last_penalty_at: Option<String>,
+    updated_at: String,
+}
+
+impl TryFrom<EffectivenessRow> for InstructionEffectiveness {
+    type Error = crate::Error;
+
+    fn try_from(row: EffectivenessRow) -> Result<Self> {
+        let last_success_at = row
+            .last_success_at
+            .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+            .transpose()
+            .map_err(|e| crate::Error::Other(e.to_string()))?
+            .map(Into::into);
+
+        let last_failure_at = row
+            .last_failure_at
+            .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+            .transpose()
+            .map_err(|e| crate::Error::Other(e.to_string()))?
+            .map(Into::into);
+
+        let last_penalty_at = row
+            .last_penalty_at
+            .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+            .transpose()
+            .map_err(|e| crate::Error::Other(e.to_string()))?
+            .map(Into::into);
+
+        let success_rate = if row.usage_count > 0 {
+            row.success_count as f64 / row.usage_count as f64
+        } else {
+            0.0
+        };
+
+        Ok(InstructionEffectiveness {
+            instruction_id: row.instruction_id,
+            usage_count: row.usage_count,
+            success_count: row.success_count,
+            failure_count: row.failure_count,
+            penalty_score: row.penalty_score,
+            avg_completion_time: row.avg_completion_time,
+            success_rate,
+            last_success_at,
+            last_failure_at,
+            last_penalty_at,
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PatternRow {
+    id: i64,
+    pattern_type: String,
+    agent_type: Option<String>,
+    pattern_signature: String,
+    pattern_data: String,
+    occurrence_count: i64,
+    first_seen_at: String,
+    last_seen_at: String,
+    instruction_id: Option<i64>,
+    status: String,
+}
+
+impl TryFrom<PatternRow> for LearningPattern {
+    type Error = crate::Error;
+
+    fn try_from(row: PatternRow) -> Result<Self> {
+        let agent_type = row
+            .agent_type
+            .map(|s| AgentType::from_str(&s))
+            .transpose()?;
+
+        Ok(LearningPattern {
+            id: row.id,
+            pattern_type: PatternType::from_str(&row.pattern_type)?,
+            agent_type,
+            pattern_signature: row.pattern_signature,
+            pattern_data: serde_json::from_str(&row.pattern_data)?,
+            occurrence_count: row.occurrence_count,
+            first_seen_at: chrono::DateTime::parse_from_rfc3339(&row.first_seen_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            last_seen_at: chrono::DateTime::parse_from_rfc3339(&row.last_seen_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            instruction_id: row.instruction_id,
+            status: PatternStatus::from_str(&row.status)?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ScheduleRow {
+    id: i64,
+    name: String,
+    cron_expression: String,
+    run_at: Option<String>,
+    timezone: String,
+    agent_type: String,
+    task: String,
+    enabled: bool,
+    jitter_secs: i64,
+    overlap_policy: String,
+    depends_on: Option<String>,
+    max_tokens: Option<i64>,
+    max_cost_usd: Option<f64>,
+    spend_cap_period: String,
+    missed_runs: i64,
+    consecutive_missed_runs: i64,
+    max_consecutive_misses: Option<i64>,
+    last_run: Option<String>,
+    next_run: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<ScheduleRow> for Schedule {
+    type Error = crate::Error;
+
+    fn try_from(row: ScheduleRow) -> Result<Self> {
+        Ok(Schedule {
+            id: row.id,
+            name: row.name,
+            cron_expression: row.cron_expression,
+            run_at: row
+                .run_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            timezone: row.timezone,
+            agent_type: row.agent_type,
+            task: row.task,
+            enabled: row.enabled,
+            jitter_secs: row.jitter_secs.max(0) as u32,
+            overlap_policy: row.overlap_policy.parse()?,
+            depends_on: row.depends_on,
+            max_tokens: row.max_tokens.map(|tokens| tokens.max(0) as u64),
+            max_cost_usd: row.max_cost_usd,
+            spend_cap_period: row.spend_cap_period.parse().map_err(crate::Error::Other)?,
+            missed_runs: row.missed_runs.max(0) as u32,
+            consecutive_missed_runs: row.consecutive_missed_runs.max(0) as u32,
+            max_consecutive_misses: row
+                .max_consecutive_misses
+                .map(|misses| misses.max(0) as u32),
+            last_run: row
+                .last_run
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            next_run: row
+                .next_run
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WebhookEventRow {
+    id: i64,
+    delivery_id: String,
+    event_type: String,
+    source: String,
+    payload: String,
+    status: String,
+    retry_count: i32,
+    max_retries: i32,
+    error_message: Option<String>,
+    next_retry_at: Option<String>,
+    received_at: String,
+    processed_at: Option<String>,
+    created_at: String,
+    updated_at: String,
+}
+
+impl TryFrom<WebhookEventRow> for WebhookEvent {
+    type Error = crate::Error;
+
+    fn try_from(row: WebhookEventRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(WebhookEvent {
+            id: Some(row.id),
+            delivery_id: row.delivery_id,
+            event_type: row.event_type,
+            source: row.source,
+            payload: row.payload,
+            status: WebhookEventStatus::from_str(&row.status)?,
+            retry_count: row.retry_count,
+            max_retries: row.max_retries,
+            error_message: row.error_message,
+            next_retry_at: row
+                .next_retry_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            received_at: chrono::DateTime::parse_from_rfc3339(&row.received_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            processed_at: row
+                .processed_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WebhookEventSummaryRow {
+    id: i64,
+    delivery_id: String,
+    event_type: String,
+    action: Option<String>,
+    source: String,
+    status: String,
+    retry_count: i32,
+    max_retries: i32,
+    error_message: Option<String>,
+    next_retry_at: Option<String>,
+    received_at: String,
+    processed_at: Option<String>,
+    payload_bytes: i64,
+}
+
+impl TryFrom<WebhookEventSummaryRow> for WebhookEventSummary {
+    type Error = crate::Error;
+
+    fn try_from(row: WebhookEventSummaryRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(WebhookEventSummary {
+            id: row.id,
+            delivery_id: row.delivery_id,
+            event_type: row.event_type,
+            action: row.action,
+            source: row.source,
+            status: WebhookEventStatus::from_str(&row.status)?,
+            retry_count: row.retry_count,
+            max_retries: row.max_retries,
+            error_message: row.error_message,
+            next_retry_at: row
+                .next_retry_at
+                .as_deref()
+                .map(parse_datetime)
+                .transpose()?,
+            received_at: parse_datetime(&row.received_at)?,
+            processed_at: row
+                .processed_at
+                .as_deref()
+                .map(parse_datetime)
+                .transpose()?,
+            payload_bytes: row.payload_bytes as usize,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WebhookSourceStatsRow {
+    source: String,
+    total: i64,
+    pending: i64,
+    processing: i64,
+    completed: i64,
+    failed: i64,
+    dead_letter: i64,
+    last_received_at: Option<String>,
+}
+
+impl TryFrom<WebhookSourceStatsRow> for WebhookSourceStats {
+    type Error = crate::Error;
+
+    fn try_from(row: WebhookSourceStatsRow) -> Result<Self> {
+        Ok(WebhookSourceStats {
+            source: row.source,
+            total: row.total,
+            pending: row.pending,
+            processing: row.processing,
+            completed: row.completed,
+            failed: row.failed,
+            dead_letter: row.dead_letter,
+            last_received_at: row
+                .last_received_at
+                .as_deref()
+                .map(parse_datetime)
+                .transpose()?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ScheduleBlackoutRow {
+    id: i64,
+    name: String,
+    schedule_id: Option<i64>,
+    starts_at: String,
+    ends_at: String,
+    reason: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<ScheduleBlackoutRow> for ScheduleBlackout {
+    type Error = crate::Error;
+
+    fn try_from(row: ScheduleBlackoutRow) -> Result<Self> {
+        let parse = |s: &str| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .map(Into::into)
+                .map_err(|e| crate::Error::Other(e.to_string()))
+        };
+
+        Ok(ScheduleBlackout {
+            id: row.id,
+            name: row.name,
+            schedule_id: row.schedule_id,
+            starts_at: parse(&row.starts_at)?,
+            ends_at: parse(&row.ends_at)?,
+            reason: row.reason,
+            created_at: parse(&row.created_at)?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ScheduleStatsRow {
+    missed_runs: i64,
+    consecutive_missed_runs: i64,
+    total_runs: i64,
+    succeeded_runs: i64,
+    failed_runs: i64,
+    skipped_runs: i64,
+    avg_duration_secs: Option<f64>,
+}
+
+impl From<ScheduleStatsRow> for ScheduleStats {
+    fn from(row: ScheduleStatsRow) -> Self {
+        Self {
+            total_runs: row.total_runs.max(0) as u64,
+            succeeded_runs: row.succeeded_runs.max(0) as u64,
+            failed_runs: row.failed_runs.max(0) as u64,
+            skipped_runs: row.skipped_runs.max(0) as u64,
+            avg_duration_secs: row.avg_duration_secs,
+            missed_runs: row.missed_runs.max(0) as u32,
+            consecutive_missed_runs: row.consecutive_missed_runs.max(0) as u32,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ScheduleRunRow {
+    id: i64,
+    schedule_id: i64,
+    agent_id: Option<String>,
+    started_at: String,
+    completed_at: Option<String>,
+    status: String,
+    error_message: Option<String>,
+}
+
+impl TryFrom<ScheduleRunRow> for ScheduleRun {
+    type Error = crate::Error;
+
+    fn try_from(row: ScheduleRunRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(ScheduleRun {
+            id: row.id,
+            schedule_id: row.schedule_id,
+            agent_id: row.agent_id,
+            started_at: chrono::DateTime::parse_from_rfc3339(&row.started_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            completed_at: row
+                .completed_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            status: ScheduleRunStatus::from_str(&row.status)?,
+            error_message: row.error_message,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CustomWebhookRow {
+    id: i64,
+    name: String,
+    definition: String,
+    enabled: i32,
+    created_at: String,
+}
+
+impl TryFrom<CustomWebhookRow> for crate::CustomWebhook {
+    type Error = crate::Error;
+
+    fn try_from(row: CustomWebhookRow) -> Result<Self> {
+        Ok(crate::CustomWebhook {
+            id: Some(row.id),
+            name: row.name,
+            definition: row.definition,
+            enabled: row.enabled != 0,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WebhookSourceRow {
+    id: i64,
+    name: String,
+    scheme: String,
+    header: String,
+    secrets: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl TryFrom<WebhookSourceRow> for crate::WebhookSource {
+    type Error = crate::Error;
+
+    fn try_from(row: WebhookSourceRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(crate::WebhookSource {
+            id: Some(row.id),
+            name: row.name,
+            scheme: crate::SignatureScheme::from_str(&row.scheme)?,
+            header: row.header,
+            secrets: serde_json::from_str(&row.secrets)?,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct OutboundWebhookRow {
+    id: i64,
+    url: String,
+    secret: Option<String>,
+    events: String,
+    description: Option<String>,
+    enabled: i32,
+    created_at: String,
+}
+
+impl TryFrom<OutboundWebhookRow> for crate::OutboundWebhook {
+    type Error = crate::Error;
+
+    fn try_from(row: OutboundWebhookRow) -> Result<Self> {
+        Ok(crate::OutboundWebhook {
+            id: Some(row.id),
+            url: row.url,
+            secret: row.secret,
+            events: serde_json::from_str(&row.events)?,
+            description: row.description,
+            enabled: row.enabled != 0,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct OutboundWebhookDeliveryRow {
+    id: i64,
+    webhook_id: i64,
+    event_type: String,
+    payload: String,
+    status: String,
+    attempts: i32,
+    max_attempts: i32,
+    response_status: Option<i32>,
+    error_message: Option<String>,
+    next_attempt_at: String,
+    created_at: String,
+    delivered_at: Option<String>,
+}
+
+impl TryFrom<OutboundWebhookDeliveryRow> for crate::OutboundWebhookDelivery {
+    type Error = crate::Error;
+
+    fn try_from(row: OutboundWebhookDeliveryRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        let parse = |s: &str| -> Result<chrono::DateTime<chrono::Utc>> {
+            Ok(chrono::DateTime::parse_from_rfc3339(s)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into())
+        };
+
+        Ok(crate::OutboundWebhookDelivery {
+            id: Some(row.id),
+            webhook_id: row.webhook_id,
+            event_type: row.event_type,
+            payload: row.payload,
+            status: crate::OutboundDeliveryStatus::from_str(&row.status)?,
+            attempts: row.attempts,
+            max_attempts: row.max_attempts,
+            response_status: row.response_status,
+            error_message: row.error_message,
+            next_attempt_at: parse(&row.next_attempt_at)?,
+            created_at: parse(&row.created_at)?,
+            delivered_at: row.delivered_at.as_deref().map(parse).transpose()?,
+        })
+    }
+}
+
+// ==================== Pipeline Row Structs ====================
+
+#[derive(sqlx::FromRow)]
+struct PipelineRow {
+    id: i64,
+    name: String,
+    definition: String,
+    enabled: i32,
+    created_at: String,
+}
+
+impl TryFrom<PipelineRow> for crate::Pipeline {
+    type Error = crate::Error;
+
+    fn try_from(row: PipelineRow) -> Result<Self> {
+        Ok(crate::Pipeline {
+            id: Some(row.id),
+            name: row.name,
+            definition: row.definition,
+            enabled: row.enabled != 0,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PipelineRunRow {
+    id: i64,
+    pipeline_id: i64,
+    status: String,
+    trigger_event: Option<String>,
+    commit_sha: Option<String>,
+    use_cache: i32,
+    variables: String,
+    trigger_payload: Option<String>,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<PipelineRunRow> for crate::PipelineRun {
+    type Error = crate::Error;
+
+    fn try_from(row: PipelineRunRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(crate::PipelineRun {
+            id: Some(row.id),
+            pipeline_id: row.pipeline_id,
+            status: crate::PipelineRunStatus::from_str(&row.status)?,
+            trigger_event: row.trigger_event,
+            commit_sha: row.commit_sha,
+            use_cache: row.use_cache != 0,
+            variables: serde_json::from_str(&row.variables)?,
+            trigger_payload: row
+                .trigger_payload
+                .map(|payload| serde_json::from_str(&payload))
+                .transpose()?,
+            started_at: row
+                .started_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            completed_at: row
+                .completed_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PipelineStageRow {
+    id: i64,
+    run_id: i64,
+    stage_name: String,
+    status: String,
+    agent_id: Option<String>,
+    cached_from_run_id: Option<i64>,
+    attempts: i32,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<PipelineStageRow> for crate::PipelineStage {
+    type Error = crate::Error;
+
+    fn try_from(row: PipelineStageRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(crate::PipelineStage {
+            id: Some(row.id),
+            run_id: row.run_id,
+            stage_name: row.stage_name,
+            status: crate::PipelineStageStatus::from_str(&row.status)?,
+            agent_id: row.agent_id,
+            cached_from_run_id: row.cached_from_run_id,
+            attempts: row.attempts,
+            started_at: row
+                .started_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            completed_at: row
+                .completed_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RollbackEventRow {
+    id: i64,
+    run_id: i64,
+    failed_stage_name: String,
+    rollback_to_stage: String,
+    trigger_type: String,
+    status: String,
+    error_message: Option<String>,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+    created_at: Option<String>,
+}
+
+impl TryFrom<RollbackEventRow> for crate::RollbackEvent {
+    type Error = crate::Error;
+
+    fn try_from(row: RollbackEventRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(crate::RollbackEvent {
+            id: Some(row.id),
+            run_id: row.run_id,
+            failed_stage_name: row.failed_stage_name,
+            rollback_to_stage: row.rollback_to_stage,
+            trigger_type: crate::RollbackTriggerType::from_str(&row.trigger_type)?,
+            status: crate::RollbackStatus::from_str(&row.status)?,
+            error_message: row.error_message,
+            started_at: row
+                .started_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            completed_at: row
+                .completed_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            created_at: row
+                .created_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+        })
+    }
+}
+
+impl Database {
+    // Approval Operations
+
+    /// Create a new approval request
+    pub async fn create_approval_request(
+        &self,
+        request: ApprovalRequest,
+    ) -> Result<ApprovalRequest> {
+        let mut request = request;
+        let result = sqlx::query(
+            r#"
+            INSERT INTO approval_requests
+            (stage_id, run_id, status, required_approvers, required_count,
+             approval_count, rejection_count, timeout_seconds, timeout_action,
+             timeout_at, escalate_to, escalated_at, resolved_at, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(request.stage_id)
+        .bind(request.run_id)
+        .bind(request.status.as_str())
+        .bind(&request.required_approvers)
+        .bind(request.required_count)
+        .bind(request.approval_count)
+        .bind(request.rejection_count)
+        .bind(request.timeout_seconds)
+        .bind(&request.timeout_action)
+        .bind(request.timeout_at.map(|t| t.to_rfc3339()))
+        .bind(&request.escalate_to)
+        .bind(request.escalated_at.map(|t| t.to_rfc3339()))
+        .bind(request.resolved_at.map(|t| t.to_rfc3339()))
+        .bind(request.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        request.id = Some(result.last_insert_rowid());
+        Ok(request)
+    }
+
+    /// Get an approval request by ID
+    pub async fn get_approval_request(&self, id: i64) -> Result<Option<ApprovalRequest>> {
+        let row = sqlx::query_as::<_, ApprovalRequestRow>(
+            r#"
+            SELECT id, stage_id, run_id, status, required_approvers, required_count,
+                   approval_count, rejection_count, timeout_seconds, timeout_action,
+                   timeout_at, escalate_to, escalated_at, resolved_at, created_at
+            FROM approval_requests
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Get approval request by stage ID
+    pub async fn get_approval_request_by_stage(
+        &self,
+        stage_id: i64,
+    ) -> Result<Option<ApprovalRequest>> {
+        let row = sqlx::query_as::<_, ApprovalRequestRow>(
+            r#"
+            SELECT id, stage_id, run_id, status, required_approvers, required_count,
+                   approval_count, rejection_count, timeout_seconds, timeout_action,
+                   timeout_at, escalate_to, escalated_at, resolved_at, created_at
+            FROM approval_requests
+            WHERE stage_id = ?
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(stage_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Update an approval request
+    pub async fn update_approval_request(&self, request: &ApprovalRequest) -> Result<()> {
+        let id = request
+            .id
+            .ok_or_else(|| crate::Error::Other("Approval request ID is required".to_string()))?;
+
+        sqlx::query(
+            r#"
+            UPDATE approval_requests
+            SET status = ?, approval_count = ?, rejection_count = ?,
+                required_approvers = ?, timeout_at = ?, escalate_to = ?,
+                escalated_at = ?, resolved_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(request.status.as_str())
+        .bind(request.approval_count)
+        .bind(request.rejection_count)
+        .bind(&request.required_approvers)
+        .bind(request.timeout_at.map(|t| t.to_rfc3339()))
+        .bind(&request.escalate_to)
+        .bind(request.escalated_at.map(|t| t.to_rfc3339()))
+        .bind(request.resolved_at.map(|t| t.to_rfc3339()))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List approval requests awaiting a decision, including delegated ones
+    pub async fn list_pending_approvals(&self) -> Result<Vec<ApprovalRequest>> {
+        let rows = sqlx::query_as::<_, ApprovalRequestRow>(
+            r#"
+            SELECT id, stage_id, run_id, status, required_approvers, required_count,
+                   approval_count, rejection_count, timeout_seconds, timeout_action,
+                   timeout_at, escalate_to, escalated_at, resolved_at, created_at
+            FROM approval_requests
+            WHERE status IN ('pending', 'delegated')
+            ORDER BY created_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// One page of pending and delegated approvals, oldest first
+    pub async fn list_pending_approvals_page(
+        &self,
+        page: &PageQuery,
+    ) -> Result<RowPage<ApprovalRequest>> {
+        self.fetch_page::<ApprovalRequestRow, _>(
+            r#"
+            SELECT id, stage_id, run_id, status, required_approvers, required_count,
+                   approval_count, rejection_count, timeout_seconds, timeout_action,
+                   timeout_at, escalate_to, escalated_at, resolved_at, created_at
+            FROM approval_requests
+            "#,
+            |q| {
+                q.push(" AND status IN ('pending', 'delegated')");
+            },
+            SortKey::asc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
+    /// List approval requests that have timed out
+    pub async fn list_timed_out_approvals(&self) -> Result<Vec<ApprovalRequest>> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let rows = sqlx::query_as::<_, ApprovalRequestRow>(
+            r#"
+            SELECT id, stage_id, run_id, status, required_approvers, required_count,
+                   approval_count, rejection_count, timeout_seconds, timeout_action,
+                   timeout_at, escalate_to, escalated_at, resolved_at, created_at
+            FROM approval_requests
+            WHERE status IN ('pending', 'delegated')
+              AND timeout_at IS NOT NULL
+              AND timeout_at < ?
+            ORDER BY timeout_at ASC
+            "#,
+        )
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Create an approval decision
+    pub async fn create_approval_decision(
+        &self,
+        decision: ApprovalDecision,
+    ) -> Result<ApprovalDecision> {
+        let mut decision = decision;
+        let result = sqlx::query(
+            r#"
+            INSERT INTO approval_decisions
+            (approval_id, approver, decision, comment, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(decision.approval_id)
+        .bind(&decision.approver)
+        .bind(decision.decision as i32)
+        .bind(&decision.comment)
+        .bind(decision.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        decision.id = Some(result.last_insert_rowid());
+        Ok(decision)
+    }
+
+    /// Get all decisions for an approval request
+    pub async fn get_approval_decisions(&self, approval_id: i64) -> Result<Vec<ApprovalDecision>> {
+        let rows = sqlx::query_as::<_, ApprovalDecisionRow>(
+            r#"
+            SELECT id, approval_id, approver, decision, comment, created_at
+            FROM approval_decisions
+            WHERE approval_id = ?
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(approval_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+}
+
+// Database row types for approval
+
+#[derive(sqlx::FromRow)]
+struct ApprovalRequestRow {
+    id: i64,
+    stage_id: i64,
+    run_id: i64,
+    status: String,
+    required_approvers: String,
+    required_count: i32,
+    approval_count: i32,
+    rejection_count: i32,
+    timeout_seconds: Option<i64>,
+    timeout_action: Option<String>,
+    timeout_at: Option<String>,
+    escalate_to: Option<String>,
+    escalated_at: Option<String>,
+    resolved_at: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<ApprovalRequestRow> for ApprovalRequest {
+    type Error = crate::Error;
+
+    fn try_from(row: ApprovalRequestRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(Self {
+            id: Some(row.id),
+            stage_id: row.stage_id,
+            run_id: row.run_id,
+            status: ApprovalStatus::from_str(&row.status)?,
+            required_approvers: row.required_approvers,
+            required_count: row.required_count,
+            approval_count: row.approval_count,
+            rejection_count: row.rejection_count,
+            timeout_seconds: row.timeout_seconds,
+            timeout_action: row.timeout_action,
+            timeout_at: row
+                .timeout_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            escalate_to: row.escalate_to,
+            escalated_at: row
+                .escalated_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            resolved_at: row
+                .resolved_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ApprovalDecisionRow {
+    id: i64,
+    approval_id: i64,
+    approver: String,
+    decision: i32,
+    comment: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<ApprovalDecisionRow> for ApprovalDecision {
+    type Error = crate::Error;
+
+    fn try_from(row: ApprovalDecisionRow) -> Result<Self> {
+        Ok(Self {
+            id: Some(row.id),
+            approval_id: row.approval_id,
+            approver: row.approver,
+            decision: row.decision != 0,
+            comment: row.comment,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+// ==================== Success Pattern Row Struct ====================
+
+#[derive(sqlx::FromRow)]
+struct SuccessPatternRow {
+    id: i64,
+    pattern_type: String,
+    agent_type: Option<String>,
+    task_type: Option<String>,
+    pattern_signature: String,
+    pattern_data: String,
+    occurrence_count: i64,
+    avg_completion_time_ms: Option<i64>,
+    avg_token_usage: Option<i64>,
+    success_rate: f64,
+    first_seen_at: String,
+    last_seen_at: String,
+}
+
+impl TryFrom<SuccessPatternRow> for SuccessPattern {
+    type Error = crate::Error;
+
+    fn try_from(row: SuccessPatternRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(SuccessPattern {
+            id: row.id,
+            pattern_type: SuccessPatternType::from_str(&row.pattern_type)?,
+            agent_type: row
+                .agent_type
+                .map(|s| AgentType::from_str(&s))
+                .transpose()?,
+            task_type: row.task_type,
+            pattern_signature: row.pattern_signature,
+            pattern_data: serde_json::from_str(&row.pattern_data)
+                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            occurrence_count: row.occurrence_count,
+            avg_completion_time_ms: row.avg_completion_time_ms,
+            avg_token_usage: row.avg_token_usage,
+            success_rate: row.success_rate,
+            first_seen_at: chrono::DateTime::parse_from_rfc3339(&row.first_seen_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            last_seen_at: chrono::DateTime::parse_from_rfc3339(&row.last_seen_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+// ==================== Feedback Row Structs ====================
+
+#[derive(sqlx::FromRow)]
+struct FeedbackRow {
+    id: i64,
+    agent_id: String,
+    message_id: Option<i64>,
+    rating: String,
+    comment: Option<String>,
+    source: String,
+    created_by: String,
+    created_at: String,
+}
+
+impl TryFrom<FeedbackRow> for Feedback {
+    type Error = crate::Error;
+
+    fn try_from(row: FeedbackRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(Feedback {
+            id: row.id,
+            agent_id: Uuid::parse_str(&row.agent_id)
+                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            message_id: row.message_id,
+            rating: FeedbackRating::from_str(&row.rating)?,
+            comment: row.comment,
+            source: FeedbackSource::from_str(&row.source)?,
+            created_by: row.created_by,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct FeedbackStatsRow {
+    #[allow(dead_code)]
+    total: i64,
+    positive: i64,
+    negative: i64,
+    neutral: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct FeedbackStatsByTypeRow {
+    agent_type: String,
+    #[allow(dead_code)]
+    total: i64,
+    positive: i64,
+    negative: i64,
+    neutral: i64,
+}
+
+// ==================== Effectiveness Analysis Row Structs ====================
+
+/// Row for effectiveness analysis queries
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct EffectivenessAnalysisRow {
+    pub instruction_id: i64,
+    pub name: String,
+    pub instruction_source: String,
+    pub enabled: bool,
+    pub usage_count: i64,
+    pub success_count: i64,
+    pub failure_count: i64,
+    pub penalty_score: f64,
+    pub avg_completion_time: f64,
+    pub success_rate: f64,
+    pub last_success_at: Option<String>,
+    pub last_failure_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl EffectivenessAnalysisRow {
+    /// Get the effectiveness level as a string
+    pub fn effectiveness_level(&self) -> &'static str {
+        if self.usage_count < 5 {
+            "insufficient_data"
+        } else if self.success_rate >= 0.8 {
+            "high"
+        } else if self.success_rate >= 0.5 {
+            "medium"
+        } else if self.success_rate >= 0.3 {
+            "low"
+        } else {
+            "very_low"
+        }
+    }
+
+    /// Check if this instruction is considered ineffective
+    pub fn is_ineffective(&self) -> bool {
+        self.usage_count >= 5 && self.success_rate < 0.5
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct EffectivenessSummaryRow {
+    total_instructions: i64,
+    enabled_count: i64,
+    used_count: i64,
+    avg_success_rate: f64,
+    avg_penalty_score: f64,
+    total_usage: i64,
+    ineffective_count: i64,
+}
+
+/// Summary of instruction effectiveness across the system
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EffectivenessSummary {
+    pub total_instructions: i64,
+    pub enabled_count: i64,
+    pub used_count: i64,
+    pub avg_success_rate: f64,
+    pub avg_penalty_score: f64,
+    pub total_usage: i64,
+    pub ineffective_count: i64,
+}
+
+// ==================== Experiment Row Structs ====================
+
+#[derive(sqlx::FromRow)]
+struct ExperimentRow {
+    id: i64,
+    name: String,
+    description: Option<String>,
+    hypothesis: Option<String>,
+    experiment_type: String,
+    metric: String,
+    agent_type: Option<String>,
+    status: String,
+    min_samples: i64,
+    confidence_level: f64,
+    created_at: String,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+    winner_variant_id: Option<i64>,
+}
+
+impl TryFrom<ExperimentRow> for Experiment {
+    type Error = crate::Error;
+
+    fn try_from(row: ExperimentRow) -> Result<Self> {
+        use std::str::FromStr;
+        Ok(Experiment {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            hypothesis: row.hypothesis,
+            experiment_type: ExperimentType::from_str(&row.experiment_type)?,
+            metric: ExperimentMetric::from_str(&row.metric)?,
+            agent_type: row.agent_type,
+            status: ExperimentStatus::from_str(&row.status)?,
+            min_samples: row.min_samples,
+            confidence_level: row.confidence_level,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            started_at: row
+                .started_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            completed_at: row
+                .completed_at
+                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(Into::into),
+            winner_variant_id: row.winner_variant_id,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ExperimentVariantRow {
+    id: i64,
+    experiment_id: i64,
+    name: String,
+    description: Option<String>,
+    is_control: bool,
+    weight: i32,
+    config: String,
+    created_at: String,
+}
+
+impl TryFrom<ExperimentVariantRow> for ExperimentVariant {
+    type Error = crate::Error;
+
+    fn try_from(row: ExperimentVariantRow) -> Result<Self> {
+        Ok(ExperimentVariant {
+            id: row.id,
+            experiment_id: row.experiment_id,
+            name: row.name,
+            description: row.description,
+            is_control: row.is_control,
+            weight: row.weight,
+            config: serde_json::from_str(&row.config)
+                .unwrap_or(serde_json::Value::Object(serde_json::Map::new())),
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct VariantResultsRow {
+    variant_id: i64,
+    variant_name: String,
+    is_control: bool,
+    sample_count: i64,
+    mean: f64,
+    variance: f64,
+    min_value: f64,
+    max_value: f64,
+    success_count: Option<i64>,
+}
+
+impl From<VariantResultsRow> for VariantResults {
+    fn from(row: VariantResultsRow) -> Self {
+        let success_rate = if row.sample_count > 0 {
+            row.success_count.map(|c| c as f64 / row.sample_count as f64)
+        } else {
+            None
+        };
+
+        VariantResults {
+            variant_id: row.variant_id,
+            variant_name: row.variant_name,
+            is_control: row.is_control,
+            sample_count: row.sample_count,
+            mean: row.mean,
+            // Rounding can leave the variance slightly below zero
+            std_dev: row.variance.max(0.0).sqrt(),
+            min_value: row.min_value,
+            max_value: row.max_value,
+            success_count: row.success_count,
+            success_rate,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct VariantDailyResultsRow {
+    variant_id: i64,
+    variant_name: String,
+    date: String,
+    sample_count: i64,
+    mean: f64,
+    success_count: i64,
+}
+
+impl From<VariantDailyResultsRow> for VariantDailyResults {
+    fn from(row: VariantDailyResultsRow) -> Self {
+        VariantDailyResults {
+            variant_id: row.variant_id,
+            variant_name: row.variant_name,
+            date: row.date,
+            sample_count: row.sample_count,
+            mean: row.mean,
+            success_count: row.success_count,
+        }
+    }
+}
+
+// ==================== Model Selection Row Structs ====================
+
+#[derive(sqlx::FromRow)]
+struct ModelPerformanceRow {
+    #[allow(dead_code)]
+    id: i64,
+    model: String,
+    task_type: String,
+    agent_type: Option<String>,
+    success_count: i64,
+    failure_count: i64,
+    total_tokens: i64,
+    total_cost: f64,
+    total_duration_secs: f64,
+    sample_count: i64,
+    last_used_at: Option<String>,
+    #[allow(dead_code)]
+    updated_at: String,
+}
+
+impl From<ModelPerformanceRow> for ModelPerformance {
+    fn from(row: ModelPerformanceRow) -> Self {
+        let success_rate = if row.sample_count > 0 {
+            row.success_count as f64 / row.sample_count as f64
+        } else {
+            0.0
+        };
+
+        let avg_tokens = if row.sample_count > 0 {
+            row.total_tokens as f64 / row.sample_count as f64
+        } else {
+            0.0
+        };
+
+        let avg_cost = if row.sample_count > 0 {
+            row.total_cost / row.sample_count as f64
+        } else {
+            0.0
+        };
+
+        let avg_duration_secs = if row.sample_count > 0 {
+            row.total_duration_secs / row.sample_count as f64
+        } else {
+            0.0
+        };
+
+        ModelPerformance {
+            model: row.model,
+            task_type: row.task_type,
+            agent_type: row.agent_type,
+            success_count: row.success_count,
+            failure_count: row.failure_count,
+            success_rate,
+            avg_tokens,
+            avg_cost,
+            avg_duration_secs,
+            sample_count: row.sample_count,
+            last_used_at: row
+                .last_used_at
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+                .map(Into::into),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ModelSelectionRuleRow {
+    id: i64,
+    name: String,
+    task_type: Option<String>,
+    agent_type: Option<String>,
+    complexity: Option<String>,
+    preferred_model: String,
+    fallback_model: Option<String>,
+    max_cost: Option<f64>,
+    min_success_rate: Option<f64>,
+    priority: i32,
+    enabled: bool,
+    created_at: String,
+}
+
+impl TryFrom<ModelSelectionRuleRow> for ModelSelectionRule {
+    type Error = crate::Error;
+
+    fn try_from(row: ModelSelectionRuleRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        let complexity = row
+            .complexity
+            .map(|s| TaskComplexity::from_str(&s))
+            .transpose()?;
+
+        Ok(ModelSelectionRule {
             id: row.id,
-            epic_id: row.epic_id,
-            title: row.title,
-            description: row.description,
-            acceptance_criteria: row
-                .acceptance_criteria
-                .map(|s| serde_json::from_str(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?,
-            status: StoryStatus::from_str(&row.status)?,
-            agent_id: row
-                .agent_id
-                .map(|s| Uuid::parse_str(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            name: row.name,
+            task_type: row.task_type,
+            agent_type: row.agent_type,
+            complexity,
+            preferred_model: row.preferred_model,
+            fallback_model: row.fallback_model,
+            max_cost: row.max_cost,
+            min_success_rate: row.min_success_rate,
+            priority: row.priority,
+            enabled: row.enabled,
             created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
                 .map_err(|e| crate::Error::Other(e.to_string()))?
                 .into(),
-            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            completed_at: row
-                .completed_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
         })
     }
-}
+}
+
+#[derive(sqlx::FromRow)]
+struct ModelSelectionConfigRow {
+    #[allow(dead_code)]
+    id: i64,
+    optimization_goal: String,
+    max_cost_per_task: Option<f64>,
+    min_success_rate: f64,
+    min_samples_for_auto: i64,
+    enabled: bool,
+    #[allow(dead_code)]
+    updated_at: String,
+}
+
+impl From<ModelSelectionConfigRow> for ModelSelectionConfig {
+    fn from(row: ModelSelectionConfigRow) -> Self {
+        use std::str::FromStr;
+
+        ModelSelectionConfig {
+            optimization_goal: OptimizationGoal::from_str(&row.optimization_goal)
+                .unwrap_or(OptimizationGoal::Balanced),
+            max_cost_per_task: row.max_cost_per_task,
+            min_success_rate: row.min_success_rate,
+            min_samples_for_auto: row.min_samples_for_auto,
+            enabled: row.enabled,
+        }
+    }
+}
+
+impl Database {
+    // ==================== Incident Operations ====================
+
+    /// Create a new incident
+    pub async fn create_incident(&self, incident: &crate::incident::Incident) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO incidents (
+                id, title, description, severity, status, detected_at,
+                acknowledged_at, resolved_at, affected_services,
+                related_incidents, tags, metadata
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&incident.id)
+        .bind(&incident.title)
+        .bind(&incident.description)
+        .bind(incident.severity.as_str())
+        .bind(incident.status.as_str())
+        .bind(incident.detected_at.to_rfc3339())
+        .bind(incident.acknowledged_at.as_ref().map(|t| t.to_rfc3339()))
+        .bind(incident.resolved_at.as_ref().map(|t| t.to_rfc3339()))
+        .bind(serde_json::to_string(&incident.affected_services)?)
+        .bind(serde_json::to_string(&incident.related_incidents)?)
+        .bind(serde_json::to_string(&incident.tags)?)
+        .bind(serde_json::to_string(&incident.metadata)?)
+        .execute(&self.pool)
+        .await?;
+
+        // Insert timeline events
+        for event in &incident.timeline {
+            self.add_timeline_event(&incident.id, event).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update an incident
+    pub async fn update_incident(&self, incident: &crate::incident::Incident) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE incidents SET
+                title = ?, description = ?, severity = ?, status = ?,
+                acknowledged_at = ?, resolved_at = ?,
+                affected_services = ?, related_incidents = ?,
+                tags = ?, metadata = ?, updated_at = datetime('now')
+            WHERE id = ?
+            "#,
+        )
+        .bind(&incident.title)
+        .bind(&incident.description)
+        .bind(incident.severity.as_str())
+        .bind(incident.status.as_str())
+        .bind(incident.acknowledged_at.as_ref().map(|t| t.to_rfc3339()))
+        .bind(incident.resolved_at.as_ref().map(|t| t.to_rfc3339()))
+        .bind(serde_json::to_string(&incident.affected_services)?)
+        .bind(serde_json::to_string(&incident.related_incidents)?)
+        .bind(serde_json::to_string(&incident.tags)?)
+        .bind(serde_json::to_string(&incident.metadata)?)
+        .bind(&incident.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get an incident by ID
+    pub async fn get_incident(&self, id: &str) -> Result<Option<crate::incident::Incident>> {
+        let row = sqlx::query_as::<_, IncidentRow>(
+            r#"
+            SELECT id, title, description, severity, status, detected_at,
+                   acknowledged_at, resolved_at, affected_services,
+                   related_incidents, tags, metadata
+            FROM incidents
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if let Some(row) = row {
+            let timeline = self.get_timeline_events(id).await?;
+            Ok(Some(row.into_incident(timeline)?))
+        } else {
+            Ok(None)
+        }
+    }
 
-#[derive(sqlx::FromRow)]
-struct StepOutputRow {
-    id: i64,
-    agent_id: String,
-    skill_name: String,
-    output_type: String,
-    data: String,
-    consumed: bool,
-    consumed_by: Option<String>,
-    consumed_at: Option<String>,
-    created_at: String,
-}
+    /// List incidents with optional filters
+    pub async fn list_incidents(
+        &self,
+        status: Option<&str>,
+        severity: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<Vec<crate::incident::Incident>> {
+        let mut query = String::from(
+            r#"
+            SELECT id, title, description, severity, status, detected_at,
+                   acknowledged_at, resolved_at, affected_services,
+                   related_incidents, tags, metadata
+            FROM incidents
+            WHERE 1=1
+            "#,
+        );
+
+        if status.is_some() {
+            query.push_str(" AND status = ?");
+        }
+        if severity.is_some() {
+            query.push_str(" AND severity = ?");
+        }
+
+        query.push_str(" ORDER BY detected_at DESC");
+
+        if let Some(limit) = limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut q = sqlx::query_as::<_, IncidentRow>(&query);
+        if let Some(s) = status {
+            q = q.bind(s);
+        }
+        if let Some(sev) = severity {
+            q = q.bind(sev);
+        }
+
+        let rows = q.fetch_all(&self.pool).await?;
+
+        let mut incidents = Vec::new();
+        for row in rows {
+            let timeline = self.get_timeline_events(&row.id).await?;
+            incidents.push(row.into_incident(timeline)?);
+        }
+
+        Ok(incidents)
+    }
+
+    /// Add timeline event to incident
+    pub async fn add_timeline_event(
+        &self,
+        incident_id: &str,
+        event: &crate::incident::TimelineEvent,
+    ) -> Result<()> {
+        let event_type_str = match event.event_type {
+            crate::incident::TimelineEventType::Detected => "detected",
+            crate::incident::TimelineEventType::Acknowledged => "acknowledged",
+            crate::incident::TimelineEventType::InvestigationStarted => "investigation_started",
+            crate::incident::TimelineEventType::RootCauseIdentified => "root_cause_identified",
+            crate::incident::TimelineEventType::MitigationStarted => "mitigation_started",
+            crate::incident::TimelineEventType::PlaybookExecuted => "playbook_executed",
+            crate::incident::TimelineEventType::Escalated => "escalated",
+            crate::incident::TimelineEventType::Resolved => "resolved",
+            crate::incident::TimelineEventType::PostMortemCreated => "post_mortem_created",
+            crate::incident::TimelineEventType::Comment => "comment",
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO incident_timeline (
+                incident_id, timestamp, event_type, description, actor, metadata
+            )
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(incident_id)
+        .bind(event.timestamp.to_rfc3339())
+        .bind(event_type_str)
+        .bind(&event.description)
+        .bind(&event.actor)
+        .bind(serde_json::to_string(&event.metadata)?)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get timeline events for an incident
+    pub async fn get_timeline_events(
+        &self,
+        incident_id: &str,
+    ) -> Result<Vec<crate::incident::TimelineEvent>> {
+        let rows = sqlx::query_as::<_, TimelineEventRow>(
+            r#"
+            SELECT timestamp, event_type, description, actor, metadata
+            FROM incident_timeline
+            WHERE incident_id = ?
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(incident_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    // ==================== Root Cause Analysis ====================
+
+    /// Save root cause analysis
+    pub async fn save_root_cause_analysis(
+        &self,
+        rca: &crate::incident::RootCauseAnalysis,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO root_cause_analyses (
+                incident_id, primary_cause, evidence, contributing_factors,
+                hypotheses, related_events, analyzed_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&rca.incident_id)
+        .bind(&rca.primary_cause)
+        .bind(serde_json::to_string(&rca.evidence)?)
+        .bind(serde_json::to_string(&rca.contributing_factors)?)
+        .bind(serde_json::to_string(&rca.hypotheses)?)
+        .bind(serde_json::to_string(&rca.related_events)?)
+        .bind(rca.analyzed_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get root cause analysis for an incident
+    pub async fn get_root_cause_analysis(
+        &self,
+        incident_id: &str,
+    ) -> Result<Option<crate::incident::RootCauseAnalysis>> {
+        let row = sqlx::query_as::<_, RootCauseAnalysisRow>(
+            r#"
+            SELECT incident_id, primary_cause, evidence, contributing_factors,
+                   hypotheses, related_events, analyzed_at
+            FROM root_cause_analyses
+            WHERE incident_id = ?
+            "#,
+        )
+        .bind(incident_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    // ==================== Playbook Operations ====================
+
+    /// Create a playbook
+    pub async fn create_playbook(&self, playbook: &crate::incident::Playbook) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO playbooks (id, name, description, triggers, actions)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&playbook.id)
+        .bind(&playbook.name)
+        .bind(&playbook.description)
+        .bind(serde_json::to_string(&playbook.triggers)?)
+        .bind(serde_json::to_string(&playbook.actions)?)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Update a playbook
+    pub async fn update_playbook(&self, playbook: &crate::incident::Playbook) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE playbooks SET
+                name = ?, description = ?, triggers = ?, actions = ?,
+                updated_at = datetime('now')
+            WHERE id = ?
+            "#,
+        )
+        .bind(&playbook.name)
+        .bind(&playbook.description)
+        .bind(serde_json::to_string(&playbook.triggers)?)
+        .bind(serde_json::to_string(&playbook.actions)?)
+        .bind(&playbook.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get a playbook by ID
+    pub async fn get_playbook(&self, id: &str) -> Result<Option<crate::incident::Playbook>> {
+        let row = sqlx::query_as::<_, PlaybookRow>(
+            r#"
+            SELECT id, name, description, triggers, actions, created_at, updated_at
+            FROM playbooks
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Get a playbook by name
+    pub async fn get_playbook_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<crate::incident::Playbook>> {
+        let row = sqlx::query_as::<_, PlaybookRow>(
+            r#"
+            SELECT id, name, description, triggers, actions, created_at, updated_at
+            FROM playbooks
+            WHERE name = ?
+            "#,
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// List all playbooks
+    pub async fn list_playbooks(&self) -> Result<Vec<crate::incident::Playbook>> {
+        let rows = sqlx::query_as::<_, PlaybookRow>(
+            r#"
+            SELECT id, name, description, triggers, actions, created_at, updated_at
+            FROM playbooks
+            ORDER BY name ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-impl TryFrom<StepOutputRow> for StepOutput {
-    type Error = crate::Error;
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
 
-    fn try_from(row: StepOutputRow) -> Result<Self> {
-        use std::str::FromStr;
+    /// Delete a playbook
+    pub async fn delete_playbook(&self, id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM playbooks WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
 
-        let agent_uuid =
-            Uuid::parse_str(&row.agent_id).map_err(|e| crate::Error::Other(e.to_string()))?;
+        Ok(())
+    }
 
-        let consumed_by = row
-            .consumed_by
-            .map(|s| Uuid::parse_str(&s))
-            .transpose()
-            .map_err(|e| crate::Error::Other(e.to_string()))?
-            .map(AgentId::from_uuid);
+    // ==================== Playbook Execution ====================
 
-        let consumed_at = row
-            .consumed_at
-            .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-            .transpose()
-            .map_err(|e| crate::Error::Other(e.to_string()))?
-            .map(Into::into);
+    /// Create playbook execution
+    pub async fn create_playbook_execution(
+        &self,
+        execution: &crate::incident::PlaybookExecution,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO playbook_executions (
+                id, playbook_id, incident_id, status, started_at,
+                completed_at, action_results, triggered_by
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&execution.id)
+        .bind(&execution.playbook_id)
+        .bind(&execution.incident_id)
+        .bind(execution.status.as_str())
+        .bind(execution.started_at.to_rfc3339())
+        .bind(execution.completed_at.as_ref().map(|t| t.to_rfc3339()))
+        .bind(serde_json::to_string(&execution.action_results)?)
+        .bind(&execution.triggered_by)
+        .execute(&self.pool)
+        .await?;
 
-        let output_type = StepOutputType::from_str(&row.output_type)?;
+        Ok(())
+    }
 
-        Ok(StepOutput {
-            id: Some(row.id),
-            agent_id: AgentId::from_uuid(agent_uuid),
-            skill_name: row.skill_name,
-            output_type,
-            data: serde_json::from_str(&row.data)?,
-            consumed: row.consumed,
-            consumed_by,
-            consumed_at,
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-        })
+    /// Update playbook execution
+    pub async fn update_playbook_execution(
+        &self,
+        execution: &crate::incident::PlaybookExecution,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE playbook_executions SET
+                status = ?, completed_at = ?, action_results = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(execution.status.as_str())
+        .bind(execution.completed_at.as_ref().map(|t| t.to_rfc3339()))
+        .bind(serde_json::to_string(&execution.action_results)?)
+        .bind(&execution.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct InstructionRow {
-    id: i64,
-    name: String,
-    content: String,
-    scope: String,
-    agent_type: Option<String>,
-    priority: i32,
-    enabled: bool,
-    source: String,
-    confidence: f64,
-    tags: Option<String>,
-    created_at: String,
-    updated_at: String,
-    created_by: Option<String>,
-}
+    /// Get playbook execution by ID
+    pub async fn get_playbook_execution(
+        &self,
+        id: &str,
+    ) -> Result<Option<crate::incident::PlaybookExecution>> {
+        let row = sqlx::query_as::<_, PlaybookExecutionRow>(
+            r#"
+            SELECT id, playbook_id, incident_id, status, started_at,
+                   completed_at, action_results, triggered_by
+            FROM playbook_executions
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
 
-impl TryFrom<InstructionRow> for CustomInstruction {
-    type Error = crate::Error;
+        row.map(|r| r.try_into()).transpose()
+    }
 
-    fn try_from(row: InstructionRow) -> Result<Self> {
-        let agent_type = row
-            .agent_type
-            .map(|s| AgentType::from_str(&s))
-            .transpose()?;
+    /// List executions for a playbook
+    pub async fn list_playbook_executions(
+        &self,
+        playbook_id: &str,
+        limit: Option<i64>,
+    ) -> Result<Vec<crate::incident::PlaybookExecution>> {
+        let mut query = String::from(
+            r#"
+            SELECT id, playbook_id, incident_id, status, started_at,
+                   completed_at, action_results, triggered_by
+            FROM playbook_executions
+            WHERE playbook_id = ?
+            ORDER BY started_at DESC
+            "#,
+        );
 
-        let tags: Vec<String> = row
-            .tags
-            .map(|s| serde_json::from_str(&s))
-            .transpose()?
-            .unwrap_or_default();
+        if let Some(limit) = limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
 
-        Ok(CustomInstruction {
-            id: row.id,
-            name: row.name,
-            content: row.content,
-            scope: InstructionScope::from_str(&row.scope)?,
-            agent_type,
-            priority: row.priority,
-            enabled: row.enabled,
-            source: InstructionSource::from_str(&row.source)?,
-            confidence: row.confidence,
-            tags,
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            created_by: row.created_by,
-        })
+        let rows = sqlx::query_as::<_, PlaybookExecutionRow>(&query)
+            .bind(playbook_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct EffectivenessRow {
-    instruction_id: i64,
-    usage_count: i64,
-    success_count: i64,
-    failure_count: i64,
-    penalty_score: f64,
-    avg_completion_time: Option<f64>,
-    last_success_at: Option<String>,
-    last_failure_at: Option<String>,
-    last_penalty_at: Option<String>,
-    updated_at: String,
-}
+    /// List executions of playbooks for an incident, oldest first
+    pub async fn list_incident_playbook_executions(
+        &self,
+        incident_id: &str,
+    ) -> Result<Vec<crate::incident::PlaybookExecution>> {
+        let rows = sqlx::query_as::<_, PlaybookExecutionRow>(
+            r#"
+            SELECT id, playbook_id, incident_id, status, started_at,
+                   completed_at, action_results, triggered_by
+            FROM playbook_executions
+            WHERE incident_id = ?
+            ORDER BY started_at ASC
+            "#,
+        )
+        .bind(incident_id)
+        .fetch_all(&self.pool)
+        .await?;
 
-impl TryFrom<EffectivenessRow> for InstructionEffectiveness {
-    type Error = crate::Error;
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
 
-    fn try_from(row: EffectivenessRow) -> Result<Self> {
-        let last_success_at = row
-            .last_success_at
-            .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-            .transpose()
-            .map_err(|e| crate::Error::Other(e.to_string()))?
-            .map(Into::into);
+    // ==================== Post-Mortem Operations ====================
 
-        let last_failure_at = row
-            .last_failure_at
-            .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-            .transpose()
-            .map_err(|e| crate::Error::Other(e.to_string()))?
-            .map(Into::into);
+    /// Save post-mortem
+    pub async fn save_post_mortem(&self, pm: &crate::incident::PostMortem) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO post_mortems (
+                incident_id, title, summary, impact, root_cause,
+                contributing_factors, resolution, action_items,
+                lessons_learned, authors
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&pm.incident_id)
+        .bind(&pm.title)
+        .bind(&pm.summary)
+        .bind(serde_json::to_string(&pm.impact)?)
+        .bind(&pm.root_cause)
+        .bind(serde_json::to_string(&pm.contributing_factors)?)
+        .bind(&pm.resolution)
+        .bind(serde_json::to_string(&pm.action_items)?)
+        .bind(serde_json::to_string(&pm.lessons_learned)?)
+        .bind(serde_json::to_string(&pm.authors)?)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
 
-        let last_penalty_at = row
-            .last_penalty_at
-            .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-            .transpose()
-            .map_err(|e| crate::Error::Other(e.to_string()))?
-            .map(Into::into);
+    /// Get post-mortem for an incident
+    pub async fn get_post_mortem(
+        &self,
+        incident_id: &str,
+    ) -> Result<Option<crate::incident::PostMortem>> {
+        let row = sqlx::query_as::<_, PostMortemRow>(
+            r#"
+            SELECT incident_id, title, summary, impact, root_cause,
+                   contributing_factors, resolution, action_items,
+                   lessons_learned, authors, created_at
+            FROM post_mortems
+            WHERE incident_id = ?
+            "#,
+        )
+        .bind(incident_id)
+        .fetch_optional(&self.pool)
+        .await?;
 
-        let success_rate = if row.usage_count > 0 {
-            row.success_count as f64 / row.usage_count as f64
+        if let Some(row) = row {
+            // Get timeline from incident
+            let timeline = self.get_timeline_events(incident_id).await?;
+            Ok(Some(row.into_post_mortem(timeline)?))
         } else {
-            0.0
-        };
-
-        Ok(InstructionEffectiveness {
-            instruction_id: row.instruction_id,
-            usage_count: row.usage_count,
-            success_count: row.success_count,
-            failure_count: row.failure_count,
-            penalty_score: row.penalty_score,
-            avg_completion_time: row.avg_completion_time,
-            success_rate,
-            last_success_at,
-            last_failure_at,
-            last_penalty_at,
-            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-        })
+            Ok(None)
+        }
     }
-}
-
-#[derive(sqlx::FromRow)]
-struct PatternRow {
-    id: i64,
-    pattern_type: String,
-    agent_type: Option<String>,
-    pattern_signature: String,
-    pattern_data: String,
-    occurrence_count: i64,
-    first_seen_at: String,
-    last_seen_at: String,
-    instruction_id: Option<i64>,
-    status: String,
-}
 
-impl TryFrom<PatternRow> for LearningPattern {
-    type Error = crate::Error;
+    // ==================== Anomaly Metrics ====================
 
-    fn try_from(row: PatternRow) -> Result<Self> {
-        let agent_type = row
-            .agent_type
-            .map(|s| AgentType::from_str(&s))
-            .transpose()?;
+    /// Record anomaly metric
+    pub async fn record_anomaly_metric(
+        &self,
+        metric: &crate::incident::AnomalyMetric,
+        incident_id: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO anomaly_metrics (
+                name, current_value, baseline_value, threshold,
+                deviation_percent, is_anomaly, incident_id, timestamp, metadata
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&metric.name)
+        .bind(metric.current_value)
+        .bind(metric.baseline_value)
+        .bind(metric.threshold)
+        .bind(metric.deviation_percent)
+        .bind(metric.is_anomaly)
+        .bind(incident_id)
+        .bind(metric.timestamp.to_rfc3339())
+        .bind("{}")
+        .execute(&self.pool)
+        .await?;
 
-        Ok(LearningPattern {
-            id: row.id,
-            pattern_type: PatternType::from_str(&row.pattern_type)?,
-            agent_type,
-            pattern_signature: row.pattern_signature,
-            pattern_data: serde_json::from_str(&row.pattern_data)?,
-            occurrence_count: row.occurrence_count,
-            first_seen_at: chrono::DateTime::parse_from_rfc3339(&row.first_seen_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            last_seen_at: chrono::DateTime::parse_from_rfc3339(&row.last_seen_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            instruction_id: row.instruction_id,
-            status: PatternStatus::from_str(&row.status)?,
-        })
+        Ok(())
     }
-}
-
-#[derive(sqlx::FromRow)]
-struct ScheduleRow {
-    id: i64,
-    name: String,
-    cron_expression: String,
-    agent_type: String,
-    task: String,
-    enabled: bool,
-    last_run: Option<String>,
-    next_run: Option<String>,
-    created_at: String,
-}
 
-impl TryFrom<ScheduleRow> for Schedule {
-    type Error = crate::Error;
+    /// Get recent anomalies
+    pub async fn get_recent_anomalies(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<crate::incident::AnomalyMetric>> {
+        let rows = sqlx::query_as::<_, AnomalyMetricRow>(
+            r#"
+            SELECT name, current_value, baseline_value, threshold,
+                   deviation_percent, is_anomaly, timestamp
+            FROM anomaly_metrics
+            WHERE is_anomaly = 1
+            ORDER BY timestamp DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
 
-    fn try_from(row: ScheduleRow) -> Result<Self> {
-        Ok(Schedule {
-            id: row.id,
-            name: row.name,
-            cron_expression: row.cron_expression,
-            agent_type: row.agent_type,
-            task: row.task,
-            enabled: row.enabled,
-            last_run: row
-                .last_run
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            next_run: row
-                .next_run
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-        })
+        rows.into_iter().map(|r| r.try_into()).collect()
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct WebhookEventRow {
-    id: i64,
-    delivery_id: String,
-    event_type: String,
-    payload: String,
-    status: String,
-    retry_count: i32,
-    max_retries: i32,
-    error_message: Option<String>,
-    next_retry_at: Option<String>,
-    received_at: String,
-    processed_at: Option<String>,
-    created_at: String,
-    updated_at: String,
-}
+    // ==================== On-call Page Operations ====================
 
-impl TryFrom<WebhookEventRow> for WebhookEvent {
-    type Error = crate::Error;
+    /// Record a page, or update the status of an existing page of the same
+    /// resource to the same provider service
+    pub async fn upsert_oncall_page(&self, page: &crate::oncall::OncallPage) -> Result<i64> {
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO oncall_pages (
+                provider, routing_key, dedup_key, resource_type, resource_id,
+                status, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (provider, routing_key, dedup_key) DO UPDATE SET
+                status = excluded.status,
+                updated_at = excluded.updated_at
+            RETURNING id
+            "#,
+        )
+        .bind(page.provider.as_str())
+        .bind(&page.routing_key)
+        .bind(page.dedup_key())
+        .bind(page.resource.resource_type())
+        .bind(page.resource.id())
+        .bind(page.status.as_str())
+        .bind(page.created_at.to_rfc3339())
+        .bind(page.updated_at.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await?;
 
-    fn try_from(row: WebhookEventRow) -> Result<Self> {
-        use std::str::FromStr;
+        Ok(id)
+    }
 
-        Ok(WebhookEvent {
-            id: Some(row.id),
-            delivery_id: row.delivery_id,
-            event_type: row.event_type,
-            payload: row.payload,
-            status: WebhookEventStatus::from_str(&row.status)?,
-            retry_count: row.retry_count,
-            max_retries: row.max_retries,
-            error_message: row.error_message,
-            next_retry_at: row
-                .next_retry_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            received_at: chrono::DateTime::parse_from_rfc3339(&row.received_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            processed_at: row
-                .processed_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-        })
+    /// List the pages sent for an incident or alert
+    pub async fn list_oncall_pages(
+        &self,
+        resource: &crate::oncall::PagedResource,
+    ) -> Result<Vec<crate::oncall::OncallPage>> {
+        let rows = sqlx::query_as::<_, OncallPageRow>(
+            r#"
+            SELECT id, provider, routing_key, resource_type, resource_id,
+                   status, created_at, updated_at
+            FROM oncall_pages
+            WHERE dedup_key = ?
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(resource.dedup_key())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct ScheduleRunRow {
-    id: i64,
-    schedule_id: i64,
-    agent_id: Option<String>,
-    started_at: String,
-    completed_at: Option<String>,
-    status: String,
-    error_message: Option<String>,
-}
+    /// Set the status of every page of a resource sent to a provider,
+    /// returning the number of pages updated
+    pub async fn update_oncall_page_status(
+        &self,
+        provider: crate::oncall::OncallProvider,
+        resource: &crate::oncall::PagedResource,
+        status: crate::oncall::OncallPageStatus,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE oncall_pages SET status = ?, updated_at = ?
+            WHERE provider = ? AND dedup_key = ?
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(provider.as_str())
+        .bind(resource.dedup_key())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
 
-impl TryFrom<ScheduleRunRow> for ScheduleRun {
-    type Error = crate::Error;
+    // ==================== Jira Sync Operations ====================
 
-    fn try_from(row: ScheduleRunRow) -> Result<Self> {
-        use std::str::FromStr;
+    /// Insert a Jira project configuration
+    pub async fn insert_jira_project(&self, project: &crate::jira::JiraProject) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO jira_projects (
+                name, base_url, project_key, epic_prefix, epic_issue_type, story_issue_type,
+                status_mapping, field_mapping, enabled, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&project.name)
+        .bind(&project.base_url)
+        .bind(&project.project_key)
+        .bind(&project.epic_prefix)
+        .bind(&project.epic_issue_type)
+        .bind(&project.story_issue_type)
+        .bind(serde_json::to_string(&project.status_mapping)?)
+        .bind(serde_json::to_string(&project.field_mapping)?)
+        .bind(project.enabled)
+        .bind(project.created_at.to_rfc3339())
+        .bind(project.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
 
-        Ok(ScheduleRun {
-            id: row.id,
-            schedule_id: row.schedule_id,
-            agent_id: row.agent_id,
-            started_at: chrono::DateTime::parse_from_rfc3339(&row.started_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            completed_at: row
-                .completed_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            status: ScheduleRunStatus::from_str(&row.status)?,
-            error_message: row.error_message,
-        })
+        Ok(result.last_insert_rowid())
     }
-}
 
-// ==================== Pipeline Row Structs ====================
+    /// Get a Jira project configuration by name
+    pub async fn get_jira_project(&self, name: &str) -> Result<Option<crate::jira::JiraProject>> {
+        let row = sqlx::query_as::<_, JiraProjectRow>("SELECT * FROM jira_projects WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
 
-#[derive(sqlx::FromRow)]
-struct PipelineRow {
-    id: i64,
-    name: String,
-    definition: String,
-    enabled: i32,
-    created_at: String,
-}
+        row.map(|r| r.try_into()).transpose()
+    }
 
-impl TryFrom<PipelineRow> for crate::Pipeline {
-    type Error = crate::Error;
+    /// Get a Jira project configuration by ID
+    pub async fn get_jira_project_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<crate::jira::JiraProject>> {
+        let row = sqlx::query_as::<_, JiraProjectRow>("SELECT * FROM jira_projects WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
 
-    fn try_from(row: PipelineRow) -> Result<Self> {
-        Ok(crate::Pipeline {
-            id: Some(row.id),
-            name: row.name,
-            definition: row.definition,
-            enabled: row.enabled != 0,
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-        })
+        row.map(|r| r.try_into()).transpose()
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct PipelineRunRow {
-    id: i64,
-    pipeline_id: i64,
-    status: String,
-    trigger_event: Option<String>,
-    started_at: Option<String>,
-    completed_at: Option<String>,
-    created_at: String,
-}
+    /// Update a Jira project configuration
+    pub async fn update_jira_project(&self, project: &crate::jira::JiraProject) -> Result<()> {
+        let id = project.id.ok_or_else(|| {
+            crate::Error::Other("Cannot update Jira project without ID".to_string())
+        })?;
 
-impl TryFrom<PipelineRunRow> for crate::PipelineRun {
-    type Error = crate::Error;
+        sqlx::query(
+            r#"
+            UPDATE jira_projects SET
+                base_url = ?,
+                project_key = ?,
+                epic_prefix = ?,
+                epic_issue_type = ?,
+                story_issue_type = ?,
+                status_mapping = ?,
+                field_mapping = ?,
+                enabled = ?,
+                updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&project.base_url)
+        .bind(&project.project_key)
+        .bind(&project.epic_prefix)
+        .bind(&project.epic_issue_type)
+        .bind(&project.story_issue_type)
+        .bind(serde_json::to_string(&project.status_mapping)?)
+        .bind(serde_json::to_string(&project.field_mapping)?)
+        .bind(project.enabled)
+        .bind(project.updated_at.to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
 
-    fn try_from(row: PipelineRunRow) -> Result<Self> {
-        use std::str::FromStr;
+        Ok(())
+    }
 
-        Ok(crate::PipelineRun {
-            id: Some(row.id),
-            pipeline_id: row.pipeline_id,
-            status: crate::PipelineRunStatus::from_str(&row.status)?,
-            trigger_event: row.trigger_event,
-            started_at: row
-                .started_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            completed_at: row
-                .completed_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-        })
+    /// List Jira project configurations
+    pub async fn list_jira_projects(&self) -> Result<Vec<crate::jira::JiraProject>> {
+        let rows =
+            sqlx::query_as::<_, JiraProjectRow>("SELECT * FROM jira_projects ORDER BY name ASC")
+                .fetch_all(&self.pool)
+                .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct PipelineStageRow {
-    id: i64,
-    run_id: i64,
-    stage_name: String,
-    status: String,
-    agent_id: Option<String>,
-    started_at: Option<String>,
-    completed_at: Option<String>,
-    created_at: String,
-}
+    /// Delete a Jira project configuration and its issue links, returning
+    /// whether it existed
+    pub async fn delete_jira_project(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM jira_projects WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
 
-impl TryFrom<PipelineStageRow> for crate::PipelineStage {
-    type Error = crate::Error;
+        Ok(result.rows_affected() > 0)
+    }
 
-    fn try_from(row: PipelineStageRow) -> Result<Self> {
-        use std::str::FromStr;
+    /// Record the Jira issue of an epic or story, or update its synced status
+    pub async fn upsert_jira_issue_link(&self, link: &crate::jira::JiraIssueLink) -> Result<i64> {
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO jira_issue_links (
+                project_id, resource_type, resource_id, issue_key, synced_status,
+                created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (resource_type, resource_id) DO UPDATE SET
+                project_id = excluded.project_id,
+                issue_key = excluded.issue_key,
+                synced_status = excluded.synced_status,
+                updated_at = excluded.updated_at
+            RETURNING id
+            "#,
+        )
+        .bind(link.project_id)
+        .bind(link.resource_type.as_str())
+        .bind(&link.resource_id)
+        .bind(&link.issue_key)
+        .bind(link.synced_status.map(|s| s.as_str()))
+        .bind(link.created_at.to_rfc3339())
+        .bind(link.updated_at.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await?;
 
-        Ok(crate::PipelineStage {
-            id: Some(row.id),
-            run_id: row.run_id,
-            stage_name: row.stage_name,
-            status: crate::PipelineStageStatus::from_str(&row.status)?,
-            agent_id: row.agent_id,
-            started_at: row
-                .started_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            completed_at: row
-                .completed_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-        })
+        Ok(id)
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct RollbackEventRow {
-    id: i64,
-    run_id: i64,
-    failed_stage_name: String,
-    rollback_to_stage: String,
-    trigger_type: String,
-    status: String,
-    error_message: Option<String>,
-    started_at: Option<String>,
-    completed_at: Option<String>,
-    created_at: Option<String>,
-}
+    /// Get the Jira issue of an epic or story
+    pub async fn get_jira_issue_link(
+        &self,
+        resource_type: crate::jira::JiraResourceType,
+        resource_id: &str,
+    ) -> Result<Option<crate::jira::JiraIssueLink>> {
+        let row = sqlx::query_as::<_, JiraIssueLinkRow>(
+            "SELECT * FROM jira_issue_links WHERE resource_type = ? AND resource_id = ?",
+        )
+        .bind(resource_type.as_str())
+        .bind(resource_id)
+        .fetch_optional(&self.pool)
+        .await?;
 
-impl TryFrom<RollbackEventRow> for crate::RollbackEvent {
-    type Error = crate::Error;
+        row.map(|r| r.try_into()).transpose()
+    }
 
-    fn try_from(row: RollbackEventRow) -> Result<Self> {
-        use std::str::FromStr;
+    /// Store a Jira comment of a story, returning whether it was new or changed
+    pub async fn upsert_jira_comment(
+        &self,
+        story_id: &str,
+        comment: &crate::jira::JiraComment,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO jira_comments (
+                story_id, issue_key, comment_id, author, body, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (issue_key, comment_id) DO UPDATE SET
+                story_id = excluded.story_id,
+                author = excluded.author,
+                body = excluded.body,
+                updated_at = excluded.updated_at
+            WHERE jira_comments.body != excluded.body
+            "#,
+        )
+        .bind(story_id)
+        .bind(&comment.issue_key)
+        .bind(&comment.comment_id)
+        .bind(&comment.author)
+        .bind(&comment.body)
+        .bind(comment.created_at.to_rfc3339())
+        .bind(comment.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
 
-        Ok(crate::RollbackEvent {
-            id: Some(row.id),
-            run_id: row.run_id,
-            failed_stage_name: row.failed_stage_name,
-            rollback_to_stage: row.rollback_to_stage,
-            trigger_type: crate::RollbackTriggerType::from_str(&row.trigger_type)?,
-            status: crate::RollbackStatus::from_str(&row.status)?,
-            error_message: row.error_message,
-            started_at: row
-                .started_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            completed_at: row
-                .completed_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            created_at: row
-                .created_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-        })
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// List the Jira comments of a story, oldest first
+    pub async fn list_jira_comments(
+        &self,
+        story_id: &str,
+    ) -> Result<Vec<crate::jira::JiraComment>> {
+        let rows = sqlx::query_as::<_, JiraCommentRow>(
+            r#"
+            SELECT issue_key, comment_id, author, body, created_at, updated_at
+            FROM jira_comments
+            WHERE story_id = ?
+            ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .bind(story_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
     }
-}
 
-impl Database {
-    // Approval Operations
+    // ==================== Linear Sync Operations ====================
 
-    /// Create a new approval request
-    pub async fn create_approval_request(
-        &self,
-        request: ApprovalRequest,
-    ) -> Result<ApprovalRequest> {
-        let mut request = request;
+    /// Insert a Linear team configuration
+    pub async fn insert_linear_team(&self, team: &crate::linear::LinearTeam) -> Result<i64> {
         let result = sqlx::query(
             r#"
-            INSERT INTO approval_requests
-            (stage_id, run_id, status, required_approvers, required_count,
-             approval_count, rejection_count, timeout_seconds, timeout_action,
-             timeout_at, resolved_at, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO linear_teams (
+                name, linear_team_id, epic_prefix, status_mapping, trigger_label, agent_type,
+                enabled, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(request.stage_id)
-        .bind(request.run_id)
-        .bind(request.status.as_str())
-        .bind(&request.required_approvers)
-        .bind(request.required_count)
-        .bind(request.approval_count)
-        .bind(request.rejection_count)
-        .bind(request.timeout_seconds)
-        .bind(&request.timeout_action)
-        .bind(request.timeout_at.map(|t| t.to_rfc3339()))
-        .bind(request.resolved_at.map(|t| t.to_rfc3339()))
-        .bind(request.created_at.to_rfc3339())
+        .bind(&team.name)
+        .bind(&team.linear_team_id)
+        .bind(&team.epic_prefix)
+        .bind(serde_json::to_string(&team.status_mapping)?)
+        .bind(&team.trigger_label)
+        .bind(team.agent_type.as_str())
+        .bind(team.enabled)
+        .bind(team.created_at.to_rfc3339())
+        .bind(team.updated_at.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        request.id = Some(result.last_insert_rowid());
-        Ok(request)
+        Ok(result.last_insert_rowid())
     }
 
-    /// Get an approval request by ID
-    pub async fn get_approval_request(&self, id: i64) -> Result<Option<ApprovalRequest>> {
-        let row = sqlx::query_as::<_, ApprovalRequestRow>(
+    /// Get a Linear team configuration by name
+    pub async fn get_linear_team(&self, name: &str) -> Result<Option<crate::linear::LinearTeam>> {
+        let row = sqlx::query_as::<_, LinearTeamRow>("SELECT * FROM linear_teams WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Get a Linear team configuration by ID
+    pub async fn get_linear_team_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<crate::linear::LinearTeam>> {
+        let row = sqlx::query_as::<_, LinearTeamRow>("SELECT * FROM linear_teams WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// List Linear team configurations
+    pub async fn list_linear_teams(&self) -> Result<Vec<crate::linear::LinearTeam>> {
+        let rows =
+            sqlx::query_as::<_, LinearTeamRow>("SELECT * FROM linear_teams ORDER BY name ASC")
+                .fetch_all(&self.pool)
+                .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Delete a Linear team configuration and its issue links, returning
+    /// whether it existed
+    pub async fn delete_linear_team(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM linear_teams WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record a Linear issue link, or update its synced status
+    pub async fn upsert_linear_issue_link(
+        &self,
+        link: &crate::linear::LinearIssueLink,
+    ) -> Result<i64> {
+        let id: i64 = sqlx::query_scalar(
             r#"
-            SELECT id, stage_id, run_id, status, required_approvers, required_count,
-                   approval_count, rejection_count, timeout_seconds, timeout_action,
-                   timeout_at, resolved_at, created_at
-            FROM approval_requests
-            WHERE id = ?
+            INSERT INTO linear_issue_links (
+                team_id, issue_id, identifier, url, story_id, agent_id, synced_status,
+                created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (issue_id) DO UPDATE SET
+                team_id = excluded.team_id,
+                identifier = excluded.identifier,
+                url = excluded.url,
+                story_id = excluded.story_id,
+                agent_id = excluded.agent_id,
+                synced_status = excluded.synced_status,
+                updated_at = excluded.updated_at
+            RETURNING id
             "#,
         )
-        .bind(id)
+        .bind(link.team_id)
+        .bind(&link.issue_id)
+        .bind(&link.identifier)
+        .bind(&link.url)
+        .bind(&link.story_id)
+        .bind(link.agent_id.map(|id| id.to_string()))
+        .bind(link.synced_status.map(|s| s.as_str()))
+        .bind(link.created_at.to_rfc3339())
+        .bind(link.updated_at.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Get the link of a Linear issue
+    pub async fn get_linear_issue_link(
+        &self,
+        issue_id: &str,
+    ) -> Result<Option<crate::linear::LinearIssueLink>> {
+        let row = sqlx::query_as::<_, LinearIssueLinkRow>(
+            "SELECT * FROM linear_issue_links WHERE issue_id = ?",
+        )
+        .bind(issue_id)
         .fetch_optional(&self.pool)
         .await?;
 
         row.map(|r| r.try_into()).transpose()
     }
 
-    /// Get approval request by stage ID
-    pub async fn get_approval_request_by_stage(
+    /// Get the link of the Linear issue synced to a story
+    pub async fn get_linear_story_link(
         &self,
-        stage_id: i64,
-    ) -> Result<Option<ApprovalRequest>> {
-        let row = sqlx::query_as::<_, ApprovalRequestRow>(
-            r#"
-            SELECT id, stage_id, run_id, status, required_approvers, required_count,
-                   approval_count, rejection_count, timeout_seconds, timeout_action,
-                   timeout_at, resolved_at, created_at
-            FROM approval_requests
-            WHERE stage_id = ?
-            ORDER BY created_at DESC
-            LIMIT 1
-            "#,
+        story_id: &str,
+    ) -> Result<Option<crate::linear::LinearIssueLink>> {
+        let row = sqlx::query_as::<_, LinearIssueLinkRow>(
+            "SELECT * FROM linear_issue_links WHERE story_id = ?",
         )
-        .bind(stage_id)
+        .bind(story_id)
         .fetch_optional(&self.pool)
         .await?;
 
         row.map(|r| r.try_into()).transpose()
     }
 
-    /// Update an approval request
-    pub async fn update_approval_request(&self, request: &ApprovalRequest) -> Result<()> {
-        let id = request
-            .id
-            .ok_or_else(|| crate::Error::Other("Approval request ID is required".to_string()))?;
+    // ==================== Issue Triage Operations ====================
 
-        sqlx::query(
+    /// Record the triage of an issue, replacing an earlier one
+    pub async fn upsert_issue_triage(
+        &self,
+        record: &crate::issue_triage::IssueTriageRecord,
+    ) -> Result<i64> {
+        let id: i64 = sqlx::query_scalar(
             r#"
-            UPDATE approval_requests
-            SET status = ?, approval_count = ?, rejection_count = ?,
-                required_approvers = ?, resolved_at = ?
-            WHERE id = ?
+            INSERT INTO issue_triages (
+                repository, issue_number, title, body, labels, duplicate_of, complexity,
+                story_id, agent_id, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (repository, issue_number) DO UPDATE SET
+                title = excluded.title,
+                body = excluded.body,
+                labels = excluded.labels,
+                duplicate_of = excluded.duplicate_of,
+                complexity = excluded.complexity,
+                story_id = excluded.story_id,
+                agent_id = excluded.agent_id,
+                updated_at = excluded.updated_at
+            RETURNING id
             "#,
         )
-        .bind(request.status.as_str())
-        .bind(request.approval_count)
-        .bind(request.rejection_count)
-        .bind(&request.required_approvers)
-        .bind(request.resolved_at.map(|t| t.to_rfc3339()))
-        .bind(id)
-        .execute(&self.pool)
+        .bind(&record.repository)
+        .bind(record.issue_number)
+        .bind(&record.title)
+        .bind(&record.body)
+        .bind(serde_json::to_string(&record.labels)?)
+        .bind(record.duplicate_of)
+        .bind(record.complexity.as_str())
+        .bind(&record.story_id)
+        .bind(record.agent_id.map(|id| id.to_string()))
+        .bind(record.created_at.to_rfc3339())
+        .bind(record.updated_at.to_rfc3339())
+        .fetch_one(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(id)
     }
 
-    /// List all pending approval requests
-    pub async fn list_pending_approvals(&self) -> Result<Vec<ApprovalRequest>> {
-        let rows = sqlx::query_as::<_, ApprovalRequestRow>(
+    /// Get the triage of an issue
+    pub async fn get_issue_triage(
+        &self,
+        repository: &str,
+        issue_number: i64,
+    ) -> Result<Option<crate::issue_triage::IssueTriageRecord>> {
+        let row = sqlx::query_as::<_, IssueTriageRow>(
+            "SELECT * FROM issue_triages WHERE repository = ? AND issue_number = ?",
+        )
+        .bind(repository)
+        .bind(issue_number)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// List issue triages, newest issue first, optionally of one repository
+    pub async fn list_issue_triages(
+        &self,
+        repository: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<crate::issue_triage::IssueTriageRecord>> {
+        let rows = sqlx::query_as::<_, IssueTriageRow>(
             r#"
-            SELECT id, stage_id, run_id, status, required_approvers, required_count,
-                   approval_count, rejection_count, timeout_seconds, timeout_action,
-                   timeout_at, resolved_at, created_at
-            FROM approval_requests
-            WHERE status = 'pending'
-            ORDER BY created_at ASC
+            SELECT * FROM issue_triages
+            WHERE ? IS NULL OR repository = ?
+            ORDER BY created_at DESC, issue_number DESC
+            LIMIT ?
             "#,
         )
+        .bind(repository)
+        .bind(repository)
+        .bind(limit)
         .fetch_all(&self.pool)
         .await?;
 
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
-    /// List approval requests that have timed out
-    pub async fn list_timed_out_approvals(&self) -> Result<Vec<ApprovalRequest>> {
-        let now = chrono::Utc::now().to_rfc3339();
-        let rows = sqlx::query_as::<_, ApprovalRequestRow>(
+    // ==================== Artifact Operations ====================
+
+    /// Record an artifact written to object storage
+    pub async fn insert_artifact(&self, artifact: &crate::object_storage::Artifact) -> Result<i64> {
+        let result = sqlx::query(
             r#"
-            SELECT id, stage_id, run_id, status, required_approvers, required_count,
-                   approval_count, rejection_count, timeout_seconds, timeout_action,
-                   timeout_at, resolved_at, created_at
-            FROM approval_requests
-            WHERE status = 'pending'
-              AND timeout_at IS NOT NULL
-              AND timeout_at < ?
-            ORDER BY timeout_at ASC
+            INSERT INTO artifacts (
+                kind, object_key, name, owner_id, content_type, size_bytes, backend, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(now)
+        .bind(artifact.kind.as_str())
+        .bind(&artifact.key)
+        .bind(&artifact.name)
+        .bind(&artifact.owner_id)
+        .bind(&artifact.content_type)
+        .bind(artifact.size_bytes)
+        .bind(&artifact.backend)
+        .bind(artifact.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get an artifact
+    pub async fn get_artifact(&self, id: i64) -> Result<Option<crate::object_storage::Artifact>> {
+        let row = sqlx::query_as::<_, ArtifactRow>("SELECT * FROM artifacts WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Get the artifact stored under an object key
+    pub async fn get_artifact_by_key(
+        &self,
+        key: &str,
+    ) -> Result<Option<crate::object_storage::Artifact>> {
+        let row = sqlx::query_as::<_, ArtifactRow>("SELECT * FROM artifacts WHERE object_key = ?")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// List artifacts, newest first, optionally of one kind or owner
+    pub async fn list_artifacts(
+        &self,
+        kind: Option<crate::object_storage::ArtifactKind>,
+        owner_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<crate::object_storage::Artifact>> {
+        let kind = kind.map(|k| k.as_str());
+        let rows = sqlx::query_as::<_, ArtifactRow>(
+            r#"
+            SELECT * FROM artifacts
+            WHERE (? IS NULL OR kind = ?) AND (? IS NULL OR owner_id = ?)
+            ORDER BY created_at DESC, id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(kind)
+        .bind(kind)
+        .bind(owner_id)
+        .bind(owner_id)
+        .bind(limit)
         .fetch_all(&self.pool)
         .await?;
 
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
-    /// Create an approval decision
-    pub async fn create_approval_decision(
+    /// One page of artifacts, optionally by kind and owner, newest first
+    pub async fn list_artifacts_page(
         &self,
-        decision: ApprovalDecision,
-    ) -> Result<ApprovalDecision> {
-        let mut decision = decision;
-        let result = sqlx::query(
+        kind: Option<crate::object_storage::ArtifactKind>,
+        owner_id: Option<&str>,
+        page: &PageQuery,
+    ) -> Result<RowPage<crate::object_storage::Artifact>> {
+        self.fetch_page::<ArtifactRow, _>(
+            "SELECT * FROM artifacts",
+            |q| {
+                if let Some(kind) = kind {
+                    q.push(" AND kind = ").push_bind(kind.as_str());
+                }
+                if let Some(owner_id) = owner_id {
+                    q.push(" AND owner_id = ").push_bind(owner_id.to_string());
+                }
+            },
+            SortKey::desc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
+    /// Delete an artifact's record
+    pub async fn delete_artifact(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM artifacts WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ==================== Data Export Operations ====================
+
+    /// Rows of an export, oldest first, up to `limit` rows
+    pub async fn export_rows(
+        &self,
+        request: &crate::ExportRequest,
+        limit: Option<i64>,
+    ) -> Result<crate::ExportTable> {
+        use crate::{ColumnType, ExportDataset, ExportValue};
+
+        // Each query selects the dataset's columns in order, filtered on its time column
+        let (select, time_column, order) = match request.dataset {
+            ExportDataset::TokenUsage => (
+                r#"
+                SELECT strftime('%Y-%m-%dT%H:%M:%SZ', created_at), agent_id, session_id,
+                    turn_number, input_tokens, output_tokens, cache_read_tokens,
+                    cache_write_tokens, epic_id, story_id
+                FROM session_token_stats
+                "#,
+                "created_at",
+                "datetime(created_at), id",
+            ),
+            ExportDataset::Costs => (
+                r#"
+                SELECT date, model, request_count, total_input_tokens, total_output_tokens,
+                    total_cache_read_tokens, total_cache_write_tokens, estimated_cost_usd
+                FROM daily_token_usage
+                "#,
+                "date",
+                "date, model",
+            ),
+            ExportDataset::AuditLog => (
+                r#"
+                SELECT strftime('%Y-%m-%dT%H:%M:%SZ', timestamp), actor, actor_type, action,
+                    resource_type, resource_id, success, error_message, details
+                FROM audit_log
+                "#,
+                "timestamp",
+                "datetime(timestamp), rowid",
+            ),
+            ExportDataset::AgentHistory => (
+                r#"
+                SELECT strftime('%Y-%m-%dT%H:%M:%SZ', e.created_at), e.agent_id, a.agent_type,
+                    e.session_id, e.event_type, e.summary, e.data
+                FROM agent_events e
+                LEFT JOIN agents a ON a.id = e.agent_id
+                "#,
+                "e.created_at",
+                "datetime(e.created_at), e.id",
+            ),
+        };
+        let query = format!(
+            "{} WHERE (? IS NULL OR datetime({t}) >= datetime(?)) \
+             AND (? IS NULL OR datetime({t}) < datetime(?)) ORDER BY {} LIMIT ?",
+            select,
+            order,
+            t = time_column
+        );
+
+        let from = request.from.map(|t| t.to_rfc3339());
+        let to = request.to.map(|t| t.to_rfc3339());
+        let rows = sqlx::query(&query)
+            .bind(&from)
+            .bind(&from)
+            .bind(&to)
+            .bind(&to)
+            .bind(limit.unwrap_or(-1))
+            .fetch_all(&self.pool)
+            .await?;
+
+        let columns = request.dataset.columns();
+        let rows = rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (_, column_type))| {
+                        Ok(match column_type {
+                            ColumnType::Integer => row
+                                .try_get::<Option<i64>, _>(index)?
+                                .map_or(ExportValue::Null, ExportValue::Integer),
+                            ColumnType::Real => row
+                                .try_get::<Option<f64>, _>(index)?
+                                .map_or(ExportValue::Null, ExportValue::Real),
+                            ColumnType::Text => row
+                                .try_get::<Option<String>, _>(index)?
+                                .map_or(ExportValue::Null, ExportValue::Text),
+                            ColumnType::Boolean => row
+                                .try_get::<Option<bool>, _>(index)?
+                                .map_or(ExportValue::Null, ExportValue::Boolean),
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(crate::ExportTable::new(request.dataset, rows))
+    }
+
+    /// Record a new export job
+    pub async fn insert_export_job(&self, job: &crate::ExportJob) -> Result<()> {
+        sqlx::query(
             r#"
-            INSERT INTO approval_decisions
-            (approval_id, approver, decision, comment, created_at)
-            VALUES (?, ?, ?, ?, ?)
+            INSERT INTO export_jobs (
+                id, dataset, format, from_time, to_time, status, row_count, artifact_id,
+                error_message, requested_by, created_at, completed_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&job.id)
+        .bind(job.request.dataset.as_str())
+        .bind(job.request.format.as_str())
+        .bind(job.request.from.map(|t| t.to_rfc3339()))
+        .bind(job.request.to.map(|t| t.to_rfc3339()))
+        .bind(job.status.as_str())
+        .bind(job.row_count)
+        .bind(job.artifact_id)
+        .bind(&job.error_message)
+        .bind(&job.requested_by)
+        .bind(job.created_at.to_rfc3339())
+        .bind(job.completed_at.map(|t| t.to_rfc3339()))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record the outcome of an export job
+    pub async fn update_export_job(&self, job: &crate::ExportJob) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE export_jobs SET
+                status = ?, row_count = ?, artifact_id = ?, error_message = ?, completed_at = ?
+            WHERE id = ?
             "#,
         )
-        .bind(decision.approval_id)
-        .bind(&decision.approver)
-        .bind(decision.decision as i32)
-        .bind(&decision.comment)
-        .bind(decision.created_at.to_rfc3339())
+        .bind(job.status.as_str())
+        .bind(job.row_count)
+        .bind(job.artifact_id)
+        .bind(&job.error_message)
+        .bind(job.completed_at.map(|t| t.to_rfc3339()))
+        .bind(&job.id)
         .execute(&self.pool)
         .await?;
 
-        decision.id = Some(result.last_insert_rowid());
-        Ok(decision)
+        Ok(())
     }
 
-    /// Get all decisions for an approval request
-    pub async fn get_approval_decisions(&self, approval_id: i64) -> Result<Vec<ApprovalDecision>> {
-        let rows = sqlx::query_as::<_, ApprovalDecisionRow>(
-            r#"
-            SELECT id, approval_id, approver, decision, comment, created_at
-            FROM approval_decisions
-            WHERE approval_id = ?
-            ORDER BY created_at ASC
-            "#,
+    /// Get an export job
+    pub async fn get_export_job(&self, id: &str) -> Result<Option<crate::ExportJob>> {
+        let row = sqlx::query_as::<_, ExportJobRow>("SELECT * FROM export_jobs WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// List export jobs, newest first
+    pub async fn list_export_jobs(&self, limit: i64) -> Result<Vec<crate::ExportJob>> {
+        let rows = sqlx::query_as::<_, ExportJobRow>(
+            "SELECT * FROM export_jobs ORDER BY created_at DESC, rowid DESC LIMIT ?",
         )
-        .bind(approval_id)
+        .bind(limit)
         .fetch_all(&self.pool)
         .await?;
 
         rows.into_iter().map(|r| r.try_into()).collect()
     }
+
+    /// One page of export jobs, newest first
+    pub async fn list_export_jobs_page(
+        &self,
+        page: &PageQuery,
+    ) -> Result<RowPage<crate::ExportJob>> {
+        self.fetch_page::<ExportJobRow, _>(
+            "SELECT * FROM export_jobs",
+            |_| {},
+            SortKey::desc(&["created_at", "id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
 }
 
-// Database row types for approval
+// ==================== Database Row Types ====================
 
 #[derive(sqlx::FromRow)]
-struct ApprovalRequestRow {
+struct OncallPageRow {
     id: i64,
-    stage_id: i64,
-    run_id: i64,
+    provider: String,
+    routing_key: String,
+    resource_type: String,
+    resource_id: String,
     status: String,
-    required_approvers: String,
-    required_count: i32,
-    approval_count: i32,
-    rejection_count: i32,
-    timeout_seconds: Option<i64>,
-    timeout_action: Option<String>,
-    timeout_at: Option<String>,
-    resolved_at: Option<String>,
     created_at: String,
+    updated_at: String,
 }
 
-impl TryFrom<ApprovalRequestRow> for ApprovalRequest {
+impl TryFrom<OncallPageRow> for crate::oncall::OncallPage {
     type Error = crate::Error;
 
-    fn try_from(row: ApprovalRequestRow) -> Result<Self> {
+    fn try_from(row: OncallPageRow) -> Result<Self> {
         use std::str::FromStr;
 
-        Ok(Self {
+        Ok(crate::oncall::OncallPage {
             id: Some(row.id),
-            stage_id: row.stage_id,
-            run_id: row.run_id,
-            status: ApprovalStatus::from_str(&row.status)?,
-            required_approvers: row.required_approvers,
-            required_count: row.required_count,
-            approval_count: row.approval_count,
-            rejection_count: row.rejection_count,
-            timeout_seconds: row.timeout_seconds,
-            timeout_action: row.timeout_action,
-            timeout_at: row
-                .timeout_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            resolved_at: row
-                .resolved_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
+            provider: crate::oncall::OncallProvider::from_str(&row.provider)?,
+            routing_key: row.routing_key,
+            resource: crate::oncall::PagedResource::from_parts(
+                &row.resource_type,
+                &row.resource_id,
+            )?,
+            status: crate::oncall::OncallPageStatus::from_str(&row.status)?,
             created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
                 .map_err(|e| crate::Error::Other(e.to_string()))?
                 .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
         })
     }
 }
 
 #[derive(sqlx::FromRow)]
-struct ApprovalDecisionRow {
+struct JiraProjectRow {
     id: i64,
-    approval_id: i64,
-    approver: String,
-    decision: i32,
-    comment: Option<String>,
+    name: String,
+    base_url: String,
+    project_key: String,
+    epic_prefix: String,
+    epic_issue_type: String,
+    story_issue_type: String,
+    status_mapping: String,
+    field_mapping: String,
+    enabled: bool,
     created_at: String,
+    updated_at: String,
 }
 
-impl TryFrom<ApprovalDecisionRow> for ApprovalDecision {
+impl TryFrom<JiraProjectRow> for crate::jira::JiraProject {
     type Error = crate::Error;
 
-    fn try_from(row: ApprovalDecisionRow) -> Result<Self> {
-        Ok(Self {
+    fn try_from(row: JiraProjectRow) -> Result<Self> {
+        Ok(crate::jira::JiraProject {
             id: Some(row.id),
-            approval_id: row.approval_id,
-            approver: row.approver,
-            decision: row.decision != 0,
-            comment: row.comment,
+            name: row.name,
+            base_url: row.base_url,
+            project_key: row.project_key,
+            epic_prefix: row.epic_prefix,
+            epic_issue_type: row.epic_issue_type,
+            story_issue_type: row.story_issue_type,
+            status_mapping: serde_json::from_str(&row.status_mapping)?,
+            field_mapping: serde_json::from_str(&row.field_mapping)?,
+            enabled: row.enabled,
             created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
                 .map_err(|e| crate::Error::Other(e.to_string()))?
                 .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
         })
     }
 }
 
-// ==================== Success Pattern Row Struct ====================
-
 #[derive(sqlx::FromRow)]
-struct SuccessPatternRow {
+struct JiraIssueLinkRow {
     id: i64,
-    pattern_type: String,
-    agent_type: Option<String>,
-    task_type: Option<String>,
-    pattern_signature: String,
-    pattern_data: String,
-    occurrence_count: i64,
-    avg_completion_time_ms: Option<i64>,
-    avg_token_usage: Option<i64>,
-    success_rate: f64,
-    first_seen_at: String,
-    last_seen_at: String,
+    project_id: i64,
+    resource_type: String,
+    resource_id: String,
+    issue_key: String,
+    synced_status: Option<String>,
+    created_at: String,
+    updated_at: String,
 }
 
-impl TryFrom<SuccessPatternRow> for SuccessPattern {
+impl TryFrom<JiraIssueLinkRow> for crate::jira::JiraIssueLink {
     type Error = crate::Error;
 
-    fn try_from(row: SuccessPatternRow) -> Result<Self> {
+    fn try_from(row: JiraIssueLinkRow) -> Result<Self> {
         use std::str::FromStr;
 
-        Ok(SuccessPattern {
-            id: row.id,
-            pattern_type: SuccessPatternType::from_str(&row.pattern_type)?,
-            agent_type: row
-                .agent_type
-                .map(|s| AgentType::from_str(&s))
+        Ok(crate::jira::JiraIssueLink {
+            id: Some(row.id),
+            project_id: row.project_id,
+            resource_type: crate::jira::JiraResourceType::from_str(&row.resource_type)?,
+            resource_id: row.resource_id,
+            issue_key: row.issue_key,
+            synced_status: row
+                .synced_status
+                .as_deref()
+                .map(StoryStatus::from_str)
                 .transpose()?,
-            task_type: row.task_type,
-            pattern_signature: row.pattern_signature,
-            pattern_data: serde_json::from_str(&row.pattern_data)
-                .map_err(|e| crate::Error::Other(e.to_string()))?,
-            occurrence_count: row.occurrence_count,
-            avg_completion_time_ms: row.avg_completion_time_ms,
-            avg_token_usage: row.avg_token_usage,
-            success_rate: row.success_rate,
-            first_seen_at: chrono::DateTime::parse_from_rfc3339(&row.first_seen_at)
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
                 .map_err(|e| crate::Error::Other(e.to_string()))?
                 .into(),
-            last_seen_at: chrono::DateTime::parse_from_rfc3339(&row.last_seen_at)
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
                 .map_err(|e| crate::Error::Other(e.to_string()))?
                 .into(),
         })
     }
 }
 
-// ==================== Feedback Row Structs ====================
-
 #[derive(sqlx::FromRow)]
-struct FeedbackRow {
-    id: i64,
-    agent_id: String,
-    message_id: Option<i64>,
-    rating: String,
-    comment: Option<String>,
-    source: String,
-    created_by: String,
+struct JiraCommentRow {
+    issue_key: String,
+    comment_id: String,
+    author: Option<String>,
+    body: String,
     created_at: String,
+    updated_at: String,
 }
 
-impl TryFrom<FeedbackRow> for Feedback {
+impl TryFrom<JiraCommentRow> for crate::jira::JiraComment {
     type Error = crate::Error;
 
-    fn try_from(row: FeedbackRow) -> Result<Self> {
-        use std::str::FromStr;
-
-        Ok(Feedback {
-            id: row.id,
-            agent_id: Uuid::parse_str(&row.agent_id)
-                .map_err(|e| crate::Error::Other(e.to_string()))?,
-            message_id: row.message_id,
-            rating: FeedbackRating::from_str(&row.rating)?,
-            comment: row.comment,
-            source: FeedbackSource::from_str(&row.source)?,
-            created_by: row.created_by,
+    fn try_from(row: JiraCommentRow) -> Result<Self> {
+        Ok(crate::jira::JiraComment {
+            issue_key: row.issue_key,
+            comment_id: row.comment_id,
+            author: row.author,
+            body: row.body,
             created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
                 .map_err(|e| crate::Error::Other(e.to_string()))?
                 .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
         })
     }
 }
-
-#[derive(sqlx::FromRow)]
-struct FeedbackStatsRow {
-    #[allow(dead_code)]
-    total: i64,
-    positive: i64,
-    negative: i64,
-    neutral: i64,
-}
-
-#[derive(sqlx::FromRow)]
-struct FeedbackStatsByTypeRow {
-    agent_type: String,
-    #[allow(dead_code)]
-    total: i64,
-    positive: i64,
-    negative: i64,
-    neutral: i64,
-}
-
-// ==================== Effectiveness Analysis Row Structs ====================
-
-/// Row for effectiveness analysis queries
-#[derive(sqlx::FromRow, Debug, Clone)]
-pub struct EffectivenessAnalysisRow {
-    pub instruction_id: i64,
-    pub name: String,
-    pub instruction_source: String,
-    pub enabled: bool,
-    pub usage_count: i64,
-    pub success_count: i64,
-    pub failure_count: i64,
-    pub penalty_score: f64,
-    pub avg_completion_time: f64,
-    pub success_rate: f64,
-    pub last_success_at: Option<String>,
-    pub last_failure_at: Option<String>,
-    pub updated_at: Option<String>,
-}
-
-impl EffectivenessAnalysisRow {
-    /// Get the effectiveness level as a string
-    pub fn effectiveness_level(&self) -> &'static str {
-        if self.usage_count < 5 {
-            "insufficient_data"
-        } else if self.success_rate >= 0.8 {
-            "high"
-        } else if self.success_rate >= 0.5 {
-            "medium"
-        } else if self.success_rate >= 0.3 {
-            "low"
-        } else {
-            "very_low"
-        }
-    }
-
-    /// Check if this instruction is considered ineffective
-    pub fn is_ineffective(&self) -> bool {
-        self.usage_count >= 5 && self.success_rate < 0.5
-    }
-}
-
-#[derive(sqlx::FromRow)]
-struct EffectivenessSummaryRow {
-    total_instructions: i64,
-    enabled_count: i64,
-    used_count: i64,
-    avg_success_rate: f64,
-    avg_penalty_score: f64,
-    total_usage: i64,
-    ineffective_count: i64,
-}
-
-/// Summary of instruction effectiveness across the system
-#[derive(Debug, Clone, serde::Serialize)]
-pub struct EffectivenessSummary {
-    pub total_instructions: i64,
-    pub enabled_count: i64,
-    pub used_count: i64,
-    pub avg_success_rate: f64,
-    pub avg_penalty_score: f64,
-    pub total_usage: i64,
-    pub ineffective_count: i64,
-}
-
-// ==================== Experiment Row Structs ====================
-
-#[derive(sqlx::FromRow)]
-struct ExperimentRow {
-    id: i64,
-    name: String,
-    description: Option<String>,
-    hypothesis: Option<String>,
-    experiment_type: String,
-    metric: String,
-    agent_type: Option<String>,
-    status: String,
-    min_samples: i64,
-    confidence_level: f64,
+
+#[derive(sqlx::FromRow)]
+struct LinearTeamRow {
+    id: i64,
+    name: String,
+    linear_team_id: String,
+    epic_prefix: String,
+    status_mapping: String,
+    trigger_label: String,
+    agent_type: String,
+    enabled: bool,
     created_at: String,
-    started_at: Option<String>,
-    completed_at: Option<String>,
-    winner_variant_id: Option<i64>,
+    updated_at: String,
 }
 
-impl TryFrom<ExperimentRow> for Experiment {
+impl TryFrom<LinearTeamRow> for crate::linear::LinearTeam {
     type Error = crate::Error;
 
-    fn try_from(row: ExperimentRow) -> Result<Self> {
-        use std::str::FromStr;
-        Ok(Experiment {
-            id: row.id,
+    fn try_from(row: LinearTeamRow) -> Result<Self> {
+        Ok(crate::linear::LinearTeam {
+            id: Some(row.id),
             name: row.name,
-            description: row.description,
-            hypothesis: row.hypothesis,
-            experiment_type: ExperimentType::from_str(&row.experiment_type)?,
-            metric: ExperimentMetric::from_str(&row.metric)?,
-            agent_type: row.agent_type,
-            status: ExperimentStatus::from_str(&row.status)?,
-            min_samples: row.min_samples,
-            confidence_level: row.confidence_level,
+            linear_team_id: row.linear_team_id,
+            epic_prefix: row.epic_prefix,
+            status_mapping: serde_json::from_str(&row.status_mapping)?,
+            trigger_label: row.trigger_label,
+            agent_type: AgentType::from_str(&row.agent_type)?,
+            enabled: row.enabled,
             created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
                 .map_err(|e| crate::Error::Other(e.to_string()))?
                 .into(),
-            started_at: row
-                .started_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            completed_at: row
-                .completed_at
-                .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
-                .transpose()
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
                 .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(Into::into),
-            winner_variant_id: row.winner_variant_id,
+                .into(),
         })
     }
 }
 
 #[derive(sqlx::FromRow)]
-struct ExperimentVariantRow {
+struct LinearIssueLinkRow {
     id: i64,
-    experiment_id: i64,
-    name: String,
-    description: Option<String>,
-    is_control: bool,
-    weight: i32,
-    config: String,
+    team_id: i64,
+    issue_id: String,
+    identifier: String,
+    url: Option<String>,
+    story_id: Option<String>,
+    agent_id: Option<String>,
+    synced_status: Option<String>,
     created_at: String,
+    updated_at: String,
 }
 
-impl TryFrom<ExperimentVariantRow> for ExperimentVariant {
+impl TryFrom<LinearIssueLinkRow> for crate::linear::LinearIssueLink {
     type Error = crate::Error;
 
-    fn try_from(row: ExperimentVariantRow) -> Result<Self> {
-        Ok(ExperimentVariant {
-            id: row.id,
-            experiment_id: row.experiment_id,
-            name: row.name,
-            description: row.description,
-            is_control: row.is_control,
-            weight: row.weight,
-            config: serde_json::from_str(&row.config)
-                .unwrap_or(serde_json::Value::Object(serde_json::Map::new())),
+    fn try_from(row: LinearIssueLinkRow) -> Result<Self> {
+        Ok(crate::linear::LinearIssueLink {
+            id: Some(row.id),
+            team_id: row.team_id,
+            issue_id: row.issue_id,
+            identifier: row.identifier,
+            url: row.url,
+            story_id: row.story_id,
+            agent_id: row
+                .agent_id
+                .as_deref()
+                .map(Uuid::parse_str)
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            synced_status: row
+                .synced_status
+                .as_deref()
+                .map(StoryStatus::from_str)
+                .transpose()?,
             created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
                 .map_err(|e| crate::Error::Other(e.to_string()))?
                 .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
         })
     }
 }
 
 #[derive(sqlx::FromRow)]
-struct VariantResultsRow {
-    variant_id: i64,
-    variant_name: String,
-    is_control: bool,
-    sample_count: i64,
-    mean: f64,
-    std_dev: f64,
-    min_value: f64,
-    max_value: f64,
-    success_count: Option<i64>,
+struct IssueTriageRow {
+    id: i64,
+    repository: String,
+    issue_number: i64,
+    title: String,
+    body: String,
+    labels: String,
+    duplicate_of: Option<i64>,
+    complexity: String,
+    story_id: Option<String>,
+    agent_id: Option<String>,
+    created_at: String,
+    updated_at: String,
 }
 
-impl From<VariantResultsRow> for VariantResults {
-    fn from(row: VariantResultsRow) -> Self {
-        let success_rate = if row.sample_count > 0 {
-            row.success_count.map(|c| c as f64 / row.sample_count as f64)
-        } else {
-            None
-        };
+impl TryFrom<IssueTriageRow> for crate::issue_triage::IssueTriageRecord {
+    type Error = crate::Error;
 
-        VariantResults {
-            variant_id: row.variant_id,
-            variant_name: row.variant_name,
-            is_control: row.is_control,
-            sample_count: row.sample_count,
-            mean: row.mean,
-            std_dev: row.std_dev,
-            min_value: row.min_value,
-            max_value: row.max_value,
-            success_count: row.success_count,
-            success_rate,
-        }
+    fn try_from(row: IssueTriageRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(crate::issue_triage::IssueTriageRecord {
+            id: Some(row.id),
+            repository: row.repository,
+            issue_number: row.issue_number,
+            title: row.title,
+            body: row.body,
+            labels: serde_json::from_str(&row.labels)?,
+            duplicate_of: row.duplicate_of,
+            complexity: crate::issue_triage::IssueComplexity::from_str(&row.complexity)?,
+            story_id: row.story_id,
+            agent_id: row
+                .agent_id
+                .as_deref()
+                .map(Uuid::parse_str)
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
     }
 }
 
-// ==================== Model Selection Row Structs ====================
-
 #[derive(sqlx::FromRow)]
-struct ModelPerformanceRow {
-    #[allow(dead_code)]
-    id: i64,
-    model: String,
-    task_type: String,
-    agent_type: Option<String>,
-    success_count: i64,
-    failure_count: i64,
-    total_tokens: i64,
-    total_cost: f64,
-    total_duration_secs: f64,
-    sample_count: i64,
-    last_used_at: Option<String>,
-    #[allow(dead_code)]
-    updated_at: String,
+struct IncidentRow {
+    id: String,
+    title: String,
+    description: String,
+    severity: String,
+    status: String,
+    detected_at: String,
+    acknowledged_at: Option<String>,
+    resolved_at: Option<String>,
+    affected_services: String,
+    related_incidents: String,
+    tags: String,
+    metadata: String,
 }
 
-impl From<ModelPerformanceRow> for ModelPerformance {
-    fn from(row: ModelPerformanceRow) -> Self {
-        let success_rate = if row.sample_count > 0 {
-            row.success_count as f64 / row.sample_count as f64
-        } else {
-            0.0
-        };
+impl IncidentRow {
+    fn into_incident(
+        self,
+        timeline: Vec<crate::incident::TimelineEvent>,
+    ) -> Result<crate::incident::Incident> {
+        use std::str::FromStr;
 
-        let avg_tokens = if row.sample_count > 0 {
-            row.total_tokens as f64 / row.sample_count as f64
-        } else {
-            0.0
-        };
+        Ok(crate::incident::Incident {
+            id: self.id,
+            title: self.title,
+            description: self.description,
+            severity: crate::incident::IncidentSeverity::from_str(&self.severity)
+                .map_err(|e| crate::Error::Other(e))?,
+            status: crate::incident::IncidentStatus::from_str(&self.status)
+                .map_err(|e| crate::Error::Other(e))?,
+            detected_at: chrono::DateTime::parse_from_rfc3339(&self.detected_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            acknowledged_at: self
+                .acknowledged_at
+                .as_ref()
+                .map(|s| chrono::DateTime::parse_from_rfc3339(s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(|dt| dt.into()),
+            resolved_at: self
+                .resolved_at
+                .as_ref()
+                .map(|s| chrono::DateTime::parse_from_rfc3339(s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(|dt| dt.into()),
+            timeline,
+            affected_services: serde_json::from_str(&self.affected_services)?,
+            related_incidents: serde_json::from_str(&self.related_incidents)?,
+            tags: serde_json::from_str(&self.tags)?,
+            metadata: serde_json::from_str(&self.metadata)?,
+        })
+    }
+}
 
-        let avg_cost = if row.sample_count > 0 {
-            row.total_cost / row.sample_count as f64
-        } else {
-            0.0
-        };
+#[derive(sqlx::FromRow)]
+struct TimelineEventRow {
+    timestamp: String,
+    event_type: String,
+    description: String,
+    actor: Option<String>,
+    metadata: String,
+}
 
-        let avg_duration_secs = if row.sample_count > 0 {
-            row.total_duration_secs / row.sample_count as f64
-        } else {
-            0.0
-        };
+impl TryFrom<TimelineEventRow> for crate::incident::TimelineEvent {
+    type Error = crate::Error;
 
-        ModelPerformance {
-            model: row.model,
-            task_type: row.task_type,
-            agent_type: row.agent_type,
-            success_count: row.success_count,
-            failure_count: row.failure_count,
-            success_rate,
-            avg_tokens,
-            avg_cost,
-            avg_duration_secs,
-            sample_count: row.sample_count,
-            last_used_at: row
-                .last_used_at
-                .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
-                .map(Into::into),
-        }
+    fn try_from(row: TimelineEventRow) -> Result<Self> {
+        let event_type = match row.event_type.as_str() {
+            "detected" => crate::incident::TimelineEventType::Detected,
+            "acknowledged" => crate::incident::TimelineEventType::Acknowledged,
+            "investigation_started" | "investigationstarted" => {
+                crate::incident::TimelineEventType::InvestigationStarted
+            }
+            "root_cause_identified" | "rootcauseidentified" => {
+                crate::incident::TimelineEventType::RootCauseIdentified
+            }
+            "mitigation_started" | "mitigationstarted" => {
+                crate::incident::TimelineEventType::MitigationStarted
+            }
+            "playbook_executed" | "playbookexecuted" => {
+                crate::incident::TimelineEventType::PlaybookExecuted
+            }
+            "escalated" => crate::incident::TimelineEventType::Escalated,
+            "resolved" => crate::incident::TimelineEventType::Resolved,
+            "post_mortem_created" | "postmortemcreated" => {
+                crate::incident::TimelineEventType::PostMortemCreated
+            }
+            "comment" => crate::incident::TimelineEventType::Comment,
+            _ => {
+                return Err(crate::Error::Other(format!(
+                    "Unknown event type: {}",
+                    row.event_type
+                )))
+            }
+        };
+
+        Ok(crate::incident::TimelineEvent {
+            timestamp: chrono::DateTime::parse_from_rfc3339(&row.timestamp)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            event_type,
+            description: row.description,
+            actor: row.actor,
+            metadata: serde_json::from_str(&row.metadata)?,
+        })
     }
 }
 
 #[derive(sqlx::FromRow)]
-struct ModelSelectionRuleRow {
-    id: i64,
-    name: String,
-    task_type: Option<String>,
-    agent_type: Option<String>,
-    complexity: Option<String>,
-    preferred_model: String,
-    fallback_model: Option<String>,
-    max_cost: Option<f64>,
-    min_success_rate: Option<f64>,
-    priority: i32,
-    enabled: bool,
-    created_at: String,
+struct RootCauseAnalysisRow {
+    incident_id: String,
+    primary_cause: String,
+    evidence: String,
+    contributing_factors: String,
+    hypotheses: String,
+    related_events: String,
+    analyzed_at: String,
 }
 
-impl TryFrom<ModelSelectionRuleRow> for ModelSelectionRule {
+impl TryFrom<RootCauseAnalysisRow> for crate::incident::RootCauseAnalysis {
     type Error = crate::Error;
 
-    fn try_from(row: ModelSelectionRuleRow) -> Result<Self> {
-        use std::str::FromStr;
-
-        let complexity = row
-            .complexity
-            .map(|s| TaskComplexity::from_str(&s))
-            .transpose()?;
-
-        Ok(ModelSelectionRule {
-            id: row.id,
-            name: row.name,
-            task_type: row.task_type,
-            agent_type: row.agent_type,
-            complexity,
-            preferred_model: row.preferred_model,
-            fallback_model: row.fallback_model,
-            max_cost: row.max_cost,
-            min_success_rate: row.min_success_rate,
-            priority: row.priority,
-            enabled: row.enabled,
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+    fn try_from(row: RootCauseAnalysisRow) -> Result<Self> {
+        Ok(crate::incident::RootCauseAnalysis {
+            incident_id: row.incident_id,
+            primary_cause: row.primary_cause,
+            evidence: serde_json::from_str(&row.evidence)?,
+            contributing_factors: serde_json::from_str(&row.contributing_factors)?,
+            hypotheses: serde_json::from_str(&row.hypotheses)?,
+            related_events: serde_json::from_str(&row.related_events)?,
+            analyzed_at: chrono::DateTime::parse_from_rfc3339(&row.analyzed_at)
                 .map_err(|e| crate::Error::Other(e.to_string()))?
                 .into(),
         })
@@ -5506,1659 +10151,1616 @@ impl TryFrom<ModelSelectionRuleRow> for ModelSelectionRule {
 }
 
 #[derive(sqlx::FromRow)]
-struct ModelSelectionConfigRow {
-    #[allow(dead_code)]
-    id: i64,
-    optimization_goal: String,
-    max_cost_per_task: Option<f64>,
-    min_success_rate: f64,
-    min_samples_for_auto: i64,
-    enabled: bool,
-    #[allow(dead_code)]
+struct PlaybookRow {
+    id: String,
+    name: String,
+    description: String,
+    triggers: String,
+    actions: String,
+    created_at: String,
     updated_at: String,
 }
 
-impl From<ModelSelectionConfigRow> for ModelSelectionConfig {
-    fn from(row: ModelSelectionConfigRow) -> Self {
-        use std::str::FromStr;
+impl TryFrom<PlaybookRow> for crate::incident::Playbook {
+    type Error = crate::Error;
 
-        ModelSelectionConfig {
-            optimization_goal: OptimizationGoal::from_str(&row.optimization_goal)
-                .unwrap_or(OptimizationGoal::Balanced),
-            max_cost_per_task: row.max_cost_per_task,
-            min_success_rate: row.min_success_rate,
-            min_samples_for_auto: row.min_samples_for_auto,
-            enabled: row.enabled,
-        }
+    fn try_from(row: PlaybookRow) -> Result<Self> {
+        Ok(crate::incident::Playbook {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            triggers: serde_json::from_str(&row.triggers)?,
+            actions: serde_json::from_str(&row.actions)?,
+            created_at: parse_datetime(&row.created_at)?,
+            updated_at: parse_datetime(&row.updated_at)?,
+        })
     }
 }
 
-impl Database {
-    // ==================== Incident Operations ====================
-
-    /// Create a new incident
-    pub async fn create_incident(&self, incident: &crate::incident::Incident) -> Result<()> {
-        sqlx::query(
-            r#"
-            INSERT INTO incidents (
-                id, title, description, severity, status, detected_at,
-                acknowledged_at, resolved_at, affected_services,
-                related_incidents, tags, metadata
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(&incident.id)
-        .bind(&incident.title)
-        .bind(&incident.description)
-        .bind(incident.severity.as_str())
-        .bind(incident.status.as_str())
-        .bind(incident.detected_at.to_rfc3339())
-        .bind(incident.acknowledged_at.as_ref().map(|t| t.to_rfc3339()))
-        .bind(incident.resolved_at.as_ref().map(|t| t.to_rfc3339()))
-        .bind(serde_json::to_string(&incident.affected_services)?)
-        .bind(serde_json::to_string(&incident.related_incidents)?)
-        .bind(serde_json::to_string(&incident.tags)?)
-        .bind(serde_json::to_string(&incident.metadata)?)
-        .execute(&self.pool)
-        .await?;
-
-        // Insert timeline events
-        for event in &incident.timeline {
-            self.add_timeline_event(&incident.id, event).await?;
-        }
-
-        Ok(())
-    }
-
-    /// Update an incident
-    pub async fn update_incident(&self, incident: &crate::incident::Incident) -> Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE incidents SET
-                title = ?, description = ?, severity = ?, status = ?,
-                acknowledged_at = ?, resolved_at = ?,
-                affected_services = ?, related_incidents = ?,
-                tags = ?, metadata = ?, updated_at = datetime('now')
-            WHERE id = ?
-            "#,
-        )
-        .bind(&incident.title)
-        .bind(&incident.description)
-        .bind(incident.severity.as_str())
-        .bind(incident.status.as_str())
-        .bind(incident.acknowledged_at.as_ref().map(|t| t.to_rfc3339()))
-        .bind(incident.resolved_at.as_ref().map(|t| t.to_rfc3339()))
-        .bind(serde_json::to_string(&incident.affected_services)?)
-        .bind(serde_json::to_string(&incident.related_incidents)?)
-        .bind(serde_json::to_string(&incident.tags)?)
-        .bind(serde_json::to_string(&incident.metadata)?)
-        .bind(&incident.id)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(())
-    }
-
-    /// Get an incident by ID
-    pub async fn get_incident(&self, id: &str) -> Result<Option<crate::incident::Incident>> {
-        let row = sqlx::query_as::<_, IncidentRow>(
-            r#"
-            SELECT id, title, description, severity, status, detected_at,
-                   acknowledged_at, resolved_at, affected_services,
-                   related_incidents, tags, metadata
-            FROM incidents
-            WHERE id = ?
-            "#,
-        )
-        .bind(id)
-        .fetch_optional(&self.pool)
-        .await?;
-
-        if let Some(row) = row {
-            let timeline = self.get_timeline_events(id).await?;
-            Ok(Some(row.into_incident(timeline)?))
-        } else {
-            Ok(None)
-        }
-    }
-
-    /// List incidents with optional filters
-    pub async fn list_incidents(
-        &self,
-        status: Option<&str>,
-        severity: Option<&str>,
-        limit: Option<i64>,
-    ) -> Result<Vec<crate::incident::Incident>> {
-        let mut query = String::from(
-            r#"
-            SELECT id, title, description, severity, status, detected_at,
-                   acknowledged_at, resolved_at, affected_services,
-                   related_incidents, tags, metadata
-            FROM incidents
-            WHERE 1=1
-            "#,
-        );
-
-        if status.is_some() {
-            query.push_str(" AND status = ?");
-        }
-        if severity.is_some() {
-            query.push_str(" AND severity = ?");
-        }
+#[derive(sqlx::FromRow)]
+struct PlaybookExecutionRow {
+    id: String,
+    playbook_id: String,
+    incident_id: Option<String>,
+    status: String,
+    started_at: String,
+    completed_at: Option<String>,
+    action_results: String,
+    triggered_by: Option<String>,
+}
 
-        query.push_str(" ORDER BY detected_at DESC");
+impl TryFrom<PlaybookExecutionRow> for crate::incident::PlaybookExecution {
+    type Error = crate::Error;
 
-        if let Some(limit) = limit {
-            query.push_str(&format!(" LIMIT {}", limit));
-        }
+    fn try_from(row: PlaybookExecutionRow) -> Result<Self> {
 
-        let mut q = sqlx::query_as::<_, IncidentRow>(&query);
-        if let Some(s) = status {
-            q = q.bind(s);
-        }
-        if let Some(sev) = severity {
-            q = q.bind(sev);
-        }
+        let status = match row.status.as_str() {
+            "running" => crate::incident::PlaybookExecutionStatus::Running,
+            "waiting_approval" => crate::incident::PlaybookExecutionStatus::WaitingApproval,
+            "completed" => crate::incident::PlaybookExecutionStatus::Completed,
+            "failed" => crate::incident::PlaybookExecutionStatus::Failed,
+            "cancelled" => crate::incident::PlaybookExecutionStatus::Cancelled,
+            _ => {
+                return Err(crate::Error::Other(format!(
+                    "Unknown execution status: {}",
+                    row.status
+                )))
+            }
+        };
 
-        let rows = q.fetch_all(&self.pool).await?;
+        Ok(crate::incident::PlaybookExecution {
+            id: row.id,
+            playbook_id: row.playbook_id,
+            incident_id: row.incident_id,
+            status,
+            started_at: chrono::DateTime::parse_from_rfc3339(&row.started_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            completed_at: row
+                .completed_at
+                .as_ref()
+                .map(|s| chrono::DateTime::parse_from_rfc3339(s))
+                .transpose()
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .map(|dt| dt.into()),
+            action_results: serde_json::from_str(&row.action_results)?,
+            triggered_by: row.triggered_by,
+        })
+    }
+}
 
-        let mut incidents = Vec::new();
-        for row in rows {
-            let timeline = self.get_timeline_events(&row.id).await?;
-            incidents.push(row.into_incident(timeline)?);
-        }
+#[derive(sqlx::FromRow)]
+struct PostMortemRow {
+    incident_id: String,
+    title: String,
+    summary: String,
+    impact: String,
+    root_cause: String,
+    contributing_factors: String,
+    resolution: String,
+    action_items: String,
+    lessons_learned: String,
+    authors: String,
+    created_at: String,
+}
 
-        Ok(incidents)
+impl PostMortemRow {
+    fn into_post_mortem(
+        self,
+        timeline: Vec<crate::incident::TimelineEvent>,
+    ) -> Result<crate::incident::PostMortem> {
+        Ok(crate::incident::PostMortem {
+            incident_id: self.incident_id,
+            title: self.title,
+            summary: self.summary,
+            impact: serde_json::from_str(&self.impact)?,
+            timeline,
+            root_cause: self.root_cause,
+            contributing_factors: serde_json::from_str(&self.contributing_factors)?,
+            resolution: self.resolution,
+            action_items: serde_json::from_str(&self.action_items)?,
+            lessons_learned: serde_json::from_str(&self.lessons_learned)?,
+            created_at: parse_datetime(&self.created_at)?,
+            authors: serde_json::from_str(&self.authors)?,
+        })
     }
+}
 
-    /// Add timeline event to incident
-    pub async fn add_timeline_event(
-        &self,
-        incident_id: &str,
-        event: &crate::incident::TimelineEvent,
-    ) -> Result<()> {
-        let event_type_str = match event.event_type {
-            crate::incident::TimelineEventType::Detected => "detected",
-            crate::incident::TimelineEventType::Acknowledged => "acknowledged",
-            crate::incident::TimelineEventType::InvestigationStarted => "investigation_started",
-            crate::incident::TimelineEventType::RootCauseIdentified => "root_cause_identified",
-            crate::incident::TimelineEventType::MitigationStarted => "mitigation_started",
-            crate::incident::TimelineEventType::PlaybookExecuted => "playbook_executed",
-            crate::incident::TimelineEventType::Escalated => "escalated",
-            crate::incident::TimelineEventType::Resolved => "resolved",
-            crate::incident::TimelineEventType::PostMortemCreated => "post_mortem_created",
-            crate::incident::TimelineEventType::Comment => "comment",
-        };
+#[derive(sqlx::FromRow)]
+struct AnomalyMetricRow {
+    name: String,
+    current_value: f64,
+    baseline_value: f64,
+    threshold: f64,
+    deviation_percent: f64,
+    is_anomaly: bool,
+    timestamp: String,
+}
 
-        sqlx::query(
-            r#"
-            INSERT INTO incident_timeline (
-                incident_id, timestamp, event_type, description, actor, metadata
-            )
-            VALUES (?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(incident_id)
-        .bind(event.timestamp.to_rfc3339())
-        .bind(event_type_str)
-        .bind(&event.description)
-        .bind(&event.actor)
-        .bind(serde_json::to_string(&event.metadata)?)
-        .execute(&self.pool)
-        .await?;
+impl TryFrom<AnomalyMetricRow> for crate::incident::AnomalyMetric {
+    type Error = crate::Error;
 
-        Ok(())
+    fn try_from(row: AnomalyMetricRow) -> Result<Self> {
+        Ok(crate::incident::AnomalyMetric {
+            name: row.name,
+            current_value: row.current_value,
+            baseline_value: row.baseline_value,
+            threshold: row.threshold,
+            deviation_percent: row.deviation_percent,
+            is_anomaly: row.is_anomaly,
+            timestamp: chrono::DateTime::parse_from_rfc3339(&row.timestamp)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
     }
+}
 
-    /// Get timeline events for an incident
-    pub async fn get_timeline_events(
-        &self,
-        incident_id: &str,
-    ) -> Result<Vec<crate::incident::TimelineEvent>> {
-        let rows = sqlx::query_as::<_, TimelineEventRow>(
-            r#"
-            SELECT timestamp, event_type, description, actor, metadata
-            FROM incident_timeline
-            WHERE incident_id = ?
-            ORDER BY timestamp ASC
-            "#,
-        )
-        .bind(incident_id)
-        .fetch_all(&self.pool)
-        .await?;
+// ==================== Monitoring & Metrics Database Methods ====================
 
-        rows.into_iter().map(|r| r.try_into()).collect()
-    }
+/// Agent count by state and type
+#[derive(Debug, Clone, Default)]
+pub struct AgentCountsByStateAndType {
+    pub by_state: std::collections::HashMap<String, i64>,
+    pub by_type: std::collections::HashMap<String, i64>,
+}
 
-    // ==================== Root Cause Analysis ====================
+/// Token usage by model
+#[derive(Debug, Clone)]
+pub struct TokenUsageByModel {
+    pub model: String,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub total_tokens: i64,
+}
 
-    /// Save root cause analysis
-    pub async fn save_root_cause_analysis(
-        &self,
-        rca: &crate::incident::RootCauseAnalysis,
-    ) -> Result<()> {
-        sqlx::query(
-            r#"
-            INSERT OR REPLACE INTO root_cause_analyses (
-                incident_id, primary_cause, evidence, contributing_factors,
-                hypotheses, related_events, analyzed_at
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(&rca.incident_id)
-        .bind(&rca.primary_cause)
-        .bind(serde_json::to_string(&rca.evidence)?)
-        .bind(serde_json::to_string(&rca.contributing_factors)?)
-        .bind(serde_json::to_string(&rca.hypotheses)?)
-        .bind(serde_json::to_string(&rca.related_events)?)
-        .bind(rca.analyzed_at.to_rfc3339())
-        .execute(&self.pool)
-        .await?;
+/// PR cycle time stats
+#[derive(Debug, Clone)]
+pub struct PrCycleTime {
+    pub pr_id: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub merged_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub cycle_time_hours: Option<f64>,
+}
 
-        Ok(())
-    }
+/// Story completion rate
+#[derive(Debug, Clone)]
+pub struct StoryCompletionRate {
+    pub epic_id: String,
+    pub total_stories: i64,
+    pub completed_stories: i64,
+    pub completion_rate: f64,
+}
 
-    /// Get root cause analysis for an incident
-    pub async fn get_root_cause_analysis(
-        &self,
-        incident_id: &str,
-    ) -> Result<Option<crate::incident::RootCauseAnalysis>> {
-        let row = sqlx::query_as::<_, RootCauseAnalysisRow>(
-            r#"
-            SELECT incident_id, primary_cause, evidence, contributing_factors,
-                   hypotheses, related_events, analyzed_at
-            FROM root_cause_analyses
-            WHERE incident_id = ?
-            "#,
-        )
-        .bind(incident_id)
-        .fetch_optional(&self.pool)
-        .await?;
+/// Agent success rate
+#[derive(Debug, Clone)]
+pub struct AgentSuccessRate {
+    pub agent_type: String,
+    pub total_runs: i64,
+    pub successful_runs: i64,
+    pub success_rate: f64,
+}
 
-        row.map(|r| r.try_into()).transpose()
-    }
+/// Pipeline run durations by pipeline and final status
+#[derive(Debug, Clone)]
+pub struct PipelineDuration {
+    pub pipeline: String,
+    pub status: String,
+    pub runs: i64,
+    pub avg_duration_seconds: f64,
+}
 
-    // ==================== Playbook Operations ====================
+/// Agent loop turns by agent type
+#[derive(Debug, Clone)]
+pub struct AgentTurnCount {
+    pub agent_type: String,
+    pub turns: i64,
+    /// Agent that ran the most recent turn
+    pub latest_agent_id: String,
+}
 
-    /// Create a playbook
-    pub async fn create_playbook(&self, playbook: &crate::incident::Playbook) -> Result<()> {
-        sqlx::query(
-            r#"
-            INSERT INTO playbooks (id, name, description, triggers, actions)
-            VALUES (?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(&playbook.id)
-        .bind(&playbook.name)
-        .bind(&playbook.description)
-        .bind(serde_json::to_string(&playbook.triggers)?)
-        .bind(serde_json::to_string(&playbook.actions)?)
-        .execute(&self.pool)
-        .await?;
+/// Webhook events received through one source, by status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSourceStats {
+    pub source: String,
+    pub total: i64,
+    pub pending: i64,
+    pub processing: i64,
+    pub completed: i64,
+    pub failed: i64,
+    pub dead_letter: i64,
+    pub last_received_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Pending webhook events of one event type, and how long the oldest waits
+#[derive(Debug, Clone)]
+pub struct WebhookQueueLag {
+    pub event_type: String,
+    pub pending: i64,
+    pub lag_seconds: f64,
+}
 
-        Ok(())
-    }
+/// How far an enabled schedule is behind its next run
+#[derive(Debug, Clone)]
+pub struct ScheduleLag {
+    pub schedule: String,
+    pub lag_seconds: f64,
+}
 
-    /// Update a playbook
-    pub async fn update_playbook(&self, playbook: &crate::incident::Playbook) -> Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE playbooks SET
-                name = ?, description = ?, triggers = ?, actions = ?,
-                updated_at = datetime('now')
-            WHERE id = ?
-            "#,
-        )
-        .bind(&playbook.name)
-        .bind(&playbook.description)
-        .bind(serde_json::to_string(&playbook.triggers)?)
-        .bind(serde_json::to_string(&playbook.actions)?)
-        .bind(&playbook.id)
-        .execute(&self.pool)
-        .await?;
+impl Database {
+    // ==================== Agent Metrics ====================
 
-        Ok(())
-    }
+    /// Get agent counts grouped by state and type
+    pub async fn get_agent_counts_by_state_and_type(&self) -> Result<AgentCountsByStateAndType> {
+        let mut result = AgentCountsByStateAndType::default();
 
-    /// Get a playbook by ID
-    pub async fn get_playbook(&self, id: &str) -> Result<Option<crate::incident::Playbook>> {
-        let row = sqlx::query_as::<_, PlaybookRow>(
-            r#"
-            SELECT id, name, description, triggers, actions, created_at, updated_at
-            FROM playbooks
-            WHERE id = ?
-            "#,
+        // Count by state
+        let state_rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT state, COUNT(*) as count FROM agents GROUP BY state"
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.try_into()).transpose()
-    }
+        for (state, count) in state_rows {
+            result.by_state.insert(state, count);
+        }
 
-    /// Get a playbook by name
-    pub async fn get_playbook_by_name(
-        &self,
-        name: &str,
-    ) -> Result<Option<crate::incident::Playbook>> {
-        let row = sqlx::query_as::<_, PlaybookRow>(
-            r#"
-            SELECT id, name, description, triggers, actions, created_at, updated_at
-            FROM playbooks
-            WHERE name = ?
-            "#,
+        // Count by type
+        let type_rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT agent_type, COUNT(*) as count FROM agents GROUP BY agent_type"
         )
-        .bind(name)
-        .fetch_optional(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.try_into()).transpose()
+        for (agent_type, count) in type_rows {
+            result.by_type.insert(agent_type, count);
+        }
+
+        Ok(result)
     }
 
-    /// List all playbooks
-    pub async fn list_playbooks(&self) -> Result<Vec<crate::incident::Playbook>> {
-        let rows = sqlx::query_as::<_, PlaybookRow>(
+    /// Get agent success rates
+    pub async fn get_agent_success_rates(&self) -> Result<Vec<AgentSuccessRate>> {
+        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
             r#"
-            SELECT id, name, description, triggers, actions, created_at, updated_at
-            FROM playbooks
-            ORDER BY name ASC
-            "#,
+            SELECT
+                agent_type,
+                COUNT(*) as total_runs,
+                SUM(CASE WHEN state = 'completed' THEN 1 ELSE 0 END) as successful_runs
+            FROM agents
+            GROUP BY agent_type
+            "#
         )
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.try_into()).collect()
-    }
-
-    /// Delete a playbook
-    pub async fn delete_playbook(&self, id: &str) -> Result<()> {
-        sqlx::query("DELETE FROM playbooks WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-
-        Ok(())
+        Ok(rows.into_iter().map(|(agent_type, total_runs, successful_runs)| {
+            AgentSuccessRate {
+                agent_type,
+                total_runs,
+                successful_runs,
+                success_rate: if total_runs > 0 { successful_runs as f64 / total_runs as f64 } else { 0.0 },
+            }
+        }).collect())
     }
 
-    // ==================== Playbook Execution ====================
+    // ==================== Token Usage ====================
 
-    /// Create playbook execution
-    pub async fn create_playbook_execution(
-        &self,
-        execution: &crate::incident::PlaybookExecution,
-    ) -> Result<()> {
-        sqlx::query(
+    /// Get token usage grouped by model
+    pub async fn get_token_usage_by_model(&self) -> Result<Vec<TokenUsageByModel>> {
+        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
             r#"
-            INSERT INTO playbook_executions (
-                id, playbook_id, incident_id, status, started_at,
-                completed_at, action_results, triggered_by
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
+            SELECT
+                model,
+                SUM(total_input_tokens) as input_tokens,
+                SUM(total_output_tokens) as output_tokens
+            FROM daily_token_usage
+            GROUP BY model
+            "#
         )
-        .bind(&execution.id)
-        .bind(&execution.playbook_id)
-        .bind(&execution.incident_id)
-        .bind(execution.status.as_str())
-        .bind(execution.started_at.to_rfc3339())
-        .bind(execution.completed_at.as_ref().map(|t| t.to_rfc3339()))
-        .bind(serde_json::to_string(&execution.action_results)?)
-        .bind(&execution.triggered_by)
-        .execute(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(rows.into_iter().map(|(model, input_tokens, output_tokens)| {
+            TokenUsageByModel {
+                model,
+                input_tokens,
+                output_tokens,
+                total_tokens: input_tokens + output_tokens,
+            }
+        }).collect())
     }
 
-    /// Update playbook execution
-    pub async fn update_playbook_execution(
-        &self,
-        execution: &crate::incident::PlaybookExecution,
-    ) -> Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE playbook_executions SET
-                status = ?, completed_at = ?, action_results = ?
-            WHERE id = ?
-            "#,
+    // ==================== Webhook Metrics ====================
+
+    /// Get count of pending webhook events
+    pub async fn get_pending_webhook_events_count(&self) -> Result<i64> {
+        let (count,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM webhook_events WHERE status = 'pending'"
         )
-        .bind(execution.status.as_str())
-        .bind(execution.completed_at.as_ref().map(|t| t.to_rfc3339()))
-        .bind(serde_json::to_string(&execution.action_results)?)
-        .bind(&execution.id)
-        .execute(&self.pool)
+        .fetch_one(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(count)
     }
 
-    /// Get playbook execution by ID
-    pub async fn get_playbook_execution(
-        &self,
-        id: &str,
-    ) -> Result<Option<crate::incident::PlaybookExecution>> {
-        let row = sqlx::query_as::<_, PlaybookExecutionRow>(
+    /// Get the age of the oldest pending webhook event of each event type
+    pub async fn get_webhook_queue_lags(&self) -> Result<Vec<WebhookQueueLag>> {
+        let rows: Vec<(String, i64, String)> = sqlx::query_as(
             r#"
-            SELECT id, playbook_id, incident_id, status, started_at,
-                   completed_at, action_results, triggered_by
-            FROM playbook_executions
-            WHERE id = ?
+            SELECT event_type, COUNT(*), MIN(received_at)
+            FROM webhook_events
+            WHERE status = 'pending'
+            GROUP BY event_type
             "#,
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.try_into()).transpose()
-    }
-
-    /// List executions for a playbook
-    pub async fn list_playbook_executions(
-        &self,
-        playbook_id: &str,
-        limit: Option<i64>,
-    ) -> Result<Vec<crate::incident::PlaybookExecution>> {
-        let mut query = String::from(
-            r#"
-            SELECT id, playbook_id, incident_id, status, started_at,
-                   completed_at, action_results, triggered_by
-            FROM playbook_executions
-            WHERE playbook_id = ?
-            ORDER BY started_at DESC
-            "#,
-        );
-
-        if let Some(limit) = limit {
-            query.push_str(&format!(" LIMIT {}", limit));
+        let now = chrono::Utc::now();
+        let mut result = Vec::new();
+        for (event_type, pending, oldest) in rows {
+            let oldest = parse_datetime(&oldest)?;
+            let lag = now.signed_duration_since(oldest).num_milliseconds().max(0);
+
+            result.push(WebhookQueueLag {
+                event_type,
+                pending,
+                lag_seconds: lag as f64 / 1000.0,
+            });
         }
 
-        let rows = sqlx::query_as::<_, PlaybookExecutionRow>(&query)
-            .bind(playbook_id)
-            .fetch_all(&self.pool)
-            .await?;
-
-        rows.into_iter().map(|r| r.try_into()).collect()
+        Ok(result)
     }
 
-    // ==================== Post-Mortem Operations ====================
+    // ==================== Pipeline Metrics ====================
 
-    /// Save post-mortem
-    pub async fn save_post_mortem(&self, pm: &crate::incident::PostMortem) -> Result<()> {
-        sqlx::query(
+    /// Get average durations of finished pipeline runs
+    pub async fn get_pipeline_durations(&self) -> Result<Vec<PipelineDuration>> {
+        let rows: Vec<(String, String, i64, f64)> = sqlx::query_as(
             r#"
-            INSERT OR REPLACE INTO post_mortems (
-                incident_id, title, summary, impact, root_cause,
-                contributing_factors, resolution, action_items,
-                lessons_learned, authors
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
+            SELECT
+                p.name,
+                r.status,
+                COUNT(*) as runs,
+                ROUND(AVG((julianday(r.completed_at) - julianday(r.started_at)) * 86400.0), 3) as avg_duration_seconds
+            FROM pipeline_runs r
+            JOIN pipelines p ON p.id = r.pipeline_id
+            WHERE r.started_at IS NOT NULL AND r.completed_at IS NOT NULL
+            GROUP BY p.name, r.status
+            "#
         )
-        .bind(&pm.incident_id)
-        .bind(&pm.title)
-        .bind(&pm.summary)
-        .bind(serde_json::to_string(&pm.impact)?)
-        .bind(&pm.root_cause)
-        .bind(serde_json::to_string(&pm.contributing_factors)?)
-        .bind(&pm.resolution)
-        .bind(serde_json::to_string(&pm.action_items)?)
-        .bind(serde_json::to_string(&pm.lessons_learned)?)
-        .bind(serde_json::to_string(&pm.authors)?)
-        .execute(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(rows.into_iter().map(|(pipeline, status, runs, avg_duration_seconds)| {
+            PipelineDuration {
+                pipeline,
+                status,
+                runs,
+                avg_duration_seconds,
+            }
+        }).collect())
     }
 
-    /// Get post-mortem for an incident
-    pub async fn get_post_mortem(
-        &self,
-        incident_id: &str,
-    ) -> Result<Option<crate::incident::PostMortem>> {
-        let row = sqlx::query_as::<_, PostMortemRow>(
+    // ==================== Agent Turn Metrics ====================
+
+    /// Get the number of recorded agent loop turns by agent type
+    pub async fn get_agent_turn_counts(&self) -> Result<Vec<AgentTurnCount>> {
+        let rows: Vec<(String, i64, String)> = sqlx::query_as(
             r#"
-            SELECT incident_id, title, summary, impact, root_cause,
-                   contributing_factors, resolution, action_items,
-                   lessons_learned, authors, created_at
-            FROM post_mortems
-            WHERE incident_id = ?
-            "#,
+            SELECT
+                a.agent_type,
+                COUNT(*) as turns,
+                (
+                    SELECT s2.agent_id
+                    FROM session_token_stats s2
+                    JOIN agents a2 ON a2.id = s2.agent_id
+                    WHERE a2.agent_type = a.agent_type
+                    ORDER BY s2.id DESC
+                    LIMIT 1
+                ) as latest_agent_id
+            FROM session_token_stats s
+            JOIN agents a ON a.id = s.agent_id
+            GROUP BY a.agent_type
+            "#
         )
-        .bind(incident_id)
-        .fetch_optional(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        if let Some(row) = row {
-            // Get timeline from incident
-            let timeline = self.get_timeline_events(incident_id).await?;
-            Ok(Some(row.into_post_mortem(timeline)?))
-        } else {
-            Ok(None)
-        }
+        Ok(rows.into_iter().map(|(agent_type, turns, latest_agent_id)| {
+            AgentTurnCount {
+                agent_type,
+                turns,
+                latest_agent_id,
+            }
+        }).collect())
     }
 
-    // ==================== Anomaly Metrics ====================
+    // ==================== Schedule Metrics ====================
 
-    /// Record anomaly metric
-    pub async fn record_anomaly_metric(
-        &self,
-        metric: &crate::incident::AnomalyMetric,
-        incident_id: Option<&str>,
-    ) -> Result<()> {
-        sqlx::query(
-            r#"
-            INSERT INTO anomaly_metrics (
-                name, current_value, baseline_value, threshold,
-                deviation_percent, is_anomaly, incident_id, timestamp, metadata
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
+    /// Get how long each enabled schedule has been due without running
+    ///
+    /// Schedules whose next run is still in the future have zero lag.
+    pub async fn get_schedule_lags(&self) -> Result<Vec<ScheduleLag>> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT name, next_run FROM schedules WHERE enabled = 1 AND next_run IS NOT NULL"
         )
-        .bind(&metric.name)
-        .bind(metric.current_value)
-        .bind(metric.baseline_value)
-        .bind(metric.threshold)
-        .bind(metric.deviation_percent)
-        .bind(metric.is_anomaly)
-        .bind(incident_id)
-        .bind(metric.timestamp.to_rfc3339())
-        .bind("{}")
-        .execute(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(())
+        let now = chrono::Utc::now();
+        let mut result = Vec::new();
+        for (schedule, next_run_str) in rows {
+            let next_run = chrono::DateTime::parse_from_rfc3339(&next_run_str)
+                .map_err(|e| crate::Error::Other(e.to_string()))?;
+            let lag = now.signed_duration_since(next_run).num_milliseconds().max(0);
+
+            result.push(ScheduleLag {
+                schedule,
+                lag_seconds: lag as f64 / 1000.0,
+            });
+        }
+
+        Ok(result)
     }
 
-    /// Get recent anomalies
-    pub async fn get_recent_anomalies(
-        &self,
-        limit: i64,
-    ) -> Result<Vec<crate::incident::AnomalyMetric>> {
-        let rows = sqlx::query_as::<_, AnomalyMetricRow>(
+    // ==================== PR Metrics ====================
+
+    /// Get PR cycle times
+    pub async fn get_pr_cycle_times(&self) -> Result<Vec<PrCycleTime>> {
+        let rows: Vec<(String, String, Option<String>)> = sqlx::query_as(
             r#"
-            SELECT name, current_value, baseline_value, threshold,
-                   deviation_percent, is_anomaly, timestamp
-            FROM anomaly_metrics
-            WHERE is_anomaly = 1
-            ORDER BY timestamp DESC
-            LIMIT ?
-            "#,
+            SELECT CAST(id AS TEXT), created_at, merged_at
+            FROM pr_queue
+            ORDER BY created_at DESC
+            LIMIT 100
+            "#
         )
-        .bind(limit)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.try_into()).collect()
-    }
-}
+        let mut result = Vec::new();
+        for (pr_id, created_at_str, merged_at_str) in rows {
+            let created_at: chrono::DateTime<chrono::Utc> = chrono::DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into();
 
-// ==================== Database Row Types ====================
+            let merged_at: Option<chrono::DateTime<chrono::Utc>> = if let Some(m) = merged_at_str {
+                Some(chrono::DateTime::parse_from_rfc3339(&m)
+                    .map_err(|e| crate::Error::Other(e.to_string()))?
+                    .into())
+            } else {
+                None
+            };
 
-#[derive(sqlx::FromRow)]
-struct IncidentRow {
-    id: String,
-    title: String,
-    description: String,
-    severity: String,
-    status: String,
-    detected_at: String,
-    acknowledged_at: Option<String>,
-    resolved_at: Option<String>,
-    affected_services: String,
-    related_incidents: String,
-    tags: String,
-    metadata: String,
-}
+            let cycle_time_hours = merged_at.map(|m| {
+                (m - created_at).num_hours() as f64
+            });
 
-impl IncidentRow {
-    fn into_incident(
-        self,
-        timeline: Vec<crate::incident::TimelineEvent>,
-    ) -> Result<crate::incident::Incident> {
-        use std::str::FromStr;
+            result.push(PrCycleTime {
+                pr_id,
+                created_at,
+                merged_at,
+                cycle_time_hours,
+            });
+        }
 
-        Ok(crate::incident::Incident {
-            id: self.id,
-            title: self.title,
-            description: self.description,
-            severity: crate::incident::IncidentSeverity::from_str(&self.severity)
-                .map_err(|e| crate::Error::Other(e))?,
-            status: crate::incident::IncidentStatus::from_str(&self.status)
-                .map_err(|e| crate::Error::Other(e))?,
-            detected_at: chrono::DateTime::parse_from_rfc3339(&self.detected_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            acknowledged_at: self
-                .acknowledged_at
-                .as_ref()
-                .map(|s| chrono::DateTime::parse_from_rfc3339(s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(|dt| dt.into()),
-            resolved_at: self
-                .resolved_at
-                .as_ref()
-                .map(|s| chrono::DateTime::parse_from_rfc3339(s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(|dt| dt.into()),
-            timeline,
-            affected_services: serde_json::from_str(&self.affected_services)?,
-            related_incidents: serde_json::from_str(&self.related_incidents)?,
-            tags: serde_json::from_str(&self.tags)?,
-            metadata: serde_json::from_str(&self.metadata)?,
-        })
+        Ok(result)
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct TimelineEventRow {
-    timestamp: String,
-    event_type: String,
-    description: String,
-    actor: Option<String>,
-    metadata: String,
-}
+    // ==================== Story Metrics ====================
 
-impl TryFrom<TimelineEventRow> for crate::incident::TimelineEvent {
-    type Error = crate::Error;
+    /// Get story completion rates by epic
+    pub async fn get_story_completion_rates(&self) -> Result<Vec<StoryCompletionRate>> {
+        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT
+                epic_id,
+                COUNT(*) as total_stories,
+                SUM(CASE WHEN status = 'completed' THEN 1 ELSE 0 END) as completed_stories
+            FROM stories
+            GROUP BY epic_id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-    fn try_from(row: TimelineEventRow) -> Result<Self> {
-        let event_type = match row.event_type.as_str() {
-            "detected" => crate::incident::TimelineEventType::Detected,
-            "acknowledged" => crate::incident::TimelineEventType::Acknowledged,
-            "investigation_started" | "investigationstarted" => {
-                crate::incident::TimelineEventType::InvestigationStarted
-            }
-            "root_cause_identified" | "rootcauseidentified" => {
-                crate::incident::TimelineEventType::RootCauseIdentified
-            }
-            "mitigation_started" | "mitigationstarted" => {
-                crate::incident::TimelineEventType::MitigationStarted
-            }
-            "playbook_executed" | "playbookexecuted" => {
-                crate::incident::TimelineEventType::PlaybookExecuted
-            }
-            "escalated" => crate::incident::TimelineEventType::Escalated,
-            "resolved" => crate::incident::TimelineEventType::Resolved,
-            "post_mortem_created" | "postmortemcreated" => {
-                crate::incident::TimelineEventType::PostMortemCreated
-            }
-            "comment" => crate::incident::TimelineEventType::Comment,
-            _ => {
-                return Err(crate::Error::Other(format!(
-                    "Unknown event type: {}",
-                    row.event_type
-                )))
+        Ok(rows.into_iter().map(|(epic_id, total_stories, completed_stories)| {
+            StoryCompletionRate {
+                epic_id,
+                total_stories,
+                completed_stories,
+                completion_rate: if total_stories > 0 { completed_stories as f64 / total_stories as f64 } else { 0.0 },
             }
-        };
+        }).collect())
+    }
 
-        Ok(crate::incident::TimelineEvent {
-            timestamp: chrono::DateTime::parse_from_rfc3339(&row.timestamp)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            event_type,
-            description: row.description,
-            actor: row.actor,
-            metadata: serde_json::from_str(&row.metadata)?,
-        })
+    // ==================== Alerting ====================
+
+    /// Create a new alert rule
+    pub async fn create_alert_rule(&self, rule: &crate::monitoring::AlertRule) -> Result<i64> {
+        let id = sqlx::query_scalar(
+            r#"
+            INSERT INTO alert_rules (name, condition, severity, channels, enabled, evaluation_interval_seconds, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))
+            RETURNING id
+            "#
+        )
+        .bind(&rule.name)
+        .bind(&rule.condition)
+        .bind(format!("{:?}", rule.severity).to_lowercase())
+        .bind(serde_json::to_string(&rule.channels).unwrap_or_default())
+        .bind(rule.enabled)
+        .bind(60i64)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Get an alert by ID
+    pub async fn get_alert(&self, id: i64) -> Result<Option<crate::monitoring::Alert>> {
+        let row: Option<AlertRowSimple> = sqlx::query_as(
+            r#"
+            SELECT a.id, a.rule_id, r.name as rule_name, a.status, r.severity,
+                   a.triggered_at, a.acknowledged_at, a.acknowledged_by, a.resolved_at
+            FROM alerts a
+            JOIN alert_rules r ON a.rule_id = r.id
+            WHERE a.id = ?
+            "#
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(r) => Ok(Some(r.try_into()?)),
+            None => Ok(None),
+        }
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct RootCauseAnalysisRow {
-    incident_id: String,
-    primary_cause: String,
-    evidence: String,
-    contributing_factors: String,
-    hypotheses: String,
-    related_events: String,
-    analyzed_at: String,
-}
+    /// Update an alert
+    pub async fn update_alert(&self, alert: &crate::monitoring::Alert) -> Result<()> {
+        let id: i64 = alert.id.parse().map_err(|_| crate::Error::Other("Invalid alert ID".into()))?;
 
-impl TryFrom<RootCauseAnalysisRow> for crate::incident::RootCauseAnalysis {
-    type Error = crate::Error;
+        sqlx::query(
+            r#"
+            UPDATE alerts
+            SET status = ?, acknowledged_at = ?, acknowledged_by = ?, resolved_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(format!("{:?}", alert.status).to_lowercase())
+        .bind(alert.acknowledged_at.map(|dt| dt.to_rfc3339()))
+        .bind(&alert.acknowledged_by)
+        .bind(alert.resolved_at.map(|dt| dt.to_rfc3339()))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
 
-    fn try_from(row: RootCauseAnalysisRow) -> Result<Self> {
-        Ok(crate::incident::RootCauseAnalysis {
-            incident_id: row.incident_id,
-            primary_cause: row.primary_cause,
-            evidence: serde_json::from_str(&row.evidence)?,
-            contributing_factors: serde_json::from_str(&row.contributing_factors)?,
-            hypotheses: serde_json::from_str(&row.hypotheses)?,
-            related_events: serde_json::from_str(&row.related_events)?,
-            analyzed_at: chrono::DateTime::parse_from_rfc3339(&row.analyzed_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-        })
+        Ok(())
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct PlaybookRow {
-    id: String,
-    name: String,
-    description: String,
-    triggers: String,
-    actions: String,
-    created_at: String,
-    updated_at: String,
-}
+    /// List alerts by status
+    pub async fn list_alerts_by_status(
+        &self,
+        status: Option<&str>,
+        severity: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<crate::monitoring::Alert>> {
+        let mut query = String::from(
+            r#"
+            SELECT a.id, a.rule_id, r.name as rule_name, a.status, r.severity,
+                   a.triggered_at, a.acknowledged_at, a.acknowledged_by, a.resolved_at
+            FROM alerts a
+            JOIN alert_rules r ON a.rule_id = r.id
+            WHERE 1=1
+            "#
+        );
 
-impl TryFrom<PlaybookRow> for crate::incident::Playbook {
-    type Error = crate::Error;
+        if status.is_some() {
+            query.push_str(" AND a.status = ?");
+        }
+        if severity.is_some() {
+            query.push_str(" AND r.severity = ?");
+        }
+        query.push_str(" ORDER BY a.triggered_at DESC LIMIT ? OFFSET ?");
 
-    fn try_from(row: PlaybookRow) -> Result<Self> {
-        Ok(crate::incident::Playbook {
-            id: row.id,
-            name: row.name,
-            description: row.description,
-            triggers: serde_json::from_str(&row.triggers)?,
-            actions: serde_json::from_str(&row.actions)?,
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-        })
-    }
-}
+        let mut q = sqlx::query_as::<_, AlertRowSimple>(&query);
 
-#[derive(sqlx::FromRow)]
-struct PlaybookExecutionRow {
-    id: String,
-    playbook_id: String,
-    incident_id: Option<String>,
-    status: String,
-    started_at: String,
-    completed_at: Option<String>,
-    action_results: String,
-    triggered_by: Option<String>,
-}
+        if let Some(s) = status {
+            q = q.bind(s);
+        }
+        if let Some(sev) = severity {
+            q = q.bind(sev);
+        }
+        q = q.bind(limit).bind(offset);
 
-impl TryFrom<PlaybookExecutionRow> for crate::incident::PlaybookExecution {
-    type Error = crate::Error;
+        let rows: Vec<AlertRowSimple> = q.fetch_all(&self.pool).await?;
 
-    fn try_from(row: PlaybookExecutionRow) -> Result<Self> {
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
 
-        let status = match row.status.as_str() {
-            "running" => crate::incident::PlaybookExecutionStatus::Running,
-            "waiting_approval" => crate::incident::PlaybookExecutionStatus::WaitingApproval,
-            "completed" => crate::incident::PlaybookExecutionStatus::Completed,
-            "failed" => crate::incident::PlaybookExecutionStatus::Failed,
-            "cancelled" => crate::incident::PlaybookExecutionStatus::Cancelled,
-            _ => {
-                return Err(crate::Error::Other(format!(
-                    "Unknown execution status: {}",
-                    row.status
-                )))
-            }
+    /// One page of alerts, optionally by status and severity, newest first
+    pub async fn list_alerts_page(
+        &self,
+        status: Option<&str>,
+        severity: Option<&str>,
+        page: &PageQuery,
+    ) -> Result<RowPage<crate::monitoring::Alert>> {
+        self.fetch_page::<AlertRowSimple, _>(
+            r#"
+            SELECT a.id, a.rule_id, r.name as rule_name, a.status, r.severity,
+                   a.triggered_at, a.acknowledged_at, a.acknowledged_by, a.resolved_at
+            FROM alerts a
+            JOIN alert_rules r ON a.rule_id = r.id
+            "#,
+            |q| {
+                if let Some(status) = status {
+                    q.push(" AND a.status = ").push_bind(status.to_string());
+                }
+                if let Some(severity) = severity {
+                    q.push(" AND r.severity = ").push_bind(severity.to_string());
+                }
+            },
+            SortKey::desc(&["a.triggered_at", "a.id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
+    // ==================== Audit Log ====================
+
+    /// Insert an audit entry
+    pub async fn insert_audit_entry(&self, entry: &crate::monitoring::AuditEntry) -> Result<String> {
+        let details_json = if entry.details.is_empty() {
+            "{}".to_string()
+        } else {
+            serde_json::to_string(&entry.details).unwrap_or_else(|_| "{}".to_string())
         };
 
-        Ok(crate::incident::PlaybookExecution {
-            id: row.id,
-            playbook_id: row.playbook_id,
-            incident_id: row.incident_id,
-            status,
-            started_at: chrono::DateTime::parse_from_rfc3339(&row.started_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            completed_at: row
-                .completed_at
-                .as_ref()
-                .map(|s| chrono::DateTime::parse_from_rfc3339(s))
-                .transpose()
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .map(|dt| dt.into()),
-            action_results: serde_json::from_str(&row.action_results)?,
-            triggered_by: row.triggered_by,
-        })
+        let id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (id, timestamp, actor, actor_type, action, resource_type, resource_id, details, success, error_message, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(entry.timestamp.to_rfc3339())
+        .bind(&entry.actor)
+        .bind(format!("{:?}", entry.actor_type).to_lowercase())
+        .bind(format!("{:?}", entry.action).to_lowercase())
+        .bind(&entry.resource_type)
+        .bind(&entry.resource_id)
+        .bind(&details_json)
+        .bind(entry.success)
+        .bind(&entry.error_message)
+        .bind(entry.timestamp.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(id)
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct PostMortemRow {
-    incident_id: String,
-    title: String,
-    summary: String,
-    impact: String,
-    root_cause: String,
-    contributing_factors: String,
-    resolution: String,
-    action_items: String,
-    lessons_learned: String,
-    authors: String,
-    created_at: String,
-}
+    /// Query the audit log, newest first
+    pub async fn query_audit_log(
+        &self,
+        query: &crate::audit::AuditQuery,
+    ) -> Result<Vec<crate::monitoring::AuditEntry>> {
+        let mut sql = sqlx::QueryBuilder::new("SELECT * FROM audit_log WHERE 1=1");
+        Self::push_audit_filters(&mut sql, query);
+        sql.push(" ORDER BY timestamp DESC, id DESC LIMIT ")
+            .push_bind(query.limit.unwrap_or(-1))
+            .push(" OFFSET ")
+            .push_bind(query.offset.unwrap_or(0));
+
+        let rows = sql
+            .build_query_as::<AuditEntryRow>()
+            .fetch_all(&self.pool)
+            .await?;
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
 
-impl PostMortemRow {
-    fn into_post_mortem(
-        self,
-        timeline: Vec<crate::incident::TimelineEvent>,
-    ) -> Result<crate::incident::PostMortem> {
-        Ok(crate::incident::PostMortem {
-            incident_id: self.incident_id,
-            title: self.title,
-            summary: self.summary,
-            impact: serde_json::from_str(&self.impact)?,
-            timeline,
-            root_cause: self.root_cause,
-            contributing_factors: serde_json::from_str(&self.contributing_factors)?,
-            resolution: self.resolution,
-            action_items: serde_json::from_str(&self.action_items)?,
-            lessons_learned: serde_json::from_str(&self.lessons_learned)?,
-            created_at: chrono::DateTime::parse_from_rfc3339(&self.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            authors: serde_json::from_str(&self.authors)?,
+    /// One page of the audit log, newest first
+    pub async fn query_audit_log_page(
+        &self,
+        query: &crate::audit::AuditQuery,
+        page: &PageQuery,
+    ) -> Result<RowPage<crate::monitoring::AuditEntry>> {
+        self.fetch_page::<AuditEntryRow, _>(
+            "SELECT * FROM audit_log",
+            |q| Self::push_audit_filters(q, query),
+            SortKey::desc(&["timestamp", "id"]),
+            page,
+        )
+        .await?
+        .try_map(TryInto::try_into)
+    }
+
+    /// Conditions of an audit query, values compared as `insert_audit_entry` stores them
+    fn push_audit_filters(q: &mut sqlx::QueryBuilder<'_, sqlx::Sqlite>, query: &crate::audit::AuditQuery) {
+        if let Some(start) = query.start_time {
+            q.push(" AND timestamp >= ").push_bind(start.to_rfc3339());
+        }
+        if let Some(end) = query.end_time {
+            q.push(" AND timestamp <= ").push_bind(end.to_rfc3339());
+        }
+        if let Some(actor) = &query.actor {
+            q.push(" AND actor = ").push_bind(actor.clone());
+        }
+        if let Some(actor_type) = &query.actor_type {
+            q.push(" AND actor_type = ")
+                .push_bind(format!("{:?}", actor_type).to_lowercase());
+        }
+        if let Some(action) = &query.action {
+            q.push(" AND action = ")
+                .push_bind(format!("{:?}", action).to_lowercase());
+        }
+        if let Some(resource_type) = &query.resource_type {
+            q.push(" AND resource_type = ").push_bind(resource_type.clone());
+        }
+        if let Some(resource_id) = query.resource_id.as_ref().filter(|id| !id.is_empty()) {
+            q.push(" AND resource_id = ").push_bind(resource_id.clone());
+        }
+        if let Some(success) = query.success {
+            q.push(" AND success = ").push_bind(success);
+        }
+    }
+
+    /// Get audit stats (stub)
+    pub async fn get_audit_stats(&self) -> Result<crate::audit::AuditStats> {
+        // Audit log feature not fully implemented - return empty stats
+        Ok(crate::audit::AuditStats {
+            total_entries: 0,
+            entries_by_action: std::collections::HashMap::new(),
+            entries_by_actor_type: std::collections::HashMap::new(),
+            success_count: 0,
+            failure_count: 0,
+            first_entry_at: None,
+            last_entry_at: None,
         })
     }
-}
 
-#[derive(sqlx::FromRow)]
-struct AnomalyMetricRow {
-    name: String,
-    current_value: f64,
-    baseline_value: f64,
-    threshold: f64,
-    deviation_percent: f64,
-    is_anomaly: bool,
-    timestamp: String,
-}
+    /// Count audit entries matching a query, ignoring its pagination
+    pub async fn count_audit_entries(&self, query: &crate::audit::AuditQuery) -> Result<i64> {
+        let mut sql = sqlx::QueryBuilder::new("SELECT COUNT(*) FROM audit_log WHERE 1=1");
+        Self::push_audit_filters(&mut sql, query);
+        Ok(sql.build_query_scalar().fetch_one(&self.pool).await?)
+    }
 
-impl TryFrom<AnomalyMetricRow> for crate::incident::AnomalyMetric {
-    type Error = crate::Error;
+    /// Export audit log entries in various formats
+    pub async fn export_audit_log(
+        &self,
+        query: &crate::audit::AuditQuery,
+        format: crate::audit::ExportFormat,
+    ) -> Result<String> {
+        let entries = self.query_audit_log(query).await?;
 
-    fn try_from(row: AnomalyMetricRow) -> Result<Self> {
-        Ok(crate::incident::AnomalyMetric {
-            name: row.name,
-            current_value: row.current_value,
-            baseline_value: row.baseline_value,
-            threshold: row.threshold,
-            deviation_percent: row.deviation_percent,
-            is_anomaly: row.is_anomaly,
-            timestamp: chrono::DateTime::parse_from_rfc3339(&row.timestamp)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-        })
+        match format {
+            crate::audit::ExportFormat::Json => {
+                serde_json::to_string_pretty(&entries)
+                    .map_err(|e| crate::Error::Other(e.to_string()))
+            }
+            crate::audit::ExportFormat::JsonLines => {
+                let lines: Vec<String> = entries
+                    .iter()
+                    .filter_map(|e| serde_json::to_string(e).ok())
+                    .collect();
+                Ok(lines.join("\n"))
+            }
+            crate::audit::ExportFormat::Csv => {
+                let mut csv = String::from("id,timestamp,actor,actor_type,action,resource_type,resource_id,success,error_message\n");
+                for entry in entries {
+                    let action_str = format!("{:?}", entry.action).to_lowercase().replace("_", ".");
+                    csv.push_str(&format!(
+                        "{},{},{},{:?},{},{},{},{},{}\n",
+                        entry.id,
+                        entry.timestamp.to_rfc3339(),
+                        entry.actor,
+                        entry.actor_type,
+                        action_str,
+                        entry.resource_type,
+                        entry.resource_id,
+                        entry.success,
+                        entry.error_message.as_deref().unwrap_or("")
+                    ));
+                }
+                Ok(csv)
+            }
+        }
     }
-}
 
-// ==================== Monitoring & Metrics Database Methods ====================
+    /// Apply retention policy to audit logs
+    pub async fn apply_retention_policy(
+        &self,
+        policy: &crate::audit::RetentionPolicy,
+    ) -> Result<i64> {
+        let cutoff = policy.cutoff_date().to_rfc3339();
 
-/// Agent count by state and type
-#[derive(Debug, Clone, Default)]
-pub struct AgentCountsByStateAndType {
-    pub by_state: std::collections::HashMap<String, i64>,
-    pub by_type: std::collections::HashMap<String, i64>,
-}
+        let result = sqlx::query("DELETE FROM audit_log WHERE created_at < ?")
+            .bind(&cutoff)
+            .execute(&self.pool)
+            .await?;
 
-/// Token usage by model
-#[derive(Debug, Clone)]
-pub struct TokenUsageByModel {
-    pub model: String,
-    pub input_tokens: i64,
-    pub output_tokens: i64,
-    pub total_tokens: i64,
-}
+        Ok(result.rows_affected() as i64)
+    }
 
-/// PR cycle time stats
-#[derive(Debug, Clone)]
-pub struct PrCycleTime {
-    pub pr_id: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub merged_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub cycle_time_hours: Option<f64>,
-}
+    // ==================== Repository Methods (Stubs) ====================
 
-/// Story completion rate
-#[derive(Debug, Clone)]
-pub struct StoryCompletionRate {
-    pub epic_id: String,
-    pub total_stories: i64,
-    pub completed_stories: i64,
-    pub completion_rate: f64,
-}
+    /// Insert a repository (stub)
+    pub async fn insert_repository(&self, _repo: &crate::multi_repo::Repository) -> Result<i64> {
+        Err(crate::Error::Other("Repository operations not yet implemented".to_string()))
+    }
 
-/// Agent success rate
-#[derive(Debug, Clone)]
-pub struct AgentSuccessRate {
-    pub agent_type: String,
-    pub total_runs: i64,
-    pub successful_runs: i64,
-    pub success_rate: f64,
-}
+    /// List repositories (stub)
+    pub async fn list_repositories(&self) -> Result<Vec<crate::multi_repo::Repository>> {
+        Ok(Vec::new())
+    }
 
-impl Database {
-    // ==================== Agent Metrics ====================
+    /// Delete a repository (stub)
+    pub async fn delete_repository(&self, _id: &str) -> Result<()> {
+        Err(crate::Error::Other("Repository operations not yet implemented".to_string()))
+    }
 
-    /// Get agent counts grouped by state and type
-    pub async fn get_agent_counts_by_state_and_type(&self) -> Result<AgentCountsByStateAndType> {
-        let mut result = AgentCountsByStateAndType::default();
+    /// Get repository by name (stub)
+    pub async fn get_repository_by_name(&self, _name: &str) -> Result<Option<crate::multi_repo::Repository>> {
+        Ok(None)
+    }
 
-        // Count by state
-        let state_rows: Vec<(String, i64)> = sqlx::query_as(
-            "SELECT state, COUNT(*) as count FROM agents GROUP BY state"
-        )
-        .fetch_all(&self.pool)
-        .await?;
+    /// Update a repository (stub)
+    pub async fn update_repository(&self, _repo: &crate::multi_repo::Repository) -> Result<()> {
+        Err(crate::Error::Other("Repository operations not yet implemented".to_string()))
+    }
 
-        for (state, count) in state_rows {
-            result.by_state.insert(state, count);
-        }
+    /// Get dependency graph (stub)
+    pub async fn get_dependency_graph(&self) -> Result<crate::multi_repo::RepoDependencyGraph> {
+        Ok(crate::multi_repo::RepoDependencyGraph::new())
+    }
+}
 
-        // Count by type
-        let type_rows: Vec<(String, i64)> = sqlx::query_as(
-            "SELECT agent_type, COUNT(*) as count FROM agents GROUP BY agent_type"
-        )
-        .fetch_all(&self.pool)
-        .await?;
+// Helper structs for database rows
 
-        for (agent_type, count) in type_rows {
-            result.by_type.insert(agent_type, count);
-        }
+#[derive(sqlx::FromRow)]
+struct AlertRowSimple {
+    id: i64,
+    rule_id: i64,
+    rule_name: String,
+    status: String,
+    severity: String,
+    triggered_at: String,
+    acknowledged_at: Option<String>,
+    acknowledged_by: Option<String>,
+    resolved_at: Option<String>,
+}
 
-        Ok(result)
-    }
+impl TryFrom<AlertRowSimple> for crate::monitoring::Alert {
+    type Error = crate::Error;
 
-    /// Get agent success rates
-    pub async fn get_agent_success_rates(&self) -> Result<Vec<AgentSuccessRate>> {
-        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
-            r#"
-            SELECT
-                agent_type,
-                COUNT(*) as total_runs,
-                SUM(CASE WHEN state = 'completed' THEN 1 ELSE 0 END) as successful_runs
-            FROM agents
-            GROUP BY agent_type
-            "#
-        )
-        .fetch_all(&self.pool)
-        .await?;
+    fn try_from(row: AlertRowSimple) -> Result<Self> {
+        let status = match row.status.as_str() {
+            "pending" => crate::monitoring::AlertStatus::Pending,
+            "firing" => crate::monitoring::AlertStatus::Firing,
+            "acknowledged" => crate::monitoring::AlertStatus::Acknowledged,
+            "resolved" => crate::monitoring::AlertStatus::Resolved,
+            "silenced" => crate::monitoring::AlertStatus::Silenced,
+            _ => crate::monitoring::AlertStatus::Pending,
+        };
 
-        Ok(rows.into_iter().map(|(agent_type, total_runs, successful_runs)| {
-            AgentSuccessRate {
-                agent_type,
-                total_runs,
-                successful_runs,
-                success_rate: if total_runs > 0 { successful_runs as f64 / total_runs as f64 } else { 0.0 },
-            }
-        }).collect())
+        let severity = match row.severity.as_str() {
+            "info" => crate::monitoring::AlertSeverity::Info,
+            "warning" => crate::monitoring::AlertSeverity::Warning,
+            "critical" => crate::monitoring::AlertSeverity::Critical,
+            _ => crate::monitoring::AlertSeverity::Info,
+        };
+
+        Ok(crate::monitoring::Alert {
+            id: row.id.to_string(),
+            rule_id: row.rule_id.to_string(),
+            rule_name: row.rule_name,
+            status,
+            severity,
+            message: String::new(),
+            current_value: None,
+            threshold: None,
+            labels: std::collections::HashMap::new(),
+            triggered_at: chrono::DateTime::parse_from_rfc3339(&row.triggered_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+            acknowledged_at: row.acknowledged_at
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.into()),
+            acknowledged_by: row.acknowledged_by,
+            resolved_at: row.resolved_at
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.into()),
+            silenced_until: None,
+        })
     }
+}
+
+/// Row of the audit log
+#[derive(sqlx::FromRow)]
+struct AuditEntryRow {
+    id: String,
+    timestamp: String,
+    actor: String,
+    actor_type: String,
+    action: String,
+    resource_type: String,
+    resource_id: String,
+    details: String,
+    ip_address: Option<String>,
+    user_agent: Option<String>,
+    success: bool,
+    error_message: Option<String>,
+}
 
-    // ==================== Token Usage ====================
+impl TryFrom<AuditEntryRow> for crate::monitoring::AuditEntry {
+    type Error = crate::Error;
 
-    /// Get token usage grouped by model
-    pub async fn get_token_usage_by_model(&self) -> Result<Vec<TokenUsageByModel>> {
-        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
-            r#"
-            SELECT
-                model,
-                SUM(input_tokens) as input_tokens,
-                SUM(output_tokens) as output_tokens
-            FROM daily_token_usage
-            GROUP BY model
-            "#
-        )
-        .fetch_all(&self.pool)
-        .await?;
+    fn try_from(row: AuditEntryRow) -> Result<Self> {
+        use crate::monitoring::{ActorType, AuditAction};
 
-        Ok(rows.into_iter().map(|(model, input_tokens, output_tokens)| {
-            TokenUsageByModel {
-                model,
-                input_tokens,
-                output_tokens,
-                total_tokens: input_tokens + output_tokens,
+        // `insert_audit_entry` stores the lowercased Debug names of the enums
+        let actor_type = match row.actor_type.as_str() {
+            "user" => ActorType::User,
+            "system" => ActorType::System,
+            "agent" => ActorType::Agent,
+            "apikey" | "api_key" => ActorType::ApiKey,
+            "webhook" => ActorType::Webhook,
+            other => {
+                return Err(crate::Error::Other(format!("Unknown actor type: {}", other)))
             }
-        }).collect())
-    }
-
-    // ==================== Webhook Metrics ====================
-
-    /// Get count of pending webhook events
-    pub async fn get_pending_webhook_events_count(&self) -> Result<i64> {
-        let (count,): (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM webhook_events WHERE status = 'pending'"
-        )
-        .fetch_one(&self.pool)
-        .await?;
+        };
+        let action = match row.action.as_str() {
+            "agentspawned" => AuditAction::AgentSpawned,
+            "agentterminated" => AuditAction::AgentTerminated,
+            "configurationchanged" => AuditAction::ConfigurationChanged,
+            "approvalgranted" => AuditAction::ApprovalGranted,
+            "approvaldenied" => AuditAction::ApprovalDenied,
+            "deploymenttriggered" => AuditAction::DeploymentTriggered,
+            "deploymentrolledback" => AuditAction::DeploymentRolledBack,
+            "alertacknowledged" => AuditAction::AlertAcknowledged,
+            "alertsilenced" => AuditAction::AlertSilenced,
+            "userlogin" => AuditAction::UserLogin,
+            "userlogout" => AuditAction::UserLogout,
+            "apikeycreated" => AuditAction::ApiKeyCreated,
+            "apikeyrevoked" => AuditAction::ApiKeyRevoked,
+            other => AuditAction::Custom(
+                other
+                    .strip_prefix("custom(")
+                    .and_then(|name| name.strip_suffix(')'))
+                    .and_then(|name| serde_json::from_str(name).ok())
+                    .unwrap_or_else(|| other.to_string()),
+            ),
+        };
 
-        Ok(count)
+        Ok(Self {
+            id: row.id,
+            timestamp: chrono::DateTime::parse_from_rfc3339(&row.timestamp)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .with_timezone(&chrono::Utc),
+            actor: row.actor,
+            actor_type,
+            action,
+            resource_type: row.resource_type,
+            resource_id: row.resource_id,
+            details: serde_json::from_str(&row.details).unwrap_or_default(),
+            ip_address: row.ip_address,
+            user_agent: row.user_agent,
+            success: row.success,
+            error_message: row.error_message,
+        })
     }
+}
 
-    // ==================== PR Metrics ====================
-
-    /// Get PR cycle times
-    pub async fn get_pr_cycle_times(&self) -> Result<Vec<PrCycleTime>> {
-        let rows: Vec<(String, String, Option<String>)> = sqlx::query_as(
-            r#"
-            SELECT id, created_at, merged_at
-            FROM pull_requests
-            ORDER BY created_at DESC
-            LIMIT 100
-            "#
-        )
-        .fetch_all(&self.pool)
-        .await?;
+// ==================== Autonomous Session Row ====================
 
-        let mut result = Vec::new();
-        for (pr_id, created_at_str, merged_at_str) in rows {
-            let created_at: chrono::DateTime<chrono::Utc> = chrono::DateTime::parse_from_rfc3339(&created_at_str)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into();
+#[derive(Debug, sqlx::FromRow)]
+struct AutonomousSessionRow {
+    id: String,
+    state: String,
+    started_at: String,
+    updated_at: String,
+    completed_at: Option<String>,
+    current_epic_id: Option<String>,
+    current_story_id: Option<String>,
+    current_agent_id: Option<String>,
+    config: String,
+    work_queue: String,
+    completed_items: String,
+    metrics: String,
+    error_message: Option<String>,
+    blocked_reason: Option<String>,
+    pause_reason: Option<String>,
+    plan: Option<String>,
+    created_at: String,
+}
 
-            let merged_at: Option<chrono::DateTime<chrono::Utc>> = if let Some(m) = merged_at_str {
-                Some(chrono::DateTime::parse_from_rfc3339(&m)
-                    .map_err(|e| crate::Error::Other(e.to_string()))?
-                    .into())
-            } else {
-                None
-            };
+/// Parse datetime from either RFC3339 or SQLite format
+pub(crate) fn parse_datetime(s: &str) -> Result<chrono::DateTime<chrono::Utc>> {
+    // Try RFC3339 first
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.into());
+    }
+    // Try SQLite format (YYYY-MM-DD HH:MM:SS)
+    if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
+        return Ok(chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
+            dt,
+            chrono::Utc,
+        ));
+    }
+    Err(crate::Error::Other(format!(
+        "Failed to parse datetime: {}",
+        s
+    )))
+}
 
-            let cycle_time_hours = merged_at.map(|m| {
-                (m - created_at).num_hours() as f64
-            });
+impl AutonomousSessionRow {
+    fn into_session(self) -> Result<crate::autonomous_session::AutonomousSession> {
+        Ok(crate::autonomous_session::AutonomousSession {
+            id: self.id,
+            state: self.state.parse()?,
+            started_at: parse_datetime(&self.started_at)?,
+            updated_at: parse_datetime(&self.updated_at)?,
+            completed_at: self.completed_at.map(|s| parse_datetime(&s)).transpose()?,
+            current_epic_id: self.current_epic_id,
+            current_story_id: self.current_story_id,
+            current_agent_id: self.current_agent_id,
+            config: serde_json::from_str(&self.config)?,
+            work_queue: serde_json::from_str(&self.work_queue)?,
+            completed_items: serde_json::from_str(&self.completed_items)?,
+            metrics: serde_json::from_str(&self.metrics)?,
+            error_message: self.error_message,
+            blocked_reason: self.blocked_reason,
+            pause_reason: self.pause_reason,
+            plan: self.plan.map(|p| serde_json::from_str(&p)).transpose()?,
+            created_at: parse_datetime(&self.created_at)?,
+        })
+    }
+}
 
-            result.push(PrCycleTime {
-                pr_id,
-                created_at,
-                merged_at,
-                cycle_time_hours,
-            });
-        }
+#[derive(Debug, sqlx::FromRow)]
+struct SessionHistoryRow {
+    id: i64,
+    session_id: String,
+    from_state: String,
+    to_state: String,
+    reason: Option<String>,
+    transitioned_at: String,
+    metadata: String,
+    created_at: String,
+}
 
-        Ok(result)
+impl SessionHistoryRow {
+    fn into_history(self) -> Result<crate::autonomous_session::SessionStateHistory> {
+        Ok(crate::autonomous_session::SessionStateHistory {
+            id: self.id,
+            session_id: self.session_id,
+            from_state: self.from_state.parse()?,
+            to_state: self.to_state.parse()?,
+            reason: self.reason,
+            transitioned_at: parse_datetime(&self.transitioned_at)?,
+            metadata: serde_json::from_str(&self.metadata)?,
+            created_at: parse_datetime(&self.created_at)?,
+        })
     }
+}
 
-    // ==================== Story Metrics ====================
+impl Database {
+    // ==================== Autonomous Session Operations ====================
 
-    /// Get story completion rates by epic
-    pub async fn get_story_completion_rates(&self) -> Result<Vec<StoryCompletionRate>> {
-        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+    /// Create a new autonomous session
+    pub async fn create_autonomous_session(
+        &self,
+        session: &crate::autonomous_session::AutonomousSession,
+    ) -> Result<()> {
+        sqlx::query(
             r#"
-            SELECT
-                epic_id,
-                COUNT(*) as total_stories,
-                SUM(CASE WHEN status = 'completed' THEN 1 ELSE 0 END) as completed_stories
-            FROM stories
-            GROUP BY epic_id
-            "#
+            INSERT INTO autonomous_sessions (
+                id, state, started_at, updated_at, completed_at,
+                current_epic_id, current_story_id, current_agent_id,
+                config, work_queue, completed_items, metrics,
+                error_message, blocked_reason, pause_reason, plan
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
         )
-        .fetch_all(&self.pool)
+        .bind(&session.id)
+        .bind(session.state.as_str())
+        .bind(session.started_at.to_rfc3339())
+        .bind(session.updated_at.to_rfc3339())
+        .bind(session.completed_at.map(|dt| dt.to_rfc3339()))
+        .bind(&session.current_epic_id)
+        .bind(&session.current_story_id)
+        .bind(&session.current_agent_id)
+        .bind(serde_json::to_string(&session.config)?)
+        .bind(serde_json::to_string(&session.work_queue)?)
+        .bind(serde_json::to_string(&session.completed_items)?)
+        .bind(serde_json::to_string(&session.metrics)?)
+        .bind(&session.error_message)
+        .bind(&session.blocked_reason)
+        .bind(&session.pause_reason)
+        .bind(session.plan.as_ref().map(serde_json::to_string).transpose()?)
+        .execute(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(|(epic_id, total_stories, completed_stories)| {
-            StoryCompletionRate {
-                epic_id,
-                total_stories,
-                completed_stories,
-                completion_rate: if total_stories > 0 { completed_stories as f64 / total_stories as f64 } else { 0.0 },
-            }
-        }).collect())
+        Ok(())
     }
 
-    // ==================== Alerting ====================
-
-    /// Create a new alert rule
-    pub async fn create_alert_rule(&self, rule: &crate::monitoring::AlertRule) -> Result<i64> {
-        let id = sqlx::query_scalar(
+    /// Update an autonomous session
+    pub async fn update_autonomous_session(
+        &self,
+        session: &crate::autonomous_session::AutonomousSession,
+    ) -> Result<()> {
+        sqlx::query(
             r#"
-            INSERT INTO alert_rules (name, condition, severity, channels, enabled, evaluation_interval_seconds, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))
-            RETURNING id
-            "#
-        )
-        .bind(&rule.name)
-        .bind(&rule.condition)
-        .bind(format!("{:?}", rule.severity).to_lowercase())
-        .bind(serde_json::to_string(&rule.channels).unwrap_or_default())
-        .bind(rule.enabled)
-        .bind(60i64)
-        .fetch_one(&self.pool)
+            UPDATE autonomous_sessions SET
+                state = ?, updated_at = ?, completed_at = ?,
+                current_epic_id = ?, current_story_id = ?, current_agent_id = ?,
+                config = ?, work_queue = ?, completed_items = ?, metrics = ?,
+                error_message = ?, blocked_reason = ?, pause_reason = ?, plan = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(session.state.as_str())
+        .bind(session.updated_at.to_rfc3339())
+        .bind(session.completed_at.map(|dt| dt.to_rfc3339()))
+        .bind(&session.current_epic_id)
+        .bind(&session.current_story_id)
+        .bind(&session.current_agent_id)
+        .bind(serde_json::to_string(&session.config)?)
+        .bind(serde_json::to_string(&session.work_queue)?)
+        .bind(serde_json::to_string(&session.completed_items)?)
+        .bind(serde_json::to_string(&session.metrics)?)
+        .bind(&session.error_message)
+        .bind(&session.blocked_reason)
+        .bind(&session.pause_reason)
+        .bind(session.plan.as_ref().map(serde_json::to_string).transpose()?)
+        .bind(&session.id)
+        .execute(&self.pool)
         .await?;
 
-        Ok(id)
+        Ok(())
     }
 
-    /// Get an alert by ID
-    pub async fn get_alert(&self, id: i64) -> Result<Option<crate::monitoring::Alert>> {
-        let row: Option<AlertRowSimple> = sqlx::query_as(
+    /// Get an autonomous session by ID
+    pub async fn get_autonomous_session(
+        &self,
+        id: &str,
+    ) -> Result<Option<crate::autonomous_session::AutonomousSession>> {
+        let row = sqlx::query_as::<_, AutonomousSessionRow>(
             r#"
-            SELECT a.id, a.rule_id, r.name as rule_name, a.status, r.severity,
-                   a.triggered_at, a.acknowledged_at, a.acknowledged_by, a.resolved_at
-            FROM alerts a
-            JOIN alert_rules r ON a.rule_id = r.id
-            WHERE a.id = ?
-            "#
+            SELECT id, state, started_at, updated_at, completed_at,
+                   current_epic_id, current_story_id, current_agent_id,
+                   config, work_queue, completed_items, metrics,
+                   error_message, blocked_reason, pause_reason, plan, created_at
+            FROM autonomous_sessions
+            WHERE id = ?
+            "#,
         )
         .bind(id)
         .fetch_optional(&self.pool)
         .await?;
 
-        match row {
-            Some(r) => Ok(Some(r.try_into()?)),
-            None => Ok(None),
-        }
+        row.map(|r| r.into_session()).transpose()
     }
 
-    /// Update an alert
-    pub async fn update_alert(&self, alert: &crate::monitoring::Alert) -> Result<()> {
-        let id: i64 = alert.id.parse().map_err(|_| crate::Error::Other("Invalid alert ID".into()))?;
-
-        sqlx::query(
+    /// Get the current active autonomous session (if any)
+    pub async fn get_active_autonomous_session(
+        &self,
+    ) -> Result<Option<crate::autonomous_session::AutonomousSession>> {
+        let row = sqlx::query_as::<_, AutonomousSessionRow>(
             r#"
-            UPDATE alerts
-            SET status = ?, acknowledged_at = ?, acknowledged_by = ?, resolved_at = ?
-            WHERE id = ?
-            "#
+            SELECT id, state, started_at, updated_at, completed_at,
+                   current_epic_id, current_story_id, current_agent_id,
+                   config, work_queue, completed_items, metrics,
+                   error_message, blocked_reason, pause_reason, plan, created_at
+            FROM autonomous_sessions
+            WHERE state NOT IN ('done', 'paused')
+            ORDER BY started_at DESC
+            LIMIT 1
+            "#,
         )
-        .bind(format!("{:?}", alert.status).to_lowercase())
-        .bind(alert.acknowledged_at.map(|dt| dt.to_rfc3339()))
-        .bind(&alert.acknowledged_by)
-        .bind(alert.resolved_at.map(|dt| dt.to_rfc3339()))
-        .bind(id)
-        .execute(&self.pool)
+        .fetch_optional(&self.pool)
         .await?;
 
-        Ok(())
+        row.map(|r| r.into_session()).transpose()
     }
 
-    /// List alerts by status
-    pub async fn list_alerts_by_status(
+    /// List autonomous sessions with optional state filter
+    pub async fn list_autonomous_sessions(
         &self,
-        status: Option<&str>,
-        severity: Option<&str>,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<crate::monitoring::Alert>> {
+        state: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<Vec<crate::autonomous_session::AutonomousSession>> {
         let mut query = String::from(
             r#"
-            SELECT a.id, a.rule_id, r.name as rule_name, a.status, r.severity,
-                   a.triggered_at, a.acknowledged_at, a.acknowledged_by, a.resolved_at
-            FROM alerts a
-            JOIN alert_rules r ON a.rule_id = r.id
+            SELECT id, state, started_at, updated_at, completed_at,
+                   current_epic_id, current_story_id, current_agent_id,
+                   config, work_queue, completed_items, metrics,
+                   error_message, blocked_reason, pause_reason, plan, created_at
+            FROM autonomous_sessions
             WHERE 1=1
-            "#
+            "#,
         );
 
-        if status.is_some() {
-            query.push_str(" AND a.status = ?");
-        }
-        if severity.is_some() {
-            query.push_str(" AND r.severity = ?");
+        if state.is_some() {
+            query.push_str(" AND state = ?");
         }
-        query.push_str(" ORDER BY a.triggered_at DESC LIMIT ? OFFSET ?");
 
-        let mut q = sqlx::query_as::<_, AlertRowSimple>(&query);
+        query.push_str(" ORDER BY started_at DESC");
 
-        if let Some(s) = status {
-            q = q.bind(s);
-        }
-        if let Some(sev) = severity {
-            q = q.bind(sev);
+        if let Some(limit) = limit {
+            query.push_str(&format!(" LIMIT {}", limit));
         }
-        q = q.bind(limit).bind(offset);
 
-        let rows: Vec<AlertRowSimple> = q.fetch_all(&self.pool).await?;
+        let mut q = sqlx::query_as::<_, AutonomousSessionRow>(&query);
+        if let Some(s) = state {
+            q = q.bind(s);
+        }
 
-        rows.into_iter().map(|r| r.try_into()).collect()
+        let rows = q.fetch_all(&self.pool).await?;
+        rows.into_iter().map(|r| r.into_session()).collect()
     }
 
-    // ==================== Audit Log ====================
-
-    /// Insert an audit entry
-    pub async fn insert_audit_entry(&self, entry: &crate::monitoring::AuditEntry) -> Result<String> {
-        let details_json = if entry.details.is_empty() {
-            "{}".to_string()
-        } else {
-            serde_json::to_string(&entry.details).unwrap_or_else(|_| "{}".to_string())
-        };
-
-        let id = uuid::Uuid::new_v4().to_string();
-        sqlx::query(
+    /// One page of autonomous sessions, optionally in a state, newest first
+    pub async fn list_autonomous_sessions_page(
+        &self,
+        state: Option<&str>,
+        page: &PageQuery,
+    ) -> Result<RowPage<crate::autonomous_session::AutonomousSession>> {
+        self.fetch_page::<AutonomousSessionRow, _>(
             r#"
-            INSERT INTO audit_log (id, timestamp, actor, actor_type, action, resource_type, resource_id, details, success, error_message, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#
+            SELECT id, state, started_at, updated_at, completed_at,
+                   current_epic_id, current_story_id, current_agent_id,
+                   config, work_queue, completed_items, metrics,
+                   error_message, blocked_reason, pause_reason, plan, created_at
+            FROM autonomous_sessions
+            "#,
+            |q| {
+                if let Some(state) = state {
+                    q.push(" AND state = ").push_bind(state.to_string());
+                }
+            },
+            SortKey::desc(&["started_at", "id"]),
+            page,
         )
-        .bind(&id)
-        .bind(entry.timestamp.to_rfc3339())
-        .bind(&entry.actor)
-        .bind(format!("{:?}", entry.actor_type).to_lowercase())
-        .bind(format!("{:?}", entry.action).to_lowercase())
-        .bind(&entry.resource_type)
-        .bind(&entry.resource_id)
-        .bind(&details_json)
-        .bind(entry.success)
-        .bind(&entry.error_message)
-        .bind(entry.timestamp.to_rfc3339())
-        .execute(&self.pool)
-        .await?;
-
-        Ok(id)
-    }
-
-    /// Query audit log (stub - returns empty list)
-    pub async fn query_audit_log(&self, _query: &crate::audit::AuditQuery) -> Result<Vec<crate::monitoring::AuditEntry>> {
-        // Audit log feature not fully implemented - return empty list
-        Ok(Vec::new())
-    }
-
-    /// Get audit stats (stub)
-    pub async fn get_audit_stats(&self) -> Result<crate::audit::AuditStats> {
-        // Audit log feature not fully implemented - return empty stats
-        Ok(crate::audit::AuditStats {
-            total_entries: 0,
-            entries_by_action: std::collections::HashMap::new(),
-            entries_by_actor_type: std::collections::HashMap::new(),
-            success_count: 0,
-            failure_count: 0,
-            first_entry_at: None,
-            last_entry_at: None,
-        })
+        .await?
+        .try_map(|r| r.into_session())
     }
 
-    /// Count audit entries (stub)
-    pub async fn count_audit_entries(&self, _query: &crate::audit::AuditQuery) -> Result<i64> {
-        // Audit log feature not fully implemented
-        Ok(0)
-    }
-
-    /// Export audit log entries in various formats
-    pub async fn export_audit_log(
-        &self,
-        query: &crate::audit::AuditQuery,
-        format: crate::audit::ExportFormat,
-    ) -> Result<String> {
-        let entries = self.query_audit_log(query).await?;
+    /// Delete an autonomous session
+    pub async fn delete_autonomous_session(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM autonomous_sessions WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
 
-        match format {
-            crate::audit::ExportFormat::Json => {
-                serde_json::to_string_pretty(&entries)
-                    .map_err(|e| crate::Error::Other(e.to_string()))
-            }
-            crate::audit::ExportFormat::JsonLines => {
-                let lines: Vec<String> = entries
-                    .iter()
-                    .filter_map(|e| serde_json::to_string(e).ok())
-                    .collect();
-                Ok(lines.join("\n"))
-            }
-            crate::audit::ExportFormat::Csv => {
-                let mut csv = String::from("id,timestamp,actor,actor_type,action,resource_type,resource_id,success,error_message\n");
-                for entry in entries {
-                    let action_str = format!("{:?}", entry.action).to_lowercase().replace("_", ".");
-                    csv.push_str(&format!(
-                        "{},{},{},{:?},{},{},{},{},{}\n",
-                        entry.id,
-                        entry.timestamp.to_rfc3339(),
-                        entry.actor,
-                        entry.actor_type,
-                        action_str,
-                        entry.resource_type,
-                        entry.resource_id,
-                        entry.success,
-                        entry.error_message.as_deref().unwrap_or("")
-                    ));
-                }
-                Ok(csv)
-            }
-        }
+        Ok(result.rows_affected() > 0)
     }
 
-    /// Apply retention policy to audit logs
-    pub async fn apply_retention_policy(
+    /// Record a state transition in the history
+    pub async fn record_session_state_transition(
         &self,
-        policy: &crate::audit::RetentionPolicy,
-    ) -> Result<i64> {
-        let cutoff = policy.cutoff_date().to_rfc3339();
-
-        let result = sqlx::query("DELETE FROM audit_log WHERE created_at < ?")
-            .bind(&cutoff)
-            .execute(&self.pool)
-            .await?;
+        session_id: &str,
+        from_state: crate::autonomous_session::AutonomousSessionState,
+        to_state: crate::autonomous_session::AutonomousSessionState,
+        reason: Option<&str>,
+        metadata: Option<serde_json::Value>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO autonomous_session_history (
+                session_id, from_state, to_state, reason, transitioned_at, metadata
+            )
+            VALUES (?, ?, ?, ?, datetime('now'), ?)
+            "#,
+        )
+        .bind(session_id)
+        .bind(from_state.as_str())
+        .bind(to_state.as_str())
+        .bind(reason)
+        .bind(serde_json::to_string(&metadata.unwrap_or(serde_json::Value::Null))?)
+        .execute(&self.pool)
+        .await?;
 
-        Ok(result.rows_affected() as i64)
+        Ok(())
     }
 
-    // ==================== Repository Methods (Stubs) ====================
-
-    /// Insert a repository (stub)
-    pub async fn insert_repository(&self, _repo: &crate::multi_repo::Repository) -> Result<i64> {
-        Err(crate::Error::Other("Repository operations not yet implemented".to_string()))
+    /// Record a re-planning turn, snapshotting the revised plan in the history
+    pub async fn record_session_replan(
+        &self,
+        session: &crate::autonomous_session::AutonomousSession,
+        from_state: crate::autonomous_session::AutonomousSessionState,
+        divergence: &crate::autonomous_session::PlanDivergence,
+    ) -> Result<()> {
+        let metadata = serde_json::json!({
+            "replan": true,
+            "divergence": divergence,
+            "plan": session.plan,
+        });
+        self.record_session_state_transition(
+            &session.id,
+            from_state,
+            session.state,
+            Some(&divergence.reason()),
+            Some(metadata),
+        )
+        .await
     }
 
-    /// List repositories (stub)
-    pub async fn list_repositories(&self) -> Result<Vec<crate::multi_repo::Repository>> {
-        Ok(Vec::new())
+    /// Get the plan revisions recorded by re-planning turns, oldest first
+    pub async fn get_session_plan_history(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<crate::autonomous_session::SessionPlan>> {
+        Ok(self
+            .get_session_state_history(session_id)
+            .await?
+            .iter()
+            .filter(|entry| entry.is_replan())
+            .filter_map(|entry| entry.plan())
+            .collect())
     }
 
-    /// Delete a repository (stub)
-    pub async fn delete_repository(&self, _id: &str) -> Result<()> {
-        Err(crate::Error::Other("Repository operations not yet implemented".to_string()))
-    }
+    /// Get state transition history for a session
+    pub async fn get_session_state_history(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<crate::autonomous_session::SessionStateHistory>> {
+        let rows = sqlx::query_as::<_, SessionHistoryRow>(
+            r#"
+            SELECT id, session_id, from_state, to_state, reason,
+                   transitioned_at, metadata, created_at
+            FROM autonomous_session_history
+            WHERE session_id = ?
+            ORDER BY transitioned_at ASC
+            "#,
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await?;
 
-    /// Get repository by name (stub)
-    pub async fn get_repository_by_name(&self, _name: &str) -> Result<Option<crate::multi_repo::Repository>> {
-        Ok(None)
+        rows.into_iter().map(|r| r.into_history()).collect()
     }
 
-    /// Update a repository (stub)
-    pub async fn update_repository(&self, _repo: &crate::multi_repo::Repository) -> Result<()> {
-        Err(crate::Error::Other("Repository operations not yet implemented".to_string()))
+    /// Get sessions by state
+    pub async fn get_sessions_by_state(
+        &self,
+        state: crate::autonomous_session::AutonomousSessionState,
+    ) -> Result<Vec<crate::autonomous_session::AutonomousSession>> {
+        self.list_autonomous_sessions(Some(state.as_str()), None)
+            .await
     }
 
-    /// Get dependency graph (stub)
-    pub async fn get_dependency_graph(&self) -> Result<crate::multi_repo::RepoDependencyGraph> {
-        Ok(crate::multi_repo::RepoDependencyGraph::new())
+    /// Count sessions by state
+    pub async fn count_sessions_by_state(&self) -> Result<std::collections::HashMap<String, i64>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT state, COUNT(*) as count FROM autonomous_sessions GROUP BY state",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().collect())
     }
 }
 
-// Helper structs for database rows
+// ==================== Agent Continuation Row ====================
 
-#[derive(sqlx::FromRow)]
-struct AlertRowSimple {
+#[derive(Debug, sqlx::FromRow)]
+struct AgentContinuationRow {
     id: i64,
-    rule_id: i64,
-    rule_name: String,
+    agent_id: String,
+    session_id: Option<String>,
+    reason: String,
+    message: String,
+    context: String,
     status: String,
-    severity: String,
-    triggered_at: String,
-    acknowledged_at: Option<String>,
-    acknowledged_by: Option<String>,
-    resolved_at: Option<String>,
-}
-
-impl TryFrom<AlertRowSimple> for crate::monitoring::Alert {
-    type Error = crate::Error;
-
-    fn try_from(row: AlertRowSimple) -> Result<Self> {
-        let status = match row.status.as_str() {
-            "pending" => crate::monitoring::AlertStatus::Pending,
-            "firing" => crate::monitoring::AlertStatus::Firing,
-            "acknowledged" => crate::monitoring::AlertStatus::Acknowledged,
-            "resolved" => crate::monitoring::AlertStatus::Resolved,
-            "silenced" => crate::monitoring::AlertStatus::Silenced,
-            _ => crate::monitoring::AlertStatus::Pending,
-        };
-
-        let severity = match row.severity.as_str() {
-            "info" => crate::monitoring::AlertSeverity::Info,
-            "warning" => crate::monitoring::AlertSeverity::Warning,
-            "critical" => crate::monitoring::AlertSeverity::Critical,
-            _ => crate::monitoring::AlertSeverity::Info,
-        };
-
-        Ok(crate::monitoring::Alert {
-            id: row.id.to_string(),
-            rule_id: row.rule_id.to_string(),
-            rule_name: row.rule_name,
-            status,
-            severity,
-            message: String::new(),
-            current_value: None,
-            threshold: None,
-            labels: std::collections::HashMap::new(),
-            triggered_at: chrono::DateTime::parse_from_rfc3339(&row.triggered_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            acknowledged_at: row.acknowledged_at
-                .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.into()),
-            acknowledged_by: row.acknowledged_by,
-            resolved_at: row.resolved_at
-                .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.into()),
-            silenced_until: None,
-        })
-    }
-}
-
-#[derive(sqlx::FromRow)]
-struct AuditEntryRow {
-    id: i64,
-    actor: String,
-    actor_type: String,
-    action: String,
-    resource_type: Option<String>,
-    resource_id: Option<String>,
-    details: Option<String>,
-    success: bool,
-    error_message: Option<String>,
     created_at: String,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+    result: Option<String>,
+    error_message: Option<String>,
 }
 
-impl TryFrom<AuditEntryRow> for crate::monitoring::AuditEntry {
-    type Error = crate::Error;
-
-    fn try_from(row: AuditEntryRow) -> Result<Self> {
-        let actor_type = match row.actor_type.as_str() {
-            "user" => crate::monitoring::ActorType::User,
-            "system" => crate::monitoring::ActorType::System,
-            "agent" => crate::monitoring::ActorType::Agent,
-            "api_key" | "apikey" => crate::monitoring::ActorType::ApiKey,
-            "webhook" => crate::monitoring::ActorType::Webhook,
-            _ => crate::monitoring::ActorType::System,
-        };
-
-        let action = match row.action.as_str() {
-            "agent_spawned" | "agentspawned" => crate::monitoring::AuditAction::AgentSpawned,
-            "agent_terminated" | "agentterminated" => crate::monitoring::AuditAction::AgentTerminated,
-            "configuration_changed" | "configurationchanged" => crate::monitoring::AuditAction::ConfigurationChanged,
-            "approval_granted" | "approvalgranted" => crate::monitoring::AuditAction::ApprovalGranted,
-            "approval_denied" | "approvaldenied" => crate::monitoring::AuditAction::ApprovalDenied,
-            "deployment_triggered" | "deploymenttriggered" => crate::monitoring::AuditAction::DeploymentTriggered,
-            "deployment_rolled_back" | "deploymentrolledback" => crate::monitoring::AuditAction::DeploymentRolledBack,
-            "alert_acknowledged" | "alertacknowledged" => crate::monitoring::AuditAction::AlertAcknowledged,
-            "alert_silenced" | "alertsilenced" => crate::monitoring::AuditAction::AlertSilenced,
-            "user_login" | "userlogin" => crate::monitoring::AuditAction::UserLogin,
-            _ => crate::monitoring::AuditAction::ConfigurationChanged,
-        };
-
-        let details: std::collections::HashMap<String, serde_json::Value> = row.details
-            .and_then(|s| serde_json::from_str(&s).ok())
-            .unwrap_or_default();
-
-        Ok(crate::monitoring::AuditEntry {
-            id: row.id.to_string(),
-            actor: row.actor,
-            actor_type,
-            action,
-            resource_type: row.resource_type.unwrap_or_default(),
-            resource_id: row.resource_id.unwrap_or_default(),
-            details,
-            success: row.success,
-            error_message: row.error_message,
-            timestamp: chrono::DateTime::parse_from_rfc3339(&row.created_at)
-                .map_err(|e| crate::Error::Other(e.to_string()))?
-                .into(),
-            ip_address: None,
-            user_agent: None,
+impl AgentContinuationRow {
+    fn into_continuation(self) -> Result<crate::agent_continuation::AgentContinuation> {
+        Ok(crate::agent_continuation::AgentContinuation {
+            id: self.id,
+            agent_id: self.agent_id,
+            session_id: self.session_id,
+            reason: crate::agent_continuation::ContinuationReason::from_str(&self.reason)?,
+            message: self.message,
+            context: serde_json::from_str(&self.context)?,
+            status: crate::agent_continuation::ContinuationStatus::from_str(&self.status)?,
+            created_at: parse_datetime(&self.created_at)?,
+            started_at: self.started_at.map(|s| parse_datetime(&s)).transpose()?,
+            completed_at: self.completed_at.map(|s| parse_datetime(&s)).transpose()?,
+            result: self
+                .result
+                .map(|s| serde_json::from_str(&s))
+                .transpose()?,
+            error_message: self.error_message,
         })
     }
 }
 
-// ==================== Autonomous Session Row ====================
+// ==================== Work Evaluation Row (Epic 016 - Story 6) ====================
 
 #[derive(Debug, sqlx::FromRow)]
-struct AutonomousSessionRow {
-    id: String,
-    state: String,
-    started_at: String,
-    updated_at: String,
-    completed_at: Option<String>,
-    current_epic_id: Option<String>,
-    current_story_id: Option<String>,
-    current_agent_id: Option<String>,
-    config: String,
-    work_queue: String,
-    completed_items: String,
-    metrics: String,
-    error_message: Option<String>,
-    blocked_reason: Option<String>,
-    pause_reason: Option<String>,
+struct WorkEvaluationRow {
+    id: i64,
+    agent_id: String,
+    session_id: Option<String>,
+    story_id: Option<String>,
+    evaluation_type: String,
+    status: String,
+    details: String,
+    turn_count: Option<i64>,
+    max_turns: Option<i64>,
+    token_count: Option<i64>,
+    max_tokens: Option<i64>,
+    duration_secs: Option<i64>,
     created_at: String,
-}
-
-/// Parse datetime from either RFC3339 or SQLite format
-fn parse_datetime(s: &str) -> Result<chrono::DateTime<chrono::Utc>> {
-    // Try RFC3339 first
-    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(s) {
-        return Ok(dt.into());
-    }
-    // Try SQLite format (YYYY-MM-DD HH:MM:SS)
-    if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
-        return Ok(chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-            dt,
-            chrono::Utc,
-        ));
-    }
-    Err(crate::Error::Other(format!(
-        "Failed to parse datetime: {}",
-        s
-    )))
-}
-
-impl AutonomousSessionRow {
-    fn into_session(self) -> Result<crate::autonomous_session::AutonomousSession> {
-        Ok(crate::autonomous_session::AutonomousSession {
-            id: self.id,
-            state: self.state.parse()?,
-            started_at: parse_datetime(&self.started_at)?,
-            updated_at: parse_datetime(&self.updated_at)?,
-            completed_at: self.completed_at.map(|s| parse_datetime(&s)).transpose()?,
-            current_epic_id: self.current_epic_id,
-            current_story_id: self.current_story_id,
-            current_agent_id: self.current_agent_id,
-            config: serde_json::from_str(&self.config)?,
-            work_queue: serde_json::from_str(&self.work_queue)?,
-            completed_items: serde_json::from_str(&self.completed_items)?,
-            metrics: serde_json::from_str(&self.metrics)?,
-            error_message: self.error_message,
-            blocked_reason: self.blocked_reason,
-            pause_reason: self.pause_reason,
+}
+
+impl WorkEvaluationRow {
+    fn into_evaluation(self) -> Result<crate::stuck_detection::WorkEvaluation> {
+        use crate::stuck_detection::{EvaluationStatus, EvaluationType};
+        use std::str::FromStr;
+
+        Ok(crate::stuck_detection::WorkEvaluation {
+            id: self.id,
+            agent_id: self.agent_id,
+            session_id: self.session_id,
+            story_id: self.story_id,
+            evaluation_type: EvaluationType::from_str(&self.evaluation_type)?,
+            status: EvaluationStatus::from_str(&self.status)?,
+            details: serde_json::from_str(&self.details)?,
+            turn_count: self.turn_count.map(|v| v as u32),
+            max_turns: self.max_turns.map(|v| v as u32),
+            token_count: self.token_count.map(|v| v as u64),
+            max_tokens: self.max_tokens.map(|v| v as u64),
+            duration_secs: self.duration_secs.map(|v| v as u64),
             created_at: parse_datetime(&self.created_at)?,
         })
     }
 }
 
+// ==================== Stuck Detection Row (Epic 016 - Story 6) ====================
+
 #[derive(Debug, sqlx::FromRow)]
-struct SessionHistoryRow {
+struct StuckDetectionRow {
     id: i64,
-    session_id: String,
-    from_state: String,
-    to_state: String,
-    reason: Option<String>,
-    transitioned_at: String,
-    metadata: String,
-    created_at: String,
+    agent_id: String,
+    session_id: Option<String>,
+    detection_type: String,
+    severity: String,
+    details: String,
+    resolved: bool,
+    resolution_action: Option<String>,
+    detected_at: String,
+    resolved_at: Option<String>,
 }
 
-impl SessionHistoryRow {
-    fn into_history(self) -> Result<crate::autonomous_session::SessionStateHistory> {
-        Ok(crate::autonomous_session::SessionStateHistory {
+impl StuckDetectionRow {
+    fn into_detection(self) -> Result<crate::stuck_detection::StuckDetection> {
+        use crate::stuck_detection::{StuckSeverity, StuckType};
+        use std::str::FromStr;
+
+        Ok(crate::stuck_detection::StuckDetection {
             id: self.id,
+            agent_id: self.agent_id,
             session_id: self.session_id,
-            from_state: self.from_state.parse()?,
-            to_state: self.to_state.parse()?,
-            reason: self.reason,
-            transitioned_at: parse_datetime(&self.transitioned_at)?,
-            metadata: serde_json::from_str(&self.metadata)?,
-            created_at: parse_datetime(&self.created_at)?,
+            detection_type: StuckType::from_str(&self.detection_type)?,
+            severity: StuckSeverity::from_str(&self.severity)?,
+            details: serde_json::from_str(&self.details)?,
+            resolved: self.resolved,
+            resolution_action: self.resolution_action,
+            detected_at: parse_datetime(&self.detected_at)?,
+            resolved_at: self.resolved_at.map(|s| parse_datetime(&s)).transpose()?,
         })
     }
 }
 
 impl Database {
-    // ==================== Autonomous Session Operations ====================
+    // ==================== Agent Continuation Operations ====================
 
-    /// Create a new autonomous session
-    pub async fn create_autonomous_session(
+    /// Create a new agent continuation request
+    pub async fn create_continuation(
         &self,
-        session: &crate::autonomous_session::AutonomousSession,
-    ) -> Result<()> {
-        sqlx::query(
+        continuation: &crate::agent_continuation::AgentContinuation,
+    ) -> Result<i64> {
+        let result = sqlx::query(
             r#"
-            INSERT INTO autonomous_sessions (
-                id, state, started_at, updated_at, completed_at,
-                current_epic_id, current_story_id, current_agent_id,
-                config, work_queue, completed_items, metrics,
-                error_message, blocked_reason, pause_reason
+            INSERT INTO agent_continuations (
+                agent_id, session_id, reason, message, context, status,
+                started_at, completed_at, result, error_message
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(&session.id)
-        .bind(session.state.as_str())
-        .bind(session.started_at.to_rfc3339())
-        .bind(session.updated_at.to_rfc3339())
-        .bind(session.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(&session.current_epic_id)
-        .bind(&session.current_story_id)
-        .bind(&session.current_agent_id)
-        .bind(serde_json::to_string(&session.config)?)
-        .bind(serde_json::to_string(&session.work_queue)?)
-        .bind(serde_json::to_string(&session.completed_items)?)
-        .bind(serde_json::to_string(&session.metrics)?)
-        .bind(&session.error_message)
-        .bind(&session.blocked_reason)
-        .bind(&session.pause_reason)
+        .bind(&continuation.agent_id)
+        .bind(&continuation.session_id)
+        .bind(continuation.reason.as_str())
+        .bind(&continuation.message)
+        .bind(serde_json::to_string(&continuation.context)?)
+        .bind(continuation.status.as_str())
+        .bind(continuation.started_at.map(|dt| dt.to_rfc3339()))
+        .bind(continuation.completed_at.map(|dt| dt.to_rfc3339()))
+        .bind(
+            continuation
+                .result
+                .as_ref()
+                .map(|r| serde_json::to_string(r))
+                .transpose()?,
+        )
+        .bind(&continuation.error_message)
         .execute(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(result.last_insert_rowid())
     }
 
-    /// Update an autonomous session
-    pub async fn update_autonomous_session(
+    /// Create a continuation carrying compressed context from the agent's earlier continuations
+    pub async fn create_continuation_with_prior_context(
         &self,
-        session: &crate::autonomous_session::AutonomousSession,
+        continuation: crate::agent_continuation::AgentContinuation,
+        compressor: &crate::agent_continuation::ContinuationCompressor,
+    ) -> Result<crate::agent_continuation::AgentContinuation> {
+        let prior = self
+            .get_continuations_for_agent(&continuation.agent_id)
+            .await?;
+        let mut continuation = continuation.with_prior_context(&prior, compressor);
+        continuation.id = self.create_continuation(&continuation).await?;
+        Ok(continuation)
+    }
+
+    /// Update an agent continuation
+    pub async fn update_continuation(
+        &self,
+        continuation: &crate::agent_continuation::AgentContinuation,
     ) -> Result<()> {
         sqlx::query(
             r#"
-            UPDATE autonomous_sessions SET
-                state = ?, updated_at = ?, completed_at = ?,
-                current_epic_id = ?, current_story_id = ?, current_agent_id = ?,
-                config = ?, work_queue = ?, completed_items = ?, metrics = ?,
-                error_message = ?, blocked_reason = ?, pause_reason = ?
+            UPDATE agent_continuations SET
+                status = ?, started_at = ?, completed_at = ?,
+                result = ?, error_message = ?
             WHERE id = ?
             "#,
         )
-        .bind(session.state.as_str())
-        .bind(session.updated_at.to_rfc3339())
-        .bind(session.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(&session.current_epic_id)
-        .bind(&session.current_story_id)
-        .bind(&session.current_agent_id)
-        .bind(serde_json::to_string(&session.config)?)
-        .bind(serde_json::to_string(&session.work_queue)?)
-        .bind(serde_json::to_string(&session.completed_items)?)
-        .bind(serde_json::to_string(&session.metrics)?)
-        .bind(&session.error_message)
-        .bind(&session.blocked_reason)
-        .bind(&session.pause_reason)
-        .bind(&session.id)
+        .bind(continuation.status.as_str())
+        .bind(continuation.started_at.map(|dt| dt.to_rfc3339()))
+        .bind(continuation.completed_at.map(|dt| dt.to_rfc3339()))
+        .bind(
+            continuation
+                .result
+                .as_ref()
+                .map(|r| serde_json::to_string(r))
+                .transpose()?,
+        )
+        .bind(&continuation.error_message)
+        .bind(continuation.id)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
 
-    /// Get an autonomous session by ID
-    pub async fn get_autonomous_session(
+    /// Get a continuation by ID
+    pub async fn get_continuation(
         &self,
-        id: &str,
-    ) -> Result<Option<crate::autonomous_session::AutonomousSession>> {
-        let row = sqlx::query_as::<_, AutonomousSessionRow>(
+        id: i64,
+    ) -> Result<Option<crate::agent_continuation::AgentContinuation>> {
+        let row = sqlx::query_as::<_, AgentContinuationRow>(
             r#"
-            SELECT id, state, started_at, updated_at, completed_at,
-                   current_epic_id, current_story_id, current_agent_id,
-                   config, work_queue, completed_items, metrics,
-                   error_message, blocked_reason, pause_reason, created_at
-            FROM autonomous_sessions
+            SELECT id, agent_id, session_id, reason, message, context, status,
+                   created_at, started_at, completed_at, result, error_message
+            FROM agent_continuations
             WHERE id = ?
             "#,
         )
@@ -7166,938 +11768,1159 @@ impl Database {
         .fetch_optional(&self.pool)
         .await?;
 
-        row.map(|r| r.into_session()).transpose()
+        row.map(|r| r.into_continuation()).transpose()
     }
 
-    /// Get the current active autonomous session (if any)
-    pub async fn get_active_autonomous_session(
+    /// Get pending continuations for an agent
+    pub async fn get_pending_continuations(
         &self,
-    ) -> Result<Option<crate::autonomous_session::AutonomousSession>> {
-        let row = sqlx::query_as::<_, AutonomousSessionRow>(
+        agent_id: &str,
+    ) -> Result<Vec<crate::agent_continuation::AgentContinuation>> {
+        let rows = sqlx::query_as::<_, AgentContinuationRow>(
             r#"
-            SELECT id, state, started_at, updated_at, completed_at,
-                   current_epic_id, current_story_id, current_agent_id,
-                   config, work_queue, completed_items, metrics,
-                   error_message, blocked_reason, pause_reason, created_at
-            FROM autonomous_sessions
-            WHERE state NOT IN ('done', 'paused')
-            ORDER BY started_at DESC
-            LIMIT 1
+            SELECT id, agent_id, session_id, reason, message, context, status,
+                   created_at, started_at, completed_at, result, error_message
+            FROM agent_continuations
+            WHERE agent_id = ? AND status = 'pending'
+            ORDER BY created_at ASC
             "#,
         )
-        .fetch_optional(&self.pool)
+        .bind(agent_id)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.into_session()).transpose()
+        rows.into_iter().map(|r| r.into_continuation()).collect()
     }
 
-    /// List autonomous sessions with optional state filter
-    pub async fn list_autonomous_sessions(
+    /// Get all continuations for an agent
+    pub async fn get_continuations_for_agent(
         &self,
-        state: Option<&str>,
-        limit: Option<i64>,
-    ) -> Result<Vec<crate::autonomous_session::AutonomousSession>> {
-        let mut query = String::from(
+        agent_id: &str,
+    ) -> Result<Vec<crate::agent_continuation::AgentContinuation>> {
+        let rows = sqlx::query_as::<_, AgentContinuationRow>(
             r#"
-            SELECT id, state, started_at, updated_at, completed_at,
-                   current_epic_id, current_story_id, current_agent_id,
-                   config, work_queue, completed_items, metrics,
-                   error_message, blocked_reason, pause_reason, created_at
-            FROM autonomous_sessions
-            WHERE 1=1
+            SELECT id, agent_id, session_id, reason, message, context, status,
+                   created_at, started_at, completed_at, result, error_message
+            FROM agent_continuations
+            WHERE agent_id = ?
+            ORDER BY created_at DESC
             "#,
-        );
-
-        if state.is_some() {
-            query.push_str(" AND state = ?");
-        }
-
-        query.push_str(" ORDER BY started_at DESC");
+        )
+        .bind(agent_id)
+        .fetch_all(&self.pool)
+        .await?;
 
-        if let Some(limit) = limit {
-            query.push_str(&format!(" LIMIT {}", limit));
-        }
+        rows.into_iter().map(|r| r.into_continuation()).collect()
+    }
 
-        let mut q = sqlx::query_as::<_, AutonomousSessionRow>(&query);
-        if let Some(s) = state {
-            q = q.bind(s);
-        }
+    /// Get the next pending continuation (oldest first)
+    pub async fn get_next_pending_continuation(
+        &self,
+    ) -> Result<Option<crate::agent_continuation::AgentContinuation>> {
+        let row = sqlx::query_as::<_, AgentContinuationRow>(
+            r#"
+            SELECT id, agent_id, session_id, reason, message, context, status,
+                   created_at, started_at, completed_at, result, error_message
+            FROM agent_continuations
+            WHERE status = 'pending'
+            ORDER BY created_at ASC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
 
-        let rows = q.fetch_all(&self.pool).await?;
-        rows.into_iter().map(|r| r.into_session()).collect()
+        row.map(|r| r.into_continuation()).transpose()
     }
 
-    /// Delete an autonomous session
-    pub async fn delete_autonomous_session(&self, id: &str) -> Result<bool> {
-        let result = sqlx::query("DELETE FROM autonomous_sessions WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+    /// Cancel all pending continuations for an agent
+    pub async fn cancel_pending_continuations(&self, agent_id: &str) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE agent_continuations
+            SET status = 'cancelled', completed_at = datetime('now')
+            WHERE agent_id = ? AND status = 'pending'
+            "#,
+        )
+        .bind(agent_id)
+        .execute(&self.pool)
+        .await?;
 
-        Ok(result.rows_affected() > 0)
+        Ok(result.rows_affected())
     }
 
-    /// Record a state transition in the history
-    pub async fn record_session_state_transition(
+    /// Count continuations by status
+    pub async fn count_continuations_by_status(
         &self,
-        session_id: &str,
-        from_state: crate::autonomous_session::AutonomousSessionState,
-        to_state: crate::autonomous_session::AutonomousSessionState,
-        reason: Option<&str>,
-        metadata: Option<serde_json::Value>,
-    ) -> Result<()> {
-        sqlx::query(
-            r#"
-            INSERT INTO autonomous_session_history (
-                session_id, from_state, to_state, reason, transitioned_at, metadata
-            )
-            VALUES (?, ?, ?, ?, datetime('now'), ?)
-            "#,
+    ) -> Result<std::collections::HashMap<String, i64>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT status, COUNT(*) as count FROM agent_continuations GROUP BY status",
         )
-        .bind(session_id)
-        .bind(from_state.as_str())
-        .bind(to_state.as_str())
-        .bind(reason)
-        .bind(serde_json::to_string(&metadata.unwrap_or(serde_json::Value::Null))?)
-        .execute(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(rows.into_iter().collect())
     }
 
-    /// Get state transition history for a session
-    pub async fn get_session_state_history(
+    // ==================== Context Summary Operations ====================
+
+    /// Store a context summary, embedding it with the default local embedder
+    pub async fn store_context_summary(
         &self,
-        session_id: &str,
-    ) -> Result<Vec<crate::autonomous_session::SessionStateHistory>> {
-        let rows = sqlx::query_as::<_, SessionHistoryRow>(
+        summary: &crate::context_summary::ContextSummary,
+        story_id: Option<&str>,
+    ) -> Result<i64> {
+        self.store_context_summary_with(
+            summary,
+            story_id,
+            &crate::context_summary::HashingEmbedder::new(),
+        )
+        .await
+    }
+
+    /// Store a context summary using the given embedder
+    pub async fn store_context_summary_with(
+        &self,
+        summary: &crate::context_summary::ContextSummary,
+        story_id: Option<&str>,
+        embedder: &dyn crate::context_summary::SummaryEmbedder,
+    ) -> Result<i64> {
+        let embedding = embedder.embed(&summary.embedding_text());
+        let result = sqlx::query(
             r#"
-            SELECT id, session_id, from_state, to_state, reason,
-                   transitioned_at, metadata, created_at
-            FROM autonomous_session_history
-            WHERE session_id = ?
-            ORDER BY transitioned_at ASC
+            INSERT INTO context_summaries
+                (agent_id, session_id, story_id, summary, embedding, embedding_model, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(session_id)
-        .fetch_all(&self.pool)
+        .bind(&summary.agent_id)
+        .bind(&summary.session_id)
+        .bind(story_id)
+        .bind(serde_json::to_string(summary)?)
+        .bind(serde_json::to_string(&embedding)?)
+        .bind(embedder.model())
+        .bind(summary.created_at.to_rfc3339())
+        .execute(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_history()).collect()
+        Ok(result.last_insert_rowid())
     }
 
-    /// Get sessions by state
-    pub async fn get_sessions_by_state(
+    /// Find stored summaries most relevant to a task, using the default local embedder
+    pub async fn find_related_summaries(
         &self,
-        state: crate::autonomous_session::AutonomousSessionState,
-    ) -> Result<Vec<crate::autonomous_session::AutonomousSession>> {
-        self.list_autonomous_sessions(Some(state.as_str()), None)
-            .await
+        task: &str,
+        limit: usize,
+    ) -> Result<Vec<crate::context_summary::RelatedSummary>> {
+        self.find_related_summaries_with(
+            task,
+            limit,
+            &crate::context_summary::HashingEmbedder::new(),
+        )
+        .await
     }
 
-    /// Count sessions by state
-    pub async fn count_sessions_by_state(&self) -> Result<std::collections::HashMap<String, i64>> {
-        let rows: Vec<(String, i64)> = sqlx::query_as(
-            "SELECT state, COUNT(*) as count FROM autonomous_sessions GROUP BY state",
+    /// Find stored summaries most relevant to a task using the given embedder
+    ///
+    /// Ranks by cosine similarity, with a bonus for each changed file the task
+    /// mentions. Only summaries embedded by the same model are considered.
+    pub async fn find_related_summaries_with(
+        &self,
+        task: &str,
+        limit: usize,
+        embedder: &dyn crate::context_summary::SummaryEmbedder,
+    ) -> Result<Vec<crate::context_summary::RelatedSummary>> {
+        let rows: Vec<(i64, Option<String>, String, String)> = sqlx::query_as(
+            r#"
+            SELECT id, story_id, summary, embedding
+            FROM context_summaries
+            WHERE embedding_model = ?
+            "#,
         )
+        .bind(embedder.model())
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().collect())
-    }
-}
-
-// ==================== Agent Continuation Row ====================
+        let query = embedder.embed(task);
+        let mut related = Vec::new();
+        for (id, story_id, summary, embedding) in rows {
+            let summary: crate::context_summary::ContextSummary = serde_json::from_str(&summary)?;
+            let embedding: Vec<f32> = serde_json::from_str(&embedding)?;
+
+            let mentioned_files = summary
+                .changed_file_paths()
+                .iter()
+                .filter(|path| task.contains(*path))
+                .count();
+            let score = crate::context_summary::cosine_similarity(&query, &embedding)
+                + (0.1 * mentioned_files as f64).min(0.3);
+            if score <= 0.0 {
+                continue;
+            }
 
-#[derive(Debug, sqlx::FromRow)]
-struct AgentContinuationRow {
-    id: i64,
-    agent_id: String,
-    session_id: Option<String>,
-    reason: String,
-    message: String,
-    context: String,
-    status: String,
-    created_at: String,
-    started_at: Option<String>,
-    completed_at: Option<String>,
-    result: Option<String>,
-    error_message: Option<String>,
-}
+            related.push(crate::context_summary::RelatedSummary {
+                id,
+                story_id,
+                summary,
+                score,
+            });
+        }
 
-impl AgentContinuationRow {
-    fn into_continuation(self) -> Result<crate::agent_continuation::AgentContinuation> {
-        Ok(crate::agent_continuation::AgentContinuation {
-            id: self.id,
-            agent_id: self.agent_id,
-            session_id: self.session_id,
-            reason: crate::agent_continuation::ContinuationReason::from_str(&self.reason)?,
-            message: self.message,
-            context: serde_json::from_str(&self.context)?,
-            status: crate::agent_continuation::ContinuationStatus::from_str(&self.status)?,
-            created_at: parse_datetime(&self.created_at)?,
-            started_at: self.started_at.map(|s| parse_datetime(&s)).transpose()?,
-            completed_at: self.completed_at.map(|s| parse_datetime(&s)).transpose()?,
-            result: self
-                .result
-                .map(|s| serde_json::from_str(&s))
-                .transpose()?,
-            error_message: self.error_message,
-        })
+        related.sort_by(|a, b| b.score.total_cmp(&a.score));
+        related.truncate(limit);
+        Ok(related)
     }
-}
 
-// ==================== Work Evaluation Row (Epic 016 - Story 6) ====================
+    // ==================== Work Evaluation Operations (Epic 016 - Story 6) ====================
 
-#[derive(Debug, sqlx::FromRow)]
-struct WorkEvaluationRow {
-    id: i64,
-    agent_id: String,
-    session_id: Option<String>,
-    story_id: Option<String>,
-    evaluation_type: String,
-    status: String,
-    details: String,
-    turn_count: Option<i64>,
-    max_turns: Option<i64>,
-    token_count: Option<i64>,
-    max_tokens: Option<i64>,
-    duration_secs: Option<i64>,
-    created_at: String,
-}
+    /// Create a new work evaluation, logging it as an agent event
+    pub async fn create_work_evaluation(
+        &self,
+        eval: &crate::stuck_detection::WorkEvaluation,
+    ) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO work_evaluations
+                (agent_id, session_id, story_id, evaluation_type, status, details,
+                 turn_count, max_turns, token_count, max_tokens, duration_secs, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&eval.agent_id)
+        .bind(&eval.session_id)
+        .bind(&eval.story_id)
+        .bind(eval.evaluation_type.as_str())
+        .bind(eval.status.as_str())
+        .bind(serde_json::to_string(&eval.details)?)
+        .bind(eval.turn_count.map(|v| v as i64))
+        .bind(eval.max_turns.map(|v| v as i64))
+        .bind(eval.token_count.map(|v| v as i64))
+        .bind(eval.max_tokens.map(|v| v as i64))
+        .bind(eval.duration_secs.map(|v| v as i64))
+        .bind(eval.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        let id = result.last_insert_rowid();
 
-impl WorkEvaluationRow {
-    fn into_evaluation(self) -> Result<crate::stuck_detection::WorkEvaluation> {
-        use crate::stuck_detection::{EvaluationStatus, EvaluationType};
-        use std::str::FromStr;
+        let mut created = eval.clone();
+        created.id = id;
+        let mut event = crate::agent_event::AgentEvent::evaluation(&eval.agent_id, &created);
+        event.session_id = eval.session_id.clone();
+        event.created_at = eval.created_at;
+        self.insert_agent_event(&event).await?;
 
-        Ok(crate::stuck_detection::WorkEvaluation {
-            id: self.id,
-            agent_id: self.agent_id,
-            session_id: self.session_id,
-            story_id: self.story_id,
-            evaluation_type: EvaluationType::from_str(&self.evaluation_type)?,
-            status: EvaluationStatus::from_str(&self.status)?,
-            details: serde_json::from_str(&self.details)?,
-            turn_count: self.turn_count.map(|v| v as u32),
-            max_turns: self.max_turns.map(|v| v as u32),
-            token_count: self.token_count.map(|v| v as u64),
-            max_tokens: self.max_tokens.map(|v| v as u64),
-            duration_secs: self.duration_secs.map(|v| v as u64),
-            created_at: parse_datetime(&self.created_at)?,
-        })
+        Ok(id)
     }
-}
 
-// ==================== Stuck Detection Row (Epic 016 - Story 6) ====================
+    /// Get work evaluation by ID
+    pub async fn get_work_evaluation(
+        &self,
+        id: i64,
+    ) -> Result<Option<crate::stuck_detection::WorkEvaluation>> {
+        let row = sqlx::query_as::<_, WorkEvaluationRow>(
+            "SELECT * FROM work_evaluations WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
 
-#[derive(Debug, sqlx::FromRow)]
-struct StuckDetectionRow {
-    id: i64,
-    agent_id: String,
-    session_id: Option<String>,
-    detection_type: String,
-    severity: String,
-    details: String,
-    resolved: bool,
-    resolution_action: Option<String>,
-    detected_at: String,
-    resolved_at: Option<String>,
-}
+        row.map(|r| r.into_evaluation()).transpose()
+    }
 
-impl StuckDetectionRow {
-    fn into_detection(self) -> Result<crate::stuck_detection::StuckDetection> {
-        use crate::stuck_detection::{StuckSeverity, StuckType};
-        use std::str::FromStr;
+    /// Get work evaluations for an agent
+    pub async fn get_work_evaluations_for_agent(
+        &self,
+        agent_id: &str,
+    ) -> Result<Vec<crate::stuck_detection::WorkEvaluation>> {
+        let rows = sqlx::query_as::<_, WorkEvaluationRow>(
+            r#"
+            SELECT * FROM work_evaluations
+            WHERE agent_id = ?
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(agent_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.into_evaluation()).collect()
+    }
 
-        Ok(crate::stuck_detection::StuckDetection {
-            id: self.id,
-            agent_id: self.agent_id,
-            session_id: self.session_id,
-            detection_type: StuckType::from_str(&self.detection_type)?,
-            severity: StuckSeverity::from_str(&self.severity)?,
-            details: serde_json::from_str(&self.details)?,
-            resolved: self.resolved,
-            resolution_action: self.resolution_action,
-            detected_at: parse_datetime(&self.detected_at)?,
-            resolved_at: self.resolved_at.map(|s| parse_datetime(&s)).transpose()?,
-        })
+    /// Get latest evaluation for an agent
+    pub async fn get_latest_evaluation(
+        &self,
+        agent_id: &str,
+    ) -> Result<Option<crate::stuck_detection::WorkEvaluation>> {
+        let row = sqlx::query_as::<_, WorkEvaluationRow>(
+            r#"
+            SELECT * FROM work_evaluations
+            WHERE agent_id = ?
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(agent_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.into_evaluation()).transpose()
     }
-}
 
-impl Database {
-    // ==================== Agent Continuation Operations ====================
+    // ==================== Stuck Detection Operations (Epic 016 - Story 6) ====================
 
-    /// Create a new agent continuation request
-    pub async fn create_continuation(
+    /// Create a new stuck detection
+    pub async fn create_stuck_detection(
         &self,
-        continuation: &crate::agent_continuation::AgentContinuation,
+        detection: &crate::stuck_detection::StuckDetection,
     ) -> Result<i64> {
         let result = sqlx::query(
             r#"
-            INSERT INTO agent_continuations (
-                agent_id, session_id, reason, message, context, status,
-                started_at, completed_at, result, error_message
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO stuck_agent_detections
+                (agent_id, session_id, detection_type, severity, details,
+                 resolved, resolution_action, detected_at, resolved_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(&continuation.agent_id)
-        .bind(&continuation.session_id)
-        .bind(continuation.reason.as_str())
-        .bind(&continuation.message)
-        .bind(serde_json::to_string(&continuation.context)?)
-        .bind(continuation.status.as_str())
-        .bind(continuation.started_at.map(|dt| dt.to_rfc3339()))
-        .bind(continuation.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(
-            continuation
-                .result
-                .as_ref()
-                .map(|r| serde_json::to_string(r))
-                .transpose()?,
-        )
-        .bind(&continuation.error_message)
+        .bind(&detection.agent_id)
+        .bind(&detection.session_id)
+        .bind(detection.detection_type.as_str())
+        .bind(detection.severity.as_str())
+        .bind(serde_json::to_string(&detection.details)?)
+        .bind(detection.resolved)
+        .bind(&detection.resolution_action)
+        .bind(detection.detected_at.to_rfc3339())
+        .bind(detection.resolved_at.map(|dt| dt.to_rfc3339()))
         .execute(&self.pool)
         .await?;
 
         Ok(result.last_insert_rowid())
     }
 
-    /// Update an agent continuation
-    pub async fn update_continuation(
+    /// Get stuck detection by ID
+    pub async fn get_stuck_detection(
         &self,
-        continuation: &crate::agent_continuation::AgentContinuation,
+        id: i64,
+    ) -> Result<Option<crate::stuck_detection::StuckDetection>> {
+        let row = sqlx::query_as::<_, StuckDetectionRow>(
+            "SELECT * FROM stuck_agent_detections WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.into_detection()).transpose()
+    }
+
+    /// Update a stuck detection (for resolution)
+    pub async fn update_stuck_detection(
+        &self,
+        detection: &crate::stuck_detection::StuckDetection,
     ) -> Result<()> {
         sqlx::query(
             r#"
-            UPDATE agent_continuations SET
-                status = ?, started_at = ?, completed_at = ?,
-                result = ?, error_message = ?
+            UPDATE stuck_agent_detections
+            SET resolved = ?, resolution_action = ?, resolved_at = ?
             WHERE id = ?
             "#,
         )
-        .bind(continuation.status.as_str())
-        .bind(continuation.started_at.map(|dt| dt.to_rfc3339()))
-        .bind(continuation.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(
-            continuation
-                .result
-                .as_ref()
-                .map(|r| serde_json::to_string(r))
-                .transpose()?,
-        )
-        .bind(&continuation.error_message)
-        .bind(continuation.id)
+        .bind(detection.resolved)
+        .bind(&detection.resolution_action)
+        .bind(detection.resolved_at.map(|dt| dt.to_rfc3339()))
+        .bind(detection.id)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
 
-    /// Get a continuation by ID
-    pub async fn get_continuation(
+    /// Get unresolved stuck detections for an agent
+    pub async fn get_unresolved_stuck_detections(
         &self,
-        id: i64,
-    ) -> Result<Option<crate::agent_continuation::AgentContinuation>> {
-        let row = sqlx::query_as::<_, AgentContinuationRow>(
+        agent_id: &str,
+    ) -> Result<Vec<crate::stuck_detection::StuckDetection>> {
+        let rows = sqlx::query_as::<_, StuckDetectionRow>(
             r#"
-            SELECT id, agent_id, session_id, reason, message, context, status,
-                   created_at, started_at, completed_at, result, error_message
-            FROM agent_continuations
-            WHERE id = ?
+            SELECT * FROM stuck_agent_detections
+            WHERE agent_id = ? AND resolved = FALSE
+            ORDER BY detected_at DESC
             "#,
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(agent_id)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.into_continuation()).transpose()
+        rows.into_iter().map(|r| r.into_detection()).collect()
     }
 
-    /// Get pending continuations for an agent
-    pub async fn get_pending_continuations(
+    /// Get all stuck detections for an agent
+    pub async fn get_stuck_detections_for_agent(
         &self,
         agent_id: &str,
-    ) -> Result<Vec<crate::agent_continuation::AgentContinuation>> {
-        let rows = sqlx::query_as::<_, AgentContinuationRow>(
+    ) -> Result<Vec<crate::stuck_detection::StuckDetection>> {
+        let rows = sqlx::query_as::<_, StuckDetectionRow>(
             r#"
-            SELECT id, agent_id, session_id, reason, message, context, status,
-                   created_at, started_at, completed_at, result, error_message
-            FROM agent_continuations
-            WHERE agent_id = ? AND status = 'pending'
-            ORDER BY created_at ASC
+            SELECT * FROM stuck_agent_detections
+            WHERE agent_id = ?
+            ORDER BY detected_at DESC
             "#,
         )
         .bind(agent_id)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_continuation()).collect()
+        rows.into_iter().map(|r| r.into_detection()).collect()
     }
 
-    /// Get all continuations for an agent
-    pub async fn get_continuations_for_agent(
+    /// Get all unresolved stuck detections (for monitoring)
+    pub async fn get_all_unresolved_stuck_detections(
         &self,
-        agent_id: &str,
-    ) -> Result<Vec<crate::agent_continuation::AgentContinuation>> {
-        let rows = sqlx::query_as::<_, AgentContinuationRow>(
+    ) -> Result<Vec<crate::stuck_detection::StuckDetection>> {
+        let rows = sqlx::query_as::<_, StuckDetectionRow>(
             r#"
-            SELECT id, agent_id, session_id, reason, message, context, status,
-                   created_at, started_at, completed_at, result, error_message
-            FROM agent_continuations
-            WHERE agent_id = ?
-            ORDER BY created_at DESC
+            SELECT * FROM stuck_agent_detections
+            WHERE resolved = FALSE
+            ORDER BY severity DESC, detected_at ASC
             "#,
         )
-        .bind(agent_id)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_continuation()).collect()
+        rows.into_iter().map(|r| r.into_detection()).collect()
     }
 
-    /// Get the next pending continuation (oldest first)
-    pub async fn get_next_pending_continuation(
+    /// Count stuck detections by type
+    pub async fn count_stuck_detections_by_type(
         &self,
-    ) -> Result<Option<crate::agent_continuation::AgentContinuation>> {
-        let row = sqlx::query_as::<_, AgentContinuationRow>(
+    ) -> Result<std::collections::HashMap<String, i64>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
             r#"
-            SELECT id, agent_id, session_id, reason, message, context, status,
-                   created_at, started_at, completed_at, result, error_message
-            FROM agent_continuations
-            WHERE status = 'pending'
-            ORDER BY created_at ASC
-            LIMIT 1
+            SELECT detection_type, COUNT(*) as count
+            FROM stuck_agent_detections
+            WHERE resolved = FALSE
+            GROUP BY detection_type
             "#,
         )
-        .fetch_optional(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.into_continuation()).transpose()
+        Ok(rows.into_iter().collect())
     }
 
-    /// Cancel all pending continuations for an agent
-    pub async fn cancel_pending_continuations(&self, agent_id: &str) -> Result<u64> {
-        let result = sqlx::query(
+    /// Get stuck detections for a session
+    pub async fn get_stuck_detections_for_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<crate::stuck_detection::StuckDetection>> {
+        let rows = sqlx::query_as::<_, StuckDetectionRow>(
             r#"
-            UPDATE agent_continuations
-            SET status = 'cancelled', completed_at = datetime('now')
-            WHERE agent_id = ? AND status = 'pending'
+            SELECT * FROM stuck_agent_detections
+            WHERE session_id = ?
+            ORDER BY detected_at DESC
             "#,
         )
-        .bind(agent_id)
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.into_detection()).collect()
+    }
+
+    /// One page of a session's stuck detections, or of all unresolved ones,
+    /// newest first
+    pub async fn list_stuck_detections_page(
+        &self,
+        session_id: Option<&str>,
+        page: &PageQuery,
+    ) -> Result<RowPage<crate::stuck_detection::StuckDetection>> {
+        self.fetch_page::<StuckDetectionRow, _>(
+            "SELECT * FROM stuck_agent_detections",
+            |q| match session_id {
+                Some(session_id) => {
+                    q.push(" AND session_id = ").push_bind(session_id.to_string());
+                }
+                None => {
+                    q.push(" AND resolved = FALSE");
+                }
+            },
+            SortKey::desc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(|r| r.into_detection())
+    }
+
+    // ==================== Stuck Detection Config Operations ====================
+
+    /// Store the stuck detection config for an agent type
+    pub async fn upsert_stuck_detection_config(
+        &self,
+        agent_type: &str,
+        config: &crate::stuck_detection::StuckDetectionConfig,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
+            r#"
+            INSERT INTO stuck_detection_configs (agent_type, config, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(agent_type) DO UPDATE SET
+                config = excluded.config,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(agent_type)
+        .bind(serde_json::to_string(config)?)
+        .bind(&now)
+        .bind(&now)
         .execute(&self.pool)
         .await?;
 
-        Ok(result.rows_affected())
+        Ok(())
+    }
+
+    /// Get the stuck detection config stored for an agent type
+    pub async fn get_stuck_detection_config(
+        &self,
+        agent_type: &str,
+    ) -> Result<Option<crate::stuck_detection::StuckDetectionConfig>> {
+        let config: Option<String> = sqlx::query_scalar(
+            "SELECT config FROM stuck_detection_configs WHERE agent_type = ?",
+        )
+        .bind(agent_type)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(config.map(|c| serde_json::from_str(&c)).transpose()?)
+    }
+
+    /// Resolve the stuck detection config that applies to an agent type
+    ///
+    /// Falls back to the `default` agent type's stored config, then to the
+    /// built-in defaults.
+    pub async fn resolve_stuck_detection_config(
+        &self,
+        agent_type: &str,
+    ) -> Result<crate::stuck_detection::StuckDetectionConfig> {
+        use crate::stuck_detection::StuckDetectionConfig;
+
+        if let Some(config) = self.get_stuck_detection_config(agent_type).await? {
+            return Ok(config);
+        }
+        Ok(self
+            .get_stuck_detection_config(StuckDetectionConfig::DEFAULT_AGENT_TYPE)
+            .await?
+            .unwrap_or_default())
     }
 
-    /// Count continuations by status
-    pub async fn count_continuations_by_status(
+    /// List stored stuck detection configs by agent type
+    pub async fn list_stuck_detection_configs(
         &self,
-    ) -> Result<std::collections::HashMap<String, i64>> {
-        let rows: Vec<(String, i64)> = sqlx::query_as(
-            "SELECT status, COUNT(*) as count FROM agent_continuations GROUP BY status",
+    ) -> Result<Vec<(String, crate::stuck_detection::StuckDetectionConfig)>> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT agent_type, config FROM stuck_detection_configs ORDER BY agent_type",
         )
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().collect())
+        rows.into_iter()
+            .map(|(agent_type, config)| Ok((agent_type, serde_json::from_str(&config)?)))
+            .collect()
     }
 
-    // ==================== Work Evaluation Operations (Epic 016 - Story 6) ====================
+    /// Delete the stuck detection config for an agent type
+    pub async fn delete_stuck_detection_config(&self, agent_type: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM stuck_detection_configs WHERE agent_type = ?")
+            .bind(agent_type)
+            .execute(&self.pool)
+            .await?;
 
-    /// Create a new work evaluation
-    pub async fn create_work_evaluation(
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ==================== Recovery Attempt Operations (Epic 016 - Story 7) ====================
+
+    /// Create a new recovery attempt, logging it as an agent event
+    pub async fn create_recovery_attempt(
         &self,
-        eval: &crate::stuck_detection::WorkEvaluation,
+        attempt: &crate::recovery::RecoveryAttempt,
     ) -> Result<i64> {
         let result = sqlx::query(
             r#"
-            INSERT INTO work_evaluations
-                (agent_id, session_id, story_id, evaluation_type, status, details,
-                 turn_count, max_turns, token_count, max_tokens, duration_secs, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO recovery_attempts
+                (agent_id, session_id, stuck_detection_id, action_type, outcome,
+                 details, attempt_number, started_at, completed_at, error_message)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(&eval.agent_id)
-        .bind(&eval.session_id)
-        .bind(&eval.story_id)
-        .bind(eval.evaluation_type.as_str())
-        .bind(eval.status.as_str())
-        .bind(serde_json::to_string(&eval.details)?)
-        .bind(eval.turn_count.map(|v| v as i64))
-        .bind(eval.max_turns.map(|v| v as i64))
-        .bind(eval.token_count.map(|v| v as i64))
-        .bind(eval.max_tokens.map(|v| v as i64))
-        .bind(eval.duration_secs.map(|v| v as i64))
-        .bind(eval.created_at.to_rfc3339())
+        .bind(&attempt.agent_id)
+        .bind(&attempt.session_id)
+        .bind(attempt.stuck_detection_id)
+        .bind(attempt.action_type.as_str())
+        .bind(attempt.outcome.as_str())
+        .bind(serde_json::to_string(&attempt.details)?)
+        .bind(attempt.attempt_number as i64)
+        .bind(attempt.started_at.to_rfc3339())
+        .bind(attempt.completed_at.map(|dt| dt.to_rfc3339()))
+        .bind(&attempt.error_message)
         .execute(&self.pool)
         .await?;
+        let id = result.last_insert_rowid();
 
-        Ok(result.last_insert_rowid())
+        let mut created = attempt.clone();
+        created.id = id;
+        let mut event = crate::agent_event::AgentEvent::recovery(&attempt.agent_id, &created);
+        event.session_id = attempt.session_id.clone();
+        event.created_at = attempt.started_at;
+        self.insert_agent_event(&event).await?;
+
+        Ok(id)
     }
 
-    /// Get work evaluation by ID
-    pub async fn get_work_evaluation(
+    /// Get a recovery attempt by ID
+    pub async fn get_recovery_attempt(
         &self,
         id: i64,
-    ) -> Result<Option<crate::stuck_detection::WorkEvaluation>> {
-        let row = sqlx::query_as::<_, WorkEvaluationRow>(
-            "SELECT * FROM work_evaluations WHERE id = ?",
+    ) -> Result<Option<crate::recovery::RecoveryAttempt>> {
+        let row = sqlx::query_as::<_, RecoveryAttemptRow>(
+            "SELECT * FROM recovery_attempts WHERE id = ?",
         )
         .bind(id)
         .fetch_optional(&self.pool)
         .await?;
 
-        row.map(|r| r.into_evaluation()).transpose()
+        row.map(|r| r.into_attempt()).transpose()
     }
 
-    /// Get work evaluations for an agent
-    pub async fn get_work_evaluations_for_agent(
+    /// Update a recovery attempt
+    pub async fn update_recovery_attempt(
+        &self,
+        attempt: &crate::recovery::RecoveryAttempt,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE recovery_attempts
+            SET outcome = ?, completed_at = ?, error_message = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(attempt.outcome.as_str())
+        .bind(attempt.completed_at.map(|dt| dt.to_rfc3339()))
+        .bind(&attempt.error_message)
+        .bind(attempt.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get recovery attempts for an agent
+    pub async fn get_recovery_attempts_for_agent(
         &self,
         agent_id: &str,
-    ) -> Result<Vec<crate::stuck_detection::WorkEvaluation>> {
-        let rows = sqlx::query_as::<_, WorkEvaluationRow>(
+    ) -> Result<Vec<crate::recovery::RecoveryAttempt>> {
+        let rows = sqlx::query_as::<_, RecoveryAttemptRow>(
             r#"
-            SELECT * FROM work_evaluations
+            SELECT * FROM recovery_attempts
             WHERE agent_id = ?
-            ORDER BY created_at DESC
+            ORDER BY started_at DESC
             "#,
         )
         .bind(agent_id)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_evaluation()).collect()
+        rows.into_iter().map(|r| r.into_attempt()).collect()
     }
 
-    /// Get latest evaluation for an agent
-    pub async fn get_latest_evaluation(
+    /// Get in-progress recovery attempts for an agent
+    pub async fn get_in_progress_recovery_attempts(
         &self,
         agent_id: &str,
-    ) -> Result<Option<crate::stuck_detection::WorkEvaluation>> {
-        let row = sqlx::query_as::<_, WorkEvaluationRow>(
+    ) -> Result<Vec<crate::recovery::RecoveryAttempt>> {
+        let rows = sqlx::query_as::<_, RecoveryAttemptRow>(
             r#"
-            SELECT * FROM work_evaluations
-            WHERE agent_id = ?
-            ORDER BY created_at DESC
-            LIMIT 1
+            SELECT * FROM recovery_attempts
+            WHERE agent_id = ? AND outcome = 'in_progress'
+            ORDER BY started_at DESC
             "#,
         )
         .bind(agent_id)
-        .fetch_optional(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.into_evaluation()).transpose()
+        rows.into_iter().map(|r| r.into_attempt()).collect()
     }
 
-    // ==================== Stuck Detection Operations (Epic 016 - Story 6) ====================
-
-    /// Create a new stuck detection
-    pub async fn create_stuck_detection(
+    /// Count recovery attempts by action type for an agent
+    pub async fn count_recovery_attempts_by_type(
         &self,
-        detection: &crate::stuck_detection::StuckDetection,
-    ) -> Result<i64> {
-        let result = sqlx::query(
+        agent_id: &str,
+    ) -> Result<std::collections::HashMap<String, u32>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
             r#"
-            INSERT INTO stuck_agent_detections
-                (agent_id, session_id, detection_type, severity, details,
-                 resolved, resolution_action, detected_at, resolved_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            SELECT action_type, COUNT(*) as count
+            FROM recovery_attempts
+            WHERE agent_id = ?
+            GROUP BY action_type
             "#,
         )
-        .bind(&detection.agent_id)
-        .bind(&detection.session_id)
-        .bind(detection.detection_type.as_str())
-        .bind(detection.severity.as_str())
-        .bind(serde_json::to_string(&detection.details)?)
-        .bind(detection.resolved)
-        .bind(&detection.resolution_action)
-        .bind(detection.detected_at.to_rfc3339())
-        .bind(detection.resolved_at.map(|dt| dt.to_rfc3339()))
-        .execute(&self.pool)
+        .bind(agent_id)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(result.last_insert_rowid())
+        Ok(rows
+            .into_iter()
+            .map(|(k, v)| (k, v as u32))
+            .collect())
     }
 
-    /// Get stuck detection by ID
-    pub async fn get_stuck_detection(
+    /// Get recovery statistics for an agent
+    pub async fn get_recovery_stats(
         &self,
-        id: i64,
-    ) -> Result<Option<crate::stuck_detection::StuckDetection>> {
-        let row = sqlx::query_as::<_, StuckDetectionRow>(
-            "SELECT * FROM stuck_agent_detections WHERE id = ?",
+        agent_id: &str,
+    ) -> Result<RecoveryStats> {
+        let total: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM recovery_attempts WHERE agent_id = ?",
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(agent_id)
+        .fetch_one(&self.pool)
         .await?;
 
-        row.map(|r| r.into_detection()).transpose()
-    }
+        let successful: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM recovery_attempts WHERE agent_id = ? AND outcome = 'success'",
+        )
+        .bind(agent_id)
+        .fetch_one(&self.pool)
+        .await?;
 
-    /// Update a stuck detection (for resolution)
-    pub async fn update_stuck_detection(
-        &self,
-        detection: &crate::stuck_detection::StuckDetection,
-    ) -> Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE stuck_agent_detections
-            SET resolved = ?, resolution_action = ?, resolved_at = ?
-            WHERE id = ?
-            "#,
+        let failed: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM recovery_attempts WHERE agent_id = ? AND outcome = 'failed'",
         )
-        .bind(detection.resolved)
-        .bind(&detection.resolution_action)
-        .bind(detection.resolved_at.map(|dt| dt.to_rfc3339()))
-        .bind(detection.id)
-        .execute(&self.pool)
+        .bind(agent_id)
+        .fetch_one(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(RecoveryStats {
+            total_attempts: total.0 as u32,
+            successful: successful.0 as u32,
+            failed: failed.0 as u32,
+            success_rate: if total.0 > 0 {
+                successful.0 as f64 / total.0 as f64
+            } else {
+                0.0
+            },
+        })
     }
 
-    /// Get unresolved stuck detections for an agent
-    pub async fn get_unresolved_stuck_detections(
+    // ==================== Story Evaluation Operations (Epic 016 - Story 8) ====================
+
+    /// Create a story evaluation record
+    pub async fn create_story_evaluation(
         &self,
-        agent_id: &str,
-    ) -> Result<Vec<crate::stuck_detection::StuckDetection>> {
-        let rows = sqlx::query_as::<_, StuckDetectionRow>(
+        record: &crate::work_evaluation::StoryEvaluationRecord,
+    ) -> Result<i64> {
+        let result = sqlx::query(
             r#"
-            SELECT * FROM stuck_agent_detections
-            WHERE agent_id = ? AND resolved = FALSE
-            ORDER BY detected_at DESC
+            INSERT INTO story_evaluations
+                (story_id, agent_id, session_id, status, criteria_met_count,
+                 criteria_total_count, ci_passed, review_passed, review_iteration,
+                 pr_mergeable, feedback, details, evaluated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(agent_id)
-        .fetch_all(&self.pool)
+        .bind(&record.story_id)
+        .bind(&record.agent_id)
+        .bind(&record.session_id)
+        .bind(record.status.as_str())
+        .bind(record.criteria_met_count as i64)
+        .bind(record.criteria_total_count as i64)
+        .bind(record.ci_passed)
+        .bind(record.review_passed)
+        .bind(record.review_iteration as i64)
+        .bind(record.pr_mergeable)
+        .bind(&record.feedback)
+        .bind(serde_json::to_string(&record.details)?)
+        .bind(record.evaluated_at.to_rfc3339())
+        .execute(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_detection()).collect()
+        Ok(result.last_insert_rowid())
     }
 
-    /// Get all stuck detections for an agent
-    pub async fn get_stuck_detections_for_agent(
+    /// Get story evaluation by ID
+    pub async fn get_story_evaluation(
         &self,
-        agent_id: &str,
-    ) -> Result<Vec<crate::stuck_detection::StuckDetection>> {
-        let rows = sqlx::query_as::<_, StuckDetectionRow>(
-            r#"
-            SELECT * FROM stuck_agent_detections
-            WHERE agent_id = ?
-            ORDER BY detected_at DESC
-            "#,
+        id: i64,
+    ) -> Result<Option<crate::work_evaluation::StoryEvaluationRecord>> {
+        let row = sqlx::query_as::<_, StoryEvaluationRow>(
+            "SELECT * FROM story_evaluations WHERE id = ?",
         )
-        .bind(agent_id)
-        .fetch_all(&self.pool)
+        .bind(id)
+        .fetch_optional(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_detection()).collect()
+        row.map(|r| r.into_record()).transpose()
     }
 
-    /// Get all unresolved stuck detections (for monitoring)
-    pub async fn get_all_unresolved_stuck_detections(
+    /// Get story evaluations for a story
+    pub async fn get_story_evaluations(
         &self,
-    ) -> Result<Vec<crate::stuck_detection::StuckDetection>> {
-        let rows = sqlx::query_as::<_, StuckDetectionRow>(
+        story_id: &str,
+    ) -> Result<Vec<crate::work_evaluation::StoryEvaluationRecord>> {
+        let rows = sqlx::query_as::<_, StoryEvaluationRow>(
             r#"
-            SELECT * FROM stuck_agent_detections
-            WHERE resolved = FALSE
-            ORDER BY severity DESC, detected_at ASC
+            SELECT * FROM story_evaluations
+            WHERE story_id = ?
+            ORDER BY evaluated_at DESC
             "#,
         )
+        .bind(story_id)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_detection()).collect()
+        rows.into_iter().map(|r| r.into_record()).collect()
     }
 
-    /// Count stuck detections by type
-    pub async fn count_stuck_detections_by_type(
+    /// Get latest story evaluation for a story
+    pub async fn get_latest_story_evaluation(
         &self,
-    ) -> Result<std::collections::HashMap<String, i64>> {
-        let rows: Vec<(String, i64)> = sqlx::query_as(
+        story_id: &str,
+    ) -> Result<Option<crate::work_evaluation::StoryEvaluationRecord>> {
+        let row = sqlx::query_as::<_, StoryEvaluationRow>(
             r#"
-            SELECT detection_type, COUNT(*) as count
-            FROM stuck_agent_detections
-            WHERE resolved = FALSE
-            GROUP BY detection_type
+            SELECT * FROM story_evaluations
+            WHERE story_id = ?
+            ORDER BY evaluated_at DESC
+            LIMIT 1
             "#,
         )
-        .fetch_all(&self.pool)
+        .bind(story_id)
+        .fetch_optional(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().collect())
+        row.map(|r| r.into_record()).transpose()
     }
 
-    /// Get stuck detections for a session
-    pub async fn get_stuck_detections_for_session(
+    /// Get story evaluations for a session
+    pub async fn get_story_evaluations_for_session(
         &self,
         session_id: &str,
-    ) -> Result<Vec<crate::stuck_detection::StuckDetection>> {
-        let rows = sqlx::query_as::<_, StuckDetectionRow>(
+    ) -> Result<Vec<crate::work_evaluation::StoryEvaluationRecord>> {
+        let rows = sqlx::query_as::<_, StoryEvaluationRow>(
             r#"
-            SELECT * FROM stuck_agent_detections
+            SELECT * FROM story_evaluations
             WHERE session_id = ?
-            ORDER BY detected_at DESC
+            ORDER BY evaluated_at DESC
             "#,
         )
         .bind(session_id)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_detection()).collect()
+        rows.into_iter().map(|r| r.into_record()).collect()
     }
 
-    // ==================== Recovery Attempt Operations (Epic 016 - Story 7) ====================
-
-    /// Create a new recovery attempt
-    pub async fn create_recovery_attempt(
+    /// Create a code review result
+    pub async fn create_code_review_result(
         &self,
-        attempt: &crate::recovery::RecoveryAttempt,
+        story_id: &str,
+        agent_id: &str,
+        session_id: Option<&str>,
+        result: &crate::work_evaluation::ReviewResult,
     ) -> Result<i64> {
-        let result = sqlx::query(
+        let blocking_count = result.issues.iter().filter(|i| i.severity.blocks_merge()).count() as i64;
+
+        let result_row = sqlx::query(
             r#"
-            INSERT INTO recovery_attempts
-                (agent_id, session_id, stuck_detection_id, action_type, outcome,
-                 details, attempt_number, started_at, completed_at, error_message)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO code_review_results
+                (story_id, agent_id, session_id, verdict, reviewer, iteration,
+                 issue_count, blocking_issue_count, issues, raw_output, reviewed_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(&attempt.agent_id)
-        .bind(&attempt.session_id)
-        .bind(attempt.stuck_detection_id)
-        .bind(attempt.action_type.as_str())
-        .bind(attempt.outcome.as_str())
-        .bind(serde_json::to_string(&attempt.details)?)
-        .bind(attempt.attempt_number as i64)
-        .bind(attempt.started_at.to_rfc3339())
-        .bind(attempt.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(&attempt.error_message)
+        .bind(story_id)
+        .bind(agent_id)
+        .bind(session_id)
+        .bind(result.verdict.as_str())
+        .bind(&result.reviewer)
+        .bind(result.iteration as i64)
+        .bind(result.issues.len() as i64)
+        .bind(blocking_count)
+        .bind(serde_json::to_string(&result.issues)?)
+        .bind(&result.raw_output)
+        .bind(result.reviewed_at.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        Ok(result.last_insert_rowid())
+        Ok(result_row.last_insert_rowid())
     }
 
-    /// Get a recovery attempt by ID
-    pub async fn get_recovery_attempt(
+    /// Get code review results for a story
+    pub async fn get_code_review_results(
         &self,
-        id: i64,
-    ) -> Result<Option<crate::recovery::RecoveryAttempt>> {
-        let row = sqlx::query_as::<_, RecoveryAttemptRow>(
-            "SELECT * FROM recovery_attempts WHERE id = ?",
+        story_id: &str,
+    ) -> Result<Vec<crate::work_evaluation::ReviewResult>> {
+        let rows = sqlx::query_as::<_, CodeReviewResultRow>(
+            r#"
+            SELECT * FROM code_review_results
+            WHERE story_id = ?
+            ORDER BY reviewed_at DESC
+            "#,
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(story_id)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.into_attempt()).transpose()
+        rows.into_iter().map(|r| r.into_result()).collect()
     }
 
-    /// Update a recovery attempt
-    pub async fn update_recovery_attempt(
+    /// Get latest code review result for a story
+    pub async fn get_latest_code_review_result(
         &self,
-        attempt: &crate::recovery::RecoveryAttempt,
-    ) -> Result<()> {
-        sqlx::query(
+        story_id: &str,
+    ) -> Result<Option<crate::work_evaluation::ReviewResult>> {
+        let row = sqlx::query_as::<_, CodeReviewResultRow>(
             r#"
-            UPDATE recovery_attempts
-            SET outcome = ?, completed_at = ?, error_message = ?
-            WHERE id = ?
+            SELECT * FROM code_review_results
+            WHERE story_id = ?
+            ORDER BY reviewed_at DESC
+            LIMIT 1
             "#,
         )
-        .bind(attempt.outcome.as_str())
-        .bind(attempt.completed_at.map(|dt| dt.to_rfc3339()))
-        .bind(&attempt.error_message)
-        .bind(attempt.id)
-        .execute(&self.pool)
+        .bind(story_id)
+        .fetch_optional(&self.pool)
         .await?;
 
-        Ok(())
+        row.map(|r| r.into_result()).transpose()
     }
 
-    /// Get recovery attempts for an agent
-    pub async fn get_recovery_attempts_for_agent(
+    /// Get review iteration count for a story
+    pub async fn get_review_iteration_count(
         &self,
+        story_id: &str,
+    ) -> Result<u32> {
+        let count: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM code_review_results WHERE story_id = ?",
+        )
+        .bind(story_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.0 as u32)
+    }
+
+    /// Create a CI check result
+    pub async fn create_ci_check_result(
+        &self,
+        story_id: Option<&str>,
         agent_id: &str,
-    ) -> Result<Vec<crate::recovery::RecoveryAttempt>> {
-        let rows = sqlx::query_as::<_, RecoveryAttemptRow>(
+        session_id: Option<&str>,
+        check: &crate::work_evaluation::CiCheckResult,
+    ) -> Result<i64> {
+        let result = sqlx::query(
             r#"
-            SELECT * FROM recovery_attempts
-            WHERE agent_id = ?
-            ORDER BY started_at DESC
+            INSERT INTO ci_check_results
+                (story_id, agent_id, session_id, check_name, status, url,
+                 failure_details, duration_secs, checked_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, datetime('now'))
             "#,
         )
+        .bind(story_id)
         .bind(agent_id)
-        .fetch_all(&self.pool)
+        .bind(session_id)
+        .bind(&check.name)
+        .bind(check.status.as_str())
+        .bind(&check.url)
+        .bind(&check.failure_details)
+        .bind(check.duration_secs.map(|d| d as i64))
+        .execute(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_attempt()).collect()
+        Ok(result.last_insert_rowid())
     }
 
-    /// Get in-progress recovery attempts for an agent
-    pub async fn get_in_progress_recovery_attempts(
+    /// Get CI check results for a story
+    pub async fn get_ci_check_results(
         &self,
-        agent_id: &str,
-    ) -> Result<Vec<crate::recovery::RecoveryAttempt>> {
-        let rows = sqlx::query_as::<_, RecoveryAttemptRow>(
+        story_id: &str,
+    ) -> Result<Vec<crate::work_evaluation::CiCheckResult>> {
+        let rows = sqlx::query_as::<_, CiCheckResultRow>(
             r#"
-            SELECT * FROM recovery_attempts
-            WHERE agent_id = ? AND outcome = 'in_progress'
-            ORDER BY started_at DESC
+            SELECT * FROM ci_check_results
+            WHERE story_id = ?
+            ORDER BY checked_at DESC
             "#,
         )
-        .bind(agent_id)
+        .bind(story_id)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_attempt()).collect()
+        rows.into_iter().map(|r| r.into_result()).collect()
     }
 
-    /// Count recovery attempts by action type for an agent
-    pub async fn count_recovery_attempts_by_type(
+    /// Get latest CI check results for a story (one per check name)
+    pub async fn get_latest_ci_check_results(
         &self,
-        agent_id: &str,
-    ) -> Result<std::collections::HashMap<String, u32>> {
-        let rows: Vec<(String, i64)> = sqlx::query_as(
+        story_id: &str,
+    ) -> Result<Vec<crate::work_evaluation::CiCheckResult>> {
+        // Use MAX(id) to get the latest record per check_name, which is more reliable than timestamp
+        let rows = sqlx::query_as::<_, CiCheckResultRow>(
             r#"
-            SELECT action_type, COUNT(*) as count
-            FROM recovery_attempts
-            WHERE agent_id = ?
-            GROUP BY action_type
+            SELECT c1.* FROM ci_check_results c1
+            INNER JOIN (
+                SELECT check_name, MAX(id) as max_id
+                FROM ci_check_results
+                WHERE story_id = ?
+                GROUP BY check_name
+            ) c2 ON c1.check_name = c2.check_name AND c1.id = c2.max_id
+            WHERE c1.story_id = ?
             "#,
         )
-        .bind(agent_id)
+        .bind(story_id)
+        .bind(story_id)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|(k, v)| (k, v as u32))
-            .collect())
+        rows.into_iter().map(|r| r.into_result()).collect()
     }
 
-    /// Get recovery statistics for an agent
-    pub async fn get_recovery_stats(
+    /// Get story evaluation summary stats
+    pub async fn get_story_evaluation_stats(
         &self,
-        agent_id: &str,
-    ) -> Result<RecoveryStats> {
-        let total: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM recovery_attempts WHERE agent_id = ?",
-        )
-        .bind(agent_id)
-        .fetch_one(&self.pool)
-        .await?;
+        session_id: Option<&str>,
+    ) -> Result<StoryEvaluationStats> {
+        let base_query = if session_id.is_some() {
+            "SELECT status, COUNT(*) as count FROM story_evaluations WHERE session_id = ? GROUP BY status"
+        } else {
+            "SELECT status, COUNT(*) as count FROM story_evaluations GROUP BY status"
+        };
 
-        let successful: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM recovery_attempts WHERE agent_id = ? AND outcome = 'success'",
-        )
-        .bind(agent_id)
-        .fetch_one(&self.pool)
-        .await?;
+        let rows: Vec<(String, i64)> = if let Some(sid) = session_id {
+            sqlx::query_as(base_query)
+                .bind(sid)
+                .fetch_all(&self.pool)
+                .await?
+        } else {
+            sqlx::query_as("SELECT status, COUNT(*) as count FROM story_evaluations GROUP BY status")
+                .fetch_all(&self.pool)
+                .await?
+        };
 
-        let failed: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM recovery_attempts WHERE agent_id = ? AND outcome = 'failed'",
-        )
-        .bind(agent_id)
-        .fetch_one(&self.pool)
-        .await?;
+        let mut stats = StoryEvaluationStats::default();
+        for (status, count) in rows {
+            match status.as_str() {
+                "complete" => stats.complete = count as u32,
+                "in_progress" => stats.in_progress = count as u32,
+                "blocked" => stats.blocked = count as u32,
+                "failed" => stats.failed = count as u32,
+                "needs_review" => stats.needs_review = count as u32,
+                "needs_review_fixes" => stats.needs_review_fixes = count as u32,
+                "needs_ci_fixes" => stats.needs_ci_fixes = count as u32,
+                "needs_pr_approval" => stats.needs_pr_approval = count as u32,
+                "ready_to_merge" => stats.ready_to_merge = count as u32,
+                _ => {}
+            }
+        }
+        stats.total = stats.complete + stats.in_progress + stats.blocked + stats.failed
+            + stats.needs_review + stats.needs_review_fixes + stats.needs_ci_fixes
+            + stats.needs_pr_approval + stats.ready_to_merge;
 
-        Ok(RecoveryStats {
-            total_attempts: total.0 as u32,
-            successful: successful.0 as u32,
-            failed: failed.0 as u32,
-            success_rate: if total.0 > 0 {
-                successful.0 as f64 / total.0 as f64
-            } else {
-                0.0
-            },
-        })
+        Ok(stats)
     }
 
-    // ==================== Story Evaluation Operations (Epic 016 - Story 8) ====================
+    // ==================== Review Iteration Operations (Epic 016 - Story 9) ====================
 
-    /// Create a story evaluation record
-    pub async fn create_story_evaluation(
+    /// Create a review iteration
+    pub async fn create_review_iteration(
         &self,
-        record: &crate::work_evaluation::StoryEvaluationRecord,
+        iteration: &crate::code_review::ReviewIteration,
     ) -> Result<i64> {
         let result = sqlx::query(
             r#"
-            INSERT INTO story_evaluations
-                (story_id, agent_id, session_id, status, criteria_met_count,
-                 criteria_total_count, ci_passed, review_passed, review_iteration,
-                 pr_mergeable, feedback, details, evaluated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO review_iterations
+                (story_id, iteration, reviewer_type, reviewer, verdict,
+                 issue_count, blocking_issue_count, escalation_level,
+                 duration_secs, started_at, completed_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(&record.story_id)
-        .bind(&record.agent_id)
-        .bind(&record.session_id)
-        .bind(record.status.as_str())
-        .bind(record.criteria_met_count as i64)
-        .bind(record.criteria_total_count as i64)
-        .bind(record.ci_passed)
-        .bind(record.review_passed)
-        .bind(record.review_iteration as i64)
-        .bind(record.pr_mergeable)
-        .bind(&record.feedback)
-        .bind(serde_json::to_string(&record.details)?)
-        .bind(record.evaluated_at.to_rfc3339())
+        .bind(&iteration.story_id)
+        .bind(iteration.iteration as i64)
+        .bind(iteration.reviewer_type.as_str())
+        .bind(&iteration.reviewer)
+        .bind(iteration.verdict.as_str())
+        .bind(iteration.issue_count as i64)
+        .bind(iteration.blocking_issue_count as i64)
+        .bind(iteration.escalation_level.as_str())
+        .bind(iteration.duration_secs.map(|d| d as i64))
+        .bind(iteration.started_at.to_rfc3339())
+        .bind(iteration.completed_at.map(|t| t.to_rfc3339()))
         .execute(&self.pool)
         .await?;
 
         Ok(result.last_insert_rowid())
     }
 
-    /// Get story evaluation by ID
-    pub async fn get_story_evaluation(
+    /// Update a review iteration
+    pub async fn update_review_iteration(
         &self,
-        id: i64,
-    ) -> Result<Option<crate::work_evaluation::StoryEvaluationRecord>> {
-        let row = sqlx::query_as::<_, StoryEvaluationRow>(
-            "SELECT * FROM story_evaluations WHERE id = ?",
+        iteration: &crate::code_review::ReviewIteration,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE review_iterations SET
+                reviewer = ?,
+                verdict = ?,
+                issue_count = ?,
+                blocking_issue_count = ?,
+                escalation_level = ?,
+                duration_secs = ?,
+                completed_at = ?
+            WHERE id = ?
+            "#,
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(&iteration.reviewer)
+        .bind(iteration.verdict.as_str())
+        .bind(iteration.issue_count as i64)
+        .bind(iteration.blocking_issue_count as i64)
+        .bind(iteration.escalation_level.as_str())
+        .bind(iteration.duration_secs.map(|d| d as i64))
+        .bind(iteration.completed_at.map(|t| t.to_rfc3339()))
+        .bind(iteration.id)
+        .execute(&self.pool)
         .await?;
 
-        row.map(|r| r.into_record()).transpose()
+        Ok(())
     }
 
-    /// Get story evaluations for a story
-    pub async fn get_story_evaluations(
+    /// Get review iterations for a story
+    pub async fn get_review_iterations(
         &self,
         story_id: &str,
-    ) -> Result<Vec<crate::work_evaluation::StoryEvaluationRecord>> {
-        let rows = sqlx::query_as::<_, StoryEvaluationRow>(
+    ) -> Result<Vec<crate::code_review::ReviewIteration>> {
+        let rows = sqlx::query_as::<_, ReviewIterationRow>(
             r#"
-            SELECT * FROM story_evaluations
+            SELECT * FROM review_iterations
             WHERE story_id = ?
-            ORDER BY evaluated_at DESC
+            ORDER BY iteration ASC
             "#,
         )
         .bind(story_id)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_record()).collect()
+        rows.into_iter().map(|r| r.into_iteration()).collect()
     }
 
-    /// Get latest story evaluation for a story
-    pub async fn get_latest_story_evaluation(
+    /// Get latest review iteration for a story
+    pub async fn get_latest_review_iteration(
         &self,
         story_id: &str,
-    ) -> Result<Option<crate::work_evaluation::StoryEvaluationRecord>> {
-        let row = sqlx::query_as::<_, StoryEvaluationRow>(
+    ) -> Result<Option<crate::code_review::ReviewIteration>> {
+        let row = sqlx::query_as::<_, ReviewIterationRow>(
             r#"
-            SELECT * FROM story_evaluations
+            SELECT * FROM review_iterations
             WHERE story_id = ?
-            ORDER BY evaluated_at DESC
+            ORDER BY iteration DESC
             LIMIT 1
             "#,
         )
@@ -8105,771 +12928,1056 @@ impl Database {
         .fetch_optional(&self.pool)
         .await?;
 
-        row.map(|r| r.into_record()).transpose()
+        row.map(|r| r.into_iteration()).transpose()
     }
 
-    /// Get story evaluations for a session
-    pub async fn get_story_evaluations_for_session(
+    /// Get review iteration count for a story
+    pub async fn get_review_iteration_number(
         &self,
-        session_id: &str,
-    ) -> Result<Vec<crate::work_evaluation::StoryEvaluationRecord>> {
-        let rows = sqlx::query_as::<_, StoryEvaluationRow>(
-            r#"
-            SELECT * FROM story_evaluations
-            WHERE session_id = ?
-            ORDER BY evaluated_at DESC
-            "#,
+        story_id: &str,
+    ) -> Result<u32> {
+        let count: (i64,) = sqlx::query_as(
+            "SELECT COALESCE(MAX(iteration), 0) FROM review_iterations WHERE story_id = ?",
         )
-        .bind(session_id)
-        .fetch_all(&self.pool)
+        .bind(story_id)
+        .fetch_one(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_record()).collect()
+        Ok(count.0 as u32)
     }
 
-    /// Create a code review result
-    pub async fn create_code_review_result(
+    /// Create a review request
+    pub async fn create_review_request(
         &self,
-        story_id: &str,
-        agent_id: &str,
-        session_id: Option<&str>,
-        result: &crate::work_evaluation::ReviewResult,
+        request: &crate::code_review::ReviewRequest,
     ) -> Result<i64> {
-        let blocking_count = result.issues.iter().filter(|i| i.severity.blocks_merge()).count() as i64;
-
-        let result_row = sqlx::query(
+        let result = sqlx::query(
             r#"
-            INSERT INTO code_review_results
-                (story_id, agent_id, session_id, verdict, reviewer, iteration,
-                 issue_count, blocking_issue_count, issues, raw_output, reviewed_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO review_requests
+                (story_id, agent_id, session_id, branch, base_branch, pr_number,
+                 iteration, changed_files, criteria, previous_issues, status, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'pending', ?, datetime('now'))
             "#,
         )
-        .bind(story_id)
-        .bind(agent_id)
-        .bind(session_id)
-        .bind(result.verdict.as_str())
-        .bind(&result.reviewer)
-        .bind(result.iteration as i64)
-        .bind(result.issues.len() as i64)
-        .bind(blocking_count)
-        .bind(serde_json::to_string(&result.issues)?)
-        .bind(&result.raw_output)
-        .bind(result.reviewed_at.to_rfc3339())
+        .bind(&request.story_id)
+        .bind(&request.agent_id)
+        .bind(&request.session_id)
+        .bind(&request.branch)
+        .bind(&request.base_branch)
+        .bind(request.pr_number.map(|n| n as i64))
+        .bind(request.iteration as i64)
+        .bind(serde_json::to_string(&request.changed_files)?)
+        .bind(serde_json::to_string(&request.criteria)?)
+        .bind(serde_json::to_string(&request.previous_issues)?)
+        .bind(request.created_at.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        Ok(result_row.last_insert_rowid())
+        Ok(result.last_insert_rowid())
     }
-
-    /// Get code review results for a story
-    pub async fn get_code_review_results(
+
+    /// Get pending review requests
+    pub async fn get_pending_review_requests(
         &self,
-        story_id: &str,
-    ) -> Result<Vec<crate::work_evaluation::ReviewResult>> {
-        let rows = sqlx::query_as::<_, CodeReviewResultRow>(
+    ) -> Result<Vec<crate::code_review::ReviewRequest>> {
+        let rows = sqlx::query_as::<_, ReviewRequestRow>(
             r#"
-            SELECT * FROM code_review_results
-            WHERE story_id = ?
-            ORDER BY reviewed_at DESC
+            SELECT * FROM review_requests
+            WHERE status = 'pending'
+            ORDER BY created_at ASC
             "#,
         )
-        .bind(story_id)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_result()).collect()
+        rows.into_iter().map(|r| r.into_request()).collect()
     }
 
-    /// Get latest code review result for a story
-    pub async fn get_latest_code_review_result(
+    /// Update review request status
+    pub async fn update_review_request_status(
         &self,
-        story_id: &str,
-    ) -> Result<Option<crate::work_evaluation::ReviewResult>> {
-        let row = sqlx::query_as::<_, CodeReviewResultRow>(
-            r#"
-            SELECT * FROM code_review_results
-            WHERE story_id = ?
-            ORDER BY reviewed_at DESC
-            LIMIT 1
-            "#,
+        id: i64,
+        status: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE review_requests SET status = ?, updated_at = datetime('now') WHERE id = ?",
         )
-        .bind(story_id)
-        .fetch_optional(&self.pool)
+        .bind(status)
+        .bind(id)
+        .execute(&self.pool)
         .await?;
 
-        row.map(|r| r.into_result()).transpose()
+        Ok(())
     }
 
-    /// Get review iteration count for a story
-    pub async fn get_review_iteration_count(
+    /// Get review stats for a story
+    pub async fn get_review_stats(
         &self,
         story_id: &str,
-    ) -> Result<u32> {
-        let count: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM code_review_results WHERE story_id = ?",
+    ) -> Result<ReviewStats> {
+        let rows = sqlx::query_as::<_, ReviewIterationRow>(
+            "SELECT * FROM review_iterations WHERE story_id = ?",
         )
         .bind(story_id)
-        .fetch_one(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(count.0 as u32)
+        let total_iterations = rows.len() as u32;
+        let approved_count = rows.iter().filter(|r| r.verdict == "approved").count() as u32;
+        let changes_requested_count = rows.iter().filter(|r| r.verdict == "changes_requested").count() as u32;
+        let total_issues: u32 = rows.iter().map(|r| r.issue_count as u32).sum();
+        let total_blocking: u32 = rows.iter().map(|r| r.blocking_issue_count as u32).sum();
+
+        Ok(ReviewStats {
+            total_iterations,
+            approved_count,
+            changes_requested_count,
+            total_issues,
+            total_blocking_issues: total_blocking,
+        })
     }
 
-    /// Create a CI check result
-    pub async fn create_ci_check_result(
+    // ==================== Edge Case Operations (Epic 016 - Story 14) ====================
+
+    /// Create an edge case event
+    pub async fn create_edge_case_event(
         &self,
-        story_id: Option<&str>,
-        agent_id: &str,
-        session_id: Option<&str>,
-        check: &crate::work_evaluation::CiCheckResult,
+        event: &crate::edge_case_handler::EdgeCaseEvent,
     ) -> Result<i64> {
         let result = sqlx::query(
             r#"
-            INSERT INTO ci_check_results
-                (story_id, agent_id, session_id, check_name, status, url,
-                 failure_details, duration_secs, checked_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, datetime('now'))
+            INSERT INTO edge_case_events
+                (session_id, agent_id, story_id, edge_case_type, resolution,
+                 action_taken, retry_count, error_message, context,
+                 detected_at, resolved_at, resolution_notes)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(story_id)
-        .bind(agent_id)
-        .bind(session_id)
-        .bind(&check.name)
-        .bind(check.status.as_str())
-        .bind(&check.url)
-        .bind(&check.failure_details)
-        .bind(check.duration_secs.map(|d| d as i64))
+        .bind(&event.session_id)
+        .bind(&event.agent_id)
+        .bind(&event.story_id)
+        .bind(event.edge_case_type.as_str())
+        .bind(event.resolution.as_str())
+        .bind(&event.action_taken)
+        .bind(event.retry_count as i64)
+        .bind(&event.error_message)
+        .bind(serde_json::to_string(&event.context)?)
+        .bind(event.detected_at.to_rfc3339())
+        .bind(event.resolved_at.map(|dt| dt.to_rfc3339()))
+        .bind(&event.resolution_notes)
         .execute(&self.pool)
         .await?;
 
         Ok(result.last_insert_rowid())
     }
 
-    /// Get CI check results for a story
-    pub async fn get_ci_check_results(
+    /// Get an edge case event by ID
+    pub async fn get_edge_case_event(
         &self,
-        story_id: &str,
-    ) -> Result<Vec<crate::work_evaluation::CiCheckResult>> {
-        let rows = sqlx::query_as::<_, CiCheckResultRow>(
+        id: i64,
+    ) -> Result<Option<crate::edge_case_handler::EdgeCaseEvent>> {
+        let row = sqlx::query_as::<_, EdgeCaseEventRow>(
+            "SELECT * FROM edge_case_events WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.into_event()).transpose()
+    }
+
+    /// Update an edge case event
+    pub async fn update_edge_case_event(
+        &self,
+        event: &crate::edge_case_handler::EdgeCaseEvent,
+    ) -> Result<()> {
+        sqlx::query(
             r#"
-            SELECT * FROM ci_check_results
-            WHERE story_id = ?
-            ORDER BY checked_at DESC
+            UPDATE edge_case_events
+            SET resolution = ?, action_taken = ?, retry_count = ?,
+                resolved_at = ?, resolution_notes = ?
+            WHERE id = ?
             "#,
         )
-        .bind(story_id)
-        .fetch_all(&self.pool)
+        .bind(event.resolution.as_str())
+        .bind(&event.action_taken)
+        .bind(event.retry_count as i64)
+        .bind(event.resolved_at.map(|dt| dt.to_rfc3339()))
+        .bind(&event.resolution_notes)
+        .bind(event.id)
+        .execute(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_result()).collect()
+        Ok(())
     }
 
-    /// Get latest CI check results for a story (one per check name)
-    pub async fn get_latest_ci_check_results(
+    /// Get edge case events for a session
+    pub async fn get_edge_case_events_for_session(
         &self,
-        story_id: &str,
-    ) -> Result<Vec<crate::work_evaluation::CiCheckResult>> {
-        // Use MAX(id) to get the latest record per check_name, which is more reliable than timestamp
-        let rows = sqlx::query_as::<_, CiCheckResultRow>(
+        session_id: &str,
+    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseEvent>> {
+        let rows = sqlx::query_as::<_, EdgeCaseEventRow>(
             r#"
-            SELECT c1.* FROM ci_check_results c1
-            INNER JOIN (
-                SELECT check_name, MAX(id) as max_id
-                FROM ci_check_results
-                WHERE story_id = ?
-                GROUP BY check_name
-            ) c2 ON c1.check_name = c2.check_name AND c1.id = c2.max_id
-            WHERE c1.story_id = ?
+            SELECT * FROM edge_case_events
+            WHERE session_id = ?
+            ORDER BY detected_at DESC
             "#,
         )
-        .bind(story_id)
-        .bind(story_id)
+        .bind(session_id)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_result()).collect()
+        rows.into_iter().map(|r| r.into_event()).collect()
     }
 
-    /// Get story evaluation summary stats
-    pub async fn get_story_evaluation_stats(
+    /// One page of a session's edge case events, or of all unresolved ones,
+    /// newest first
+    pub async fn list_edge_case_events_page(
         &self,
         session_id: Option<&str>,
-    ) -> Result<StoryEvaluationStats> {
-        let base_query = if session_id.is_some() {
-            "SELECT status, COUNT(*) as count FROM story_evaluations WHERE session_id = ? GROUP BY status"
-        } else {
-            "SELECT status, COUNT(*) as count FROM story_evaluations GROUP BY status"
-        };
-
-        let rows: Vec<(String, i64)> = if let Some(sid) = session_id {
-            sqlx::query_as(base_query)
-                .bind(sid)
-                .fetch_all(&self.pool)
-                .await?
-        } else {
-            sqlx::query_as("SELECT status, COUNT(*) as count FROM story_evaluations GROUP BY status")
-                .fetch_all(&self.pool)
-                .await?
-        };
-
-        let mut stats = StoryEvaluationStats::default();
-        for (status, count) in rows {
-            match status.as_str() {
-                "complete" => stats.complete = count as u32,
-                "in_progress" => stats.in_progress = count as u32,
-                "blocked" => stats.blocked = count as u32,
-                "failed" => stats.failed = count as u32,
-                "needs_review" => stats.needs_review = count as u32,
-                "needs_review_fixes" => stats.needs_review_fixes = count as u32,
-                "needs_ci_fixes" => stats.needs_ci_fixes = count as u32,
-                "needs_pr_approval" => stats.needs_pr_approval = count as u32,
-                "ready_to_merge" => stats.ready_to_merge = count as u32,
-                _ => {}
-            }
-        }
-        stats.total = stats.complete + stats.in_progress + stats.blocked + stats.failed
-            + stats.needs_review + stats.needs_review_fixes + stats.needs_ci_fixes
-            + stats.needs_pr_approval + stats.ready_to_merge;
-
-        Ok(stats)
+        page: &PageQuery,
+    ) -> Result<RowPage<crate::edge_case_handler::EdgeCaseEvent>> {
+        self.fetch_page::<EdgeCaseEventRow, _>(
+            "SELECT * FROM edge_case_events",
+            |q| match session_id {
+                Some(session_id) => {
+                    q.push(" AND session_id = ").push_bind(session_id.to_string());
+                }
+                None => {
+                    q.push(
+                        " AND resolution NOT IN ('auto_resolved', 'manual_resolved', 'bypassed')",
+                    );
+                }
+            },
+            SortKey::desc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(|r| r.into_event())
     }
 
-    // ==================== Review Iteration Operations (Epic 016 - Story 9) ====================
-
-    /// Create a review iteration
-    pub async fn create_review_iteration(
+    /// Get edge case events for an agent
+    pub async fn get_edge_case_events_for_agent(
         &self,
-        iteration: &crate::code_review::ReviewIteration,
-    ) -> Result<i64> {
-        let result = sqlx::query(
+        agent_id: &str,
+    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseEvent>> {
+        let rows = sqlx::query_as::<_, EdgeCaseEventRow>(
             r#"
-            INSERT INTO review_iterations
-                (story_id, iteration, reviewer_type, reviewer, verdict,
-                 issue_count, blocking_issue_count, escalation_level,
-                 duration_secs, started_at, completed_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            SELECT * FROM edge_case_events
+            WHERE agent_id = ?
+            ORDER BY detected_at DESC
             "#,
         )
-        .bind(&iteration.story_id)
-        .bind(iteration.iteration as i64)
-        .bind(iteration.reviewer_type.as_str())
-        .bind(&iteration.reviewer)
-        .bind(iteration.verdict.as_str())
-        .bind(iteration.issue_count as i64)
-        .bind(iteration.blocking_issue_count as i64)
-        .bind(iteration.escalation_level.as_str())
-        .bind(iteration.duration_secs.map(|d| d as i64))
-        .bind(iteration.started_at.to_rfc3339())
-        .bind(iteration.completed_at.map(|t| t.to_rfc3339()))
-        .execute(&self.pool)
+        .bind(agent_id)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(result.last_insert_rowid())
+        rows.into_iter().map(|r| r.into_event()).collect()
     }
 
-    /// Update a review iteration
-    pub async fn update_review_iteration(
+    /// Get unresolved edge case events
+    pub async fn get_unresolved_edge_case_events(
         &self,
-        iteration: &crate::code_review::ReviewIteration,
-    ) -> Result<()> {
-        sqlx::query(
+    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseEvent>> {
+        let rows = sqlx::query_as::<_, EdgeCaseEventRow>(
             r#"
-            UPDATE review_iterations SET
-                reviewer = ?,
-                verdict = ?,
-                issue_count = ?,
-                blocking_issue_count = ?,
-                escalation_level = ?,
-                duration_secs = ?,
-                completed_at = ?
-            WHERE id = ?
+            SELECT * FROM edge_case_events
+            WHERE resolution NOT IN ('auto_resolved', 'manual_resolved', 'bypassed')
+            ORDER BY detected_at ASC
             "#,
         )
-        .bind(&iteration.reviewer)
-        .bind(iteration.verdict.as_str())
-        .bind(iteration.issue_count as i64)
-        .bind(iteration.blocking_issue_count as i64)
-        .bind(iteration.escalation_level.as_str())
-        .bind(iteration.duration_secs.map(|d| d as i64))
-        .bind(iteration.completed_at.map(|t| t.to_rfc3339()))
-        .bind(iteration.id)
-        .execute(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(())
+        rows.into_iter().map(|r| r.into_event()).collect()
     }
 
-    /// Get review iterations for a story
-    pub async fn get_review_iterations(
+    /// Get edge case events by type
+    pub async fn get_edge_case_events_by_type(
         &self,
-        story_id: &str,
-    ) -> Result<Vec<crate::code_review::ReviewIteration>> {
-        let rows = sqlx::query_as::<_, ReviewIterationRow>(
+        edge_case_type: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseEvent>> {
+        let limit_val = limit.unwrap_or(100) as i64;
+        let rows = sqlx::query_as::<_, EdgeCaseEventRow>(
             r#"
-            SELECT * FROM review_iterations
-            WHERE story_id = ?
-            ORDER BY iteration ASC
+            SELECT * FROM edge_case_events
+            WHERE edge_case_type = ?
+            ORDER BY detected_at DESC
+            LIMIT ?
             "#,
         )
-        .bind(story_id)
+        .bind(edge_case_type)
+        .bind(limit_val)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_iteration()).collect()
+        rows.into_iter().map(|r| r.into_event()).collect()
     }
 
-    /// Get latest review iteration for a story
-    pub async fn get_latest_review_iteration(
-        &self,
-        story_id: &str,
-    ) -> Result<Option<crate::code_review::ReviewIteration>> {
-        let row = sqlx::query_as::<_, ReviewIterationRow>(
+    /// Get edge case statistics
+    pub async fn get_edge_case_stats(&self) -> Result<EdgeCaseStats> {
+        let total: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM edge_case_events",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let resolved: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM edge_case_events WHERE resolution IN ('auto_resolved', 'manual_resolved', 'bypassed')",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let pending: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM edge_case_events WHERE resolution IN ('pending', 'retrying', 'waiting')",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let failed: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM edge_case_events WHERE resolution = 'failed'",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let by_type_rows: Vec<(String, i64)> = sqlx::query_as(
             r#"
-            SELECT * FROM review_iterations
-            WHERE story_id = ?
-            ORDER BY iteration DESC
-            LIMIT 1
+            SELECT edge_case_type, COUNT(*) as count
+            FROM edge_case_events
+            GROUP BY edge_case_type
             "#,
         )
-        .bind(story_id)
-        .fetch_optional(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.into_iteration()).transpose()
-    }
-
-    /// Get review iteration count for a story
-    pub async fn get_review_iteration_number(
-        &self,
-        story_id: &str,
-    ) -> Result<u32> {
-        let count: (i64,) = sqlx::query_as(
-            "SELECT COALESCE(MAX(iteration), 0) FROM review_iterations WHERE story_id = ?",
+        let avg_time: (Option<f64>,) = sqlx::query_as(
+            r#"
+            SELECT AVG(
+                CAST((julianday(resolved_at) - julianday(detected_at)) * 86400 AS REAL)
+            )
+            FROM edge_case_events
+            WHERE resolved_at IS NOT NULL
+            "#,
         )
-        .bind(story_id)
         .fetch_one(&self.pool)
         .await?;
 
-        Ok(count.0 as u32)
+        Ok(EdgeCaseStats {
+            total: total.0 as u32,
+            resolved: resolved.0 as u32,
+            pending: pending.0 as u32,
+            failed: failed.0 as u32,
+            by_type: by_type_rows.into_iter().map(|(k, v)| (k, v as u32)).collect(),
+            avg_resolution_time_seconds: avg_time.0,
+        })
     }
 
-    /// Create a review request
-    pub async fn create_review_request(
+    /// Create or update edge case learning record
+    pub async fn upsert_edge_case_learning(
         &self,
-        request: &crate::code_review::ReviewRequest,
+        learning: &crate::edge_case_handler::EdgeCaseLearning,
     ) -> Result<i64> {
-        let result = sqlx::query(
-            r#"
-            INSERT INTO review_requests
-                (story_id, agent_id, session_id, branch, base_branch, pr_number,
-                 iteration, changed_files, criteria, previous_issues, status, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'pending', ?, datetime('now'))
-            "#,
+        let existing = sqlx::query_as::<_, (i64,)>(
+            "SELECT id FROM edge_case_learnings WHERE edge_case_type = ? AND pattern = ?",
         )
-        .bind(&request.story_id)
-        .bind(&request.agent_id)
-        .bind(&request.session_id)
-        .bind(&request.branch)
-        .bind(&request.base_branch)
-        .bind(request.pr_number.map(|n| n as i64))
-        .bind(request.iteration as i64)
-        .bind(serde_json::to_string(&request.changed_files)?)
-        .bind(serde_json::to_string(&request.criteria)?)
-        .bind(serde_json::to_string(&request.previous_issues)?)
-        .bind(request.created_at.to_rfc3339())
-        .execute(&self.pool)
+        .bind(learning.edge_case_type.as_str())
+        .bind(&learning.pattern)
+        .fetch_optional(&self.pool)
         .await?;
 
-        Ok(result.last_insert_rowid())
+        if let Some((id,)) = existing {
+            // Update existing
+            sqlx::query(
+                r#"
+                UPDATE edge_case_learnings
+                SET success_rate = ?, avg_resolution_time_seconds = ?,
+                    recommended_action = ?, occurrence_count = ?,
+                    last_occurrence = ?, updated_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(learning.success_rate)
+            .bind(learning.avg_resolution_time_seconds)
+            .bind(&learning.recommended_action)
+            .bind(learning.occurrence_count as i64)
+            .bind(learning.last_occurrence.to_rfc3339())
+            .bind(learning.updated_at.to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+            Ok(id)
+        } else {
+            // Insert new
+            let result = sqlx::query(
+                r#"
+                INSERT INTO edge_case_learnings
+                    (edge_case_type, pattern, success_rate, avg_resolution_time_seconds,
+                     recommended_action, occurrence_count, last_occurrence, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(learning.edge_case_type.as_str())
+            .bind(&learning.pattern)
+            .bind(learning.success_rate)
+            .bind(learning.avg_resolution_time_seconds)
+            .bind(&learning.recommended_action)
+            .bind(learning.occurrence_count as i64)
+            .bind(learning.last_occurrence.to_rfc3339())
+            .bind(learning.created_at.to_rfc3339())
+            .bind(learning.updated_at.to_rfc3339())
+            .execute(&self.pool)
+            .await?;
+
+            Ok(result.last_insert_rowid())
+        }
     }
 
-    /// Get pending review requests
-    pub async fn get_pending_review_requests(
+    /// Get edge case learning by type and pattern
+    pub async fn get_edge_case_learning(
         &self,
-    ) -> Result<Vec<crate::code_review::ReviewRequest>> {
-        let rows = sqlx::query_as::<_, ReviewRequestRow>(
-            r#"
-            SELECT * FROM review_requests
-            WHERE status = 'pending'
-            ORDER BY created_at ASC
-            "#,
+        edge_case_type: &str,
+        pattern: &str,
+    ) -> Result<Option<crate::edge_case_handler::EdgeCaseLearning>> {
+        let row = sqlx::query_as::<_, EdgeCaseLearningRow>(
+            "SELECT * FROM edge_case_learnings WHERE edge_case_type = ? AND pattern = ?",
         )
-        .fetch_all(&self.pool)
+        .bind(edge_case_type)
+        .bind(pattern)
+        .fetch_optional(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_request()).collect()
+        row.map(|r| r.into_learning()).transpose()
     }
 
-    /// Update review request status
-    pub async fn update_review_request_status(
+    /// Get all edge case learnings for a type
+    pub async fn get_edge_case_learnings_by_type(
         &self,
-        id: i64,
-        status: &str,
-    ) -> Result<()> {
-        sqlx::query(
-            "UPDATE review_requests SET status = ?, updated_at = datetime('now') WHERE id = ?",
+        edge_case_type: &str,
+    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseLearning>> {
+        let rows = sqlx::query_as::<_, EdgeCaseLearningRow>(
+            r#"
+            SELECT * FROM edge_case_learnings
+            WHERE edge_case_type = ?
+            ORDER BY occurrence_count DESC
+            "#,
         )
-        .bind(status)
-        .bind(id)
-        .execute(&self.pool)
+        .bind(edge_case_type)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(())
+        rows.into_iter().map(|r| r.into_learning()).collect()
     }
 
-    /// Get review stats for a story
-    pub async fn get_review_stats(
+    /// Get top edge case learnings by success rate
+    pub async fn get_top_edge_case_learnings(
         &self,
-        story_id: &str,
-    ) -> Result<ReviewStats> {
-        let rows = sqlx::query_as::<_, ReviewIterationRow>(
-            "SELECT * FROM review_iterations WHERE story_id = ?",
+        limit: u32,
+    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseLearning>> {
+        let rows = sqlx::query_as::<_, EdgeCaseLearningRow>(
+            r#"
+            SELECT * FROM edge_case_learnings
+            WHERE occurrence_count >= 3
+            ORDER BY success_rate DESC, occurrence_count DESC
+            LIMIT ?
+            "#,
         )
-        .bind(story_id)
+        .bind(limit as i64)
         .fetch_all(&self.pool)
         .await?;
 
-        let total_iterations = rows.len() as u32;
-        let approved_count = rows.iter().filter(|r| r.verdict == "approved").count() as u32;
-        let changes_requested_count = rows.iter().filter(|r| r.verdict == "changes_requested").count() as u32;
-        let total_issues: u32 = rows.iter().map(|r| r.issue_count as u32).sum();
-        let total_blocking: u32 = rows.iter().map(|r| r.blocking_issue_count as u32).sum();
-
-        Ok(ReviewStats {
-            total_iterations,
-            approved_count,
-            changes_requested_count,
-            total_issues,
-            total_blocking_issues: total_blocking,
-        })
+        rows.into_iter().map(|r| r.into_learning()).collect()
     }
 
-    // ==================== Edge Case Operations (Epic 016 - Story 14) ====================
+    // ==================== Agent Event Operations ====================
 
-    /// Create an edge case event
-    pub async fn create_edge_case_event(
+    /// Append an event to an agent's event log
+    pub async fn insert_agent_event(
         &self,
-        event: &crate::edge_case_handler::EdgeCaseEvent,
+        event: &crate::agent_event::AgentEvent,
     ) -> Result<i64> {
         let result = sqlx::query(
             r#"
-            INSERT INTO edge_case_events
-                (session_id, agent_id, story_id, edge_case_type, resolution,
-                 action_taken, retry_count, error_message, context,
-                 detected_at, resolved_at, resolution_notes)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO agent_events (agent_id, session_id, event_type, summary, data, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(&event.session_id)
         .bind(&event.agent_id)
-        .bind(&event.story_id)
-        .bind(event.edge_case_type.as_str())
-        .bind(event.resolution.as_str())
-        .bind(&event.action_taken)
-        .bind(event.retry_count as i64)
-        .bind(&event.error_message)
-        .bind(serde_json::to_string(&event.context)?)
-        .bind(event.detected_at.to_rfc3339())
-        .bind(event.resolved_at.map(|dt| dt.to_rfc3339()))
-        .bind(&event.resolution_notes)
+        .bind(&event.session_id)
+        .bind(event.event_type.as_str())
+        .bind(&event.summary)
+        .bind(serde_json::to_string(&event.data)?)
+        .bind(event.created_at.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
         Ok(result.last_insert_rowid())
     }
 
-    /// Get an edge case event by ID
-    pub async fn get_edge_case_event(
+    /// Get the event log for an agent in chronological order, optionally
+    /// restricted to a single event type
+    pub async fn get_agent_events(
         &self,
-        id: i64,
-    ) -> Result<Option<crate::edge_case_handler::EdgeCaseEvent>> {
-        let row = sqlx::query_as::<_, EdgeCaseEventRow>(
-            "SELECT * FROM edge_case_events WHERE id = ?",
+        agent_id: &str,
+        event_type: Option<crate::agent_event::AgentEventType>,
+    ) -> Result<Vec<crate::agent_event::AgentEvent>> {
+        let rows = sqlx::query_as::<_, AgentEventRow>(
+            r#"
+            SELECT id, agent_id, session_id, event_type, summary, data, created_at
+            FROM agent_events
+            WHERE agent_id = ? AND (? IS NULL OR event_type = ?)
+            ORDER BY created_at ASC, id ASC
+            "#,
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(agent_id)
+        .bind(event_type.map(|t| t.as_str()))
+        .bind(event_type.map(|t| t.as_str()))
+        .fetch_all(&self.pool)
         .await?;
 
-        row.map(|r| r.into_event()).transpose()
+        rows.into_iter().map(|r| r.into_event()).collect()
     }
 
-    /// Update an edge case event
-    pub async fn update_edge_case_event(
+    /// One page of an agent's events, oldest first
+    pub async fn get_agent_events_page(
         &self,
-        event: &crate::edge_case_handler::EdgeCaseEvent,
-    ) -> Result<()> {
+        agent_id: &str,
+        event_type: Option<crate::agent_event::AgentEventType>,
+        page: &PageQuery,
+    ) -> Result<RowPage<crate::agent_event::AgentEvent>> {
+        self.fetch_page::<AgentEventRow, _>(
+            "SELECT id, agent_id, session_id, event_type, summary, data, created_at FROM agent_events",
+            |q| {
+                q.push(" AND agent_id = ").push_bind(agent_id.to_string());
+                if let Some(event_type) = event_type {
+                    q.push(" AND event_type = ").push_bind(event_type.as_str());
+                }
+            },
+            SortKey::asc(&["id"]),
+            page,
+        )
+        .await?
+        .try_map(|r| r.into_event())
+    }
+
+    /// Delete all events for an agent
+    pub async fn delete_agent_events(&self, agent_id: &str) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM agent_events WHERE agent_id = ?")
+            .bind(agent_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // ==================== Custom Agent Type Operations ====================
+
+    /// Insert or update a custom agent type definition (keyed by name)
+    pub async fn upsert_agent_type_definition(
+        &self,
+        def: &crate::agent_type_definition::AgentTypeDefinition,
+    ) -> Result<i64> {
+        def.validate()?;
+
         sqlx::query(
             r#"
-            UPDATE edge_case_events
-            SET resolution = ?, action_taken = ?, retry_count = ?,
-                resolved_at = ?, resolution_notes = ?
-            WHERE id = ?
+            INSERT INTO custom_agent_types
+                (name, description, base_type, system_prompt, skills, allowed_tools,
+                 model, max_turns, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(name) DO UPDATE SET
+                description = excluded.description,
+                base_type = excluded.base_type,
+                system_prompt = excluded.system_prompt,
+                skills = excluded.skills,
+                allowed_tools = excluded.allowed_tools,
+                model = excluded.model,
+                max_turns = excluded.max_turns,
+                updated_at = excluded.updated_at
             "#,
         )
-        .bind(event.resolution.as_str())
-        .bind(&event.action_taken)
-        .bind(event.retry_count as i64)
-        .bind(event.resolved_at.map(|dt| dt.to_rfc3339()))
-        .bind(&event.resolution_notes)
-        .bind(event.id)
+        .bind(&def.name)
+        .bind(&def.description)
+        .bind(def.base_type.as_str())
+        .bind(&def.system_prompt)
+        .bind(serde_json::to_string(&def.skills)?)
+        .bind(serde_json::to_string(&def.allowed_tools)?)
+        .bind(&def.model)
+        .bind(def.max_turns.map(|v| v as i64))
+        .bind(def.created_at.to_rfc3339())
+        .bind(chrono::Utc::now().to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        Ok(())
+        let id: i64 = sqlx::query_scalar("SELECT id FROM custom_agent_types WHERE name = ?")
+            .bind(&def.name)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(id)
     }
 
-    /// Get edge case events for a session
-    pub async fn get_edge_case_events_for_session(
+    /// Get a custom agent type definition by name
+    pub async fn get_agent_type_definition(
         &self,
-        session_id: &str,
-    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseEvent>> {
-        let rows = sqlx::query_as::<_, EdgeCaseEventRow>(
-            r#"
-            SELECT * FROM edge_case_events
-            WHERE session_id = ?
-            ORDER BY detected_at DESC
-            "#,
+        name: &str,
+    ) -> Result<Option<crate::agent_type_definition::AgentTypeDefinition>> {
+        let row = sqlx::query_as::<_, AgentTypeDefinitionRow>(
+            "SELECT * FROM custom_agent_types WHERE name = ?",
         )
-        .bind(session_id)
-        .fetch_all(&self.pool)
+        .bind(name)
+        .fetch_optional(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_event()).collect()
+        row.map(|r| r.into_definition()).transpose()
     }
 
-    /// Get edge case events for an agent
-    pub async fn get_edge_case_events_for_agent(
+    /// List all custom agent type definitions
+    pub async fn list_agent_type_definitions(
         &self,
-        agent_id: &str,
-    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseEvent>> {
-        let rows = sqlx::query_as::<_, EdgeCaseEventRow>(
-            r#"
-            SELECT * FROM edge_case_events
-            WHERE agent_id = ?
-            ORDER BY detected_at DESC
-            "#,
+    ) -> Result<Vec<crate::agent_type_definition::AgentTypeDefinition>> {
+        let rows = sqlx::query_as::<_, AgentTypeDefinitionRow>(
+            "SELECT * FROM custom_agent_types ORDER BY name ASC",
         )
-        .bind(agent_id)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_event()).collect()
+        rows.into_iter().map(|r| r.into_definition()).collect()
     }
 
-    /// Get unresolved edge case events
-    pub async fn get_unresolved_edge_case_events(
-        &self,
-    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseEvent>> {
-        let rows = sqlx::query_as::<_, EdgeCaseEventRow>(
+    /// Delete a custom agent type definition
+    pub async fn delete_agent_type_definition(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM custom_agent_types WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ==================== Secret Operations ====================
+
+    /// Store an encrypted secret, replacing any existing secret with the same name
+    pub async fn set_secret(&self, name: &str, encrypted_value: &str) -> Result<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
             r#"
-            SELECT * FROM edge_case_events
-            WHERE resolution NOT IN ('auto_resolved', 'manual_resolved', 'bypassed')
-            ORDER BY detected_at ASC
+            INSERT INTO secrets (name, value, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(name) DO UPDATE SET
+                value = excluded.value,
+                updated_at = excluded.updated_at
             "#,
         )
-        .fetch_all(&self.pool)
+        .bind(name)
+        .bind(encrypted_value)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_event()).collect()
+        Ok(())
     }
 
-    /// Get edge case events by type
-    pub async fn get_edge_case_events_by_type(
+    /// Get the encrypted value of a secret
+    pub async fn get_secret(&self, name: &str) -> Result<Option<String>> {
+        let value = sqlx::query_scalar("SELECT value FROM secrets WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(value)
+    }
+
+    /// List the names of all stored secrets
+    pub async fn list_secret_names(&self) -> Result<Vec<String>> {
+        let names = sqlx::query_scalar("SELECT name FROM secrets ORDER BY name ASC")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(names)
+    }
+
+    /// Delete a secret
+    pub async fn delete_secret(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM secrets WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ==================== Pipeline Template Operations ====================
+
+    /// Insert or update a pipeline template (keyed by name)
+    pub async fn upsert_pipeline_template(
         &self,
-        edge_case_type: &str,
-        limit: Option<u32>,
-    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseEvent>> {
-        let limit_val = limit.unwrap_or(100) as i64;
-        let rows = sqlx::query_as::<_, EdgeCaseEventRow>(
+        template: &crate::pipeline_template::PipelineTemplate,
+    ) -> Result<()> {
+        template.validate()?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
             r#"
-            SELECT * FROM edge_case_events
-            WHERE edge_case_type = ?
-            ORDER BY detected_at DESC
-            LIMIT ?
+            INSERT INTO pipeline_templates (name, description, yaml, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(name) DO UPDATE SET
+                description = excluded.description,
+                yaml = excluded.yaml,
+                updated_at = excluded.updated_at
             "#,
         )
-        .bind(edge_case_type)
-        .bind(limit_val)
-        .fetch_all(&self.pool)
+        .bind(&template.name)
+        .bind(&template.description)
+        .bind(&template.yaml)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_event()).collect()
+        Ok(())
     }
 
-    /// Get edge case statistics
-    pub async fn get_edge_case_stats(&self) -> Result<EdgeCaseStats> {
-        let total: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM edge_case_events",
-        )
-        .fetch_one(&self.pool)
-        .await?;
+    /// Get a stored pipeline template by name
+    pub async fn get_pipeline_template(
+        &self,
+        name: &str,
+    ) -> Result<Option<crate::pipeline_template::PipelineTemplate>> {
+        let row: Option<(String, String, String)> =
+            sqlx::query_as("SELECT name, description, yaml FROM pipeline_templates WHERE name = ?")
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await?;
 
-        let resolved: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM edge_case_events WHERE resolution IN ('auto_resolved', 'manual_resolved', 'bypassed')",
-        )
-        .fetch_one(&self.pool)
-        .await?;
+        Ok(row.map(|(name, description, yaml)| {
+            crate::pipeline_template::PipelineTemplate::new(name, description, yaml)
+        }))
+    }
 
-        let pending: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM edge_case_events WHERE resolution IN ('pending', 'retrying', 'waiting')",
+    /// List all stored pipeline templates
+    pub async fn list_pipeline_templates(
+        &self,
+    ) -> Result<Vec<crate::pipeline_template::PipelineTemplate>> {
+        let rows: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT name, description, yaml FROM pipeline_templates ORDER BY name ASC",
         )
-        .fetch_one(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        let failed: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM edge_case_events WHERE resolution = 'failed'",
-        )
-        .fetch_one(&self.pool)
-        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(name, description, yaml)| {
+                crate::pipeline_template::PipelineTemplate::new(name, description, yaml)
+            })
+            .collect())
+    }
 
-        let by_type_rows: Vec<(String, i64)> = sqlx::query_as(
-            r#"
-            SELECT edge_case_type, COUNT(*) as count
-            FROM edge_case_events
-            GROUP BY edge_case_type
-            "#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+    /// Delete a stored pipeline template
+    pub async fn delete_pipeline_template(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM pipeline_templates WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
 
-        let avg_time: (Option<f64>,) = sqlx::query_as(
+    // ==================== Saga Operations ====================
+
+    /// Insert a saga
+    pub async fn insert_saga(&self, saga: &crate::saga::Saga) -> Result<i64> {
+        let result = sqlx::query(
             r#"
-            SELECT AVG(
-                CAST((julianday(resolved_at) - julianday(detected_at)) * 86400 AS REAL)
-            )
-            FROM edge_case_events
-            WHERE resolved_at IS NOT NULL
+            INSERT INTO sagas
+                (workflow_type, workflow_id, status, failed_step, error_message,
+                 created_at, updated_at, completed_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .fetch_one(&self.pool)
+        .bind(saga.workflow_type.as_str())
+        .bind(&saga.workflow_id)
+        .bind(saga.status.as_str())
+        .bind(&saga.failed_step)
+        .bind(&saga.error_message)
+        .bind(saga.created_at.to_rfc3339())
+        .bind(saga.updated_at.to_rfc3339())
+        .bind(saga.completed_at.map(|t| t.to_rfc3339()))
+        .execute(&self.pool)
         .await?;
 
-        Ok(EdgeCaseStats {
-            total: total.0 as u32,
-            resolved: resolved.0 as u32,
-            pending: pending.0 as u32,
-            failed: failed.0 as u32,
-            by_type: by_type_rows.into_iter().map(|(k, v)| (k, v as u32)).collect(),
-            avg_resolution_time_seconds: avg_time.0,
-        })
+        Ok(result.last_insert_rowid())
     }
 
-    /// Create or update edge case learning record
-    pub async fn upsert_edge_case_learning(
-        &self,
-        learning: &crate::edge_case_handler::EdgeCaseLearning,
-    ) -> Result<i64> {
-        let existing = sqlx::query_as::<_, (i64,)>(
-            "SELECT id FROM edge_case_learnings WHERE edge_case_type = ? AND pattern = ?",
+    /// Update a saga's status
+    pub async fn update_saga(&self, saga: &crate::saga::Saga) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sagas
+            SET status = ?, failed_step = ?, error_message = ?, updated_at = ?, completed_at = ?
+            WHERE id = ?
+            "#,
         )
-        .bind(learning.edge_case_type.as_str())
-        .bind(&learning.pattern)
-        .fetch_optional(&self.pool)
+        .bind(saga.status.as_str())
+        .bind(&saga.failed_step)
+        .bind(&saga.error_message)
+        .bind(saga.updated_at.to_rfc3339())
+        .bind(saga.completed_at.map(|t| t.to_rfc3339()))
+        .bind(saga.id)
+        .execute(&self.pool)
         .await?;
 
-        if let Some((id,)) = existing {
-            // Update existing
-            sqlx::query(
-                r#"
-                UPDATE edge_case_learnings
-                SET success_rate = ?, avg_resolution_time_seconds = ?,
-                    recommended_action = ?, occurrence_count = ?,
-                    last_occurrence = ?, updated_at = ?
-                WHERE id = ?
-                "#,
-            )
-            .bind(learning.success_rate)
-            .bind(learning.avg_resolution_time_seconds)
-            .bind(&learning.recommended_action)
-            .bind(learning.occurrence_count as i64)
-            .bind(learning.last_occurrence.to_rfc3339())
-            .bind(learning.updated_at.to_rfc3339())
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+        Ok(())
+    }
 
-            Ok(id)
-        } else {
-            // Insert new
-            let result = sqlx::query(
-                r#"
-                INSERT INTO edge_case_learnings
-                    (edge_case_type, pattern, success_rate, avg_resolution_time_seconds,
-                     recommended_action, occurrence_count, last_occurrence, created_at, updated_at)
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
-                "#,
-            )
-            .bind(learning.edge_case_type.as_str())
-            .bind(&learning.pattern)
-            .bind(learning.success_rate)
-            .bind(learning.avg_resolution_time_seconds)
-            .bind(&learning.recommended_action)
-            .bind(learning.occurrence_count as i64)
-            .bind(learning.last_occurrence.to_rfc3339())
-            .bind(learning.created_at.to_rfc3339())
-            .bind(learning.updated_at.to_rfc3339())
-            .execute(&self.pool)
+    /// Get a saga by ID
+    pub async fn get_saga(&self, id: i64) -> Result<Option<crate::saga::Saga>> {
+        let row = sqlx::query_as::<_, SagaRow>("SELECT * FROM sagas WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
             .await?;
 
-            Ok(result.last_insert_rowid())
-        }
+        row.map(|r| r.into_saga()).transpose()
     }
 
-    /// Get edge case learning by type and pattern
-    pub async fn get_edge_case_learning(
+    /// Get the saga tracking a workflow execution
+    pub async fn get_saga_for_workflow(
         &self,
-        edge_case_type: &str,
-        pattern: &str,
-    ) -> Result<Option<crate::edge_case_handler::EdgeCaseLearning>> {
-        let row = sqlx::query_as::<_, EdgeCaseLearningRow>(
-            "SELECT * FROM edge_case_learnings WHERE edge_case_type = ? AND pattern = ?",
+        workflow_type: crate::saga::SagaWorkflowType,
+        workflow_id: &str,
+    ) -> Result<Option<crate::saga::Saga>> {
+        let row = sqlx::query_as::<_, SagaRow>(
+            "SELECT * FROM sagas WHERE workflow_type = ? AND workflow_id = ?",
         )
-        .bind(edge_case_type)
-        .bind(pattern)
+        .bind(workflow_type.as_str())
+        .bind(workflow_id)
         .fetch_optional(&self.pool)
         .await?;
 
-        row.map(|r| r.into_learning()).transpose()
+        row.map(|r| r.into_saga()).transpose()
     }
 
-    /// Get all edge case learnings for a type
-    pub async fn get_edge_case_learnings_by_type(
+    /// List sagas, newest first, optionally filtered by status
+    pub async fn list_sagas(
         &self,
-        edge_case_type: &str,
-    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseLearning>> {
-        let rows = sqlx::query_as::<_, EdgeCaseLearningRow>(
+        status: Option<crate::saga::SagaStatus>,
+    ) -> Result<Vec<crate::saga::Saga>> {
+        let rows = sqlx::query_as::<_, SagaRow>(
             r#"
-            SELECT * FROM edge_case_learnings
-            WHERE edge_case_type = ?
-            ORDER BY occurrence_count DESC
+            SELECT * FROM sagas
+            WHERE (? IS NULL OR status = ?)
+            ORDER BY created_at DESC, id DESC
             "#,
         )
-        .bind(edge_case_type)
+        .bind(status.map(|s| s.as_str()))
+        .bind(status.map(|s| s.as_str()))
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_learning()).collect()
+        rows.into_iter().map(|r| r.into_saga()).collect()
     }
 
-    /// Get top edge case learnings by success rate
-    pub async fn get_top_edge_case_learnings(
+    /// Record a compensation on a saga
+    pub async fn insert_saga_compensation(
         &self,
-        limit: u32,
-    ) -> Result<Vec<crate::edge_case_handler::EdgeCaseLearning>> {
-        let rows = sqlx::query_as::<_, EdgeCaseLearningRow>(
+        compensation: &crate::saga::SagaCompensation,
+    ) -> Result<i64> {
+        let result = sqlx::query(
             r#"
-            SELECT * FROM edge_case_learnings
-            WHERE occurrence_count >= 3
-            ORDER BY success_rate DESC, occurrence_count DESC
-            LIMIT ?
+            INSERT INTO saga_compensations
+                (saga_id, step_name, sequence, action, agent, task, params, status,
+                 error_message, executed_at, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(limit as i64)
+        .bind(compensation.saga_id)
+        .bind(&compensation.step_name)
+        .bind(compensation.sequence)
+        .bind(compensation.action.as_str())
+        .bind(&compensation.agent)
+        .bind(&compensation.task)
+        .bind(serde_json::to_string(&compensation.params)?)
+        .bind(compensation.status.as_str())
+        .bind(&compensation.error_message)
+        .bind(compensation.executed_at.map(|t| t.to_rfc3339()))
+        .bind(compensation.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Update a compensation's execution status
+    pub async fn update_saga_compensation(
+        &self,
+        compensation: &crate::saga::SagaCompensation,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE saga_compensations
+            SET status = ?, error_message = ?, executed_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(compensation.status.as_str())
+        .bind(&compensation.error_message)
+        .bind(compensation.executed_at.map(|t| t.to_rfc3339()))
+        .bind(compensation.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get a saga's compensations in registration order
+    pub async fn list_saga_compensations(
+        &self,
+        saga_id: i64,
+    ) -> Result<Vec<crate::saga::SagaCompensation>> {
+        let rows = sqlx::query_as::<_, SagaCompensationRow>(
+            "SELECT * FROM saga_compensations WHERE saga_id = ? ORDER BY sequence ASC, id ASC",
+        )
+        .bind(saga_id)
         .fetch_all(&self.pool)
         .await?;
 
-        rows.into_iter().map(|r| r.into_learning()).collect()
+        rows.into_iter().map(|r| r.into_compensation()).collect()
+    }
+}
+
+// ==================== Saga Rows ====================
+
+#[derive(Debug, sqlx::FromRow)]
+struct SagaRow {
+    id: i64,
+    workflow_type: String,
+    workflow_id: String,
+    status: String,
+    failed_step: Option<String>,
+    error_message: Option<String>,
+    created_at: String,
+    updated_at: String,
+    completed_at: Option<String>,
+}
+
+impl SagaRow {
+    fn into_saga(self) -> Result<crate::saga::Saga> {
+        use std::str::FromStr;
+
+        Ok(crate::saga::Saga {
+            id: self.id,
+            workflow_type: crate::saga::SagaWorkflowType::from_str(&self.workflow_type)?,
+            workflow_id: self.workflow_id,
+            status: crate::saga::SagaStatus::from_str(&self.status)?,
+            failed_step: self.failed_step,
+            error_message: self.error_message,
+            created_at: parse_datetime(&self.created_at)?,
+            updated_at: parse_datetime(&self.updated_at)?,
+            completed_at: self.completed_at.map(|s| parse_datetime(&s)).transpose()?,
+        })
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct SagaCompensationRow {
+    id: i64,
+    saga_id: i64,
+    step_name: String,
+    sequence: i64,
+    action: String,
+    agent: String,
+    task: String,
+    params: String,
+    status: String,
+    error_message: Option<String>,
+    executed_at: Option<String>,
+    created_at: String,
+}
+
+impl SagaCompensationRow {
+    fn into_compensation(self) -> Result<crate::saga::SagaCompensation> {
+        use std::str::FromStr;
+
+        Ok(crate::saga::SagaCompensation {
+            id: self.id,
+            saga_id: self.saga_id,
+            step_name: self.step_name,
+            sequence: self.sequence,
+            action: crate::saga::CompensationActionType::from_str(&self.action)?,
+            agent: self.agent,
+            task: self.task,
+            params: serde_json::from_str(&self.params)?,
+            status: crate::saga::CompensationStatus::from_str(&self.status)?,
+            error_message: self.error_message,
+            executed_at: self.executed_at.map(|s| parse_datetime(&s)).transpose()?,
+            created_at: parse_datetime(&self.created_at)?,
+        })
+    }
+}
+
+// ==================== Custom Agent Type Row ====================
+
+#[derive(Debug, sqlx::FromRow)]
+struct AgentTypeDefinitionRow {
+    id: i64,
+    name: String,
+    description: Option<String>,
+    base_type: String,
+    system_prompt: Option<String>,
+    skills: String,
+    allowed_tools: String,
+    model: Option<String>,
+    max_turns: Option<i64>,
+    created_at: String,
+    updated_at: String,
+}
+
+impl AgentTypeDefinitionRow {
+    fn into_definition(self) -> Result<crate::agent_type_definition::AgentTypeDefinition> {
+        Ok(crate::agent_type_definition::AgentTypeDefinition {
+            id: self.id,
+            name: self.name,
+            description: self.description,
+            base_type: AgentType::from_str(&self.base_type)?,
+            system_prompt: self.system_prompt,
+            skills: serde_json::from_str(&self.skills)?,
+            allowed_tools: serde_json::from_str(&self.allowed_tools)?,
+            model: self.model,
+            max_turns: self.max_turns.map(|v| v as u32),
+            created_at: parse_datetime(&self.created_at)?,
+            updated_at: parse_datetime(&self.updated_at)?,
+        })
+    }
+}
+
+// ==================== Agent Event Row ====================
+
+#[derive(Debug, sqlx::FromRow)]
+struct AgentEventRow {
+    id: i64,
+    agent_id: String,
+    session_id: Option<String>,
+    event_type: String,
+    summary: String,
+    data: String,
+    created_at: String,
+}
+
+impl AgentEventRow {
+    fn into_event(self) -> Result<crate::agent_event::AgentEvent> {
+        use crate::agent_event::AgentEventType;
+        use std::str::FromStr;
+
+        Ok(crate::agent_event::AgentEvent {
+            id: self.id,
+            agent_id: self.agent_id,
+            session_id: self.session_id,
+            event_type: AgentEventType::from_str(&self.event_type)?,
+            summary: self.summary,
+            data: serde_json::from_str(&self.data)?,
+            created_at: parse_datetime(&self.created_at)?,
+        })
     }
 }
 
@@ -9223,3 +14331,82 @@ pub struct EdgeCaseStats {
     pub by_type: std::collections::HashMap<String, u32>,
     pub avg_resolution_time_seconds: Option<f64>,
 }
+
+#[derive(sqlx::FromRow)]
+struct ArtifactRow {
+    id: i64,
+    kind: String,
+    object_key: String,
+    name: String,
+    owner_id: Option<String>,
+    content_type: String,
+    size_bytes: i64,
+    backend: String,
+    created_at: String,
+}
+
+impl TryFrom<ArtifactRow> for crate::object_storage::Artifact {
+    type Error = crate::Error;
+
+    fn try_from(row: ArtifactRow) -> Result<Self> {
+        use std::str::FromStr;
+
+        Ok(crate::object_storage::Artifact {
+            id: Some(row.id),
+            kind: crate::object_storage::ArtifactKind::from_str(&row.kind)?,
+            key: row.object_key,
+            name: row.name,
+            owner_id: row.owner_id,
+            content_type: row.content_type,
+            size_bytes: row.size_bytes,
+            backend: row.backend,
+            created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ExportJobRow {
+    id: String,
+    dataset: String,
+    format: String,
+    from_time: Option<String>,
+    to_time: Option<String>,
+    status: String,
+    row_count: Option<i64>,
+    artifact_id: Option<i64>,
+    error_message: Option<String>,
+    requested_by: Option<String>,
+    created_at: String,
+    completed_at: Option<String>,
+}
+
+impl TryFrom<ExportJobRow> for crate::ExportJob {
+    type Error = crate::Error;
+
+    fn try_from(row: ExportJobRow) -> Result<Self> {
+        let parse = |s: &str| -> Result<chrono::DateTime<chrono::Utc>> {
+            Ok(chrono::DateTime::parse_from_rfc3339(s)
+                .map_err(|e| crate::Error::Other(e.to_string()))?
+                .into())
+        };
+        Ok(crate::ExportJob {
+            id: row.id,
+            request: crate::ExportRequest {
+                dataset: row.dataset.parse()?,
+                format: row.format.parse()?,
+                from: row.from_time.as_deref().map(parse).transpose()?,
+                to: row.to_time.as_deref().map(parse).transpose()?,
+            },
+            status: row.status.parse()?,
+            row_count: row.row_count,
+            artifact_id: row.artifact_id,
+            error_message: row.error_message,
+            requested_by: row.requested_by,
+            created_at: parse(&row.created_at)?,
+            completed_at: row.completed_at.as_deref().map(parse).transpose()?,
+        })
+    }
+}
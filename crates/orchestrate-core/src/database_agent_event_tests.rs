@@ -0,0 +1,110 @@
+//! Database tests for agent event log operations
+
+use crate::agent_event::{AgentEvent, AgentEventType};
+use crate::recovery::{RecoveryActionType, RecoveryAttempt};
+use crate::stuck_detection::{EvaluationStatus, EvaluationType, WorkEvaluation};
+use crate::{AgentState, Database};
+
+#[tokio::test]
+async fn test_insert_and_get_agent_events() {
+    let db = Database::in_memory().await.unwrap();
+
+    let event = AgentEvent::state_transition("agent-1", AgentState::Created, AgentState::Initializing)
+        .with_session("session-1");
+    let id = db.insert_agent_event(&event).await.unwrap();
+    assert!(id > 0);
+
+    let events = db.get_agent_events("agent-1", None).await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].id, id);
+    assert_eq!(events[0].event_type, AgentEventType::StateTransition);
+    assert_eq!(events[0].summary, "created -> initializing");
+    assert_eq!(events[0].session_id.as_deref(), Some("session-1"));
+    assert_eq!(events[0].data["to"], "initializing");
+}
+
+#[tokio::test]
+async fn test_agent_events_are_chronological() {
+    let db = Database::in_memory().await.unwrap();
+
+    db.insert_agent_event(&AgentEvent::state_transition(
+        "agent-1",
+        AgentState::Initializing,
+        AgentState::Running,
+    ))
+    .await
+    .unwrap();
+    db.insert_agent_event(&AgentEvent::tool_call("agent-1", "Read", false, 5))
+        .await
+        .unwrap();
+    db.insert_agent_event(&AgentEvent::tool_call("agent-1", "Bash", true, 120))
+        .await
+        .unwrap();
+
+    let events = db.get_agent_events("agent-1", None).await.unwrap();
+    let summaries: Vec<&str> = events.iter().map(|e| e.summary.as_str()).collect();
+    assert_eq!(summaries, vec!["initializing -> running", "Read", "Bash"]);
+}
+
+#[tokio::test]
+async fn test_get_agent_events_filtered_by_type() {
+    let db = Database::in_memory().await.unwrap();
+
+    db.insert_agent_event(&AgentEvent::tool_call("agent-1", "Read", false, 5))
+        .await
+        .unwrap();
+    let attempt = RecoveryAttempt::new("agent-1", RecoveryActionType::Retry);
+    db.insert_agent_event(&AgentEvent::recovery("agent-1", &attempt))
+        .await
+        .unwrap();
+
+    let recoveries = db
+        .get_agent_events("agent-1", Some(AgentEventType::Recovery))
+        .await
+        .unwrap();
+    assert_eq!(recoveries.len(), 1);
+    assert_eq!(recoveries[0].data["action_type"], "retry");
+
+    let tools = db
+        .get_agent_events("agent-1", Some(AgentEventType::ToolCall))
+        .await
+        .unwrap();
+    assert_eq!(tools.len(), 1);
+}
+
+#[tokio::test]
+async fn test_agent_events_isolated_per_agent() {
+    let db = Database::in_memory().await.unwrap();
+
+    db.insert_agent_event(&AgentEvent::tool_call("agent-1", "Read", false, 5))
+        .await
+        .unwrap();
+    db.insert_agent_event(&AgentEvent::tool_call("agent-2", "Write", false, 5))
+        .await
+        .unwrap();
+
+    assert_eq!(db.get_agent_events("agent-1", None).await.unwrap().len(), 1);
+    assert_eq!(db.delete_agent_events("agent-2").await.unwrap(), 1);
+    assert!(db.get_agent_events("agent-2", None).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_recoveries_and_evaluations_are_logged() {
+    let db = Database::in_memory().await.unwrap();
+
+    let eval = WorkEvaluation::new("agent-1", EvaluationType::StuckCheck, EvaluationStatus::Stuck)
+        .with_session("session-1");
+    let eval_id = db.create_work_evaluation(&eval).await.unwrap();
+    let attempt = RecoveryAttempt::new("agent-1", RecoveryActionType::Retry);
+    let attempt_id = db.create_recovery_attempt(&attempt).await.unwrap();
+
+    let events = db.get_agent_events("agent-1", None).await.unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].event_type, AgentEventType::Evaluation);
+    assert_eq!(events[0].session_id.as_deref(), Some("session-1"));
+    assert_eq!(events[0].data["work_evaluation_id"], eval_id);
+    assert_eq!(events[0].data["status"], "stuck");
+    assert_eq!(events[1].event_type, AgentEventType::Recovery);
+    assert_eq!(events[1].data["recovery_attempt_id"], attempt_id);
+    assert_eq!(events[1].data["action_type"], "retry");
+}
@@ -0,0 +1,27 @@
+//! Database tests for agent labels
+
+use crate::{Agent, AgentState, AgentType, Database};
+
+#[tokio::test]
+async fn test_agent_labels_persisted() {
+    let db = Database::in_memory().await.unwrap();
+
+    let mut agent =
+        Agent::new(AgentType::StoryDeveloper, "Implement login").with_label("team", "web");
+    db.insert_agent(&agent).await.unwrap();
+    let loaded = db.get_agent(agent.id).await.unwrap().unwrap();
+    assert_eq!(loaded.labels, agent.labels);
+
+    agent
+        .labels
+        .insert("env".to_string(), "staging".to_string());
+    agent.transition_to(AgentState::Initializing).unwrap();
+    db.update_agent(&agent).await.unwrap();
+
+    let loaded = db.get_agent(agent.id).await.unwrap().unwrap();
+    assert_eq!(loaded.labels.get("team").map(String::as_str), Some("web"));
+    assert_eq!(
+        loaded.labels.get("env").map(String::as_str),
+        Some("staging")
+    );
+}
@@ -0,0 +1,24 @@
+//! Database tests for agent trace IDs
+
+use crate::{Agent, AgentState, AgentType, Database};
+
+#[tokio::test]
+async fn test_agent_trace_id_persisted() {
+    let db = Database::in_memory().await.unwrap();
+
+    let mut agent = Agent::new(AgentType::StoryDeveloper, "Implement login");
+    db.insert_agent(&agent).await.unwrap();
+    let loaded = db.get_agent(agent.id).await.unwrap().unwrap();
+    assert!(loaded.trace_id.is_none());
+
+    // A run records its trace when it starts
+    agent.trace_id = Some("4bf92f3577b34da6a3ce929d0e0e4736".to_string());
+    agent.transition_to(AgentState::Initializing).unwrap();
+    db.update_agent(&agent).await.unwrap();
+
+    let loaded = db.get_agent(agent.id).await.unwrap().unwrap();
+    assert_eq!(
+        loaded.trace_id.as_deref(),
+        Some("4bf92f3577b34da6a3ce929d0e0e4736")
+    );
+}
@@ -0,0 +1,82 @@
+//! Database tests for custom agent type operations
+
+use crate::agent_type_definition::AgentTypeDefinition;
+use crate::{Agent, AgentType, Database};
+
+#[tokio::test]
+async fn test_upsert_and_get_agent_type_definition() {
+    let db = Database::in_memory().await.unwrap();
+
+    let def = AgentTypeDefinition::new("terraform-reviewer", AgentType::CodeReviewer)
+        .with_description("Reviews Terraform plans")
+        .with_system_prompt("You review Terraform changes.")
+        .with_skills(vec!["terraform".to_string()])
+        .with_allowed_tools(vec!["Read".to_string(), "Grep".to_string()])
+        .with_max_turns(25);
+
+    let id = db.upsert_agent_type_definition(&def).await.unwrap();
+    assert!(id > 0);
+
+    let loaded = db
+        .get_agent_type_definition("terraform-reviewer")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(loaded.id, id);
+    assert_eq!(loaded.base_type, AgentType::CodeReviewer);
+    assert_eq!(loaded.skills, vec!["terraform"]);
+    assert_eq!(loaded.allowed_tools, vec!["Read", "Grep"]);
+    assert_eq!(loaded.max_turns, Some(25));
+}
+
+#[tokio::test]
+async fn test_upsert_updates_existing_definition() {
+    let db = Database::in_memory().await.unwrap();
+
+    let def = AgentTypeDefinition::new("docs-checker", AgentType::Explorer);
+    let first_id = db.upsert_agent_type_definition(&def).await.unwrap();
+
+    let updated = def.with_model("claude-3-haiku-20240307");
+    let second_id = db.upsert_agent_type_definition(&updated).await.unwrap();
+    assert_eq!(first_id, second_id);
+
+    let all = db.list_agent_type_definitions().await.unwrap();
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].model.as_deref(), Some("claude-3-haiku-20240307"));
+}
+
+#[tokio::test]
+async fn test_upsert_rejects_invalid_definition() {
+    let db = Database::in_memory().await.unwrap();
+
+    let def = AgentTypeDefinition::new("story-developer", AgentType::StoryDeveloper);
+    assert!(db.upsert_agent_type_definition(&def).await.is_err());
+}
+
+#[tokio::test]
+async fn test_delete_agent_type_definition() {
+    let db = Database::in_memory().await.unwrap();
+
+    let def = AgentTypeDefinition::new("docs-checker", AgentType::Explorer);
+    db.upsert_agent_type_definition(&def).await.unwrap();
+
+    assert!(db.delete_agent_type_definition("docs-checker").await.unwrap());
+    assert!(!db.delete_agent_type_definition("docs-checker").await.unwrap());
+    assert!(db
+        .get_agent_type_definition("docs-checker")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_agent_custom_type_persisted() {
+    let db = Database::in_memory().await.unwrap();
+
+    let agent =
+        Agent::new(AgentType::CodeReviewer, "Review plan").with_custom_type("terraform-reviewer");
+    db.insert_agent(&agent).await.unwrap();
+
+    let loaded = db.get_agent(agent.id).await.unwrap().unwrap();
+    assert_eq!(loaded.custom_type.as_deref(), Some("terraform-reviewer"));
+}
@@ -197,6 +197,24 @@ mod tests {
         assert_eq!(pending.len(), 1);
         assert_eq!(pending[0].stage_id, stage1_id);
         assert_eq!(pending[0].status, ApprovalStatus::Pending);
+
+        // Delegated approvals still await a decision
+        let stage3 = PipelineStage::new(run_id, "deploy-canary".to_string());
+        let stage3_id = db.insert_pipeline_stage(&stage3).await.unwrap();
+        let mut request3 = ApprovalRequest::new(
+            stage3_id,
+            run_id,
+            "user@example.com".to_string(),
+            1,
+            None,
+            None,
+        );
+        request3.mark_delegated();
+        db.create_approval_request(request3).await.unwrap();
+
+        let pending = db.list_pending_approvals().await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[1].status, ApprovalStatus::Delegated);
     }
 
     #[tokio::test]
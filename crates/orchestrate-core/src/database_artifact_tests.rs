@@ -0,0 +1,110 @@
+//! Database tests for artifact operations
+
+#[cfg(test)]
+mod tests {
+    use crate::object_storage::*;
+    use crate::Database;
+
+    fn artifact(kind: ArtifactKind, key: &str, owner_id: Option<&str>) -> Artifact {
+        Artifact {
+            id: None,
+            kind,
+            key: key.to_string(),
+            name: key.rsplit('/').next().unwrap().to_string(),
+            owner_id: owner_id.map(String::from),
+            content_type: "application/json".to_string(),
+            size_bytes: 42,
+            backend: "s3".to_string(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_artifact_roundtrip() {
+        let db = Database::in_memory().await.unwrap();
+
+        let id = db
+            .insert_artifact(&artifact(
+                ArtifactKind::SecurityReport,
+                "security-reports/scan-1/report.sarif",
+                Some("scan-1"),
+            ))
+            .await
+            .unwrap();
+
+        let stored = db.get_artifact(id).await.unwrap().unwrap();
+        assert_eq!(stored.id, Some(id));
+        assert_eq!(stored.kind, ArtifactKind::SecurityReport);
+        assert_eq!(stored.name, "report.sarif");
+        assert_eq!(stored.owner_id.as_deref(), Some("scan-1"));
+        assert_eq!(stored.size_bytes, 42);
+
+        let by_key = db
+            .get_artifact_by_key("security-reports/scan-1/report.sarif")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(by_key.id, Some(id));
+
+        // Keys are unique
+        assert!(db
+            .insert_artifact(&artifact(
+                ArtifactKind::SecurityReport,
+                "security-reports/scan-1/report.sarif",
+                None,
+            ))
+            .await
+            .is_err());
+
+        assert!(db.delete_artifact(id).await.unwrap());
+        assert!(!db.delete_artifact(id).await.unwrap());
+        assert!(db.get_artifact(id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_artifacts() {
+        let db = Database::in_memory().await.unwrap();
+
+        db.insert_artifact(&artifact(
+            ArtifactKind::Postmortem,
+            "postmortems/INC-1/pm.md",
+            Some("INC-1"),
+        ))
+        .await
+        .unwrap();
+        db.insert_artifact(&artifact(
+            ArtifactKind::AgentArtifact,
+            "agents/a1/log.txt",
+            Some("a1"),
+        ))
+        .await
+        .unwrap();
+        let latest = db
+            .insert_artifact(&artifact(
+                ArtifactKind::AgentArtifact,
+                "agents/a2/diff.patch",
+                Some("a2"),
+            ))
+            .await
+            .unwrap();
+
+        let all = db.list_artifacts(None, None, 10).await.unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].id, Some(latest));
+
+        let agents = db
+            .list_artifacts(Some(ArtifactKind::AgentArtifact), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(agents.len(), 2);
+
+        let owned = db
+            .list_artifacts(Some(ArtifactKind::AgentArtifact), Some("a1"), 10)
+            .await
+            .unwrap();
+        assert_eq!(owned.len(), 1);
+        assert_eq!(owned[0].key, "agents/a1/log.txt");
+
+        assert_eq!(db.list_artifacts(None, None, 1).await.unwrap().len(), 1);
+    }
+}
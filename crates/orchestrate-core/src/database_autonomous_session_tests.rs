@@ -608,3 +608,68 @@ async fn test_full_session_lifecycle_in_db() {
     assert_eq!(final_session.completed_items.len(), 1);
     assert!(final_session.work_queue.is_empty());
 }
+
+#[tokio::test]
+async fn test_session_plan_persistence_and_replan_history() {
+    let db = Database::in_memory().await.unwrap();
+
+    let mut session = AutonomousSession::with_id("plan-test");
+    for (id, priority) in [("story-1", 1), ("story-2", 2)] {
+        session.add_work_item(WorkItem {
+            id: id.to_string(),
+            work_type: WorkItemType::Story,
+            epic_id: "epic-016".to_string(),
+            story_id: Some(id.to_string()),
+            priority,
+            dependencies: vec![],
+            metadata: serde_json::Value::Null,
+        });
+    }
+    session.set_goal("Complete epic-016");
+    session.plan.as_mut().unwrap().failure_threshold = 1;
+    db.create_autonomous_session(&session).await.unwrap();
+
+    let retrieved = db.get_autonomous_session("plan-test").await.unwrap().unwrap();
+    let plan = retrieved.plan.unwrap();
+    assert_eq!(plan.goal, "Complete epic-016");
+    assert_eq!(plan.steps.len(), 2);
+
+    session.start().unwrap();
+    session.transition_to(AutonomousSessionState::Discovering).unwrap();
+    session.transition_to(AutonomousSessionState::Planning).unwrap();
+    session.transition_to(AutonomousSessionState::Executing).unwrap();
+    session.pop_work_item();
+    session.record_completed(CompletedItem {
+        id: "story-1".to_string(),
+        work_type: WorkItemType::Story,
+        epic_id: "epic-016".to_string(),
+        story_id: Some("story-1".to_string()),
+        success: false,
+        completed_at: Utc::now(),
+        error: Some("Tests failed".to_string()),
+        agent_id: None,
+    });
+
+    let divergence = session.plan_divergence().unwrap();
+    let from_state = session.state;
+    session.replan().unwrap();
+    db.update_autonomous_session(&session).await.unwrap();
+    db.record_session_replan(&session, from_state, &divergence)
+        .await
+        .unwrap();
+
+    let retrieved = db.get_autonomous_session("plan-test").await.unwrap().unwrap();
+    assert_eq!(retrieved.plan.unwrap().revision, 2);
+
+    let history = db.get_session_state_history("plan-test").await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert!(history[0].is_replan());
+    assert_eq!(history[0].from_state, AutonomousSessionState::Executing);
+    assert_eq!(history[0].to_state, AutonomousSessionState::Planning);
+    assert!(history[0].reason.as_deref().unwrap().contains("failed"));
+
+    let plans = db.get_session_plan_history("plan-test").await.unwrap();
+    assert_eq!(plans.len(), 1);
+    assert_eq!(plans[0].revision, 2);
+    assert_eq!(plans[0].steps.len(), 1);
+}
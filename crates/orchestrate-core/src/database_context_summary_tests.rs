@@ -0,0 +1,123 @@
+//! Database tests for context summary indexing and related-work search
+
+use crate::context_summary::{
+    ContextSummary, FileChange, FileChangeType, HashingEmbedder, SummaryEmbedder, WorkStatus,
+};
+use crate::Database;
+
+fn summary(agent_id: &str, text: &str, files: &[&str]) -> ContextSummary {
+    let mut summary = ContextSummary::new()
+        .with_agent(agent_id)
+        .with_status(WorkStatus::Completed);
+    summary.set_summary(text);
+    for file in files {
+        summary.add_file_change(FileChange::new(*file, FileChangeType::Modified));
+    }
+    summary
+}
+
+#[tokio::test]
+async fn test_find_related_summaries_ranks_by_similarity() {
+    let db = Database::in_memory().await.unwrap();
+
+    db.store_context_summary(
+        &summary(
+            "agent-1",
+            "Implemented OAuth login with session token refresh",
+            &["src/auth/login.rs"],
+        ),
+        Some("story-1"),
+    )
+    .await
+    .unwrap();
+    db.store_context_summary(
+        &summary(
+            "agent-2",
+            "Added CSV export for billing reports",
+            &["src/reports/export.rs"],
+        ),
+        Some("story-2"),
+    )
+    .await
+    .unwrap();
+
+    let related = db
+        .find_related_summaries("Fix session token refresh after OAuth login", 5)
+        .await
+        .unwrap();
+
+    assert!(!related.is_empty());
+    assert_eq!(related[0].story_id.as_deref(), Some("story-1"));
+    assert_eq!(related[0].summary.agent_id.as_deref(), Some("agent-1"));
+    assert!(related.windows(2).all(|w| w[0].score >= w[1].score));
+
+    let limited = db
+        .find_related_summaries("billing export session login", 1)
+        .await
+        .unwrap();
+    assert_eq!(limited.len(), 1);
+}
+
+#[tokio::test]
+async fn test_find_related_summaries_file_overlap_bonus() {
+    let db = Database::in_memory().await.unwrap();
+
+    db.store_context_summary(
+        &summary("agent-1", "Refactored handler", &["src/api/handlers.rs"]),
+        None,
+    )
+    .await
+    .unwrap();
+    db.store_context_summary(
+        &summary("agent-2", "Refactored handler", &["src/cli/main.rs"]),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let related = db
+        .find_related_summaries("Refactored handler follow-up in src/api/handlers.rs", 2)
+        .await
+        .unwrap();
+
+    assert_eq!(related.len(), 2);
+    assert_eq!(related[0].summary.agent_id.as_deref(), Some("agent-1"));
+    assert!(related[0].score > related[1].score);
+}
+
+#[tokio::test]
+async fn test_find_related_summaries_only_matches_same_model() {
+    struct OtherModel;
+
+    impl SummaryEmbedder for OtherModel {
+        fn model(&self) -> &str {
+            "other"
+        }
+
+        fn embed(&self, text: &str) -> Vec<f32> {
+            HashingEmbedder::new().embed(text)
+        }
+    }
+
+    let db = Database::in_memory().await.unwrap();
+    db.store_context_summary_with(
+        &summary("agent-1", "Tuned database connection pool", &[]),
+        None,
+        &OtherModel,
+    )
+    .await
+    .unwrap();
+
+    assert!(db
+        .find_related_summaries("database connection pool", 5)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        db.find_related_summaries_with("database connection pool", 5, &OtherModel)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
@@ -1,8 +1,8 @@
 //! Database tests for agent continuation operations
 
 use crate::agent_continuation::{
-    AgentContinuation, ContinuationBuilder, ContinuationReason, ContinuationResult,
-    ContinuationStatus,
+    AgentContinuation, ContinuationBuilder, ContinuationCompressor, ContinuationReason,
+    ContinuationResult, ContinuationStatus,
 };
 use crate::Database;
 
@@ -334,3 +334,54 @@ async fn test_continuation_not_found() {
     let result = db.get_continuation(99999).await.unwrap();
     assert!(result.is_none());
 }
+
+#[tokio::test]
+async fn test_create_continuation_with_prior_context() {
+    let db = Database::in_memory().await.unwrap();
+    let compressor = ContinuationCompressor::new();
+
+    let first = db
+        .create_continuation_with_prior_context(
+            ContinuationBuilder::review_feedback("agent-123")
+                .add_comment("src/lib.rs", Some(10), "Missing error handling")
+                .build(),
+            &compressor,
+        )
+        .await
+        .unwrap();
+    assert!(first.prior_context().is_none());
+
+    for attempt in 0..5 {
+        db.create_continuation_with_prior_context(
+            ContinuationBuilder::review_feedback("agent-123")
+                .add_comment("src/lib.rs", Some(10), "Missing error handling")
+                .add_line(format!("- Attempt {} still incomplete", attempt))
+                .build(),
+            &compressor,
+        )
+        .await
+        .unwrap();
+    }
+
+    let last = db
+        .create_continuation_with_prior_context(
+            AgentContinuation::new("agent-123", ContinuationReason::Retry, "Handle the error"),
+            &compressor,
+        )
+        .await
+        .unwrap();
+
+    let stored = db.get_continuation(last.id).await.unwrap().unwrap();
+    let context = stored.prior_context().unwrap();
+    assert_eq!(context.continuations_seen, 6);
+    // Repeated feedback is carried once, not once per handoff
+    assert_eq!(
+        context
+            .entries
+            .iter()
+            .filter(|e| e.text.contains("Missing error handling"))
+            .count(),
+        1
+    );
+    assert_eq!(context.entries.len(), 6);
+}
@@ -0,0 +1,137 @@
+//! Database tests for epic/story cost attribution
+
+use crate::{Agent, AgentContext, AgentType, Database, Epic, Story};
+
+async fn setup_story(db: &Database) {
+    db.upsert_epic(&Epic::new("epic-001", "Auth")).await.unwrap();
+    db.upsert_story(&Story::new("epic-001.1", "epic-001", "Login form"))
+        .await
+        .unwrap();
+    db.upsert_story(&Story::new("epic-001.2", "epic-001", "Logout"))
+        .await
+        .unwrap();
+}
+
+fn story_context(story_id: &str) -> AgentContext {
+    AgentContext {
+        epic_id: Some("epic-001".to_string()),
+        story_id: Some(story_id.to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_attribute_token_usage_to_epic_and_story() {
+    let db = Database::in_memory().await.unwrap();
+    setup_story(&db).await;
+
+    let context = story_context("epic-001.1");
+    db.attribute_token_usage(&context, "claude-sonnet-4", 100_000, 10_000, 0, 0)
+        .await
+        .unwrap();
+    db.attribute_token_usage(&context, "claude-sonnet-4", 50_000, 5_000, 0, 0)
+        .await
+        .unwrap();
+
+    let epic_costs = db.get_costs_by_epic("epic-001", 7).await.unwrap();
+    assert_eq!(epic_costs.len(), 1);
+    assert_eq!(epic_costs[0].request_count, 2);
+    assert_eq!(epic_costs[0].total_input_tokens, 150_000);
+
+    let story_costs = db.get_costs_by_story("epic-001.1", 7).await.unwrap();
+    assert_eq!(story_costs.len(), 1);
+    // 150k input at $3/M + 15k output at $15/M
+    assert!((story_costs[0].estimated_cost_usd - 0.675).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_attribute_token_usage_without_context_is_noop() {
+    let db = Database::in_memory().await.unwrap();
+
+    db.attribute_token_usage(&AgentContext::default(), "claude-sonnet-4", 1_000, 100, 0, 0)
+        .await
+        .unwrap();
+
+    assert!(db.list_epic_costs(None).await.unwrap().is_empty());
+    assert!(db.list_story_costs(None, None).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_list_story_costs_sorted_and_filtered_by_epic() {
+    let db = Database::in_memory().await.unwrap();
+    setup_story(&db).await;
+    db.upsert_epic(&Epic::new("epic-002", "Billing")).await.unwrap();
+    db.upsert_story(&Story::new("epic-002.1", "epic-002", "Invoices"))
+        .await
+        .unwrap();
+
+    db.update_cost_by_story("epic-001.1", "claude-sonnet-4", 10_000, 1_000, 0, 0)
+        .await
+        .unwrap();
+    db.update_cost_by_story("epic-001.2", "claude-opus-4", 10_000, 1_000, 0, 0)
+        .await
+        .unwrap();
+    db.update_cost_by_story("epic-002.1", "claude-sonnet-4", 10_000, 1_000, 0, 0)
+        .await
+        .unwrap();
+
+    let costs = db.list_story_costs(Some("epic-001"), Some(30)).await.unwrap();
+    let ids: Vec<_> = costs.iter().map(|c| c.entity_id.as_str()).collect();
+    assert_eq!(ids, vec!["epic-001.2", "epic-001.1"]);
+
+    assert_eq!(db.list_story_costs(None, None).await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_session_tokens_attributed_from_agent_context() {
+    let db = Database::in_memory().await.unwrap();
+    setup_story(&db).await;
+
+    let agent = Agent::new(AgentType::StoryDeveloper, "Implement login")
+        .with_context(story_context("epic-001.1"));
+    db.insert_agent(&agent).await.unwrap();
+    let other = Agent::new(AgentType::Explorer, "Explore");
+    db.insert_agent(&other).await.unwrap();
+
+    db.record_session_tokens("s1", agent.id, 1, 1_000, 200, 0, 0, 1_000, 2, 0)
+        .await
+        .unwrap();
+    db.record_session_tokens("s1", agent.id, 2, 2_000, 300, 0, 0, 2_000, 4, 0)
+        .await
+        .unwrap();
+    db.record_session_tokens("s2", other.id, 1, 5_000, 500, 0, 0, 5_000, 2, 0)
+        .await
+        .unwrap();
+
+    let stats = db.get_story_token_stats("epic-001.1").await.unwrap();
+    assert_eq!(stats.turn_count, 2);
+    assert_eq!(stats.total_input_tokens, 3_000);
+    assert_eq!(stats.total_output_tokens, 500);
+}
+
+#[tokio::test]
+async fn test_estimate_agent_tokens() {
+    let db = Database::in_memory().await.unwrap();
+
+    // Without recorded usage the default estimate applies
+    let estimate = db.estimate_agent_tokens("code-reviewer").await.unwrap();
+    assert_eq!(estimate.sampled_agents, 0);
+    assert_eq!(estimate.tokens, 50_000);
+
+    for (turns, input) in [(1, 100_000), (2, 50_000)] {
+        let agent = Agent::new(AgentType::CodeReviewer, "Review");
+        db.insert_agent(&agent).await.unwrap();
+        for turn in 0..turns {
+            db.record_session_tokens("session", agent.id, turn, input, 10_000, 0, 0, 0, 0, 0)
+                .await
+                .unwrap();
+        }
+    }
+
+    // Pipeline agent names match agent types with dashes for underscores
+    let estimate = db.estimate_agent_tokens("code-reviewer").await.unwrap();
+    assert_eq!(estimate.sampled_agents, 2);
+    assert_eq!(estimate.tokens, 115_000);
+    // 200k input at $3/M + 30k output at $15/M, split over two agents
+    assert!((estimate.cost_usd - 0.525).abs() < 1e-9);
+}
@@ -0,0 +1,181 @@
+//! Database tests for data exports
+
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use crate::agent_event::AgentEvent;
+use crate::monitoring::{AuditAction, AuditEntry};
+use crate::{
+    Agent, AgentState, AgentType, ArtifactKind, ArtifactStore, DataExportFormat, Database,
+    ExportDataset, ExportJob, ExportJobStatus, ExportRequest, ExportValue, LocalObjectStore,
+    PageQuery,
+};
+
+#[tokio::test]
+async fn test_export_rows_by_date_range() {
+    let db = Database::in_memory().await.unwrap();
+    let now = Utc::now();
+
+    let mut old = AuditEntry::new("alice", AuditAction::AgentSpawned, "agent", "a1");
+    old.timestamp = now - Duration::days(10);
+    let recent = AuditEntry::new("bob", AuditAction::AgentSpawned, "agent", "a2")
+        .with_detail("task", serde_json::json!("Fix, \"login\""));
+    db.insert_audit_entry(&old).await.unwrap();
+    db.insert_audit_entry(&recent).await.unwrap();
+
+    let since_yesterday = |dataset| {
+        ExportRequest::new(dataset, DataExportFormat::Csv)
+            .between(Some(now - Duration::days(1)), None)
+    };
+
+    let audit = db
+        .export_rows(&since_yesterday(ExportDataset::AuditLog), None)
+        .await
+        .unwrap();
+    assert_eq!(audit.rows.len(), 1);
+    assert_eq!(audit.rows[0][1], ExportValue::Text("bob".to_string()));
+    assert_eq!(audit.rows[0][6], ExportValue::Boolean(true));
+    assert_eq!(audit.rows[0][7], ExportValue::Null);
+
+    let all = ExportRequest::new(ExportDataset::AuditLog, DataExportFormat::Csv);
+    assert_eq!(db.export_rows(&all, None).await.unwrap().rows.len(), 2);
+    assert_eq!(db.export_rows(&all, Some(1)).await.unwrap().rows.len(), 1);
+
+    let agent = Agent::new(AgentType::StoryDeveloper, "Implement login");
+    db.insert_agent(&agent).await.unwrap();
+    let event = AgentEvent::state_transition(
+        agent.id.to_string(),
+        AgentState::Created,
+        AgentState::Initializing,
+    );
+    db.insert_agent_event(&event).await.unwrap();
+    let history = db
+        .export_rows(&since_yesterday(ExportDataset::AgentHistory), None)
+        .await
+        .unwrap();
+    assert_eq!(history.rows.len(), 1);
+    assert_eq!(
+        history.rows[0][2],
+        ExportValue::Text("story_developer".to_string())
+    );
+
+    db.record_session_tokens("s1", agent.id, 1, 1200, 300, 800, 0, 1500, 4, 0)
+        .await
+        .unwrap();
+    let tokens = db
+        .export_rows(&since_yesterday(ExportDataset::TokenUsage), None)
+        .await
+        .unwrap();
+    assert_eq!(tokens.rows.len(), 1);
+    assert_eq!(tokens.rows[0][4], ExportValue::Integer(1200));
+
+    db.update_daily_token_usage("claude-sonnet-4", 1200, 300, 800, 0)
+        .await
+        .unwrap();
+    let costs = db
+        .export_rows(&since_yesterday(ExportDataset::Costs), None)
+        .await
+        .unwrap();
+    assert_eq!(costs.rows.len(), 1);
+    assert!(matches!(costs.rows[0][7], ExportValue::Real(cost) if cost > 0.0));
+
+    // The range end is exclusive
+    let before = ExportRequest::new(ExportDataset::Costs, DataExportFormat::Csv)
+        .between(None, Some(now - Duration::days(1)));
+    assert!(db.export_rows(&before, None).await.unwrap().rows.is_empty());
+}
+
+#[tokio::test]
+async fn test_export_job_stores_artifact() {
+    let db = Database::in_memory().await.unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let store = Arc::new(LocalObjectStore::new(
+        dir.path(),
+        "http://localhost:8080",
+        "key",
+    ));
+    let artifacts = ArtifactStore::new(store, db.clone());
+    db.insert_audit_entry(&AuditEntry::new(
+        "alice",
+        AuditAction::AgentSpawned,
+        "agent",
+        "a1",
+    ))
+    .await
+    .unwrap();
+
+    let mut job = ExportJob::new(ExportRequest::new(
+        ExportDataset::AuditLog,
+        DataExportFormat::Parquet,
+    ))
+    .with_requested_by("alice");
+    db.insert_export_job(&job).await.unwrap();
+    job.run(&db, &artifacts).await.unwrap();
+
+    let stored = db.get_export_job(&job.id).await.unwrap().unwrap();
+    assert_eq!(stored.status, ExportJobStatus::Completed);
+    assert_eq!(stored.row_count, Some(1));
+    assert_eq!(stored.requested_by.as_deref(), Some("alice"));
+
+    let artifact = db
+        .get_artifact(stored.artifact_id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(artifact.kind, ArtifactKind::Export);
+    assert_eq!(artifact.name, "audit_log.parquet");
+    assert_eq!(artifact.owner_id.as_deref(), Some(job.id.as_str()));
+    let body = artifacts.get(&artifact).await.unwrap();
+    assert_eq!(&body[..4], b"PAR1");
+
+    let jobs = db.list_export_jobs(10).await.unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].request.format, DataExportFormat::Parquet);
+}
+
+#[tokio::test]
+async fn test_list_export_jobs_by_page() {
+    let db = Database::in_memory().await.unwrap();
+    let now = Utc::now();
+
+    // Two jobs share a creation time, so pages are split on the id as well
+    let mut ids = Vec::new();
+    for minutes in [1, 2, 2, 3, 4] {
+        let mut job = ExportJob::new(ExportRequest::new(
+            ExportDataset::Costs,
+            DataExportFormat::Csv,
+        ));
+        job.created_at = now - Duration::minutes(minutes);
+        db.insert_export_job(&job).await.unwrap();
+        ids.push(job.id);
+    }
+
+    let mut page = PageQuery::first(2);
+    page.include_total = true;
+    let mut listed = Vec::new();
+    loop {
+        let jobs = db.list_export_jobs_page(&page).await.unwrap();
+        assert!(jobs.items.len() <= 2);
+        assert_eq!(jobs.total, Some(5));
+        listed.extend(jobs.items.into_iter().map(|job| job.id));
+        match jobs.next {
+            Some(next) => page.after = Some(next),
+            None => break,
+        }
+    }
+
+    assert_eq!(listed.len(), 5);
+    assert_eq!(listed[0], ids[0]);
+    assert_eq!(listed[4], ids[4]);
+    let mut tied = [ids[1].clone(), ids[2].clone()];
+    tied.sort_by(|a, b| b.cmp(a));
+    assert_eq!(listed[1..3], tied[..]);
+
+    let first = db
+        .list_export_jobs_page(&PageQuery::first(5))
+        .await
+        .unwrap();
+    assert_eq!(first.items.len(), 5);
+    assert!(first.next.is_none());
+    assert!(first.total.is_none());
+}
@@ -0,0 +1,66 @@
+//! Database tests for issue triage operations
+
+#[cfg(test)]
+mod tests {
+    use crate::issue_triage::*;
+    use crate::Database;
+
+    fn triaged(repository: &str, number: i64, title: &str) -> IssueTriageRecord {
+        let issue = TriageIssue {
+            number,
+            title: title.to_string(),
+            body: String::new(),
+            labels: vec!["triage".to_string()],
+        };
+        let triage = IssueTriager::new(IssueTriageConfig::default()).triage(&issue, &[]);
+        IssueTriageRecord::new(repository, &issue, &triage)
+    }
+
+    #[tokio::test]
+    async fn test_issue_triage_roundtrip() {
+        let db = Database::in_memory().await.unwrap();
+
+        let mut record = triaged("acme/app", 7, "App crashes on startup");
+        let id = db.upsert_issue_triage(&record).await.unwrap();
+
+        let stored = db.get_issue_triage("acme/app", 7).await.unwrap().unwrap();
+        assert_eq!(stored.id, Some(id));
+        assert_eq!(stored.labels, vec!["triage", "bug", "size:S"]);
+        assert_eq!(stored.complexity, IssueComplexity::Small);
+        assert!(stored.agent_id.is_none());
+
+        // Triaging the issue again updates the record
+        let agent_id = uuid::Uuid::new_v4();
+        record.agent_id = Some(agent_id);
+        record.duplicate_of = Some(3);
+        assert_eq!(db.upsert_issue_triage(&record).await.unwrap(), id);
+        let stored = db.get_issue_triage("acme/app", 7).await.unwrap().unwrap();
+        assert_eq!(stored.agent_id, Some(agent_id));
+        assert_eq!(stored.duplicate_of, Some(3));
+
+        assert!(db
+            .get_issue_triage("acme/other", 7)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_issue_triages() {
+        let db = Database::in_memory().await.unwrap();
+        for record in [
+            triaged("acme/app", 1, "Login fails"),
+            triaged("acme/app", 2, "Add CSV export"),
+            triaged("acme/api", 1, "Rate limit errors"),
+        ] {
+            db.upsert_issue_triage(&record).await.unwrap();
+        }
+
+        let app = db.list_issue_triages(Some("acme/app"), 50).await.unwrap();
+        assert_eq!(app.len(), 2);
+        assert!(app.iter().all(|record| record.repository == "acme/app"));
+
+        assert_eq!(db.list_issue_triages(None, 50).await.unwrap().len(), 3);
+        assert_eq!(db.list_issue_triages(None, 1).await.unwrap().len(), 1);
+    }
+}
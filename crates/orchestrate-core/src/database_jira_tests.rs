@@ -0,0 +1,128 @@
+//! Database tests for Jira sync operations
+
+#[cfg(test)]
+mod tests {
+    use crate::jira::*;
+    use crate::{Database, StoryStatus};
+    use chrono::{Duration, Utc};
+
+    fn comment(id: &str, body: &str, minutes_ago: i64) -> JiraComment {
+        let created_at = Utc::now() - Duration::minutes(minutes_ago);
+        JiraComment {
+            issue_key: "WEB-2".to_string(),
+            comment_id: id.to_string(),
+            author: Some("Jane Doe".to_string()),
+            body: body.to_string(),
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_jira_project_crud() {
+        let db = Database::in_memory().await.unwrap();
+
+        let project = JiraProject::new("web", "https://example.atlassian.net", "WEB")
+            .with_epic_prefix("web-")
+            .with_status(StoryStatus::Completed, "Closed")
+            .with_field("epic_key", "customfield_10014");
+        let id = db.insert_jira_project(&project).await.unwrap();
+
+        let mut stored = db.get_jira_project("web").await.unwrap().unwrap();
+        assert_eq!(stored.id, Some(id));
+        assert_eq!(stored.epic_prefix, "web-");
+        assert_eq!(stored.jira_status(StoryStatus::Completed), Some("Closed"));
+        assert_eq!(
+            stored.field_mapping.get("epic_key").map(String::as_str),
+            Some("customfield_10014")
+        );
+
+        stored.enabled = false;
+        stored.project_key = "WEBAPP".to_string();
+        db.update_jira_project(&stored).await.unwrap();
+        let updated = db.get_jira_project_by_id(id).await.unwrap().unwrap();
+        assert!(!updated.enabled);
+        assert_eq!(updated.project_key, "WEBAPP");
+
+        assert_eq!(db.list_jira_projects().await.unwrap().len(), 1);
+        assert!(db.delete_jira_project("web").await.unwrap());
+        assert!(!db.delete_jira_project("web").await.unwrap());
+        assert!(db.get_jira_project("web").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_jira_issue_links() {
+        let db = Database::in_memory().await.unwrap();
+        let project_id = db
+            .insert_jira_project(&JiraProject::new(
+                "web",
+                "https://example.atlassian.net",
+                "WEB",
+            ))
+            .await
+            .unwrap();
+
+        let mut link =
+            JiraIssueLink::new(project_id, JiraResourceType::Story, "web-001.1", "WEB-2");
+        let id = db.upsert_jira_issue_link(&link).await.unwrap();
+
+        link.synced_status = Some(StoryStatus::InProgress);
+        assert_eq!(db.upsert_jira_issue_link(&link).await.unwrap(), id);
+
+        let stored = db
+            .get_jira_issue_link(JiraResourceType::Story, "web-001.1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.issue_key, "WEB-2");
+        assert_eq!(stored.synced_status, Some(StoryStatus::InProgress));
+        assert!(db
+            .get_jira_issue_link(JiraResourceType::Epic, "web-001.1")
+            .await
+            .unwrap()
+            .is_none());
+
+        // Removing the project removes its links
+        db.delete_jira_project("web").await.unwrap();
+        assert!(db
+            .get_jira_issue_link(JiraResourceType::Story, "web-001.1")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_jira_comments() {
+        let db = Database::in_memory().await.unwrap();
+
+        assert!(db
+            .upsert_jira_comment("web-001.1", &comment("10001", "Use the design tokens", 5))
+            .await
+            .unwrap());
+        assert!(db
+            .upsert_jira_comment("web-001.1", &comment("10000", "Check with design", 10))
+            .await
+            .unwrap());
+
+        // Unchanged comments are not reported again, edited ones are
+        assert!(!db
+            .upsert_jira_comment("web-001.1", &comment("10001", "Use the design tokens", 5))
+            .await
+            .unwrap());
+        assert!(db
+            .upsert_jira_comment("web-001.1", &comment("10001", "Use design tokens v2", 5))
+            .await
+            .unwrap());
+
+        let comments = db.list_jira_comments("web-001.1").await.unwrap();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].comment_id, "10000");
+        assert_eq!(comments[1].body, "Use design tokens v2");
+        assert!(db.list_jira_comments("web-001.2").await.unwrap().is_empty());
+
+        let context = comments_context(&comments).unwrap();
+        assert!(context.starts_with("Jira comments on WEB-2:"));
+        assert!(context.contains("Jane Doe"));
+        assert!(comments_context(&[]).is_none());
+    }
+}
@@ -0,0 +1,88 @@
+//! Database tests for Linear sync operations
+
+#[cfg(test)]
+mod tests {
+    use crate::linear::*;
+    use crate::{AgentType, Database, StoryStatus};
+
+    fn issue(id: &str, identifier: &str) -> LinearIssue {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "identifier": identifier,
+            "title": "Add login form",
+            "url": format!("https://linear.app/acme/issue/{}", identifier),
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_linear_team_crud() {
+        let db = Database::in_memory().await.unwrap();
+
+        let team = LinearTeam::new("eng", "team-uuid")
+            .with_epic_prefix("eng-")
+            .with_status(StoryStatus::Completed, "Merged")
+            .with_trigger_label("agent")
+            .with_agent_type(AgentType::IssueFixer);
+        let id = db.insert_linear_team(&team).await.unwrap();
+
+        let stored = db.get_linear_team("eng").await.unwrap().unwrap();
+        assert_eq!(stored.id, Some(id));
+        assert_eq!(stored.linear_team_id, "team-uuid");
+        assert_eq!(stored.epic_prefix, "eng-");
+        assert_eq!(stored.linear_state(StoryStatus::Completed), Some("Merged"));
+        assert_eq!(stored.trigger_label, "agent");
+        assert_eq!(stored.agent_type, AgentType::IssueFixer);
+        assert!(db.get_linear_team_by_id(id).await.unwrap().is_some());
+
+        assert_eq!(db.list_linear_teams().await.unwrap().len(), 1);
+        assert!(db.delete_linear_team("eng").await.unwrap());
+        assert!(!db.delete_linear_team("eng").await.unwrap());
+        assert!(db.get_linear_team("eng").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_linear_issue_links() {
+        let db = Database::in_memory().await.unwrap();
+        let team_id = db
+            .insert_linear_team(&LinearTeam::new("eng", "team-uuid"))
+            .await
+            .unwrap();
+
+        let mut link =
+            LinearIssueLink::new(team_id, &issue("issue-1", "ENG-1")).with_story("eng-001.1");
+        let id = db.upsert_linear_issue_link(&link).await.unwrap();
+
+        link.synced_status = Some(StoryStatus::InProgress);
+        assert_eq!(db.upsert_linear_issue_link(&link).await.unwrap(), id);
+
+        let stored = db
+            .get_linear_story_link("eng-001.1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.issue_id, "issue-1");
+        assert_eq!(stored.identifier, "ENG-1");
+        assert_eq!(stored.synced_status, Some(StoryStatus::InProgress));
+        assert!(db
+            .get_linear_story_link("eng-001.2")
+            .await
+            .unwrap()
+            .is_none());
+
+        let agent_id = uuid::Uuid::new_v4();
+        db.upsert_linear_issue_link(
+            &LinearIssueLink::new(team_id, &issue("issue-2", "ENG-2")).with_agent(agent_id),
+        )
+        .await
+        .unwrap();
+        let spawned = db.get_linear_issue_link("issue-2").await.unwrap().unwrap();
+        assert_eq!(spawned.agent_id, Some(agent_id));
+        assert!(spawned.story_id.is_none());
+
+        // Removing the team removes its links
+        db.delete_linear_team("eng").await.unwrap();
+        assert!(db.get_linear_issue_link("issue-1").await.unwrap().is_none());
+        assert!(db.get_linear_issue_link("issue-2").await.unwrap().is_none());
+    }
+}
@@ -280,6 +280,7 @@ mod tests {
         let updated = db.get_pipeline_stage(stage_id).await.unwrap().unwrap();
         assert_eq!(updated.status, PipelineStageStatus::Running);
         assert!(updated.started_at.is_some());
+        assert_eq!(updated.attempts, 1);
     }
 
     #[tokio::test]
@@ -432,4 +433,265 @@ mod tests {
         assert!(db.get_pipeline_run(run_id).await.unwrap().is_none());
         assert!(db.get_pipeline_stage(stage_id).await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn test_pipeline_run_cache_fields_round_trip() {
+        let db = Database::in_memory().await.unwrap();
+
+        let pipeline = Pipeline::new("cache-fields".to_string(), "stages: []".to_string());
+        let pipeline_id = db.insert_pipeline(&pipeline).await.unwrap();
+
+        let run = PipelineRun::new(pipeline_id, None)
+            .with_commit_sha("abc123")
+            .without_cache();
+        let run_id = db.insert_pipeline_run(&run).await.unwrap();
+
+        let retrieved = db.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(retrieved.commit_sha.as_deref(), Some("abc123"));
+        assert!(!retrieved.use_cache);
+
+        let stage = PipelineStage::new(run_id, "build".to_string());
+        let stage_id = db.insert_pipeline_stage(&stage).await.unwrap();
+        let mut stage = db.get_pipeline_stage(stage_id).await.unwrap().unwrap();
+        stage.mark_cached(7);
+        db.update_pipeline_stage(&stage).await.unwrap();
+
+        let stage = db.get_pipeline_stage(stage_id).await.unwrap().unwrap();
+        assert_eq!(stage.status, PipelineStageStatus::Succeeded);
+        assert_eq!(stage.cached_from_run_id, Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_pipeline_run_by_trigger() {
+        let db = Database::in_memory().await.unwrap();
+
+        let pipeline = Pipeline::new("nightly".to_string(), "stages: []".to_string());
+        let pipeline_id = db.insert_pipeline(&pipeline).await.unwrap();
+
+        assert!(db
+            .get_latest_pipeline_run_by_trigger(pipeline_id, "schedule:@daily")
+            .await
+            .unwrap()
+            .is_none());
+
+        let mut first = PipelineRun::new(pipeline_id, Some("schedule:@daily".to_string()));
+        first.created_at -= chrono::Duration::days(1);
+        db.insert_pipeline_run(&first).await.unwrap();
+        let second = PipelineRun::new(pipeline_id, Some("schedule:@daily".to_string()));
+        let second_id = db.insert_pipeline_run(&second).await.unwrap();
+        db.insert_pipeline_run(&PipelineRun::new(pipeline_id, Some("manual".to_string())))
+            .await
+            .unwrap();
+
+        let latest = db
+            .get_latest_pipeline_run_by_trigger(pipeline_id, "schedule:@daily")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.id, Some(second_id));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_run_variables_round_trip() {
+        let db = Database::in_memory().await.unwrap();
+
+        let pipeline = Pipeline::new("run-variables".to_string(), "stages: []".to_string());
+        let pipeline_id = db.insert_pipeline(&pipeline).await.unwrap();
+
+        let run_id = db
+            .insert_pipeline_run(&PipelineRun::new(pipeline_id, None))
+            .await
+            .unwrap();
+        let retrieved = db.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert!(retrieved.variables.is_empty());
+
+        let variables = std::collections::HashMap::from([
+            ("pr_number".to_string(), "42".to_string()),
+            ("label".to_string(), "release".to_string()),
+        ]);
+        let run = PipelineRun::new(pipeline_id, Some("pull_request.labeled".to_string()))
+            .with_variables(variables.clone());
+        let run_id = db.insert_pipeline_run(&run).await.unwrap();
+
+        let retrieved = db.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(retrieved.variables, variables);
+    }
+
+    #[tokio::test]
+    async fn test_stage_cache_entries() {
+        let db = Database::in_memory().await.unwrap();
+
+        let pipeline = Pipeline::new("stage-cache".to_string(), "stages: []".to_string());
+        let pipeline_id = db.insert_pipeline(&pipeline).await.unwrap();
+
+        assert!(db
+            .find_stage_cache_run(pipeline_id, "build", "key-1")
+            .await
+            .unwrap()
+            .is_none());
+
+        db.insert_stage_cache_entry(pipeline_id, "build", "key-1", "abc123", 1)
+            .await
+            .unwrap();
+        db.insert_stage_cache_entry(pipeline_id, "build", "key-1", "abc123", 2)
+            .await
+            .unwrap();
+        db.insert_stage_cache_entry(pipeline_id, "test", "key-2", "abc123", 2)
+            .await
+            .unwrap();
+
+        // A repeated key points at the latest run
+        assert_eq!(
+            db.find_stage_cache_run(pipeline_id, "build", "key-1")
+                .await
+                .unwrap(),
+            Some(2)
+        );
+        assert!(db
+            .find_stage_cache_run(pipeline_id, "test", "key-1")
+            .await
+            .unwrap()
+            .is_none());
+
+        assert_eq!(db.clear_stage_cache(pipeline_id).await.unwrap(), 2);
+        assert!(db
+            .find_stage_cache_run(pipeline_id, "build", "key-1")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_template_crud() {
+        use crate::{PipelineTemplate, TemplateLibrary};
+
+        let db = Database::in_memory().await.unwrap();
+        let yaml = r#"
+parameters:
+  package: ~
+name: ${{ params.package }}-ci
+description: CI
+stages:
+  - name: build
+    agent: builder
+    task: Build ${{ params.package }}
+"#;
+        db.upsert_pipeline_template(&PipelineTemplate::new("rust-ci", "Rust CI", yaml))
+            .await
+            .unwrap();
+        db.upsert_pipeline_template(&PipelineTemplate::new("rust-ci", "Canonical Rust CI", yaml))
+            .await
+            .unwrap();
+
+        let template = db.get_pipeline_template("rust-ci").await.unwrap().unwrap();
+        assert_eq!(template.description, "Canonical Rust CI");
+        assert_eq!(db.list_pipeline_templates().await.unwrap().len(), 1);
+
+        let library = TemplateLibrary::load(&db).await.unwrap();
+        assert!(library.get("rust-ci").is_some());
+        assert!(library.get("ci").is_some());
+
+        // Templates referencing undeclared parameters are rejected
+        let invalid = PipelineTemplate::new("bad", "", "name: ${{ params.missing }}\n");
+        assert!(db.upsert_pipeline_template(&invalid).await.is_err());
+
+        assert!(db.delete_pipeline_template("rust-ci").await.unwrap());
+        assert!(!db.delete_pipeline_template("rust-ci").await.unwrap());
+        assert!(db.get_pipeline_template("rust-ci").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_admit_pipeline_run_concurrency() {
+        use crate::{ConcurrencyAction, PipelineConcurrency, RunAdmission};
+
+        let db = Database::in_memory().await.unwrap();
+        let pipeline = Pipeline::new("deploy".to_string(), "stages: []".to_string());
+        let pipeline_id = db.insert_pipeline(&pipeline).await.unwrap();
+        let run = PipelineRun::new(pipeline_id, Some("push".to_string()));
+        let policy = |on_limit| PipelineConcurrency { limit: 1, on_limit };
+
+        // Without a limit every run is admitted
+        let first = db.admit_pipeline_run(&run, None).await.unwrap();
+        assert!(matches!(first, RunAdmission::Admitted { .. }));
+
+        let queued = db
+            .admit_pipeline_run(&run, Some(&policy(ConcurrencyAction::Queue)))
+            .await
+            .unwrap();
+        assert_eq!(
+            queued,
+            RunAdmission::Queued {
+                run_id: queued.run_id(),
+                position: 1
+            }
+        );
+
+        let rejected = db
+            .admit_pipeline_run(&run, Some(&policy(ConcurrencyAction::Reject)))
+            .await
+            .unwrap();
+        assert!(matches!(
+            rejected,
+            RunAdmission::Rejected { in_progress: 2, .. }
+        ));
+        let rejected_run = db
+            .get_pipeline_run(rejected.run_id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rejected_run.status, PipelineRunStatus::Cancelled);
+
+        // Cancelling in-progress runs frees every slot but the new run's
+        let admitted = db
+            .admit_pipeline_run(&run, Some(&policy(ConcurrencyAction::CancelInProgress)))
+            .await
+            .unwrap();
+        assert_eq!(
+            admitted,
+            RunAdmission::Admitted {
+                run_id: admitted.run_id(),
+                cancelled: vec![first.run_id(), queued.run_id()]
+            }
+        );
+        let active = db.list_active_pipeline_runs(pipeline_id).await.unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, Some(admitted.run_id()));
+    }
+
+    #[tokio::test]
+    async fn test_admit_pipeline_runs_concurrently() {
+        use crate::{ConcurrencyAction, PipelineConcurrency, RunAdmission};
+
+        // A file database, so admissions run on separate connections
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = Database::new(dir.path().join("test.db")).await.unwrap();
+        let pipeline = Pipeline::new("deploy".to_string(), "stages: []".to_string());
+        let pipeline_id = db.insert_pipeline(&pipeline).await.unwrap();
+        let run = PipelineRun::new(pipeline_id, Some("push".to_string()));
+        let policy = PipelineConcurrency {
+            limit: 2,
+            on_limit: ConcurrencyAction::Reject,
+        };
+
+        let admissions =
+            futures::future::join_all((0..10).map(|_| db.admit_pipeline_run(&run, Some(&policy))))
+                .await;
+
+        let admitted = admissions
+            .iter()
+            .filter(|a| matches!(a, Ok(RunAdmission::Admitted { .. })))
+            .count();
+        let rejected = admissions
+            .iter()
+            .filter(|a| matches!(a, Ok(RunAdmission::Rejected { .. })))
+            .count();
+        assert_eq!((admitted, rejected), (2, 8));
+        assert_eq!(
+            db.list_active_pipeline_runs(pipeline_id)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
 }
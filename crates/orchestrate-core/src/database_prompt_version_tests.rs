@@ -0,0 +1,94 @@
+//! Database tests for prompt versions and system prompt overrides
+
+use crate::{Agent, AgentType, Database};
+
+#[tokio::test]
+async fn test_agent_system_prompt_persisted() {
+    let db = Database::in_memory().await.unwrap();
+
+    let agent = Agent::new(AgentType::StoryDeveloper, "Implement story")
+        .with_system_prompt("You are a meticulous developer.");
+    db.insert_agent(&agent).await.unwrap();
+
+    let loaded = db.get_agent(agent.id).await.unwrap().unwrap();
+    assert_eq!(
+        loaded.system_prompt.as_deref(),
+        Some("You are a meticulous developer.")
+    );
+}
+
+#[tokio::test]
+async fn test_get_or_create_prompt_version() {
+    let db = Database::in_memory().await.unwrap();
+
+    let first = db
+        .get_or_create_prompt_version("story_developer", "Prompt A", None)
+        .await
+        .unwrap();
+    assert_eq!(first.version, 1);
+    assert!(!first.is_active);
+
+    // Same content reuses the version
+    let again = db
+        .get_or_create_prompt_version("story_developer", "Prompt A", None)
+        .await
+        .unwrap();
+    assert_eq!(again.id, first.id);
+
+    let second = db
+        .get_or_create_prompt_version("story_developer", "Prompt B", Some("Experiment"))
+        .await
+        .unwrap();
+    assert_eq!(second.version, 2);
+    assert_eq!(second.description.as_deref(), Some("Experiment"));
+
+    // Versions are numbered per agent type
+    let other = db
+        .get_or_create_prompt_version("code_reviewer", "Prompt A", None)
+        .await
+        .unwrap();
+    assert_eq!(other.version, 1);
+
+    let versions = db.list_prompt_versions("story_developer").await.unwrap();
+    assert_eq!(versions.len(), 2);
+    assert_eq!(
+        db.get_prompt_version(second.id).await.unwrap().unwrap().content,
+        "Prompt B"
+    );
+}
+
+#[tokio::test]
+async fn test_record_agent_prompt_override_and_outcomes() {
+    let db = Database::in_memory().await.unwrap();
+
+    let plain = Agent::new(AgentType::CodeReviewer, "Review");
+    assert!(db.record_agent_prompt_override(&plain).await.unwrap().is_none());
+
+    let agent = Agent::new(AgentType::CodeReviewer, "Review")
+        .with_custom_type("strict-reviewer")
+        .with_system_prompt("Reject anything without tests.");
+    let version = db
+        .record_agent_prompt_override(&agent)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(version.agent_type, "strict-reviewer");
+    assert!(version.description.unwrap().contains(&agent.id.to_string()));
+
+    assert!(db.get_prompt_effectiveness(version.id).await.unwrap().is_none());
+
+    db.record_prompt_version_outcome(version.id, true, 1000, 30.0)
+        .await
+        .unwrap();
+    db.record_prompt_version_outcome(version.id, false, 3000, 90.0)
+        .await
+        .unwrap();
+
+    let effectiveness = db.get_prompt_effectiveness(version.id).await.unwrap().unwrap();
+    assert_eq!(effectiveness.usage_count, 2);
+    assert_eq!(effectiveness.success_count, 1);
+    assert_eq!(effectiveness.failure_count, 1);
+    assert_eq!(effectiveness.success_rate, 0.5);
+    assert_eq!(effectiveness.avg_tokens, 2000.0);
+    assert_eq!(effectiveness.avg_duration_secs, 60.0);
+}
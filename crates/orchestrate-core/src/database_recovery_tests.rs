@@ -277,3 +277,19 @@ async fn test_recovery_attempt_not_found() {
     let result = db.get_recovery_attempt(99999).await.unwrap();
     assert!(result.is_none());
 }
+
+#[tokio::test]
+async fn test_script_recovery_attempt_persists() {
+    let db = Database::in_memory().await.unwrap();
+
+    let mut attempt = RecoveryAttempt::new("agent-script", RecoveryActionType::Script)
+        .with_details(serde_json::json!({ "script": "clear-cache" }));
+    let id = db.create_recovery_attempt(&attempt).await.unwrap();
+    attempt.id = id;
+    attempt.fail("Recovery script exited with 1");
+    db.update_recovery_attempt(&attempt).await.unwrap();
+
+    let retrieved = db.get_recovery_attempt(id).await.unwrap().unwrap();
+    assert_eq!(retrieved.action_type, RecoveryActionType::Script);
+    assert_eq!(retrieved.outcome, RecoveryOutcome::Failed);
+}
@@ -0,0 +1,99 @@
+//! Database tests for saga compensation operations
+
+use crate::saga::{
+    CompensationActionType, CompensationDefinition, CompensationStatus, Saga, SagaCompensation,
+    SagaStatus, SagaWorkflowType,
+};
+use crate::Database;
+
+#[tokio::test]
+async fn test_insert_and_get_saga() {
+    let db = Database::in_memory().await.unwrap();
+
+    let mut saga = Saga::new(SagaWorkflowType::PipelineRun, "42");
+    saga.id = db.insert_saga(&saga).await.unwrap();
+    assert!(saga.id > 0);
+
+    let loaded = db.get_saga(saga.id).await.unwrap().unwrap();
+    assert_eq!(loaded.workflow_type, SagaWorkflowType::PipelineRun);
+    assert_eq!(loaded.workflow_id, "42");
+    assert_eq!(loaded.status, SagaStatus::Running);
+
+    let by_workflow = db
+        .get_saga_for_workflow(SagaWorkflowType::PipelineRun, "42")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(by_workflow.id, saga.id);
+
+    assert!(db
+        .get_saga_for_workflow(SagaWorkflowType::Epic, "42")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_update_saga_status() {
+    let db = Database::in_memory().await.unwrap();
+
+    let mut saga = Saga::new(SagaWorkflowType::Epic, "epic-7");
+    saga.id = db.insert_saga(&saga).await.unwrap();
+
+    saga.mark_compensating("deploy", "health check failed");
+    db.update_saga(&saga).await.unwrap();
+
+    let compensating = db.list_sagas(Some(SagaStatus::Compensating)).await.unwrap();
+    assert_eq!(compensating.len(), 1);
+    assert_eq!(compensating[0].failed_step.as_deref(), Some("deploy"));
+
+    saga.mark_compensation_finished(true);
+    db.update_saga(&saga).await.unwrap();
+
+    let loaded = db.get_saga(saga.id).await.unwrap().unwrap();
+    assert_eq!(loaded.status, SagaStatus::Compensated);
+    assert!(loaded.completed_at.is_some());
+    assert_eq!(db.list_sagas(None).await.unwrap().len(), 1);
+    assert!(db
+        .list_sagas(Some(SagaStatus::Compensating))
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_saga_compensations_roundtrip() {
+    let db = Database::in_memory().await.unwrap();
+
+    let mut saga = Saga::new(SagaWorkflowType::PipelineRun, "1");
+    saga.id = db.insert_saga(&saga).await.unwrap();
+
+    let open_pr = CompensationDefinition::new(CompensationActionType::RevertPr)
+        .with_param("pr_number", "12");
+    let branch = CompensationDefinition::new(CompensationActionType::DeleteBranch)
+        .with_agent("git-janitor")
+        .with_param("branch", "feature/x");
+
+    let mut first = SagaCompensation::from_definition(saga.id, "open-pr", 1, &open_pr);
+    first.id = db.insert_saga_compensation(&first).await.unwrap();
+    let second = SagaCompensation::from_definition(saga.id, "push", 2, &branch);
+    db.insert_saga_compensation(&second).await.unwrap();
+
+    first.mark_running();
+    first.mark_failed("PR already merged");
+    db.update_saga_compensation(&first).await.unwrap();
+
+    let compensations = db.list_saga_compensations(saga.id).await.unwrap();
+    assert_eq!(compensations.len(), 2);
+    assert_eq!(compensations[0].step_name, "open-pr");
+    assert_eq!(compensations[0].status, CompensationStatus::Failed);
+    assert_eq!(
+        compensations[0].error_message.as_deref(),
+        Some("PR already merged")
+    );
+    assert!(compensations[0].executed_at.is_some());
+    assert_eq!(compensations[0].params.get("pr_number").unwrap(), "12");
+    assert_eq!(compensations[1].agent, "git-janitor");
+    assert_eq!(compensations[1].action, CompensationActionType::DeleteBranch);
+    assert_eq!(compensations[1].status, CompensationStatus::Pending);
+}
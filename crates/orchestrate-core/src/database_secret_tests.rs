@@ -0,0 +1,52 @@
+//! Database tests for secret vault operations
+
+use crate::secrets::{SecretVault, SecretsManager};
+use crate::Database;
+
+#[tokio::test]
+async fn test_set_and_get_secret() {
+    let db = Database::in_memory().await.unwrap();
+
+    db.set_secret("DEPLOY_TOKEN", "encrypted-1").await.unwrap();
+    db.set_secret("DEPLOY_TOKEN", "encrypted-2").await.unwrap();
+    db.set_secret("API_KEY", "encrypted-3").await.unwrap();
+
+    assert_eq!(
+        db.get_secret("DEPLOY_TOKEN").await.unwrap().as_deref(),
+        Some("encrypted-2")
+    );
+    assert_eq!(db.get_secret("MISSING").await.unwrap(), None);
+    assert_eq!(
+        db.list_secret_names().await.unwrap(),
+        vec!["API_KEY", "DEPLOY_TOKEN"]
+    );
+
+    assert!(db.delete_secret("API_KEY").await.unwrap());
+    assert!(!db.delete_secret("API_KEY").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_vault_stores_secrets_encrypted() {
+    let db = Database::in_memory().await.unwrap();
+    let vault = SecretVault::new(db.clone(), SecretsManager::from_passphrase("test"));
+
+    vault.set("DEPLOY_TOKEN", "s3cr3t-value").await.unwrap();
+
+    let stored = db.get_secret("DEPLOY_TOKEN").await.unwrap().unwrap();
+    assert!(!stored.contains("s3cr3t-value"));
+    assert_eq!(
+        vault.get("DEPLOY_TOKEN").await.unwrap().as_deref(),
+        Some("s3cr3t-value")
+    );
+
+    let resolved = vault.resolve(&["DEPLOY_TOKEN".to_string()]).await.unwrap();
+    assert_eq!(resolved["DEPLOY_TOKEN"], "s3cr3t-value");
+
+    let err = vault
+        .resolve(&["DEPLOY_TOKEN".to_string(), "MISSING".to_string()])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Secret 'MISSING' not found"));
+
+    assert!(vault.set("deploy-token", "value").await.is_err());
+}
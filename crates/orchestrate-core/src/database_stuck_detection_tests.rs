@@ -1,7 +1,8 @@
 //! Database tests for stuck detection and work evaluation operations
 
 use crate::stuck_detection::{
-    EvaluationStatus, EvaluationType, StuckDetection, StuckSeverity, StuckType, WorkEvaluation,
+    EvaluationStatus, EvaluationType, StuckDetection, StuckDetectionConfig, StuckSeverity,
+    StuckType, WorkEvaluation,
 };
 use crate::Database;
 
@@ -342,3 +343,82 @@ async fn test_work_evaluation_all_statuses() {
         assert_eq!(retrieved.status, status);
     }
 }
+
+#[tokio::test]
+async fn test_stuck_detection_new_types_persist() {
+    let db = Database::in_memory().await.unwrap();
+
+    // Migrations are re-run on every open; the table rebuild must be skipped
+    db.run_migrations().await.unwrap();
+
+    for detection_type in [StuckType::NoFileChanges, StuckType::TokenBurn] {
+        let detection = StuckDetection::new("agent-new", detection_type, StuckSeverity::Medium);
+        let id = db.create_stuck_detection(&detection).await.unwrap();
+        let retrieved = db.get_stuck_detection(id).await.unwrap().unwrap();
+        assert_eq!(retrieved.detection_type, detection_type);
+    }
+}
+
+#[tokio::test]
+async fn test_stuck_detection_config_crud() {
+    let db = Database::in_memory().await.unwrap();
+
+    assert!(db.get_stuck_detection_config("story_developer").await.unwrap().is_none());
+    assert_eq!(
+        db.resolve_stuck_detection_config("story_developer").await.unwrap(),
+        StuckDetectionConfig::default()
+    );
+
+    let mut config = StuckDetectionConfig {
+        token_burn_threshold: 20_000,
+        ..Default::default()
+    };
+    config.set_enabled(StuckType::ReviewDelay, false);
+    db.upsert_stuck_detection_config("story_developer", &config)
+        .await
+        .unwrap();
+
+    let stored = db
+        .get_stuck_detection_config("story_developer")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored, config);
+    assert!(!stored.is_enabled(StuckType::ReviewDelay));
+
+    config.token_burn_threshold = 30_000;
+    db.upsert_stuck_detection_config("story_developer", &config)
+        .await
+        .unwrap();
+    let configs = db.list_stuck_detection_configs().await.unwrap();
+    assert_eq!(configs.len(), 1);
+    assert_eq!(configs[0].1.token_burn_threshold, 30_000);
+
+    assert!(db.delete_stuck_detection_config("story_developer").await.unwrap());
+    assert!(!db.delete_stuck_detection_config("story_developer").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_resolve_stuck_detection_config_falls_back_to_default_type() {
+    let db = Database::in_memory().await.unwrap();
+
+    let default_config = StuckDetectionConfig {
+        error_loop_threshold: 7,
+        ..Default::default()
+    };
+    db.upsert_stuck_detection_config(StuckDetectionConfig::DEFAULT_AGENT_TYPE, &default_config)
+        .await
+        .unwrap();
+    let pr_config = StuckDetectionConfig {
+        error_loop_threshold: 2,
+        ..Default::default()
+    };
+    db.upsert_stuck_detection_config("pr_shepherd", &pr_config)
+        .await
+        .unwrap();
+
+    let resolved = db.resolve_stuck_detection_config("story_developer").await.unwrap();
+    assert_eq!(resolved.error_loop_threshold, 7);
+    let resolved = db.resolve_stuck_detection_config("pr_shepherd").await.unwrap();
+    assert_eq!(resolved.error_loop_threshold, 2);
+}
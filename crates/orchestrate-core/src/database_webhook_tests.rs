@@ -2,7 +2,11 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::{Database, WebhookEvent, WebhookEventStatus};
+    use crate::{
+        Agent, AgentState, AgentType, CustomWebhook, Database, OutboundDeliveryStatus,
+        OutboundEvent, OutboundEventType, OutboundWebhook, SignatureScheme, WebhookEvent,
+        WebhookEventStatus, WebhookSource,
+    };
 
     #[tokio::test]
     async fn test_insert_webhook_event() {
@@ -284,4 +288,159 @@ mod tests {
         let retrieved = db.get_webhook_event(id).await.unwrap();
         assert!(retrieved.is_none());
     }
+
+    #[tokio::test]
+    async fn test_custom_webhook_crud() {
+        let db = Database::in_memory().await.unwrap();
+
+        let webhook = CustomWebhook::new(
+            "sentry".to_string(),
+            "name: sentry\nevent: sentry_issue\n".to_string(),
+        );
+        let id = db.insert_custom_webhook(&webhook).await.unwrap();
+
+        // Names are unique
+        assert!(db.insert_custom_webhook(&webhook).await.is_err());
+
+        let mut stored = db
+            .get_custom_webhook_by_name("sentry")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.id, Some(id));
+        assert!(stored.enabled);
+
+        stored.enabled = false;
+        db.update_custom_webhook(&stored).await.unwrap();
+        let listed = db.list_custom_webhooks().await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert!(!listed[0].enabled);
+
+        assert!(db.delete_custom_webhook("sentry").await.unwrap());
+        assert!(!db.delete_custom_webhook("sentry").await.unwrap());
+        assert!(db
+            .get_custom_webhook_by_name("sentry")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_outbound_webhook_crud_and_enqueue() {
+        let db = Database::in_memory().await.unwrap();
+
+        let pipelines = OutboundWebhook::new(
+            "https://example.com/pipelines".to_string(),
+            vec!["pipeline.*".to_string()],
+        )
+        .with_secret("s3cret")
+        .with_description("CI dashboard");
+        let pipelines_id = db.insert_outbound_webhook(&pipelines).await.unwrap();
+        let agents_id = db
+            .insert_outbound_webhook(&OutboundWebhook::new(
+                "https://example.com/agents".to_string(),
+                vec!["agent.completed".to_string()],
+            ))
+            .await
+            .unwrap();
+
+        let mut stored = db
+            .get_outbound_webhook(pipelines_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.secret.as_deref(), Some("s3cret"));
+        assert_eq!(stored.events, vec!["pipeline.*".to_string()]);
+        assert_eq!(stored.description.as_deref(), Some("CI dashboard"));
+
+        let failed = OutboundEvent::new(OutboundEventType::PipelineFailed, serde_json::json!({}));
+        assert_eq!(db.enqueue_outbound_event(&failed).await.unwrap(), 1);
+
+        stored.enabled = false;
+        db.update_outbound_webhook(&stored).await.unwrap();
+        assert_eq!(db.enqueue_outbound_event(&failed).await.unwrap(), 0);
+
+        let deliveries = db
+            .list_outbound_deliveries(Some(pipelines_id), 10)
+            .await
+            .unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].event_type, "pipeline.failed");
+        assert_eq!(deliveries[0].status, OutboundDeliveryStatus::Pending);
+        assert_eq!(db.list_due_outbound_deliveries(10).await.unwrap().len(), 1);
+        assert!(db
+            .list_outbound_deliveries(Some(agents_id), 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert!(db.delete_outbound_webhook(pipelines_id).await.unwrap());
+        assert!(!db.delete_outbound_webhook(pipelines_id).await.unwrap());
+        assert!(db
+            .list_outbound_deliveries(None, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(db.list_outbound_webhooks().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_agent_transitions_enqueue_outbound_events() {
+        let db = Database::in_memory().await.unwrap();
+        db.insert_outbound_webhook(&OutboundWebhook::new(
+            "https://example.com/agents".to_string(),
+            vec!["agent.*".to_string()],
+        ))
+        .await
+        .unwrap();
+
+        let mut agent = Agent::new(AgentType::StoryDeveloper, "Implement story");
+        db.insert_agent(&agent).await.unwrap();
+        agent.transition_to(AgentState::Initializing).unwrap();
+        agent.transition_to(AgentState::Running).unwrap();
+        db.update_agent(&agent).await.unwrap();
+        assert!(db
+            .list_outbound_deliveries(None, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        agent.transition_to(AgentState::Completed).unwrap();
+        db.update_agent(&agent).await.unwrap();
+        // Saving the finished agent again is not a new event
+        db.update_agent(&agent).await.unwrap();
+
+        let deliveries = db.list_outbound_deliveries(None, 10).await.unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].event_type, "agent.completed");
+        let payload: serde_json::Value = serde_json::from_str(&deliveries[0].payload).unwrap();
+        assert_eq!(payload["data"]["agent_id"], agent.id.to_string());
+        assert_eq!(payload["data"]["task"], "Implement story");
+    }
+
+    #[tokio::test]
+    async fn test_webhook_source_crud() {
+        let db = Database::in_memory().await.unwrap();
+
+        let source = WebhookSource::new("github", SignatureScheme::HmacSha512, "old-secret");
+        let id = db.insert_webhook_source(&source).await.unwrap();
+
+        let mut stored = db.get_webhook_source("github").await.unwrap().unwrap();
+        assert_eq!(stored.id, Some(id));
+        assert_eq!(stored.scheme, SignatureScheme::HmacSha512);
+        assert_eq!(stored.header, "X-Hub-Signature-512");
+
+        stored.rotate("new-secret", chrono::Duration::hours(1));
+        db.update_webhook_source(&stored).await.unwrap();
+
+        let stored = db.get_webhook_source("github").await.unwrap().unwrap();
+        assert_eq!(stored.secrets.len(), 2);
+        assert!(stored.secrets[0].expires_at.is_some());
+        assert_eq!(stored.secrets[1].secret, "new-secret");
+        assert_eq!(db.list_webhook_sources().await.unwrap().len(), 1);
+
+        assert!(db.delete_webhook_source("github").await.unwrap());
+        assert!(!db.delete_webhook_source("github").await.unwrap());
+        assert!(db.get_webhook_source("github").await.unwrap().is_none());
+    }
 }
@@ -3,11 +3,17 @@
 //! The decision engine determines what action to take next in the autonomous
 //! workflow by evaluating agent output, checking completion criteria, and
 //! detecting when reviews or escalation are needed.
+//!
+//! Escalation thresholds are expressed as a [`DecisionPolicy`], which can be
+//! loaded from YAML and swapped at runtime.
 
 use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+use crate::decision_policy::{DecisionPolicy, PolicyContext, SharedDecisionPolicy};
 
 // Pre-compiled regex patterns for hot paths
 static STATUS_REGEX_PLAIN: Lazy<Regex> = Lazy::new(|| {
@@ -152,6 +158,10 @@ pub enum Decision {
     TransitionState {
         new_state: String,
     },
+    /// Pause the work until a human resumes it
+    Pause {
+        reason: String,
+    },
 }
 
 /// Types of code review
@@ -169,7 +179,7 @@ pub enum ReviewType {
 }
 
 /// Severity levels for escalation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EscalationSeverity {
     /// Informational, can continue
@@ -257,19 +267,35 @@ impl Default for DecisionEngineConfig {
 #[derive(Debug, Clone)]
 pub struct DecisionEngine {
     config: DecisionEngineConfig,
+    policy: SharedDecisionPolicy,
 }
 
 impl DecisionEngine {
     /// Create a new decision engine with default config
     pub fn new() -> Self {
-        Self {
-            config: DecisionEngineConfig::default(),
-        }
+        Self::with_config(DecisionEngineConfig::default())
     }
 
     /// Create a new decision engine with custom config
+    ///
+    /// The config's retry threshold becomes the engine's policy.
     pub fn with_config(config: DecisionEngineConfig) -> Self {
-        Self { config }
+        let policy = DecisionPolicy::from_config(&config);
+        Self {
+            config,
+            policy: Arc::new(RwLock::new(policy)),
+        }
+    }
+
+    /// Replace the decision policy
+    pub fn with_policy(self, policy: DecisionPolicy) -> Self {
+        self.with_shared_policy(Arc::new(RwLock::new(policy)))
+    }
+
+    /// Use a policy that may be swapped at runtime (see [`PolicyReloader`](crate::PolicyReloader))
+    pub fn with_shared_policy(mut self, policy: SharedDecisionPolicy) -> Self {
+        self.policy = policy;
+        self
     }
 
     /// Evaluate agent output and return evaluation result
@@ -599,19 +625,26 @@ impl DecisionEngine {
         current_state: &str,
         retry_count: u32,
     ) -> Decision {
-        // Check for max retries
-        if retry_count >= self.config.max_retries {
-            return Decision::Escalate {
-                reason: format!(
-                    "Maximum retries ({}) exceeded",
-                    self.config.max_retries
-                ),
-                severity: EscalationSeverity::High,
-                context: Some(serde_json::json!({
-                    "retry_count": retry_count,
-                    "last_status": evaluation.status_signal.as_ref().map(|s| s.status.as_str()),
-                })),
-            };
+        let context = PolicyContext::from_evaluation(evaluation, retry_count);
+        self.make_decision_with_context(evaluation, current_state, &context)
+    }
+
+    /// Make a decision, applying the policy to a caller-supplied context
+    /// (cost so far, time of day, severity)
+    pub fn make_decision_with_context(
+        &self,
+        evaluation: &EvaluationResult,
+        current_state: &str,
+        context: &PolicyContext,
+    ) -> Decision {
+        // The first matching policy rule overrides the default decision
+        let policy_decision = self.policy.read().ok().and_then(|policy| {
+            policy
+                .evaluate(context)
+                .and_then(|rule| rule.decision(context))
+        });
+        if let Some(decision) = policy_decision {
+            return decision;
         }
 
         // Use the recommended decision if available
@@ -1009,4 +1042,70 @@ STATUS: COMPLETE - Feature fully implemented
             _ => panic!("Expected Retry decision when auto_escalate_on_error is false"),
         }
     }
+
+    // ==================== Policy Tests ====================
+
+    #[test]
+    fn test_policy_overrides_default_decision() {
+        let policy = DecisionPolicy::from_yaml(
+            r#"
+rules:
+  - name: budget
+    when: { min_cost_usd: 10.0 }
+    then: { action: pause, reason: Over budget }
+  - name: blocked-is-critical
+    when: { status: [BLOCKED] }
+    then: { action: escalate, severity: critical }
+"#,
+        )
+        .unwrap();
+        let engine = DecisionEngine::new().with_policy(policy);
+
+        let eval = engine.evaluate_agent_output("STATUS: BLOCKED - missing credentials");
+        match engine.make_decision(&eval, "executing", 0) {
+            Decision::Escalate { severity, context, .. } => {
+                assert_eq!(severity, EscalationSeverity::Critical);
+                assert_eq!(context.unwrap()["rule"], "blocked-is-critical");
+            }
+            other => panic!("Expected Escalate decision, got {:?}", other),
+        }
+
+        let context = PolicyContext::from_evaluation(&eval, 0).with_cost(12.5);
+        assert_eq!(
+            engine.make_decision_with_context(&eval, "executing", &context),
+            Decision::Pause {
+                reason: "Over budget".to_string()
+            }
+        );
+
+        // Replacing the policy drops the config-derived retry threshold
+        let eval = engine.evaluate_agent_output("Normal progress, no status signal");
+        assert_eq!(
+            engine.make_decision(&eval, "planning", 10),
+            Decision::TransitionState {
+                new_state: "executing".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_shared_policy_swapped_at_runtime() {
+        let shared: SharedDecisionPolicy = Arc::new(RwLock::new(DecisionPolicy::default()));
+        let engine = DecisionEngine::new().with_shared_policy(shared.clone());
+        let eval = engine.evaluate_agent_output("STATUS: WAITING - CI running");
+
+        assert!(matches!(
+            engine.make_decision(&eval, "executing", 0),
+            Decision::Wait { timeout_seconds: Some(300), .. }
+        ));
+
+        *shared.write().unwrap() = DecisionPolicy::from_yaml(
+            "rules:\n  - {name: short-wait, when: {status: [WAITING]}, then: {action: wait, timeout_seconds: 30}}\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            engine.make_decision(&eval, "executing", 0),
+            Decision::Wait { timeout_seconds: Some(30), .. }
+        ));
+    }
 }
@@ -0,0 +1,511 @@
+//! Decision Policy DSL
+//!
+//! Rules that steer the [`DecisionEngine`](crate::DecisionEngine): each rule
+//! has conditions on the agent's status signal, cost, time of day, severity
+//! and retry count, and a decision to take when they all hold. Rules are
+//! evaluated in order and the first match wins.
+//!
+//! ```yaml
+//! rules:
+//!   - name: budget-exceeded
+//!     when:
+//!       min_cost_usd: 25.0
+//!     then:
+//!       action: pause
+//!       reason: Story budget exceeded
+//!   - name: night-errors
+//!     when:
+//!       status: [ERROR]
+//!       hours: { start: 22, end: 6 }
+//!     then:
+//!       action: escalate
+//!       severity: critical
+//! ```
+//!
+//! Policies are loaded from YAML; [`PolicyReloader`] re-reads the file when it
+//! changes so a running daemon picks up edits without restarting.
+
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use crate::decision_engine::{
+    AgentStatus, Decision, DecisionEngineConfig, EscalationSeverity, EvaluationResult, WaitType,
+};
+use crate::{Error, Result};
+
+/// Hour range in UTC, `start` inclusive and `end` exclusive; wraps past midnight when `start > end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HourRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl HourRange {
+    pub fn contains(&self, hour: u32) -> bool {
+        if self.start <= self.end {
+            hour >= self.start && hour < self.end
+        } else {
+            hour >= self.start || hour < self.end
+        }
+    }
+}
+
+/// Conditions of a rule; all that are set must hold
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyCondition {
+    /// Agent status signal is one of these
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Vec<AgentStatus>>,
+    /// Cost so far is at least this much (USD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_cost_usd: Option<f64>,
+    /// Cost so far is below this much (USD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost_usd: Option<f64>,
+    /// Current UTC hour falls in this range
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hours: Option<HourRange>,
+    /// Severity is at least this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_severity: Option<EscalationSeverity>,
+    /// Retry count is at least this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_retries: Option<u32>,
+}
+
+impl PolicyCondition {
+    pub fn matches(&self, context: &PolicyContext) -> bool {
+        if let Some(statuses) = &self.status {
+            if !context.status.is_some_and(|s| statuses.contains(&s)) {
+                return false;
+            }
+        }
+        if self.min_cost_usd.is_some_and(|min| context.cost_usd < min) {
+            return false;
+        }
+        if self.max_cost_usd.is_some_and(|max| context.cost_usd >= max) {
+            return false;
+        }
+        if let Some(hours) = &self.hours {
+            if !hours.contains(context.now.hour()) {
+                return false;
+            }
+        }
+        if let Some(min) = self.min_severity {
+            if context.severity.is_none_or(|s| s < min) {
+                return false;
+            }
+        }
+        if self.min_retries.is_some_and(|min| context.retry_count < min) {
+            return false;
+        }
+        true
+    }
+}
+
+fn default_escalation_severity() -> EscalationSeverity {
+    EscalationSeverity::High
+}
+
+/// Decision taken when a rule matches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PolicyAction {
+    /// Keep the engine's default decision and stop evaluating rules
+    Continue,
+    /// Escalate for human intervention
+    Escalate {
+        #[serde(default = "default_escalation_severity")]
+        severity: EscalationSeverity,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    /// Pause the work until a human resumes it
+    Pause {
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    /// Retry the current task
+    Retry {
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    /// Wait before continuing
+    Wait {
+        #[serde(default)]
+        timeout_seconds: Option<u32>,
+    },
+}
+
+/// A named policy rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyRule {
+    pub name: String,
+    #[serde(default)]
+    pub when: PolicyCondition,
+    pub then: PolicyAction,
+}
+
+impl PolicyRule {
+    pub fn new(name: impl Into<String>, when: PolicyCondition, then: PolicyAction) -> Self {
+        Self {
+            name: name.into(),
+            when,
+            then,
+        }
+    }
+
+    /// Decision for this rule, or `None` for `continue`
+    pub fn decision(&self, context: &PolicyContext) -> Option<Decision> {
+        let default_reason = || format!("Policy rule '{}' matched", self.name);
+        let rule_context = || {
+            Some(serde_json::json!({
+                "rule": self.name,
+                "retry_count": context.retry_count,
+                "cost_usd": context.cost_usd,
+                "last_status": context.status.map(|s| s.as_str()),
+            }))
+        };
+
+        match &self.then {
+            PolicyAction::Continue => None,
+            PolicyAction::Escalate { severity, reason } => Some(Decision::Escalate {
+                reason: reason.clone().unwrap_or_else(default_reason),
+                severity: *severity,
+                context: rule_context(),
+            }),
+            PolicyAction::Pause { reason } => Some(Decision::Pause {
+                reason: reason.clone().unwrap_or_else(default_reason),
+            }),
+            PolicyAction::Retry { reason } => Some(Decision::Retry {
+                reason: reason.clone().unwrap_or_else(default_reason),
+                modified_context: rule_context(),
+            }),
+            PolicyAction::Wait { timeout_seconds } => Some(Decision::Wait {
+                wait_type: WaitType::Timeout,
+                timeout_seconds: *timeout_seconds,
+            }),
+        }
+    }
+}
+
+/// Facts a policy is evaluated against
+#[derive(Debug, Clone)]
+pub struct PolicyContext {
+    pub status: Option<AgentStatus>,
+    pub cost_usd: f64,
+    pub now: DateTime<Utc>,
+    pub severity: Option<EscalationSeverity>,
+    pub retry_count: u32,
+}
+
+impl PolicyContext {
+    pub fn new() -> Self {
+        Self {
+            status: None,
+            cost_usd: 0.0,
+            now: Utc::now(),
+            severity: None,
+            retry_count: 0,
+        }
+    }
+
+    /// Context from an evaluation; severity comes from a recommended escalation
+    pub fn from_evaluation(evaluation: &EvaluationResult, retry_count: u32) -> Self {
+        let severity = match &evaluation.recommended_decision {
+            Some(Decision::Escalate { severity, .. }) => Some(*severity),
+            _ => None,
+        };
+        Self {
+            status: evaluation.status_signal.as_ref().map(|s| s.status),
+            severity,
+            retry_count,
+            ..Self::new()
+        }
+    }
+
+    pub fn with_cost(mut self, cost_usd: f64) -> Self {
+        self.cost_usd = cost_usd;
+        self
+    }
+
+    pub fn with_time(mut self, now: DateTime<Utc>) -> Self {
+        self.now = now;
+        self
+    }
+
+    pub fn with_severity(mut self, severity: EscalationSeverity) -> Self {
+        self.severity = Some(severity);
+        self
+    }
+}
+
+impl Default for PolicyContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An ordered set of policy rules
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecisionPolicy {
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+}
+
+impl DecisionPolicy {
+    /// Rules equivalent to the thresholds of a [`DecisionEngineConfig`]
+    pub fn from_config(config: &DecisionEngineConfig) -> Self {
+        Self {
+            rules: vec![PolicyRule::new(
+                "max_retries",
+                PolicyCondition {
+                    min_retries: Some(config.max_retries),
+                    ..Default::default()
+                },
+                PolicyAction::Escalate {
+                    severity: EscalationSeverity::High,
+                    reason: Some(format!("Maximum retries ({}) exceeded", config.max_retries)),
+                },
+            )],
+        }
+    }
+
+    /// Parse and validate a policy from YAML
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let policy: Self = serde_yaml::from_str(content)
+            .map_err(|e| Error::Config(format!("Invalid decision policy: {}", e)))?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Load a policy from a YAML file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_yaml(&content)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let mut seen = std::collections::HashSet::new();
+        for rule in &self.rules {
+            if rule.name.trim().is_empty() {
+                return Err(Error::Config("Policy rule name cannot be empty".to_string()));
+            }
+            if !seen.insert(rule.name.as_str()) {
+                return Err(Error::Config(format!("Duplicate policy rule '{}'", rule.name)));
+            }
+            if let Some(hours) = &rule.when.hours {
+                if hours.start > 23 || hours.end > 24 {
+                    return Err(Error::Config(format!(
+                        "Rule '{}': hours must be within 0-24",
+                        rule.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// First rule whose conditions hold
+    pub fn evaluate(&self, context: &PolicyContext) -> Option<&PolicyRule> {
+        self.rules.iter().find(|rule| rule.when.matches(context))
+    }
+}
+
+/// Policy shared between a [`DecisionEngine`](crate::DecisionEngine) and its reloader
+pub type SharedDecisionPolicy = Arc<RwLock<DecisionPolicy>>;
+
+/// Reloads a policy file when its modification time changes
+#[derive(Debug)]
+pub struct PolicyReloader {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    policy: SharedDecisionPolicy,
+}
+
+impl PolicyReloader {
+    /// Load the policy file
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let modified = std::fs::metadata(&path)?.modified().ok();
+        let policy = DecisionPolicy::load(&path)?;
+        Ok(Self {
+            path,
+            modified,
+            policy: Arc::new(RwLock::new(policy)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Handle to the live policy, for [`DecisionEngine::with_shared_policy`](crate::DecisionEngine::with_shared_policy)
+    pub fn handle(&self) -> SharedDecisionPolicy {
+        self.policy.clone()
+    }
+
+    /// Reload the policy if the file changed; returns whether it was reloaded
+    ///
+    /// An invalid file leaves the current policy in place.
+    pub fn reload_if_changed(&mut self) -> Result<bool> {
+        let modified = std::fs::metadata(&self.path)?.modified().ok();
+        if modified == self.modified {
+            return Ok(false);
+        }
+        self.modified = modified;
+
+        let policy = DecisionPolicy::load(&self.path)?;
+        *self
+            .policy
+            .write()
+            .map_err(|_| Error::Other("Decision policy lock poisoned".to_string()))? = policy;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const POLICY: &str = r#"
+rules:
+  - name: budget-exceeded
+    when:
+      min_cost_usd: 25.0
+    then:
+      action: pause
+      reason: Story budget exceeded
+  - name: night-errors
+    when:
+      status: [ERROR]
+      hours: { start: 22, end: 6 }
+    then:
+      action: escalate
+      severity: critical
+  - name: waiting
+    when:
+      status: [WAITING]
+    then:
+      action: continue
+"#;
+
+    fn at_hour(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, hour, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        let policy = DecisionPolicy::from_yaml(POLICY).unwrap();
+        assert_eq!(policy.rules.len(), 3);
+        assert_eq!(
+            policy.rules[1].then,
+            PolicyAction::Escalate {
+                severity: EscalationSeverity::Critical,
+                reason: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_policies_rejected() {
+        let duplicate = "rules:\n  - {name: a, then: {action: continue}}\n  - {name: a, then: {action: continue}}\n";
+        assert!(DecisionPolicy::from_yaml(duplicate).is_err());
+
+        let bad_hours = "rules:\n  - {name: a, when: {hours: {start: 25, end: 3}}, then: {action: continue}}\n";
+        assert!(DecisionPolicy::from_yaml(bad_hours).is_err());
+
+        let unknown_condition = "rules:\n  - {name: a, when: {colour: red}, then: {action: continue}}\n";
+        assert!(DecisionPolicy::from_yaml(unknown_condition).is_err());
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        let policy = DecisionPolicy::from_yaml(POLICY).unwrap();
+
+        let context = PolicyContext {
+            status: Some(AgentStatus::Error),
+            ..PolicyContext::new()
+        }
+        .with_time(at_hour(23));
+        assert_eq!(policy.evaluate(&context).unwrap().name, "night-errors");
+
+        let expensive = context.clone().with_cost(30.0);
+        assert_eq!(policy.evaluate(&expensive).unwrap().name, "budget-exceeded");
+
+        let daytime = context.with_time(at_hour(12));
+        assert!(policy.evaluate(&daytime).is_none());
+    }
+
+    #[test]
+    fn test_hour_range_wraps_midnight() {
+        let night = HourRange { start: 22, end: 6 };
+        assert!(night.contains(23));
+        assert!(night.contains(0));
+        assert!(!night.contains(6));
+        assert!(!night.contains(12));
+
+        let office = HourRange { start: 9, end: 17 };
+        assert!(office.contains(9));
+        assert!(!office.contains(17));
+    }
+
+    #[test]
+    fn test_severity_condition() {
+        let condition = PolicyCondition {
+            min_severity: Some(EscalationSeverity::High),
+            ..Default::default()
+        };
+        let low = PolicyContext::new().with_severity(EscalationSeverity::Medium);
+        let critical = PolicyContext::new().with_severity(EscalationSeverity::Critical);
+        assert!(!condition.matches(&low));
+        assert!(condition.matches(&critical));
+        assert!(!condition.matches(&PolicyContext::new()));
+    }
+
+    #[test]
+    fn test_rule_decisions() {
+        let policy = DecisionPolicy::from_yaml(POLICY).unwrap();
+        let context = PolicyContext::new().with_cost(50.0);
+
+        assert_eq!(
+            policy.rules[0].decision(&context),
+            Some(Decision::Pause {
+                reason: "Story budget exceeded".to_string()
+            })
+        );
+        assert_eq!(policy.rules[2].decision(&context), None);
+    }
+
+    #[test]
+    fn test_reloader_picks_up_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.yaml");
+        std::fs::write(&path, POLICY).unwrap();
+
+        let mut reloader = PolicyReloader::new(&path).unwrap();
+        let handle = reloader.handle();
+        assert!(!reloader.reload_if_changed().unwrap());
+        assert_eq!(handle.read().unwrap().rules.len(), 3);
+
+        std::fs::write(&path, "rules: []\n").unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(5))
+            .unwrap();
+
+        assert!(reloader.reload_if_changed().unwrap());
+        assert!(handle.read().unwrap().rules.is_empty());
+
+        // Broken edits keep the last good policy
+        std::fs::write(&path, "rules: [").unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(10))
+            .unwrap();
+        assert!(reloader.reload_if_changed().is_err());
+        assert!(handle.read().unwrap().rules.is_empty());
+    }
+}
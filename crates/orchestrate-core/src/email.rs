@@ -0,0 +1,738 @@
+//! Email Notifications
+//!
+//! Types for the email notification channel:
+//! - SMTP server settings, read from the environment or from an email
+//!   [`NotificationChannel`]
+//! - Messages rendered as MIME with plain-text and HTML alternatives
+//! - Templates for approval requests, daily cost summaries, and incident
+//!   escalations, rendered as HTML digests
+//!
+//! Delivery is handled by [`crate::smtp::SmtpClient`]; recipients are chosen
+//! by [`crate::email_service::EmailNotificationService`].
+
+use base64::Engine;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::monitoring::{NotificationChannel, NotificationChannelType};
+use crate::{Error, Result};
+
+/// How the SMTP connection is secured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    /// TLS from the start of the connection (SMTPS, usually port 465)
+    Implicit,
+    /// Plain connection upgraded with STARTTLS (usually port 587)
+    StartTls,
+    /// No TLS; only for local relays
+    None,
+}
+
+impl SmtpTls {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Implicit => "implicit",
+            Self::StartTls => "starttls",
+            Self::None => "none",
+        }
+    }
+
+    /// Port used when none is configured
+    pub fn default_port(&self) -> u16 {
+        match self {
+            Self::Implicit => 465,
+            Self::StartTls => 587,
+            Self::None => 25,
+        }
+    }
+}
+
+impl FromStr for SmtpTls {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "implicit" | "tls" | "smtps" => Ok(Self::Implicit),
+            "starttls" => Ok(Self::StartTls),
+            "none" => Ok(Self::None),
+            _ => Err(Error::Other(format!("Invalid SMTP TLS mode: {}", s))),
+        }
+    }
+}
+
+/// SMTP server settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub tls: SmtpTls,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Sender address
+    pub from: String,
+}
+
+impl SmtpConfig {
+    /// Settings for `host` using STARTTLS on port 587
+    pub fn new(host: impl Into<String>, from: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port: SmtpTls::StartTls.default_port(),
+            tls: SmtpTls::StartTls,
+            username: None,
+            password: None,
+            from: from.into(),
+        }
+    }
+
+    /// Secure the connection with `tls`, on that mode's default port
+    pub fn with_tls(mut self, tls: SmtpTls) -> Self {
+        self.tls = tls;
+        self.port = tls.default_port();
+        self
+    }
+
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Authenticate with AUTH PLAIN
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.username = Some(username.into());
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Read settings from `ORCHESTRATE_SMTP_*` variables
+    ///
+    /// Returns `None` when `ORCHESTRATE_SMTP_HOST` is unset. `_TLS` is
+    /// `starttls` (default), `implicit`, or `none`; `_PORT` defaults to the
+    /// TLS mode's port; `_FROM` defaults to `orchestrate@<host>`.
+    pub fn from_env() -> Result<Option<Self>> {
+        let var = |key: &str| {
+            std::env::var(format!("ORCHESTRATE_SMTP_{}", key.to_uppercase()))
+                .ok()
+                .filter(|v| !v.is_empty())
+        };
+        Self::from_settings(var)
+    }
+
+    /// Read settings from an email notification channel's config
+    ///
+    /// Uses the keys `host`, `port`, `tls`, `username`, `password`, and
+    /// `from`, with the same defaults as [`SmtpConfig::from_env`].
+    pub fn from_channel(channel: &NotificationChannel) -> Result<Self> {
+        if channel.channel_type != NotificationChannelType::Email {
+            return Err(Error::Other(format!(
+                "Notification channel {} is not an email channel",
+                channel.name
+            )));
+        }
+        Self::from_settings(|key| channel.config.get(key).filter(|v| !v.is_empty()).cloned())?
+            .ok_or_else(|| Error::Other(format!("Email channel {} has no SMTP host", channel.name)))
+    }
+
+    fn from_settings(setting: impl Fn(&str) -> Option<String>) -> Result<Option<Self>> {
+        let Some(host) = setting("host") else {
+            return Ok(None);
+        };
+
+        let from = setting("from").unwrap_or_else(|| format!("orchestrate@{}", host));
+        let mut config = Self::new(host, from);
+        if let Some(tls) = setting("tls") {
+            config = config.with_tls(tls.parse()?);
+        }
+        if let Some(port) = setting("port") {
+            config.port = port
+                .parse()
+                .map_err(|_| Error::Other(format!("Invalid SMTP port: {}", port)))?;
+        }
+        if let (Some(username), Some(password)) = (setting("username"), setting("password")) {
+            config = config.with_credentials(username, password);
+        }
+        Ok(Some(config))
+    }
+}
+
+/// An email with plain-text and HTML bodies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailMessage {
+    pub to: Vec<String>,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+impl EmailMessage {
+    pub fn new(
+        to: Vec<String>,
+        subject: impl Into<String>,
+        text: impl Into<String>,
+        html: impl Into<String>,
+    ) -> Self {
+        Self {
+            to,
+            subject: subject.into(),
+            text: text.into(),
+            html: html.into(),
+        }
+    }
+
+    /// Render as a `multipart/alternative` message from `from`, with CRLF
+    /// line endings
+    ///
+    /// Both bodies are base64 encoded, so lines stay short and non-ASCII
+    /// text survives any relay.
+    pub fn to_mime(&self, from: &str) -> String {
+        let boundary = format!("orchestrate-{}", uuid::Uuid::new_v4().simple());
+        let mut mime = String::new();
+        for (name, value) in [
+            ("From", from.to_string()),
+            ("To", self.to.join(", ")),
+            ("Subject", encode_header(&self.subject)),
+            ("Date", Utc::now().to_rfc2822()),
+            (
+                "Message-ID",
+                format!("<{}@orchestrate>", uuid::Uuid::new_v4().simple()),
+            ),
+            ("MIME-Version", "1.0".to_string()),
+            (
+                "Content-Type",
+                format!("multipart/alternative; boundary=\"{}\"", boundary),
+            ),
+        ] {
+            mime.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        mime.push_str("\r\n");
+
+        for (content_type, body) in [("text/plain", &self.text), ("text/html", &self.html)] {
+            mime.push_str(&format!("--{}\r\n", boundary));
+            mime.push_str(&format!(
+                "Content-Type: {}; charset=utf-8\r\nContent-Transfer-Encoding: base64\r\n\r\n",
+                content_type
+            ));
+            let encoded = base64::engine::general_purpose::STANDARD.encode(body.as_bytes());
+            for line in encoded.as_bytes().chunks(76) {
+                mime.push_str(std::str::from_utf8(line).unwrap_or_default());
+                mime.push_str("\r\n");
+            }
+        }
+        mime.push_str(&format!("--{}--\r\n", boundary));
+        mime
+    }
+}
+
+/// RFC 2047 encode a header value that is not plain ASCII
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        format!(
+            "=?utf-8?B?{}?=",
+            base64::engine::general_purpose::STANDARD.encode(value.as_bytes())
+        )
+    }
+}
+
+/// Escape text for inclusion in HTML
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Content of a digest email: a heading, an optional summary, tables, and a
+/// link
+///
+/// Rendered both as HTML and as the plain-text alternative, so the two stay
+/// in step.
+#[derive(Debug, Clone, Default)]
+pub struct EmailDigest {
+    title: String,
+    summary: String,
+    tables: Vec<DigestTable>,
+    link: Option<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+struct DigestTable {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl EmailDigest {
+    pub fn new(title: impl Into<String>, summary: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            summary: summary.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a table; an empty `headers` renders label/value rows
+    pub fn with_table(mut self, headers: &[&str], rows: Vec<Vec<String>>) -> Self {
+        self.tables.push(DigestTable {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows,
+        });
+        self
+    }
+
+    pub fn with_link(mut self, label: impl Into<String>, url: impl Into<String>) -> Self {
+        self.link = Some((label.into(), url.into()));
+        self
+    }
+
+    /// Render as an email to `to` with `subject`
+    pub fn into_message(self, to: Vec<String>, subject: impl Into<String>) -> EmailMessage {
+        EmailMessage::new(to, subject, self.render_text(), self.render_html())
+    }
+
+    pub fn render_html(&self) -> String {
+        let cell = "padding:4px 12px 4px 0;text-align:left;vertical-align:top";
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html><body style=\"font-family:-apple-system,'Segoe UI',Helvetica,Arial,sans-serif;font-size:14px;color:#24292f\">\n",
+        );
+        html.push_str(&format!("<h2>{}</h2>\n", escape_html(&self.title)));
+        if !self.summary.is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", escape_html(&self.summary)));
+        }
+        for table in &self.tables {
+            html.push_str("<table style=\"border-collapse:collapse;margin-bottom:16px\">\n");
+            if !table.headers.is_empty() {
+                html.push_str("<tr>");
+                for header in &table.headers {
+                    html.push_str(&format!(
+                        "<th style=\"{};border-bottom:1px solid #d0d7de\">{}</th>",
+                        cell,
+                        escape_html(header)
+                    ));
+                }
+                html.push_str("</tr>\n");
+            }
+            for row in &table.rows {
+                html.push_str("<tr>");
+                for (i, value) in row.iter().enumerate() {
+                    if table.headers.is_empty() && i == 0 {
+                        html.push_str(&format!(
+                            "<th style=\"{}\">{}</th>",
+                            cell,
+                            escape_html(value)
+                        ));
+                    } else {
+                        html.push_str(&format!(
+                            "<td style=\"{}\">{}</td>",
+                            cell,
+                            escape_html(value)
+                        ));
+                    }
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table>\n");
+        }
+        if let Some((label, url)) = &self.link {
+            html.push_str(&format!(
+                "<p><a href=\"{}\">{}</a></p>\n",
+                escape_html(url),
+                escape_html(label)
+            ));
+        }
+        html.push_str(
+            "<p style=\"color:#57606a;font-size:12px\">Sent by Orchestrate</p>\n</body></html>\n",
+        );
+        html
+    }
+
+    pub fn render_text(&self) -> String {
+        let mut text = format!("{}\n", self.title);
+        if !self.summary.is_empty() {
+            text.push_str(&format!("\n{}\n", self.summary));
+        }
+        for table in &self.tables {
+            text.push('\n');
+            if !table.headers.is_empty() {
+                text.push_str(&table.headers.join(" | "));
+                text.push('\n');
+            }
+            for row in &table.rows {
+                if table.headers.is_empty() && row.len() == 2 {
+                    text.push_str(&format!("{}: {}\n", row[0], row[1]));
+                } else {
+                    text.push_str(&row.join(" | "));
+                    text.push('\n');
+                }
+            }
+        }
+        if let Some((label, url)) = &self.link {
+            text.push_str(&format!("\n{}: {}\n", label, url));
+        }
+        text
+    }
+}
+
+/// Build common email templates
+pub mod templates {
+    use super::*;
+    use crate::approval::ApprovalRequest;
+    use crate::database::DailyTokenUsage;
+    use crate::incident::{EscalationRule, Incident};
+
+    /// Ask `to` to approve a pipeline stage
+    pub fn approval_request_email(
+        to: Vec<String>,
+        pipeline: &str,
+        run_id: i64,
+        stage: &str,
+        approval: &ApprovalRequest,
+        run_url: Option<&str>,
+    ) -> EmailMessage {
+        let approvers = approval.required_approvers.replace(',', ", ");
+        let mut rows = vec![
+            vec!["Pipeline".to_string(), pipeline.to_string()],
+            vec!["Run".to_string(), format!("#{}", run_id)],
+            vec!["Stage".to_string(), stage.to_string()],
+            vec!["Approvers".to_string(), approvers],
+            vec![
+                "Approvals needed".to_string(),
+                approval.required_count.to_string(),
+            ],
+        ];
+        if let Some(timeout_at) = approval.timeout_at {
+            rows.push(vec![
+                "Times out".to_string(),
+                timeout_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+            ]);
+        }
+        if let Some(id) = approval.id {
+            rows.push(vec![
+                "Approve".to_string(),
+                format!("orchestrate approval approve {}", id),
+            ]);
+        }
+
+        let mut digest = EmailDigest::new(
+            format!("Approval required: {} / {}", pipeline, stage),
+            format!(
+                "Pipeline run #{} is waiting for approval to run stage {}.",
+                run_id, stage
+            ),
+        )
+        .with_table(&[], rows);
+        if let Some(url) = run_url {
+            digest = digest.with_link(format!("View run #{}", run_id), url);
+        }
+        digest.into_message(
+            to,
+            format!(
+                "[orchestrate] Approval required: {} run #{} stage {}",
+                pipeline, run_id, stage
+            ),
+        )
+    }
+
+    /// Summarize token usage and estimated cost for `date`, by model
+    pub fn cost_summary_email(
+        to: Vec<String>,
+        date: NaiveDate,
+        usage: &[DailyTokenUsage],
+    ) -> EmailMessage {
+        let total_cost: f64 = usage.iter().filter_map(|u| u.estimated_cost_usd).sum();
+        let total_requests: i64 = usage.iter().map(|u| u.request_count).sum();
+
+        let mut rows: Vec<Vec<String>> = usage
+            .iter()
+            .map(|u| {
+                vec![
+                    u.model.clone(),
+                    u.request_count.to_string(),
+                    u.total_input_tokens.to_string(),
+                    u.total_output_tokens.to_string(),
+                    format!("${:.2}", u.estimated_cost_usd.unwrap_or_default()),
+                ]
+            })
+            .collect();
+        rows.push(vec![
+            "Total".to_string(),
+            total_requests.to_string(),
+            usage
+                .iter()
+                .map(|u| u.total_input_tokens)
+                .sum::<i64>()
+                .to_string(),
+            usage
+                .iter()
+                .map(|u| u.total_output_tokens)
+                .sum::<i64>()
+                .to_string(),
+            format!("${:.2}", total_cost),
+        ]);
+
+        let summary = if usage.is_empty() {
+            "No token usage was recorded.".to_string()
+        } else {
+            format!(
+                "{} requests across {} models cost an estimated ${:.2}.",
+                total_requests,
+                usage.len(),
+                total_cost
+            )
+        };
+
+        EmailDigest::new(format!("Daily cost summary for {}", date), summary)
+            .with_table(
+                &["Model", "Requests", "Input tokens", "Output tokens", "Cost"],
+                rows,
+            )
+            .into_message(
+                to,
+                format!(
+                    "[orchestrate] Daily cost summary for {}: ${:.2}",
+                    date, total_cost
+                ),
+            )
+    }
+
+    /// Escalate `incident` under `rule`
+    pub fn incident_escalation_email(
+        to: Vec<String>,
+        incident: &Incident,
+        rule: &EscalationRule,
+    ) -> EmailMessage {
+        let mut rows = vec![
+            vec!["Incident".to_string(), incident.id.clone()],
+            vec![
+                "Severity".to_string(),
+                incident.severity.as_str().to_string(),
+            ],
+            vec!["Status".to_string(), incident.status.as_str().to_string()],
+            vec![
+                "Detected".to_string(),
+                incident
+                    .detected_at
+                    .format("%Y-%m-%d %H:%M UTC")
+                    .to_string(),
+            ],
+            vec!["Escalation rule".to_string(), rule.name.clone()],
+        ];
+        if !incident.affected_services.is_empty() {
+            rows.push(vec![
+                "Affected services".to_string(),
+                incident.affected_services.join(", "),
+            ]);
+        }
+
+        let summary = if incident.description.is_empty() {
+            format!("Incident {} has been escalated.", incident.id)
+        } else {
+            incident.description.clone()
+        };
+        let timeline: Vec<Vec<String>> = incident
+            .timeline
+            .iter()
+            .rev()
+            .take(10)
+            .map(|event| {
+                vec![
+                    event.timestamp.format("%Y-%m-%d %H:%M UTC").to_string(),
+                    event.description.clone(),
+                ]
+            })
+            .collect();
+
+        EmailDigest::new(
+            format!(
+                "Escalated {} incident: {}",
+                incident.severity.as_str(),
+                incident.title
+            ),
+            summary,
+        )
+        .with_table(&[], rows)
+        .with_table(&["Time", "Timeline"], timeline)
+        .into_message(
+            to,
+            format!(
+                "[orchestrate] [{}] Incident {} escalated: {}",
+                incident.severity.as_str().to_uppercase(),
+                incident.id,
+                incident.title
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approval::ApprovalRequest;
+    use crate::database::DailyTokenUsage;
+
+    fn decode_part(mime: &str, content_type: &str) -> String {
+        let start = mime
+            .find(&format!("Content-Type: {}", content_type))
+            .unwrap();
+        let body_start = start + mime[start..].find("\r\n\r\n").unwrap() + 4;
+        let body_end = body_start + mime[body_start..].find("--").unwrap();
+        let encoded: String = mime[body_start..body_end].lines().map(str::trim).collect();
+        String::from_utf8(
+            base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_smtp_tls_from_str() {
+        assert_eq!("STARTTLS".parse::<SmtpTls>().unwrap(), SmtpTls::StartTls);
+        assert_eq!("smtps".parse::<SmtpTls>().unwrap(), SmtpTls::Implicit);
+        assert_eq!("none".parse::<SmtpTls>().unwrap(), SmtpTls::None);
+        assert!("ssl3".parse::<SmtpTls>().is_err());
+
+        let config =
+            SmtpConfig::new("smtp.example.com", "ci@example.com").with_tls(SmtpTls::Implicit);
+        assert_eq!(config.port, 465);
+    }
+
+    #[test]
+    fn test_config_from_channel() {
+        let channel = NotificationChannel::new("ops-email", NotificationChannelType::Email)
+            .with_config("host", "smtp.example.com")
+            .with_config("tls", "implicit")
+            .with_config("username", "ci")
+            .with_config("password", "secret");
+        let config = SmtpConfig::from_channel(&channel).unwrap();
+        assert_eq!(config.tls, SmtpTls::Implicit);
+        assert_eq!(config.port, 465);
+        assert_eq!(config.from, "orchestrate@smtp.example.com");
+        assert_eq!(config.username.as_deref(), Some("ci"));
+
+        let channel = NotificationChannel::new("ops-email", NotificationChannelType::Email)
+            .with_config("port", "2525");
+        assert!(SmtpConfig::from_channel(&channel).is_err());
+
+        let channel = NotificationChannel::new("ops-slack", NotificationChannelType::Slack)
+            .with_config("host", "smtp.example.com");
+        assert!(SmtpConfig::from_channel(&channel).is_err());
+    }
+
+    #[test]
+    fn test_mime_has_both_alternatives() {
+        let message = EmailMessage::new(
+            vec!["a@example.com".to_string(), "b@example.com".to_string()],
+            "Café deploy",
+            "Plain body",
+            "<p>HTML body</p>",
+        );
+        let mime = message.to_mime("orchestrate@example.com");
+
+        assert!(mime.starts_with("From: orchestrate@example.com\r\n"));
+        assert!(mime.contains("To: a@example.com, b@example.com\r\n"));
+        assert!(mime.contains("Subject: =?utf-8?B?"));
+        assert!(mime.contains("Content-Type: multipart/alternative; boundary="));
+        assert_eq!(decode_part(&mime, "text/plain"), "Plain body");
+        assert_eq!(decode_part(&mime, "text/html"), "<p>HTML body</p>");
+        assert!(mime.lines().all(|line| line.len() <= 998));
+    }
+
+    #[test]
+    fn test_digest_escapes_html() {
+        let digest = EmailDigest::new("Deploy <prod>", "Fish & chips").with_table(
+            &[],
+            vec![vec!["Stage".to_string(), "\"deploy\"".to_string()]],
+        );
+
+        let html = digest.render_html();
+        assert!(html.contains("<h2>Deploy &lt;prod&gt;</h2>"));
+        assert!(html.contains("<p>Fish &amp; chips</p>"));
+        assert!(html.contains("&quot;deploy&quot;"));
+
+        let text = digest.render_text();
+        assert!(text.contains("Stage: \"deploy\""));
+    }
+
+    #[test]
+    fn test_approval_request_email() {
+        let mut approval = ApprovalRequest::new(5, 42, "alice,bob".to_string(), 2, None, None);
+        approval.id = Some(7);
+
+        let message = templates::approval_request_email(
+            vec!["alice@example.com".to_string()],
+            "deploy",
+            42,
+            "production",
+            &approval,
+            Some("https://orchestrate.example.com/pipelines/deploy/runs/42"),
+        );
+        assert_eq!(
+            message.subject,
+            "[orchestrate] Approval required: deploy run #42 stage production"
+        );
+        assert!(message.text.contains("Approvers: alice, bob"));
+        assert!(message
+            .text
+            .contains("Approve: orchestrate approval approve 7"));
+        assert!(message
+            .html
+            .contains("href=\"https://orchestrate.example.com/pipelines/deploy/runs/42\""));
+    }
+
+    #[test]
+    fn test_cost_summary_email() {
+        let usage = vec![
+            DailyTokenUsage {
+                date: "2026-10-16".to_string(),
+                model: "claude-sonnet".to_string(),
+                total_input_tokens: 1000,
+                total_output_tokens: 200,
+                total_cache_read_tokens: 0,
+                total_cache_write_tokens: 0,
+                request_count: 3,
+                agent_count: 1,
+                estimated_cost_usd: Some(1.25),
+            },
+            DailyTokenUsage {
+                date: "2026-10-16".to_string(),
+                model: "claude-haiku".to_string(),
+                total_input_tokens: 500,
+                total_output_tokens: 100,
+                total_cache_read_tokens: 0,
+                total_cache_write_tokens: 0,
+                request_count: 2,
+                agent_count: 1,
+                estimated_cost_usd: Some(0.5),
+            },
+        ];
+
+        let message = templates::cost_summary_email(
+            vec!["lead@example.com".to_string()],
+            NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+            &usage,
+        );
+        assert_eq!(
+            message.subject,
+            "[orchestrate] Daily cost summary for 2026-10-16: $1.75"
+        );
+        assert!(message
+            .text
+            .contains("5 requests across 2 models cost an estimated $1.75."));
+        assert!(message.text.contains("Total | 5 | 1500 | 300 | $1.75"));
+    }
+}
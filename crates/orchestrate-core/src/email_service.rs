@@ -0,0 +1,308 @@
+//! Email Notification Service
+//!
+//! Sends email notifications to users mapped in `slack_user_mappings` that
+//! have an email address, according to their email preferences:
+//! - Approval requests, to the approvers of a pipeline stage
+//! - The daily cost summary, to users who opted in
+//! - Incident escalations, to users who opted in and to the email targets of
+//!   the escalation rule
+//!
+//! Each recipient gets their own message, so addresses are not shared.
+
+use chrono::NaiveDate;
+use std::collections::BTreeSet;
+use tracing::{info, warn};
+
+use crate::{
+    approval::ApprovalRequest,
+    email::{templates, EmailMessage},
+    error::{Error, Result},
+    incident::{EscalationRule, EscalationTargetType, Incident},
+    slack::UserMapping,
+    slack_service::SlackService,
+    slack_user_service::SlackUserService,
+    smtp::SmtpClient,
+    Database,
+};
+
+/// Service delivering notification emails through an SMTP server
+pub struct EmailNotificationService {
+    db: Database,
+    user_service: SlackUserService,
+    client: SmtpClient,
+}
+
+impl EmailNotificationService {
+    /// Create a new email notification service
+    pub fn new(db: Database, client: SmtpClient) -> Self {
+        let user_service = SlackUserService::new(db.clone(), SlackService::new(db.clone()));
+        Self {
+            db,
+            user_service,
+            client,
+        }
+    }
+
+    /// Service using the SMTP server configured by `ORCHESTRATE_SMTP_*`, if set
+    pub fn from_env(db: Database) -> Result<Option<Self>> {
+        Ok(SmtpClient::from_env()?.map(|client| Self::new(db, client)))
+    }
+
+    /// Addresses of the approvers of `approval` who want approval emails
+    pub async fn approval_recipients(&self, approval: &ApprovalRequest) -> Result<Vec<String>> {
+        let approvers: Vec<&str> = approval
+            .required_approvers
+            .split(',')
+            .map(str::trim)
+            .collect();
+        self.recipients(|mapping| {
+            mapping.email_on_approval && approvers.contains(&mapping.github_username.as_str())
+        })
+        .await
+    }
+
+    /// Addresses of users who want the daily cost summary
+    pub async fn cost_summary_recipients(&self) -> Result<Vec<String>> {
+        self.recipients(|mapping| mapping.email_cost_summary).await
+    }
+
+    /// Addresses to escalate to under `rule`: its email targets and users
+    /// who want incident emails
+    pub async fn incident_recipients(&self, rule: &EscalationRule) -> Result<Vec<String>> {
+        let mut recipients: BTreeSet<String> = self
+            .recipients(|mapping| mapping.email_on_incident)
+            .await?
+            .into_iter()
+            .collect();
+        recipients.extend(
+            rule.targets
+                .iter()
+                .filter(|target| target.target_type == EscalationTargetType::Email)
+                .map(|target| target.destination.clone()),
+        );
+        Ok(recipients.into_iter().collect())
+    }
+
+    /// Email the approvers of a pipeline stage's new approval request
+    ///
+    /// Returns the number of emails sent.
+    pub async fn notify_approval(
+        &self,
+        pipeline: &str,
+        run_id: i64,
+        stage: &str,
+        approval: &ApprovalRequest,
+        run_url: Option<&str>,
+    ) -> Result<usize> {
+        let recipients = self.approval_recipients(approval).await?;
+        self.send_each(recipients, |to| {
+            templates::approval_request_email(to, pipeline, run_id, stage, approval, run_url)
+        })
+        .await
+    }
+
+    /// Email the token usage and estimated cost of `date`
+    ///
+    /// Returns the number of emails sent.
+    pub async fn send_cost_summary(&self, date: NaiveDate) -> Result<usize> {
+        let recipients = self.cost_summary_recipients().await?;
+        if recipients.is_empty() {
+            return Ok(0);
+        }
+
+        let days = (chrono::Utc::now().date_naive() - date).num_days().max(0) + 1;
+        let day = date.format("%Y-%m-%d").to_string();
+        let usage: Vec<_> = self
+            .db
+            .get_daily_token_usage(days as i32)
+            .await?
+            .into_iter()
+            .filter(|usage| usage.date == day)
+            .collect();
+
+        self.send_each(recipients, |to| {
+            templates::cost_summary_email(to, date, &usage)
+        })
+        .await
+    }
+
+    /// Email an escalation of `incident` under `rule`
+    ///
+    /// Returns the number of emails sent.
+    pub async fn notify_incident_escalation(
+        &self,
+        incident: &Incident,
+        rule: &EscalationRule,
+    ) -> Result<usize> {
+        let recipients = self.incident_recipients(rule).await?;
+        self.send_each(recipients, |to| {
+            templates::incident_escalation_email(to, incident, rule)
+        })
+        .await
+    }
+
+    async fn recipients(&self, wanted: impl Fn(&UserMapping) -> bool) -> Result<Vec<String>> {
+        let mut recipients: Vec<String> = self
+            .user_service
+            .list_user_mappings()
+            .await?
+            .into_iter()
+            .filter(|mapping| wanted(mapping))
+            .filter_map(|mapping| mapping.email)
+            .filter(|email| !email.is_empty())
+            .collect();
+        recipients.sort();
+        recipients.dedup();
+        Ok(recipients)
+    }
+
+    /// Send one message per recipient, continuing past failed deliveries
+    ///
+    /// Fails only when no message could be delivered.
+    async fn send_each(
+        &self,
+        recipients: Vec<String>,
+        build: impl Fn(Vec<String>) -> EmailMessage,
+    ) -> Result<usize> {
+        let mut sent = 0;
+        let mut last_error = None;
+        for recipient in recipients {
+            let message = build(vec![recipient.clone()]);
+            match self.client.send(&message).await {
+                Ok(()) => {
+                    info!(to = %recipient, subject = %message.subject, "Sent notification email");
+                    sent += 1;
+                }
+                Err(e) => {
+                    warn!(to = %recipient, error = %e, "Failed to send notification email");
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match last_error {
+            Some(e) if sent == 0 => Err(Error::Other(format!(
+                "Failed to send notification email: {}",
+                e
+            ))),
+            _ => Ok(sent),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::email::{SmtpConfig, SmtpTls};
+    use crate::incident::{EscalationCondition, EscalationTarget, IncidentSeverity};
+    use crate::slack::SlackConnection;
+    use std::time::Duration;
+
+    async fn setup() -> EmailNotificationService {
+        let db = Database::in_memory().await.unwrap();
+        let slack_service = SlackService::new(db.clone());
+        slack_service
+            .save_connection(&SlackConnection::new("T123", "Test Team", "xoxb-test"))
+            .await
+            .unwrap();
+
+        let mut alice = UserMapping::new("alice", "U1");
+        alice.email = Some("alice@example.com".to_string());
+        alice.email_cost_summary = true;
+        slack_service.save_user_mapping(&alice).await.unwrap();
+
+        let mut bob = UserMapping::new("bob", "U2");
+        bob.email = Some("bob@example.com".to_string());
+        bob.email_on_approval = false;
+        bob.email_on_incident = false;
+        slack_service.save_user_mapping(&bob).await.unwrap();
+
+        // No email address
+        slack_service
+            .save_user_mapping(&UserMapping::new("carol", "U3"))
+            .await
+            .unwrap();
+
+        // Port 9 (discard) is not listening, so any delivery attempt fails
+        let client = SmtpClient::new(
+            SmtpConfig::new("127.0.0.1", "ci@example.com")
+                .with_tls(SmtpTls::None)
+                .with_port(9),
+        )
+        .with_timeout(Duration::from_secs(2));
+        EmailNotificationService::new(db, client)
+    }
+
+    #[tokio::test]
+    async fn test_recipients_follow_preferences() {
+        let service = setup().await;
+
+        let approval = ApprovalRequest::new(1, 1, "alice,bob,carol".to_string(), 3, None, None);
+        assert_eq!(
+            service.approval_recipients(&approval).await.unwrap(),
+            vec!["alice@example.com"]
+        );
+        assert_eq!(
+            service.cost_summary_recipients().await.unwrap(),
+            vec!["alice@example.com"]
+        );
+
+        let rule = EscalationRule {
+            name: "critical".to_string(),
+            condition: EscalationCondition::SeverityCritical,
+            targets: vec![
+                EscalationTarget {
+                    target_type: EscalationTargetType::Email,
+                    destination: "oncall@example.com".to_string(),
+                },
+                EscalationTarget {
+                    target_type: EscalationTargetType::Slack,
+                    destination: "#incidents".to_string(),
+                },
+                EscalationTarget {
+                    target_type: EscalationTargetType::Email,
+                    destination: "alice@example.com".to_string(),
+                },
+            ],
+            delay_seconds: 0,
+            repeat_interval_seconds: None,
+        };
+        assert_eq!(
+            service.incident_recipients(&rule).await.unwrap(),
+            vec!["alice@example.com", "oncall@example.com"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_recipients_sends_nothing() {
+        let service = setup().await;
+
+        let approval = ApprovalRequest::new(1, 1, "dave".to_string(), 1, None, None);
+        let sent = service
+            .notify_approval("deploy", 1, "production", &approval, None)
+            .await
+            .unwrap();
+        assert_eq!(sent, 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_an_error() {
+        let service = setup().await;
+
+        let incident = Incident::new("INC-1", "API down", IncidentSeverity::Critical);
+        let rule = EscalationRule {
+            name: "critical".to_string(),
+            condition: EscalationCondition::SeverityCritical,
+            targets: Vec::new(),
+            delay_seconds: 0,
+            repeat_interval_seconds: None,
+        };
+        let err = service
+            .notify_incident_escalation(&incident, &rule)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Failed to send notification email"));
+    }
+}
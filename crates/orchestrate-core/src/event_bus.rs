@@ -0,0 +1,650 @@
+//! Event Bus
+//!
+//! Publishes orchestrator activity to NATS or Kafka so external systems can
+//! react to it without polling the REST API:
+//! - [`BusEvent`]: a JSON event, converted from a [`NetworkEvent`] (agent
+//!   registration, state changes, propagation, validation, self-healing) or
+//!   an agent lifecycle [`AgentEvent`] (state transitions, tool calls,
+//!   recoveries, evaluations)
+//! - [`EventPublisher`]: a broker connection; [`NatsPublisher`] is built with
+//!   the `nats` feature and [`KafkaPublisher`] with the `kafka` feature
+//! - [`EventBus`]: names subjects and publishes in the background
+//!
+//! Subjects are `<prefix>.network.<event>` and `<prefix>.agent.<event>`,
+//! e.g. `orchestrate.agent.state_transition`; Kafka topics use the same
+//! names and key agent events by agent ID so they stay ordered.
+//!
+//! [`emit`] also delivers every event to in-process [`subscribe`]rs, such as
+//! the web server's event stream, whether or not a broker is configured.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::agent_event::AgentEvent;
+use crate::network::{NetworkEvent, PropagationEvent, RecoveryAction};
+use crate::{Error, Result};
+
+/// Subject prefix used unless `ORCHESTRATE_EVENT_PREFIX` is set
+pub const DEFAULT_SUBJECT_PREFIX: &str = "orchestrate";
+
+/// NATS server used unless `NATS_URL` is set
+pub const DEFAULT_NATS_URL: &str = "nats://localhost:4222";
+
+/// Kafka brokers used unless `KAFKA_BROKERS` is set
+pub const DEFAULT_KAFKA_BROKERS: &str = "localhost:9092";
+
+/// Events buffered per in-process subscriber before it lags
+const FEED_CAPACITY: usize = 1024;
+
+static BUS: OnceLock<EventBus> = OnceLock::new();
+
+static FEED: OnceLock<broadcast::Sender<BusEvent>> = OnceLock::new();
+
+/// An event published to the bus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BusEvent {
+    pub id: String,
+    /// Subject without the prefix, e.g. `network.state_changed`
+    pub kind: String,
+    /// Agent the event is about, if any
+    pub agent_id: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+impl BusEvent {
+    pub fn new(kind: impl Into<String>, data: serde_json::Value) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind: kind.into(),
+            agent_id: None,
+            occurred_at: Utc::now(),
+            data,
+        }
+    }
+
+    pub fn with_agent(mut self, agent_id: impl ToString) -> Self {
+        self.agent_id = Some(agent_id.to_string());
+        self
+    }
+}
+
+fn propagation_event_name(event: &PropagationEvent) -> String {
+    match event {
+        PropagationEvent::DependencyReady => "dependency_ready".to_string(),
+        PropagationEvent::DependencyCompleted => "dependency_completed".to_string(),
+        PropagationEvent::DependencyFailed => "dependency_failed".to_string(),
+        PropagationEvent::DependencyBlocked => "dependency_blocked".to_string(),
+        PropagationEvent::Custom(name) => name.clone(),
+    }
+}
+
+impl From<&NetworkEvent> for BusEvent {
+    fn from(event: &NetworkEvent) -> Self {
+        use serde_json::json;
+
+        match event {
+            NetworkEvent::AgentRegistered {
+                agent_id,
+                agent_type,
+            } => Self::new(
+                "network.agent_registered",
+                json!({ "agent_type": agent_type.as_str() }),
+            )
+            .with_agent(agent_id),
+            NetworkEvent::AgentRemoved { agent_id } => {
+                Self::new("network.agent_removed", json!({})).with_agent(agent_id)
+            }
+            NetworkEvent::StateChanged { agent_id, from, to } => Self::new(
+                "network.state_changed",
+                json!({ "from": from.as_str(), "to": to.as_str() }),
+            )
+            .with_agent(agent_id),
+            NetworkEvent::DependencyAdded { from, to } => Self::new(
+                "network.dependency_added",
+                json!({ "from": from.to_string(), "to": to.to_string() }),
+            )
+            .with_agent(from),
+            NetworkEvent::SkillRegistered { name } => {
+                Self::new("network.skill_registered", json!({ "name": name }))
+            }
+            NetworkEvent::SkillUnregistered { name } => {
+                Self::new("network.skill_unregistered", json!({ "name": name }))
+            }
+            NetworkEvent::StatePropagated {
+                source,
+                target,
+                event,
+            } => Self::new(
+                "network.state_propagated",
+                json!({
+                    "source": source.to_string(),
+                    "target": target.to_string(),
+                    "event": propagation_event_name(event),
+                }),
+            )
+            .with_agent(target),
+            NetworkEvent::ValidationCompleted { result } => Self::new(
+                "network.validation_completed",
+                json!({
+                    "is_valid": result.is_valid,
+                    "errors": result.errors.iter().map(|e| &e.message).collect::<Vec<_>>(),
+                    "warnings": result.warnings.iter().map(|w| &w.message).collect::<Vec<_>>(),
+                }),
+            ),
+            NetworkEvent::SelfHealingAction { action } => {
+                let (name, agent_id, detail) = match action {
+                    RecoveryAction::RestartAgent { agent_id, reason } => {
+                        ("restart_agent", Some(agent_id), reason.clone())
+                    }
+                    RecoveryAction::PauseAgent { agent_id, reason } => {
+                        ("pause_agent", Some(agent_id), reason.clone())
+                    }
+                    RecoveryAction::TerminateAgent { agent_id, reason } => {
+                        ("terminate_agent", Some(agent_id), reason.clone())
+                    }
+                    RecoveryAction::SpawnDependency {
+                        for_agent,
+                        agent_type,
+                        reason,
+                    } => (
+                        "spawn_dependency",
+                        Some(for_agent),
+                        format!("{}: {}", agent_type.as_str(), reason),
+                    ),
+                    RecoveryAction::RetryTransition {
+                        agent_id,
+                        target_state,
+                    } => (
+                        "retry_transition",
+                        Some(agent_id),
+                        format!("retry transition to {}", target_state.as_str()),
+                    ),
+                    RecoveryAction::None => ("none", None, String::new()),
+                };
+                let event = Self::new(
+                    "network.self_healing_action",
+                    json!({ "action": name, "detail": detail }),
+                );
+                match agent_id {
+                    Some(agent_id) => event.with_agent(agent_id),
+                    None => event,
+                }
+            }
+        }
+    }
+}
+
+impl From<&AgentEvent> for BusEvent {
+    fn from(event: &AgentEvent) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind: format!("agent.{}", event.event_type.as_str()),
+            agent_id: Some(event.agent_id.clone()),
+            occurred_at: event.created_at,
+            data: serde_json::json!({
+                "session_id": event.session_id,
+                "summary": event.summary,
+                "data": event.data,
+            }),
+        }
+    }
+}
+
+/// A connection to a message broker
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    /// Broker name, e.g. `nats`
+    fn name(&self) -> &str;
+
+    /// Publish a payload to a subject (a topic in Kafka); `key` keeps
+    /// messages with the same key in order where the broker supports it
+    async fn publish(&self, subject: &str, key: Option<&str>, payload: Vec<u8>) -> Result<()>;
+}
+
+/// Publishes to NATS core subjects
+#[cfg(feature = "nats")]
+pub struct NatsPublisher {
+    client: async_nats::Client,
+}
+
+#[cfg(feature = "nats")]
+impl NatsPublisher {
+    /// Connect to a NATS server, e.g. `nats://localhost:4222`
+    pub async fn connect(url: &str) -> Result<Self> {
+        let client = async_nats::connect(url)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to connect to NATS at {}: {}", url, e)))?;
+        Ok(Self { client })
+    }
+}
+
+#[cfg(feature = "nats")]
+#[async_trait]
+impl EventPublisher for NatsPublisher {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn publish(&self, subject: &str, _key: Option<&str>, payload: Vec<u8>) -> Result<()> {
+        self.client
+            .publish(subject.to_string(), payload.into())
+            .await
+            .map_err(|e| Error::Other(format!("Failed to publish to NATS: {}", e)))
+    }
+}
+
+/// Publishes to Kafka topics
+#[cfg(feature = "kafka")]
+pub struct KafkaPublisher {
+    producer: rdkafka::producer::FutureProducer,
+}
+
+#[cfg(feature = "kafka")]
+impl KafkaPublisher {
+    /// Create a producer for comma-separated bootstrap brokers
+    pub fn connect(brokers: &str) -> Result<Self> {
+        let producer = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("message.timeout.ms", "10000")
+            .create()
+            .map_err(|e| Error::Other(format!("Failed to create Kafka producer: {}", e)))?;
+        Ok(Self { producer })
+    }
+}
+
+#[cfg(feature = "kafka")]
+#[async_trait]
+impl EventPublisher for KafkaPublisher {
+    fn name(&self) -> &str {
+        "kafka"
+    }
+
+    async fn publish(&self, subject: &str, key: Option<&str>, payload: Vec<u8>) -> Result<()> {
+        let mut record =
+            rdkafka::producer::FutureRecord::<str, [u8]>::to(subject).payload(&payload);
+        if let Some(key) = key {
+            record = record.key(key);
+        }
+        self.producer
+            .send(record, std::time::Duration::from_secs(5))
+            .await
+            .map(|_| ())
+            .map_err(|(e, _)| Error::Other(format!("Failed to publish to Kafka: {}", e)))
+    }
+}
+
+/// Message broker to publish to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventBusBackend {
+    Nats,
+    Kafka,
+}
+
+impl EventBusBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Nats => "nats",
+            Self::Kafka => "kafka",
+        }
+    }
+}
+
+impl FromStr for EventBusBackend {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "nats" => Ok(Self::Nats),
+            "kafka" => Ok(Self::Kafka),
+            _ => Err(Error::Config(format!(
+                "Unknown event bus: {} (expected nats or kafka)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Where to publish events
+#[derive(Debug, Clone)]
+pub struct EventBusConfig {
+    pub backend: EventBusBackend,
+    /// NATS server URL or comma-separated Kafka brokers
+    pub url: String,
+    pub prefix: String,
+}
+
+impl EventBusConfig {
+    pub fn new(backend: EventBusBackend, url: impl Into<String>) -> Self {
+        Self {
+            backend,
+            url: url.into(),
+            prefix: DEFAULT_SUBJECT_PREFIX.to_string(),
+        }
+    }
+
+    /// Prefix subjects with something other than `orchestrate`
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into().trim_end_matches('.').to_string();
+        self
+    }
+
+    /// Configuration from `ORCHESTRATE_EVENT_BUS` (`nats` or `kafka`), if set
+    ///
+    /// NATS is reached at `NATS_URL` and Kafka at `KAFKA_BROKERS`;
+    /// `ORCHESTRATE_EVENT_PREFIX` replaces the subject prefix.
+    pub fn from_env() -> Result<Option<Self>> {
+        fn var(name: &str) -> Option<String> {
+            std::env::var(name).ok().filter(|v| !v.is_empty())
+        }
+
+        let Some(backend) = var("ORCHESTRATE_EVENT_BUS") else {
+            return Ok(None);
+        };
+        let backend = EventBusBackend::from_str(&backend)?;
+        let url = match backend {
+            EventBusBackend::Nats => {
+                var("NATS_URL").unwrap_or_else(|| DEFAULT_NATS_URL.to_string())
+            }
+            EventBusBackend::Kafka => {
+                var("KAFKA_BROKERS").unwrap_or_else(|| DEFAULT_KAFKA_BROKERS.to_string())
+            }
+        };
+
+        let config = Self::new(backend, url);
+        Ok(Some(match var("ORCHESTRATE_EVENT_PREFIX") {
+            Some(prefix) => config.with_prefix(prefix),
+            None => config,
+        }))
+    }
+
+    /// Connect to the broker
+    ///
+    /// Fails if orchestrate was built without the backend's feature.
+    pub async fn connect(&self) -> Result<EventBus> {
+        let publisher = self.connect_publisher().await?;
+        Ok(EventBus::new(publisher).with_prefix(self.prefix.clone()))
+    }
+
+    async fn connect_publisher(&self) -> Result<Arc<dyn EventPublisher>> {
+        match self.backend {
+            #[cfg(feature = "nats")]
+            EventBusBackend::Nats => Ok(Arc::new(NatsPublisher::connect(&self.url).await?)),
+            #[cfg(feature = "kafka")]
+            EventBusBackend::Kafka => Ok(Arc::new(KafkaPublisher::connect(&self.url)?)),
+            #[allow(unreachable_patterns)]
+            backend => Err(Error::Config(format!(
+                "Event bus {} requires building with the `{}` feature",
+                backend.as_str(),
+                backend.as_str()
+            ))),
+        }
+    }
+}
+
+/// Publishes [`BusEvent`]s under a subject prefix
+#[derive(Clone)]
+pub struct EventBus {
+    publisher: Arc<dyn EventPublisher>,
+    prefix: String,
+}
+
+impl EventBus {
+    pub fn new(publisher: Arc<dyn EventPublisher>) -> Self {
+        Self {
+            publisher,
+            prefix: DEFAULT_SUBJECT_PREFIX.to_string(),
+        }
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Subject an event is published to
+    pub fn subject(&self, event: &BusEvent) -> String {
+        format!("{}.{}", self.prefix, event.kind)
+    }
+
+    /// Publish an event and wait for the broker to accept it
+    pub async fn publish(&self, event: &BusEvent) -> Result<()> {
+        let payload = serde_json::to_vec(event)?;
+        self.publisher
+            .publish(&self.subject(event), event.agent_id.as_deref(), payload)
+            .await
+    }
+
+    /// Publish an event in the background, logging failures
+    pub fn emit(&self, event: BusEvent) {
+        let bus = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = bus.publish(&event).await {
+                warn!("Failed to publish {} event: {}", event.kind, e);
+            }
+        });
+    }
+
+    /// Publish network events as they are broadcast, until the network is dropped
+    pub fn forward_network_events(
+        &self,
+        mut events: broadcast::Receiver<NetworkEvent>,
+    ) -> JoinHandle<()> {
+        let bus = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        let event = BusEvent::from(&event);
+                        if let Err(e) = bus.publish(&event).await {
+                            warn!("Failed to publish {} event: {}", event.kind, e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(
+                            "Event bus fell behind; {} network events not published",
+                            skipped
+                        );
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+/// Install the bus agent lifecycle events are published to
+///
+/// Only the first call has an effect.
+pub fn init(bus: EventBus) {
+    let _ = BUS.set(bus);
+}
+
+/// Connect to the bus configured by the environment and install it
+///
+/// Returns whether publishing is enabled.
+pub async fn init_from_env() -> Result<bool> {
+    match EventBusConfig::from_env()? {
+        Some(config) => {
+            init(config.connect().await?);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Bus installed by [`init`], if any
+pub fn bus() -> Option<&'static EventBus> {
+    BUS.get()
+}
+
+fn feed() -> &'static broadcast::Sender<BusEvent> {
+    FEED.get_or_init(|| broadcast::channel(FEED_CAPACITY).0)
+}
+
+/// Receive the events emitted in this process from now on
+pub fn subscribe() -> broadcast::Receiver<BusEvent> {
+    feed().subscribe()
+}
+
+/// Deliver an event to in-process subscribers and publish it to the bus,
+/// if one is installed
+pub fn emit(event: BusEvent) {
+    let _ = feed().send(event.clone());
+    if let Some(bus) = bus() {
+        bus.emit(event);
+    }
+}
+
+/// [`emit`] network events as they are broadcast, until the network is dropped
+pub fn forward_network_events(mut events: broadcast::Receiver<NetworkEvent>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => emit(BusEvent::from(&event)),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Event feed fell behind; {} network events dropped", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{AgentId, NetworkCoordinator, SkillDefinition};
+    use crate::{AgentState, AgentType};
+    use tokio::sync::Mutex;
+
+    /// Records published messages
+    #[derive(Default)]
+    struct RecordingPublisher {
+        messages: Mutex<Vec<(String, Option<String>, serde_json::Value)>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for RecordingPublisher {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn publish(&self, subject: &str, key: Option<&str>, payload: Vec<u8>) -> Result<()> {
+            self.messages.lock().await.push((
+                subject.to_string(),
+                key.map(String::from),
+                serde_json::from_slice(&payload)?,
+            ));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_network_events() {
+        let agent_id = AgentId::new();
+
+        let event = BusEvent::from(&NetworkEvent::StateChanged {
+            agent_id,
+            from: AgentState::Running,
+            to: AgentState::Completed,
+        });
+        assert_eq!(event.kind, "network.state_changed");
+        assert_eq!(event.agent_id, Some(agent_id.to_string()));
+        assert_eq!(event.data["to"], "completed");
+
+        let event = BusEvent::from(&NetworkEvent::StatePropagated {
+            source: AgentId::new(),
+            target: agent_id,
+            event: PropagationEvent::DependencyFailed,
+        });
+        assert_eq!(event.kind, "network.state_propagated");
+        assert_eq!(event.data["event"], "dependency_failed");
+
+        let event = BusEvent::from(&NetworkEvent::SelfHealingAction {
+            action: RecoveryAction::RestartAgent {
+                agent_id,
+                reason: "stuck".to_string(),
+            },
+        });
+        assert_eq!(event.data["action"], "restart_agent");
+        assert_eq!(event.agent_id, Some(agent_id.to_string()));
+
+        let event = BusEvent::from(&NetworkEvent::SkillRegistered {
+            name: "audit".to_string(),
+        });
+        assert!(event.agent_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_publish_agent_event() {
+        let publisher = Arc::new(RecordingPublisher::default());
+        let bus = EventBus::new(publisher.clone()).with_prefix("acme");
+
+        let event =
+            AgentEvent::state_transition("agent-1", AgentState::Running, AgentState::Failed)
+                .with_session("session-1");
+        bus.publish(&BusEvent::from(&event)).await.unwrap();
+
+        let messages = publisher.messages.lock().await;
+        let (subject, key, payload) = &messages[0];
+        assert_eq!(subject, "acme.agent.state_transition");
+        assert_eq!(key.as_deref(), Some("agent-1"));
+        assert_eq!(payload["kind"], "agent.state_transition");
+        assert_eq!(payload["data"]["session_id"], "session-1");
+        assert_eq!(payload["data"]["data"]["to"], "failed");
+    }
+
+    #[tokio::test]
+    async fn test_forward_network_events() {
+        let publisher = Arc::new(RecordingPublisher::default());
+        let bus = EventBus::new(publisher.clone());
+        let coordinator = NetworkCoordinator::with_defaults();
+        let forwarding = bus.forward_network_events(coordinator.subscribe());
+
+        let agent_id = AgentId::new();
+        coordinator
+            .register_agent(agent_id, AgentType::Explorer, AgentState::Running)
+            .await
+            .unwrap();
+        coordinator
+            .register_skill(SkillDefinition::new("audit", vec![AgentType::Explorer]))
+            .await
+            .unwrap();
+        drop(coordinator);
+        forwarding.await.unwrap();
+
+        let subjects: Vec<String> = publisher
+            .messages
+            .lock()
+            .await
+            .iter()
+            .map(|(subject, _, _)| subject.clone())
+            .collect();
+        assert_eq!(
+            subjects,
+            vec![
+                "orchestrate.network.agent_registered",
+                "orchestrate.network.skill_registered"
+            ]
+        );
+    }
+
+    #[cfg(not(feature = "nats"))]
+    #[tokio::test]
+    async fn test_backend_requires_feature() {
+        let err = EventBusConfig::new(EventBusBackend::Nats, DEFAULT_NATS_URL)
+            .connect()
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("`nats` feature"));
+        assert!(EventBusBackend::from_str("rabbitmq").is_err());
+    }
+}
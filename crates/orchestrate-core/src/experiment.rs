@@ -338,6 +338,18 @@ impl VariantResults {
     }
 }
 
+/// Observations of a variant on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantDailyResults {
+    pub variant_id: i64,
+    pub variant_name: String,
+    /// Day of the observations, as YYYY-MM-DD
+    pub date: String,
+    pub sample_count: i64,
+    pub mean: f64,
+    pub success_count: i64,
+}
+
 /// Results of an A/B test comparison
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExperimentResults {
@@ -0,0 +1,521 @@
+//! Google Chat Notifications
+//!
+//! Posts card messages to Google Chat spaces through incoming webhooks:
+//! - [`GoogleChatMessage`]: a `cardsV2` card for a firing alert or a
+//!   deployment, with a plain-text fallback for push notifications
+//! - [`GoogleChatClient`]: posts messages to a webhook URL
+//!
+//! Webhooks are selected per [`NotificationType`] in
+//! [`ChannelConfig`](crate::slack::ChannelConfig), so alerts and deployments
+//! can go to a Google Chat space alongside, or instead of, a Slack channel.
+//! Webhook URLs carry their key and token as query parameters and are kept
+//! out of error messages.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::deployment::{Deployment, DeploymentStatus};
+use crate::monitoring::{Alert, AlertSeverity};
+use crate::slack::NotificationType;
+use crate::{Error, Result};
+
+/// Prefix of every Google Chat incoming webhook URL
+pub const GOOGLE_CHAT_WEBHOOK_PREFIX: &str = "https://chat.googleapis.com/v1/spaces/";
+
+/// Longest text widget content rendered before truncation
+const MAX_TEXT_LEN: usize = 4000;
+
+/// Whether a URL looks like a Google Chat incoming webhook
+pub fn is_webhook_url(url: &str) -> bool {
+    url.starts_with(GOOGLE_CHAT_WEBHOOK_PREFIX) && url.contains("key=") && url.contains("token=")
+}
+
+/// Notification type a deployment's status is routed as, or `None` for
+/// states that are not announced
+pub fn deployment_notification_type(deployment: &Deployment) -> Option<NotificationType> {
+    match deployment.status {
+        DeploymentStatus::InProgress => Some(NotificationType::DeploymentStarted),
+        DeploymentStatus::Succeeded => Some(NotificationType::DeploymentCompleted),
+        DeploymentStatus::Failed | DeploymentStatus::RolledBack => {
+            Some(NotificationType::DeploymentFailed)
+        }
+        _ => None,
+    }
+}
+
+/// Message posted to a webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoogleChatMessage {
+    /// Plain text shown in notifications and clients without card support
+    pub text: String,
+    #[serde(rename = "cardsV2", default, skip_serializing_if = "Vec::is_empty")]
+    pub cards_v2: Vec<CardWithId>,
+}
+
+/// Card with the ID Google Chat uses to address it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardWithId {
+    pub card_id: String,
+    pub card: Card,
+}
+
+/// Card with a header and sections of widgets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Card {
+    pub header: CardHeader,
+    pub sections: Vec<CardSection>,
+}
+
+/// Card header
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardHeader {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+}
+
+/// Group of widgets, optionally under a header
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardSection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    pub widgets: Vec<Widget>,
+}
+
+/// Card widget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Widget {
+    /// Value with a label above it
+    #[serde(rename_all = "camelCase")]
+    DecoratedText { top_label: String, text: String },
+    /// Paragraph of formatted text
+    TextParagraph { text: String },
+    /// Row of buttons
+    ButtonList { buttons: Vec<Button> },
+}
+
+impl Widget {
+    /// Labelled value, escaped for card text
+    pub fn field(label: impl Into<String>, value: &str) -> Self {
+        Self::DecoratedText {
+            top_label: label.into(),
+            text: escape_html(value),
+        }
+    }
+
+    /// Paragraph of plain text, escaped for card text
+    pub fn paragraph(text: &str) -> Self {
+        Self::TextParagraph {
+            text: escape_html(&truncate(text, MAX_TEXT_LEN)),
+        }
+    }
+}
+
+/// Button opening a link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Button {
+    pub text: String,
+    pub on_click: OnClick,
+}
+
+impl Button {
+    /// Button opening a URL
+    pub fn link(text: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            on_click: OnClick {
+                open_link: OpenLink { url: url.into() },
+            },
+        }
+    }
+}
+
+/// Button action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnClick {
+    pub open_link: OpenLink,
+}
+
+/// Link opened by a button
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenLink {
+    pub url: String,
+}
+
+impl GoogleChatMessage {
+    /// Plain text message
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            cards_v2: Vec::new(),
+        }
+    }
+
+    /// Attach a card
+    pub fn with_card(mut self, card_id: impl Into<String>, card: Card) -> Self {
+        self.cards_v2.push(CardWithId {
+            card_id: card_id.into(),
+            card,
+        });
+        self
+    }
+
+    /// Card for a firing monitoring alert
+    pub fn alert(alert: &Alert) -> Self {
+        let (icon, severity) = match alert.severity {
+            AlertSeverity::Critical => ("🔴", "critical"),
+            AlertSeverity::Warning => ("🟠", "warning"),
+            AlertSeverity::Info => ("🔵", "info"),
+        };
+        let title = format!("{} Alert firing: {}", icon, alert.rule_name);
+
+        let mut widgets = Vec::new();
+        if !alert.message.is_empty() {
+            widgets.push(Widget::paragraph(&alert.message));
+        }
+        widgets.push(Widget::field("Severity", severity));
+        if let Some(value) = alert.current_value {
+            widgets.push(Widget::field("Current value", &value.to_string()));
+        }
+        if let Some(threshold) = alert.threshold {
+            widgets.push(Widget::field("Threshold", &threshold.to_string()));
+        }
+        widgets.push(Widget::field(
+            "Triggered",
+            &alert
+                .triggered_at
+                .format("%Y-%m-%d %H:%M:%S UTC")
+                .to_string(),
+        ));
+
+        let mut sections = vec![CardSection {
+            header: None,
+            widgets,
+        }];
+        if !alert.labels.is_empty() {
+            let mut labels: Vec<_> = alert.labels.iter().collect();
+            labels.sort();
+            sections.push(CardSection {
+                header: Some("Labels".to_string()),
+                widgets: labels
+                    .into_iter()
+                    .map(|(key, value)| Widget::field(key.clone(), value))
+                    .collect(),
+            });
+        }
+
+        let text = if alert.message.is_empty() {
+            title.clone()
+        } else {
+            format!("{}: {}", title, alert.message)
+        };
+        Self::new(truncate(&text, MAX_TEXT_LEN)).with_card(
+            format!("alert-{}", alert.id),
+            Card {
+                header: CardHeader {
+                    title,
+                    subtitle: Some(format!("Alert {}", alert.id)),
+                },
+                sections,
+            },
+        )
+    }
+
+    /// Card for a deployment that started, succeeded, failed, or was
+    /// rolled back
+    pub fn deployment(deployment: &Deployment) -> Self {
+        let headline = match deployment.status {
+            DeploymentStatus::InProgress => "🚀 Deployment started",
+            DeploymentStatus::Succeeded => "✅ Deployment succeeded",
+            DeploymentStatus::Failed => "❌ Deployment failed",
+            DeploymentStatus::RolledBack => "↩️ Deployment rolled back",
+            DeploymentStatus::Cancelled => "⏹️ Deployment cancelled",
+            _ => "📦 Deployment",
+        };
+        let title = format!("{}: {}", headline, deployment.environment_name);
+
+        let mut widgets = vec![
+            Widget::field("Version", &deployment.version),
+            Widget::field("Strategy", &deployment.strategy.to_string()),
+            Widget::field("Initiated by", &deployment.initiated_by),
+        ];
+        if let Some(previous) = &deployment.previous_version {
+            widgets.push(Widget::field("Previous version", previous));
+        }
+        if let Some(sha) = &deployment.commit_sha {
+            widgets.push(Widget::field("Commit", sha));
+        }
+        if let Some(seconds) = deployment.duration_seconds {
+            widgets.push(Widget::field("Duration", &format!("{:.1}s", seconds)));
+        }
+        if let Some(error) = &deployment.error_message {
+            widgets.push(Widget::paragraph(error));
+        }
+        if let Some(url) = &deployment.artifact_url {
+            widgets.push(Widget::ButtonList {
+                buttons: vec![Button::link("View artifact", url)],
+            });
+        }
+
+        let text = format!(
+            "{} ({} → {})",
+            headline, deployment.version, deployment.environment_name
+        );
+        Self::new(text).with_card(
+            format!("deployment-{}", deployment.id),
+            Card {
+                header: CardHeader {
+                    title,
+                    subtitle: Some(format!("Deployment {}", deployment.id)),
+                },
+                sections: vec![CardSection {
+                    header: None,
+                    widgets,
+                }],
+            },
+        )
+    }
+}
+
+/// Escape text for card widgets, which render a subset of HTML
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn truncate(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_len - 3).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+/// Error body returned by the Chat API
+#[derive(Debug, Deserialize)]
+struct ApiError {
+    error: ApiErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorDetail {
+    #[serde(default)]
+    message: String,
+}
+
+/// Client posting messages to incoming webhooks
+#[derive(Debug, Clone)]
+pub struct GoogleChatClient {
+    http_client: reqwest::Client,
+}
+
+impl Default for GoogleChatClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GoogleChatClient {
+    /// Create a client with a 10 second request timeout
+    pub fn new() -> Self {
+        Self::with_http_client(
+            reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Create a client sharing an existing HTTP client
+    pub fn with_http_client(http_client: reqwest::Client) -> Self {
+        Self { http_client }
+    }
+
+    /// Post a message to a webhook
+    pub async fn post(&self, webhook_url: &str, message: &GoogleChatMessage) -> Result<()> {
+        let response = self
+            .http_client
+            .post(webhook_url)
+            .json(message)
+            .send()
+            .await
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Google Chat webhook request failed: {}",
+                    e.without_url()
+                ))
+            })?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(Error::Other(
+                "Google Chat rate limited the webhook, retry later".to_string(),
+            ));
+        }
+        let message = response
+            .json::<ApiError>()
+            .await
+            .map(|body| body.error.message)
+            .unwrap_or_default();
+        Err(Error::Other(format!(
+            "Google Chat webhook failed (HTTP {}): {}",
+            status,
+            if message.is_empty() {
+                "unknown error"
+            } else {
+                &message
+            }
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deployment::DeploymentStrategy;
+    use crate::monitoring::AlertRule;
+    use crate::test_servers::{json_body, serve};
+
+    fn firing_alert() -> Alert {
+        let rule = AlertRule::new("queue-backup", "queue_depth > 100", AlertSeverity::Critical)
+            .with_threshold(100.0);
+        let mut alert = Alert::new(&rule, "Queue depth <250>");
+        alert.id = "7".to_string();
+        alert.current_value = Some(250.0);
+        alert
+            .labels
+            .insert("component".to_string(), "scheduler".to_string());
+        alert
+    }
+
+    #[test]
+    fn test_is_webhook_url() {
+        assert!(is_webhook_url(
+            "https://chat.googleapis.com/v1/spaces/AAA/messages?key=k&token=t"
+        ));
+        assert!(!is_webhook_url("https://hooks.slack.com/services/T/B/X"));
+        assert!(!is_webhook_url(
+            "https://chat.googleapis.com/v1/spaces/AAA/messages"
+        ));
+    }
+
+    #[test]
+    fn test_alert_card() {
+        let json = serde_json::to_value(GoogleChatMessage::alert(&firing_alert())).unwrap();
+
+        assert_eq!(
+            json["text"],
+            "🔴 Alert firing: queue-backup: Queue depth <250>"
+        );
+        let card = &json["cardsV2"][0];
+        assert_eq!(card["cardId"], "alert-7");
+        assert_eq!(
+            card["card"]["header"]["title"],
+            "🔴 Alert firing: queue-backup"
+        );
+
+        let widgets = &card["card"]["sections"][0]["widgets"];
+        assert_eq!(
+            widgets[0]["textParagraph"]["text"],
+            "Queue depth &lt;250&gt;"
+        );
+        assert_eq!(widgets[1]["decoratedText"]["topLabel"], "Severity");
+        assert_eq!(widgets[1]["decoratedText"]["text"], "critical");
+        assert_eq!(widgets[2]["decoratedText"]["text"], "250");
+        assert_eq!(widgets[3]["decoratedText"]["text"], "100");
+
+        let labels = &card["card"]["sections"][1];
+        assert_eq!(labels["header"], "Labels");
+        assert_eq!(
+            labels["widgets"][0]["decoratedText"]["topLabel"],
+            "component"
+        );
+    }
+
+    #[test]
+    fn test_deployment_card() {
+        let mut deployment = Deployment::new(
+            "env-prod",
+            "production",
+            "1.4.0",
+            DeploymentStrategy::Rolling,
+            "deploy-bot",
+        );
+        deployment.start();
+        assert_eq!(
+            deployment_notification_type(&deployment),
+            Some(NotificationType::DeploymentStarted)
+        );
+
+        deployment.artifact_url = Some("https://example.com/build/42".to_string());
+        deployment.complete(false, Some("health check timed out".to_string()));
+        assert_eq!(
+            deployment_notification_type(&deployment),
+            Some(NotificationType::DeploymentFailed)
+        );
+
+        let json = serde_json::to_value(GoogleChatMessage::deployment(&deployment)).unwrap();
+        let card = &json["cardsV2"][0]["card"];
+        assert_eq!(card["header"]["title"], "❌ Deployment failed: production");
+
+        let widgets = card["sections"][0]["widgets"].as_array().unwrap();
+        assert_eq!(widgets[0]["decoratedText"]["text"], "1.4.0");
+        assert!(widgets
+            .iter()
+            .any(|w| w["textParagraph"]["text"] == "health check timed out"));
+        let button = &widgets.last().unwrap()["buttonList"]["buttons"][0];
+        assert_eq!(
+            button["onClick"]["openLink"]["url"],
+            "https://example.com/build/42"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_message() {
+        let (url, server) = serve(vec![(200, r#"{"name":"spaces/AAA/messages/1"}"#)]).await;
+
+        GoogleChatClient::new()
+            .post(
+                &format!("{}/v1/spaces/AAA/messages?key=k&token=t", url),
+                &GoogleChatMessage::alert(&firing_alert()),
+            )
+            .await
+            .unwrap();
+
+        let request = server.await.unwrap().remove(0);
+        assert!(request.starts_with("POST /v1/spaces/AAA/messages?key=k&token=t HTTP/1.1"));
+        assert_eq!(json_body(&request)["cardsV2"][0]["cardId"], "alert-7");
+    }
+
+    #[tokio::test]
+    async fn test_post_error_hides_webhook_token() {
+        let (url, server) = serve(vec![(
+            400,
+            r#"{"error":{"code":400,"message":"Invalid JSON payload","status":"INVALID_ARGUMENT"}}"#,
+        )])
+        .await;
+
+        let err = GoogleChatClient::new()
+            .post(
+                &format!("{}/v1/spaces/AAA/messages?key=k&token=secret-token", url),
+                &GoogleChatMessage::new("hello"),
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        server.await.unwrap();
+
+        assert!(err.contains("HTTP 400"));
+        assert!(err.contains("Invalid JSON payload"));
+        assert!(!err.contains("secret-token"));
+    }
+}
@@ -486,6 +486,40 @@ pub struct EscalationRule {
     pub repeat_interval_seconds: Option<u32>,
 }
 
+impl EscalationRule {
+    /// Check if the rule escalates an active incident at `now`
+    ///
+    /// Only conditions that can be judged from the incident itself are
+    /// evaluated; remediation, approval, and custom conditions never match.
+    pub fn applies_to(&self, incident: &Incident, now: DateTime<Utc>) -> bool {
+        let open_seconds = (now - incident.detected_at).num_seconds();
+        if !incident.status.is_active() || open_seconds < self.delay_seconds as i64 {
+            return false;
+        }
+
+        match &self.condition {
+            EscalationCondition::SeverityCritical => {
+                incident.severity == IncidentSeverity::Critical
+            }
+            EscalationCondition::NoAcknowledgmentWithin { seconds } => {
+                incident.acknowledged_at.is_none() && open_seconds >= *seconds as i64
+            }
+            EscalationCondition::RemediationFailed
+            | EscalationCondition::ApprovalTimeout { .. }
+            | EscalationCondition::Custom { .. } => false,
+        }
+    }
+
+    /// Destinations of the rule's targets of one type
+    pub fn destinations(&self, target_type: EscalationTargetType) -> Vec<&str> {
+        self.targets
+            .iter()
+            .filter(|target| target.target_type == target_type)
+            .map(|target| target.destination.as_str())
+            .collect()
+    }
+}
+
 /// Escalation condition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -510,6 +544,7 @@ pub struct EscalationTarget {
 pub enum EscalationTargetType {
     Slack,
     PagerDuty,
+    Opsgenie,
     Email,
     Webhook,
 }
@@ -519,6 +554,7 @@ impl EscalationTargetType {
         match self {
             Self::Slack => "slack",
             Self::PagerDuty => "pagerduty",
+            Self::Opsgenie => "opsgenie",
             Self::Email => "email",
             Self::Webhook => "webhook",
         }
@@ -561,6 +597,51 @@ impl PostMortem {
         }
     }
 
+    /// Draft a post-mortem from an incident and its root cause analysis
+    ///
+    /// The resolution comes from the incident's latest resolved event, and
+    /// every known cause becomes an action item.
+    pub fn generate(incident: &Incident, rca: Option<&RootCauseAnalysis>) -> Self {
+        let mut pm = Self::from_incident(incident);
+        pm.summary = if incident.description.is_empty() {
+            format!("{} severity incident: {}", incident.severity.as_str(), incident.title)
+        } else {
+            incident.description.clone()
+        };
+        pm.impact = IncidentImpact {
+            duration_minutes: incident
+                .duration()
+                .and_then(|secs| u32::try_from(secs / 60).ok()),
+            services_affected: incident.affected_services.clone(),
+            ..Default::default()
+        };
+        pm.resolution = incident
+            .timeline
+            .iter()
+            .rev()
+            .find(|e| e.event_type == TimelineEventType::Resolved)
+            .map(|e| {
+                e.description
+                    .trim_start_matches("Incident resolved: ")
+                    .to_string()
+            })
+            .unwrap_or_default();
+
+        if let Some(rca) = rca {
+            pm.root_cause = rca.primary_cause.clone();
+            pm.contributing_factors = rca.contributing_factors.clone();
+        }
+        if !pm.root_cause.is_empty() {
+            let item = format!("Prevent recurrence: {}", pm.root_cause);
+            pm.add_action_item(&item, ActionItemPriority::High, None);
+        }
+        for factor in pm.contributing_factors.clone() {
+            let item = format!("Address contributing factor: {}", factor);
+            pm.add_action_item(&item, ActionItemPriority::Medium, None);
+        }
+        pm
+    }
+
     /// Add an action item
     pub fn add_action_item(&mut self, description: &str, priority: ActionItemPriority, assignee: Option<&str>) {
         self.action_items.push(ActionItem {
@@ -754,6 +835,47 @@ mod tests {
         assert!(incident.duration().is_some());
     }
 
+    #[test]
+    fn test_escalation_rule_applies_to() {
+        let now = Utc::now();
+        let mut incident = Incident::new("INC-004", "Queue backlog", IncidentSeverity::High);
+        incident.detected_at = now - chrono::Duration::minutes(10);
+
+        let critical = EscalationRule {
+            name: "critical".to_string(),
+            condition: EscalationCondition::SeverityCritical,
+            targets: vec![],
+            delay_seconds: 0,
+            repeat_interval_seconds: None,
+        };
+        assert!(!critical.applies_to(&incident, now));
+
+        let unacknowledged = EscalationRule {
+            name: "unacknowledged".to_string(),
+            condition: EscalationCondition::NoAcknowledgmentWithin { seconds: 300 },
+            targets: vec![],
+            delay_seconds: 0,
+            repeat_interval_seconds: None,
+        };
+        assert!(unacknowledged.applies_to(&incident, now));
+        assert!(!unacknowledged.applies_to(&incident, now - chrono::Duration::minutes(6)));
+
+        incident.severity = IncidentSeverity::Critical;
+        assert!(critical.applies_to(&incident, now));
+
+        let delayed = EscalationRule {
+            delay_seconds: 3600,
+            ..critical.clone()
+        };
+        assert!(!delayed.applies_to(&incident, now));
+
+        incident.acknowledge(Some("on-call"));
+        assert!(!unacknowledged.applies_to(&incident, now));
+
+        incident.resolve("Drained queue", None);
+        assert!(!critical.applies_to(&incident, now));
+    }
+
     #[test]
     fn test_root_cause_analysis() {
         let mut rca = RootCauseAnalysis::new("INC-001");
@@ -800,6 +922,26 @@ mod tests {
         assert!(md.contains("Review LB configuration"));
     }
 
+    #[test]
+    fn test_post_mortem_generate() {
+        let mut incident = Incident::new("INC-004", "Checkout errors", IncidentSeverity::High);
+        incident.affected_services.push("checkout".to_string());
+        incident.resolve("Rolled back the release", Some("oncall"));
+
+        let mut rca = RootCauseAnalysis::new("INC-004");
+        rca.set_primary_cause("Bad release");
+        rca.contributing_factors.push("No canary".to_string());
+
+        let pm = PostMortem::generate(&incident, Some(&rca));
+        assert_eq!(pm.summary, "high severity incident: Checkout errors");
+        assert_eq!(pm.resolution, "Rolled back the release");
+        assert_eq!(pm.root_cause, "Bad release");
+        assert_eq!(pm.impact.services_affected, vec!["checkout"]);
+        assert_eq!(pm.action_items.len(), 2);
+        assert_eq!(pm.action_items[0].priority, ActionItemPriority::High);
+        assert_eq!(pm.timeline.len(), 2);
+    }
+
     #[test]
     fn test_anomaly_detection() {
         let normal = AnomalyMetric::calculate_anomaly("error_rate", 2.0, 2.0, 50.0);
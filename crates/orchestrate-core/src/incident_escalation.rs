@@ -0,0 +1,827 @@
+//! Incident Escalation Service
+//!
+//! Delivers incident escalations and on-call pages, and keeps incidents and
+//! alerts in sync with PagerDuty and Opsgenie:
+//! - Critical incidents and firing alerts page the default PagerDuty service
+//!   and create an Opsgenie alert, for whichever is configured
+//! - [`EscalationRule`] targets are mapped to PagerDuty services (the target
+//!   destination is the routing key), Opsgenie teams, and email addresses
+//! - Acknowledgements and resolutions from PagerDuty webhooks update the
+//!   incident or alert, and resolving in orchestrate resolves the PagerDuty
+//!   page and closes the Opsgenie alert
+//!
+//! Every page is recorded in `oncall_pages` so later events reach every
+//! service that was paged for a resource.
+
+use chrono::Utc;
+use tracing::{info, warn};
+
+use crate::{
+    email_service::EmailNotificationService,
+    error::{Error, Result},
+    incident::{EscalationRule, EscalationTargetType, Incident, TimelineEventType},
+    monitoring::{Alert, AlertStatus},
+    oncall::{OncallPage, OncallPageStatus, OncallProvider, PagedResource},
+    opsgenie::{OpsgenieAlert, OpsgenieClient, OpsgenieResponder},
+    pagerduty::{PagerDutyClient, PagerDutyEvent, PagerDutyWebhookEvent},
+    Database,
+};
+
+/// Service paging the on-call and escalating incidents
+pub struct IncidentEscalationService {
+    db: Database,
+    pagerduty: PagerDutyClient,
+    routing_key: Option<String>,
+    opsgenie: Option<OpsgenieClient>,
+    email: Option<EmailNotificationService>,
+}
+
+impl IncidentEscalationService {
+    /// Create a service with no default PagerDuty service, no Opsgenie,
+    /// and no email
+    pub fn new(db: Database) -> Self {
+        Self {
+            db,
+            pagerduty: PagerDutyClient::new(),
+            routing_key: None,
+            opsgenie: None,
+            email: None,
+        }
+    }
+
+    /// Service configured by `PAGERDUTY_ROUTING_KEY`, `OPSGENIE_API_KEY`,
+    /// and `ORCHESTRATE_SMTP_*`
+    pub fn from_env(db: Database) -> Result<Self> {
+        let mut service = Self::new(db.clone());
+        if let Some(routing_key) = std::env::var("PAGERDUTY_ROUTING_KEY")
+            .ok()
+            .filter(|key| !key.is_empty())
+        {
+            service = service.with_routing_key(routing_key);
+        }
+        if let Some(opsgenie) = OpsgenieClient::from_env() {
+            service = service.with_opsgenie(opsgenie);
+        }
+        if let Some(email) = EmailNotificationService::from_env(db)? {
+            service = service.with_email(email);
+        }
+        Ok(service)
+    }
+
+    /// Send PagerDuty events with a different client
+    pub fn with_pagerduty_client(mut self, client: PagerDutyClient) -> Self {
+        self.pagerduty = client;
+        self
+    }
+
+    /// Page this PagerDuty service for critical incidents, firing alerts,
+    /// and PagerDuty targets without a destination
+    pub fn with_routing_key(mut self, routing_key: impl Into<String>) -> Self {
+        self.routing_key = Some(routing_key.into());
+        self
+    }
+
+    /// Create Opsgenie alerts for critical incidents, firing alerts, and
+    /// Opsgenie targets
+    pub fn with_opsgenie(mut self, client: OpsgenieClient) -> Self {
+        self.opsgenie = Some(client);
+        self
+    }
+
+    /// Deliver email escalation targets
+    pub fn with_email(mut self, email: EmailNotificationService) -> Self {
+        self.email = Some(email);
+        self
+    }
+
+    /// Routing key of the default PagerDuty service
+    pub fn routing_key(&self) -> Option<&str> {
+        self.routing_key.as_deref()
+    }
+
+    /// Whether Opsgenie alerts can be created
+    pub fn has_opsgenie(&self) -> bool {
+        self.opsgenie.is_some()
+    }
+
+    /// Page the default on-call for an active critical incident
+    ///
+    /// Returns whether a page was sent.
+    pub async fn page_incident(&self, incident: &Incident) -> Result<bool> {
+        if !incident.severity.requires_escalation() || !incident.status.is_active() {
+            return Ok(false);
+        }
+
+        let resource = PagedResource::Incident(incident.id.clone());
+        let mut deliveries = Deliveries::default();
+        if let Some(routing_key) = &self.routing_key {
+            let event = PagerDutyEvent::trigger_incident(routing_key, incident, None);
+            deliveries.record(self.trigger(routing_key, &event, &resource).await);
+        }
+        if let Some(opsgenie) = &self.opsgenie {
+            let alert = OpsgenieAlert::for_incident(incident, Vec::new());
+            deliveries.record(self.create_alert(opsgenie, &alert, &[""], &resource).await);
+        }
+
+        let sent = deliveries.finish(&format!("page incident {}", incident.id))?;
+        Ok(sent > 0)
+    }
+
+    /// Page the default on-call for a firing alert
+    ///
+    /// Returns whether a page was sent.
+    pub async fn page_alert(&self, alert: &Alert) -> Result<bool> {
+        if alert.status != AlertStatus::Firing {
+            return Ok(false);
+        }
+
+        let resource = PagedResource::Alert(alert.id.clone());
+        let mut deliveries = Deliveries::default();
+        if let Some(routing_key) = &self.routing_key {
+            let event = PagerDutyEvent::trigger_alert(routing_key, alert);
+            deliveries.record(self.trigger(routing_key, &event, &resource).await);
+        }
+        if let Some(opsgenie) = &self.opsgenie {
+            let opsgenie_alert = OpsgenieAlert::for_alert(alert, Vec::new());
+            deliveries.record(
+                self.create_alert(opsgenie, &opsgenie_alert, &[""], &resource)
+                    .await,
+            );
+        }
+
+        let sent = deliveries.finish(&format!("page alert {}", alert.id))?;
+        Ok(sent > 0)
+    }
+
+    /// Escalate an incident to the PagerDuty, Opsgenie, and email targets of
+    /// a rule
+    ///
+    /// Records an `escalated` timeline event on the incident. Delivery
+    /// continues past failed targets and fails only when nothing could be
+    /// delivered. Returns the number of pages and emails sent.
+    pub async fn escalate(&self, incident: &mut Incident, rule: &EscalationRule) -> Result<usize> {
+        let resource = PagedResource::Incident(incident.id.clone());
+        let mut pages = Deliveries::default();
+
+        for destination in rule.destinations(EscalationTargetType::PagerDuty) {
+            let routing_key = match (destination.is_empty(), &self.routing_key) {
+                (false, _) => destination,
+                (true, Some(routing_key)) => routing_key.as_str(),
+                (true, None) => {
+                    pages.fail("PagerDuty target has no routing key");
+                    continue;
+                }
+            };
+            let event = PagerDutyEvent::trigger_incident(routing_key, incident, Some(rule));
+            pages.record(self.trigger(routing_key, &event, &resource).await);
+        }
+
+        let teams = rule.destinations(EscalationTargetType::Opsgenie);
+        if !teams.is_empty() {
+            match &self.opsgenie {
+                Some(opsgenie) => {
+                    let responders = teams
+                        .iter()
+                        .filter(|team| !team.is_empty())
+                        .map(|team| OpsgenieResponder::team(*team))
+                        .collect();
+                    let alert = OpsgenieAlert::for_incident(incident, responders);
+                    pages.record(self.create_alert(opsgenie, &alert, &teams, &resource).await);
+                }
+                None => pages.fail("Opsgenie is not configured"),
+            }
+        }
+
+        let mut emails = Deliveries::default();
+        if let Some(email) = &self.email {
+            match email.notify_incident_escalation(incident, rule).await {
+                Ok(sent) => emails.sent += sent,
+                Err(e) => emails.fail(&e.to_string()),
+            }
+        } else if !rule.destinations(EscalationTargetType::Email).is_empty() {
+            warn!(rule = %rule.name, "SMTP is not configured, skipping email escalation targets");
+        }
+
+        let (paged, emailed) = (pages.sent, emails.sent);
+        pages.sent += emails.sent;
+        pages.errors.append(&mut emails.errors);
+        let sent = pages.finish(&format!(
+            "escalate incident {} under rule {}",
+            incident.id, rule.name
+        ))?;
+
+        incident.add_timeline_event(
+            TimelineEventType::Escalated,
+            &format!(
+                "Escalated under rule {}: paged {} on-call target(s), emailed {} recipient(s)",
+                rule.name, paged, emailed
+            ),
+            None,
+        );
+        self.persist_timeline_event(incident).await?;
+
+        Ok(sent)
+    }
+
+    /// Resolve an incident and every open page of it
+    ///
+    /// Returns the number of pages resolved.
+    pub async fn resolve_incident(
+        &self,
+        incident: &mut Incident,
+        resolution: &str,
+        actor: Option<&str>,
+    ) -> Result<usize> {
+        incident.resolve(resolution, actor);
+        self.db.update_incident(incident).await?;
+        self.persist_timeline_event(incident).await?;
+        self.resolve_pages(&PagedResource::Incident(incident.id.clone()))
+            .await
+    }
+
+    /// Acknowledge an alert and every open page of it
+    ///
+    /// Returns the number of pages acknowledged.
+    pub async fn acknowledge_alert(&self, alert: &mut Alert, by: &str) -> Result<usize> {
+        alert.acknowledge(by);
+        self.db.update_alert(alert).await?;
+        self.acknowledge_pages(&PagedResource::Alert(alert.id.clone()))
+            .await
+    }
+
+    /// Resolve an alert and every open page of it, closing its Opsgenie alert
+    ///
+    /// Returns the number of pages resolved.
+    pub async fn resolve_alert(&self, alert: &mut Alert) -> Result<usize> {
+        alert.resolve();
+        self.db.update_alert(alert).await?;
+        self.resolve_pages(&PagedResource::Alert(alert.id.clone()))
+            .await
+    }
+
+    /// Acknowledge every open page of a resource
+    ///
+    /// Returns the number of pages acknowledged.
+    pub async fn acknowledge_pages(&self, resource: &PagedResource) -> Result<usize> {
+        self.follow_up(resource, OncallPageStatus::Acknowledged)
+            .await
+    }
+
+    /// Resolve every open page of a resource
+    ///
+    /// Returns the number of pages resolved.
+    pub async fn resolve_pages(&self, resource: &PagedResource) -> Result<usize> {
+        self.follow_up(resource, OncallPageStatus::Resolved).await
+    }
+
+    /// Apply an acknowledgement or resolution reported by a PagerDuty webhook
+    /// to the incident or alert that was paged
+    ///
+    /// Pages of the resource at other providers follow, so an Opsgenie alert
+    /// is closed when the PagerDuty incident is resolved. Returns whether the
+    /// event was about a resource orchestrate paged.
+    pub async fn apply_webhook(&self, event: &PagerDutyWebhookEvent) -> Result<bool> {
+        let Some(resource) = event.resource() else {
+            return Ok(false);
+        };
+        let status = match event.event_type.as_str() {
+            "incident.acknowledged" => OncallPageStatus::Acknowledged,
+            "incident.resolved" => OncallPageStatus::Resolved,
+            _ => return Ok(false),
+        };
+        let actor = event.actor().unwrap_or("PagerDuty");
+
+        let found = match &resource {
+            PagedResource::Incident(id) => self.sync_incident(id, status, actor).await?,
+            PagedResource::Alert(id) => self.sync_alert(id, status, actor).await?,
+        };
+        if !found {
+            return Ok(false);
+        }
+
+        self.db
+            .update_oncall_page_status(OncallProvider::PagerDuty, &resource, status)
+            .await?;
+        info!(
+            resource = %resource.dedup_key(),
+            status = status.as_str(),
+            actor = %actor,
+            "Synced PagerDuty page status"
+        );
+
+        if let Err(e) = self.follow_up(&resource, status).await {
+            warn!(resource = %resource.dedup_key(), error = %e, "Failed to sync other on-call pages");
+        }
+        Ok(true)
+    }
+
+    async fn trigger(
+        &self,
+        routing_key: &str,
+        event: &PagerDutyEvent,
+        resource: &PagedResource,
+    ) -> Result<()> {
+        self.pagerduty.send(event).await?;
+        let page = OncallPage::new(OncallProvider::PagerDuty, routing_key, resource.clone());
+        self.db.upsert_oncall_page(&page).await?;
+        info!(resource = %page.dedup_key(), "Paged PagerDuty");
+        Ok(())
+    }
+
+    /// Create an Opsgenie alert, recording a page for each team paged
+    async fn create_alert(
+        &self,
+        opsgenie: &OpsgenieClient,
+        alert: &OpsgenieAlert,
+        teams: &[&str],
+        resource: &PagedResource,
+    ) -> Result<()> {
+        opsgenie.create_alert(alert).await?;
+        for team in teams {
+            let page = OncallPage::new(OncallProvider::Opsgenie, *team, resource.clone());
+            self.db.upsert_oncall_page(&page).await?;
+        }
+        info!(resource = %resource.dedup_key(), priority = alert.priority.as_str(), "Created Opsgenie alert");
+        Ok(())
+    }
+
+    /// Move every open page of a resource to `status` at its provider
+    async fn follow_up(&self, resource: &PagedResource, status: OncallPageStatus) -> Result<usize> {
+        let pages: Vec<OncallPage> = self
+            .db
+            .list_oncall_pages(resource)
+            .await?
+            .into_iter()
+            .filter(|page| page.is_open() && page.status != status)
+            .collect();
+        let mut deliveries = Deliveries::default();
+
+        for mut page in pages
+            .iter()
+            .filter(|page| page.provider == OncallProvider::PagerDuty)
+            .cloned()
+        {
+            let event = match status {
+                OncallPageStatus::Resolved => PagerDutyEvent::resolve(&page.routing_key, resource),
+                _ => PagerDutyEvent::acknowledge(&page.routing_key, resource),
+            };
+            match self.pagerduty.send(&event).await {
+                Ok(_) => {
+                    page.status = status;
+                    page.updated_at = Utc::now();
+                    self.db.upsert_oncall_page(&page).await?;
+                    deliveries.sent += 1;
+                }
+                Err(e) => deliveries.fail(&e.to_string()),
+            }
+        }
+
+        // Every Opsgenie page of a resource is the same alert, addressed by alias
+        let opsgenie_pages = pages
+            .iter()
+            .filter(|page| page.provider == OncallProvider::Opsgenie)
+            .count();
+        if opsgenie_pages > 0 {
+            match &self.opsgenie {
+                Some(opsgenie) => {
+                    let alias = resource.dedup_key();
+                    let result = match status {
+                        OncallPageStatus::Resolved => {
+                            opsgenie
+                                .close_alert(&alias, "Resolved in orchestrate")
+                                .await
+                        }
+                        _ => {
+                            opsgenie
+                                .acknowledge_alert(&alias, "Acknowledged in orchestrate")
+                                .await
+                        }
+                    };
+                    match result {
+                        Ok(_) => {
+                            self.db
+                                .update_oncall_page_status(
+                                    OncallProvider::Opsgenie,
+                                    resource,
+                                    status,
+                                )
+                                .await?;
+                            deliveries.sent += opsgenie_pages;
+                        }
+                        Err(e) => deliveries.fail(&e.to_string()),
+                    }
+                }
+                None => deliveries.fail("Opsgenie is not configured"),
+            }
+        }
+
+        deliveries.finish(&format!(
+            "mark pages of {} {}",
+            resource.dedup_key(),
+            status.as_str()
+        ))
+    }
+
+    async fn sync_incident(&self, id: &str, status: OncallPageStatus, actor: &str) -> Result<bool> {
+        let Some(mut incident) = self.db.get_incident(id).await? else {
+            return Ok(false);
+        };
+
+        match status {
+            OncallPageStatus::Acknowledged if incident.acknowledged_at.is_none() => {
+                incident.acknowledge(Some(actor));
+            }
+            OncallPageStatus::Resolved if incident.status.is_active() => {
+                incident.resolve("Resolved in PagerDuty", Some(actor));
+            }
+            _ => return Ok(true),
+        }
+
+        self.db.update_incident(&incident).await?;
+        self.persist_timeline_event(&incident).await?;
+        Ok(true)
+    }
+
+    async fn sync_alert(&self, id: &str, status: OncallPageStatus, actor: &str) -> Result<bool> {
+        let Ok(id) = id.parse::<i64>() else {
+            return Ok(false);
+        };
+        let Some(mut alert) = self.db.get_alert(id).await? else {
+            return Ok(false);
+        };
+
+        match status {
+            OncallPageStatus::Acknowledged
+                if !matches!(
+                    alert.status,
+                    AlertStatus::Acknowledged | AlertStatus::Resolved
+                ) =>
+            {
+                alert.acknowledge(actor);
+            }
+            OncallPageStatus::Resolved if alert.status != AlertStatus::Resolved => {
+                alert.resolve();
+            }
+            _ => return Ok(true),
+        }
+
+        self.db.update_alert(&alert).await?;
+        Ok(true)
+    }
+
+    /// Store the latest timeline event, which `update_incident` does not
+    async fn persist_timeline_event(&self, incident: &Incident) -> Result<()> {
+        if let Some(event) = incident.timeline.last() {
+            self.db.add_timeline_event(&incident.id, event).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of delivering to several targets
+#[derive(Default)]
+struct Deliveries {
+    sent: usize,
+    errors: Vec<String>,
+}
+
+impl Deliveries {
+    fn record(&mut self, result: Result<()>) {
+        match result {
+            Ok(()) => self.sent += 1,
+            Err(e) => self.fail(&e.to_string()),
+        }
+    }
+
+    fn fail(&mut self, error: &str) {
+        self.errors.push(error.to_string());
+    }
+
+    /// Number delivered, failing only when every target failed
+    fn finish(self, action: &str) -> Result<usize> {
+        if self.sent == 0 && !self.errors.is_empty() {
+            return Err(Error::Other(format!(
+                "Failed to {}: {}",
+                action,
+                self.errors.join("; ")
+            )));
+        }
+        for error in &self.errors {
+            warn!(error = %error, "Failed to {}", action);
+        }
+        Ok(self.sent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::incident::{
+        EscalationCondition, EscalationTarget, IncidentSeverity, IncidentStatus,
+    };
+    use crate::monitoring::{AlertRule, AlertSeverity};
+    use crate::pagerduty::{PagerDutyIncidentData, PagerDutyReference};
+    use crate::test_servers::{json_body, request_line, serve};
+    use serde_json::Value;
+
+    /// Accept `count` PagerDuty events, returning their bodies
+    async fn events_api(count: usize) -> (String, tokio::task::JoinHandle<Vec<Value>>) {
+        let (url, server) = serve(vec![
+            (
+                202,
+                r#"{"status":"success","message":"Event processed"}"#
+            );
+            count
+        ])
+        .await;
+        let handle = tokio::spawn(async move {
+            server
+                .await
+                .unwrap()
+                .iter()
+                .map(|request| json_body(request))
+                .collect()
+        });
+        (format!("{}/v2/enqueue", url), handle)
+    }
+
+    /// Accept `count` Opsgenie alert requests, returning their request lines
+    /// and bodies
+    async fn opsgenie_api(
+        count: usize,
+    ) -> (
+        OpsgenieClient,
+        tokio::task::JoinHandle<Vec<(String, Value)>>,
+    ) {
+        let (url, server) = serve(vec![
+            (
+                202,
+                r#"{"result":"Request will be processed","took":0.01,"requestId":"req-1"}"#,
+            );
+            count
+        ])
+        .await;
+        let handle = tokio::spawn(async move {
+            server
+                .await
+                .unwrap()
+                .iter()
+                .map(|request| (request_line(request).to_string(), json_body(request)))
+                .collect()
+        });
+        (OpsgenieClient::new("genie-key").with_base_url(url), handle)
+    }
+
+    fn rule(targets: Vec<EscalationTarget>) -> EscalationRule {
+        EscalationRule {
+            name: "critical".to_string(),
+            condition: EscalationCondition::SeverityCritical,
+            targets,
+            delay_seconds: 0,
+            repeat_interval_seconds: None,
+        }
+    }
+
+    fn webhook(event_type: &str, incident_key: &str) -> PagerDutyWebhookEvent {
+        PagerDutyWebhookEvent {
+            event_type: event_type.to_string(),
+            agent: Some(PagerDutyReference {
+                id: "PLH1HKV".to_string(),
+                summary: Some("Jane Doe".to_string()),
+            }),
+            data: PagerDutyIncidentData {
+                incident_key: Some(incident_key.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_page_and_resolve_critical_incident() {
+        let db = Database::in_memory().await.unwrap();
+        let (url, server) = events_api(2).await;
+        let service = IncidentEscalationService::new(db.clone())
+            .with_pagerduty_client(PagerDutyClient::new().with_events_url(url))
+            .with_routing_key("DEFAULT");
+
+        let minor = Incident::new("INC-1", "Slow page", IncidentSeverity::Low);
+        assert!(!service.page_incident(&minor).await.unwrap());
+
+        let incident = Incident::new("INC-2", "API down", IncidentSeverity::Critical);
+        assert!(service.page_incident(&incident).await.unwrap());
+
+        let resource = PagedResource::Incident("INC-2".to_string());
+        assert_eq!(service.resolve_pages(&resource).await.unwrap(), 1);
+        assert_eq!(service.resolve_pages(&resource).await.unwrap(), 0);
+
+        let events = server.await.unwrap();
+        assert_eq!(events[0]["event_action"], "trigger");
+        assert_eq!(events[0]["routing_key"], "DEFAULT");
+        assert_eq!(events[1]["event_action"], "resolve");
+        assert_eq!(events[1]["dedup_key"], "orchestrate:incident:INC-2");
+
+        let pages = db.list_oncall_pages(&resource).await.unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].status, OncallPageStatus::Resolved);
+    }
+
+    #[tokio::test]
+    async fn test_escalate_to_rule_targets() {
+        let db = Database::in_memory().await.unwrap();
+        let (url, server) = events_api(2).await;
+        let service = IncidentEscalationService::new(db.clone())
+            .with_pagerduty_client(PagerDutyClient::new().with_events_url(url))
+            .with_routing_key("DEFAULT");
+
+        let mut incident = Incident::new("INC-3", "Queue backlog", IncidentSeverity::High);
+        db.create_incident(&incident).await.unwrap();
+
+        let rule = rule(vec![
+            EscalationTarget {
+                target_type: EscalationTargetType::PagerDuty,
+                destination: "DATABASE-TEAM".to_string(),
+            },
+            EscalationTarget {
+                target_type: EscalationTargetType::PagerDuty,
+                destination: String::new(),
+            },
+            EscalationTarget {
+                target_type: EscalationTargetType::Slack,
+                destination: "#incidents".to_string(),
+            },
+        ]);
+        assert_eq!(service.escalate(&mut incident, &rule).await.unwrap(), 2);
+
+        let events = server.await.unwrap();
+        assert_eq!(events[0]["routing_key"], "DATABASE-TEAM");
+        assert_eq!(events[1]["routing_key"], "DEFAULT");
+        assert_eq!(
+            events[0]["payload"]["custom_details"]["escalation_rule"],
+            "critical"
+        );
+
+        let stored = db.get_incident("INC-3").await.unwrap().unwrap();
+        let last = stored.timeline.last().unwrap();
+        assert_eq!(last.event_type, TimelineEventType::Escalated);
+        assert!(last.description.contains("paged 2 on-call target(s)"));
+        assert_eq!(
+            db.list_oncall_pages(&PagedResource::Incident("INC-3".to_string()))
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_escalate_fails_when_nothing_is_delivered() {
+        let db = Database::in_memory().await.unwrap();
+        let service = IncidentEscalationService::new(db);
+
+        let mut incident = Incident::new("INC-4", "API down", IncidentSeverity::Critical);
+        let rule = rule(vec![EscalationTarget {
+            target_type: EscalationTargetType::PagerDuty,
+            destination: String::new(),
+        }]);
+        let err = service.escalate(&mut incident, &rule).await.unwrap_err();
+        assert!(err.to_string().contains("no routing key"));
+        assert_eq!(incident.timeline.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_syncs_incident() {
+        let db = Database::in_memory().await.unwrap();
+        let service = IncidentEscalationService::new(db.clone());
+
+        let incident = Incident::new("INC-5", "API down", IncidentSeverity::Critical);
+        db.create_incident(&incident).await.unwrap();
+        let resource = PagedResource::Incident("INC-5".to_string());
+        db.upsert_oncall_page(&OncallPage::new(
+            OncallProvider::PagerDuty,
+            "DEFAULT",
+            resource.clone(),
+        ))
+        .await
+        .unwrap();
+
+        let acknowledged = webhook("incident.acknowledged", "orchestrate:incident:INC-5");
+        assert!(service.apply_webhook(&acknowledged).await.unwrap());
+        let stored = db.get_incident("INC-5").await.unwrap().unwrap();
+        assert!(stored.acknowledged_at.is_some());
+        assert_eq!(
+            stored.timeline.last().unwrap().actor.as_deref(),
+            Some("Jane Doe")
+        );
+        assert_eq!(
+            db.list_oncall_pages(&resource).await.unwrap()[0].status,
+            OncallPageStatus::Acknowledged
+        );
+
+        let resolved = webhook("incident.resolved", "orchestrate:incident:INC-5");
+        assert!(service.apply_webhook(&resolved).await.unwrap());
+        let stored = db.get_incident("INC-5").await.unwrap().unwrap();
+        assert_eq!(stored.status, IncidentStatus::Resolved);
+        assert_eq!(stored.timeline.len(), 3);
+
+        // Redelivery does not add another timeline event
+        assert!(service.apply_webhook(&resolved).await.unwrap());
+        assert_eq!(
+            db.get_incident("INC-5")
+                .await
+                .unwrap()
+                .unwrap()
+                .timeline
+                .len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_ignores_foreign_events() {
+        let db = Database::in_memory().await.unwrap();
+        let service = IncidentEscalationService::new(db);
+
+        assert!(!service
+            .apply_webhook(&webhook("incident.resolved", "srv01/HTTP"))
+            .await
+            .unwrap());
+        assert!(!service
+            .apply_webhook(&webhook(
+                "incident.resolved",
+                "orchestrate:incident:INC-404"
+            ))
+            .await
+            .unwrap());
+        assert!(!service
+            .apply_webhook(&webhook("incident.annotated", "orchestrate:incident:INC-5"))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_opsgenie_alert_auto_closes() {
+        let db = Database::in_memory().await.unwrap();
+        let (opsgenie, server) = opsgenie_api(2).await;
+        let service = IncidentEscalationService::new(db.clone()).with_opsgenie(opsgenie);
+
+        let rule = AlertRule::new("queue-backup", "queue_depth > 100", AlertSeverity::Critical);
+        let mut alert = Alert::new(&rule, "Queue depth 250");
+        alert.id = "7".to_string();
+        assert!(service.page_alert(&alert).await.unwrap());
+        let resource = PagedResource::Alert("7".to_string());
+        assert_eq!(service.resolve_pages(&resource).await.unwrap(), 1);
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests[0].0, "POST /v2/alerts HTTP/1.1");
+        assert_eq!(requests[0].1["priority"], "P1");
+        assert_eq!(requests[0].1["alias"], "orchestrate:alert:7");
+        assert!(requests[1]
+            .0
+            .starts_with("POST /v2/alerts/orchestrate:alert:7/close?identifierType=alias"));
+
+        let pages = db.list_oncall_pages(&resource).await.unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].provider, OncallProvider::Opsgenie);
+        assert_eq!(pages[0].status, OncallPageStatus::Resolved);
+    }
+
+    #[tokio::test]
+    async fn test_escalate_to_opsgenie_teams() {
+        let db = Database::in_memory().await.unwrap();
+        let (opsgenie, server) = opsgenie_api(1).await;
+        let service = IncidentEscalationService::new(db.clone()).with_opsgenie(opsgenie);
+
+        let mut incident = Incident::new("INC-6", "Queue backlog", IncidentSeverity::High);
+        db.create_incident(&incident).await.unwrap();
+        let rule = rule(vec![
+            EscalationTarget {
+                target_type: EscalationTargetType::Opsgenie,
+                destination: "database".to_string(),
+            },
+            EscalationTarget {
+                target_type: EscalationTargetType::Opsgenie,
+                destination: "platform".to_string(),
+            },
+        ]);
+        assert_eq!(service.escalate(&mut incident, &rule).await.unwrap(), 1);
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests[0].1["priority"], "P2");
+        assert_eq!(requests[0].1["responders"][0]["name"], "database");
+        assert_eq!(requests[0].1["responders"][1]["type"], "team");
+        assert_eq!(
+            db.list_oncall_pages(&PagedResource::Incident("INC-6".to_string()))
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let err = IncidentEscalationService::new(db)
+            .escalate(&mut incident, &rule)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Opsgenie is not configured"));
+    }
+}
@@ -0,0 +1,617 @@
+//! GitHub Issue Triage
+//!
+//! Automated first-pass triage of newly opened issues:
+//! - Suggests labels from keyword rules
+//! - Finds likely duplicates among previously triaged issues of the same
+//!   repository by comparing titles and bodies
+//! - Estimates complexity in story points
+//! - Optionally turns feature requests into BMAD stories
+//!
+//! The result is recorded as an [`IssueTriageRecord`], commented on the
+//! issue, and handed to the issue-triager agent, which reviews and refines
+//! it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use crate::epic::Story;
+use crate::{Error, Result};
+
+/// Words ignored when comparing issues
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "this", "that", "when", "from", "are", "was", "not", "but",
+    "have", "has", "its", "into", "after", "before", "should", "would", "could", "can", "does",
+    "doesn", "don", "will", "there", "then", "than", "what", "which", "while", "our", "your",
+];
+
+/// Words suggesting an issue reworks existing code
+const REWORK_WORDS: &[&str] = &[
+    "refactor",
+    "migrate",
+    "migration",
+    "redesign",
+    "architecture",
+    "rewrite",
+];
+
+/// Duplicate candidates reported per issue
+const MAX_DUPLICATES: usize = 3;
+
+/// Issue triage configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueTriageConfig {
+    /// Keywords suggesting each label
+    pub label_rules: BTreeMap<String, Vec<String>>,
+    /// Similarity from which an issue is reported as a possible duplicate
+    pub duplicate_threshold: f64,
+    /// Epic stories are created in; no stories are created when unset
+    pub story_epic: Option<String>,
+    /// Issues carrying one of these labels become stories
+    pub story_labels: Vec<String>,
+}
+
+impl Default for IssueTriageConfig {
+    fn default() -> Self {
+        let rules: &[(&str, &[&str])] = &[
+            (
+                "bug",
+                &[
+                    "bug",
+                    "crash",
+                    "crashes",
+                    "error",
+                    "fails",
+                    "failure",
+                    "broken",
+                    "exception",
+                    "panic",
+                    "regression",
+                    "segfault",
+                ],
+            ),
+            (
+                "enhancement",
+                &[
+                    "feature",
+                    "add",
+                    "support",
+                    "request",
+                    "improve",
+                    "enhancement",
+                    "allow",
+                ],
+            ),
+            (
+                "documentation",
+                &["docs", "documentation", "readme", "typo", "example"],
+            ),
+            (
+                "performance",
+                &["slow", "performance", "latency", "memory", "leak"],
+            ),
+            (
+                "security",
+                &[
+                    "security",
+                    "vulnerability",
+                    "cve",
+                    "xss",
+                    "injection",
+                    "csrf",
+                ],
+            ),
+            ("question", &["question", "how", "why", "help"]),
+        ];
+
+        Self {
+            label_rules: rules
+                .iter()
+                .map(|(label, keywords)| {
+                    (
+                        label.to_string(),
+                        keywords.iter().map(|k| k.to_string()).collect(),
+                    )
+                })
+                .collect(),
+            duplicate_threshold: 0.5,
+            story_epic: None,
+            story_labels: vec!["enhancement".to_string()],
+        }
+    }
+}
+
+impl IssueTriageConfig {
+    /// Default configuration, creating stories in `ORCHESTRATE_TRIAGE_EPIC`
+    /// if set
+    pub fn from_env() -> Self {
+        let config = Self::default();
+        match std::env::var("ORCHESTRATE_TRIAGE_EPIC") {
+            Ok(epic) if !epic.is_empty() => config.with_story_epic(epic),
+            _ => config,
+        }
+    }
+
+    /// Create stories for qualifying issues in `epic_id`
+    pub fn with_story_epic(mut self, epic_id: impl Into<String>) -> Self {
+        self.story_epic = Some(epic_id.into());
+        self
+    }
+}
+
+/// Complexity estimate of an issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueComplexity {
+    Small,
+    Medium,
+    Large,
+    XLarge,
+}
+
+impl IssueComplexity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Small => "small",
+            Self::Medium => "medium",
+            Self::Large => "large",
+            Self::XLarge => "xlarge",
+        }
+    }
+
+    /// Story points of the estimate
+    pub fn story_points(&self) -> u32 {
+        match self {
+            Self::Small => 1,
+            Self::Medium => 3,
+            Self::Large => 5,
+            Self::XLarge => 8,
+        }
+    }
+
+    /// Estimate with its story points, e.g. `medium (3 story points)`
+    pub fn describe(&self) -> String {
+        match self.story_points() {
+            1 => format!("{} (1 story point)", self.as_str()),
+            points => format!("{} ({} story points)", self.as_str(), points),
+        }
+    }
+
+    /// Label applied for the estimate, e.g. `size:M`
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Small => "size:S",
+            Self::Medium => "size:M",
+            Self::Large => "size:L",
+            Self::XLarge => "size:XL",
+        }
+    }
+}
+
+impl FromStr for IssueComplexity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "small" => Ok(Self::Small),
+            "medium" => Ok(Self::Medium),
+            "large" => Ok(Self::Large),
+            "xlarge" => Ok(Self::XLarge),
+            _ => Err(Error::Other(format!("Unknown complexity: {}", s))),
+        }
+    }
+}
+
+/// An issue to triage
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TriageIssue {
+    pub number: i64,
+    pub title: String,
+    pub body: String,
+    /// Labels the issue already has
+    pub labels: Vec<String>,
+}
+
+/// A previously triaged issue resembling the one being triaged
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateCandidate {
+    pub issue_number: i64,
+    pub title: String,
+    /// Similarity between 0 and 1
+    pub similarity: f64,
+}
+
+/// Result of triaging an issue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueTriage {
+    pub issue_number: i64,
+    /// Labels to add, including the size label
+    pub labels: Vec<String>,
+    /// Possible duplicates, most similar first
+    pub duplicates: Vec<DuplicateCandidate>,
+    pub complexity: IssueComplexity,
+    /// Story created for the issue
+    pub story_id: Option<String>,
+}
+
+impl IssueTriage {
+    /// Issue this one most likely duplicates
+    pub fn duplicate_of(&self) -> Option<i64> {
+        self.duplicates.first().map(|d| d.issue_number)
+    }
+
+    /// Markdown comment reporting the triage on the issue
+    pub fn comment(&self) -> String {
+        let mut comment = String::from("🤖 **Orchestrate triage**\n\n");
+
+        if self.labels.is_empty() {
+            comment.push_str("- **Labels:** none suggested\n");
+        } else {
+            comment.push_str(&format!(
+                "- **Labels:** {}\n",
+                self.labels
+                    .iter()
+                    .map(|label| format!("`{}`", label))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        comment.push_str(&format!(
+            "- **Estimated complexity:** {}\n",
+            self.complexity.describe()
+        ));
+        if let Some(story_id) = &self.story_id {
+            comment.push_str(&format!("- **Story:** `{}`\n", story_id));
+        }
+
+        if !self.duplicates.is_empty() {
+            comment.push_str("\n**Possible duplicates:**\n");
+            for duplicate in &self.duplicates {
+                comment.push_str(&format!(
+                    "- #{} {} ({:.0}% similar)\n",
+                    duplicate.issue_number,
+                    duplicate.title,
+                    duplicate.similarity * 100.0
+                ));
+            }
+        }
+
+        comment.push_str("\nAn issue-triager agent will review this triage.");
+        comment
+    }
+}
+
+/// Recorded triage of an issue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueTriageRecord {
+    pub id: Option<i64>,
+    /// Repository full name, e.g. `owner/repo`
+    pub repository: String,
+    pub issue_number: i64,
+    pub title: String,
+    pub body: String,
+    /// Labels of the issue after triage
+    pub labels: Vec<String>,
+    pub duplicate_of: Option<i64>,
+    pub complexity: IssueComplexity,
+    pub story_id: Option<String>,
+    /// Issue-triager agent spawned for the issue
+    pub agent_id: Option<uuid::Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl IssueTriageRecord {
+    pub fn new(repository: impl Into<String>, issue: &TriageIssue, triage: &IssueTriage) -> Self {
+        let now = Utc::now();
+        let mut labels = issue.labels.clone();
+        for label in &triage.labels {
+            if !labels.contains(label) {
+                labels.push(label.clone());
+            }
+        }
+
+        Self {
+            id: None,
+            repository: repository.into(),
+            issue_number: issue.number,
+            title: issue.title.clone(),
+            body: issue.body.clone(),
+            labels,
+            duplicate_of: triage.duplicate_of(),
+            complexity: triage.complexity,
+            story_id: triage.story_id.clone(),
+            agent_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Rule-based issue triager
+pub struct IssueTriager {
+    config: IssueTriageConfig,
+}
+
+impl IssueTriager {
+    pub fn new(config: IssueTriageConfig) -> Self {
+        Self { config }
+    }
+
+    /// Triage an issue against previously triaged issues of its repository
+    pub fn triage(&self, issue: &TriageIssue, existing: &[IssueTriageRecord]) -> IssueTriage {
+        let mut labels = self.suggest_labels(issue);
+        let complexity = estimate_complexity(issue);
+
+        let mut duplicates: Vec<DuplicateCandidate> = existing
+            .iter()
+            .filter(|other| other.issue_number != issue.number)
+            .map(|other| DuplicateCandidate {
+                issue_number: other.issue_number,
+                title: other.title.clone(),
+                similarity: similarity(&issue.title, &issue.body, &other.title, &other.body),
+            })
+            .filter(|candidate| candidate.similarity >= self.config.duplicate_threshold)
+            .collect();
+        duplicates.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        duplicates.truncate(MAX_DUPLICATES);
+
+        labels.push(complexity.label().to_string());
+        if !duplicates.is_empty() && !issue.labels.iter().any(|l| l == "duplicate") {
+            labels.push("duplicate".to_string());
+        }
+
+        IssueTriage {
+            issue_number: issue.number,
+            labels,
+            duplicates,
+            complexity,
+            story_id: None,
+        }
+    }
+
+    /// Story to create for a triaged issue, if the configuration asks for
+    /// one
+    ///
+    /// Likely duplicates never become stories.
+    pub fn story(
+        &self,
+        repository: &str,
+        issue: &TriageIssue,
+        triage: &IssueTriage,
+    ) -> Option<Story> {
+        let epic_id = self.config.story_epic.as_ref()?;
+        if triage.duplicate_of().is_some() {
+            return None;
+        }
+        let qualifies = issue
+            .labels
+            .iter()
+            .chain(&triage.labels)
+            .any(|label| self.config.story_labels.contains(label));
+        if !qualifies {
+            return None;
+        }
+
+        let mut story = Story::new(
+            format!("{}.issue-{}", epic_id, issue.number),
+            epic_id.clone(),
+            issue.title.clone(),
+        );
+        let mut description = issue.body.trim().to_string();
+        if !description.is_empty() {
+            description.push_str("\n\n");
+        }
+        description.push_str(&format!(
+            "GitHub issue: {}#{}\nEstimated complexity: {}",
+            repository,
+            issue.number,
+            triage.complexity.describe()
+        ));
+        story.description = Some(description);
+        Some(story)
+    }
+
+    fn suggest_labels(&self, issue: &TriageIssue) -> Vec<String> {
+        let words = words(&format!("{} {}", issue.title, issue.body));
+        self.config
+            .label_rules
+            .iter()
+            .filter(|(label, _)| !issue.labels.iter().any(|l| l.eq_ignore_ascii_case(label)))
+            .filter(|(_, keywords)| keywords.iter().any(|k| words.contains(k.as_str())))
+            .map(|(label, _)| label.clone())
+            .collect()
+    }
+}
+
+/// Estimate complexity from the size and shape of an issue
+///
+/// Long descriptions, code blocks, task lists, referenced files, and words
+/// such as "refactor" or "migrate" each push the estimate up.
+pub fn estimate_complexity(issue: &TriageIssue) -> IssueComplexity {
+    let body = &issue.body;
+    let mut score = 0;
+
+    let word_count = body.split_whitespace().count();
+    score += match word_count {
+        0..=50 => 0,
+        51..=150 => 1,
+        151..=400 => 2,
+        _ => 3,
+    };
+    if body.contains("```") {
+        score += 1;
+    }
+    let tasks = body
+        .lines()
+        .filter(|line| {
+            let line = line.trim_start();
+            line.starts_with("- [ ]") || line.starts_with("- [x]")
+        })
+        .count();
+    score += tasks.div_ceil(3);
+    let files = body
+        .split_whitespace()
+        .filter(|word| {
+            let word = word.trim_matches(|c: char| !c.is_alphanumeric() && c != '.' && c != '/');
+            word.contains('/')
+                && word
+                    .rsplit_once('.')
+                    .is_some_and(|(_, ext)| (1..=4).contains(&ext.len()))
+        })
+        .count();
+    score += files.min(2);
+    let words = words(&format!("{} {}", issue.title, body));
+    if REWORK_WORDS.iter().any(|word| words.contains(*word)) {
+        score += 2;
+    }
+
+    match score {
+        0..=1 => IssueComplexity::Small,
+        2..=3 => IssueComplexity::Medium,
+        4..=5 => IssueComplexity::Large,
+        _ => IssueComplexity::XLarge,
+    }
+}
+
+/// Similarity of two issues between 0 and 1, weighting titles over bodies
+pub fn similarity(title: &str, body: &str, other_title: &str, other_body: &str) -> f64 {
+    let titles = jaccard(&words(title), &words(other_title));
+    let (body_words, other_body_words) = (words(body), words(other_body));
+    if body_words.is_empty() || other_body_words.is_empty() {
+        return titles;
+    }
+    0.7 * titles + 0.3 * jaccard(&body_words, &other_body_words)
+}
+
+fn words(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.len() > 2 && !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(number: i64, title: &str, body: &str) -> TriageIssue {
+        TriageIssue {
+            number,
+            title: title.to_string(),
+            body: body.to_string(),
+            labels: Vec::new(),
+        }
+    }
+
+    fn record(number: i64, title: &str, body: &str) -> IssueTriageRecord {
+        let issue = issue(number, title, body);
+        let triage = IssueTriager::new(IssueTriageConfig::default()).triage(&issue, &[]);
+        IssueTriageRecord::new("acme/app", &issue, &triage)
+    }
+
+    #[test]
+    fn test_suggests_labels() {
+        let triager = IssueTriager::new(IssueTriageConfig::default());
+        let triage = triager.triage(
+            &issue(1, "App crashes on startup", "Segfault right after launch"),
+            &[],
+        );
+        assert!(triage.labels.contains(&"bug".to_string()));
+        assert!(!triage.labels.contains(&"enhancement".to_string()));
+        assert_eq!(triage.labels.last().unwrap(), "size:S");
+
+        // Labels the issue already has are not suggested again
+        let mut labelled = issue(2, "Add dark mode support", "");
+        labelled.labels.push("enhancement".to_string());
+        let triage = triager.triage(&labelled, &[]);
+        assert!(!triage.labels.contains(&"enhancement".to_string()));
+    }
+
+    #[test]
+    fn test_finds_duplicates() {
+        let triager = IssueTriager::new(IssueTriageConfig::default());
+        let existing = vec![
+            record(
+                10,
+                "Login page crashes on Safari",
+                "Clicking sign in crashes the tab",
+            ),
+            record(
+                11,
+                "Add CSV export to reports",
+                "Users want to download reports",
+            ),
+        ];
+
+        let triage = triager.triage(
+            &issue(
+                12,
+                "Login page crashes in Safari",
+                "The tab crashes after clicking sign in",
+            ),
+            &existing,
+        );
+        assert_eq!(triage.duplicate_of(), Some(10));
+        assert_eq!(triage.duplicates.len(), 1);
+        assert!(triage.labels.contains(&"duplicate".to_string()));
+        assert!(triage
+            .comment()
+            .contains("#10 Login page crashes on Safari"));
+
+        let triage = triager.triage(&issue(13, "Dark mode", ""), &existing);
+        assert!(triage.duplicates.is_empty());
+    }
+
+    #[test]
+    fn test_estimates_complexity() {
+        assert_eq!(
+            estimate_complexity(&issue(1, "Fix typo in README", "")),
+            IssueComplexity::Small
+        );
+
+        let body = format!(
+            "{}\n\n- [ ] Move src/db/pool.rs\n- [ ] Update src/api/routes.rs\n- [ ] Docs\n\n```rust\nfn main() {{}}\n```",
+            "We need to rework how connections are pooled. ".repeat(20)
+        );
+        let complexity = estimate_complexity(&issue(2, "Refactor database layer", &body));
+        assert_eq!(complexity, IssueComplexity::XLarge);
+        assert_eq!(complexity.story_points(), 8);
+        assert_eq!(
+            IssueComplexity::from_str("large").unwrap(),
+            IssueComplexity::Large
+        );
+    }
+
+    #[test]
+    fn test_creates_story_for_feature_requests() {
+        let triager = IssueTriager::new(IssueTriageConfig::default().with_story_epic("epic-inbox"));
+
+        let feature = issue(42, "Add CSV export", "Allow downloading reports as CSV");
+        let triage = triager.triage(&feature, &[]);
+        let story = triager.story("acme/app", &feature, &triage).unwrap();
+        assert_eq!(story.id, "epic-inbox.issue-42");
+        assert_eq!(story.epic_id, "epic-inbox");
+        assert!(story
+            .description
+            .unwrap()
+            .ends_with("GitHub issue: acme/app#42\nEstimated complexity: small (1 story point)"));
+
+        let bug = issue(43, "Crash on startup", "");
+        let triage = triager.triage(&bug, &[]);
+        assert!(triager.story("acme/app", &bug, &triage).is_none());
+
+        let triager = IssueTriager::new(IssueTriageConfig::default());
+        let triage = triager.triage(&feature, &[]);
+        assert!(triager.story("acme/app", &feature, &triage).is_none());
+    }
+}
@@ -0,0 +1,700 @@
+//! Jira Integration
+//!
+//! Types and REST API v2 client for syncing BMAD epics and stories with Jira:
+//! - [`JiraProject`]: per-project configuration naming the Jira instance and
+//!   project, which epics sync there, and how story statuses and fields map
+//!   to Jira
+//! - [`JiraIssueLink`]: the Jira issue created for an epic or story
+//! - [`JiraComment`]: a Jira comment pulled back as story context
+//! - [`JiraClient`]: creates and transitions issues and reads comments
+//!
+//! Jira Cloud authenticates with an account email and API token, Jira
+//! Server and Data Center with a personal access token.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::epic::{Epic, Story, StoryStatus};
+use crate::{Error, Result};
+
+/// Story fields that can be mapped to Jira fields
+pub const JIRA_FIELD_SOURCES: &[&str] = &[
+    "story_id",
+    "epic_id",
+    "epic_key",
+    "acceptance_criteria",
+    "agent_id",
+];
+
+/// Label added to every issue orchestrate creates
+const JIRA_LABEL: &str = "orchestrate";
+
+/// Longest summary Jira accepts
+const MAX_SUMMARY_LEN: usize = 255;
+
+/// Comments requested per page
+const COMMENT_PAGE_SIZE: usize = 100;
+
+/// Jira configuration of a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraProject {
+    pub id: Option<i64>,
+    /// Name the configuration is managed by
+    pub name: String,
+    /// Jira base URL, e.g. `https://example.atlassian.net`
+    pub base_url: String,
+    /// Key of the Jira project issues are created in
+    pub project_key: String,
+    /// Epics whose ID starts with this prefix sync to this project; empty
+    /// matches every epic
+    pub epic_prefix: String,
+    pub epic_issue_type: String,
+    pub story_issue_type: String,
+    /// Jira status per story status, overriding the defaults
+    pub status_mapping: BTreeMap<String, String>,
+    /// Jira field ID per story field in [`JIRA_FIELD_SOURCES`]
+    pub field_mapping: BTreeMap<String, String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl JiraProject {
+    /// Create a configuration syncing every epic
+    pub fn new(
+        name: impl Into<String>,
+        base_url: impl Into<String>,
+        project_key: impl Into<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            name: name.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            project_key: project_key.into(),
+            epic_prefix: String::new(),
+            epic_issue_type: "Epic".to_string(),
+            story_issue_type: "Story".to_string(),
+            status_mapping: BTreeMap::new(),
+            field_mapping: BTreeMap::new(),
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Only sync epics whose ID starts with `prefix`
+    pub fn with_epic_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.epic_prefix = prefix.into();
+        self
+    }
+
+    /// Map a story status to a Jira status
+    pub fn with_status(mut self, status: StoryStatus, jira_status: impl Into<String>) -> Self {
+        self.status_mapping
+            .insert(status.as_str().to_string(), jira_status.into());
+        self
+    }
+
+    /// Map a story field to a Jira field ID
+    pub fn with_field(mut self, source: impl Into<String>, field_id: impl Into<String>) -> Self {
+        self.field_mapping.insert(source.into(), field_id.into());
+        self
+    }
+
+    /// Check the configuration
+    pub fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(Error::Other("Jira project name is required".to_string()));
+        }
+        if !self.base_url.starts_with("http://") && !self.base_url.starts_with("https://") {
+            return Err(Error::Other(format!(
+                "Invalid Jira URL: {} (expected http:// or https://)",
+                self.base_url
+            )));
+        }
+        if self.project_key.is_empty() {
+            return Err(Error::Other("Jira project key is required".to_string()));
+        }
+        for status in self.status_mapping.keys() {
+            StoryStatus::from_str(status)?;
+        }
+        for source in self.field_mapping.keys() {
+            if !JIRA_FIELD_SOURCES.contains(&source.as_str()) {
+                return Err(Error::Other(format!(
+                    "Unknown story field: {} (expected one of {})",
+                    source,
+                    JIRA_FIELD_SOURCES.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether stories of an epic sync to this project
+    pub fn matches_epic(&self, epic_id: &str) -> bool {
+        self.enabled && epic_id.starts_with(&self.epic_prefix)
+    }
+
+    /// Jira status a story in `status` should be in, if any
+    ///
+    /// Unless mapped, pending stories are `To Do`, in-progress stories
+    /// `In Progress`, and completed stories `Done`; blocked and skipped
+    /// stories are left where they are.
+    pub fn jira_status(&self, status: StoryStatus) -> Option<&str> {
+        if let Some(mapped) = self.status_mapping.get(status.as_str()) {
+            return Some(mapped.as_str()).filter(|mapped| !mapped.is_empty());
+        }
+        match status {
+            StoryStatus::Pending => Some("To Do"),
+            StoryStatus::InProgress => Some("In Progress"),
+            StoryStatus::Completed => Some("Done"),
+            StoryStatus::Blocked | StoryStatus::Skipped => None,
+        }
+    }
+
+    /// Fields of the issue created for an epic
+    pub fn epic_fields(&self, epic: &Epic) -> Value {
+        let mut description = format!("Synced from orchestrate epic {}.", epic.id);
+        if let Some(source) = &epic.source_file {
+            description.push_str(&format!("\n\nSource: {}", source));
+        }
+
+        json!({
+            "project": {"key": self.project_key},
+            "issuetype": {"name": self.epic_issue_type},
+            "summary": truncate(&epic.title, MAX_SUMMARY_LEN),
+            "description": description,
+            "labels": [JIRA_LABEL],
+        })
+    }
+
+    /// Fields of the issue created for a story
+    ///
+    /// The story is parented to its epic's issue unless `epic_key` is
+    /// mapped to a field, as Jira instances using an Epic Link field need.
+    pub fn story_fields(&self, story: &Story, epic_key: Option<&str>) -> Value {
+        let mut description = story.description.clone().unwrap_or_default();
+        if let Some(criteria) = story.acceptance_criteria.as_ref().map(criteria_text) {
+            if !description.is_empty() {
+                description.push_str("\n\n");
+            }
+            description.push_str(&format!("Acceptance criteria:\n{}", criteria));
+        }
+        if !description.is_empty() {
+            description.push_str("\n\n");
+        }
+        description.push_str(&format!("Synced from orchestrate story {}.", story.id));
+
+        let mut fields = Map::new();
+        fields.insert("project".into(), json!({"key": self.project_key}));
+        fields.insert("issuetype".into(), json!({"name": self.story_issue_type}));
+        fields.insert(
+            "summary".into(),
+            json!(truncate(&story.title, MAX_SUMMARY_LEN)),
+        );
+        fields.insert("description".into(), json!(description));
+        fields.insert("labels".into(), json!([JIRA_LABEL]));
+        if let Some(key) = epic_key.filter(|_| !self.field_mapping.contains_key("epic_key")) {
+            fields.insert("parent".into(), json!({"key": key}));
+        }
+
+        for (source, field_id) in &self.field_mapping {
+            let value = match source.as_str() {
+                "story_id" => Some(story.id.clone()),
+                "epic_id" => Some(story.epic_id.clone()),
+                "epic_key" => epic_key.map(str::to_string),
+                "acceptance_criteria" => story.acceptance_criteria.as_ref().map(criteria_text),
+                "agent_id" => story.agent_id.map(|id| id.to_string()),
+                _ => None,
+            };
+            if let Some(value) = value {
+                fields.insert(field_id.clone(), json!(value));
+            }
+        }
+
+        Value::Object(fields)
+    }
+}
+
+/// Acceptance criteria as text, one bullet per criterion
+fn criteria_text(criteria: &Value) -> String {
+    match criteria {
+        Value::Array(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::String(text) => format!("- {}", text),
+                other => format!("- {}", other),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars - 3).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+/// Kind of resource a Jira issue was created for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JiraResourceType {
+    Epic,
+    Story,
+}
+
+impl JiraResourceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Epic => "epic",
+            Self::Story => "story",
+        }
+    }
+}
+
+impl FromStr for JiraResourceType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "epic" => Ok(Self::Epic),
+            "story" => Ok(Self::Story),
+            _ => Err(Error::Other(format!("Unknown Jira resource type: {}", s))),
+        }
+    }
+}
+
+/// Jira issue created for an epic or story
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraIssueLink {
+    pub id: Option<i64>,
+    pub project_id: i64,
+    pub resource_type: JiraResourceType,
+    pub resource_id: String,
+    pub issue_key: String,
+    /// Story status the issue was last transitioned for
+    pub synced_status: Option<StoryStatus>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl JiraIssueLink {
+    pub fn new(
+        project_id: i64,
+        resource_type: JiraResourceType,
+        resource_id: impl Into<String>,
+        issue_key: impl Into<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            project_id,
+            resource_type,
+            resource_id: resource_id.into(),
+            issue_key: issue_key.into(),
+            synced_status: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// A comment on a Jira issue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JiraComment {
+    pub issue_key: String,
+    pub comment_id: String,
+    /// Display name of the author
+    pub author: Option<String>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Comments of a story's issue formatted as agent context, if any
+pub fn comments_context(comments: &[JiraComment]) -> Option<String> {
+    let first = comments.first()?;
+    let mut context = format!("Jira comments on {}:", first.issue_key);
+    for comment in comments {
+        context.push_str(&format!(
+            "\n- {} ({}): {}",
+            comment.author.as_deref().unwrap_or("unknown"),
+            comment.created_at.format("%Y-%m-%d %H:%M"),
+            comment.body.trim()
+        ));
+    }
+    Some(context)
+}
+
+/// Workflow transition available on an issue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JiraTransition {
+    pub id: String,
+    pub name: String,
+    /// Status the issue moves to
+    pub to_status: String,
+}
+
+/// Credentials sent with every request
+#[derive(Debug, Clone)]
+pub enum JiraAuth {
+    /// Jira Cloud account email and API token
+    Basic { email: String, api_token: String },
+    /// Jira Server or Data Center personal access token
+    Bearer(String),
+}
+
+impl JiraAuth {
+    /// Credentials from `JIRA_API_TOKEN`, with `JIRA_EMAIL` for Jira Cloud
+    pub fn from_env() -> Option<Self> {
+        let api_token = std::env::var("JIRA_API_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty())?;
+        Some(match std::env::var("JIRA_EMAIL") {
+            Ok(email) if !email.is_empty() => Self::Basic { email, api_token },
+            _ => Self::Bearer(api_token),
+        })
+    }
+}
+
+/// Jira REST API v2 client
+pub struct JiraClient {
+    http_client: reqwest::Client,
+    base_url: String,
+    auth: JiraAuth,
+}
+
+impl JiraClient {
+    /// Create a client with a 30 second request timeout
+    pub fn new(base_url: impl Into<String>, auth: JiraAuth) -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            auth,
+        }
+    }
+
+    /// Create an issue, returning its key
+    pub async fn create_issue(&self, fields: &Value) -> Result<String> {
+        #[derive(Deserialize)]
+        struct Created {
+            key: String,
+        }
+
+        let response = self
+            .request(reqwest::Method::POST, "/rest/api/2/issue")
+            .json(&json!({ "fields": fields }))
+            .send()
+            .await
+            .map_err(|e| request_failed("create issue", e))?;
+        let created: Created = parse(response, "create issue").await?;
+        Ok(created.key)
+    }
+
+    /// Transitions available on an issue
+    pub async fn transitions(&self, issue_key: &str) -> Result<Vec<JiraTransition>> {
+        #[derive(Deserialize)]
+        struct Transitions {
+            transitions: Vec<Transition>,
+        }
+        #[derive(Deserialize)]
+        struct Transition {
+            id: String,
+            name: String,
+            to: Status,
+        }
+        #[derive(Deserialize)]
+        struct Status {
+            name: String,
+        }
+
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/rest/api/2/issue/{}/transitions", issue_key),
+            )
+            .send()
+            .await
+            .map_err(|e| request_failed("list transitions", e))?;
+        let transitions: Transitions = parse(response, "list transitions").await?;
+        Ok(transitions
+            .transitions
+            .into_iter()
+            .map(|t| JiraTransition {
+                id: t.id,
+                name: t.name,
+                to_status: t.to.name,
+            })
+            .collect())
+    }
+
+    /// Move an issue to the status `jira_status`
+    ///
+    /// Matches the transition by its target status, then by its name.
+    pub async fn transition_to(&self, issue_key: &str, jira_status: &str) -> Result<()> {
+        let transitions = self.transitions(issue_key).await?;
+        let transition = transitions
+            .iter()
+            .find(|t| t.to_status.eq_ignore_ascii_case(jira_status))
+            .or_else(|| {
+                transitions
+                    .iter()
+                    .find(|t| t.name.eq_ignore_ascii_case(jira_status))
+            })
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "No transition to '{}' available on {} (available: {})",
+                    jira_status,
+                    issue_key,
+                    transitions
+                        .iter()
+                        .map(|t| t.to_status.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            })?;
+
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/rest/api/2/issue/{}/transitions", issue_key),
+            )
+            .json(&json!({ "transition": { "id": transition.id } }))
+            .send()
+            .await
+            .map_err(|e| request_failed("transition issue", e))?;
+        check(response, "transition issue").await?;
+        Ok(())
+    }
+
+    /// Every comment on an issue, oldest first
+    pub async fn comments(&self, issue_key: &str) -> Result<Vec<JiraComment>> {
+        #[derive(Deserialize)]
+        struct Page {
+            #[serde(default)]
+            total: usize,
+            comments: Vec<Comment>,
+        }
+        #[derive(Deserialize)]
+        struct Comment {
+            id: String,
+            author: Option<Author>,
+            body: String,
+            created: String,
+            updated: Option<String>,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Author {
+            display_name: Option<String>,
+        }
+
+        let mut comments = Vec::new();
+        loop {
+            let response = self
+                .request(
+                    reqwest::Method::GET,
+                    &format!(
+                        "/rest/api/2/issue/{}/comment?startAt={}&maxResults={}",
+                        issue_key,
+                        comments.len(),
+                        COMMENT_PAGE_SIZE
+                    ),
+                )
+                .send()
+                .await
+                .map_err(|e| request_failed("list comments", e))?;
+            let page: Page = parse(response, "list comments").await?;
+            let fetched = page.comments.len();
+
+            for comment in page.comments {
+                let created_at = parse_jira_time(&comment.created)?;
+                comments.push(JiraComment {
+                    issue_key: issue_key.to_string(),
+                    comment_id: comment.id,
+                    author: comment.author.and_then(|a| a.display_name),
+                    body: comment.body,
+                    created_at,
+                    updated_at: match comment.updated {
+                        Some(updated) => parse_jira_time(&updated)?,
+                        None => created_at,
+                    },
+                });
+            }
+
+            if fetched == 0 || comments.len() >= page.total {
+                return Ok(comments);
+            }
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self
+            .http_client
+            .request(method, format!("{}{}", self.base_url, path))
+            .header(reqwest::header::ACCEPT, "application/json");
+        match &self.auth {
+            JiraAuth::Basic { email, api_token } => request.basic_auth(email, Some(api_token)),
+            JiraAuth::Bearer(token) => request.bearer_auth(token),
+        }
+    }
+}
+
+/// Jira timestamps look like `2024-01-01T12:00:00.000+0000`
+fn parse_jira_time(value: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| Error::Other(format!("Invalid Jira timestamp {}: {}", value, e)))
+}
+
+fn request_failed(operation: &str, e: reqwest::Error) -> Error {
+    Error::Other(format!("Jira {} request failed: {}", operation, e))
+}
+
+/// Fail with Jira's error messages unless the response is successful
+async fn check(response: reqwest::Response, operation: &str) -> Result<reqwest::Response> {
+    #[derive(Deserialize, Default)]
+    #[serde(rename_all = "camelCase")]
+    struct ErrorBody {
+        #[serde(default)]
+        error_messages: Vec<String>,
+        #[serde(default)]
+        errors: BTreeMap<String, String>,
+    }
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body: ErrorBody = response.json().await.unwrap_or_default();
+    let mut messages = body.error_messages;
+    messages.extend(
+        body.errors
+            .into_iter()
+            .map(|(field, message)| format!("{}: {}", field, message)),
+    );
+    Err(Error::Other(format!(
+        "Jira {} failed (HTTP {}): {}",
+        operation,
+        status,
+        if messages.is_empty() {
+            "no error details".to_string()
+        } else {
+            messages.join("; ")
+        }
+    )))
+}
+
+async fn parse<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+    operation: &str,
+) -> Result<T> {
+    check(response, operation)
+        .await?
+        .json()
+        .await
+        .map_err(|e| Error::Other(format!("Invalid Jira {} response: {}", operation, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_validation() {
+        let project = JiraProject::new("web", "https://example.atlassian.net/", "WEB");
+        assert_eq!(project.base_url, "https://example.atlassian.net");
+        assert!(project.validate().is_ok());
+
+        assert!(JiraProject::new("web", "example.atlassian.net", "WEB")
+            .validate()
+            .is_err());
+        assert!(project
+            .clone()
+            .with_field("story_points", "customfield_10016")
+            .validate()
+            .is_err());
+        assert!(project
+            .clone()
+            .with_status(StoryStatus::Blocked, "Blocked")
+            .with_field("epic_key", "customfield_10014")
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_status_mapping() {
+        let project = JiraProject::new("web", "https://example.atlassian.net", "WEB")
+            .with_status(StoryStatus::Completed, "Closed")
+            .with_status(StoryStatus::Blocked, "Impeded");
+
+        assert_eq!(project.jira_status(StoryStatus::Pending), Some("To Do"));
+        assert_eq!(
+            project.jira_status(StoryStatus::InProgress),
+            Some("In Progress")
+        );
+        assert_eq!(project.jira_status(StoryStatus::Completed), Some("Closed"));
+        assert_eq!(project.jira_status(StoryStatus::Blocked), Some("Impeded"));
+        assert_eq!(project.jira_status(StoryStatus::Skipped), None);
+    }
+
+    #[test]
+    fn test_epic_matching() {
+        let project = JiraProject::new("web", "https://example.atlassian.net", "WEB")
+            .with_epic_prefix("web-");
+        assert!(project.matches_epic("web-001"));
+        assert!(!project.matches_epic("api-001"));
+        assert!(
+            JiraProject::new("all", "https://example.atlassian.net", "ALL").matches_epic("api-001")
+        );
+    }
+
+    #[test]
+    fn test_story_fields() {
+        let story = Story::new("epic-001.1", "epic-001", "Add login form")
+            .with_criteria(json!(["Form validates email", "Errors are shown"]));
+
+        let fields = JiraProject::new("web", "https://example.atlassian.net", "WEB")
+            .story_fields(&story, Some("WEB-1"));
+        assert_eq!(fields["project"]["key"], "WEB");
+        assert_eq!(fields["issuetype"]["name"], "Story");
+        assert_eq!(fields["summary"], "Add login form");
+        assert_eq!(fields["parent"]["key"], "WEB-1");
+        let description = fields["description"].as_str().unwrap();
+        assert!(description.contains("- Form validates email\n- Errors are shown"));
+        assert!(description.ends_with("Synced from orchestrate story epic-001.1."));
+
+        let fields = JiraProject::new("web", "https://example.atlassian.net", "WEB")
+            .with_field("epic_key", "customfield_10014")
+            .with_field("story_id", "customfield_10100")
+            .with_field("agent_id", "customfield_10101")
+            .story_fields(&story, Some("WEB-1"));
+        assert!(fields.get("parent").is_none());
+        assert_eq!(fields["customfield_10014"], "WEB-1");
+        assert_eq!(fields["customfield_10100"], "epic-001.1");
+        assert!(fields.get("customfield_10101").is_none());
+    }
+
+    #[test]
+    fn test_parse_jira_time() {
+        let parsed = parse_jira_time("2024-01-01T12:00:00.000+0100").unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2024-01-01T11:00:00+00:00");
+        assert!(parse_jira_time("yesterday").is_err());
+    }
+}
@@ -0,0 +1,413 @@
+//! Jira Sync Service
+//!
+//! Keeps BMAD epics and stories in sync with Jira:
+//! - A story gets a Jira issue when it is first synced, parented to an
+//!   issue created for its epic
+//! - The issue is transitioned whenever the story status differs from the
+//!   status it was last synced for
+//! - Comments on the issue are pulled back and handed to story agents as
+//!   context
+//!
+//! Stories sync to the enabled [`JiraProject`] with the longest epic prefix
+//! matching their epic; stories of other epics are left alone.
+
+use tracing::{info, warn};
+
+use crate::{
+    epic::{Story, StoryStatus},
+    error::{Error, Result},
+    jira::{JiraAuth, JiraClient, JiraIssueLink, JiraProject, JiraResourceType},
+    Database,
+};
+
+/// What syncing a story did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JiraSyncAction {
+    /// An issue was created
+    Created(String),
+    /// The issue was transitioned to a Jira status
+    Transitioned { issue_key: String, status: String },
+    /// The issue was already up to date
+    Unchanged(String),
+}
+
+impl JiraSyncAction {
+    /// Key of the story's issue
+    pub fn issue_key(&self) -> &str {
+        match self {
+            Self::Created(key) | Self::Unchanged(key) => key,
+            Self::Transitioned { issue_key, .. } => issue_key,
+        }
+    }
+}
+
+/// Totals of syncing every story
+#[derive(Debug, Clone, Default)]
+pub struct JiraSyncReport {
+    pub created: usize,
+    pub transitioned: usize,
+    pub comments: usize,
+    pub errors: Vec<String>,
+}
+
+/// Service syncing epics and stories with Jira
+pub struct JiraSyncService {
+    db: Database,
+    auth: JiraAuth,
+}
+
+impl JiraSyncService {
+    pub fn new(db: Database, auth: JiraAuth) -> Self {
+        Self { db, auth }
+    }
+
+    /// Service authenticated by `JIRA_API_TOKEN` and `JIRA_EMAIL`, if set
+    pub fn from_env(db: Database) -> Option<Self> {
+        JiraAuth::from_env().map(|auth| Self::new(db, auth))
+    }
+
+    /// Project the stories of an epic sync to
+    pub async fn project_for_epic(&self, epic_id: &str) -> Result<Option<JiraProject>> {
+        Ok(self
+            .db
+            .list_jira_projects()
+            .await?
+            .into_iter()
+            .filter(|project| project.matches_epic(epic_id))
+            .max_by_key(|project| project.epic_prefix.len()))
+    }
+
+    /// Create the Jira issue of a story or transition it to the story's
+    /// status
+    ///
+    /// Returns `None` when no project is configured for the story's epic.
+    pub async fn sync_story(&self, story: &Story) -> Result<Option<JiraSyncAction>> {
+        let link = self
+            .db
+            .get_jira_issue_link(JiraResourceType::Story, &story.id)
+            .await?;
+        let project = match &link {
+            Some(link) => self.db.get_jira_project_by_id(link.project_id).await?,
+            None => self.project_for_epic(&story.epic_id).await?,
+        };
+        let Some(project) = project.filter(|project| project.enabled) else {
+            return Ok(None);
+        };
+        let project_id = project
+            .id
+            .ok_or_else(|| Error::Other("Jira project has no ID".to_string()))?;
+        let client = self.client(&project);
+
+        let (mut link, created) = match link {
+            Some(link) => (link, false),
+            None => {
+                let epic_key = self.sync_epic(&project, &client, &story.epic_id).await?;
+                let fields = project.story_fields(story, epic_key.as_deref());
+                let key = client.create_issue(&fields).await?;
+                let mut link =
+                    JiraIssueLink::new(project_id, JiraResourceType::Story, &story.id, &key);
+                // New issues start in the status pending stories map to
+                link.synced_status = Some(StoryStatus::Pending);
+                self.db.upsert_jira_issue_link(&link).await?;
+                info!(story_id = %story.id, issue_key = %key, "Created Jira issue");
+                (link, true)
+            }
+        };
+
+        let transitioned = match project.jira_status(story.status) {
+            Some(jira_status) if link.synced_status != Some(story.status) => {
+                client.transition_to(&link.issue_key, jira_status).await?;
+                info!(
+                    story_id = %story.id,
+                    issue_key = %link.issue_key,
+                    status = %jira_status,
+                    "Transitioned Jira issue"
+                );
+                Some(jira_status.to_string())
+            }
+            _ => None,
+        };
+        if link.synced_status != Some(story.status) {
+            link.synced_status = Some(story.status);
+            link.updated_at = chrono::Utc::now();
+            self.db.upsert_jira_issue_link(&link).await?;
+        }
+
+        Ok(Some(match (created, transitioned) {
+            (true, _) => JiraSyncAction::Created(link.issue_key),
+            (false, Some(status)) => JiraSyncAction::Transitioned {
+                issue_key: link.issue_key,
+                status,
+            },
+            (false, None) => JiraSyncAction::Unchanged(link.issue_key),
+        }))
+    }
+
+    /// Pull the comments of a story's Jira issue, returning how many were
+    /// new or edited
+    pub async fn pull_comments(&self, story: &Story) -> Result<usize> {
+        let Some(link) = self
+            .db
+            .get_jira_issue_link(JiraResourceType::Story, &story.id)
+            .await?
+        else {
+            return Ok(0);
+        };
+        let Some(project) = self.db.get_jira_project_by_id(link.project_id).await? else {
+            return Ok(0);
+        };
+
+        let comments = self.client(&project).comments(&link.issue_key).await?;
+        let mut changed = 0;
+        for comment in &comments {
+            if self.db.upsert_jira_comment(&story.id, comment).await? {
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Sync every story and pull its comments, oldest story first
+    pub async fn sync_all(&self) -> Result<JiraSyncReport> {
+        let mut stories = self.db.list_stories(None).await?;
+        stories.reverse();
+        Ok(self.sync_stories(&stories).await)
+    }
+
+    /// Sync stories and pull their comments, continuing past failures
+    pub async fn sync_stories(&self, stories: &[Story]) -> JiraSyncReport {
+        let mut report = JiraSyncReport::default();
+
+        for story in stories {
+            match self.sync_story(story).await {
+                Ok(Some(JiraSyncAction::Created(_))) => report.created += 1,
+                Ok(Some(JiraSyncAction::Transitioned { .. })) => report.transitioned += 1,
+                Ok(Some(JiraSyncAction::Unchanged(_))) => {}
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!(story_id = %story.id, error = %e, "Failed to sync story with Jira");
+                    report.errors.push(format!("{}: {}", story.id, e));
+                    continue;
+                }
+            }
+            match self.pull_comments(story).await {
+                Ok(changed) => report.comments += changed,
+                Err(e) => {
+                    warn!(story_id = %story.id, error = %e, "Failed to pull Jira comments");
+                    report.errors.push(format!("{}: {}", story.id, e));
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Key of the epic's issue, creating it on first use
+    ///
+    /// Returns `None` when the epic is not in the database.
+    async fn sync_epic(
+        &self,
+        project: &JiraProject,
+        client: &JiraClient,
+        epic_id: &str,
+    ) -> Result<Option<String>> {
+        if let Some(link) = self
+            .db
+            .get_jira_issue_link(JiraResourceType::Epic, epic_id)
+            .await?
+        {
+            return Ok(Some(link.issue_key));
+        }
+        let Some(epic) = self.db.get_epic(epic_id).await? else {
+            return Ok(None);
+        };
+
+        let project_id = project
+            .id
+            .ok_or_else(|| Error::Other("Jira project has no ID".to_string()))?;
+        let key = client.create_issue(&project.epic_fields(&epic)).await?;
+        self.db
+            .upsert_jira_issue_link(&JiraIssueLink::new(
+                project_id,
+                JiraResourceType::Epic,
+                &epic.id,
+                &key,
+            ))
+            .await?;
+        info!(epic_id = %epic.id, issue_key = %key, "Created Jira epic");
+        Ok(Some(key))
+    }
+
+    fn client(&self, project: &JiraProject) -> JiraClient {
+        JiraClient::new(&project.base_url, self.auth.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::epic::Epic;
+    use crate::test_servers::serve_with;
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+
+    type Requests = Arc<Mutex<Vec<(String, Value)>>>;
+
+    /// Fake Jira answering issue creation, transitions, and comments,
+    /// recording every request line and body
+    async fn jira_api() -> (String, Requests) {
+        let requests: Requests = Arc::default();
+        let recorded = requests.clone();
+        let mut issues = 0;
+        let url = serve_with(move |request| {
+            let (head, body) = request.split_once("\r\n\r\n").unwrap();
+            let line = head.lines().next().unwrap().to_string();
+
+            let reply = if line.starts_with("POST /rest/api/2/issue ") {
+                issues += 1;
+                (201, format!(r#"{{"key":"WEB-{}"}}"#, issues))
+            } else if line.starts_with("GET") && line.contains("/transitions") {
+                (
+                    200,
+                    r#"{"transitions":[
+                        {"id":"11","name":"Start work","to":{"name":"In Progress"}},
+                        {"id":"31","name":"Finish","to":{"name":"Done"}}
+                    ]}"#
+                    .to_string(),
+                )
+            } else if line.starts_with("POST") && line.contains("/transitions") {
+                (204, String::new())
+            } else if line.contains("/comment") {
+                (
+                    200,
+                    r#"{"startAt":0,"maxResults":100,"total":1,"comments":[
+                        {"id":"10000","author":{"displayName":"Jane Doe"},
+                         "body":"Please keep the old endpoint","created":"2024-01-01T12:00:00.000+0000",
+                         "updated":"2024-01-01T12:00:00.000+0000"}
+                    ]}"#
+                    .to_string(),
+                )
+            } else {
+                (404, r#"{"errorMessages":["Not found"]}"#.to_string())
+            };
+
+            recorded
+                .lock()
+                .unwrap()
+                .push((line, serde_json::from_str(body).unwrap_or(Value::Null)));
+            reply
+        })
+        .await;
+
+        (url, requests)
+    }
+
+    async fn setup(url: &str) -> (Database, JiraSyncService) {
+        let db = Database::in_memory().await.unwrap();
+        db.insert_jira_project(&JiraProject::new("web", url, "WEB").with_epic_prefix("web-"))
+            .await
+            .unwrap();
+        db.upsert_epic(&Epic::new("web-001", "Login"))
+            .await
+            .unwrap();
+        let service = JiraSyncService::new(db.clone(), JiraAuth::Bearer("token".to_string()));
+        (db, service)
+    }
+
+    #[tokio::test]
+    async fn test_sync_creates_and_transitions_story() {
+        let (url, requests) = jira_api().await;
+        let (db, service) = setup(&url).await;
+
+        let mut story = Story::new("web-001.1", "web-001", "Add login form");
+        db.upsert_story(&story).await.unwrap();
+
+        let action = service.sync_story(&story).await.unwrap().unwrap();
+        assert_eq!(action, JiraSyncAction::Created("WEB-2".to_string()));
+        assert_eq!(
+            service.sync_story(&story).await.unwrap(),
+            Some(JiraSyncAction::Unchanged("WEB-2".to_string()))
+        );
+
+        story.start(uuid::Uuid::new_v4());
+        assert_eq!(
+            service.sync_story(&story).await.unwrap(),
+            Some(JiraSyncAction::Transitioned {
+                issue_key: "WEB-2".to_string(),
+                status: "In Progress".to_string()
+            })
+        );
+
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests[0].1["fields"]["issuetype"]["name"], "Epic");
+        assert_eq!(requests[1].1["fields"]["parent"]["key"], "WEB-1");
+        assert_eq!(requests[1].1["fields"]["summary"], "Add login form");
+        assert!(requests[2]
+            .0
+            .starts_with("GET /rest/api/2/issue/WEB-2/transitions"));
+        assert_eq!(requests[3].1["transition"]["id"], "11");
+        assert_eq!(requests.len(), 4);
+
+        let link = db
+            .get_jira_issue_link(JiraResourceType::Story, "web-001.1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(link.synced_status, Some(StoryStatus::InProgress));
+    }
+
+    #[tokio::test]
+    async fn test_sync_all_pulls_comments() {
+        let (url, _requests) = jira_api().await;
+        let (db, service) = setup(&url).await;
+
+        let mut done = Story::new("web-001.1", "web-001", "Add login form");
+        done.complete();
+        db.upsert_story(&done).await.unwrap();
+        db.upsert_epic(&Epic::new("api-001", "Public API"))
+            .await
+            .unwrap();
+        db.upsert_story(&Story::new("api-001.1", "api-001", "Add endpoint"))
+            .await
+            .unwrap();
+
+        let report = service.sync_all().await.unwrap();
+        assert_eq!(report.created, 1);
+        assert_eq!(report.comments, 1);
+        assert!(report.errors.is_empty());
+
+        // Stories of epics without a project are not synced
+        assert!(db
+            .get_jira_issue_link(JiraResourceType::Story, "api-001.1")
+            .await
+            .unwrap()
+            .is_none());
+
+        let comments = db.list_jira_comments("web-001.1").await.unwrap();
+        assert_eq!(comments[0].author.as_deref(), Some("Jane Doe"));
+        assert_eq!(comments[0].body, "Please keep the old endpoint");
+
+        let report = service.sync_all().await.unwrap();
+        assert_eq!(
+            (report.created, report.transitioned, report.comments),
+            (0, 0, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_transition_is_reported() {
+        let (url, _requests) = jira_api().await;
+        let db = Database::in_memory().await.unwrap();
+        db.insert_jira_project(
+            &JiraProject::new("web", &url, "WEB").with_status(StoryStatus::Blocked, "Impeded"),
+        )
+        .await
+        .unwrap();
+        let service = JiraSyncService::new(db, JiraAuth::Bearer("token".to_string()));
+
+        let mut story = Story::new("web-001.1", "web-001", "Add login form");
+        story.status = StoryStatus::Blocked;
+        let err = service.sync_story(&story).await.unwrap_err();
+        assert!(err.to_string().contains("No transition to 'Impeded'"));
+    }
+}
@@ -6,16 +6,28 @@
 //! - Session management
 //! - Message handling
 //! - Agent network with state/skill dependencies
+//! - OpenTelemetry tracing export
+//! - Sentry error reporting
+//! - Object storage for reports and artifacts
+//! - CSV and Parquet exports of token usage, costs, audit logs, and agent history
+//! - Event bus publishing to NATS or Kafka
+//! - Telegram bot notifications with inline approvals
+//! - Google Chat webhook notifications with cards
 
 pub mod agent;
 pub mod agent_continuation;
+pub mod agent_event;
+pub mod agent_type_definition;
 pub mod autonomous_session;
 pub mod context_summary;
 pub mod decision_engine;
+pub mod decision_policy;
 pub mod approval;
 pub mod approval_service;
 pub mod condition_evaluator;
+pub mod condition_expression;
 pub mod cron;
+pub mod data_export;
 pub mod database;
 #[cfg(test)]
 mod database_approval_tests;
@@ -29,16 +41,48 @@ mod database_autonomous_session_tests;
 mod database_continuation_tests;
 #[cfg(test)]
 mod database_incident_tests;
+#[cfg(test)]
+mod database_issue_triage_tests;
+#[cfg(test)]
+mod database_jira_tests;
+#[cfg(test)]
+mod database_linear_tests;
+#[cfg(test)]
+mod test_servers;
 pub mod documentation;
 pub mod epic;
+pub mod event_bus;
 pub mod requirements;
 pub mod multi_repo;
+pub mod branch_policy;
 pub mod ci_integration;
+pub mod comment_command;
 pub mod incident;
+pub mod incident_escalation;
+pub mod issue_triage;
+pub mod jira;
+pub mod jira_sync;
+pub mod linear;
+pub mod linear_sync;
+pub mod object_storage;
+pub mod oncall;
+pub mod opsgenie;
+pub mod pagerduty;
+pub mod pagination;
 pub mod test_generation;
 pub mod deployment;
 pub mod monitoring;
 pub mod slack;
+pub mod slack_api;
+pub mod slack_approval_service;
+pub mod slack_interactions;
+pub mod slack_service;
+pub mod slack_user_service;
+pub mod email;
+pub mod email_service;
+pub mod google_chat;
+pub mod smtp;
+pub mod telegram;
 pub mod security;
 pub mod security_gate;
 pub mod security_report;
@@ -57,20 +101,32 @@ pub mod pattern_export;
 pub mod prompt_optimization;
 pub mod pipeline;
 pub mod pipeline_executor;
+pub mod pipeline_graph;
+pub mod pipeline_notifications;
 pub mod pipeline_parser;
+pub mod pipeline_plan;
 pub mod pipeline_template;
 pub mod pr;
+pub mod saga;
 pub mod schedule;
+pub mod schedule_blackout;
 pub mod schedule_template;
+pub mod secrets;
+pub mod sentry;
 pub mod session;
 pub mod shell_state;
+pub mod telemetry;
 pub mod webhook;
 pub mod webhook_config;
+pub mod webhook_source;
+pub mod custom_webhook;
+pub mod outbound_webhook;
 pub mod worktree;
 pub mod test_stubs;
 pub mod stuck_detection;
 pub mod recovery;
 pub mod work_evaluation;
+pub mod work_checks;
 pub mod code_review;
 pub mod pr_workflow;
 pub mod epic_discovery;
@@ -83,11 +139,41 @@ mod database_edge_case_tests;
 mod database_recovery_tests;
 #[cfg(test)]
 mod database_work_evaluation_tests;
+#[cfg(test)]
+mod database_agent_event_tests;
+#[cfg(test)]
+mod database_agent_label_tests;
+#[cfg(test)]
+mod database_agent_trace_tests;
+#[cfg(test)]
+mod database_agent_type_tests;
+#[cfg(test)]
+mod database_artifact_tests;
+#[cfg(test)]
+mod database_export_tests;
+#[cfg(test)]
+mod database_saga_tests;
+#[cfg(test)]
+mod database_cost_attribution_tests;
+#[cfg(test)]
+mod database_context_summary_tests;
+#[cfg(test)]
+mod database_prompt_version_tests;
+#[cfg(test)]
+mod database_secret_tests;
 
-pub use agent::{Agent, AgentContext, AgentState, AgentType};
+pub use agent::{
+    validate_labels, Agent, AgentContext, AgentState, AgentType, LabelRequirement, LabelSelector,
+};
+pub use agent_event::{AgentEvent, AgentEventType};
+pub use agent_type_definition::{AgentTypeDefinition, AgentTypesFile};
 pub use database::{
     AgentStats, DailyTokenUsage, Database, EffectivenessAnalysisRow, EffectivenessSummary,
-    TokenStats,
+    TokenStats, WebhookSourceStats,
+};
+pub use saga::{
+    CompensationActionType, CompensationDefinition, CompensationStatus, Saga, SagaCompensation,
+    SagaStatus, SagaWorkflowType,
 };
 pub use epic::{BmadPhase, Epic, EpicStatus, Story, StoryStatus};
 pub use error::{Error, Result};
@@ -112,52 +198,88 @@ pub use feedback::{Feedback, FeedbackRating, FeedbackSource, FeedbackStats};
 // Re-export experiment types
 pub use experiment::{
     Experiment, ExperimentAssignment, ExperimentMetric, ExperimentObservation, ExperimentResults,
-    ExperimentStatus, ExperimentType, ExperimentVariant, VariantResults,
+    ExperimentStatus, ExperimentType, ExperimentVariant, VariantDailyResults, VariantResults,
 };
 
 // Re-export network types
 pub use network::{
     AgentCapability, AgentHandle, AgentId, DependencyCondition, DependencyGraph, DependencySet,
     NetworkCoordinator, NetworkEvent, NetworkValidator, RecoveryAction, Skill, SkillDefinition,
-    SkillRegistry, StateGraph, StateMachine, StatePropagation, StateRequirement, StateTransition,
-    StepOutput, StepOutputType, ValidationError, ValidationResult, MAX_STEP_OUTPUT_DATA_SIZE,
+    SkillRegistry, SkillRegistryError, StateGraph, StateMachine, StateMachineConfig,
+    StatePropagation, StateRequirement, StateTransition, StepOutput, StepOutputType,
+    ValidationError, ValidationResult, MAX_STEP_OUTPUT_DATA_SIZE,
 };
 
 // Re-export shell state types
 pub use shell_state::{QueueEntry, ShellState, ShepherdLock};
 
 // Re-export schedule types
-pub use schedule::{Schedule, ScheduleRun, ScheduleRunStatus};
+pub use schedule::{
+    parse_run_at, Schedule, ScheduleOverlapPolicy, ScheduleRun, ScheduleRunStatus,
+    ScheduleSkipReason, ScheduleSpend, ScheduleStats, DEFAULT_TIMEZONE,
+};
+
+// Re-export schedule blackout types
+pub use schedule_blackout::ScheduleBlackout;
 
 // Re-export schedule template types
 pub use schedule_template::ScheduleTemplate;
 
+// Re-export secrets types
+pub use secrets::{scrub_secrets, SecretVault, SecretsManager, REDACTED};
+
+// Re-export pagination types
+pub use pagination::{KeyValue, PageQuery, RowPage, SortKey};
+
 // Re-export cron types
-pub use cron::CronSchedule;
+pub use cron::{parse_timezone, CronSchedule};
+pub use data_export::{
+    ColumnType, DataExportFormat, ExportDataset, ExportJob, ExportJobStatus, ExportRequest,
+    ExportTable, ExportValue,
+};
 
 // Re-export webhook types
-pub use webhook::{WebhookEvent, WebhookEventStatus};
+pub use custom_webhook::{resolve_path, CustomWebhook, CustomWebhookDefinition};
+pub use outbound_webhook::{
+    sign_payload, OutboundDeliveryStatus, OutboundEvent, OutboundEventType, OutboundWebhook,
+    OutboundWebhookDelivery, OutboundWebhookDispatcher,
+};
+pub use webhook::{WebhookEvent, WebhookEventStatus, WebhookEventSummary};
 pub use webhook_config::{EventConfig, EventFilter, WebhookConfig};
+pub use webhook_source::{verify_signature, SignatureScheme, WebhookSource, WebhookSourceSecret};
 
 // Re-export pipeline types
 pub use pipeline::{
     Pipeline, PipelineRun, PipelineRunStatus, PipelineStage, PipelineStageStatus, RollbackEvent,
-    RollbackStatus, RollbackTriggerType,
+    RollbackStatus, RollbackTriggerType, RunAdmission,
+};
+pub use pipeline_executor::{
+    ExecutionContext, PipelineExecutor, StageRunner, DEFAULT_MAX_PARALLEL_STAGES,
+};
+pub use pipeline_graph::{GraphEdge, GraphEdgeKind, GraphNode, GraphNodeKind, PipelineGraph};
+pub use pipeline_notifications::{
+    HttpNotificationSender, NotificationSender, NotificationTarget, PipelineNotification,
 };
-pub use pipeline_executor::{ExecutionContext, PipelineExecutor};
 pub use pipeline_parser::{
-    FailureAction, PipelineDefinition, StageCondition, StageDefinition, TriggerDefinition,
+    ApprovalTimeoutAction, ApprovalTimeoutPolicy, ConcurrencyAction, FailureAction,
+    PipelineConcurrency, PipelineDefinition, PipelineNotificationEvent, PipelineNotifications,
+    PipelineValidationIssue, RollbackHook, StageCondition, StageDefinition, StageMatrix,
+    StageRetryPolicy, TriggerDefinition, MATRIX_REPO_PARAMETER, PIPELINE_SCHEMA, SCHEDULE_EVENT,
+};
+pub use pipeline_plan::{
+    AgentTokenEstimate, PipelinePlan, PlannedAgent, PlannedStage, PlannedStageAction,
 };
 
 // Re-export condition evaluator types
 pub use condition_evaluator::{ConditionContext, ConditionEvaluator, EvaluationResult, SkipReason};
+pub use condition_expression::ConditionExpression;
 
 // Re-export approval types
 pub use approval::{ApprovalDecision, ApprovalRequest, ApprovalStatus};
 pub use approval_service::ApprovalService;
 
 // Re-export pipeline template types
-pub use pipeline_template::PipelineTemplate;
+pub use pipeline_template::{PipelineTemplate, TemplateLibrary};
 
 // Re-export model selection types
 pub use model_selection::{
@@ -208,11 +330,16 @@ pub use multi_repo::{
     RepoBranchStatus, RepoConfig, RepoDependencyGraph, RepoProvider, RepoRelease, RepoStatus,
     Repository,
 };
+pub use branch_policy::{
+    BranchPolicyResult, BranchPolicySpec, BranchProtection, BranchRule, PolicyDrift,
+    ProtectionSetting,
+};
 
 // Re-export CI integration types
 pub use ci_integration::{
-    CiArtifact, CiAuthType, CiConclusion, CiConfig, CiFailureAnalysis, CiJob, CiProvider, CiRun,
-    CiRunStatus, CiStep, CiTriggerRequest, FailedJob, FailedTest,
+    check_status, ci_status_cache, CiArtifact, CiAuthType, CiConclusion, CiConfig,
+    CiFailureAnalysis, CiJob, CiProvider, CiRun, CiRunStatus, CiStatusCache, CiStatusTransition,
+    CiStep, CiTriggerRequest, FailedJob, FailedTest,
 };
 
 // Re-export incident types
@@ -223,6 +350,34 @@ pub use incident::{
     PlaybookExecution, PlaybookExecutionStatus, PlaybookTrigger, PostMortem, RelatedEvent,
     RootCauseAnalysis, TimelineEvent, TimelineEventType,
 };
+pub use comment_command::{CommentCommand, RepoRole};
+pub use incident_escalation::IncidentEscalationService;
+pub use issue_triage::{
+    DuplicateCandidate, IssueComplexity, IssueTriage, IssueTriageConfig, IssueTriageRecord,
+    IssueTriager, TriageIssue,
+};
+pub use jira::{
+    JiraAuth, JiraClient, JiraComment, JiraIssueLink, JiraProject, JiraResourceType,
+    JiraTransition,
+};
+pub use jira_sync::{JiraSyncAction, JiraSyncReport, JiraSyncService};
+pub use linear::{
+    LinearClient, LinearIssue, LinearIssueLink, LinearTeam, LinearWebhook, LinearWorkflowState,
+};
+pub use linear_sync::{
+    LinearSyncAction, LinearSyncReport, LinearSyncService, LinearWebhookOutcome,
+};
+pub use event_bus::{BusEvent, EventBus, EventBusBackend, EventBusConfig, EventPublisher};
+pub use object_storage::{
+    Artifact, ArtifactKind, ArtifactStore, LocalObjectStore, ObjectStore, S3Config, S3ObjectStore,
+};
+pub use oncall::{OncallPage, OncallPageStatus, OncallProvider, PagedResource};
+pub use opsgenie::{OpsgenieAlert, OpsgenieClient, OpsgeniePriority, OpsgenieResponder};
+pub use pagerduty::{
+    PagerDutyAction, PagerDutyClient, PagerDutyEvent, PagerDutySeverity, PagerDutyWebhook,
+    PagerDutyWebhookEvent,
+};
+pub use sentry::{SentryClient, SentryConfig, SentryDsn, SentryEvent, SentryLevel};
 
 // Re-export test generation types
 pub use test_generation::{
@@ -253,7 +408,9 @@ pub use monitoring::{
 pub use audit::{AuditQuery, AuditStats, ExportFormat, RetentionPolicy};
 
 // Re-export cost analytics types
-pub use cost_analytics::BudgetPeriod;
+pub use cost_analytics::{
+    BudgetBurnDown, BudgetPeriod, BurnDownPoint, CostAnalytics, CostBudget, EntityCostSummary,
+};
 
 // Re-export Slack types
 pub use slack::{
@@ -264,6 +421,25 @@ pub use slack::{
     SlackText, SlashCommand, SlashCommandResponse, TextType, UserMapping,
     ApprovalDecision as SlackApprovalDecision,
 };
+pub use slack_api::{SlackApiClient, SlackAuthInfo, SlackUser};
+pub use slack_approval_service::SlackApprovalService;
+pub use slack_interactions::{ApprovalButtonHandler, ApprovalResponse, PrThreadManager};
+pub use slack_service::{AgentLifecycleEvent, PrNotificationEvent, RateLimitConfig, SlackService};
+pub use slack_user_service::{CodeOwner, SlackUserService};
+
+// Re-export email types
+pub use email::{EmailDigest, EmailMessage, SmtpConfig, SmtpTls};
+pub use email_service::EmailNotificationService;
+pub use smtp::SmtpClient;
+
+// Re-export Google Chat types
+pub use google_chat::{GoogleChatClient, GoogleChatMessage};
+
+// Re-export Telegram types
+pub use telegram::{
+    CallbackQuery, InlineKeyboardButton, TelegramClient, TelegramConfig, TelegramDecision,
+    TelegramMessage, TelegramService, TelegramUpdate,
+};
 
 // Re-export security types
 pub use security::{
@@ -283,15 +459,15 @@ pub use security_report::ReportFormat;
 // Re-export test stub types (placeholders for incomplete features)
 pub use test_stubs::{
     CoverageReport, FileCoverage, GeneratedTest, IssueSeverity, ModuleCoverage,
-    SlackService, SlackUserService,
     TestFramework, TestQualityIssue, TestQualityIssueType, TestQualityReport,
     TestResult, TestResultStatus, TestRun, TestRunStatus,
 };
 
 // Re-export autonomous session types (Epic 016)
 pub use autonomous_session::{
-    AutonomousSession, AutonomousSessionState, CompletedItem, SessionConfig, SessionMetrics,
-    SessionStateHistory, WorkItem, WorkItemType,
+    AutonomousSession, AutonomousSessionState, CompletedItem, PlanDivergence, PlanStep,
+    PlanStepStatus, SessionConfig, SessionMetrics, SessionPlan, SessionStateHistory, WorkItem,
+    WorkItemType,
 };
 
 // Re-export decision engine types (Epic 016)
@@ -299,18 +475,23 @@ pub use decision_engine::{
     AgentStatus, Decision, DecisionEngine, DecisionEngineConfig, EscalationSeverity,
     EvaluationResult as DecisionEvaluationResult, ReviewType, StatusSignal, WaitType,
 };
+pub use decision_policy::{
+    DecisionPolicy, HourRange, PolicyAction, PolicyCondition, PolicyContext, PolicyReloader,
+    PolicyRule, SharedDecisionPolicy,
+};
 
 // Re-export agent continuation types (Epic 016)
 pub use agent_continuation::{
-    AgentContinuation, ContinuationBuilder, ContinuationReason, ContinuationResult,
-    ContinuationStatus,
+    AgentContinuation, CompressedContinuationContext, ContinuationBuilder,
+    ContinuationCompressor, ContinuationReason, ContinuationResult, ContinuationStatus,
+    PriorContextEntry, PRIOR_CONTEXT_KEY,
 };
 
 // Re-export context summary types (Epic 016)
 pub use context_summary::{
-    Blocker, BlockerSeverity, BlockerType, ContextSummary, DecisionCategory, FileChange,
-    FileChangeType, KeyDecision, OutputSummarizer, TestAdded, TestType as SummaryTestType,
-    WorkStatus,
+    cosine_similarity, Blocker, BlockerSeverity, BlockerType, ContextSummary, DecisionCategory,
+    FileChange, FileChangeType, HashingEmbedder, KeyDecision, OutputSummarizer, RelatedSummary,
+    SummaryEmbedder, TestAdded, TestType as SummaryTestType, WorkStatus,
 };
 
 // Re-export stuck detection types (Epic 016)
@@ -322,7 +503,8 @@ pub use stuck_detection::{
 // Re-export recovery types (Epic 016)
 pub use recovery::{
     FixerAgentType, FixerRequest, PlannedRecoveryAction, RecoveryActionType, RecoveryAttempt,
-    RecoveryConfig, RecoveryOutcome, RecoverySelector,
+    RecoveryConfig, RecoveryOutcome, RecoveryScript, RecoveryScriptContext, RecoveryScriptOutput,
+    RecoverySelector,
 };
 
 // Re-export work evaluation types (Epic 016 - Story 8)
@@ -331,18 +513,24 @@ pub use work_evaluation::{
     ReviewIssue, ReviewIssueSeverity, ReviewResult, ReviewVerdict, StoryEvaluationRecord,
     WorkCompletionStatus, WorkEvaluationResult, WorkEvaluator, WorkEvaluatorConfig,
 };
+pub use work_checks::{
+    AcceptanceCriteriaCheck, CiGreenCheck, CoverageDeltaCheck, CriteriaJudge, LintCleanCheck,
+    LlmAcceptanceCriteriaCheck, PrMergeableCheck, ReviewApprovedCheck, WorkCheck, WorkCheckInput,
+    WorkCheckOutcome,
+};
 
 // Re-export code review types (Epic 016 - Story 9)
 pub use code_review::{
     CodeReviewConfig, CodeReviewCoordinator, ReviewEscalationLevel, ReviewIteration,
-    ReviewRequest, ReviewResponse, ReviewerType,
+    ReviewRequest, ReviewResponse, ReviewerType, ThreadReply,
 };
 
 // Re-export PR workflow types (Epic 016 - Story 10)
 pub use pr_workflow::{
-    CiAggregateStatus, ConflictInfo, ConflictResolutionStrategy, MergeMethod, PrDescription,
-    PrStateTransition, PrWorkflowAction, PrWorkflowConfig, PrWorkflowContext, PrWorkflowManager,
-    PrWorkflowRecord, PrWorkflowState,
+    ChangedFile, CiAggregateStatus, ConflictInfo, ConflictResolutionStrategy, DequeueReason,
+    MergeMethod, MergeQueueStatus, OpenedPr, PathRule, PrApprovals, PrDescription,
+    PrLabelConfig, PrLabels, PrProvider, PrRisk, PrSize, PrStateTransition, PrWorkflowAction,
+    PrWorkflowConfig, PrWorkflowContext, PrWorkflowManager, PrWorkflowRecord, PrWorkflowState,
 };
 
 // Re-export epic discovery types (Epic 016 - Story 11)
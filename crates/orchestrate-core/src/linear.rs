@@ -0,0 +1,654 @@
+//! Linear Integration
+//!
+//! Types and GraphQL API client for syncing BMAD stories with Linear:
+//! - [`LinearTeam`]: per-team configuration naming the Linear team, which
+//!   epics sync there, how story statuses map to workflow states, and which
+//!   label spawns an agent
+//! - [`LinearIssueLink`]: the Linear issue linked to a story, or to the agent
+//!   it spawned
+//! - [`LinearWebhook`]: a webhook delivery, verified with
+//!   [`verify_webhook_signature`]
+//! - [`LinearClient`]: creates issues and moves them between workflow states
+//!
+//! The client authenticates with a personal API key or an OAuth access token.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::agent::AgentType;
+use crate::epic::{Story, StoryStatus};
+use crate::{Error, Result};
+
+/// GraphQL API endpoint
+pub const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+
+/// Header carrying the webhook signature
+pub const LINEAR_SIGNATURE_HEADER: &str = "Linear-Signature";
+
+/// Label that spawns an agent unless configured otherwise
+pub const DEFAULT_TRIGGER_LABEL: &str = "orchestrate";
+
+/// Linear configuration of a team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearTeam {
+    pub id: Option<i64>,
+    /// Name the configuration is managed by
+    pub name: String,
+    /// UUID of the Linear team issues are created in
+    pub linear_team_id: String,
+    /// Epics whose ID starts with this prefix sync to this team; empty
+    /// matches every epic
+    pub epic_prefix: String,
+    /// Workflow state name per story status, overriding the defaults
+    pub status_mapping: BTreeMap<String, String>,
+    /// Issues carrying this label spawn an agent
+    pub trigger_label: String,
+    /// Type of the agents spawned from issues
+    pub agent_type: AgentType,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl LinearTeam {
+    /// Create a configuration syncing every epic
+    pub fn new(name: impl Into<String>, linear_team_id: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            name: name.into(),
+            linear_team_id: linear_team_id.into(),
+            epic_prefix: String::new(),
+            status_mapping: BTreeMap::new(),
+            trigger_label: DEFAULT_TRIGGER_LABEL.to_string(),
+            agent_type: AgentType::StoryDeveloper,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Only sync epics whose ID starts with `prefix`
+    pub fn with_epic_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.epic_prefix = prefix.into();
+        self
+    }
+
+    /// Map a story status to a workflow state
+    pub fn with_status(mut self, status: StoryStatus, state_name: impl Into<String>) -> Self {
+        self.status_mapping
+            .insert(status.as_str().to_string(), state_name.into());
+        self
+    }
+
+    /// Spawn agents from issues carrying `label`
+    pub fn with_trigger_label(mut self, label: impl Into<String>) -> Self {
+        self.trigger_label = label.into();
+        self
+    }
+
+    /// Spawn agents of `agent_type` from issues
+    pub fn with_agent_type(mut self, agent_type: AgentType) -> Self {
+        self.agent_type = agent_type;
+        self
+    }
+
+    /// Check the configuration
+    pub fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(Error::Other("Linear team name is required".to_string()));
+        }
+        if self.linear_team_id.is_empty() {
+            return Err(Error::Other("Linear team ID is required".to_string()));
+        }
+        for status in self.status_mapping.keys() {
+            StoryStatus::from_str(status)?;
+        }
+        Ok(())
+    }
+
+    /// Whether stories of an epic sync to this team
+    pub fn matches_epic(&self, epic_id: &str) -> bool {
+        self.enabled && epic_id.starts_with(&self.epic_prefix)
+    }
+
+    /// Workflow state a story in `status` should be in, if any
+    ///
+    /// Unless mapped, pending stories are `Todo`, in-progress stories
+    /// `In Progress`, and completed stories `Done`; blocked and skipped
+    /// stories are left where they are.
+    pub fn linear_state(&self, status: StoryStatus) -> Option<&str> {
+        if let Some(mapped) = self.status_mapping.get(status.as_str()) {
+            return Some(mapped.as_str()).filter(|mapped| !mapped.is_empty());
+        }
+        match status {
+            StoryStatus::Pending => Some("Todo"),
+            StoryStatus::InProgress => Some("In Progress"),
+            StoryStatus::Completed => Some("Done"),
+            StoryStatus::Blocked | StoryStatus::Skipped => None,
+        }
+    }
+
+    /// Story status of an issue in a workflow state, if any
+    ///
+    /// Mapped state names win; otherwise the state's type decides, so custom
+    /// states sync too. Triage issues have no story status.
+    pub fn story_status(&self, state: &LinearWorkflowState) -> Option<StoryStatus> {
+        let mapped = self
+            .status_mapping
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(&state.name))
+            .and_then(|(status, _)| StoryStatus::from_str(status).ok());
+        mapped.or(match state.state_type.as_str() {
+            "backlog" | "unstarted" => Some(StoryStatus::Pending),
+            "started" => Some(StoryStatus::InProgress),
+            "completed" => Some(StoryStatus::Completed),
+            "canceled" => Some(StoryStatus::Skipped),
+            _ => None,
+        })
+    }
+
+    /// Whether an issue should spawn an agent
+    pub fn triggers_on(&self, issue: &LinearIssue) -> bool {
+        self.enabled
+            && issue
+                .labels
+                .iter()
+                .any(|label| label.name.eq_ignore_ascii_case(&self.trigger_label))
+    }
+}
+
+/// Description of the issue created for a story
+pub fn story_description(story: &Story) -> String {
+    let mut description = story.description.clone().unwrap_or_default();
+    if let Some(criteria) = &story.acceptance_criteria {
+        if !description.is_empty() {
+            description.push_str("\n\n");
+        }
+        description.push_str("**Acceptance criteria**\n");
+        let items = match criteria {
+            Value::Array(items) => items
+                .iter()
+                .map(|item| match item {
+                    Value::String(text) => format!("- [ ] {}", text),
+                    other => format!("- [ ] {}", other),
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        description.push_str(&items);
+    }
+    if !description.is_empty() {
+        description.push_str("\n\n");
+    }
+    description.push_str(&format!(
+        "Synced from orchestrate story `{}` (epic `{}`).",
+        story.id, story.epic_id
+    ));
+    description
+}
+
+/// Linear issue linked to a story or to the agent it spawned
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearIssueLink {
+    pub id: Option<i64>,
+    pub team_id: i64,
+    /// UUID of the issue
+    pub issue_id: String,
+    /// Human-readable identifier, e.g. `ENG-123`
+    pub identifier: String,
+    pub url: Option<String>,
+    pub story_id: Option<String>,
+    pub agent_id: Option<uuid::Uuid>,
+    /// Story status the issue was last moved for, or moved the story to
+    pub synced_status: Option<StoryStatus>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl LinearIssueLink {
+    pub fn new(team_id: i64, issue: &LinearIssue) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            team_id,
+            issue_id: issue.id.clone(),
+            identifier: issue.identifier.clone(),
+            url: issue.url.clone(),
+            story_id: None,
+            agent_id: None,
+            synced_status: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Link the issue to a story
+    pub fn with_story(mut self, story_id: impl Into<String>) -> Self {
+        self.story_id = Some(story_id.into());
+        self
+    }
+
+    /// Link the issue to the agent it spawned
+    pub fn with_agent(mut self, agent_id: uuid::Uuid) -> Self {
+        self.agent_id = Some(agent_id);
+        self
+    }
+}
+
+/// Workflow state of a team
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinearWorkflowState {
+    pub id: String,
+    pub name: String,
+    /// One of `triage`, `backlog`, `unstarted`, `started`, `completed`, or
+    /// `canceled`
+    #[serde(rename = "type")]
+    pub state_type: String,
+}
+
+/// Label of an issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinearLabel {
+    pub name: String,
+}
+
+/// A Linear issue, as returned by the API or sent in webhooks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinearIssue {
+    pub id: String,
+    pub identifier: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub team_id: Option<String>,
+    #[serde(default)]
+    pub state: Option<LinearWorkflowState>,
+    #[serde(default)]
+    pub labels: Vec<LinearLabel>,
+}
+
+impl LinearIssue {
+    /// Task of the agent spawned from the issue
+    pub fn agent_task(&self) -> String {
+        match self.description.as_deref().map(str::trim) {
+            Some(description) if !description.is_empty() => {
+                format!("{}: {}\n\n{}", self.identifier, self.title, description)
+            }
+            _ => format!("{}: {}", self.identifier, self.title),
+        }
+    }
+}
+
+/// A Linear webhook delivery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinearWebhook {
+    /// `create`, `update`, or `remove`
+    pub action: String,
+    /// Type of the entity, e.g. `Issue` or `Comment`
+    #[serde(rename = "type")]
+    pub entity_type: String,
+    pub data: Value,
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Previous values of the fields an update changed
+    #[serde(default)]
+    pub updated_from: Option<Value>,
+}
+
+impl LinearWebhook {
+    /// The issue the delivery is about, if it is about one
+    pub fn issue(&self) -> Option<LinearIssue> {
+        if self.entity_type != "Issue" {
+            return None;
+        }
+        let mut issue: LinearIssue = serde_json::from_value(self.data.clone()).ok()?;
+        if issue.url.is_none() {
+            issue.url = self.url.clone();
+        }
+        Some(issue)
+    }
+
+    /// Whether an update moved the issue to another workflow state
+    pub fn state_changed(&self) -> bool {
+        self.action == "update"
+            && self
+                .updated_from
+                .as_ref()
+                .is_some_and(|from| from.get("stateId").is_some())
+    }
+}
+
+/// Verify a `Linear-Signature` header: the hex HMAC-SHA256 of the body keyed
+/// with the webhook's signing secret
+pub fn verify_webhook_signature(secret: &str, body: &[u8], header: &str) -> bool {
+    let Ok(expected) = hex::decode(header.trim()) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// Linear GraphQL API client
+#[derive(Debug, Clone)]
+pub struct LinearClient {
+    http_client: reqwest::Client,
+    api_key: String,
+    api_url: String,
+}
+
+impl LinearClient {
+    /// Create a client with a 30 second request timeout
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            api_key: api_key.into(),
+            api_url: LINEAR_API_URL.to_string(),
+        }
+    }
+
+    /// Client authenticated by `LINEAR_API_KEY`, if set
+    pub fn from_env() -> Option<Self> {
+        std::env::var("LINEAR_API_KEY")
+            .ok()
+            .filter(|key| !key.is_empty())
+            .map(Self::new)
+    }
+
+    /// Send requests to a different GraphQL endpoint
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
+    }
+
+    /// Create an issue, optionally in a workflow state
+    pub async fn create_issue(
+        &self,
+        team_id: &str,
+        title: &str,
+        description: &str,
+        state_id: Option<&str>,
+    ) -> Result<LinearIssue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            issue_create: Payload,
+        }
+        #[derive(Deserialize)]
+        struct Payload {
+            success: bool,
+            issue: Option<LinearIssue>,
+        }
+
+        let mut input = json!({
+            "teamId": team_id,
+            "title": title,
+            "description": description,
+        });
+        if let Some(state_id) = state_id {
+            input["stateId"] = json!(state_id);
+        }
+
+        let data: Data = self
+            .query(
+                "create issue",
+                "mutation IssueCreate($input: IssueCreateInput!) { \
+                 issueCreate(input: $input) { success issue { id identifier title url } } }",
+                json!({ "input": input }),
+            )
+            .await?;
+        match data.issue_create {
+            Payload {
+                success: true,
+                issue: Some(issue),
+            } => Ok(issue),
+            _ => Err(Error::Other("Linear did not create the issue".to_string())),
+        }
+    }
+
+    /// Workflow states of a team
+    pub async fn workflow_states(&self, team_id: &str) -> Result<Vec<LinearWorkflowState>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            workflow_states: Nodes,
+        }
+        #[derive(Deserialize)]
+        struct Nodes {
+            nodes: Vec<LinearWorkflowState>,
+        }
+
+        let data: Data = self
+            .query(
+                "list workflow states",
+                "query WorkflowStates($teamId: ID!) { \
+                 workflowStates(filter: { team: { id: { eq: $teamId } } }) { nodes { id name type } } }",
+                json!({ "teamId": team_id }),
+            )
+            .await?;
+        Ok(data.workflow_states.nodes)
+    }
+
+    /// Move an issue to a workflow state
+    pub async fn update_issue_state(&self, issue_id: &str, state_id: &str) -> Result<()> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            issue_update: Payload,
+        }
+        #[derive(Deserialize)]
+        struct Payload {
+            success: bool,
+        }
+
+        let data: Data = self
+            .query(
+                "update issue",
+                "mutation IssueUpdate($id: String!, $stateId: String!) { \
+                 issueUpdate(id: $id, input: { stateId: $stateId }) { success } }",
+                json!({ "id": issue_id, "stateId": state_id }),
+            )
+            .await?;
+        if !data.issue_update.success {
+            return Err(Error::Other(format!(
+                "Linear did not update issue {}",
+                issue_id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn query<T: serde::de::DeserializeOwned>(
+        &self,
+        operation: &str,
+        query: &str,
+        variables: Value,
+    ) -> Result<T> {
+        #[derive(Deserialize)]
+        struct Response<T> {
+            data: Option<T>,
+            #[serde(default)]
+            errors: Vec<GraphqlError>,
+        }
+        #[derive(Deserialize)]
+        struct GraphqlError {
+            message: String,
+        }
+
+        let response = self
+            .http_client
+            .post(&self.api_url)
+            .header(reqwest::header::AUTHORIZATION, &self.api_key)
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .map_err(|e| Error::Other(format!("Linear {} request failed: {}", operation, e)))?;
+
+        let status = response.status();
+        let body: Response<T> = response.json().await.map_err(|e| {
+            Error::Other(format!(
+                "Invalid Linear {} response (HTTP {}): {}",
+                operation, status, e
+            ))
+        })?;
+
+        if !body.errors.is_empty() {
+            return Err(Error::Other(format!(
+                "Linear {} failed: {}",
+                operation,
+                body.errors
+                    .iter()
+                    .map(|e| e.message.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            )));
+        }
+        body.data.ok_or_else(|| {
+            Error::Other(format!(
+                "Linear {} failed (HTTP {}): no data returned",
+                operation, status
+            ))
+        })
+    }
+}
+
+/// Find a workflow state by name
+pub fn find_state<'a>(
+    states: &'a [LinearWorkflowState],
+    name: &str,
+) -> Result<&'a LinearWorkflowState> {
+    states
+        .iter()
+        .find(|state| state.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            Error::Other(format!(
+                "No workflow state named '{}' (available: {})",
+                name,
+                states
+                    .iter()
+                    .map(|state| state.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(name: &str, state_type: &str) -> LinearWorkflowState {
+        LinearWorkflowState {
+            id: format!("state-{}", name),
+            name: name.to_string(),
+            state_type: state_type.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_status_mapping() {
+        let team = LinearTeam::new("eng", "team-uuid")
+            .with_status(StoryStatus::Completed, "Merged")
+            .with_status(StoryStatus::Blocked, "Blocked");
+
+        assert_eq!(team.linear_state(StoryStatus::Pending), Some("Todo"));
+        assert_eq!(team.linear_state(StoryStatus::Completed), Some("Merged"));
+        assert_eq!(team.linear_state(StoryStatus::Blocked), Some("Blocked"));
+        assert_eq!(team.linear_state(StoryStatus::Skipped), None);
+
+        assert_eq!(
+            team.story_status(&state("blocked", "started")),
+            Some(StoryStatus::Blocked)
+        );
+        assert_eq!(
+            team.story_status(&state("In Review", "started")),
+            Some(StoryStatus::InProgress)
+        );
+        assert_eq!(
+            team.story_status(&state("Merged", "completed")),
+            Some(StoryStatus::Completed)
+        );
+        assert_eq!(
+            team.story_status(&state("Duplicate", "canceled")),
+            Some(StoryStatus::Skipped)
+        );
+        assert_eq!(team.story_status(&state("Triage", "triage")), None);
+
+        assert!(team.validate().is_ok());
+        assert!(LinearTeam::new("eng", "").validate().is_err());
+    }
+
+    #[test]
+    fn test_webhook_issue() {
+        let webhook: LinearWebhook = serde_json::from_value(json!({
+            "action": "update",
+            "type": "Issue",
+            "url": "https://linear.app/acme/issue/ENG-12/add-login",
+            "data": {
+                "id": "issue-uuid",
+                "identifier": "ENG-12",
+                "title": "Add login",
+                "description": "Use OAuth",
+                "teamId": "team-uuid",
+                "state": {"id": "state-1", "name": "In Progress", "type": "started"},
+                "labels": [{"id": "label-1", "name": "Orchestrate", "color": "#000000"}]
+            },
+            "updatedFrom": {"stateId": "state-0", "updatedAt": "2024-01-01T12:00:00.000Z"}
+        }))
+        .unwrap();
+
+        assert!(webhook.state_changed());
+        let issue = webhook.issue().unwrap();
+        assert_eq!(issue.team_id.as_deref(), Some("team-uuid"));
+        assert_eq!(
+            issue.url.as_deref(),
+            Some("https://linear.app/acme/issue/ENG-12/add-login")
+        );
+        assert_eq!(issue.agent_task(), "ENG-12: Add login\n\nUse OAuth");
+        assert!(LinearTeam::new("eng", "team-uuid").triggers_on(&issue));
+        assert!(!LinearTeam::new("eng", "team-uuid")
+            .with_trigger_label("agent")
+            .triggers_on(&issue));
+    }
+
+    #[test]
+    fn test_verify_webhook_signature() {
+        let body = br#"{"action":"create"}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(body);
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        assert!(verify_webhook_signature("secret", body, &signature));
+        assert!(!verify_webhook_signature("other", body, &signature));
+        assert!(!verify_webhook_signature("secret", body, "not-hex"));
+    }
+
+    #[test]
+    fn test_story_description() {
+        let story = Story::new("eng-001.1", "eng-001", "Add login")
+            .with_criteria(json!(["Email is validated"]));
+        assert_eq!(
+            story_description(&story),
+            "**Acceptance criteria**\n- [ ] Email is validated\n\n\
+             Synced from orchestrate story `eng-001.1` (epic `eng-001`)."
+        );
+    }
+}
@@ -0,0 +1,568 @@
+//! Linear Sync Service
+//!
+//! Keeps BMAD stories in sync with Linear in both directions:
+//! - A story gets a Linear issue when it is first synced, created in the
+//!   workflow state its status maps to
+//! - The issue is moved whenever the story status differs from the status
+//!   it was last synced for
+//! - Moving a linked issue to another workflow state in Linear updates the
+//!   story's status, as reported by webhooks
+//! - An issue labelled with a team's trigger label spawns an agent working
+//!   on it
+//!
+//! Stories sync to the enabled [`LinearTeam`] with the longest epic prefix
+//! matching their epic; stories of other epics are left alone.
+
+use serde_json::json;
+use tracing::{info, warn};
+
+use crate::{
+    agent::{Agent, AgentContext},
+    epic::{Story, StoryStatus},
+    error::{Error, Result},
+    linear::{
+        find_state, story_description, LinearClient, LinearIssue, LinearIssueLink, LinearTeam,
+        LinearWebhook,
+    },
+    Database,
+};
+
+/// What syncing a story did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinearSyncAction {
+    /// An issue was created
+    Created(String),
+    /// The issue was moved to a workflow state
+    Moved { identifier: String, state: String },
+    /// The issue was already up to date
+    Unchanged(String),
+}
+
+impl LinearSyncAction {
+    /// Identifier of the story's issue
+    pub fn identifier(&self) -> &str {
+        match self {
+            Self::Created(identifier) | Self::Unchanged(identifier) => identifier,
+            Self::Moved { identifier, .. } => identifier,
+        }
+    }
+}
+
+/// Totals of syncing stories
+#[derive(Debug, Clone, Default)]
+pub struct LinearSyncReport {
+    pub created: usize,
+    pub moved: usize,
+    pub errors: Vec<String>,
+}
+
+/// What applying a webhook did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinearWebhookOutcome {
+    /// A linked story's status followed its issue
+    StoryUpdated {
+        story_id: String,
+        status: StoryStatus,
+    },
+    /// An agent was spawned for the issue
+    AgentSpawned {
+        identifier: String,
+        agent_id: uuid::Uuid,
+    },
+    /// The delivery needed no change
+    Ignored,
+}
+
+/// Service syncing stories with Linear
+pub struct LinearSyncService {
+    db: Database,
+    client: Option<LinearClient>,
+}
+
+impl LinearSyncService {
+    /// Create a service applying webhooks only
+    pub fn new(db: Database) -> Self {
+        Self { db, client: None }
+    }
+
+    /// Push stories to Linear with `client`
+    pub fn with_client(mut self, client: LinearClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Service pushing stories with `LINEAR_API_KEY`, if set
+    pub fn from_env(db: Database) -> Self {
+        let service = Self::new(db);
+        match LinearClient::from_env() {
+            Some(client) => service.with_client(client),
+            None => service,
+        }
+    }
+
+    /// Whether stories can be pushed to Linear
+    pub fn has_client(&self) -> bool {
+        self.client.is_some()
+    }
+
+    /// Team the stories of an epic sync to
+    pub async fn team_for_epic(&self, epic_id: &str) -> Result<Option<LinearTeam>> {
+        Ok(self
+            .db
+            .list_linear_teams()
+            .await?
+            .into_iter()
+            .filter(|team| team.matches_epic(epic_id))
+            .max_by_key(|team| team.epic_prefix.len()))
+    }
+
+    /// Create the Linear issue of a story or move it to the story's status
+    ///
+    /// Returns `None` when no team is configured for the story's epic.
+    pub async fn sync_story(&self, story: &Story) -> Result<Option<LinearSyncAction>> {
+        let client = self.client.as_ref().ok_or_else(|| {
+            Error::Other("Linear is not configured (LINEAR_API_KEY is not set)".to_string())
+        })?;
+
+        let link = self.db.get_linear_story_link(&story.id).await?;
+        let team = match &link {
+            Some(link) => self.db.get_linear_team_by_id(link.team_id).await?,
+            None => self.team_for_epic(&story.epic_id).await?,
+        };
+        let Some(team) = team.filter(|team| team.enabled) else {
+            return Ok(None);
+        };
+        let team_id = team
+            .id
+            .ok_or_else(|| Error::Other("Linear team has no ID".to_string()))?;
+        let target = team.linear_state(story.status);
+
+        let Some(mut link) = link else {
+            let state_id = match target {
+                Some(name) => {
+                    let states = client.workflow_states(&team.linear_team_id).await?;
+                    Some(find_state(&states, name)?.id.clone())
+                }
+                None => None,
+            };
+            let issue = client
+                .create_issue(
+                    &team.linear_team_id,
+                    &story.title,
+                    &story_description(story),
+                    state_id.as_deref(),
+                )
+                .await?;
+            let mut link = LinearIssueLink::new(team_id, &issue).with_story(&story.id);
+            link.synced_status = Some(story.status);
+            self.db.upsert_linear_issue_link(&link).await?;
+            info!(story_id = %story.id, issue = %issue.identifier, "Created Linear issue");
+            return Ok(Some(LinearSyncAction::Created(issue.identifier)));
+        };
+
+        if link.synced_status == Some(story.status) {
+            return Ok(Some(LinearSyncAction::Unchanged(link.identifier)));
+        }
+
+        let moved = match target {
+            Some(name) => {
+                let states = client.workflow_states(&team.linear_team_id).await?;
+                let state = find_state(&states, name)?;
+                client.update_issue_state(&link.issue_id, &state.id).await?;
+                info!(
+                    story_id = %story.id,
+                    issue = %link.identifier,
+                    state = %state.name,
+                    "Moved Linear issue"
+                );
+                Some(state.name.clone())
+            }
+            None => None,
+        };
+        link.synced_status = Some(story.status);
+        link.updated_at = chrono::Utc::now();
+        self.db.upsert_linear_issue_link(&link).await?;
+
+        Ok(Some(match moved {
+            Some(state) => LinearSyncAction::Moved {
+                identifier: link.identifier,
+                state,
+            },
+            None => LinearSyncAction::Unchanged(link.identifier),
+        }))
+    }
+
+    /// Sync every story, oldest first
+    pub async fn sync_all(&self) -> Result<LinearSyncReport> {
+        let mut stories = self.db.list_stories(None).await?;
+        stories.reverse();
+        Ok(self.sync_stories(&stories).await)
+    }
+
+    /// Sync stories, continuing past failures
+    pub async fn sync_stories(&self, stories: &[Story]) -> LinearSyncReport {
+        let mut report = LinearSyncReport::default();
+
+        for story in stories {
+            match self.sync_story(story).await {
+                Ok(Some(LinearSyncAction::Created(_))) => report.created += 1,
+                Ok(Some(LinearSyncAction::Moved { .. })) => report.moved += 1,
+                Ok(Some(LinearSyncAction::Unchanged(_))) | Ok(None) => {}
+                Err(e) => {
+                    warn!(story_id = %story.id, error = %e, "Failed to sync story with Linear");
+                    report.errors.push(format!("{}: {}", story.id, e));
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Apply an issue webhook
+    ///
+    /// State changes of an issue linked to a story update the story; issues
+    /// of a configured team carrying its trigger label spawn an agent once.
+    pub async fn apply_webhook(&self, webhook: &LinearWebhook) -> Result<LinearWebhookOutcome> {
+        let Some(issue) = webhook.issue().filter(|_| webhook.action != "remove") else {
+            return Ok(LinearWebhookOutcome::Ignored);
+        };
+
+        match self.db.get_linear_issue_link(&issue.id).await? {
+            Some(link) if link.story_id.is_some() => {
+                if !webhook.state_changed() {
+                    return Ok(LinearWebhookOutcome::Ignored);
+                }
+                self.apply_state(link, &issue).await
+            }
+            // An agent was already spawned for the issue
+            Some(_) => Ok(LinearWebhookOutcome::Ignored),
+            None => self.spawn_agent(&issue).await,
+        }
+    }
+
+    /// Move a linked story to the status of its issue's workflow state
+    async fn apply_state(
+        &self,
+        mut link: LinearIssueLink,
+        issue: &LinearIssue,
+    ) -> Result<LinearWebhookOutcome> {
+        let Some(team) = self.db.get_linear_team_by_id(link.team_id).await? else {
+            return Ok(LinearWebhookOutcome::Ignored);
+        };
+        let Some(status) = issue
+            .state
+            .as_ref()
+            .and_then(|state| team.story_status(state))
+        else {
+            return Ok(LinearWebhookOutcome::Ignored);
+        };
+        let story_id = link.story_id.clone().unwrap_or_default();
+        let Some(story) = self.db.get_story(&story_id).await? else {
+            return Ok(LinearWebhookOutcome::Ignored);
+        };
+        if story.status == status {
+            return Ok(LinearWebhookOutcome::Ignored);
+        }
+
+        self.db
+            .update_story_status(&story.id, status, story.agent_id)
+            .await?;
+        // Recorded as synced so the next sync does not move the issue back
+        link.synced_status = Some(status);
+        link.updated_at = chrono::Utc::now();
+        self.db.upsert_linear_issue_link(&link).await?;
+        info!(
+            story_id = %story.id,
+            issue = %link.identifier,
+            status = %status.as_str(),
+            "Updated story from Linear"
+        );
+
+        Ok(LinearWebhookOutcome::StoryUpdated { story_id, status })
+    }
+
+    /// Spawn an agent for an issue carrying a team's trigger label
+    async fn spawn_agent(&self, issue: &LinearIssue) -> Result<LinearWebhookOutcome> {
+        let Some(team_id) = issue.team_id.as_deref() else {
+            return Ok(LinearWebhookOutcome::Ignored);
+        };
+        let Some(team) = self
+            .db
+            .list_linear_teams()
+            .await?
+            .into_iter()
+            .find(|team| team.linear_team_id == team_id && team.triggers_on(issue))
+        else {
+            return Ok(LinearWebhookOutcome::Ignored);
+        };
+        let team_row_id = team
+            .id
+            .ok_or_else(|| Error::Other("Linear team has no ID".to_string()))?;
+
+        let context = AgentContext {
+            custom: json!({
+                "linear_team": team.name,
+                "linear_issue_id": issue.id,
+                "linear_issue": issue.identifier,
+                "linear_url": issue.url,
+            }),
+            ..Default::default()
+        };
+        let agent = Agent::new(team.agent_type, issue.agent_task()).with_context(context);
+        self.db.insert_agent(&agent).await?;
+        self.db
+            .upsert_linear_issue_link(
+                &LinearIssueLink::new(team_row_id, issue).with_agent(agent.id),
+            )
+            .await?;
+        info!(
+            agent_id = %agent.id,
+            issue = %issue.identifier,
+            agent_type = %team.agent_type.as_str(),
+            "Spawned agent from Linear issue"
+        );
+
+        Ok(LinearWebhookOutcome::AgentSpawned {
+            identifier: issue.identifier.clone(),
+            agent_id: agent.id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::epic::Epic;
+    use crate::test_servers::{json_body, serve_with};
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+
+    type Requests = Arc<Mutex<Vec<Value>>>;
+
+    /// Fake Linear GraphQL API answering issue creation, workflow states,
+    /// and issue updates, recording every request body
+    async fn linear_api() -> (String, Requests) {
+        let requests: Requests = Arc::default();
+        let recorded = requests.clone();
+        let url = serve_with(move |request| {
+            let body = json_body(request);
+            let query = body["query"].as_str().unwrap_or_default();
+
+            let reply = if query.contains("issueCreate") {
+                r#"{"data":{"issueCreate":{"success":true,"issue":{
+                    "id":"issue-uuid","identifier":"ENG-7","title":"Add login form",
+                    "url":"https://linear.app/acme/issue/ENG-7"}}}}"#
+            } else if query.contains("workflowStates") {
+                r#"{"data":{"workflowStates":{"nodes":[
+                    {"id":"state-todo","name":"Todo","type":"unstarted"},
+                    {"id":"state-progress","name":"In Progress","type":"started"},
+                    {"id":"state-done","name":"Done","type":"completed"}
+                ]}}}"#
+            } else if query.contains("issueUpdate") {
+                r#"{"data":{"issueUpdate":{"success":true}}}"#
+            } else {
+                r#"{"errors":[{"message":"Unknown query"}]}"#
+            };
+
+            recorded.lock().unwrap().push(body);
+            (200, reply.to_string())
+        })
+        .await;
+
+        (format!("{}/graphql", url), requests)
+    }
+
+    async fn setup() -> Database {
+        let db = Database::in_memory().await.unwrap();
+        db.insert_linear_team(&LinearTeam::new("eng", "team-uuid").with_epic_prefix("eng-"))
+            .await
+            .unwrap();
+        db.upsert_epic(&Epic::new("eng-001", "Login"))
+            .await
+            .unwrap();
+        db
+    }
+
+    fn webhook(action: &str, data: Value, updated_from: Option<Value>) -> LinearWebhook {
+        serde_json::from_value(json!({
+            "action": action,
+            "type": "Issue",
+            "data": data,
+            "url": "https://linear.app/acme/issue/ENG-7",
+            "updatedFrom": updated_from,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sync_creates_and_moves_issue() {
+        let (url, requests) = linear_api().await;
+        let db = setup().await;
+        let service = LinearSyncService::new(db.clone())
+            .with_client(LinearClient::new("lin_api_key").with_api_url(url));
+
+        let mut story = Story::new("eng-001.1", "eng-001", "Add login form");
+        db.upsert_story(&story).await.unwrap();
+
+        assert_eq!(
+            service.sync_story(&story).await.unwrap(),
+            Some(LinearSyncAction::Created("ENG-7".to_string()))
+        );
+        assert_eq!(
+            service.sync_story(&story).await.unwrap(),
+            Some(LinearSyncAction::Unchanged("ENG-7".to_string()))
+        );
+
+        story.start(uuid::Uuid::new_v4());
+        assert_eq!(
+            service.sync_story(&story).await.unwrap(),
+            Some(LinearSyncAction::Moved {
+                identifier: "ENG-7".to_string(),
+                state: "In Progress".to_string()
+            })
+        );
+
+        // Stories of other epics are left alone
+        let other = Story::new("api-001.1", "api-001", "Add endpoint");
+        assert_eq!(service.sync_story(&other).await.unwrap(), None);
+
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 4);
+        let input = &requests[1]["variables"]["input"];
+        assert_eq!(input["teamId"], "team-uuid");
+        assert_eq!(input["stateId"], "state-todo");
+        assert_eq!(input["title"], "Add login form");
+        assert_eq!(requests[3]["variables"]["id"], "issue-uuid");
+        assert_eq!(requests[3]["variables"]["stateId"], "state-progress");
+
+        let link = db
+            .get_linear_story_link("eng-001.1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(link.synced_status, Some(StoryStatus::InProgress));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_updates_story_status() {
+        let db = setup().await;
+        let service = LinearSyncService::new(db.clone());
+
+        let story = Story::new("eng-001.1", "eng-001", "Add login form");
+        db.upsert_story(&story).await.unwrap();
+        let team = db.get_linear_team("eng").await.unwrap().unwrap();
+        let issue: LinearIssue = serde_json::from_value(json!({
+            "id": "issue-uuid", "identifier": "ENG-7", "title": "Add login form"
+        }))
+        .unwrap();
+        let mut link = LinearIssueLink::new(team.id.unwrap(), &issue).with_story(&story.id);
+        link.synced_status = Some(StoryStatus::Pending);
+        db.upsert_linear_issue_link(&link).await.unwrap();
+
+        let data = json!({
+            "id": "issue-uuid",
+            "identifier": "ENG-7",
+            "title": "Add login form",
+            "teamId": "team-uuid",
+            "state": {"id": "state-done", "name": "Done", "type": "completed"}
+        });
+
+        // Edits that do not change the state are ignored
+        assert_eq!(
+            service
+                .apply_webhook(&webhook(
+                    "update",
+                    data.clone(),
+                    Some(json!({"title": "Login"}))
+                ))
+                .await
+                .unwrap(),
+            LinearWebhookOutcome::Ignored
+        );
+
+        let outcome = service
+            .apply_webhook(&webhook(
+                "update",
+                data,
+                Some(json!({"stateId": "state-todo"})),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            outcome,
+            LinearWebhookOutcome::StoryUpdated {
+                story_id: "eng-001.1".to_string(),
+                status: StoryStatus::Completed
+            }
+        );
+
+        let story = db.get_story("eng-001.1").await.unwrap().unwrap();
+        assert_eq!(story.status, StoryStatus::Completed);
+        let link = db
+            .get_linear_story_link("eng-001.1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(link.synced_status, Some(StoryStatus::Completed));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_spawns_agent_once() {
+        let db = setup().await;
+        let service = LinearSyncService::new(db.clone());
+
+        let data = json!({
+            "id": "issue-uuid",
+            "identifier": "ENG-9",
+            "title": "Fix flaky login test",
+            "description": "Fails about once a day",
+            "teamId": "team-uuid",
+            "labels": [{"id": "label-1", "name": "orchestrate"}]
+        });
+        let unlabelled = json!({
+            "id": "issue-other",
+            "identifier": "ENG-10",
+            "title": "Write release notes",
+            "teamId": "team-uuid",
+            "labels": []
+        });
+
+        assert_eq!(
+            service
+                .apply_webhook(&webhook("create", unlabelled, None))
+                .await
+                .unwrap(),
+            LinearWebhookOutcome::Ignored
+        );
+
+        let outcome = service
+            .apply_webhook(&webhook("create", data.clone(), None))
+            .await
+            .unwrap();
+        let LinearWebhookOutcome::AgentSpawned {
+            identifier,
+            agent_id,
+        } = outcome
+        else {
+            panic!("expected an agent to be spawned, got {:?}", outcome);
+        };
+        assert_eq!(identifier, "ENG-9");
+
+        let agent = db.get_agent(agent_id).await.unwrap().unwrap();
+        assert_eq!(agent.agent_type, crate::AgentType::StoryDeveloper);
+        assert_eq!(
+            agent.task,
+            "ENG-9: Fix flaky login test\n\nFails about once a day"
+        );
+        assert_eq!(agent.context.custom["linear_issue"], "ENG-9");
+
+        // Later deliveries for the issue do not spawn another agent
+        assert_eq!(
+            service
+                .apply_webhook(&webhook("update", data, Some(json!({"labelIds": []}))))
+                .await
+                .unwrap(),
+            LinearWebhookOutcome::Ignored
+        );
+    }
+}
@@ -325,6 +325,7 @@ pub enum NotificationChannelType {
     PagerDuty,
     Webhook,
     Discord,
+    Telegram,
 }
 
 impl FromStr for NotificationChannelType {
@@ -337,6 +338,7 @@ impl FromStr for NotificationChannelType {
             "pagerduty" => Ok(Self::PagerDuty),
             "webhook" => Ok(Self::Webhook),
             "discord" => Ok(Self::Discord),
+            "telegram" => Ok(Self::Telegram),
             _ => Err(format!("Unknown channel type: {}", s)),
         }
     }
@@ -412,6 +414,12 @@ impl CostRecord {
         self.agent_type = Some(agent_type.into());
         self
     }
+
+    pub fn with_story(mut self, epic_id: impl Into<String>, story_id: impl Into<String>) -> Self {
+        self.epic_id = Some(epic_id.into());
+        self.story_id = Some(story_id.into());
+        self
+    }
 }
 
 /// Cost report aggregation
@@ -425,6 +433,8 @@ pub struct CostReport {
     pub by_model: HashMap<String, f64>,
     pub by_agent_type: HashMap<String, f64>,
     pub by_epic: HashMap<String, f64>,
+    #[serde(default)]
+    pub by_story: HashMap<String, f64>,
     pub daily_breakdown: Vec<DailyCost>,
     pub budget_usd: Option<f64>,
     pub forecast_usd: Option<f64>,
@@ -441,6 +451,7 @@ impl CostReport {
             by_model: HashMap::new(),
             by_agent_type: HashMap::new(),
             by_epic: HashMap::new(),
+            by_story: HashMap::new(),
             daily_breakdown: Vec::new(),
             budget_usd: None,
             forecast_usd: None,
@@ -461,6 +472,10 @@ impl CostReport {
         if let Some(epic_id) = &record.epic_id {
             *self.by_epic.entry(epic_id.clone()).or_insert(0.0) += record.cost_usd;
         }
+
+        if let Some(story_id) = &record.story_id {
+            *self.by_story.entry(story_id.clone()).or_insert(0.0) += record.cost_usd;
+        }
     }
 
     pub fn budget_percentage(&self) -> Option<f64> {
@@ -486,6 +501,20 @@ impl CostReport {
             }
         }
 
+        for (title, costs) in [("By Epic:", &self.by_epic), ("By Story:", &self.by_story)] {
+            if costs.is_empty() {
+                continue;
+            }
+            let mut sorted: Vec<_> = costs.iter().collect();
+            sorted.sort_by(|a, b| b.1.total_cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+            lines.push(String::new());
+            lines.push(title.to_string());
+            for (id, cost) in sorted {
+                lines.push(format!("  {}: ${:.2}", id, cost));
+            }
+        }
+
         if let Some(budget) = self.budget_usd {
             lines.push(String::new());
             lines.push(format!("Budget: ${:.2} ({:.0}% used)", budget, self.budget_percentage().unwrap_or(0.0)));
@@ -730,6 +759,20 @@ impl ComponentHealth {
             latency_ms: None,
         }
     }
+
+    pub fn degraded(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: HealthStatus::Degraded,
+            message: Some(message.into()),
+            latency_ms: None,
+        }
+    }
+
+    pub fn with_latency(mut self, latency: std::time::Duration) -> Self {
+        self.latency_ms = Some(latency.as_millis() as u64);
+        self
+    }
 }
 
 /// Summary of key metrics
@@ -845,6 +888,27 @@ mod tests {
         assert!(summary.contains("Total: $0.60"));
     }
 
+    #[test]
+    fn test_cost_report_by_story() {
+        let mut report = CostReport::new(Utc::now() - chrono::Duration::days(7), Utc::now());
+
+        report.add_record(
+            &CostRecord::new("claude-3-sonnet", 5000, 1000, 0.10).with_story("epic-1", "epic-1.1"),
+        );
+        report.add_record(
+            &CostRecord::new("claude-3-sonnet", 5000, 1000, 0.30).with_story("epic-1", "epic-1.2"),
+        );
+
+        assert!((report.by_epic["epic-1"] - 0.40).abs() < 0.001);
+        assert!((report.by_story["epic-1.2"] - 0.30).abs() < 0.001);
+
+        let summary = report.to_summary();
+        let story_1 = summary.find("epic-1.1: $0.10").unwrap();
+        let story_2 = summary.find("epic-1.2: $0.30").unwrap();
+        assert!(summary.contains("By Story:"));
+        assert!(story_2 < story_1, "most expensive story listed first");
+    }
+
     #[test]
     fn test_audit_entry() {
         let entry = AuditEntry::new(
@@ -869,6 +933,13 @@ mod tests {
 
         assert_eq!(health.status, HealthStatus::Healthy);
 
+        health.add_component(
+            ComponentHealth::degraded("github", "Slow response")
+                .with_latency(std::time::Duration::from_millis(2500)),
+        );
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert_eq!(health.components[2].latency_ms, Some(2500));
+
         health.add_component(ComponentHealth::unhealthy("cache", "Connection timeout"));
         assert_eq!(health.status, HealthStatus::Unhealthy);
     }
@@ -6,14 +6,16 @@
 //! - Network-wide validation
 //! - Self-healing capabilities
 
-use super::skills::default_skill_registry;
-use super::state::default_agent_state_graph;
+use super::skills::{default_skill_registry, SkillRegistryError};
+use super::state::{default_agent_state_graph, PropagationEvent, StateMachineError};
 use super::validation::{NetworkValidator, ValidationErrorCode};
 use super::{
-    AgentHandle, AgentId, DependencyGraph, SkillRegistry, StateGraph, StateMachine,
+    AgentHandle, AgentId, DependencyGraph, SkillDefinition, SkillRegistry, StateGraph, StateMachine,
     StatePropagation, ValidationResult,
 };
 use crate::{AgentState, AgentType};
+use async_trait::async_trait;
+use futures::future::join_all;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
@@ -36,6 +38,16 @@ pub enum NetworkEvent {
     },
     /// Dependency added
     DependencyAdded { from: AgentId, to: AgentId },
+    /// Skill registered (or replaced) at runtime
+    SkillRegistered { name: String },
+    /// Skill removed at runtime
+    SkillUnregistered { name: String },
+    /// State change propagated to a dependent agent
+    StatePropagated {
+        source: AgentId,
+        target: AgentId,
+        event: PropagationEvent,
+    },
     /// Validation completed
     ValidationCompleted { result: ValidationResult },
     /// Self-healing action taken
@@ -66,6 +78,17 @@ pub enum RecoveryAction {
     None,
 }
 
+/// Receives state propagations delivered to dependent agents
+///
+/// Handlers for agents in independent subgraphs (and in the same wave of a
+/// subgraph) are invoked concurrently, so implementations must not assume
+/// a global delivery order.
+#[async_trait]
+pub trait PropagationHandler: Send + Sync {
+    /// Handle a propagation from `source` to `target`
+    async fn on_propagation(&self, source: AgentId, target: AgentId, event: &PropagationEvent);
+}
+
 /// Configuration for the network coordinator
 #[derive(Debug, Clone)]
 pub struct CoordinatorConfig {
@@ -102,7 +125,7 @@ pub struct NetworkCoordinator {
     /// Dependency graph
     dependency_graph: RwLock<DependencyGraph>,
     /// Skill registry
-    skill_registry: Arc<SkillRegistry>,
+    skill_registry: RwLock<SkillRegistry>,
     /// Network validator
     validator: NetworkValidator,
     /// State graph template
@@ -113,6 +136,8 @@ pub struct NetworkCoordinator {
     config: CoordinatorConfig,
     /// Recovery attempt counts
     recovery_attempts: RwLock<HashMap<AgentId, u32>>,
+    /// Receiver for propagated state changes
+    propagation_handler: Option<Arc<dyn PropagationHandler>>,
 }
 
 impl NetworkCoordinator {
@@ -124,15 +149,28 @@ impl NetworkCoordinator {
             agents: RwLock::new(HashMap::new()),
             state_machines: RwLock::new(HashMap::new()),
             dependency_graph: RwLock::new(DependencyGraph::new()),
-            skill_registry: Arc::new(default_skill_registry()),
+            skill_registry: RwLock::new(default_skill_registry()),
             validator: NetworkValidator::default(),
             state_graph: default_agent_state_graph(),
             event_tx,
             config,
             recovery_attempts: RwLock::new(HashMap::new()),
+            propagation_handler: None,
         }
     }
 
+    /// Use a custom state graph for newly registered agents
+    pub fn with_state_graph(mut self, state_graph: StateGraph) -> Self {
+        self.state_graph = state_graph;
+        self
+    }
+
+    /// Set the handler that receives propagated state changes
+    pub fn with_propagation_handler(mut self, handler: Arc<dyn PropagationHandler>) -> Self {
+        self.propagation_handler = Some(handler);
+        self
+    }
+
     /// Create with default configuration
     pub fn with_defaults() -> Self {
         Self::new(CoordinatorConfig::default())
@@ -234,7 +272,45 @@ impl NetworkCoordinator {
         new_state: AgentState,
         trigger: Option<String>,
     ) -> Result<(), CoordinatorError> {
+        self.apply_transition(agent_id, |machine, dependency_states| {
+            machine.transition(new_state, dependency_states, trigger)
+        })
+        .await
+    }
+
+    /// Transition an agent to a built-in or custom state by name
+    ///
+    /// `role` is checked against the transition's allowed roles.
+    pub async fn transition_to_named(
+        &self,
+        agent_id: AgentId,
+        state_name: &str,
+        trigger: Option<String>,
+        role: Option<&str>,
+    ) -> Result<(), CoordinatorError> {
+        self.apply_transition(agent_id, |machine, dependency_states| {
+            machine.transition_to_named(state_name, dependency_states, trigger, role)
+        })
+        .await
+    }
+
+    /// Get the name of an agent's current state (custom name if in a custom state)
+    pub async fn get_agent_state_name(&self, agent_id: AgentId) -> Option<String> {
+        let machines = self.state_machines.read().await;
+        machines
+            .get(&agent_id)
+            .map(|m| m.current_state_name().to_string())
+    }
+
+    async fn apply_transition<F>(&self, agent_id: AgentId, transition: F) -> Result<(), CoordinatorError>
+    where
+        F: FnOnce(
+            &mut StateMachine,
+            &HashMap<AgentId, (AgentType, AgentState)>,
+        ) -> Result<Vec<StatePropagation>, StateMachineError>,
+    {
         let old_state;
+        let new_state;
         let propagations;
 
         // Get dependency states for validation
@@ -248,9 +324,9 @@ impl NetworkCoordinator {
                 .ok_or(CoordinatorError::AgentNotFound(agent_id))?;
 
             old_state = machine.current_state();
-            propagations = machine
-                .transition(new_state, &dependency_states, trigger)
+            propagations = transition(machine, &dependency_states)
                 .map_err(|e| CoordinatorError::TransitionError(e.to_string()))?;
+            new_state = machine.current_state();
         }
 
         // Update agent handle
@@ -297,6 +373,10 @@ impl NetworkCoordinator {
     }
 
     /// Propagate state changes to dependent agents
+    ///
+    /// Independent subgraphs of dependents are processed concurrently. Within
+    /// a subgraph, agents are notified wave by wave so an agent only receives
+    /// a propagation after every affected agent it depends on has.
     async fn propagate_state_changes(
         &self,
         source_agent: AgentId,
@@ -306,33 +386,73 @@ impl NetworkCoordinator {
             return Ok(());
         }
 
-        let affected_agents = {
+        let plan = {
             let graph = self.dependency_graph.read().await;
-            graph.affected_agents(source_agent)
+            graph.propagation_waves(source_agent)
         };
 
-        // Queue propagation events for affected agents
-        let agents = self.agents.read().await;
-        for propagation in propagations {
-            for &affected_id in &affected_agents {
-                if let Some(agent) = agents.get(&affected_id) {
-                    if agent.agent_type == propagation.target_type {
-                        // Notify the agent about the propagation event
-                        // In a real implementation, this would trigger the agent's event handler
-                        tracing::debug!(
-                            "Propagating {:?} from {} to {}",
-                            propagation.event,
-                            source_agent,
-                            affected_id
-                        );
-                    }
-                }
+        // Snapshot agent types so no lock is held while handlers run
+        let agent_types: HashMap<AgentId, AgentType> = {
+            let agents = self.agents.read().await;
+            plan.iter()
+                .flatten()
+                .flatten()
+                .filter_map(|id| agents.get(id).map(|a| (*id, a.agent_type)))
+                .collect()
+        };
+
+        let agent_types = &agent_types;
+        let propagations = &propagations;
+        join_all(plan.iter().map(|waves| async move {
+            for wave in waves {
+                join_all(wave.iter().map(|&target| {
+                    self.deliver_propagations(source_agent, target, agent_types, propagations)
+                }))
+                .await;
             }
-        }
+        }))
+        .await;
 
         Ok(())
     }
 
+    /// Deliver the propagations targeting an agent's type, in declaration order
+    async fn deliver_propagations(
+        &self,
+        source_agent: AgentId,
+        target: AgentId,
+        agent_types: &HashMap<AgentId, AgentType>,
+        propagations: &[StatePropagation],
+    ) {
+        let Some(&agent_type) = agent_types.get(&target) else {
+            return;
+        };
+
+        for propagation in propagations
+            .iter()
+            .filter(|p| p.target_type == agent_type)
+        {
+            tracing::debug!(
+                "Propagating {:?} from {} to {}",
+                propagation.event,
+                source_agent,
+                target
+            );
+
+            if let Some(handler) = &self.propagation_handler {
+                handler
+                    .on_propagation(source_agent, target, &propagation.event)
+                    .await;
+            }
+
+            let _ = self.event_tx.send(NetworkEvent::StatePropagated {
+                source: source_agent,
+                target,
+                event: propagation.event.clone(),
+            });
+        }
+    }
+
     /// Validate the entire network
     pub async fn validate_network(&self) -> ValidationResult {
         let agents = self.agents.read().await;
@@ -514,15 +634,51 @@ impl NetworkCoordinator {
             .collect();
 
         self.skill_registry
+            .read()
+            .await
             .available_skills(agent.agent_type, agent.state, &dependency_states)
             .iter()
             .map(|s| s.name.clone())
             .collect()
     }
 
-    /// Get the skill registry
-    pub fn skill_registry(&self) -> Arc<SkillRegistry> {
-        Arc::clone(&self.skill_registry)
+    /// Get a snapshot of the skill registry
+    pub async fn skill_registry(&self) -> SkillRegistry {
+        self.skill_registry.read().await.clone()
+    }
+
+    /// Register a skill at runtime
+    ///
+    /// The skill's dependency requirements are validated against the
+    /// existing skills; an existing skill with the same name is replaced.
+    pub async fn register_skill(&self, skill: SkillDefinition) -> Result<(), CoordinatorError> {
+        let name = skill.name.clone();
+        {
+            let mut registry = self.skill_registry.write().await;
+            registry
+                .try_register(skill)
+                .map_err(|e| CoordinatorError::SkillError(e.to_string()))?;
+        }
+
+        let _ = self.event_tx.send(NetworkEvent::SkillRegistered { name });
+
+        Ok(())
+    }
+
+    /// Unregister a skill at runtime
+    pub async fn unregister_skill(&self, name: &str) -> Result<SkillDefinition, CoordinatorError> {
+        let removed = {
+            let mut registry = self.skill_registry.write().await;
+            registry.unregister(name).ok_or_else(|| {
+                CoordinatorError::SkillError(SkillRegistryError::NotFound(name.to_string()).to_string())
+            })?
+        };
+
+        let _ = self.event_tx.send(NetworkEvent::SkillUnregistered {
+            name: name.to_string(),
+        });
+
+        Ok(removed)
     }
 
     /// Get dependency agent IDs for an agent
@@ -579,6 +735,9 @@ pub enum CoordinatorError {
 
     #[error("Validation error: {0}")]
     ValidationError(String),
+
+    #[error("Skill error: {0}")]
+    SkillError(String),
 }
 
 #[cfg(test)]
@@ -653,4 +812,182 @@ mod tests {
         // Single agent with no dependencies should be valid (but may have warnings)
         assert!(result.is_valid);
     }
+
+    /// Records delivery order and the peak number of concurrent deliveries
+    #[derive(Default)]
+    struct RecordingHandler {
+        delivered: std::sync::Mutex<Vec<AgentId>>,
+        in_flight: std::sync::atomic::AtomicUsize,
+        max_in_flight: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PropagationHandler for RecordingHandler {
+        async fn on_propagation(&self, _source: AgentId, target: AgentId, _event: &PropagationEvent) {
+            use std::sync::atomic::Ordering;
+
+            let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.delivered.lock().unwrap().push(target);
+        }
+    }
+
+    fn propagating_state_graph() -> StateGraph {
+        use super::super::state::StateGraphBuilder;
+        use super::super::StateTransition;
+
+        StateGraphBuilder::new(AgentState::Created)
+            .transition(AgentState::Created, AgentState::Initializing)
+            .transition(AgentState::Initializing, AgentState::Running)
+            .conditional_transition(
+                StateTransition::new(AgentState::Running, AgentState::Completed).propagate(
+                    StatePropagation::signal(
+                        AgentType::CodeReviewer,
+                        PropagationEvent::DependencyCompleted,
+                    ),
+                ),
+            )
+            .terminal(AgentState::Completed)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_propagation_concurrent_and_ordered() {
+        let handler = Arc::new(RecordingHandler::default());
+        let coordinator = NetworkCoordinator::with_defaults()
+            .with_state_graph(propagating_state_graph())
+            .with_propagation_handler(handler.clone());
+
+        let source = AgentId::new();
+        coordinator
+            .register_agent(source, AgentType::StoryDeveloper, AgentState::Created)
+            .await
+            .unwrap();
+
+        // Four independent reviewers, plus one that depends on the first
+        let reviewers: Vec<AgentId> = (0..4).map(|_| AgentId::new()).collect();
+        let downstream = AgentId::new();
+        for &id in reviewers.iter().chain(std::iter::once(&downstream)) {
+            coordinator
+                .register_agent(id, AgentType::CodeReviewer, AgentState::Created)
+                .await
+                .unwrap();
+        }
+        for &id in &reviewers {
+            coordinator.add_dependency(id, source).await.unwrap();
+        }
+        coordinator.add_dependency(downstream, reviewers[0]).await.unwrap();
+
+        for state in [
+            AgentState::Initializing,
+            AgentState::Running,
+            AgentState::Completed,
+        ] {
+            coordinator
+                .transition_state(source, state, None)
+                .await
+                .unwrap();
+        }
+
+        let delivered = handler.delivered.lock().unwrap().clone();
+        assert_eq!(delivered.len(), 5);
+        let position = |id: AgentId| delivered.iter().position(|d| *d == id).unwrap();
+        assert!(position(reviewers[0]) < position(downstream));
+        assert!(handler.max_in_flight.load(std::sync::atomic::Ordering::SeqCst) > 1);
+    }
+
+    #[tokio::test]
+    async fn test_coordinator_custom_state_transition() {
+        use super::super::StateMachineConfig;
+
+        let config = StateMachineConfig::from_yaml(
+            r#"
+states:
+  - name: waiting_for_qa
+    base: waiting_for_external
+transitions:
+  - from: running
+    to: waiting_for_qa
+  - from: waiting_for_qa
+    to: completed
+    allowed_roles: [qa]
+"#,
+        )
+        .unwrap();
+        let graph = config.apply(&default_agent_state_graph()).unwrap();
+        let coordinator = NetworkCoordinator::with_defaults().with_state_graph(graph);
+
+        let agent_id = AgentId::new();
+        coordinator
+            .register_agent(agent_id, AgentType::StoryDeveloper, AgentState::Created)
+            .await
+            .unwrap();
+        for state in ["initializing", "running", "waiting_for_qa"] {
+            coordinator
+                .transition_to_named(agent_id, state, None, None)
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            coordinator.get_agent_state(agent_id).await,
+            Some(AgentState::WaitingForExternal)
+        );
+        assert_eq!(
+            coordinator.get_agent_state_name(agent_id).await.as_deref(),
+            Some("waiting_for_qa")
+        );
+
+        assert!(coordinator
+            .transition_to_named(agent_id, "completed", None, Some("developer"))
+            .await
+            .is_err());
+        coordinator
+            .transition_to_named(agent_id, "completed", None, Some("qa"))
+            .await
+            .unwrap();
+        assert_eq!(
+            coordinator.get_agent_state(agent_id).await,
+            Some(AgentState::Completed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_coordinator_runtime_skill_registration() {
+        let coordinator = NetworkCoordinator::with_defaults();
+        let mut events = coordinator.subscribe();
+
+        let agent_id = AgentId::new();
+        coordinator
+            .register_agent(agent_id, AgentType::Explorer, AgentState::Running)
+            .await
+            .unwrap();
+
+        coordinator
+            .register_skill(SkillDefinition::new("audit", vec![AgentType::Explorer]))
+            .await
+            .unwrap();
+        assert!(coordinator
+            .available_skills(agent_id)
+            .await
+            .contains(&"audit".to_string()));
+
+        coordinator.unregister_skill("audit").await.unwrap();
+        assert!(!coordinator
+            .available_skills(agent_id)
+            .await
+            .contains(&"audit".to_string()));
+        assert!(coordinator.unregister_skill("audit").await.is_err());
+
+        let mut skill_events = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            match event {
+                NetworkEvent::SkillRegistered { name } => skill_events.push(format!("+{}", name)),
+                NetworkEvent::SkillUnregistered { name } => skill_events.push(format!("-{}", name)),
+                _ => {}
+            }
+        }
+        assert_eq!(skill_events, vec!["+audit", "-audit"]);
+    }
 }
@@ -7,10 +7,12 @@
 
 use super::AgentId;
 use crate::{AgentState, AgentType};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Condition that must be satisfied by dependencies
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DependencyCondition {
     /// A specific agent must be in a specific state
     AgentInState {
@@ -301,6 +303,89 @@ impl DependencyGraph {
         affected
     }
 
+    /// Plan how a state change of `agent_id` propagates through its dependents
+    ///
+    /// Affected agents are split into independent subgraphs (components that
+    /// share no dependency edges), and each subgraph into waves by longest
+    /// path from the source. Agents in the same wave never depend on each
+    /// other, so a wave can be delivered concurrently, while waves run in
+    /// order so an agent is only notified after everything it depends on.
+    /// Waves are sorted by agent ID and subgraphs by their first agent, so
+    /// the plan is deterministic.
+    pub fn propagation_waves(&self, agent_id: AgentId) -> Vec<Vec<Vec<AgentId>>> {
+        let affected = self.affected_agents(agent_id);
+
+        // Longest-path level of each affected agent, in topological order
+        let mut level: HashMap<AgentId, usize> = HashMap::new();
+        let mut pending: HashMap<AgentId, usize> = affected
+            .iter()
+            .map(|&id| {
+                let count = self
+                    .get_dependencies(id)
+                    .filter(|dep| affected.contains(dep))
+                    .count();
+                (id, count)
+            })
+            .collect();
+        let mut queue: VecDeque<AgentId> = pending
+            .iter()
+            .filter(|(_, &count)| count == 0)
+            .map(|(&id, _)| id)
+            .collect();
+        for &id in &queue {
+            level.insert(id, 1);
+        }
+        while let Some(current) = queue.pop_front() {
+            let current_level = level[&current];
+            for dependent in self.get_dependents(current) {
+                if let Some(count) = pending.get_mut(&dependent) {
+                    let entry = level.entry(dependent).or_insert(0);
+                    *entry = (*entry).max(current_level + 1);
+                    *count -= 1;
+                    if *count == 0 {
+                        queue.push_back(dependent);
+                    }
+                }
+            }
+        }
+
+        // Group affected agents into independent subgraphs
+        let mut seen = HashSet::new();
+        let mut components = Vec::new();
+        let mut roots: Vec<AgentId> = affected.iter().copied().collect();
+        roots.sort_by_key(|id| id.0);
+        for root in roots {
+            if !seen.insert(root) {
+                continue;
+            }
+            let mut members = vec![root];
+            let mut stack = vec![root];
+            while let Some(current) = stack.pop() {
+                let neighbors = self
+                    .get_dependencies(current)
+                    .chain(self.get_dependents(current));
+                for next in neighbors {
+                    if affected.contains(&next) && seen.insert(next) {
+                        members.push(next);
+                        stack.push(next);
+                    }
+                }
+            }
+
+            let depth = members.iter().map(|id| level[id]).max().unwrap_or(0);
+            let mut waves = vec![Vec::new(); depth];
+            for id in members {
+                waves[level[&id] - 1].push(id);
+            }
+            for wave in &mut waves {
+                wave.sort_by_key(|id| id.0);
+            }
+            components.push(waves);
+        }
+
+        components
+    }
+
     /// Check if agent A can observe agent B (B is a dependency of A)
     pub fn can_observe(&self, observer: AgentId, target: AgentId) -> bool {
         self.dependencies
@@ -382,6 +467,46 @@ mod tests {
         assert!(matches!(result, Err(DependencyError::CycleDetected { .. })));
     }
 
+    #[test]
+    fn test_propagation_waves() {
+        let mut graph = DependencyGraph::new();
+
+        let source = AgentId::new();
+        let left_a = AgentId::new();
+        let left_b = AgentId::new();
+        let left_c = AgentId::new();
+        let right = AgentId::new();
+        for id in [source, left_a, left_b, left_c, right] {
+            graph.register_agent(id, AgentType::CodeReviewer);
+        }
+
+        // Left subgraph: a and b depend on source, c depends on a and b
+        // (and directly on source, which must not pull it into wave one)
+        graph.add_dependency(left_a, source).unwrap();
+        graph.add_dependency(left_b, source).unwrap();
+        graph.add_dependency(left_c, left_a).unwrap();
+        graph.add_dependency(left_c, left_b).unwrap();
+        graph.add_dependency(left_c, source).unwrap();
+        // Right subgraph: independent of the left one
+        graph.add_dependency(right, source).unwrap();
+
+        let plan = graph.propagation_waves(source);
+        assert_eq!(plan.len(), 2);
+
+        let left = plan.iter().find(|c| c.len() == 2).unwrap();
+        let mut first_wave = vec![left_a, left_b];
+        first_wave.sort_by_key(|id| id.0);
+        assert_eq!(left[0], first_wave);
+        assert_eq!(left[1], vec![left_c]);
+
+        let right_component = plan.iter().find(|c| c.len() == 1).unwrap();
+        assert_eq!(right_component[0], vec![right]);
+
+        // Planning is deterministic
+        assert_eq!(plan, graph.propagation_waves(source));
+        assert!(graph.propagation_waves(left_c).is_empty());
+    }
+
     #[test]
     fn test_dependency_condition() {
         let a = AgentId::new();
@@ -10,17 +10,25 @@
 //! - **Runtime validation**: NetworkCoordinator ensures consistency
 //! - **Auto propagation**: State changes flow through dependency graph
 //! - **Self-healing**: Automatic recovery from invalid states
+//! - **Configurable states**: Custom states and transitions loaded from YAML
 
 pub mod coordinator;
 pub mod dependency;
 pub mod skills;
 pub mod state;
+pub mod transition_config;
 pub mod validation;
 
-pub use coordinator::{NetworkCoordinator, NetworkEvent, RecoveryAction};
+pub use coordinator::{NetworkCoordinator, NetworkEvent, PropagationHandler, RecoveryAction};
 pub use dependency::{DependencyCondition, DependencyGraph, DependencySet};
-pub use skills::{Skill, SkillDefinition, SkillRegistry};
-pub use state::{StateGraph, StateMachine, StatePropagation, StateTransition};
+pub use skills::{Skill, SkillDefinition, SkillRegistry, SkillRegistryError};
+pub use state::{
+    PropagationEvent, StateGraph, StateGraphBuilder, StateMachine, StatePropagation,
+    StateTransition,
+};
+pub use transition_config::{
+    CustomStateConfig, SideEffectConfig, StateMachineConfig, TransitionRuleConfig,
+};
 pub use validation::{NetworkValidator, ValidationError, ValidationResult};
 
 use crate::AgentType;
@@ -4,10 +4,13 @@
 //! - Required agent state for execution
 //! - Required dependency states
 //! - State changes produced by skill execution
+//!
+//! Skills can be registered and unregistered at runtime; registration is
+//! validated so the capability dependencies between agent types stay acyclic.
 
 use super::AgentId;
 use crate::{AgentState, AgentType};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Definition of a skill that an agent can execute
 #[derive(Debug, Clone)]
@@ -229,8 +232,10 @@ impl SkillRegistry {
         Self::default()
     }
 
-    /// Register a skill
+    /// Register a skill, replacing any existing skill with the same name
     pub fn register(&mut self, skill: SkillDefinition) {
+        self.unregister(&skill.name);
+
         let name = skill.name.clone();
         for agent_type in &skill.agent_types {
             self.skills_by_type
@@ -241,6 +246,92 @@ impl SkillRegistry {
         self.skills.insert(name, skill);
     }
 
+    /// Validate and register a skill
+    ///
+    /// Fails if the definition is malformed or if its dependency requirements
+    /// would introduce a cycle between agent types.
+    pub fn try_register(&mut self, skill: SkillDefinition) -> Result<(), SkillRegistryError> {
+        self.validate(&skill)?;
+        self.register(skill);
+        Ok(())
+    }
+
+    /// Remove a skill, returning its definition if it was registered
+    pub fn unregister(&mut self, name: &str) -> Option<SkillDefinition> {
+        let skill = self.skills.remove(name)?;
+        for agent_type in &skill.agent_types {
+            if let Some(names) = self.skills_by_type.get_mut(agent_type) {
+                names.retain(|n| n != name);
+                if names.is_empty() {
+                    self.skills_by_type.remove(agent_type);
+                }
+            }
+        }
+        Some(skill)
+    }
+
+    /// Check whether a skill definition can be registered
+    pub fn validate(&self, skill: &SkillDefinition) -> Result<(), SkillRegistryError> {
+        if skill.name.trim().is_empty() {
+            return Err(SkillRegistryError::InvalidSkill(
+                "skill name cannot be empty".to_string(),
+            ));
+        }
+        if skill.agent_types.is_empty() {
+            return Err(SkillRegistryError::InvalidSkill(format!(
+                "skill '{}' must be executable by at least one agent type",
+                skill.name
+            )));
+        }
+
+        // Build the agent type dependency graph as it would look after
+        // registration (the skill replaces any existing one with its name)
+        let mut edges: HashMap<AgentType, HashSet<AgentType>> = HashMap::new();
+        let others = self.skills.values().filter(|s| s.name != skill.name);
+        for def in others.chain(std::iter::once(skill)) {
+            for executor in &def.agent_types {
+                for (dep_type, _) in &def.dependency_states {
+                    edges.entry(*executor).or_default().insert(*dep_type);
+                }
+            }
+        }
+
+        for executor in &skill.agent_types {
+            for (dep_type, _) in &skill.dependency_states {
+                if Self::reaches(&edges, *dep_type, *executor) {
+                    return Err(SkillRegistryError::CycleDetected {
+                        skill: skill.name.clone(),
+                        from: *executor,
+                        to: *dep_type,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check if `target` is reachable from `start` in the type dependency graph
+    fn reaches(
+        edges: &HashMap<AgentType, HashSet<AgentType>>,
+        start: AgentType,
+        target: AgentType,
+    ) -> bool {
+        let mut visited = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(current) = stack.pop() {
+            if current == target {
+                return true;
+            }
+            if visited.insert(current) {
+                if let Some(next) = edges.get(&current) {
+                    stack.extend(next.iter().copied());
+                }
+            }
+        }
+        false
+    }
+
     /// Get a skill by name
     pub fn get(&self, name: &str) -> Option<&SkillDefinition> {
         self.skills.get(name)
@@ -278,6 +369,27 @@ impl SkillRegistry {
     }
 }
 
+/// Errors from skill registry operations
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SkillRegistryError {
+    #[error("Invalid skill: {0}")]
+    InvalidSkill(String),
+
+    #[error(
+        "Skill '{skill}' would create a dependency cycle between {} and {}",
+        .from.as_str(),
+        .to.as_str()
+    )]
+    CycleDetected {
+        skill: String,
+        from: AgentType,
+        to: AgentType,
+    },
+
+    #[error("Skill not found: {0}")]
+    NotFound(String),
+}
+
 /// Create the default skill registry with standard agent skills
 pub fn default_skill_registry() -> SkillRegistry {
     let mut registry = SkillRegistry::new();
@@ -434,6 +546,71 @@ mod tests {
         assert!(!skill.requirements_met(AgentState::Paused, &deps));
     }
 
+    #[test]
+    fn test_register_and_unregister_at_runtime() {
+        let mut registry = default_skill_registry();
+
+        registry
+            .try_register(
+                SkillDefinition::new("audit", vec![AgentType::Explorer]).with_timeout(120),
+            )
+            .unwrap();
+        assert!(registry.get("audit").is_some());
+        assert!(registry
+            .skills_for_type(AgentType::Explorer)
+            .iter()
+            .any(|s| s.name == "audit"));
+
+        // Re-registering replaces the definition without duplicating it
+        registry
+            .try_register(SkillDefinition::new("audit", vec![AgentType::Explorer]))
+            .unwrap();
+        let audits = registry
+            .skills_for_type(AgentType::Explorer)
+            .iter()
+            .filter(|s| s.name == "audit")
+            .count();
+        assert_eq!(audits, 1);
+
+        let removed = registry.unregister("audit").unwrap();
+        assert_eq!(removed.name, "audit");
+        assert!(registry.get("audit").is_none());
+        assert!(registry.unregister("audit").is_none());
+    }
+
+    #[test]
+    fn test_register_rejects_dependency_cycle() {
+        let mut registry = default_skill_registry();
+
+        // review already makes CodeReviewer depend on StoryDeveloper
+        let skill = SkillDefinition::new("wait_for_review", vec![AgentType::StoryDeveloper])
+            .requires_dependency(AgentType::CodeReviewer, AgentState::Completed);
+        let result = registry.try_register(skill);
+        assert!(matches!(
+            result,
+            Err(SkillRegistryError::CycleDetected { .. })
+        ));
+        assert!(registry.get("wait_for_review").is_none());
+
+        // A skill depending on its own agent type is also a cycle
+        let skill = SkillDefinition::new("self_wait", vec![AgentType::Explorer])
+            .requires_dependency(AgentType::Explorer, AgentState::Completed);
+        assert!(registry.try_register(skill).is_err());
+    }
+
+    #[test]
+    fn test_register_rejects_invalid_definition() {
+        let mut registry = SkillRegistry::new();
+        assert!(matches!(
+            registry.try_register(SkillDefinition::new("", vec![AgentType::Explorer])),
+            Err(SkillRegistryError::InvalidSkill(_))
+        ));
+        assert!(matches!(
+            registry.try_register(SkillDefinition::new("orphan", vec![])),
+            Err(SkillRegistryError::InvalidSkill(_))
+        ));
+    }
+
     #[test]
     fn test_skill_execution() {
         let definition =
@@ -2,6 +2,11 @@
 //!
 //! This module provides state transition management that respects
 //! dependencies between agents in the network.
+//!
+//! Besides the built-in [`AgentState`]s, a graph may declare custom states
+//! (e.g. `waiting_for_qa`). A custom state is a named sub-state of a
+//! built-in base state: agents report the base state everywhere else, and
+//! inherit the base state's unlabeled transitions.
 
 use super::{AgentId, DependencyCondition};
 use crate::{AgentState, AgentType};
@@ -20,6 +25,12 @@ pub struct StateTransition {
     pub propagates: Vec<StatePropagation>,
     /// Optional guard condition name (for runtime evaluation)
     pub guard: Option<String>,
+    /// Custom source state (None matches any sub-state of `from`)
+    pub from_label: Option<String>,
+    /// Custom target state (None enters the plain `to` state)
+    pub to_label: Option<String>,
+    /// Roles allowed to trigger this transition (empty allows anyone)
+    pub allowed_roles: Vec<String>,
 }
 
 impl StateTransition {
@@ -31,9 +42,36 @@ impl StateTransition {
             requires: Vec::new(),
             propagates: Vec::new(),
             guard: None,
+            from_label: None,
+            to_label: None,
+            allowed_roles: Vec::new(),
         }
     }
 
+    /// Only take this transition from the given custom state
+    pub fn from_custom(mut self, label: impl Into<String>) -> Self {
+        self.from_label = Some(label.into());
+        self
+    }
+
+    /// Enter the given custom state instead of the plain target state
+    pub fn to_custom(mut self, label: impl Into<String>) -> Self {
+        self.to_label = Some(label.into());
+        self
+    }
+
+    /// Restrict which roles may trigger this transition
+    pub fn with_allowed_roles(mut self, roles: Vec<String>) -> Self {
+        self.allowed_roles = roles;
+        self
+    }
+
+    /// Check if a role may trigger this transition
+    pub fn allows_role(&self, role: Option<&str>) -> bool {
+        self.allowed_roles.is_empty()
+            || role.is_some_and(|r| self.allowed_roles.iter().any(|allowed| allowed == r))
+    }
+
     /// Add a dependency requirement
     pub fn when(mut self, condition: DependencyCondition) -> Self {
         self.requires.push(condition);
@@ -77,7 +115,7 @@ impl StatePropagation {
 }
 
 /// Events that can be propagated to dependent agents
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PropagationEvent {
     /// Dependency is now ready
     DependencyReady,
@@ -91,6 +129,19 @@ pub enum PropagationEvent {
     Custom(String),
 }
 
+impl PropagationEvent {
+    /// Parse from a name; unknown names become [`PropagationEvent::Custom`]
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "dependency_ready" => Self::DependencyReady,
+            "dependency_completed" => Self::DependencyCompleted,
+            "dependency_failed" => Self::DependencyFailed,
+            "dependency_blocked" => Self::DependencyBlocked,
+            other => Self::Custom(other.to_string()),
+        }
+    }
+}
+
 /// State graph representing all possible states and transitions
 #[derive(Debug, Clone)]
 pub struct StateGraph {
@@ -100,6 +151,8 @@ pub struct StateGraph {
     initial: AgentState,
     /// Terminal states
     terminals: HashSet<AgentState>,
+    /// Custom states and the built-in state each one refines
+    custom_states: HashMap<String, AgentState>,
 }
 
 impl StateGraph {
@@ -109,7 +162,31 @@ impl StateGraph {
             transitions: HashMap::new(),
             initial,
             terminals: HashSet::new(),
+            custom_states: HashMap::new(),
+        }
+    }
+
+    /// Declare a custom state refining a built-in base state
+    pub fn add_custom_state(&mut self, name: impl Into<String>, base: AgentState) {
+        self.custom_states.insert(name.into(), base);
+    }
+
+    /// Get the base state of a custom state
+    pub fn custom_state_base(&self, name: &str) -> Option<AgentState> {
+        self.custom_states.get(name).copied()
+    }
+
+    /// Resolve a state name to its base state and custom label
+    pub fn resolve_state(&self, name: &str) -> Option<(AgentState, Option<String>)> {
+        if let Some(base) = self.custom_state_base(name) {
+            return Some((base, Some(name.to_string())));
         }
+        AgentState::from_str(name).ok().map(|state| (state, None))
+    }
+
+    /// Iterate over all transitions in the graph
+    pub fn all_transitions(&self) -> impl Iterator<Item = &StateTransition> {
+        self.transitions.values().flatten()
     }
 
     /// Add a transition
@@ -150,9 +227,27 @@ impl StateGraph {
         to: AgentState,
         dependency_states: &HashMap<AgentId, (AgentType, AgentState)>,
     ) -> Option<&StateTransition> {
-        self.transitions_from(from)
-            .iter()
-            .find(|t| t.to == to && t.can_take(dependency_states))
+        self.find_labeled_transition(from, None, to, None, dependency_states)
+    }
+
+    /// Find a valid transition between (possibly custom) states
+    ///
+    /// Transitions without a source label apply to every sub-state of their
+    /// source state, so custom states inherit their base state's transitions.
+    pub fn find_labeled_transition(
+        &self,
+        from: AgentState,
+        from_label: Option<&str>,
+        to: AgentState,
+        to_label: Option<&str>,
+        dependency_states: &HashMap<AgentId, (AgentType, AgentState)>,
+    ) -> Option<&StateTransition> {
+        self.transitions_from(from).iter().find(|t| {
+            t.to == to
+                && t.to_label.as_deref() == to_label
+                && (t.from_label.is_none() || t.from_label.as_deref() == from_label)
+                && t.can_take(dependency_states)
+        })
     }
 
     /// Get all reachable states from current state
@@ -174,6 +269,8 @@ impl StateGraph {
 pub struct StateMachine {
     /// Current state
     current: AgentState,
+    /// Current custom state, if the agent is in one
+    current_label: Option<String>,
     /// State graph defining valid transitions
     graph: StateGraph,
     /// History of state transitions
@@ -187,6 +284,8 @@ pub struct StateTransitionRecord {
     pub from: AgentState,
     /// New state
     pub to: AgentState,
+    /// New custom state (if any)
+    pub to_label: Option<String>,
     /// Timestamp
     pub timestamp: chrono::DateTime<chrono::Utc>,
     /// Trigger event (if any)
@@ -199,6 +298,7 @@ impl StateMachine {
         let initial = graph.initial_state();
         Self {
             current: initial,
+            current_label: None,
             graph,
             history: Vec::new(),
         }
@@ -209,6 +309,18 @@ impl StateMachine {
         self.current
     }
 
+    /// Get the current custom state, if any
+    pub fn current_label(&self) -> Option<&str> {
+        self.current_label.as_deref()
+    }
+
+    /// Get the name of the current state (custom name if in a custom state)
+    pub fn current_state_name(&self) -> &str {
+        self.current_label
+            .as_deref()
+            .unwrap_or_else(|| self.current.as_str())
+    }
+
     /// Check if the machine is in a terminal state
     pub fn is_terminal(&self) -> bool {
         self.graph.is_terminal(self.current)
@@ -220,20 +332,61 @@ impl StateMachine {
         to: AgentState,
         dependency_states: &HashMap<AgentId, (AgentType, AgentState)>,
         trigger: Option<String>,
+    ) -> Result<Vec<StatePropagation>, StateMachineError> {
+        self.apply_transition(to, None, dependency_states, trigger, None)
+    }
+
+    /// Attempt a transition to a built-in or custom state by name, as a role
+    pub fn transition_to_named(
+        &mut self,
+        name: &str,
+        dependency_states: &HashMap<AgentId, (AgentType, AgentState)>,
+        trigger: Option<String>,
+        role: Option<&str>,
+    ) -> Result<Vec<StatePropagation>, StateMachineError> {
+        let (to, to_label) = self
+            .graph
+            .resolve_state(name)
+            .ok_or_else(|| StateMachineError::UnknownState(name.to_string()))?;
+        self.apply_transition(to, to_label, dependency_states, trigger, role)
+    }
+
+    fn apply_transition(
+        &mut self,
+        to: AgentState,
+        to_label: Option<String>,
+        dependency_states: &HashMap<AgentId, (AgentType, AgentState)>,
+        trigger: Option<String>,
+        role: Option<&str>,
     ) -> Result<Vec<StatePropagation>, StateMachineError> {
         // Check if transition exists and is valid
         let transition = self
             .graph
-            .find_transition(self.current, to, dependency_states)
+            .find_labeled_transition(
+                self.current,
+                self.current_label.as_deref(),
+                to,
+                to_label.as_deref(),
+                dependency_states,
+            )
             .ok_or_else(|| StateMachineError::InvalidTransition {
                 from: self.current,
                 to,
             })?;
 
+        if !transition.allows_role(role) {
+            return Err(StateMachineError::RoleNotAllowed {
+                role: role.unwrap_or("none").to_string(),
+                from: self.current,
+                to,
+            });
+        }
+
         // Record the transition
         let record = StateTransitionRecord {
             from: self.current,
             to,
+            to_label: to_label.clone(),
             timestamp: chrono::Utc::now(),
             trigger,
         };
@@ -242,6 +395,7 @@ impl StateMachine {
         // Update state
         let propagations = transition.propagates.clone();
         self.current = to;
+        self.current_label = to_label;
 
         Ok(propagations)
     }
@@ -271,6 +425,16 @@ pub enum StateMachineError {
 
     #[error("Guard condition failed: {0}")]
     GuardFailed(String),
+
+    #[error("Unknown state: {0}")]
+    UnknownState(String),
+
+    #[error("Role '{role}' may not transition from {from:?} to {to:?}")]
+    RoleNotAllowed {
+        role: String,
+        from: AgentState,
+        to: AgentState,
+    },
 }
 
 /// Builder for creating state graphs
@@ -304,6 +468,12 @@ impl StateGraphBuilder {
         self
     }
 
+    /// Declare a custom state refining a built-in base state
+    pub fn custom_state(mut self, name: impl Into<String>, base: AgentState) -> Self {
+        self.graph.add_custom_state(name, base);
+        self
+    }
+
     /// Build the state graph
     pub fn build(self) -> StateGraph {
         self.graph
@@ -366,6 +536,55 @@ mod tests {
         assert!(machine.is_terminal());
     }
 
+    #[test]
+    fn test_custom_state_transitions() {
+        let graph = StateGraphBuilder::new(AgentState::Running)
+            .custom_state("waiting_for_qa", AgentState::WaitingForExternal)
+            .conditional_transition(
+                StateTransition::new(AgentState::Running, AgentState::WaitingForExternal)
+                    .to_custom("waiting_for_qa"),
+            )
+            .conditional_transition(
+                StateTransition::new(AgentState::WaitingForExternal, AgentState::Completed)
+                    .from_custom("waiting_for_qa")
+                    .with_allowed_roles(vec!["qa".to_string()]),
+            )
+            .transition(AgentState::WaitingForExternal, AgentState::Running)
+            .terminal(AgentState::Completed)
+            .build();
+        let deps = HashMap::new();
+
+        let mut machine = StateMachine::new(graph.clone());
+        machine
+            .transition_to_named("waiting_for_qa", &deps, None, None)
+            .unwrap();
+        assert_eq!(machine.current_state(), AgentState::WaitingForExternal);
+        assert_eq!(machine.current_state_name(), "waiting_for_qa");
+
+        // Only the qa role may sign off
+        let result = machine.transition_to_named("completed", &deps, None, Some("developer"));
+        assert!(matches!(result, Err(StateMachineError::RoleNotAllowed { .. })));
+        machine
+            .transition_to_named("completed", &deps, None, Some("qa"))
+            .unwrap();
+        assert!(machine.is_terminal());
+
+        // Custom states inherit unlabeled transitions of their base state
+        let mut machine = StateMachine::new(graph);
+        machine
+            .transition_to_named("waiting_for_qa", &deps, None, None)
+            .unwrap();
+        machine
+            .transition(AgentState::Running, &deps, None)
+            .unwrap();
+        assert_eq!(machine.current_state_name(), "running");
+
+        assert!(matches!(
+            machine.transition_to_named("bogus", &deps, None, None),
+            Err(StateMachineError::UnknownState(_))
+        ));
+    }
+
     #[test]
     fn test_invalid_transition() {
         let graph = default_agent_state_graph();
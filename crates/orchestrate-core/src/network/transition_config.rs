@@ -0,0 +1,224 @@
+//! Config-driven state machine transitions
+//!
+//! Lets deployments extend the agent state graph from YAML instead of
+//! patching the crate: declare custom states (refinements of built-in
+//! states, such as `waiting_for_qa`) and extra transitions with guards,
+//! side effects and allowed roles. Configs are checked by
+//! [`NetworkValidator::validate_state_config`] before they are applied.
+//!
+//! ```yaml
+//! states:
+//!   - name: waiting_for_qa
+//!     base: waiting_for_external
+//! transitions:
+//!   - from: running
+//!     to: waiting_for_qa
+//!   - from: waiting_for_qa
+//!     to: completed
+//!     allowed_roles: [qa]
+//!     guard:
+//!       all_of_type: { agent_type: code_reviewer, state: completed }
+//!     side_effects:
+//!       - propagate: { target_type: pr_shepherd, event: qa_approved }
+//! ```
+
+use super::validation::NetworkValidator;
+use super::{DependencyCondition, PropagationEvent, StateGraph, StatePropagation, StateTransition};
+use crate::{AgentState, AgentType, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A custom state refining a built-in state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomStateConfig {
+    /// Unique state name, e.g. "waiting_for_qa"
+    pub name: String,
+    /// Built-in state agents report while in this state
+    pub base: AgentState,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Side effect executed after a configured transition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SideEffectConfig {
+    /// Propagate an event to dependents of the given type
+    Propagate {
+        target_type: AgentType,
+        event: String,
+    },
+}
+
+/// A transition rule; `from` and `to` name built-in or custom states
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionRuleConfig {
+    pub from: String,
+    pub to: String,
+    /// Dependency condition that must hold for the transition
+    #[serde(default, with = "serde_yaml::with::singleton_map_recursive")]
+    pub guard: Option<DependencyCondition>,
+    #[serde(default, with = "serde_yaml::with::singleton_map_recursive")]
+    pub side_effects: Vec<SideEffectConfig>,
+    /// Roles allowed to trigger the transition (empty allows anyone)
+    #[serde(default)]
+    pub allowed_roles: Vec<String>,
+}
+
+/// Additional states and transitions layered onto a base state graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StateMachineConfig {
+    #[serde(default)]
+    pub states: Vec<CustomStateConfig>,
+    #[serde(default)]
+    pub transitions: Vec<TransitionRuleConfig>,
+}
+
+impl StateMachineConfig {
+    /// Parse a state machine config from YAML
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        serde_yaml::from_str(content)
+            .map_err(|e| Error::Config(format!("Invalid state machine config: {}", e)))
+    }
+
+    /// Load a state machine config from a YAML file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_yaml(&content)
+    }
+
+    /// Validate this config against `base` and return the extended graph
+    pub fn apply(&self, base: &StateGraph) -> Result<StateGraph> {
+        let result = NetworkValidator::new().validate_state_config(self, base);
+        if !result.is_valid {
+            let messages: Vec<String> = result.errors.iter().map(|e| e.message.clone()).collect();
+            return Err(Error::Config(format!(
+                "Invalid state machine config: {}",
+                messages.join("; ")
+            )));
+        }
+
+        let mut graph = base.clone();
+        for state in &self.states {
+            graph.add_custom_state(state.name.clone(), state.base);
+        }
+
+        for rule in &self.transitions {
+            // Validation guarantees both names resolve
+            let (from, from_label) = graph
+                .resolve_state(&rule.from)
+                .ok_or_else(|| Error::Config(format!("Unknown state: {}", rule.from)))?;
+            let (to, to_label) = graph
+                .resolve_state(&rule.to)
+                .ok_or_else(|| Error::Config(format!("Unknown state: {}", rule.to)))?;
+
+            let mut transition =
+                StateTransition::new(from, to).with_allowed_roles(rule.allowed_roles.clone());
+            if let Some(label) = from_label {
+                transition = transition.from_custom(label);
+            }
+            if let Some(label) = to_label {
+                transition = transition.to_custom(label);
+            }
+            if let Some(guard) = &rule.guard {
+                transition = transition.when(guard.clone());
+            }
+            for effect in &rule.side_effects {
+                match effect {
+                    SideEffectConfig::Propagate { target_type, event } => {
+                        transition = transition.propagate(StatePropagation::signal(
+                            *target_type,
+                            PropagationEvent::from_name(event),
+                        ));
+                    }
+                }
+            }
+
+            graph.add_transition(transition);
+        }
+
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::state::{default_agent_state_graph, StateMachine};
+    use std::collections::HashMap;
+
+    const QA_CONFIG: &str = r#"
+states:
+  - name: waiting_for_qa
+    base: waiting_for_external
+transitions:
+  - from: running
+    to: waiting_for_qa
+  - from: waiting_for_qa
+    to: completed
+    allowed_roles: [qa]
+    side_effects:
+      - propagate: { target_type: pr_shepherd, event: qa_approved }
+"#;
+
+    #[test]
+    fn test_apply_qa_config() {
+        let config = StateMachineConfig::from_yaml(QA_CONFIG).unwrap();
+        let graph = config.apply(&default_agent_state_graph()).unwrap();
+        assert_eq!(
+            graph.custom_state_base("waiting_for_qa"),
+            Some(AgentState::WaitingForExternal)
+        );
+
+        let deps = HashMap::new();
+        let mut machine = StateMachine::new(graph);
+        for state in ["initializing", "running", "waiting_for_qa"] {
+            machine
+                .transition_to_named(state, &deps, None, None)
+                .unwrap();
+        }
+        assert!(machine
+            .transition_to_named("completed", &deps, None, None)
+            .is_err());
+
+        let propagations = machine
+            .transition_to_named("completed", &deps, None, Some("qa"))
+            .unwrap();
+        assert_eq!(propagations.len(), 1);
+        assert_eq!(propagations[0].target_type, AgentType::PrShepherd);
+        assert_eq!(
+            propagations[0].event,
+            PropagationEvent::Custom("qa_approved".to_string())
+        );
+    }
+
+    #[test]
+    fn test_guard_from_config() {
+        let yaml = r#"
+transitions:
+  - from: running
+    to: waiting_for_input
+    guard:
+      all_of_type: { agent_type: code_reviewer, state: completed }
+"#;
+        let config = StateMachineConfig::from_yaml(yaml).unwrap();
+        let graph = config.apply(&default_agent_state_graph()).unwrap();
+        let transition = graph
+            .transitions_from(AgentState::Running)
+            .iter()
+            .find(|t| t.to == AgentState::WaitingForInput)
+            .unwrap();
+        assert_eq!(transition.requires.len(), 1);
+    }
+
+    #[test]
+    fn test_apply_rejects_invalid_config() {
+        let yaml = r#"
+transitions:
+  - from: running
+    to: waiting_for_qa
+"#;
+        let config = StateMachineConfig::from_yaml(yaml).unwrap();
+        assert!(config.apply(&default_agent_state_graph()).is_err());
+    }
+}
@@ -4,10 +4,12 @@
 //! - Runtime state invariant checks
 //! - Dependency satisfaction verification
 //! - Network consistency validation
+//! - State machine configuration checks
 
-use super::{AgentHandle, AgentId, DependencyGraph};
+use super::transition_config::StateMachineConfig;
+use super::{AgentHandle, AgentId, DependencyGraph, StateGraph};
 use crate::AgentState;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Result of network validation
 #[derive(Debug, Clone)]
@@ -114,6 +116,8 @@ pub enum ValidationErrorCode {
     TimeoutExceeded,
     /// Skill requirements not met
     SkillRequirementsNotMet,
+    /// State machine configuration is invalid
+    InvalidStateConfig,
 }
 
 /// Validation warning (non-fatal issue)
@@ -155,6 +159,8 @@ pub enum ValidationWarningCode {
     ManyDependencies,
     /// State unchanged for a long time
     StaleState,
+    /// Custom state that no transition enters
+    UnreachableState,
 }
 
 /// Network validator
@@ -320,6 +326,108 @@ impl NetworkValidator {
         }
     }
 
+    /// Validate a state machine config against the graph it extends
+    pub fn validate_state_config(
+        &self,
+        config: &StateMachineConfig,
+        base: &StateGraph,
+    ) -> ValidationResult {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let config_error =
+            |message: String| ValidationError::new(ValidationErrorCode::InvalidStateConfig, message);
+
+        // Custom states must have unique names that don't shadow built-ins
+        let mut custom_states: HashMap<&str, AgentState> = HashMap::new();
+        for state in &config.states {
+            let name = state.name.as_str();
+            if name.trim().is_empty() {
+                errors.push(config_error("Custom state name cannot be empty".to_string()));
+            } else if AgentState::from_str(name).is_ok() {
+                errors.push(config_error(format!(
+                    "Custom state '{}' conflicts with a built-in state",
+                    name
+                )));
+            } else if base.custom_state_base(name).is_some()
+                || custom_states.insert(name, state.base).is_some()
+            {
+                errors.push(config_error(format!("Duplicate custom state '{}'", name)));
+            }
+        }
+
+        let resolve = |name: &str| -> Option<AgentState> {
+            custom_states
+                .get(name)
+                .copied()
+                .or_else(|| base.resolve_state(name).map(|(state, _)| state))
+        };
+
+        let mut seen_rules = HashSet::new();
+        let mut entered = HashSet::new();
+        for rule in &config.transitions {
+            let from = resolve(&rule.from);
+            let to = resolve(&rule.to);
+            for (name, resolved) in [(&rule.from, from), (&rule.to, to)] {
+                if resolved.is_none() {
+                    errors.push(
+                        config_error(format!(
+                            "Transition {} -> {} references unknown state '{}'",
+                            rule.from, rule.to, name
+                        ))
+                        .with_suggestion(format!("Declare '{}' under states", name)),
+                    );
+                }
+            }
+
+            if let Some(from) = from {
+                if base.is_terminal(from) {
+                    errors.push(config_error(format!(
+                        "Transition {} -> {} leaves a terminal state",
+                        rule.from, rule.to
+                    )));
+                }
+            }
+
+            if rule.from == rule.to {
+                errors.push(config_error(format!(
+                    "Transition {} -> {} does not change state",
+                    rule.from, rule.to
+                )));
+            }
+
+            if !seen_rules.insert((rule.from.as_str(), rule.to.as_str())) {
+                errors.push(config_error(format!(
+                    "Duplicate transition {} -> {}",
+                    rule.from, rule.to
+                )));
+            }
+
+            if rule.allowed_roles.iter().any(|r| r.trim().is_empty()) {
+                errors.push(config_error(format!(
+                    "Transition {} -> {} has an empty role",
+                    rule.from, rule.to
+                )));
+            }
+
+            entered.insert(rule.to.as_str());
+        }
+
+        for state in &config.states {
+            if !entered.contains(state.name.as_str()) {
+                warnings.push(ValidationWarning::new(
+                    ValidationWarningCode::UnreachableState,
+                    format!("No transition enters custom state '{}'", state.name),
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            ValidationResult::success().with_warnings(warnings)
+        } else {
+            ValidationResult::failure(errors).with_warnings(warnings)
+        }
+    }
+
     /// Validate a proposed state transition
     pub fn validate_transition(
         &self,
@@ -502,4 +610,53 @@ mod tests {
             .iter()
             .any(|e| e.code == ValidationErrorCode::DependencyStateInvalid));
     }
+
+    #[test]
+    fn test_validate_state_config() {
+        use super::super::state::default_agent_state_graph;
+
+        let validator = NetworkValidator::new();
+        let base = default_agent_state_graph();
+
+        let config = StateMachineConfig::from_yaml(
+            r#"
+states:
+  - name: waiting_for_qa
+    base: waiting_for_external
+  - name: staging
+    base: paused
+transitions:
+  - from: running
+    to: waiting_for_qa
+"#,
+        )
+        .unwrap();
+        let result = validator.validate_state_config(&config, &base);
+        assert!(result.is_valid);
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.code == ValidationWarningCode::UnreachableState));
+
+        let config = StateMachineConfig::from_yaml(
+            r#"
+states:
+  - name: running
+    base: running
+transitions:
+  - from: completed
+    to: running
+  - from: running
+    to: nowhere
+"#,
+        )
+        .unwrap();
+        let result = validator.validate_state_config(&config, &base);
+        assert!(!result.is_valid);
+        assert_eq!(result.errors.len(), 3);
+        assert!(result
+            .errors
+            .iter()
+            .all(|e| e.code == ValidationErrorCode::InvalidStateConfig));
+    }
 }
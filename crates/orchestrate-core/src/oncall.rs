@@ -0,0 +1,230 @@
+//! On-call Paging
+//!
+//! Provider-neutral records of the incidents and alerts paged to an on-call
+//! provider. Every event for a resource carries the same deduplication key
+//! (`orchestrate:incident:<id>` or `orchestrate:alert:<id>`), which is how
+//! acknowledgements and resolutions reported by the provider are matched
+//! back to the incident or alert, and how resolutions made in orchestrate
+//! close the provider's page.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// Prefix of the deduplication keys orchestrate sends
+const DEDUP_KEY_PREFIX: &str = "orchestrate";
+
+/// On-call provider a page was sent to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OncallProvider {
+    PagerDuty,
+    Opsgenie,
+}
+
+impl OncallProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PagerDuty => "pagerduty",
+            Self::Opsgenie => "opsgenie",
+        }
+    }
+}
+
+impl FromStr for OncallProvider {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "pagerduty" => Ok(Self::PagerDuty),
+            "opsgenie" => Ok(Self::Opsgenie),
+            _ => Err(Error::Other(format!("Unknown on-call provider: {}", s))),
+        }
+    }
+}
+
+/// Incident or alert a page is about
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum PagedResource {
+    Incident(String),
+    Alert(String),
+}
+
+impl PagedResource {
+    /// `incident` or `alert`
+    pub fn resource_type(&self) -> &'static str {
+        match self {
+            Self::Incident(_) => "incident",
+            Self::Alert(_) => "alert",
+        }
+    }
+
+    /// ID of the incident or alert
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Incident(id) | Self::Alert(id) => id,
+        }
+    }
+
+    /// Deduplication key sent with every event about this resource
+    pub fn dedup_key(&self) -> String {
+        format!(
+            "{}:{}:{}",
+            DEDUP_KEY_PREFIX,
+            self.resource_type(),
+            self.id()
+        )
+    }
+
+    /// Resource a deduplication key sent by orchestrate refers to
+    pub fn from_dedup_key(key: &str) -> Option<Self> {
+        let rest = key.strip_prefix(DEDUP_KEY_PREFIX)?.strip_prefix(':')?;
+        let (resource_type, id) = rest.split_once(':')?;
+        if id.is_empty() {
+            return None;
+        }
+        Self::from_parts(resource_type, id).ok()
+    }
+
+    /// Resource from its type and ID as stored in the database
+    pub fn from_parts(resource_type: &str, id: &str) -> Result<Self> {
+        match resource_type {
+            "incident" => Ok(Self::Incident(id.to_string())),
+            "alert" => Ok(Self::Alert(id.to_string())),
+            _ => Err(Error::Other(format!(
+                "Unknown paged resource type: {}",
+                resource_type
+            ))),
+        }
+    }
+}
+
+/// State of a page at the provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OncallPageStatus {
+    Triggered,
+    Acknowledged,
+    Resolved,
+}
+
+impl OncallPageStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Triggered => "triggered",
+            Self::Acknowledged => "acknowledged",
+            Self::Resolved => "resolved",
+        }
+    }
+}
+
+impl FromStr for OncallPageStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "triggered" => Ok(Self::Triggered),
+            "acknowledged" => Ok(Self::Acknowledged),
+            "resolved" => Ok(Self::Resolved),
+            _ => Err(Error::Other(format!("Unknown page status: {}", s))),
+        }
+    }
+}
+
+/// An incident or alert paged to one provider service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OncallPage {
+    pub id: Option<i64>,
+    pub provider: OncallProvider,
+    /// PagerDuty integration key or Opsgenie team that was paged
+    pub routing_key: String,
+    pub resource: PagedResource,
+    pub status: OncallPageStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OncallPage {
+    /// A newly triggered page
+    pub fn new(
+        provider: OncallProvider,
+        routing_key: impl Into<String>,
+        resource: PagedResource,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            provider,
+            routing_key: routing_key.into(),
+            resource,
+            status: OncallPageStatus::Triggered,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Deduplication key of the paged resource
+    pub fn dedup_key(&self) -> String {
+        self.resource.dedup_key()
+    }
+
+    /// Whether the page has not been resolved yet
+    pub fn is_open(&self) -> bool {
+        self.status != OncallPageStatus::Resolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup_key_round_trip() {
+        let incident = PagedResource::Incident("INC-20240101120000".to_string());
+        assert_eq!(
+            incident.dedup_key(),
+            "orchestrate:incident:INC-20240101120000"
+        );
+        assert_eq!(
+            PagedResource::from_dedup_key(&incident.dedup_key()),
+            Some(incident)
+        );
+
+        let alert = PagedResource::Alert("42".to_string());
+        assert_eq!(alert.dedup_key(), "orchestrate:alert:42");
+        assert_eq!(
+            PagedResource::from_dedup_key("orchestrate:alert:42"),
+            Some(alert)
+        );
+    }
+
+    #[test]
+    fn test_foreign_dedup_keys_are_ignored() {
+        assert_eq!(PagedResource::from_dedup_key("srv01/HTTP"), None);
+        assert_eq!(PagedResource::from_dedup_key("orchestrate:deploy:1"), None);
+        assert_eq!(PagedResource::from_dedup_key("orchestrate:incident:"), None);
+    }
+
+    #[test]
+    fn test_page_status_parsing() {
+        for status in [
+            OncallPageStatus::Triggered,
+            OncallPageStatus::Acknowledged,
+            OncallPageStatus::Resolved,
+        ] {
+            assert_eq!(status.as_str().parse::<OncallPageStatus>().unwrap(), status);
+        }
+        assert!("open".parse::<OncallPageStatus>().is_err());
+        assert_eq!(
+            "PagerDuty".parse::<OncallProvider>().unwrap(),
+            OncallProvider::PagerDuty
+        );
+        assert_eq!(
+            "opsgenie".parse::<OncallProvider>().unwrap(),
+            OncallProvider::Opsgenie
+        );
+    }
+}
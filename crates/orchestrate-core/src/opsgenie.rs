@@ -0,0 +1,433 @@
+//! Opsgenie Integration
+//!
+//! Creates, acknowledges, and closes Opsgenie alerts through the Alert API v2:
+//! - [`OpsgenieAlert`]: an alert for an incident or a monitoring alert,
+//!   aliased by [`PagedResource::dedup_key`] so repeated pages are
+//!   deduplicated and later requests can address the alert
+//! - [`OpsgenieClient`]: authenticates with an API integration key
+//!
+//! Priorities map from alert severities as critical → P1, warning → P3, and
+//! info → P5, and from incident severities as critical → P1, high → P2,
+//! medium → P3, and low → P4.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::incident::{Incident, IncidentSeverity};
+use crate::monitoring::{Alert, AlertSeverity};
+use crate::oncall::PagedResource;
+use crate::{Error, Result};
+
+/// Alert API base URL
+pub const OPSGENIE_API_URL: &str = "https://api.opsgenie.com";
+
+/// Alert API base URL for accounts in the EU region
+pub const OPSGENIE_EU_API_URL: &str = "https://api.eu.opsgenie.com";
+
+/// Longest message Opsgenie accepts
+const MAX_MESSAGE_LEN: usize = 130;
+
+/// Longest description Opsgenie accepts
+const MAX_DESCRIPTION_LEN: usize = 15000;
+
+/// Source reported for every request
+const ALERT_SOURCE: &str = "orchestrate";
+
+/// Alert priority, P1 being the most urgent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum OpsgeniePriority {
+    P1,
+    P2,
+    P3,
+    P4,
+    P5,
+}
+
+impl OpsgeniePriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::P1 => "P1",
+            Self::P2 => "P2",
+            Self::P3 => "P3",
+            Self::P4 => "P4",
+            Self::P5 => "P5",
+        }
+    }
+}
+
+impl From<&AlertSeverity> for OpsgeniePriority {
+    fn from(severity: &AlertSeverity) -> Self {
+        match severity {
+            AlertSeverity::Critical => Self::P1,
+            AlertSeverity::Warning => Self::P3,
+            AlertSeverity::Info => Self::P5,
+        }
+    }
+}
+
+impl From<IncidentSeverity> for OpsgeniePriority {
+    fn from(severity: IncidentSeverity) -> Self {
+        match severity {
+            IncidentSeverity::Critical => Self::P1,
+            IncidentSeverity::High => Self::P2,
+            IncidentSeverity::Medium => Self::P3,
+            IncidentSeverity::Low => Self::P4,
+        }
+    }
+}
+
+/// Team notified of an alert
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpsgenieResponder {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub responder_type: String,
+}
+
+impl OpsgenieResponder {
+    /// Responder team by name
+    pub fn team(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            responder_type: "team".to_string(),
+        }
+    }
+}
+
+/// Alert created with the Alert API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpsgenieAlert {
+    pub message: String,
+    pub alias: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Teams to notify; when empty the API integration's own routing applies
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub responders: Vec<OpsgenieResponder>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity: Option<String>,
+    pub source: String,
+    pub priority: OpsgeniePriority,
+}
+
+impl OpsgenieAlert {
+    /// Alert for an incident
+    pub fn for_incident(incident: &Incident, responders: Vec<OpsgenieResponder>) -> Self {
+        let mut details = BTreeMap::new();
+        details.insert("incident_id".to_string(), incident.id.clone());
+        details.insert("status".to_string(), incident.status.as_str().to_string());
+        details.insert("detected_at".to_string(), incident.detected_at.to_rfc3339());
+
+        Self {
+            message: truncate(
+                &format!("[{}] {}", incident.id, incident.title),
+                MAX_MESSAGE_LEN,
+            ),
+            alias: PagedResource::Incident(incident.id.clone()).dedup_key(),
+            description: (!incident.description.is_empty())
+                .then(|| truncate(&incident.description, MAX_DESCRIPTION_LEN)),
+            responders,
+            tags: incident.tags.clone(),
+            details,
+            entity: incident.affected_services.first().cloned(),
+            source: ALERT_SOURCE.to_string(),
+            priority: incident.severity.into(),
+        }
+    }
+
+    /// Alert for a firing monitoring alert
+    pub fn for_alert(alert: &Alert, responders: Vec<OpsgenieResponder>) -> Self {
+        let message = if alert.message.is_empty() {
+            format!("Alert {} is firing", alert.rule_name)
+        } else {
+            format!("{}: {}", alert.rule_name, alert.message)
+        };
+        let mut details: BTreeMap<String, String> = alert
+            .labels
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        details.insert("alert_id".to_string(), alert.id.clone());
+        details.insert("rule".to_string(), alert.rule_name.clone());
+        if let Some(value) = alert.current_value {
+            details.insert("current_value".to_string(), value.to_string());
+        }
+        if let Some(threshold) = alert.threshold {
+            details.insert("threshold".to_string(), threshold.to_string());
+        }
+
+        Self {
+            message: truncate(&message, MAX_MESSAGE_LEN),
+            alias: PagedResource::Alert(alert.id.clone()).dedup_key(),
+            description: (!alert.message.is_empty())
+                .then(|| truncate(&alert.message, MAX_DESCRIPTION_LEN)),
+            responders,
+            tags: vec![format!("rule:{}", alert.rule_name)],
+            details,
+            entity: alert.labels.get("component").cloned(),
+            source: ALERT_SOURCE.to_string(),
+            priority: (&alert.severity).into(),
+        }
+    }
+}
+
+/// Truncate text to the length Opsgenie accepts
+fn truncate(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_len - 3).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+/// Alert API response
+#[derive(Debug, Clone, Deserialize)]
+struct ApiResponse {
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default, rename = "requestId")]
+    request_id: Option<String>,
+}
+
+/// Alert API v2 client authenticated with an API integration key
+#[derive(Debug, Clone)]
+pub struct OpsgenieClient {
+    http_client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+}
+
+impl OpsgenieClient {
+    /// Create a client with a 10 second request timeout
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            api_key: api_key.into(),
+            base_url: OPSGENIE_API_URL.to_string(),
+        }
+    }
+
+    /// Client configured by `OPSGENIE_API_KEY` and `OPSGENIE_API_URL`, if set
+    pub fn from_env() -> Option<Self> {
+        let api_key = std::env::var("OPSGENIE_API_KEY")
+            .ok()
+            .filter(|key| !key.is_empty())?;
+        let client = Self::new(api_key);
+        Some(match std::env::var("OPSGENIE_API_URL") {
+            Ok(url) if !url.is_empty() => client.with_base_url(url),
+            _ => client,
+        })
+    }
+
+    /// Send requests to a different API base URL, e.g. [`OPSGENIE_EU_API_URL`]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Create an alert, returning the request ID
+    ///
+    /// Opsgenie processes requests asynchronously; an open alert with the
+    /// same alias is deduplicated instead of creating another.
+    pub async fn create_alert(&self, alert: &OpsgenieAlert) -> Result<String> {
+        self.call(
+            "create alert",
+            &format!("{}/v2/alerts", self.base_url),
+            alert,
+        )
+        .await
+    }
+
+    /// Acknowledge the alert with an alias
+    pub async fn acknowledge_alert(&self, alias: &str, note: &str) -> Result<String> {
+        self.alert_action(alias, "acknowledge", note).await
+    }
+
+    /// Close the alert with an alias
+    pub async fn close_alert(&self, alias: &str, note: &str) -> Result<String> {
+        self.alert_action(alias, "close", note).await
+    }
+
+    async fn alert_action(&self, alias: &str, action: &str, note: &str) -> Result<String> {
+        let url = format!(
+            "{}/v2/alerts/{}/{}?identifierType=alias",
+            self.base_url, alias, action
+        );
+        let body = serde_json::json!({
+            "source": ALERT_SOURCE,
+            "user": ALERT_SOURCE,
+            "note": note,
+        });
+        self.call(&format!("{} alert", action), &url, &body).await
+    }
+
+    async fn call(&self, operation: &str, url: &str, body: &impl Serialize) -> Result<String> {
+        let response = self
+            .http_client
+            .post(url)
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("GenieKey {}", self.api_key),
+            )
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| Error::Other(format!("Opsgenie {} request failed: {}", operation, e)))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(Error::Other(format!(
+                "Opsgenie rate limited {}, retry later",
+                operation
+            )));
+        }
+        let body: ApiResponse = response.json().await.map_err(|e| {
+            Error::Other(format!(
+                "Invalid Opsgenie {} response (HTTP {}): {}",
+                operation, status, e
+            ))
+        })?;
+
+        if !status.is_success() {
+            return Err(Error::Other(format!(
+                "Opsgenie {} failed (HTTP {}): {}",
+                operation,
+                status,
+                body.message.as_deref().unwrap_or("unknown error")
+            )));
+        }
+        Ok(body.request_id.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitoring::AlertRule;
+    use crate::test_servers::{json_body, serve};
+
+    #[test]
+    fn test_priority_mapping() {
+        assert_eq!(
+            OpsgeniePriority::from(&AlertSeverity::Critical),
+            OpsgeniePriority::P1
+        );
+        assert_eq!(
+            OpsgeniePriority::from(&AlertSeverity::Warning),
+            OpsgeniePriority::P3
+        );
+        assert_eq!(
+            OpsgeniePriority::from(&AlertSeverity::Info),
+            OpsgeniePriority::P5
+        );
+        assert_eq!(
+            OpsgeniePriority::from(IncidentSeverity::High),
+            OpsgeniePriority::P2
+        );
+        assert_eq!(
+            OpsgeniePriority::from(IncidentSeverity::Low),
+            OpsgeniePriority::P4
+        );
+    }
+
+    #[test]
+    fn test_alert_for_monitoring_alert() {
+        let rule = AlertRule::new("queue-backup", "queue_depth > 100", AlertSeverity::Critical)
+            .with_threshold(100.0);
+        let mut alert = Alert::new(&rule, "Queue depth 250");
+        alert.id = "7".to_string();
+
+        let opsgenie = OpsgenieAlert::for_alert(&alert, vec![OpsgenieResponder::team("sre")]);
+        let json = serde_json::to_value(&opsgenie).unwrap();
+        assert_eq!(json["alias"], "orchestrate:alert:7");
+        assert_eq!(json["priority"], "P1");
+        assert_eq!(json["message"], "queue-backup: Queue depth 250");
+        assert_eq!(json["responders"][0]["type"], "team");
+        assert_eq!(json["details"]["threshold"], "100");
+        assert!(json.get("entity").is_none());
+    }
+
+    #[test]
+    fn test_alert_for_incident_truncates_message() {
+        let incident = Incident::new("INC-1", &"x".repeat(200), IncidentSeverity::Medium);
+        let opsgenie = OpsgenieAlert::for_incident(&incident, Vec::new());
+        assert_eq!(opsgenie.message.chars().count(), MAX_MESSAGE_LEN);
+        assert_eq!(opsgenie.priority, OpsgeniePriority::P3);
+        assert_eq!(opsgenie.alias, "orchestrate:incident:INC-1");
+
+        let json = serde_json::to_value(&opsgenie).unwrap();
+        assert!(json.get("responders").is_none());
+        assert!(json.get("description").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_alert() {
+        let (url, server) = serve(vec![(
+            202,
+            r#"{"result":"Request will be processed","took":0.302,"requestId":"43a29c5c-3dbf-4fa4-9c26-f4f71023e120"}"#,
+        )])
+        .await;
+        let client = OpsgenieClient::new("genie-key").with_base_url(url);
+
+        let incident = Incident::new("INC-1", "API down", IncidentSeverity::Critical);
+        let request_id = client
+            .create_alert(&OpsgenieAlert::for_incident(&incident, Vec::new()))
+            .await
+            .unwrap();
+        assert_eq!(request_id, "43a29c5c-3dbf-4fa4-9c26-f4f71023e120");
+
+        let request = server.await.unwrap().remove(0);
+        assert!(request.starts_with("POST /v2/alerts HTTP/1.1"));
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("authorization: geniekey genie-key"));
+        assert_eq!(json_body(&request)["priority"], "P1");
+    }
+
+    #[tokio::test]
+    async fn test_close_alert_by_alias() {
+        let (url, server) = serve(vec![(
+            202,
+            r#"{"result":"Request will be processed","took":0.1,"requestId":"r-1"}"#,
+        )])
+        .await;
+        let client = OpsgenieClient::new("genie-key").with_base_url(url);
+
+        client
+            .close_alert("orchestrate:alert:7", "Resolved in orchestrate")
+            .await
+            .unwrap();
+
+        let request = server.await.unwrap().remove(0);
+        assert!(
+            request.starts_with("POST /v2/alerts/orchestrate:alert:7/close?identifierType=alias")
+        );
+        assert_eq!(json_body(&request)["note"], "Resolved in orchestrate");
+    }
+
+    #[tokio::test]
+    async fn test_api_errors_are_reported() {
+        let (url, _server) = serve(vec![(
+            422,
+            r#"{"message":"Request body is not processable. Please check the errors.","took":0.0,"requestId":"r-2"}"#,
+        )])
+        .await;
+        let client = OpsgenieClient::new("genie-key").with_base_url(url);
+
+        let err = client
+            .close_alert("orchestrate:alert:7", "Resolved")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("422"));
+        assert!(err.to_string().contains("not processable"));
+    }
+}
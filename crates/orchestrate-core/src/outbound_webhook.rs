@@ -0,0 +1,709 @@
+//! Outbound Webhooks
+//!
+//! Subscribers register a URL and the events they want (`agent.completed`,
+//! `pr.merged`, `pipeline.failed`, ...). When an event occurs a delivery is
+//! logged for every matching enabled subscriber, and the
+//! [`OutboundWebhookDispatcher`] posts the JSON payload, retrying failed
+//! attempts with exponential backoff.
+//!
+//! Payloads are signed with the subscriber's secret. Each request carries:
+//!
+//! - `X-Orchestrate-Event`: the event type
+//! - `X-Orchestrate-Delivery`: the delivery ID, stable across retries
+//! - `X-Orchestrate-Signature-256`: `sha256=<hex HMAC-SHA256 of the body>`
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+use crate::{Agent, AgentState, Database, Error, PipelineRun, PipelineRunStatus, Result};
+
+/// Attempts made for a delivery before it is marked failed
+pub const DEFAULT_MAX_ATTEMPTS: i32 = 5;
+
+/// Delay before the first retry, doubled for every later retry
+const BASE_RETRY_DELAY_SECS: i64 = 30;
+
+/// Longest delay between retries
+const MAX_RETRY_DELAY_SECS: i64 = 3600;
+
+/// Events subscribers can register for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutboundEventType {
+    /// An agent completed successfully
+    #[serde(rename = "agent.completed")]
+    AgentCompleted,
+    /// An agent failed
+    #[serde(rename = "agent.failed")]
+    AgentFailed,
+    /// A pull request was merged
+    #[serde(rename = "pr.merged")]
+    PrMerged,
+    /// A pipeline run succeeded
+    #[serde(rename = "pipeline.succeeded")]
+    PipelineSucceeded,
+    /// A pipeline run failed
+    #[serde(rename = "pipeline.failed")]
+    PipelineFailed,
+    /// Test delivery sent with `orchestrate webhook outbound test`
+    #[serde(rename = "ping")]
+    Ping,
+}
+
+impl OutboundEventType {
+    /// All event types
+    pub const ALL: [OutboundEventType; 6] = [
+        Self::AgentCompleted,
+        Self::AgentFailed,
+        Self::PrMerged,
+        Self::PipelineSucceeded,
+        Self::PipelineFailed,
+        Self::Ping,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AgentCompleted => "agent.completed",
+            Self::AgentFailed => "agent.failed",
+            Self::PrMerged => "pr.merged",
+            Self::PipelineSucceeded => "pipeline.succeeded",
+            Self::PipelineFailed => "pipeline.failed",
+            Self::Ping => "ping",
+        }
+    }
+}
+
+impl FromStr for OutboundEventType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str() == s)
+            .ok_or_else(|| Error::Other(format!("Invalid outbound event type: {}", s)))
+    }
+}
+
+/// An event to deliver to subscribers
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboundEvent {
+    /// Event type
+    pub event_type: OutboundEventType,
+    /// Event details
+    pub data: Value,
+    /// When the event occurred
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl OutboundEvent {
+    /// Create an event that occurred now
+    pub fn new(event_type: OutboundEventType, data: Value) -> Self {
+        Self {
+            event_type,
+            data,
+            occurred_at: Utc::now(),
+        }
+    }
+
+    /// Event for an agent that finished, `None` unless it completed or failed
+    pub fn for_agent(agent: &Agent) -> Option<Self> {
+        let event_type = match agent.state {
+            AgentState::Completed => OutboundEventType::AgentCompleted,
+            AgentState::Failed => OutboundEventType::AgentFailed,
+            _ => return None,
+        };
+        Some(Self::new(
+            event_type,
+            json!({
+                "agent_id": agent.id.to_string(),
+                "agent_type": agent.agent_type.as_str(),
+                "custom_type": agent.custom_type,
+                "task": agent.task,
+                "state": agent.state.as_str(),
+                "error_message": agent.error_message,
+            }),
+        ))
+    }
+
+    /// Event for a pipeline run that finished, `None` unless it succeeded or failed
+    pub fn for_pipeline_run(run: &PipelineRun, pipeline_name: &str) -> Option<Self> {
+        let event_type = match run.status {
+            PipelineRunStatus::Succeeded => OutboundEventType::PipelineSucceeded,
+            PipelineRunStatus::Failed => OutboundEventType::PipelineFailed,
+            _ => return None,
+        };
+        Some(Self::new(
+            event_type,
+            json!({
+                "pipeline": pipeline_name,
+                "run_id": run.id,
+                "status": run.status.as_str(),
+                "trigger_event": run.trigger_event,
+                "commit_sha": run.commit_sha,
+                "started_at": run.started_at,
+                "completed_at": run.completed_at,
+            }),
+        ))
+    }
+
+    /// JSON body posted to subscribers
+    pub fn payload(&self) -> Value {
+        json!({
+            "event": self.event_type.as_str(),
+            "occurred_at": self.occurred_at.to_rfc3339(),
+            "data": self.data,
+        })
+    }
+}
+
+/// A subscriber registered for outbound events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboundWebhook {
+    /// Database ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    /// URL payloads are posted to
+    pub url: String,
+    /// Secret used to sign payloads
+    #[serde(skip_serializing)]
+    pub secret: Option<String>,
+    /// Event filters: an event type, a prefix such as `pipeline.*`, or `*`
+    pub events: Vec<String>,
+    /// Description
+    pub description: Option<String>,
+    /// Whether deliveries are sent
+    pub enabled: bool,
+    /// Created timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+impl OutboundWebhook {
+    /// Create a subscriber for the given event filters
+    pub fn new(url: String, events: Vec<String>) -> Self {
+        Self {
+            id: None,
+            url,
+            secret: None,
+            events,
+            description: None,
+            enabled: true,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Set the signing secret
+    pub fn with_secret(mut self, secret: impl Into<String>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Set the description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Check the URL and event filters
+    pub fn validate(&self) -> Result<()> {
+        if !self.url.starts_with("http://") && !self.url.starts_with("https://") {
+            return Err(Error::Other(format!(
+                "Outbound webhook URL must start with http:// or https://: {}",
+                self.url
+            )));
+        }
+        if self.events.is_empty() {
+            return Err(Error::Other(
+                "Outbound webhook must subscribe to at least one event".to_string(),
+            ));
+        }
+        for filter in &self.events {
+            let known = filter == "*"
+                || filter.strip_suffix(".*").is_some_and(|prefix| {
+                    OutboundEventType::ALL
+                        .iter()
+                        .any(|event_type| event_type.as_str().split('.').next() == Some(prefix))
+                })
+                || OutboundEventType::from_str(filter).is_ok();
+            if !known {
+                return Err(Error::Other(format!(
+                    "Unknown outbound event filter '{}'",
+                    filter
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the subscriber wants events of this type
+    ///
+    /// Test deliveries (`ping`) go to every subscriber.
+    pub fn matches(&self, event_type: OutboundEventType) -> bool {
+        let event = event_type.as_str();
+        event_type == OutboundEventType::Ping
+            || self.events.iter().any(|filter| {
+                filter == "*"
+                    || filter == event
+                    || filter
+                        .strip_suffix(".*")
+                        .is_some_and(|prefix| event.split('.').next() == Some(prefix))
+            })
+    }
+}
+
+/// Status of an outbound delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboundDeliveryStatus {
+    /// Waiting for its next attempt
+    Pending,
+    /// The subscriber accepted the payload
+    Succeeded,
+    /// Every attempt failed
+    Failed,
+}
+
+impl OutboundDeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for OutboundDeliveryStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            _ => Err(Error::Other(format!(
+                "Invalid outbound delivery status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Logged delivery of an event to one subscriber
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboundWebhookDelivery {
+    /// Database ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    /// Subscriber the payload is posted to
+    pub webhook_id: i64,
+    /// Event type
+    pub event_type: String,
+    /// JSON body
+    pub payload: String,
+    /// Current status
+    pub status: OutboundDeliveryStatus,
+    /// Attempts made so far
+    pub attempts: i32,
+    /// Attempts made before the delivery is marked failed
+    pub max_attempts: i32,
+    /// HTTP status of the last attempt, if the subscriber responded
+    pub response_status: Option<i32>,
+    /// Error of the last failed attempt
+    pub error_message: Option<String>,
+    /// When the next attempt is due
+    pub next_attempt_at: DateTime<Utc>,
+    /// Created timestamp
+    pub created_at: DateTime<Utc>,
+    /// When the subscriber accepted the payload
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl OutboundWebhookDelivery {
+    /// Create a delivery of the event, due now
+    pub fn new(webhook_id: i64, event: &OutboundEvent) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            webhook_id,
+            event_type: event.event_type.as_str().to_string(),
+            payload: event.payload().to_string(),
+            status: OutboundDeliveryStatus::Pending,
+            attempts: 0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            response_status: None,
+            error_message: None,
+            next_attempt_at: now,
+            created_at: now,
+            delivered_at: None,
+        }
+    }
+
+    /// Record an attempt the subscriber accepted
+    pub fn record_success(&mut self, response_status: u16) {
+        self.attempts += 1;
+        self.status = OutboundDeliveryStatus::Succeeded;
+        self.response_status = Some(response_status.into());
+        self.error_message = None;
+        self.delivered_at = Some(Utc::now());
+    }
+
+    /// Record a failed attempt, scheduling a retry while attempts remain
+    ///
+    /// Backoff: 30s, 1m, 2m, 4m, ... capped at an hour.
+    pub fn record_failure(&mut self, response_status: Option<u16>, error: impl Into<String>) {
+        self.attempts += 1;
+        self.response_status = response_status.map(i32::from);
+        self.error_message = Some(error.into());
+        if self.attempts >= self.max_attempts {
+            self.status = OutboundDeliveryStatus::Failed;
+        } else {
+            let delay = BASE_RETRY_DELAY_SECS
+                .saturating_mul(1 << (self.attempts - 1).min(16))
+                .min(MAX_RETRY_DELAY_SECS);
+            self.next_attempt_at = Utc::now() + chrono::Duration::seconds(delay);
+        }
+    }
+
+    /// Mark the delivery failed without attempting it
+    pub fn abandon(&mut self, error: impl Into<String>) {
+        self.status = OutboundDeliveryStatus::Failed;
+        self.error_message = Some(error.into());
+    }
+}
+
+/// Signature header value for a payload: `sha256=<hex HMAC-SHA256>`
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    type HmacSha256 = Hmac<Sha256>;
+
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Posts logged deliveries to subscribers
+pub struct OutboundWebhookDispatcher {
+    database: Arc<Database>,
+    http_client: reqwest::Client,
+    batch_size: i64,
+}
+
+impl OutboundWebhookDispatcher {
+    /// Create a dispatcher with a 10 second request timeout
+    pub fn new(database: Arc<Database>) -> Self {
+        Self {
+            database,
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            batch_size: 50,
+        }
+    }
+
+    /// Set the number of due deliveries attempted per poll
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Run the delivery loop (blocking)
+    pub async fn run(&self, poll_interval: Duration) {
+        info!(
+            poll_interval_secs = poll_interval.as_secs(),
+            "Starting outbound webhook dispatcher"
+        );
+
+        loop {
+            if let Err(e) = self.deliver_due().await {
+                error!(error = %e, "Error delivering outbound webhooks");
+            }
+
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// Attempt every pending delivery that is due, returning how many succeeded
+    pub async fn deliver_due(&self) -> Result<usize> {
+        let deliveries = self
+            .database
+            .list_due_outbound_deliveries(self.batch_size)
+            .await?;
+        if !deliveries.is_empty() {
+            debug!(
+                count = deliveries.len(),
+                "Attempting outbound webhook deliveries"
+            );
+        }
+
+        let mut succeeded = 0;
+        for mut delivery in deliveries {
+            self.attempt(&mut delivery).await?;
+            if delivery.status == OutboundDeliveryStatus::Succeeded {
+                succeeded += 1;
+            }
+        }
+        Ok(succeeded)
+    }
+
+    /// Send a `ping` delivery to the subscriber right away
+    ///
+    /// Test deliveries are attempted once and logged like any other.
+    pub async fn send_test(&self, webhook_id: i64) -> Result<OutboundWebhookDelivery> {
+        let event = OutboundEvent::new(
+            OutboundEventType::Ping,
+            json!({ "message": "Test delivery from orchestrate" }),
+        );
+        let mut delivery = OutboundWebhookDelivery::new(webhook_id, &event);
+        delivery.max_attempts = 1;
+        delivery.id = Some(self.database.insert_outbound_delivery(&delivery).await?);
+
+        self.attempt(&mut delivery).await?;
+        Ok(delivery)
+    }
+
+    /// Post a delivery to its subscriber and record the outcome
+    async fn attempt(&self, delivery: &mut OutboundWebhookDelivery) -> Result<()> {
+        let delivery_id = delivery
+            .id
+            .ok_or_else(|| Error::Other("Cannot attempt delivery without ID".to_string()))?;
+
+        match self
+            .database
+            .get_outbound_webhook(delivery.webhook_id)
+            .await?
+        {
+            Some(webhook) if webhook.enabled => match self.post(&webhook, delivery).await {
+                Ok(status) => {
+                    delivery.record_success(status);
+                    info!(
+                        delivery_id = delivery_id,
+                        url = %webhook.url,
+                        event_type = %delivery.event_type,
+                        "Outbound webhook delivered"
+                    );
+                }
+                Err((status, e)) => {
+                    delivery.record_failure(status, e.clone());
+                    warn!(
+                        delivery_id = delivery_id,
+                        url = %webhook.url,
+                        attempts = delivery.attempts,
+                        error = %e,
+                        "Outbound webhook delivery failed"
+                    );
+                }
+            },
+            Some(_) => delivery.abandon("Outbound webhook is disabled"),
+            None => delivery.abandon("Outbound webhook no longer exists"),
+        }
+
+        self.database.update_outbound_delivery(delivery).await
+    }
+
+    /// Post the signed payload, returning the response status or the failure
+    async fn post(
+        &self,
+        webhook: &OutboundWebhook,
+        delivery: &OutboundWebhookDelivery,
+    ) -> std::result::Result<u16, (Option<u16>, String)> {
+        let mut request = self
+            .http_client
+            .post(&webhook.url)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "orchestrate-webhooks")
+            .header("X-Orchestrate-Event", &delivery.event_type)
+            .header(
+                "X-Orchestrate-Delivery",
+                delivery.id.unwrap_or_default().to_string(),
+            );
+        if let Some(secret) = &webhook.secret {
+            request = request.header(
+                "X-Orchestrate-Signature-256",
+                sign_payload(secret, delivery.payload.as_bytes()),
+            );
+        }
+
+        let response = request
+            .body(delivery.payload.clone())
+            .send()
+            .await
+            .map_err(|e| (None, format!("Request failed: {}", e)))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(status.as_u16())
+        } else {
+            Err((
+                Some(status.as_u16()),
+                format!("Subscriber returned {}", status),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_servers::serve;
+
+    fn completed_agent_event() -> OutboundEvent {
+        OutboundEvent::new(
+            OutboundEventType::AgentCompleted,
+            json!({ "agent_id": "a1", "task": "Fix the build" }),
+        )
+    }
+
+    #[test]
+    fn test_event_type_roundtrip() {
+        for event_type in OutboundEventType::ALL {
+            assert_eq!(
+                OutboundEventType::from_str(event_type.as_str()).unwrap(),
+                event_type
+            );
+        }
+        assert!(OutboundEventType::from_str("agent.started").is_err());
+    }
+
+    #[test]
+    fn test_subscription_filters() {
+        let webhook = OutboundWebhook::new(
+            "https://example.com/hook".to_string(),
+            vec!["agent.completed".to_string(), "pipeline.*".to_string()],
+        );
+        assert!(webhook.validate().is_ok());
+        assert!(webhook.matches(OutboundEventType::AgentCompleted));
+        assert!(!webhook.matches(OutboundEventType::AgentFailed));
+        assert!(webhook.matches(OutboundEventType::PipelineFailed));
+        assert!(!webhook.matches(OutboundEventType::PrMerged));
+        assert!(webhook.matches(OutboundEventType::Ping));
+
+        let all = OutboundWebhook::new("http://localhost/".to_string(), vec!["*".to_string()]);
+        assert!(all.matches(OutboundEventType::PrMerged));
+    }
+
+    #[test]
+    fn test_subscription_validation() {
+        let cases = [
+            ("ftp://example.com", vec!["*"], "must start with"),
+            ("https://example.com", vec![], "at least one event"),
+            ("https://example.com", vec!["agent.started"], "Unknown"),
+            ("https://example.com", vec!["deploy.*"], "Unknown"),
+        ];
+        for (url, events, message) in cases {
+            let webhook = OutboundWebhook::new(
+                url.to_string(),
+                events.into_iter().map(String::from).collect(),
+            );
+            let err = webhook.validate().unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_delivery_retry_backoff() {
+        let mut delivery = OutboundWebhookDelivery::new(1, &completed_agent_event());
+        delivery.max_attempts = 3;
+
+        delivery.record_failure(Some(500), "Subscriber returned 500");
+        assert_eq!(delivery.status, OutboundDeliveryStatus::Pending);
+        let delay = delivery.next_attempt_at - Utc::now();
+        assert!(delay > chrono::Duration::seconds(25) && delay <= chrono::Duration::seconds(30));
+
+        delivery.record_failure(None, "Request failed");
+        let delay = delivery.next_attempt_at - Utc::now();
+        assert!(delay > chrono::Duration::seconds(55));
+
+        delivery.record_failure(Some(502), "Subscriber returned 502");
+        assert_eq!(delivery.status, OutboundDeliveryStatus::Failed);
+        assert_eq!(delivery.attempts, 3);
+        assert_eq!(delivery.response_status, Some(502));
+    }
+
+    #[test]
+    fn test_sign_payload() {
+        // HMAC-SHA256 test vector from RFC 4231 (test case 2)
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_posts_signed_payload_and_retries() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let (url, server) = serve(vec![(503, ""), (200, "")]).await;
+
+        let webhook = OutboundWebhook::new(format!("{}/hook", url), vec!["agent.*".to_string()])
+            .with_secret("s3cret");
+        database.insert_outbound_webhook(&webhook).await.unwrap();
+        let event = completed_agent_event();
+        assert_eq!(database.enqueue_outbound_event(&event).await.unwrap(), 1);
+
+        let dispatcher = OutboundWebhookDispatcher::new(database.clone());
+        assert_eq!(dispatcher.deliver_due().await.unwrap(), 0);
+
+        let mut delivery = database
+            .list_outbound_deliveries(None, 10)
+            .await
+            .unwrap()
+            .remove(0);
+        assert_eq!(delivery.status, OutboundDeliveryStatus::Pending);
+        assert_eq!(delivery.response_status, Some(503));
+
+        // Not due yet
+        assert_eq!(dispatcher.deliver_due().await.unwrap(), 0);
+
+        delivery.next_attempt_at = Utc::now();
+        database.update_outbound_delivery(&delivery).await.unwrap();
+        assert_eq!(dispatcher.deliver_due().await.unwrap(), 1);
+
+        let delivery = database
+            .get_outbound_delivery(delivery.id.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(delivery.status, OutboundDeliveryStatus::Succeeded);
+        assert_eq!(delivery.attempts, 2);
+
+        let requests = server.await.unwrap();
+        let request = &requests[1];
+        let body = request.split("\r\n\r\n").nth(1).unwrap();
+        let headers = request.to_ascii_lowercase();
+        assert!(headers.contains("x-orchestrate-event: agent.completed"));
+        assert!(headers.contains(&format!(
+            "x-orchestrate-signature-256: {}",
+            sign_payload("s3cret", body.as_bytes())
+        )));
+
+        let payload: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(payload["event"], "agent.completed");
+        assert_eq!(payload["data"]["task"], "Fix the build");
+    }
+
+    #[tokio::test]
+    async fn test_send_test_delivery() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let (url, server) = serve(vec![(500, "")]).await;
+
+        let webhook = OutboundWebhook::new(format!("{}/hook", url), vec!["pr.merged".to_string()]);
+        let id = database.insert_outbound_webhook(&webhook).await.unwrap();
+
+        let dispatcher = OutboundWebhookDispatcher::new(database.clone());
+        let delivery = dispatcher.send_test(id).await.unwrap();
+        assert_eq!(delivery.event_type, "ping");
+        assert_eq!(delivery.status, OutboundDeliveryStatus::Failed);
+        assert_eq!(delivery.response_status, Some(500));
+
+        let requests = server.await.unwrap();
+        assert!(!requests[0]
+            .to_ascii_lowercase()
+            .contains("x-orchestrate-signature-256"));
+    }
+}
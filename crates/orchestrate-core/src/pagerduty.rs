@@ -0,0 +1,553 @@
+//! PagerDuty Integration
+//!
+//! Pages the on-call through the PagerDuty Events API v2 and reads back the
+//! acknowledgements and resolutions PagerDuty reports in v3 webhooks:
+//! - [`PagerDutyEvent`]: trigger, acknowledge, and resolve events for
+//!   incidents and alerts, deduplicated by [`PagedResource::dedup_key`]
+//! - [`PagerDutyClient`]: posts events to a service's integration (routing) key
+//! - [`PagerDutyWebhook`]: the `incident.*` webhook payload, verified with
+//!   [`verify_webhook_signature`]
+//!
+//! Incident severities map to PagerDuty severities as critical → critical,
+//! high → error, medium → warning, and low → info.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use std::time::Duration;
+
+use crate::incident::{EscalationRule, Incident, IncidentSeverity};
+use crate::monitoring::{Alert, AlertSeverity};
+use crate::oncall::PagedResource;
+use crate::{Error, Result};
+
+/// Events API v2 endpoint
+pub const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// Header carrying the webhook signatures
+pub const PAGERDUTY_SIGNATURE_HEADER: &str = "X-PagerDuty-Signature";
+
+/// Longest summary PagerDuty accepts
+const MAX_SUMMARY_LEN: usize = 1024;
+
+/// Source reported for every event
+const EVENT_SOURCE: &str = "orchestrate";
+
+/// Event action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PagerDutyAction {
+    Trigger,
+    Acknowledge,
+    Resolve,
+}
+
+/// Severity of a triggered event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PagerDutySeverity {
+    Critical,
+    Error,
+    Warning,
+    Info,
+}
+
+impl From<IncidentSeverity> for PagerDutySeverity {
+    fn from(severity: IncidentSeverity) -> Self {
+        match severity {
+            IncidentSeverity::Critical => Self::Critical,
+            IncidentSeverity::High => Self::Error,
+            IncidentSeverity::Medium => Self::Warning,
+            IncidentSeverity::Low => Self::Info,
+        }
+    }
+}
+
+impl From<&AlertSeverity> for PagerDutySeverity {
+    fn from(severity: &AlertSeverity) -> Self {
+        match severity {
+            AlertSeverity::Critical => Self::Critical,
+            AlertSeverity::Warning => Self::Warning,
+            AlertSeverity::Info => Self::Info,
+        }
+    }
+}
+
+/// Details of a triggered event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagerDutyPayload {
+    pub summary: String,
+    pub source: String,
+    pub severity: PagerDutySeverity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_details: Option<Value>,
+}
+
+/// Event sent to the Events API v2
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagerDutyEvent {
+    pub routing_key: String,
+    pub event_action: PagerDutyAction,
+    pub dedup_key: String,
+    /// Required for triggers, omitted for acknowledge and resolve
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<PagerDutyPayload>,
+}
+
+impl PagerDutyEvent {
+    /// Trigger a page for an incident, noting the escalation rule if any
+    pub fn trigger_incident(
+        routing_key: impl Into<String>,
+        incident: &Incident,
+        rule: Option<&EscalationRule>,
+    ) -> Self {
+        let mut details = serde_json::json!({
+            "incident_id": incident.id,
+            "status": incident.status.as_str(),
+            "detected_at": incident.detected_at.to_rfc3339(),
+        });
+        if !incident.description.is_empty() {
+            details["description"] = Value::String(incident.description.clone());
+        }
+        if !incident.tags.is_empty() {
+            details["tags"] = serde_json::json!(incident.tags);
+        }
+        if let Some(rule) = rule {
+            details["escalation_rule"] = Value::String(rule.name.clone());
+        }
+
+        let resource = PagedResource::Incident(incident.id.clone());
+        Self {
+            routing_key: routing_key.into(),
+            event_action: PagerDutyAction::Trigger,
+            dedup_key: resource.dedup_key(),
+            payload: Some(PagerDutyPayload {
+                summary: truncate_summary(&format!("[{}] {}", incident.id, incident.title)),
+                source: EVENT_SOURCE.to_string(),
+                severity: incident.severity.into(),
+                timestamp: Some(incident.detected_at),
+                component: incident.affected_services.first().cloned(),
+                group: None,
+                class: Some("incident".to_string()),
+                custom_details: Some(details),
+            }),
+        }
+    }
+
+    /// Trigger a page for a firing alert
+    pub fn trigger_alert(routing_key: impl Into<String>, alert: &Alert) -> Self {
+        let summary = if alert.message.is_empty() {
+            format!("Alert {} is firing", alert.rule_name)
+        } else {
+            format!("{}: {}", alert.rule_name, alert.message)
+        };
+        let mut details = serde_json::json!({
+            "alert_id": alert.id,
+            "rule": alert.rule_name,
+        });
+        if let Some(value) = alert.current_value {
+            details["current_value"] = serde_json::json!(value);
+        }
+        if let Some(threshold) = alert.threshold {
+            details["threshold"] = serde_json::json!(threshold);
+        }
+        if !alert.labels.is_empty() {
+            details["labels"] = serde_json::json!(alert.labels);
+        }
+
+        let resource = PagedResource::Alert(alert.id.clone());
+        Self {
+            routing_key: routing_key.into(),
+            event_action: PagerDutyAction::Trigger,
+            dedup_key: resource.dedup_key(),
+            payload: Some(PagerDutyPayload {
+                summary: truncate_summary(&summary),
+                source: EVENT_SOURCE.to_string(),
+                severity: (&alert.severity).into(),
+                timestamp: Some(alert.triggered_at),
+                component: alert.labels.get("component").cloned(),
+                group: alert.labels.get("group").cloned(),
+                class: Some("alert".to_string()),
+                custom_details: Some(details),
+            }),
+        }
+    }
+
+    /// Acknowledge the page for a resource
+    pub fn acknowledge(routing_key: impl Into<String>, resource: &PagedResource) -> Self {
+        Self::follow_up(routing_key, resource, PagerDutyAction::Acknowledge)
+    }
+
+    /// Resolve the page for a resource
+    pub fn resolve(routing_key: impl Into<String>, resource: &PagedResource) -> Self {
+        Self::follow_up(routing_key, resource, PagerDutyAction::Resolve)
+    }
+
+    fn follow_up(
+        routing_key: impl Into<String>,
+        resource: &PagedResource,
+        event_action: PagerDutyAction,
+    ) -> Self {
+        Self {
+            routing_key: routing_key.into(),
+            event_action,
+            dedup_key: resource.dedup_key(),
+            payload: None,
+        }
+    }
+}
+
+/// Truncate a summary to the length PagerDuty accepts
+fn truncate_summary(summary: &str) -> String {
+    if summary.chars().count() <= MAX_SUMMARY_LEN {
+        return summary.to_string();
+    }
+    let mut truncated: String = summary.chars().take(MAX_SUMMARY_LEN - 3).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+/// Events API v2 response
+#[derive(Debug, Clone, Deserialize)]
+struct EventResponse {
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    dedup_key: Option<String>,
+    #[serde(default)]
+    errors: Vec<String>,
+}
+
+/// Events API v2 client
+#[derive(Debug, Clone)]
+pub struct PagerDutyClient {
+    http_client: reqwest::Client,
+    events_url: String,
+}
+
+impl Default for PagerDutyClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PagerDutyClient {
+    /// Create a client with a 10 second request timeout
+    pub fn new() -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            events_url: PAGERDUTY_EVENTS_URL.to_string(),
+        }
+    }
+
+    /// Send events to a different endpoint
+    pub fn with_events_url(mut self, events_url: impl Into<String>) -> Self {
+        self.events_url = events_url.into();
+        self
+    }
+
+    /// Send an event, returning the deduplication key PagerDuty recorded
+    pub async fn send(&self, event: &PagerDutyEvent) -> Result<String> {
+        let response = self
+            .http_client
+            .post(&self.events_url)
+            .json(event)
+            .send()
+            .await
+            .map_err(|e| Error::Other(format!("PagerDuty event request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(Error::Other(
+                "PagerDuty rate limited the event, retry later".to_string(),
+            ));
+        }
+
+        let body: EventResponse = response.json().await.map_err(|e| {
+            Error::Other(format!(
+                "Invalid PagerDuty event response (HTTP {}): {}",
+                status, e
+            ))
+        })?;
+
+        if !status.is_success() || body.status != "success" {
+            let mut message = format!("PagerDuty rejected the event (HTTP {})", status);
+            if !body.message.is_empty() {
+                message.push_str(&format!(": {}", body.message));
+            }
+            if !body.errors.is_empty() {
+                message.push_str(&format!(" ({})", body.errors.join("; ")));
+            }
+            return Err(Error::Other(message));
+        }
+
+        Ok(body.dedup_key.unwrap_or_else(|| event.dedup_key.clone()))
+    }
+}
+
+/// V3 webhook delivery
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PagerDutyWebhook {
+    #[serde(default)]
+    pub event: PagerDutyWebhookEvent,
+}
+
+/// Event in a V3 webhook delivery
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PagerDutyWebhookEvent {
+    #[serde(default)]
+    pub id: String,
+    /// e.g. `incident.acknowledged`, `incident.resolved`, `pagey.ping`
+    #[serde(default)]
+    pub event_type: String,
+    #[serde(default)]
+    pub occurred_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub agent: Option<PagerDutyReference>,
+    #[serde(default)]
+    pub data: PagerDutyIncidentData,
+}
+
+impl PagerDutyWebhookEvent {
+    /// Resource paged by orchestrate that the event is about, if any
+    pub fn resource(&self) -> Option<PagedResource> {
+        self.data
+            .incident_key
+            .as_deref()
+            .and_then(PagedResource::from_dedup_key)
+    }
+
+    /// Who acted, e.g. the responder's name
+    pub fn actor(&self) -> Option<&str> {
+        self.agent
+            .as_ref()
+            .and_then(|agent| agent.summary.as_deref())
+    }
+}
+
+/// Reference to a PagerDuty object
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PagerDutyReference {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+}
+
+/// PagerDuty incident in a webhook event
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PagerDutyIncidentData {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Deduplication key of the event that opened the PagerDuty incident
+    #[serde(default)]
+    pub incident_key: Option<String>,
+    #[serde(default)]
+    pub html_url: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+/// Whether an `X-PagerDuty-Signature` header is valid for the body
+///
+/// The header lists one or more `v1=<hex HMAC-SHA256>` signatures, one per
+/// active secret of the webhook subscription; any match is accepted.
+pub fn verify_webhook_signature(secret: &str, body: &[u8], header: &str) -> bool {
+    header
+        .split(',')
+        .filter_map(|signature| signature.trim().strip_prefix("v1="))
+        .filter_map(|signature| hex::decode(signature).ok())
+        .any(|expected| {
+            let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+                return false;
+            };
+            mac.update(body);
+            mac.verify_slice(&expected).is_ok()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::incident::{EscalationCondition, EscalationTarget, EscalationTargetType};
+    use crate::monitoring::AlertRule;
+    use crate::test_servers::{json_body, serve};
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        format!("v1={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn test_incident_trigger_event() {
+        let mut incident = Incident::new("INC-1", "API latency", IncidentSeverity::High);
+        incident.affected_services.push("api".to_string());
+        let rule = EscalationRule {
+            name: "high-latency".to_string(),
+            condition: EscalationCondition::SeverityCritical,
+            targets: vec![EscalationTarget {
+                target_type: EscalationTargetType::PagerDuty,
+                destination: "R0UTING".to_string(),
+            }],
+            delay_seconds: 0,
+            repeat_interval_seconds: None,
+        };
+
+        let event = PagerDutyEvent::trigger_incident("R0UTING", &incident, Some(&rule));
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event_action"], "trigger");
+        assert_eq!(json["dedup_key"], "orchestrate:incident:INC-1");
+        assert_eq!(json["payload"]["severity"], "error");
+        assert_eq!(json["payload"]["summary"], "[INC-1] API latency");
+        assert_eq!(json["payload"]["component"], "api");
+        assert_eq!(
+            json["payload"]["custom_details"]["escalation_rule"],
+            "high-latency"
+        );
+        assert!(json["payload"].get("group").is_none());
+    }
+
+    #[test]
+    fn test_alert_trigger_event() {
+        let rule = AlertRule::new(
+            "high-error-rate",
+            "error_rate > 0.1",
+            AlertSeverity::Warning,
+        );
+        let mut alert = Alert::new(&rule, "Error rate 12%");
+        alert.id = "42".to_string();
+
+        let json = serde_json::to_value(PagerDutyEvent::trigger_alert("R0UTING", &alert)).unwrap();
+        assert_eq!(json["dedup_key"], "orchestrate:alert:42");
+        assert_eq!(json["payload"]["severity"], "warning");
+        assert_eq!(
+            json["payload"]["summary"],
+            "high-error-rate: Error rate 12%"
+        );
+    }
+
+    #[test]
+    fn test_follow_up_events_have_no_payload() {
+        let resource = PagedResource::Incident("INC-1".to_string());
+        let json = serde_json::to_value(PagerDutyEvent::resolve("R0UTING", &resource)).unwrap();
+        assert_eq!(json["event_action"], "resolve");
+        assert_eq!(json["dedup_key"], "orchestrate:incident:INC-1");
+        assert!(json.get("payload").is_none());
+    }
+
+    #[test]
+    fn test_long_summaries_are_truncated() {
+        let incident = Incident::new("INC-1", &"x".repeat(2000), IncidentSeverity::Low);
+        let event = PagerDutyEvent::trigger_incident("R0UTING", &incident, None);
+        let summary = event.payload.unwrap().summary;
+        assert_eq!(summary.chars().count(), MAX_SUMMARY_LEN);
+        assert!(summary.ends_with("..."));
+    }
+
+    #[tokio::test]
+    async fn test_send_event() {
+        let (url, server) = serve(vec![(
+            202,
+            r#"{"status":"success","message":"Event processed","dedup_key":"orchestrate:incident:INC-1"}"#,
+        )])
+        .await;
+        let client = PagerDutyClient::new().with_events_url(format!("{}/v2/enqueue", url));
+
+        let incident = Incident::new("INC-1", "API down", IncidentSeverity::Critical);
+        let dedup_key = client
+            .send(&PagerDutyEvent::trigger_incident(
+                "R0UTING", &incident, None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(dedup_key, "orchestrate:incident:INC-1");
+
+        let body = json_body(&server.await.unwrap()[0]);
+        assert_eq!(body["routing_key"], "R0UTING");
+        assert_eq!(body["payload"]["severity"], "critical");
+    }
+
+    #[tokio::test]
+    async fn test_rejected_event_is_an_error() {
+        let (url, _server) = serve(vec![(
+            400,
+            r#"{"status":"invalid event","message":"Event object is invalid","errors":["Length of 'routing_key' is incorrect (should be 32 characters)"]}"#,
+        )])
+        .await;
+        let client = PagerDutyClient::new().with_events_url(format!("{}/v2/enqueue", url));
+
+        let resource = PagedResource::Alert("42".to_string());
+        let err = client
+            .send(&PagerDutyEvent::resolve("bad", &resource))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Event object is invalid"));
+        assert!(err.to_string().contains("routing_key"));
+    }
+
+    #[test]
+    fn test_webhook_parsing() {
+        let body = r#"{
+            "event": {
+                "id": "01DEN4HPBQAAAG05V5QQYBRZMF",
+                "event_type": "incident.acknowledged",
+                "resource_type": "incident",
+                "occurred_at": "2024-01-01T12:00:00.000Z",
+                "agent": {"id": "PLH1HKV", "summary": "Tenex Engineer", "type": "user_reference"},
+                "data": {
+                    "id": "PGR0VU2",
+                    "type": "incident",
+                    "status": "acknowledged",
+                    "incident_key": "orchestrate:incident:INC-1",
+                    "html_url": "https://acme.pagerduty.com/incidents/PGR0VU2",
+                    "title": "[INC-1] API down"
+                }
+            }
+        }"#;
+        let webhook: PagerDutyWebhook = serde_json::from_str(body).unwrap();
+        assert_eq!(webhook.event.event_type, "incident.acknowledged");
+        assert_eq!(webhook.event.actor(), Some("Tenex Engineer"));
+        assert_eq!(
+            webhook.event.resource(),
+            Some(PagedResource::Incident("INC-1".to_string()))
+        );
+
+        let ping: PagerDutyWebhook =
+            serde_json::from_str(r#"{"event":{"event_type":"pagey.ping"}}"#).unwrap();
+        assert_eq!(ping.event.resource(), None);
+    }
+
+    #[test]
+    fn test_webhook_signature() {
+        let body = br#"{"event":{"event_type":"pagey.ping"}}"#;
+        let valid = sign("secret", body);
+        assert!(verify_webhook_signature("secret", body, &valid));
+        assert!(verify_webhook_signature(
+            "secret",
+            body,
+            &format!("{}, {}", sign("old-secret", body), valid)
+        ));
+        assert!(!verify_webhook_signature("other", body, &valid));
+        assert!(!verify_webhook_signature("secret", b"{}", &valid));
+        assert!(!verify_webhook_signature("secret", body, "v1=zz"));
+        assert!(!verify_webhook_signature("secret", body, ""));
+    }
+}
@@ -0,0 +1,96 @@
+//! Keyset pagination of database lists
+//!
+//! A list is sorted by a key of columns ending in a unique one, and a page is
+//! the rows after the key of the last row of the previous page:
+//!
+//! ```sql
+//! SELECT * FROM artifacts WHERE ... AND (created_at, id) < (?, ?)
+//! ORDER BY created_at DESC, id DESC LIMIT ?
+//! ```
+//!
+//! Listing reads one page (plus one row telling whether another follows)
+//! however large the table, and rows are only counted when a total is asked
+//! for.
+
+use serde::{Deserialize, Serialize};
+
+/// Value of a sort key column, as stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyValue {
+    Integer(i64),
+    Text(String),
+}
+
+/// Columns a list is sorted by, all ascending or all descending
+///
+/// The last column must be unique, so that every row has its own key.
+#[derive(Debug, Clone, Copy)]
+pub struct SortKey {
+    pub columns: &'static [&'static str],
+    pub descending: bool,
+}
+
+impl SortKey {
+    pub const fn asc(columns: &'static [&'static str]) -> Self {
+        Self {
+            columns,
+            descending: false,
+        }
+    }
+
+    pub const fn desc(columns: &'static [&'static str]) -> Self {
+        Self {
+            columns,
+            descending: true,
+        }
+    }
+}
+
+/// Page of a list to fetch
+#[derive(Debug, Clone)]
+pub struct PageQuery {
+    /// Sort key of the last row of the previous page
+    pub after: Option<Vec<KeyValue>>,
+    pub limit: usize,
+    /// Count all rows matching the filters
+    pub include_total: bool,
+}
+
+impl PageQuery {
+    /// First page of `limit` rows
+    pub fn first(limit: usize) -> Self {
+        Self {
+            after: None,
+            limit,
+            include_total: false,
+        }
+    }
+}
+
+/// Rows of one page
+#[derive(Debug, Clone)]
+pub struct RowPage<T> {
+    pub items: Vec<T>,
+    /// Sort key of the last row, when more rows follow
+    pub next: Option<Vec<KeyValue>>,
+    pub total: Option<i64>,
+}
+
+impl<T> RowPage<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> RowPage<U> {
+        RowPage {
+            items: self.items.into_iter().map(f).collect(),
+            next: self.next,
+            total: self.total,
+        }
+    }
+
+    pub fn try_map<U, E>(self, f: impl FnMut(T) -> Result<U, E>) -> Result<RowPage<U>, E> {
+        Ok(RowPage {
+            items: self.items.into_iter().map(f).collect::<Result<_, _>>()?,
+            next: self.next,
+            total: self.total,
+        })
+    }
+}
@@ -4,6 +4,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use crate::{Error, Result};
@@ -147,6 +148,16 @@ pub struct PipelineRun {
     pub status: PipelineRunStatus,
     /// Event that triggered this run
     pub trigger_event: Option<String>,
+    /// Commit the run builds, used to key cached stage results
+    pub commit_sha: Option<String>,
+    /// Whether stages may reuse cached results from earlier runs
+    pub use_cache: bool,
+    /// Variables supplied by the trigger, overriding pipeline variables
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// Payload of the triggering event, readable from stage conditions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger_payload: Option<serde_json::Value>,
     /// When the run started
     pub started_at: Option<DateTime<Utc>>,
     /// When the run completed
@@ -163,12 +174,52 @@ impl PipelineRun {
             pipeline_id,
             status: PipelineRunStatus::Pending,
             trigger_event,
+            commit_sha: None,
+            use_cache: true,
+            variables: HashMap::new(),
+            trigger_payload: None,
             started_at: None,
             completed_at: None,
             created_at: Utc::now(),
         }
     }
 
+    /// Set the commit the run builds
+    pub fn with_commit_sha(mut self, commit_sha: impl Into<String>) -> Self {
+        self.commit_sha = Some(commit_sha.into());
+        self
+    }
+
+    /// Set variables supplied by the trigger
+    pub fn with_variables(mut self, variables: HashMap<String, String>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    /// Set the payload of the triggering event
+    pub fn with_trigger_payload(mut self, payload: serde_json::Value) -> Self {
+        self.trigger_payload = Some(payload);
+        self
+    }
+
+    /// New run of the same pipeline with this run's trigger, commit, and
+    /// variables, e.g. to retry a failed run
+    pub fn retry(&self) -> Self {
+        Self {
+            commit_sha: self.commit_sha.clone(),
+            use_cache: self.use_cache,
+            variables: self.variables.clone(),
+            trigger_payload: self.trigger_payload.clone(),
+            ..Self::new(self.pipeline_id, self.trigger_event.clone())
+        }
+    }
+
+    /// Always execute every stage, ignoring cached results
+    pub fn without_cache(mut self) -> Self {
+        self.use_cache = false;
+        self
+    }
+
     /// Mark run as running
     pub fn mark_running(&mut self) {
         self.status = PipelineRunStatus::Running;
@@ -182,6 +233,12 @@ impl PipelineRun {
         self.status = PipelineRunStatus::WaitingApproval;
     }
 
+    /// Mark a finished run as running again
+    pub fn mark_resumed(&mut self) {
+        self.completed_at = None;
+        self.mark_running();
+    }
+
     /// Mark run as succeeded
     pub fn mark_succeeded(&mut self) {
         self.status = PipelineRunStatus::Succeeded;
@@ -201,6 +258,29 @@ impl PipelineRun {
     }
 }
 
+/// Outcome of admitting a triggered run under its pipeline's concurrency limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunAdmission {
+    /// The run may start right away, after cancelling the listed runs to
+    /// make room for it
+    Admitted { run_id: i64, cancelled: Vec<i64> },
+    /// The run waits behind `position` earlier runs
+    Queued { run_id: i64, position: usize },
+    /// The run was recorded as cancelled because the pipeline is at its limit
+    Rejected { run_id: i64, in_progress: usize },
+}
+
+impl RunAdmission {
+    /// ID of the inserted run
+    pub fn run_id(&self) -> i64 {
+        match self {
+            Self::Admitted { run_id, .. }
+            | Self::Queued { run_id, .. }
+            | Self::Rejected { run_id, .. } => *run_id,
+        }
+    }
+}
+
 /// A stage within a pipeline run
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PipelineStage {
@@ -215,6 +295,10 @@ pub struct PipelineStage {
     pub status: PipelineStageStatus,
     /// Agent ID executing this stage
     pub agent_id: Option<String>,
+    /// Run whose cached result this stage reused instead of executing
+    pub cached_from_run_id: Option<i64>,
+    /// Number of attempts started, including retries
+    pub attempts: i32,
     /// When the stage started
     pub started_at: Option<DateTime<Utc>>,
     /// When the stage completed
@@ -232,16 +316,19 @@ impl PipelineStage {
             stage_name,
             status: PipelineStageStatus::Pending,
             agent_id: None,
+            cached_from_run_id: None,
+            attempts: 0,
             started_at: None,
             completed_at: None,
             created_at: Utc::now(),
         }
     }
 
-    /// Mark stage as running
+    /// Mark stage as running, starting a new attempt
     pub fn mark_running(&mut self, agent_id: Option<String>) {
         self.status = PipelineStageStatus::Running;
         self.agent_id = agent_id;
+        self.attempts += 1;
         if self.started_at.is_none() {
             self.started_at = Some(Utc::now());
         }
@@ -258,12 +345,23 @@ impl PipelineStage {
         self.completed_at = Some(Utc::now());
     }
 
+    /// Mark stage as succeeded using the cached result of an earlier run
+    pub fn mark_cached(&mut self, from_run_id: i64) {
+        self.cached_from_run_id = Some(from_run_id);
+        self.mark_succeeded();
+    }
+
     /// Mark stage as failed
     pub fn mark_failed(&mut self) {
         self.status = PipelineStageStatus::Failed;
         self.completed_at = Some(Utc::now());
     }
 
+    /// Whether the stage failed after being retried
+    pub fn failed_after_retries(&self) -> bool {
+        self.status == PipelineStageStatus::Failed && self.attempts > 1
+    }
+
     /// Mark stage as skipped
     pub fn mark_skipped(&mut self) {
         self.status = PipelineStageStatus::Skipped;
@@ -275,6 +373,15 @@ impl PipelineStage {
         self.status = PipelineStageStatus::Cancelled;
         self.completed_at = Some(Utc::now());
     }
+
+    /// Return the stage to pending so a resumed run executes it again
+    pub fn reset(&mut self) {
+        self.status = PipelineStageStatus::Pending;
+        self.agent_id = None;
+        self.attempts = 0;
+        self.started_at = None;
+        self.completed_at = None;
+    }
 }
 
 /// Rollback event trigger type
@@ -443,6 +550,48 @@ mod tests {
         assert!(run.completed_at.is_none());
     }
 
+    #[test]
+    fn test_pipeline_run_cache_settings() {
+        let run = PipelineRun::new(1, None);
+        assert!(run.use_cache);
+        assert!(run.commit_sha.is_none());
+
+        let run = PipelineRun::new(1, None).with_commit_sha("abc123").without_cache();
+        assert_eq!(run.commit_sha.as_deref(), Some("abc123"));
+        assert!(!run.use_cache);
+    }
+
+    #[test]
+    fn test_pipeline_run_with_variables() {
+        let run = PipelineRun::new(1, None);
+        assert!(run.variables.is_empty());
+
+        let variables = HashMap::from([("pr_number".to_string(), "42".to_string())]);
+        let run = PipelineRun::new(1, Some("pull_request.labeled".to_string()))
+            .with_variables(variables.clone());
+        assert_eq!(run.variables, variables);
+    }
+
+    #[test]
+    fn test_pipeline_run_retry() {
+        let mut run = PipelineRun::new(1, Some("push".to_string()))
+            .with_commit_sha("abc123")
+            .with_variables(HashMap::from([("env".to_string(), "staging".to_string())]));
+        run.id = Some(7);
+        run.mark_running();
+        run.mark_failed();
+
+        let retry = run.retry();
+        assert_eq!(retry.id, None);
+        assert_eq!(retry.pipeline_id, 1);
+        assert_eq!(retry.status, PipelineRunStatus::Pending);
+        assert_eq!(retry.trigger_event.as_deref(), Some("push"));
+        assert_eq!(retry.commit_sha.as_deref(), Some("abc123"));
+        assert_eq!(retry.variables, run.variables);
+        assert!(retry.started_at.is_none());
+        assert!(retry.completed_at.is_none());
+    }
+
     #[test]
     fn test_pipeline_run_mark_running() {
         let mut run = PipelineRun::new(1, None);
@@ -479,6 +628,18 @@ mod tests {
         assert!(run.completed_at.is_some());
     }
 
+    #[test]
+    fn test_pipeline_run_mark_resumed() {
+        let mut run = PipelineRun::new(1, None);
+        run.mark_running();
+        run.mark_failed();
+        run.mark_resumed();
+
+        assert_eq!(run.status, PipelineRunStatus::Running);
+        assert!(run.started_at.is_some());
+        assert!(run.completed_at.is_none());
+    }
+
     #[test]
     fn test_pipeline_run_mark_waiting_approval() {
         let mut run = PipelineRun::new(1, None);
@@ -505,6 +666,20 @@ mod tests {
         assert_eq!(stage.status, PipelineStageStatus::Running);
         assert_eq!(stage.agent_id, Some("agent-123".to_string()));
         assert!(stage.started_at.is_some());
+        assert_eq!(stage.attempts, 1);
+    }
+
+    #[test]
+    fn test_pipeline_stage_failed_after_retries() {
+        let mut stage = PipelineStage::new(1, "test".to_string());
+        stage.mark_running(None);
+        stage.mark_failed();
+        assert!(!stage.failed_after_retries());
+
+        stage.mark_running(None);
+        stage.mark_failed();
+        assert_eq!(stage.attempts, 2);
+        assert!(stage.failed_after_retries());
     }
 
     #[test]
@@ -516,6 +691,16 @@ mod tests {
         assert!(stage.completed_at.is_some());
     }
 
+    #[test]
+    fn test_pipeline_stage_mark_cached() {
+        let mut stage = PipelineStage::new(2, "build".to_string());
+        stage.mark_cached(1);
+
+        assert_eq!(stage.status, PipelineStageStatus::Succeeded);
+        assert_eq!(stage.cached_from_run_id, Some(1));
+        assert!(stage.completed_at.is_some());
+    }
+
     #[test]
     fn test_pipeline_stage_mark_failed() {
         let mut stage = PipelineStage::new(1, "test".to_string());
@@ -543,6 +728,20 @@ mod tests {
         assert!(stage.completed_at.is_some());
     }
 
+    #[test]
+    fn test_pipeline_stage_reset() {
+        let mut stage = PipelineStage::new(1, "test".to_string());
+        stage.mark_running(Some("agent-123".to_string()));
+        stage.mark_failed();
+        stage.reset();
+
+        assert_eq!(stage.status, PipelineStageStatus::Pending);
+        assert!(stage.agent_id.is_none());
+        assert_eq!(stage.attempts, 0);
+        assert!(stage.started_at.is_none());
+        assert!(stage.completed_at.is_none());
+    }
+
     #[test]
     fn test_pipeline_run_status_parsing() {
         assert_eq!(
@@ -3,30 +3,99 @@
 //! This module provides the core execution engine for pipelines, managing:
 //! - Pipeline run creation from triggers
 //! - Stage execution respecting dependencies (DAG)
-//! - Parallel stage execution
+//! - Parallel stage execution with a configurable concurrency cap
 //! - Agent spawning for each stage
+//! - Pausing at approval gates and resuming once the approval is decided
 //! - Stage status and timing tracking
 //! - Stage timeouts
-//! - Stage retry on failure
+//! - Stage retry on failure, with exponential backoff
+//! - Reuse of cached stage results keyed by commit SHA and stage definition
 //! - Variable passing between stages
+//! - Saga compensation of completed stages when a later stage fails
+//! - Rollback hooks run when a later stage fails and rolls back to their stage
+//! - Dry-run plans of what a run would execute and what it would cost
+//! - Notifications when a run fails, rolls back, or recovers
+//! - Outbound webhook deliveries when a run succeeds or fails
+//! - Vault secrets exposed to a stage's agent and scrubbed from stage errors
 
 use crate::{
+    approval::{ApprovalRequest, ApprovalStatus},
     approval_service::ApprovalService,
     condition_evaluator::{ConditionContext, ConditionEvaluator, EvaluationResult},
-    pipeline::{PipelineRun, PipelineStage},
-    pipeline_parser::{FailureAction, PipelineDefinition, StageDefinition},
+    email_service::EmailNotificationService,
+    event_bus::BusEvent,
+    outbound_webhook::OutboundEvent,
+    pipeline::{PipelineRun, PipelineRunStatus, PipelineStage, PipelineStageStatus},
+    pipeline_graph::describe_condition,
+    pipeline_notifications::{
+        HttpNotificationSender, NotificationSender, NotificationTarget, PipelineNotification,
+    },
+    pipeline_parser::{
+        FailureAction, PipelineDefinition, PipelineNotificationEvent, StageDefinition, StageMatrix,
+        StageRetryPolicy, MATRIX_REPO_PARAMETER,
+    },
+    pipeline_plan::{
+        AgentTokenEstimate, PipelinePlan, PlannedAgent, PlannedStage, PlannedStageAction,
+    },
+    saga::{CompensationStatus, Saga, SagaCompensation, SagaStatus, SagaWorkflowType},
+    secrets::{scrub_secrets, SecretVault},
+    slack_approval_service::SlackApprovalService,
+    slack_service::SlackService,
+    telegram::TelegramService,
     Database, Error, Result,
 };
-use std::collections::{HashMap, HashSet};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
+use tokio::task::JoinSet;
 use tokio::time::{timeout, Duration};
 use tracing::{debug, error, info, warn};
 
+/// Default number of stages allowed to run at the same time
+pub const DEFAULT_MAX_PARALLEL_STAGES: usize = 4;
+
+/// Runs the agent behind a pipeline stage
+#[async_trait]
+pub trait StageRunner: Send + Sync {
+    /// Run `agent_type` on `task`, exposing `secrets` to it as environment
+    /// variables
+    async fn run(
+        &self,
+        agent_type: &str,
+        task: &str,
+        secrets: &HashMap<String, String>,
+    ) -> Result<()>;
+}
+
+/// Identifies a stage's inputs in the stage result cache
+struct StageCacheKey {
+    pipeline_id: i64,
+    commit_sha: String,
+    key: String,
+}
+
+/// How a stage (or the stages of a run) left off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StageOutcome {
+    /// Finished, successfully or by being skipped
+    Finished,
+    /// Paused until an approval is decided
+    AwaitingApproval,
+}
+
 /// Pipeline execution engine
 pub struct PipelineExecutor {
     database: Arc<Database>,
     condition_evaluator: ConditionEvaluator,
     approval_service: ApprovalService,
+    max_parallel: usize,
+    stage_runner: Option<Arc<dyn StageRunner>>,
+    notifier: Option<Arc<dyn NotificationSender>>,
+    dashboard_url: Option<String>,
+    secret_vault: Option<Arc<SecretVault>>,
+    slack_approvals: Option<Arc<SlackApprovalService>>,
+    email_notifications: Option<Arc<EmailNotificationService>>,
+    telegram: Option<Arc<TelegramService>>,
 }
 
 /// Context for pipeline execution containing runtime variables
@@ -36,6 +105,8 @@ pub struct ExecutionContext {
     pub variables: HashMap<String, String>,
     /// Trigger event that initiated the pipeline
     pub trigger_event: Option<String>,
+    /// Payload of the trigger event (for condition expressions)
+    pub trigger_payload: serde_json::Value,
     /// Current branch (for condition evaluation)
     pub branch: Option<String>,
     /// Changed file paths (for condition evaluation)
@@ -50,6 +121,7 @@ impl ExecutionContext {
         Self {
             variables: HashMap::new(),
             trigger_event: None,
+            trigger_payload: serde_json::Value::Null,
             branch: None,
             changed_paths: Vec::new(),
             labels: Vec::new(),
@@ -68,6 +140,12 @@ impl ExecutionContext {
         self
     }
 
+    /// Set the trigger event payload
+    pub fn with_trigger_payload(mut self, payload: serde_json::Value) -> Self {
+        self.trigger_payload = payload;
+        self
+    }
+
     /// Set branch name
     pub fn with_branch(mut self, branch: String) -> Self {
         self.branch = Some(branch);
@@ -113,6 +191,9 @@ impl ExecutionContext {
             changed_paths: self.changed_paths.clone(),
             labels: self.labels.clone(),
             variables: self.variables.clone(),
+            trigger_event: self.trigger_event.clone(),
+            trigger_payload: self.trigger_payload.clone(),
+            stage_results: HashMap::new(),
         }
     }
 }
@@ -131,7 +212,265 @@ impl PipelineExecutor {
             database,
             condition_evaluator: ConditionEvaluator::new(),
             approval_service,
+            max_parallel: DEFAULT_MAX_PARALLEL_STAGES,
+            stage_runner: None,
+            notifier: None,
+            dashboard_url: None,
+            secret_vault: None,
+            slack_approvals: None,
+            email_notifications: None,
+            telegram: None,
+        }
+    }
+
+    /// Set how many independent stages may run concurrently (minimum 1) in
+    /// pipelines that don't set `max_parallel` themselves
+    pub fn with_max_parallel(mut self, max_parallel: usize) -> Self {
+        self.max_parallel = max_parallel.max(1);
+        self
+    }
+
+    /// Run stage agents through `stage_runner`
+    pub fn with_stage_runner(mut self, stage_runner: Arc<dyn StageRunner>) -> Self {
+        self.stage_runner = Some(stage_runner);
+        self
+    }
+
+    /// Deliver pipeline notifications through `notifier`
+    pub fn with_notifier(mut self, notifier: Arc<dyn NotificationSender>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Link runs in notifications to the dashboard at `dashboard_url`
+    pub fn with_dashboard_url(mut self, dashboard_url: impl Into<String>) -> Self {
+        self.dashboard_url = Some(dashboard_url.into());
+        self
+    }
+
+    /// Resolve stage secrets from `vault`
+    ///
+    /// Without a vault, stages that use secrets read them from a vault
+    /// keyed by `ORCHESTRATE_ENCRYPTION_KEY`.
+    pub fn with_secret_vault(mut self, vault: Arc<SecretVault>) -> Self {
+        self.secret_vault = Some(vault);
+        self
+    }
+
+    /// Post approval requests to Slack through `slack_approvals`
+    pub fn with_slack_approvals(mut self, slack_approvals: Arc<SlackApprovalService>) -> Self {
+        self.slack_approvals = Some(slack_approvals);
+        self
+    }
+
+    /// Email approval requests to approvers through `email_notifications`
+    pub fn with_email_notifications(
+        mut self,
+        email_notifications: Arc<EmailNotificationService>,
+    ) -> Self {
+        self.email_notifications = Some(email_notifications);
+        self
+    }
+
+    /// Send approval requests with Approve and Reject buttons to Telegram
+    /// through `telegram`
+    pub fn with_telegram(mut self, telegram: Arc<TelegramService>) -> Self {
+        self.telegram = Some(telegram);
+        self
+    }
+
+    /// Deliver notifications with an [`HttpNotificationSender`] configured
+    /// from the environment, linking runs to `ORCHESTRATE_DASHBOARD_URL`,
+    /// post approval requests to the connected Slack workspace, email them
+    /// when `ORCHESTRATE_SMTP_HOST` is set, and send them to Telegram when
+    /// `TELEGRAM_BOT_TOKEN` is set
+    pub fn with_notifications_from_env(mut self) -> Self {
+        self.notifier = Some(Arc::new(HttpNotificationSender::from_env()));
+        self.dashboard_url = std::env::var("ORCHESTRATE_DASHBOARD_URL").ok();
+        let db = (*self.database).clone();
+        match EmailNotificationService::from_env(db.clone()) {
+            Ok(service) => self.email_notifications = service.map(Arc::new),
+            Err(e) => warn!(error = %e, "Invalid SMTP configuration - approval emails disabled"),
+        }
+        match TelegramService::from_env(db.clone()) {
+            Ok(service) => self.telegram = service.map(Arc::new),
+            Err(e) => warn!(error = %e, "Invalid Telegram configuration - Telegram approvals disabled"),
+        }
+        self.slack_approvals = Some(Arc::new(SlackApprovalService::new(
+            db.clone(),
+            SlackService::new(db),
+        )));
+        self
+    }
+
+    /// Plan `run` of `definition` without starting it
+    ///
+    /// Stages are planned in the order they would start, assuming every stage
+    /// that runs succeeds, so conditions on earlier stages' results see them
+    /// as succeeded or skipped. Nothing is written to the database.
+    pub async fn plan_run(
+        &self,
+        definition: &PipelineDefinition,
+        run: &PipelineRun,
+    ) -> Result<PipelinePlan> {
+        let context = Self::run_context(definition, run);
+        let mut stage_results: HashMap<String, serde_json::Value> = definition
+            .stages
+            .iter()
+            .map(|stage| {
+                let pending = serde_json::json!({ "status": "pending", "attempts": 0 });
+                (stage.name.clone(), pending)
+            })
+            .collect();
+        let mut actions: HashMap<String, PlannedStageAction> = HashMap::new();
+        let mut estimates: HashMap<String, AgentTokenEstimate> = HashMap::new();
+        let mut remaining: Vec<&StageDefinition> = definition.stages.iter().collect();
+        let mut stages = Vec::new();
+
+        // Validation rejects dependency cycles, so a ready stage always exists
+        while let Some(index) = remaining
+            .iter()
+            .position(|stage| stage.depends_on.iter().all(|dep| actions.contains_key(dep)))
+        {
+            let stage_def = remaining.remove(index);
+            let blocked_by = stage_def.depends_on.iter().find(|dep| {
+                matches!(
+                    actions.get(*dep),
+                    Some(PlannedStageAction::Fail { .. } | PlannedStageAction::Blocked { .. })
+                )
+            });
+
+            let (action, instances) = match blocked_by {
+                Some(dep) => (
+                    PlannedStageAction::Blocked {
+                        reason: format!("Waits on '{}', which would not complete", dep),
+                    },
+                    Vec::new(),
+                ),
+                None => self.plan_stage(stage_def, &context, &stage_results).await,
+            };
+
+            let mut agents = Vec::new();
+            for (instance, prompt) in instances {
+                let estimate = match estimates.get(&stage_def.agent) {
+                    Some(estimate) => *estimate,
+                    None => {
+                        let estimate = self
+                            .database
+                            .estimate_agent_tokens(&stage_def.agent)
+                            .await?;
+                        estimates.insert(stage_def.agent.clone(), estimate);
+                        estimate
+                    }
+                };
+                agents.push(PlannedAgent {
+                    instance,
+                    agent: stage_def.agent.clone(),
+                    prompt,
+                    estimate,
+                });
+            }
+
+            let result = match &action {
+                PlannedStageAction::Run => Some(("succeeded", 1)),
+                PlannedStageAction::Skip { .. } => Some(("skipped", 0)),
+                PlannedStageAction::Fail { .. } => Some(("failed", 1)),
+                PlannedStageAction::Blocked { .. } => None,
+            };
+            if let Some((status, attempts)) = result {
+                stage_results.insert(
+                    stage_def.name.clone(),
+                    serde_json::json!({ "status": status, "attempts": attempts }),
+                );
+            }
+            actions.insert(stage_def.name.clone(), action.clone());
+
+            stages.push(PlannedStage {
+                name: stage_def.name.clone(),
+                depends_on: stage_def.depends_on.clone(),
+                condition: stage_def.when.as_ref().map(describe_condition),
+                action,
+                approvers: if stage_def.requires_approval {
+                    stage_def.approvers.clone()
+                } else {
+                    Vec::new()
+                },
+                agents,
+            });
+        }
+
+        Ok(PipelinePlan {
+            pipeline: definition.name.clone(),
+            trigger: run.trigger_event.clone(),
+            variables: context.variables.into_iter().collect(),
+            stages,
+        })
+    }
+
+    /// Planned action of a stage whose dependencies would complete, with the
+    /// instance names and resolved prompts of the agents it would spawn
+    async fn plan_stage(
+        &self,
+        stage_def: &StageDefinition,
+        context: &ExecutionContext,
+        stage_results: &HashMap<String, serde_json::Value>,
+    ) -> (PlannedStageAction, Vec<(String, String)>) {
+        if let Some(condition) = &stage_def.when {
+            let condition_context = context
+                .to_condition_context()
+                .with_stage_results(stage_results.clone());
+            match self
+                .condition_evaluator
+                .evaluate(condition, &condition_context)
+            {
+                Ok(EvaluationResult::Execute) => {}
+                Ok(EvaluationResult::Skip(reason)) => {
+                    let reason = reason.to_string();
+                    return (PlannedStageAction::Skip { reason }, Vec::new());
+                }
+                Err(e) => {
+                    let reason = format!("Condition could not be evaluated: {}", e);
+                    return (PlannedStageAction::Fail { reason }, Vec::new());
+                }
+            }
         }
+
+        let Some(matrix) = &stage_def.matrix else {
+            let prompt = context.substitute_variables(&stage_def.task);
+            return (
+                PlannedStageAction::Run,
+                vec![(stage_def.name.clone(), prompt)],
+            );
+        };
+
+        let combinations = match self.resolve_matrix(matrix).await {
+            Ok(combinations) if !combinations.is_empty() => combinations,
+            Ok(_) => {
+                let reason = format!(
+                    "Matrix stage '{}' has no combinations to run",
+                    stage_def.name
+                );
+                return (PlannedStageAction::Fail { reason }, Vec::new());
+            }
+            Err(e) => {
+                let reason = e.to_string();
+                return (PlannedStageAction::Fail { reason }, Vec::new());
+            }
+        };
+        let instances = combinations
+            .into_iter()
+            .map(|combination| {
+                let mut instance_context = context.clone();
+                for (name, value) in &combination {
+                    instance_context.set_variable(format!("matrix.{}", name), value.clone());
+                }
+                (
+                    StageMatrix::instance_name(&stage_def.name, &combination),
+                    instance_context.substitute_variables(&stage_def.task),
+                )
+            })
+            .collect();
+        (PlannedStageAction::Run, instances)
     }
 
     /// Create a pipeline run from a trigger event
@@ -167,14 +506,20 @@ impl PipelineExecutor {
             .await?
             .ok_or_else(|| Error::Other(format!("Pipeline run {} not found", run_id)))?;
 
+        if run.status == PipelineRunStatus::Cancelled {
+            return Err(Error::Other(format!(
+                "Pipeline run {} was cancelled",
+                run_id
+            )));
+        }
+        self.check_concurrency(&run, definition).await?;
+
         // Mark run as running
         run.mark_running();
-        self.database.update_pipeline_run(&run).await?;
+        self.save_run(&run).await?;
 
-        // Create execution context with pipeline variables
-        let mut context = ExecutionContext::new()
-            .with_variables(definition.variables.clone())
-            .with_trigger(run.trigger_event.clone().unwrap_or_default());
+        // Create execution context with pipeline and trigger variables
+        let context = Self::run_context(definition, &run);
 
         // Create initial stages in database
         for stage_def in &definition.stages {
@@ -182,906 +527,3283 @@ impl PipelineExecutor {
             self.database.insert_pipeline_stage(&stage).await?;
         }
 
-        // Execute stages
-        let result = self.execute_stages(run_id, definition, &mut context).await;
+        // Track a saga only when some stage declares compensation
+        if definition.stages.iter().any(|s| !s.compensation.is_empty()) {
+            let mut new_saga = Saga::new(SagaWorkflowType::PipelineRun, run_id.to_string());
+            new_saga.id = self.database.insert_saga(&new_saga).await?;
+        }
 
-        // Update run status based on result
+        self.drive_run(run_id, definition, context).await
+    }
+
+    /// Refuse to start a run that is queued behind earlier runs of its pipeline
+    ///
+    /// Runs in progress and pending runs triggered before this one take the
+    /// pipeline's concurrency slots first.
+    async fn check_concurrency(
+        &self,
+        run: &PipelineRun,
+        definition: &PipelineDefinition,
+    ) -> Result<()> {
+        let Some(concurrency) = &definition.concurrency else {
+            return Ok(());
+        };
+
+        let ahead = self
+            .database
+            .list_active_pipeline_runs(run.pipeline_id)
+            .await?
+            .iter()
+            .filter(|other| match (other.id, run.id) {
+                (Some(other_id), Some(run_id)) if other_id != run_id => {
+                    other.status != PipelineRunStatus::Pending || other_id < run_id
+                }
+                _ => false,
+            })
+            .count();
+
+        if ahead >= concurrency.limit as usize {
+            return Err(Error::Other(format!(
+                "Pipeline run {} is queued behind {} earlier run(s) of pipeline '{}'",
+                run.id.unwrap_or_default(),
+                ahead,
+                definition.name
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Continue a run that paused for approval
+    ///
+    /// Stages that already finished keep their results; stages waiting for
+    /// approval proceed if approved and are cancelled if rejected.
+    pub async fn resume_run(&self, run_id: i64) -> Result<()> {
         let mut run = self
             .database
             .get_pipeline_run(run_id)
             .await?
             .ok_or_else(|| Error::Other(format!("Pipeline run {} not found", run_id)))?;
 
-        match &result {
-            Ok(_) => {
-                run.mark_succeeded();
-                info!(run_id = run_id, "Pipeline run succeeded");
-            }
-            Err(ref e) => {
-                run.mark_failed();
-                error!(run_id = run_id, error = %e, "Pipeline run failed");
-            }
+        if run.status != PipelineRunStatus::WaitingApproval {
+            return Err(Error::Other(format!(
+                "Pipeline run {} is not waiting for approval (status: {})",
+                run_id,
+                run.status.as_str()
+            )));
         }
 
-        self.database.update_pipeline_run(&run).await?;
+        let pipeline = self
+            .database
+            .get_pipeline(run.pipeline_id)
+            .await?
+            .ok_or_else(|| Error::Other(format!("Pipeline {} not found", run.pipeline_id)))?;
+        let definition = PipelineDefinition::from_yaml_str(&pipeline.definition)?;
+
+        info!(run_id = run_id, "Resuming pipeline execution");
 
-        result
+        run.mark_running();
+        self.save_run(&run).await?;
+
+        let context = Self::run_context(&definition, &run);
+
+        self.drive_run(run_id, &definition, context).await
     }
 
-    /// Execute all stages respecting dependencies
-    async fn execute_stages(
-        &self,
-        run_id: i64,
-        definition: &PipelineDefinition,
-        context: &mut ExecutionContext,
-    ) -> Result<()> {
-        // Build dependency graph
-        let graph = self.build_dependency_graph(definition)?;
+    /// Resume a failed run from the stages that did not complete
+    ///
+    /// Stages that succeeded or were skipped keep their results and are not
+    /// executed again. Failed and cancelled stages are reset and run with the
+    /// pipeline's current definition, followed by the stages waiting on them.
+    /// Runs whose completed stages were already compensated cannot be resumed.
+    pub async fn resume_failed_run(&self, run_id: i64) -> Result<()> {
+        let mut run = self
+            .database
+            .get_pipeline_run(run_id)
+            .await?
+            .ok_or_else(|| Error::Other(format!("Pipeline run {} not found", run_id)))?;
 
-        // Track completed stages
-        let mut completed: HashSet<String> = HashSet::new();
-        let mut failed: HashSet<String> = HashSet::new();
+        if run.status != PipelineRunStatus::Failed {
+            return Err(Error::Other(format!(
+                "Pipeline run {} has not failed (status: {})",
+                run_id,
+                run.status.as_str()
+            )));
+        }
 
-        // Execute stages in topological order
-        while completed.len() + failed.len() < definition.stages.len() {
-            // Find stages ready to execute (all dependencies completed)
-            let ready_stages: Vec<&StageDefinition> = definition
-                .stages
+        if let Some(mut saga) = self
+            .database
+            .get_saga_for_workflow(SagaWorkflowType::PipelineRun, &run_id.to_string())
+            .await?
+        {
+            let compensated = self
+                .database
+                .list_saga_compensations(saga.id)
+                .await?
                 .iter()
-                .filter(|stage| {
-                    !completed.contains(&stage.name)
-                        && !failed.contains(&stage.name)
-                        && graph
-                            .get(&stage.name)
-                            .map(|deps| deps.iter().all(|dep| completed.contains(dep)))
-                            .unwrap_or(true)
-                })
-                .collect();
+                .any(|compensation| compensation.status != CompensationStatus::Pending);
+            if compensated {
+                return Err(Error::Other(format!(
+                    "Pipeline run {} compensated its completed stages; start a new run instead",
+                    run_id
+                )));
+            }
+            saga.status = SagaStatus::Running;
+            saga.failed_step = None;
+            saga.error_message = None;
+            saga.completed_at = None;
+            self.database.update_saga(&saga).await?;
+        }
 
-            if ready_stages.is_empty() {
-                // No more stages ready, check if we're done or stuck
-                if !failed.is_empty() {
-                    return Err(Error::Other(format!(
-                        "Pipeline execution halted due to failed stages: {:?}",
-                        failed
-                    )));
-                }
-                break;
+        let pipeline = self
+            .database
+            .get_pipeline(run.pipeline_id)
+            .await?
+            .ok_or_else(|| Error::Other(format!("Pipeline {} not found", run.pipeline_id)))?;
+        let definition = PipelineDefinition::from_yaml_str(&pipeline.definition)?;
+        self.check_concurrency(&run, &definition).await?;
+
+        let stages = self.database.list_pipeline_stages(run_id).await?;
+        for mut stage in stages.iter().cloned() {
+            if matches!(
+                stage.status,
+                PipelineStageStatus::Failed | PipelineStageStatus::Cancelled
+            ) {
+                stage.reset();
+                self.save_stage(&stage).await?;
+            }
+        }
+        // Stages added to the definition since the run started
+        for stage_def in &definition.stages {
+            if !stages.iter().any(|s| s.stage_name == stage_def.name) {
+                let stage = PipelineStage::new(run_id, stage_def.name.clone());
+                self.database.insert_pipeline_stage(&stage).await?;
             }
+        }
 
-            // Group stages for parallel execution
-            let parallel_groups = self.group_parallel_stages(&ready_stages);
+        info!(run_id = run_id, "Resuming failed pipeline run");
 
-            // Execute each group in parallel
-            for group in parallel_groups {
-                let mut tasks = vec![];
+        run.mark_resumed();
+        self.save_run(&run).await?;
 
-                for stage_def in group {
-                    let stage_name = stage_def.name.clone();
-                    let executor = self.clone_for_stage();
-                    let stage_def = stage_def.clone();
-                    let context_clone = context.clone();
+        let context = Self::run_context(&definition, &run);
 
-                    // Spawn parallel task for each stage in the group
-                    let task = tokio::spawn(async move {
-                        executor
-                            .execute_stage(run_id, &stage_def, &context_clone)
-                            .await
-                    });
+        self.drive_run(run_id, &definition, context).await
+    }
 
-                    tasks.push((stage_name, task));
-                }
+    /// Build the execution context for a run
+    ///
+    /// Variables supplied by the trigger override the pipeline's defaults.
+    fn run_context(definition: &PipelineDefinition, run: &PipelineRun) -> ExecutionContext {
+        let mut variables = definition.variables.clone();
+        variables.extend(run.variables.clone());
+
+        ExecutionContext::new()
+            .with_variables(variables)
+            .with_trigger(run.trigger_event.clone().unwrap_or_default())
+            .with_trigger_payload(run.trigger_payload.clone().unwrap_or_default())
+    }
 
-                // Wait for all parallel stages to complete
-                for (stage_name, task) in tasks {
-                    match task.await {
-                        Ok(Ok(_)) => {
-                            completed.insert(stage_name.clone());
-                            info!(stage = %stage_name, "Stage completed successfully");
-                        }
-                        Ok(Err(e)) => {
-                            failed.insert(stage_name.clone());
-                            error!(stage = %stage_name, error = %e, "Stage failed");
-
-                            // Check failure action
-                            let stage_def = definition
-                                .stages
-                                .iter()
-                                .find(|s| s.name == stage_name)
-                                .unwrap();
-
-                            match stage_def.on_failure {
-                                Some(FailureAction::Halt) | None => {
-                                    return Err(Error::Other(format!(
-                                        "Stage '{}' failed with halt action",
-                                        stage_name
-                                    )));
-                                }
-                                Some(FailureAction::Continue) => {
-                                    warn!(stage = %stage_name, "Continuing despite stage failure");
-                                }
-                                Some(FailureAction::Rollback) => {
-                                    // Execute rollback
-                                    if let Some(rollback_to) = &stage_def.rollback_to {
-                                        warn!(
-                                            stage = %stage_name,
-                                            rollback_to = %rollback_to,
-                                            "Stage failed, executing rollback"
-                                        );
-
-                                        // Execute rollback
-                                        match self
-                                            .execute_rollback(
-                                                run_id,
-                                                &stage_name,
-                                                rollback_to,
-                                                crate::RollbackTriggerType::Automatic,
-                                            )
-                                            .await
-                                        {
-                                            Ok(_) => {
-                                                info!(
-                                                    stage = %stage_name,
-                                                    "Rollback completed successfully"
-                                                );
-                                            }
-                                            Err(rollback_err) => {
-                                                error!(
-                                                    stage = %stage_name,
-                                                    error = %rollback_err,
-                                                    "Rollback failed"
-                                                );
-                                            }
-                                        }
-                                    }
-
-                                    return Err(Error::Other(format!(
-                                        "Stage '{}' failed with rollback action",
-                                        stage_name
-                                    )));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            failed.insert(stage_name.clone());
-                            error!(stage = %stage_name, error = %e, "Stage task panicked");
-                            return Err(Error::Other(format!(
-                                "Stage '{}' task panicked: {}",
-                                stage_name, e
-                            )));
-                        }
-                    }
-                }
+    /// Resume the run behind an approval once the approval has been decided
+    ///
+    /// Does nothing while the approval is still undecided or when the run is
+    /// not paused (e.g. other stages are still executing).
+    pub async fn resume_after_approval(&self, approval: &ApprovalRequest) -> Result<()> {
+        if !approval.status.is_terminal() {
+            return Ok(());
+        }
+
+        let waiting = self
+            .database
+            .get_pipeline_run(approval.run_id)
+            .await?
+            .is_some_and(|run| run.status == PipelineRunStatus::WaitingApproval);
+        if !waiting {
+            debug!(
+                run_id = approval.run_id,
+                "Run is not paused for approval, nothing to resume"
+            );
+            return Ok(());
+        }
+
+        self.resume_run(approval.run_id).await
+    }
+
+    /// Apply timeout actions to expired approvals and resume the affected runs
+    pub async fn process_approval_timeouts(&self) -> Result<Vec<ApprovalRequest>> {
+        let processed = self.approval_service.process_timeouts().await?;
+
+        for approval in &processed {
+            if approval.status.is_terminal() {
+                warn!(
+                    run_id = approval.run_id,
+                    status = approval.status.as_str(),
+                    "Approval timed out"
+                );
+            } else {
+                warn!(
+                    run_id = approval.run_id,
+                    approvers = %approval.required_approvers,
+                    "Approval timed out, escalated"
+                );
             }
+            self.resume_after_decision(approval).await;
         }
 
-        Ok(())
+        Ok(processed)
     }
 
-    /// Execute a single stage
-    async fn execute_stage(
+    /// Resume the run behind a decided approval, logging rather than
+    /// returning how the resumed run ended
+    async fn resume_after_decision(&self, approval: &ApprovalRequest) {
+        if let Err(e) = self.resume_after_approval(approval).await {
+            warn!(
+                run_id = approval.run_id,
+                error = %e,
+                "Pipeline run did not succeed after approval decision"
+            );
+        }
+    }
+
+    /// Execute stages and record the run's outcome
+    ///
+    /// A run with stages still waiting for approval is left paused; it is
+    /// neither completed nor compensated until it is resumed.
+    #[tracing::instrument(name = "pipeline_run", skip(self, definition, context), fields(pipeline = %definition.name))]
+    async fn drive_run(
         &self,
         run_id: i64,
-        stage_def: &StageDefinition,
-        context: &ExecutionContext,
+        definition: &PipelineDefinition,
+        mut context: ExecutionContext,
     ) -> Result<()> {
-        info!(
-            stage = %stage_def.name,
-            agent = %stage_def.agent,
-            "Executing stage"
-        );
+        let started_at = chrono::Utc::now();
+        let result = self.execute_stages(run_id, definition, &mut context).await;
 
-        // Load stage from database
-        let mut stage = self
+        // Update run status based on result
+        let mut run = self
             .database
-            .get_pipeline_stage_by_name(run_id, &stage_def.name)
+            .get_pipeline_run(run_id)
             .await?
-            .ok_or_else(|| {
-                Error::Other(format!("Stage '{}' not found in database", stage_def.name))
-            })?;
-
-        // Evaluate condition if present
-        if let Some(ref condition) = stage_def.when {
-            let condition_context = context.to_condition_context();
-            let eval_result = self
-                .condition_evaluator
-                .evaluate(condition, &condition_context)?;
-
-            if let EvaluationResult::Skip(reason) = eval_result {
-                info!(
-                    stage = %stage_def.name,
-                    reason = %reason,
-                    "Skipping stage due to condition"
-                );
-
-                // Mark stage as skipped
-                stage.mark_skipped();
-                self.database.update_pipeline_stage(&stage).await?;
+            .ok_or_else(|| Error::Other(format!("Pipeline run {} not found", run_id)))?;
 
-                // Return success (skipped stages don't fail the pipeline)
+        match &result {
+            Ok(StageOutcome::AwaitingApproval) => {
+                run.mark_waiting_approval();
+                self.save_run(&run).await?;
+                info!(run_id = run_id, "Pipeline run paused for approval");
+                if let Err(e) = self.notify_approvals(&run, definition, started_at).await {
+                    warn!(run_id = run_id, error = %e, "Failed to send approval notifications");
+                }
                 return Ok(());
             }
+            Ok(StageOutcome::Finished) => {
+                run.mark_succeeded();
+                info!(run_id = run_id, "Pipeline run succeeded");
+            }
+            Err(ref e) => {
+                // A rejected approval or a newer run superseding this one
+                // cancels the run rather than failing it
+                let cancelled = run.status == PipelineRunStatus::Cancelled
+                    || !self
+                        .database
+                        .list_pipeline_stages_by_status(run_id, PipelineStageStatus::Cancelled)
+                        .await?
+                        .is_empty();
+                if cancelled {
+                    run.mark_cancelled();
+                    warn!(run_id = run_id, error = %e, "Pipeline run cancelled");
+                } else {
+                    run.mark_failed();
+                    error!(run_id = run_id, error = %e, "Pipeline run failed");
+                }
+            }
         }
 
-        // Handle approval gate if required
-        if stage_def.requires_approval {
-            info!(
-                stage = %stage_def.name,
-                approvers = ?stage_def.approvers,
-                "Stage requires approval"
-            );
+        self.save_run(&run).await?;
 
-            // Mark stage as waiting for approval
-            stage.mark_waiting_approval();
-            self.database.update_pipeline_stage(&stage).await?;
+        if let Some(mut saga) = self
+            .database
+            .get_saga_for_workflow(SagaWorkflowType::PipelineRun, &run_id.to_string())
+            .await?
+        {
+            match &result {
+                Ok(_) => self.complete_saga(&mut saga).await?,
+                Err(e) => self.compensate_saga(run_id, &mut saga, &e.to_string()).await?,
+            }
+        }
 
-            // Mark pipeline run as waiting for approval
-            let mut run = self
-                .database
-                .get_pipeline_run(run_id)
-                .await?
-                .ok_or_else(|| Error::Other("Pipeline run not found".to_string()))?;
-            run.mark_waiting_approval();
-            self.database.update_pipeline_run(&run).await?;
+        if let Err(e) = self.publish_run_event(&run, definition).await {
+            warn!(run_id = run_id, error = %e, "Failed to enqueue outbound webhooks");
+        }
+        if let Err(e) = self.notify(&run, definition, result.as_ref().err()).await {
+            warn!(run_id = run_id, error = %e, "Failed to send pipeline notifications");
+        }
 
-            // Create approval request
-            let stage_id = stage
-                .id
-                .ok_or_else(|| Error::Other("Stage ID is required".to_string()))?;
+        result.map(|_| ())
+    }
 
-            // Determine required count (default to all approvers)
-            let required_count = stage_def.approvers.len() as i32;
+    /// Log outbound webhook deliveries for a run that succeeded or failed
+    async fn publish_run_event(
+        &self,
+        run: &PipelineRun,
+        definition: &PipelineDefinition,
+    ) -> Result<()> {
+        let pipeline_name = self
+            .database
+            .get_pipeline(run.pipeline_id)
+            .await?
+            .map(|pipeline| pipeline.name)
+            .unwrap_or_else(|| definition.name.clone());
 
-            // Create approval with default 24-hour timeout
-            let _approval = self
-                .approval_service
-                .create_approval(
-                    stage_id,
-                    run_id,
-                    stage_def.approvers.clone(),
-                    required_count,
-                    Some(24 * 3600), // 24 hours default timeout
-                    Some("reject".to_string()), // Default to reject on timeout
-                )
-                .await?;
+        if let Some(event) = OutboundEvent::for_pipeline_run(run, &pipeline_name) {
+            self.database.enqueue_outbound_event(&event).await?;
+        }
+        Ok(())
+    }
 
-            info!(
-                stage = %stage_def.name,
-                "Approval request created - pipeline will pause until approved"
-            );
+    /// Store a run's progress and emit it to the event feed
+    async fn save_run(&self, run: &PipelineRun) -> Result<()> {
+        self.database.update_pipeline_run(run).await?;
+        crate::event_bus::emit(BusEvent::new(
+            "pipeline.run_updated",
+            serde_json::json!({
+                "run_id": run.id,
+                "pipeline_id": run.pipeline_id,
+                "status": run.status.as_str(),
+            }),
+        ));
+        Ok(())
+    }
 
-            // NOTE: The executor should not proceed with this stage yet.
-            // In a real implementation, this would return an indication that
-            // the pipeline is paused, and a separate approval handler would
-            // resume execution after approval is granted.
-            // For now, we return an error to halt execution.
-            return Err(Error::Other(format!(
-                "Stage '{}' requires approval - pipeline execution paused",
-                stage_def.name
-            )));
+    /// Store a stage's progress and emit it to the event feed
+    async fn save_stage(&self, stage: &PipelineStage) -> Result<()> {
+        self.database.update_pipeline_stage(stage).await?;
+        let mut event = BusEvent::new(
+            "pipeline.stage_updated",
+            serde_json::json!({
+                "run_id": stage.run_id,
+                "stage_id": stage.id,
+                "stage": stage.stage_name,
+                "status": stage.status.as_str(),
+            }),
+        );
+        if let Some(agent_id) = &stage.agent_id {
+            event = event.with_agent(agent_id);
         }
+        crate::event_bus::emit(event);
+        Ok(())
+    }
 
-        // Mark stage as running
-        // TODO: Set actual agent_id when agent spawning is implemented
-        stage.mark_running(None);
-        self.database.update_pipeline_stage(&stage).await?;
-
-        // Substitute variables in task
-        let task = context.substitute_variables(&stage_def.task);
-
-        // Execute with timeout if specified
-        let result = if let Some(timeout_str) = &stage_def.timeout {
-            let duration = parse_timeout(timeout_str)?;
-            match timeout(duration, self.spawn_agent(&stage_def.agent, &task)).await {
-                Ok(r) => r,
-                Err(_) => {
-                    warn!(
-                        stage = %stage_def.name,
-                        timeout = %timeout_str,
-                        "Stage timed out"
-                    );
-                    Err(Error::Other(format!(
-                        "Stage '{}' timed out after {}",
-                        stage_def.name, timeout_str
-                    )))
-                }
-            }
-        } else {
-            self.spawn_agent(&stage_def.agent, &task).await
+    /// Notify the pipeline's configured targets about a finished run
+    ///
+    /// Failed runs are reported as a rollback when a stage rolled back, and
+    /// succeeded runs only when the pipeline's previous finished run failed.
+    /// Delivery failures are logged and do not affect the run.
+    async fn notify(
+        &self,
+        run: &PipelineRun,
+        definition: &PipelineDefinition,
+        error: Option<&Error>,
+    ) -> Result<()> {
+        let (Some(notifier), Some(settings)) = (&self.notifier, &definition.notifications) else {
+            return Ok(());
         };
-
-        // Update stage status based on result
-        let mut stage = self
+        let run_id = run.id.unwrap_or_default();
+        // The rerun command and dashboard link refer to the stored pipeline
+        let pipeline_name = self
             .database
-            .get_pipeline_stage_by_name(run_id, &stage_def.name)
+            .get_pipeline(run.pipeline_id)
             .await?
-            .ok_or_else(|| {
-                Error::Other(format!("Stage '{}' not found in database", stage_def.name))
-            })?;
-
-        match result {
-            Ok(_) => {
-                stage.mark_succeeded();
-                self.database.update_pipeline_stage(&stage).await?;
-                Ok(())
+            .map(|pipeline| pipeline.name)
+            .unwrap_or_else(|| definition.name.clone());
+
+        let mut notification = match run.status {
+            PipelineRunStatus::Failed => {
+                let failed_stage = self
+                    .database
+                    .list_pipeline_stages_by_status(run_id, PipelineStageStatus::Failed)
+                    .await?
+                    .into_iter()
+                    .next()
+                    .map(|stage| stage.stage_name);
+                let error = error.map(|e| e.to_string()).unwrap_or_default();
+                let rollback = self.database.list_rollback_events(run_id).await?.pop();
+
+                match rollback {
+                    Some(rollback) => PipelineNotification::new(
+                        PipelineNotificationEvent::Rollback,
+                        &pipeline_name,
+                        run_id,
+                    )
+                    .with_failure(Some(rollback.failed_stage_name.clone()), error)
+                    .with_rollback(&rollback),
+                    None => PipelineNotification::new(
+                        PipelineNotificationEvent::Failure,
+                        &pipeline_name,
+                        run_id,
+                    )
+                    .with_failure(failed_stage, error),
+                }
             }
-            Err(e) => {
-                stage.mark_failed();
-                self.database.update_pipeline_stage(&stage).await?;
-                Err(e)
+            PipelineRunStatus::Succeeded => {
+                let previous = self
+                    .database
+                    .list_pipeline_runs(run.pipeline_id)
+                    .await?
+                    .into_iter()
+                    .filter(|r| r.id.is_some_and(|id| id < run_id))
+                    .filter(|r| {
+                        matches!(
+                            r.status,
+                            PipelineRunStatus::Succeeded | PipelineRunStatus::Failed
+                        )
+                    })
+                    .max_by_key(|r| r.id);
+                if !previous.is_some_and(|r| r.status == PipelineRunStatus::Failed) {
+                    return Ok(());
+                }
+                PipelineNotification::new(
+                    PipelineNotificationEvent::Recovery,
+                    &pipeline_name,
+                    run_id,
+                )
             }
-        }
-    }
+            _ => return Ok(()),
+        };
 
-    /// Spawn an agent for a stage
-    async fn spawn_agent(&self, agent_type: &str, _task: &str) -> Result<()> {
-        // TODO: Implement actual agent spawning
-        // For now, this is a placeholder that simulates agent execution
+        if !settings.notifies(notification.event) {
+            return Ok(());
+        }
+        if let Some(commit_sha) = &run.commit_sha {
+            notification = notification.with_commit(commit_sha);
+        }
+        if let Some(dashboard_url) = &self.dashboard_url {
+            notification = notification.with_dashboard_url(dashboard_url);
+        }
 
-        // Special handling for test agents
-        if agent_type.starts_with("failing-") {
-            debug!("Simulating agent failure for testing");
-            return Err(Error::Other("Simulated agent failure".to_string()));
+        for target in NotificationTarget::from_settings(settings) {
+            if let Err(e) = notifier.send(&target, &notification).await {
+                warn!(
+                    run_id = run_id,
+                    target = ?target,
+                    error = %e,
+                    "Failed to deliver pipeline notification"
+                );
+            }
         }
 
-        debug!("Agent spawning not yet implemented");
+        info!(
+            run_id = run_id,
+            event = notification.event.as_str(),
+            "Sent pipeline notifications"
+        );
         Ok(())
     }
 
-    /// Build dependency graph from stage definitions
-    fn build_dependency_graph(
+    /// Notify the pipeline's configured targets about approvals requested
+    /// since `since`
+    ///
+    /// Stages still waiting on approvals requested before `since` were
+    /// already notified when the run first paused for them.
+    async fn notify_approvals(
         &self,
+        run: &PipelineRun,
         definition: &PipelineDefinition,
-    ) -> Result<HashMap<String, Vec<String>>> {
-        let mut graph: HashMap<String, Vec<String>> = HashMap::new();
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let (Some(notifier), Some(settings)) = (&self.notifier, &definition.notifications) else {
+            return Ok(());
+        };
+        if !settings.notifies(PipelineNotificationEvent::Approval) {
+            return Ok(());
+        }
+        let run_id = run.id.unwrap_or_default();
+        let pipeline_name = self
+            .database
+            .get_pipeline(run.pipeline_id)
+            .await?
+            .map(|pipeline| pipeline.name)
+            .unwrap_or_else(|| definition.name.clone());
 
-        for stage in &definition.stages {
-            let deps = stage.depends_on.clone();
+        let waiting = self
+            .database
+            .list_pipeline_stages_by_status(run_id, PipelineStageStatus::WaitingApproval)
+            .await?;
+        for stage in waiting {
+            let Some(stage_id) = stage.id else {
+                continue;
+            };
+            let Some(approval) = self
+                .approval_service
+                .get_approval_by_stage(stage_id)
+                .await?
+            else {
+                continue;
+            };
+            if approval.created_at < since {
+                continue;
+            }
 
-            // Add parallel_with as a dependency (they must start together, but depend on same parents)
-            if let Some(parallel) = &stage.parallel_with {
-                // The parallel stage and this stage share dependencies
-                // We don't add the parallel stage as a dependency, but we need to track it
-                debug!(
-                    stage = %stage.name,
-                    parallel_with = %parallel,
-                    "Stage runs in parallel with another"
-                );
+            let mut notification = PipelineNotification::new(
+                PipelineNotificationEvent::Approval,
+                &pipeline_name,
+                run_id,
+            )
+            .with_approval(&stage.stage_name, &approval);
+            if let Some(dashboard_url) = &self.dashboard_url {
+                notification = notification.with_dashboard_url(dashboard_url);
             }
 
-            graph.insert(stage.name.clone(), deps);
+            for target in NotificationTarget::from_settings(settings) {
+                if let Err(e) = notifier.send(&target, &notification).await {
+                    warn!(
+                        run_id = run_id,
+                        target = ?target,
+                        error = %e,
+                        "Failed to deliver approval notification"
+                    );
+                }
+            }
+            info!(
+                run_id = run_id,
+                stage = %stage.stage_name,
+                "Sent approval notifications"
+            );
         }
-
-        Ok(graph)
+        Ok(())
     }
 
-    /// Group stages for parallel execution
-    fn group_parallel_stages<'a>(
+    /// Execute all stages respecting dependencies
+    ///
+    /// Stages start as soon as everything they depend on has completed, with
+    /// at most `max_parallel` stages in flight (the pipeline's own setting, or
+    /// the executor's default). Once a stage halts the
+    /// pipeline no further stages are started, but stages already running are
+    /// allowed to finish. Stages that finished before the run was paused for
+    /// approval are not executed again.
+    async fn execute_stages(
         &self,
-        stages: &[&'a StageDefinition],
-    ) -> Vec<Vec<&'a StageDefinition>> {
-        let mut groups: Vec<Vec<&StageDefinition>> = vec![];
-        let mut processed: HashSet<String> = HashSet::new();
+        run_id: i64,
+        definition: &PipelineDefinition,
+        context: &mut ExecutionContext,
+    ) -> Result<StageOutcome> {
+        // Build dependency graph
+        let graph = self.build_dependency_graph(definition)?;
+        let max_parallel = definition.max_parallel.unwrap_or(self.max_parallel).max(1);
 
-        for stage in stages {
-            if processed.contains(&stage.name) {
-                continue;
+        // Track completed stages
+        let mut completed: HashSet<String> = HashSet::new();
+        let mut failed: HashSet<String> = HashSet::new();
+        let mut started: HashSet<String> = HashSet::new();
+        let mut awaiting_approval: HashSet<String> = HashSet::new();
+
+        for stage in self.database.list_pipeline_stages(run_id).await? {
+            match stage.status {
+                PipelineStageStatus::Succeeded | PipelineStageStatus::Skipped => {
+                    completed.insert(stage.stage_name.clone());
+                    started.insert(stage.stage_name);
+                }
+                PipelineStageStatus::Failed | PipelineStageStatus::Cancelled => {
+                    failed.insert(stage.stage_name.clone());
+                    started.insert(stage.stage_name);
+                }
+                _ => {}
             }
+        }
 
-            let mut group = vec![*stage];
-            processed.insert(stage.name.clone());
+        // Compensations are registered as stages succeed, in completion order
+        let saga = self
+            .database
+            .get_saga_for_workflow(SagaWorkflowType::PipelineRun, &run_id.to_string())
+            .await?;
+        let mut compensation_sequence = match &saga {
+            Some(saga) => self
+                .database
+                .list_saga_compensations(saga.id)
+                .await?
+                .iter()
+                .map(|c| c.sequence)
+                .max()
+                .unwrap_or(0),
+            None => 0,
+        };
 
-            // Find all stages that run in parallel with this one
-            if let Some(parallel_with) = &stage.parallel_with {
-                for other_stage in stages {
-                    if other_stage.name == *parallel_with && !processed.contains(&other_stage.name)
-                    {
-                        group.push(*other_stage);
-                        processed.insert(other_stage.name.clone());
+        let mut running: JoinSet<Result<StageOutcome>> = JoinSet::new();
+        let mut running_names: HashMap<tokio::task::Id, String> = HashMap::new();
+        let mut halt_error: Option<Error> = None;
+
+        loop {
+            if halt_error.is_none() && self.is_run_cancelled(run_id).await? {
+                warn!(
+                    run_id = run_id,
+                    "Pipeline run cancelled, not starting more stages"
+                );
+                halt_error = Some(Error::Other(format!(
+                    "Pipeline run {} was cancelled",
+                    run_id
+                )));
+            }
+
+            if halt_error.is_none() {
+                // Find stages ready to execute (all dependencies completed)
+                let ready_stages: Vec<&StageDefinition> = definition
+                    .stages
+                    .iter()
+                    .filter(|stage| {
+                        !started.contains(&stage.name)
+                            && graph
+                                .get(&stage.name)
+                                .map(|deps| deps.iter().all(|dep| completed.contains(dep)))
+                                .unwrap_or(true)
+                    })
+                    .collect();
+
+                // Stages grouped with parallel_with are launched back to back
+                let launch_order = self
+                    .group_parallel_stages(&ready_stages)
+                    .into_iter()
+                    .flatten();
+
+                for stage_def in launch_order {
+                    if running.len() >= max_parallel {
+                        debug!(
+                            stage = %stage_def.name,
+                            max_parallel = max_parallel,
+                            "Parallelism cap reached, deferring stage"
+                        );
+                        break;
                     }
+
+                    let mut executor = self.clone_for_stage();
+                    executor.max_parallel = max_parallel;
+                    let stage = stage_def.clone();
+                    let context_clone = context.clone();
+
+                    let handle = running.spawn(async move {
+                        executor
+                            .execute_stage(run_id, &stage, &context_clone)
+                            .await
+                    });
+                    running_names.insert(handle.id(), stage_def.name.clone());
+                    started.insert(stage_def.name.clone());
                 }
             }
 
-            // Also check if other stages want to run parallel with this one
-            for other_stage in stages {
-                if let Some(other_parallel) = &other_stage.parallel_with {
-                    if *other_parallel == stage.name && !processed.contains(&other_stage.name) {
-                        group.push(*other_stage);
-                        processed.insert(other_stage.name.clone());
+            let Some(joined) = running.join_next_with_id().await else {
+                break;
+            };
+
+            let (task_id, outcome) = match joined {
+                Ok((id, outcome)) => (id, Ok(outcome)),
+                Err(e) => (e.id(), Err(e)),
+            };
+            let stage_name = running_names.remove(&task_id).unwrap_or_default();
+
+            match outcome {
+                Ok(Ok(StageOutcome::AwaitingApproval)) => {
+                    info!(stage = %stage_name, "Stage waiting for approval");
+                    awaiting_approval.insert(stage_name);
+                }
+                Ok(Ok(StageOutcome::Finished)) => {
+                    completed.insert(stage_name.clone());
+                    info!(stage = %stage_name, "Stage completed successfully");
+
+                    if let Some(saga) = &saga {
+                        compensation_sequence = self
+                            .register_compensations(
+                                run_id,
+                                saga.id,
+                                &stage_name,
+                                definition,
+                                compensation_sequence,
+                            )
+                            .await?;
+                    }
+                }
+                Ok(Err(e)) => {
+                    failed.insert(stage_name.clone());
+                    error!(stage = %stage_name, error = %e, "Stage failed");
+
+                    if let Err(halt) = self
+                        .handle_stage_failure(run_id, definition, context, &stage_name)
+                        .await
+                    {
+                        halt_error.get_or_insert(halt);
                     }
                 }
+                Err(e) => {
+                    failed.insert(stage_name.clone());
+                    error!(stage = %stage_name, error = %e, "Stage task panicked");
+                    halt_error.get_or_insert(Error::Other(format!(
+                        "Stage '{}' task panicked: {}",
+                        stage_name, e
+                    )));
+                }
             }
+        }
 
-            groups.push(group);
+        if let Some(e) = halt_error {
+            return Err(e);
         }
 
-        groups
+        // No more stages ready, check if we're done or stuck
+        if !awaiting_approval.is_empty() {
+            return Ok(StageOutcome::AwaitingApproval);
+        }
+        if started.len() < definition.stages.len() && !failed.is_empty() {
+            return Err(Error::Other(format!(
+                "Pipeline execution halted due to failed stages: {:?}",
+                failed
+            )));
+        }
+
+        Ok(StageOutcome::Finished)
     }
 
-    /// Clone executor for parallel stage execution
-    fn clone_for_stage(&self) -> Self {
-        let approval_service = ApprovalService::new((*self.database).clone());
-        Self {
-            database: Arc::clone(&self.database),
-            condition_evaluator: ConditionEvaluator::new(),
-            approval_service,
-        }
+    /// Whether a run was cancelled while executing (e.g. by a newer run)
+    async fn is_run_cancelled(&self, run_id: i64) -> Result<bool> {
+        Ok(self
+            .database
+            .get_pipeline_run(run_id)
+            .await?
+            .is_some_and(|run| run.status == PipelineRunStatus::Cancelled))
     }
 
-    /// Execute a rollback to a previous stage
-    async fn execute_rollback(
+    /// Apply a failed stage's `on_failure` action
+    ///
+    /// Returns an error when the failure should halt the pipeline.
+    async fn handle_stage_failure(
         &self,
         run_id: i64,
-        failed_stage_name: &str,
-        rollback_to_stage: &str,
-        trigger_type: crate::RollbackTriggerType,
+        definition: &PipelineDefinition,
+        context: &ExecutionContext,
+        stage_name: &str,
     ) -> Result<()> {
-        info!(
-            run_id = run_id,
-            failed_stage = failed_stage_name,
-            rollback_to = rollback_to_stage,
-            trigger = ?trigger_type,
-            "Executing rollback"
-        );
+        let stage_def = definition
+            .stages
+            .iter()
+            .find(|s| s.name == stage_name)
+            .unwrap();
 
-        // Prevent rollback loops - check if we've already rolled back to this stage
-        let rollback_count = self
-            .database
-            .count_rollback_events_for_stage(run_id, rollback_to_stage)
-            .await?;
+        match stage_def.on_failure {
+            Some(FailureAction::Halt) | None => Err(Error::Other(format!(
+                "Stage '{}' failed with halt action",
+                stage_name
+            ))),
+            Some(FailureAction::Continue) => {
+                warn!(stage = %stage_name, "Continuing despite stage failure");
+                Ok(())
+            }
+            Some(FailureAction::Rollback) => {
+                // Execute rollback
+                if let Some(rollback_to) = &stage_def.rollback_to {
+                    warn!(
+                        stage = %stage_name,
+                        rollback_to = %rollback_to,
+                        "Stage failed, executing rollback"
+                    );
 
-        if rollback_count > 0 {
-            return Err(Error::Other(format!(
-                "Rollback loop detected: stage '{}' has already been rolled back to {} time(s)",
-                rollback_to_stage, rollback_count
-            )));
-        }
+                    // Execute rollback
+                    match self
+                        .execute_rollback(
+                            run_id,
+                            stage_name,
+                            rollback_to,
+                            crate::RollbackTriggerType::Automatic,
+                            definition.stages.iter().find(|s| &s.name == rollback_to),
+                            context,
+                        )
+                        .await
+                    {
+                        Ok(_) => {
+                            info!(
+                                stage = %stage_name,
+                                "Rollback completed successfully"
+                            );
+                        }
+                        Err(rollback_err) => {
+                            error!(
+                                stage = %stage_name,
+                                error = %rollback_err,
+                                "Rollback failed"
+                            );
+                        }
+                    }
+                }
 
-        // Prevent rolling back to the same stage (self-rollback)
-        if failed_stage_name == rollback_to_stage {
-            return Err(Error::Other(format!(
-                "Cannot rollback to itself: stage '{}' attempted to rollback to itself",
-                failed_stage_name
-            )));
+                Err(Error::Other(format!(
+                    "Stage '{}' failed with rollback action",
+                    stage_name
+                )))
+            }
         }
+    }
 
-        // Create rollback event
-        let mut rollback_event = crate::RollbackEvent::new(
-            run_id,
-            failed_stage_name.to_string(),
-            rollback_to_stage.to_string(),
-            trigger_type,
+    /// Execute a single stage
+    #[tracing::instrument(
+        name = "pipeline_stage",
+        skip(self, stage_def, context),
+        fields(stage = %stage_def.name, agent = %stage_def.agent)
+    )]
+    async fn execute_stage(
+        &self,
+        run_id: i64,
+        stage_def: &StageDefinition,
+        context: &ExecutionContext,
+    ) -> Result<StageOutcome> {
+        info!(
+            stage = %stage_def.name,
+            agent = %stage_def.agent,
+            "Executing stage"
         );
 
-        let event_id = self
+        // Load stage from database
+        let mut stage = self
             .database
-            .insert_rollback_event(&rollback_event)
-            .await?;
-        rollback_event.id = Some(event_id);
-
-        // Mark rollback as running
-        rollback_event.mark_running();
-        self.database.update_rollback_event(&rollback_event).await?;
+            .get_pipeline_stage_by_name(run_id, &stage_def.name)
+            .await?
+            .ok_or_else(|| {
+                Error::Other(format!("Stage '{}' not found in database", stage_def.name))
+            })?;
 
-        // Execute rollback task
-        // TODO: In a real implementation, this would:
-        // 1. Find the rollback agent/task from the stage definition
-        // 2. Execute the rollback operation (e.g., revert deployment)
-        // 3. Update the stage status appropriately
-        //
-        // For now, we simulate success
-        let rollback_result = self
-            .spawn_agent("rollback-agent", &format!("Rollback {}", rollback_to_stage))
-            .await;
+        // Evaluate condition if present
+        if let Some(ref condition) = stage_def.when {
+            let stage_results = self
+                .database
+                .list_pipeline_stages(run_id)
+                .await?
+                .into_iter()
+                .map(|stage| {
+                    let result = serde_json::json!({
+                        "status": stage.status.as_str(),
+                        "attempts": stage.attempts,
+                    });
+                    (stage.stage_name, result)
+                })
+                .collect();
+            let condition_context = context
+                .to_condition_context()
+                .with_stage_results(stage_results);
+            let eval_result = match self
+                .condition_evaluator
+                .evaluate(condition, &condition_context)
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    stage.mark_failed();
+                    self.save_stage(&stage).await?;
+                    return Err(e);
+                }
+            };
 
-        // Update rollback event based on result
-        match rollback_result {
-            Ok(_) => {
-                rollback_event.mark_succeeded();
-                self.database.update_rollback_event(&rollback_event).await?;
+            if let EvaluationResult::Skip(reason) = eval_result {
                 info!(
-                    run_id = run_id,
-                    rollback_to = rollback_to_stage,
-                    "Rollback succeeded"
+                    stage = %stage_def.name,
+                    reason = %reason,
+                    "Skipping stage due to condition"
                 );
-                Ok(())
+
+                // Mark stage as skipped
+                stage.mark_skipped();
+                self.save_stage(&stage).await?;
+
+                // Return success (skipped stages don't fail the pipeline)
+                return Ok(StageOutcome::Finished);
             }
-            Err(e) => {
-                rollback_event.mark_failed(e.to_string());
-                self.database.update_rollback_event(&rollback_event).await?;
-                error!(
-                    run_id = run_id,
-                    rollback_to = rollback_to_stage,
-                    error = %e,
-                    "Rollback failed"
+        }
+
+        // Reuse the outcome of an earlier run with identical inputs
+        let cache_key = self.stage_cache_key(run_id, stage_def, context).await?;
+        if let Some(key) = &cache_key {
+            if let Some(from_run_id) = self
+                .database
+                .find_stage_cache_run(key.pipeline_id, &stage_def.name, &key.key)
+                .await?
+            {
+                info!(
+                    stage = %stage_def.name,
+                    from_run_id = from_run_id,
+                    "Reusing cached stage result"
                 );
-                Err(Error::Other(format!("Rollback failed: {}", e)))
+                stage.mark_cached(from_run_id);
+                self.save_stage(&stage).await?;
+                return Ok(StageOutcome::Finished);
+            }
+        }
+
+        // Hold the stage until its approval is granted
+        if stage_def.requires_approval
+            && !self.check_approval(run_id, stage_def, &mut stage).await?
+        {
+            return Ok(StageOutcome::AwaitingApproval);
+        }
+
+        let result = match &stage_def.matrix {
+            Some(matrix) => {
+                self.execute_matrix_stage(run_id, stage_def, matrix, context, stage)
+                    .await
             }
+            None => self.execute_agent_stage(run_id, stage_def, context, stage).await,
+        };
+
+        if let (Ok(()), Some(key)) = (&result, &cache_key) {
+            self.database
+                .insert_stage_cache_entry(
+                    key.pipeline_id,
+                    &stage_def.name,
+                    &key.key,
+                    &key.commit_sha,
+                    run_id,
+                )
+                .await?;
         }
+
+        result.map(|()| StageOutcome::Finished)
     }
 
-    /// Manually trigger a rollback from one stage to another
-    pub async fn trigger_rollback(
+    /// Check a stage's approval gate, requesting approval when first reached
+    ///
+    /// Returns whether the stage may run. A rejected or timed out approval
+    /// cancels the stage.
+    async fn check_approval(
         &self,
         run_id: i64,
-        from_stage: &str,
-        to_stage: &str,
-    ) -> Result<()> {
-        info!(
-            run_id = run_id,
-            from_stage = from_stage,
-            to_stage = to_stage,
-            "Manual rollback triggered"
+        stage_def: &StageDefinition,
+        stage: &mut PipelineStage,
+    ) -> Result<bool> {
+        let stage_id = stage
+            .id
+            .ok_or_else(|| Error::Other("Stage ID is required".to_string()))?;
+
+        let approval = match self
+            .approval_service
+            .get_approval_by_stage(stage_id)
+            .await?
+        {
+            Some(approval) => approval,
+            None => {
+                let policy = stage_def.approval_timeout.clone().unwrap_or_default();
+                let timeout_seconds = parse_timeout(&policy.after)?.as_secs() as i64;
+
+                // Determine required count (default to all approvers)
+                let required_count = stage_def.approvers.len() as i32;
+
+                let request = ApprovalRequest::new(
+                    stage_id,
+                    run_id,
+                    stage_def.approvers.join(","),
+                    required_count,
+                    Some(timeout_seconds),
+                    Some(policy.action.as_str().to_string()),
+                )
+                .with_escalation(policy.escalate_to);
+                let approval = self.database.create_approval_request(request).await?;
+
+                info!(
+                    stage = %stage_def.name,
+                    approvers = ?stage_def.approvers,
+                    timeout = %policy.after,
+                    "Approval requested - stage paused until decided"
+                );
+                self.post_slack_approval(run_id, stage_def, &approval).await;
+                self.email_approval(run_id, stage_def, &approval).await;
+                self.post_telegram_approval(run_id, stage_def, &approval).await;
+                approval
+            }
+        };
+
+        match approval.status {
+            ApprovalStatus::Approved => {
+                info!(stage = %stage_def.name, "Stage approved");
+                Ok(true)
+            }
+            ApprovalStatus::Rejected | ApprovalStatus::TimedOut => {
+                stage.mark_cancelled();
+                self.save_stage(stage).await?;
+                Err(Error::Other(format!(
+                    "Stage '{}' approval was {}",
+                    stage_def.name,
+                    approval.status.as_str()
+                )))
+            }
+            ApprovalStatus::Pending | ApprovalStatus::Delegated => {
+                if stage.status != PipelineStageStatus::WaitingApproval {
+                    stage.mark_waiting_approval();
+                    self.save_stage(stage).await?;
+                }
+                Ok(false)
+            }
+        }
+    }
+
+    /// Post a new approval request to Slack, if configured
+    ///
+    /// Failures are logged; approvers can still decide through the API.
+    async fn post_slack_approval(
+        &self,
+        run_id: i64,
+        stage_def: &StageDefinition,
+        approval: &ApprovalRequest,
+    ) {
+        let Some(slack_approvals) = &self.slack_approvals else {
+            return;
+        };
+
+        let description = format!(
+            "Pipeline run #{} is waiting for approval to run this stage.",
+            run_id
         );
+        if let Err(e) = slack_approvals
+            .post_approval(approval, "Pipeline stage", &stage_def.name, &description)
+            .await
+        {
+            warn!(stage = %stage_def.name, error = %e, "Failed to post approval request to Slack");
+        }
+    }
 
-        // Verify run exists
-        let _run = self
+    /// Email a new approval request to its approvers, if configured
+    ///
+    /// Failures are logged like those of Slack approval messages.
+    async fn email_approval(
+        &self,
+        run_id: i64,
+        stage_def: &StageDefinition,
+        approval: &ApprovalRequest,
+    ) {
+        let Some(email_notifications) = &self.email_notifications else {
+            return;
+        };
+
+        let pipeline = match self.database.get_pipeline_run(run_id).await {
+            Ok(Some(run)) => self
+                .database
+                .get_pipeline(run.pipeline_id)
+                .await
+                .ok()
+                .flatten(),
+            _ => None,
+        };
+        let Some(pipeline) = pipeline else {
+            warn!(
+                run_id = run_id,
+                "Pipeline not found - approval email not sent"
+            );
+            return;
+        };
+        let run_url = self.dashboard_url.as_ref().map(|dashboard_url| {
+            format!(
+                "{}/pipelines/{}/runs/{}",
+                dashboard_url.trim_end_matches('/'),
+                pipeline.name,
+                run_id
+            )
+        });
+
+        if let Err(e) = email_notifications
+            .notify_approval(
+                &pipeline.name,
+                run_id,
+                &stage_def.name,
+                approval,
+                run_url.as_deref(),
+            )
+            .await
+        {
+            warn!(stage = %stage_def.name, error = %e, "Failed to email approval request");
+        }
+    }
+
+    /// Send a new approval request to Telegram, if configured
+    ///
+    /// Failures are logged like those of Slack approval messages.
+    async fn post_telegram_approval(
+        &self,
+        run_id: i64,
+        stage_def: &StageDefinition,
+        approval: &ApprovalRequest,
+    ) {
+        let Some(telegram) = &self.telegram else {
+            return;
+        };
+
+        let description = format!(
+            "Pipeline run #{} is waiting for approval to run this stage.",
+            run_id
+        );
+        if let Err(e) = telegram
+            .post_approval(approval, "Pipeline stage", &stage_def.name, &description)
+            .await
+        {
+            warn!(stage = %stage_def.name, error = %e, "Failed to send approval request to Telegram");
+        }
+    }
+
+    /// Run a plain (non-matrix) stage's agent and record the outcome
+    async fn execute_agent_stage(
+        &self,
+        run_id: i64,
+        stage_def: &StageDefinition,
+        context: &ExecutionContext,
+        mut stage: PipelineStage,
+    ) -> Result<()> {
+        // Substitute variables in task
+        let task = context.substitute_variables(&stage_def.task);
+        let result = self.run_stage_attempts(stage_def, &task, &mut stage).await;
+
+        // Update stage status based on result
+        let mut stage = self
+            .database
+            .get_pipeline_stage_by_name(run_id, &stage_def.name)
+            .await?
+            .ok_or_else(|| {
+                Error::Other(format!("Stage '{}' not found in database", stage_def.name))
+            })?;
+
+        match result {
+            Ok(_) => {
+                stage.mark_succeeded();
+                self.save_stage(&stage).await?;
+                Ok(())
+            }
+            Err(e) => {
+                stage.mark_failed();
+                self.save_stage(&stage).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Cache key for a stage's inputs, if its outcome may be cached in this run
+    ///
+    /// Runs without a commit SHA or with caching disabled never use the cache,
+    /// nor do approval-gated stages since approval is decided per run.
+    async fn stage_cache_key(
+        &self,
+        run_id: i64,
+        stage_def: &StageDefinition,
+        context: &ExecutionContext,
+    ) -> Result<Option<StageCacheKey>> {
+        if stage_def.requires_approval {
+            return Ok(None);
+        }
+
+        let run = self
             .database
             .get_pipeline_run(run_id)
             .await?
             .ok_or_else(|| Error::Other(format!("Pipeline run {} not found", run_id)))?;
+        let Some(commit_sha) = run.commit_sha.filter(|_| run.use_cache) else {
+            return Ok(None);
+        };
 
-        // Execute rollback
-        self.execute_rollback(
-            run_id,
-            from_stage,
-            to_stage,
-            crate::RollbackTriggerType::Manual,
-        )
-        .await
+        let task = context.substitute_variables(&stage_def.task);
+        Ok(Some(StageCacheKey {
+            pipeline_id: run.pipeline_id,
+            key: stage_def.cache_key(&commit_sha, &task)?,
+            commit_sha,
+        }))
     }
-}
 
-/// Parse timeout string (e.g., "30m", "1h", "90s") into Duration
-fn parse_timeout(timeout_str: &str) -> Result<Duration> {
-    let timeout_str = timeout_str.trim();
+    /// Run a stage's agent, retrying failed attempts per the stage's retry policy
+    ///
+    /// Every attempt marks `stage` as running again, so the stage records how
+    /// many attempts it took.
+    async fn run_stage_attempts(
+        &self,
+        stage_def: &StageDefinition,
+        task: &str,
+        stage: &mut PipelineStage,
+    ) -> Result<()> {
+        let max_attempts = stage_def
+            .retry
+            .as_ref()
+            .map_or(1, StageRetryPolicy::max_attempts);
+        let mut retry_delay = match stage_def.retry.as_ref().and_then(|r| r.backoff.as_ref()) {
+            Some(backoff) => parse_timeout(backoff)?,
+            None => Duration::ZERO,
+        };
 
-    if timeout_str.is_empty() {
-        return Err(Error::Other("Empty timeout string".to_string()));
-    }
+        let secrets = self.stage_secrets(stage_def).await?;
 
-    // Extract number and unit
-    let (number_str, unit) = if let Some(idx) = timeout_str.find(|c: char| c.is_alphabetic()) {
-        (&timeout_str[..idx], &timeout_str[idx..])
-    } else {
-        return Err(Error::Other(format!(
-            "Invalid timeout format: {}",
-            timeout_str
-        )));
-    };
+        let mut attempt = 1;
+        loop {
+            // TODO: Set actual agent_id when agent spawning is implemented
+            stage.mark_running(None);
+            self.save_stage(stage).await?;
 
-    let number: u64 = number_str
-        .trim()
-        .parse()
-        .map_err(|_| Error::Other(format!("Invalid timeout number: {}", number_str)))?;
+            let error = match self.run_stage_agent(stage_def, task, &secrets).await {
+                Ok(()) => return Ok(()),
+                Err(e) => Error::Other(scrub_secrets(&e.to_string(), &secrets)),
+            };
 
-    let duration = match unit {
-        "s" | "sec" | "secs" | "second" | "seconds" => Duration::from_secs(number),
-        "m" | "min" | "mins" | "minute" | "minutes" => Duration::from_secs(number * 60),
-        "h" | "hr" | "hrs" | "hour" | "hours" => Duration::from_secs(number * 3600),
-        _ => {
-            return Err(Error::Other(format!(
-                "Invalid timeout unit: {}",
-                unit
-            )))
-        }
-    };
+            if attempt >= max_attempts {
+                if max_attempts == 1 {
+                    return Err(error);
+                }
+                return Err(Error::Other(format!(
+                    "Stage '{}' failed after {} attempts: {}",
+                    stage_def.name, max_attempts, error
+                )));
+            }
 
-    Ok(duration)
-}
+            warn!(
+                stage = %stage_def.name,
+                attempt = attempt,
+                max_attempts = max_attempts,
+                retry_in = ?retry_delay,
+                error = %error,
+                "Stage attempt failed, retrying"
+            );
+            tokio::time::sleep(retry_delay).await;
+            retry_delay = retry_delay.saturating_mul(2);
+            attempt += 1;
+        }
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{PipelineRunStatus, PipelineStageStatus};
+    /// Values of the vault secrets a stage uses, keyed by secret name
+    async fn stage_secrets(&self, stage_def: &StageDefinition) -> Result<HashMap<String, String>> {
+        if stage_def.secrets.is_empty() {
+            return Ok(HashMap::new());
+        }
 
-    #[test]
-    fn test_execution_context_new() {
-        let ctx = ExecutionContext::new();
-        assert!(ctx.variables.is_empty());
-        assert!(ctx.trigger_event.is_none());
+        let vault = match &self.secret_vault {
+            Some(vault) => Arc::clone(vault),
+            None => Arc::new(SecretVault::from_env((*self.database).clone())),
+        };
+        vault.resolve(&stage_def.secrets).await.map_err(|e| {
+            Error::Other(format!(
+                "Stage '{}' could not load its secrets: {}",
+                stage_def.name, e
+            ))
+        })
     }
 
-    #[test]
-    fn test_execution_context_with_variables() {
-        let mut vars = HashMap::new();
-        vars.insert("env".to_string(), "staging".to_string());
+    /// Run a stage's agent, applying the stage timeout if one is set
+    async fn run_stage_agent(
+        &self,
+        stage_def: &StageDefinition,
+        task: &str,
+        secrets: &HashMap<String, String>,
+    ) -> Result<()> {
+        let Some(timeout_str) = &stage_def.timeout else {
+            return self.spawn_agent(&stage_def.agent, task, secrets).await;
+        };
 
-        let ctx = ExecutionContext::new().with_variables(vars);
-        assert_eq!(ctx.get_variable("env"), Some(&"staging".to_string()));
+        let duration = parse_timeout(timeout_str)?;
+        match timeout(duration, self.spawn_agent(&stage_def.agent, task, secrets)).await {
+            Ok(r) => r,
+            Err(_) => {
+                warn!(
+                    stage = %stage_def.name,
+                    timeout = %timeout_str,
+                    "Stage timed out"
+                );
+                Err(Error::Other(format!(
+                    "Stage '{}' timed out after {}",
+                    stage_def.name, timeout_str
+                )))
+            }
+        }
     }
 
-    #[test]
-    fn test_execution_context_with_trigger() {
-        let ctx = ExecutionContext::new().with_trigger("pull_request.merged".to_string());
+    /// Execute a matrix stage as one pipeline stage per parameter combination
+    ///
+    /// Combinations run concurrently up to `max_parallel`. The parent stage
+    /// succeeds only when every combination succeeds.
+    async fn execute_matrix_stage(
+        &self,
+        run_id: i64,
+        stage_def: &StageDefinition,
+        matrix: &StageMatrix,
+        context: &ExecutionContext,
+        mut stage: PipelineStage,
+    ) -> Result<()> {
+        stage.mark_running(None);
+        self.save_stage(&stage).await?;
+
+        let combinations = match self.resolve_matrix(matrix).await {
+            Ok(combinations) if !combinations.is_empty() => combinations,
+            Ok(_) => {
+                stage.mark_failed();
+                self.save_stage(&stage).await?;
+                return Err(Error::Other(format!(
+                    "Matrix stage '{}' has no combinations to run",
+                    stage_def.name
+                )));
+            }
+            Err(e) => {
+                stage.mark_failed();
+                self.save_stage(&stage).await?;
+                return Err(e);
+            }
+        };
+
+        info!(
+            stage = %stage_def.name,
+            combinations = combinations.len(),
+            "Expanding matrix stage"
+        );
+
+        let total = combinations.len();
+        let mut pending = combinations.into_iter();
+        let mut running: JoinSet<(String, Result<()>)> = JoinSet::new();
+        let mut failed_instances = Vec::new();
+
+        loop {
+            while running.len() < self.max_parallel {
+                let Some(combination) = pending.next() else {
+                    break;
+                };
+                let instance_name = StageMatrix::instance_name(&stage_def.name, &combination);
+
+                // Reuse the instance row when a rollback re-runs the stage
+                let instance = match self
+                    .database
+                    .get_pipeline_stage_by_name(run_id, &instance_name)
+                    .await?
+                {
+                    Some(existing) => existing,
+                    None => {
+                        let mut instance = PipelineStage::new(run_id, instance_name.clone());
+                        instance.id = Some(self.database.insert_pipeline_stage(&instance).await?);
+                        instance
+                    }
+                };
+
+                let mut instance_context = context.clone();
+                for (name, value) in &combination {
+                    instance_context.set_variable(format!("matrix.{}", name), value.clone());
+                }
+
+                let executor = self.clone_for_stage();
+                let instance_def = stage_def.clone();
+                running.spawn(async move {
+                    let result = executor
+                        .execute_matrix_instance(instance, &instance_def, &instance_context)
+                        .await;
+                    (instance_name, result)
+                });
+            }
+
+            let Some(joined) = running.join_next().await else {
+                break;
+            };
+            match joined {
+                Ok((_, Ok(()))) => {}
+                Ok((instance_name, Err(e))) => {
+                    error!(stage = %instance_name, error = %e, "Matrix combination failed");
+                    failed_instances.push(instance_name);
+                }
+                Err(e) => {
+                    error!(stage = %stage_def.name, error = %e, "Matrix combination panicked");
+                    failed_instances.push(format!("{} (panicked)", stage_def.name));
+                }
+            }
+        }
+
+        // Aggregate combination results onto the parent stage
+        if failed_instances.is_empty() {
+            stage.mark_succeeded();
+            self.save_stage(&stage).await?;
+            Ok(())
+        } else {
+            stage.mark_failed();
+            self.save_stage(&stage).await?;
+            Err(Error::Other(format!(
+                "Matrix stage '{}' failed for {} of {} combinations: {}",
+                stage_def.name,
+                failed_instances.len(),
+                total,
+                failed_instances.join(", ")
+            )))
+        }
+    }
+
+    /// Run one matrix combination and record its status on its own stage row
+    async fn execute_matrix_instance(
+        &self,
+        mut instance: PipelineStage,
+        stage_def: &StageDefinition,
+        context: &ExecutionContext,
+    ) -> Result<()> {
+        let task = context.substitute_variables(&stage_def.task);
+        let result = self
+            .run_stage_attempts(stage_def, &task, &mut instance)
+            .await;
+
+        match result {
+            Ok(_) => instance.mark_succeeded(),
+            Err(_) => instance.mark_failed(),
+        }
+        self.save_stage(&instance).await?;
+        result
+    }
+
+    /// Resolve a matrix into combinations, filling in repositories when requested
+    async fn resolve_matrix(&self, matrix: &StageMatrix) -> Result<Vec<BTreeMap<String, String>>> {
+        if !matrix.all_repos {
+            return Ok(matrix.combinations());
+        }
+
+        let repos: Vec<String> = self
+            .database
+            .list_repositories()
+            .await?
+            .into_iter()
+            .map(|repo| repo.name)
+            .collect();
+        if repos.is_empty() {
+            return Err(Error::Other(
+                "Matrix requested all repositories but none are registered".to_string(),
+            ));
+        }
+
+        Ok(matrix
+            .clone()
+            .with_parameter(MATRIX_REPO_PARAMETER, repos)
+            .combinations())
+    }
+
+    /// Record a succeeded stage's compensations on the saga, returning the last sequence used
+    async fn register_compensations(
+        &self,
+        run_id: i64,
+        saga_id: i64,
+        stage_name: &str,
+        definition: &PipelineDefinition,
+        mut sequence: i64,
+    ) -> Result<i64> {
+        let Some(stage_def) = definition.stages.iter().find(|s| s.name == stage_name) else {
+            return Ok(sequence);
+        };
+        if stage_def.compensation.is_empty() {
+            return Ok(sequence);
+        }
+
+        // Skipped stages made no changes, so there is nothing to undo
+        let succeeded = self
+            .database
+            .get_pipeline_stage_by_name(run_id, stage_name)
+            .await?
+            .map(|stage| stage.status == PipelineStageStatus::Succeeded)
+            .unwrap_or(false);
+        if !succeeded {
+            return Ok(sequence);
+        }
+
+        for compensation_def in &stage_def.compensation {
+            sequence += 1;
+            let compensation =
+                SagaCompensation::from_definition(saga_id, stage_name, sequence, compensation_def);
+            self.database.insert_saga_compensation(&compensation).await?;
+        }
+
+        debug!(
+            stage = %stage_name,
+            count = stage_def.compensation.len(),
+            "Registered stage compensations"
+        );
+
+        Ok(sequence)
+    }
+
+    /// Close a saga whose workflow succeeded; its compensations are no longer needed
+    async fn complete_saga(&self, saga: &mut Saga) -> Result<()> {
+        for mut compensation in self.database.list_saga_compensations(saga.id).await? {
+            compensation.mark_skipped();
+            self.database.update_saga_compensation(&compensation).await?;
+        }
+
+        saga.mark_completed();
+        self.database.update_saga(saga).await
+    }
+
+    /// Run a saga's pending compensations in reverse registration order
+    ///
+    /// A failing compensation does not stop the remaining ones; the saga ends
+    /// as `compensation_failed` so that an operator can finish the cleanup.
+    async fn compensate_saga(&self, run_id: i64, saga: &mut Saga, error: &str) -> Result<()> {
+        let failed_step = self
+            .database
+            .list_pipeline_stages_by_status(run_id, PipelineStageStatus::Failed)
+            .await?
+            .into_iter()
+            .next()
+            .map(|stage| stage.stage_name)
+            .unwrap_or_default();
+
+        saga.mark_compensating(failed_step, error);
+        self.database.update_saga(saga).await?;
+
+        let compensations = self.database.list_saga_compensations(saga.id).await?;
+        let mut all_succeeded = true;
+
+        for mut compensation in crate::saga::compensation_order(compensations) {
+            warn!(
+                run_id = run_id,
+                step = %compensation.step_name,
+                action = %compensation.action,
+                "Executing compensation"
+            );
+
+            compensation.mark_running();
+            self.database.update_saga_compensation(&compensation).await?;
+
+            match self
+                .spawn_agent(&compensation.agent, &compensation.task, &HashMap::new())
+                .await
+            {
+                Ok(_) => compensation.mark_succeeded(),
+                Err(e) => {
+                    error!(
+                        run_id = run_id,
+                        step = %compensation.step_name,
+                        error = %e,
+                        "Compensation failed"
+                    );
+                    compensation.mark_failed(e.to_string());
+                    all_succeeded = false;
+                }
+            }
+            self.database.update_saga_compensation(&compensation).await?;
+        }
+
+        saga.mark_compensation_finished(all_succeeded);
+        self.database.update_saga(saga).await
+    }
+
+    /// Spawn an agent for a stage
+    ///
+    /// `secrets` are exposed to the agent's tools as environment variables.
+    async fn spawn_agent(
+        &self,
+        agent_type: &str,
+        task: &str,
+        secrets: &HashMap<String, String>,
+    ) -> Result<()> {
+        if let Some(stage_runner) = &self.stage_runner {
+            return stage_runner.run(agent_type, task, secrets).await;
+        }
+
+        // TODO: Implement actual agent spawning
+        // For now, this is a placeholder that simulates agent execution
+        if !secrets.is_empty() {
+            debug!(
+                secrets = ?secrets.keys().collect::<Vec<_>>(),
+                "Exposing secrets to agent"
+            );
+        }
+
+        // Special handling for test agents
+        if agent_type.starts_with("failing-") {
+            debug!("Simulating agent failure for testing");
+            return Err(Error::Other("Simulated agent failure".to_string()));
+        }
+
+        debug!("Agent spawning not yet implemented");
+        Ok(())
+    }
+
+    /// Build dependency graph from stage definitions
+    fn build_dependency_graph(
+        &self,
+        definition: &PipelineDefinition,
+    ) -> Result<HashMap<String, Vec<String>>> {
+        let mut graph: HashMap<String, Vec<String>> = HashMap::new();
+
+        for stage in &definition.stages {
+            let deps = stage.depends_on.clone();
+
+            // Add parallel_with as a dependency (they must start together, but depend on same parents)
+            if let Some(parallel) = &stage.parallel_with {
+                // The parallel stage and this stage share dependencies
+                // We don't add the parallel stage as a dependency, but we need to track it
+                debug!(
+                    stage = %stage.name,
+                    parallel_with = %parallel,
+                    "Stage runs in parallel with another"
+                );
+            }
+
+            graph.insert(stage.name.clone(), deps);
+        }
+
+        Ok(graph)
+    }
+
+    /// Group stages for parallel execution
+    fn group_parallel_stages<'a>(
+        &self,
+        stages: &[&'a StageDefinition],
+    ) -> Vec<Vec<&'a StageDefinition>> {
+        let mut groups: Vec<Vec<&StageDefinition>> = vec![];
+        let mut processed: HashSet<String> = HashSet::new();
+
+        for stage in stages {
+            if processed.contains(&stage.name) {
+                continue;
+            }
+
+            let mut group = vec![*stage];
+            processed.insert(stage.name.clone());
+
+            // Find all stages that run in parallel with this one
+            if let Some(parallel_with) = &stage.parallel_with {
+                for other_stage in stages {
+                    if other_stage.name == *parallel_with && !processed.contains(&other_stage.name)
+                    {
+                        group.push(*other_stage);
+                        processed.insert(other_stage.name.clone());
+                    }
+                }
+            }
+
+            // Also check if other stages want to run parallel with this one
+            for other_stage in stages {
+                if let Some(other_parallel) = &other_stage.parallel_with {
+                    if *other_parallel == stage.name && !processed.contains(&other_stage.name) {
+                        group.push(*other_stage);
+                        processed.insert(other_stage.name.clone());
+                    }
+                }
+            }
+
+            groups.push(group);
+        }
+
+        groups
+    }
+
+    /// Clone executor for parallel stage execution
+    fn clone_for_stage(&self) -> Self {
+        let approval_service = ApprovalService::new((*self.database).clone());
+        Self {
+            database: Arc::clone(&self.database),
+            condition_evaluator: ConditionEvaluator::new(),
+            approval_service,
+            max_parallel: self.max_parallel,
+            stage_runner: self.stage_runner.clone(),
+            notifier: self.notifier.clone(),
+            dashboard_url: self.dashboard_url.clone(),
+            secret_vault: self.secret_vault.clone(),
+            slack_approvals: self.slack_approvals.clone(),
+            email_notifications: self.email_notifications.clone(),
+            telegram: self.telegram.clone(),
+        }
+    }
+
+    /// Execute a rollback to a previous stage
+    ///
+    /// Runs the `rollback` hook of `target_stage` when it declares one, and a
+    /// generic rollback agent otherwise. The outcome is recorded as a rollback
+    /// event with its trigger type.
+    async fn execute_rollback(
+        &self,
+        run_id: i64,
+        failed_stage_name: &str,
+        rollback_to_stage: &str,
+        trigger_type: crate::RollbackTriggerType,
+        target_stage: Option<&StageDefinition>,
+        context: &ExecutionContext,
+    ) -> Result<()> {
+        info!(
+            run_id = run_id,
+            failed_stage = failed_stage_name,
+            rollback_to = rollback_to_stage,
+            trigger = ?trigger_type,
+            "Executing rollback"
+        );
+
+        // Prevent rollback loops - check if we've already rolled back to this stage
+        let rollback_count = self
+            .database
+            .count_rollback_events_for_stage(run_id, rollback_to_stage)
+            .await?;
+
+        if rollback_count > 0 {
+            return Err(Error::Other(format!(
+                "Rollback loop detected: stage '{}' has already been rolled back to {} time(s)",
+                rollback_to_stage, rollback_count
+            )));
+        }
+
+        // Prevent rolling back to the same stage (self-rollback)
+        if failed_stage_name == rollback_to_stage {
+            return Err(Error::Other(format!(
+                "Cannot rollback to itself: stage '{}' attempted to rollback to itself",
+                failed_stage_name
+            )));
+        }
+
+        // Create rollback event
+        let mut rollback_event = crate::RollbackEvent::new(
+            run_id,
+            failed_stage_name.to_string(),
+            rollback_to_stage.to_string(),
+            trigger_type,
+        );
+
+        let event_id = self
+            .database
+            .insert_rollback_event(&rollback_event)
+            .await?;
+        rollback_event.id = Some(event_id);
+
+        // Mark rollback as running
+        rollback_event.mark_running();
+        self.database.update_rollback_event(&rollback_event).await?;
+
+        let rollback_result = self
+            .run_rollback_hook(rollback_to_stage, target_stage, context)
+            .await;
+
+        // Update rollback event based on result
+        match rollback_result {
+            Ok(_) => {
+                rollback_event.mark_succeeded();
+                self.database.update_rollback_event(&rollback_event).await?;
+                info!(
+                    run_id = run_id,
+                    rollback_to = rollback_to_stage,
+                    "Rollback succeeded"
+                );
+                Ok(())
+            }
+            Err(e) => {
+                rollback_event.mark_failed(e.to_string());
+                self.database.update_rollback_event(&rollback_event).await?;
+                error!(
+                    run_id = run_id,
+                    rollback_to = rollback_to_stage,
+                    error = %e,
+                    "Rollback failed"
+                );
+                Err(Error::Other(format!("Rollback failed: {}", e)))
+            }
+        }
+    }
+
+    /// Run the rollback hook of the stage being rolled back to
+    async fn run_rollback_hook(
+        &self,
+        rollback_to_stage: &str,
+        target_stage: Option<&StageDefinition>,
+        context: &ExecutionContext,
+    ) -> Result<()> {
+        let Some((stage_def, hook)) =
+            target_stage.and_then(|stage| stage.rollback.as_ref().map(|hook| (stage, hook)))
+        else {
+            return self
+                .spawn_agent(
+                    "rollback-agent",
+                    &format!("Rollback {}", rollback_to_stage),
+                    &HashMap::new(),
+                )
+                .await;
+        };
+
+        info!(
+            stage = %stage_def.name,
+            agent = %hook.agent,
+            "Running stage rollback hook"
+        );
+        // The hook undoes the stage, so it gets the stage's secrets
+        let secrets = self.stage_secrets(stage_def).await?;
+        let task = context.substitute_variables(&hook.task);
+        self.spawn_agent(&hook.agent, &task, &secrets)
+            .await
+            .map_err(|e| Error::Other(scrub_secrets(&e.to_string(), &secrets)))
+    }
+
+    /// Manually trigger a rollback from one stage to another
+    pub async fn trigger_rollback(
+        &self,
+        run_id: i64,
+        from_stage: &str,
+        to_stage: &str,
+    ) -> Result<()> {
+        info!(
+            run_id = run_id,
+            from_stage = from_stage,
+            to_stage = to_stage,
+            "Manual rollback triggered"
+        );
+
+        // Verify run exists
+        let run = self
+            .database
+            .get_pipeline_run(run_id)
+            .await?
+            .ok_or_else(|| Error::Other(format!("Pipeline run {} not found", run_id)))?;
+
+        // Stored pipelines without a valid definition fall back to the
+        // generic rollback agent
+        let definition = self
+            .database
+            .get_pipeline(run.pipeline_id)
+            .await?
+            .and_then(|pipeline| PipelineDefinition::from_yaml_str(&pipeline.definition).ok());
+        let context = match &definition {
+            Some(definition) => Self::run_context(definition, &run),
+            None => ExecutionContext::new(),
+        };
+        let target_stage = definition
+            .as_ref()
+            .and_then(|d| d.stages.iter().find(|s| s.name == to_stage));
+
+        // Execute rollback
+        self.execute_rollback(
+            run_id,
+            from_stage,
+            to_stage,
+            crate::RollbackTriggerType::Manual,
+            target_stage,
+            &context,
+        )
+        .await
+    }
+}
+
+/// Parse timeout string (e.g., "30m", "1h", "90s") into Duration
+pub(crate) fn parse_timeout(timeout_str: &str) -> Result<Duration> {
+    let timeout_str = timeout_str.trim();
+
+    if timeout_str.is_empty() {
+        return Err(Error::Other("Empty timeout string".to_string()));
+    }
+
+    // Extract number and unit
+    let (number_str, unit) = if let Some(idx) = timeout_str.find(|c: char| c.is_alphabetic()) {
+        (&timeout_str[..idx], &timeout_str[idx..])
+    } else {
+        return Err(Error::Other(format!(
+            "Invalid timeout format: {}",
+            timeout_str
+        )));
+    };
+
+    let number: u64 = number_str
+        .trim()
+        .parse()
+        .map_err(|_| Error::Other(format!("Invalid timeout number: {}", number_str)))?;
+
+    let duration = match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Duration::from_secs(number),
+        "m" | "min" | "mins" | "minute" | "minutes" => Duration::from_secs(number * 60),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Duration::from_secs(number * 3600),
+        _ => {
+            return Err(Error::Other(format!(
+                "Invalid timeout unit: {}",
+                unit
+            )))
+        }
+    };
+
+    Ok(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PipelineRunStatus, PipelineStageStatus};
+
+    #[test]
+    fn test_execution_context_new() {
+        let ctx = ExecutionContext::new();
+        assert!(ctx.variables.is_empty());
+        assert!(ctx.trigger_event.is_none());
+    }
+
+    #[test]
+    fn test_execution_context_with_variables() {
+        let mut vars = HashMap::new();
+        vars.insert("env".to_string(), "staging".to_string());
+
+        let ctx = ExecutionContext::new().with_variables(vars);
+        assert_eq!(ctx.get_variable("env"), Some(&"staging".to_string()));
+    }
+
+    #[test]
+    fn test_execution_context_with_trigger() {
+        let ctx = ExecutionContext::new().with_trigger("pull_request.merged".to_string());
+        assert_eq!(
+            ctx.trigger_event,
+            Some("pull_request.merged".to_string())
+        );
+    }
+
+    #[test]
+    fn test_execution_context_set_get_variable() {
+        let mut ctx = ExecutionContext::new();
+        ctx.set_variable("key".to_string(), "value".to_string());
+        assert_eq!(ctx.get_variable("key"), Some(&"value".to_string()));
+        assert_eq!(ctx.get_variable("missing"), None);
+    }
+
+    #[test]
+    fn test_execution_context_substitute_variables() {
+        let mut ctx = ExecutionContext::new();
+        ctx.set_variable("environment".to_string(), "production".to_string());
+        ctx.set_variable("version".to_string(), "1.2.3".to_string());
+
+        let result = ctx.substitute_variables("Deploy ${environment} version ${version}");
+        assert_eq!(result, "Deploy production version 1.2.3");
+    }
+
+    #[test]
+    fn test_execution_context_substitute_missing_variables() {
+        let ctx = ExecutionContext::new();
+        let result = ctx.substitute_variables("Deploy ${environment}");
+        assert_eq!(result, "Deploy ${environment}"); // Missing variables unchanged
+    }
+
+    #[test]
+    fn test_parse_timeout_seconds() {
+        assert_eq!(parse_timeout("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_timeout("90sec").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_timeout("120seconds").unwrap(), Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_parse_timeout_minutes() {
+        assert_eq!(parse_timeout("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_timeout("10min").unwrap(), Duration::from_secs(600));
+        assert_eq!(parse_timeout("30minutes").unwrap(), Duration::from_secs(1800));
+    }
+
+    #[test]
+    fn test_parse_timeout_hours() {
+        assert_eq!(parse_timeout("1h").unwrap(), Duration::from_secs(3600));
+        assert_eq!(parse_timeout("2hr").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_timeout("3hours").unwrap(), Duration::from_secs(10800));
+    }
+
+    #[test]
+    fn test_parse_timeout_with_whitespace() {
+        assert_eq!(parse_timeout(" 30m ").unwrap(), Duration::from_secs(1800));
+        assert_eq!(parse_timeout("30 m").unwrap(), Duration::from_secs(1800));
+    }
+
+    #[test]
+    fn test_parse_timeout_invalid() {
+        assert!(parse_timeout("").is_err());
+        assert!(parse_timeout("30").is_err());
+        assert!(parse_timeout("abc").is_err());
+        assert!(parse_timeout("30x").is_err());
+        assert!(parse_timeout("-30m").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_run() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+
+        // Create a pipeline first
+        let pipeline = crate::Pipeline::new(
+            "test-pipeline".to_string(),
+            "name: test\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        // Create a run
+        let run_id = executor
+            .create_run(pipeline_id, Some("manual".to_string()))
+            .await
+            .unwrap();
+
+        // Verify run was created
+        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(run.pipeline_id, pipeline_id);
+        assert_eq!(run.status, PipelineRunStatus::Pending);
+        assert_eq!(run.trigger_event, Some("manual".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_execute_simple_pipeline() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+
+        // Create a pipeline
+        let pipeline = crate::Pipeline::new(
+            "simple-pipeline".to_string(),
+            "name: simple\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        // Create a run
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+
+        // Create a simple pipeline definition
+        let definition = PipelineDefinition {
+            name: "simple-pipeline".to_string(),
+            description: "A simple pipeline".to_string(),
+            version: 1,
+            triggers: vec![],
+            variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
+            stages: vec![StageDefinition {
+                name: "build".to_string(),
+                agent: "builder".to_string(),
+                task: "Build the project".to_string(),
+                timeout: None,
+                retry: None,
+                on_failure: None,
+                rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
+                requires_approval: false,
+                approvers: vec![],
+                approval_timeout: None,
+                environment: None,
+                secrets: Vec::new(),
+                depends_on: vec![],
+                parallel_with: None,
+                matrix: None,
+                when: None,
+            }],
+        };
+
+        // Execute the pipeline
+        let result = executor.execute_run(run_id, &definition).await;
+        assert!(result.is_ok());
+
+        // Verify run status
+        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(run.status, PipelineRunStatus::Succeeded);
+        assert!(run.started_at.is_some());
+        assert!(run.completed_at.is_some());
+
+        // Verify stage status
+        let stages = database.list_pipeline_stages(run_id).await.unwrap();
+        assert_eq!(stages.len(), 1);
+        assert_eq!(stages[0].stage_name, "build");
+        assert_eq!(stages[0].status, PipelineStageStatus::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_execute_pipeline_with_dependencies() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+
+        // Create a pipeline
+        let pipeline = crate::Pipeline::new(
+            "dep-pipeline".to_string(),
+            "name: dep\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+
+        // Pipeline with dependencies: build -> test -> deploy
+        let definition = PipelineDefinition {
+            name: "dep-pipeline".to_string(),
+            description: "Pipeline with dependencies".to_string(),
+            version: 1,
+            triggers: vec![],
+            variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
+            stages: vec![
+                StageDefinition {
+                    name: "build".to_string(),
+                    agent: "builder".to_string(),
+                    task: "Build".to_string(),
+                    timeout: None,
+                    retry: None,
+                    on_failure: None,
+                    rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
+                    requires_approval: false,
+                    approvers: vec![],
+                    approval_timeout: None,
+                    environment: None,
+                    secrets: Vec::new(),
+                    depends_on: vec![],
+                    parallel_with: None,
+                    matrix: None,
+                    when: None,
+                },
+                StageDefinition {
+                    name: "test".to_string(),
+                    agent: "tester".to_string(),
+                    task: "Test".to_string(),
+                    timeout: None,
+                    retry: None,
+                    on_failure: None,
+                    rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
+                    requires_approval: false,
+                    approvers: vec![],
+                    approval_timeout: None,
+                    environment: None,
+                    secrets: Vec::new(),
+                    depends_on: vec!["build".to_string()],
+                    parallel_with: None,
+                    matrix: None,
+                    when: None,
+                },
+                StageDefinition {
+                    name: "deploy".to_string(),
+                    agent: "deployer".to_string(),
+                    task: "Deploy".to_string(),
+                    timeout: None,
+                    retry: None,
+                    on_failure: None,
+                    rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
+                    requires_approval: false,
+                    approvers: vec![],
+                    approval_timeout: None,
+                    environment: None,
+                    secrets: Vec::new(),
+                    depends_on: vec!["test".to_string()],
+                    parallel_with: None,
+                    matrix: None,
+                    when: None,
+                },
+            ],
+        };
+
+        let result = executor.execute_run(run_id, &definition).await;
+        assert!(result.is_ok());
+
+        // Verify all stages completed
+        let stages = database.list_pipeline_stages(run_id).await.unwrap();
+        assert_eq!(stages.len(), 3);
+
+        for stage in stages {
+            assert_eq!(stage.status, PipelineStageStatus::Succeeded);
+            assert!(stage.started_at.is_some());
+            assert!(stage.completed_at.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_pipeline_with_parallel_stages() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+
+        let pipeline = crate::Pipeline::new(
+            "parallel-pipeline".to_string(),
+            "name: parallel\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+
+        // Pipeline with parallel stages
+        let definition = PipelineDefinition {
+            name: "parallel-pipeline".to_string(),
+            description: "Pipeline with parallel stages".to_string(),
+            version: 1,
+            triggers: vec![],
+            variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
+            stages: vec![
+                StageDefinition {
+                    name: "lint".to_string(),
+                    agent: "linter".to_string(),
+                    task: "Lint".to_string(),
+                    timeout: None,
+                    retry: None,
+                    on_failure: None,
+                    rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
+                    requires_approval: false,
+                    approvers: vec![],
+                    approval_timeout: None,
+                    environment: None,
+                    secrets: Vec::new(),
+                    depends_on: vec![],
+                    parallel_with: None,
+                    matrix: None,
+                    when: None,
+                },
+                StageDefinition {
+                    name: "test".to_string(),
+                    agent: "tester".to_string(),
+                    task: "Test".to_string(),
+                    timeout: None,
+                    retry: None,
+                    on_failure: None,
+                    rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
+                    requires_approval: false,
+                    approvers: vec![],
+                    approval_timeout: None,
+                    environment: None,
+                    secrets: Vec::new(),
+                    depends_on: vec![],
+                    parallel_with: Some("lint".to_string()),
+                    matrix: None,
+                    when: None,
+                },
+            ],
+        };
+
+        let result = executor.execute_run(run_id, &definition).await;
+        assert!(result.is_ok());
+
+        // Verify both stages completed
+        let stages = database.list_pipeline_stages(run_id).await.unwrap();
+        assert_eq!(stages.len(), 2);
+
+        for stage in stages {
+            assert_eq!(stage.status, PipelineStageStatus::Succeeded);
+        }
+    }
+
+    /// Stage runner that records how many stages run at the same time
+    #[derive(Default)]
+    struct CountingStageRunner {
+        running: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl StageRunner for CountingStageRunner {
+        async fn run(
+            &self,
+            _agent_type: &str,
+            _task: &str,
+            _secrets: &HashMap<String, String>,
+        ) -> Result<()> {
+            use std::sync::atomic::Ordering;
+
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_pipeline_with_needs_and_parallelism_cap() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let runner = Arc::new(CountingStageRunner::default());
+        let executor = PipelineExecutor::new(database.clone()).with_stage_runner(runner.clone());
+
+        let pipeline = crate::Pipeline::new(
+            "fan-out".to_string(),
+            "name: fan-out\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+
+        let definition = PipelineDefinition::from_yaml_str(
+            r#"
+name: fan-out
+description: Independent stages joined by a final stage
+max_parallel: 2
+stages:
+  - name: build
+    agent: builder
+    task: Build
+  - name: lint
+    agent: linter
+    task: Lint
+  - name: docs
+    agent: writer
+    task: Docs
+  - name: test
+    agent: tester
+    task: Test
+  - name: release
+    agent: deployer
+    task: Release
+    needs: [build, lint, docs, test]
+"#,
+        )
+        .unwrap();
+
+        executor.execute_run(run_id, &definition).await.unwrap();
+
+        // The pipeline's cap overrides the executor default of four
+        assert_eq!(runner.peak.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        let stages = database.list_pipeline_stages(run_id).await.unwrap();
+        assert_eq!(stages.len(), 5);
+        for stage in &stages {
+            assert_eq!(stage.status, PipelineStageStatus::Succeeded);
+        }
+
+        // release only starts once all of its needs have finished
+        let release = stages.iter().find(|s| s.stage_name == "release").unwrap();
+        let release_started = release.started_at.unwrap();
+        for stage in stages.iter().filter(|s| s.stage_name != "release") {
+            assert!(stage.completed_at.unwrap() <= release_started);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_matrix_stage() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone()).with_max_parallel(2);
+
+        let pipeline = crate::Pipeline::new(
+            "matrix".to_string(),
+            "name: matrix\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+
+        let definition = PipelineDefinition::from_yaml_str(
+            r#"
+name: matrix
+description: Matrix pipeline
+stages:
+  - name: test
+    agent: tester
+    task: Test on ${matrix.os}
+    matrix:
+      os: [linux, macos, windows]
+  - name: release
+    agent: deployer
+    task: Release
+    needs: [test]
+"#,
+        )
+        .unwrap();
+
+        executor.execute_run(run_id, &definition).await.unwrap();
+
+        let stages = database.list_pipeline_stages(run_id).await.unwrap();
+        let names: HashSet<&str> = stages.iter().map(|s| s.stage_name.as_str()).collect();
+        assert_eq!(stages.len(), 5);
+        assert!(names.contains("test (linux)"));
+        assert!(names.contains("test (macos)"));
+        assert!(names.contains("test (windows)"));
+        for stage in &stages {
+            assert_eq!(stage.status, PipelineStageStatus::Succeeded);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_matrix_stage_without_repositories_fails() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+
+        let pipeline = crate::Pipeline::new(
+            "repos".to_string(),
+            "name: repos\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+
+        let definition = PipelineDefinition::from_yaml_str(
+            r#"
+name: repos
+description: Every repository
+stages:
+  - name: test
+    agent: tester
+    task: Test ${matrix.repo}
+    matrix:
+      all_repos: true
+"#,
+        )
+        .unwrap();
+
+        assert!(executor.execute_run(run_id, &definition).await.is_err());
+
+        let stage = database
+            .get_pipeline_stage_by_name(run_id, "test")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stage.status, PipelineStageStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_rerun_reuses_cached_stages() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+
+        let pipeline = crate::Pipeline::new(
+            "cached".to_string(),
+            "name: cached\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        let definition = PipelineDefinition::from_yaml_str(
+            r#"
+name: cached
+description: Cached pipeline
+stages:
+  - name: build
+    agent: builder
+    task: Build
+  - name: deploy
+    agent: failing-deployer
+    task: Deploy
+    needs: [build]
+"#,
+        )
+        .unwrap();
+
+        let first_run = PipelineRun::new(pipeline_id, None).with_commit_sha("abc123");
+        let first_run_id = database.insert_pipeline_run(&first_run).await.unwrap();
+        assert!(executor.execute_run(first_run_id, &definition).await.is_err());
+
+        // The green build stage is reused, the failed deploy stage runs again
+        let second_run = PipelineRun::new(pipeline_id, None).with_commit_sha("abc123");
+        let second_run_id = database.insert_pipeline_run(&second_run).await.unwrap();
+        assert!(executor.execute_run(second_run_id, &definition).await.is_err());
+
+        let build = database
+            .get_pipeline_stage_by_name(second_run_id, "build")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(build.status, PipelineStageStatus::Succeeded);
+        assert_eq!(build.cached_from_run_id, Some(first_run_id));
+        let deploy = database
+            .get_pipeline_stage_by_name(second_run_id, "deploy")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(deploy.status, PipelineStageStatus::Failed);
+        assert!(deploy.cached_from_run_id.is_none());
+
+        // Disabling the cache or changing the commit executes every stage
+        for run in [
+            PipelineRun::new(pipeline_id, None)
+                .with_commit_sha("abc123")
+                .without_cache(),
+            PipelineRun::new(pipeline_id, None).with_commit_sha("def456"),
+        ] {
+            let run_id = database.insert_pipeline_run(&run).await.unwrap();
+            assert!(executor.execute_run(run_id, &definition).await.is_err());
+
+            let build = database
+                .get_pipeline_stage_by_name(run_id, "build")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(build.status, PipelineStageStatus::Succeeded);
+            assert!(build.cached_from_run_id.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stage_retries_before_failing() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+
+        let pipeline = crate::Pipeline::new(
+            "retrying".to_string(),
+            "name: retrying\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        let definition = PipelineDefinition::from_yaml_str(
+            r#"
+name: retrying
+description: Retrying pipeline
+stages:
+  - name: flaky
+    agent: failing-deployer
+    task: Deploy
+    on_failure: continue
+    retry:
+      count: 2
+      backoff: 0s
+  - name: once
+    agent: failing-tester
+    task: Test
+    on_failure: continue
+"#,
+        )
+        .unwrap();
+
+        let run = PipelineRun::new(pipeline_id, None);
+        let run_id = database.insert_pipeline_run(&run).await.unwrap();
+        let _ = executor.execute_run(run_id, &definition).await;
+
+        let flaky = database
+            .get_pipeline_stage_by_name(run_id, "flaky")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(flaky.status, PipelineStageStatus::Failed);
+        assert_eq!(flaky.attempts, 3);
+        assert!(flaky.failed_after_retries());
+
+        let once = database
+            .get_pipeline_stage_by_name(run_id, "once")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(once.status, PipelineStageStatus::Failed);
+        assert_eq!(once.attempts, 1);
+        assert!(!once.failed_after_retries());
+    }
+
+    /// Records notifications instead of delivering them
+    #[derive(Default)]
+    struct RecordingSender {
+        sent: std::sync::Mutex<Vec<(NotificationTarget, PipelineNotification)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl NotificationSender for RecordingSender {
+        async fn send(
+            &self,
+            target: &NotificationTarget,
+            notification: &PipelineNotification,
+        ) -> Result<()> {
+            self.sent
+                .lock()
+                .unwrap()
+                .push((target.clone(), notification.clone()));
+            Ok(())
+        }
+    }
+
+    fn notifying_definition(agent: &str, on_failure: &str) -> PipelineDefinition {
+        PipelineDefinition::from_yaml_str(&format!(
+            r##"
+name: notifying
+description: Notifying pipeline
+notifications:
+  slack_channel: "#deployments"
+  webhook_url: https://hooks.example.com/pipelines
+stages:
+  - name: build
+    agent: builder
+    task: Build
+  - name: deploy
+    agent: {}
+    task: Deploy
+    depends_on: [build]
+    {}
+"##,
+            agent, on_failure
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_notifies_failure_and_recovery() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let sender = Arc::new(RecordingSender::default());
+        let executor = PipelineExecutor::new(database.clone())
+            .with_notifier(sender.clone())
+            .with_dashboard_url("https://orchestrate.example.com");
+
+        let pipeline = crate::Pipeline::new(
+            "notifying".to_string(),
+            "name: notifying\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        let run = PipelineRun::new(pipeline_id, None).with_commit_sha("abc123");
+        let failed_run_id = database.insert_pipeline_run(&run).await.unwrap();
+        let failing = notifying_definition("failing-deployer", "");
+        assert!(executor.execute_run(failed_run_id, &failing).await.is_err());
+
+        {
+            let sent = sender.sent.lock().unwrap();
+            assert_eq!(sent.len(), 2);
+            assert_eq!(
+                sent[0].0,
+                NotificationTarget::Slack {
+                    channel: "#deployments".to_string()
+                }
+            );
+            let notification = &sent[0].1;
+            assert_eq!(notification.event, PipelineNotificationEvent::Failure);
+            assert_eq!(notification.failed_stage.as_deref(), Some("deploy"));
+            assert_eq!(
+                notification.rerun_command,
+                "orchestrate pipeline run notifying --commit abc123"
+            );
+            assert_eq!(
+                notification.run_url,
+                Some(format!(
+                    "https://orchestrate.example.com/pipelines/notifying/runs/{}",
+                    failed_run_id
+                ))
+            );
+        }
+
+        let passing = notifying_definition("deployer", "");
+        for _ in 0..2 {
+            let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+            executor.execute_run(run_id, &passing).await.unwrap();
+        }
+
+        // Only the first successful run after the failure is a recovery
+        let sent = sender.sent.lock().unwrap();
+        assert_eq!(sent.len(), 4);
+        assert_eq!(sent[3].1.event, PipelineNotificationEvent::Recovery);
+    }
+
+    #[tokio::test]
+    async fn test_enqueues_outbound_webhooks_for_finished_runs() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+        database
+            .insert_outbound_webhook(&crate::OutboundWebhook::new(
+                "https://example.com/hook".to_string(),
+                vec!["pipeline.*".to_string()],
+            ))
+            .await
+            .unwrap();
+
+        let pipeline = crate::Pipeline::new(
+            "notifying".to_string(),
+            "name: notifying\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        let failed_run_id = executor.create_run(pipeline_id, None).await.unwrap();
+        let failing = notifying_definition("failing-deployer", "");
+        assert!(executor.execute_run(failed_run_id, &failing).await.is_err());
+        let passed_run_id = executor.create_run(pipeline_id, None).await.unwrap();
+        executor
+            .execute_run(passed_run_id, &notifying_definition("deployer", ""))
+            .await
+            .unwrap();
+
+        let deliveries = database.list_outbound_deliveries(None, 10).await.unwrap();
+        let events: Vec<_> = deliveries.iter().map(|d| d.event_type.as_str()).collect();
+        assert_eq!(events, vec!["pipeline.succeeded", "pipeline.failed"]);
+
+        let payload: serde_json::Value = serde_json::from_str(&deliveries[1].payload).unwrap();
+        assert_eq!(payload["data"]["pipeline"], "notifying");
+        assert_eq!(payload["data"]["run_id"], failed_run_id);
+    }
+
+    #[tokio::test]
+    async fn test_notifies_rollback() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let sender = Arc::new(RecordingSender::default());
+        let executor = PipelineExecutor::new(database.clone()).with_notifier(sender.clone());
+
+        let pipeline = crate::Pipeline::new(
+            "notifying".to_string(),
+            "name: notifying\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+
+        let definition = notifying_definition(
+            "failing-deployer",
+            "on_failure: rollback\n    rollback_to: build",
+        );
+        assert!(executor.execute_run(run_id, &definition).await.is_err());
+
+        let sent = sender.sent.lock().unwrap();
+        let notification = &sent[0].1;
+        assert_eq!(notification.event, PipelineNotificationEvent::Rollback);
+        assert_eq!(notification.failed_stage.as_deref(), Some("deploy"));
+        assert_eq!(notification.rollback_to.as_deref(), Some("build"));
+        assert_eq!(
+            notification.rollback_trigger,
+            Some(crate::RollbackTriggerType::Automatic)
+        );
+        assert_eq!(
+            notification.rollback_status,
+            Some(crate::RollbackStatus::Succeeded)
+        );
+        assert_eq!(
+            notification.rerun_command,
+            "orchestrate pipeline run notifying"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stage_secrets_resolved_from_vault() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let vault = Arc::new(SecretVault::new(
+            (*database).clone(),
+            crate::secrets::SecretsManager::from_passphrase("test"),
+        ));
+        vault.set("DEPLOY_TOKEN", "s3cr3t-value").await.unwrap();
+        let executor = PipelineExecutor::new(database.clone()).with_secret_vault(vault);
+
+        let pipeline = crate::Pipeline::new(
+            "secrets".to_string(),
+            "name: secrets\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        let definition = |secret: &str| {
+            PipelineDefinition::from_yaml_str(&format!(
+                "name: secrets\ndescription: Secrets\nstages:\n  - name: deploy\n    agent: deployer\n    task: Deploy\n    secrets: [{}]\n",
+                secret
+            ))
+            .unwrap()
+        };
+
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+        executor
+            .execute_run(run_id, &definition("DEPLOY_TOKEN"))
+            .await
+            .unwrap();
         assert_eq!(
-            ctx.trigger_event,
-            Some("pull_request.merged".to_string())
+            stage_status(&database, run_id, "deploy").await,
+            PipelineStageStatus::Succeeded
         );
-    }
 
-    #[test]
-    fn test_execution_context_set_get_variable() {
-        let mut ctx = ExecutionContext::new();
-        ctx.set_variable("key".to_string(), "value".to_string());
-        assert_eq!(ctx.get_variable("key"), Some(&"value".to_string()));
-        assert_eq!(ctx.get_variable("missing"), None);
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+        assert!(executor
+            .execute_run(run_id, &definition("MISSING_TOKEN"))
+            .await
+            .is_err());
+        assert_eq!(
+            stage_status(&database, run_id, "deploy").await,
+            PipelineStageStatus::Failed
+        );
     }
 
-    #[test]
-    fn test_execution_context_substitute_variables() {
-        let mut ctx = ExecutionContext::new();
-        ctx.set_variable("environment".to_string(), "production".to_string());
-        ctx.set_variable("version".to_string(), "1.2.3".to_string());
+    #[tokio::test]
+    async fn test_condition_expression_reads_stages_and_payload() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
 
-        let result = ctx.substitute_variables("Deploy ${environment} version ${version}");
-        assert_eq!(result, "Deploy production version 1.2.3");
-    }
+        let pipeline =
+            crate::Pipeline::new("expr".to_string(), "name: expr\nstages: []".to_string());
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
 
-    #[test]
-    fn test_execution_context_substitute_missing_variables() {
-        let ctx = ExecutionContext::new();
-        let result = ctx.substitute_variables("Deploy ${environment}");
-        assert_eq!(result, "Deploy ${environment}"); // Missing variables unchanged
-    }
+        let definition = PipelineDefinition::from_yaml_str(
+            r#"
+name: expr
+description: Expression conditions
+variables:
+  replicas: "3"
+stages:
+  - name: test
+    agent: tester
+    task: Test
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    depends_on: [test]
+    when:
+      expr: "stages.test.status == 'succeeded' && trigger.payload.ref == 'refs/heads/main'"
+  - name: scale
+    agent: deployer
+    task: Scale
+    depends_on: [test]
+    when:
+      expr: variables.replicas > 5
+"#,
+        )
+        .unwrap();
 
-    #[test]
-    fn test_parse_timeout_seconds() {
-        assert_eq!(parse_timeout("30s").unwrap(), Duration::from_secs(30));
-        assert_eq!(parse_timeout("90sec").unwrap(), Duration::from_secs(90));
-        assert_eq!(parse_timeout("120seconds").unwrap(), Duration::from_secs(120));
-    }
+        let run = PipelineRun::new(pipeline_id, Some("push".to_string()))
+            .with_trigger_payload(serde_json::json!({ "ref": "refs/heads/main" }));
+        let run_id = database.insert_pipeline_run(&run).await.unwrap();
+        executor.execute_run(run_id, &definition).await.unwrap();
 
-    #[test]
-    fn test_parse_timeout_minutes() {
-        assert_eq!(parse_timeout("5m").unwrap(), Duration::from_secs(300));
-        assert_eq!(parse_timeout("10min").unwrap(), Duration::from_secs(600));
-        assert_eq!(parse_timeout("30minutes").unwrap(), Duration::from_secs(1800));
-    }
+        assert_eq!(
+            stage_status(&database, run_id, "deploy").await,
+            PipelineStageStatus::Succeeded
+        );
+        assert_eq!(
+            stage_status(&database, run_id, "scale").await,
+            PipelineStageStatus::Skipped
+        );
 
-    #[test]
-    fn test_parse_timeout_hours() {
-        assert_eq!(parse_timeout("1h").unwrap(), Duration::from_secs(3600));
-        assert_eq!(parse_timeout("2hr").unwrap(), Duration::from_secs(7200));
-        assert_eq!(parse_timeout("3hours").unwrap(), Duration::from_secs(10800));
-    }
+        let run = PipelineRun::new(pipeline_id, Some("push".to_string()))
+            .with_trigger_payload(serde_json::json!({ "ref": "refs/heads/feature" }));
+        let run_id = database.insert_pipeline_run(&run).await.unwrap();
+        executor.execute_run(run_id, &definition).await.unwrap();
 
-    #[test]
-    fn test_parse_timeout_with_whitespace() {
-        assert_eq!(parse_timeout(" 30m ").unwrap(), Duration::from_secs(1800));
-        assert_eq!(parse_timeout("30 m").unwrap(), Duration::from_secs(1800));
-    }
+        assert_eq!(
+            stage_status(&database, run_id, "deploy").await,
+            PipelineStageStatus::Skipped
+        );
 
-    #[test]
-    fn test_parse_timeout_invalid() {
-        assert!(parse_timeout("").is_err());
-        assert!(parse_timeout("30").is_err());
-        assert!(parse_timeout("abc").is_err());
-        assert!(parse_timeout("30x").is_err());
-        assert!(parse_timeout("-30m").is_err());
+        // A condition that cannot be evaluated fails its stage
+        let definition = PipelineDefinition::from_yaml_str(
+            "name: expr\ndescription: Expr\nstages:\n  - name: check\n    agent: tester\n    task: Check\n    when:\n      expr: labels > 1\n",
+        )
+        .unwrap();
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+        assert!(executor.execute_run(run_id, &definition).await.is_err());
+        assert_eq!(
+            stage_status(&database, run_id, "check").await,
+            PipelineStageStatus::Failed
+        );
     }
 
     #[tokio::test]
-    async fn test_create_run() {
+    async fn test_plan_run_resolves_stages() {
         let database = Arc::new(Database::in_memory().await.unwrap());
         let executor = PipelineExecutor::new(database.clone());
 
-        // Create a pipeline first
-        let pipeline = crate::Pipeline::new(
-            "test-pipeline".to_string(),
-            "name: test\nstages: []".to_string(),
+        let definition = PipelineDefinition::from_yaml_str(
+            r#"
+name: release
+description: Release pipeline
+variables:
+  VERSION: "1.2.0"
+stages:
+  - name: build
+    agent: builder
+    task: Build ${VERSION} for ${matrix.os}
+    matrix: { os: [linux, mac] }
+  - name: docs
+    agent: writer
+    task: Publish docs
+    when:
+      branch: [main]
+  - name: check
+    agent: tester
+    task: Check
+    when:
+      expr: labels > 1
+  - name: verify
+    agent: tester
+    task: Verify ${VERSION}
+    needs: [check]
+  - name: deploy
+    agent: deployer
+    task: Deploy ${VERSION}
+    needs: [build, docs]
+    requires_approval: true
+    approvers: [lead]
+    when:
+      expr: stages.build.status == 'succeeded' && stages.docs.status == 'skipped'
+"#,
+        )
+        .unwrap();
+        let run = PipelineRun::new(1, Some("manual".to_string()));
+
+        let plan = executor.plan_run(&definition, &run).await.unwrap();
+        assert_eq!(plan.trigger.as_deref(), Some("manual"));
+        assert_eq!(plan.variables.get("VERSION").map(String::as_str), Some("1.2.0"));
+        let names: Vec<&str> = plan.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "docs", "check", "verify", "deploy"]);
+
+        let build = &plan.stages[0];
+        assert_eq!(build.action, PlannedStageAction::Run);
+        let prompts: Vec<&str> = build.agents.iter().map(|a| a.prompt.as_str()).collect();
+        assert_eq!(prompts, vec!["Build 1.2.0 for linux", "Build 1.2.0 for mac"]);
+        assert_eq!(build.agents[0].instance, "build (linux)");
+
+        let docs = &plan.stages[1];
+        assert_eq!(docs.action.as_str(), "skip");
+        assert!(docs.action.reason().unwrap().contains("Branch condition not met"));
+        assert_eq!(docs.condition.as_deref(), Some("branch in [main]"));
+        assert!(docs.agents.is_empty());
+
+        assert_eq!(plan.stages[2].action.as_str(), "fail");
+        assert_eq!(
+            plan.stages[3].action,
+            PlannedStageAction::Blocked {
+                reason: "Waits on 'check', which would not complete".to_string()
+            }
         );
-        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
 
-        // Create a run
-        let run_id = executor
-            .create_run(pipeline_id, Some("manual".to_string()))
-            .await
-            .unwrap();
+        // Conditions see earlier stages as succeeded or skipped
+        let deploy = &plan.stages[4];
+        assert_eq!(deploy.action, PlannedStageAction::Run);
+        assert_eq!(deploy.approvers, vec!["lead"]);
+        assert_eq!(deploy.agents[0].prompt, "Deploy 1.2.0");
 
-        // Verify run was created
-        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
-        assert_eq!(run.pipeline_id, pipeline_id);
-        assert_eq!(run.status, PipelineRunStatus::Pending);
-        assert_eq!(run.trigger_event, Some("manual".to_string()));
+        // Agents without recorded usage get the default estimate
+        let estimate = deploy.agents[0].estimate;
+        assert_eq!(estimate.sampled_agents, 0);
+        assert_eq!(
+            estimate.tokens,
+            crate::pipeline_plan::DEFAULT_AGENT_INPUT_TOKENS
+                + crate::pipeline_plan::DEFAULT_AGENT_OUTPUT_TOKENS
+        );
+        assert_eq!(plan.estimated_tokens(), estimate.tokens * 3);
+        assert!(plan.fails());
+
+        // Planning writes nothing
+        assert!(database.list_pipeline_runs(1).await.unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn test_execute_simple_pipeline() {
+    async fn test_concurrency_limit_queues_and_cancels_runs() {
         let database = Arc::new(Database::in_memory().await.unwrap());
         let executor = PipelineExecutor::new(database.clone());
+        let pipeline = crate::Pipeline::new("deploy".to_string(), "stages: []".to_string());
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let definition = |on_limit: &str| {
+            PipelineDefinition::from_yaml_str(&format!(
+                "name: deploy\ndescription: Deploy\nconcurrency:\n  on_limit: {}\nstages:\n  - name: deploy\n    agent: deployer\n    task: Deploy\n",
+                on_limit
+            ))
+            .unwrap()
+        };
+        let admit = |definition: PipelineDefinition| {
+            let database = database.clone();
+            async move {
+                database
+                    .admit_pipeline_run(
+                        &PipelineRun::new(pipeline_id, Some("push".to_string())),
+                        definition.concurrency.as_ref(),
+                    )
+                    .await
+                    .unwrap()
+                    .run_id()
+            }
+        };
 
-        // Create a pipeline
-        let pipeline = crate::Pipeline::new(
-            "simple-pipeline".to_string(),
-            "name: simple\nstages: []".to_string(),
+        // A queued run waits until the earlier run finishes
+        let queue = definition("queue");
+        let first = admit(queue.clone()).await;
+        let second = admit(queue.clone()).await;
+        let err = executor.execute_run(second, &queue).await.unwrap_err();
+        assert!(err.to_string().contains("queued behind 1 earlier run(s)"));
+        executor.execute_run(first, &queue).await.unwrap();
+        executor.execute_run(second, &queue).await.unwrap();
+
+        // A run cancelled by a newer run never starts
+        let cancel = definition("cancel_in_progress");
+        let superseded = admit(cancel.clone()).await;
+        let latest = admit(cancel.clone()).await;
+        let err = executor.execute_run(superseded, &cancel).await.unwrap_err();
+        assert!(err.to_string().contains("was cancelled"));
+        executor.execute_run(latest, &cancel).await.unwrap();
+
+        let run = database
+            .get_pipeline_run(superseded)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(run.status, PipelineRunStatus::Cancelled);
+        assert!(database
+            .list_pipeline_stages(superseded)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_run_context_layers_trigger_variables() {
+        let definition = PipelineDefinition::from_yaml_str(
+            r#"
+name: labeled
+description: Labeled pipeline
+variables:
+  environment: staging
+  label: none
+stages:
+  - name: deploy
+    agent: deployer
+    task: Deploy ${label} to ${environment}
+"#,
+        )
+        .unwrap();
+
+        let run = PipelineRun::new(1, Some("pull_request.labeled".to_string())).with_variables(
+            HashMap::from([("label".to_string(), "release".to_string())]),
         );
-        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let context = PipelineExecutor::run_context(&definition, &run);
 
-        // Create a run
-        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+        assert_eq!(context.trigger_event.as_deref(), Some("pull_request.labeled"));
+        assert_eq!(
+            context.substitute_variables(&definition.stages[0].task),
+            "Deploy release to staging"
+        );
+    }
 
-        // Create a simple pipeline definition
-        let definition = PipelineDefinition {
-            name: "simple-pipeline".to_string(),
-            description: "A simple pipeline".to_string(),
-            version: 1,
-            triggers: vec![],
-            variables: HashMap::new(),
-            stages: vec![StageDefinition {
-                name: "build".to_string(),
-                agent: "builder".to_string(),
-                task: "Build the project".to_string(),
-                timeout: None,
-                on_failure: None,
-                rollback_to: None,
-                requires_approval: false,
-                approvers: vec![],
-                environment: None,
-                depends_on: vec![],
-                parallel_with: None,
-                when: None,
-            }],
-        };
+    const APPROVAL_PIPELINE: &str = r#"
+name: gated
+description: Pipeline with an approval gate
+stages:
+  - name: build
+    agent: builder
+    task: Build
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    needs: [build]
+    requires_approval: true
+    approvers: [alice, bob]
+    approval_timeout:
+      after: 1h
+      escalate_to: [carol]
+  - name: verify
+    agent: verifier
+    task: Verify
+    needs: [deploy]
+"#;
 
-        // Execute the pipeline
-        let result = executor.execute_run(run_id, &definition).await;
-        assert!(result.is_ok());
+    async fn start_gated_run(database: &Arc<Database>, executor: &PipelineExecutor) -> i64 {
+        let pipeline = crate::Pipeline::new("gated".to_string(), APPROVAL_PIPELINE.to_string());
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let definition = PipelineDefinition::from_yaml_str(APPROVAL_PIPELINE).unwrap();
 
-        // Verify run status
-        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
-        assert_eq!(run.status, PipelineRunStatus::Succeeded);
-        assert!(run.started_at.is_some());
-        assert!(run.completed_at.is_some());
+        let run = PipelineRun::new(pipeline_id, None);
+        let run_id = database.insert_pipeline_run(&run).await.unwrap();
+        executor.execute_run(run_id, &definition).await.unwrap();
+        run_id
+    }
 
-        // Verify stage status
-        let stages = database.list_pipeline_stages(run_id).await.unwrap();
-        assert_eq!(stages.len(), 1);
-        assert_eq!(stages[0].stage_name, "build");
-        assert_eq!(stages[0].status, PipelineStageStatus::Succeeded);
+    async fn stage_status(database: &Database, run_id: i64, name: &str) -> PipelineStageStatus {
+        database
+            .get_pipeline_stage_by_name(run_id, name)
+            .await
+            .unwrap()
+            .unwrap()
+            .status
+    }
+
+    async fn stage_approval(database: &Database, run_id: i64, name: &str) -> ApprovalRequest {
+        let stage = database
+            .get_pipeline_stage_by_name(run_id, name)
+            .await
+            .unwrap()
+            .unwrap();
+        database
+            .get_approval_request_by_stage(stage.id.unwrap())
+            .await
+            .unwrap()
+            .unwrap()
     }
 
     #[tokio::test]
-    async fn test_execute_pipeline_with_dependencies() {
+    async fn test_approval_gate_pauses_run() {
         let database = Arc::new(Database::in_memory().await.unwrap());
         let executor = PipelineExecutor::new(database.clone());
+        let run_id = start_gated_run(&database, &executor).await;
 
-        // Create a pipeline
-        let pipeline = crate::Pipeline::new(
-            "dep-pipeline".to_string(),
-            "name: dep\nstages: []".to_string(),
+        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(run.status, PipelineRunStatus::WaitingApproval);
+        assert_eq!(
+            stage_status(&database, run_id, "build").await,
+            PipelineStageStatus::Succeeded
+        );
+        assert_eq!(
+            stage_status(&database, run_id, "deploy").await,
+            PipelineStageStatus::WaitingApproval
+        );
+        assert_eq!(
+            stage_status(&database, run_id, "verify").await,
+            PipelineStageStatus::Pending
         );
-        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
-        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
 
-        // Pipeline with dependencies: build -> test -> deploy
-        let definition = PipelineDefinition {
-            name: "dep-pipeline".to_string(),
-            description: "Pipeline with dependencies".to_string(),
-            version: 1,
-            triggers: vec![],
-            variables: HashMap::new(),
-            stages: vec![
-                StageDefinition {
-                    name: "build".to_string(),
-                    agent: "builder".to_string(),
-                    task: "Build".to_string(),
-                    timeout: None,
-                    on_failure: None,
-                    rollback_to: None,
-                    requires_approval: false,
-                    approvers: vec![],
-                    environment: None,
-                    depends_on: vec![],
-                    parallel_with: None,
-                    when: None,
-                },
-                StageDefinition {
-                    name: "test".to_string(),
-                    agent: "tester".to_string(),
-                    task: "Test".to_string(),
-                    timeout: None,
-                    on_failure: None,
-                    rollback_to: None,
-                    requires_approval: false,
-                    approvers: vec![],
-                    environment: None,
-                    depends_on: vec!["build".to_string()],
-                    parallel_with: None,
-                    when: None,
-                },
-                StageDefinition {
-                    name: "deploy".to_string(),
-                    agent: "deployer".to_string(),
-                    task: "Deploy".to_string(),
-                    timeout: None,
-                    on_failure: None,
-                    rollback_to: None,
-                    requires_approval: false,
-                    approvers: vec![],
-                    environment: None,
-                    depends_on: vec!["test".to_string()],
-                    parallel_with: None,
-                    when: None,
-                },
-            ],
-        };
+        let approval = stage_approval(&database, run_id, "deploy").await;
+        assert_eq!(approval.status, ApprovalStatus::Pending);
+        assert_eq!(approval.required_approvers, "alice,bob");
+        assert_eq!(approval.escalate_to.as_deref(), Some("carol"));
+        assert_eq!(approval.timeout_action.as_deref(), Some("reject"));
+    }
+
+    #[tokio::test]
+    async fn test_approval_gate_posts_to_slack() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let slack_service = SlackService::new_for_testing((*database).clone());
+        slack_service
+            .save_connection(&crate::slack::SlackConnection::new(
+                "T123",
+                "Test Team",
+                "xoxb-test",
+            ))
+            .await
+            .unwrap();
+        let executor = PipelineExecutor::new(database.clone()).with_slack_approvals(Arc::new(
+            SlackApprovalService::new((*database).clone(), slack_service),
+        ));
+        let run_id = start_gated_run(&database, &executor).await;
+        let approval = stage_approval(&database, run_id, "deploy").await;
+
+        let (approval_id, resource_id): (i64, String) =
+            sqlx::query_as("SELECT approval_id, resource_id FROM slack_approval_requests")
+                .fetch_one(database.pool())
+                .await
+                .unwrap();
+        assert_eq!(approval_id, approval.id.unwrap());
+        assert_eq!(resource_id, "deploy");
+    }
 
-        let result = executor.execute_run(run_id, &definition).await;
-        assert!(result.is_ok());
+    #[tokio::test]
+    async fn test_approval_gate_survives_email_failure() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let slack_service = SlackService::new((*database).clone());
+        slack_service
+            .save_connection(&crate::slack::SlackConnection::new(
+                "T123",
+                "Test Team",
+                "xoxb-test",
+            ))
+            .await
+            .unwrap();
+        let mut alice = crate::slack::UserMapping::new("alice", "U1");
+        alice.email = Some("alice@example.com".to_string());
+        slack_service.save_user_mapping(&alice).await.unwrap();
+
+        // Nothing listens on the discard port, so the email cannot be sent
+        let client = crate::smtp::SmtpClient::new(
+            crate::email::SmtpConfig::new("127.0.0.1", "ci@example.com")
+                .with_tls(crate::email::SmtpTls::None)
+                .with_port(9),
+        )
+        .with_timeout(Duration::from_secs(2));
+        let executor = PipelineExecutor::new(database.clone()).with_email_notifications(Arc::new(
+            EmailNotificationService::new((*database).clone(), client),
+        ));
+        let run_id = start_gated_run(&database, &executor).await;
 
-        // Verify all stages completed
-        let stages = database.list_pipeline_stages(run_id).await.unwrap();
-        assert_eq!(stages.len(), 3);
+        let approval = stage_approval(&database, run_id, "deploy").await;
+        assert_eq!(approval.status, ApprovalStatus::Pending);
+    }
 
-        for stage in stages {
-            assert_eq!(stage.status, PipelineStageStatus::Succeeded);
-            assert!(stage.started_at.is_some());
-            assert!(stage.completed_at.is_some());
-        }
+    #[tokio::test]
+    async fn test_notifies_approval_requests() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let sender = Arc::new(RecordingSender::default());
+        let executor = PipelineExecutor::new(database.clone()).with_notifier(sender.clone());
+
+        let yaml = APPROVAL_PIPELINE.replace(
+            "stages:\n",
+            "notifications:\n  teams_webhook_url: https://example.webhook.office.com/webhookb2/abc\n  on: [approval]\nstages:\n",
+        );
+        let pipeline = crate::Pipeline::new("gated".to_string(), yaml.clone());
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let definition = PipelineDefinition::from_yaml_str(&yaml).unwrap();
+        let run_id = database
+            .insert_pipeline_run(&PipelineRun::new(pipeline_id, None))
+            .await
+            .unwrap();
+        executor.execute_run(run_id, &definition).await.unwrap();
+        let approval = stage_approval(&database, run_id, "deploy").await;
+
+        let sent = sender.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(
+            sent[0].0,
+            NotificationTarget::Teams {
+                webhook_url: "https://example.webhook.office.com/webhookb2/abc".to_string()
+            }
+        );
+        let notification = &sent[0].1;
+        assert_eq!(notification.event, PipelineNotificationEvent::Approval);
+        assert_eq!(notification.approval_stage.as_deref(), Some("deploy"));
+        assert_eq!(notification.approvers, vec!["alice", "bob"]);
+        assert_eq!(notification.approval_id, approval.id);
     }
 
     #[tokio::test]
-    async fn test_execute_pipeline_with_parallel_stages() {
+    async fn test_approval_resumes_run() {
         let database = Arc::new(Database::in_memory().await.unwrap());
         let executor = PipelineExecutor::new(database.clone());
+        let run_id = start_gated_run(&database, &executor).await;
+        let approval = stage_approval(&database, run_id, "deploy").await;
 
-        let pipeline = crate::Pipeline::new(
-            "parallel-pipeline".to_string(),
-            "name: parallel\nstages: []".to_string(),
+        let approvals = ApprovalService::new((*database).clone());
+
+        // The first of two required approvals keeps the run paused
+        let approval = approvals
+            .approve(approval.id.unwrap(), "alice".to_string(), None)
+            .await
+            .unwrap();
+        executor.resume_after_approval(&approval).await.unwrap();
+        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(run.status, PipelineRunStatus::WaitingApproval);
+
+        let approval = approvals
+            .approve(approval.id.unwrap(), "bob".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(approval.status, ApprovalStatus::Approved);
+        executor.resume_after_approval(&approval).await.unwrap();
+
+        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(run.status, PipelineRunStatus::Succeeded);
+        assert_eq!(
+            stage_status(&database, run_id, "deploy").await,
+            PipelineStageStatus::Succeeded
         );
-        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
-        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+        assert_eq!(
+            stage_status(&database, run_id, "verify").await,
+            PipelineStageStatus::Succeeded
+        );
+    }
 
-        // Pipeline with parallel stages
-        let definition = PipelineDefinition {
-            name: "parallel-pipeline".to_string(),
-            description: "Pipeline with parallel stages".to_string(),
-            version: 1,
-            triggers: vec![],
-            variables: HashMap::new(),
-            stages: vec![
-                StageDefinition {
-                    name: "lint".to_string(),
-                    agent: "linter".to_string(),
-                    task: "Lint".to_string(),
-                    timeout: None,
-                    on_failure: None,
-                    rollback_to: None,
-                    requires_approval: false,
-                    approvers: vec![],
-                    environment: None,
-                    depends_on: vec![],
-                    parallel_with: None,
-                    when: None,
-                },
-                StageDefinition {
-                    name: "test".to_string(),
-                    agent: "tester".to_string(),
-                    task: "Test".to_string(),
-                    timeout: None,
-                    on_failure: None,
-                    rollback_to: None,
-                    requires_approval: false,
-                    approvers: vec![],
-                    environment: None,
-                    depends_on: vec![],
-                    parallel_with: Some("lint".to_string()),
-                    when: None,
-                },
-            ],
-        };
+    #[tokio::test]
+    async fn test_approval_rejection_cancels_run() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+        let run_id = start_gated_run(&database, &executor).await;
+        let approval = stage_approval(&database, run_id, "deploy").await;
+
+        let approval = ApprovalService::new((*database).clone())
+            .reject(
+                approval.id.unwrap(),
+                "alice".to_string(),
+                Some("Not today".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(approval.status, ApprovalStatus::Rejected);
+        executor.resume_after_approval(&approval).await.unwrap_err();
 
-        let result = executor.execute_run(run_id, &definition).await;
-        assert!(result.is_ok());
+        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(run.status, PipelineRunStatus::Cancelled);
+        assert_eq!(
+            stage_status(&database, run_id, "deploy").await,
+            PipelineStageStatus::Cancelled
+        );
+        assert_eq!(
+            stage_status(&database, run_id, "verify").await,
+            PipelineStageStatus::Pending
+        );
+        assert_eq!(
+            stage_approval(&database, run_id, "deploy").await.status,
+            ApprovalStatus::Rejected
+        );
+    }
 
-        // Verify both stages completed
-        let stages = database.list_pipeline_stages(run_id).await.unwrap();
-        assert_eq!(stages.len(), 2);
+    #[tokio::test]
+    async fn test_approval_timeout_escalates_then_applies_action() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+        let run_id = start_gated_run(&database, &executor).await;
 
-        for stage in stages {
-            assert_eq!(stage.status, PipelineStageStatus::Succeeded);
-        }
+        let expire = |mut approval: ApprovalRequest| {
+            approval.timeout_at = Some(chrono::Utc::now() - chrono::Duration::seconds(1));
+            approval
+        };
+
+        // First timeout escalates and keeps the run paused
+        let approval = expire(stage_approval(&database, run_id, "deploy").await);
+        database.update_approval_request(&approval).await.unwrap();
+        let processed = executor.process_approval_timeouts().await.unwrap();
+        assert_eq!(processed.len(), 1);
+        assert_eq!(processed[0].status, ApprovalStatus::Pending);
+        assert!(processed[0].required_approvers.contains("carol"));
+        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(run.status, PipelineRunStatus::WaitingApproval);
+
+        // Second timeout applies the reject action and cancels the run
+        let approval = expire(stage_approval(&database, run_id, "deploy").await);
+        database.update_approval_request(&approval).await.unwrap();
+        let processed = executor.process_approval_timeouts().await.unwrap();
+        assert_eq!(processed.len(), 1);
+        assert_eq!(processed[0].status, ApprovalStatus::Rejected);
+        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(run.status, PipelineRunStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_with_max_parallel_minimum() {
+        let executor = PipelineExecutor::new(Arc::new(
+            tokio::runtime::Runtime::new()
+                .unwrap()
+                .block_on(Database::in_memory())
+                .unwrap(),
+        ));
+        assert_eq!(executor.max_parallel, DEFAULT_MAX_PARALLEL_STAGES);
+        assert_eq!(executor.with_max_parallel(0).max_parallel, 1);
     }
 
     #[tokio::test]
@@ -1105,18 +3827,27 @@ mod tests {
             version: 1,
             triggers: vec![],
             variables,
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
             stages: vec![StageDefinition {
                 name: "deploy".to_string(),
                 agent: "deployer".to_string(),
                 task: "Deploy to ${environment}".to_string(), // Will be substituted
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec![],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             }],
         };
@@ -1144,19 +3875,28 @@ mod tests {
             version: 1,
             triggers: vec![],
             variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
             stages: vec![
                 StageDefinition {
                     name: "a".to_string(),
                     agent: "agent".to_string(),
                     task: "task".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: None,
                     rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec![],
                     parallel_with: None,
+                    matrix: None,
                     when: None,
                 },
                 StageDefinition {
@@ -1164,13 +3904,19 @@ mod tests {
                     agent: "agent".to_string(),
                     task: "task".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: None,
                     rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec!["a".to_string()],
                     parallel_with: None,
+                    matrix: None,
                     when: None,
                 },
                 StageDefinition {
@@ -1178,13 +3924,19 @@ mod tests {
                     agent: "agent".to_string(),
                     task: "task".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: None,
                     rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec!["a".to_string(), "b".to_string()],
                     parallel_with: None,
+                    matrix: None,
                     when: None,
                 },
             ],
@@ -1214,13 +3966,19 @@ mod tests {
             agent: "agent".to_string(),
             task: "task".to_string(),
             timeout: None,
+            retry: None,
             on_failure: None,
             rollback_to: None,
+            rollback: None,
+            compensation: Vec::new(),
             requires_approval: false,
             approvers: vec![],
+            approval_timeout: None,
             environment: None,
+            secrets: Vec::new(),
             depends_on: vec![],
             parallel_with: None,
+            matrix: None,
             when: None,
         };
 
@@ -1229,13 +3987,19 @@ mod tests {
             agent: "agent".to_string(),
             task: "task".to_string(),
             timeout: None,
+            retry: None,
             on_failure: None,
             rollback_to: None,
+            rollback: None,
+            compensation: Vec::new(),
             requires_approval: false,
             approvers: vec![],
+            approval_timeout: None,
             environment: None,
+            secrets: Vec::new(),
             depends_on: vec![],
             parallel_with: Some("a".to_string()),
+            matrix: None,
             when: None,
         };
 
@@ -1244,13 +4008,19 @@ mod tests {
             agent: "agent".to_string(),
             task: "task".to_string(),
             timeout: None,
+            retry: None,
             on_failure: None,
             rollback_to: None,
+            rollback: None,
+            compensation: Vec::new(),
             requires_approval: false,
             approvers: vec![],
+            approval_timeout: None,
             environment: None,
+            secrets: Vec::new(),
             depends_on: vec![],
             parallel_with: None,
+            matrix: None,
             when: None,
         };
 
@@ -1281,23 +4051,33 @@ mod tests {
             version: 1,
             triggers: vec![],
             variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
             stages: vec![StageDefinition {
                 name: "deploy".to_string(),
                 agent: "deployer".to_string(),
                 task: "Deploy".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec![],
                 parallel_with: None,
+                matrix: None,
                 when: Some(crate::StageCondition {
                     branch: Some(vec!["main".to_string()]),
                     paths: None,
                     labels: None,
                     variable: None,
+                    expr: None,
                     or: None,
                 }),
             }],
@@ -1339,23 +4119,33 @@ mod tests {
             version: 1,
             triggers: vec![],
             variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
             stages: vec![StageDefinition {
                 name: "deploy".to_string(),
                 agent: "deployer".to_string(),
                 task: "Deploy".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec![],
                 parallel_with: None,
+                matrix: None,
                 when: Some(crate::StageCondition {
                     branch: Some(vec!["main".to_string()]),
                     paths: None,
                     labels: None,
                     variable: None,
+                    expr: None,
                     or: None,
                 }),
             }],
@@ -1395,23 +4185,33 @@ mod tests {
             version: 1,
             triggers: vec![],
             variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
             stages: vec![StageDefinition {
                 name: "deploy-docs".to_string(),
                 agent: "doc-deployer".to_string(),
                 task: "Deploy docs".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec![],
                 parallel_with: None,
+                matrix: None,
                 when: Some(crate::StageCondition {
                     branch: None,
                     paths: Some(vec!["docs/**".to_string()]),
                     labels: None,
                     variable: None,
+                    expr: None,
                     or: None,
                 }),
             }],
@@ -1452,28 +4252,39 @@ mod tests {
             version: 1,
             triggers: vec![],
             variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
             stages: vec![StageDefinition {
                 name: "full-test".to_string(),
                 agent: "tester".to_string(),
                 task: "Run tests".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec![],
                 parallel_with: None,
+                matrix: None,
                 when: Some(crate::StageCondition {
                     branch: None,
                     paths: None,
                     labels: Some(vec!["needs-full-test".to_string()]),
                     variable: None,
+                    expr: None,
                     or: Some(Box::new(crate::StageCondition {
                         branch: None,
                         paths: Some(vec!["src/core/**".to_string()]),
                         labels: None,
                         variable: None,
+                        expr: None,
                         or: None,
                     })),
                 }),
@@ -1515,19 +4326,28 @@ mod tests {
             version: 1,
             triggers: vec![],
             variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
             stages: vec![
                 StageDefinition {
                     name: "always-run".to_string(),
                     agent: "builder".to_string(),
                     task: "Build".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: None,
                     rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec![],
                     parallel_with: None,
+                    matrix: None,
                     when: None, // No condition - always runs
                 },
                 StageDefinition {
@@ -1535,18 +4355,25 @@ mod tests {
                     agent: "doc-deployer".to_string(),
                     task: "Deploy docs".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: None,
                     rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec![],
                     parallel_with: None,
+                    matrix: None,
                     when: Some(crate::StageCondition {
                         branch: None,
                         paths: Some(vec!["docs/**".to_string()]),
                         labels: None,
                         variable: None,
+                        expr: None,
                         or: None,
                     }),
                 },
@@ -1596,19 +4423,28 @@ mod tests {
             version: 1,
             triggers: vec![],
             variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
             stages: vec![
                 StageDefinition {
                     name: "deploy-staging".to_string(),
                     agent: "deployer".to_string(),
                     task: "Deploy to staging".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: None,
                     rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec![],
                     parallel_with: None,
+                    matrix: None,
                     when: None,
                 },
                 StageDefinition {
@@ -1616,13 +4452,19 @@ mod tests {
                     agent: "failing-agent".to_string(), // This will fail
                     task: "Run smoke tests".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: Some(FailureAction::Rollback),
                     rollback_to: Some("deploy-staging".to_string()),
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec!["deploy-staging".to_string()],
                     parallel_with: None,
+                    matrix: None,
                     when: None,
                 },
             ],
@@ -1644,6 +4486,75 @@ mod tests {
         assert_eq!(rollbacks[0].status, crate::RollbackStatus::Succeeded);
     }
 
+    fn verified_deploy_definition(rollback_agent: &str) -> PipelineDefinition {
+        PipelineDefinition::from_yaml_str(&format!(
+            r#"
+name: verified-deploy
+description: Deploy rolled back when verification fails
+variables:
+  ENV: production
+stages:
+  - name: deploy
+    agent: deployer
+    task: Deploy to ${{ENV}}
+    rollback:
+      agent: {}
+      task: Redeploy the previous release to ${{ENV}}
+  - name: verify
+    agent: failing-verifier
+    task: Verify deployment
+    depends_on: [deploy]
+    on_failure: rollback
+    rollback_to: deploy
+"#,
+            rollback_agent
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rollback_hook_runs_when_verification_fails() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+
+        let pipeline = crate::Pipeline::new(
+            "verified-deploy".to_string(),
+            "name: verified-deploy\nstages: []".to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+        assert!(executor
+            .execute_run(run_id, &verified_deploy_definition("deployer"))
+            .await
+            .is_err());
+
+        let rollbacks = database.list_rollback_events(run_id).await.unwrap();
+        assert_eq!(rollbacks.len(), 1);
+        assert_eq!(rollbacks[0].failed_stage_name, "verify");
+        assert_eq!(rollbacks[0].rollback_to_stage, "deploy");
+        assert_eq!(
+            rollbacks[0].trigger_type,
+            crate::RollbackTriggerType::Automatic
+        );
+        assert_eq!(rollbacks[0].status, crate::RollbackStatus::Succeeded);
+
+        // A failing hook is recorded as a failed rollback
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+        assert!(executor
+            .execute_run(run_id, &verified_deploy_definition("failing-deployer"))
+            .await
+            .is_err());
+
+        let rollbacks = database.list_rollback_events(run_id).await.unwrap();
+        assert_eq!(rollbacks.len(), 1);
+        assert_eq!(rollbacks[0].status, crate::RollbackStatus::Failed);
+        assert_eq!(
+            rollbacks[0].error_message.as_deref(),
+            Some("Simulated agent failure")
+        );
+    }
+
     #[tokio::test]
     async fn test_rollback_loop_prevention() {
         // Test 1: Self-rollback should be caught during validation
@@ -1703,19 +4614,28 @@ stages:
             version: 1,
             triggers: vec![],
             variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
             stages: vec![
                 StageDefinition {
                     name: "deploy-staging".to_string(),
                     agent: "deployer".to_string(),
                     task: "Deploy to staging".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: None,
                     rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec![],
                     parallel_with: None,
+                    matrix: None,
                     when: None,
                 },
                 StageDefinition {
@@ -1723,13 +4643,19 @@ stages:
                     agent: "deployer".to_string(),
                     task: "Deploy to production".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: None,
                     rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec!["deploy-staging".to_string()],
                     parallel_with: None,
+                    matrix: None,
                     when: None,
                 },
             ],
@@ -1767,19 +4693,28 @@ stages:
             version: 1,
             triggers: vec![],
             variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
             stages: vec![
                 StageDefinition {
                     name: "deploy".to_string(),
                     agent: "deployer".to_string(),
                     task: "Deploy".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: None,
                     rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec![],
                     parallel_with: None,
+                    matrix: None,
                     when: None,
                 },
                 StageDefinition {
@@ -1787,13 +4722,19 @@ stages:
                     agent: "failing-tester".to_string(),
                     task: "Test".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: Some(FailureAction::Rollback),
                     rollback_to: Some("deploy".to_string()),
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec!["deploy".to_string()],
                     parallel_with: None,
+                    matrix: None,
                     when: None,
                 },
             ],
@@ -1809,4 +4750,225 @@ stages:
         assert!(rollbacks[0].created_at.is_some());
         assert_eq!(rollbacks[0].run_id, run_id);
     }
+
+    const SAGA_PIPELINE_YAML: &str = r#"
+name: saga-pipeline
+description: Pipeline with compensations
+stages:
+  - name: open-pr
+    agent: pr-creator
+    task: Open PR
+    compensation:
+      - action: revert_pr
+        params:
+          pr_number: "42"
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    depends_on: [open-pr]
+    compensation:
+      - action: rollback_deploy
+      - action: delete_branch
+        agent: AGENT
+  - name: verify
+    agent: VERIFIER
+    task: Verify deployment
+    depends_on: [deploy]
+"#;
+
+    async fn run_saga_pipeline(
+        verifier: &str,
+        branch_agent: &str,
+    ) -> (Arc<Database>, i64, Result<()>) {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+
+        let pipeline = crate::Pipeline::new(
+            "saga-pipeline".to_string(),
+            SAGA_PIPELINE_YAML.to_string(),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+
+        let yaml = SAGA_PIPELINE_YAML
+            .replace("VERIFIER", verifier)
+            .replace("AGENT", branch_agent);
+        let definition = PipelineDefinition::from_yaml_str(&yaml).unwrap();
+        let result = executor.execute_run(run_id, &definition).await;
+
+        (database, run_id, result)
+    }
+
+    #[tokio::test]
+    async fn test_saga_compensates_in_reverse_order_on_failure() {
+        let (database, run_id, result) = run_saga_pipeline("failing-verifier", "git-janitor").await;
+        assert!(result.is_err());
+
+        let saga = database
+            .get_saga_for_workflow(SagaWorkflowType::PipelineRun, &run_id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saga.status, crate::SagaStatus::Compensated);
+        assert_eq!(saga.failed_step.as_deref(), Some("verify"));
+
+        let compensations = database.list_saga_compensations(saga.id).await.unwrap();
+        let steps: Vec<_> = compensations
+            .iter()
+            .map(|c| (c.sequence, c.step_name.as_str(), c.action))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                (1, "open-pr", crate::CompensationActionType::RevertPr),
+                (2, "deploy", crate::CompensationActionType::RollbackDeploy),
+                (3, "deploy", crate::CompensationActionType::DeleteBranch),
+            ]
+        );
+        assert!(compensations
+            .iter()
+            .all(|c| c.status == crate::CompensationStatus::Succeeded));
+
+        // Later registrations are executed first
+        assert!(compensations[2].executed_at <= compensations[1].executed_at);
+        assert!(compensations[1].executed_at <= compensations[0].executed_at);
+    }
+
+    #[tokio::test]
+    async fn test_saga_continues_after_failed_compensation() {
+        let (database, run_id, result) =
+            run_saga_pipeline("failing-verifier", "failing-janitor").await;
+        assert!(result.is_err());
+
+        let saga = database
+            .get_saga_for_workflow(SagaWorkflowType::PipelineRun, &run_id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saga.status, crate::SagaStatus::CompensationFailed);
+
+        let compensations = database.list_saga_compensations(saga.id).await.unwrap();
+        assert_eq!(compensations[2].status, crate::CompensationStatus::Failed);
+        assert!(compensations[2].error_message.is_some());
+        assert_eq!(compensations[1].status, crate::CompensationStatus::Succeeded);
+        assert_eq!(compensations[0].status, crate::CompensationStatus::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_saga_completed_on_success() {
+        let (database, run_id, result) = run_saga_pipeline("verifier", "git-janitor").await;
+        assert!(result.is_ok());
+
+        let saga = database
+            .get_saga_for_workflow(SagaWorkflowType::PipelineRun, &run_id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saga.status, crate::SagaStatus::Completed);
+
+        let compensations = database.list_saga_compensations(saga.id).await.unwrap();
+        assert_eq!(compensations.len(), 3);
+        assert!(compensations
+            .iter()
+            .all(|c| c.status == crate::CompensationStatus::Skipped));
+    }
+
+    #[tokio::test]
+    async fn test_no_saga_without_compensation() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+
+        let pipeline = crate::Pipeline::new("plain".to_string(), "name: plain".to_string());
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+
+        let definition = PipelineDefinition::from_yaml_str(
+            "name: plain\ndescription: No compensation\nstages:\n  - name: build\n    agent: builder\n    task: Build\n",
+        )
+        .unwrap();
+        executor.execute_run(run_id, &definition).await.unwrap();
+
+        assert!(database.list_sagas(None).await.unwrap().is_empty());
+    }
+
+    const RESUMABLE_YAML: &str = r#"
+name: resumable
+description: Pipeline resumed after a failed stage
+stages:
+  - name: build
+    agent: builder
+    task: Build
+  - name: test
+    agent: TESTER
+    task: Test
+    depends_on: [build]
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    depends_on: [test]
+"#;
+
+    #[tokio::test]
+    async fn test_resume_failed_run_from_failed_stage() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let executor = PipelineExecutor::new(database.clone());
+
+        let mut pipeline = crate::Pipeline::new(
+            "resumable".to_string(),
+            RESUMABLE_YAML.replace("TESTER", "failing-tester"),
+        );
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        pipeline.id = Some(pipeline_id);
+        let run_id = executor.create_run(pipeline_id, None).await.unwrap();
+
+        let definition = PipelineDefinition::from_yaml_str(&pipeline.definition).unwrap();
+        assert!(executor.execute_run(run_id, &definition).await.is_err());
+
+        let build = database
+            .get_pipeline_stage_by_name(run_id, "build")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(build.status, PipelineStageStatus::Succeeded);
+
+        // Fix the failing stage and resume
+        pipeline.definition = RESUMABLE_YAML.replace("TESTER", "tester");
+        database.update_pipeline(&pipeline).await.unwrap();
+        executor.resume_failed_run(run_id).await.unwrap();
+
+        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(run.status, PipelineRunStatus::Succeeded);
+        assert!(run.completed_at.is_some());
+
+        // The succeeded stage is reused rather than executed again
+        let resumed_build = database
+            .get_pipeline_stage_by_name(run_id, "build")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resumed_build.agent_id, build.agent_id);
+        assert_eq!(resumed_build.completed_at, build.completed_at);
+
+        let stages = database.list_pipeline_stages(run_id).await.unwrap();
+        assert!(stages
+            .iter()
+            .all(|stage| stage.status == PipelineStageStatus::Succeeded));
+
+        // Only failed runs can be resumed
+        let err = executor.resume_failed_run(run_id).await.unwrap_err();
+        assert!(err.to_string().contains("has not failed"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_refused_after_compensation() {
+        let (database, run_id, result) = run_saga_pipeline("failing-verifier", "git-janitor").await;
+        assert!(result.is_err());
+
+        let executor = PipelineExecutor::new(database.clone());
+        let err = executor.resume_failed_run(run_id).await.unwrap_err();
+        assert!(err.to_string().contains("compensated"));
+
+        let run = database.get_pipeline_run(run_id).await.unwrap().unwrap();
+        assert_eq!(run.status, PipelineRunStatus::Failed);
+    }
 }
@@ -0,0 +1,445 @@
+//! Pipeline Graph Export
+//!
+//! This module turns a pipeline definition into a graph of triggers and
+//! stages that can be rendered as Mermaid or Graphviz DOT, or serialized
+//! as JSON for the web UI.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::pipeline_parser::{PipelineDefinition, StageCondition};
+
+/// Kind of node in a pipeline graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphNodeKind {
+    /// Event that starts the pipeline
+    Trigger,
+    /// Pipeline stage
+    Stage,
+}
+
+/// Kind of edge in a pipeline graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphEdgeKind {
+    /// Trigger starts a stage without dependencies
+    Trigger,
+    /// Target stage depends on the source stage
+    DependsOn,
+    /// Stages run alongside each other
+    ParallelWith,
+    /// Failure of the source stage rolls back to the target stage
+    RollbackTo,
+}
+
+/// Node in a pipeline graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// Stable node identifier (`trigger:<index>` or `stage:<name>`)
+    pub id: String,
+    /// Node kind
+    pub kind: GraphNodeKind,
+    /// Trigger event or stage name
+    pub label: String,
+    /// Agent executing the stage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// Human-readable summary of the stage `when` condition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    /// Branches a trigger is limited to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<String>,
+    /// Whether the stage waits for approval
+    #[serde(default)]
+    pub requires_approval: bool,
+    /// Deployment environment of the stage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+}
+
+/// Edge in a pipeline graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphEdge {
+    /// Source node id
+    pub from: String,
+    /// Target node id
+    pub to: String,
+    /// Edge kind
+    pub kind: GraphEdgeKind,
+}
+
+/// Graph of a pipeline's triggers and stages
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineGraph {
+    /// Pipeline name
+    pub name: String,
+    /// Trigger and stage nodes, triggers first, in definition order
+    pub nodes: Vec<GraphNode>,
+    /// Edges between nodes
+    pub edges: Vec<GraphEdge>,
+}
+
+impl PipelineGraph {
+    /// Build the graph for a pipeline definition
+    pub fn from_definition(definition: &PipelineDefinition) -> Self {
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+
+        for (index, trigger) in definition.triggers.iter().enumerate() {
+            nodes.push(GraphNode {
+                id: trigger_id(index),
+                kind: GraphNodeKind::Trigger,
+                label: match &trigger.cron {
+                    Some(cron) => format!("{} ({})", trigger.event, cron),
+                    None => trigger.event.clone(),
+                },
+                agent: None,
+                condition: None,
+                branches: trigger.branches.clone(),
+                requires_approval: false,
+                environment: None,
+            });
+        }
+
+        for stage in &definition.stages {
+            nodes.push(GraphNode {
+                id: stage_id(&stage.name),
+                kind: GraphNodeKind::Stage,
+                label: stage.name.clone(),
+                agent: Some(stage.agent.clone()),
+                condition: stage.when.as_ref().map(describe_condition),
+                branches: Vec::new(),
+                requires_approval: stage.requires_approval,
+                environment: stage.environment.clone(),
+            });
+        }
+
+        for stage in &definition.stages {
+            if stage.depends_on.is_empty() && stage.parallel_with.is_none() {
+                for index in 0..definition.triggers.len() {
+                    edges.push(GraphEdge {
+                        from: trigger_id(index),
+                        to: stage_id(&stage.name),
+                        kind: GraphEdgeKind::Trigger,
+                    });
+                }
+            }
+            for dep in &stage.depends_on {
+                edges.push(GraphEdge {
+                    from: stage_id(dep),
+                    to: stage_id(&stage.name),
+                    kind: GraphEdgeKind::DependsOn,
+                });
+            }
+            if let Some(parallel) = &stage.parallel_with {
+                edges.push(GraphEdge {
+                    from: stage_id(parallel),
+                    to: stage_id(&stage.name),
+                    kind: GraphEdgeKind::ParallelWith,
+                });
+            }
+            if let Some(target) = &stage.rollback_to {
+                edges.push(GraphEdge {
+                    from: stage_id(&stage.name),
+                    to: stage_id(target),
+                    kind: GraphEdgeKind::RollbackTo,
+                });
+            }
+        }
+
+        Self {
+            name: definition.name.clone(),
+            nodes,
+            edges,
+        }
+    }
+
+    /// Render the graph as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let ids = self.short_ids();
+        let mut out = String::from("flowchart TD\n");
+
+        for node in &self.nodes {
+            let label = mermaid_escape(&node_lines(node).join("<br/>"));
+            let id = &ids[node.id.as_str()];
+            let shape = match node.kind {
+                GraphNodeKind::Trigger => format!("([\"{}\"])", label),
+                GraphNodeKind::Stage if node.requires_approval => format!("{{{{\"{}\"}}}}", label),
+                GraphNodeKind::Stage => format!("[\"{}\"]", label),
+            };
+            out.push_str(&format!("    {}{}\n", id, shape));
+        }
+
+        for edge in &self.edges {
+            let (from, to) = match (ids.get(edge.from.as_str()), ids.get(edge.to.as_str())) {
+                (Some(from), Some(to)) => (from, to),
+                _ => continue,
+            };
+            let arrow = match edge.kind {
+                GraphEdgeKind::Trigger | GraphEdgeKind::DependsOn => "-->",
+                GraphEdgeKind::ParallelWith => "-. parallel .-",
+                GraphEdgeKind::RollbackTo => "-. rollback .->",
+            };
+            out.push_str(&format!("    {} {} {}\n", from, arrow, to));
+        }
+
+        out
+    }
+
+    /// Render the graph as a Graphviz DOT digraph
+    pub fn to_dot(&self) -> String {
+        let mut out = format!("digraph \"{}\" {{\n", dot_escape(&self.name));
+        out.push_str("    rankdir=TB;\n");
+
+        for node in &self.nodes {
+            let shape = match node.kind {
+                GraphNodeKind::Trigger => "oval",
+                GraphNodeKind::Stage if node.requires_approval => "hexagon",
+                GraphNodeKind::Stage => "box",
+            };
+            let label = node_lines(node)
+                .iter()
+                .map(|line| dot_escape(line))
+                .collect::<Vec<_>>()
+                .join("\\n");
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\", shape={}];\n",
+                dot_escape(&node.id),
+                label,
+                shape
+            ));
+        }
+
+        for edge in &self.edges {
+            let attrs = match edge.kind {
+                GraphEdgeKind::Trigger | GraphEdgeKind::DependsOn => "",
+                GraphEdgeKind::ParallelWith => " [style=dashed, dir=none, label=\"parallel\"]",
+                GraphEdgeKind::RollbackTo => " [style=dotted, label=\"rollback\"]",
+            };
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\"{};\n",
+                dot_escape(&edge.from),
+                dot_escape(&edge.to),
+                attrs
+            ));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Mermaid node ids must be plain identifiers, so number the nodes
+    fn short_ids(&self) -> HashMap<&str, String> {
+        let mut triggers = 0;
+        let mut stages = 0;
+        self.nodes
+            .iter()
+            .map(|node| {
+                let id = match node.kind {
+                    GraphNodeKind::Trigger => {
+                        triggers += 1;
+                        format!("t{}", triggers - 1)
+                    }
+                    GraphNodeKind::Stage => {
+                        stages += 1;
+                        format!("s{}", stages - 1)
+                    }
+                };
+                (node.id.as_str(), id)
+            })
+            .collect()
+    }
+}
+
+fn trigger_id(index: usize) -> String {
+    format!("trigger:{}", index)
+}
+
+fn stage_id(name: &str) -> String {
+    format!("stage:{}", name)
+}
+
+/// Label lines shown for a node in the rendered graphs
+fn node_lines(node: &GraphNode) -> Vec<String> {
+    let mut lines = vec![node.label.clone()];
+    if !node.branches.is_empty() {
+        lines.push(format!("branches: {}", node.branches.join(", ")));
+    }
+    if let Some(agent) = &node.agent {
+        lines.push(format!("agent: {}", agent));
+    }
+    if let Some(environment) = &node.environment {
+        lines.push(format!("env: {}", environment));
+    }
+    if let Some(condition) = &node.condition {
+        lines.push(format!("when: {}", condition));
+    }
+    if node.requires_approval {
+        lines.push("requires approval".to_string());
+    }
+    lines
+}
+
+/// Summarize a stage condition, e.g. `branch in [main] and labels has [deploy]`
+pub fn describe_condition(condition: &StageCondition) -> String {
+    let mut parts = Vec::new();
+    if let Some(branches) = &condition.branch {
+        parts.push(format!("branch in [{}]", branches.join(", ")));
+    }
+    if let Some(paths) = &condition.paths {
+        parts.push(format!("paths match [{}]", paths.join(", ")));
+    }
+    if let Some(labels) = &condition.labels {
+        parts.push(format!("labels has [{}]", labels.join(", ")));
+    }
+    if let Some(variables) = &condition.variable {
+        let sorted: BTreeMap<_, _> = variables.iter().collect();
+        for (key, value) in sorted {
+            parts.push(format!("{} == {}", key, value));
+        }
+    }
+    if let Some(expr) = &condition.expr {
+        parts.push(expr.clone());
+    }
+
+    let summary = if parts.is_empty() {
+        "always".to_string()
+    } else {
+        parts.join(" and ")
+    };
+
+    match &condition.or {
+        Some(alternative) => format!("({}) or ({})", summary, describe_condition(alternative)),
+        None => summary,
+    }
+}
+
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIPELINE: &str = r#"
+name: release
+description: Release pipeline
+triggers:
+  - event: pull_request.merged
+    branches: [main]
+stages:
+  - name: build
+    agent: regression-tester
+    task: Build
+  - name: lint
+    agent: code-reviewer
+    task: Lint
+    parallel_with: build
+  - name: deploy
+    agent: deployer
+    task: Deploy "prod"
+    depends_on: [build, lint]
+    requires_approval: true
+    approvers: [release-manager]
+    environment: production
+    on_failure: rollback
+    rollback_to: build
+    when:
+      branch: [main]
+      or:
+        labels: [hotfix]
+"#;
+
+    fn graph() -> PipelineGraph {
+        PipelineGraph::from_definition(&PipelineDefinition::from_yaml_str(PIPELINE).unwrap())
+    }
+
+    #[test]
+    fn test_graph_nodes_and_edges() {
+        let graph = graph();
+
+        assert_eq!(graph.name, "release");
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.nodes[0].kind, GraphNodeKind::Trigger);
+        assert_eq!(graph.nodes[0].branches, vec!["main"]);
+
+        let deploy = graph.nodes.iter().find(|n| n.id == "stage:deploy").unwrap();
+        assert!(deploy.requires_approval);
+        assert_eq!(deploy.environment.as_deref(), Some("production"));
+        assert_eq!(
+            deploy.condition.as_deref(),
+            Some("(branch in [main]) or (labels has [hotfix])")
+        );
+
+        let edge = |from: &str, to: &str, kind| GraphEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind,
+        };
+        assert_eq!(
+            graph.edges,
+            vec![
+                edge("trigger:0", "stage:build", GraphEdgeKind::Trigger),
+                edge("stage:build", "stage:lint", GraphEdgeKind::ParallelWith),
+                edge("stage:build", "stage:deploy", GraphEdgeKind::DependsOn),
+                edge("stage:lint", "stage:deploy", GraphEdgeKind::DependsOn),
+                edge("stage:deploy", "stage:build", GraphEdgeKind::RollbackTo),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_graph_to_mermaid() {
+        let mermaid = graph().to_mermaid();
+
+        assert!(mermaid.starts_with("flowchart TD\n"));
+        assert!(mermaid.contains("    t0([\"pull_request.merged<br/>branches: main\"])\n"));
+        assert!(mermaid.contains("    s0[\"build<br/>agent: regression-tester\"]\n"));
+        assert!(mermaid.contains("    s2{{\"deploy<br/>agent: deployer"));
+        assert!(mermaid.contains("    t0 --> s0\n"));
+        assert!(mermaid.contains("    s0 -. parallel .- s1\n"));
+        assert!(mermaid.contains("    s1 --> s2\n"));
+        assert!(mermaid.contains("    s2 -. rollback .-> s0\n"));
+    }
+
+    #[test]
+    fn test_graph_to_dot() {
+        let dot = graph().to_dot();
+
+        assert!(dot.starts_with("digraph \"release\" {\n"));
+        assert!(dot.contains(
+            "\"trigger:0\" [label=\"pull_request.merged\\nbranches: main\", shape=oval];"
+        ));
+        assert!(
+            dot.contains("\"stage:deploy\" [label=\"deploy\\nagent: deployer\\nenv: production")
+        );
+        assert!(dot.contains("shape=hexagon];"));
+        assert!(dot.contains(
+            "\"stage:build\" -> \"stage:lint\" [style=dashed, dir=none, label=\"parallel\"];"
+        ));
+        assert!(
+            dot.contains("\"stage:deploy\" -> \"stage:build\" [style=dotted, label=\"rollback\"];")
+        );
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_graph_json_round_trip() {
+        let graph = graph();
+        let json = serde_json::to_value(&graph).unwrap();
+
+        assert_eq!(json["nodes"][0]["kind"], "trigger");
+        assert_eq!(json["edges"][0]["kind"], "trigger");
+        assert_eq!(json["edges"][4]["kind"], "rollback_to");
+
+        let parsed: PipelineGraph = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, graph);
+    }
+}
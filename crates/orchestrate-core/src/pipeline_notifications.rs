@@ -0,0 +1,789 @@
+//! Pipeline run notifications
+//!
+//! Pipelines may declare `notifications` settings naming a Slack channel,
+//! email addresses, a webhook URL, and a Microsoft Teams incoming webhook.
+//! When a run fails, rolls back, succeeds after a failed run, or pauses for
+//! approval, the executor builds a [`PipelineNotification`] summarizing the
+//! outcome and hands it to a [`NotificationSender`] for each target.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    approval::ApprovalRequest,
+    email::{EmailDigest, EmailMessage},
+    pipeline::{RollbackEvent, RollbackStatus, RollbackTriggerType},
+    pipeline_parser::{PipelineNotificationEvent, PipelineNotifications},
+    slack::{SlackBlock, SlackContextElement, SlackMessage, SlackText},
+    smtp::SmtpClient,
+    Error, Result,
+};
+
+/// Summary of a pipeline run outcome worth notifying about
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineNotification {
+    /// What happened to the run
+    pub event: PipelineNotificationEvent,
+    /// Pipeline name
+    pub pipeline: String,
+    /// Run ID
+    pub run_id: i64,
+    /// Stage that failed, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_stage: Option<String>,
+    /// Error the run failed with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Stage the run rolled back to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollback_to: Option<String>,
+    /// Whether the rollback was automatic or manual
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollback_trigger: Option<RollbackTriggerType>,
+    /// Outcome of the rollback
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollback_status: Option<RollbackStatus>,
+    /// Error the rollback failed with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollback_error: Option<String>,
+    /// Stage waiting for approval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_stage: Option<String>,
+    /// Users who may approve the stage
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub approvers: Vec<String>,
+    /// Approval request the stage is waiting for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_id: Option<i64>,
+    /// Link to the run in the dashboard
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_url: Option<String>,
+    /// Command that starts the pipeline again
+    pub rerun_command: String,
+}
+
+impl PipelineNotification {
+    /// Create a notification for a run
+    pub fn new(event: PipelineNotificationEvent, pipeline: impl Into<String>, run_id: i64) -> Self {
+        let pipeline = pipeline.into();
+        let rerun_command = format!("orchestrate pipeline run {}", pipeline);
+        Self {
+            event,
+            pipeline,
+            run_id,
+            failed_stage: None,
+            error: None,
+            rollback_to: None,
+            rollback_trigger: None,
+            rollback_status: None,
+            rollback_error: None,
+            approval_stage: None,
+            approvers: Vec::new(),
+            approval_id: None,
+            run_url: None,
+            rerun_command,
+        }
+    }
+
+    /// Set the failed stage and the error it failed with
+    pub fn with_failure(mut self, stage: Option<String>, error: impl Into<String>) -> Self {
+        self.failed_stage = stage;
+        self.error = Some(error.into());
+        self
+    }
+
+    /// Set the stage the run rolled back to
+    pub fn with_rollback_to(mut self, stage: impl Into<String>) -> Self {
+        self.rollback_to = Some(stage.into());
+        self
+    }
+
+    /// Set the rollback target, trigger, and outcome from a rollback event
+    pub fn with_rollback(mut self, rollback: &RollbackEvent) -> Self {
+        self.rollback_to = Some(rollback.rollback_to_stage.clone());
+        self.rollback_trigger = Some(rollback.trigger_type);
+        self.rollback_status = Some(rollback.status);
+        self.rollback_error = rollback.error_message.clone();
+        self
+    }
+
+    /// Set the stage waiting for `approval`
+    pub fn with_approval(mut self, stage: impl Into<String>, approval: &ApprovalRequest) -> Self {
+        self.approval_stage = Some(stage.into());
+        self.approvers = approval
+            .required_approvers
+            .split(',')
+            .map(str::to_string)
+            .collect();
+        self.approval_id = approval.id;
+        self
+    }
+
+    /// Rollback target with its trigger and outcome, e.g. "deploy (automatic, succeeded)"
+    fn rollback_summary(&self) -> Option<String> {
+        let stage = self.rollback_to.as_ref()?;
+        let outcome: Vec<&str> = [
+            self.rollback_trigger.map(|t| t.as_str()),
+            self.rollback_status.map(|s| s.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if outcome.is_empty() {
+            Some(stage.clone())
+        } else {
+            Some(format!("{} ({})", stage, outcome.join(", ")))
+        }
+    }
+
+    /// Link the run in the dashboard at `dashboard_url`
+    pub fn with_dashboard_url(mut self, dashboard_url: &str) -> Self {
+        self.run_url = Some(format!(
+            "{}/pipelines/{}/runs/{}",
+            dashboard_url.trim_end_matches('/'),
+            self.pipeline,
+            self.run_id
+        ));
+        self
+    }
+
+    /// Rerun the same commit
+    pub fn with_commit(mut self, commit_sha: &str) -> Self {
+        self.rerun_command = format!("{} --commit {}", self.rerun_command, commit_sha);
+        self
+    }
+
+    /// One-line summary of the outcome
+    pub fn title(&self) -> String {
+        match self.event {
+            PipelineNotificationEvent::Failure => {
+                format!("Pipeline {} run #{} failed", self.pipeline, self.run_id)
+            }
+            PipelineNotificationEvent::Rollback => {
+                format!(
+                    "Pipeline {} run #{} rolled back",
+                    self.pipeline, self.run_id
+                )
+            }
+            PipelineNotificationEvent::Recovery => {
+                format!(
+                    "Pipeline {} recovered in run #{}",
+                    self.pipeline, self.run_id
+                )
+            }
+            PipelineNotificationEvent::Approval => {
+                format!(
+                    "Pipeline {} run #{} is waiting for approval",
+                    self.pipeline, self.run_id
+                )
+            }
+        }
+    }
+
+    /// Command that acts on the notification: rerunning a failed run or
+    /// approving a waiting stage
+    fn command(&self) -> Option<(&'static str, String)> {
+        match self.event {
+            PipelineNotificationEvent::Failure | PipelineNotificationEvent::Rollback => {
+                Some(("Rerun", self.rerun_command.clone()))
+            }
+            PipelineNotificationEvent::Recovery => None,
+            PipelineNotificationEvent::Approval => self
+                .approval_id
+                .map(|id| ("Approve", format!("orchestrate approval approve {}", id))),
+        }
+    }
+
+    /// Labelled details, in display order
+    fn facts(&self) -> Vec<(&'static str, String)> {
+        let mut facts = Vec::new();
+        if let Some(stage) = &self.failed_stage {
+            facts.push(("Failed stage", stage.clone()));
+        }
+        if let Some(rollback) = self.rollback_summary() {
+            facts.push(("Rolled back to", rollback));
+        }
+        if let Some(error) = &self.rollback_error {
+            facts.push(("Rollback error", error.clone()));
+        }
+        if let Some(error) = &self.error {
+            facts.push(("Error", error.clone()));
+        }
+        if let Some(stage) = &self.approval_stage {
+            facts.push(("Stage", stage.clone()));
+        }
+        if !self.approvers.is_empty() {
+            facts.push(("Approvers", self.approvers.join(", ")));
+        }
+        if let Some(url) = &self.run_url {
+            facts.push(("Run", url.clone()));
+        }
+        facts.extend(self.command());
+        facts
+    }
+
+    /// Plain-text details, one per line
+    pub fn details(&self) -> Vec<String> {
+        self.facts()
+            .into_iter()
+            .map(|(label, value)| format!("{}: {}", label, value))
+            .collect()
+    }
+
+    /// Slack message for `channel`
+    pub fn slack_message(&self, channel: &str) -> SlackMessage {
+        let icon = match self.event {
+            PipelineNotificationEvent::Failure => "❌",
+            PipelineNotificationEvent::Rollback => "↩️",
+            PipelineNotificationEvent::Recovery => "✅",
+            PipelineNotificationEvent::Approval => "⏸️",
+        };
+
+        let mut summary = format!("{} *{}*", icon, self.title());
+        if let Some(stage) = &self.failed_stage {
+            summary.push_str(&format!("\n*Failed stage:* {}", stage));
+        }
+        if let Some(rollback) = self.rollback_summary() {
+            summary.push_str(&format!("\n*Rolled back to:* {}", rollback));
+        }
+        if let Some(error) = &self.rollback_error {
+            summary.push_str(&format!("\n*Rollback error:* {}", error));
+        }
+        if let Some(error) = &self.error {
+            summary.push_str(&format!("\n*Error:* {}", error));
+        }
+        if let Some(stage) = &self.approval_stage {
+            summary.push_str(&format!("\n*Stage:* {}", stage));
+        }
+        if !self.approvers.is_empty() {
+            summary.push_str(&format!("\n*Approvers:* {}", self.approvers.join(", ")));
+        }
+
+        let mut blocks = vec![SlackBlock::Section {
+            text: SlackText::mrkdwn(summary),
+            accessory: None,
+            fields: None,
+        }];
+
+        let mut context = Vec::new();
+        if let Some(url) = &self.run_url {
+            context.push(SlackContextElement::Mrkdwn {
+                text: format!("<{}|View run #{}>", url, self.run_id),
+            });
+        }
+        if let Some((label, command)) = self.command() {
+            context.push(SlackContextElement::Mrkdwn {
+                text: format!("{}: `{}`", label, command),
+            });
+        }
+        if !context.is_empty() {
+            blocks.push(SlackBlock::Context { elements: context });
+        }
+
+        SlackMessage::new(channel, self.title()).with_blocks(blocks)
+    }
+
+    /// Microsoft Teams incoming webhook payload carrying an Adaptive Card
+    pub fn teams_message(&self) -> serde_json::Value {
+        let color = match self.event {
+            PipelineNotificationEvent::Failure | PipelineNotificationEvent::Rollback => "Attention",
+            PipelineNotificationEvent::Recovery => "Good",
+            PipelineNotificationEvent::Approval => "Warning",
+        };
+
+        let mut body = vec![serde_json::json!({
+            "type": "TextBlock",
+            "text": self.title(),
+            "weight": "Bolder",
+            "size": "Medium",
+            "color": color,
+            "wrap": true,
+        })];
+        // The run link is an action rather than a fact
+        let facts: Vec<serde_json::Value> = self
+            .facts()
+            .into_iter()
+            .filter(|(label, _)| *label != "Run")
+            .map(|(title, value)| serde_json::json!({ "title": title, "value": value }))
+            .collect();
+        if !facts.is_empty() {
+            body.push(serde_json::json!({ "type": "FactSet", "facts": facts }));
+        }
+
+        let mut card = serde_json::json!({
+            "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+            "type": "AdaptiveCard",
+            "version": "1.4",
+            "body": body,
+        });
+        if let Some(url) = &self.run_url {
+            card["actions"] = serde_json::json!([{
+                "type": "Action.OpenUrl",
+                "title": format!("View run #{}", self.run_id),
+                "url": url,
+            }]);
+        }
+
+        serde_json::json!({
+            "type": "message",
+            "attachments": [{
+                "contentType": "application/vnd.microsoft.card.adaptive",
+                "contentUrl": null,
+                "content": card,
+            }],
+        })
+    }
+
+    /// HTML email for `to`, sent when SMTP is configured
+    pub fn email(&self, to: &[String]) -> EmailMessage {
+        let mut rows: Vec<Vec<String>> = self
+            .facts()
+            .into_iter()
+            .filter(|(label, _)| *label != "Run")
+            .map(|(label, value)| vec![label.to_string(), value])
+            .collect();
+        if let Some((label, command)) = self.command() {
+            rows.push(vec![label.to_string(), command]);
+        }
+
+        let mut digest = EmailDigest::new(self.title(), "").with_table(&[], rows);
+        if let Some(url) = &self.run_url {
+            digest = digest.with_link(format!("View run #{}", self.run_id), url);
+        }
+        digest.into_message(to.to_vec(), format!("[orchestrate] {}", self.title()))
+    }
+
+    /// Email message in RFC 5322 form, addressed to `to`
+    pub fn email_message(&self, to: &[String]) -> String {
+        format!(
+            "To: {}\nSubject: [orchestrate] {}\nContent-Type: text/plain; charset=utf-8\n\n{}\n\n{}\n",
+            to.join(", "),
+            self.title(),
+            self.title(),
+            self.details().join("\n")
+        )
+    }
+}
+
+/// Where a notification is delivered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationTarget {
+    /// Slack channel
+    Slack { channel: String },
+    /// Email addresses
+    Email { to: Vec<String> },
+    /// URL receiving the notification as JSON
+    Webhook { url: String },
+    /// Microsoft Teams incoming webhook
+    Teams { webhook_url: String },
+}
+
+impl NotificationTarget {
+    /// Targets configured in a pipeline's notification settings
+    pub fn from_settings(settings: &PipelineNotifications) -> Vec<Self> {
+        let mut targets = Vec::new();
+        if let Some(channel) = &settings.slack_channel {
+            targets.push(Self::Slack {
+                channel: channel.clone(),
+            });
+        }
+        if !settings.email.is_empty() {
+            targets.push(Self::Email {
+                to: settings.email.clone(),
+            });
+        }
+        if let Some(url) = &settings.webhook_url {
+            targets.push(Self::Webhook { url: url.clone() });
+        }
+        if let Some(webhook_url) = &settings.teams_webhook_url {
+            targets.push(Self::Teams {
+                webhook_url: webhook_url.clone(),
+            });
+        }
+        targets
+    }
+}
+
+/// Delivers pipeline notifications
+#[async_trait]
+pub trait NotificationSender: Send + Sync {
+    /// Deliver a notification to a single target
+    async fn send(
+        &self,
+        target: &NotificationTarget,
+        notification: &PipelineNotification,
+    ) -> Result<()>;
+}
+
+/// Sends notifications over HTTP (Slack, webhooks, and Teams), with email
+/// sent through SMTP when configured and handed to `sendmail` otherwise
+pub struct HttpNotificationSender {
+    http_client: reqwest::Client,
+    slack_token: Option<String>,
+    slack_api_url: String,
+    sendmail_path: String,
+    smtp: Option<SmtpClient>,
+}
+
+impl HttpNotificationSender {
+    /// Create a sender without Slack credentials
+    pub fn new() -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            slack_token: None,
+            slack_api_url: "https://slack.com/api".to_string(),
+            sendmail_path: "sendmail".to_string(),
+            smtp: None,
+        }
+    }
+
+    /// Create a sender using the Slack bot token in `SLACK_BOT_TOKEN` and the
+    /// SMTP server configured by `ORCHESTRATE_SMTP_*`
+    pub fn from_env() -> Self {
+        let mut sender = Self::new();
+        match SmtpClient::from_env() {
+            Ok(Some(client)) => sender = sender.with_smtp(client),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(error = %e, "Invalid SMTP configuration - using sendmail"),
+        }
+        match std::env::var("SLACK_BOT_TOKEN") {
+            Ok(token) if !token.is_empty() => sender.with_slack_token(token),
+            _ => sender,
+        }
+    }
+
+    /// Set the Slack bot token used to post messages
+    pub fn with_slack_token(mut self, token: impl Into<String>) -> Self {
+        self.slack_token = Some(token.into());
+        self
+    }
+
+    /// Set the Slack Web API base URL
+    pub fn with_slack_api_url(mut self, url: impl Into<String>) -> Self {
+        self.slack_api_url = url.into();
+        self
+    }
+
+    /// Set the sendmail binary used for email
+    pub fn with_sendmail_path(mut self, path: impl Into<String>) -> Self {
+        self.sendmail_path = path.into();
+        self
+    }
+
+    /// Send email through `client` instead of sendmail
+    pub fn with_smtp(mut self, client: SmtpClient) -> Self {
+        self.smtp = Some(client);
+        self
+    }
+
+    async fn post_slack(&self, channel: &str, notification: &PipelineNotification) -> Result<()> {
+        let token = self.slack_token.as_ref().ok_or_else(|| {
+            Error::Other("Slack notifications require SLACK_BOT_TOKEN".to_string())
+        })?;
+
+        let response: serde_json::Value = self
+            .http_client
+            .post(format!("{}/chat.postMessage", self.slack_api_url))
+            .bearer_auth(token)
+            .json(&notification.slack_message(channel))
+            .send()
+            .await
+            .map_err(|e| Error::Other(format!("Failed to post Slack message: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| Error::Other(format!("Invalid Slack response: {}", e)))?;
+
+        if response["ok"].as_bool() != Some(true) {
+            return Err(Error::Other(format!(
+                "Slack rejected message: {}",
+                response["error"].as_str().unwrap_or("unknown error")
+            )));
+        }
+        Ok(())
+    }
+
+    async fn post_webhook(&self, url: &str, notification: &PipelineNotification) -> Result<()> {
+        let response = self
+            .http_client
+            .post(url)
+            .json(notification)
+            .send()
+            .await
+            .map_err(|e| Error::Other(format!("Failed to post notification webhook: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(Error::Other(format!(
+                "Notification webhook returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn post_teams(
+        &self,
+        webhook_url: &str,
+        notification: &PipelineNotification,
+    ) -> Result<()> {
+        let response = self
+            .http_client
+            .post(webhook_url)
+            .json(&notification.teams_message())
+            .send()
+            .await
+            .map_err(|e| Error::Other(format!("Failed to post Teams message: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(Error::Other(format!(
+                "Teams webhook returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn send_email(&self, to: &[String], notification: &PipelineNotification) -> Result<()> {
+        if let Some(smtp) = &self.smtp {
+            return smtp.send(&notification.email(to)).await;
+        }
+
+        let mut child = tokio::process::Command::new(&self.sendmail_path)
+            .arg("-t")
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| Error::Other(format!("Failed to run {}: {}", self.sendmail_path, e)))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(notification.email_message(to).as_bytes())
+                .await
+                .map_err(|e| Error::Other(format!("Failed to write email: {}", e)))?;
+        }
+
+        let status = child
+            .wait()
+            .await
+            .map_err(|e| Error::Other(format!("Failed to send email: {}", e)))?;
+        if !status.success() {
+            return Err(Error::Other(format!(
+                "{} exited with {}",
+                self.sendmail_path, status
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for HttpNotificationSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl NotificationSender for HttpNotificationSender {
+    async fn send(
+        &self,
+        target: &NotificationTarget,
+        notification: &PipelineNotification,
+    ) -> Result<()> {
+        match target {
+            NotificationTarget::Slack { channel } => self.post_slack(channel, notification).await,
+            NotificationTarget::Email { to } => self.send_email(to, notification).await,
+            NotificationTarget::Webhook { url } => self.post_webhook(url, notification).await,
+            NotificationTarget::Teams { webhook_url } => {
+                self.post_teams(webhook_url, notification).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure() -> PipelineNotification {
+        PipelineNotification::new(PipelineNotificationEvent::Failure, "deploy", 42)
+            .with_failure(Some("smoke-test".to_string()), "Simulated agent failure")
+            .with_dashboard_url("https://orchestrate.example.com/")
+            .with_commit("abc123")
+    }
+
+    #[test]
+    fn test_failure_summary() {
+        let notification = failure();
+        assert_eq!(notification.title(), "Pipeline deploy run #42 failed");
+        assert_eq!(
+            notification.run_url.as_deref(),
+            Some("https://orchestrate.example.com/pipelines/deploy/runs/42")
+        );
+        assert_eq!(
+            notification.details(),
+            vec![
+                "Failed stage: smoke-test",
+                "Error: Simulated agent failure",
+                "Run: https://orchestrate.example.com/pipelines/deploy/runs/42",
+                "Rerun: orchestrate pipeline run deploy --commit abc123",
+            ]
+        );
+
+        let email = notification.email_message(&["oncall@example.com".to_string()]);
+        assert!(email.starts_with(
+            "To: oncall@example.com\nSubject: [orchestrate] Pipeline deploy run #42 failed\n"
+        ));
+        assert!(email.contains("Failed stage: smoke-test"));
+
+        let email = notification.email(&["oncall@example.com".to_string()]);
+        assert_eq!(
+            email.subject,
+            "[orchestrate] Pipeline deploy run #42 failed"
+        );
+        assert!(email
+            .text
+            .contains("Rerun: orchestrate pipeline run deploy --commit abc123"));
+        assert!(email.html.contains(
+            "<a href=\"https://orchestrate.example.com/pipelines/deploy/runs/42\">View run #42</a>"
+        ));
+    }
+
+    #[test]
+    fn test_slack_message() {
+        let message = failure().slack_message("#deployments");
+        assert_eq!(message.channel, "#deployments");
+        assert_eq!(message.text, "Pipeline deploy run #42 failed");
+
+        let json = serde_json::to_string(&message.blocks).unwrap();
+        assert!(json.contains("*Failed stage:* smoke-test"));
+        assert!(json
+            .contains("<https://orchestrate.example.com/pipelines/deploy/runs/42|View run #42>"));
+        assert!(json.contains("Rerun: `orchestrate pipeline run deploy --commit abc123`"));
+    }
+
+    #[test]
+    fn test_rollback_outcome() {
+        let mut rollback = RollbackEvent::new(
+            42,
+            "verify".to_string(),
+            "deploy".to_string(),
+            RollbackTriggerType::Automatic,
+        );
+        rollback.mark_failed("Simulated agent failure".to_string());
+
+        let notification =
+            PipelineNotification::new(PipelineNotificationEvent::Rollback, "deploy", 42)
+                .with_failure(Some("verify".to_string()), "Smoke tests failed")
+                .with_rollback(&rollback);
+        assert_eq!(
+            notification.details(),
+            vec![
+                "Failed stage: verify",
+                "Rolled back to: deploy (automatic, failed)",
+                "Rollback error: Simulated agent failure",
+                "Error: Smoke tests failed",
+                "Rerun: orchestrate pipeline run deploy",
+            ]
+        );
+
+        let json =
+            serde_json::to_string(&notification.slack_message("#deployments").blocks).unwrap();
+        assert!(json.contains("*Rolled back to:* deploy (automatic, failed)"));
+    }
+
+    #[test]
+    fn test_recovery_omits_rerun() {
+        let notification =
+            PipelineNotification::new(PipelineNotificationEvent::Recovery, "deploy", 43);
+        assert_eq!(notification.title(), "Pipeline deploy recovered in run #43");
+        assert!(notification.details().is_empty());
+    }
+
+    #[test]
+    fn test_approval_summary() {
+        let mut approval = ApprovalRequest::new(5, 42, "alice,bob".to_string(), 2, None, None);
+        approval.id = Some(7);
+
+        let notification =
+            PipelineNotification::new(PipelineNotificationEvent::Approval, "deploy", 42)
+                .with_approval("production", &approval);
+        assert_eq!(
+            notification.title(),
+            "Pipeline deploy run #42 is waiting for approval"
+        );
+        assert_eq!(
+            notification.details(),
+            vec![
+                "Stage: production",
+                "Approvers: alice, bob",
+                "Approve: orchestrate approval approve 7",
+            ]
+        );
+
+        let json =
+            serde_json::to_string(&notification.slack_message("#deployments").blocks).unwrap();
+        assert!(json.contains("*Approvers:* alice, bob"));
+        assert!(json.contains("Approve: `orchestrate approval approve 7`"));
+    }
+
+    #[test]
+    fn test_teams_message() {
+        let message = failure().teams_message();
+        assert_eq!(message["type"], "message");
+
+        let attachment = &message["attachments"][0];
+        assert_eq!(
+            attachment["contentType"],
+            "application/vnd.microsoft.card.adaptive"
+        );
+        let card = &attachment["content"];
+        assert_eq!(card["type"], "AdaptiveCard");
+        assert_eq!(card["body"][0]["text"], "Pipeline deploy run #42 failed");
+        assert_eq!(card["body"][0]["color"], "Attention");
+        assert_eq!(
+            card["body"][1]["facts"],
+            serde_json::json!([
+                { "title": "Failed stage", "value": "smoke-test" },
+                { "title": "Error", "value": "Simulated agent failure" },
+                { "title": "Rerun", "value": "orchestrate pipeline run deploy --commit abc123" },
+            ])
+        );
+        assert_eq!(card["actions"][0]["type"], "Action.OpenUrl");
+        assert_eq!(
+            card["actions"][0]["url"],
+            "https://orchestrate.example.com/pipelines/deploy/runs/42"
+        );
+
+        // Nothing to list for a recovery without a dashboard link
+        let card = PipelineNotification::new(PipelineNotificationEvent::Recovery, "deploy", 43)
+            .teams_message()["attachments"][0]["content"]
+            .clone();
+        assert_eq!(card["body"].as_array().unwrap().len(), 1);
+        assert!(card.get("actions").is_none());
+    }
+
+    #[test]
+    fn test_targets_from_settings() {
+        let settings = PipelineNotifications {
+            slack_channel: Some("#ci".to_string()),
+            email: vec!["oncall@example.com".to_string()],
+            webhook_url: None,
+            teams_webhook_url: Some("https://example.webhook.office.com/webhookb2/abc".to_string()),
+            on: vec![PipelineNotificationEvent::Failure],
+        };
+        assert_eq!(
+            NotificationTarget::from_settings(&settings),
+            vec![
+                NotificationTarget::Slack {
+                    channel: "#ci".to_string()
+                },
+                NotificationTarget::Email {
+                    to: vec!["oncall@example.com".to_string()]
+                },
+                NotificationTarget::Teams {
+                    webhook_url: "https://example.webhook.office.com/webhookb2/abc".to_string()
+                },
+            ]
+        );
+    }
+}
@@ -1,16 +1,26 @@
 //! Pipeline YAML parser
 //!
 //! This module provides parsing and validation for pipeline definitions
-//! from YAML format.
+//! from YAML format. The accepted format is published as a JSON Schema in
+//! `docs/pipeline.schema.json`.
 
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 use std::path::Path;
 
-use crate::{Error, Result};
+use crate::{CronSchedule, Error, Result};
+
+/// JSON Schema describing the pipeline YAML format
+pub const PIPELINE_SCHEMA: &str = include_str!("../../../docs/pipeline.schema.json");
+
+/// Trigger event for pipelines started on a cron schedule
+pub const SCHEDULE_EVENT: &str = "schedule";
 
 /// Pipeline definition parsed from YAML
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct PipelineDefinition {
     /// Pipeline name
     pub name: String,
@@ -25,6 +35,15 @@ pub struct PipelineDefinition {
     /// Pipeline variables
     #[serde(default)]
     pub variables: HashMap<String, String>,
+    /// Where to report failed, rolled back, and recovered runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<PipelineNotifications>,
+    /// How many runs may be in progress at once, and what happens to the rest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<PipelineConcurrency>,
+    /// How many independent stages of a run may execute at once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallel: Option<usize>,
     /// Stage definitions
     pub stages: Vec<StageDefinition>,
 }
@@ -35,16 +54,137 @@ fn default_version() -> u32 {
 
 /// Trigger definition
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct TriggerDefinition {
-    /// Event type (e.g., "pull_request.merged")
+    /// Event type (e.g., "pull_request.merged"), or "schedule" for cron triggers
     pub event: String,
+    /// Cron expression for "schedule" triggers (e.g., "0 2 * * *" or "@daily")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron: Option<String>,
     /// Branches to trigger on
     #[serde(default)]
     pub branches: Vec<String>,
+    /// Labels to trigger on; the event must carry at least one of them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Pipeline variables taken from the event payload, mapped to the
+    /// dot-separated payload path they are read from (e.g., "pull_request.number")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+}
+
+impl TriggerDefinition {
+    /// Whether a webhook event fires this trigger
+    ///
+    /// `event_key` is the event type with its action (e.g.,
+    /// "pull_request.labeled"). A trigger on the bare event type matches
+    /// every action, and "pull_request.merged" matches a closed pull
+    /// request that was merged.
+    pub fn matches_event(&self, event_key: &str, payload: &serde_json::Value) -> bool {
+        if self.cron.is_some() {
+            return false;
+        }
+
+        let event_type = event_key.split('.').next().unwrap_or(event_key);
+        let merged = event_key == "pull_request.closed"
+            && payload
+                .pointer("/pull_request/merged")
+                .and_then(|m| m.as_bool())
+                .unwrap_or(false);
+        let event_matches = self.event == event_key
+            || self.event == event_type
+            || (merged && self.event == "pull_request.merged");
+        if !event_matches {
+            return false;
+        }
+
+        if !self.branches.is_empty() {
+            let branch = match payload_branch(payload) {
+                Some(branch) => branch,
+                None => return false,
+            };
+            let allowed = self.branches.iter().any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => branch.starts_with(prefix),
+                None => branch == *pattern,
+            });
+            if !allowed {
+                return false;
+            }
+        }
+
+        if !self.labels.is_empty() {
+            let labels = payload_labels(payload);
+            if !self.labels.iter().any(|label| labels.contains(label)) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Trigger event recorded on runs started by this cron trigger
+    /// (e.g., "schedule:0 2 * * *"), or `None` for webhook triggers
+    pub fn scheduled_event(&self) -> Option<String> {
+        self.cron
+            .as_ref()
+            .map(|cron| format!("{}:{}", SCHEDULE_EVENT, cron))
+    }
+
+    /// Read this trigger's variables from an event payload
+    ///
+    /// Strings are used as-is and other values as JSON; variables whose
+    /// path is missing from the payload are left out.
+    pub fn payload_variables(&self, payload: &serde_json::Value) -> HashMap<String, String> {
+        self.variables
+            .iter()
+            .filter_map(|(name, path)| {
+                let pointer = format!("/{}", path.replace('.', "/"));
+                let value = match payload.pointer(&pointer)? {
+                    serde_json::Value::Null => return None,
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                Some((name.clone(), value))
+            })
+            .collect()
+    }
+}
+
+/// Branch an event payload refers to: the base branch of a pull request or
+/// the branch pushed to
+fn payload_branch(payload: &serde_json::Value) -> Option<String> {
+    if let Some(base) = payload
+        .pointer("/pull_request/base/ref")
+        .and_then(|r| r.as_str())
+    {
+        return Some(base.to_string());
+    }
+    payload
+        .get("ref")
+        .and_then(|r| r.as_str())
+        .map(|r| r.strip_prefix("refs/heads/").unwrap_or(r).to_string())
+}
+
+/// Labels an event payload carries
+///
+/// For `labeled` events only the label that was just added counts, so adding
+/// an unrelated label does not re-fire a trigger.
+fn payload_labels(payload: &serde_json::Value) -> Vec<String> {
+    if let Some(label) = payload.pointer("/label/name").and_then(|n| n.as_str()) {
+        return vec![label.to_string()];
+    }
+    ["/pull_request/labels", "/issue/labels"]
+        .iter()
+        .filter_map(|pointer| payload.pointer(pointer).and_then(|l| l.as_array()))
+        .flatten()
+        .filter_map(|l| l.get("name").and_then(|n| n.as_str()))
+        .map(|s| s.to_string())
+        .collect()
 }
 
 /// Stage definition
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct StageDefinition {
     /// Stage name
     pub name: String,
@@ -55,32 +195,69 @@ pub struct StageDefinition {
     /// Timeout duration (e.g., "30m", "1h")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<String>,
+    /// Retries after a failed or timed out attempt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<StageRetryPolicy>,
     /// Action on failure
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on_failure: Option<FailureAction>,
     /// Rollback target stage
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rollback_to: Option<String>,
+    /// Agent task that undoes this stage when a later stage rolls back to it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollback: Option<RollbackHook>,
+    /// Compensation actions to run if a later stage fails
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub compensation: Vec<crate::saga::CompensationDefinition>,
     /// Requires human approval
     #[serde(default)]
     pub requires_approval: bool,
     /// List of approvers
     #[serde(default)]
     pub approvers: Vec<String>,
+    /// What happens when nobody decides on the approval in time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_timeout: Option<ApprovalTimeoutPolicy>,
     /// Environment for this stage
     #[serde(skip_serializing_if = "Option::is_none")]
     pub environment: Option<String>,
-    /// Dependencies (stages that must complete first)
-    #[serde(default)]
+    /// Vault secrets exposed to the stage's agent as environment variables
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secrets: Vec<String>,
+    /// Dependencies (stages that must complete first), also accepted as `needs`
+    #[serde(default, alias = "needs")]
     pub depends_on: Vec<String>,
     /// Stage to run in parallel with
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parallel_with: Option<String>,
+    /// Run the stage once per combination of matrix parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matrix: Option<StageMatrix>,
     /// Conditional execution
     #[serde(skip_serializing_if = "Option::is_none")]
     pub when: Option<StageCondition>,
 }
 
+impl StageDefinition {
+    /// Key identifying this stage's inputs for the stage result cache
+    ///
+    /// Hashes the commit SHA, the stage definition, and the task after
+    /// variable substitution, so any change to one of them misses the cache.
+    pub fn cache_key(&self, commit_sha: &str, resolved_task: &str) -> Result<String> {
+        // Going through `Value` sorts map keys, keeping the hash stable
+        let definition = serde_json::to_string(&serde_json::to_value(self)?)?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(commit_sha.as_bytes());
+        hasher.update([0]);
+        hasher.update(definition.as_bytes());
+        hasher.update([0]);
+        hasher.update(resolved_task.as_bytes());
+        Ok(hex::encode(hasher.finalize()))
+    }
+}
+
 /// Action to take on stage failure
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -93,8 +270,292 @@ pub enum FailureAction {
     Rollback,
 }
 
+/// Retry policy for a stage's agent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StageRetryPolicy {
+    /// Number of retries after the first attempt
+    pub count: u32,
+    /// Delay before the first retry (e.g., "30s"), doubled for each further
+    /// retry; retries start immediately when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backoff: Option<String>,
+}
+
+impl StageRetryPolicy {
+    /// Total number of attempts, including the first
+    pub fn max_attempts(&self) -> u32 {
+        self.count.saturating_add(1)
+    }
+}
+
+/// Rollback hook of a stage
+///
+/// Runs automatically when a later stage (e.g. post-deploy verification)
+/// fails with `on_failure: rollback` and `rollback_to` names this stage, and
+/// when a rollback to this stage is triggered manually.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RollbackHook {
+    /// Agent that performs the rollback
+    pub agent: String,
+    /// Rollback task description, with variables substituted like stage tasks
+    pub task: String,
+}
+
+/// Timeout handling for a stage's approval request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApprovalTimeoutPolicy {
+    /// How long approvers have to decide (e.g., "4h")
+    #[serde(default = "default_approval_timeout")]
+    pub after: String,
+    /// Decision applied once the timeout expires
+    #[serde(default)]
+    pub action: ApprovalTimeoutAction,
+    /// Approvers added when the timeout first expires; the timeout then
+    /// restarts and `action` applies only if it expires again
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub escalate_to: Vec<String>,
+}
+
+impl Default for ApprovalTimeoutPolicy {
+    fn default() -> Self {
+        Self {
+            after: default_approval_timeout(),
+            action: ApprovalTimeoutAction::default(),
+            escalate_to: Vec::new(),
+        }
+    }
+}
+
+fn default_approval_timeout() -> String {
+    "24h".to_string()
+}
+
+/// Decision applied to an approval that timed out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalTimeoutAction {
+    /// Approve the stage
+    Approve,
+    /// Reject the stage
+    #[default]
+    Reject,
+}
+
+impl ApprovalTimeoutAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Approve => "approve",
+            Self::Reject => "reject",
+        }
+    }
+}
+
+/// Notification settings for a pipeline's runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineNotifications {
+    /// Slack channel to post to (e.g., "#deployments")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slack_channel: Option<String>,
+    /// Email addresses to notify
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub email: Vec<String>,
+    /// URL that receives the notification as a JSON POST
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    /// Microsoft Teams incoming webhook that receives an Adaptive Card
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teams_webhook_url: Option<String>,
+    /// Run outcomes to notify about
+    #[serde(default = "default_notification_events")]
+    pub on: Vec<PipelineNotificationEvent>,
+}
+
+impl PipelineNotifications {
+    /// Whether a run outcome should be notified
+    pub fn notifies(&self, event: PipelineNotificationEvent) -> bool {
+        self.on.contains(&event)
+    }
+}
+
+fn default_notification_events() -> Vec<PipelineNotificationEvent> {
+    vec![
+        PipelineNotificationEvent::Failure,
+        PipelineNotificationEvent::Rollback,
+        PipelineNotificationEvent::Recovery,
+    ]
+}
+
+/// Run outcome that can trigger a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PipelineNotificationEvent {
+    /// The run failed
+    Failure,
+    /// The run failed and rolled back
+    Rollback,
+    /// The run succeeded after the previous run failed
+    Recovery,
+    /// A stage is waiting for approval
+    Approval,
+}
+
+impl PipelineNotificationEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Failure => "failure",
+            Self::Rollback => "rollback",
+            Self::Recovery => "recovery",
+            Self::Approval => "approval",
+        }
+    }
+}
+
+/// Concurrency limit for a pipeline's runs
+///
+/// Runs that are pending, running, or waiting for approval count against the
+/// limit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineConcurrency {
+    /// Runs allowed to be in progress at the same time
+    #[serde(default = "default_concurrency_limit")]
+    pub limit: u32,
+    /// What happens to a run triggered while the limit is reached
+    #[serde(default)]
+    pub on_limit: ConcurrencyAction,
+}
+
+impl Default for PipelineConcurrency {
+    fn default() -> Self {
+        Self {
+            limit: default_concurrency_limit(),
+            on_limit: ConcurrencyAction::default(),
+        }
+    }
+}
+
+fn default_concurrency_limit() -> u32 {
+    1
+}
+
+/// What happens to a run triggered while a pipeline is at its concurrency limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcurrencyAction {
+    /// Wait until an earlier run finishes
+    #[default]
+    Queue,
+    /// Cancel the oldest runs in progress to make room
+    CancelInProgress,
+    /// Drop the new run
+    Reject,
+}
+
+impl ConcurrencyAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Queue => "queue",
+            Self::CancelInProgress => "cancel_in_progress",
+            Self::Reject => "reject",
+        }
+    }
+}
+
+/// Matrix parameter filled with the names of all known repositories
+pub const MATRIX_REPO_PARAMETER: &str = "repo";
+
+/// Matrix expansion for a stage
+///
+/// Every key other than `exclude` and `all_repos` is a parameter mapped to the
+/// values it takes; the stage runs once for each combination of values.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StageMatrix {
+    /// Parameter name to its values
+    #[serde(flatten)]
+    pub parameters: BTreeMap<String, Vec<String>>,
+    /// Combinations to leave out; an entry matches when all of its pairs match
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<BTreeMap<String, String>>,
+    /// Add a `repo` parameter covering every repository in the multi-repo registry
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub all_repos: bool,
+}
+
+impl StageMatrix {
+    /// Create an empty matrix
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a parameter and its values
+    pub fn with_parameter(
+        mut self,
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.parameters
+            .insert(name.into(), values.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Leave out combinations matching all the given pairs
+    pub fn with_exclude(
+        mut self,
+        pairs: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.exclude.push(
+            pairs
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Expand into parameter combinations, in parameter name then value order
+    pub fn combinations(&self) -> Vec<BTreeMap<String, String>> {
+        if self.parameters.is_empty() {
+            return Vec::new();
+        }
+
+        let mut combinations = vec![BTreeMap::new()];
+        for (name, values) in &self.parameters {
+            combinations = combinations
+                .into_iter()
+                .flat_map(|combination| {
+                    values.iter().map(move |value| {
+                        let mut next = combination.clone();
+                        next.insert(name.clone(), value.clone());
+                        next
+                    })
+                })
+                .collect();
+        }
+
+        combinations.retain(|combination| {
+            !self.exclude.iter().any(|excluded| {
+                excluded
+                    .iter()
+                    .all(|(k, v)| combination.get(k) == Some(v))
+            })
+        });
+        combinations
+    }
+
+    /// Stage name for one combination, e.g. `test (linux, stable)`
+    pub fn instance_name(stage_name: &str, combination: &BTreeMap<String, String>) -> String {
+        let values: Vec<&str> = combination.values().map(|v| v.as_str()).collect();
+        format!("{} ({})", stage_name, values.join(", "))
+    }
+}
+
 /// Stage execution condition
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct StageCondition {
     /// Branch conditions
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -108,11 +569,81 @@ pub struct StageCondition {
     /// Variable conditions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variable: Option<HashMap<String, String>>,
+    /// Expression that must evaluate to true (see [`crate::ConditionExpression`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expr: Option<String>,
     /// OR condition (alternative conditions)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub or: Option<Box<StageCondition>>,
 }
 
+/// A problem found in a pipeline definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineValidationIssue {
+    /// 1-based line in the YAML source, when the problem can be located
+    pub line: Option<usize>,
+    /// 1-based column in the YAML source, when the problem can be located
+    pub column: Option<usize>,
+    /// What is wrong
+    pub message: String,
+}
+
+impl PipelineValidationIssue {
+    fn new(location: Option<(usize, usize)>, message: impl Into<String>) -> Self {
+        Self {
+            line: location.map(|(line, _)| line),
+            column: location.map(|(_, column)| column),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for PipelineValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{}:{}: {}", line, column, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Line and column of the `index`th entry of the top-level `stages` list
+fn locate_stage(yaml: &str, index: usize) -> Option<(usize, usize)> {
+    let mut lines = yaml
+        .lines()
+        .enumerate()
+        .skip_while(|(_, line)| !line.starts_with("stages:"));
+    lines.next()?;
+
+    let mut item_indent = None;
+    let mut seen = 0;
+    for (number, line) in lines {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+        if !trimmed.starts_with('-') {
+            if indent == 0 {
+                // Next top-level key
+                break;
+            }
+            continue;
+        }
+        // Nested lists inside a stage are indented further than the stages
+        if *item_indent.get_or_insert(indent) != indent {
+            continue;
+        }
+        if seen == index {
+            let content = trimmed[1..].trim_start();
+            return Some((number + 1, line.len() - content.len() + 1));
+        }
+        seen += 1;
+    }
+
+    None
+}
+
 impl PipelineDefinition {
     /// Parse pipeline from YAML string
     pub fn from_yaml_str(yaml: &str) -> Result<Self> {
@@ -144,6 +675,22 @@ impl PipelineDefinition {
             return Err(Error::Other("Pipeline must have at least one stage".to_string()));
         }
 
+        for trigger in &self.triggers {
+            Self::validate_trigger(trigger)?;
+        }
+
+        if let Some(notifications) = &self.notifications {
+            Self::validate_notifications(notifications)?;
+        }
+
+        if let Some(concurrency) = &self.concurrency {
+            Self::validate_concurrency(concurrency)?;
+        }
+
+        if let Some(max_parallel) = self.max_parallel {
+            Self::validate_max_parallel(max_parallel)?;
+        }
+
         // Collect stage names for dependency validation
         let stage_names: HashSet<_> = self.stages.iter().map(|s| s.name.as_str()).collect();
 
@@ -158,6 +705,286 @@ impl PipelineDefinition {
         Ok(())
     }
 
+    /// Parse and validate pipeline YAML, reporting problems with their location
+    ///
+    /// Unlike [`Self::from_yaml_str`], this reports every stage with a problem
+    /// rather than only the first, and also reports stages that can never run
+    /// because something they depend on never completes.
+    pub fn check_yaml(yaml: &str) -> std::result::Result<Self, Vec<PipelineValidationIssue>> {
+        let definition: PipelineDefinition = serde_yaml::from_str(yaml).map_err(|e| {
+            let location = e.location().map(|l| (l.line(), l.column()));
+            let message = e.to_string();
+            // The location is reported separately
+            let message = match message.rsplit_once(" at line ") {
+                Some((message, _)) if location.is_some() => message.to_string(),
+                _ => message,
+            };
+            vec![PipelineValidationIssue::new(location, message)]
+        })?;
+
+        let mut issues = Vec::new();
+        if definition.name.is_empty() {
+            issues.push(PipelineValidationIssue::new(
+                None,
+                "Pipeline name cannot be empty",
+            ));
+        }
+        if definition.stages.is_empty() {
+            issues.push(PipelineValidationIssue::new(
+                None,
+                "Pipeline must have at least one stage",
+            ));
+        }
+
+        for trigger in &definition.triggers {
+            if let Err(e) = Self::validate_trigger(trigger) {
+                issues.push(PipelineValidationIssue::new(None, e.to_string()));
+            }
+        }
+
+        if let Some(notifications) = &definition.notifications {
+            if let Err(e) = Self::validate_notifications(notifications) {
+                issues.push(PipelineValidationIssue::new(None, e.to_string()));
+            }
+        }
+
+        if let Some(concurrency) = &definition.concurrency {
+            if let Err(e) = Self::validate_concurrency(concurrency) {
+                issues.push(PipelineValidationIssue::new(None, e.to_string()));
+            }
+        }
+
+        if let Some(max_parallel) = definition.max_parallel {
+            if let Err(e) = Self::validate_max_parallel(max_parallel) {
+                issues.push(PipelineValidationIssue::new(None, e.to_string()));
+            }
+        }
+
+        let stage_names: HashSet<_> = definition.stages.iter().map(|s| s.name.as_str()).collect();
+        let mut invalid_stages = HashSet::new();
+        for (index, stage) in definition.stages.iter().enumerate() {
+            if let Err(e) = definition.validate_stage(stage, &stage_names) {
+                invalid_stages.insert(stage.name.as_str());
+                issues.push(PipelineValidationIssue::new(
+                    locate_stage(yaml, index),
+                    e.to_string(),
+                ));
+            }
+        }
+
+        if let Err(e) = definition.validate_no_cycles() {
+            issues.push(PipelineValidationIssue::new(None, e.to_string()));
+        }
+
+        let unreachable = definition.unreachable_stages();
+        for (index, stage) in definition.stages.iter().enumerate() {
+            if invalid_stages.contains(stage.name.as_str()) {
+                continue;
+            }
+            if let Some(blocked_on) = unreachable.get(stage.name.as_str()) {
+                issues.push(PipelineValidationIssue::new(
+                    locate_stage(yaml, index),
+                    format!(
+                        "Stage '{}' can never run: it waits on {} which never completes",
+                        stage.name,
+                        blocked_on.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(definition)
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Report stages whose agent is not a known agent type
+    pub fn check_agents(
+        &self,
+        yaml: &str,
+        is_known_agent: impl Fn(&str) -> bool,
+    ) -> Vec<PipelineValidationIssue> {
+        self.stages
+            .iter()
+            .enumerate()
+            .filter(|(_, stage)| !stage.agent.is_empty() && !is_known_agent(&stage.agent))
+            .map(|(index, stage)| {
+                PipelineValidationIssue::new(
+                    locate_stage(yaml, index),
+                    format!(
+                        "Stage '{}' uses unknown agent type '{}'",
+                        stage.name, stage.agent
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    /// Stages that can never become ready, mapped to the dependencies they wait on
+    ///
+    /// A stage is unreachable when it depends on a stage that does not exist,
+    /// is part of a dependency cycle, or is itself unreachable.
+    fn unreachable_stages(&self) -> BTreeMap<&str, Vec<&str>> {
+        let mut reachable: HashSet<&str> = HashSet::new();
+
+        loop {
+            let newly_reachable: Vec<&str> = self
+                .stages
+                .iter()
+                .filter(|stage| !reachable.contains(stage.name.as_str()))
+                .filter(|stage| {
+                    stage
+                        .depends_on
+                        .iter()
+                        .all(|dep| reachable.contains(dep.as_str()))
+                })
+                .map(|stage| stage.name.as_str())
+                .collect();
+            if newly_reachable.is_empty() {
+                break;
+            }
+            reachable.extend(newly_reachable);
+        }
+
+        self.stages
+            .iter()
+            .filter(|stage| !reachable.contains(stage.name.as_str()))
+            .map(|stage| {
+                let blocked_on = stage
+                    .depends_on
+                    .iter()
+                    .map(|dep| dep.as_str())
+                    .filter(|dep| !reachable.contains(dep))
+                    .collect();
+                (stage.name.as_str(), blocked_on)
+            })
+            .collect()
+    }
+
+    /// Validate a single trigger
+    fn validate_trigger(trigger: &TriggerDefinition) -> Result<()> {
+        if trigger.event.is_empty() {
+            return Err(Error::Other("Trigger event cannot be empty".to_string()));
+        }
+
+        match (&trigger.cron, trigger.event == SCHEDULE_EVENT) {
+            (Some(cron), true) => {
+                CronSchedule::validate(cron)?;
+                if !trigger.branches.is_empty()
+                    || !trigger.labels.is_empty()
+                    || !trigger.variables.is_empty()
+                {
+                    return Err(Error::Other(format!(
+                        "Schedule trigger '{}' cannot filter on branches or labels or map payload variables",
+                        cron
+                    )));
+                }
+            }
+            (None, true) => {
+                return Err(Error::Other(
+                    "Schedule trigger requires a cron expression".to_string(),
+                ));
+            }
+            (Some(_), false) => {
+                return Err(Error::Other(format!(
+                    "Trigger '{}' has a cron expression but its event is not '{}'",
+                    trigger.event, SCHEDULE_EVENT
+                )));
+            }
+            (None, false) => {}
+        }
+
+        for (name, path) in &trigger.variables {
+            if name.is_empty() || path.is_empty() {
+                return Err(Error::Other(format!(
+                    "Trigger '{}' maps variable '{}' to an empty payload path",
+                    trigger.event, name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate notification settings
+    fn validate_notifications(notifications: &PipelineNotifications) -> Result<()> {
+        if notifications.slack_channel.is_none()
+            && notifications.email.is_empty()
+            && notifications.webhook_url.is_none()
+            && notifications.teams_webhook_url.is_none()
+        {
+            return Err(Error::Other(
+                "Notifications must set a slack_channel, email, webhook_url, or teams_webhook_url"
+                    .to_string(),
+            ));
+        }
+
+        if let Some(channel) = &notifications.slack_channel {
+            if channel.trim().is_empty() {
+                return Err(Error::Other(
+                    "Notification slack_channel cannot be empty".to_string(),
+                ));
+            }
+        }
+
+        for address in &notifications.email {
+            if !address.contains('@') {
+                return Err(Error::Other(format!(
+                    "Notification email '{}' is not an email address",
+                    address
+                )));
+            }
+        }
+
+        if let Some(url) = &notifications.webhook_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(Error::Other(format!(
+                    "Notification webhook_url '{}' must be an http(s) URL",
+                    url
+                )));
+            }
+        }
+
+        if let Some(url) = &notifications.teams_webhook_url {
+            if !url.starts_with("https://") {
+                return Err(Error::Other(format!(
+                    "Notification teams_webhook_url '{}' must be an https URL",
+                    url
+                )));
+            }
+        }
+
+        if notifications.on.is_empty() {
+            return Err(Error::Other(
+                "Notifications must list at least one event in 'on'".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validate concurrency settings
+    fn validate_concurrency(concurrency: &PipelineConcurrency) -> Result<()> {
+        if concurrency.limit == 0 {
+            return Err(Error::Other(
+                "Concurrency limit must be at least 1".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validate the parallel stage cap
+    fn validate_max_parallel(max_parallel: usize) -> Result<()> {
+        if max_parallel == 0 {
+            return Err(Error::Other("max_parallel must be at least 1".to_string()));
+        }
+
+        Ok(())
+    }
+
     /// Validate a single stage
     fn validate_stage(
         &self,
@@ -205,6 +1032,36 @@ impl PipelineDefinition {
             }
         }
 
+        // Validate matrix parameters
+        if let Some(matrix) = &stage.matrix {
+            if matrix.parameters.is_empty() && !matrix.all_repos {
+                return Err(Error::Other(format!(
+                    "Stage '{}' has a matrix without parameters",
+                    stage.name
+                )));
+            }
+            for (name, values) in &matrix.parameters {
+                if values.is_empty() {
+                    return Err(Error::Other(format!(
+                        "Stage '{}' matrix parameter '{}' has no values",
+                        stage.name, name
+                    )));
+                }
+            }
+            if matrix.all_repos && matrix.parameters.contains_key(MATRIX_REPO_PARAMETER) {
+                return Err(Error::Other(format!(
+                    "Stage '{}' matrix sets both all_repos and a '{}' parameter",
+                    stage.name, MATRIX_REPO_PARAMETER
+                )));
+            }
+            if !matrix.all_repos && matrix.combinations().is_empty() {
+                return Err(Error::Other(format!(
+                    "Stage '{}' matrix excludes every combination",
+                    stage.name
+                )));
+            }
+        }
+
         // Validate rollback_to exists and on_failure is rollback
         if let Some(rollback_to) = &stage.rollback_to {
             if stage.on_failure != Some(FailureAction::Rollback) {
@@ -228,6 +1085,15 @@ impl PipelineDefinition {
             }
         }
 
+        if let Some(hook) = &stage.rollback {
+            if hook.agent.trim().is_empty() || hook.task.trim().is_empty() {
+                return Err(Error::Other(format!(
+                    "Stage '{}' rollback needs both an agent and a task",
+                    stage.name
+                )));
+            }
+        }
+
         // Validate approvers when requires_approval is true
         if stage.requires_approval && stage.approvers.is_empty() {
             return Err(Error::Other(format!(
@@ -236,6 +1102,69 @@ impl PipelineDefinition {
             )));
         }
 
+        if let Some(timeout) = &stage.timeout {
+            if crate::pipeline_executor::parse_timeout(timeout).is_err() {
+                return Err(Error::Other(format!(
+                    "Stage '{}' has an invalid timeout '{}'",
+                    stage.name, timeout
+                )));
+            }
+        }
+
+        for secret in &stage.secrets {
+            if let Err(e) = crate::secrets::validate_secret_name(secret) {
+                return Err(Error::Other(format!("Stage '{}': {}", stage.name, e)));
+            }
+        }
+
+        // Validate condition expressions and the stages they read
+        let mut condition = stage.when.as_ref();
+        while let Some(when) = condition {
+            if let Some(expr) = &when.expr {
+                let expression = crate::ConditionExpression::parse(expr)
+                    .map_err(|e| Error::Other(format!("Stage '{}': {}", stage.name, e)))?;
+                for referenced in expression.stage_references() {
+                    if !all_stage_names.contains(referenced.as_str()) {
+                        return Err(Error::Other(format!(
+                            "Stage '{}' condition reads non-existent stage '{}'",
+                            stage.name, referenced
+                        )));
+                    }
+                    if referenced == stage.name {
+                        return Err(Error::Other(format!(
+                            "Stage '{}' condition cannot read its own result",
+                            stage.name
+                        )));
+                    }
+                }
+            }
+            condition = when.or.as_deref();
+        }
+
+        if let Some(backoff) = stage.retry.as_ref().and_then(|r| r.backoff.as_ref()) {
+            if crate::pipeline_executor::parse_timeout(backoff).is_err() {
+                return Err(Error::Other(format!(
+                    "Stage '{}' has an invalid retry backoff '{}'",
+                    stage.name, backoff
+                )));
+            }
+        }
+
+        if let Some(policy) = &stage.approval_timeout {
+            if !stage.requires_approval {
+                return Err(Error::Other(format!(
+                    "Stage '{}' has an approval_timeout but does not require approval",
+                    stage.name
+                )));
+            }
+            if crate::pipeline_executor::parse_timeout(&policy.after).is_err() {
+                return Err(Error::Other(format!(
+                    "Stage '{}' has an invalid approval_timeout '{}'",
+                    stage.name, policy.after
+                )));
+            }
+        }
+
         Ok(())
     }
 
@@ -252,102 +1181,439 @@ impl PipelineDefinition {
         let mut visited = HashSet::new();
         let mut rec_stack = HashSet::new();
 
-        for stage in &self.stages {
-            if !visited.contains(stage.name.as_str()) {
-                if self.has_cycle(&graph, stage.name.as_str(), &mut visited, &mut rec_stack) {
-                    return Err(Error::Other(format!(
-                        "Circular dependency detected involving stage '{}'",
-                        stage.name
-                    )));
-                }
-            }
-        }
+        for stage in &self.stages {
+            if !visited.contains(stage.name.as_str()) {
+                if self.has_cycle(&graph, stage.name.as_str(), &mut visited, &mut rec_stack) {
+                    return Err(Error::Other(format!(
+                        "Circular dependency detected involving stage '{}'",
+                        stage.name
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check for cycle in dependency graph using DFS
+    fn has_cycle<'a>(
+        &self,
+        graph: &HashMap<&'a str, Vec<&'a str>>,
+        node: &'a str,
+        visited: &mut HashSet<&'a str>,
+        rec_stack: &mut HashSet<&'a str>,
+    ) -> bool {
+        visited.insert(node);
+        rec_stack.insert(node);
+
+        if let Some(neighbors) = graph.get(node) {
+            for &neighbor in neighbors {
+                if !visited.contains(neighbor) {
+                    if self.has_cycle(graph, neighbor, visited, rec_stack) {
+                        return true;
+                    }
+                } else if rec_stack.contains(neighbor) {
+                    return true;
+                }
+            }
+        }
+
+        rec_stack.remove(node);
+        false
+    }
+
+    /// Convert pipeline definition to YAML string
+    pub fn to_yaml_string(&self) -> Result<String> {
+        serde_yaml::to_string(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize pipeline: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_parse_basic_pipeline() {
+        let yaml = r#"
+name: test-pipeline
+description: A test pipeline
+version: 1
+stages:
+  - name: build
+    agent: builder
+    task: Build the project
+"#;
+
+        let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
+        assert_eq!(pipeline.name, "test-pipeline");
+        assert_eq!(pipeline.description, "A test pipeline");
+        assert_eq!(pipeline.version, 1);
+        assert_eq!(pipeline.stages.len(), 1);
+        assert_eq!(pipeline.stages[0].name, "build");
+        assert_eq!(pipeline.stages[0].agent, "builder");
+        assert_eq!(pipeline.stages[0].task, "Build the project");
+    }
+
+    #[test]
+    fn test_parse_pipeline_with_triggers() {
+        let yaml = r#"
+name: ci-pipeline
+description: CI pipeline
+triggers:
+  - event: pull_request.opened
+    branches: [main, develop]
+  - event: push
+    branches: [main]
+stages:
+  - name: test
+    agent: tester
+    task: Run tests
+"#;
+
+        let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
+        assert_eq!(pipeline.triggers.len(), 2);
+        assert_eq!(pipeline.triggers[0].event, "pull_request.opened");
+        assert_eq!(pipeline.triggers[0].branches, vec!["main", "develop"]);
+        assert_eq!(pipeline.triggers[1].event, "push");
+        assert_eq!(pipeline.triggers[1].branches, vec!["main"]);
+    }
+
+    #[test]
+    fn test_trigger_matches_filtered_webhook_event() {
+        let yaml = r#"
+name: release-pipeline
+description: Release on label
+triggers:
+  - event: pull_request.labeled
+    branches: [main, release/*]
+    labels: [release]
+    variables:
+      pr_number: pull_request.number
+      head_sha: pull_request.head.sha
+      author: pull_request.user.login
+stages:
+  - name: release
+    agent: deployer
+    task: Release PR ${pr_number}
+"#;
+        let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
+        let trigger = &pipeline.triggers[0];
+        assert_eq!(trigger.labels, vec!["release"]);
+
+        let payload = |label: &str, base: &str| {
+            serde_json::json!({
+                "action": "labeled",
+                "label": { "name": label },
+                "pull_request": {
+                    "number": 42,
+                    "base": { "ref": base },
+                    "head": { "sha": "abc123" },
+                    "labels": [{ "name": "release" }, { "name": label }]
+                }
+            })
+        };
+
+        assert!(trigger.matches_event("pull_request.labeled", &payload("release", "main")));
+        assert!(trigger.matches_event("pull_request.labeled", &payload("release", "release/1.2")));
+        // Only the label that was just added counts
+        assert!(!trigger.matches_event("pull_request.labeled", &payload("docs", "main")));
+        assert!(!trigger.matches_event("pull_request.labeled", &payload("release", "develop")));
+        assert!(!trigger.matches_event("pull_request.opened", &payload("release", "main")));
+
+        let variables = trigger.payload_variables(&payload("release", "main"));
+        assert_eq!(variables.len(), 2);
+        assert_eq!(variables["pr_number"], "42");
+        assert_eq!(variables["head_sha"], "abc123");
+    }
+
+    #[test]
+    fn test_trigger_matches_event_type_push_and_merged() {
+        let trigger = |event: &str| TriggerDefinition {
+            event: event.to_string(),
+            cron: None,
+            branches: vec!["main".to_string()],
+            labels: Vec::new(),
+            variables: BTreeMap::new(),
+        };
+
+        let push = serde_json::json!({ "ref": "refs/heads/main" });
+        assert!(trigger("push").matches_event("push", &push));
+        assert!(!trigger("push").matches_event(
+            "push",
+            &serde_json::json!({ "ref": "refs/heads/feature" })
+        ));
+
+        let closed = |merged: bool| {
+            serde_json::json!({
+                "action": "closed",
+                "pull_request": { "merged": merged, "base": { "ref": "main" } }
+            })
+        };
+        assert!(trigger("pull_request.merged").matches_event("pull_request.closed", &closed(true)));
+        assert!(!trigger("pull_request.merged").matches_event("pull_request.closed", &closed(false)));
+        assert!(trigger("pull_request").matches_event("pull_request.closed", &closed(false)));
+    }
+
+    #[test]
+    fn test_parse_schedule_trigger() {
+        let yaml = r#"
+name: nightly
+description: Nightly build
+triggers:
+  - event: schedule
+    cron: "0 2 * * *"
+  - event: push
+    branches: [main]
+stages:
+  - name: build
+    agent: builder
+    task: Build
+"#;
+        let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
+        let schedule = &pipeline.triggers[0];
+        assert_eq!(schedule.cron.as_deref(), Some("0 2 * * *"));
+        assert_eq!(
+            schedule.scheduled_event().as_deref(),
+            Some("schedule:0 2 * * *")
+        );
+        assert!(!schedule.matches_event("schedule", &serde_json::json!({})));
+        assert!(pipeline.triggers[1].scheduled_event().is_none());
+    }
+
+    #[test]
+    fn test_validation_invalid_schedule_triggers() {
+        let pipeline = |trigger: &str| {
+            format!(
+                "name: test\ndescription: Test\ntriggers:\n{}\nstages:\n  - name: build\n    agent: builder\n    task: Build\n",
+                trigger
+            )
+        };
+
+        let err = PipelineDefinition::from_yaml_str(&pipeline("  - event: schedule")).unwrap_err();
+        assert!(err.to_string().contains("requires a cron expression"));
+
+        let err =
+            PipelineDefinition::from_yaml_str(&pipeline("  - event: push\n    cron: \"@daily\""))
+                .unwrap_err();
+        assert!(err.to_string().contains("its event is not 'schedule'"));
+
+        let err = PipelineDefinition::from_yaml_str(&pipeline(
+            "  - event: schedule\n    cron: \"not a cron\"",
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid cron expression"));
+
+        let err = PipelineDefinition::from_yaml_str(&pipeline(
+            "  - event: schedule\n    cron: \"@daily\"\n    labels: [release]",
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("cannot filter"));
+    }
+
+    #[test]
+    fn test_validation_trigger_variable_without_path() {
+        let yaml = r#"
+name: test
+description: Test
+triggers:
+  - event: push
+    variables:
+      sha: ""
+stages:
+  - name: build
+    agent: builder
+    task: Build
+"#;
+        let err = PipelineDefinition::from_yaml_str(yaml).unwrap_err();
+        assert!(err.to_string().contains("empty payload path"));
+    }
+
+    #[test]
+    fn test_parse_notifications() {
+        let yaml = r##"
+name: deploy
+description: Deploy
+notifications:
+  slack_channel: "#deployments"
+  email: [oncall@example.com]
+  webhook_url: https://hooks.example.com/pipelines
+  teams_webhook_url: https://example.webhook.office.com/webhookb2/abc
+stages:
+  - name: deploy
+    agent: deployer
+    task: Deploy
+"##;
+        let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
+        let notifications = pipeline.notifications.unwrap();
+        assert_eq!(notifications.slack_channel.as_deref(), Some("#deployments"));
+        assert_eq!(notifications.email, vec!["oncall@example.com"]);
+        assert_eq!(
+            notifications.teams_webhook_url.as_deref(),
+            Some("https://example.webhook.office.com/webhookb2/abc")
+        );
+        assert!(notifications.notifies(PipelineNotificationEvent::Failure));
+        assert!(notifications.notifies(PipelineNotificationEvent::Rollback));
+        assert!(notifications.notifies(PipelineNotificationEvent::Recovery));
+        assert!(!notifications.notifies(PipelineNotificationEvent::Approval));
+
+        let yaml = yaml.replace(
+            "  webhook_url: https://hooks.example.com/pipelines\n",
+            "  webhook_url: https://hooks.example.com/pipelines\n  on: [failure, approval]\n",
+        );
+        let notifications = PipelineDefinition::from_yaml_str(&yaml)
+            .unwrap()
+            .notifications
+            .unwrap();
+        assert!(notifications.notifies(PipelineNotificationEvent::Failure));
+        assert!(notifications.notifies(PipelineNotificationEvent::Approval));
+        assert!(!notifications.notifies(PipelineNotificationEvent::Recovery));
+    }
+
+    #[test]
+    fn test_parse_concurrency() {
+        let pipeline = |concurrency: &str| {
+            format!(
+                "name: deploy\ndescription: Deploy\nconcurrency:\n{}\nstages:\n  - name: deploy\n    agent: deployer\n    task: Deploy\n",
+                concurrency
+            )
+        };
 
-        Ok(())
+        let concurrency =
+            PipelineDefinition::from_yaml_str(&pipeline("  on_limit: cancel_in_progress"))
+                .unwrap()
+                .concurrency
+                .unwrap();
+        assert_eq!(concurrency.limit, 1);
+        assert_eq!(concurrency.on_limit, ConcurrencyAction::CancelInProgress);
+
+        let concurrency = PipelineDefinition::from_yaml_str(&pipeline("  limit: 3"))
+            .unwrap()
+            .concurrency
+            .unwrap();
+        assert_eq!(concurrency.limit, 3);
+        assert_eq!(concurrency.on_limit, ConcurrencyAction::Queue);
+
+        let err = PipelineDefinition::from_yaml_str(&pipeline("  limit: 0")).unwrap_err();
+        assert!(err.to_string().contains("must be at least 1"));
+
+        let err = PipelineDefinition::from_yaml_str(&pipeline("  on_limit: drop")).unwrap_err();
+        assert!(err.to_string().contains("unknown variant"));
     }
 
-    /// Check for cycle in dependency graph using DFS
-    fn has_cycle<'a>(
-        &self,
-        graph: &HashMap<&'a str, Vec<&'a str>>,
-        node: &'a str,
-        visited: &mut HashSet<&'a str>,
-        rec_stack: &mut HashSet<&'a str>,
-    ) -> bool {
-        visited.insert(node);
-        rec_stack.insert(node);
+    #[test]
+    fn test_parse_max_parallel() {
+        let pipeline = |max_parallel: &str| {
+            format!(
+                "name: ci\ndescription: CI\n{}stages:\n  - name: build\n    agent: builder\n    task: Build\n",
+                max_parallel
+            )
+        };
 
-        if let Some(neighbors) = graph.get(node) {
-            for &neighbor in neighbors {
-                if !visited.contains(neighbor) {
-                    if self.has_cycle(graph, neighbor, visited, rec_stack) {
-                        return true;
-                    }
-                } else if rec_stack.contains(neighbor) {
-                    return true;
-                }
-            }
-        }
+        let definition = PipelineDefinition::from_yaml_str(&pipeline("")).unwrap();
+        assert_eq!(definition.max_parallel, None);
 
-        rec_stack.remove(node);
-        false
-    }
+        let definition = PipelineDefinition::from_yaml_str(&pipeline("max_parallel: 2\n")).unwrap();
+        assert_eq!(definition.max_parallel, Some(2));
 
-    /// Convert pipeline definition to YAML string
-    pub fn to_yaml_string(&self) -> Result<String> {
-        serde_yaml::to_string(self)
-            .map_err(|e| Error::Other(format!("Failed to serialize pipeline: {}", e)))
+        let err = PipelineDefinition::from_yaml_str(&pipeline("max_parallel: 0\n")).unwrap_err();
+        assert!(err.to_string().contains("max_parallel must be at least 1"));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_validation_invalid_notifications() {
+        let pipeline = |notifications: &str| {
+            format!(
+                "name: test\ndescription: Test\nnotifications:\n{}\nstages:\n  - name: build\n    agent: builder\n    task: Build\n",
+                notifications
+            )
+        };
+
+        let err = PipelineDefinition::from_yaml_str(&pipeline("  on: [failure]")).unwrap_err();
+        assert!(err.to_string().contains("must set a slack_channel"));
+
+        let err = PipelineDefinition::from_yaml_str(&pipeline("  email: [oncall]")).unwrap_err();
+        assert!(err.to_string().contains("is not an email address"));
+
+        let err = PipelineDefinition::from_yaml_str(&pipeline("  webhook_url: ftp://example.com"))
+            .unwrap_err();
+        assert!(err.to_string().contains("must be an http(s) URL"));
+
+        let err = PipelineDefinition::from_yaml_str(&pipeline(
+            "  teams_webhook_url: http://example.webhook.office.com/webhookb2/abc",
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("must be an https URL"));
+
+        let err = PipelineDefinition::from_yaml_str(&pipeline(
+            "  slack_channel: \"#ci\"\n  on: [success]",
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown variant"));
+    }
 
     #[test]
-    fn test_parse_basic_pipeline() {
+    fn test_parse_stage_secrets() {
         let yaml = r#"
-name: test-pipeline
-description: A test pipeline
-version: 1
+name: deploy
+description: Deploy
 stages:
-  - name: build
-    agent: builder
-    task: Build the project
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    secrets: [DEPLOY_TOKEN, AWS_SECRET_ACCESS_KEY]
 "#;
-
         let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
-        assert_eq!(pipeline.name, "test-pipeline");
-        assert_eq!(pipeline.description, "A test pipeline");
-        assert_eq!(pipeline.version, 1);
-        assert_eq!(pipeline.stages.len(), 1);
-        assert_eq!(pipeline.stages[0].name, "build");
-        assert_eq!(pipeline.stages[0].agent, "builder");
-        assert_eq!(pipeline.stages[0].task, "Build the project");
+        assert_eq!(
+            pipeline.stages[0].secrets,
+            vec!["DEPLOY_TOKEN", "AWS_SECRET_ACCESS_KEY"]
+        );
+
+        let err = PipelineDefinition::from_yaml_str(&yaml.replace("DEPLOY_TOKEN", "deploy-token"))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Stage 'deploy': Invalid secret name 'deploy-token'"));
     }
 
     #[test]
-    fn test_parse_pipeline_with_triggers() {
+    fn test_parse_condition_expression() {
         let yaml = r#"
-name: ci-pipeline
-description: CI pipeline
-triggers:
-  - event: pull_request.opened
-    branches: [main, develop]
-  - event: push
-    branches: [main]
+name: deploy
+description: Deploy
 stages:
   - name: test
     agent: tester
-    task: Run tests
+    task: Test
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    depends_on: [test]
+    when:
+      expr: "stages.test.status == 'succeeded' && trigger.payload.ref == 'refs/heads/main'"
 "#;
-
         let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
-        assert_eq!(pipeline.triggers.len(), 2);
-        assert_eq!(pipeline.triggers[0].event, "pull_request.opened");
-        assert_eq!(pipeline.triggers[0].branches, vec!["main", "develop"]);
-        assert_eq!(pipeline.triggers[1].event, "push");
-        assert_eq!(pipeline.triggers[1].branches, vec!["main"]);
+        assert!(pipeline.stages[1]
+            .when
+            .as_ref()
+            .and_then(|when| when.expr.as_deref())
+            .unwrap()
+            .starts_with("stages.test.status"));
+
+        let err =
+            PipelineDefinition::from_yaml_str(&yaml.replace(" == 'succeeded'", " = 'succeeded'"))
+                .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Stage 'deploy': Invalid condition"));
+
+        let err = PipelineDefinition::from_yaml_str(&yaml.replace("stages.test", "stages.build"))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Stage 'deploy' condition reads non-existent stage 'build'"
+        );
     }
 
     #[test]
@@ -398,6 +1664,202 @@ stages:
         assert_eq!(pipeline.stages[2].rollback_to, Some("build".to_string()));
     }
 
+    #[test]
+    fn test_parse_stage_with_retry() {
+        let yaml = r#"
+name: test-pipeline
+description: Test pipeline
+stages:
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    timeout: 10m
+    retry:
+      count: 2
+      backoff: 30s
+  - name: smoke
+    agent: tester
+    task: Smoke test
+    retry:
+      count: 1
+"#;
+
+        let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
+        let retry = pipeline.stages[0].retry.as_ref().unwrap();
+        assert_eq!(pipeline.stages[0].timeout, Some("10m".to_string()));
+        assert_eq!(retry.count, 2);
+        assert_eq!(retry.backoff, Some("30s".to_string()));
+        assert_eq!(retry.max_attempts(), 3);
+        assert_eq!(pipeline.stages[1].retry.as_ref().unwrap().backoff, None);
+    }
+
+    #[test]
+    fn test_validation_invalid_timeout_and_backoff() {
+        let timeout_yaml = r#"
+name: test-pipeline
+description: Test pipeline
+stages:
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    timeout: soon
+"#;
+        let err = PipelineDefinition::from_yaml_str(timeout_yaml).unwrap_err();
+        assert!(err.to_string().contains("invalid timeout"));
+
+        let backoff_yaml = r#"
+name: test-pipeline
+description: Test pipeline
+stages:
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    retry:
+      count: 1
+      backoff: 30
+"#;
+        let err = PipelineDefinition::from_yaml_str(backoff_yaml).unwrap_err();
+        assert!(err.to_string().contains("invalid retry backoff"));
+    }
+
+    #[test]
+    fn test_check_yaml_unknown_key_location() {
+        let yaml = r#"name: test-pipeline
+description: Test pipeline
+stages:
+  - name: build
+    agent: builder
+    task: Build
+    timout: 10m
+"#;
+
+        let issues = PipelineDefinition::check_yaml(yaml).unwrap_err();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(7));
+        assert_eq!(issues[0].column, Some(5));
+        assert!(issues[0].message.contains("unknown field `timout`"));
+        assert!(!issues[0].message.contains("at line"));
+        assert!(issues[0].to_string().starts_with("7:5: "));
+    }
+
+    #[test]
+    fn test_check_yaml_reports_every_stage() {
+        let yaml = r#"name: test-pipeline
+description: Test pipeline
+stages:
+  - name: build
+    agent: builder
+    task: Build
+    depends_on: [prepare]
+  - name: test
+    agent: tester
+    task: Test
+    depends_on: [build]
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    requires_approval: true
+"#;
+
+        let issues = PipelineDefinition::check_yaml(yaml).unwrap_err();
+        let messages: Vec<String> = issues.iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "4:5: Stage 'build' depends on non-existent stage 'prepare'",
+                "12:5: Stage 'deploy' requires approval but has no approvers",
+                "8:5: Stage 'test' can never run: it waits on build which never completes",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_yaml_unreachable_cycle() {
+        let yaml = r#"name: test-pipeline
+description: Test pipeline
+stages:
+  - name: a
+    agent: builder
+    task: A
+    needs: [b]
+  - name: b
+    agent: builder
+    task: B
+    needs: [a]
+  - name: c
+    agent: builder
+    task: C
+"#;
+
+        let issues = PipelineDefinition::check_yaml(yaml).unwrap_err();
+        assert!(issues[0].message.contains("Circular dependency"));
+        assert_eq!(issues[1].line, Some(4));
+        assert!(issues[1].message.contains("Stage 'a' can never run"));
+        assert_eq!(issues[2].line, Some(8));
+        assert_eq!(issues.len(), 3);
+    }
+
+    #[test]
+    fn test_check_agents() {
+        let yaml = r#"name: test-pipeline
+description: Test pipeline
+stages:
+- name: build
+  agent: builder
+  task: Build
+- name: review
+  agent: code_reviewer
+  task: Review
+"#;
+
+        let definition = PipelineDefinition::check_yaml(yaml).unwrap();
+        let issues = definition.check_agents(yaml, |agent| agent == "code_reviewer");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].to_string(),
+            "4:3: Stage 'build' uses unknown agent type 'builder'"
+        );
+    }
+
+    #[test]
+    fn test_schema_lists_stage_fields() {
+        let schema: serde_json::Value = serde_json::from_str(PIPELINE_SCHEMA).unwrap();
+        let schema_fields: BTreeSet<&str> = schema["definitions"]["stage"]["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .filter(|k| *k != "needs")
+            .collect();
+
+        let yaml = r#"
+name: full
+agent: builder
+task: Build
+timeout: 10m
+retry: { count: 1 }
+on_failure: rollback
+rollback_to: other
+rollback: { agent: deployer, task: Undo }
+compensation: [{ action: custom }]
+requires_approval: true
+approvers: [lead]
+approval_timeout: { after: 1h }
+environment: staging
+secrets: [DEPLOY_TOKEN]
+depends_on: [other]
+parallel_with: other
+matrix: { os: [linux] }
+when: { branch: [main] }
+"#;
+        let stage: StageDefinition = serde_yaml::from_str(yaml).unwrap();
+        let stage = serde_json::to_value(&stage).unwrap();
+        let stage_fields: BTreeSet<&str> =
+            stage.as_object().unwrap().keys().map(|k| k.as_str()).collect();
+
+        assert_eq!(schema_fields, stage_fields);
+    }
+
     #[test]
     fn test_parse_stage_with_approval() {
         let yaml = r#"
@@ -440,6 +1902,150 @@ stages:
         assert_eq!(pipeline.stages[2].depends_on, vec!["test"]);
     }
 
+    #[test]
+    fn test_parse_stage_with_needs() {
+        let yaml = r#"
+name: needs-pipeline
+description: Pipeline using needs
+stages:
+  - name: build
+    agent: builder
+    task: Build
+  - name: lint
+    agent: linter
+    task: Lint
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    needs: [build, lint]
+"#;
+
+        let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
+        assert!(pipeline.stages[1].depends_on.is_empty());
+        assert_eq!(pipeline.stages[2].depends_on, vec!["build", "lint"]);
+    }
+
+    #[test]
+    fn test_parse_stage_with_matrix() {
+        let yaml = r#"
+name: matrix-pipeline
+description: Test across toolchains
+stages:
+  - name: test
+    agent: tester
+    task: Test on ${matrix.os} with ${matrix.rust-version}
+    matrix:
+      rust-version: [stable, beta]
+      os: [linux, macos]
+      exclude:
+        - os: macos
+          rust-version: beta
+"#;
+
+        let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
+        let matrix = pipeline.stages[0].matrix.as_ref().unwrap();
+        assert_eq!(matrix.parameters.len(), 2);
+        assert!(!matrix.all_repos);
+
+        let names: Vec<String> = matrix
+            .combinations()
+            .iter()
+            .map(|c| StageMatrix::instance_name("test", c))
+            .collect();
+        assert_eq!(
+            names,
+            vec!["test (linux, stable)", "test (linux, beta)", "test (macos, stable)"]
+        );
+    }
+
+    #[test]
+    fn test_stage_cache_key() {
+        let yaml = r#"
+name: cache
+description: Cache keys
+stages:
+  - name: build
+    agent: builder
+    task: Build ${target}
+    when:
+      variable:
+        a: "1"
+        b: "2"
+        c: "3"
+"#;
+        let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
+        let stage = &pipeline.stages[0];
+
+        let key = stage.cache_key("abc123", "Build app").unwrap();
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, stage.clone().cache_key("abc123", "Build app").unwrap());
+        assert_ne!(key, stage.cache_key("def456", "Build app").unwrap());
+        assert_ne!(key, stage.cache_key("abc123", "Build lib").unwrap());
+
+        let mut changed = stage.clone();
+        changed.agent = "other-builder".to_string();
+        assert_ne!(key, changed.cache_key("abc123", "Build app").unwrap());
+    }
+
+    #[test]
+    fn test_stage_matrix_combinations() {
+        assert!(StageMatrix::new().combinations().is_empty());
+
+        let matrix = StageMatrix::new()
+            .with_parameter("a", ["1", "2"])
+            .with_parameter("b", ["x", "y", "z"]);
+        assert_eq!(matrix.combinations().len(), 6);
+
+        let matrix = matrix.with_exclude([("b", "z")]);
+        let combinations = matrix.combinations();
+        assert_eq!(combinations.len(), 4);
+        assert!(combinations.iter().all(|c| c["b"] != "z"));
+    }
+
+    #[test]
+    fn test_validation_invalid_matrix() {
+        let empty_values = r#"
+name: bad
+description: Bad matrix
+stages:
+  - name: test
+    agent: tester
+    task: Test
+    matrix:
+      os: []
+"#;
+        let err = PipelineDefinition::from_yaml_str(empty_values).unwrap_err();
+        assert!(err.to_string().contains("has no values"));
+
+        let all_excluded = r#"
+name: bad
+description: Bad matrix
+stages:
+  - name: test
+    agent: tester
+    task: Test
+    matrix:
+      os: [linux]
+      exclude:
+        - os: linux
+"#;
+        let err = PipelineDefinition::from_yaml_str(all_excluded).unwrap_err();
+        assert!(err.to_string().contains("excludes every combination"));
+
+        let repos_only = r#"
+name: repos
+description: Every repository
+stages:
+  - name: test
+    agent: tester
+    task: Test ${matrix.repo}
+    matrix:
+      all_repos: true
+"#;
+        let pipeline = PipelineDefinition::from_yaml_str(repos_only).unwrap();
+        assert!(pipeline.stages[0].matrix.as_ref().unwrap().all_repos);
+    }
+
     #[test]
     fn test_parse_stage_with_parallel() {
         let yaml = r#"
@@ -735,6 +2341,45 @@ stages:
         assert!(result.unwrap_err().to_string().contains("rollback to itself"));
     }
 
+    #[test]
+    fn test_parse_rollback_hook() {
+        let yaml = r#"
+name: test-pipeline
+description: Test
+stages:
+  - name: deploy
+    agent: deployer
+    task: Deploy ${VERSION}
+    rollback:
+      agent: deployer
+      task: Redeploy the previous release
+  - name: verify
+    agent: tester
+    task: Verify deployment
+    depends_on: [deploy]
+    on_failure: rollback
+    rollback_to: deploy
+"#;
+
+        let pipeline = PipelineDefinition::from_yaml_str(yaml).unwrap();
+        assert_eq!(
+            pipeline.stages[0].rollback,
+            Some(RollbackHook {
+                agent: "deployer".to_string(),
+                task: "Redeploy the previous release".to_string(),
+            })
+        );
+        assert_eq!(pipeline.stages[1].rollback, None);
+
+        let result = PipelineDefinition::from_yaml_str(
+            &yaml.replace("task: Redeploy the previous release", "task: \"\""),
+        );
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("rollback needs both an agent and a task"));
+    }
+
     #[test]
     fn test_validation_approval_without_approvers() {
         let yaml = r#"
@@ -806,19 +2451,28 @@ stages:
             version: 1,
             triggers: vec![],
             variables: HashMap::new(),
+            notifications: None,
+            concurrency: None,
+            max_parallel: None,
             stages: vec![
                 StageDefinition {
                     name: "build".to_string(),
                     agent: "builder".to_string(),
                     task: "Build the project".to_string(),
                     timeout: None,
+                    retry: None,
                     on_failure: None,
                     rollback_to: None,
+                    rollback: None,
+                    compensation: Vec::new(),
                     requires_approval: false,
                     approvers: vec![],
+                    approval_timeout: None,
                     environment: None,
+                    secrets: Vec::new(),
                     depends_on: vec![],
                     parallel_with: None,
+                    matrix: None,
                     when: None,
                 },
             ],
@@ -0,0 +1,201 @@
+//! Pipeline Dry-Run Plans
+//!
+//! This module describes what a pipeline run would do without starting it:
+//! the evaluated stage conditions, which stages would run or be skipped and
+//! why, the agents that would be spawned with their resolved prompts, and an
+//! estimate of the tokens and cost the run would use.
+//!
+//! Plans are built by [`crate::PipelineExecutor::plan_run`], which assumes
+//! every stage that runs succeeds.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Input tokens assumed for an agent type without recorded usage
+pub const DEFAULT_AGENT_INPUT_TOKENS: u64 = 40_000;
+
+/// Output tokens assumed for an agent type without recorded usage
+pub const DEFAULT_AGENT_OUTPUT_TOKENS: u64 = 10_000;
+
+/// Estimated usage of one agent
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AgentTokenEstimate {
+    /// Estimated tokens (input and output)
+    pub tokens: u64,
+    /// Estimated cost in USD
+    pub cost_usd: f64,
+    /// Number of earlier agents of the same type the estimate averages over,
+    /// 0 when it is the default estimate
+    pub sampled_agents: u64,
+}
+
+/// What a stage would do in the run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PlannedStageAction {
+    /// The stage's agents would be spawned
+    Run,
+    /// The stage would be skipped
+    Skip { reason: String },
+    /// The stage would fail before spawning an agent
+    Fail { reason: String },
+    /// The stage would never start because a stage it depends on fails
+    Blocked { reason: String },
+}
+
+impl PlannedStageAction {
+    /// Short label for the action
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Run => "run",
+            Self::Skip { .. } => "skip",
+            Self::Fail { .. } => "fail",
+            Self::Blocked { .. } => "blocked",
+        }
+    }
+
+    /// Why the stage would not run, if it would not
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Run => None,
+            Self::Skip { reason } | Self::Fail { reason } | Self::Blocked { reason } => {
+                Some(reason)
+            }
+        }
+    }
+}
+
+/// Agent a stage would spawn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedAgent {
+    /// Stage name, or the matrix combination's instance name
+    pub instance: String,
+    /// Agent type
+    pub agent: String,
+    /// Task with variables substituted
+    pub prompt: String,
+    /// Estimated usage of the agent
+    pub estimate: AgentTokenEstimate,
+}
+
+/// Planned outcome of a stage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedStage {
+    /// Stage name
+    pub name: String,
+    /// Stages that must complete first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Summary of the stage's `when` condition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    /// What the stage would do
+    #[serde(flatten)]
+    pub action: PlannedStageAction,
+    /// Approvers the stage would wait for before spawning its agents
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approvers: Vec<String>,
+    /// Agents the stage would spawn, one per matrix combination
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agents: Vec<PlannedAgent>,
+}
+
+/// Fully resolved plan of a pipeline run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelinePlan {
+    /// Pipeline name
+    pub pipeline: String,
+    /// Event the run would be triggered by
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<String>,
+    /// Variables available to stage tasks and conditions
+    pub variables: BTreeMap<String, String>,
+    /// Stages in the order they would start
+    pub stages: Vec<PlannedStage>,
+}
+
+impl PipelinePlan {
+    /// Agents that would be spawned across all stages
+    pub fn agents(&self) -> impl Iterator<Item = &PlannedAgent> {
+        self.stages.iter().flat_map(|stage| stage.agents.iter())
+    }
+
+    /// Estimated tokens of all agents that would be spawned
+    pub fn estimated_tokens(&self) -> u64 {
+        self.agents().map(|agent| agent.estimate.tokens).sum()
+    }
+
+    /// Estimated cost in USD of all agents that would be spawned
+    pub fn estimated_cost_usd(&self) -> f64 {
+        self.agents().map(|agent| agent.estimate.cost_usd).sum()
+    }
+
+    /// Whether the run would fail
+    pub fn fails(&self) -> bool {
+        self.stages
+            .iter()
+            .any(|stage| matches!(stage.action, PlannedStageAction::Fail { .. }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(instance: &str, tokens: u64, cost_usd: f64) -> PlannedAgent {
+        PlannedAgent {
+            instance: instance.to_string(),
+            agent: "builder".to_string(),
+            prompt: "Build".to_string(),
+            estimate: AgentTokenEstimate {
+                tokens,
+                cost_usd,
+                sampled_agents: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_plan_totals() {
+        let plan = PipelinePlan {
+            pipeline: "ci".to_string(),
+            trigger: Some("manual".to_string()),
+            variables: BTreeMap::new(),
+            stages: vec![
+                PlannedStage {
+                    name: "build".to_string(),
+                    depends_on: vec![],
+                    condition: None,
+                    action: PlannedStageAction::Run,
+                    approvers: vec![],
+                    agents: vec![
+                        agent("build[os=linux]", 1_000, 0.5),
+                        agent("build[os=mac]", 2_000, 1.0),
+                    ],
+                },
+                PlannedStage {
+                    name: "docs".to_string(),
+                    depends_on: vec!["build".to_string()],
+                    condition: Some("branch in [main]".to_string()),
+                    action: PlannedStageAction::Skip {
+                        reason: "Branch condition not met".to_string(),
+                    },
+                    approvers: vec![],
+                    agents: vec![],
+                },
+            ],
+        };
+
+        assert_eq!(plan.estimated_tokens(), 3_000);
+        assert_eq!(plan.estimated_cost_usd(), 1.5);
+        assert!(!plan.fails());
+        assert_eq!(
+            plan.stages[1].action.reason(),
+            Some("Branch condition not met")
+        );
+
+        let json = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["stages"][1]["action"], "skip");
+        assert_eq!(json["stages"][1]["reason"], "Branch condition not met");
+    }
+}
@@ -1,9 +1,24 @@
-//! Built-in pipeline templates
+//! Pipeline templates
 //!
-//! This module provides pre-configured pipeline templates for common workflows.
+//! This module provides pre-configured pipeline templates for common workflows,
+//! and expands pipelines that build on a template with `extends:` or pull in
+//! another template's stages with `include:`.
+//!
+//! A template is a pipeline definition that may declare a top-level
+//! `parameters:` mapping of parameter name to default value (`~` when the
+//! parameter is required) and reference parameters as `${{ params.NAME }}`.
 
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_yaml::{Mapping, Value};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{Database, Error, Result};
+
+/// Matches a `${{ params.NAME }}` parameter reference
+static PARAM_REF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$\{\{\s*params\.([A-Za-z_][A-Za-z0-9_-]*)\s*\}\}").unwrap());
 
 /// A pipeline template with pre-configured YAML definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +31,397 @@ pub struct PipelineTemplate {
     pub yaml: String,
 }
 
+impl PipelineTemplate {
+    /// Create a template from its parameterized YAML definition
+    pub fn new(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        yaml: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            yaml: yaml.into(),
+        }
+    }
+
+    /// Check that the template parses and only references declared parameters
+    pub fn validate(&self) -> Result<()> {
+        let document = self.document()?;
+        let parameters = self.parameters()?;
+        let mut undeclared = Vec::new();
+        collect_param_refs(&Value::Mapping(document), &mut undeclared);
+        match undeclared
+            .into_iter()
+            .find(|name| !parameters.contains_key(name))
+        {
+            Some(name) => Err(Error::Other(format!(
+                "Template '{}' references undeclared parameter '{}'",
+                self.name, name
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Declared parameters with their default values (`None` when required)
+    pub fn parameters(&self) -> Result<BTreeMap<String, Option<String>>> {
+        let Some(declared) = self.document()?.remove("parameters") else {
+            return Ok(BTreeMap::new());
+        };
+        serde_yaml::from_value(declared).map_err(|e| {
+            Error::Other(format!(
+                "Template '{}' has invalid parameters: {}",
+                self.name, e
+            ))
+        })
+    }
+
+    /// Substitute parameter values into the template definition
+    pub fn render(&self, values: &BTreeMap<String, String>) -> Result<Mapping> {
+        let parameters = self.parameters()?;
+        if let Some(unknown) = values.keys().find(|name| !parameters.contains_key(*name)) {
+            return Err(Error::Other(format!(
+                "Template '{}' has no parameter '{}'",
+                self.name, unknown
+            )));
+        }
+
+        let mut resolved = HashMap::new();
+        for (name, default) in parameters {
+            let value = values.get(&name).cloned().or(default).ok_or_else(|| {
+                Error::Other(format!(
+                    "Template '{}' requires parameter '{}'",
+                    self.name, name
+                ))
+            })?;
+            resolved.insert(name, value);
+        }
+
+        let mut document = self.document()?;
+        document.remove("parameters");
+        let mut document = Value::Mapping(document);
+        substitute_params(&mut document, &resolved).map_err(|name| {
+            Error::Other(format!(
+                "Template '{}' references undeclared parameter '{}'",
+                self.name, name
+            ))
+        })?;
+
+        match document {
+            Value::Mapping(mapping) => Ok(mapping),
+            _ => unreachable!("substitution preserves the document shape"),
+        }
+    }
+
+    fn document(&self) -> Result<Mapping> {
+        match serde_yaml::from_str(&self.yaml) {
+            Ok(Value::Mapping(mapping)) => Ok(mapping),
+            Ok(_) => Err(Error::Other(format!(
+                "Template '{}' must be a YAML mapping",
+                self.name
+            ))),
+            Err(e) => Err(Error::Other(format!(
+                "Failed to parse template '{}': {}",
+                self.name, e
+            ))),
+        }
+    }
+}
+
+fn collect_param_refs(value: &Value, names: &mut Vec<String>) {
+    match value {
+        Value::String(text) => names.extend(
+            PARAM_REF
+                .captures_iter(text)
+                .map(|captures| captures[1].to_string()),
+        ),
+        Value::Sequence(items) => items
+            .iter()
+            .for_each(|item| collect_param_refs(item, names)),
+        Value::Mapping(mapping) => mapping
+            .values()
+            .for_each(|item| collect_param_refs(item, names)),
+        _ => {}
+    }
+}
+
+/// Replace parameter references in string values, returning the first undeclared name
+fn substitute_params(
+    value: &mut Value,
+    values: &HashMap<String, String>,
+) -> std::result::Result<(), String> {
+    match value {
+        Value::String(text) => {
+            let mut missing = None;
+            let substituted =
+                PARAM_REF.replace_all(text, |captures: &regex::Captures| {
+                    match values.get(&captures[1]) {
+                        Some(value) => value.clone(),
+                        None => {
+                            missing.get_or_insert_with(|| captures[1].to_string());
+                            captures[0].to_string()
+                        }
+                    }
+                });
+            if let Some(name) = missing {
+                return Err(name);
+            }
+            *text = substituted.into_owned();
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                substitute_params(item, values)?;
+            }
+        }
+        Value::Mapping(mapping) => {
+            for (_, item) in mapping.iter_mut() {
+                substitute_params(item, values)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Order of the top-level fields in an expanded pipeline definition
+const FIELD_ORDER: [&str; 9] = [
+    "name",
+    "description",
+    "version",
+    "triggers",
+    "variables",
+    "notifications",
+    "concurrency",
+    "max_parallel",
+    "stages",
+];
+
+/// Reference to a template from a pipeline's `extends:` or `include:`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateReference {
+    /// Template name
+    template: String,
+    /// Parameter values
+    #[serde(default)]
+    with: BTreeMap<String, String>,
+}
+
+/// Templates available to `extends:` and `include:`
+#[derive(Debug, Clone, Default)]
+pub struct TemplateLibrary {
+    templates: HashMap<String, PipelineTemplate>,
+}
+
+impl TemplateLibrary {
+    /// Library of the built-in templates
+    pub fn builtin() -> Self {
+        Self {
+            templates: get_templates(),
+        }
+    }
+
+    /// Built-in templates plus those stored in the database, which take precedence
+    pub async fn load(db: &Database) -> Result<Self> {
+        let mut library = Self::builtin();
+        for template in db.list_pipeline_templates().await? {
+            library = library.with_template(template);
+        }
+        Ok(library)
+    }
+
+    /// Add a template, replacing any template with the same name
+    pub fn with_template(mut self, template: PipelineTemplate) -> Self {
+        self.templates.insert(template.name.clone(), template);
+        self
+    }
+
+    /// Get a template by name
+    pub fn get(&self, name: &str) -> Option<&PipelineTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Expand `extends:` and `include:` into a self-contained pipeline definition
+    ///
+    /// The pipeline starts from the rendered `extends` template, appends the
+    /// stages of each `include` template, and then applies its own fields:
+    /// top-level fields replace the template's, variables are merged, and a
+    /// stage named like a template stage overrides that stage's fields.
+    /// YAML that uses neither key is returned unchanged.
+    pub fn expand(&self, yaml: &str) -> Result<String> {
+        let document = match serde_yaml::from_str::<Value>(yaml) {
+            Ok(Value::Mapping(mapping))
+                if mapping.contains_key("extends") || mapping.contains_key("include") =>
+            {
+                mapping
+            }
+            _ => return Ok(yaml.to_string()),
+        };
+
+        let mut resolved = self.resolve(document, &mut Vec::new())?;
+        let mut expanded = Mapping::new();
+        for key in FIELD_ORDER {
+            if let Some(value) = resolved.remove(key) {
+                expanded.insert(Value::from(key), value);
+            }
+        }
+        expanded.extend(resolved);
+
+        serde_yaml::to_string(&expanded)
+            .map_err(|e| Error::Other(format!("Failed to serialize expanded pipeline: {}", e)))
+    }
+
+    fn resolve(&self, mut document: Mapping, chain: &mut Vec<String>) -> Result<Mapping> {
+        let extends = document.remove("extends");
+        let include = document.remove("include");
+
+        let mut pipeline = match extends {
+            Some(reference) => self.instantiate(parse_reference(reference)?, chain)?,
+            None => Mapping::new(),
+        };
+
+        if let Some(include) = include {
+            let references: Vec<Value> = serde_yaml::from_value(include)
+                .map_err(|e| Error::Other(format!("Invalid template include: {}", e)))?;
+            for reference in references {
+                let reference = parse_reference(reference)?;
+                let name = reference.template.clone();
+                let included = self.instantiate(reference, chain)?;
+                include_stages(&mut pipeline, included, &name)?;
+            }
+        }
+
+        for (key, value) in document {
+            match key.as_str() {
+                Some("stages") => merge_stages(&mut pipeline, value),
+                Some("variables") => merge_mapping(&mut pipeline, key, value),
+                _ => {
+                    pipeline.insert(key, value);
+                }
+            }
+        }
+
+        Ok(pipeline)
+    }
+
+    fn instantiate(
+        &self,
+        reference: TemplateReference,
+        chain: &mut Vec<String>,
+    ) -> Result<Mapping> {
+        if chain.contains(&reference.template) {
+            return Err(Error::Other(format!(
+                "Pipeline template '{}' extends itself: {} -> {}",
+                reference.template,
+                chain.join(" -> "),
+                reference.template
+            )));
+        }
+        let template = self.get(&reference.template).ok_or_else(|| {
+            Error::Other(format!(
+                "Unknown pipeline template '{}'",
+                reference.template
+            ))
+        })?;
+
+        let rendered = template.render(&reference.with)?;
+        chain.push(reference.template);
+        let resolved = self.resolve(rendered, chain);
+        chain.pop();
+        resolved
+    }
+}
+
+fn parse_reference(value: Value) -> Result<TemplateReference> {
+    serde_yaml::from_value(value)
+        .map_err(|e| Error::Other(format!("Invalid template reference: {}", e)))
+}
+
+fn stage_name(stage: &Value) -> Option<&str> {
+    stage.get("name").and_then(Value::as_str)
+}
+
+fn stages_mut(pipeline: &mut Mapping) -> &mut Vec<Value> {
+    let stages = pipeline
+        .entry(Value::from("stages"))
+        .or_insert_with(|| Value::Sequence(Vec::new()));
+    if !stages.is_sequence() {
+        *stages = Value::Sequence(Vec::new());
+    }
+    stages.as_sequence_mut().unwrap()
+}
+
+/// Append an included template's stages, keeping variables already set
+fn include_stages(pipeline: &mut Mapping, mut included: Mapping, template: &str) -> Result<()> {
+    if let Some(Value::Mapping(variables)) = included.remove("variables") {
+        let existing = pipeline
+            .entry(Value::from("variables"))
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+        if let Value::Mapping(existing) = existing {
+            for (name, value) in variables {
+                existing.entry(name).or_insert(value);
+            }
+        }
+    }
+
+    let Some(Value::Sequence(new_stages)) = included.remove("stages") else {
+        return Ok(());
+    };
+    let stages = stages_mut(pipeline);
+    for stage in new_stages {
+        if let Some(name) = stage_name(&stage) {
+            if stages
+                .iter()
+                .any(|existing| stage_name(existing) == Some(name))
+            {
+                return Err(Error::Other(format!(
+                    "Stage '{}' from template '{}' conflicts with an existing stage",
+                    name, template
+                )));
+            }
+        }
+        stages.push(stage);
+    }
+    Ok(())
+}
+
+/// Override template stages by name and append the pipeline's other stages
+fn merge_stages(pipeline: &mut Mapping, value: Value) {
+    let Value::Sequence(overrides) = value else {
+        pipeline.insert(Value::from("stages"), value);
+        return;
+    };
+    let stages = stages_mut(pipeline);
+    for stage in overrides {
+        let existing = stage_name(&stage).and_then(|name| {
+            stages
+                .iter_mut()
+                .find(|existing| stage_name(existing) == Some(name))
+        });
+        match (existing, stage) {
+            (Some(Value::Mapping(existing)), Value::Mapping(fields)) => {
+                for (key, value) in fields {
+                    existing.insert(key, value);
+                }
+            }
+            (_, stage) => stages.push(stage),
+        }
+    }
+}
+
+fn merge_mapping(pipeline: &mut Mapping, key: Value, value: Value) {
+    match (pipeline.get_mut(&key), value) {
+        (Some(Value::Mapping(existing)), Value::Mapping(fields)) => {
+            for (name, value) in fields {
+                existing.insert(name, value);
+            }
+        }
+        (_, value) => {
+            pipeline.insert(key, value);
+        }
+    }
+}
+
 /// Get all built-in pipeline templates
 pub fn get_templates() -> HashMap<String, PipelineTemplate> {
     let templates = HashMap::new();
@@ -247,4 +653,147 @@ mod tests {
             assert_eq!(&template.name, key, "Template key '{}' doesn't match template.name '{}'", key, template.name);
         }
     }
+
+    const RUST_CI: &str = r#"
+parameters:
+  package: ~
+  toolchain: stable
+name: ${{ params.package }}-ci
+description: CI for ${{ params.package }}
+variables:
+  toolchain: ${{ params.toolchain }}
+  profile: debug
+stages:
+  - name: lint
+    agent: linter
+    task: Run clippy on ${{ params.package }}
+  - name: test
+    agent: tester
+    task: Run cargo test -p ${{ params.package }}
+    depends_on: [lint]
+"#;
+
+    fn library() -> TemplateLibrary {
+        TemplateLibrary::builtin()
+            .with_template(PipelineTemplate::new("rust-ci", "Rust CI", RUST_CI))
+            .with_template(PipelineTemplate::new(
+                "audit",
+                "Dependency audit",
+                r#"
+name: audit
+description: Audit
+variables:
+  profile: release
+stages:
+  - name: audit
+    agent: dependency-scanner
+    task: Audit dependencies
+"#,
+            ))
+    }
+
+    fn expand(yaml: &str) -> crate::Result<crate::PipelineDefinition> {
+        crate::PipelineDefinition::from_yaml_str(&library().expand(yaml)?)
+    }
+
+    #[test]
+    fn test_extends_renders_parameters_and_overrides_stages() {
+        let definition = expand(
+            r#"
+name: api-ci
+extends:
+  template: rust-ci
+  with:
+    package: api
+variables:
+  profile: release
+stages:
+  - name: test
+    timeout: 30m
+  - name: build
+    agent: builder
+    task: Build api
+    depends_on: [test]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(definition.name, "api-ci");
+        assert_eq!(definition.description, "CI for api");
+        assert_eq!(definition.variables["toolchain"], "stable");
+        assert_eq!(definition.variables["profile"], "release");
+
+        let names: Vec<_> = definition.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["lint", "test", "build"]);
+        assert_eq!(definition.stages[0].task, "Run clippy on api");
+        assert_eq!(definition.stages[1].task, "Run cargo test -p api");
+        assert_eq!(definition.stages[1].timeout.as_deref(), Some("30m"));
+    }
+
+    #[test]
+    fn test_include_appends_template_stages() {
+        let definition = expand(
+            r#"
+name: api-ci
+extends:
+  template: rust-ci
+  with: { package: api }
+include:
+  - template: audit
+"#,
+        )
+        .unwrap();
+
+        let names: Vec<_> = definition.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["lint", "test", "audit"]);
+        // Included variables do not override the pipeline's own
+        assert_eq!(definition.variables["profile"], "debug");
+
+        let conflict = library().expand(
+            r#"
+name: twice
+description: Twice
+include:
+  - template: audit
+  - template: audit
+"#,
+        );
+        assert!(conflict.unwrap_err().to_string().contains("conflicts"));
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let library = library();
+
+        let missing = library.expand("name: x\nextends: { template: rust-ci }\n");
+        assert!(missing
+            .unwrap_err()
+            .to_string()
+            .contains("requires parameter 'package'"));
+
+        let unknown_param =
+            library.expand("name: x\nextends: { template: audit, with: { os: linux } }\n");
+        assert!(unknown_param
+            .unwrap_err()
+            .to_string()
+            .contains("has no parameter 'os'"));
+
+        let unknown = library.expand("name: x\nextends: { template: nope }\n");
+        assert!(unknown
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown pipeline template 'nope'"));
+
+        let cyclic = TemplateLibrary::default()
+            .with_template(PipelineTemplate::new("a", "", "extends: { template: b }\n"))
+            .with_template(PipelineTemplate::new("b", "", "extends: { template: a }\n"))
+            .expand("name: x\nextends: { template: a }\n");
+        assert!(cyclic.unwrap_err().to_string().contains("extends itself"));
+    }
+
+    #[test]
+    fn test_expand_leaves_plain_pipelines_unchanged() {
+        let yaml = get_template("ci").unwrap().yaml;
+        assert_eq!(library().expand(&yaml).unwrap(), yaml);
+    }
 }
@@ -19,6 +19,8 @@ pub enum PrStatus {
     Reviewing,
     /// PR needs fixes
     Fixing,
+    /// PR is waiting in the GitHub merge queue
+    Enqueued,
     /// PR is being merged
     Merging,
     /// PR has been merged
@@ -38,6 +40,7 @@ impl PrStatus {
             PrStatus::Open => "open",
             PrStatus::Reviewing => "reviewing",
             PrStatus::Fixing => "fixing",
+            PrStatus::Enqueued => "enqueued",
             PrStatus::Merging => "merging",
             PrStatus::Merged => "merged",
             PrStatus::Failed => "failed",
@@ -53,6 +56,7 @@ impl PrStatus {
             "open" => Ok(PrStatus::Open),
             "reviewing" => Ok(PrStatus::Reviewing),
             "fixing" => Ok(PrStatus::Fixing),
+            "enqueued" => Ok(PrStatus::Enqueued),
             "merging" => Ok(PrStatus::Merging),
             "merged" => Ok(PrStatus::Merged),
             "failed" => Ok(PrStatus::Failed),
@@ -140,6 +144,8 @@ pub struct PullRequest {
     pub agent_id: Option<Uuid>,
     /// Error message if failed
     pub error_message: Option<String>,
+    /// Position in the GitHub merge queue, while enqueued
+    pub merge_queue_position: Option<i32>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -164,6 +170,7 @@ impl PullRequest {
             merge_strategy: MergeStrategy::default(),
             agent_id: None,
             error_message: None,
+            merge_queue_position: None,
             created_at: now,
             updated_at: now,
             merged_at: None,
@@ -231,6 +238,7 @@ mod tests {
         assert_eq!(PrStatus::from_str("queued").unwrap(), PrStatus::Queued);
         assert_eq!(PrStatus::from_str("open").unwrap(), PrStatus::Open);
         assert_eq!(PrStatus::from_str("merged").unwrap(), PrStatus::Merged);
+        assert_eq!(PrStatus::from_str("enqueued").unwrap(), PrStatus::Enqueued);
         assert!(PrStatus::from_str("invalid").is_err());
     }
 
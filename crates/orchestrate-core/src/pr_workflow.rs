@@ -4,14 +4,19 @@
 //!
 //! Manages the complete PR lifecycle in autonomous mode:
 //! - Create PR with structured description
+//! - Label PRs by size, touched areas, and risk
 //! - Monitor CI checks
 //! - Handle reviews and comments
 //! - Manage merge conflicts
-//! - Execute merge and cleanup
+//! - Execute merge and cleanup, directly or through a GitHub merge queue
+//! - Drive all of the above on GitHub, GitLab, or Bitbucket through [`PrProvider`]
 
+use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
+use crate::condition_evaluator::ConditionEvaluator;
 use crate::work_evaluation::{CiCheckResult, CiStatus, ReviewVerdict};
 
 /// PR workflow state
@@ -32,6 +37,8 @@ pub enum PrWorkflowState {
     ResolvingConflicts,
     /// Ready to merge
     ReadyToMerge,
+    /// Waiting in the merge queue
+    InMergeQueue,
     /// Merging PR
     Merging,
     /// Cleaning up after merge
@@ -52,6 +59,7 @@ impl PrWorkflowState {
             Self::FixingReview => "fixing_review",
             Self::ResolvingConflicts => "resolving_conflicts",
             Self::ReadyToMerge => "ready_to_merge",
+            Self::InMergeQueue => "in_merge_queue",
             Self::Merging => "merging",
             Self::CleaningUp => "cleaning_up",
             Self::Completed => "completed",
@@ -83,6 +91,7 @@ impl std::str::FromStr for PrWorkflowState {
             "fixing_review" => Ok(Self::FixingReview),
             "resolving_conflicts" => Ok(Self::ResolvingConflicts),
             "ready_to_merge" => Ok(Self::ReadyToMerge),
+            "in_merge_queue" => Ok(Self::InMergeQueue),
             "merging" => Ok(Self::Merging),
             "cleaning_up" => Ok(Self::CleaningUp),
             "completed" => Ok(Self::Completed),
@@ -351,6 +360,15 @@ pub struct PrWorkflowContext {
     pub merge_method: MergeMethod,
     /// PR URL
     pub url: Option<String>,
+    /// Merge queue status, once enqueued
+    #[serde(default)]
+    pub merge_queue: Option<MergeQueueStatus>,
+    /// Labels applied to the PR
+    #[serde(default)]
+    pub labels: Option<PrLabels>,
+    /// Current or last merge conflict and its resolution attempts
+    #[serde(default)]
+    pub conflict: Option<ConflictInfo>,
     /// Created at
     pub created_at: DateTime<Utc>,
     /// Updated at
@@ -384,6 +402,9 @@ impl PrWorkflowContext {
             has_conflicts: false,
             merge_method: MergeMethod::default(),
             url: None,
+            merge_queue: None,
+            labels: None,
+            conflict: None,
             created_at: now,
             updated_at: now,
             completed_at: None,
@@ -427,6 +448,21 @@ impl PrWorkflowContext {
         self.updated_at = Utc::now();
     }
 
+    /// Update CI status for the head commit from the shared CI status cache,
+    /// fetching checks from the provider only when the cache has none fresh
+    pub async fn refresh_ci_status<F, Fut>(&mut self, head_sha: &str, fetch: F) -> crate::Result<()>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = crate::Result<Vec<CiCheckResult>>>,
+    {
+        let status = crate::ci_integration::ci_status_cache()
+            .get_or_fetch(head_sha, fetch)
+            .await?;
+        self.ci_status = Some(status);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
     pub fn update_review(&mut self, verdict: ReviewVerdict, iteration: u32) {
         self.review_verdict = Some(verdict);
         self.review_iterations = iteration;
@@ -434,10 +470,64 @@ impl PrWorkflowContext {
     }
 
     pub fn set_has_conflicts(&mut self, has_conflicts: bool) {
+        let open_conflict = self.conflict.as_mut().filter(|c| c.resolved_at.is_none());
+        match open_conflict {
+            None if has_conflicts => self.conflict = Some(ConflictInfo::new(Vec::new())),
+            Some(conflict) if !has_conflicts => conflict.resolved_at = Some(Utc::now()),
+            _ => {}
+        }
         self.has_conflicts = has_conflicts;
         self.updated_at = Utc::now();
     }
 
+    /// Record an attempt to resolve the current conflict, returning the attempt count
+    pub fn record_conflict_attempt(&mut self, strategy: ConflictResolutionStrategy) -> u32 {
+        let conflict = self
+            .conflict
+            .get_or_insert_with(|| ConflictInfo::new(Vec::new()));
+        conflict.record_attempt(strategy);
+        self.updated_at = Utc::now();
+        conflict.attempts
+    }
+
+    /// Resolution attempts made for the current conflict
+    pub fn conflict_attempts(&self) -> u32 {
+        self.conflict
+            .as_ref()
+            .filter(|c| c.resolved_at.is_none())
+            .map(|c| c.attempts)
+            .unwrap_or(0)
+    }
+
+    /// Record the labels applied to the PR
+    pub fn set_labels(&mut self, labels: PrLabels) {
+        self.labels = Some(labels);
+        self.updated_at = Utc::now();
+    }
+
+    /// Record the PR's position in the merge queue
+    pub fn update_merge_queue_position(&mut self, position: Option<u32>) {
+        self.merge_queue = Some(MergeQueueStatus::Queued { position });
+        self.updated_at = Utc::now();
+    }
+
+    /// Record the PR leaving the merge queue
+    pub fn dequeue(&mut self, reason: DequeueReason) {
+        if reason == DequeueReason::MergeConflict {
+            self.has_conflicts = true;
+        }
+        self.merge_queue = Some(MergeQueueStatus::Dequeued { reason });
+        self.updated_at = Utc::now();
+    }
+
+    /// Position in the merge queue, while queued
+    pub fn merge_queue_position(&self) -> Option<u32> {
+        match &self.merge_queue {
+            Some(MergeQueueStatus::Queued { position }) => *position,
+            _ => None,
+        }
+    }
+
     pub fn duration(&self) -> Duration {
         let end = self.completed_at.unwrap_or_else(Utc::now);
         end - self.created_at
@@ -466,6 +556,12 @@ pub struct ConflictInfo {
     pub strategy: Option<ConflictResolutionStrategy>,
     /// Resolved at
     pub resolved_at: Option<DateTime<Utc>>,
+    /// Resolution attempts made so far
+    #[serde(default)]
+    pub attempts: u32,
+    /// Why the last attempt failed
+    #[serde(default)]
+    pub last_error: Option<String>,
 }
 
 impl ConflictInfo {
@@ -476,9 +572,23 @@ impl ConflictInfo {
             resolution_attempted: false,
             strategy: None,
             resolved_at: None,
+            attempts: 0,
+            last_error: None,
         }
     }
 
+    /// Record the start of a resolution attempt
+    pub fn record_attempt(&mut self, strategy: ConflictResolutionStrategy) {
+        self.resolution_attempted = true;
+        self.strategy = Some(strategy);
+        self.attempts += 1;
+    }
+
+    /// Record why a resolution attempt failed
+    pub fn record_failure(&mut self, error: impl Into<String>) {
+        self.last_error = Some(error.into());
+    }
+
     pub fn mark_resolved(&mut self, strategy: ConflictResolutionStrategy) {
         self.resolution_attempted = true;
         self.strategy = Some(strategy);
@@ -514,6 +624,181 @@ impl ConflictResolutionStrategy {
     }
 }
 
+/// Why GitHub removed a PR from the merge queue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DequeueReason {
+    /// The PR was merged
+    Merged,
+    /// Required checks failed or timed out on the merge group
+    ChecksFailed,
+    /// The PR conflicts with PRs ahead of it or with the base branch
+    MergeConflict,
+    /// Someone removed the PR from the queue
+    Manual,
+    /// Any other reason, as reported by GitHub
+    Other(String),
+}
+
+impl DequeueReason {
+    /// Parse the `reason` of a `pull_request.dequeued` webhook
+    pub fn from_github(reason: &str) -> Self {
+        match reason.to_ascii_uppercase().as_str() {
+            "MERGE" | "MERGED" | "ALREADY_MERGED" => Self::Merged,
+            "CI_FAILURE" | "CI_TIMEOUT" => Self::ChecksFailed,
+            "MERGE_CONFLICT" => Self::MergeConflict,
+            "MANUAL" => Self::Manual,
+            _ => Self::Other(reason.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Merged => "merged",
+            Self::ChecksFailed => "checks_failed",
+            Self::MergeConflict => "merge_conflict",
+            Self::Manual => "manual",
+            Self::Other(reason) => reason,
+        }
+    }
+}
+
+/// Where a PR stands in the merge queue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum MergeQueueStatus {
+    /// Waiting in the queue; position 1 is next to merge
+    Queued { position: Option<u32> },
+    /// Removed from the queue
+    Dequeued { reason: DequeueReason },
+}
+
+/// A file changed by a PR, with its diff size
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangedFile {
+    pub path: String,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+/// PR size by number of changed lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrSize {
+    Xs,
+    S,
+    M,
+    L,
+    Xl,
+}
+
+impl PrSize {
+    /// Classify a diff of `changed_lines` additions plus deletions
+    pub fn from_changed_lines(changed_lines: u32) -> Self {
+        match changed_lines {
+            0..=9 => Self::Xs,
+            10..=99 => Self::S,
+            100..=299 => Self::M,
+            300..=999 => Self::L,
+            _ => Self::Xl,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Xs => "XS",
+            Self::S => "S",
+            Self::M => "M",
+            Self::L => "L",
+            Self::Xl => "XL",
+        }
+    }
+}
+
+/// How risky a PR is to merge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrRisk {
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+impl PrRisk {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+/// Maps changed paths to an area, and optionally a minimum risk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathRule {
+    /// Glob pattern, e.g. `migrations/**` or `*.md`
+    pub pattern: String,
+    /// Area label for matching files
+    pub area: String,
+    /// Risk of any PR touching matching files
+    #[serde(default)]
+    pub risk: Option<PrRisk>,
+}
+
+/// Configuration for PR labeling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrLabelConfig {
+    /// Label PRs after they are created
+    pub enabled: bool,
+    /// Rules mapping changed paths to areas
+    pub path_rules: Vec<PathRule>,
+    /// Approvals required per risk level
+    pub required_approvals: BTreeMap<PrRisk, u32>,
+}
+
+impl Default for PrLabelConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path_rules: Vec::new(),
+            required_approvals: BTreeMap::from([
+                (PrRisk::Low, 1),
+                (PrRisk::Medium, 1),
+                (PrRisk::High, 2),
+            ]),
+        }
+    }
+}
+
+/// Labels computed for a PR
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrLabels {
+    pub size: PrSize,
+    /// Areas touched, from the path rules
+    pub areas: Vec<String>,
+    pub risk: PrRisk,
+}
+
+impl PrLabels {
+    /// Prefixes of the labels managed by orchestrate
+    pub const PREFIXES: [&'static str; 3] = ["size/", "area/", "risk/"];
+
+    /// Label names, e.g. `size/M`, `area/database`, `risk/high`
+    pub fn to_labels(&self) -> Vec<String> {
+        let mut labels = vec![format!("size/{}", self.size.as_str())];
+        labels.extend(self.areas.iter().map(|a| format!("area/{}", a)));
+        labels.push(format!("risk/{}", self.risk.as_str()));
+        labels
+    }
+
+    /// Whether a label is one orchestrate manages
+    pub fn is_managed(label: &str) -> bool {
+        Self::PREFIXES.iter().any(|p| label.starts_with(p))
+    }
+}
+
 /// Configuration for PR workflow
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrWorkflowConfig {
@@ -535,6 +820,12 @@ pub struct PrWorkflowConfig {
     pub require_ci_pass: bool,
     /// Require review approval before merge
     pub require_review_approval: bool,
+    /// Add ready PRs to the base branch's merge queue instead of merging
+    #[serde(default)]
+    pub use_merge_queue: bool,
+    /// Size, area, and risk labeling
+    #[serde(default)]
+    pub labeling: PrLabelConfig,
 }
 
 impl Default for PrWorkflowConfig {
@@ -549,10 +840,74 @@ impl Default for PrWorkflowConfig {
             max_conflict_resolution_attempts: 3,
             require_ci_pass: true,
             require_review_approval: true,
+            use_merge_queue: false,
+            labeling: PrLabelConfig::default(),
+        }
+    }
+}
+
+/// A PR or MR just opened on a provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenedPr {
+    /// PR number, or project-scoped MR number on GitLab
+    pub number: u64,
+    pub url: Option<String>,
+}
+
+/// Approval state of a PR
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrApprovals {
+    /// Whether the PR has the approvals the provider requires
+    pub approved: bool,
+    /// Whether a reviewer requested changes
+    pub changes_requested: bool,
+    /// Usernames or display names of the approvers
+    pub approvers: Vec<String>,
+}
+
+impl PrApprovals {
+    /// Review verdict, requested changes winning over approvals
+    pub fn verdict(&self) -> ReviewVerdict {
+        if self.changes_requested {
+            ReviewVerdict::ChangesRequested
+        } else if self.approved {
+            ReviewVerdict::Approved
+        } else {
+            ReviewVerdict::Pending
         }
     }
 }
 
+/// Hosting provider the PR workflow opens, inspects, and merges PRs on
+///
+/// Implemented by the GitHub, GitLab, and Bitbucket clients.
+#[async_trait]
+pub trait PrProvider: Send + Sync {
+    /// Provider name, as in `RepoProvider::as_str`
+    fn name(&self) -> &str;
+
+    /// Open a PR from `head` into `base`
+    async fn create_pr(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> crate::Result<OpenedPr>;
+
+    /// Approval state of a PR
+    async fn get_approvals(&self, number: u64) -> crate::Result<PrApprovals>;
+
+    /// CI checks or pipeline jobs of a PR's head commit
+    async fn get_ci_checks(&self, number: u64) -> crate::Result<Vec<CiCheckResult>>;
+
+    /// Whether a PR conflicts with its base branch
+    async fn has_conflicts(&self, number: u64) -> crate::Result<bool>;
+
+    /// Merge a PR
+    async fn merge_pr(&self, number: u64, method: MergeMethod) -> crate::Result<()>;
+}
+
 /// PR workflow manager
 #[derive(Debug, Clone)]
 pub struct PrWorkflowManager {
@@ -652,17 +1007,29 @@ impl PrWorkflowManager {
             }
             PrWorkflowState::ReadyToMerge => {
                 if self.config.auto_merge {
+                    if self.config.use_merge_queue {
+                        return Some(PrWorkflowState::InMergeQueue);
+                    }
                     return Some(PrWorkflowState::Merging);
                 }
                 // Wait for manual merge
                 None
             }
+            PrWorkflowState::InMergeQueue => match &context.merge_queue {
+                Some(MergeQueueStatus::Dequeued { reason }) => match reason {
+                    DequeueReason::Merged => Some(self.after_merge_state()),
+                    DequeueReason::ChecksFailed => Some(PrWorkflowState::FixingCi),
+                    DequeueReason::MergeConflict => Some(PrWorkflowState::ResolvingConflicts),
+                    DequeueReason::Manual | DequeueReason::Other(_) => {
+                        Some(PrWorkflowState::Failed)
+                    }
+                },
+                // Still queued
+                _ => None,
+            },
             PrWorkflowState::Merging => {
                 // Merge complete, cleanup
-                if self.config.cleanup_worktree || self.config.delete_branch_after_merge {
-                    return Some(PrWorkflowState::CleaningUp);
-                }
-                return Some(PrWorkflowState::Completed);
+                Some(self.after_merge_state())
             }
             PrWorkflowState::CleaningUp => {
                 return Some(PrWorkflowState::Completed);
@@ -674,6 +1041,15 @@ impl PrWorkflowManager {
         }
     }
 
+    /// State after the PR is merged
+    fn after_merge_state(&self) -> PrWorkflowState {
+        if self.config.cleanup_worktree || self.config.delete_branch_after_merge {
+            PrWorkflowState::CleaningUp
+        } else {
+            PrWorkflowState::Completed
+        }
+    }
+
     /// Check if PR is ready to merge
     pub fn is_ready_to_merge(&self, context: &PrWorkflowContext) -> bool {
         // Must have CI passing if required
@@ -702,6 +1078,120 @@ impl PrWorkflowManager {
         true
     }
 
+    /// Open the PR on the provider and start waiting for CI
+    pub async fn open_pr(
+        &self,
+        provider: &dyn PrProvider,
+        context: &mut PrWorkflowContext,
+        description: &PrDescription,
+    ) -> crate::Result<()> {
+        let pr = provider
+            .create_pr(
+                &context.head_branch,
+                &context.base_branch,
+                &description.title,
+                &description.to_markdown(),
+            )
+            .await?;
+        context.pr_number = pr.number;
+        context.url = pr.url;
+        context.merge_method = self.config.default_merge_method;
+        context.transition(
+            PrWorkflowState::AwaitingCi,
+            format!("Opened on {}", provider.name()),
+        );
+        Ok(())
+    }
+
+    /// Refresh CI status, review verdict, and conflicts from the provider,
+    /// returning the state the PR should move to next
+    pub async fn sync_pr(
+        &self,
+        provider: &dyn PrProvider,
+        context: &mut PrWorkflowContext,
+    ) -> crate::Result<Option<PrWorkflowState>> {
+        let number = context.pr_number;
+        context.update_ci_status(&provider.get_ci_checks(number).await?);
+        let approvals = provider.get_approvals(number).await?;
+        context.update_review(approvals.verdict(), context.review_iterations);
+        context.set_has_conflicts(provider.has_conflicts(number).await?);
+        Ok(self.determine_next_state(context))
+    }
+
+    /// Merge a PR that is ready to merge, failing the workflow if the
+    /// provider rejects the merge
+    pub async fn merge_pr(
+        &self,
+        provider: &dyn PrProvider,
+        context: &mut PrWorkflowContext,
+    ) -> crate::Result<()> {
+        if !self.is_ready_to_merge(context) {
+            return Err(crate::Error::Other(format!(
+                "PR #{} is not ready to merge",
+                context.pr_number
+            )));
+        }
+
+        context.transition(PrWorkflowState::Merging, "Merging");
+        if let Err(e) = provider
+            .merge_pr(context.pr_number, context.merge_method)
+            .await
+        {
+            context.transition(PrWorkflowState::Failed, format!("Merge failed: {}", e));
+            return Err(e);
+        }
+        context.transition(self.after_merge_state(), "Merged");
+        Ok(())
+    }
+
+    /// Compute size, area, and risk labels for a PR's changed files
+    ///
+    /// Risk is the highest of the matched path rules' risk and the size
+    /// risk: large PRs are medium risk and extra large ones high.
+    pub fn classify_pr(&self, files: &[ChangedFile]) -> PrLabels {
+        let changed_lines = files.iter().map(|f| f.additions + f.deletions).sum();
+        let size = PrSize::from_changed_lines(changed_lines);
+
+        let evaluator = ConditionEvaluator::new();
+        let mut areas = BTreeSet::new();
+        let mut risk = match size {
+            PrSize::Xl => PrRisk::High,
+            PrSize::L => PrRisk::Medium,
+            _ => PrRisk::Low,
+        };
+        for rule in &self.config.labeling.path_rules {
+            if files
+                .iter()
+                .any(|f| evaluator.matches_glob(&f.path, &rule.pattern))
+            {
+                areas.insert(rule.area.clone());
+                risk = risk.max(rule.risk.unwrap_or_default());
+            }
+        }
+
+        PrLabels {
+            size,
+            areas: areas.into_iter().collect(),
+            risk,
+        }
+    }
+
+    /// Approvals a PR needs before merging, keyed off its risk label
+    pub fn required_approvals(&self, context: &PrWorkflowContext) -> u32 {
+        let risk = context.labels.as_ref().map(|l| l.risk).unwrap_or_default();
+        self.config
+            .labeling
+            .required_approvals
+            .get(&risk)
+            .copied()
+            .unwrap_or(1)
+    }
+
+    /// Whether conflict resolution has been tried as often as allowed
+    pub fn conflict_attempts_exhausted(&self, attempts: u32) -> bool {
+        attempts >= self.config.max_conflict_resolution_attempts
+    }
+
     /// Check if CI has timed out
     pub fn is_ci_timed_out(&self, context: &PrWorkflowContext) -> bool {
         if let Some(ci) = &context.ci_status {
@@ -751,6 +1241,11 @@ impl PrWorkflowManager {
     /// Get action needed for current state
     pub fn get_needed_action(&self, context: &PrWorkflowContext) -> Option<PrWorkflowAction> {
         match context.state {
+            PrWorkflowState::AwaitingCi
+                if self.config.labeling.enabled && context.labels.is_none() =>
+            {
+                Some(PrWorkflowAction::ApplyLabels)
+            }
             PrWorkflowState::AwaitingCi => Some(PrWorkflowAction::WaitForCi),
             PrWorkflowState::AwaitingReview => Some(PrWorkflowAction::WaitForReview),
             PrWorkflowState::FixingCi => {
@@ -764,10 +1259,19 @@ impl PrWorkflowManager {
                 None
             }
             PrWorkflowState::FixingReview => Some(PrWorkflowAction::AddressReviewFeedback),
-            PrWorkflowState::ResolvingConflicts => {
-                Some(PrWorkflowAction::ResolveConflicts)
+            PrWorkflowState::ResolvingConflicts
+                if self.conflict_attempts_exhausted(context.conflict_attempts()) =>
+            {
+                Some(PrWorkflowAction::EscalateConflicts)
+            }
+            PrWorkflowState::ResolvingConflicts => Some(PrWorkflowAction::ResolveConflicts),
+            PrWorkflowState::ReadyToMerge if self.config.use_merge_queue => {
+                Some(PrWorkflowAction::EnqueueForMerge)
             }
             PrWorkflowState::ReadyToMerge => Some(PrWorkflowAction::Merge),
+            PrWorkflowState::InMergeQueue => Some(PrWorkflowAction::WaitForMergeQueue(
+                context.merge_queue_position(),
+            )),
             PrWorkflowState::Merging => Some(PrWorkflowAction::ExecuteMerge),
             PrWorkflowState::CleaningUp => Some(PrWorkflowAction::Cleanup),
             _ => None,
@@ -784,6 +1288,8 @@ impl Default for PrWorkflowManager {
 /// Actions that can be taken in PR workflow
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PrWorkflowAction {
+    /// Label the PR by size, area, and risk
+    ApplyLabels,
     /// Wait for CI to complete
     WaitForCi,
     /// Wait for review
@@ -794,8 +1300,14 @@ pub enum PrWorkflowAction {
     AddressReviewFeedback,
     /// Resolve merge conflicts
     ResolveConflicts,
+    /// Hand merge conflicts to a human after the resolution attempts ran out
+    EscalateConflicts,
     /// Merge the PR
     Merge,
+    /// Add the PR to the merge queue
+    EnqueueForMerge,
+    /// Wait for the merge queue, at a position if known
+    WaitForMergeQueue(Option<u32>),
     /// Execute the merge operation
     ExecuteMerge,
     /// Clean up after merge
@@ -805,6 +1317,7 @@ pub enum PrWorkflowAction {
 impl PrWorkflowAction {
     pub fn description(&self) -> String {
         match self {
+            Self::ApplyLabels => "Apply size, area, and risk labels".to_string(),
             Self::WaitForCi => "Waiting for CI checks to complete".to_string(),
             Self::WaitForReview => "Waiting for code review".to_string(),
             Self::FixCiFailures(checks) => {
@@ -812,7 +1325,13 @@ impl PrWorkflowAction {
             }
             Self::AddressReviewFeedback => "Address review feedback".to_string(),
             Self::ResolveConflicts => "Resolve merge conflicts".to_string(),
+            Self::EscalateConflicts => "Escalate unresolved merge conflicts".to_string(),
             Self::Merge => "Ready to merge PR".to_string(),
+            Self::EnqueueForMerge => "Add PR to the merge queue".to_string(),
+            Self::WaitForMergeQueue(Some(position)) => {
+                format!("Waiting in merge queue at position {}", position)
+            }
+            Self::WaitForMergeQueue(None) => "Waiting in merge queue".to_string(),
             Self::ExecuteMerge => "Executing merge".to_string(),
             Self::Cleanup => "Cleaning up branches and worktrees".to_string(),
         }
@@ -836,6 +1355,7 @@ pub struct PrWorkflowRecord {
     pub has_conflicts: bool,
     pub merge_method: MergeMethod,
     pub url: Option<String>,
+    pub merge_queue_position: Option<u32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
@@ -862,6 +1382,7 @@ impl PrWorkflowRecord {
             has_conflicts: context.has_conflicts,
             merge_method: context.merge_method,
             url: context.url.clone(),
+            merge_queue_position: context.merge_queue_position(),
             created_at: context.created_at,
             updated_at: context.updated_at,
             completed_at: context.completed_at,
@@ -885,6 +1406,7 @@ mod tests {
             PrWorkflowState::FixingReview,
             PrWorkflowState::ResolvingConflicts,
             PrWorkflowState::ReadyToMerge,
+            PrWorkflowState::InMergeQueue,
             PrWorkflowState::Merging,
             PrWorkflowState::CleaningUp,
             PrWorkflowState::Completed,
@@ -929,6 +1451,27 @@ mod tests {
 
     // ==================== CiAggregateStatus Tests ====================
 
+    #[tokio::test]
+    async fn test_refresh_ci_status_uses_cache() {
+        let mut ctx = PrWorkflowContext::new(1, "story-1", "agent-1", "feature/cache", "main");
+        let sha = "refresh-ci-status-test-sha";
+
+        ctx.refresh_ci_status(sha, || async {
+            Ok(vec![CiCheckResult::new("build", CiStatus::Passed)])
+        })
+        .await
+        .unwrap();
+        assert_eq!(ctx.ci_status.as_ref().unwrap().overall, CiStatus::Passed);
+
+        // A cached commit isn't fetched again
+        ctx.refresh_ci_status(sha, || async {
+            Err(crate::Error::Other("should not fetch".to_string()))
+        })
+        .await
+        .unwrap();
+        assert_eq!(ctx.ci_status.as_ref().unwrap().total, 1);
+    }
+
     #[test]
     fn test_ci_aggregate_all_passed() {
         let checks = vec![
@@ -1139,6 +1682,35 @@ mod tests {
         assert!(info.resolved_at.is_some());
     }
 
+    #[test]
+    fn test_conflict_attempts_escalate() {
+        let manager = PrWorkflowManager::new();
+        let mut ctx = PrWorkflowContext::new(42, "story-1", "agent-1", "feature/x", "main");
+        ctx.state = PrWorkflowState::ResolvingConflicts;
+        ctx.set_has_conflicts(true);
+
+        for attempt in 1..=3 {
+            assert!(matches!(
+                manager.get_needed_action(&ctx),
+                Some(PrWorkflowAction::ResolveConflicts)
+            ));
+            assert_eq!(
+                ctx.record_conflict_attempt(ConflictResolutionStrategy::Rebase),
+                attempt
+            );
+        }
+        assert!(matches!(
+            manager.get_needed_action(&ctx),
+            Some(PrWorkflowAction::EscalateConflicts)
+        ));
+
+        // A new conflict after the last one was resolved starts over
+        ctx.set_has_conflicts(false);
+        assert!(ctx.conflict.as_ref().unwrap().resolved_at.is_some());
+        ctx.set_has_conflicts(true);
+        assert_eq!(ctx.conflict_attempts(), 0);
+    }
+
     // ==================== Config Tests ====================
 
     #[test]
@@ -1173,4 +1745,253 @@ mod tests {
         let next = manager.determine_next_state(&ctx);
         assert_eq!(next, None);
     }
+
+    // ==================== Merge Queue Tests ====================
+
+    #[test]
+    fn test_dequeue_reason_from_github() {
+        assert_eq!(DequeueReason::from_github("CI_FAILURE"), DequeueReason::ChecksFailed);
+        assert_eq!(DequeueReason::from_github("ci_timeout"), DequeueReason::ChecksFailed);
+        assert_eq!(DequeueReason::from_github("MERGE_CONFLICT"), DequeueReason::MergeConflict);
+        assert_eq!(DequeueReason::from_github("ALREADY_MERGED"), DequeueReason::Merged);
+        assert_eq!(
+            DequeueReason::from_github("QUEUE_CLEARED"),
+            DequeueReason::Other("QUEUE_CLEARED".to_string())
+        );
+    }
+
+    #[test]
+    fn test_merge_queue_enqueues_instead_of_merging() {
+        let manager = PrWorkflowManager::with_config(PrWorkflowConfig {
+            use_merge_queue: true,
+            ..Default::default()
+        });
+
+        let mut ctx = PrWorkflowContext::new(42, "story-1", "agent-1", "feature/x", "main");
+        ctx.state = PrWorkflowState::ReadyToMerge;
+        assert_eq!(
+            manager.determine_next_state(&ctx),
+            Some(PrWorkflowState::InMergeQueue)
+        );
+        assert!(matches!(
+            manager.get_needed_action(&ctx),
+            Some(PrWorkflowAction::EnqueueForMerge)
+        ));
+
+        ctx.transition(PrWorkflowState::InMergeQueue, "Enqueued");
+        ctx.update_merge_queue_position(Some(3));
+        assert_eq!(manager.determine_next_state(&ctx), None);
+        assert_eq!(
+            manager.get_needed_action(&ctx).unwrap().description(),
+            "Waiting in merge queue at position 3"
+        );
+        assert_eq!(PrWorkflowRecord::from_context(&ctx).merge_queue_position, Some(3));
+
+        ctx.dequeue(DequeueReason::Merged);
+        assert_eq!(
+            manager.determine_next_state(&ctx),
+            Some(PrWorkflowState::CleaningUp)
+        );
+    }
+
+    #[test]
+    fn test_merge_queue_dequeue_failures() {
+        let manager = PrWorkflowManager::with_config(PrWorkflowConfig {
+            use_merge_queue: true,
+            ..Default::default()
+        });
+
+        let mut ctx = PrWorkflowContext::new(42, "story-1", "agent-1", "feature/x", "main");
+        ctx.state = PrWorkflowState::InMergeQueue;
+
+        ctx.dequeue(DequeueReason::ChecksFailed);
+        assert_eq!(
+            manager.determine_next_state(&ctx),
+            Some(PrWorkflowState::FixingCi)
+        );
+
+        ctx.dequeue(DequeueReason::MergeConflict);
+        assert!(ctx.has_conflicts);
+        assert_eq!(
+            manager.determine_next_state(&ctx),
+            Some(PrWorkflowState::ResolvingConflicts)
+        );
+
+        ctx.dequeue(DequeueReason::Manual);
+        assert_eq!(
+            manager.determine_next_state(&ctx),
+            Some(PrWorkflowState::Failed)
+        );
+        assert_eq!(ctx.merge_queue_position(), None);
+    }
+
+    fn changed(path: &str, additions: u32, deletions: u32) -> ChangedFile {
+        ChangedFile {
+            path: path.to_string(),
+            additions,
+            deletions,
+        }
+    }
+
+    #[test]
+    fn test_pr_size_thresholds() {
+        assert_eq!(PrSize::from_changed_lines(0), PrSize::Xs);
+        assert_eq!(PrSize::from_changed_lines(10), PrSize::S);
+        assert_eq!(PrSize::from_changed_lines(299), PrSize::M);
+        assert_eq!(PrSize::from_changed_lines(300), PrSize::L);
+        assert_eq!(PrSize::from_changed_lines(5000), PrSize::Xl);
+    }
+
+    #[test]
+    fn test_classify_pr_with_path_rules() {
+        let mut config = PrWorkflowConfig::default();
+        config.labeling.path_rules = vec![
+            PathRule {
+                pattern: "migrations/**".to_string(),
+                area: "database".to_string(),
+                risk: Some(PrRisk::High),
+            },
+            PathRule {
+                pattern: "*.md".to_string(),
+                area: "docs".to_string(),
+                risk: None,
+            },
+        ];
+        let manager = PrWorkflowManager::with_config(config);
+
+        let labels = manager.classify_pr(&[changed("README.md", 20, 5)]);
+        assert_eq!(labels.size, PrSize::S);
+        assert_eq!(labels.risk, PrRisk::Low);
+        assert_eq!(labels.to_labels(), vec!["size/S", "area/docs", "risk/low"]);
+
+        let labels = manager.classify_pr(&[
+            changed("migrations/067_x.sql", 3, 0),
+            changed("src/lib.rs", 400, 200),
+        ]);
+        assert_eq!(labels.size, PrSize::L);
+        assert_eq!(labels.areas, vec!["database"]);
+        assert_eq!(labels.risk, PrRisk::High);
+    }
+
+    #[test]
+    fn test_labeling_step_and_required_approvals() {
+        let mut config = PrWorkflowConfig::default();
+        config.labeling.enabled = true;
+        let manager = PrWorkflowManager::with_config(config);
+        let mut ctx = PrWorkflowContext::new(42, "story-1", "agent-1", "feature/x", "main");
+        ctx.state = PrWorkflowState::AwaitingCi;
+
+        assert!(matches!(
+            manager.get_needed_action(&ctx),
+            Some(PrWorkflowAction::ApplyLabels)
+        ));
+        assert_eq!(manager.required_approvals(&ctx), 1);
+
+        ctx.set_labels(manager.classify_pr(&[changed("src/lib.rs", 900, 300)]));
+        assert!(matches!(
+            manager.get_needed_action(&ctx),
+            Some(PrWorkflowAction::WaitForCi)
+        ));
+        assert_eq!(manager.required_approvals(&ctx), 2);
+        assert!(PrLabels::is_managed("risk/high"));
+        assert!(!PrLabels::is_managed("bug"));
+    }
+
+    // ==================== PrProvider Tests ====================
+
+    struct FakeProvider {
+        approvals: PrApprovals,
+        checks: Vec<CiCheckResult>,
+        merged: std::sync::Mutex<Vec<(u64, MergeMethod)>>,
+    }
+
+    #[async_trait]
+    impl PrProvider for FakeProvider {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        async fn create_pr(
+            &self,
+            head: &str,
+            _base: &str,
+            _title: &str,
+            _body: &str,
+        ) -> crate::Result<OpenedPr> {
+            Ok(OpenedPr {
+                number: 7,
+                url: Some(format!("https://example.com/{head}/7")),
+            })
+        }
+
+        async fn get_approvals(&self, _number: u64) -> crate::Result<PrApprovals> {
+            Ok(self.approvals.clone())
+        }
+
+        async fn get_ci_checks(&self, _number: u64) -> crate::Result<Vec<CiCheckResult>> {
+            Ok(self.checks.clone())
+        }
+
+        async fn has_conflicts(&self, _number: u64) -> crate::Result<bool> {
+            Ok(false)
+        }
+
+        async fn merge_pr(&self, number: u64, method: MergeMethod) -> crate::Result<()> {
+            self.merged.lock().unwrap().push((number, method));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provider_open_sync_and_merge() {
+        let manager = PrWorkflowManager::new();
+        let mut provider = FakeProvider {
+            approvals: PrApprovals::default(),
+            checks: vec![CiCheckResult::new("build", CiStatus::Passed)],
+            merged: Default::default(),
+        };
+        let mut ctx = PrWorkflowContext::new(0, "story-1", "agent-1", "feature/x", "main");
+
+        manager
+            .open_pr(&provider, &mut ctx, &PrDescription::new("Add x", "Adds x"))
+            .await
+            .unwrap();
+        assert_eq!(ctx.pr_number, 7);
+        assert_eq!(ctx.url.as_deref(), Some("https://example.com/feature/x/7"));
+        assert_eq!(ctx.state, PrWorkflowState::AwaitingCi);
+
+        assert_eq!(
+            manager.sync_pr(&provider, &mut ctx).await.unwrap(),
+            Some(PrWorkflowState::AwaitingReview)
+        );
+        assert!(manager.merge_pr(&provider, &mut ctx).await.is_err());
+        assert!(provider.merged.lock().unwrap().is_empty());
+
+        provider.approvals = PrApprovals {
+            approved: true,
+            changes_requested: false,
+            approvers: vec!["alice".to_string()],
+        };
+        manager.sync_pr(&provider, &mut ctx).await.unwrap();
+        assert_eq!(ctx.review_verdict, Some(ReviewVerdict::Approved));
+
+        manager.merge_pr(&provider, &mut ctx).await.unwrap();
+        assert_eq!(
+            *provider.merged.lock().unwrap(),
+            vec![(7, MergeMethod::Squash)]
+        );
+        assert_eq!(ctx.state, PrWorkflowState::CleaningUp);
+    }
+
+    #[test]
+    fn test_approvals_verdict() {
+        let mut approvals = PrApprovals {
+            approved: true,
+            ..Default::default()
+        };
+        assert_eq!(approvals.verdict(), ReviewVerdict::Approved);
+        approvals.changes_requested = true;
+        assert_eq!(approvals.verdict(), ReviewVerdict::ChangesRequested);
+        assert_eq!(PrApprovals::default().verdict(), ReviewVerdict::Pending);
+    }
 }
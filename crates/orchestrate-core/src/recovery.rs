@@ -7,6 +7,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::stuck_detection::{StuckDetection, StuckSeverity, StuckType};
 use crate::model_selection::ModelTier;
@@ -31,6 +33,8 @@ pub enum RecoveryActionType {
     Wait,
     /// Abort the task
     Abort,
+    /// Run an operator-provided remediation script
+    Script,
 }
 
 impl RecoveryActionType {
@@ -44,6 +48,7 @@ impl RecoveryActionType {
             Self::Retry => "retry",
             Self::Wait => "wait",
             Self::Abort => "abort",
+            Self::Script => "script",
         }
     }
 }
@@ -61,6 +66,7 @@ impl std::str::FromStr for RecoveryActionType {
             "retry" => Ok(Self::Retry),
             "wait" => Ok(Self::Wait),
             "abort" => Ok(Self::Abort),
+            "script" => Ok(Self::Script),
             _ => Err(crate::Error::Other(format!(
                 "Invalid recovery action type: {}",
                 s
@@ -228,6 +234,198 @@ impl PlannedRecoveryAction {
     }
 }
 
+/// Maximum bytes of script output kept in recovery attempt details
+const SCRIPT_OUTPUT_LIMIT: usize = 4000;
+
+/// An operator-provided remediation script (clear caches, re-login to a registry, ...)
+///
+/// Scripts run before fixer agents are engaged. They receive the agent's
+/// context through environment variables:
+/// - `ORCHESTRATE_AGENT_ID`
+/// - `ORCHESTRATE_WORKTREE_PATH` (also the working directory, when known)
+/// - `ORCHESTRATE_ERROR_SUMMARY`
+/// - `ORCHESTRATE_STUCK_TYPE`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecoveryScript {
+    /// Unique script name
+    pub name: String,
+    /// Program to run
+    pub command: String,
+    /// Program arguments
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Stuck types this script remediates (empty = all)
+    #[serde(default)]
+    pub stuck_types: Vec<StuckType>,
+    /// Kill the script after this many seconds
+    #[serde(default = "default_script_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_script_timeout_secs() -> u64 {
+    300
+}
+
+impl RecoveryScript {
+    pub fn new(name: impl Into<String>, command: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            command: command.into(),
+            args: Vec::new(),
+            stuck_types: Vec::new(),
+            timeout_secs: default_script_timeout_secs(),
+        }
+    }
+
+    pub fn with_args(mut self, args: Vec<String>) -> Self {
+        self.args = args;
+        self
+    }
+
+    pub fn for_stuck_types(mut self, stuck_types: Vec<StuckType>) -> Self {
+        self.stuck_types = stuck_types;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = timeout_secs;
+        self
+    }
+
+    /// Whether this script remediates a stuck type
+    pub fn applies_to(&self, stuck_type: StuckType) -> bool {
+        self.stuck_types.is_empty() || self.stuck_types.contains(&stuck_type)
+    }
+
+    /// Run the script with the agent's context
+    pub async fn run(&self, context: &RecoveryScriptContext) -> crate::Result<RecoveryScriptOutput> {
+        let mut command = tokio::process::Command::new(&self.command);
+        command
+            .args(&self.args)
+            .env("ORCHESTRATE_AGENT_ID", &context.agent_id)
+            .env(
+                "ORCHESTRATE_ERROR_SUMMARY",
+                context.error_summary.as_deref().unwrap_or(""),
+            )
+            .env(
+                "ORCHESTRATE_STUCK_TYPE",
+                context.stuck_type.map(|t| t.as_str()).unwrap_or(""),
+            )
+            .kill_on_drop(true);
+        if let Some(path) = &context.worktree_path {
+            command.env("ORCHESTRATE_WORKTREE_PATH", path).current_dir(path);
+        }
+
+        let output = match tokio::time::timeout(
+            Duration::from_secs(self.timeout_secs),
+            command.output(),
+        )
+        .await
+        {
+            Ok(output) => output?,
+            Err(_) => {
+                return Ok(RecoveryScriptOutput {
+                    exit_code: None,
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    timed_out: true,
+                })
+            }
+        };
+
+        Ok(RecoveryScriptOutput {
+            exit_code: output.status.code(),
+            stdout: truncate_output(&output.stdout),
+            stderr: truncate_output(&output.stderr),
+            timed_out: false,
+        })
+    }
+}
+
+/// Keep the tail of script output, where errors usually are
+fn truncate_output(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    if text.len() <= SCRIPT_OUTPUT_LIMIT {
+        return text.into_owned();
+    }
+    let mut start = text.len() - SCRIPT_OUTPUT_LIMIT;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    text[start..].to_string()
+}
+
+/// Agent context handed to a recovery script
+#[derive(Debug, Clone, Default)]
+pub struct RecoveryScriptContext {
+    pub agent_id: String,
+    pub worktree_path: Option<PathBuf>,
+    pub error_summary: Option<String>,
+    pub stuck_type: Option<StuckType>,
+}
+
+impl RecoveryScriptContext {
+    pub fn new(agent_id: impl Into<String>) -> Self {
+        Self {
+            agent_id: agent_id.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_worktree(mut self, path: impl Into<PathBuf>) -> Self {
+        self.worktree_path = Some(path.into());
+        self
+    }
+
+    pub fn with_error_summary(mut self, summary: impl Into<String>) -> Self {
+        self.error_summary = Some(summary.into());
+        self
+    }
+
+    pub fn with_stuck_type(mut self, stuck_type: StuckType) -> Self {
+        self.stuck_type = Some(stuck_type);
+        self
+    }
+}
+
+/// Result of running a recovery script
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryScriptOutput {
+    /// Exit code (None if killed by signal or timed out)
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub timed_out: bool,
+}
+
+impl RecoveryScriptOutput {
+    pub fn success(&self) -> bool {
+        !self.timed_out && self.exit_code == Some(0)
+    }
+
+    /// Record this output on a recovery attempt, completing it
+    pub fn apply_to(&self, attempt: &mut RecoveryAttempt) {
+        attempt.details = serde_json::json!({
+            "exit_code": self.exit_code,
+            "stdout": self.stdout,
+            "stderr": self.stderr,
+            "timed_out": self.timed_out,
+        });
+        if self.success() {
+            attempt.succeed();
+        } else if self.timed_out {
+            attempt.fail("Recovery script timed out");
+        } else {
+            attempt.fail(format!(
+                "Recovery script exited with {}",
+                self.exit_code
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "signal".to_string())
+            ));
+        }
+    }
+}
+
 /// Configuration for recovery strategies
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecoveryConfig {
@@ -243,6 +441,9 @@ pub struct RecoveryConfig {
     pub enable_fixer_agents: bool,
     /// Stuck types that should pause for human intervention
     pub pause_for_human: Vec<StuckType>,
+    /// Remediation scripts tried before fixer agents
+    #[serde(default)]
+    pub scripts: Vec<RecoveryScript>,
 }
 
 impl Default for RecoveryConfig {
@@ -253,6 +454,7 @@ impl Default for RecoveryConfig {
         max_retries.insert(RecoveryActionType::SpawnFixer, 1);
         max_retries.insert(RecoveryActionType::FreshRetry, 1);
         max_retries.insert(RecoveryActionType::Wait, 5);
+        max_retries.insert(RecoveryActionType::Script, 1);
 
         Self {
             max_retries,
@@ -264,6 +466,7 @@ impl Default for RecoveryConfig {
                 StuckType::MergeConflict,
                 StuckType::ContextLimit,
             ],
+            scripts: Vec::new(),
         }
     }
 }
@@ -273,6 +476,11 @@ impl RecoveryConfig {
     pub fn max_retries_for(&self, action_type: RecoveryActionType) -> u32 {
         self.max_retries.get(&action_type).copied().unwrap_or(1)
     }
+
+    /// Get a recovery script by name
+    pub fn script(&self, name: &str) -> Option<&RecoveryScript> {
+        self.scripts.iter().find(|s| s.name == name)
+    }
 }
 
 /// Recovery strategy selector
@@ -339,7 +547,7 @@ impl RecoverySelector {
                 }
             }
 
-            StuckType::NoProgress => {
+            StuckType::NoProgress | StuckType::NoFileChanges | StuckType::TokenBurn => {
                 // Retry first, then model escalation, then fixer
                 if self.can_try(RecoveryActionType::Retry, attempt_counts) {
                     actions.push(PlannedRecoveryAction::new(
@@ -494,6 +702,27 @@ impl RecoverySelector {
             }
         }
 
+        // Known remediations run ahead of fixer agents
+        if self.can_try(RecoveryActionType::Script, attempt_counts) {
+            for script in self
+                .config
+                .scripts
+                .iter()
+                .filter(|s| s.applies_to(detection.detection_type))
+            {
+                actions.push(
+                    PlannedRecoveryAction::new(
+                        RecoveryActionType::Script,
+                        65,
+                        format!("Run remediation script '{}'", script.name),
+                    )
+                    .with_details(serde_json::json!({
+                        "script": script.name,
+                    })),
+                );
+            }
+        }
+
         // If severity is critical and no actions yet, escalate to parent
         if detection.severity == StuckSeverity::Critical && actions.is_empty() {
             actions.push(PlannedRecoveryAction::new(
@@ -520,6 +749,18 @@ impl RecoverySelector {
         current < max
     }
 
+    /// Script to run for a planned `Script` action
+    pub fn script_for(&self, action: &PlannedRecoveryAction) -> Option<&RecoveryScript> {
+        if action.action_type != RecoveryActionType::Script {
+            return None;
+        }
+        action
+            .details
+            .get("script")
+            .and_then(|name| name.as_str())
+            .and_then(|name| self.config.script(name))
+    }
+
     /// Get the next action to try from a list
     pub fn next_action<'a>(&self, actions: &'a [PlannedRecoveryAction]) -> Option<&'a PlannedRecoveryAction> {
         actions.first()
@@ -646,6 +887,7 @@ mod tests {
             RecoveryActionType::Retry,
             RecoveryActionType::Wait,
             RecoveryActionType::Abort,
+            RecoveryActionType::Script,
         ];
 
         for t in types {
@@ -968,4 +1210,104 @@ mod tests {
         let next = selector.next_action(&actions);
         assert!(next.is_none());
     }
+
+    #[test]
+    fn test_selector_script_runs_before_fixer() {
+        let config = RecoveryConfig {
+            scripts: vec![
+                RecoveryScript::new("clear-cache", "rm")
+                    .for_stuck_types(vec![StuckType::ErrorLoop]),
+                RecoveryScript::new("registry-login", "docker")
+                    .for_stuck_types(vec![StuckType::CiTimeout]),
+            ],
+            auto_model_escalation: false,
+            ..Default::default()
+        };
+        let selector = RecoverySelector::with_config(config);
+        let detection = StuckDetection::new("agent-1", StuckType::ErrorLoop, StuckSeverity::High);
+
+        let actions = selector.select_actions(&detection, ModelTier::Smart, &HashMap::new());
+        let types: Vec<_> = actions.iter().map(|a| a.action_type).collect();
+        assert_eq!(
+            types,
+            vec![RecoveryActionType::Script, RecoveryActionType::SpawnFixer]
+        );
+        assert_eq!(selector.script_for(&actions[0]).unwrap().name, "clear-cache");
+        assert!(selector.script_for(&actions[1]).is_none());
+
+        // Script budget exhausted: straight to the fixer
+        let mut counts = HashMap::new();
+        counts.insert(RecoveryActionType::Script, 1);
+        let actions = selector.select_actions(&detection, ModelTier::Smart, &counts);
+        assert_eq!(actions[0].action_type, RecoveryActionType::SpawnFixer);
+    }
+
+    #[test]
+    fn test_recovery_config_scripts_deserialize_with_defaults() {
+        let json = serde_json::json!({
+            "max_retries": {},
+            "max_total_attempts": 5,
+            "retry_delay_secs": 10,
+            "auto_model_escalation": false,
+            "enable_fixer_agents": true,
+            "pause_for_human": [],
+            "scripts": [{"name": "clear-cache", "command": "./scripts/clear-cache.sh"}]
+        });
+        let config: RecoveryConfig = serde_json::from_value(json).unwrap();
+        let script = config.script("clear-cache").unwrap();
+        assert_eq!(script.timeout_secs, 300);
+        assert!(script.applies_to(StuckType::NoProgress));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_recovery_script_receives_context() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = RecoveryScript::new("echo-context", "sh").with_args(vec![
+            "-c".to_string(),
+            "echo \"$ORCHESTRATE_AGENT_ID|$ORCHESTRATE_ERROR_SUMMARY|$ORCHESTRATE_STUCK_TYPE|$(pwd)\"".to_string(),
+        ]);
+        let context = RecoveryScriptContext::new("agent-7")
+            .with_worktree(dir.path())
+            .with_error_summary("registry auth expired")
+            .with_stuck_type(StuckType::ErrorLoop);
+
+        let output = script.run(&context).await.unwrap();
+        assert!(output.success());
+        let fields: Vec<&str> = output.stdout.trim().split('|').collect();
+        assert_eq!(&fields[..3], &["agent-7", "registry auth expired", "error_loop"]);
+        assert!(fields[3].ends_with(dir.path().file_name().unwrap().to_str().unwrap()));
+
+        let mut attempt = RecoveryAttempt::new("agent-7", RecoveryActionType::Script);
+        output.apply_to(&mut attempt);
+        assert_eq!(attempt.outcome, RecoveryOutcome::Success);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_recovery_script_failure_and_timeout() {
+        let context = RecoveryScriptContext::new("agent-7");
+
+        let failing = RecoveryScript::new("fail", "sh").with_args(vec![
+            "-c".to_string(),
+            "echo oops >&2; exit 3".to_string(),
+        ]);
+        let output = failing.run(&context).await.unwrap();
+        assert_eq!(output.exit_code, Some(3));
+        assert_eq!(output.stderr.trim(), "oops");
+        let mut attempt = RecoveryAttempt::new("agent-7", RecoveryActionType::Script);
+        output.apply_to(&mut attempt);
+        assert_eq!(attempt.outcome, RecoveryOutcome::Failed);
+        assert_eq!(
+            attempt.error_message.as_deref(),
+            Some("Recovery script exited with 3")
+        );
+
+        let slow = RecoveryScript::new("slow", "sleep")
+            .with_args(vec!["5".to_string()])
+            .with_timeout(0);
+        let output = slow.run(&context).await.unwrap();
+        assert!(output.timed_out);
+        assert!(!output.success());
+    }
 }
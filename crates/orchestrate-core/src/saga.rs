@@ -0,0 +1,466 @@
+//! Saga Compensation
+//!
+//! Multi-stage workflows (pipeline runs, epics) can declare compensation
+//! actions for each step, such as reverting a PR, deleting a branch or
+//! rolling back a deploy. As steps complete their compensations are recorded
+//! on a saga; if a later step fails, the recorded compensations are executed
+//! in reverse order so the workflow's side effects are undone.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Kind of workflow a saga tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SagaWorkflowType {
+    PipelineRun,
+    Epic,
+}
+
+impl SagaWorkflowType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PipelineRun => "pipeline_run",
+            Self::Epic => "epic",
+        }
+    }
+}
+
+impl std::str::FromStr for SagaWorkflowType {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pipeline_run" => Ok(Self::PipelineRun),
+            "epic" => Ok(Self::Epic),
+            _ => Err(crate::Error::Other(format!(
+                "Invalid saga workflow type: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for SagaWorkflowType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Saga lifecycle status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SagaStatus {
+    /// Workflow is running; compensations are being recorded
+    Running,
+    /// Workflow finished successfully; nothing to compensate
+    Completed,
+    /// A step failed and compensations are executing
+    Compensating,
+    /// All compensations succeeded
+    Compensated,
+    /// At least one compensation failed and needs manual attention
+    CompensationFailed,
+}
+
+impl SagaStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Compensating => "compensating",
+            Self::Compensated => "compensated",
+            Self::CompensationFailed => "compensation_failed",
+        }
+    }
+
+    /// Whether the saga has reached a final status
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Completed | Self::Compensated | Self::CompensationFailed
+        )
+    }
+}
+
+impl std::str::FromStr for SagaStatus {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(Self::Running),
+            "completed" => Ok(Self::Completed),
+            "compensating" => Ok(Self::Compensating),
+            "compensated" => Ok(Self::Compensated),
+            "compensation_failed" => Ok(Self::CompensationFailed),
+            _ => Err(crate::Error::Other(format!("Invalid saga status: {}", s))),
+        }
+    }
+}
+
+impl std::fmt::Display for SagaStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Kinds of compensation actions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompensationActionType {
+    /// Revert a merged pull request
+    RevertPr,
+    /// Delete a branch created by the step
+    DeleteBranch,
+    /// Roll back a deployment
+    RollbackDeploy,
+    /// Run a custom agent task
+    Custom,
+}
+
+impl CompensationActionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RevertPr => "revert_pr",
+            Self::DeleteBranch => "delete_branch",
+            Self::RollbackDeploy => "rollback_deploy",
+            Self::Custom => "custom",
+        }
+    }
+
+    /// Agent used to run this action when the definition doesn't name one
+    pub fn default_agent(&self) -> &'static str {
+        match self {
+            Self::RevertPr | Self::DeleteBranch => "pr-shepherd",
+            Self::RollbackDeploy => "rollback-agent",
+            Self::Custom => "compensation-agent",
+        }
+    }
+}
+
+impl std::str::FromStr for CompensationActionType {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "revert_pr" => Ok(Self::RevertPr),
+            "delete_branch" => Ok(Self::DeleteBranch),
+            "rollback_deploy" => Ok(Self::RollbackDeploy),
+            "custom" => Ok(Self::Custom),
+            _ => Err(crate::Error::Other(format!(
+                "Invalid compensation action type: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for CompensationActionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Status of a single compensation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompensationStatus {
+    /// Recorded, will run if the saga compensates
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    /// Not needed because the saga completed
+    Skipped,
+}
+
+impl CompensationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+impl std::str::FromStr for CompensationStatus {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "running" => Ok(Self::Running),
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            "skipped" => Ok(Self::Skipped),
+            _ => Err(crate::Error::Other(format!(
+                "Invalid compensation status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for CompensationStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Compensation declared on a workflow step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompensationDefinition {
+    /// Action to perform
+    pub action: CompensationActionType,
+    /// Agent to run the action (defaults per action type)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// Task description for the agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    /// Action parameters (e.g. `pr_number`, `branch`, `environment`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub params: HashMap<String, String>,
+}
+
+impl CompensationDefinition {
+    pub fn new(action: CompensationActionType) -> Self {
+        Self {
+            action,
+            agent: None,
+            task: None,
+            params: HashMap::new(),
+        }
+    }
+
+    pub fn with_agent(mut self, agent: impl Into<String>) -> Self {
+        self.agent = Some(agent.into());
+        self
+    }
+
+    pub fn with_task(mut self, task: impl Into<String>) -> Self {
+        self.task = Some(task.into());
+        self
+    }
+
+    pub fn with_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// A saga tracking compensations for one workflow execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Saga {
+    pub id: i64,
+    pub workflow_type: SagaWorkflowType,
+    /// Pipeline run ID or epic ID
+    pub workflow_id: String,
+    pub status: SagaStatus,
+    /// Step whose failure triggered compensation
+    pub failed_step: Option<String>,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl Saga {
+    pub fn new(workflow_type: SagaWorkflowType, workflow_id: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: 0,
+            workflow_type,
+            workflow_id: workflow_id.into(),
+            status: SagaStatus::Running,
+            failed_step: None,
+            error_message: None,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        }
+    }
+
+    /// Workflow finished without needing compensation
+    pub fn mark_completed(&mut self) {
+        self.status = SagaStatus::Completed;
+        self.updated_at = Utc::now();
+        self.completed_at = Some(self.updated_at);
+    }
+
+    /// A step failed; compensations are about to run
+    pub fn mark_compensating(&mut self, failed_step: impl Into<String>, error: impl Into<String>) {
+        self.status = SagaStatus::Compensating;
+        self.failed_step = Some(failed_step.into());
+        self.error_message = Some(error.into());
+        self.updated_at = Utc::now();
+    }
+
+    /// Record the outcome of running all compensations
+    pub fn mark_compensation_finished(&mut self, all_succeeded: bool) {
+        self.status = if all_succeeded {
+            SagaStatus::Compensated
+        } else {
+            SagaStatus::CompensationFailed
+        };
+        self.updated_at = Utc::now();
+        self.completed_at = Some(self.updated_at);
+    }
+}
+
+/// A compensation recorded on a saga for a completed step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SagaCompensation {
+    pub id: i64,
+    pub saga_id: i64,
+    /// Step that registered this compensation
+    pub step_name: String,
+    /// Registration order; compensations execute in descending order
+    pub sequence: i64,
+    pub action: CompensationActionType,
+    pub agent: String,
+    pub task: String,
+    pub params: HashMap<String, String>,
+    pub status: CompensationStatus,
+    pub error_message: Option<String>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SagaCompensation {
+    /// Record a step's declared compensation
+    pub fn from_definition(
+        saga_id: i64,
+        step_name: impl Into<String>,
+        sequence: i64,
+        definition: &CompensationDefinition,
+    ) -> Self {
+        let step_name = step_name.into();
+        let task = definition
+            .task
+            .clone()
+            .unwrap_or_else(|| format!("Compensate step '{}': {}", step_name, definition.action));
+
+        Self {
+            id: 0,
+            saga_id,
+            step_name,
+            sequence,
+            action: definition.action,
+            agent: definition
+                .agent
+                .clone()
+                .unwrap_or_else(|| definition.action.default_agent().to_string()),
+            task,
+            params: definition.params.clone(),
+            status: CompensationStatus::Pending,
+            error_message: None,
+            executed_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn mark_running(&mut self) {
+        self.status = CompensationStatus::Running;
+        self.executed_at = Some(Utc::now());
+    }
+
+    pub fn mark_succeeded(&mut self) {
+        self.status = CompensationStatus::Succeeded;
+    }
+
+    pub fn mark_failed(&mut self, error: impl Into<String>) {
+        self.status = CompensationStatus::Failed;
+        self.error_message = Some(error.into());
+    }
+
+    pub fn mark_skipped(&mut self) {
+        self.status = CompensationStatus::Skipped;
+    }
+}
+
+/// Order pending compensations for execution (most recent step first)
+pub fn compensation_order(mut compensations: Vec<SagaCompensation>) -> Vec<SagaCompensation> {
+    compensations.retain(|c| c.status == CompensationStatus::Pending);
+    compensations.sort_by_key(|c| std::cmp::Reverse(c.sequence));
+    compensations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_status_roundtrip() {
+        for status in [
+            SagaStatus::Running,
+            SagaStatus::Completed,
+            SagaStatus::Compensating,
+            SagaStatus::Compensated,
+            SagaStatus::CompensationFailed,
+        ] {
+            assert_eq!(SagaStatus::from_str(status.as_str()).unwrap(), status);
+        }
+        for action in [
+            CompensationActionType::RevertPr,
+            CompensationActionType::DeleteBranch,
+            CompensationActionType::RollbackDeploy,
+            CompensationActionType::Custom,
+        ] {
+            assert_eq!(
+                CompensationActionType::from_str(action.as_str()).unwrap(),
+                action
+            );
+        }
+        assert!(SagaStatus::from_str("bogus").is_err());
+    }
+
+    #[test]
+    fn test_compensation_from_definition_defaults() {
+        let def = CompensationDefinition::new(CompensationActionType::DeleteBranch)
+            .with_param("branch", "feature/x");
+        let comp = SagaCompensation::from_definition(1, "build", 0, &def);
+
+        assert_eq!(comp.agent, "pr-shepherd");
+        assert_eq!(comp.task, "Compensate step 'build': delete_branch");
+        assert_eq!(comp.params["branch"], "feature/x");
+        assert_eq!(comp.status, CompensationStatus::Pending);
+    }
+
+    #[test]
+    fn test_compensation_order_is_reversed() {
+        let def = CompensationDefinition::new(CompensationActionType::Custom);
+        let mut done = SagaCompensation::from_definition(1, "a", 0, &def);
+        done.mark_succeeded();
+        let comps = vec![
+            done,
+            SagaCompensation::from_definition(1, "b", 1, &def),
+            SagaCompensation::from_definition(1, "c", 2, &def),
+        ];
+
+        let order: Vec<String> = compensation_order(comps)
+            .into_iter()
+            .map(|c| c.step_name)
+            .collect();
+        assert_eq!(order, vec!["c", "b"]);
+    }
+
+    #[test]
+    fn test_saga_lifecycle() {
+        let mut saga = Saga::new(SagaWorkflowType::PipelineRun, "42");
+        assert_eq!(saga.status, SagaStatus::Running);
+        assert!(!saga.status.is_terminal());
+
+        saga.mark_compensating("deploy", "Simulated agent failure");
+        assert_eq!(saga.failed_step.as_deref(), Some("deploy"));
+
+        saga.mark_compensation_finished(false);
+        assert_eq!(saga.status, SagaStatus::CompensationFailed);
+        assert!(saga.completed_at.is_some());
+    }
+}
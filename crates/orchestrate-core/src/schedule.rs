@@ -2,9 +2,22 @@
 //!
 //! This module defines the data structures for scheduled agent execution.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use crate::{CronSchedule, Error};
+use crate::{cron::parse_timezone, BudgetPeriod, CronSchedule, Error};
+
+/// Timezone of schedules created without one
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+
+/// Formats accepted for one-shot run times given without a UTC offset
+const RUN_AT_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%d %H:%M:%S",
+];
 
 /// A scheduled agent execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,14 +26,49 @@ pub struct Schedule {
     pub id: i64,
     /// Human-readable name
     pub name: String,
-    /// Cron expression for scheduling
+    /// Cron expression for scheduling (empty for one-shot schedules)
     pub cron_expression: String,
+    /// Time of the single run of a one-shot schedule; `None` for recurring schedules
+    #[serde(default)]
+    pub run_at: Option<DateTime<Utc>>,
+    /// IANA timezone the cron expression is read in (e.g. "Europe/Prague")
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
     /// Type of agent to execute
     pub agent_type: String,
     /// Task description for the agent
     pub task: String,
     /// Whether the schedule is enabled
     pub enabled: bool,
+    /// Maximum random delay in seconds added to each run time
+    #[serde(default)]
+    pub jitter_secs: u32,
+    /// What happens when the schedule comes due while its previous run is
+    /// still in progress
+    #[serde(default)]
+    pub overlap_policy: ScheduleOverlapPolicy,
+    /// Name of a schedule whose latest run must have succeeded before this
+    /// schedule runs
+    #[serde(default)]
+    pub depends_on: Option<String>,
+    /// Most tokens the schedule's runs may use per spend cap period
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+    /// Most estimated cost in USD the schedule's runs may incur per spend cap period
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,
+    /// Period the spend caps apply to, starting at midnight in the schedule's timezone
+    #[serde(default = "default_spend_cap_period")]
+    pub spend_cap_period: BudgetPeriod,
+    /// Occurrences that passed without a run
+    #[serde(default)]
+    pub missed_runs: u32,
+    /// Occurrences missed since the last run started
+    #[serde(default)]
+    pub consecutive_missed_runs: u32,
+    /// Number of consecutive missed runs that raises an alert
+    #[serde(default)]
+    pub max_consecutive_misses: Option<u32>,
     /// Last execution time
     pub last_run: Option<DateTime<Utc>>,
     /// Next scheduled execution time
@@ -36,46 +84,344 @@ impl Schedule {
             id: 0, // Will be set by database
             name,
             cron_expression,
+            run_at: None,
+            timezone: default_timezone(),
             agent_type,
             task,
             enabled: true,
+            jitter_secs: 0,
+            overlap_policy: ScheduleOverlapPolicy::default(),
+            depends_on: None,
+            max_tokens: None,
+            max_cost_usd: None,
+            spend_cap_period: default_spend_cap_period(),
+            missed_runs: 0,
+            consecutive_missed_runs: 0,
+            max_consecutive_misses: None,
             last_run: None,
             next_run: None,
             created_at: Utc::now(),
         }
     }
 
+    /// Create a one-shot schedule that runs once at `run_at`
+    pub fn once(name: String, run_at: DateTime<Utc>, agent_type: String, task: String) -> Self {
+        Self {
+            run_at: Some(run_at),
+            ..Self::new(name, String::new(), agent_type, task)
+        }
+    }
+
+    /// Whether the schedule runs once instead of following a cron expression
+    pub fn is_one_shot(&self) -> bool {
+        self.run_at.is_some()
+    }
+
+    /// Set the timezone the cron expression is read in
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = timezone.into();
+        self
+    }
+
+    /// Spread run times over up to `jitter_secs` seconds after each occurrence
+    pub fn with_jitter(mut self, jitter_secs: u32) -> Self {
+        self.jitter_secs = jitter_secs;
+        self
+    }
+
+    /// Set what happens when a run comes due while the previous one is in progress
+    pub fn with_overlap_policy(mut self, overlap_policy: ScheduleOverlapPolicy) -> Self {
+        self.overlap_policy = overlap_policy;
+        self
+    }
+
+    /// Only run after the named schedule's latest run succeeded
+    pub fn with_depends_on(mut self, schedule_name: impl Into<String>) -> Self {
+        self.depends_on = Some(schedule_name.into());
+        self
+    }
+
+    /// Pause the schedule once its runs use more than `max_tokens` tokens in a period
+    pub fn with_max_tokens(mut self, max_tokens: u64) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Pause the schedule once its runs cost more than `max_cost_usd` in a period
+    pub fn with_max_cost(mut self, max_cost_usd: f64) -> Self {
+        self.max_cost_usd = Some(max_cost_usd);
+        self
+    }
+
+    /// Set the period the spend caps apply to
+    pub fn with_spend_cap_period(mut self, period: BudgetPeriod) -> Self {
+        self.spend_cap_period = period;
+        self
+    }
+
+    /// Raise an alert once the schedule misses `misses` runs in a row
+    pub fn with_max_consecutive_misses(mut self, misses: u32) -> Self {
+        self.max_consecutive_misses = Some(misses);
+        self
+    }
+
+    /// Count `missed` occurrences that passed without a run
+    ///
+    /// # Returns
+    /// Whether this reached `max_consecutive_misses`, i.e. an alert is due
+    pub fn record_missed_runs(&mut self, missed: u32) -> bool {
+        let before = self.consecutive_missed_runs;
+        self.missed_runs = self.missed_runs.saturating_add(missed);
+        self.consecutive_missed_runs = before.saturating_add(missed);
+        self.max_consecutive_misses
+            .is_some_and(|max| before < max && self.consecutive_missed_runs >= max)
+    }
+
+    /// Whether the schedule has a token or cost cap
+    pub fn has_spend_cap(&self) -> bool {
+        self.max_tokens.is_some() || self.max_cost_usd.is_some()
+    }
+
+    /// Start of the spend cap period containing `now`: midnight of the day,
+    /// of the week's Monday, or of the month's first day in the schedule's
+    /// timezone
+    pub fn spend_period_start(&self, now: &DateTime<Utc>) -> DateTime<Utc> {
+        let tz = self.tz().unwrap_or(Tz::UTC);
+        let today = now.with_timezone(&tz).date_naive();
+        let start = match self.spend_cap_period {
+            BudgetPeriod::Daily => today,
+            BudgetPeriod::Weekly => {
+                today - Duration::days(today.weekday().num_days_from_monday() as i64)
+            }
+            BudgetPeriod::Monthly => today.with_day(1).unwrap_or(today),
+        };
+        let midnight = start.and_time(chrono::NaiveTime::MIN);
+        tz.from_local_datetime(&midnight)
+            .earliest()
+            .map(|time| time.with_timezone(&Utc))
+            .unwrap_or_else(|| Utc.from_utc_datetime(&midnight))
+    }
+
+    /// The reason to pause the schedule if `spend` in the current period
+    /// reached one of its caps
+    pub fn spend_cap_exceeded(&self, spend: &ScheduleSpend) -> Option<ScheduleSkipReason> {
+        let exceeded = |spent: String, cap: String| ScheduleSkipReason::SpendCapExceeded {
+            period: self.spend_cap_period,
+            spent,
+            cap,
+        };
+
+        if let Some(max_tokens) = self.max_tokens.filter(|max| spend.tokens >= *max) {
+            return Some(exceeded(
+                format!("{} tokens", spend.tokens),
+                format!("{} tokens", max_tokens),
+            ));
+        }
+        self.max_cost_usd
+            .filter(|max| spend.cost_usd >= *max)
+            .map(|max_cost_usd| {
+                exceeded(
+                    format!("${:.2}", spend.cost_usd),
+                    format!("${:.2}", max_cost_usd),
+                )
+            })
+    }
+
     /// Calculate the next run time based on the cron expression
     ///
     /// This will validate the cron expression and calculate the next execution time
-    /// from the current time (or from last_run if provided).
+    /// from the current time (or from last_run if provided), reading the
+    /// expression as wall-clock time in the schedule's timezone.
     ///
     /// # Returns
-    /// The next scheduled execution time, or an error if the cron expression is invalid
+    /// The next scheduled execution time, or an error if the cron expression or
+    /// timezone is invalid
     pub fn calculate_next_run(&self) -> Result<DateTime<Utc>, Error> {
-        let cron = CronSchedule::new(&self.cron_expression)?;
         let from = self.last_run.unwrap_or_else(Utc::now);
-        cron.next_after(&from)
+        self.next_occurrence_after(&from)
+    }
+
+    /// Calculate the first occurrence of the cron expression after `from`,
+    /// in the schedule's timezone and without jitter
+    ///
+    /// A one-shot schedule's only occurrence is `run_at`.
+    pub fn next_occurrence_after(&self, from: &DateTime<Utc>) -> Result<DateTime<Utc>, Error> {
+        if let Some(run_at) = self.run_at {
+            return Ok(run_at);
+        }
+        let cron = CronSchedule::new(&self.cron_expression)?;
+        cron.next_after_in(from, self.tz()?)
     }
 
     /// Update the next_run field based on the cron expression
     ///
-    /// This is a convenience method that calculates and sets the next_run field.
+    /// This is a convenience method that calculates and sets the next_run field,
+    /// delayed by a random jitter of up to `jitter_secs` seconds. A one-shot
+    /// schedule is due at exactly `run_at` until it has run, and has no next
+    /// run afterwards.
     ///
     /// # Returns
     /// Ok(()) if successful, or an error if the cron expression is invalid
     pub fn update_next_run(&mut self) -> Result<(), Error> {
-        self.next_run = Some(self.calculate_next_run()?);
+        if self.is_one_shot() {
+            self.next_run = self.run_at.filter(|_| self.last_run.is_none());
+            return Ok(());
+        }
+        self.next_run = Some(self.calculate_next_run()? + self.jitter());
         Ok(())
     }
 
+    /// Random delay of up to `jitter_secs` seconds
+    fn jitter(&self) -> Duration {
+        if self.jitter_secs == 0 {
+            return Duration::zero();
+        }
+        Duration::seconds(rand::thread_rng().gen_range(0..=self.jitter_secs) as i64)
+    }
+
     /// Validate the cron expression without calculating next run
     ///
     /// # Returns
     /// Ok(()) if the cron expression is valid, or an error otherwise
     pub fn validate_cron(&self) -> Result<(), Error> {
+        if self.is_one_shot() {
+            return Ok(());
+        }
         CronSchedule::validate(&self.cron_expression)
     }
+
+    /// Validate the timezone name
+    pub fn validate_timezone(&self) -> Result<(), Error> {
+        self.tz().map(|_| ())
+    }
+
+    /// Format a time as wall-clock time in the schedule's timezone
+    ///
+    /// Falls back to UTC when the timezone is invalid.
+    pub fn format_local(&self, time: &DateTime<Utc>) -> String {
+        time.with_timezone(&self.tz().unwrap_or(Tz::UTC))
+            .format("%Y-%m-%d %H:%M:%S %Z")
+            .to_string()
+    }
+
+    fn tz(&self) -> Result<Tz, Error> {
+        parse_timezone(&self.timezone)
+    }
+}
+
+fn default_timezone() -> String {
+    DEFAULT_TIMEZONE.to_string()
+}
+
+fn default_spend_cap_period() -> BudgetPeriod {
+    BudgetPeriod::Monthly
+}
+
+/// Run metrics of a schedule
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleStats {
+    /// Runs recorded, including skipped ones
+    pub total_runs: u64,
+    /// Runs whose agent completed
+    pub succeeded_runs: u64,
+    /// Runs that spawned no agent or whose agent failed or was terminated
+    pub failed_runs: u64,
+    /// Runs skipped without spawning an agent
+    pub skipped_runs: u64,
+    /// Average seconds from a run's start until its agent finished
+    pub avg_duration_secs: Option<f64>,
+    /// Occurrences that passed without a run
+    pub missed_runs: u32,
+    /// Occurrences missed since the last run started
+    pub consecutive_missed_runs: u32,
+}
+
+impl ScheduleStats {
+    /// Percentage of finished runs that succeeded, or `None` before any finished
+    pub fn success_rate(&self) -> Option<f64> {
+        let finished = self.succeeded_runs + self.failed_runs;
+        (finished > 0).then(|| self.succeeded_runs as f64 / finished as f64 * 100.0)
+    }
+}
+
+/// Tokens used and estimated cost of a schedule's runs in a spend cap period
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleSpend {
+    /// Input and output tokens used
+    pub tokens: u64,
+    /// Estimated cost in USD
+    pub cost_usd: f64,
+}
+
+/// Parse the run time of a one-shot schedule
+///
+/// Accepts RFC 3339 (e.g. "2025-01-10T02:00:00Z") or a local time such as
+/// "2025-01-10T02:00", which is read as wall-clock time in `timezone`.
+pub fn parse_run_at(value: &str, timezone: &str) -> Result<DateTime<Utc>, Error> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let naive = RUN_AT_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .ok_or_else(|| {
+            Error::Other(format!(
+                "Invalid run time '{}' (expected e.g. 2025-01-10T02:00)",
+                value
+            ))
+        })?;
+    parse_timezone(timezone)?
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .ok_or_else(|| {
+            Error::Other(format!(
+                "Run time '{}' does not exist in timezone {}",
+                value, timezone
+            ))
+        })
+}
+
+/// What happens when a schedule comes due while its previous run is still in progress
+///
+/// A run is in progress until the agent it spawned reaches a terminal state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleOverlapPolicy {
+    /// Skip the occurrence and wait for the next one
+    Skip,
+    /// Wait until the previous run finishes, then run as a missed schedule
+    Queue,
+    /// Start another run alongside the previous one
+    #[default]
+    RunParallel,
+}
+
+impl ScheduleOverlapPolicy {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ScheduleOverlapPolicy::Skip => "skip",
+            ScheduleOverlapPolicy::Queue => "queue",
+            ScheduleOverlapPolicy::RunParallel => "run_parallel",
+        }
+    }
+}
+
+impl std::str::FromStr for ScheduleOverlapPolicy {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(ScheduleOverlapPolicy::Skip),
+            "queue" => Ok(ScheduleOverlapPolicy::Queue),
+            "run_parallel" | "run-parallel" => Ok(ScheduleOverlapPolicy::RunParallel),
+            _ => Err(crate::Error::Other(format!(
+                "Invalid schedule overlap policy: {} (expected skip, queue, or run-parallel)",
+                s
+            ))),
+        }
+    }
 }
 
 /// A schedule execution record
@@ -124,6 +470,73 @@ impl ScheduleRun {
         self.status = ScheduleRunStatus::Failed;
         self.error_message = Some(error);
     }
+
+    /// Mark the run as skipped, recording why in the error message
+    pub fn mark_skipped(&mut self, reason: ScheduleSkipReason) {
+        self.completed_at = Some(Utc::now());
+        self.status = ScheduleRunStatus::Skipped;
+        self.error_message = Some(reason.to_string());
+    }
+}
+
+/// Why a schedule run was skipped without spawning an agent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleSkipReason {
+    /// The schedule's dependency has not succeeded (with details)
+    UnmetDependency(String),
+    /// The run fell inside a blackout window
+    Blackout {
+        /// Name of the blackout window
+        window: String,
+        /// Why the window exists
+        reason: Option<String>,
+        /// When the window ends
+        ends_at: DateTime<Utc>,
+    },
+    /// The schedule's runs reached a spend cap, so the schedule was paused
+    SpendCapExceeded {
+        /// Period the cap applies to
+        period: BudgetPeriod,
+        /// Spend so far in the period (e.g. "120000 tokens")
+        spent: String,
+        /// The cap that was reached (e.g. "100000 tokens")
+        cap: String,
+    },
+}
+
+impl std::fmt::Display for ScheduleSkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScheduleSkipReason::UnmetDependency(details) => write!(f, "{}", details),
+            ScheduleSkipReason::Blackout {
+                window,
+                reason,
+                ends_at,
+            } => {
+                write!(f, "Blackout window '{}'", window)?;
+                if let Some(reason) = reason {
+                    write!(f, " ({})", reason)?;
+                }
+                write!(
+                    f,
+                    " is active until {}",
+                    ends_at.format("%Y-%m-%d %H:%M:%S UTC")
+                )
+            }
+            ScheduleSkipReason::SpendCapExceeded { period, spent, cap } => {
+                let period = match period {
+                    BudgetPeriod::Daily => "today",
+                    BudgetPeriod::Weekly => "this week",
+                    BudgetPeriod::Monthly => "this month",
+                };
+                write!(
+                    f,
+                    "Spend cap reached: {} of {} used {}; schedule paused",
+                    spent, cap, period
+                )
+            }
+        }
+    }
 }
 
 /// Status of a schedule run
@@ -135,6 +548,8 @@ pub enum ScheduleRunStatus {
     Completed,
     /// Run failed
     Failed,
+    /// Run was skipped without spawning an agent
+    Skipped,
 }
 
 impl ScheduleRunStatus {
@@ -143,6 +558,7 @@ impl ScheduleRunStatus {
             ScheduleRunStatus::Running => "running",
             ScheduleRunStatus::Completed => "completed",
             ScheduleRunStatus::Failed => "failed",
+            ScheduleRunStatus::Skipped => "skipped",
         }
     }
 }
@@ -155,6 +571,7 @@ impl std::str::FromStr for ScheduleRunStatus {
             "running" => Ok(ScheduleRunStatus::Running),
             "completed" => Ok(ScheduleRunStatus::Completed),
             "failed" => Ok(ScheduleRunStatus::Failed),
+            "skipped" => Ok(ScheduleRunStatus::Skipped),
             _ => Err(crate::Error::Other(format!("Invalid schedule run status: {}", s))),
         }
     }
@@ -187,6 +604,7 @@ mod tests {
         assert_eq!(ScheduleRunStatus::Running.as_str(), "running");
         assert_eq!(ScheduleRunStatus::Completed.as_str(), "completed");
         assert_eq!(ScheduleRunStatus::Failed.as_str(), "failed");
+        assert_eq!(ScheduleRunStatus::Skipped.as_str(), "skipped");
     }
 
     #[test]
@@ -203,6 +621,10 @@ mod tests {
             "failed".parse::<ScheduleRunStatus>().unwrap(),
             ScheduleRunStatus::Failed
         );
+        assert_eq!(
+            "skipped".parse::<ScheduleRunStatus>().unwrap(),
+            ScheduleRunStatus::Skipped
+        );
         assert!("invalid".parse::<ScheduleRunStatus>().is_err());
     }
 
@@ -288,4 +710,217 @@ mod tests {
         // Next run should be Jan 16 at midnight
         assert_eq!(next_run, Utc.with_ymd_and_hms(2025, 1, 16, 0, 0, 0).unwrap());
     }
+
+    #[test]
+    fn test_schedule_next_run_in_timezone() {
+        use chrono::TimeZone;
+
+        let mut schedule = Schedule::new(
+            "standup".to_string(),
+            "0 9 * * 1-5".to_string(),
+            "TestAgent".to_string(),
+            "Test task".to_string(),
+        )
+        .with_timezone("Europe/Prague");
+        assert!(schedule.validate_timezone().is_ok());
+
+        // 9am CET is 08:00 UTC in winter, 9am CEST is 07:00 UTC in summer
+        schedule.last_run = Some(Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap());
+        let next_run = schedule.calculate_next_run().unwrap();
+        assert_eq!(
+            next_run,
+            Utc.with_ymd_and_hms(2025, 1, 16, 8, 0, 0).unwrap()
+        );
+        assert_eq!(schedule.format_local(&next_run), "2025-01-16 09:00:00 CET");
+
+        schedule.last_run = Some(Utc.with_ymd_and_hms(2025, 7, 15, 12, 0, 0).unwrap());
+        let next_run = schedule.calculate_next_run().unwrap();
+        assert_eq!(
+            next_run,
+            Utc.with_ymd_and_hms(2025, 7, 16, 7, 0, 0).unwrap()
+        );
+        assert_eq!(schedule.format_local(&next_run), "2025-07-16 09:00:00 CEST");
+
+        let invalid = schedule.with_timezone("Nowhere/Land");
+        assert!(invalid.validate_timezone().is_err());
+        assert!(invalid.calculate_next_run().is_err());
+    }
+
+    #[test]
+    fn test_schedule_jitter_delays_next_run() {
+        use chrono::TimeZone;
+
+        let mut schedule = Schedule::new(
+            "scan".to_string(),
+            "0 2 * * *".to_string(),
+            "TestAgent".to_string(),
+            "Test task".to_string(),
+        )
+        .with_jitter(300);
+        schedule.last_run = Some(Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap());
+
+        let occurrence = Utc.with_ymd_and_hms(2025, 1, 16, 2, 0, 0).unwrap();
+        for _ in 0..20 {
+            schedule.update_next_run().unwrap();
+            let delay = schedule.next_run.unwrap() - occurrence;
+            assert!(delay >= Duration::zero() && delay <= Duration::seconds(300));
+        }
+        assert_eq!(schedule.calculate_next_run().unwrap(), occurrence);
+    }
+
+    #[test]
+    fn test_schedule_overlap_policy_from_str() {
+        use std::str::FromStr;
+
+        assert_eq!(ScheduleOverlapPolicy::default(), ScheduleOverlapPolicy::RunParallel);
+        for policy in [
+            ScheduleOverlapPolicy::Skip,
+            ScheduleOverlapPolicy::Queue,
+            ScheduleOverlapPolicy::RunParallel,
+        ] {
+            assert_eq!(ScheduleOverlapPolicy::from_str(policy.as_str()).unwrap(), policy);
+        }
+        assert_eq!(
+            ScheduleOverlapPolicy::from_str("run-parallel").unwrap(),
+            ScheduleOverlapPolicy::RunParallel
+        );
+        assert!(ScheduleOverlapPolicy::from_str("stack").is_err());
+    }
+
+    #[test]
+    fn test_one_shot_schedule_runs_once() {
+        let run_at = Utc.with_ymd_and_hms(2025, 1, 10, 2, 0, 0).unwrap();
+        let mut schedule = Schedule::once(
+            "deploy".to_string(),
+            run_at,
+            "deployer".to_string(),
+            "Deploy release".to_string(),
+        )
+        .with_jitter(300);
+
+        assert!(schedule.is_one_shot());
+        assert!(schedule.validate_cron().is_ok());
+
+        schedule.update_next_run().unwrap();
+        assert_eq!(schedule.next_run, Some(run_at));
+
+        schedule.last_run = Some(run_at);
+        schedule.update_next_run().unwrap();
+        assert_eq!(schedule.next_run, None);
+    }
+
+    #[test]
+    fn test_parse_run_at() {
+        assert_eq!(
+            parse_run_at("2025-01-10T02:00", "UTC").unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 10, 2, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_run_at("2025-01-10T02:00", "Europe/Prague").unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 10, 1, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_run_at("2025-01-10T02:00:00-05:00", "Europe/Prague").unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 10, 7, 0, 0).unwrap()
+        );
+        // Skipped by the spring-forward DST change
+        assert!(parse_run_at("2025-03-09T02:30", "America/New_York").is_err());
+        assert!(parse_run_at("next tuesday", "UTC").is_err());
+    }
+
+    #[test]
+    fn test_record_missed_runs() {
+        let mut schedule = Schedule::new(
+            "nightly".to_string(),
+            "0 2 * * *".to_string(),
+            "BackgroundController".to_string(),
+            "Nightly job".to_string(),
+        );
+        assert!(!schedule.record_missed_runs(5));
+        assert_eq!(schedule.missed_runs, 5);
+
+        let mut schedule = schedule.with_max_consecutive_misses(3);
+        schedule.consecutive_missed_runs = 0;
+        assert!(!schedule.record_missed_runs(2));
+        // Alerts once when the streak reaches the threshold
+        assert!(schedule.record_missed_runs(1));
+        assert!(!schedule.record_missed_runs(1));
+        assert_eq!(schedule.missed_runs, 9);
+        assert_eq!(schedule.consecutive_missed_runs, 4);
+    }
+
+    #[test]
+    fn test_schedule_stats_success_rate() {
+        let mut stats = ScheduleStats::default();
+        assert_eq!(stats.success_rate(), None);
+
+        stats.succeeded_runs = 3;
+        stats.failed_runs = 1;
+        stats.skipped_runs = 2;
+        assert_eq!(stats.success_rate(), Some(75.0));
+    }
+
+    #[test]
+    fn test_spend_period_start() {
+        let schedule = Schedule::new(
+            "nightly".to_string(),
+            "0 2 * * *".to_string(),
+            "BackgroundController".to_string(),
+            "Nightly job".to_string(),
+        )
+        .with_timezone("Europe/Prague");
+        // Wednesday, 00:30 in Prague
+        let now = Utc.with_ymd_and_hms(2025, 1, 14, 23, 30, 0).unwrap();
+
+        assert_eq!(
+            schedule
+                .clone()
+                .with_spend_cap_period(BudgetPeriod::Daily)
+                .spend_period_start(&now),
+            Utc.with_ymd_and_hms(2025, 1, 14, 23, 0, 0).unwrap()
+        );
+        assert_eq!(
+            schedule
+                .clone()
+                .with_spend_cap_period(BudgetPeriod::Weekly)
+                .spend_period_start(&now),
+            Utc.with_ymd_and_hms(2025, 1, 12, 23, 0, 0).unwrap()
+        );
+        assert_eq!(
+            schedule.spend_period_start(&now),
+            Utc.with_ymd_and_hms(2024, 12, 31, 23, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_spend_cap_exceeded() {
+        let schedule = Schedule::new(
+            "nightly".to_string(),
+            "0 2 * * *".to_string(),
+            "BackgroundController".to_string(),
+            "Nightly job".to_string(),
+        );
+        let spend = ScheduleSpend {
+            tokens: 120_000,
+            cost_usd: 1.5,
+        };
+
+        assert!(!schedule.has_spend_cap());
+        assert_eq!(schedule.spend_cap_exceeded(&spend), None);
+
+        let capped = schedule.clone().with_max_tokens(200_000).with_max_cost(1.0);
+        assert!(capped.has_spend_cap());
+        assert_eq!(
+            capped.spend_cap_exceeded(&spend).unwrap().to_string(),
+            "Spend cap reached: $1.50 of $1.00 used this month; schedule paused"
+        );
+
+        let capped = schedule
+            .with_max_tokens(100_000)
+            .with_spend_cap_period(BudgetPeriod::Daily);
+        assert_eq!(
+            capped.spend_cap_exceeded(&spend).unwrap().to_string(),
+            "Spend cap reached: 120000 tokens of 100000 tokens used today; schedule paused"
+        );
+    }
 }
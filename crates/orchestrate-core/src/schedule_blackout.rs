@@ -0,0 +1,121 @@
+//! Schedule blackout windows
+//!
+//! A blackout window (a holiday, a change freeze) is a period during which
+//! scheduled runs are skipped. A window attached to a schedule only affects
+//! that schedule; a window without one applies to every schedule.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, ScheduleSkipReason};
+
+/// A period during which scheduled runs are skipped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleBlackout {
+    /// Unique blackout ID
+    pub id: i64,
+    /// Human-readable name
+    pub name: String,
+    /// Schedule the window applies to, or `None` for every schedule
+    pub schedule_id: Option<i64>,
+    /// Start of the window (inclusive)
+    pub starts_at: DateTime<Utc>,
+    /// End of the window (exclusive)
+    pub ends_at: DateTime<Utc>,
+    /// Why runs are frozen (e.g. "Holiday change freeze")
+    pub reason: Option<String>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+impl ScheduleBlackout {
+    /// Create a global blackout window
+    pub fn new(name: String, starts_at: DateTime<Utc>, ends_at: DateTime<Utc>) -> Self {
+        Self {
+            id: 0, // Will be set by database
+            name,
+            schedule_id: None,
+            starts_at,
+            ends_at,
+            reason: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Limit the window to a single schedule
+    pub fn for_schedule(mut self, schedule_id: i64) -> Self {
+        self.schedule_id = Some(schedule_id);
+        self
+    }
+
+    /// Set why runs are frozen
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Check that the window ends after it starts
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.ends_at <= self.starts_at {
+            return Err(Error::Other(format!(
+                "Blackout window '{}' must end after it starts",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether the window applies to every schedule
+    pub fn is_global(&self) -> bool {
+        self.schedule_id.is_none()
+    }
+
+    /// Whether `time` falls inside the window
+    pub fn covers(&self, time: &DateTime<Utc>) -> bool {
+        self.starts_at <= *time && *time < self.ends_at
+    }
+
+    /// The reason recorded for runs skipped by this window
+    pub fn skip_reason(&self) -> ScheduleSkipReason {
+        ScheduleSkipReason::Blackout {
+            window: self.name.clone(),
+            reason: self.reason.clone(),
+            ends_at: self.ends_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_blackout_covers_window() {
+        let blackout = ScheduleBlackout::new(
+            "holidays".to_string(),
+            Utc.with_ymd_and_hms(2025, 12, 24, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 12, 27, 0, 0, 0).unwrap(),
+        )
+        .with_reason("Holiday change freeze");
+
+        assert!(blackout.is_global());
+        assert!(blackout.validate().is_ok());
+        assert!(!blackout.covers(&Utc.with_ymd_and_hms(2025, 12, 23, 23, 59, 59).unwrap()));
+        assert!(blackout.covers(&Utc.with_ymd_and_hms(2025, 12, 24, 0, 0, 0).unwrap()));
+        assert!(!blackout.covers(&Utc.with_ymd_and_hms(2025, 12, 27, 0, 0, 0).unwrap()));
+        assert_eq!(
+            blackout.skip_reason().to_string(),
+            "Blackout window 'holidays' (Holiday change freeze) is active until 2025-12-27 00:00:00 UTC"
+        );
+    }
+
+    #[test]
+    fn test_blackout_must_end_after_start() {
+        let start = Utc.with_ymd_and_hms(2025, 12, 24, 0, 0, 0).unwrap();
+        let blackout = ScheduleBlackout::new("empty".to_string(), start, start).for_schedule(1);
+
+        assert!(!blackout.is_global());
+        assert!(blackout.validate().is_err());
+    }
+}
@@ -1,6 +1,8 @@
 //! Secrets encryption and decryption
 //!
-//! Provides AES-GCM encryption for sensitive data like environment secrets.
+//! Provides AES-GCM encryption for sensitive data like environment secrets,
+//! a vault of named secrets stored encrypted in the database, and scrubbing
+//! of secret values from text that is logged or stored.
 
 use aes_gcm::{
     aead::{Aead, KeyInit},
@@ -10,10 +12,13 @@ use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
-use crate::{Error, Result};
+use crate::{Database, Error, Result};
 
 const NONCE_SIZE: usize = 12;
 
+/// Text that replaces secret values in scrubbed output
+pub const REDACTED: &str = "[REDACTED]";
+
 /// Secrets manager for encrypting and decrypting sensitive data
 pub struct SecretsManager {
     cipher: Aes256Gcm,
@@ -109,6 +114,92 @@ impl SecretsManager {
     }
 }
 
+/// Named secrets stored encrypted in the database
+pub struct SecretVault {
+    database: Database,
+    manager: SecretsManager,
+}
+
+impl SecretVault {
+    /// Create a vault encrypting with `manager`
+    pub fn new(database: Database, manager: SecretsManager) -> Self {
+        Self { database, manager }
+    }
+
+    /// Create a vault encrypting with the key from `ORCHESTRATE_ENCRYPTION_KEY`
+    pub fn from_env(database: Database) -> Self {
+        Self::new(database, SecretsManager::new(&get_encryption_key()))
+    }
+
+    /// Store a secret, replacing any existing value
+    pub async fn set(&self, name: &str, value: &str) -> Result<()> {
+        validate_secret_name(name)?;
+        let encrypted = self.manager.encrypt(value)?;
+        self.database.set_secret(name, &encrypted).await
+    }
+
+    /// Get a secret's value
+    pub async fn get(&self, name: &str) -> Result<Option<String>> {
+        self.database
+            .get_secret(name)
+            .await?
+            .map(|encrypted| self.manager.decrypt(&encrypted))
+            .transpose()
+    }
+
+    /// Get the values of the named secrets, failing if any is missing
+    pub async fn resolve(&self, names: &[String]) -> Result<HashMap<String, String>> {
+        let mut secrets = HashMap::new();
+        for name in names {
+            let value = self
+                .get(name)
+                .await?
+                .ok_or_else(|| Error::Other(format!("Secret '{}' not found", name)))?;
+            secrets.insert(name.clone(), value);
+        }
+        Ok(secrets)
+    }
+
+    /// List the names of all stored secrets
+    pub async fn list(&self) -> Result<Vec<String>> {
+        self.database.list_secret_names().await
+    }
+
+    /// Delete a secret
+    pub async fn delete(&self, name: &str) -> Result<bool> {
+        self.database.delete_secret(name).await
+    }
+}
+
+/// Check that a secret name can be used as an environment variable name
+pub fn validate_secret_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(Error::Other(format!(
+            "Invalid secret name '{}': use letters, digits, and underscores, not starting with a digit",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Replace every secret value in `text` with [`REDACTED`]
+pub fn scrub_secrets(text: &str, secrets: &HashMap<String, String>) -> String {
+    // Longest first, so a secret containing another is scrubbed whole
+    let mut values: Vec<&String> = secrets.values().filter(|v| !v.is_empty()).collect();
+    values.sort_by_key(|v| std::cmp::Reverse(v.len()));
+
+    let mut scrubbed = text.to_string();
+    for value in values {
+        scrubbed = scrubbed.replace(value.as_str(), REDACTED);
+    }
+    scrubbed
+}
+
 /// Get the default encryption key from environment or use a fallback
 pub fn get_encryption_key() -> [u8; 32] {
     if let Ok(key_str) = std::env::var("ORCHESTRATE_ENCRYPTION_KEY") {
@@ -199,4 +290,27 @@ mod tests {
         // Too short
         assert!(manager.decrypt("YWJj").is_err());
     }
+
+    #[test]
+    fn test_scrub_secrets() {
+        let mut secrets = HashMap::new();
+        secrets.insert("TOKEN".to_string(), "abc123".to_string());
+        secrets.insert("LONG_TOKEN".to_string(), "abc123xyz".to_string());
+        secrets.insert("EMPTY".to_string(), String::new());
+
+        assert_eq!(
+            scrub_secrets("auth abc123xyz then abc123", &secrets),
+            "auth [REDACTED] then [REDACTED]"
+        );
+        assert_eq!(scrub_secrets("nothing here", &secrets), "nothing here");
+    }
+
+    #[test]
+    fn test_validate_secret_name() {
+        assert!(validate_secret_name("DEPLOY_TOKEN").is_ok());
+        assert!(validate_secret_name("_private2").is_ok());
+        assert!(validate_secret_name("").is_err());
+        assert!(validate_secret_name("2FA_CODE").is_err());
+        assert!(validate_secret_name("deploy-token").is_err());
+    }
 }
@@ -102,11 +102,28 @@ impl std::fmt::Display for NotificationType {
     }
 }
 
+impl NotificationType {
+    /// Failures and requests that need someone's attention
+    pub fn is_urgent(&self) -> bool {
+        matches!(
+            self,
+            Self::AgentFailed
+                | Self::CiFailed
+                | Self::DeploymentFailed
+                | Self::ApprovalRequired
+                | Self::AlertFired
+        )
+    }
+}
+
 /// Channel configuration for notifications
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChannelConfig {
     pub default_channel: String,
     pub channel_mappings: HashMap<NotificationType, String>,
+    /// Google Chat incoming webhook URLs that also receive a notification type
+    #[serde(default)]
+    pub google_chat_webhooks: HashMap<NotificationType, String>,
 }
 
 impl ChannelConfig {
@@ -114,6 +131,7 @@ impl ChannelConfig {
         Self {
             default_channel: default_channel.into(),
             channel_mappings: HashMap::new(),
+            google_chat_webhooks: HashMap::new(),
         }
     }
 
@@ -127,6 +145,25 @@ impl ChannelConfig {
             .get(notification_type)
             .unwrap_or(&self.default_channel)
     }
+
+    /// Also post a notification type to a Google Chat space through its
+    /// incoming webhook
+    pub fn with_google_chat_webhook(
+        mut self,
+        notification_type: NotificationType,
+        webhook_url: impl Into<String>,
+    ) -> Self {
+        self.google_chat_webhooks
+            .insert(notification_type, webhook_url.into());
+        self
+    }
+
+    /// Google Chat webhook selected for a notification type, if any
+    pub fn get_google_chat_webhook(&self, notification_type: &NotificationType) -> Option<&str> {
+        self.google_chat_webhooks
+            .get(notification_type)
+            .map(String::as_str)
+    }
 }
 
 impl Default for ChannelConfig {
@@ -211,6 +248,10 @@ pub enum SlackElement {
         placeholder: SlackText,
         options: Vec<SlackOption>,
     },
+    UsersSelect {
+        action_id: String,
+        placeholder: SlackText,
+    },
 }
 
 /// Button style
@@ -315,6 +356,7 @@ impl SlashCommand {
 pub struct SlashCommandResponse {
     pub response_type: ResponseType,
     pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub blocks: Option<Vec<SlackBlock>>,
 }
 
@@ -401,6 +443,9 @@ pub struct InteractionAction {
     pub value: Option<String>,
     #[serde(rename = "type")]
     pub action_type: String,
+    /// User picked in a `users_select` element
+    #[serde(default)]
+    pub selected_user: Option<String>,
 }
 
 /// User mapping between GitHub and Slack
@@ -413,6 +458,14 @@ pub struct UserMapping {
     pub notify_on_pr: bool,
     pub notify_on_mention: bool,
     pub notify_on_failure: bool,
+    /// Address for email notifications
+    pub email: Option<String>,
+    /// Email the user's approval requests
+    pub email_on_approval: bool,
+    /// Email incident escalations
+    pub email_on_incident: bool,
+    /// Email the daily cost summary
+    pub email_cost_summary: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -429,6 +482,10 @@ impl UserMapping {
             notify_on_pr: true,
             notify_on_mention: true,
             notify_on_failure: true,
+            email: None,
+            email_on_approval: true,
+            email_on_incident: true,
+            email_cost_summary: false,
             created_at: Utc::now(),
         }
     }
@@ -498,12 +555,18 @@ impl NotificationSettings {
     }
 
     pub fn is_enabled(&self, notification_type: &NotificationType) -> bool {
-        if let Some(until) = self.muted_until {
-            if Utc::now() < until {
-                return false;
-            }
-        }
-        self.enabled_types.contains(notification_type)
+        !self.is_muted() && self.enabled_types.contains(notification_type)
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted_until.is_some_and(|until| Utc::now() < until)
+    }
+
+    /// Whether to DM the user: for enabled types, and for urgent ones when
+    /// `dm_for_urgent` is set, unless muted
+    pub fn should_dm(&self, notification_type: &NotificationType) -> bool {
+        self.is_enabled(notification_type)
+            || (self.dm_for_urgent && notification_type.is_urgent() && !self.is_muted())
     }
 
     pub fn mute(&mut self, until: DateTime<Utc>) {
@@ -524,6 +587,29 @@ pub enum DigestMode {
     Daily,
 }
 
+impl DigestMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Instant => "instant",
+            Self::Hourly => "hourly",
+            Self::Daily => "daily",
+        }
+    }
+}
+
+impl std::str::FromStr for DigestMode {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "instant" => Ok(Self::Instant),
+            "hourly" => Ok(Self::Hourly),
+            "daily" => Ok(Self::Daily),
+            _ => Err(crate::Error::Other(format!("Invalid digest mode: {}", s))),
+        }
+    }
+}
+
 /// Notification template
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationTemplate {
@@ -709,6 +795,7 @@ pub mod templates {
         .with_blocks(blocks)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn pr_created_message(
         pr_number: i32,
         title: &str,
@@ -784,6 +871,17 @@ mod tests {
         assert_eq!(config.get_channel(&NotificationType::DeploymentStarted), "#deployments");
         assert_eq!(config.get_channel(&NotificationType::AlertFired), "#alerts");
         assert_eq!(config.get_channel(&NotificationType::AgentCompleted), "#orchestrate");
+        assert_eq!(config.get_google_chat_webhook(&NotificationType::AlertFired), None);
+
+        let config = config.with_google_chat_webhook(
+            NotificationType::AlertFired,
+            "https://chat.googleapis.com/v1/spaces/AAA/messages?key=k&token=t",
+        );
+        assert!(config
+            .get_google_chat_webhook(&NotificationType::AlertFired)
+            .unwrap()
+            .contains("/spaces/AAA/"));
+        assert_eq!(config.get_channel(&NotificationType::AlertFired), "#alerts");
     }
 
     #[test]
@@ -0,0 +1,331 @@
+//! Slack Web API Client
+//!
+//! A small client for the Slack Web API methods Orchestrate uses:
+//! - `auth.test`: identify the workspace and bot user behind a token
+//! - `chat.postMessage`: send messages with blocks, to channels, DMs, and threads
+//! - `chat.update`: edit a message, e.g. once an approval has been decided
+//! - `users.lookupByEmail`: find the Slack user for an email address
+//!
+//! Slack reports failures as HTTP 200 with `"ok": false`; those are surfaced
+//! as errors carrying Slack's error code (e.g. `channel_not_found`).
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::slack::{SentMessage, SlackMessage};
+use crate::{Error, Result};
+
+/// Base URL of the Slack Web API
+pub const SLACK_API_BASE_URL: &str = "https://slack.com/api";
+
+/// Workspace and bot identity returned by `auth.test`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackAuthInfo {
+    pub team_id: String,
+    pub team: String,
+    pub user_id: String,
+    #[serde(default)]
+    pub bot_id: Option<String>,
+}
+
+/// Slack user returned by `users.lookupByEmail`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackUser {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub real_name: Option<String>,
+}
+
+/// Slack Web API client authenticated with a bot token
+#[derive(Debug, Clone)]
+pub struct SlackApiClient {
+    http_client: reqwest::Client,
+    token: String,
+    base_url: String,
+}
+
+impl SlackApiClient {
+    /// Create a client with a 10 second request timeout
+    pub fn new(token: impl Into<String>) -> Self {
+        Self::with_http_client(
+            reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            token,
+        )
+    }
+
+    /// Create a client sharing an existing HTTP client
+    pub fn with_http_client(http_client: reqwest::Client, token: impl Into<String>) -> Self {
+        Self {
+            http_client,
+            token: token.into(),
+            base_url: SLACK_API_BASE_URL.to_string(),
+        }
+    }
+
+    /// Send requests to a different API base URL
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Identify the workspace and bot user behind the token
+    pub async fn auth_test(&self) -> Result<SlackAuthInfo> {
+        let response = self.call("auth.test", self.post("auth.test")).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Post a message to a channel, DM (user ID as channel), or thread
+    pub async fn post_message(&self, message: &SlackMessage) -> Result<SentMessage> {
+        let body = message_body(message)?;
+        let response = self
+            .call(
+                "chat.postMessage",
+                self.post("chat.postMessage").json(&body),
+            )
+            .await?;
+
+        let channel = response
+            .get("channel")
+            .and_then(Value::as_str)
+            .unwrap_or(&message.channel)
+            .to_string();
+        let ts = response
+            .get("ts")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::Other("Slack chat.postMessage response has no ts".to_string()))?
+            .to_string();
+        let message_id = response
+            .pointer("/message/client_msg_id")
+            .and_then(Value::as_str)
+            .unwrap_or(&ts)
+            .to_string();
+
+        Ok(SentMessage {
+            ok: true,
+            channel,
+            ts,
+            message_id,
+        })
+    }
+
+    /// Replace the text and blocks of a message posted earlier
+    pub async fn update_message(&self, ts: &str, message: &SlackMessage) -> Result<()> {
+        let mut body = message_body(message)?;
+        body["ts"] = Value::String(ts.to_string());
+        self.call("chat.update", self.post("chat.update").json(&body))
+            .await?;
+        Ok(())
+    }
+
+    /// Find the Slack user with an email address, or `None` if there is none
+    pub async fn lookup_user_by_email(&self, email: &str) -> Result<Option<SlackUser>> {
+        let request = self
+            .http_client
+            .get(self.url("users.lookupByEmail"))
+            .bearer_auth(&self.token)
+            .query(&[("email", email)]);
+
+        match self.call("users.lookupByEmail", request).await {
+            Ok(response) => {
+                let user = response.get("user").cloned().ok_or_else(|| {
+                    Error::Other("Slack users.lookupByEmail response has no user".to_string())
+                })?;
+                Ok(Some(serde_json::from_value(user)?))
+            }
+            Err(Error::Other(message)) if message.ends_with(": users_not_found") => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn url(&self, method: &str) -> String {
+        format!("{}/{}", self.base_url, method)
+    }
+
+    fn post(&self, method: &str) -> reqwest::RequestBuilder {
+        self.http_client
+            .post(self.url(method))
+            .bearer_auth(&self.token)
+    }
+
+    /// Send a request and return the response body if Slack reports `ok`
+    async fn call(&self, method: &str, request: reqwest::RequestBuilder) -> Result<Value> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Other(format!("Slack {} request failed: {}", method, e)))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("unknown");
+            return Err(Error::Other(format!(
+                "Slack {} rate limited, retry after {}s",
+                method, retry_after
+            )));
+        }
+        if !status.is_success() {
+            return Err(Error::Other(format!(
+                "Slack {} returned HTTP {}",
+                method, status
+            )));
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| Error::Other(format!("Invalid Slack {} response: {}", method, e)))?;
+
+        if body.get("ok").and_then(Value::as_bool) == Some(true) {
+            Ok(body)
+        } else {
+            let error = body
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown_error");
+            Err(Error::Other(format!("Slack {} failed: {}", method, error)))
+        }
+    }
+}
+
+/// Serialize a message for `chat.postMessage`, dropping unset fields and empty blocks
+fn message_body(message: &SlackMessage) -> Result<Value> {
+    let mut body = serde_json::to_value(message)?;
+    strip_nulls(&mut body);
+    if let Some(object) = body.as_object_mut() {
+        if object
+            .get("blocks")
+            .and_then(Value::as_array)
+            .is_some_and(|blocks| blocks.is_empty())
+        {
+            object.remove("blocks");
+        }
+    }
+    Ok(body)
+}
+
+/// Remove null object fields, which Block Kit rejects
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|_, v| !v.is_null());
+            object.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slack::{SlackBlock, SlackText};
+    use crate::test_servers::{json_body, serve};
+
+    #[test]
+    fn test_message_body_drops_unset_fields() {
+        let message =
+            SlackMessage::new("#general", "Hello").with_blocks(vec![SlackBlock::Section {
+                text: SlackText::mrkdwn("*Hello*"),
+                accessory: None,
+                fields: None,
+            }]);
+
+        let body = message_body(&message).unwrap();
+        assert_eq!(body["channel"], "#general");
+        assert!(body.get("thread_ts").is_none());
+        assert!(body["blocks"][0].get("accessory").is_none());
+        assert_eq!(body["blocks"][0]["text"]["type"], "mrkdwn");
+
+        let body = message_body(&SlackMessage::new("#general", "Hello")).unwrap();
+        assert!(body.get("blocks").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_post_message_in_thread() {
+        let (url, server) = serve(vec![(200, r#"{"ok":true,"channel":"C123","ts":"1700000000.000200","message":{"client_msg_id":"m-1"}}"#)])
+        .await;
+        let client = SlackApiClient::new("xoxb-test").with_base_url(format!("{}/api", url));
+
+        let message = SlackMessage::new("#prs", "CI passed").in_thread("1700000000.000100");
+        let sent = client.post_message(&message).await.unwrap();
+        assert_eq!(sent.channel, "C123");
+        assert_eq!(sent.ts, "1700000000.000200");
+        assert_eq!(sent.message_id, "m-1");
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("POST /api/chat.postMessage"));
+        assert!(requests[0]
+            .to_ascii_lowercase()
+            .contains("authorization: bearer xoxb-test"));
+        let body = json_body(&requests[0]);
+        assert_eq!(body["channel"], "#prs");
+        assert_eq!(body["thread_ts"], "1700000000.000100");
+    }
+
+    #[tokio::test]
+    async fn test_update_message() {
+        let (url, server) = serve(vec![(200, r#"{"ok":true,"channel":"C123","ts":"1.2"}"#)]).await;
+        let client = SlackApiClient::new("xoxb-test").with_base_url(format!("{}/api", url));
+
+        client
+            .update_message("1.2", &SlackMessage::new("C123", "Approved"))
+            .await
+            .unwrap();
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("POST /api/chat.update"));
+        let body = json_body(&requests[0]);
+        assert_eq!(body["ts"], "1.2");
+        assert_eq!(body["text"], "Approved");
+    }
+
+    #[tokio::test]
+    async fn test_api_errors_are_reported() {
+        let (url, _server) =
+            serve(vec![(200, r#"{"ok":false,"error":"channel_not_found"}"#)]).await;
+        let client = SlackApiClient::new("xoxb-test").with_base_url(format!("{}/api", url));
+
+        let err = client
+            .post_message(&SlackMessage::new("#missing", "Hello"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("channel_not_found"));
+    }
+
+    #[tokio::test]
+    async fn test_lookup_user_by_email() {
+        let (url, server) = serve(vec![
+            (
+                200,
+                r#"{"ok":true,"user":{"id":"U123","name":"jane","real_name":"Jane Doe"}}"#,
+            ),
+            (200, r#"{"ok":false,"error":"users_not_found"}"#),
+        ])
+        .await;
+        let client = SlackApiClient::new("xoxb-test").with_base_url(format!("{}/api", url));
+
+        let user = client
+            .lookup_user_by_email("jane@example.com")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(user.id, "U123");
+        assert_eq!(user.real_name.as_deref(), Some("Jane Doe"));
+        assert!(client
+            .lookup_user_by_email("nobody@example.com")
+            .await
+            .unwrap()
+            .is_none());
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("GET /api/users.lookupByEmail?email=jane%40example.com"));
+    }
+}
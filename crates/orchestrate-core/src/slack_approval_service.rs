@@ -0,0 +1,517 @@
+//! Slack Interactive Approvals
+//!
+//! Story 5: Interactive Approvals
+//! - Post pending approval requests with approve/reject buttons and a
+//!   delegate picker, mentioning the approvers mapped to Slack users
+//! - Record button clicks as decisions on the approval request, as the
+//!   GitHub user mapped to the Slack user who clicked
+//! - Edit the posted message to show progress and the final decision
+//! - Delegate to another mapped user, who is sent the request by DM
+
+use crate::{
+    approval::{ApprovalRequest, ApprovalStatus},
+    approval_service::ApprovalService,
+    database::parse_datetime,
+    error::{Error, Result},
+    slack::{
+        ApprovalDecision, InteractionPayload, NotificationType, SlackApprovalRequest, SlackBlock,
+        SlackContextElement, SlackMessage,
+    },
+    slack_interactions::ApprovalButtonHandler,
+    slack_service::SlackService,
+    slack_user_service::SlackUserService,
+    Database,
+};
+use chrono::Utc;
+use sqlx::Row;
+
+/// Service connecting approval requests to interactive Slack messages
+pub struct SlackApprovalService {
+    db: Database,
+    slack_service: SlackService,
+    user_service: SlackUserService,
+    handler: ApprovalButtonHandler,
+}
+
+impl SlackApprovalService {
+    /// Create a new Slack approval service
+    pub fn new(db: Database, slack_service: SlackService) -> Self {
+        let user_service = SlackUserService::new(db.clone(), SlackService::new(db.clone()));
+        Self {
+            db,
+            slack_service,
+            user_service,
+            handler: ApprovalButtonHandler::new(),
+        }
+    }
+
+    /// Post an interactive message for a pending approval request
+    ///
+    /// The message goes to the channel configured for approval
+    /// notifications. Returns `None` when no Slack workspace is connected.
+    pub async fn post_approval(
+        &self,
+        approval: &ApprovalRequest,
+        resource_type: &str,
+        resource_id: &str,
+        description: &str,
+    ) -> Result<Option<SlackApprovalRequest>> {
+        let Some(connection) = self.slack_service.get_active_connection().await? else {
+            return Ok(None);
+        };
+        let approval_id = approval
+            .id
+            .ok_or_else(|| Error::Other("Approval request has no ID".to_string()))?;
+
+        let mut approvers = Vec::new();
+        for approver in approval.required_approvers.split(',') {
+            approvers.push(match self.user_service.get_slack_user_id(approver).await? {
+                Some(slack_user_id) => format!("<@{}>", slack_user_id),
+                None => approver.to_string(),
+            });
+        }
+        let description = format!("{}\n\nApprovers: {}", description, approvers.join(", "));
+
+        let mut request = SlackApprovalRequest::new(
+            approval_id.to_string(),
+            resource_type,
+            resource_id,
+            description,
+        );
+        request.requester_slack_id = connection.bot_user_id.clone();
+
+        let message = self.handler.create_approval_message(&request, "");
+        let sent = self
+            .slack_service
+            .send_notification(NotificationType::ApprovalRequired, message, None, None)
+            .await?;
+        request.channel_id = sent.channel;
+        request.message_ts = sent.ts;
+
+        sqlx::query(
+            r#"
+            INSERT INTO slack_approval_requests (
+                id, approval_id, connection_id, channel_id, message_ts, requester_slack_id,
+                resource_type, resource_id, description, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&request.id)
+        .bind(approval_id)
+        .bind(&connection.id)
+        .bind(&request.channel_id)
+        .bind(&request.message_ts)
+        .bind(&request.requester_slack_id)
+        .bind(&request.resource_type)
+        .bind(&request.resource_id)
+        .bind(&request.description)
+        .bind(request.created_at)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(Some(request))
+    }
+
+    /// Get a posted approval message by its ID
+    pub async fn get_request(&self, id: &str) -> Result<Option<SlackApprovalRequest>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, approval_id, channel_id, message_ts, requester_slack_id, resource_type,
+                   resource_id, description, created_at, responded_at, responder_slack_id,
+                   decision
+            FROM slack_approval_requests
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        Ok(Some(SlackApprovalRequest {
+            id: row.try_get("id")?,
+            approval_id: row.try_get::<i64, _>("approval_id")?.to_string(),
+            channel_id: row.try_get("channel_id")?,
+            message_ts: row.try_get("message_ts")?,
+            requester_slack_id: row.try_get("requester_slack_id")?,
+            resource_type: row.try_get("resource_type")?,
+            resource_id: row.try_get("resource_id")?,
+            description: row.try_get("description")?,
+            created_at: parse_datetime(&row.try_get::<String, _>("created_at")?)?,
+            responded_at: row
+                .try_get::<Option<String>, _>("responded_at")?
+                .map(|at| parse_datetime(&at))
+                .transpose()?,
+            responder_slack_id: row.try_get("responder_slack_id")?,
+            decision: row
+                .try_get::<Option<String>, _>("decision")?
+                .map(|decision| serde_json::from_value(serde_json::Value::String(decision)))
+                .transpose()?,
+        }))
+    }
+
+    /// Apply a click on an approval message's buttons or delegate picker
+    ///
+    /// Returns the updated approval request, whose run can be resumed once
+    /// it is decided, or `None` for actions on other messages. Errors (e.g.
+    /// the user is not an approver) should be shown to the user who clicked.
+    pub async fn handle_interaction(
+        &self,
+        payload: &InteractionPayload,
+    ) -> Result<Option<ApprovalRequest>> {
+        let action = payload
+            .actions
+            .first()
+            .ok_or_else(|| Error::Other("No action in payload".to_string()))?;
+        let Some((kind, request_id)) = action
+            .action_id
+            .split_once('_')
+            .filter(|(kind, _)| matches!(*kind, "approve" | "reject" | "delegate"))
+        else {
+            return Ok(None);
+        };
+
+        let mut request = self.get_request(request_id).await?.ok_or_else(|| {
+            Error::Other(format!("Unknown Slack approval request: {}", request_id))
+        })?;
+        if !request.is_pending() {
+            return Err(Error::Other(
+                "This approval request has already been decided".to_string(),
+            ));
+        }
+        let approval_id = request
+            .approval_id
+            .parse::<i64>()
+            .map_err(|_| Error::Other(format!("Invalid approval ID: {}", request.approval_id)))?;
+
+        // Approvers are GitHub users; unmapped Slack users act as their Slack username
+        let approver = self
+            .user_service
+            .get_github_username(&payload.user.id)
+            .await?
+            .unwrap_or_else(|| payload.user.username.clone());
+        let approval_service = ApprovalService::new(self.db.clone());
+
+        if kind == "delegate" {
+            let delegate_slack_id = action
+                .selected_user
+                .as_deref()
+                .ok_or_else(|| Error::Other("No user selected to delegate to".to_string()))?;
+            let delegate = self
+                .user_service
+                .get_github_username(delegate_slack_id)
+                .await?
+                .ok_or_else(|| {
+                    Error::Other(format!(
+                        "<@{}> is not mapped to a GitHub user",
+                        delegate_slack_id
+                    ))
+                })?;
+
+            let approval = approval_service
+                .delegate(approval_id, approver, delegate)
+                .await?;
+
+            let note = format!(
+                "Delegated by <@{}> to <@{}>",
+                payload.user.id, delegate_slack_id
+            );
+            self.update_pending_message(&request, &note).await?;
+
+            // The delegate can decide from the DM; clicks there update the channel message
+            let dm = self
+                .handler
+                .create_approval_message(&request, delegate_slack_id);
+            self.slack_service
+                .send_direct_message(delegate_slack_id, NotificationType::ApprovalRequired, dm)
+                .await?;
+            return Ok(Some(approval));
+        }
+
+        let response = self.handler.handle_button_click(payload)?;
+        let approval = match response.decision {
+            ApprovalDecision::Approved => {
+                approval_service
+                    .approve(approval_id, approver, None)
+                    .await?
+            }
+            _ => approval_service.reject(approval_id, approver, None).await?,
+        };
+
+        if approval.status.is_terminal() {
+            let decision = if approval.status == ApprovalStatus::Approved {
+                ApprovalDecision::Approved
+            } else {
+                ApprovalDecision::Rejected
+            };
+            request.respond(&payload.user.id, decision);
+            self.save_response(&request).await?;
+
+            let mut message = self.handler.create_response_message(
+                &request,
+                &response,
+                &format!("<@{}>", payload.user.id),
+            );
+            message.channel = request.channel_id.clone();
+            self.slack_service
+                .update_message(&request.message_ts, &message)
+                .await?;
+        } else {
+            let note = format!(
+                "{} by <@{}> ({}/{} approvals)",
+                if response.decision == ApprovalDecision::Approved {
+                    "Approved"
+                } else {
+                    "Rejected"
+                },
+                payload.user.id,
+                approval.approval_count,
+                approval.required_count
+            );
+            self.update_pending_message(&request, &note).await?;
+        }
+
+        Ok(Some(approval))
+    }
+
+    /// Report an error to the user who clicked
+    pub async fn report_error(&self, payload: &InteractionPayload, error: &Error) -> Result<()> {
+        self.slack_service
+            .respond_ephemeral(&payload.response_url, &error.to_string())
+            .await
+    }
+
+    /// Edit a still-pending approval message, keeping its buttons and adding a note
+    async fn update_pending_message(
+        &self,
+        request: &SlackApprovalRequest,
+        note: &str,
+    ) -> Result<()> {
+        let mut message: SlackMessage = self
+            .handler
+            .create_approval_message(request, &request.channel_id);
+        // Notes go above the divider and buttons
+        let at = message.blocks.len().saturating_sub(2);
+        message.blocks.insert(
+            at,
+            SlackBlock::Context {
+                elements: vec![SlackContextElement::Mrkdwn {
+                    text: format!("{} at {}", note, Utc::now().format("%Y-%m-%d %H:%M UTC")),
+                }],
+            },
+        );
+        self.slack_service
+            .update_message(&request.message_ts, &message)
+            .await
+    }
+
+    async fn save_response(&self, request: &SlackApprovalRequest) -> Result<()> {
+        let decision = request
+            .decision
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?
+            .and_then(|decision| decision.as_str().map(str::to_string));
+
+        sqlx::query(
+            r#"
+            UPDATE slack_approval_requests
+            SET responded_at = ?, responder_slack_id = ?, decision = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(request.responded_at)
+        .bind(&request.responder_slack_id)
+        .bind(decision)
+        .bind(&request.id)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slack::{
+        InteractionAction, InteractionChannel, InteractionType, InteractionUser, SlackConnection,
+    };
+    use crate::{Pipeline, PipelineRun, PipelineStage};
+
+    async fn setup() -> (Database, SlackApprovalService, ApprovalRequest) {
+        let db = Database::in_memory().await.unwrap();
+        let slack_service = SlackService::new_for_testing(db.clone());
+        slack_service
+            .save_connection(&SlackConnection::new("T123", "Test Team", "xoxb-test"))
+            .await
+            .unwrap();
+        let users = SlackUserService::new(db.clone(), SlackService::new_for_testing(db.clone()));
+        users.map_user("alice", "UALICE", "alice").await.unwrap();
+        users.map_user("bob", "UBOB", "bob").await.unwrap();
+        users.map_user("carol", "UCAROL", "carol").await.unwrap();
+
+        let pipeline = Pipeline::new("deploy".to_string(), "name: deploy".to_string());
+        let pipeline_id = db.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = db
+            .insert_pipeline_run(&PipelineRun::new(pipeline_id, None))
+            .await
+            .unwrap();
+        let stage_id = db
+            .insert_pipeline_stage(&PipelineStage::new(run_id, "production".to_string()))
+            .await
+            .unwrap();
+        let approval = ApprovalService::new(db.clone())
+            .create_approval(
+                stage_id,
+                run_id,
+                vec!["alice".to_string(), "bob".to_string()],
+                2,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let service = SlackApprovalService::new(db.clone(), slack_service);
+        (db, service, approval)
+    }
+
+    fn click(action_id: String, user_id: &str, selected_user: Option<&str>) -> InteractionPayload {
+        InteractionPayload {
+            interaction_type: InteractionType::BlockActions,
+            trigger_id: "trigger".to_string(),
+            user: InteractionUser {
+                id: user_id.to_string(),
+                name: user_id.to_lowercase(),
+                username: user_id.to_lowercase(),
+            },
+            channel: InteractionChannel {
+                id: "C123".to_string(),
+                name: "approvals".to_string(),
+            },
+            message: None,
+            actions: vec![InteractionAction {
+                action_id,
+                block_id: None,
+                value: None,
+                action_type: "button".to_string(),
+                selected_user: selected_user.map(str::to_string),
+            }],
+            response_url: "https://hooks.slack.com/actions/1".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_post_approval_mentions_mapped_approvers() {
+        let (_, service, approval) = setup().await;
+
+        let request = service
+            .post_approval(&approval, "pipeline stage", "production", "Deploy v1.2")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.approval_id, approval.id.unwrap().to_string());
+        assert!(request.description.contains("<@UALICE>, <@UBOB>"));
+        assert!(!request.message_ts.is_empty());
+
+        let stored = service.get_request(&request.id).await.unwrap().unwrap();
+        assert_eq!(stored.channel_id, request.channel_id);
+        assert!(stored.is_pending());
+    }
+
+    #[tokio::test]
+    async fn test_post_approval_without_connection() {
+        let db = Database::in_memory().await.unwrap();
+        let service = SlackApprovalService::new(db.clone(), SlackService::new_for_testing(db));
+        let mut approval = ApprovalRequest::new(1, 1, "alice".to_string(), 1, None, None);
+        approval.id = Some(1);
+
+        assert!(service
+            .post_approval(&approval, "pipeline stage", "production", "Deploy")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_clicks_decide_the_approval() {
+        let (_, service, approval) = setup().await;
+        let request = service
+            .post_approval(&approval, "pipeline stage", "production", "Deploy v1.2")
+            .await
+            .unwrap()
+            .unwrap();
+
+        let updated = service
+            .handle_interaction(&click(format!("approve_{}", request.id), "UALICE", None))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.status, ApprovalStatus::Pending);
+        assert_eq!(updated.approval_count, 1);
+
+        // Only mapped approvers may decide
+        let err = service
+            .handle_interaction(&click(format!("approve_{}", request.id), "UCAROL", None))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not an authorized approver"));
+
+        let updated = service
+            .handle_interaction(&click(format!("approve_{}", request.id), "UBOB", None))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.status, ApprovalStatus::Approved);
+
+        let stored = service.get_request(&request.id).await.unwrap().unwrap();
+        assert_eq!(stored.decision, Some(ApprovalDecision::Approved));
+        assert_eq!(stored.responder_slack_id.as_deref(), Some("UBOB"));
+
+        let err = service
+            .handle_interaction(&click(format!("reject_{}", request.id), "UALICE", None))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already been decided"));
+
+        // Buttons on other messages are not approval actions
+        assert!(service
+            .handle_interaction(&click("view_agent".to_string(), "UALICE", None))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delegate_to_mapped_user() {
+        let (_, service, approval) = setup().await;
+        let request = service
+            .post_approval(&approval, "pipeline stage", "production", "Deploy v1.2")
+            .await
+            .unwrap()
+            .unwrap();
+
+        let updated = service
+            .handle_interaction(&click(
+                format!("delegate_{}", request.id),
+                "UBOB",
+                Some("UCAROL"),
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.status, ApprovalStatus::Delegated);
+        assert_eq!(updated.required_approvers, "alice,carol");
+
+        let updated = service
+            .handle_interaction(&click(format!("reject_{}", request.id), "UCAROL", None))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.status, ApprovalStatus::Rejected);
+    }
+}
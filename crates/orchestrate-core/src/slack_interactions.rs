@@ -1,9 +1,10 @@
 //! Slack Interactive Features
 //!
 //! This module provides interactive Slack features including:
-//! - Interactive approval buttons
-//! - Slash command handling
+//! - Interactive approval buttons, with a user picker to delegate
 //! - Thread-based PR discussions
+//!
+//! Slash commands are served by the web crate's `/api/slack/commands` endpoint.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -11,7 +12,7 @@ use std::collections::HashMap;
 
 use crate::slack::{
     ApprovalDecision, InteractionPayload, SlackApprovalRequest, SlackBlock, SlackElement,
-    SlackMessage, SlackText, SlashCommand, SlashCommandResponse, ButtonStyle, ResponseType,
+    SlackMessage, SlackText, ButtonStyle,
 };
 use crate::{Error, Result};
 
@@ -85,6 +86,10 @@ impl ApprovalButtonHandler {
                         style: Some(ButtonStyle::Danger),
                         url: None,
                     },
+                    SlackElement::UsersSelect {
+                        action_id: format!("delegate_{}", request.id),
+                        placeholder: SlackText::plain("Delegate to..."),
+                    },
                 ],
             },
         ];
@@ -188,185 +193,6 @@ pub struct ApprovalResponse {
     pub timestamp: DateTime<Utc>,
 }
 
-/// Slash command handler
-#[derive(Debug, Clone)]
-pub struct SlashCommandHandler {
-    /// Base URL for dashboard links
-    dashboard_url: String,
-}
-
-impl SlashCommandHandler {
-    /// Create a new slash command handler
-    pub fn new(dashboard_url: String) -> Self {
-        Self { dashboard_url }
-    }
-
-    /// Handle a slash command
-    pub fn handle_command(&self, cmd: &SlashCommand) -> Result<SlashCommandResponse> {
-        let args = cmd.parse_args();
-
-        if args.is_empty() {
-            return Ok(self.help_response());
-        }
-
-        match args[0].as_str() {
-            "status" => self.status_command(),
-            "agents" => self.agents_command(),
-            "pr" => self.pr_command(&args),
-            "deploy" => self.deploy_command(&args),
-            "approve" => self.approve_command(&args),
-            "help" => Ok(self.help_response()),
-            _ => Ok(SlashCommandResponse::ephemeral(
-                format!("Unknown command: {}. Type `/orchestrate help` for available commands.", args[0])
-            )),
-        }
-    }
-
-    /// Status command - show system status
-    fn status_command(&self) -> Result<SlashCommandResponse> {
-        let blocks = vec![
-            SlackBlock::Section {
-                text: SlackText::mrkdwn("*System Status*\n\nAll systems operational"),
-                accessory: None,
-                fields: Some(vec![
-                    SlackText::mrkdwn("*Agents:* 3 running"),
-                    SlackText::mrkdwn("*Queue:* 2 pending"),
-                    SlackText::mrkdwn("*PRs:* 5 open"),
-                    SlackText::mrkdwn("*Deployments:* 1 in progress"),
-                ]),
-            },
-            SlackBlock::Actions {
-                elements: vec![
-                    SlackElement::Button {
-                        text: SlackText::plain("View Dashboard"),
-                        action_id: "view_dashboard".to_string(),
-                        value: None,
-                        style: Some(ButtonStyle::Primary),
-                        url: Some(self.dashboard_url.clone()),
-                    },
-                ],
-            },
-        ];
-
-        Ok(SlashCommandResponse::ephemeral("System status").with_blocks(blocks))
-    }
-
-    /// Agents command - list running agents
-    fn agents_command(&self) -> Result<SlashCommandResponse> {
-        let blocks = vec![
-            SlackBlock::Section {
-                text: SlackText::mrkdwn("*Running Agents*"),
-                accessory: None,
-                fields: None,
-            },
-            SlackBlock::Section {
-                text: SlackText::mrkdwn(
-                    "• story-developer - Implementing user authentication\n\
-                     • pr-reviewer - Reviewing PR #123\n\
-                     • deployment-agent - Deploying to staging"
-                ),
-                accessory: None,
-                fields: None,
-            },
-        ];
-
-        Ok(SlashCommandResponse::ephemeral("Running agents").with_blocks(blocks))
-    }
-
-    /// PR command - get PR status
-    fn pr_command(&self, args: &[String]) -> Result<SlashCommandResponse> {
-        if args.len() < 2 {
-            return Ok(SlashCommandResponse::ephemeral(
-                "Usage: /orchestrate pr <number>"
-            ));
-        }
-
-        let pr_number = &args[1];
-
-        let blocks = vec![
-            SlackBlock::Section {
-                text: SlackText::mrkdwn(format!("*PR #{}*\n\nStatus: Open\nCI: Passing\nReviews: 1/2", pr_number)),
-                accessory: None,
-                fields: None,
-            },
-            SlackBlock::Actions {
-                elements: vec![
-                    SlackElement::Button {
-                        text: SlackText::plain("View PR"),
-                        action_id: "view_pr".to_string(),
-                        value: Some(pr_number.clone()),
-                        style: Some(ButtonStyle::Primary),
-                        url: Some(format!("{}/pr/{}", self.dashboard_url, pr_number)),
-                    },
-                ],
-            },
-        ];
-
-        Ok(SlashCommandResponse::ephemeral(format!("PR #{} status", pr_number)).with_blocks(blocks))
-    }
-
-    /// Deploy command - trigger deployment
-    fn deploy_command(&self, args: &[String]) -> Result<SlashCommandResponse> {
-        if args.len() < 2 {
-            return Ok(SlashCommandResponse::ephemeral(
-                "Usage: /orchestrate deploy <environment>"
-            ));
-        }
-
-        let environment = &args[1];
-
-        if !["dev", "staging", "production"].contains(&environment.as_str()) {
-            return Ok(SlashCommandResponse::ephemeral(
-                "Invalid environment. Must be: dev, staging, or production"
-            ));
-        }
-
-        Ok(SlashCommandResponse::in_channel(
-            format!("Deployment to {} triggered by <@{{user_id}}>", environment)
-        ))
-    }
-
-    /// Approve command - approve from Slack
-    fn approve_command(&self, args: &[String]) -> Result<SlashCommandResponse> {
-        if args.len() < 2 {
-            return Ok(SlashCommandResponse::ephemeral(
-                "Usage: /orchestrate approve <approval-id>"
-            ));
-        }
-
-        let approval_id = &args[1];
-
-        Ok(SlashCommandResponse::ephemeral(
-            format!("Approval {} has been approved", approval_id)
-        ))
-    }
-
-    /// Help response
-    fn help_response(&self) -> SlashCommandResponse {
-        let blocks = vec![
-            SlackBlock::Section {
-                text: SlackText::mrkdwn("*Available Commands*"),
-                accessory: None,
-                fields: None,
-            },
-            SlackBlock::Section {
-                text: SlackText::mrkdwn(
-                    "• `/orchestrate status` - Show system status\n\
-                     • `/orchestrate agents` - List running agents\n\
-                     • `/orchestrate pr <number>` - Get PR status\n\
-                     • `/orchestrate deploy <env>` - Trigger deployment\n\
-                     • `/orchestrate approve <id>` - Approve request\n\
-                     • `/orchestrate help` - Show this help"
-                ),
-                accessory: None,
-                fields: None,
-            },
-        ];
-
-        SlashCommandResponse::ephemeral("Orchestrate Commands").with_blocks(blocks)
-    }
-}
-
 /// Thread manager for PR discussions
 #[derive(Debug, Clone)]
 pub struct PrThreadManager {
@@ -442,6 +268,7 @@ impl PrThreadManager {
         thread_ts: &str,
     ) -> SlackMessage {
         let (emoji, text) = match conclusion {
+            _ if status != "completed" => ("⏳", "CI checks running"),
             "success" => ("✅", "CI checks passed"),
             "failure" => ("❌", "CI checks failed"),
             "pending" => ("⏳", "CI checks running"),
@@ -534,6 +361,7 @@ mod tests {
                     block_id: None,
                     value: Some("approval-123".to_string()),
                     action_type: "button".to_string(),
+                    selected_user: None,
                 },
             ],
             response_url: "https://hooks.slack.com/...".to_string(),
@@ -569,6 +397,7 @@ mod tests {
                     block_id: None,
                     value: Some("approval-123".to_string()),
                     action_type: "button".to_string(),
+                    selected_user: None,
                 },
             ],
             response_url: "https://hooks.slack.com/...".to_string(),
@@ -580,160 +409,6 @@ mod tests {
         assert_eq!(response.decision, ApprovalDecision::Rejected);
     }
 
-    #[test]
-    fn test_slash_command_handler_status() {
-        let handler = SlashCommandHandler::new("https://dashboard.example.com".to_string());
-
-        let cmd = SlashCommand {
-            command: "/orchestrate".to_string(),
-            text: "status".to_string(),
-            response_url: "https://hooks.slack.com/...".to_string(),
-            trigger_id: "trigger123".to_string(),
-            user_id: "U123".to_string(),
-            user_name: "testuser".to_string(),
-            channel_id: "C123".to_string(),
-            channel_name: "general".to_string(),
-            team_id: "T123".to_string(),
-        };
-
-        let response = handler.handle_command(&cmd).unwrap();
-
-        assert_eq!(response.response_type, ResponseType::Ephemeral);
-        assert!(response.blocks.is_some());
-    }
-
-    #[test]
-    fn test_slash_command_handler_agents() {
-        let handler = SlashCommandHandler::new("https://dashboard.example.com".to_string());
-
-        let cmd = SlashCommand {
-            command: "/orchestrate".to_string(),
-            text: "agents".to_string(),
-            response_url: "https://hooks.slack.com/...".to_string(),
-            trigger_id: "trigger123".to_string(),
-            user_id: "U123".to_string(),
-            user_name: "testuser".to_string(),
-            channel_id: "C123".to_string(),
-            channel_name: "general".to_string(),
-            team_id: "T123".to_string(),
-        };
-
-        let response = handler.handle_command(&cmd).unwrap();
-
-        assert_eq!(response.response_type, ResponseType::Ephemeral);
-        assert!(response.blocks.is_some());
-    }
-
-    #[test]
-    fn test_slash_command_handler_pr() {
-        let handler = SlashCommandHandler::new("https://dashboard.example.com".to_string());
-
-        let cmd = SlashCommand {
-            command: "/orchestrate".to_string(),
-            text: "pr 123".to_string(),
-            response_url: "https://hooks.slack.com/...".to_string(),
-            trigger_id: "trigger123".to_string(),
-            user_id: "U123".to_string(),
-            user_name: "testuser".to_string(),
-            channel_id: "C123".to_string(),
-            channel_name: "general".to_string(),
-            team_id: "T123".to_string(),
-        };
-
-        let response = handler.handle_command(&cmd).unwrap();
-
-        assert_eq!(response.response_type, ResponseType::Ephemeral);
-        assert!(response.text.contains("123"));
-    }
-
-    #[test]
-    fn test_slash_command_handler_deploy() {
-        let handler = SlashCommandHandler::new("https://dashboard.example.com".to_string());
-
-        let cmd = SlashCommand {
-            command: "/orchestrate".to_string(),
-            text: "deploy staging".to_string(),
-            response_url: "https://hooks.slack.com/...".to_string(),
-            trigger_id: "trigger123".to_string(),
-            user_id: "U123".to_string(),
-            user_name: "testuser".to_string(),
-            channel_id: "C123".to_string(),
-            channel_name: "general".to_string(),
-            team_id: "T123".to_string(),
-        };
-
-        let response = handler.handle_command(&cmd).unwrap();
-
-        assert_eq!(response.response_type, ResponseType::InChannel);
-        assert!(response.text.contains("staging"));
-    }
-
-    #[test]
-    fn test_slash_command_handler_deploy_invalid_env() {
-        let handler = SlashCommandHandler::new("https://dashboard.example.com".to_string());
-
-        let cmd = SlashCommand {
-            command: "/orchestrate".to_string(),
-            text: "deploy invalid".to_string(),
-            response_url: "https://hooks.slack.com/...".to_string(),
-            trigger_id: "trigger123".to_string(),
-            user_id: "U123".to_string(),
-            user_name: "testuser".to_string(),
-            channel_id: "C123".to_string(),
-            channel_name: "general".to_string(),
-            team_id: "T123".to_string(),
-        };
-
-        let response = handler.handle_command(&cmd).unwrap();
-
-        assert_eq!(response.response_type, ResponseType::Ephemeral);
-        assert!(response.text.contains("Invalid environment"));
-    }
-
-    #[test]
-    fn test_slash_command_handler_help() {
-        let handler = SlashCommandHandler::new("https://dashboard.example.com".to_string());
-
-        let cmd = SlashCommand {
-            command: "/orchestrate".to_string(),
-            text: "help".to_string(),
-            response_url: "https://hooks.slack.com/...".to_string(),
-            trigger_id: "trigger123".to_string(),
-            user_id: "U123".to_string(),
-            user_name: "testuser".to_string(),
-            channel_id: "C123".to_string(),
-            channel_name: "general".to_string(),
-            team_id: "T123".to_string(),
-        };
-
-        let response = handler.handle_command(&cmd).unwrap();
-
-        assert_eq!(response.response_type, ResponseType::Ephemeral);
-        assert!(response.text.contains("Commands"));
-    }
-
-    #[test]
-    fn test_slash_command_handler_unknown() {
-        let handler = SlashCommandHandler::new("https://dashboard.example.com".to_string());
-
-        let cmd = SlashCommand {
-            command: "/orchestrate".to_string(),
-            text: "unknown".to_string(),
-            response_url: "https://hooks.slack.com/...".to_string(),
-            trigger_id: "trigger123".to_string(),
-            user_id: "U123".to_string(),
-            user_name: "testuser".to_string(),
-            channel_id: "C123".to_string(),
-            channel_name: "general".to_string(),
-            team_id: "T123".to_string(),
-        };
-
-        let response = handler.handle_command(&cmd).unwrap();
-
-        assert_eq!(response.response_type, ResponseType::Ephemeral);
-        assert!(response.text.contains("Unknown command"));
-    }
-
     #[test]
     fn test_pr_thread_manager_create_thread() {
         let manager = PrThreadManager::new("#prs".to_string());
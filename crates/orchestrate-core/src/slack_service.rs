@@ -13,13 +13,21 @@
 //! - Rate limiting to prevent spam
 //! - Rich message formatting with blocks
 //! - Template support for consistent formatting
+//! - Per-user notification settings for direct messages
+//! - Google Chat cards for notification types routed to a webhook
+//!
+//! Messages are sent through the Slack Web API with the active connection's
+//! bot token (see [`SlackApiClient`]).
 
 use crate::{
+    database::parse_datetime,
     error::{Error, Result},
+    google_chat::{GoogleChatClient, GoogleChatMessage},
     slack::*,
+    slack_api::{SlackApiClient, SlackUser, SLACK_API_BASE_URL},
     AgentId, Database,
 };
-use chrono::{DateTime, Duration, Utc};
+use chrono::{Duration, Utc};
 use serde_json;
 use sqlx::Row;
 use std::collections::HashMap;
@@ -45,6 +53,7 @@ pub struct SlackService {
     db: Database,
     rate_limit_config: RateLimitConfig,
     http_client: Option<reqwest::Client>,
+    api_base_url: String,
 }
 
 impl SlackService {
@@ -53,7 +62,13 @@ impl SlackService {
         Self {
             db,
             rate_limit_config: RateLimitConfig::default(),
-            http_client: Some(reqwest::Client::new()),
+            http_client: Some(
+                reqwest::Client::builder()
+                    .timeout(std::time::Duration::from_secs(10))
+                    .build()
+                    .unwrap_or_default(),
+            ),
+            api_base_url: SLACK_API_BASE_URL.to_string(),
         }
     }
 
@@ -63,6 +78,12 @@ impl SlackService {
         self
     }
 
+    /// Send Slack Web API requests to a different base URL
+    pub fn with_api_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.api_base_url = base_url.into();
+        self
+    }
+
     /// Create a new Slack service for testing (no HTTP client)
     #[cfg(test)]
     pub fn new_for_testing(db: Database) -> Self {
@@ -70,9 +91,25 @@ impl SlackService {
             db,
             rate_limit_config: RateLimitConfig::default(),
             http_client: None,
+            api_base_url: SLACK_API_BASE_URL.to_string(),
         }
     }
 
+    /// API client for a connection, or `None` when testing without HTTP
+    fn api_client(&self, connection: &SlackConnection) -> Option<SlackApiClient> {
+        self.http_client.as_ref().map(|http_client| {
+            SlackApiClient::with_http_client(http_client.clone(), &connection.bot_token)
+                .with_base_url(&self.api_base_url)
+        })
+    }
+
+    /// Get the active connection, failing if there is none
+    async fn require_connection(&self) -> Result<SlackConnection> {
+        self.get_active_connection()
+            .await?
+            .ok_or_else(|| Error::Other("No active Slack connection".to_string()))
+    }
+
     /// Save a Slack connection to the database
     pub async fn save_connection(&self, connection: &SlackConnection) -> Result<()> {
         sqlx::query(
@@ -97,7 +134,7 @@ impl SlackService {
         .bind(&connection.bot_token)
         .bind(&connection.bot_user_id)
         .bind(&connection.app_id)
-        .bind(&connection.connected_at)
+        .bind(connection.connected_at)
         .bind(&connection.connected_by)
         .bind(connection.is_active)
         .bind(serde_json::to_string(&connection.scopes)?)
@@ -134,7 +171,7 @@ impl SlackService {
                 bot_token: row.try_get("bot_token")?,
                 bot_user_id: row.try_get("bot_user_id")?,
                 app_id: row.try_get("app_id")?,
-                connected_at: row.try_get::<String, _>("connected_at")?.parse()?,
+                connected_at: parse_datetime(&row.try_get::<String, _>("connected_at")?)?,
                 connected_by: row.try_get("connected_by")?,
                 is_active: row.try_get::<i32, _>("is_active")? == 1,
                 scopes,
@@ -151,6 +188,7 @@ impl SlackService {
         config: &ChannelConfig,
     ) -> Result<()> {
         let mappings_json = serde_json::to_string(&config.channel_mappings)?;
+        let google_chat_json = serde_json::to_string(&config.google_chat_webhooks)?;
 
         // Check if config exists
         let exists: Option<String> = sqlx::query_scalar(
@@ -165,12 +203,14 @@ impl SlackService {
             sqlx::query(
                 r#"
                 UPDATE slack_channel_configs
-                SET default_channel = ?, channel_mappings = ?, updated_at = datetime('now')
+                SET default_channel = ?, channel_mappings = ?, google_chat_webhooks = ?,
+                    updated_at = datetime('now')
                 WHERE id = ?
                 "#,
             )
             .bind(&config.default_channel)
             .bind(&mappings_json)
+            .bind(&google_chat_json)
             .bind(&id)
             .execute(self.db.pool())
             .await?;
@@ -180,15 +220,17 @@ impl SlackService {
             sqlx::query(
                 r#"
                 INSERT INTO slack_channel_configs (
-                    id, connection_id, default_channel, channel_mappings, created_at, updated_at
+                    id, connection_id, default_channel, channel_mappings, google_chat_webhooks,
+                    created_at, updated_at
                 )
-                VALUES (?, ?, ?, ?, datetime('now'), datetime('now'))
+                VALUES (?, ?, ?, ?, ?, datetime('now'), datetime('now'))
                 "#,
             )
             .bind(&id)
             .bind(connection_id)
             .bind(&config.default_channel)
             .bind(&mappings_json)
+            .bind(&google_chat_json)
             .execute(self.db.pool())
             .await?;
         }
@@ -200,7 +242,7 @@ impl SlackService {
     pub async fn get_channel_config(&self, connection_id: &str) -> Result<Option<ChannelConfig>> {
         let row = sqlx::query(
             r#"
-            SELECT default_channel, channel_mappings
+            SELECT default_channel, channel_mappings, google_chat_webhooks
             FROM slack_channel_configs
             WHERE connection_id = ?
             "#,
@@ -214,42 +256,57 @@ impl SlackService {
             let mappings_json: String = row.try_get("channel_mappings")?;
             let channel_mappings: HashMap<NotificationType, String> =
                 serde_json::from_str(&mappings_json)?;
+            let google_chat_json: String = row.try_get("google_chat_webhooks")?;
+            let google_chat_webhooks: HashMap<NotificationType, String> =
+                serde_json::from_str(&google_chat_json)?;
 
             Ok(Some(ChannelConfig {
                 default_channel,
                 channel_mappings,
+                google_chat_webhooks,
             }))
         } else {
             Ok(None)
         }
     }
 
-    /// Save user mapping
+    /// Save user mapping for the active connection
     pub async fn save_user_mapping(&self, mapping: &UserMapping) -> Result<()> {
+        let connection = self.require_connection().await?;
+
         sqlx::query(
             r#"
             INSERT INTO slack_user_mappings (
                 id, connection_id, github_username, slack_user_id, slack_username,
-                notify_on_pr, notify_on_mention, notify_on_failure, created_at
+                notify_on_pr, notify_on_mention, notify_on_failure, email,
+                email_on_approval, email_on_incident, email_cost_summary, created_at
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(connection_id, github_username) DO UPDATE SET
                 slack_user_id = excluded.slack_user_id,
                 slack_username = excluded.slack_username,
                 notify_on_pr = excluded.notify_on_pr,
                 notify_on_mention = excluded.notify_on_mention,
-                notify_on_failure = excluded.notify_on_failure
+                notify_on_failure = excluded.notify_on_failure,
+                email = excluded.email,
+                email_on_approval = excluded.email_on_approval,
+                email_on_incident = excluded.email_on_incident,
+                email_cost_summary = excluded.email_cost_summary
             "#,
         )
         .bind(&mapping.id)
-        .bind(&mapping.id) // Using id as connection_id for now - would be set properly in real usage
+        .bind(&connection.id)
         .bind(&mapping.github_username)
         .bind(&mapping.slack_user_id)
         .bind(&mapping.slack_username)
         .bind(mapping.notify_on_pr)
         .bind(mapping.notify_on_mention)
         .bind(mapping.notify_on_failure)
-        .bind(&mapping.created_at)
+        .bind(&mapping.email)
+        .bind(mapping.email_on_approval)
+        .bind(mapping.email_on_incident)
+        .bind(mapping.email_cost_summary)
+        .bind(mapping.created_at)
         .execute(self.db.pool())
         .await?;
 
@@ -261,7 +318,8 @@ impl SlackService {
         let row = sqlx::query(
             r#"
             SELECT id, github_username, slack_user_id, slack_username,
-                   notify_on_pr, notify_on_mention, notify_on_failure, created_at
+                   notify_on_pr, notify_on_mention, notify_on_failure, email,
+                   email_on_approval, email_on_incident, email_cost_summary, created_at
             FROM slack_user_mappings
             WHERE github_username = ?
             LIMIT 1
@@ -280,15 +338,21 @@ impl SlackService {
                 notify_on_pr: row.try_get::<i32, _>("notify_on_pr")? == 1,
                 notify_on_mention: row.try_get::<i32, _>("notify_on_mention")? == 1,
                 notify_on_failure: row.try_get::<i32, _>("notify_on_failure")? == 1,
-                created_at: row.try_get::<String, _>("created_at")?.parse()?,
+                email: row.try_get("email")?,
+                email_on_approval: row.try_get::<i32, _>("email_on_approval")? == 1,
+                email_on_incident: row.try_get::<i32, _>("email_on_incident")? == 1,
+                email_cost_summary: row.try_get::<i32, _>("email_cost_summary")? == 1,
+                created_at: parse_datetime(&row.try_get::<String, _>("created_at")?)?,
             }))
         } else {
             Ok(None)
         }
     }
 
-    /// Save PR thread
+    /// Save PR thread for the active connection
     pub async fn save_pr_thread(&self, thread: &PrThread) -> Result<()> {
+        let connection = self.require_connection().await?;
+
         sqlx::query(
             r#"
             INSERT INTO slack_pr_threads (
@@ -304,12 +368,12 @@ impl SlackService {
             "#,
         )
         .bind(&thread.id)
-        .bind(&thread.id) // Using id as connection_id for now
+        .bind(&connection.id)
         .bind(thread.pr_number)
         .bind(&thread.channel_id)
         .bind(&thread.thread_ts)
-        .bind(&thread.created_at)
-        .bind(&thread.last_updated)
+        .bind(thread.created_at)
+        .bind(thread.last_updated)
         .bind(thread.is_archived)
         .execute(self.db.pool())
         .await?;
@@ -337,8 +401,8 @@ impl SlackService {
                 pr_number: row.try_get("pr_number")?,
                 channel_id: row.try_get("channel_id")?,
                 thread_ts: row.try_get("thread_ts")?,
-                created_at: row.try_get::<String, _>("created_at")?.parse()?,
-                last_updated: row.try_get::<String, _>("last_updated")?.parse()?,
+                created_at: parse_datetime(&row.try_get::<String, _>("created_at")?)?,
+                last_updated: parse_datetime(&row.try_get::<String, _>("last_updated")?)?,
                 is_archived: row.try_get::<i32, _>("is_archived")? == 1,
             }))
         } else {
@@ -365,14 +429,14 @@ impl SlackService {
         )
         .bind(connection_id)
         .bind(channel_id)
-        .bind(&notification_type.to_string())
+        .bind(notification_type.to_string())
         .fetch_optional(self.db.pool())
         .await?;
 
         if let Some(row) = row {
             let count: i32 = row.try_get("message_count")?;
             let db_window_start: String = row.try_get("window_start")?;
-            let db_window_start: DateTime<Utc> = db_window_start.parse()?;
+            let db_window_start = parse_datetime(&db_window_start)?;
 
             // Reset window if expired
             if db_window_start < window_start {
@@ -397,7 +461,7 @@ impl SlackService {
             .bind(&id)
             .bind(connection_id)
             .bind(channel_id)
-            .bind(&notification_type.to_string())
+            .bind(notification_type.to_string())
             .execute(self.db.pool())
             .await?;
 
@@ -421,7 +485,7 @@ impl SlackService {
         )
         .bind(connection_id)
         .bind(channel_id)
-        .bind(&notification_type.to_string())
+        .bind(notification_type.to_string())
         .execute(self.db.pool())
         .await?;
 
@@ -444,7 +508,7 @@ impl SlackService {
         )
         .bind(connection_id)
         .bind(channel_id)
-        .bind(&notification_type.to_string())
+        .bind(notification_type.to_string())
         .execute(self.db.pool())
         .await?;
 
@@ -486,58 +550,278 @@ impl SlackService {
     }
 
     /// Send a notification (Stories 2, 3, 4)
-    /// This is the main entry point for sending notifications
+    /// This is the main entry point for sending notifications; the message is
+    /// routed to the channel configured for its notification type
     pub async fn send_notification(
         &self,
         notification_type: NotificationType,
-        message: SlackMessage,
+        mut message: SlackMessage,
         agent_id: Option<AgentId>,
         pr_number: Option<i32>,
     ) -> Result<SentMessage> {
-        // Get active connection
-        let connection = self
-            .get_active_connection()
-            .await?
-            .ok_or_else(|| Error::Other("No active Slack connection".to_string()))?;
+        let connection = self.require_connection().await?;
 
-        // Get channel config
         let channel_config = self
             .get_channel_config(&connection.id)
             .await?
-            .unwrap_or_else(|| ChannelConfig::default());
+            .unwrap_or_default();
+        message.channel = channel_config.get_channel(&notification_type).to_string();
+
+        self.deliver(&connection, notification_type, message, agent_id, pr_number)
+            .await
+    }
+
+    /// Post a card to the Google Chat webhook selected for a notification
+    /// type in the active connection's channel configuration
+    ///
+    /// Returns whether a webhook was selected; without one nothing is sent.
+    pub async fn send_google_chat(
+        &self,
+        notification_type: &NotificationType,
+        message: &GoogleChatMessage,
+    ) -> Result<bool> {
+        let Some(connection) = self.get_active_connection().await? else {
+            return Ok(false);
+        };
+        let Some(config) = self.get_channel_config(&connection.id).await? else {
+            return Ok(false);
+        };
+        let Some(webhook_url) = config.get_google_chat_webhook(notification_type) else {
+            return Ok(false);
+        };
+
+        // Testing without HTTP: treat the card as delivered
+        if let Some(http_client) = &self.http_client {
+            GoogleChatClient::with_http_client(http_client.clone())
+                .post(webhook_url, message)
+                .await?;
+        }
+        Ok(true)
+    }
+
+    /// Send a message to the channel it names, without routing or rate limiting
+    pub async fn send_message(&self, message: SlackMessage) -> Result<SentMessage> {
+        let connection = self.require_connection().await?;
+        let sent = self.post(&connection, &message).await?;
+        self.track_sent_message(&connection.id, &sent, None, None, None)
+            .await?;
+        Ok(sent)
+    }
+
+    /// Replace the message at `ts` in `message.channel` with `message`
+    pub async fn update_message(&self, ts: &str, message: &SlackMessage) -> Result<()> {
+        let connection = self.require_connection().await?;
+        match self.api_client(&connection) {
+            Some(client) => client.update_message(ts, message).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Reply to an interaction through its `response_url`, visible only to
+    /// the user who interacted
+    pub async fn respond_ephemeral(&self, response_url: &str, text: &str) -> Result<()> {
+        let Some(http_client) = self.http_client.as_ref() else {
+            return Ok(());
+        };
+
+        let response = http_client
+            .post(response_url)
+            .json(&serde_json::json!({
+                "response_type": "ephemeral",
+                "replace_original": false,
+                "text": text,
+            }))
+            .send()
+            .await
+            .map_err(|e| Error::Other(format!("Slack response request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(Error::Other(format!(
+                "Slack response URL returned HTTP {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Send a direct message to a Slack user
+    pub async fn send_direct_message(
+        &self,
+        slack_user_id: &str,
+        notification_type: NotificationType,
+        mut message: SlackMessage,
+    ) -> Result<SentMessage> {
+        let connection = self.require_connection().await?;
+        // In Slack, DMs are sent to the user's ID as the channel
+        message.channel = slack_user_id.to_string();
+        self.deliver(&connection, notification_type, message, None, None)
+            .await
+    }
+
+    /// Send a direct message to the Slack user mapped to a GitHub user if
+    /// their notification settings allow it
+    ///
+    /// Returns `None` when the user is not mapped or the notification is
+    /// disabled or muted. Users without saved settings get the defaults.
+    pub async fn notify_user(
+        &self,
+        github_username: &str,
+        notification_type: NotificationType,
+        message: SlackMessage,
+    ) -> Result<Option<SentMessage>> {
+        let Some(mapping) = self.get_user_mapping(github_username).await? else {
+            return Ok(None);
+        };
+        let settings = self
+            .get_notification_settings(github_username)
+            .await?
+            .unwrap_or_else(|| NotificationSettings::new(&mapping.slack_user_id));
+        if !settings.should_dm(&notification_type) {
+            return Ok(None);
+        }
+
+        self.send_direct_message(&mapping.slack_user_id, notification_type, message)
+            .await
+            .map(Some)
+    }
+
+    /// Look up a Slack user by email with the active connection's token
+    pub async fn lookup_user_by_email(&self, email: &str) -> Result<Option<SlackUser>> {
+        let connection = self.require_connection().await?;
+        let client = self
+            .api_client(&connection)
+            .ok_or_else(|| Error::Other("Slack API client not available".to_string()))?;
+        client.lookup_user_by_email(email).await
+    }
+
+    /// Save notification settings for a mapped GitHub user
+    pub async fn save_notification_settings(
+        &self,
+        github_username: &str,
+        settings: &NotificationSettings,
+    ) -> Result<()> {
+        let mapping_id: String = sqlx::query_scalar(
+            "SELECT id FROM slack_user_mappings WHERE github_username = ? LIMIT 1",
+        )
+        .bind(github_username)
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(|| Error::Other(format!("No Slack user mapping for {}", github_username)))?;
 
-        // Determine target channel
-        let channel = channel_config.get_channel(&notification_type);
+        sqlx::query(
+            r#"
+            INSERT INTO slack_notification_settings (
+                id, user_mapping_id, enabled_types, muted_until, dm_for_urgent, digest_mode,
+                created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))
+            ON CONFLICT(user_mapping_id) DO UPDATE SET
+                enabled_types = excluded.enabled_types,
+                muted_until = excluded.muted_until,
+                dm_for_urgent = excluded.dm_for_urgent,
+                digest_mode = excluded.digest_mode,
+                updated_at = datetime('now')
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&mapping_id)
+        .bind(serde_json::to_string(&settings.enabled_types)?)
+        .bind(settings.muted_until.map(|until| until.to_rfc3339()))
+        .bind(settings.dm_for_urgent)
+        .bind(settings.digest_mode.as_str())
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get notification settings for a mapped GitHub user
+    pub async fn get_notification_settings(
+        &self,
+        github_username: &str,
+    ) -> Result<Option<NotificationSettings>> {
+        let row = sqlx::query(
+            r#"
+            SELECT m.slack_user_id, s.enabled_types, s.muted_until, s.dm_for_urgent, s.digest_mode
+            FROM slack_notification_settings s
+            JOIN slack_user_mappings m ON m.id = s.user_mapping_id
+            WHERE m.github_username = ?
+            LIMIT 1
+            "#,
+        )
+        .bind(github_username)
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        if let Some(row) = row {
+            Ok(Some(NotificationSettings {
+                user_id: row.try_get("slack_user_id")?,
+                enabled_types: serde_json::from_str(&row.try_get::<String, _>("enabled_types")?)?,
+                muted_until: row
+                    .try_get::<Option<String>, _>("muted_until")?
+                    .map(|until| parse_datetime(&until))
+                    .transpose()?,
+                dm_for_urgent: row.try_get::<i32, _>("dm_for_urgent")? == 1,
+                digest_mode: row.try_get::<String, _>("digest_mode")?.parse()?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
 
-        // Check rate limit
+    /// Check the rate limit, post the message, and record it
+    async fn deliver(
+        &self,
+        connection: &SlackConnection,
+        notification_type: NotificationType,
+        message: SlackMessage,
+        agent_id: Option<AgentId>,
+        pr_number: Option<i32>,
+    ) -> Result<SentMessage> {
         if !self
-            .check_rate_limit(&connection.id, channel, &notification_type)
+            .check_rate_limit(&connection.id, &message.channel, &notification_type)
             .await?
         {
             return Err(Error::Other(format!(
                 "Rate limit exceeded for channel {} and notification type {}",
-                channel, notification_type
+                message.channel, notification_type
             )));
         }
 
-        // In a real implementation, this would send to Slack API
-        // For testing, we'll just create a mock response
-        let sent_message = SentMessage {
-            ok: true,
-            channel: channel.to_string(),
-            ts: format!("{}.000000", Utc::now().timestamp()),
-            message_id: uuid::Uuid::new_v4().to_string(),
-        };
+        let sent = self.post(connection, &message).await?;
 
-        // Track the message
-        self.track_sent_message(&connection.id, &sent_message, Some(notification_type.clone()), agent_id, pr_number)
+        self.track_sent_message(
+            &connection.id,
+            &sent,
+            Some(notification_type.clone()),
+            agent_id,
+            pr_number,
+        )
+        .await?;
+        self.increment_rate_limit(&connection.id, &message.channel, &notification_type)
             .await?;
 
-        // Increment rate limit
-        self.increment_rate_limit(&connection.id, channel, &notification_type)
-            .await?;
+        Ok(sent)
+    }
 
-        Ok(sent_message)
+    /// Post a message with chat.postMessage
+    async fn post(
+        &self,
+        connection: &SlackConnection,
+        message: &SlackMessage,
+    ) -> Result<SentMessage> {
+        match self.api_client(connection) {
+            Some(client) => client.post_message(message).await,
+            // Testing without HTTP: acknowledge the message locally
+            None => {
+                let now = Utc::now();
+                Ok(SentMessage {
+                    ok: true,
+                    channel: message.channel.clone(),
+                    ts: format!("{}.{:06}", now.timestamp(), now.timestamp_subsec_micros()),
+                    message_id: uuid::Uuid::new_v4().to_string(),
+                })
+            }
+        }
     }
 
     /// Send agent lifecycle notification (Story 3)
@@ -776,6 +1060,12 @@ mod tests {
         Database::in_memory().await.unwrap()
     }
 
+    async fn create_agent(db: &Database) -> AgentId {
+        let agent = crate::Agent::new(crate::AgentType::StoryDeveloper, "Implement login feature");
+        db.insert_agent(&agent).await.unwrap();
+        AgentId::from_uuid(agent.id)
+    }
+
     #[tokio::test]
     async fn test_save_and_get_connection() {
         let db = setup_test_db().await;
@@ -828,6 +1118,9 @@ mod tests {
         let db = setup_test_db().await;
         let service = SlackService::new_for_testing(db);
 
+        let connection = SlackConnection::new("T12345", "Test Team", "xoxb-token");
+        service.save_connection(&connection).await.unwrap();
+
         let mapping = UserMapping::new("github-user", "U12345");
 
         service.save_user_mapping(&mapping).await.unwrap();
@@ -848,6 +1141,9 @@ mod tests {
         let db = setup_test_db().await;
         let service = SlackService::new_for_testing(db);
 
+        let connection = SlackConnection::new("T12345", "Test Team", "xoxb-token");
+        service.save_connection(&connection).await.unwrap();
+
         let thread = PrThread::new(123, "C12345", "1234.5678");
 
         service.save_pr_thread(&thread).await.unwrap();
@@ -900,7 +1196,7 @@ mod tests {
     #[tokio::test]
     async fn test_send_notification() {
         let db = setup_test_db().await;
-        let service = SlackService::new_for_testing(db);
+        let service = SlackService::new_for_testing(db.clone());
 
         let connection = SlackConnection::new("T12345", "Test Team", "xoxb-token");
         service.save_connection(&connection).await.unwrap();
@@ -912,7 +1208,7 @@ mod tests {
             .unwrap();
 
         let message = SlackMessage::new("#test", "Test notification");
-        let agent_id = AgentId::new();
+        let agent_id = create_agent(&db).await;
 
         let result = service
             .send_notification(NotificationType::AgentCompleted, message, Some(agent_id), None)
@@ -924,9 +1220,49 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_notify_agent_lifecycle_started() {
+    async fn test_send_google_chat_uses_selected_webhook() {
         let db = setup_test_db().await;
         let service = SlackService::new_for_testing(db);
+        let message = GoogleChatMessage::new("Deployment started");
+
+        // No connection, so no channel configuration to select a webhook
+        assert!(!service
+            .send_google_chat(&NotificationType::DeploymentStarted, &message)
+            .await
+            .unwrap());
+
+        let connection = SlackConnection::new("T12345", "Test Team", "xoxb-token");
+        service.save_connection(&connection).await.unwrap();
+        let config = ChannelConfig::new("#orchestrate").with_google_chat_webhook(
+            NotificationType::DeploymentStarted,
+            "https://chat.googleapis.com/v1/spaces/AAA/messages?key=k&token=t",
+        );
+        service
+            .save_channel_config(&connection.id, &config)
+            .await
+            .unwrap();
+
+        let retrieved = service
+            .get_channel_config(&connection.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(retrieved.google_chat_webhooks.len(), 1);
+
+        assert!(service
+            .send_google_chat(&NotificationType::DeploymentStarted, &message)
+            .await
+            .unwrap());
+        assert!(!service
+            .send_google_chat(&NotificationType::AlertFired, &message)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_notify_agent_lifecycle_started() {
+        let db = setup_test_db().await;
+        let service = SlackService::new_for_testing(db.clone());
 
         let connection = SlackConnection::new("T12345", "Test Team", "xoxb-token");
         service.save_connection(&connection).await.unwrap();
@@ -937,7 +1273,7 @@ mod tests {
             .await
             .unwrap();
 
-        let agent_id = AgentId::new();
+        let agent_id = create_agent(&db).await;
         let event = AgentLifecycleEvent::Started {
             agent_type: "story-developer".to_string(),
             task: "Implement login feature".to_string(),
@@ -1016,4 +1352,88 @@ mod tests {
 
         assert!(result.ok);
     }
+
+    #[tokio::test]
+    async fn test_notify_user_honours_notification_settings() {
+        let db = setup_test_db().await;
+        let service = SlackService::new_for_testing(db);
+
+        let connection = SlackConnection::new("T12345", "Test Team", "xoxb-token");
+        service.save_connection(&connection).await.unwrap();
+        service
+            .save_user_mapping(&UserMapping::new("github-user", "U12345"))
+            .await
+            .unwrap();
+
+        // Unmapped users are skipped
+        let message = SlackMessage::new("#ignored", "Agent completed");
+        assert!(service
+            .notify_user(
+                "someone-else",
+                NotificationType::AgentCompleted,
+                message.clone()
+            )
+            .await
+            .unwrap()
+            .is_none());
+
+        // Default settings DM enabled types to the user's ID
+        let sent = service
+            .notify_user(
+                "github-user",
+                NotificationType::AgentCompleted,
+                message.clone(),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(sent.channel, "U12345");
+
+        let mut settings = NotificationSettings::new("U12345");
+        settings.enabled_types = vec![NotificationType::PrMerged];
+        settings.digest_mode = DigestMode::Daily;
+        service
+            .save_notification_settings("github-user", &settings)
+            .await
+            .unwrap();
+        let saved = service
+            .get_notification_settings("github-user")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.enabled_types, vec![NotificationType::PrMerged]);
+        assert_eq!(saved.digest_mode, DigestMode::Daily);
+
+        // Disabled types are skipped, urgent ones still DM'd
+        assert!(service
+            .notify_user(
+                "github-user",
+                NotificationType::AgentCompleted,
+                message.clone()
+            )
+            .await
+            .unwrap()
+            .is_none());
+        assert!(service
+            .notify_user(
+                "github-user",
+                NotificationType::AgentFailed,
+                message.clone()
+            )
+            .await
+            .unwrap()
+            .is_some());
+
+        // Nothing is sent while muted
+        settings.mute(Utc::now() + Duration::hours(1));
+        service
+            .save_notification_settings("github-user", &settings)
+            .await
+            .unwrap();
+        assert!(service
+            .notify_user("github-user", NotificationType::AgentFailed, message)
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
@@ -8,6 +8,7 @@
 //! - Send DMs for urgent notifications
 
 use crate::{
+    database::parse_datetime,
     error::Result,
     slack::{NotificationType, SlackMessage, UserMapping},
     slack_service::SlackService,
@@ -93,7 +94,7 @@ impl SlackUserService {
         .bind(mapping.notify_on_pr)
         .bind(mapping.notify_on_mention)
         .bind(mapping.notify_on_failure)
-        .bind(&mapping.created_at)
+        .bind(mapping.created_at)
         .execute(self.db.pool())
         .await?;
 
@@ -106,12 +107,30 @@ impl SlackUserService {
         Ok(mapping.map(|m| m.slack_user_id))
     }
 
+    /// Get the GitHub username mapped to a Slack user ID
+    pub async fn get_github_username(&self, slack_user_id: &str) -> Result<Option<String>> {
+        let username = sqlx::query_scalar(
+            r#"
+            SELECT github_username FROM slack_user_mappings
+            WHERE slack_user_id = ?
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(slack_user_id)
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        Ok(username)
+    }
+
     /// Get all user mappings
     pub async fn list_user_mappings(&self) -> Result<Vec<UserMapping>> {
         let rows = sqlx::query(
             r#"
             SELECT id, github_username, slack_user_id, slack_username,
-                   notify_on_pr, notify_on_mention, notify_on_failure, created_at
+                   notify_on_pr, notify_on_mention, notify_on_failure, email,
+                   email_on_approval, email_on_incident, email_cost_summary, created_at
             FROM slack_user_mappings
             ORDER BY created_at DESC
             "#,
@@ -129,7 +148,11 @@ impl SlackUserService {
                 notify_on_pr: row.try_get::<i32, _>("notify_on_pr")? == 1,
                 notify_on_mention: row.try_get::<i32, _>("notify_on_mention")? == 1,
                 notify_on_failure: row.try_get::<i32, _>("notify_on_failure")? == 1,
-                created_at: row.try_get::<String, _>("created_at")?.parse()?,
+                email: row.try_get("email")?,
+                email_on_approval: row.try_get::<i32, _>("email_on_approval")? == 1,
+                email_on_incident: row.try_get::<i32, _>("email_on_incident")? == 1,
+                email_cost_summary: row.try_get::<i32, _>("email_cost_summary")? == 1,
+                created_at: parse_datetime(&row.try_get::<String, _>("created_at")?)?,
             });
         }
 
@@ -158,7 +181,7 @@ impl SlackUserService {
         .bind(&owner.id)
         .bind(&owner.pattern)
         .bind(&owner.github_username)
-        .bind(&owner.created_at)
+        .bind(owner.created_at)
         .execute(self.db.pool())
         .await?;
 
@@ -214,7 +237,7 @@ impl SlackUserService {
                 id: row.try_get("id")?,
                 pattern: row.try_get("pattern")?,
                 github_username: row.try_get("github_username")?,
-                created_at: row.try_get::<String, _>("created_at")?.parse()?,
+                created_at: parse_datetime(&row.try_get::<String, _>("created_at")?)?,
             });
         }
 
@@ -254,12 +277,8 @@ impl SlackUserService {
         message: SlackMessage,
         notification_type: NotificationType,
     ) -> Result<()> {
-        // In Slack, DMs are sent to the user's ID as the channel
-        let mut dm_message = message;
-        dm_message.channel = slack_user_id.to_string();
-
         self.slack_service
-            .send_notification(notification_type, dm_message, None, None)
+            .send_direct_message(slack_user_id, notification_type, message)
             .await?;
 
         Ok(())
@@ -324,6 +343,10 @@ mod tests {
 
         let not_found = service.get_slack_user_id("nonexistent").await.unwrap();
         assert_eq!(not_found, None);
+
+        let github_username = service.get_github_username("U12345").await.unwrap();
+        assert_eq!(github_username, Some("github-user".to_string()));
+        assert_eq!(service.get_github_username("U99999").await.unwrap(), None);
     }
 
     #[tokio::test]
@@ -0,0 +1,343 @@
+//! SMTP Client
+//!
+//! A minimal SMTP client for delivering notification emails:
+//! - Implicit TLS (SMTPS), STARTTLS, or plain connections
+//! - `AUTH PLAIN` when credentials are configured
+//! - One transaction per message: `MAIL FROM`, `RCPT TO` for each recipient,
+//!   then `DATA`
+//!
+//! Any reply other than the expected one aborts delivery with an error that
+//! carries the server's reply.
+
+use base64::Engine;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use crate::email::{EmailMessage, SmtpConfig, SmtpTls};
+use crate::{Error, Result};
+
+/// Default limit on delivering one message, connection included
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Client that delivers messages through one SMTP server
+#[derive(Debug, Clone)]
+pub struct SmtpClient {
+    config: SmtpConfig,
+    timeout: Duration,
+}
+
+impl SmtpClient {
+    pub fn new(config: SmtpConfig) -> Self {
+        Self {
+            config,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Client configured from `ORCHESTRATE_SMTP_*`, if set
+    pub fn from_env() -> Result<Option<Self>> {
+        Ok(SmtpConfig::from_env()?.map(Self::new))
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn config(&self) -> &SmtpConfig {
+        &self.config
+    }
+
+    /// Deliver `message` to all of its recipients
+    pub async fn send(&self, message: &EmailMessage) -> Result<()> {
+        if message.to.is_empty() {
+            return Err(Error::Other("Email has no recipients".to_string()));
+        }
+
+        tokio::time::timeout(self.timeout, self.deliver(message))
+            .await
+            .map_err(|_| {
+                Error::Other(format!(
+                    "SMTP delivery to {}:{} timed out",
+                    self.config.host, self.config.port
+                ))
+            })?
+    }
+
+    async fn deliver(&self, message: &EmailMessage) -> Result<()> {
+        let stream = TcpStream::connect((self.config.host.as_str(), self.config.port))
+            .await
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Failed to connect to SMTP server {}:{}: {}",
+                    self.config.host, self.config.port, e
+                ))
+            })?;
+
+        match self.config.tls {
+            SmtpTls::Implicit => {
+                let stream = self.tls_connect(stream).await?;
+                let mut connection = SmtpConnection::new(stream);
+                connection.expect(220).await?;
+                self.transact(connection, message).await
+            }
+            SmtpTls::StartTls => {
+                let mut connection = SmtpConnection::new(stream);
+                connection.expect(220).await?;
+                let extensions = connection.ehlo(self.helo_name()).await?;
+                if !extensions
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case("STARTTLS"))
+                {
+                    return Err(Error::Other(format!(
+                        "SMTP server {} does not support STARTTLS",
+                        self.config.host
+                    )));
+                }
+                connection.command("STARTTLS", 220).await?;
+                let stream = self.tls_connect(connection.into_inner()).await?;
+                self.transact(SmtpConnection::new(stream), message).await
+            }
+            SmtpTls::None => {
+                let mut connection = SmtpConnection::new(stream);
+                connection.expect(220).await?;
+                self.transact(connection, message).await
+            }
+        }
+    }
+
+    async fn tls_connect(
+        &self,
+        stream: TcpStream,
+    ) -> Result<tokio_native_tls::TlsStream<TcpStream>> {
+        let connector = native_tls::TlsConnector::new()
+            .map_err(|e| Error::Other(format!("Failed to create TLS connector: {}", e)))?;
+        tokio_native_tls::TlsConnector::from(connector)
+            .connect(&self.config.host, stream)
+            .await
+            .map_err(|e| {
+                Error::Other(format!(
+                    "TLS handshake with {} failed: {}",
+                    self.config.host, e
+                ))
+            })
+    }
+
+    /// Authenticate if configured and send one message over a greeted
+    /// connection
+    async fn transact<S>(
+        &self,
+        mut connection: SmtpConnection<S>,
+        message: &EmailMessage,
+    ) -> Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        connection.ehlo(self.helo_name()).await?;
+
+        if let (Some(username), Some(password)) = (&self.config.username, &self.config.password) {
+            let token = base64::engine::general_purpose::STANDARD
+                .encode(format!("\0{}\0{}", username, password));
+            connection
+                .command(&format!("AUTH PLAIN {}", token), 235)
+                .await?;
+        }
+
+        connection
+            .command(&format!("MAIL FROM:<{}>", mailbox(&self.config.from)), 250)
+            .await?;
+        for recipient in &message.to {
+            connection
+                .command(&format!("RCPT TO:<{}>", mailbox(recipient)), 250)
+                .await?;
+        }
+        connection.command("DATA", 354).await?;
+        connection
+            .write(&format!(
+                "{}.\r\n",
+                dot_stuff(&message.to_mime(&self.config.from))
+            ))
+            .await?;
+        connection.expect(250).await?;
+
+        // The message is accepted; a failed QUIT does not matter
+        let _ = connection.command("QUIT", 221).await;
+        Ok(())
+    }
+
+    /// Name sent with EHLO: the domain of the sender address
+    fn helo_name(&self) -> &str {
+        mailbox(&self.config.from)
+            .rsplit_once('@')
+            .map(|(_, domain)| domain)
+            .unwrap_or("localhost")
+    }
+}
+
+/// Address part of `Name <address>`, or the whole value
+fn mailbox(address: &str) -> &str {
+    match (address.find('<'), address.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &address[start + 1..end],
+        _ => address.trim(),
+    }
+}
+
+/// Escape lines starting with `.` so they do not end `DATA` early
+fn dot_stuff(data: &str) -> String {
+    let mut stuffed = String::with_capacity(data.len());
+    for line in data.split_inclusive("\r\n") {
+        if line.starts_with('.') {
+            stuffed.push('.');
+        }
+        stuffed.push_str(line);
+    }
+    if !stuffed.ends_with("\r\n") {
+        stuffed.push_str("\r\n");
+    }
+    stuffed
+}
+
+/// Command/reply exchange over one stream
+struct SmtpConnection<S> {
+    stream: BufReader<S>,
+}
+
+impl<S> SmtpConnection<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    fn new(stream: S) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+        }
+    }
+
+    fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+
+    async fn write(&mut self, data: &str) -> Result<()> {
+        let stream = self.stream.get_mut();
+        stream
+            .write_all(data.as_bytes())
+            .await
+            .map_err(|e| Error::Other(format!("Failed to write to SMTP server: {}", e)))?;
+        stream
+            .flush()
+            .await
+            .map_err(|e| Error::Other(format!("Failed to write to SMTP server: {}", e)))
+    }
+
+    /// Read a possibly multi-line reply, returning its code and text lines
+    async fn read_reply(&mut self) -> Result<(u16, Vec<String>)> {
+        let mut lines = Vec::new();
+        loop {
+            let mut line = String::new();
+            let n = self
+                .stream
+                .read_line(&mut line)
+                .await
+                .map_err(|e| Error::Other(format!("Failed to read from SMTP server: {}", e)))?;
+            if n == 0 {
+                return Err(Error::Other(
+                    "SMTP server closed the connection".to_string(),
+                ));
+            }
+
+            let line = line.trim_end();
+            let code = line
+                .get(..3)
+                .and_then(|code| code.parse::<u16>().ok())
+                .ok_or_else(|| Error::Other(format!("Invalid SMTP reply: {}", line)))?;
+            lines.push(line.get(4..).unwrap_or_default().to_string());
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok((code, lines));
+            }
+        }
+    }
+
+    async fn expect(&mut self, code: u16) -> Result<Vec<String>> {
+        let (reply, lines) = self.read_reply().await?;
+        if reply != code {
+            return Err(Error::Other(format!(
+                "SMTP server replied {} {}",
+                reply,
+                lines.join(" ")
+            )));
+        }
+        Ok(lines)
+    }
+
+    async fn command(&mut self, command: &str, code: u16) -> Result<Vec<String>> {
+        self.write(&format!("{}\r\n", command)).await?;
+        self.expect(code).await
+    }
+
+    /// Send EHLO and return the advertised extensions
+    async fn ehlo(&mut self, name: &str) -> Result<Vec<String>> {
+        let lines = self.command(&format!("EHLO {}", name), 250).await?;
+        Ok(lines.into_iter().skip(1).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_servers::smtp;
+
+    fn client(port: u16) -> SmtpClient {
+        SmtpClient::new(
+            SmtpConfig::new("127.0.0.1", "Orchestrate <ci@example.com>")
+                .with_tls(SmtpTls::None)
+                .with_port(port)
+                .with_credentials("ci", "secret"),
+        )
+        .with_timeout(Duration::from_secs(5))
+    }
+
+    fn message() -> EmailMessage {
+        EmailMessage::new(
+            vec![
+                "alice@example.com".to_string(),
+                "bob@example.com".to_string(),
+            ],
+            "Approval required",
+            "Please approve",
+            "<p>Please approve</p>",
+        )
+    }
+
+    #[test]
+    fn test_dot_stuff() {
+        assert_eq!(dot_stuff("a\r\n.b\r\n..c\r\n"), "a\r\n..b\r\n...c\r\n");
+        assert_eq!(dot_stuff("a"), "a\r\n");
+        assert_eq!(mailbox("Orchestrate <ci@example.com>"), "ci@example.com");
+        assert_eq!(mailbox("ci@example.com"), "ci@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_send_message() {
+        let (port, server) = smtp(None).await;
+
+        client(port).send(&message()).await.unwrap();
+
+        let transcript = server.await.unwrap();
+        let auth = base64::engine::general_purpose::STANDARD.encode("\0ci\0secret");
+        assert!(transcript.starts_with("EHLO example.com\r\n"));
+        assert!(transcript.contains(&format!("AUTH PLAIN {}\r\n", auth)));
+        assert!(transcript.contains("MAIL FROM:<ci@example.com>\r\n"));
+        assert!(transcript.contains("RCPT TO:<alice@example.com>\r\n"));
+        assert!(transcript.contains("RCPT TO:<bob@example.com>\r\n"));
+        assert!(transcript.contains("Subject: Approval required\r\n"));
+        assert!(transcript.ends_with(".\r\nQUIT\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_recipient_fails() {
+        let (port, _server) = smtp(Some("RCPT TO:<bob@example.com>")).await;
+
+        let err = client(port).send(&message()).await.unwrap_err();
+        assert!(err.to_string().contains("550 no such user"));
+    }
+}
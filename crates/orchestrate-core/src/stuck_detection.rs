@@ -27,6 +27,10 @@ pub enum StuckType {
     ContextLimit,
     /// Agent in error loop
     ErrorLoop,
+    /// No files changed in last N turns
+    NoFileChanges,
+    /// Tokens consumed without meaningful progress
+    TokenBurn,
 }
 
 impl StuckType {
@@ -40,8 +44,26 @@ impl StuckType {
             Self::RateLimit => "rate_limit",
             Self::ContextLimit => "context_limit",
             Self::ErrorLoop => "error_loop",
+            Self::NoFileChanges => "no_file_changes",
+            Self::TokenBurn => "token_burn",
         }
     }
+
+    /// All stuck types, in the order the detector checks them
+    pub fn all() -> &'static [StuckType] {
+        &[
+            Self::TurnLimit,
+            Self::ContextLimit,
+            Self::NoProgress,
+            Self::NoFileChanges,
+            Self::TokenBurn,
+            Self::CiTimeout,
+            Self::ReviewDelay,
+            Self::MergeConflict,
+            Self::RateLimit,
+            Self::ErrorLoop,
+        ]
+    }
 }
 
 impl std::str::FromStr for StuckType {
@@ -57,6 +79,8 @@ impl std::str::FromStr for StuckType {
             "rate_limit" => Ok(Self::RateLimit),
             "context_limit" => Ok(Self::ContextLimit),
             "error_loop" => Ok(Self::ErrorLoop),
+            "no_file_changes" => Ok(Self::NoFileChanges),
+            "token_burn" => Ok(Self::TokenBurn),
             _ => Err(crate::Error::Other(format!("Invalid stuck type: {}", s))),
         }
     }
@@ -184,6 +208,12 @@ pub struct AgentProgress {
     pub has_merge_conflicts: bool,
     /// Recent rate limit encountered
     pub rate_limited_until: Option<DateTime<Utc>>,
+    /// Turns since the agent last changed a file
+    #[serde(default)]
+    pub turns_since_file_change: u32,
+    /// Tokens consumed since the last meaningful output
+    #[serde(default)]
+    pub tokens_since_progress: u64,
 }
 
 impl AgentProgress {
@@ -213,7 +243,10 @@ impl AgentProgress {
 }
 
 /// Configuration for stuck detection
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Stored per agent type in the database; the `default` agent type applies to
+/// agent types without their own configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StuckDetectionConfig {
     /// Alert when turn count reaches this percentage of max_turns
     pub turn_warning_threshold: f64,
@@ -227,6 +260,74 @@ pub struct StuckDetectionConfig {
     pub review_delay_minutes: u32,
     /// Number of errors in sequence to consider stuck
     pub error_loop_threshold: u32,
+    /// Number of turns without file changes to consider stuck
+    #[serde(default = "default_no_file_changes_turn_threshold")]
+    pub no_file_changes_turn_threshold: u32,
+    /// Tokens consumed without progress to consider stuck
+    #[serde(default = "default_token_burn_threshold")]
+    pub token_burn_threshold: u64,
+    /// Minutes of rate limiting before it counts as a stall
+    #[serde(default)]
+    pub rate_limit_stall_minutes: u32,
+    /// Detectors that are switched off
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_detectors: Vec<StuckType>,
+}
+
+fn default_no_file_changes_turn_threshold() -> u32 {
+    10
+}
+
+fn default_token_burn_threshold() -> u64 {
+    100_000
+}
+
+impl StuckDetectionConfig {
+    /// Agent type whose configuration applies when none is stored for an agent type
+    pub const DEFAULT_AGENT_TYPE: &'static str = "default";
+
+    /// Whether a detector is enabled
+    pub fn is_enabled(&self, detector: StuckType) -> bool {
+        !self.disabled_detectors.contains(&detector)
+    }
+
+    /// Enable or disable a detector
+    pub fn set_enabled(&mut self, detector: StuckType, enabled: bool) {
+        self.disabled_detectors.retain(|d| *d != detector);
+        if !enabled {
+            self.disabled_detectors.push(detector);
+        }
+    }
+
+    /// Set a tuning parameter by name (as used by the CLI)
+    pub fn set_param(&mut self, name: &str, value: &str) -> crate::Result<()> {
+        fn parse<T: std::str::FromStr>(name: &str, value: &str) -> crate::Result<T> {
+            value.parse().map_err(|_| {
+                crate::Error::Other(format!("Invalid value for {}: {}", name, value))
+            })
+        }
+
+        match name {
+            "turn_warning_threshold" => self.turn_warning_threshold = parse(name, value)?,
+            "token_warning_threshold" => self.token_warning_threshold = parse(name, value)?,
+            "no_progress_turn_threshold" => self.no_progress_turn_threshold = parse(name, value)?,
+            "ci_timeout_minutes" => self.ci_timeout_minutes = parse(name, value)?,
+            "review_delay_minutes" => self.review_delay_minutes = parse(name, value)?,
+            "error_loop_threshold" => self.error_loop_threshold = parse(name, value)?,
+            "no_file_changes_turn_threshold" => {
+                self.no_file_changes_turn_threshold = parse(name, value)?
+            }
+            "token_burn_threshold" => self.token_burn_threshold = parse(name, value)?,
+            "rate_limit_stall_minutes" => self.rate_limit_stall_minutes = parse(name, value)?,
+            _ => {
+                return Err(crate::Error::Other(format!(
+                    "Unknown stuck detection parameter: {}",
+                    name
+                )))
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for StuckDetectionConfig {
@@ -238,6 +339,10 @@ impl Default for StuckDetectionConfig {
             ci_timeout_minutes: 30,
             review_delay_minutes: 60,
             error_loop_threshold: 3,
+            no_file_changes_turn_threshold: default_no_file_changes_turn_threshold(),
+            token_burn_threshold: default_token_burn_threshold(),
+            rate_limit_stall_minutes: 0,
+            disabled_detectors: Vec::new(),
         }
     }
 }
@@ -259,51 +364,37 @@ impl StuckDetector {
         Self { config }
     }
 
-    /// Check agent progress and return any stuck detections
-    pub fn check(&self, agent_id: &str, progress: &AgentProgress) -> Vec<StuckDetection> {
-        let mut detections = Vec::new();
-
-        // Check turn limit
-        if let Some(detection) = self.check_turn_limit(agent_id, progress) {
-            detections.push(detection);
-        }
-
-        // Check token/context limit
-        if let Some(detection) = self.check_context_limit(agent_id, progress) {
-            detections.push(detection);
-        }
-
-        // Check for no progress
-        if let Some(detection) = self.check_no_progress(agent_id, progress) {
-            detections.push(detection);
-        }
-
-        // Check CI timeout
-        if let Some(detection) = self.check_ci_timeout(agent_id, progress) {
-            detections.push(detection);
-        }
-
-        // Check review delay
-        if let Some(detection) = self.check_review_delay(agent_id, progress) {
-            detections.push(detection);
-        }
-
-        // Check merge conflicts
-        if let Some(detection) = self.check_merge_conflict(agent_id, progress) {
-            detections.push(detection);
-        }
-
-        // Check rate limit
-        if let Some(detection) = self.check_rate_limit(agent_id, progress) {
-            detections.push(detection);
-        }
+    pub fn config(&self) -> &StuckDetectionConfig {
+        &self.config
+    }
 
-        // Check error loop
-        if let Some(detection) = self.check_error_loop(agent_id, progress) {
-            detections.push(detection);
+    /// Check agent progress and return any stuck detections from enabled detectors
+    pub fn check(&self, agent_id: &str, progress: &AgentProgress) -> Vec<StuckDetection> {
+        StuckType::all()
+            .iter()
+            .filter(|detector| self.config.is_enabled(**detector))
+            .filter_map(|detector| self.run_detector(*detector, agent_id, progress))
+            .collect()
+    }
+
+    fn run_detector(
+        &self,
+        detector: StuckType,
+        agent_id: &str,
+        progress: &AgentProgress,
+    ) -> Option<StuckDetection> {
+        match detector {
+            StuckType::TurnLimit => self.check_turn_limit(agent_id, progress),
+            StuckType::ContextLimit => self.check_context_limit(agent_id, progress),
+            StuckType::NoProgress => self.check_no_progress(agent_id, progress),
+            StuckType::NoFileChanges => self.check_no_file_changes(agent_id, progress),
+            StuckType::TokenBurn => self.check_token_burn(agent_id, progress),
+            StuckType::CiTimeout => self.check_ci_timeout(agent_id, progress),
+            StuckType::ReviewDelay => self.check_review_delay(agent_id, progress),
+            StuckType::MergeConflict => self.check_merge_conflict(agent_id, progress),
+            StuckType::RateLimit => self.check_rate_limit(agent_id, progress),
+            StuckType::ErrorLoop => self.check_error_loop(agent_id, progress),
         }
-
-        detections
     }
 
     fn check_turn_limit(&self, agent_id: &str, progress: &AgentProgress) -> Option<StuckDetection> {
@@ -385,6 +476,52 @@ impl StuckDetector {
         None
     }
 
+    fn check_no_file_changes(&self, agent_id: &str, progress: &AgentProgress) -> Option<StuckDetection> {
+        let threshold = self.config.no_file_changes_turn_threshold;
+        if threshold == 0 || progress.turns_since_file_change < threshold {
+            return None;
+        }
+
+        let severity = if progress.turns_since_file_change >= threshold * 2 {
+            StuckSeverity::High
+        } else {
+            StuckSeverity::Medium
+        };
+
+        Some(
+            StuckDetection::new(agent_id, StuckType::NoFileChanges, severity).with_details(
+                serde_json::json!({
+                    "turns_since_file_change": progress.turns_since_file_change,
+                    "threshold": threshold,
+                }),
+            ),
+        )
+    }
+
+    fn check_token_burn(&self, agent_id: &str, progress: &AgentProgress) -> Option<StuckDetection> {
+        let threshold = self.config.token_burn_threshold;
+        if threshold == 0 || progress.tokens_since_progress < threshold {
+            return None;
+        }
+
+        let severity = if progress.tokens_since_progress >= threshold * 3 {
+            StuckSeverity::Critical
+        } else if progress.tokens_since_progress >= threshold * 2 {
+            StuckSeverity::High
+        } else {
+            StuckSeverity::Medium
+        };
+
+        Some(
+            StuckDetection::new(agent_id, StuckType::TokenBurn, severity).with_details(
+                serde_json::json!({
+                    "tokens_since_progress": progress.tokens_since_progress,
+                    "threshold": threshold,
+                }),
+            ),
+        )
+    }
+
     fn check_ci_timeout(&self, agent_id: &str, progress: &AgentProgress) -> Option<StuckDetection> {
         if let Some(last_update) = progress.last_ci_update {
             let minutes_since = (Utc::now() - last_update).num_minutes() as u32;
@@ -450,6 +587,9 @@ impl StuckDetector {
         if let Some(until) = progress.rate_limited_until {
             if until > Utc::now() {
                 let wait_minutes = (until - Utc::now()).num_minutes();
+                if wait_minutes < self.config.rate_limit_stall_minutes as i64 {
+                    return None;
+                }
                 let severity = if wait_minutes > 30 {
                     StuckSeverity::High
                 } else if wait_minutes > 10 {
@@ -731,6 +871,8 @@ mod tests {
             StuckType::RateLimit,
             StuckType::ContextLimit,
             StuckType::ErrorLoop,
+            StuckType::NoFileChanges,
+            StuckType::TokenBurn,
         ];
 
         for t in types {
@@ -935,6 +1077,87 @@ mod tests {
         assert_eq!(detections[0].detection_type, StuckType::TurnLimit);
     }
 
+    #[test]
+    fn test_detector_no_file_changes() {
+        let detector = StuckDetector::new();
+        let mut progress = AgentProgress::new(100, 100000);
+        progress.turns_since_file_change = 25;
+
+        let detections = detector.check("agent-1", &progress);
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].detection_type, StuckType::NoFileChanges);
+        assert_eq!(detections[0].severity, StuckSeverity::High);
+    }
+
+    #[test]
+    fn test_detector_token_burn() {
+        let detector = StuckDetector::new();
+        let mut progress = AgentProgress::new(100, 10_000_000);
+        progress.tokens_since_progress = 150_000;
+
+        let detections = detector.check("agent-1", &progress);
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].detection_type, StuckType::TokenBurn);
+        assert_eq!(detections[0].severity, StuckSeverity::Medium);
+    }
+
+    #[test]
+    fn test_disabled_detectors_are_skipped() {
+        let mut config = StuckDetectionConfig::default();
+        config.set_enabled(StuckType::ErrorLoop, false);
+        config.set_enabled(StuckType::MergeConflict, false);
+        config.set_enabled(StuckType::MergeConflict, true);
+        let detector = StuckDetector::with_config(config);
+
+        let mut progress = AgentProgress::new(100, 100000);
+        progress.has_merge_conflicts = true;
+        progress.recent_error_count = 10;
+
+        let types: Vec<_> = detector
+            .check("agent-1", &progress)
+            .iter()
+            .map(|d| d.detection_type)
+            .collect();
+        assert_eq!(types, vec![StuckType::MergeConflict]);
+    }
+
+    #[test]
+    fn test_rate_limit_stall_threshold() {
+        let config = StuckDetectionConfig {
+            rate_limit_stall_minutes: 20,
+            ..Default::default()
+        };
+        let detector = StuckDetector::with_config(config);
+
+        let mut progress = AgentProgress::new(100, 100000);
+        progress.rate_limited_until = Some(Utc::now() + Duration::minutes(15));
+        assert!(detector.check("agent-1", &progress).is_empty());
+
+        progress.rate_limited_until = Some(Utc::now() + Duration::minutes(45));
+        assert_eq!(detector.check("agent-1", &progress).len(), 1);
+    }
+
+    #[test]
+    fn test_config_set_param() {
+        let mut config = StuckDetectionConfig::default();
+        config.set_param("token_burn_threshold", "5000").unwrap();
+        config.set_param("turn_warning_threshold", "75.5").unwrap();
+        assert_eq!(config.token_burn_threshold, 5000);
+        assert_eq!(config.turn_warning_threshold, 75.5);
+
+        assert!(config.set_param("token_burn_threshold", "lots").is_err());
+        assert!(config.set_param("bogus", "1").is_err());
+    }
+
+    #[test]
+    fn test_config_deserializes_without_new_fields() {
+        let json = r#"{"turn_warning_threshold":80.0,"token_warning_threshold":85.0,
+            "no_progress_turn_threshold":5,"ci_timeout_minutes":30,
+            "review_delay_minutes":60,"error_loop_threshold":3}"#;
+        let config: StuckDetectionConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config, StuckDetectionConfig::default());
+    }
+
     #[test]
     fn test_work_evaluation_new() {
         let eval = WorkEvaluation::new("agent-1", EvaluationType::Progress, EvaluationStatus::Healthy);
@@ -0,0 +1,799 @@
+//! Telegram Notifications
+//!
+//! Personal notifications through a Telegram bot, for solo developers who
+//! don't run Slack:
+//! - [`TelegramMessage`]: agent finished (completed or failed) and approval
+//!   needed messages; approval messages carry inline Approve / Reject buttons
+//! - [`TelegramClient`]: a Bot API client bound to one chat
+//! - [`TelegramService`]: posts approval requests and records button presses,
+//!   delivered to the bot's webhook as callback queries, as decisions
+//!
+//! The bot only talks to the chat in `TELEGRAM_CHAT_ID`; button presses from
+//! other chats are refused. Decisions are recorded as `TELEGRAM_APPROVER` (a
+//! GitHub username listed as a stage approver), or as the Telegram username
+//! of whoever pressed the button when it is not set.
+
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::approval::{ApprovalRequest, ApprovalStatus};
+use crate::approval_service::ApprovalService;
+use crate::monitoring::{NotificationChannel, NotificationChannelType};
+use crate::{Agent, AgentState, Database, Error, Result};
+
+/// Bot API base URL
+pub const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+
+/// Header carrying the secret token the webhook was registered with
+pub const TELEGRAM_SECRET_HEADER: &str = "x-telegram-bot-api-secret-token";
+
+/// Longest message text Telegram accepts
+const MAX_MESSAGE_LEN: usize = 4096;
+
+static CLIENT: OnceLock<TelegramClient> = OnceLock::new();
+
+/// Telegram bot configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelegramConfig {
+    pub bot_token: String,
+    /// Chat notifications are sent to, usually the developer's private chat
+    /// with the bot
+    pub chat_id: String,
+    /// GitHub username decisions are recorded as
+    #[serde(default)]
+    pub approver: Option<String>,
+    /// Secret token the webhook was registered with
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+}
+
+impl TelegramConfig {
+    pub fn new(bot_token: impl Into<String>, chat_id: impl Into<String>) -> Self {
+        Self {
+            bot_token: bot_token.into(),
+            chat_id: chat_id.into(),
+            approver: None,
+            webhook_secret: None,
+        }
+    }
+
+    pub fn with_approver(mut self, approver: impl Into<String>) -> Self {
+        self.approver = Some(approver.into());
+        self
+    }
+
+    pub fn with_webhook_secret(mut self, secret: impl Into<String>) -> Self {
+        self.webhook_secret = Some(secret.into());
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.bot_token.is_empty() {
+            return Err(Error::Config(
+                "Telegram bot token cannot be empty".to_string(),
+            ));
+        }
+        if !self.bot_token.contains(':') {
+            return Err(Error::Config(
+                "Invalid Telegram bot token format".to_string(),
+            ));
+        }
+        if self.chat_id.is_empty() {
+            return Err(Error::Config(
+                "Telegram chat ID cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Configuration from `TELEGRAM_BOT_TOKEN` and `TELEGRAM_CHAT_ID`, if a
+    /// bot token is set
+    ///
+    /// `TELEGRAM_APPROVER` and `TELEGRAM_WEBHOOK_SECRET` are optional.
+    pub fn from_env() -> Result<Option<Self>> {
+        Self::from_settings(|key| {
+            std::env::var(format!("TELEGRAM_{}", key.to_uppercase()))
+                .ok()
+                .filter(|v| !v.is_empty())
+        })
+    }
+
+    /// Read settings from a Telegram notification channel's config
+    ///
+    /// Uses the keys `bot_token`, `chat_id`, `approver`, and `webhook_secret`.
+    pub fn from_channel(channel: &NotificationChannel) -> Result<Self> {
+        if channel.channel_type != NotificationChannelType::Telegram {
+            return Err(Error::Other(format!(
+                "Notification channel {} is not a Telegram channel",
+                channel.name
+            )));
+        }
+        Self::from_settings(|key| channel.config.get(key).filter(|v| !v.is_empty()).cloned())?
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "Telegram channel {} has no bot token",
+                    channel.name
+                ))
+            })
+    }
+
+    fn from_settings(setting: impl Fn(&str) -> Option<String>) -> Result<Option<Self>> {
+        let Some(bot_token) = setting("bot_token") else {
+            return Ok(None);
+        };
+        let chat_id = setting("chat_id").ok_or_else(|| {
+            Error::Config("Telegram chat ID must be set with the bot token".to_string())
+        })?;
+
+        let mut config = Self::new(bot_token, chat_id);
+        config.approver = setting("approver");
+        config.webhook_secret = setting("webhook_secret");
+        config.validate()?;
+        Ok(Some(config))
+    }
+}
+
+/// Button under a message that sends `callback_data` back to the bot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InlineKeyboardButton {
+    pub text: String,
+    pub callback_data: String,
+}
+
+/// Rows of buttons under a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InlineKeyboardMarkup {
+    pub inline_keyboard: Vec<Vec<InlineKeyboardButton>>,
+}
+
+/// Message sent with `sendMessage`, formatted as HTML
+#[derive(Debug, Clone, Serialize)]
+pub struct TelegramMessage {
+    pub text: String,
+    pub parse_mode: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_markup: Option<InlineKeyboardMarkup>,
+}
+
+impl TelegramMessage {
+    pub fn new(text: impl Into<String>) -> Self {
+        let mut text = text.into();
+        if text.chars().count() > MAX_MESSAGE_LEN {
+            text = text.chars().take(MAX_MESSAGE_LEN - 3).collect::<String>() + "...";
+        }
+        Self {
+            text,
+            parse_mode: "HTML",
+            reply_markup: None,
+        }
+    }
+
+    /// Add a row of buttons
+    pub fn with_buttons(mut self, buttons: Vec<InlineKeyboardButton>) -> Self {
+        self.reply_markup
+            .get_or_insert_with(|| InlineKeyboardMarkup {
+                inline_keyboard: Vec::new(),
+            })
+            .inline_keyboard
+            .push(buttons);
+        self
+    }
+
+    /// Message reporting that an agent completed or failed
+    pub fn agent_finished(agent: &Agent) -> Self {
+        let (icon, outcome) = match agent.state {
+            AgentState::Completed => ("✅", "completed"),
+            AgentState::Failed => ("❌", "failed"),
+            _ => ("ℹ️", agent.state.as_str()),
+        };
+        let agent_type = agent
+            .custom_type
+            .clone()
+            .unwrap_or_else(|| agent.agent_type.as_str().to_string());
+
+        let mut text = format!(
+            "{} <b>Agent {}</b>: {}\n\n{}",
+            icon,
+            outcome,
+            escape_html(&agent_type),
+            escape_html(&agent.task)
+        );
+        if let Some(error) = &agent.error_message {
+            text.push_str(&format!("\n\n<b>Error:</b> {}", escape_html(error)));
+        }
+        text.push_str(&format!("\n\n<code>{}</code>", agent.id));
+        Self::new(text)
+    }
+
+    /// Message asking for a decision on `approval`, with Approve and Reject
+    /// buttons
+    pub fn approval_needed(
+        approval: &ApprovalRequest,
+        resource_type: &str,
+        resource_id: &str,
+        description: &str,
+    ) -> Self {
+        let approval_id = approval.id.unwrap_or_default();
+        let text = format!(
+            "⏸️ <b>Approval needed</b>: {} <b>{}</b>\n\n{}\n\nApprovers: {} ({} required)",
+            escape_html(resource_type),
+            escape_html(resource_id),
+            escape_html(description),
+            escape_html(&approval.required_approvers.replace(',', ", ")),
+            approval.required_count
+        );
+        Self::new(text).with_buttons(vec![
+            InlineKeyboardButton {
+                text: "✅ Approve".to_string(),
+                callback_data: TelegramDecision::Approve.callback_data(approval_id),
+            },
+            InlineKeyboardButton {
+                text: "❌ Reject".to_string(),
+                callback_data: TelegramDecision::Reject.callback_data(approval_id),
+            },
+        ])
+    }
+}
+
+/// Escape text for messages formatted as HTML
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Decision sent by an approval message's buttons
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelegramDecision {
+    Approve,
+    Reject,
+}
+
+impl TelegramDecision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Approve => "approve",
+            Self::Reject => "reject",
+        }
+    }
+
+    /// Callback data of the button deciding `approval_id`, e.g. `approve:7`
+    pub fn callback_data(&self, approval_id: i64) -> String {
+        format!("{}:{}", self.as_str(), approval_id)
+    }
+
+    /// Parse a button's callback data into a decision and approval ID
+    pub fn parse(data: &str) -> Option<(Self, i64)> {
+        let (decision, approval_id) = data.split_once(':')?;
+        let decision = match decision {
+            "approve" => Self::Approve,
+            "reject" => Self::Reject,
+            _ => return None,
+        };
+        Some((decision, approval_id.parse().ok()?))
+    }
+}
+
+/// Update delivered to the bot's webhook
+///
+/// Only callback queries are handled; other updates (e.g. messages sent to
+/// the bot) are ignored.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramUpdate {
+    pub update_id: i64,
+    #[serde(default)]
+    pub callback_query: Option<CallbackQuery>,
+}
+
+/// Press of an inline button
+#[derive(Debug, Clone, Deserialize)]
+pub struct CallbackQuery {
+    pub id: String,
+    pub from: TelegramUser,
+    /// Message the button was under
+    #[serde(default)]
+    pub message: Option<CallbackMessage>,
+    #[serde(default)]
+    pub data: Option<String>,
+}
+
+/// Telegram user who pressed a button
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramUser {
+    pub id: i64,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub first_name: String,
+}
+
+/// Message a pressed button was under
+#[derive(Debug, Clone, Deserialize)]
+pub struct CallbackMessage {
+    pub message_id: i64,
+    pub chat: TelegramChat,
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Chat a message was posted in
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramChat {
+    pub id: i64,
+}
+
+/// Bot API response
+#[derive(Debug, Clone, Deserialize)]
+struct ApiResponse {
+    ok: bool,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    result: Option<serde_json::Value>,
+}
+
+/// Bot API client sending to the configured chat
+#[derive(Debug, Clone)]
+pub struct TelegramClient {
+    http_client: reqwest::Client,
+    config: TelegramConfig,
+    base_url: String,
+}
+
+impl TelegramClient {
+    /// Create a client with a 10 second request timeout
+    pub fn new(config: TelegramConfig) -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            config,
+            base_url: TELEGRAM_API_URL.to_string(),
+        }
+    }
+
+    /// Client configured by [`TelegramConfig::from_env`], if a bot token is set
+    pub fn from_env() -> Result<Option<Self>> {
+        Ok(TelegramConfig::from_env()?.map(Self::new))
+    }
+
+    /// Send requests to a different Bot API server
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn config(&self) -> &TelegramConfig {
+        &self.config
+    }
+
+    /// Send a message to the configured chat, returning its message ID
+    pub async fn send_message(&self, message: &TelegramMessage) -> Result<i64> {
+        let mut body = serde_json::to_value(message)?;
+        body["chat_id"] = serde_json::Value::String(self.config.chat_id.clone());
+        let result = self.call("sendMessage", &body).await?;
+        Ok(result["message_id"].as_i64().unwrap_or_default())
+    }
+
+    /// Replace the text of a sent message, removing its buttons
+    pub async fn edit_message_text(&self, message_id: i64, text: &str) -> Result<()> {
+        let body = serde_json::json!({
+            "chat_id": self.config.chat_id,
+            "message_id": message_id,
+            "text": text,
+            "parse_mode": "HTML",
+        });
+        self.call("editMessageText", &body).await?;
+        Ok(())
+    }
+
+    /// Answer a button press, showing `text` to the user who pressed it
+    pub async fn answer_callback_query(&self, callback_query_id: &str, text: &str) -> Result<()> {
+        let body = serde_json::json!({
+            "callback_query_id": callback_query_id,
+            "text": text,
+        });
+        self.call("answerCallbackQuery", &body).await?;
+        Ok(())
+    }
+
+    /// Notify the chat that an agent finished, logging failures
+    pub async fn notify_agent_finished(&self, agent: &Agent) {
+        if let Err(e) = self
+            .send_message(&TelegramMessage::agent_finished(agent))
+            .await
+        {
+            warn!(agent_id = %agent.id, error = %e, "Failed to send Telegram notification");
+        }
+    }
+
+    async fn call(&self, method: &str, body: &serde_json::Value) -> Result<serde_json::Value> {
+        let url = format!("{}/bot{}/{}", self.base_url, self.config.bot_token, method);
+        let response = self
+            .http_client
+            .post(&url)
+            .json(body)
+            .send()
+            .await
+            // Errors would otherwise include the URL, and with it the bot token
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Telegram {} request failed: {}",
+                    method,
+                    e.without_url()
+                ))
+            })?;
+
+        let status = response.status();
+        let body: ApiResponse = response.json().await.map_err(|e| {
+            Error::Other(format!(
+                "Invalid Telegram {} response (HTTP {}): {}",
+                method,
+                status,
+                e.without_url()
+            ))
+        })?;
+        if !body.ok {
+            return Err(Error::Other(format!(
+                "Telegram {} failed (HTTP {}): {}",
+                method,
+                status,
+                body.description.as_deref().unwrap_or("unknown error")
+            )));
+        }
+        Ok(body.result.unwrap_or_default())
+    }
+}
+
+/// Service connecting approval requests to Telegram approval messages
+pub struct TelegramService {
+    db: Database,
+    client: TelegramClient,
+}
+
+impl TelegramService {
+    /// Create a new Telegram service
+    pub fn new(db: Database, client: TelegramClient) -> Self {
+        Self { db, client }
+    }
+
+    /// Service using the bot configured by `TELEGRAM_*`, if set
+    pub fn from_env(db: Database) -> Result<Option<Self>> {
+        Ok(TelegramClient::from_env()?.map(|client| Self::new(db, client)))
+    }
+
+    pub fn client(&self) -> &TelegramClient {
+        &self.client
+    }
+
+    /// Send an approval message with Approve and Reject buttons, returning
+    /// its message ID
+    pub async fn post_approval(
+        &self,
+        approval: &ApprovalRequest,
+        resource_type: &str,
+        resource_id: &str,
+        description: &str,
+    ) -> Result<i64> {
+        if approval.id.is_none() {
+            return Err(Error::Other("Approval request has no ID".to_string()));
+        }
+        self.client
+            .send_message(&TelegramMessage::approval_needed(
+                approval,
+                resource_type,
+                resource_id,
+                description,
+            ))
+            .await
+    }
+
+    /// Record a button press as a decision on its approval request
+    ///
+    /// Returns the updated approval request, whose run can be resumed once
+    /// it is decided, or `None` for presses of other buttons. Errors (e.g.
+    /// the press came from another chat) should be shown to the user who
+    /// pressed the button.
+    pub async fn handle_callback(&self, query: &CallbackQuery) -> Result<Option<ApprovalRequest>> {
+        let Some((decision, approval_id)) = query.data.as_deref().and_then(TelegramDecision::parse)
+        else {
+            return Ok(None);
+        };
+
+        let chat_id = query.message.as_ref().map(|message| message.chat.id);
+        if chat_id.map(|id| id.to_string()).as_deref() != Some(self.client.config.chat_id.as_str())
+        {
+            return Err(Error::Other(
+                "Approvals can only be decided from the configured chat".to_string(),
+            ));
+        }
+
+        let approver = self
+            .client
+            .config
+            .approver
+            .clone()
+            .or_else(|| query.from.username.clone())
+            .unwrap_or_else(|| query.from.id.to_string());
+        let comment = Some("Decided from Telegram".to_string());
+        let approval_service = ApprovalService::new(self.db.clone());
+        let approval = match decision {
+            TelegramDecision::Approve => {
+                approval_service
+                    .approve(approval_id, approver, comment)
+                    .await?
+            }
+            TelegramDecision::Reject => {
+                approval_service
+                    .reject(approval_id, approver, comment)
+                    .await?
+            }
+        };
+        Ok(Some(approval))
+    }
+
+    /// Answer a button press with the outcome of [`Self::handle_callback`],
+    /// replacing the buttons with the decision once the approval is decided
+    pub async fn confirm_callback(
+        &self,
+        query: &CallbackQuery,
+        outcome: &Result<Option<ApprovalRequest>>,
+    ) -> Result<()> {
+        let approval = match outcome {
+            Ok(Some(approval)) => approval,
+            Ok(None) => return self.client.answer_callback_query(&query.id, "").await,
+            Err(e) => {
+                return self
+                    .client
+                    .answer_callback_query(&query.id, &e.to_string())
+                    .await
+            }
+        };
+
+        let status = match approval.status {
+            ApprovalStatus::Approved => "Approved",
+            ApprovalStatus::Rejected => "Rejected",
+            _ => {
+                let text = format!(
+                    "Recorded ({}/{} approvals)",
+                    approval.approval_count, approval.required_count
+                );
+                return self.client.answer_callback_query(&query.id, &text).await;
+            }
+        };
+
+        if let Some(message) = &query.message {
+            let who = query
+                .from
+                .username
+                .as_ref()
+                .map(|username| format!("@{}", username))
+                .unwrap_or_else(|| query.from.first_name.clone());
+            let text = format!(
+                "{}\n\n<b>{}</b> by {}",
+                escape_html(message.text.as_deref().unwrap_or_default()),
+                status,
+                escape_html(&who)
+            );
+            self.client
+                .edit_message_text(message.message_id, &text)
+                .await?;
+        }
+        self.client.answer_callback_query(&query.id, status).await
+    }
+}
+
+/// Install the client agents report finishing to
+///
+/// Only the first call has an effect.
+pub fn init(client: TelegramClient) {
+    let _ = CLIENT.set(client);
+}
+
+/// Call [`init`] with [`TelegramClient::from_env`], returning whether a bot
+/// was configured
+pub fn init_from_env() -> Result<bool> {
+    match TelegramClient::from_env()? {
+        Some(client) => {
+            init(client);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Client installed by [`init`], if any
+pub fn client() -> Option<&'static TelegramClient> {
+    CLIENT.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_servers::{json_body, serve};
+    use crate::AgentType;
+    use serde_json::json;
+
+    fn config() -> TelegramConfig {
+        TelegramConfig::new("123456:ABC-DEF", "42")
+    }
+
+    fn callback(data: &str, chat_id: i64) -> CallbackQuery {
+        serde_json::from_value(json!({
+            "id": "cb-1",
+            "from": { "id": 42, "username": "jane", "first_name": "Jane" },
+            "message": { "message_id": 7, "chat": { "id": chat_id }, "text": "Approval needed" },
+            "data": data,
+        }))
+        .unwrap()
+    }
+
+    async fn pending_approval(db: &Database, approvers: &str) -> ApprovalRequest {
+        let pipeline = crate::Pipeline::new("deploy".to_string(), "name: deploy".to_string());
+        let pipeline_id = db.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = db
+            .insert_pipeline_run(&crate::PipelineRun::new(pipeline_id, None))
+            .await
+            .unwrap();
+        let stage_id = db
+            .insert_pipeline_stage(&crate::PipelineStage::new(run_id, "deploy".to_string()))
+            .await
+            .unwrap();
+
+        db.create_approval_request(ApprovalRequest::new(
+            stage_id,
+            run_id,
+            approvers.to_string(),
+            1,
+            None,
+            None,
+        ))
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(config().validate().is_ok());
+        assert!(TelegramConfig::new("", "42").validate().is_err());
+        assert!(TelegramConfig::new("no-colon", "42").validate().is_err());
+        assert!(TelegramConfig::new("123456:ABC", "").validate().is_err());
+    }
+
+    #[test]
+    fn test_config_from_channel() {
+        let channel = NotificationChannel::new("me", NotificationChannelType::Telegram)
+            .with_config("bot_token", "123456:ABC-DEF")
+            .with_config("chat_id", "42")
+            .with_config("approver", "alice");
+        let config = TelegramConfig::from_channel(&channel).unwrap();
+        assert_eq!(config.chat_id, "42");
+        assert_eq!(config.approver.as_deref(), Some("alice"));
+        assert!(config.webhook_secret.is_none());
+
+        let channel = NotificationChannel::new("me", NotificationChannelType::Telegram)
+            .with_config("bot_token", "123456:ABC-DEF");
+        assert!(TelegramConfig::from_channel(&channel).is_err());
+
+        let channel = NotificationChannel::new("ops-slack", NotificationChannelType::Slack)
+            .with_config("bot_token", "123456:ABC-DEF")
+            .with_config("chat_id", "42");
+        assert!(TelegramConfig::from_channel(&channel).is_err());
+    }
+
+    #[test]
+    fn test_callback_data() {
+        assert_eq!(TelegramDecision::Approve.callback_data(7), "approve:7");
+        assert_eq!(
+            TelegramDecision::parse("reject:12"),
+            Some((TelegramDecision::Reject, 12))
+        );
+        assert_eq!(TelegramDecision::parse("delegate:12"), None);
+        assert_eq!(TelegramDecision::parse("approve:x"), None);
+    }
+
+    #[test]
+    fn test_messages() {
+        let mut agent = Agent::new(AgentType::StoryDeveloper, "Fix <login> & logout");
+        agent.state = AgentState::Failed;
+        agent.error_message = Some("Tests failed".to_string());
+        let message = TelegramMessage::agent_finished(&agent);
+        assert!(message.text.starts_with("❌ <b>Agent failed</b>"));
+        assert!(message.text.contains("Fix &lt;login&gt; &amp; logout"));
+        assert!(message.text.contains("<b>Error:</b> Tests failed"));
+        assert!(message.reply_markup.is_none());
+
+        let mut approval = ApprovalRequest::new(5, 42, "alice,bob".to_string(), 1, None, None);
+        approval.id = Some(9);
+        let message =
+            TelegramMessage::approval_needed(&approval, "Pipeline stage", "deploy", "Run #42");
+        assert!(message.text.contains("Approvers: alice, bob (1 required)"));
+        let buttons = &message.reply_markup.unwrap().inline_keyboard[0];
+        assert_eq!(buttons[0].callback_data, "approve:9");
+        assert_eq!(buttons[1].callback_data, "reject:9");
+    }
+
+    #[tokio::test]
+    async fn test_send_message() {
+        let (url, request) = serve(vec![(200, r#"{"ok":true,"result":{"message_id":99}}"#)]).await;
+        let client = TelegramClient::new(config()).with_base_url(url);
+
+        let message_id = client
+            .send_message(&TelegramMessage::new("Hello"))
+            .await
+            .unwrap();
+        assert_eq!(message_id, 99);
+
+        let request = request.await.unwrap().remove(0);
+        assert!(request.starts_with("POST /bot123456:ABC-DEF/sendMessage "));
+        let body = json_body(&request);
+        assert_eq!(body["chat_id"], "42");
+        assert_eq!(body["parse_mode"], "HTML");
+    }
+
+    #[tokio::test]
+    async fn test_api_error() {
+        let (url, _request) = serve(vec![(
+            200,
+            r#"{"ok":false,"description":"Bad Request: chat not found"}"#,
+        )])
+        .await;
+        let client = TelegramClient::new(config()).with_base_url(url);
+
+        let err = client
+            .send_message(&TelegramMessage::new("Hello"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("chat not found"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_callback() {
+        let db = Database::in_memory().await.unwrap();
+        let service = TelegramService::new(
+            db.clone(),
+            TelegramClient::new(config().with_approver("alice")),
+        );
+        let approval = pending_approval(&db, "alice,bob").await;
+        let data = TelegramDecision::Approve.callback_data(approval.id.unwrap());
+
+        // Presses from other chats are refused
+        let err = service
+            .handle_callback(&callback(&data, 7))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("configured chat"));
+
+        let decided = service
+            .handle_callback(&callback(&data, 42))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(decided.status, ApprovalStatus::Approved);
+
+        // Other buttons are ignored
+        assert!(service
+            .handle_callback(&callback("view:1", 42))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_callback_as_telegram_user() {
+        let db = Database::in_memory().await.unwrap();
+        let service = TelegramService::new(db.clone(), TelegramClient::new(config()));
+        let approval = pending_approval(&db, "jane").await;
+        let data = TelegramDecision::Reject.callback_data(approval.id.unwrap());
+
+        let decided = service
+            .handle_callback(&callback(&data, 42))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(decided.status, ApprovalStatus::Rejected);
+    }
+}
@@ -0,0 +1,105 @@
+//! OpenTelemetry tracing
+//!
+//! Exports `tracing` spans — agent loop turns, tool executions, database
+//! queries, webhook processing, and pipeline stages — to an OpenTelemetry
+//! collector over OTLP/HTTP. Export is enabled by the standard
+//! `OTEL_EXPORTER_OTLP_ENDPOINT` (or `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`)
+//! variable; the service name defaults to `orchestrate` unless
+//! `OTEL_SERVICE_NAME` is set.
+//!
+//! Agent runs record their trace ID on the agent (see [`current_trace_id`]),
+//! so a slow story can be looked up in the tracing backend end to end.
+
+use crate::{Error, Result};
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::{runtime, trace as sdktrace, Resource};
+use tracing::Subscriber;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Service name reported when `OTEL_SERVICE_NAME` is not set
+pub const DEFAULT_SERVICE_NAME: &str = "orchestrate";
+
+/// Whether an OTLP endpoint is configured in the environment
+pub fn otlp_configured() -> bool {
+    [
+        "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+        "OTEL_EXPORTER_OTLP_ENDPOINT",
+    ]
+    .iter()
+    .any(|var| std::env::var(var).is_ok_and(|v| !v.is_empty()))
+}
+
+/// Build a `tracing` layer exporting spans over OTLP
+///
+/// Returns `None` when no OTLP endpoint is configured. Spans are exported in
+/// batches from the Tokio runtime, so this must be called within one.
+pub fn otlp_layer<S>() -> Result<Option<OpenTelemetryLayer<S, sdktrace::Tracer>>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    if !otlp_configured() {
+        return Ok(None);
+    }
+
+    let service_name =
+        std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| DEFAULT_SERVICE_NAME.to_string());
+
+    let tracer =
+        opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(opentelemetry_otlp::new_exporter().http())
+            .with_trace_config(sdktrace::config().with_resource(Resource::new(vec![
+                KeyValue::new("service.name", service_name),
+            ])))
+            .install_batch(runtime::Tokio)
+            .map_err(|e| Error::Other(format!("Failed to start OTLP exporter: {}", e)))?;
+
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+}
+
+/// Flush pending spans and stop exporting
+pub fn shutdown() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+/// Trace ID of the current span, if spans are being exported
+pub fn current_trace_id() -> Option<String> {
+    let context = tracing::Span::current().context();
+    let span = context.span();
+    let span_context = span.span_context();
+    span_context
+        .is_valid()
+        .then(|| span_context.trace_id().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TracerProvider as _;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_current_trace_id() {
+        // No OpenTelemetry layer, no trace
+        assert!(current_trace_id().is_none());
+
+        let provider = sdktrace::TracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(current_trace_id().is_none());
+
+            let root = tracing::info_span!("agent_run");
+            let _entered = root.enter();
+            let trace_id = current_trace_id().unwrap();
+            assert_eq!(trace_id.len(), 32);
+
+            // Child spans share the trace
+            let child = tracing::info_span!("agent_turn");
+            assert_eq!(child.in_scope(current_trace_id), Some(trace_id));
+        });
+    }
+}
@@ -0,0 +1,155 @@
+//! Local servers standing in for the HTTP APIs and mail servers that
+//! clients call in tests
+
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Answer one request per `(status, body)` reply, in order, returning the
+/// raw requests received
+pub async fn serve(replies: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for (status, body) in replies {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            requests.push(read_request(&mut socket).await);
+            write_response(&mut socket, status, body).await;
+        }
+        requests
+    });
+
+    (url, handle)
+}
+
+/// Answer every request with the `(status, body)` that `reply` returns for
+/// the raw request, until the test ends
+pub async fn serve_with<F>(mut reply: F) -> String
+where
+    F: FnMut(&str) -> (u16, String) + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let request = read_request(&mut socket).await;
+            let (status, body) = reply(&request);
+            write_response(&mut socket, status, &body).await;
+        }
+    });
+
+    url
+}
+
+/// Request line of a raw request, e.g. `POST /v2/enqueue HTTP/1.1`
+pub fn request_line(request: &str) -> &str {
+    request.lines().next().unwrap()
+}
+
+/// Body of a raw request, parsed as JSON
+pub fn json_body(request: &str) -> Value {
+    serde_json::from_str(request.split("\r\n\r\n").nth(1).unwrap()).unwrap()
+}
+
+/// Read a request's head and as much body as its `Content-Length` says
+async fn read_request(socket: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request).to_string();
+        if let Some(end) = text.find("\r\n\r\n") {
+            let length = text
+                .lines()
+                .find_map(|line| {
+                    line.to_ascii_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|v| v.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+            if request.len() >= end + 4 + length || n == 0 {
+                break;
+            }
+        }
+    }
+    String::from_utf8_lossy(&request).to_string()
+}
+
+async fn write_response(socket: &mut TcpStream, status: u16, body: &str) {
+    let reason = reqwest::StatusCode::from_u16(status)
+        .ok()
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("Status");
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    socket.write_all(response.as_bytes()).await.unwrap();
+}
+
+/// Accept one SMTP session, rejecting the `reject_rcpt` command, returning
+/// the port listened on and the session transcript
+pub async fn smtp(reject_rcpt: Option<&'static str>) -> (u16, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let handle = tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut socket = BufReader::new(socket);
+        let mut transcript = String::new();
+        socket
+            .get_mut()
+            .write_all(b"220 fake ESMTP\r\n")
+            .await
+            .unwrap();
+        loop {
+            let mut line = String::new();
+            if socket.read_line(&mut line).await.unwrap() == 0 {
+                break;
+            }
+            transcript.push_str(&line);
+            let reply: &[u8] = match line.trim_end() {
+                l if l.starts_with("EHLO") => b"250-fake\r\n250-AUTH PLAIN\r\n250 8BITMIME\r\n",
+                l if l.starts_with("AUTH") => b"235 ok\r\n",
+                l if Some(l) == reject_rcpt => b"550 no such user\r\n",
+                l if l.starts_with("MAIL") || l.starts_with("RCPT") => b"250 ok\r\n",
+                "DATA" => {
+                    socket
+                        .get_mut()
+                        .write_all(b"354 go ahead\r\n")
+                        .await
+                        .unwrap();
+                    loop {
+                        let mut data = String::new();
+                        socket.read_line(&mut data).await.unwrap();
+                        transcript.push_str(&data);
+                        if data == ".\r\n" {
+                            break;
+                        }
+                    }
+                    b"250 queued\r\n"
+                }
+                "QUIT" => {
+                    socket.get_mut().write_all(b"221 bye\r\n").await.unwrap();
+                    break;
+                }
+                _ => b"500 unknown\r\n",
+            };
+            socket.get_mut().write_all(reply).await.unwrap();
+        }
+        let mut rest = String::new();
+        let _ = socket.read_to_string(&mut rest).await;
+        transcript
+    });
+
+    (port, handle)
+}
@@ -373,62 +373,6 @@ pub enum IssueSeverity {
     Critical,
 }
 
-/// Slack service (stub)
-#[derive(Debug, Clone)]
-pub struct SlackService {
-    // Placeholder
-}
-
-impl SlackService {
-    pub fn new() -> Self {
-        Self {}
-    }
-
-    pub async fn get_active_connection(&self) -> Result<crate::slack::SlackConnection, crate::Error> {
-        Err(crate::Error::Other("Slack not configured".to_string()))
-    }
-
-    pub async fn get_channel_config(&self, _id: &str) -> Result<crate::slack::ChannelConfig, crate::Error> {
-        Err(crate::Error::Other("Slack not configured".to_string()))
-    }
-
-    pub async fn save_connection(&self, _conn: &crate::slack::SlackConnection) -> Result<(), crate::Error> {
-        Err(crate::Error::Other("Slack not configured".to_string()))
-    }
-
-    pub async fn update_channel_config(&self, _config: &crate::slack::ChannelConfig) -> Result<(), crate::Error> {
-        Err(crate::Error::Other("Slack not configured".to_string()))
-    }
-}
-
-impl Default for SlackService {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-/// Slack user service (stub)
-#[derive(Debug, Clone)]
-pub struct SlackUserService {
-    // Placeholder
-}
-
-impl SlackUserService {
-    pub fn new() -> Self {
-        Self {}
-    }
-
-    pub async fn list_user_mappings(&self) -> Result<Vec<crate::slack::UserMapping>, crate::Error> {
-        Ok(Vec::new())
-    }
-}
-
-impl Default for SlackUserService {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 // Re-export TestType for easy access
 pub use crate::test_generation::TestType;
 
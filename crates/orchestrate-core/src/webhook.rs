@@ -61,6 +61,8 @@ pub struct WebhookEvent {
     pub delivery_id: String,
     /// Event type (e.g., "pull_request", "check_run")
     pub event_type: String,
+    /// Receiver the event came in through: github, gitlab, or custom:<name>
+    pub source: String,
     /// Raw JSON payload from GitHub
     pub payload: String,
     /// Current status
@@ -91,6 +93,7 @@ impl WebhookEvent {
             id: None,
             delivery_id,
             event_type,
+            source: "github".to_string(),
             payload,
             status: WebhookEventStatus::Pending,
             retry_count: 0,
@@ -104,6 +107,12 @@ impl WebhookEvent {
         }
     }
 
+    /// Set the receiver the event came in through
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = source.into();
+        self
+    }
+
     /// Check if event can be retried
     pub fn can_retry(&self) -> bool {
         self.retry_count < self.max_retries
@@ -144,6 +153,67 @@ impl WebhookEvent {
             self.next_retry_at = None;
         }
     }
+
+    /// Queue the event again with a fresh retry budget
+    pub fn requeue(&mut self) {
+        self.status = WebhookEventStatus::Pending;
+        self.retry_count = 0;
+        self.error_message = None;
+        self.next_retry_at = None;
+        self.processed_at = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Move an event that can never succeed straight to the dead letter queue
+    pub fn mark_poisoned(&mut self, error: String) {
+        self.error_message = Some(error);
+        self.status = WebhookEventStatus::DeadLetter;
+        self.next_retry_at = None;
+        self.updated_at = Utc::now();
+    }
+}
+
+/// A webhook event without its payload, as listed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEventSummary {
+    pub id: i64,
+    pub delivery_id: String,
+    pub event_type: String,
+    /// Payload `action`, e.g. "opened" for pull_request events
+    pub action: Option<String>,
+    pub source: String,
+    pub status: WebhookEventStatus,
+    pub retry_count: i32,
+    pub max_retries: i32,
+    pub error_message: Option<String>,
+    pub next_retry_at: Option<DateTime<Utc>>,
+    pub received_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+    pub payload_bytes: usize,
+}
+
+impl WebhookEventSummary {
+    /// Summary of an event whose payload is parsed as `payload`
+    pub fn new(event: &WebhookEvent, payload: &serde_json::Value) -> Self {
+        Self {
+            id: event.id.unwrap_or_default(),
+            delivery_id: event.delivery_id.clone(),
+            event_type: event.event_type.clone(),
+            action: payload
+                .get("action")
+                .and_then(|a| a.as_str())
+                .map(str::to_string),
+            source: event.source.clone(),
+            status: event.status,
+            retry_count: event.retry_count,
+            max_retries: event.max_retries,
+            error_message: event.error_message.clone(),
+            next_retry_at: event.next_retry_at,
+            received_at: event.received_at,
+            processed_at: event.processed_at,
+            payload_bytes: event.payload.len(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -243,6 +313,42 @@ mod tests {
         assert!(event.next_retry_at.is_none());
     }
 
+    #[test]
+    fn test_webhook_event_mark_poisoned() {
+        let mut event = WebhookEvent::new(
+            "delivery-123".to_string(),
+            "pull_request".to_string(),
+            "not json".to_string(),
+        );
+
+        event.mark_poisoned("invalid payload".to_string());
+        assert_eq!(event.status, WebhookEventStatus::DeadLetter);
+        assert_eq!(event.retry_count, 0);
+        assert_eq!(event.error_message.as_deref(), Some("invalid payload"));
+    }
+
+    #[test]
+    fn test_webhook_event_requeue() {
+        let mut event = WebhookEvent::new(
+            "delivery-123".to_string(),
+            "pull_request".to_string(),
+            "{}".to_string(),
+        )
+        .with_source("gitlab");
+        assert_eq!(event.source, "gitlab");
+
+        for _ in 0..4 {
+            event.mark_failed("error".to_string());
+        }
+        assert_eq!(event.status, WebhookEventStatus::DeadLetter);
+
+        event.requeue();
+        assert_eq!(event.status, WebhookEventStatus::Pending);
+        assert_eq!(event.retry_count, 0);
+        assert!(event.error_message.is_none());
+        assert!(event.can_retry());
+    }
+
     #[test]
     fn test_webhook_event_exponential_backoff() {
         let mut event = WebhookEvent::new(
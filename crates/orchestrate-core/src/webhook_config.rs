@@ -177,7 +177,7 @@ impl EventFilter {
 
 /// Substitute environment variables in the YAML string
 /// Supports ${VAR_NAME} syntax
-fn substitute_env_vars(yaml: &str) -> String {
+pub(crate) fn substitute_env_vars(yaml: &str) -> String {
     let mut result = yaml.to_string();
 
     // Match ${VAR_NAME} patterns
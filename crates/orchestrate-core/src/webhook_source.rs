@@ -0,0 +1,439 @@
+//! Webhook Source Signatures
+//!
+//! Each inbound webhook source (`github`, `gitlab`, or `custom:<name>`) can
+//! be configured with its own signature scheme, header, and secrets instead
+//! of the single global secret:
+//!
+//! - `hmac-sha256`: hex HMAC-SHA256 of the body, optionally prefixed `sha256=`
+//! - `hmac-sha512`: hex HMAC-SHA512 of the body, optionally prefixed `sha512=`
+//! - `ed25519`: hex or base64 Ed25519 signature of the body, verified with
+//!   the sender's public key
+//! - `token`: the secret itself, sent as a shared token
+//!
+//! Rotating a secret keeps the previous ones valid for an overlap window so
+//! senders can switch over without dropped deliveries.
+
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Sha512};
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// Signature scheme a webhook source signs its payloads with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignatureScheme {
+    /// HMAC-SHA256 with a shared secret
+    HmacSha256,
+    /// HMAC-SHA512 with a shared secret
+    HmacSha512,
+    /// Ed25519 signature checked with the sender's public key
+    Ed25519,
+    /// Shared token compared with the secret
+    Token,
+}
+
+impl SignatureScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::HmacSha256 => "hmac-sha256",
+            Self::HmacSha512 => "hmac-sha512",
+            Self::Ed25519 => "ed25519",
+            Self::Token => "token",
+        }
+    }
+
+    /// Header the signature is read from unless the source sets another
+    pub fn default_header(&self, source: &str) -> &'static str {
+        match (source, self) {
+            ("gitlab", Self::Token) => "X-Gitlab-Token",
+            (_, Self::HmacSha256) => "X-Hub-Signature-256",
+            (_, Self::HmacSha512) => "X-Hub-Signature-512",
+            (_, Self::Ed25519) => "X-Signature-Ed25519",
+            (_, Self::Token) => "X-Webhook-Token",
+        }
+    }
+}
+
+impl FromStr for SignatureScheme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "hmac-sha256" => Ok(Self::HmacSha256),
+            "hmac-sha512" => Ok(Self::HmacSha512),
+            "ed25519" => Ok(Self::Ed25519),
+            "token" => Ok(Self::Token),
+            _ => Err(Error::Other(format!(
+                "Invalid signature scheme: {} (expected hmac-sha256, hmac-sha512, ed25519, or token)",
+                s
+            ))),
+        }
+    }
+}
+
+/// A secret of a webhook source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookSourceSecret {
+    /// Shared secret, or the sender's public key for Ed25519
+    pub secret: String,
+    /// When the secret was added
+    pub created_at: DateTime<Utc>,
+    /// When the secret stops being accepted, set once it is rotated out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookSourceSecret {
+    /// Whether the secret is accepted at the given time
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
+
+/// Signature configuration of an inbound webhook source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSource {
+    /// Database ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    /// Source name: `github`, `gitlab`, or `custom:<name>`
+    pub name: String,
+    /// Signature scheme
+    pub scheme: SignatureScheme,
+    /// Header the signature or token is read from
+    pub header: String,
+    /// Secrets, newest last
+    pub secrets: Vec<WebhookSourceSecret>,
+    /// Created timestamp
+    pub created_at: DateTime<Utc>,
+    /// Updated timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookSource {
+    /// Create a source reading the scheme's default header
+    pub fn new(
+        name: impl Into<String>,
+        scheme: SignatureScheme,
+        secret: impl Into<String>,
+    ) -> Self {
+        let name = name.into();
+        let now = Utc::now();
+        Self {
+            id: None,
+            header: scheme.default_header(&name).to_string(),
+            name,
+            scheme,
+            secrets: vec![WebhookSourceSecret {
+                secret: secret.into(),
+                created_at: now,
+                expires_at: None,
+            }],
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Read the signature from another header
+    pub fn with_header(mut self, header: impl Into<String>) -> Self {
+        self.header = header.into();
+        self
+    }
+
+    /// Check the source name, header, and secrets
+    pub fn validate(&self) -> Result<()> {
+        let valid_name = match self.name.strip_prefix("custom:") {
+            Some(custom) => {
+                !custom.is_empty()
+                    && custom
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            }
+            None => matches!(self.name.as_str(), "github" | "gitlab"),
+        };
+        if !valid_name {
+            return Err(Error::Other(format!(
+                "Invalid webhook source '{}' (expected github, gitlab, or custom:<name>)",
+                self.name
+            )));
+        }
+        if self.header.is_empty() {
+            return Err(Error::Other(format!(
+                "Webhook source '{}' needs a signature header",
+                self.name
+            )));
+        }
+        for secret in &self.secrets {
+            if secret.secret.is_empty() {
+                return Err(Error::Other(format!(
+                    "Webhook source '{}' has an empty secret",
+                    self.name
+                )));
+            }
+            if self.scheme == SignatureScheme::Ed25519 {
+                ed25519_public_key(&secret.secret)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Secrets accepted at the given time
+    pub fn active_secrets(&self, now: DateTime<Utc>) -> impl Iterator<Item = &WebhookSourceSecret> {
+        self.secrets
+            .iter()
+            .filter(move |secret| secret.is_active(now))
+    }
+
+    /// Add a new secret, keeping the current ones valid for the overlap window
+    ///
+    /// Secrets that already expired are dropped.
+    pub fn rotate(&mut self, secret: impl Into<String>, overlap: Duration) {
+        let now = Utc::now();
+        let expires_at = now + overlap;
+        self.secrets.retain(|secret| secret.is_active(now));
+        for secret in &mut self.secrets {
+            secret.expires_at = Some(secret.expires_at.map_or(expires_at, |e| e.min(expires_at)));
+        }
+        self.secrets.push(WebhookSourceSecret {
+            secret: secret.into(),
+            created_at: now,
+            expires_at: None,
+        });
+        self.updated_at = now;
+    }
+
+    /// Whether the signature is valid for the body under any active secret
+    pub fn verify(&self, body: &[u8], signature: &str) -> bool {
+        self.active_secrets(Utc::now())
+            .any(|secret| verify_signature(self.scheme, &secret.secret, body, signature))
+    }
+}
+
+/// Whether a signature header value is valid for the body
+///
+/// For HMAC schemes the secret is the shared key, for Ed25519 the sender's
+/// hex or base64 public key, and for tokens the expected token.
+pub fn verify_signature(
+    scheme: SignatureScheme,
+    secret: &str,
+    body: &[u8],
+    signature: &str,
+) -> bool {
+    let signature = signature.trim();
+    match scheme {
+        SignatureScheme::HmacSha256 => {
+            let Ok(expected) = hex::decode(signature.strip_prefix("sha256=").unwrap_or(signature))
+            else {
+                return false;
+            };
+            let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+                return false;
+            };
+            mac.update(body);
+            mac.verify_slice(&expected).is_ok()
+        }
+        SignatureScheme::HmacSha512 => {
+            let Ok(expected) = hex::decode(signature.strip_prefix("sha512=").unwrap_or(signature))
+            else {
+                return false;
+            };
+            let Ok(mut mac) = Hmac::<Sha512>::new_from_slice(secret.as_bytes()) else {
+                return false;
+            };
+            mac.update(body);
+            mac.verify_slice(&expected).is_ok()
+        }
+        SignatureScheme::Ed25519 => {
+            let Ok(key) = ed25519_public_key(secret) else {
+                return false;
+            };
+            let signature = signature.strip_prefix("ed25519=").unwrap_or(signature);
+            let Some(bytes) = decode_hex_or_base64(signature)
+                .and_then(|bytes| <[u8; 64]>::try_from(bytes.as_slice()).ok())
+            else {
+                return false;
+            };
+            key.verify_strict(body, &ed25519_dalek::Signature::from_bytes(&bytes))
+                .is_ok()
+        }
+        SignatureScheme::Token => {
+            secret.len() == signature.len()
+                && secret
+                    .bytes()
+                    .zip(signature.bytes())
+                    .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                    == 0
+        }
+    }
+}
+
+/// Parse a hex or base64 Ed25519 public key
+fn ed25519_public_key(key: &str) -> Result<ed25519_dalek::VerifyingKey> {
+    let invalid =
+        || Error::Other("Ed25519 public key must be 32 bytes of hex or base64".to_string());
+    let bytes = decode_hex_or_base64(key.trim())
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes.as_slice()).ok())
+        .ok_or_else(invalid)?;
+    ed25519_dalek::VerifyingKey::from_bytes(&bytes).map_err(|_| invalid())
+}
+
+fn decode_hex_or_base64(value: &str) -> Option<Vec<u8>> {
+    hex::decode(value)
+        .ok()
+        .or_else(|| base64::engine::general_purpose::STANDARD.decode(value).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn hmac_sha512_hex(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha512>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn test_scheme_roundtrip() {
+        for scheme in [
+            SignatureScheme::HmacSha256,
+            SignatureScheme::HmacSha512,
+            SignatureScheme::Ed25519,
+            SignatureScheme::Token,
+        ] {
+            assert_eq!(SignatureScheme::from_str(scheme.as_str()).unwrap(), scheme);
+        }
+        assert!(SignatureScheme::from_str("md5").is_err());
+        assert_eq!(
+            SignatureScheme::Token.default_header("gitlab"),
+            "X-Gitlab-Token"
+        );
+        assert_eq!(
+            SignatureScheme::Token.default_header("custom:sentry"),
+            "X-Webhook-Token"
+        );
+    }
+
+    #[test]
+    fn test_verify_hmac_signatures() {
+        let body = b"{\"action\":\"opened\"}";
+        let sha256 = crate::sign_payload("secret", body);
+        assert!(verify_signature(
+            SignatureScheme::HmacSha256,
+            "secret",
+            body,
+            &sha256
+        ));
+        assert!(!verify_signature(
+            SignatureScheme::HmacSha256,
+            "other",
+            body,
+            &sha256
+        ));
+
+        let sha512 = hmac_sha512_hex("secret", body);
+        assert!(verify_signature(
+            SignatureScheme::HmacSha512,
+            "secret",
+            body,
+            &format!("sha512={}", sha512)
+        ));
+        assert!(verify_signature(
+            SignatureScheme::HmacSha512,
+            "secret",
+            body,
+            &sha512
+        ));
+        assert!(!verify_signature(
+            SignatureScheme::HmacSha512,
+            "secret",
+            b"{}",
+            &sha512
+        ));
+        assert!(!verify_signature(
+            SignatureScheme::HmacSha512,
+            "secret",
+            body,
+            "zz"
+        ));
+    }
+
+    #[test]
+    fn test_verify_ed25519_signature() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = hex::encode(signing_key.verifying_key().to_bytes());
+        let body = b"{\"event\":\"deploy\"}";
+        let signature = signing_key.sign(body).to_bytes();
+
+        assert!(verify_signature(
+            SignatureScheme::Ed25519,
+            &public_key,
+            body,
+            &hex::encode(signature)
+        ));
+        let base64_signature = base64::engine::general_purpose::STANDARD.encode(signature);
+        assert!(verify_signature(
+            SignatureScheme::Ed25519,
+            &public_key,
+            body,
+            &base64_signature
+        ));
+        assert!(!verify_signature(
+            SignatureScheme::Ed25519,
+            &public_key,
+            b"{}",
+            &base64_signature
+        ));
+
+        let source = WebhookSource::new("custom:deployer", SignatureScheme::Ed25519, "not-a-key");
+        assert!(source
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("Ed25519"));
+    }
+
+    #[test]
+    fn test_rotation_overlap() {
+        let body = b"{}";
+        let mut source = WebhookSource::new("github", SignatureScheme::HmacSha256, "old");
+        assert_eq!(source.header, "X-Hub-Signature-256");
+
+        source.rotate("new", Duration::hours(24));
+        assert_eq!(source.active_secrets(Utc::now()).count(), 2);
+        assert!(source.verify(body, &crate::sign_payload("old", body)));
+        assert!(source.verify(body, &crate::sign_payload("new", body)));
+
+        // Once the window passes only the new secret is accepted
+        let later = Utc::now() + Duration::hours(25);
+        let active: Vec<_> = source
+            .active_secrets(later)
+            .map(|s| s.secret.as_str())
+            .collect();
+        assert_eq!(active, vec!["new"]);
+
+        // Rotating without overlap retires the current secrets immediately
+        source.rotate("newest", Duration::zero());
+        assert!(!source.verify(body, &crate::sign_payload("new", body)));
+        assert!(source.verify(body, &crate::sign_payload("newest", body)));
+    }
+
+    #[test]
+    fn test_validate_source_names() {
+        for name in ["github", "gitlab", "custom:sentry"] {
+            assert!(WebhookSource::new(name, SignatureScheme::Token, "t")
+                .validate()
+                .is_ok());
+        }
+        for name in ["bitbucket", "custom:", "custom:bad/name"] {
+            assert!(WebhookSource::new(name, SignatureScheme::Token, "t")
+                .validate()
+                .is_err());
+        }
+    }
+}
@@ -0,0 +1,612 @@
+//! Pluggable Work Checks
+//!
+//! Individual checks that the [`WorkEvaluator`](crate::WorkEvaluator) runs to
+//! decide whether agent work is complete. Checks are registered by name and
+//! the set (and order) of checks to run is configured per agent type.
+//!
+//! Built-in checks:
+//! - `ci_green` - build and test checks pass
+//! - `lint_clean` - lint/format checks pass
+//! - `coverage_delta` - test coverage did not drop beyond a threshold
+//! - `review_approved` - code review approved without blocking issues
+//! - `pr_mergeable` - PR has no conflicts and is not blocked
+//! - `acceptance_criteria` - pre-computed acceptance criteria checks are met
+//! - `acceptance_criteria_llm` - acceptance criteria judged by an LLM (registered
+//!   by the caller with a [`CriteriaJudge`])
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::decision_engine::AgentStatus;
+use crate::work_evaluation::{
+    CiCheckResult, CiStatus, CriterionCheck, FeedbackItem, FeedbackType, PrMergeStatus,
+    ReviewResult, ReviewVerdict, WorkCompletionStatus,
+};
+
+/// CI check name fragments identifying build checks
+const BUILD_CHECK_NAMES: &[&str] = &["build", "compile"];
+/// CI check name fragments identifying test checks
+const TEST_CHECK_NAMES: &[&str] = &["test", "tests", "pytest", "cargo-test"];
+/// CI check name fragments identifying lint checks
+const LINT_CHECK_NAMES: &[&str] = &["lint", "clippy", "eslint", "fmt", "format"];
+
+/// Everything a check may inspect about the work under evaluation
+#[derive(Debug, Clone, Default)]
+pub struct WorkCheckInput {
+    /// Agent type whose work is evaluated (selects the configured checks)
+    pub agent_type: String,
+    /// Status signal emitted by the agent
+    pub agent_status: Option<AgentStatus>,
+    /// Acceptance criteria of the story, as written
+    pub acceptance_criteria: Vec<String>,
+    /// Acceptance criteria checks already computed by the caller
+    pub criteria_checks: Vec<CriterionCheck>,
+    /// CI check results
+    pub ci_checks: Vec<CiCheckResult>,
+    /// Code review result
+    pub review_result: Option<ReviewResult>,
+    /// PR merge status
+    pub pr_status: Option<PrMergeStatus>,
+    /// Change in test coverage, in percentage points
+    pub coverage_delta: Option<f64>,
+    /// Agent's summary of the work (or the diff) used as evidence
+    pub work_summary: Option<String>,
+}
+
+impl WorkCheckInput {
+    pub fn new(agent_type: impl Into<String>) -> Self {
+        Self {
+            agent_type: agent_type.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_agent_status(mut self, status: AgentStatus) -> Self {
+        self.agent_status = Some(status);
+        self
+    }
+
+    pub fn with_acceptance_criteria(mut self, criteria: Vec<String>) -> Self {
+        self.acceptance_criteria = criteria;
+        self
+    }
+
+    pub fn with_criteria_checks(mut self, checks: Vec<CriterionCheck>) -> Self {
+        self.criteria_checks = checks;
+        self
+    }
+
+    pub fn with_ci_checks(mut self, checks: Vec<CiCheckResult>) -> Self {
+        self.ci_checks = checks;
+        self
+    }
+
+    pub fn with_review(mut self, review: ReviewResult) -> Self {
+        self.review_result = Some(review);
+        self
+    }
+
+    pub fn with_pr_status(mut self, status: PrMergeStatus) -> Self {
+        self.pr_status = Some(status);
+        self
+    }
+
+    pub fn with_coverage_delta(mut self, delta: f64) -> Self {
+        self.coverage_delta = Some(delta);
+        self
+    }
+
+    pub fn with_work_summary(mut self, summary: impl Into<String>) -> Self {
+        self.work_summary = Some(summary.into());
+        self
+    }
+
+    /// CI checks whose name contains any of `names`
+    fn ci_checks_named<'a>(
+        &'a self,
+        names: &'a [&str],
+    ) -> impl Iterator<Item = &'a CiCheckResult> + 'a {
+        self.ci_checks.iter().filter(move |check| {
+            let name = check.name.to_lowercase();
+            names.iter().any(|n| name.contains(n))
+        })
+    }
+}
+
+/// Result of running a single check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkCheckOutcome {
+    /// Name of the check that produced this outcome
+    pub check: String,
+    /// Whether the check passed
+    pub passed: bool,
+    /// Completion status the work is in when this check fails
+    pub failure_status: Option<WorkCompletionStatus>,
+    /// Feedback for the agent
+    pub feedback: Vec<FeedbackItem>,
+    /// Acceptance criteria checks produced by this check
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub criteria_checks: Vec<CriterionCheck>,
+}
+
+impl WorkCheckOutcome {
+    pub fn pass(check: impl Into<String>) -> Self {
+        Self {
+            check: check.into(),
+            passed: true,
+            failure_status: None,
+            feedback: Vec::new(),
+            criteria_checks: Vec::new(),
+        }
+    }
+
+    pub fn fail(check: impl Into<String>, status: WorkCompletionStatus) -> Self {
+        Self {
+            check: check.into(),
+            passed: false,
+            failure_status: Some(status),
+            feedback: Vec::new(),
+            criteria_checks: Vec::new(),
+        }
+    }
+
+    pub fn with_feedback(mut self, item: FeedbackItem) -> Self {
+        self.feedback.push(item);
+        self
+    }
+
+    pub fn with_criteria_checks(mut self, checks: Vec<CriterionCheck>) -> Self {
+        self.criteria_checks = checks;
+        self
+    }
+}
+
+/// A single registrable work completion check
+#[async_trait]
+pub trait WorkCheck: Send + Sync {
+    /// Unique name used to register and configure the check
+    fn name(&self) -> &str;
+
+    /// Run the check
+    async fn check(&self, input: &WorkCheckInput) -> WorkCheckOutcome;
+}
+
+/// Judges whether a single acceptance criterion is met (typically via an LLM)
+#[async_trait]
+pub trait CriteriaJudge: Send + Sync {
+    async fn judge(&self, criterion: &str, work_summary: &str) -> crate::Result<CriterionCheck>;
+}
+
+/// Failure feedback for a failed CI check
+fn ci_failure_feedback(check: &CiCheckResult, feedback_type: FeedbackType) -> FeedbackItem {
+    let msg = match &check.failure_details {
+        Some(details) => format!("{} failed: {}", check.name, details),
+        None => format!("{} failed", check.name),
+    };
+    FeedbackItem::new(feedback_type, msg)
+        .with_priority(90)
+        .with_action(format!("Fix {} failures", check.name))
+}
+
+/// Build and test CI checks pass
+#[derive(Debug, Clone, Default)]
+pub struct CiGreenCheck;
+
+#[async_trait]
+impl WorkCheck for CiGreenCheck {
+    fn name(&self) -> &str {
+        "ci_green"
+    }
+
+    async fn check(&self, input: &WorkCheckInput) -> WorkCheckOutcome {
+        let mut outcome = WorkCheckOutcome::pass(self.name());
+        let mut running = false;
+
+        for (names, feedback_type) in [
+            (BUILD_CHECK_NAMES, FeedbackType::BuildFailure),
+            (TEST_CHECK_NAMES, FeedbackType::TestFailure),
+        ] {
+            for check in input.ci_checks_named(names) {
+                match check.status {
+                    CiStatus::Failed | CiStatus::Timeout | CiStatus::Cancelled => {
+                        outcome.passed = false;
+                        outcome.failure_status = Some(WorkCompletionStatus::NeedsCiFixes);
+                        outcome
+                            .feedback
+                            .push(ci_failure_feedback(check, feedback_type));
+                    }
+                    CiStatus::Running => running = true,
+                    _ => {}
+                }
+            }
+        }
+
+        if outcome.passed && running {
+            return WorkCheckOutcome::fail(self.name(), WorkCompletionStatus::InProgress);
+        }
+        outcome
+    }
+}
+
+/// Lint and format CI checks pass
+#[derive(Debug, Clone, Default)]
+pub struct LintCleanCheck;
+
+#[async_trait]
+impl WorkCheck for LintCleanCheck {
+    fn name(&self) -> &str {
+        "lint_clean"
+    }
+
+    async fn check(&self, input: &WorkCheckInput) -> WorkCheckOutcome {
+        let mut outcome = WorkCheckOutcome::pass(self.name());
+
+        for check in input.ci_checks_named(LINT_CHECK_NAMES) {
+            if matches!(check.status, CiStatus::Failed) {
+                outcome.passed = false;
+                outcome.failure_status = Some(WorkCompletionStatus::NeedsCiFixes);
+                outcome
+                    .feedback
+                    .push(ci_failure_feedback(check, FeedbackType::LintIssue));
+            }
+        }
+
+        outcome
+    }
+}
+
+/// Test coverage did not drop by more than `max_drop` percentage points
+#[derive(Debug, Clone)]
+pub struct CoverageDeltaCheck {
+    pub max_drop: f64,
+}
+
+impl CoverageDeltaCheck {
+    pub fn new(max_drop: f64) -> Self {
+        Self { max_drop }
+    }
+}
+
+impl Default for CoverageDeltaCheck {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+#[async_trait]
+impl WorkCheck for CoverageDeltaCheck {
+    fn name(&self) -> &str {
+        "coverage_delta"
+    }
+
+    async fn check(&self, input: &WorkCheckInput) -> WorkCheckOutcome {
+        // No coverage report means nothing to hold the work to
+        match input.coverage_delta {
+            Some(delta) if delta < -self.max_drop => {
+                let msg = format!("Test coverage dropped by {:.1} percentage points", -delta);
+                WorkCheckOutcome::fail(self.name(), WorkCompletionStatus::NeedsCiFixes).with_feedback(
+                    FeedbackItem::new(FeedbackType::TestFailure, msg)
+                        .with_priority(70)
+                        .with_action("Add tests covering the new code"),
+                )
+            }
+            _ => WorkCheckOutcome::pass(self.name()),
+        }
+    }
+}
+
+/// Code review approved without blocking issues
+#[derive(Debug, Clone, Default)]
+pub struct ReviewApprovedCheck;
+
+#[async_trait]
+impl WorkCheck for ReviewApprovedCheck {
+    fn name(&self) -> &str {
+        "review_approved"
+    }
+
+    async fn check(&self, input: &WorkCheckInput) -> WorkCheckOutcome {
+        let Some(review) = &input.review_result else {
+            return WorkCheckOutcome::fail(self.name(), WorkCompletionStatus::NeedsReview);
+        };
+
+        let mut outcome = WorkCheckOutcome::pass(self.name());
+        for issue in review.issues.iter().filter(|i| i.severity.blocks_merge()) {
+            let msg = format!(
+                "[{}] {}",
+                issue.severity.as_str().to_uppercase(),
+                issue.description
+            );
+            let mut item = FeedbackItem::new(FeedbackType::ReviewIssue, msg).with_priority(85);
+            if let Some(suggestion) = &issue.suggestion {
+                item = item.with_action(suggestion.clone());
+            }
+            outcome.feedback.push(item);
+        }
+
+        if review.has_blocking_issues() || matches!(review.verdict, ReviewVerdict::ChangesRequested)
+        {
+            outcome.passed = false;
+            outcome.failure_status = Some(WorkCompletionStatus::NeedsReviewFixes);
+        } else if !review.verdict.is_passing() {
+            outcome.passed = false;
+            outcome.failure_status = Some(WorkCompletionStatus::NeedsReview);
+        }
+        outcome
+    }
+}
+
+/// PR has no conflicts and is not blocked (passes when there is no PR yet)
+#[derive(Debug, Clone, Default)]
+pub struct PrMergeableCheck;
+
+#[async_trait]
+impl WorkCheck for PrMergeableCheck {
+    fn name(&self) -> &str {
+        "pr_mergeable"
+    }
+
+    async fn check(&self, input: &WorkCheckInput) -> WorkCheckOutcome {
+        match input.pr_status {
+            Some(PrMergeStatus::Conflicts) => {
+                WorkCheckOutcome::fail(self.name(), WorkCompletionStatus::Blocked).with_feedback(
+                    FeedbackItem::new(
+                        FeedbackType::MergeConflict,
+                        "PR has merge conflicts that need resolution.",
+                    )
+                    .with_priority(95)
+                    .with_action("Resolve merge conflicts"),
+                )
+            }
+            Some(PrMergeStatus::Blocked) => {
+                WorkCheckOutcome::fail(self.name(), WorkCompletionStatus::NeedsPrApproval)
+            }
+            _ => WorkCheckOutcome::pass(self.name()),
+        }
+    }
+}
+
+/// Feedback for unmet criteria, failing the check if any are unmet
+fn criteria_outcome(name: &str, checks: Vec<CriterionCheck>) -> WorkCheckOutcome {
+    let mut outcome = if checks.iter().all(|c| c.is_met) {
+        WorkCheckOutcome::pass(name)
+    } else {
+        WorkCheckOutcome::fail(name, WorkCompletionStatus::InProgress)
+    };
+
+    for check in checks.iter().filter(|c| !c.is_met) {
+        outcome.feedback.push(
+            FeedbackItem::new(
+                FeedbackType::MissingCriterion,
+                format!("Criterion not met: {}", check.criterion),
+            )
+            .with_priority(80)
+            .with_action(format!("Implement: {}", check.criterion)),
+        );
+    }
+
+    outcome.with_criteria_checks(checks)
+}
+
+/// Acceptance criteria checks supplied by the caller are met
+#[derive(Debug, Clone)]
+pub struct AcceptanceCriteriaCheck {
+    /// Checks below this confidence count as unmet
+    pub min_confidence: f64,
+}
+
+impl Default for AcceptanceCriteriaCheck {
+    fn default() -> Self {
+        Self { min_confidence: 0.5 }
+    }
+}
+
+#[async_trait]
+impl WorkCheck for AcceptanceCriteriaCheck {
+    fn name(&self) -> &str {
+        "acceptance_criteria"
+    }
+
+    async fn check(&self, input: &WorkCheckInput) -> WorkCheckOutcome {
+        if input.criteria_checks.is_empty() {
+            return WorkCheckOutcome::fail(self.name(), WorkCompletionStatus::InProgress);
+        }
+
+        let checks = input
+            .criteria_checks
+            .iter()
+            .cloned()
+            .map(|mut c| {
+                c.is_met = c.is_met && c.confidence >= self.min_confidence;
+                c
+            })
+            .collect();
+        criteria_outcome(self.name(), checks)
+    }
+}
+
+/// Acceptance criteria judged one by one by a [`CriteriaJudge`]
+pub struct LlmAcceptanceCriteriaCheck {
+    judge: Arc<dyn CriteriaJudge>,
+    min_confidence: f64,
+}
+
+impl LlmAcceptanceCriteriaCheck {
+    pub fn new(judge: Arc<dyn CriteriaJudge>) -> Self {
+        Self {
+            judge,
+            min_confidence: 0.5,
+        }
+    }
+
+    pub fn with_min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+}
+
+#[async_trait]
+impl WorkCheck for LlmAcceptanceCriteriaCheck {
+    fn name(&self) -> &str {
+        "acceptance_criteria_llm"
+    }
+
+    async fn check(&self, input: &WorkCheckInput) -> WorkCheckOutcome {
+        let Some(summary) = input.work_summary.as_deref() else {
+            return WorkCheckOutcome::fail(self.name(), WorkCompletionStatus::InProgress);
+        };
+        if input.acceptance_criteria.is_empty() {
+            return WorkCheckOutcome::fail(self.name(), WorkCompletionStatus::InProgress);
+        }
+
+        let mut checks = Vec::with_capacity(input.acceptance_criteria.len());
+        for criterion in &input.acceptance_criteria {
+            let check = match self.judge.judge(criterion, summary).await {
+                Ok(mut check) => {
+                    check.is_met = check.is_met && check.confidence >= self.min_confidence;
+                    check
+                }
+                // An unanswered criterion cannot be assumed met
+                Err(e) => CriterionCheck::unmet(criterion.clone())
+                    .with_evidence(format!("Judge failed: {}", e))
+                    .with_confidence(0.0),
+            };
+            checks.push(check);
+        }
+
+        criteria_outcome(self.name(), checks)
+    }
+}
+
+/// Checks run when no per-agent-type list is configured, in evaluation order
+pub fn default_work_checks() -> Vec<String> {
+    ["ci_green", "lint_clean", "review_approved", "pr_mergeable", "acceptance_criteria"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// All built-in checks that need no external dependencies
+pub fn builtin_work_checks() -> Vec<Arc<dyn WorkCheck>> {
+    vec![
+        Arc::new(CiGreenCheck),
+        Arc::new(LintCleanCheck),
+        Arc::new(CoverageDeltaCheck::default()),
+        Arc::new(ReviewApprovedCheck),
+        Arc::new(PrMergeableCheck),
+        Arc::new(AcceptanceCriteriaCheck::default()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct KeywordJudge;
+
+    #[async_trait]
+    impl CriteriaJudge for KeywordJudge {
+        async fn judge(&self, criterion: &str, summary: &str) -> crate::Result<CriterionCheck> {
+            if criterion.contains("explode") {
+                return Err(crate::Error::Other("model unavailable".to_string()));
+            }
+            let keyword = criterion.split_whitespace().last().unwrap_or_default();
+            Ok(if summary.contains(keyword) {
+                CriterionCheck::met(criterion).with_confidence(0.9)
+            } else {
+                CriterionCheck::unmet(criterion).with_confidence(0.9)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ci_green_check() {
+        let input = WorkCheckInput::new("story_developer").with_ci_checks(vec![
+            CiCheckResult::new("build", CiStatus::Passed),
+            CiCheckResult::new("cargo-test", CiStatus::Failed).with_failure("2 failed"),
+            CiCheckResult::new("lint", CiStatus::Failed),
+        ]);
+
+        let outcome = CiGreenCheck.check(&input).await;
+        assert!(!outcome.passed);
+        assert_eq!(outcome.failure_status, Some(WorkCompletionStatus::NeedsCiFixes));
+        assert_eq!(outcome.feedback.len(), 1);
+        assert_eq!(outcome.feedback[0].feedback_type, FeedbackType::TestFailure);
+
+        let running = WorkCheckInput::new("story_developer")
+            .with_ci_checks(vec![CiCheckResult::new("build", CiStatus::Running)]);
+        let outcome = CiGreenCheck.check(&running).await;
+        assert_eq!(outcome.failure_status, Some(WorkCompletionStatus::InProgress));
+    }
+
+    #[tokio::test]
+    async fn test_lint_clean_check() {
+        let input = WorkCheckInput::new("story_developer")
+            .with_ci_checks(vec![CiCheckResult::new("clippy", CiStatus::Failed)]);
+        let outcome = LintCleanCheck.check(&input).await;
+        assert!(!outcome.passed);
+        assert_eq!(outcome.feedback[0].feedback_type, FeedbackType::LintIssue);
+
+        let clean = WorkCheckInput::new("story_developer")
+            .with_ci_checks(vec![CiCheckResult::new("clippy", CiStatus::Passed)]);
+        assert!(LintCleanCheck.check(&clean).await.passed);
+    }
+
+    #[tokio::test]
+    async fn test_coverage_delta_check() {
+        let check = CoverageDeltaCheck::new(1.0);
+
+        let small_drop = WorkCheckInput::new("story_developer").with_coverage_delta(-0.5);
+        assert!(check.check(&small_drop).await.passed);
+
+        let big_drop = WorkCheckInput::new("story_developer").with_coverage_delta(-3.0);
+        let outcome = check.check(&big_drop).await;
+        assert!(!outcome.passed);
+        assert!(outcome.feedback[0].message.contains("3.0"));
+
+        let unreported = WorkCheckInput::new("story_developer");
+        assert!(check.check(&unreported).await.passed);
+    }
+
+    #[tokio::test]
+    async fn test_acceptance_criteria_check_respects_confidence() {
+        let input = WorkCheckInput::new("story_developer").with_criteria_checks(vec![
+            CriterionCheck::met("Login works"),
+            CriterionCheck::met("Logout works").with_confidence(0.2),
+        ]);
+
+        let outcome = AcceptanceCriteriaCheck::default().check(&input).await;
+        assert!(!outcome.passed);
+        assert_eq!(outcome.feedback.len(), 1);
+        assert!(outcome.feedback[0].message.contains("Logout works"));
+    }
+
+    #[tokio::test]
+    async fn test_llm_acceptance_criteria_check() {
+        let check = LlmAcceptanceCriteriaCheck::new(Arc::new(KeywordJudge));
+        let input = WorkCheckInput::new("story_developer")
+            .with_acceptance_criteria(vec![
+                "User can login".to_string(),
+                "User can logout".to_string(),
+            ])
+            .with_work_summary("Implemented login form");
+
+        let outcome = check.check(&input).await;
+        assert!(!outcome.passed);
+        assert_eq!(outcome.criteria_checks.len(), 2);
+        assert!(outcome.criteria_checks[0].is_met);
+        assert!(!outcome.criteria_checks[1].is_met);
+
+        let failing = WorkCheckInput::new("story_developer")
+            .with_acceptance_criteria(vec!["Judge will explode".to_string()])
+            .with_work_summary("anything");
+        let outcome = check.check(&failing).await;
+        assert!(!outcome.passed);
+        assert!(outcome.criteria_checks[0]
+            .evidence
+            .as_deref()
+            .unwrap()
+            .contains("model unavailable"));
+    }
+}
@@ -9,11 +9,21 @@
 //! - Build/lint status
 //! - PR approval status
 //! - Generates feedback for agent continuation
+//!
+//! Besides the fixed [`WorkEvaluator::evaluate`] sequence, the evaluator runs
+//! registrable [`WorkCheck`]s configured per agent type via
+//! [`WorkEvaluator::evaluate_checks`].
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 use crate::decision_engine::AgentStatus;
+use crate::work_checks::{
+    builtin_work_checks, default_work_checks, AcceptanceCriteriaCheck, WorkCheck,
+    WorkCheckInput, WorkCheckOutcome,
+};
 
 /// Review verdict from code review
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -139,6 +149,9 @@ pub struct ReviewIssue {
     pub suggestion: Option<String>,
     /// Issue category
     pub category: Option<String>,
+    /// PR review thread the issue was raised in (if applicable)
+    #[serde(default)]
+    pub thread_id: Option<String>,
 }
 
 impl ReviewIssue {
@@ -150,6 +163,7 @@ impl ReviewIssue {
             line_number: None,
             suggestion: None,
             category: None,
+            thread_id: None,
         }
     }
 
@@ -168,6 +182,23 @@ impl ReviewIssue {
         self.category = Some(category.into());
         self
     }
+
+    pub fn with_thread(mut self, thread_id: impl Into<String>) -> Self {
+        self.thread_id = Some(thread_id.into());
+        self
+    }
+
+    /// Whether `other` reports the same problem, by thread or by location
+    pub fn same_issue(&self, other: &ReviewIssue) -> bool {
+        match (&self.thread_id, &other.thread_id) {
+            (Some(a), Some(b)) => a == b,
+            _ => {
+                self.file_path.is_some()
+                    && self.file_path == other.file_path
+                    && self.line_number == other.line_number
+            }
+        }
+    }
 }
 
 /// Code review result
@@ -551,6 +582,9 @@ pub struct WorkEvaluationResult {
     pub story_id: Option<String>,
     /// Agent ID being evaluated
     pub agent_id: Option<String>,
+    /// Outcomes of the pluggable checks that ran (empty for `evaluate`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub check_outcomes: Vec<WorkCheckOutcome>,
 }
 
 impl WorkEvaluationResult {
@@ -571,6 +605,7 @@ impl WorkEvaluationResult {
             evaluated_at: Utc::now(),
             story_id: None,
             agent_id: None,
+            check_outcomes: Vec::new(),
         }
     }
 
@@ -767,6 +802,12 @@ pub struct WorkEvaluatorConfig {
     pub max_review_iterations: u32,
     /// Minimum confidence for criterion check
     pub min_criterion_confidence: f64,
+    /// Checks run by `evaluate_checks`, in order, for agent types without their own list
+    #[serde(default = "default_work_checks")]
+    pub default_checks: Vec<String>,
+    /// Checks run by `evaluate_checks` per agent type
+    #[serde(default)]
+    pub checks_by_agent_type: HashMap<String, Vec<String>>,
 }
 
 impl Default for WorkEvaluatorConfig {
@@ -777,25 +818,166 @@ impl Default for WorkEvaluatorConfig {
             allow_merge_with_minor_issues: true,
             max_review_iterations: 3,
             min_criterion_confidence: 0.5,
+            default_checks: default_work_checks(),
+            checks_by_agent_type: HashMap::new(),
         }
     }
 }
 
 /// Work evaluator
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct WorkEvaluator {
     config: WorkEvaluatorConfig,
+    /// Registered checks by name
+    checks: HashMap<String, Arc<dyn WorkCheck>>,
+}
+
+impl std::fmt::Debug for WorkEvaluator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut checks: Vec<&String> = self.checks.keys().collect();
+        checks.sort();
+        f.debug_struct("WorkEvaluator")
+            .field("config", &self.config)
+            .field("checks", &checks)
+            .finish()
+    }
 }
 
 impl WorkEvaluator {
     pub fn new() -> Self {
-        Self {
-            config: WorkEvaluatorConfig::default(),
-        }
+        Self::with_config(WorkEvaluatorConfig::default())
     }
 
     pub fn with_config(config: WorkEvaluatorConfig) -> Self {
-        Self { config }
+        let mut evaluator = Self {
+            config,
+            checks: HashMap::new(),
+        };
+        for check in builtin_work_checks() {
+            evaluator.register_check(check);
+        }
+        evaluator.register_check(Arc::new(AcceptanceCriteriaCheck {
+            min_confidence: evaluator.config.min_criterion_confidence,
+        }));
+        evaluator
+    }
+
+    /// Register a check, replacing any check with the same name
+    pub fn register_check(&mut self, check: Arc<dyn WorkCheck>) {
+        self.checks.insert(check.name().to_string(), check);
+    }
+
+    /// Builder form of [`register_check`](Self::register_check)
+    pub fn with_check(mut self, check: Arc<dyn WorkCheck>) -> Self {
+        self.register_check(check);
+        self
+    }
+
+    /// Names of the checks run for an agent type, in order
+    ///
+    /// CI checks are dropped when `require_ci_pass` is off and the review
+    /// check when `require_review_approval` is off.
+    pub fn checks_for(&self, agent_type: &str) -> Vec<String> {
+        self.config
+            .checks_by_agent_type
+            .get(agent_type)
+            .unwrap_or(&self.config.default_checks)
+            .iter()
+            .filter(|name| match name.as_str() {
+                "ci_green" | "lint_clean" => self.config.require_ci_pass,
+                "review_approved" => self.config.require_review_approval,
+                _ => true,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Evaluate work by running the checks configured for the input's agent type
+    ///
+    /// A blocked or errored agent short-circuits the checks. Otherwise the
+    /// first failing check (in configured order) decides the status; when all
+    /// checks pass the work is ready to merge if the PR is mergeable, complete
+    /// if the agent says so, and in progress otherwise.
+    pub async fn evaluate_checks(
+        &self,
+        input: WorkCheckInput,
+    ) -> crate::Result<WorkEvaluationResult> {
+        let names = self.checks_for(&input.agent_type);
+        let checks = names
+            .iter()
+            .map(|name| {
+                self.checks.get(name).cloned().ok_or_else(|| {
+                    crate::Error::Other(format!("Unknown work check: {}", name))
+                })
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let mut result = WorkEvaluationResult::new(WorkCompletionStatus::InProgress);
+        result.build_status = self.extract_ci_status(&input.ci_checks, &["build", "compile"]);
+        result.lint_status =
+            self.extract_ci_status(&input.ci_checks, &["lint", "clippy", "eslint", "fmt", "format"]);
+        result.test_status =
+            self.extract_ci_status(&input.ci_checks, &["test", "tests", "pytest", "cargo-test"]);
+        result.ci_status = self.aggregate_ci_status(&input.ci_checks);
+
+        let mut status = match input.agent_status {
+            Some(AgentStatus::Blocked) => Some(WorkCompletionStatus::Blocked),
+            Some(AgentStatus::Error) => Some(WorkCompletionStatus::Failed),
+            _ => None,
+        };
+        let mut criteria_checks = input.criteria_checks.clone();
+
+        if status.is_none() {
+            for check in checks {
+                let outcome = check.check(&input).await;
+                if !outcome.criteria_checks.is_empty() {
+                    criteria_checks = outcome.criteria_checks.clone();
+                }
+                if !outcome.passed && status.is_none() {
+                    status = outcome.failure_status;
+                }
+                result.check_outcomes.push(outcome);
+            }
+        }
+
+        let status = status.unwrap_or(match (input.pr_status, input.agent_status) {
+            (Some(PrMergeStatus::Mergeable), _) => WorkCompletionStatus::ReadyToMerge,
+            (_, Some(AgentStatus::Complete)) => WorkCompletionStatus::Complete,
+            _ => WorkCompletionStatus::InProgress,
+        });
+
+        let mut items: Vec<FeedbackItem> = result
+            .check_outcomes
+            .iter()
+            .flat_map(|o| o.feedback.iter().cloned())
+            .collect();
+        let mut messages: Vec<String> = match status {
+            WorkCompletionStatus::Complete | WorkCompletionStatus::ReadyToMerge => {
+                vec!["Work is complete and ready.".to_string()]
+            }
+            WorkCompletionStatus::InProgress => vec!["Work is still in progress.".to_string()],
+            WorkCompletionStatus::Blocked => {
+                items.push(FeedbackItem::new(FeedbackType::Blocker, "Agent is blocked"));
+                vec!["Work is blocked and needs intervention.".to_string()]
+            }
+            WorkCompletionStatus::Failed => {
+                items.push(FeedbackItem::new(FeedbackType::Blocker, "Agent reported error"));
+                vec!["Work has failed.".to_string()]
+            }
+            _ => Vec::new(),
+        };
+        items.sort_by_key(|i| std::cmp::Reverse(i.priority));
+        messages.extend(items.iter().map(|i| i.message.clone()));
+
+        result.status = status;
+        result.agent_status = input.agent_status;
+        result.criteria_checks = criteria_checks;
+        result.ci_checks = input.ci_checks;
+        result.review_result = input.review_result;
+        result.pr_status = input.pr_status;
+        result.feedback = Some(messages.join("\n"));
+        result.feedback_items = items;
+        Ok(result)
     }
 
     /// Evaluate work completion from various inputs
@@ -850,6 +1032,7 @@ impl WorkEvaluator {
             evaluated_at: Utc::now(),
             story_id: None,
             agent_id: None,
+            check_outcomes: Vec::new(),
         }
     }
 
@@ -1844,4 +2027,95 @@ src/main.rs:100: [MEDIUM] - Consider error handling
         // Should not be blocked by CI when not required
         assert_ne!(result.status, WorkCompletionStatus::NeedsCiFixes);
     }
+
+    #[tokio::test]
+    async fn test_evaluate_checks_first_failure_decides_status() {
+        let evaluator = WorkEvaluator::new();
+        let input = WorkCheckInput::new("story_developer")
+            .with_agent_status(AgentStatus::Complete)
+            .with_criteria_checks(vec![CriterionCheck::unmet("Feature")])
+            .with_ci_checks(vec![CiCheckResult::new("build", CiStatus::Failed)])
+            .with_review(ReviewResult::new(ReviewVerdict::Approved));
+
+        let result = evaluator.evaluate_checks(input).await.unwrap();
+        assert_eq!(result.status, WorkCompletionStatus::NeedsCiFixes);
+        assert_eq!(result.check_outcomes.len(), 5);
+        assert!(result
+            .feedback_items
+            .iter()
+            .any(|i| i.feedback_type == FeedbackType::MissingCriterion));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_checks_all_passing() {
+        let evaluator = WorkEvaluator::new();
+        let input = WorkCheckInput::new("story_developer")
+            .with_agent_status(AgentStatus::Complete)
+            .with_criteria_checks(vec![CriterionCheck::met("Feature")])
+            .with_ci_checks(vec![CiCheckResult::new("build", CiStatus::Passed)])
+            .with_review(ReviewResult::new(ReviewVerdict::Approved))
+            .with_pr_status(PrMergeStatus::Mergeable);
+
+        let result = evaluator.evaluate_checks(input).await.unwrap();
+        assert_eq!(result.status, WorkCompletionStatus::ReadyToMerge);
+        assert!(result.check_outcomes.iter().all(|o| o.passed));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_checks_per_agent_type() {
+        let mut config = WorkEvaluatorConfig::default();
+        config.checks_by_agent_type.insert(
+            "test_writer".to_string(),
+            vec!["ci_green".to_string(), "coverage_delta".to_string()],
+        );
+        let evaluator = WorkEvaluator::with_config(config);
+
+        let input = WorkCheckInput::new("test_writer")
+            .with_agent_status(AgentStatus::Complete)
+            .with_ci_checks(vec![CiCheckResult::new("cargo-test", CiStatus::Passed)])
+            .with_coverage_delta(2.5);
+        let result = evaluator.evaluate_checks(input).await.unwrap();
+        assert_eq!(result.status, WorkCompletionStatus::Complete);
+        assert_eq!(result.check_outcomes.len(), 2);
+
+        // Other agent types keep the default sequence, which requires review
+        let input = WorkCheckInput::new("story_developer")
+            .with_agent_status(AgentStatus::Complete)
+            .with_criteria_checks(vec![CriterionCheck::met("Feature")]);
+        let result = evaluator.evaluate_checks(input).await.unwrap();
+        assert_eq!(result.status, WorkCompletionStatus::NeedsReview);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_checks_blocked_agent_and_unknown_check() {
+        let evaluator = WorkEvaluator::new();
+        let input = WorkCheckInput::new("story_developer").with_agent_status(AgentStatus::Blocked);
+        let result = evaluator.evaluate_checks(input).await.unwrap();
+        assert_eq!(result.status, WorkCompletionStatus::Blocked);
+        assert!(result.check_outcomes.is_empty());
+
+        let config = WorkEvaluatorConfig {
+            default_checks: vec!["acceptance_criteria_llm".to_string()],
+            ..Default::default()
+        };
+        let evaluator = WorkEvaluator::with_config(config);
+        let err = evaluator
+            .evaluate_checks(WorkCheckInput::new("story_developer"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("acceptance_criteria_llm"));
+    }
+
+    #[test]
+    fn test_checks_for_respects_requirements() {
+        let config = WorkEvaluatorConfig {
+            require_ci_pass: false,
+            ..Default::default()
+        };
+        let evaluator = WorkEvaluator::with_config(config);
+        assert_eq!(
+            evaluator.checks_for("story_developer"),
+            vec!["review_approved", "pr_mergeable", "acceptance_criteria"]
+        );
+    }
 }
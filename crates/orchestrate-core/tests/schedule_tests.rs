@@ -1,7 +1,10 @@
 //! Tests for schedule database operations
 
 use chrono::Utc;
-use orchestrate_core::{Database, Schedule, ScheduleRun, ScheduleRunStatus};
+use orchestrate_core::{
+    Agent, AgentState, AgentType, BudgetPeriod, Database, Schedule, ScheduleBlackout,
+    ScheduleOverlapPolicy, ScheduleRun, ScheduleRunStatus, ScheduleSkipReason, ScheduleSpend,
+};
 
 #[tokio::test]
 async fn test_insert_and_get_schedule() {
@@ -111,6 +114,304 @@ async fn test_update_schedule() {
     assert!(updated.next_run.is_some());
 }
 
+#[tokio::test]
+async fn test_schedule_timezone_round_trip() {
+    let db = Database::in_memory().await.unwrap();
+
+    let schedule = Schedule::new(
+        "standup".to_string(),
+        "0 9 * * 1-5".to_string(),
+        "BackgroundController".to_string(),
+        "Prepare standup notes".to_string(),
+    );
+    let id = db.insert_schedule(&schedule).await.unwrap();
+    let mut retrieved = db.get_schedule(id).await.unwrap().unwrap();
+    assert_eq!(retrieved.timezone, "UTC");
+
+    retrieved.timezone = "America/New_York".to_string();
+    db.update_schedule(&retrieved).await.unwrap();
+
+    let updated = db.get_schedule(id).await.unwrap().unwrap();
+    assert_eq!(updated.timezone, "America/New_York");
+}
+
+#[tokio::test]
+async fn test_schedule_jitter_and_overlap_round_trip() {
+    let db = Database::in_memory().await.unwrap();
+
+    let schedule = Schedule::new(
+        "nightly-scan".to_string(),
+        "0 2 * * *".to_string(),
+        "BackgroundController".to_string(),
+        "Run security scan".to_string(),
+    );
+    let id = db.insert_schedule(&schedule).await.unwrap();
+    let mut retrieved = db.get_schedule(id).await.unwrap().unwrap();
+    assert_eq!(retrieved.jitter_secs, 0);
+    assert_eq!(retrieved.overlap_policy, ScheduleOverlapPolicy::RunParallel);
+
+    retrieved.jitter_secs = 300;
+    retrieved.overlap_policy = ScheduleOverlapPolicy::Skip;
+    db.update_schedule(&retrieved).await.unwrap();
+
+    let updated = db.get_schedule(id).await.unwrap().unwrap();
+    assert_eq!(updated.jitter_secs, 300);
+    assert_eq!(updated.overlap_policy, ScheduleOverlapPolicy::Skip);
+}
+
+#[tokio::test]
+async fn test_schedule_dependency_round_trip() {
+    let db = Database::in_memory().await.unwrap();
+
+    let scan = Schedule::new(
+        "security-scan".to_string(),
+        "0 2 * * *".to_string(),
+        "BackgroundController".to_string(),
+        "Run security scan".to_string(),
+    );
+    db.insert_schedule(&scan).await.unwrap();
+
+    let check = Schedule::new(
+        "dependency-check".to_string(),
+        "0 3 * * *".to_string(),
+        "BackgroundController".to_string(),
+        "Check dependencies".to_string(),
+    )
+    .with_depends_on("security-scan");
+    let id = db.insert_schedule(&check).await.unwrap();
+
+    let retrieved = db.get_schedule(id).await.unwrap().unwrap();
+    assert_eq!(retrieved.depends_on.as_deref(), Some("security-scan"));
+
+    let mut run = ScheduleRun::new(id);
+    run.mark_skipped(ScheduleSkipReason::UnmetDependency(
+        "Dependency 'security-scan' has not run yet".to_string(),
+    ));
+    db.insert_schedule_run(&run).await.unwrap();
+
+    let runs = db.get_schedule_runs(id, 10).await.unwrap();
+    assert_eq!(runs[0].status, ScheduleRunStatus::Skipped);
+    assert_eq!(
+        runs[0].error_message.as_deref(),
+        Some("Dependency 'security-scan' has not run yet")
+    );
+}
+
+#[tokio::test]
+async fn test_one_shot_schedule_round_trip() {
+    let db = Database::in_memory().await.unwrap();
+
+    let run_at = Utc::now() + chrono::Duration::days(1);
+    let mut schedule = Schedule::once(
+        "deploy-release".to_string(),
+        run_at,
+        "deployer".to_string(),
+        "Deploy release".to_string(),
+    );
+    schedule.update_next_run().unwrap();
+    let id = db.insert_schedule(&schedule).await.unwrap();
+
+    let retrieved = db.get_schedule(id).await.unwrap().unwrap();
+    assert!(retrieved.is_one_shot());
+    assert_eq!(retrieved.run_at.unwrap().timestamp(), run_at.timestamp());
+    assert_eq!(retrieved.next_run.unwrap().timestamp(), run_at.timestamp());
+
+    // Not due until its run time
+    assert!(db.get_due_schedules().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_schedule_blackouts() {
+    let db = Database::in_memory().await.unwrap();
+
+    let schedule = Schedule::new(
+        "nightly-deploy".to_string(),
+        "0 2 * * *".to_string(),
+        "deployer".to_string(),
+        "Deploy".to_string(),
+    );
+    let schedule_id = db.insert_schedule(&schedule).await.unwrap();
+    let other_id = db
+        .insert_schedule(&Schedule::new(
+            "report".to_string(),
+            "0 8 * * *".to_string(),
+            "BackgroundController".to_string(),
+            "Report".to_string(),
+        ))
+        .await
+        .unwrap();
+
+    let now = Utc::now();
+    let global = ScheduleBlackout::new(
+        "holidays".to_string(),
+        now + chrono::Duration::days(1),
+        now + chrono::Duration::days(3),
+    );
+    db.insert_schedule_blackout(&global).await.unwrap();
+    let freeze = ScheduleBlackout::new(
+        "deploy-freeze".to_string(),
+        now - chrono::Duration::hours(1),
+        now + chrono::Duration::hours(1),
+    )
+    .for_schedule(schedule_id)
+    .with_reason("Release freeze");
+    db.insert_schedule_blackout(&freeze).await.unwrap();
+    let ended = ScheduleBlackout::new(
+        "last-week".to_string(),
+        now - chrono::Duration::days(8),
+        now - chrono::Duration::days(7),
+    );
+    db.insert_schedule_blackout(&ended).await.unwrap();
+
+    let active = db
+        .get_active_schedule_blackout(schedule_id, now)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(active.name, "deploy-freeze");
+    assert_eq!(active.reason.as_deref(), Some("Release freeze"));
+    assert!(db
+        .get_active_schedule_blackout(other_id, now)
+        .await
+        .unwrap()
+        .is_none());
+
+    // The global window applies to every schedule
+    let later = now + chrono::Duration::days(2);
+    let active = db
+        .get_active_schedule_blackout(other_id, later)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(active.name, "holidays");
+
+    assert_eq!(db.list_schedule_blackouts(false).await.unwrap().len(), 2);
+    assert_eq!(db.list_schedule_blackouts(true).await.unwrap().len(), 3);
+
+    assert!(db.delete_schedule_blackout("holidays").await.unwrap());
+    assert!(!db.delete_schedule_blackout("holidays").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_schedule_spend_cap_round_trip() {
+    let db = Database::in_memory().await.unwrap();
+
+    let schedule = Schedule::new(
+        "nightly-refactor".to_string(),
+        "0 1 * * *".to_string(),
+        "BackgroundController".to_string(),
+        "Refactor".to_string(),
+    );
+    let id = db.insert_schedule(&schedule).await.unwrap();
+    let mut retrieved = db.get_schedule(id).await.unwrap().unwrap();
+    assert!(!retrieved.has_spend_cap());
+    assert_eq!(retrieved.spend_cap_period, BudgetPeriod::Monthly);
+
+    retrieved = retrieved
+        .with_max_tokens(2_000_000)
+        .with_max_cost(25.0)
+        .with_spend_cap_period(BudgetPeriod::Weekly);
+    db.update_schedule(&retrieved).await.unwrap();
+
+    let updated = db.get_schedule(id).await.unwrap().unwrap();
+    assert_eq!(updated.max_tokens, Some(2_000_000));
+    assert_eq!(updated.max_cost_usd, Some(25.0));
+    assert_eq!(updated.spend_cap_period, BudgetPeriod::Weekly);
+
+    // No runs yet, so nothing has been spent
+    let spend = db
+        .get_schedule_spend(id, updated.spend_period_start(&Utc::now()))
+        .await
+        .unwrap();
+    assert_eq!(spend, ScheduleSpend::default());
+}
+
+#[tokio::test]
+async fn test_schedule_stats() {
+    let db = Database::in_memory().await.unwrap();
+
+    let mut schedule = Schedule::new(
+        "nightly-tests".to_string(),
+        "0 1 * * *".to_string(),
+        "BackgroundController".to_string(),
+        "Run tests".to_string(),
+    )
+    .with_max_consecutive_misses(3);
+    schedule.record_missed_runs(2);
+    let id = db.insert_schedule(&schedule).await.unwrap();
+
+    let stats = db.get_schedule_stats(id).await.unwrap().unwrap();
+    assert_eq!(stats.total_runs, 0);
+    assert_eq!(stats.success_rate(), None);
+    assert_eq!(stats.avg_duration_secs, None);
+    assert_eq!(stats.missed_runs, 2);
+    assert_eq!(stats.consecutive_missed_runs, 2);
+
+    for state in [AgentState::Completed, AgentState::Completed, AgentState::Failed] {
+        let mut agent = Agent::new(AgentType::BackgroundController, "Run tests");
+        agent.state = state;
+        db.insert_agent(&agent).await.unwrap();
+
+        let mut run = ScheduleRun::new(id);
+        run.started_at = agent.updated_at - chrono::Duration::seconds(90);
+        run.mark_completed(agent.id.to_string());
+        db.insert_schedule_run(&run).await.unwrap();
+    }
+    let mut failed = ScheduleRun::new(id);
+    failed.mark_failed("Unknown agent type".to_string());
+    db.insert_schedule_run(&failed).await.unwrap();
+    let mut skipped = ScheduleRun::new(id);
+    skipped.mark_skipped(ScheduleSkipReason::UnmetDependency(
+        "Dependency 'build' has not run yet".to_string(),
+    ));
+    db.insert_schedule_run(&skipped).await.unwrap();
+
+    let stats = db.get_schedule_stats(id).await.unwrap().unwrap();
+    assert_eq!(stats.total_runs, 5);
+    assert_eq!(stats.succeeded_runs, 2);
+    assert_eq!(stats.failed_runs, 2);
+    assert_eq!(stats.skipped_runs, 1);
+    assert_eq!(stats.success_rate(), Some(50.0));
+    assert!((stats.avg_duration_secs.unwrap() - 90.0).abs() < 1.0);
+
+    assert!(db.get_schedule_stats(id + 1).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_validate_schedule_dependency() {
+    let db = Database::in_memory().await.unwrap();
+
+    let scan = Schedule::new(
+        "security-scan".to_string(),
+        "0 2 * * *".to_string(),
+        "BackgroundController".to_string(),
+        "Run security scan".to_string(),
+    )
+    .with_depends_on("dependency-check");
+    db.insert_schedule(&scan).await.unwrap();
+
+    assert!(db
+        .validate_schedule_dependency("dependency-check", "dependency-check")
+        .await
+        .is_err());
+    assert!(db
+        .validate_schedule_dependency("dependency-check", "missing")
+        .await
+        .is_err());
+
+    // security-scan already depends on dependency-check
+    let err = db
+        .validate_schedule_dependency("dependency-check", "security-scan")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("cycle"));
+
+    assert!(db
+        .validate_schedule_dependency("code-quality", "security-scan")
+        .await
+        .is_ok());
+}
+
 #[tokio::test]
 async fn test_delete_schedule() {
     let db = Database::in_memory().await.unwrap();
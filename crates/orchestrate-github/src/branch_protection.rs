@@ -0,0 +1,189 @@
+//! Branch protection (via gh CLI)
+
+use anyhow::Result;
+use orchestrate_core::{BranchProtection, ProtectionSetting};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::client::GitHubClient;
+use crate::rate_limit::{gh, gh_with_input};
+
+/// Settings GitHub branch protection can enforce
+pub const SUPPORTED_SETTINGS: [ProtectionSetting; 9] = ProtectionSetting::ALL;
+
+#[derive(Deserialize)]
+struct Protection {
+    required_status_checks: Option<StatusChecks>,
+    required_pull_request_reviews: Option<Reviews>,
+    enforce_admins: Option<Enabled>,
+    allow_force_pushes: Option<Enabled>,
+    allow_deletions: Option<Enabled>,
+    required_linear_history: Option<Enabled>,
+}
+
+#[derive(Deserialize)]
+struct StatusChecks {
+    #[serde(default)]
+    strict: bool,
+    #[serde(default)]
+    contexts: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Reviews {
+    #[serde(default)]
+    required_approving_review_count: u32,
+    #[serde(default)]
+    dismiss_stale_reviews: bool,
+    #[serde(default)]
+    require_code_owner_reviews: bool,
+}
+
+#[derive(Deserialize)]
+struct Enabled {
+    enabled: bool,
+}
+
+fn enabled(setting: &Option<Enabled>) -> bool {
+    setting.as_ref().is_some_and(|s| s.enabled)
+}
+
+impl From<Protection> for BranchProtection {
+    fn from(p: Protection) -> Self {
+        let checks = p.required_status_checks;
+        let reviews = p.required_pull_request_reviews;
+        Self {
+            strict_checks: checks.as_ref().is_some_and(|c| c.strict),
+            required_checks: checks.map(|c| c.contexts).unwrap_or_default(),
+            required_approvals: reviews
+                .as_ref()
+                .map_or(0, |r| r.required_approving_review_count),
+            dismiss_stale_reviews: reviews.as_ref().is_some_and(|r| r.dismiss_stale_reviews),
+            require_code_owner_reviews: reviews
+                .as_ref()
+                .is_some_and(|r| r.require_code_owner_reviews),
+            enforce_admins: enabled(&p.enforce_admins),
+            allow_force_pushes: enabled(&p.allow_force_pushes),
+            allow_deletions: enabled(&p.allow_deletions),
+            require_linear_history: enabled(&p.required_linear_history),
+        }
+    }
+}
+
+/// Request body for `PUT /repos/{repo}/branches/{branch}/protection`
+pub fn protection_body(protection: &BranchProtection) -> serde_json::Value {
+    let checks = (!protection.required_checks.is_empty() || protection.strict_checks).then(|| {
+        json!({
+            "strict": protection.strict_checks,
+            "contexts": protection.required_checks,
+        })
+    });
+    let reviews = (protection.required_approvals > 0
+        || protection.dismiss_stale_reviews
+        || protection.require_code_owner_reviews)
+        .then(|| {
+            json!({
+                "required_approving_review_count": protection.required_approvals,
+                "dismiss_stale_reviews": protection.dismiss_stale_reviews,
+                "require_code_owner_reviews": protection.require_code_owner_reviews,
+            })
+        });
+    json!({
+        "required_status_checks": checks,
+        "required_pull_request_reviews": reviews,
+        "enforce_admins": protection.enforce_admins,
+        "restrictions": null,
+        "allow_force_pushes": protection.allow_force_pushes,
+        "allow_deletions": protection.allow_deletions,
+        "required_linear_history": protection.require_linear_history,
+    })
+}
+
+impl GitHubClient {
+    /// Current protection of a branch, or `None` if it isn't protected
+    pub fn get_branch_protection(&self, branch: &str) -> Result<Option<BranchProtection>> {
+        let output = gh([
+            "api",
+            &format!("repos/{}/branches/{}/protection", self.full_name(), branch),
+        ])?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("HTTP 404") {
+                return Ok(None);
+            }
+            anyhow::bail!("Failed to get branch protection: {}", stderr);
+        }
+
+        let protection: Protection = serde_json::from_slice(&output.stdout)?;
+        Ok(Some(protection.into()))
+    }
+
+    /// Replace a branch's protection
+    pub fn set_branch_protection(&self, branch: &str, protection: &BranchProtection) -> Result<()> {
+        let output = gh_with_input(
+            [
+                "api",
+                "--method",
+                "PUT",
+                &format!("repos/{}/branches/{}/protection", self.full_name(), branch),
+                "--input",
+                "-",
+            ],
+            protection_body(protection).to_string().as_bytes(),
+        )?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to set branch protection: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_protection() {
+        let protection: Protection = serde_json::from_value(json!({
+            "required_status_checks": { "strict": true, "contexts": ["build", "test"] },
+            "required_pull_request_reviews": {
+                "required_approving_review_count": 2,
+                "dismiss_stale_reviews": true
+            },
+            "enforce_admins": { "enabled": true },
+            "allow_force_pushes": { "enabled": false },
+            "required_linear_history": { "enabled": true }
+        }))
+        .unwrap();
+        let protection = BranchProtection::from(protection);
+
+        assert_eq!(protection.required_checks, vec!["build", "test"]);
+        assert!(protection.strict_checks);
+        assert_eq!(protection.required_approvals, 2);
+        assert!(!protection.require_code_owner_reviews);
+        assert!(protection.enforce_admins);
+        assert!(!protection.allow_deletions);
+        assert!(protection.require_linear_history);
+    }
+
+    #[test]
+    fn test_protection_body() {
+        let protection = BranchProtection {
+            required_approvals: 1,
+            ..BranchProtection::default()
+        };
+        let body = protection_body(&protection);
+        assert!(body["required_status_checks"].is_null());
+        assert_eq!(
+            body["required_pull_request_reviews"]["required_approving_review_count"],
+            1
+        );
+        assert_eq!(body["allow_force_pushes"], false);
+    }
+}
@@ -0,0 +1,417 @@
+//! Check runs (via gh CLI)
+//!
+//! Publishes code review and security scan results as an
+//! `orchestrate/review` check run on the PR head commit, with findings as
+//! line annotations, so they show in the PR's checks and diff views.
+//!
+//! Creating check runs needs a GitHub App token, such as the `GITHUB_TOKEN`
+//! of an Actions workflow with `checks: write`.
+
+use anyhow::Result;
+use orchestrate_core::{
+    DetectedSecret, ReviewIssue, ReviewIssueSeverity, ReviewResult, ReviewVerdict, SecurityPolicy,
+    SecurityScan, Severity, Vulnerability,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::client::GitHubClient;
+use crate::rate_limit::{gh, gh_with_input};
+
+/// Name of the check run orchestrate publishes
+pub const REVIEW_CHECK_NAME: &str = "orchestrate/review";
+
+/// GitHub accepts at most 50 annotations per request
+const MAX_ANNOTATIONS_PER_REQUEST: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotationLevel {
+    Notice,
+    Warning,
+    Failure,
+}
+
+/// A finding attached to lines of a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Annotation {
+    pub path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub annotation_level: AnnotationLevel,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl Annotation {
+    fn new(
+        path: impl Into<String>,
+        line: u32,
+        level: AnnotationLevel,
+        title: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        let line = line.max(1);
+        Self {
+            path: path.into(),
+            start_line: line,
+            end_line: line,
+            annotation_level: level,
+            message: message.into(),
+            title: Some(title.into()),
+        }
+    }
+
+    /// Annotation for a review issue; issues without a file have none
+    pub fn from_review_issue(issue: &ReviewIssue) -> Option<Self> {
+        let level = match issue.severity {
+            ReviewIssueSeverity::Critical | ReviewIssueSeverity::High => AnnotationLevel::Failure,
+            ReviewIssueSeverity::Medium => AnnotationLevel::Warning,
+            ReviewIssueSeverity::Low | ReviewIssueSeverity::Nitpick => AnnotationLevel::Notice,
+        };
+        let mut message = issue.description.clone();
+        if let Some(suggestion) = &issue.suggestion {
+            message.push_str(&format!("\n\nSuggestion: {}", suggestion));
+        }
+        Some(Self::new(
+            issue.file_path.as_deref()?,
+            issue.line_number.unwrap_or(1),
+            level,
+            format!("Review: {}", issue.severity),
+            message,
+        ))
+    }
+
+    /// Annotation for a vulnerability; vulnerabilities without a file have none
+    pub fn from_vulnerability(vuln: &Vulnerability, policy: &SecurityPolicy) -> Option<Self> {
+        let level = if policy.should_block(&vuln.severity) {
+            AnnotationLevel::Failure
+        } else if vuln.severity >= Severity::Medium {
+            AnnotationLevel::Warning
+        } else {
+            AnnotationLevel::Notice
+        };
+        let title = match (&vuln.cve_id, &vuln.package_name) {
+            (Some(cve), _) => format!("{} ({})", cve, vuln.severity),
+            (None, Some(package)) => format!("{} ({})", package, vuln.severity),
+            (None, None) => format!("Vulnerability ({})", vuln.severity),
+        };
+        let mut message = if vuln.description.is_empty() {
+            vuln.title.clone()
+        } else {
+            vuln.description.clone()
+        };
+        if let Some(fixed) = &vuln.fixed_version {
+            message.push_str(&format!("\n\nFixed in {}", fixed));
+        }
+        Some(Self::new(
+            vuln.file_path.as_deref()?,
+            vuln.line_number.unwrap_or(1),
+            level,
+            title,
+            message,
+        ))
+    }
+
+    /// Annotation for a secret found in the code
+    pub fn from_secret(secret: &DetectedSecret, policy: &SecurityPolicy) -> Self {
+        let level = if policy.block_on_secrets {
+            AnnotationLevel::Failure
+        } else {
+            AnnotationLevel::Warning
+        };
+        Self::new(
+            &secret.file_path,
+            secret.line_number,
+            level,
+            format!("Secret: {}", secret.secret_type),
+            "Possible secret committed; remove it and rotate the credential",
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckConclusion {
+    Success,
+    Neutral,
+    Failure,
+}
+
+/// Results to publish as the check run's output
+#[derive(Debug, Clone)]
+pub struct CheckRunReport {
+    pub conclusion: CheckConclusion,
+    pub title: String,
+    /// Markdown summary
+    pub summary: String,
+    pub annotations: Vec<Annotation>,
+}
+
+impl Default for CheckRunReport {
+    fn default() -> Self {
+        Self {
+            conclusion: CheckConclusion::Success,
+            title: "No issues found".to_string(),
+            summary: String::new(),
+            annotations: Vec::new(),
+        }
+    }
+}
+
+impl CheckRunReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a code review result
+    ///
+    /// Requested changes fail the check; an open discussion makes it neutral.
+    pub fn with_review(mut self, result: &ReviewResult) -> Self {
+        let conclusion = match result.verdict {
+            ReviewVerdict::ChangesRequested => CheckConclusion::Failure,
+            _ if result.has_blocking_issues() => CheckConclusion::Failure,
+            ReviewVerdict::NeedsDiscussion | ReviewVerdict::Pending => CheckConclusion::Neutral,
+            ReviewVerdict::Approved => CheckConclusion::Success,
+        };
+        self.raise(conclusion);
+        self.summary.push_str(&format!(
+            "### Code review: {}\n\n{} issue(s)\n\n",
+            result.verdict.as_str(),
+            result.issues.len()
+        ));
+        for issue in &result.issues {
+            match Annotation::from_review_issue(issue) {
+                Some(annotation) => self.annotations.push(annotation),
+                None => self
+                    .summary
+                    .push_str(&format!("- **{}** {}\n", issue.severity, issue.description)),
+            }
+        }
+        self.update_title();
+        self
+    }
+
+    /// Add a security scan, judged against the policy
+    pub fn with_security_scan(mut self, scan: &SecurityScan, policy: &SecurityPolicy) -> Self {
+        if scan.has_blocking_issues(policy) {
+            self.raise(CheckConclusion::Failure);
+        } else if !scan.vulnerabilities.is_empty() || !scan.secrets.is_empty() {
+            self.raise(CheckConclusion::Neutral);
+        }
+        let s = &scan.summary;
+        self.summary.push_str(&format!(
+            "### Security scan\n\n| Critical | High | Medium | Low | Secrets |\n\
+             |---|---|---|---|---|\n| {} | {} | {} | {} | {} |\n\n",
+            s.critical_count, s.high_count, s.medium_count, s.low_count, s.secrets_count
+        ));
+        for vuln in &scan.vulnerabilities {
+            match Annotation::from_vulnerability(vuln, policy) {
+                Some(annotation) => self.annotations.push(annotation),
+                None => self.summary.push_str(&format!(
+                    "- **{}** {} {}\n",
+                    vuln.severity,
+                    vuln.package_name.as_deref().unwrap_or("-"),
+                    vuln.cve_id.as_deref().unwrap_or(&vuln.title)
+                )),
+            }
+        }
+        self.annotations.extend(
+            scan.secrets
+                .iter()
+                .map(|secret| Annotation::from_secret(secret, policy)),
+        );
+        self.update_title();
+        self
+    }
+
+    fn raise(&mut self, conclusion: CheckConclusion) {
+        let rank = |c: CheckConclusion| match c {
+            CheckConclusion::Success => 0,
+            CheckConclusion::Neutral => 1,
+            CheckConclusion::Failure => 2,
+        };
+        if rank(conclusion) > rank(self.conclusion) {
+            self.conclusion = conclusion;
+        }
+    }
+
+    fn update_title(&mut self) {
+        let count = |level| {
+            self.annotations
+                .iter()
+                .filter(|a| a.annotation_level == level)
+                .count()
+        };
+        let failures = count(AnnotationLevel::Failure);
+        let warnings = count(AnnotationLevel::Warning);
+        self.title = match (self.conclusion, failures, warnings) {
+            (CheckConclusion::Success, 0, 0) => "No issues found".to_string(),
+            (_, failures, warnings) => {
+                format!("{} blocking, {} warning(s)", failures, warnings)
+            }
+        };
+    }
+}
+
+impl GitHubClient {
+    /// Create or update the `orchestrate/review` check run on a commit
+    ///
+    /// Reuses the commit's existing check run so re-reviews update it in
+    /// place. Returns the check run id.
+    pub fn publish_review_check(&self, head_sha: &str, report: &CheckRunReport) -> Result<u64> {
+        let mut batches = report.annotations.chunks(MAX_ANNOTATIONS_PER_REQUEST);
+        let output = |annotations: &[Annotation]| {
+            json!({
+                "title": report.title,
+                "summary": report.summary,
+                "annotations": annotations,
+            })
+        };
+        let body = json!({
+            "name": REVIEW_CHECK_NAME,
+            "head_sha": head_sha,
+            "status": "completed",
+            "conclusion": report.conclusion,
+            "output": output(batches.next().unwrap_or_default()),
+        });
+
+        let id = match self.find_check_run(head_sha, REVIEW_CHECK_NAME)? {
+            Some(id) => {
+                self.api(
+                    "PATCH",
+                    &format!("repos/{}/check-runs/{}", self.full_name(), id),
+                    &body,
+                )?;
+                id
+            }
+            None => {
+                let created: CheckRunId = serde_json::from_value(self.api(
+                    "POST",
+                    &format!("repos/{}/check-runs", self.full_name()),
+                    &body,
+                )?)?;
+                created.id
+            }
+        };
+
+        // Annotations beyond the first batch are appended by further updates
+        for batch in batches {
+            self.api(
+                "PATCH",
+                &format!("repos/{}/check-runs/{}", self.full_name(), id),
+                &json!({ "output": output(batch) }),
+            )?;
+        }
+        Ok(id)
+    }
+
+    /// Id of the named check run on a commit, if there is one
+    pub fn find_check_run(&self, head_sha: &str, name: &str) -> Result<Option<u64>> {
+        #[derive(Deserialize)]
+        struct CheckRuns {
+            check_runs: Vec<CheckRunId>,
+        }
+
+        let output = gh([
+            "api",
+            &format!(
+                "repos/{}/commits/{}/check-runs?check_name={}",
+                self.full_name(),
+                head_sha,
+                name
+            ),
+        ])?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to list check runs: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let runs: CheckRuns = serde_json::from_slice(&output.stdout)?;
+        Ok(runs.check_runs.first().map(|r| r.id))
+    }
+
+    /// Send a REST request with a JSON body through `gh api`
+    fn api(&self, method: &str, path: &str, body: &serde_json::Value) -> Result<serde_json::Value> {
+        let output = gh_with_input(
+            ["api", "--method", method, path, "--input", "-"],
+            body.to_string().as_bytes(),
+        )?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "GitHub API request failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(serde_json::from_slice(&output.stdout)?)
+    }
+}
+
+#[derive(Deserialize)]
+struct CheckRunId {
+    id: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orchestrate_core::{ScanType, SecretType};
+
+    #[test]
+    fn test_review_report() {
+        let result = ReviewResult::new(ReviewVerdict::ChangesRequested).with_issues(vec![
+            ReviewIssue::new(ReviewIssueSeverity::High, "Unchecked input")
+                .with_location("src/api.rs", 12)
+                .with_suggestion("Validate the length"),
+            ReviewIssue::new(ReviewIssueSeverity::Low, "Missing changelog entry"),
+        ]);
+        let report = CheckRunReport::new().with_review(&result);
+
+        assert_eq!(report.conclusion, CheckConclusion::Failure);
+        assert_eq!(report.annotations.len(), 1);
+        let annotation = &report.annotations[0];
+        assert_eq!(annotation.path, "src/api.rs");
+        assert_eq!(annotation.start_line, 12);
+        assert_eq!(annotation.annotation_level, AnnotationLevel::Failure);
+        assert!(annotation.message.contains("Validate the length"));
+        assert!(report.summary.contains("Missing changelog entry"));
+        assert_eq!(report.title, "1 blocking, 0 warning(s)");
+    }
+
+    #[test]
+    fn test_security_report_and_serialization() {
+        let policy = SecurityPolicy::default();
+        let mut scan = SecurityScan::new(vec![ScanType::Secrets], "test");
+        scan.add_secret(DetectedSecret::new(
+            SecretType::AwsAccessKey,
+            "config/.env",
+            15,
+            "AKIA***",
+        ));
+        scan.complete();
+
+        let report = CheckRunReport::new()
+            .with_review(&ReviewResult::new(ReviewVerdict::Approved))
+            .with_security_scan(&scan, &policy);
+
+        // The default policy blocks on secrets
+        assert_eq!(report.conclusion, CheckConclusion::Failure);
+        let value = serde_json::to_value(&report.annotations[0]).unwrap();
+        assert_eq!(value["path"], "config/.env");
+        assert_eq!(value["start_line"], 15);
+        assert_eq!(value["annotation_level"], "failure");
+    }
+
+    #[test]
+    fn test_clean_report_succeeds() {
+        let report = CheckRunReport::new().with_review(&ReviewResult::new(ReviewVerdict::Approved));
+        assert_eq!(report.conclusion, CheckConclusion::Success);
+        assert_eq!(report.title, "No issues found");
+    }
+}
@@ -1,10 +1,13 @@
 //! GitHub API client (via gh CLI)
 
 use anyhow::Result;
+use orchestrate_core::RepoRole;
 use serde::Deserialize;
-use std::process::Command;
+
+use crate::rate_limit::gh;
 
 /// GitHub client using gh CLI
+#[derive(Debug, Clone)]
 pub struct GitHubClient {
     /// Repository owner
     pub owner: String,
@@ -15,9 +18,7 @@ pub struct GitHubClient {
 impl GitHubClient {
     /// Create a new GitHub client for the current repository
     pub fn new() -> Result<Self> {
-        let output = Command::new("gh")
-            .args(["repo", "view", "--json", "owner,name"])
-            .output()?;
+        let output = gh(["repo", "view", "--json", "owner,name"])?;
 
         if !output.status.success() {
             anyhow::bail!(
@@ -44,13 +45,36 @@ impl GitHubClient {
         })
     }
 
+    /// Create a client for a repository given as `owner/repo`
+    pub fn for_repo(full_name: &str) -> Result<Self> {
+        let (owner, repo) = full_name
+            .split_once('/')
+            .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Invalid repository name: {}", full_name))?;
+        Ok(Self {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    /// Create a client for a repository's clone or web URL
+    pub fn from_url(url: &str) -> Result<Self> {
+        let path = match url.split_once("://") {
+            Some((_, rest)) => rest.split_once('/').map(|(_, path)| path),
+            None => url.split_once(':').map(|(_, path)| path),
+        }
+        .ok_or_else(|| anyhow::anyhow!("Invalid repository URL: {}", url))?;
+        let mut segments = path.trim_end_matches('/').splitn(3, '/');
+        let owner = segments.next().unwrap_or_default();
+        let repo = segments.next().unwrap_or_default().trim_end_matches(".git");
+        Self::for_repo(&format!("{}/{}", owner, repo))
+    }
+
     /// Create a PR
     pub fn create_pr(&self, title: &str, body: &str, base: &str) -> Result<i32> {
-        let output = Command::new("gh")
-            .args([
-                "pr", "create", "--title", title, "--body", body, "--base", base,
-            ])
-            .output()?;
+        let output = gh([
+            "pr", "create", "--title", title, "--body", body, "--base", base,
+        ])?;
 
         if !output.status.success() {
             anyhow::bail!(
@@ -60,25 +84,62 @@ impl GitHubClient {
         }
 
         // Get PR number
-        let output = Command::new("gh")
-            .args(["pr", "view", "--json", "number", "-q", ".number"])
-            .output()?;
+        let output = gh(["pr", "view", "--json", "number", "-q", ".number"])?;
 
         let number: i32 = String::from_utf8_lossy(&output.stdout).trim().parse()?;
         Ok(number)
     }
 
+    /// Create a PR from `head` into `base` in this client's repository,
+    /// returning its number and URL
+    pub fn create_pr_from(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<(i32, String)> {
+        let output = gh([
+            "pr",
+            "create",
+            "--repo",
+            &self.full_name(),
+            "--head",
+            head,
+            "--base",
+            base,
+            "--title",
+            title,
+            "--body",
+            body,
+        ])?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to create PR: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        // gh prints the new PR's URL, ending in its number
+        let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let number = url
+            .rsplit('/')
+            .next()
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| anyhow::anyhow!("Unexpected gh pr create output: {}", url))?;
+        Ok((number, url))
+    }
+
     /// Get PR state
     pub fn get_pr_state(&self, number: i32) -> Result<PrState> {
-        let output = Command::new("gh")
-            .args([
-                "pr",
-                "view",
-                &number.to_string(),
-                "--json",
-                "state,mergeable,reviewDecision",
-            ])
-            .output()?;
+        let output = gh([
+            "pr",
+            "view",
+            &number.to_string(),
+            "--json",
+            "state,mergeable,reviewDecision",
+        ])?;
 
         if !output.status.success() {
             anyhow::bail!(
@@ -92,15 +153,13 @@ impl GitHubClient {
 
     /// Get CI check status
     pub fn get_checks(&self, number: i32) -> Result<Vec<Check>> {
-        let output = Command::new("gh")
-            .args([
-                "pr",
-                "checks",
-                &number.to_string(),
-                "--json",
-                "name,conclusion,status",
-            ])
-            .output()?;
+        let output = gh([
+            "pr",
+            "checks",
+            &number.to_string(),
+            "--json",
+            "name,conclusion,status",
+        ])?;
 
         if !output.status.success() {
             anyhow::bail!(
@@ -121,15 +180,15 @@ impl GitHubClient {
             _ => "--squash",
         };
 
-        let output = Command::new("gh")
-            .args([
-                "pr",
-                "merge",
-                &number.to_string(),
-                strategy_arg,
-                "--delete-branch",
-            ])
-            .output()?;
+        let output = gh([
+            "pr",
+            "merge",
+            &number.to_string(),
+            "--repo",
+            &self.full_name(),
+            strategy_arg,
+            "--delete-branch",
+        ])?;
 
         if !output.status.success() {
             anyhow::bail!(
@@ -141,146 +200,61 @@ impl GitHubClient {
         Ok(())
     }
 
-    /// Get unresolved review threads
-    pub fn get_unresolved_threads(&self, number: i32) -> Result<Vec<ReviewThread>> {
-        let query = format!(
-            r#"
-            query {{
-                repository(owner: "{}", name: "{}") {{
-                    pullRequest(number: {}) {{
-                        reviewThreads(first: 100) {{
-                            nodes {{
-                                id
-                                isResolved
-                                path
-                                line
-                                comments(first: 10) {{
-                                    nodes {{
-                                        body
-                                        author {{ login }}
-                                    }}
-                                }}
-                            }}
-                        }}
-                    }}
-                }}
-            }}
-            "#,
-            self.owner, self.repo, number
-        );
-
-        let output = Command::new("gh")
-            .args(["api", "graphql", "-f", &format!("query={}", query)])
-            .output()?;
+    /// Post a comment on a PR
+    pub fn post_comment(&self, number: i32, body: &str) -> Result<()> {
+        let output = gh(["pr", "comment", &number.to_string(), "--body", body])?;
 
         if !output.status.success() {
             anyhow::bail!(
-                "Failed to get threads: {}",
+                "Failed to post comment: {}",
                 String::from_utf8_lossy(&output.stderr)
             );
         }
 
-        #[derive(Deserialize)]
-        struct Response {
-            data: Data,
-        }
-
-        #[derive(Deserialize)]
-        struct Data {
-            repository: Repository,
-        }
-
-        #[derive(Deserialize)]
-        struct Repository {
-            #[serde(rename = "pullRequest")]
-            pull_request: PullRequest,
-        }
-
-        #[derive(Deserialize)]
-        struct PullRequest {
-            #[serde(rename = "reviewThreads")]
-            review_threads: Threads,
-        }
-
-        #[derive(Deserialize)]
-        struct Threads {
-            nodes: Vec<ThreadNode>,
-        }
+        Ok(())
+    }
 
-        #[derive(Deserialize)]
-        struct ThreadNode {
-            id: String,
-            #[serde(rename = "isResolved")]
-            is_resolved: bool,
-            path: Option<String>,
-            line: Option<i32>,
-            comments: Comments,
-        }
+    /// Post a comment on an issue
+    pub fn post_issue_comment(&self, number: i64, body: &str) -> Result<()> {
+        let output = gh([
+            "issue",
+            "comment",
+            &number.to_string(),
+            "--repo",
+            &self.full_name(),
+            "--body",
+            body,
+        ])?;
 
-        #[derive(Deserialize)]
-        struct Comments {
-            nodes: Vec<CommentNode>,
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to post issue comment: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
         }
 
-        #[derive(Deserialize)]
-        struct CommentNode {
-            body: String,
-            author: Author,
-        }
+        Ok(())
+    }
 
-        #[derive(Deserialize)]
-        struct Author {
-            login: String,
+    /// Add labels to an issue
+    pub fn add_issue_labels(&self, number: i64, labels: &[String]) -> Result<()> {
+        if labels.is_empty() {
+            return Ok(());
         }
 
-        let response: Response = serde_json::from_slice(&output.stdout)?;
-        let threads = response
-            .data
-            .repository
-            .pull_request
-            .review_threads
-            .nodes
-            .into_iter()
-            .filter(|t| !t.is_resolved)
-            .map(|t| ReviewThread {
-                id: t.id,
-                path: t.path,
-                line: t.line,
-                comments: t
-                    .comments
-                    .nodes
-                    .into_iter()
-                    .map(|c| ThreadComment {
-                        author: c.author.login,
-                        body: c.body,
-                    })
-                    .collect(),
-            })
-            .collect();
-
-        Ok(threads)
-    }
-
-    /// Resolve a review thread
-    pub fn resolve_thread(&self, thread_id: &str) -> Result<()> {
-        let mutation = format!(
-            r#"
-            mutation {{
-                resolveReviewThread(input: {{threadId: "{}"}}) {{
-                    thread {{ isResolved }}
-                }}
-            }}
-            "#,
-            thread_id
-        );
-
-        let output = Command::new("gh")
-            .args(["api", "graphql", "-f", &format!("query={}", mutation)])
-            .output()?;
+        let output = gh([
+            "issue",
+            "edit",
+            &number.to_string(),
+            "--repo",
+            &self.full_name(),
+            "--add-label",
+            &labels.join(","),
+        ])?;
 
         if !output.status.success() {
             anyhow::bail!(
-                "Failed to resolve thread: {}",
+                "Failed to add issue labels: {}",
                 String::from_utf8_lossy(&output.stderr)
             );
         }
@@ -288,20 +262,42 @@ impl GitHubClient {
         Ok(())
     }
 
-    /// Post a comment on a PR
-    pub fn post_comment(&self, number: i32, body: &str) -> Result<()> {
-        let output = Command::new("gh")
-            .args(["pr", "comment", &number.to_string(), "--body", body])
-            .output()?;
+    /// A user's role in the repository, from their collaborator permission
+    pub fn get_repo_role(&self, login: &str) -> Result<RepoRole> {
+        #[derive(Deserialize)]
+        struct Permission {
+            permission: String,
+            role_name: Option<String>,
+        }
+
+        let output = gh([
+            "api",
+            &format!(
+                "repos/{}/collaborators/{}/permission",
+                self.full_name(),
+                login
+            ),
+        ])?;
 
         if !output.status.success() {
             anyhow::bail!(
-                "Failed to post comment: {}",
+                "Failed to get permission of {}: {}",
+                login,
                 String::from_utf8_lossy(&output.stderr)
             );
         }
 
-        Ok(())
+        let permission: Permission = serde_json::from_slice(&output.stdout)?;
+        Ok(RepoRole::from_permission(
+            permission
+                .role_name
+                .as_deref()
+                .unwrap_or(&permission.permission),
+        ))
+    }
+
+    pub(crate) fn full_name(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
     }
 }
 
@@ -313,23 +309,26 @@ pub struct PrState {
     pub review_decision: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Check {
     pub name: String,
     pub conclusion: Option<String>,
     pub status: String,
+    #[serde(default, alias = "link")]
+    pub url: Option<String>,
 }
 
-#[derive(Debug)]
-pub struct ReviewThread {
-    pub id: String,
-    pub path: Option<String>,
-    pub line: Option<i32>,
-    pub comments: Vec<ThreadComment>,
-}
-
-#[derive(Debug)]
-pub struct ThreadComment {
-    pub author: String,
-    pub body: String,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_url() {
+        let client =
+            GitHubClient::from_url("https://github.com/hanibalsk/orchestrate.git").unwrap();
+        assert_eq!(client.full_name(), "hanibalsk/orchestrate");
+        let client = GitHubClient::from_url("git@github.com:hanibalsk/orchestrate.git").unwrap();
+        assert_eq!(client.full_name(), "hanibalsk/orchestrate");
+        assert!(GitHubClient::from_url("https://github.com/hanibalsk").is_err());
+    }
 }
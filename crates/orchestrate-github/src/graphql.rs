@@ -0,0 +1,543 @@
+//! GitHub GraphQL API (via gh CLI)
+//!
+//! Fetches in one request what takes several REST calls per PR:
+//! - [`PullRequestStatus`]: state, mergeability, review decision, latest
+//!   reviews, CI checks, and unresolved review threads, for one PR or a
+//!   batch of PRs
+//!
+//! Queries take their arguments as GraphQL variables rather than
+//! interpolating them into the query text.
+
+use anyhow::Result;
+use orchestrate_core::{CiCheckResult, CiStatus, PrMergeStatus, PrWorkflowContext, ReviewVerdict};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::client::{Check, GitHubClient};
+use crate::rate_limit::gh;
+
+/// Most PRs fetched by one batched query, keeping it under GitHub's node limits
+const MAX_BATCH_SIZE: usize = 25;
+
+/// Fields fetched for every PR status
+const PR_STATUS_FRAGMENT: &str = r#"
+fragment PrStatusFields on PullRequest {
+    number
+    url
+    state
+    isDraft
+    mergeable
+    reviewDecision
+    headRefOid
+    mergeQueueEntry { position }
+    latestReviews(first: 50) {
+        nodes { state author { login } }
+    }
+    reviewThreads(first: 100) {
+        nodes { isResolved }
+    }
+    commits(last: 1) {
+        nodes {
+            commit {
+                statusCheckRollup {
+                    contexts(first: 100) {
+                        nodes {
+                            __typename
+                            ... on CheckRun { name status conclusion detailsUrl }
+                            ... on StatusContext { context state targetUrl }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+"#;
+
+/// GraphQL variable value
+#[derive(Debug, Clone, Copy)]
+pub enum Variable<'a> {
+    String(&'a str),
+    Int(i64),
+}
+
+/// Run a GraphQL query or mutation with `gh api graphql`
+pub fn graphql<T: DeserializeOwned>(query: &str, variables: &[(&str, Variable<'_>)]) -> Result<T> {
+    let mut args = vec![
+        "api".to_string(),
+        "graphql".to_string(),
+        "-f".to_string(),
+        format!("query={}", query),
+    ];
+    for (name, value) in variables {
+        match value {
+            Variable::String(value) => {
+                args.push("-f".to_string());
+                args.push(format!("{}={}", name, value));
+            }
+            Variable::Int(value) => {
+                args.push("-F".to_string());
+                args.push(format!("{}={}", name, value));
+            }
+        }
+    }
+
+    let output = gh(&args)?;
+    if !output.status.success() {
+        // GraphQL errors come back as a JSON body; anything else is on stderr
+        if let Err(e) = parse_response::<serde_json::Value>(&output.stdout) {
+            anyhow::bail!("GraphQL request failed: {}", e);
+        }
+        anyhow::bail!(
+            "GraphQL request failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    parse_response(&output.stdout)
+}
+
+/// Extract `data` from a GraphQL response, failing on reported errors
+pub fn parse_response<T: DeserializeOwned>(body: &[u8]) -> Result<T> {
+    #[derive(Deserialize)]
+    struct Response<T> {
+        data: Option<T>,
+        #[serde(default)]
+        errors: Vec<GraphQlError>,
+    }
+
+    #[derive(Deserialize)]
+    struct GraphQlError {
+        message: String,
+    }
+
+    let response: Response<T> = serde_json::from_slice(body)?;
+    if !response.errors.is_empty() {
+        let messages: Vec<_> = response.errors.into_iter().map(|e| e.message).collect();
+        anyhow::bail!("{}", messages.join("; "));
+    }
+    response
+        .data
+        .ok_or_else(|| anyhow::anyhow!("GraphQL response has no data"))
+}
+
+/// Batched query fetching the status of several PRs, aliased `pr0`, `pr1`, ...
+fn pr_status_query(numbers: &[i32]) -> String {
+    let fields: String = numbers
+        .iter()
+        .enumerate()
+        .map(|(i, number)| {
+            format!(
+                "pr{}: pullRequest(number: {}) {{ ...PrStatusFields }}\n",
+                i, number
+            )
+        })
+        .collect();
+    format!(
+        "query($owner: String!, $repo: String!) {{\n\
+         repository(owner: $owner, name: $repo) {{\n{}}}\n}}\n{}",
+        fields, PR_STATUS_FRAGMENT
+    )
+}
+
+/// Review left on a PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrReview {
+    pub author: String,
+    /// `APPROVED`, `CHANGES_REQUESTED`, `COMMENTED`, `DISMISSED`, or `PENDING`
+    pub state: String,
+}
+
+/// Everything the PR workflow needs to decide its next step, from one query
+#[derive(Debug, Clone)]
+pub struct PullRequestStatus {
+    pub number: i32,
+    pub url: String,
+    /// `OPEN`, `CLOSED`, or `MERGED`
+    pub state: String,
+    pub is_draft: bool,
+    /// `MERGEABLE`, `CONFLICTING`, or `UNKNOWN` while GitHub computes it
+    pub mergeable: String,
+    pub review_decision: Option<String>,
+    pub head_sha: String,
+    /// Latest review of each reviewer
+    pub reviews: Vec<PrReview>,
+    /// Check runs and commit statuses of the head commit
+    pub checks: Vec<Check>,
+    pub unresolved_threads: usize,
+    /// Position in the merge queue, while enqueued
+    pub merge_queue_position: Option<u32>,
+}
+
+impl PullRequestStatus {
+    /// Whether the PR conflicts with its base branch
+    pub fn has_conflicts(&self) -> bool {
+        self.mergeable == "CONFLICTING"
+    }
+
+    /// Merge status, the first blocking condition winning
+    pub fn merge_status(&self) -> PrMergeStatus {
+        match self.state.as_str() {
+            "MERGED" => PrMergeStatus::Merged,
+            "CLOSED" => PrMergeStatus::Closed,
+            _ if self.is_draft => PrMergeStatus::Draft,
+            _ if self.has_conflicts() => PrMergeStatus::Conflicts,
+            _ if self.review_verdict() != ReviewVerdict::Approved
+                || self.unresolved_threads > 0
+                || self.ci_checks().iter().any(|c| !c.status.is_passing()) =>
+            {
+                PrMergeStatus::Blocked
+            }
+            _ => PrMergeStatus::Mergeable,
+        }
+    }
+
+    /// Review verdict from the review decision and open threads
+    ///
+    /// An approved PR with unresolved threads still needs discussion.
+    pub fn review_verdict(&self) -> ReviewVerdict {
+        match self.review_decision.as_deref() {
+            Some("CHANGES_REQUESTED") => ReviewVerdict::ChangesRequested,
+            Some("APPROVED") if self.unresolved_threads > 0 => ReviewVerdict::NeedsDiscussion,
+            Some("APPROVED") => ReviewVerdict::Approved,
+            _ => ReviewVerdict::Pending,
+        }
+    }
+
+    /// Checks as CI results
+    pub fn ci_checks(&self) -> Vec<CiCheckResult> {
+        self.checks
+            .iter()
+            .map(|check| {
+                let status = match (check.status.as_str(), check.conclusion.as_deref()) {
+                    ("COMPLETED", Some("SUCCESS" | "NEUTRAL" | "SKIPPED")) => CiStatus::Passed,
+                    ("COMPLETED", Some("CANCELLED")) => CiStatus::Cancelled,
+                    ("COMPLETED", Some("TIMED_OUT")) => CiStatus::Timeout,
+                    ("COMPLETED", _) => CiStatus::Failed,
+                    ("IN_PROGRESS", _) => CiStatus::Running,
+                    _ => CiStatus::Pending,
+                };
+                let result = CiCheckResult::new(&check.name, status);
+                match &check.url {
+                    Some(url) => result.with_url(url),
+                    None => result,
+                }
+            })
+            .collect()
+    }
+
+    /// Update a PR workflow's CI status, review verdict, and conflict flag
+    pub fn apply_to(&self, context: &mut PrWorkflowContext) {
+        context.update_ci_status(&self.ci_checks());
+        context.update_review(self.review_verdict(), context.review_iterations);
+        context.set_has_conflicts(self.has_conflicts());
+        if self.merge_queue_position.is_some() {
+            context.update_merge_queue_position(self.merge_queue_position);
+        }
+        if context.url.is_none() && !self.url.is_empty() {
+            context.url = Some(self.url.clone());
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrStatusNode {
+    number: i32,
+    url: String,
+    state: String,
+    is_draft: bool,
+    mergeable: String,
+    review_decision: Option<String>,
+    head_ref_oid: String,
+    merge_queue_entry: Option<QueuePosition>,
+    latest_reviews: Nodes<ReviewNode>,
+    review_threads: Nodes<ResolvedNode>,
+    commits: Nodes<CommitNode>,
+}
+
+#[derive(Deserialize)]
+struct QueuePosition {
+    position: u32,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct Nodes<T> {
+    pub(crate) nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct ReviewNode {
+    state: String,
+    author: Option<Author>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct Author {
+    pub(crate) login: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResolvedNode {
+    is_resolved: bool,
+}
+
+#[derive(Deserialize)]
+struct CommitNode {
+    commit: Commit,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Commit {
+    status_check_rollup: Option<Rollup>,
+}
+
+#[derive(Deserialize)]
+struct Rollup {
+    contexts: Nodes<ContextNode>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "__typename")]
+enum ContextNode {
+    #[serde(rename_all = "camelCase")]
+    CheckRun {
+        name: String,
+        status: String,
+        conclusion: Option<String>,
+        details_url: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    StatusContext {
+        context: String,
+        state: String,
+        target_url: Option<String>,
+    },
+}
+
+impl From<ContextNode> for Check {
+    fn from(node: ContextNode) -> Self {
+        match node {
+            ContextNode::CheckRun {
+                name,
+                status,
+                conclusion,
+                details_url,
+            } => Check {
+                name,
+                conclusion,
+                status,
+                url: details_url,
+            },
+            // Commit statuses are PENDING until they settle on a result
+            ContextNode::StatusContext {
+                context,
+                state,
+                target_url,
+            } => {
+                let (status, conclusion) = match state.as_str() {
+                    "PENDING" | "EXPECTED" => ("PENDING", None),
+                    "SUCCESS" => ("COMPLETED", Some("SUCCESS")),
+                    _ => ("COMPLETED", Some("FAILURE")),
+                };
+                Check {
+                    name: context,
+                    conclusion: conclusion.map(str::to_string),
+                    status: status.to_string(),
+                    url: target_url,
+                }
+            }
+        }
+    }
+}
+
+impl From<PrStatusNode> for PullRequestStatus {
+    fn from(node: PrStatusNode) -> Self {
+        let checks = node
+            .commits
+            .nodes
+            .into_iter()
+            .filter_map(|c| c.commit.status_check_rollup)
+            .flat_map(|rollup| rollup.contexts.nodes)
+            .map(Check::from)
+            .collect();
+        Self {
+            number: node.number,
+            url: node.url,
+            state: node.state,
+            is_draft: node.is_draft,
+            mergeable: node.mergeable,
+            review_decision: node.review_decision,
+            head_sha: node.head_ref_oid,
+            reviews: node
+                .latest_reviews
+                .nodes
+                .into_iter()
+                .map(|r| PrReview {
+                    author: r
+                        .author
+                        .map(|a| a.login)
+                        .unwrap_or_else(|| "ghost".to_string()),
+                    state: r.state,
+                })
+                .collect(),
+            checks,
+            unresolved_threads: node
+                .review_threads
+                .nodes
+                .iter()
+                .filter(|t| !t.is_resolved)
+                .count(),
+            merge_queue_position: node.merge_queue_entry.map(|e| e.position),
+        }
+    }
+}
+
+/// Statuses from a batched query response, in request order
+fn parse_pr_statuses(data: serde_json::Value, count: usize) -> Result<Vec<PullRequestStatus>> {
+    #[derive(Deserialize)]
+    struct Data {
+        repository: HashMap<String, Option<PrStatusNode>>,
+    }
+
+    let mut data: Data = serde_json::from_value(data)?;
+    (0..count)
+        .map(|i| {
+            data.repository
+                .remove(&format!("pr{}", i))
+                .flatten()
+                .map(PullRequestStatus::from)
+                .ok_or_else(|| anyhow::anyhow!("PR not found in GraphQL response"))
+        })
+        .collect()
+}
+
+impl GitHubClient {
+    /// Status of a PR, with its reviews and checks, in one request
+    pub fn get_pr_status(&self, number: i32) -> Result<PullRequestStatus> {
+        self.get_pr_statuses(&[number])?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("PR #{} not found", number))
+    }
+
+    /// Statuses of several PRs, in order, with one request per 25 PRs
+    pub fn get_pr_statuses(&self, numbers: &[i32]) -> Result<Vec<PullRequestStatus>> {
+        let mut statuses = Vec::with_capacity(numbers.len());
+        for batch in numbers.chunks(MAX_BATCH_SIZE) {
+            let data: serde_json::Value = graphql(
+                &pr_status_query(batch),
+                &[
+                    ("owner", Variable::String(&self.owner)),
+                    ("repo", Variable::String(&self.repo)),
+                ],
+            )?;
+            statuses.extend(parse_pr_statuses(data, batch.len())?);
+        }
+        Ok(statuses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pr_node(number: i32) -> serde_json::Value {
+        json!({
+            "number": number,
+            "url": format!("https://github.com/o/r/pull/{}", number),
+            "state": "OPEN",
+            "isDraft": false,
+            "mergeable": "MERGEABLE",
+            "reviewDecision": "APPROVED",
+            "headRefOid": "abc123",
+            "mergeQueueEntry": null,
+            "latestReviews": { "nodes": [
+                { "state": "APPROVED", "author": { "login": "alice" } }
+            ] },
+            "reviewThreads": { "nodes": [ { "isResolved": true } ] },
+            "commits": { "nodes": [ { "commit": { "statusCheckRollup": { "contexts": { "nodes": [
+                { "__typename": "CheckRun", "name": "test", "status": "COMPLETED",
+                  "conclusion": "SUCCESS", "detailsUrl": "https://ci/test" },
+                { "__typename": "StatusContext", "context": "ci/lint", "state": "SUCCESS",
+                  "targetUrl": null }
+            ] } } } } ] }
+        })
+    }
+
+    #[test]
+    fn test_pr_status_query_aliases_each_pr() {
+        let query = pr_status_query(&[12, 34]);
+        assert!(query.contains("pr0: pullRequest(number: 12) { ...PrStatusFields }"));
+        assert!(query.contains("pr1: pullRequest(number: 34) { ...PrStatusFields }"));
+        assert!(query.contains("fragment PrStatusFields on PullRequest"));
+    }
+
+    #[test]
+    fn test_parse_response_reports_errors() {
+        let body = br#"{"data":null,"errors":[{"message":"Could not resolve to a PullRequest"}]}"#;
+        let err = parse_response::<serde_json::Value>(body).unwrap_err();
+        assert_eq!(err.to_string(), "Could not resolve to a PullRequest");
+    }
+
+    #[test]
+    fn test_parse_batched_statuses_in_order() {
+        let data = json!({ "repository": { "pr1": pr_node(34), "pr0": pr_node(12) } });
+        let statuses = parse_pr_statuses(data, 2).unwrap();
+
+        assert_eq!(statuses[0].number, 12);
+        assert_eq!(statuses[1].number, 34);
+        assert_eq!(statuses[0].reviews[0].author, "alice");
+        assert_eq!(statuses[0].checks.len(), 2);
+        assert_eq!(statuses[0].unresolved_threads, 0);
+        assert_eq!(statuses[0].merge_status(), PrMergeStatus::Mergeable);
+
+        let data = json!({ "repository": { "pr0": null } });
+        assert!(parse_pr_statuses(data, 1).is_err());
+    }
+
+    #[test]
+    fn test_status_blocked_by_threads_and_checks() {
+        let mut node = pr_node(12);
+        node["reviewThreads"]["nodes"] = json!([{ "isResolved": false }]);
+        node["commits"]["nodes"][0]["commit"]["statusCheckRollup"]["contexts"]["nodes"] = json!([
+            { "__typename": "CheckRun", "name": "build", "status": "IN_PROGRESS",
+              "conclusion": null, "detailsUrl": null },
+            { "__typename": "StatusContext", "context": "deploy", "state": "ERROR",
+              "targetUrl": null }
+        ]);
+        let status: PullRequestStatus =
+            serde_json::from_value::<PrStatusNode>(node).unwrap().into();
+
+        assert_eq!(status.review_verdict(), ReviewVerdict::NeedsDiscussion);
+        let checks = status.ci_checks();
+        assert_eq!(checks[0].status, CiStatus::Running);
+        assert_eq!(checks[1].status, CiStatus::Failed);
+        assert_eq!(status.merge_status(), PrMergeStatus::Blocked);
+    }
+
+    #[test]
+    fn test_apply_to_workflow_context() {
+        let mut node = pr_node(12);
+        node["mergeable"] = json!("CONFLICTING");
+        node["mergeQueueEntry"] = json!({ "position": 2 });
+        let status: PullRequestStatus =
+            serde_json::from_value::<PrStatusNode>(node).unwrap().into();
+
+        let mut context = PrWorkflowContext::new(12, "story-1", "agent-1", "feature", "main");
+        status.apply_to(&mut context);
+
+        assert!(context.has_conflicts);
+        assert_eq!(context.review_verdict, Some(ReviewVerdict::Approved));
+        assert!(context.ci_status.as_ref().unwrap().is_all_passed());
+        assert_eq!(
+            context.url.as_deref(),
+            Some("https://github.com/o/r/pull/12")
+        );
+        assert_eq!(status.merge_status(), PrMergeStatus::Conflicts);
+        assert_eq!(context.merge_queue_position(), Some(2));
+    }
+}
@@ -0,0 +1,211 @@
+//! PR labeling
+//!
+//! Applies the size, area, and risk labels computed by the PR workflow.
+//! Only labels with orchestrate's prefixes are replaced; labels added by
+//! people are left alone.
+
+use anyhow::Result;
+use orchestrate_core::{ChangedFile, PrLabels, PrRisk, PrWorkflowManager};
+use serde::Deserialize;
+
+use crate::client::GitHubClient;
+use crate::rate_limit::gh;
+
+/// Labels to add and remove to move a PR from `current` to `labels`
+pub fn label_changes(current: &[String], labels: &PrLabels) -> (Vec<String>, Vec<String>) {
+    let wanted = labels.to_labels();
+    let add = wanted
+        .iter()
+        .filter(|l| !current.contains(l))
+        .cloned()
+        .collect();
+    let remove = current
+        .iter()
+        .filter(|l| PrLabels::is_managed(l) && !wanted.contains(l))
+        .cloned()
+        .collect();
+    (add, remove)
+}
+
+/// Color for a label created by orchestrate
+fn label_color(label: &str) -> &'static str {
+    match label {
+        l if l == format!("risk/{}", PrRisk::High.as_str()) => "d73a4a",
+        l if l == format!("risk/{}", PrRisk::Medium.as_str()) => "fbca04",
+        l if l.starts_with("risk/") => "0e8a16",
+        l if l.starts_with("size/") => "c5def5",
+        _ => "bfd4f2",
+    }
+}
+
+impl GitHubClient {
+    /// Files changed by a PR, with additions and deletions
+    pub fn get_pr_files(&self, number: i32) -> Result<Vec<ChangedFile>> {
+        #[derive(Deserialize)]
+        struct Files {
+            files: Vec<ChangedFile>,
+        }
+
+        let output = gh([
+            "pr",
+            "view",
+            &number.to_string(),
+            "--repo",
+            &self.full_name(),
+            "--json",
+            "files",
+        ])?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to get PR files: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let files: Files = serde_json::from_slice(&output.stdout)?;
+        Ok(files.files)
+    }
+
+    /// Names of the labels on a PR
+    pub fn get_pr_labels(&self, number: i32) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct Labels {
+            labels: Vec<Label>,
+        }
+
+        #[derive(Deserialize)]
+        struct Label {
+            name: String,
+        }
+
+        let output = gh([
+            "pr",
+            "view",
+            &number.to_string(),
+            "--repo",
+            &self.full_name(),
+            "--json",
+            "labels",
+        ])?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to get PR labels: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let labels: Labels = serde_json::from_slice(&output.stdout)?;
+        Ok(labels.labels.into_iter().map(|l| l.name).collect())
+    }
+
+    /// Replace a PR's size, area, and risk labels
+    ///
+    /// Labels missing from the repository are created.
+    pub fn apply_pr_labels(&self, number: i32, labels: &PrLabels) -> Result<()> {
+        let current = self.get_pr_labels(number)?;
+        let (add, remove) = label_changes(&current, labels);
+        if add.is_empty() && remove.is_empty() {
+            return Ok(());
+        }
+
+        if self.edit_pr_labels(number, &add, &remove).is_ok() {
+            return Ok(());
+        }
+        for label in &add {
+            self.create_label(label)?;
+        }
+        self.edit_pr_labels(number, &add, &remove)
+    }
+
+    /// Classify a PR from its changed files and apply the labels
+    pub fn label_pr(&self, number: i32, manager: &PrWorkflowManager) -> Result<PrLabels> {
+        let labels = manager.classify_pr(&self.get_pr_files(number)?);
+        self.apply_pr_labels(number, &labels)?;
+        Ok(labels)
+    }
+
+    fn edit_pr_labels(&self, number: i32, add: &[String], remove: &[String]) -> Result<()> {
+        let mut args = vec![
+            "pr".to_string(),
+            "edit".to_string(),
+            number.to_string(),
+            "--repo".to_string(),
+            self.full_name(),
+        ];
+        if !add.is_empty() {
+            args.push("--add-label".to_string());
+            args.push(add.join(","));
+        }
+        if !remove.is_empty() {
+            args.push("--remove-label".to_string());
+            args.push(remove.join(","));
+        }
+
+        let output = gh(&args)?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to edit PR labels: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+
+    fn create_label(&self, label: &str) -> Result<()> {
+        let output = gh([
+            "label",
+            "create",
+            label,
+            "--repo",
+            &self.full_name(),
+            "--color",
+            label_color(label),
+            "--force",
+        ])?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to create label {}: {}",
+                label,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orchestrate_core::PrSize;
+
+    #[test]
+    fn test_label_changes_keeps_unmanaged_labels() {
+        let labels = PrLabels {
+            size: PrSize::M,
+            areas: vec!["api".to_string()],
+            risk: PrRisk::Low,
+        };
+        let current = vec![
+            "bug".to_string(),
+            "size/S".to_string(),
+            "area/api".to_string(),
+        ];
+
+        let (add, remove) = label_changes(&current, &labels);
+        assert_eq!(add, vec!["size/M", "risk/low"]);
+        assert_eq!(remove, vec!["size/S"]);
+    }
+
+    #[test]
+    fn test_label_colors() {
+        assert_eq!(label_color("risk/high"), "d73a4a");
+        assert_eq!(label_color("risk/low"), "0e8a16");
+        assert_eq!(label_color("size/XL"), "c5def5");
+    }
+}
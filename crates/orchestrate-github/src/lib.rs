@@ -1,12 +1,36 @@
 //! Orchestrate GitHub - GitHub API integration
 //!
 //! This crate provides GitHub integration:
-//! - PR management
-//! - Review handling
+//! - PR management, also as a PR workflow provider
+//! - Size, area, and risk labels
+//! - Review threads: listing, threaded replies, and resolving
 //! - CI check monitoring
+//! - Publishing review and security results as check runs
+//! - Batched PR status queries through the GraphQL API
+//! - Merge queues
+//! - Applying reviewer suggested changes
+//! - Publishing releases with notes from merged PRs
+//! - Waiting out primary and secondary rate limits
+//! - Branch protection
 
+pub mod branch_protection;
+pub mod checks;
 pub mod client;
+pub mod graphql;
+pub mod labels;
+pub mod merge_queue;
 pub mod pr;
+pub mod provider;
+pub mod rate_limit;
+pub mod release;
 pub mod review;
+pub mod suggestion;
 
+pub use checks::{CheckRunReport, REVIEW_CHECK_NAME};
 pub use client::GitHubClient;
+pub use graphql::{PrReview, PullRequestStatus};
+pub use merge_queue::MergeQueueEntry;
+pub use rate_limit::{rate_limits, RateLimit};
+pub use release::PublishOptions;
+pub use review::{ReviewThread, ThreadComment};
+pub use suggestion::{AppliedSuggestions, Suggestion};
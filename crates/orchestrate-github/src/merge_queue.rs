@@ -0,0 +1,176 @@
+//! Merge queue support
+//!
+//! Branches protected by a merge queue don't accept direct merges: PRs are
+//! enqueued, GitHub tests them together with the PRs ahead of them, and
+//! merges or dequeues them. Dequeues are reported to the webhook as
+//! `pull_request.dequeued` events.
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::client::GitHubClient;
+use crate::graphql::{graphql, Variable};
+
+const PR_QUEUE_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+    repository(owner: $owner, name: $repo) {
+        pullRequest(number: $number) {
+            id
+            mergeQueueEntry { position state enqueuedAt estimatedTimeToMerge }
+        }
+    }
+}
+"#;
+
+const ENQUEUE_MUTATION: &str = r#"
+mutation($pullRequestId: ID!) {
+    enqueuePullRequest(input: {pullRequestId: $pullRequestId}) {
+        mergeQueueEntry { position state enqueuedAt estimatedTimeToMerge }
+    }
+}
+"#;
+
+const DEQUEUE_MUTATION: &str = r#"
+mutation($id: ID!) {
+    dequeuePullRequest(input: {id: $id}) {
+        mergeQueueEntry { position }
+    }
+}
+"#;
+
+/// A PR's entry in the merge queue
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeQueueEntry {
+    /// Position in the queue; 1 is next to merge
+    pub position: u32,
+    /// `QUEUED`, `AWAITING_CHECKS`, `MERGEABLE`, `UNMERGEABLE`, or `LOCKED`
+    pub state: String,
+    pub enqueued_at: Option<String>,
+    /// Estimated seconds until the PR merges
+    pub estimated_time_to_merge: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueuedPullRequest {
+    id: String,
+    merge_queue_entry: Option<MergeQueueEntry>,
+}
+
+impl GitHubClient {
+    fn queued_pull_request(&self, number: i32) -> Result<QueuedPullRequest> {
+        #[derive(Deserialize)]
+        struct Data {
+            repository: Repository,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Repository {
+            pull_request: QueuedPullRequest,
+        }
+
+        let data: Data = graphql(
+            PR_QUEUE_QUERY,
+            &[
+                ("owner", Variable::String(&self.owner)),
+                ("repo", Variable::String(&self.repo)),
+                ("number", Variable::Int(number.into())),
+            ],
+        )?;
+        Ok(data.repository.pull_request)
+    }
+
+    /// The PR's merge queue entry, or `None` when it is not enqueued
+    pub fn get_merge_queue_entry(&self, number: i32) -> Result<Option<MergeQueueEntry>> {
+        Ok(self.queued_pull_request(number)?.merge_queue_entry)
+    }
+
+    /// Add a PR to its base branch's merge queue
+    ///
+    /// Enqueuing a PR that is already queued returns its current entry.
+    pub fn enqueue_pr(&self, number: i32) -> Result<MergeQueueEntry> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            enqueue_pull_request: Enqueued,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Enqueued {
+            merge_queue_entry: MergeQueueEntry,
+        }
+
+        let pr = self.queued_pull_request(number)?;
+        if let Some(entry) = pr.merge_queue_entry {
+            return Ok(entry);
+        }
+        let data: Data = graphql(
+            ENQUEUE_MUTATION,
+            &[("pullRequestId", Variable::String(&pr.id))],
+        )?;
+        Ok(data.enqueue_pull_request.merge_queue_entry)
+    }
+
+    /// Remove a PR from the merge queue
+    pub fn dequeue_pr(&self, number: i32) -> Result<()> {
+        let pr = self.queued_pull_request(number)?;
+        if pr.merge_queue_entry.is_none() {
+            return Ok(());
+        }
+        let _: serde_json::Value = graphql(DEQUEUE_MUTATION, &[("id", Variable::String(&pr.id))])?;
+        Ok(())
+    }
+
+    /// Merge a PR, or enqueue it when its base branch uses a merge queue
+    ///
+    /// Returns the queue entry when the PR was enqueued.
+    pub fn merge_or_enqueue(
+        &self,
+        number: i32,
+        strategy: &str,
+        use_merge_queue: bool,
+    ) -> Result<Option<MergeQueueEntry>> {
+        if use_merge_queue {
+            return self.enqueue_pr(number).map(Some);
+        }
+        self.merge_pr(number, strategy)?;
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphql::parse_response;
+
+    #[test]
+    fn test_parse_queue_entry() {
+        let body = br#"{"data":{"repository":{"pullRequest":{
+            "id":"PR_kwDOA",
+            "mergeQueueEntry":{"position":2,"state":"AWAITING_CHECKS",
+                "enqueuedAt":"2026-10-17T09:00:00Z","estimatedTimeToMerge":540}
+        }}}}"#;
+
+        #[derive(Deserialize)]
+        struct Data {
+            repository: Repository,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Repository {
+            pull_request: QueuedPullRequest,
+        }
+
+        let data: Data = parse_response(body).unwrap();
+        let pr = data.repository.pull_request;
+        assert_eq!(pr.id, "PR_kwDOA");
+        let entry = pr.merge_queue_entry.unwrap();
+        assert_eq!(entry.position, 2);
+        assert_eq!(entry.state, "AWAITING_CHECKS");
+        assert_eq!(entry.estimated_time_to_merge, Some(540));
+    }
+}
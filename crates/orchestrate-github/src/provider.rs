@@ -0,0 +1,85 @@
+//! GitHub as a PR workflow provider
+
+use async_trait::async_trait;
+use orchestrate_core::{CiCheckResult, MergeMethod, OpenedPr, PrApprovals, PrProvider};
+
+use crate::client::GitHubClient;
+
+impl GitHubClient {
+    /// Run a `gh` call off the async runtime, since rate limit waits block
+    async fn blocking<T, F>(&self, call: F) -> orchestrate_core::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&GitHubClient) -> anyhow::Result<T> + Send + 'static,
+    {
+        let client = self.clone();
+        tokio::task::spawn_blocking(move || call(&client))
+            .await
+            .map_err(|e| orchestrate_core::Error::Other(e.to_string()))?
+            .map_err(|e| orchestrate_core::Error::Other(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl PrProvider for GitHubClient {
+    fn name(&self) -> &str {
+        "github"
+    }
+
+    async fn create_pr(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> orchestrate_core::Result<OpenedPr> {
+        let (head, base, title, body) = (
+            head.to_string(),
+            base.to_string(),
+            title.to_string(),
+            body.to_string(),
+        );
+        let (number, url) = self
+            .blocking(move |client| client.create_pr_from(&head, &base, &title, &body))
+            .await?;
+        Ok(OpenedPr {
+            number: number as u64,
+            url: Some(url),
+        })
+    }
+
+    async fn get_approvals(&self, number: u64) -> orchestrate_core::Result<PrApprovals> {
+        let status = self
+            .blocking(move |client| client.get_pr_status(number as i32))
+            .await?;
+        Ok(PrApprovals {
+            approved: status.review_decision.as_deref() == Some("APPROVED"),
+            changes_requested: status.review_decision.as_deref() == Some("CHANGES_REQUESTED"),
+            approvers: status
+                .reviews
+                .into_iter()
+                .filter(|r| r.state == "APPROVED")
+                .map(|r| r.author)
+                .collect(),
+        })
+    }
+
+    async fn get_ci_checks(&self, number: u64) -> orchestrate_core::Result<Vec<CiCheckResult>> {
+        let status = self
+            .blocking(move |client| client.get_pr_status(number as i32))
+            .await?;
+        Ok(status.ci_checks())
+    }
+
+    async fn has_conflicts(&self, number: u64) -> orchestrate_core::Result<bool> {
+        let status = self
+            .blocking(move |client| client.get_pr_status(number as i32))
+            .await?;
+        Ok(status.has_conflicts())
+    }
+
+    async fn merge_pr(&self, number: u64, method: MergeMethod) -> orchestrate_core::Result<()> {
+        self.blocking(move |client| client.merge_pr(number as i32, method.as_str()))
+            .await
+    }
+}
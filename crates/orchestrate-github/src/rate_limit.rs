@@ -0,0 +1,436 @@
+//! GitHub rate limits
+//!
+//! Every `gh` invocation in this crate goes through [`gh`], which:
+//! - reads the primary rate limit (`x-ratelimit-*` headers) of `gh api`
+//!   responses and tracks the remaining quota per resource
+//! - holds requests back until the quota resets once it is nearly spent
+//! - backs off and retries when GitHub reports a primary or secondary
+//!   (abuse) rate limit, honoring `retry-after`
+//!
+//! Without this, a busy PR workflow runs into 403s that fail its steps.
+//! The tracked quota is exposed through [`rate_limits`] for metrics.
+//!
+//! Waiting is done by sleeping the calling thread, for up to 15 minutes, so
+//! async code must call the clients of this crate through
+//! `tokio::task::spawn_blocking` rather than on a runtime worker.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Retries of a request that hit a rate limit
+const MAX_RETRIES: u32 = 3;
+
+/// Longest a request waits for quota before failing instead
+const MAX_WAIT: Duration = Duration::from_secs(15 * 60);
+
+/// Backoff for a secondary rate limit without `retry-after`, doubled per retry
+const SECONDARY_BACKOFF_SECS: u64 = 60;
+
+/// Quota held back for requests already in flight, as a fraction of the limit
+const RESERVE_DIVISOR: u64 = 50;
+
+/// Primary rate limit of one API resource (`core`, `graphql`, `search`, ...)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimit {
+    pub resource: String,
+    pub limit: u64,
+    pub remaining: u64,
+    /// When the quota resets, in seconds since the Unix epoch
+    pub reset: u64,
+}
+
+impl RateLimit {
+    /// Parse the `x-ratelimit-*` headers of a response
+    pub fn from_headers(headers: &HashMap<String, String>) -> Option<Self> {
+        let number = |name: &str| headers.get(name)?.trim().parse::<u64>().ok();
+        Some(Self {
+            resource: headers
+                .get("x-ratelimit-resource")
+                .cloned()
+                .unwrap_or_else(|| "core".to_string()),
+            limit: number("x-ratelimit-limit")?,
+            remaining: number("x-ratelimit-remaining")?,
+            reset: number("x-ratelimit-reset")?,
+        })
+    }
+
+    /// Whether the quota is spent down to the reserve
+    pub fn is_nearly_exhausted(&self) -> bool {
+        self.remaining <= (self.limit / RESERVE_DIVISOR).max(1)
+    }
+}
+
+/// Rate limit state shared by all requests of the process
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    limits: HashMap<String, RateLimit>,
+    /// Set by a secondary rate limit, which applies to every resource
+    blocked_until: Option<u64>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the rate limit headers of a response
+    pub fn record(&self, headers: &HashMap<String, String>) {
+        if let Some(limit) = RateLimit::from_headers(headers) {
+            let mut state = self.state.lock().unwrap();
+            state.limits.insert(limit.resource.clone(), limit);
+        }
+    }
+
+    /// Record a rate limited response, returning how long to back off
+    pub fn record_limited(
+        &self,
+        headers: &HashMap<String, String>,
+        secondary: bool,
+        attempt: u32,
+        now: u64,
+    ) -> Duration {
+        let retry_after = headers
+            .get("retry-after")
+            .and_then(|v| v.trim().parse::<u64>().ok());
+        let mut state = self.state.lock().unwrap();
+
+        let until = match (RateLimit::from_headers(headers), retry_after) {
+            // A spent primary limit only holds back its own resource
+            (Some(limit), None) if !secondary => {
+                let reset = limit.reset;
+                state.limits.insert(limit.resource.clone(), limit);
+                return Duration::from_secs(reset.saturating_sub(now));
+            }
+            (_, Some(secs)) => now + secs,
+            _ => now + SECONDARY_BACKOFF_SECS * 2u64.pow(attempt),
+        };
+        state.blocked_until = state.blocked_until.max(Some(until));
+        Duration::from_secs(until.saturating_sub(now))
+    }
+
+    /// How long a request must wait before it is sent
+    ///
+    /// `resource` is `None` when it isn't known which resource the request
+    /// counts against, as for `gh pr` commands; every tracked resource is
+    /// considered then.
+    pub fn delay(&self, resource: Option<&str>, now: u64) -> Duration {
+        let state = self.state.lock().unwrap();
+        let exhausted_until = state
+            .limits
+            .values()
+            .filter(|l| resource.is_none_or(|r| l.resource == r))
+            .filter(|l| l.is_nearly_exhausted())
+            .map(|l| l.reset)
+            .max();
+
+        let until = state.blocked_until.max(exhausted_until).unwrap_or(0);
+        Duration::from_secs(until.saturating_sub(now))
+    }
+
+    /// Current quota of every resource seen so far
+    pub fn limits(&self) -> Vec<RateLimit> {
+        let state = self.state.lock().unwrap();
+        let mut limits: Vec<_> = state.limits.values().cloned().collect();
+        limits.sort_by(|a, b| a.resource.cmp(&b.resource));
+        limits
+    }
+}
+
+/// The process-wide rate limiter
+pub fn rate_limiter() -> &'static RateLimiter {
+    static LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+    LIMITER.get_or_init(RateLimiter::new)
+}
+
+/// Current quota of every GitHub API resource seen so far
+pub fn rate_limits() -> Vec<RateLimit> {
+    rate_limiter().limits()
+}
+
+/// Run a `gh` command, waiting for rate limit quota and retrying when limited
+pub(crate) fn gh<I, S>(args: I) -> Result<Output>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    run(args.into_iter().map(Into::into).collect(), None)
+}
+
+/// Run a `gh` command with `input` on stdin, as [`gh`] does
+pub(crate) fn gh_with_input<I, S>(args: I, input: &[u8]) -> Result<Output>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    run(args.into_iter().map(Into::into).collect(), Some(input))
+}
+
+fn run(mut args: Vec<OsString>, input: Option<&[u8]>) -> Result<Output> {
+    // `gh api` can show response headers; other commands can't
+    let is_api = args.first().is_some_and(|a| a == "api");
+    let resource = match args.get(1) {
+        _ if !is_api => None,
+        Some(endpoint) if endpoint == "graphql" => Some("graphql"),
+        _ => Some("core"),
+    };
+    if is_api {
+        args.insert(1, "--include".into());
+    }
+
+    send(
+        rate_limiter(),
+        resource,
+        is_api,
+        || execute(&args, input),
+        std::thread::sleep,
+        now,
+    )
+}
+
+/// Send a request through `limiter`, sleeping until it may be sent and
+/// retrying it when rate limited
+///
+/// The backoff of a rate limited request is recorded in `limiter`, so it is
+/// slept out before the retry along with any other delay.
+fn send(
+    limiter: &RateLimiter,
+    resource: Option<&str>,
+    is_api: bool,
+    mut execute: impl FnMut() -> Result<Output>,
+    mut sleep: impl FnMut(Duration),
+    now: impl Fn() -> u64,
+) -> Result<Output> {
+    let mut attempt = 0;
+    loop {
+        let delay = limiter.delay(resource, now());
+        if delay > MAX_WAIT {
+            anyhow::bail!(
+                "GitHub rate limit exhausted for another {} seconds",
+                delay.as_secs()
+            );
+        }
+        if !delay.is_zero() {
+            tracing::warn!(
+                "GitHub rate limit nearly exhausted, waiting {}s",
+                delay.as_secs()
+            );
+            sleep(delay);
+        }
+
+        let mut output = execute()?;
+        let headers = if is_api {
+            let (headers, body) = split_response(&output.stdout);
+            output.stdout = body;
+            headers
+        } else {
+            HashMap::new()
+        };
+        limiter.record(&headers);
+
+        if output.status.success() || attempt >= MAX_RETRIES {
+            return Ok(output);
+        }
+        let Some(secondary) = rate_limit_error(&output.stderr, &output.stdout) else {
+            return Ok(output);
+        };
+
+        let backoff = limiter.record_limited(&headers, secondary, attempt, now());
+        tracing::warn!(
+            "GitHub {} rate limit hit, retrying in {}s",
+            if secondary { "secondary" } else { "primary" },
+            backoff.as_secs()
+        );
+        attempt += 1;
+    }
+}
+
+fn execute(args: &[OsString], input: Option<&[u8]>) -> Result<Output> {
+    let Some(input) = input else {
+        return Ok(Command::new("gh").args(args).output()?);
+    };
+
+    let mut child = Command::new("gh")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input)?;
+    }
+    Ok(child.wait_with_output()?)
+}
+
+/// Split `gh api --include` output into lowercased headers and the body
+fn split_response(stdout: &[u8]) -> (HashMap<String, String>, Vec<u8>) {
+    if !stdout.starts_with(b"HTTP/") {
+        return (HashMap::new(), stdout.to_vec());
+    }
+    let (head, body) = match find(stdout, b"\r\n\r\n") {
+        Some(i) => (&stdout[..i], &stdout[i + 4..]),
+        None => match find(stdout, b"\n\n") {
+            Some(i) => (&stdout[..i], &stdout[i + 2..]),
+            None => (stdout, &[][..]),
+        },
+    };
+
+    let headers = String::from_utf8_lossy(head)
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+    (headers, body.to_vec())
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Whether a failed request was rate limited, and if so by a secondary limit
+fn rate_limit_error(stderr: &[u8], stdout: &[u8]) -> Option<bool> {
+    let message = format!(
+        "{}{}",
+        String::from_utf8_lossy(stderr),
+        String::from_utf8_lossy(stdout)
+    )
+    .to_lowercase();
+
+    if message.contains("secondary rate limit") || message.contains("abuse detection") {
+        Some(true)
+    } else if message.contains("rate limit exceeded") || message.contains("rate_limited") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_response() {
+        let stdout = b"HTTP/2.0 200 OK\r\nX-Ratelimit-Limit: 5000\r\nX-Ratelimit-Remaining: 4990\r\nX-Ratelimit-Reset: 1700000000\r\nX-Ratelimit-Resource: graphql\r\n\r\n{\"data\":{}}";
+        let (headers, body) = split_response(stdout);
+        assert_eq!(body, b"{\"data\":{}}");
+
+        let limit = RateLimit::from_headers(&headers).unwrap();
+        assert_eq!(limit.resource, "graphql");
+        assert_eq!((limit.limit, limit.remaining), (5000, 4990));
+
+        let (headers, body) = split_response(b"{\"id\":1}");
+        assert!(headers.is_empty());
+        assert_eq!(body, b"{\"id\":1}");
+    }
+
+    #[test]
+    fn test_delay_when_nearly_exhausted() {
+        let limiter = RateLimiter::new();
+        limiter.record(&headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "500"),
+            ("x-ratelimit-reset", "1000"),
+            ("x-ratelimit-resource", "core"),
+        ]));
+        assert!(limiter.delay(Some("core"), 900).is_zero());
+
+        limiter.record(&headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "100"),
+            ("x-ratelimit-reset", "1000"),
+            ("x-ratelimit-resource", "core"),
+        ]));
+        assert_eq!(limiter.delay(Some("core"), 900), Duration::from_secs(100));
+        assert_eq!(limiter.delay(None, 900), Duration::from_secs(100));
+        assert!(limiter.delay(Some("graphql"), 900).is_zero());
+        assert!(limiter.delay(Some("core"), 1000).is_zero());
+    }
+
+    #[test]
+    fn test_secondary_limit_backoff() {
+        let limiter = RateLimiter::new();
+        let backoff = limiter.record_limited(&headers(&[("retry-after", "30")]), true, 0, 100);
+        assert_eq!(backoff, Duration::from_secs(30));
+        assert_eq!(limiter.delay(Some("graphql"), 110), Duration::from_secs(20));
+
+        let backoff = limiter.record_limited(&HashMap::new(), true, 1, 100);
+        assert_eq!(backoff, Duration::from_secs(120));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_secondary_limit_backoff_is_waited_out() {
+        use std::cell::{Cell, RefCell};
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        let limited = Output {
+            status: ExitStatus::from_raw(1 << 8),
+            stdout: b"HTTP/2.0 403 Forbidden\r\nRetry-After: 30\r\n\r\n{}".to_vec(),
+            stderr: b"gh: You have exceeded a secondary rate limit. (HTTP 403)".to_vec(),
+        };
+        let ok = Output {
+            status: ExitStatus::from_raw(0),
+            stdout: b"HTTP/2.0 200 OK\r\n\r\n{\"id\":1}".to_vec(),
+            stderr: Vec::new(),
+        };
+        let mut responses = vec![ok, limited];
+
+        let limiter = RateLimiter::new();
+        let clock = Cell::new(1000);
+        let sent_at = RefCell::new(Vec::new());
+        let output = send(
+            &limiter,
+            Some("core"),
+            true,
+            || {
+                sent_at.borrow_mut().push(clock.get());
+                Ok(responses.pop().unwrap())
+            },
+            |delay| clock.set(clock.get() + delay.as_secs()),
+            || clock.get(),
+        )
+        .unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"{\"id\":1}");
+        // The retry is only sent once the retry-after has passed
+        assert_eq!(*sent_at.borrow(), vec![1000, 1030]);
+    }
+
+    #[test]
+    fn test_rate_limit_error() {
+        let secondary = b"gh: You have exceeded a secondary rate limit. (HTTP 403)";
+        assert_eq!(rate_limit_error(secondary, b""), Some(true));
+
+        let primary = br#"{"message":"API rate limit exceeded for user ID 1."}"#;
+        assert_eq!(rate_limit_error(b"gh: HTTP 403", primary), Some(false));
+
+        assert_eq!(rate_limit_error(b"Not Found (HTTP 404)", b""), None);
+    }
+}
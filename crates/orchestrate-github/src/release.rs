@@ -0,0 +1,365 @@
+//! GitHub releases (via gh CLI)
+//!
+//! Publishing a release creates its tag on the target commit, writes
+//! release notes from the PRs merged since the previous tag, and uploads
+//! the release assets.
+
+use anyhow::Result;
+use orchestrate_core::{Release, ReleaseAsset, ReleaseType};
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::client::GitHubClient;
+use crate::rate_limit::{gh, gh_with_input};
+
+/// A merged PR included in release notes
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergedPr {
+    pub number: i32,
+    pub title: String,
+    pub author: Option<PrAuthor>,
+    #[serde(default)]
+    pub labels: Vec<PrLabel>,
+    pub merged_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrAuthor {
+    pub login: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrLabel {
+    pub name: String,
+}
+
+impl MergedPr {
+    fn has_label(&self, names: &[&str]) -> bool {
+        self.labels
+            .iter()
+            .any(|l| names.contains(&l.name.to_lowercase().as_str()))
+    }
+
+    fn title_prefix(&self) -> Option<&str> {
+        let (prefix, _) = self.title.split_once(':')?;
+        Some(
+            prefix
+                .split('(')
+                .next()
+                .unwrap_or(prefix)
+                .trim_end_matches('!'),
+        )
+    }
+
+    /// Release notes section, from labels or a conventional commit title
+    pub fn section(&self) -> &'static str {
+        if self.has_label(&["breaking", "breaking-change"]) || self.title.contains("!:") {
+            "Breaking Changes"
+        } else if self.has_label(&["feature", "enhancement"]) || self.title_prefix() == Some("feat")
+        {
+            "Features"
+        } else if self.has_label(&["bug", "fix"]) || self.title_prefix() == Some("fix") {
+            "Bug Fixes"
+        } else {
+            "Other Changes"
+        }
+    }
+}
+
+const SECTIONS: [&str; 4] = ["Breaking Changes", "Features", "Bug Fixes", "Other Changes"];
+
+/// Markdown release notes grouping merged PRs by section
+pub fn release_notes(previous_tag: Option<&str>, tag: &str, prs: &[MergedPr]) -> String {
+    let mut notes = String::new();
+    for section in SECTIONS {
+        let entries: Vec<&MergedPr> = prs.iter().filter(|pr| pr.section() == section).collect();
+        if entries.is_empty() {
+            continue;
+        }
+        notes.push_str(&format!("## {}\n\n", section));
+        for pr in entries {
+            match &pr.author {
+                Some(author) => notes.push_str(&format!(
+                    "- {} (#{}) @{}\n",
+                    pr.title, pr.number, author.login
+                )),
+                None => notes.push_str(&format!("- {} (#{})\n", pr.title, pr.number)),
+            }
+        }
+        notes.push('\n');
+    }
+    if prs.is_empty() {
+        notes.push_str("No pull requests merged since the previous release.\n\n");
+    }
+    if let Some(previous) = previous_tag {
+        notes.push_str(&format!("**Full Changelog**: {}...{}\n", previous, tag));
+    }
+    notes
+}
+
+/// Whether a release should be marked as a pre-release
+pub fn is_prerelease(release: &Release) -> bool {
+    release.release_type == ReleaseType::PreRelease
+        || release
+            .version
+            .trim_start_matches('v')
+            .split('+')
+            .next()
+            .is_some_and(|v| v.contains('-'))
+}
+
+/// Tag name for a release version, e.g. `v1.2.3`
+pub fn tag_name(version: &str) -> String {
+    if version.starts_with('v') {
+        version.to_string()
+    } else {
+        format!("v{}", version)
+    }
+}
+
+/// Options for publishing a release
+#[derive(Debug, Clone, Default)]
+pub struct PublishOptions {
+    /// Files to upload as release assets
+    pub assets: Vec<PathBuf>,
+    /// Commit or branch to tag; defaults to the default branch
+    pub target: Option<String>,
+    /// Keep the release as a draft
+    pub draft: bool,
+    /// Previous tag to collect merged PRs from; defaults to the latest tag
+    pub previous_tag: Option<String>,
+}
+
+impl GitHubClient {
+    /// The most recent tag reachable from HEAD in the local repository
+    pub fn latest_tag(&self) -> Result<Option<String>> {
+        let output = Command::new("git")
+            .args(["describe", "--tags", "--abbrev=0"])
+            .output()?;
+
+        if !output.status.success() {
+            // No tags yet
+            return Ok(None);
+        }
+
+        let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(Some(tag).filter(|t| !t.is_empty()))
+    }
+
+    /// PRs merged since a tag was created, or all merged PRs without a tag
+    pub fn merged_prs_since(&self, tag: Option<&str>) -> Result<Vec<MergedPr>> {
+        let mut search = String::from("is:merged");
+        if let Some(tag) = tag {
+            let output = Command::new("git")
+                .args(["log", "-1", "--format=%cI", tag])
+                .output()?;
+            if !output.status.success() {
+                anyhow::bail!(
+                    "Failed to get date of tag {}: {}",
+                    tag,
+                    String::from_utf8_lossy(&output.stderr)
+                );
+            }
+            search.push_str(&format!(
+                " merged:>{}",
+                String::from_utf8_lossy(&output.stdout).trim()
+            ));
+        }
+
+        let output = gh([
+            "pr",
+            "list",
+            "--repo",
+            &self.full_name(),
+            "--state",
+            "merged",
+            "--search",
+            &search,
+            "--limit",
+            "500",
+            "--json",
+            "number,title,author,labels,mergedAt",
+        ])?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to list merged PRs: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let mut prs: Vec<MergedPr> = serde_json::from_slice(&output.stdout)?;
+        prs.sort_by_key(|pr| pr.number);
+        Ok(prs)
+    }
+
+    /// Create the GitHub release for `release`, with its tag and assets
+    ///
+    /// Fills in the release's tag, notes, previous version, and uploaded
+    /// assets, and marks it published unless it was kept as a draft.
+    pub fn publish_release(&self, release: &mut Release, options: &PublishOptions) -> Result<()> {
+        let tag = tag_name(&release.version);
+        let previous_tag = match &options.previous_tag {
+            Some(previous) => Some(previous.clone()),
+            None => self.latest_tag()?.filter(|t| *t != tag),
+        };
+        let prs = self.merged_prs_since(previous_tag.as_deref())?;
+        let notes = release_notes(previous_tag.as_deref(), &tag, &prs);
+
+        for asset in &options.assets {
+            if !asset.is_file() {
+                anyhow::bail!("Release asset not found: {}", asset.display());
+            }
+        }
+
+        let mut args = vec![
+            "release".to_string(),
+            "create".to_string(),
+            tag.clone(),
+            "--repo".to_string(),
+            self.full_name(),
+            "--title".to_string(),
+            tag.clone(),
+            "--notes-file".to_string(),
+            "-".to_string(),
+        ];
+        if let Some(target) = options.target.as_ref().or(release.commit_sha.as_ref()) {
+            args.push("--target".to_string());
+            args.push(target.clone());
+        }
+        if options.draft {
+            args.push("--draft".to_string());
+        }
+        if is_prerelease(release) {
+            args.push("--prerelease".to_string());
+        }
+        args.extend(options.assets.iter().map(|a| a.display().to_string()));
+
+        let output = gh_with_input(&args, notes.as_bytes())?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to create release: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        release.tag = Some(tag.clone());
+        release.previous_version = previous_tag;
+        release.release_notes = Some(notes);
+        release.assets = self.get_release_assets(&tag)?;
+        if !options.draft {
+            release.publish();
+        }
+        Ok(())
+    }
+
+    /// Assets uploaded to a release
+    pub fn get_release_assets(&self, tag: &str) -> Result<Vec<ReleaseAsset>> {
+        #[derive(Deserialize)]
+        struct Assets {
+            assets: Vec<Asset>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Asset {
+            name: String,
+            url: String,
+            size: u64,
+            content_type: String,
+        }
+
+        let output = gh([
+            "release",
+            "view",
+            tag,
+            "--repo",
+            &self.full_name(),
+            "--json",
+            "assets",
+        ])?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to get release assets: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let assets: Assets = serde_json::from_slice(&output.stdout)?;
+        Ok(assets
+            .assets
+            .into_iter()
+            .map(|a| ReleaseAsset {
+                name: a.name,
+                url: a.url,
+                size_bytes: a.size,
+                content_type: a.content_type,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: i32, title: &str, labels: &[&str]) -> MergedPr {
+        MergedPr {
+            number,
+            title: title.to_string(),
+            author: Some(PrAuthor {
+                login: "alice".to_string(),
+            }),
+            labels: labels
+                .iter()
+                .map(|l| PrLabel {
+                    name: l.to_string(),
+                })
+                .collect(),
+            merged_at: None,
+        }
+    }
+
+    #[test]
+    fn test_pr_sections() {
+        assert_eq!(pr(1, "feat(api): add export", &[]).section(), "Features");
+        assert_eq!(pr(2, "Fix crash on start", &["bug"]).section(), "Bug Fixes");
+        assert_eq!(
+            pr(3, "feat!: drop v1 API", &[]).section(),
+            "Breaking Changes"
+        );
+        assert_eq!(pr(4, "Update dependencies", &[]).section(), "Other Changes");
+    }
+
+    #[test]
+    fn test_release_notes() {
+        let prs = vec![
+            pr(10, "fix: handle empty config", &[]),
+            pr(11, "Add dark mode", &["enhancement"]),
+        ];
+        let notes = release_notes(Some("v1.2.0"), "v1.3.0", &prs);
+
+        let features = notes.find("## Features").unwrap();
+        let fixes = notes.find("## Bug Fixes").unwrap();
+        assert!(features < fixes);
+        assert!(notes.contains("- Add dark mode (#11) @alice"));
+        assert!(notes.contains("**Full Changelog**: v1.2.0...v1.3.0"));
+        assert!(!notes.contains("## Other Changes"));
+    }
+
+    #[test]
+    fn test_prerelease_detection() {
+        let release = Release::new("1.3.0-rc.1", ReleaseType::Minor, "test");
+        assert!(is_prerelease(&release));
+        let release = Release::new("1.3.0+build.5", ReleaseType::Minor, "test");
+        assert!(!is_prerelease(&release));
+        let release = Release::new("2.0.0", ReleaseType::PreRelease, "test");
+        assert!(is_prerelease(&release));
+        assert_eq!(tag_name("1.3.0"), "v1.3.0");
+        assert_eq!(tag_name("v1.3.0"), "v1.3.0");
+    }
+}
@@ -1,4 +1,286 @@
 //! Review handling
+//!
+//! Review threads are the inline comment conversations on a PR's diff.
+//! Answering feedback in its thread, and resolving the thread once it is
+//! addressed, keeps the discussion next to the code instead of in
+//! top-level PR comments.
 
-// Re-export from client for now
-pub use crate::client::*;
+use anyhow::Result;
+use orchestrate_core::{ReviewIssue, ReviewIssueSeverity, ThreadReply};
+use serde::Deserialize;
+
+use crate::client::GitHubClient;
+use crate::graphql::{graphql, Author, Nodes, Variable};
+
+const REVIEW_THREADS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!, $after: String) {
+    repository(owner: $owner, name: $repo) {
+        pullRequest(number: $number) {
+            reviewThreads(first: 100, after: $after) {
+                pageInfo { hasNextPage endCursor }
+                nodes {
+                    id
+                    isResolved
+                    isOutdated
+                    path
+                    line
+                    startLine
+                    comments(first: 100) {
+                        nodes { id url body author { login } }
+                    }
+                }
+            }
+        }
+    }
+}
+"#;
+
+const REPLY_MUTATION: &str = r#"
+mutation($threadId: ID!, $body: String!) {
+    addPullRequestReviewThreadReply(input: {pullRequestReviewThreadId: $threadId, body: $body}) {
+        comment { id url body author { login } }
+    }
+}
+"#;
+
+const RESOLVE_THREAD_MUTATION: &str = r#"
+mutation($threadId: ID!) {
+    resolveReviewThread(input: {threadId: $threadId}) {
+        thread { isResolved }
+    }
+}
+"#;
+
+#[derive(Debug, Clone)]
+pub struct ReviewThread {
+    pub id: String,
+    pub is_resolved: bool,
+    /// The diff changed under the thread since it was started
+    pub is_outdated: bool,
+    pub path: Option<String>,
+    pub line: Option<i32>,
+    /// First line of a multi-line comment
+    pub start_line: Option<i32>,
+    pub comments: Vec<ThreadComment>,
+}
+
+impl ReviewThread {
+    /// The comment that started the thread
+    pub fn first_comment(&self) -> Option<&ThreadComment> {
+        self.comments.first()
+    }
+
+    /// Convert the thread into a review issue for the code review coordinator
+    ///
+    /// The description is the opening comment; the thread id is kept so the
+    /// coordinator's replies land in the same thread.
+    pub fn to_review_issue(&self) -> ReviewIssue {
+        let description = self
+            .first_comment()
+            .map(|c| c.body.clone())
+            .unwrap_or_default();
+        let mut issue =
+            ReviewIssue::new(ReviewIssueSeverity::Medium, description).with_thread(&self.id);
+        if let Some(path) = &self.path {
+            issue.file_path = Some(path.clone());
+            issue.line_number = self.line.and_then(|l| u32::try_from(l).ok());
+        }
+        issue
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ThreadComment {
+    pub id: String,
+    pub url: Option<String>,
+    pub author: String,
+    pub body: String,
+}
+
+#[derive(Deserialize)]
+struct CommentNode {
+    id: String,
+    url: Option<String>,
+    body: String,
+    author: Option<Author>,
+}
+
+impl From<CommentNode> for ThreadComment {
+    fn from(c: CommentNode) -> Self {
+        Self {
+            id: c.id,
+            url: c.url,
+            author: c
+                .author
+                .map(|a| a.login)
+                .unwrap_or_else(|| "ghost".to_string()),
+            body: c.body,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadNode {
+    id: String,
+    is_resolved: bool,
+    #[serde(default)]
+    is_outdated: bool,
+    path: Option<String>,
+    line: Option<i32>,
+    #[serde(default)]
+    start_line: Option<i32>,
+    comments: Nodes<CommentNode>,
+}
+
+impl From<ThreadNode> for ReviewThread {
+    fn from(t: ThreadNode) -> Self {
+        Self {
+            id: t.id,
+            is_resolved: t.is_resolved,
+            is_outdated: t.is_outdated,
+            path: t.path,
+            line: t.line,
+            start_line: t.start_line,
+            comments: t.comments.nodes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl GitHubClient {
+    /// All review threads of a PR, resolved or not
+    pub fn get_review_threads(&self, number: i32) -> Result<Vec<ReviewThread>> {
+        #[derive(Deserialize)]
+        struct Data {
+            repository: Repository,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Repository {
+            pull_request: PullRequest,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct PullRequest {
+            review_threads: Threads,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Threads {
+            page_info: PageInfo,
+            nodes: Vec<ThreadNode>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct PageInfo {
+            has_next_page: bool,
+            end_cursor: Option<String>,
+        }
+
+        let mut threads = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let mut variables = vec![
+                ("owner", Variable::String(&self.owner)),
+                ("repo", Variable::String(&self.repo)),
+                ("number", Variable::Int(number.into())),
+            ];
+            if let Some(cursor) = &after {
+                variables.push(("after", Variable::String(cursor)));
+            }
+            let data: Data = graphql(REVIEW_THREADS_QUERY, &variables)?;
+            let page = data.repository.pull_request.review_threads;
+
+            threads.extend(page.nodes.into_iter().map(ReviewThread::from));
+
+            match page.page_info.end_cursor {
+                Some(cursor) if page.page_info.has_next_page => after = Some(cursor),
+                _ => break,
+            }
+        }
+        Ok(threads)
+    }
+
+    /// Get unresolved review threads
+    pub fn get_unresolved_threads(&self, number: i32) -> Result<Vec<ReviewThread>> {
+        Ok(self
+            .get_review_threads(number)?
+            .into_iter()
+            .filter(|t| !t.is_resolved)
+            .collect())
+    }
+
+    /// Reply in a review thread
+    pub fn reply_to_thread(&self, thread_id: &str, body: &str) -> Result<ThreadComment> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            add_pull_request_review_thread_reply: Reply,
+        }
+
+        #[derive(Deserialize)]
+        struct Reply {
+            comment: CommentNode,
+        }
+
+        let data: Data = graphql(
+            REPLY_MUTATION,
+            &[
+                ("threadId", Variable::String(thread_id)),
+                ("body", Variable::String(body)),
+            ],
+        )?;
+        Ok(data.add_pull_request_review_thread_reply.comment.into())
+    }
+
+    /// Resolve a review thread
+    pub fn resolve_thread(&self, thread_id: &str) -> Result<()> {
+        let _: serde_json::Value = graphql(
+            RESOLVE_THREAD_MUTATION,
+            &[("threadId", Variable::String(thread_id))],
+        )?;
+        Ok(())
+    }
+
+    /// Post a coordinator reply, resolving its thread when asked to
+    pub fn post_thread_reply(&self, reply: &ThreadReply) -> Result<ThreadComment> {
+        let comment = self.reply_to_thread(&reply.thread_id, &reply.body)?;
+        if reply.resolve {
+            self.resolve_thread(&reply.thread_id)?;
+        }
+        Ok(comment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_thread_to_review_issue() {
+        let node = json!({
+            "id": "PRRT_1",
+            "isResolved": false,
+            "path": "src/api.rs",
+            "line": 42,
+            "comments": { "nodes": [
+                { "id": "PRRC_1", "url": "https://github.com/o/r/pull/1#discussion_r1",
+                  "body": "Validate the input here", "author": { "login": "alice" } },
+                { "id": "PRRC_2", "url": null, "body": "+1", "author": null }
+            ] }
+        });
+        let thread: ReviewThread = serde_json::from_value::<ThreadNode>(node).unwrap().into();
+
+        assert_eq!(thread.comments[1].author, "ghost");
+        let issue = thread.to_review_issue();
+        assert_eq!(issue.description, "Validate the input here");
+        assert_eq!(issue.file_path.as_deref(), Some("src/api.rs"));
+        assert_eq!(issue.line_number, Some(42));
+        assert_eq!(issue.thread_id.as_deref(), Some("PRRT_1"));
+    }
+}
@@ -0,0 +1,361 @@
+//! Reviewer suggested changes
+//!
+//! A review comment can carry a ```` ```suggestion ```` block that replaces
+//! the lines it is attached to. Applying those directly in the PR's worktree
+//! saves an agent iteration for trivial fixes. Each commit credits the
+//! reviewers with `Co-authored-by` trailers, as GitHub's own "Commit
+//! suggestion" button does.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process::Command;
+
+use crate::client::GitHubClient;
+use crate::review::ReviewThread;
+
+const SUGGESTION_FENCE: &str = "```suggestion";
+
+/// A suggested change from a review thread
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// Review thread the suggestion was made in
+    pub thread_id: String,
+    /// Reviewer login
+    pub author: String,
+    pub path: String,
+    /// First replaced line (1-based)
+    pub start_line: usize,
+    /// Last replaced line (1-based, inclusive)
+    pub end_line: usize,
+    /// Replacement text; empty deletes the lines
+    pub replacement: String,
+}
+
+impl Suggestion {
+    /// The suggestion in a thread, if the thread opens with one
+    ///
+    /// Resolved and outdated threads are skipped, as are comments with more
+    /// than one suggestion block, which GitHub doesn't apply either.
+    pub fn from_thread(thread: &ReviewThread) -> Option<Self> {
+        if thread.is_resolved || thread.is_outdated {
+            return None;
+        }
+        let comment = thread.first_comment()?;
+        let mut blocks = parse_suggestion_blocks(&comment.body);
+        if blocks.len() != 1 {
+            return None;
+        }
+        let end_line = usize::try_from(thread.line?).ok()?;
+        let start_line = thread
+            .start_line
+            .and_then(|l| usize::try_from(l).ok())
+            .unwrap_or(end_line);
+        if start_line == 0 || start_line > end_line {
+            return None;
+        }
+        Some(Self {
+            thread_id: thread.id.clone(),
+            author: comment.author.clone(),
+            path: thread.path.clone()?,
+            start_line,
+            end_line,
+            replacement: blocks.remove(0),
+        })
+    }
+
+    /// Apply the suggestion to the file content
+    ///
+    /// Returns `None` when the file is shorter than the suggested range.
+    pub fn apply_to(&self, content: &str) -> Option<String> {
+        let mut lines: Vec<&str> = content.lines().collect();
+        if self.end_line > lines.len() {
+            return None;
+        }
+        lines.splice(self.start_line - 1..self.end_line, self.replacement.lines());
+
+        let mut result = lines.join("\n");
+        if content.ends_with('\n') && !lines.is_empty() {
+            result.push('\n');
+        }
+        Some(result)
+    }
+}
+
+/// Extract the contents of the ```` ```suggestion ```` blocks in a comment
+pub fn parse_suggestion_blocks(body: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in body.lines() {
+        let trimmed = line.trim_end_matches('\r');
+        match current.as_mut() {
+            None if trimmed.trim_start().starts_with(SUGGESTION_FENCE) => {
+                current = Some(Vec::new());
+            }
+            None => {}
+            Some(block) if trimmed.trim() == "```" => {
+                blocks.push(block.join("\n"));
+                current = None;
+            }
+            Some(block) => block.push(trimmed),
+        }
+    }
+    blocks
+}
+
+/// Result of applying suggestions in a worktree
+#[derive(Debug, Clone, Default)]
+pub struct AppliedSuggestions {
+    /// Suggestions included in the commit
+    pub applied: Vec<Suggestion>,
+    /// Suggestions that overlapped another one or no longer fit the file
+    pub skipped: Vec<Suggestion>,
+    /// SHA of the commit, when anything was applied
+    pub commit_sha: Option<String>,
+}
+
+/// Apply suggestions to the files in a worktree and commit them
+///
+/// Suggestions are applied bottom-up per file so earlier line numbers stay
+/// valid; a suggestion overlapping one already applied is skipped.
+pub fn apply_suggestions(
+    worktree: &Path,
+    suggestions: &[Suggestion],
+) -> Result<AppliedSuggestions> {
+    let mut by_file: BTreeMap<&str, Vec<&Suggestion>> = BTreeMap::new();
+    for suggestion in suggestions {
+        by_file
+            .entry(&suggestion.path)
+            .or_default()
+            .push(suggestion);
+    }
+
+    let mut result = AppliedSuggestions::default();
+    for (path, mut file_suggestions) in by_file {
+        let file = worktree.join(path);
+        let mut content = std::fs::read_to_string(&file)?;
+        file_suggestions.sort_by_key(|s| std::cmp::Reverse(s.start_line));
+
+        let mut applied_from = usize::MAX;
+        for suggestion in file_suggestions {
+            let updated = if suggestion.end_line < applied_from {
+                suggestion.apply_to(&content)
+            } else {
+                None
+            };
+            match updated {
+                Some(updated) => {
+                    content = updated;
+                    applied_from = suggestion.start_line;
+                    result.applied.push(suggestion.clone());
+                }
+                None => result.skipped.push(suggestion.clone()),
+            }
+        }
+        std::fs::write(&file, content)?;
+    }
+
+    if result.applied.is_empty() {
+        return Ok(result);
+    }
+
+    let paths: BTreeSet<&str> = result.applied.iter().map(|s| s.path.as_str()).collect();
+    git(
+        worktree,
+        &["add", "--"],
+        &paths.into_iter().collect::<Vec<_>>(),
+    )?;
+    git(
+        worktree,
+        &["commit", "-m", &commit_message(&result.applied)],
+        &[],
+    )?;
+    result.commit_sha = Some(git(worktree, &["rev-parse", "HEAD"], &[])?);
+    Ok(result)
+}
+
+/// Commit message crediting each reviewer whose suggestion was applied
+pub fn commit_message(applied: &[Suggestion]) -> String {
+    let mut message = String::from("Apply suggestions from code review\n");
+    let authors: BTreeSet<&str> = applied
+        .iter()
+        .map(|s| s.author.as_str())
+        .filter(|a| *a != "ghost")
+        .collect();
+    if !authors.is_empty() {
+        message.push('\n');
+        for author in authors {
+            message.push_str(&format!(
+                "Co-authored-by: {author} <{author}@users.noreply.github.com>\n"
+            ));
+        }
+    }
+    message
+}
+
+fn git(worktree: &Path, args: &[&str], paths: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(worktree)
+        .args(args)
+        .args(paths)
+        .output()?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+impl GitHubClient {
+    /// Suggested changes in the PR's open review threads
+    pub fn get_suggestions(&self, number: i32) -> Result<Vec<Suggestion>> {
+        Ok(self
+            .get_unresolved_threads(number)?
+            .iter()
+            .filter_map(Suggestion::from_thread)
+            .collect())
+    }
+
+    /// Apply a PR's suggested changes in its worktree and push them
+    ///
+    /// Each applied suggestion's thread gets a reply pointing at the commit
+    /// and is resolved.
+    pub fn apply_pr_suggestions(&self, number: i32, worktree: &Path) -> Result<AppliedSuggestions> {
+        let suggestions = self.get_suggestions(number)?;
+        if suggestions.is_empty() {
+            return Ok(AppliedSuggestions::default());
+        }
+
+        let result = apply_suggestions(worktree, &suggestions)?;
+        let Some(sha) = &result.commit_sha else {
+            return Ok(result);
+        };
+        git(worktree, &["push"], &[])?;
+
+        for suggestion in &result.applied {
+            self.reply_to_thread(&suggestion.thread_id, &format!("Applied in {sha}."))?;
+            self.resolve_thread(&suggestion.thread_id)?;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::review::ThreadComment;
+
+    fn thread(body: &str, start_line: Option<i32>, line: i32) -> ReviewThread {
+        ReviewThread {
+            id: "PRRT_1".to_string(),
+            is_resolved: false,
+            is_outdated: false,
+            path: Some("src/lib.rs".to_string()),
+            line: Some(line),
+            start_line,
+            comments: vec![ThreadComment {
+                id: "PRRC_1".to_string(),
+                url: None,
+                author: "alice".to_string(),
+                body: body.to_string(),
+            }],
+        }
+    }
+
+    fn suggestion(start_line: usize, end_line: usize, replacement: &str) -> Suggestion {
+        Suggestion {
+            thread_id: format!("PRRT_{}", start_line),
+            author: "alice".to_string(),
+            path: "src/lib.rs".to_string(),
+            start_line,
+            end_line,
+            replacement: replacement.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_suggestion_blocks() {
+        let body = "Use a constant:\r\n```suggestion\r\nconst MAX: usize = 10;\r\n```\r\n";
+        assert_eq!(
+            parse_suggestion_blocks(body),
+            vec!["const MAX: usize = 10;"]
+        );
+
+        let deletion = "Not needed\n```suggestion\n```";
+        assert_eq!(parse_suggestion_blocks(deletion), vec![""]);
+
+        assert!(parse_suggestion_blocks("```rust\nlet x = 1;\n```").is_empty());
+    }
+
+    #[test]
+    fn test_suggestion_from_thread() {
+        let s = Suggestion::from_thread(&thread("```suggestion\nb\n```", Some(2), 3)).unwrap();
+        assert_eq!((s.start_line, s.end_line), (2, 3));
+        assert_eq!(s.author, "alice");
+
+        let single = Suggestion::from_thread(&thread("```suggestion\nb\n```", None, 3)).unwrap();
+        assert_eq!((single.start_line, single.end_line), (3, 3));
+
+        assert!(Suggestion::from_thread(&thread("Please rename", None, 3)).is_none());
+        let mut outdated = thread("```suggestion\nb\n```", None, 3);
+        outdated.is_outdated = true;
+        assert!(Suggestion::from_thread(&outdated).is_none());
+    }
+
+    #[test]
+    fn test_apply_to_replaces_range() {
+        let content = "a\nb\nc\nd\n";
+        assert_eq!(
+            suggestion(2, 3, "x").apply_to(content).unwrap(),
+            "a\nx\nd\n"
+        );
+        assert_eq!(suggestion(4, 4, "").apply_to(content).unwrap(), "a\nb\nc\n");
+        assert!(suggestion(4, 5, "x").apply_to(content).is_none());
+    }
+
+    #[test]
+    fn test_apply_suggestions_commits_with_attribution() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let run = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(root)
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        run(&["init", "-q"]);
+        run(&["config", "user.email", "agent@example.com"]);
+        run(&["config", "user.name", "agent"]);
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "a\nb\nc\nd\n").unwrap();
+        run(&["add", "."]);
+        run(&["commit", "-q", "-m", "init"]);
+
+        let suggestions = vec![
+            suggestion(1, 1, "A"),
+            suggestion(3, 4, "C"),
+            suggestion(3, 3, "overlaps"),
+        ];
+        let result = apply_suggestions(root, &suggestions).unwrap();
+
+        assert_eq!(result.applied.len(), 2);
+        assert_eq!(result.skipped.len(), 1);
+        assert!(result.commit_sha.is_some());
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            "A\nb\nC\n"
+        );
+        let message = git(root, &["log", "-1", "--format=%B"], &[]).unwrap();
+        assert!(message.contains("Co-authored-by: alice <alice@users.noreply.github.com>"));
+    }
+}
@@ -0,0 +1,185 @@
+//! GitLab API client (via glab CLI)
+
+use anyhow::Result;
+use orchestrate_core::{RepoProvider, Repository};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Host of GitLab.com
+pub const DEFAULT_HOST: &str = "gitlab.com";
+
+/// GitLab client using glab CLI
+#[derive(Debug, Clone)]
+pub struct GitLabClient {
+    /// GitLab host, e.g. `gitlab.com` or a self-managed instance
+    pub host: String,
+    /// Project path with namespace, e.g. `group/subgroup/project`
+    pub project: String,
+}
+
+impl GitLabClient {
+    /// Create a client for a project given as `namespace/project` on GitLab.com
+    pub fn for_project(path: &str) -> Result<Self> {
+        let project = path.trim_matches('/');
+        if !project.contains('/') || project.split('/').any(str::is_empty) {
+            anyhow::bail!("Invalid GitLab project path: {}", path);
+        }
+        Ok(Self {
+            host: DEFAULT_HOST.to_string(),
+            project: project.to_string(),
+        })
+    }
+
+    /// Create a client for a project's clone URL (HTTPS or SSH)
+    pub fn from_url(url: &str) -> Result<Self> {
+        let (host, project) = parse_project_url(url)
+            .ok_or_else(|| anyhow::anyhow!("Invalid GitLab project URL: {}", url))?;
+        Ok(Self { host, project })
+    }
+
+    /// Create a client for a repository declared in multi-repo configuration
+    pub fn for_repository(repository: &Repository) -> Result<Self> {
+        // Self-managed hosts aren't detected as GitLab, so only reject
+        // repositories known to live elsewhere
+        if matches!(
+            repository.provider,
+            RepoProvider::GitHub | RepoProvider::Bitbucket
+        ) {
+            anyhow::bail!(
+                "Repository {} is hosted on {}, not GitLab",
+                repository.name,
+                repository.provider.as_str()
+            );
+        }
+        Self::from_url(&repository.url)
+    }
+
+    /// `projects/:id` API path, with the project path URL-encoded as its ID
+    pub(crate) fn project_endpoint(&self, path: &str) -> String {
+        format!("projects/{}/{}", encode(&self.project), path)
+    }
+
+    /// Run `glab api` against this client's host, sending `body` as JSON
+    pub(crate) fn api(&self, method: &str, endpoint: &str, body: Option<&Value>) -> Result<Output> {
+        let mut command = Command::new("glab");
+        command.args([
+            "api",
+            "--hostname",
+            &self.host,
+            "--method",
+            method,
+            endpoint,
+        ]);
+        let Some(body) = body else {
+            return Ok(command.output()?);
+        };
+
+        let mut child = command
+            .args(["--header", "Content-Type: application/json", "--input", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(body.to_string().as_bytes())?;
+        }
+        Ok(child.wait_with_output()?)
+    }
+
+    /// Run `glab api` and parse the JSON response
+    pub(crate) fn api_json<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        endpoint: &str,
+        body: Option<&Value>,
+        action: &str,
+    ) -> Result<T> {
+        let output = self.api(method, endpoint, body)?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to {}: {}",
+                action,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(serde_json::from_slice(&output.stdout)?)
+    }
+}
+
+/// Percent-encode a path segment or query value
+pub(crate) fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Host and project path of a GitLab clone URL
+///
+/// Accepts `https://host/group/project(.git)`, `ssh://git@host:port/group/project.git`,
+/// and `git@host:group/project.git`.
+pub fn parse_project_url(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    let (host, path) = match url.split_once("://") {
+        Some((_, rest)) => {
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority.rsplit('@').next()?;
+            (host.split(':').next()?, path)
+        }
+        None => {
+            let (authority, path) = url.split_once(':')?;
+            (authority.rsplit('@').next()?, path)
+        }
+    };
+
+    let project = path.trim_end_matches('/').trim_end_matches(".git");
+    if host.is_empty() || !project.contains('/') || project.split('/').any(str::is_empty) {
+        return None;
+    }
+    Some((host.to_string(), project.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_project_url() {
+        assert_eq!(
+            parse_project_url("https://gitlab.com/group/sub/project.git"),
+            Some(("gitlab.com".to_string(), "group/sub/project".to_string()))
+        );
+        assert_eq!(
+            parse_project_url("git@gitlab.example.com:team/app.git"),
+            Some(("gitlab.example.com".to_string(), "team/app".to_string()))
+        );
+        assert_eq!(
+            parse_project_url("ssh://git@gitlab.example.com:2222/team/app.git"),
+            Some(("gitlab.example.com".to_string(), "team/app".to_string()))
+        );
+        assert_eq!(parse_project_url("https://gitlab.com/project"), None);
+    }
+
+    #[test]
+    fn test_for_repository() {
+        let repo = Repository::new("app", "https://gitlab.com/team/app");
+        let client = GitLabClient::for_repository(&repo).unwrap();
+        assert_eq!(client.host, "gitlab.com");
+        assert_eq!(
+            client.project_endpoint("merge_requests"),
+            "projects/team%2Fapp/merge_requests"
+        );
+
+        let repo = Repository::new("app", "https://github.com/team/app");
+        assert!(GitLabClient::for_repository(&repo).is_err());
+    }
+}
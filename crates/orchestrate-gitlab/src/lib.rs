@@ -0,0 +1,19 @@
+//! Orchestrate GitLab - GitLab API integration
+//!
+//! This crate provides GitLab integration for projects declared in
+//! multi-repo configuration:
+//! - Merge request creation and merging
+//! - Approvals
+//! - Pipeline status as CI check results
+//! - Protected branches
+//! - All of the above as a PR workflow provider
+
+pub mod client;
+pub mod merge_request;
+pub mod pipeline;
+pub mod protected_branch;
+pub mod provider;
+
+pub use client::GitLabClient;
+pub use merge_request::{Approvals, MergeRequest};
+pub use pipeline::PipelineJob;
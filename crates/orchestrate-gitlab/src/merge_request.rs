@@ -0,0 +1,269 @@
+//! Merge requests and approvals
+
+use anyhow::Result;
+use orchestrate_core::PrMergeStatus;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::client::{encode, GitLabClient};
+
+/// A GitLab merge request
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergeRequest {
+    /// Project-scoped MR number (`!iid`)
+    pub iid: i64,
+    pub title: String,
+    /// `opened`, `closed`, `locked`, or `merged`
+    pub state: String,
+    #[serde(default)]
+    pub draft: bool,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub web_url: String,
+    pub sha: Option<String>,
+    #[serde(default)]
+    pub has_conflicts: bool,
+    /// Why the MR can or can't merge, e.g. `mergeable` or `not_approved`
+    pub detailed_merge_status: Option<String>,
+    pub head_pipeline: Option<PipelineRef>,
+}
+
+/// The latest pipeline of a merge request
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineRef {
+    pub id: i64,
+    pub status: String,
+    pub web_url: Option<String>,
+}
+
+impl MergeRequest {
+    /// Merge status in the same terms as GitHub PRs
+    pub fn merge_status(&self) -> PrMergeStatus {
+        match self.state.as_str() {
+            "merged" => return PrMergeStatus::Merged,
+            "closed" | "locked" => return PrMergeStatus::Closed,
+            _ => {}
+        }
+        if self.draft {
+            return PrMergeStatus::Draft;
+        }
+        if self.has_conflicts {
+            return PrMergeStatus::Conflicts;
+        }
+        match self.detailed_merge_status.as_deref() {
+            Some("mergeable") => PrMergeStatus::Mergeable,
+            Some("conflict") => PrMergeStatus::Conflicts,
+            Some("draft_status") => PrMergeStatus::Draft,
+            Some("checking") | Some("unchecked") | Some("preparing") | None => {
+                PrMergeStatus::Unknown
+            }
+            Some(_) => PrMergeStatus::Blocked,
+        }
+    }
+}
+
+/// Approval state of a merge request
+#[derive(Debug, Clone, Deserialize)]
+pub struct Approvals {
+    #[serde(default)]
+    pub approved: bool,
+    #[serde(default)]
+    pub approvals_required: u32,
+    #[serde(default)]
+    pub approvals_left: u32,
+    #[serde(default)]
+    pub approved_by: Vec<Approver>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Approver {
+    pub user: ApprovalUser,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApprovalUser {
+    pub username: String,
+}
+
+impl Approvals {
+    /// Usernames of the approvers
+    pub fn approvers(&self) -> Vec<&str> {
+        self.approved_by
+            .iter()
+            .map(|a| a.user.username.as_str())
+            .collect()
+    }
+}
+
+impl GitLabClient {
+    /// Create an MR from `source` into `target`
+    pub fn create_mr(
+        &self,
+        source: &str,
+        target: &str,
+        title: &str,
+        description: &str,
+    ) -> Result<MergeRequest> {
+        let body = json!({
+            "source_branch": source,
+            "target_branch": target,
+            "title": title,
+            "description": description,
+            "remove_source_branch": true,
+        });
+        self.api_json(
+            "POST",
+            &self.project_endpoint("merge_requests"),
+            Some(&body),
+            "create merge request",
+        )
+    }
+
+    /// Get an MR by its project-scoped number
+    pub fn get_mr(&self, iid: i64) -> Result<MergeRequest> {
+        self.api_json(
+            "GET",
+            &self.project_endpoint(&format!("merge_requests/{}", iid)),
+            None,
+            "get merge request",
+        )
+    }
+
+    /// Open MR for a source branch, if any
+    pub fn find_mr_for_branch(&self, source: &str) -> Result<Option<MergeRequest>> {
+        let mrs: Vec<MergeRequest> = self.api_json(
+            "GET",
+            &self.project_endpoint(&format!(
+                "merge_requests?state=opened&source_branch={}",
+                encode(source)
+            )),
+            None,
+            "list merge requests",
+        )?;
+        Ok(mrs.into_iter().next())
+    }
+
+    /// Merge an MR with the `squash`, `rebase`, or `merge` strategy
+    ///
+    /// `rebase` rebases the source branch first, then merges without squashing.
+    pub fn merge_mr(&self, iid: i64, strategy: &str) -> Result<MergeRequest> {
+        if strategy == "rebase" {
+            let output = self.api(
+                "PUT",
+                &self.project_endpoint(&format!("merge_requests/{}/rebase", iid)),
+                None,
+            )?;
+            if !output.status.success() {
+                anyhow::bail!(
+                    "Failed to rebase merge request: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+            }
+        }
+
+        let body = json!({
+            "squash": strategy != "merge" && strategy != "rebase",
+            "should_remove_source_branch": true,
+        });
+        self.api_json(
+            "PUT",
+            &self.project_endpoint(&format!("merge_requests/{}/merge", iid)),
+            Some(&body),
+            "merge merge request",
+        )
+    }
+
+    /// Approval state of an MR
+    pub fn get_approvals(&self, iid: i64) -> Result<Approvals> {
+        self.api_json(
+            "GET",
+            &self.project_endpoint(&format!("merge_requests/{}/approvals", iid)),
+            None,
+            "get approvals",
+        )
+    }
+
+    /// Approve an MR as the authenticated user
+    pub fn approve_mr(&self, iid: i64) -> Result<Approvals> {
+        self.api_json(
+            "POST",
+            &self.project_endpoint(&format!("merge_requests/{}/approve", iid)),
+            None,
+            "approve merge request",
+        )
+    }
+
+    /// Post a comment (note) on an MR
+    pub fn post_mr_note(&self, iid: i64, body: &str) -> Result<()> {
+        let output = self.api(
+            "POST",
+            &self.project_endpoint(&format!("merge_requests/{}/notes", iid)),
+            Some(&json!({ "body": body })),
+        )?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to post merge request note: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mr(state: &str, detailed: Option<&str>) -> MergeRequest {
+        serde_json::from_value(json!({
+            "iid": 7,
+            "title": "Add export",
+            "state": state,
+            "source_branch": "feature/export",
+            "target_branch": "main",
+            "web_url": "https://gitlab.com/team/app/-/merge_requests/7",
+            "detailed_merge_status": detailed,
+            "head_pipeline": { "id": 99, "status": "running" }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge_status() {
+        assert_eq!(
+            mr("opened", Some("mergeable")).merge_status(),
+            PrMergeStatus::Mergeable
+        );
+        assert_eq!(
+            mr("opened", Some("not_approved")).merge_status(),
+            PrMergeStatus::Blocked
+        );
+        assert_eq!(
+            mr("opened", Some("checking")).merge_status(),
+            PrMergeStatus::Unknown
+        );
+        assert_eq!(
+            mr("merged", Some("not_open")).merge_status(),
+            PrMergeStatus::Merged
+        );
+
+        let mut conflicting = mr("opened", Some("mergeable"));
+        conflicting.has_conflicts = true;
+        assert_eq!(conflicting.merge_status(), PrMergeStatus::Conflicts);
+    }
+
+    #[test]
+    fn test_parse_approvals() {
+        let approvals: Approvals = serde_json::from_value(json!({
+            "approved": false,
+            "approvals_required": 2,
+            "approvals_left": 1,
+            "approved_by": [{ "user": { "username": "alice", "id": 1 } }]
+        }))
+        .unwrap();
+        assert_eq!(approvals.approvers(), vec!["alice"]);
+        assert_eq!(approvals.approvals_left, 1);
+    }
+}
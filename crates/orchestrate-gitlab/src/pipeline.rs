@@ -0,0 +1,133 @@
+//! Merge request pipeline status
+
+use anyhow::Result;
+use orchestrate_core::{CiAggregateStatus, CiCheckResult, CiStatus};
+use serde::Deserialize;
+
+use crate::client::GitLabClient;
+
+/// A job in a GitLab pipeline
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineJob {
+    pub name: String,
+    pub status: String,
+    pub web_url: Option<String>,
+    #[serde(default)]
+    pub allow_failure: bool,
+    pub failure_reason: Option<String>,
+    pub duration: Option<f64>,
+}
+
+impl PipelineJob {
+    /// CI status of the job; failures of jobs allowed to fail count as passed
+    pub fn ci_status(&self) -> CiStatus {
+        match self.status.as_str() {
+            "success" | "skipped" => CiStatus::Passed,
+            "failed" if self.allow_failure => CiStatus::Passed,
+            "failed" if self.failure_reason.as_deref() == Some("job_execution_timeout") => {
+                CiStatus::Timeout
+            }
+            "failed" => CiStatus::Failed,
+            "canceled" | "canceling" => CiStatus::Cancelled,
+            "running" => CiStatus::Running,
+            // created, pending, preparing, scheduled, manual, waiting_for_resource
+            _ => CiStatus::Pending,
+        }
+    }
+
+    /// The job as a CI check result
+    pub fn to_check(&self) -> CiCheckResult {
+        let status = self.ci_status();
+        let mut check = CiCheckResult::new(&self.name, status);
+        if let Some(url) = &self.web_url {
+            check = check.with_url(url);
+        }
+        if matches!(status, CiStatus::Failed | CiStatus::Timeout) {
+            check = check.with_failure(
+                self.failure_reason
+                    .clone()
+                    .unwrap_or_else(|| "job failed".to_string()),
+            );
+        }
+        check.duration_secs = self.duration.map(|d| d as u64);
+        check
+    }
+}
+
+impl GitLabClient {
+    /// Jobs of an MR's latest pipeline as CI check results
+    ///
+    /// Empty when the MR has no pipeline yet.
+    pub fn get_pipeline_checks(&self, iid: i64) -> Result<Vec<CiCheckResult>> {
+        let mr = self.get_mr(iid)?;
+        let Some(pipeline) = mr.head_pipeline else {
+            return Ok(Vec::new());
+        };
+
+        let jobs: Vec<PipelineJob> = self.api_json(
+            "GET",
+            &self.project_endpoint(&format!("pipelines/{}/jobs?per_page=100", pipeline.id)),
+            None,
+            "get pipeline jobs",
+        )?;
+        Ok(jobs.iter().map(PipelineJob::to_check).collect())
+    }
+
+    /// Overall CI status of an MR's latest pipeline
+    pub fn get_pipeline_status(&self, iid: i64) -> Result<CiAggregateStatus> {
+        Ok(CiAggregateStatus::from_checks(
+            &self.get_pipeline_checks(iid)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(status: &str, allow_failure: bool, reason: Option<&str>) -> PipelineJob {
+        PipelineJob {
+            name: "test".to_string(),
+            status: status.to_string(),
+            web_url: Some("https://gitlab.com/team/app/-/jobs/1".to_string()),
+            allow_failure,
+            failure_reason: reason.map(str::to_string),
+            duration: Some(12.7),
+        }
+    }
+
+    #[test]
+    fn test_job_status() {
+        assert_eq!(job("success", false, None).ci_status(), CiStatus::Passed);
+        assert_eq!(
+            job("failed", true, Some("script_failure")).ci_status(),
+            CiStatus::Passed
+        );
+        assert_eq!(
+            job("failed", false, Some("job_execution_timeout")).ci_status(),
+            CiStatus::Timeout
+        );
+        assert_eq!(job("manual", false, None).ci_status(), CiStatus::Pending);
+
+        let check = job("failed", false, Some("script_failure")).to_check();
+        assert_eq!(check.status, CiStatus::Failed);
+        assert_eq!(check.failure_details.as_deref(), Some("script_failure"));
+        assert_eq!(check.duration_secs, Some(12));
+    }
+
+    #[test]
+    fn test_aggregate_status() {
+        let checks: Vec<CiCheckResult> = [
+            job("success", false, None),
+            job("failed", false, Some("script_failure")),
+            job("running", false, None),
+        ]
+        .iter()
+        .map(PipelineJob::to_check)
+        .collect();
+        assert_eq!(
+            CiAggregateStatus::from_checks(&checks).overall,
+            CiStatus::Failed
+        );
+    }
+}
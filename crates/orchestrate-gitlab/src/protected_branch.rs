@@ -0,0 +1,134 @@
+//! Protected branches
+//!
+//! GitLab protected branches cover force pushes, deletion, and code owner
+//! approval. Approvals and required pipelines are project or merge request
+//! settings, so they aren't enforced per branch here.
+
+use anyhow::Result;
+use orchestrate_core::{BranchProtection, ProtectionSetting};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::client::{encode, GitLabClient};
+
+/// Settings GitLab protected branches can enforce
+pub const SUPPORTED_SETTINGS: [ProtectionSetting; 3] = [
+    ProtectionSetting::RequireCodeOwnerReviews,
+    ProtectionSetting::AllowForcePushes,
+    ProtectionSetting::AllowDeletions,
+];
+
+#[derive(Debug, Deserialize)]
+struct ProtectedBranch {
+    #[serde(default)]
+    allow_force_push: bool,
+    #[serde(default)]
+    code_owner_approval_required: bool,
+}
+
+impl From<ProtectedBranch> for BranchProtection {
+    fn from(branch: ProtectedBranch) -> Self {
+        Self {
+            require_code_owner_reviews: branch.code_owner_approval_required,
+            allow_force_pushes: branch.allow_force_push,
+            // Protected branches can't be deleted
+            allow_deletions: false,
+            ..Self::default()
+        }
+    }
+}
+
+impl GitLabClient {
+    /// Current protection of a branch, or `None` if it isn't protected
+    pub fn get_branch_protection(&self, branch: &str) -> Result<Option<BranchProtection>> {
+        let output = self.api(
+            "GET",
+            &self.project_endpoint(&format!("protected_branches/{}", encode(branch))),
+            None,
+        )?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("404") {
+                return Ok(None);
+            }
+            anyhow::bail!("Failed to get protected branch: {}", stderr);
+        }
+
+        let branch: ProtectedBranch = serde_json::from_slice(&output.stdout)?;
+        Ok(Some(branch.into()))
+    }
+
+    /// Protect a branch with the supported settings of `protection`
+    ///
+    /// Allowing deletion unprotects the branch, since GitLab never lets
+    /// protected branches be deleted.
+    pub fn set_branch_protection(&self, branch: &str, protection: &BranchProtection) -> Result<()> {
+        let existing = self.get_branch_protection(branch)?;
+        let endpoint = self.project_endpoint(&format!("protected_branches/{}", encode(branch)));
+
+        if protection.allow_deletions {
+            if existing.is_some() {
+                let output = self.api("DELETE", &endpoint, None)?;
+                if !output.status.success() {
+                    anyhow::bail!(
+                        "Failed to unprotect branch: {}",
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                }
+            }
+            return Ok(());
+        }
+
+        let _: serde_json::Value = match existing {
+            Some(_) => self.api_json(
+                "PATCH",
+                &endpoint,
+                Some(&json!({
+                    "allow_force_push": protection.allow_force_pushes,
+                    "code_owner_approval_required": protection.require_code_owner_reviews,
+                })),
+                "update protected branch",
+            )?,
+            None => self.api_json(
+                "POST",
+                &self.project_endpoint("protected_branches"),
+                Some(&json!({
+                    "name": branch,
+                    "allow_force_push": protection.allow_force_pushes,
+                    "code_owner_approval_required": protection.require_code_owner_reviews,
+                })),
+                "protect branch",
+            )?,
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_protected_branch() {
+        let branch: ProtectedBranch = serde_json::from_value(json!({
+            "name": "main",
+            "allow_force_push": false,
+            "code_owner_approval_required": true,
+            "push_access_levels": []
+        }))
+        .unwrap();
+        let protection = BranchProtection::from(branch);
+        assert!(protection.require_code_owner_reviews);
+        assert!(!protection.allow_force_pushes);
+        assert!(!protection.allow_deletions);
+
+        let policy = BranchProtection {
+            require_code_owner_reviews: true,
+            ..BranchProtection::default()
+        };
+        assert!(policy
+            .drift(Some(&protection), &SUPPORTED_SETTINGS)
+            .is_empty());
+    }
+}
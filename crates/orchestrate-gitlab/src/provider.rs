@@ -0,0 +1,84 @@
+//! GitLab as a PR workflow provider
+
+use async_trait::async_trait;
+use orchestrate_core::{CiCheckResult, MergeMethod, OpenedPr, PrApprovals, PrProvider};
+
+use crate::client::GitLabClient;
+
+impl GitLabClient {
+    /// Run a `glab` call off the async runtime
+    async fn blocking<T, F>(&self, call: F) -> orchestrate_core::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&GitLabClient) -> anyhow::Result<T> + Send + 'static,
+    {
+        let client = self.clone();
+        tokio::task::spawn_blocking(move || call(&client))
+            .await
+            .map_err(|e| orchestrate_core::Error::Other(e.to_string()))?
+            .map_err(|e| orchestrate_core::Error::Other(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl PrProvider for GitLabClient {
+    fn name(&self) -> &str {
+        "gitlab"
+    }
+
+    async fn create_pr(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> orchestrate_core::Result<OpenedPr> {
+        let (head, base, title, body) = (
+            head.to_string(),
+            base.to_string(),
+            title.to_string(),
+            body.to_string(),
+        );
+        let mr = self
+            .blocking(move |client| client.create_mr(&head, &base, &title, &body))
+            .await?;
+        Ok(OpenedPr {
+            number: mr.iid as u64,
+            url: Some(mr.web_url),
+        })
+    }
+
+    /// GitLab approvals can't request changes, so only `approved` is set
+    async fn get_approvals(&self, number: u64) -> orchestrate_core::Result<PrApprovals> {
+        let approvals = self
+            .blocking(move |client| client.get_approvals(number as i64))
+            .await?;
+        Ok(PrApprovals {
+            approved: approvals.approved,
+            changes_requested: false,
+            approvers: approvals
+                .approvers()
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        })
+    }
+
+    async fn get_ci_checks(&self, number: u64) -> orchestrate_core::Result<Vec<CiCheckResult>> {
+        self.blocking(move |client| client.get_pipeline_checks(number as i64))
+            .await
+    }
+
+    async fn has_conflicts(&self, number: u64) -> orchestrate_core::Result<bool> {
+        let mr = self
+            .blocking(move |client| client.get_mr(number as i64))
+            .await?;
+        Ok(mr.has_conflicts)
+    }
+
+    async fn merge_pr(&self, number: u64, method: MergeMethod) -> orchestrate_core::Result<()> {
+        self.blocking(move |client| client.merge_mr(number as i64, method.as_str()))
+            .await?;
+        Ok(())
+    }
+}
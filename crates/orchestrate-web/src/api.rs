@@ -2,30 +2,47 @@
 
 use axum::{
     body::Body,
-    extract::{Path, Query, State},
-    http::{Request, StatusCode},
+    extract::{MatchedPath, Path, Query, State},
+    http::{header, HeaderMap, Request, StatusCode},
     middleware::{self, Next},
     response::{IntoResponse, Response},
-    routing::{get, post},
+    routing::{delete, get, post},
     Json, Router,
 };
 use orchestrate_core::{
-    Agent, AgentState, AgentType, ApprovalDecision, ApprovalRequest, ApprovalService,
-    ApprovalStatus, CustomInstruction, Database, Feedback, FeedbackRating, FeedbackSource,
-    FeedbackStats, InstructionEffectiveness, InstructionScope, InstructionSource, LearningEngine,
-    LearningPattern, PatternStatus, Pipeline, PipelineRun, PipelineRunStatus, PipelineStage,
-    Schedule, ScheduleRun,
+    parse_run_at, validate_labels, Agent, AgentState, AgentType, ApprovalDecision, ApprovalRequest,
+    ApprovalService, ApprovalStatus, Artifact, ArtifactKind, ArtifactStore, AuditAction, AuditEntry,
+    CustomInstruction, Database, Feedback, FeedbackRating, FeedbackSource, FeedbackStats, InstructionEffectiveness, InstructionScope,
+    InstructionSource, LearningEngine, LearningPattern, NetworkCoordinator, PatternStatus,
+    Pipeline, PipelineDefinition, PipelineExecutor, PipelineGraph, PipelineRun, PipelineRunStatus,
+    PipelineStage, RunAdmission, Saga, SagaCompensation, SagaWorkflowType, Schedule,
+    ScheduleOverlapPolicy, ScheduleRun, ScheduleStats, SkillDefinition, TemplateLibrary,
 };
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Instant;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::auth::{request_token, AuthConfig, Identity};
+use crate::pagination::{Page, PageParams};
+use crate::probes::{ProbeConfig, Probes};
+use crate::metrics::{MetricsCollector, OPENMETRICS_CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE};
+use crate::rate_limit::{rate_limit_middleware, RateLimitConfig, RateLimiter};
+use crate::rbac::{rbac_middleware, RbacPolicy};
+use crate::security::{security_headers_middleware, CorsConfig};
+use crate::versioning::Deprecation;
+
 /// Maximum task length
-const MAX_TASK_LENGTH: usize = 10_000;
+pub(crate) const MAX_TASK_LENGTH: usize = 10_000;
+
+/// Maximum system prompt override length
+const MAX_SYSTEM_PROMPT_LENGTH: usize = 50_000;
 
 /// API error response
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ApiError {
     pub error: String,
     pub code: String,
@@ -39,6 +56,7 @@ impl IntoResponse for ApiError {
             "not_found" => StatusCode::NOT_FOUND,
             "bad_request" | "validation_error" => StatusCode::BAD_REQUEST,
             "conflict" => StatusCode::CONFLICT,
+            "rate_limited" => StatusCode::TOO_MANY_REQUESTS,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         };
         (status, Json(self)).into_response()
@@ -46,9 +64,9 @@ impl IntoResponse for ApiError {
 }
 
 impl ApiError {
-    fn unauthorized() -> Self {
+    pub(crate) fn unauthorized() -> Self {
         Self {
-            error: "Invalid or missing API key".to_string(),
+            error: "Invalid or missing API key or session token".to_string(),
             code: "unauthorized".to_string(),
         }
     }
@@ -87,6 +105,20 @@ impl ApiError {
             code: "conflict".to_string(),
         }
     }
+
+    pub fn forbidden(msg: impl Into<String>) -> Self {
+        Self {
+            error: msg.into(),
+            code: "forbidden".to_string(),
+        }
+    }
+
+    pub fn rate_limited(msg: impl Into<String>) -> Self {
+        Self {
+            error: msg.into(),
+            code: "rate_limited".to_string(),
+        }
+    }
 }
 
 /// Application state
@@ -94,6 +126,22 @@ impl ApiError {
 pub struct AppState {
     pub db: Database,
     pub api_key: Option<SecretString>,
+    /// Agent network, reconfigurable at runtime
+    pub network: Arc<NetworkCoordinator>,
+    /// Prometheus metrics served on `/metrics`
+    pub metrics: Arc<MetricsCollector>,
+    /// Object storage for reports and agent artifacts, if configured
+    pub artifacts: Option<ArtifactStore>,
+    /// OIDC login and session tokens, if configured
+    pub auth: Option<Arc<AuthConfig>>,
+    /// Roles of signed-in users
+    pub rbac: Arc<RbacPolicy>,
+    /// Request rate limits of API clients, if configured
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+    /// Origins allowed to call the API from a browser, if any
+    pub cors: Option<CorsConfig>,
+    /// Dependencies checked by `/healthz` and `/readyz`
+    pub probes: Arc<Probes>,
 }
 
 impl AppState {
@@ -102,31 +150,118 @@ impl AppState {
         Self {
             db,
             api_key: api_key.map(SecretString::new),
+            network: Arc::new(NetworkCoordinator::with_defaults()),
+            metrics: Arc::new(MetricsCollector::default()),
+            artifacts: None,
+            auth: None,
+            rbac: Arc::new(RbacPolicy::default()),
+            rate_limiter: None,
+            cors: None,
+            probes: Arc::new(Probes::new(ProbeConfig::default())),
         }
     }
+
+    /// Share a metrics collector, e.g. with an exporter
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Keep reports and artifacts in object storage, linked by signed URLs
+    pub fn with_artifacts(mut self, artifacts: ArtifactStore) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+
+    /// Accept session tokens issued by OIDC login
+    pub fn with_auth(mut self, auth: AuthConfig) -> Self {
+        self.auth = Some(Arc::new(auth));
+        self
+    }
+
+    /// Assign roles to signed-in users
+    pub fn with_rbac(mut self, rbac: RbacPolicy) -> Self {
+        self.rbac = Arc::new(rbac);
+        self
+    }
+
+    /// Limit the request rate of API clients
+    pub fn with_rate_limit(mut self, config: RateLimitConfig) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(config)));
+        self
+    }
+
+    /// Allow cross-origin requests, e.g. from a separately hosted UI
+    pub fn with_cors(mut self, cors: CorsConfig) -> Self {
+        self.cors = Some(cors);
+        self
+    }
+
+    /// Change what liveness and readiness probes check
+    pub fn with_probes(mut self, config: ProbeConfig) -> Self {
+        self.probes = Arc::new(Probes::new(config));
+        self
+    }
+}
+
+/// Record request count, latency, and in-flight requests by route
+async fn http_metrics_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    // Label by route pattern rather than raw path to bound cardinality
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = request.method().to_string();
+    let started = Instant::now();
+
+    let in_flight = state.metrics.track_in_flight(&method, &path);
+    let response = next.run(request).await;
+    drop(in_flight);
+
+    state.metrics.record_http_request(
+        &method,
+        &path,
+        response.status().as_u16(),
+        started.elapsed().as_secs_f64(),
+    );
+    response
 }
 
 /// Authentication middleware
+///
+/// Accepts a session token from OIDC login, attaching its [`Identity`] to
+/// the request, or the shared API key.
 async fn auth_middleware(
     State(state): State<Arc<AppState>>,
-    request: Request<Body>,
+    mut request: Request<Body>,
     next: Next,
 ) -> Result<Response, ApiError> {
-    // If no API key configured, allow all requests
-    let Some(ref expected_key) = state.api_key else {
+    // Without an API key or login configured, allow all requests
+    if state.api_key.is_none() && state.auth.is_none() {
         return Ok(next.run(request).await);
-    };
+    }
 
-    // Check for API key in headers
-    let headers = request.headers();
-    let provided_key = headers
-        .get("x-api-key")
-        .or_else(|| headers.get("authorization"))
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.strip_prefix("Bearer ").unwrap_or(s));
+    let provided = request_token(request.headers());
+
+    let identity = state
+        .auth
+        .as_ref()
+        .zip(provided)
+        .and_then(|(auth, token)| auth.verify_token(token));
+    if let Some(identity) = identity {
+        request.extensions_mut().insert(identity);
+        return Ok(next.run(request).await);
+    }
 
-    match provided_key {
-        Some(key) if key == expected_key.expose_secret() => Ok(next.run(request).await),
+    match (&state.api_key, provided) {
+        (Some(expected_key), Some(key)) if key == expected_key.expose_secret() => {
+            Ok(next.run(request).await)
+        }
         _ => Err(ApiError::unauthorized()),
     }
 }
@@ -138,12 +273,78 @@ pub fn create_api_router(state: Arc<AppState>) -> Router {
     let protected_routes = Router::new()
         // Agent routes
         .route("/api/agents", get(list_agents).post(create_agent))
+        .route("/api/agents/bulk/spawn", post(crate::bulk::bulk_spawn))
+        .route("/api/agents/bulk/terminate", post(crate::bulk::bulk_terminate))
         .route("/api/agents/:id", get(get_agent))
         .route("/api/agents/:id/pause", post(pause_agent))
         .route("/api/agents/:id/resume", post(resume_agent))
         .route("/api/agents/:id/terminate", post(terminate_agent))
         .route("/api/agents/:id/messages", get(get_messages))
+        .route("/api/agents/:id/events", get(get_agent_events))
+        .route("/api/agents/:id/message", post(crate::chat::send_message))
+        .route("/api/agents/:id/story", post(crate::chat::create_story))
+        .route("/api/agents/:id/pr", post(crate::chat::create_pr))
+        .route("/api/agent-types", get(crate::chat::list_agent_types))
         .route("/api/status", get(system_status))
+        // Event feed
+        .route("/api/events", get(crate::events::stream_events))
+        // Cost dashboard
+        .route("/api/costs/daily", get(crate::costs::get_daily_costs))
+        .route("/api/costs/breakdown", get(crate::costs::get_cost_breakdown))
+        .route(
+            "/api/costs/budget",
+            get(crate::costs::get_budget).put(crate::costs::set_budget),
+        )
+        // Data exports
+        .route("/api/exports/:dataset", get(crate::exports::download_export))
+        .route(
+            "/api/export-jobs",
+            get(crate::exports::list_export_jobs).post(crate::exports::create_export_job),
+        )
+        .route("/api/export-jobs/:id", get(crate::exports::get_export_job))
+        // Epic and story board
+        .route("/api/board", get(crate::board::get_board))
+        .route(
+            "/api/epics/:id/transition",
+            post(crate::board::transition_epic),
+        )
+        .route(
+            "/api/stories/:id/transition",
+            post(crate::board::transition_story),
+        )
+        .route("/api/stories/:id/assign", post(crate::board::assign_story))
+        // Incidents
+        .route("/api/incidents", get(crate::incidents::list_incidents))
+        .route("/api/incidents/:id", get(crate::incidents::get_incident))
+        .route(
+            "/api/incidents/:id/timeline",
+            post(crate::incidents::add_timeline_comment),
+        )
+        .route(
+            "/api/incidents/:id/postmortem",
+            post(crate::incidents::generate_post_mortem),
+        )
+        // Webhook event administration
+        .route(
+            "/api/webhooks/events",
+            get(crate::webhook_events::list_webhook_events),
+        )
+        .route(
+            "/api/webhooks/events/:id",
+            get(crate::webhook_events::get_webhook_event),
+        )
+        .route(
+            "/api/webhooks/events/:id/retry",
+            post(crate::webhook_events::retry_webhook_event),
+        )
+        .route(
+            "/api/webhooks/dead-letters",
+            delete(crate::webhook_events::purge_dead_letters),
+        )
+        .route(
+            "/api/webhooks/stats",
+            get(crate::webhook_events::webhook_stats),
+        )
         // Instruction routes
         .route(
             "/api/instructions",
@@ -179,15 +380,25 @@ pub fn create_api_router(state: Arc<AppState>) -> Router {
                 .put(update_pipeline)
                 .delete(delete_pipeline),
         )
+        .route("/api/pipelines/:name/graph", get(get_pipeline_graph))
         .route("/api/pipelines/:name/run", post(trigger_pipeline_run))
         .route("/api/pipelines/:name/runs", get(list_pipeline_runs))
+        .route(
+            "/api/pipeline-runs/bulk/retry",
+            post(crate::bulk::bulk_retry_runs),
+        )
         .route("/api/pipeline-runs/:id", get(get_pipeline_run))
         .route("/api/pipeline-runs/:id/cancel", post(cancel_pipeline_run))
         .route("/api/pipeline-runs/:id/stages", get(list_pipeline_stages))
+        .route("/api/pipeline-runs/:id/saga", get(get_pipeline_run_saga))
         // Approval routes
-        .route("/api/approvals", get(list_pending_approvals))
+        .route("/api/approvals", get(crate::approvals::list_approval_inbox))
         .route("/api/approvals/:id/approve", post(approve_approval))
         .route("/api/approvals/:id/reject", post(reject_approval))
+        .route(
+            "/api/approvals/:id/delegate",
+            post(crate::approvals::delegate_approval),
+        )
         // Schedule routes
         .route("/api/schedules", get(list_schedules).post(create_schedule))
         .route(
@@ -198,6 +409,7 @@ pub fn create_api_router(state: Arc<AppState>) -> Router {
         .route("/api/schedules/:id/resume", post(resume_schedule))
         .route("/api/schedules/:id/run", post(run_schedule))
         .route("/api/schedules/:id/runs", get(get_schedule_runs))
+        .route("/api/schedules/:id/stats", get(get_schedule_stats))
         // Feedback routes
         .route("/api/feedback", get(list_feedback).post(create_feedback))
         .route("/api/feedback/:id", get(get_feedback).delete(delete_feedback))
@@ -210,6 +422,9 @@ pub fn create_api_router(state: Arc<AppState>) -> Router {
         .route("/api/experiments", get(list_experiments).post(create_experiment))
         .route("/api/experiments/:id", get(get_experiment))
         .route("/api/experiments/:id/results", get(get_experiment_results))
+        .route("/api/experiments/:id/daily", get(get_experiment_daily_results))
+        .route("/api/experiments/:id/start", post(start_experiment))
+        .route("/api/experiments/:id/pause", post(pause_experiment))
         .route("/api/experiments/:id/promote", post(promote_experiment))
         // Prediction routes
         .route("/api/predictions", post(get_prediction))
@@ -228,6 +443,31 @@ pub fn create_api_router(state: Arc<AppState>) -> Router {
         .route("/api/security/report", get(download_security_report))
         .route("/api/security/policy", get(get_security_policy))
         .route("/api/security/gate/evaluate", post(evaluate_security_gate))
+        // Artifact routes
+        .route("/api/artifacts", get(list_artifacts))
+        .route(
+            "/api/artifacts/:id",
+            get(get_artifact).delete(delete_artifact),
+        )
+        // Agent network routes
+        .route("/api/network/skills", get(list_skills).post(register_skill))
+        .route(
+            "/api/network/skills/:name",
+            get(get_skill).delete(unregister_skill),
+        );
+
+    let protected_routes =
+        protected_routes.merge(crate::autonomous_api::create_autonomous_router());
+
+    #[cfg(feature = "graphql")]
+    let protected_routes = protected_routes.merge(crate::graphql::create_graphql_router());
+
+    let protected_routes = protected_routes
+        // Layers run bottom-up: authenticate, then authorize
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            rbac_middleware,
+        ))
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
             auth_middleware,
@@ -235,6 +475,8 @@ pub fn create_api_router(state: Arc<AppState>) -> Router {
 
     Router::new()
         .merge(protected_routes)
+        // Signed URLs of locally stored artifacts carry their own authorization
+        .route("/artifacts/*key", get(serve_artifact))
         .with_state(state)
 }
 
@@ -245,19 +487,39 @@ pub fn create_router(state: Arc<AppState>) -> Router {
 
 /// Create the full router with both API, UI routes, and optional webhook endpoint
 pub fn create_router_with_webhook(state: Arc<AppState>, webhook_secret: Option<String>) -> Router {
+    crate::versioning::with_versioned_api(
+        create_unversioned_router(state, webhook_secret),
+        Deprecation::unversioned_from_env(),
+    )
+}
+
+/// The router of [`create_router_with_webhook`], without the `/api/v1`
+/// paths of its `/api` routes
+pub(crate) fn create_unversioned_router(
+    state: Arc<AppState>,
+    webhook_secret: Option<String>,
+) -> Router {
     let api_router = create_api_router(state.clone());
-    let autonomous_router = crate::autonomous_api::create_autonomous_router().with_state(state.clone());
     let ui_router = crate::ui::create_ui_router().with_state(state.clone());
     let monitoring_router = crate::monitoring::create_monitoring_router().with_state(state.clone());
+    let openapi_router = crate::openapi::create_openapi_router().with_state(state.clone());
+    let auth_router = crate::auth::create_auth_router().with_state(state.clone());
 
     // Create WebSocket state with the same database
     let ws_state = Arc::new(crate::websocket::WsState::new(state.db.clone()));
 
     let mut router = Router::new()
         .merge(api_router)
-        .merge(autonomous_router)
         .merge(monitoring_router)
+        .merge(openapi_router)
+        .merge(auth_router)
         .merge(ui_router)
+        // Routes added below, the WebSocket and webhook receivers, aren't
+        // rate limited
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_middleware,
+        ))
         .route(
             "/ws",
             axum::routing::get(crate::websocket::ws_handler).with_state(ws_state),
@@ -273,11 +535,146 @@ pub fn create_router_with_webhook(state: Arc<AppState>, webhook_secret: Option<S
         post(crate::webhook::github_webhook_handler).with_state(webhook_state),
     );
 
-    router
+    // GitLab sends a secret token instead of signing the payload
+    let gitlab_token = std::env::var("GITLAB_WEBHOOK_TOKEN").ok();
+    let gitlab_config = crate::webhook::WebhookConfig::new(gitlab_token);
+    let gitlab_state = Arc::new(crate::webhook::WebhookState::new(
+        gitlab_config,
+        state.db.clone(),
+    ));
+
+    router = router.route(
+        "/webhooks/gitlab",
+        post(crate::gitlab_webhook::gitlab_webhook_handler).with_state(gitlab_state),
+    );
+
+    // Custom webhooks verify the token from their own definition
+    let custom_config = crate::webhook::WebhookConfig::new(None);
+    let custom_state = Arc::new(crate::webhook::WebhookState::new(
+        custom_config,
+        state.db.clone(),
+    ));
+
+    router = router.route(
+        "/webhooks/custom/:name",
+        post(crate::custom_webhook::custom_webhook_handler).with_state(custom_state),
+    );
+
+    // Slack signs slash command and interaction requests with the app's
+    // signing secret, which takes the place of the API key
+    let slack_secret = std::env::var("SLACK_SIGNING_SECRET").ok();
+    let slack_state = Arc::new(crate::webhook::WebhookState::new(
+        crate::webhook::WebhookConfig::new(slack_secret),
+        state.db.clone(),
+    ));
+
+    router = router.route(
+        "/api/slack/commands",
+        post(crate::slack_commands::slack_command_handler).with_state(slack_state.clone()),
+    );
+    router = router.route(
+        "/api/slack/interactions",
+        post(crate::slack_interactions::slack_interaction_handler).with_state(slack_state),
+    );
+
+    // Telegram sends the secret token the bot's webhook was registered with
+    let telegram_secret = std::env::var("TELEGRAM_WEBHOOK_SECRET").ok();
+    let telegram_state = Arc::new(crate::webhook::WebhookState::new(
+        crate::webhook::WebhookConfig::new(telegram_secret),
+        state.db.clone(),
+    ));
+
+    router = router.route(
+        "/api/telegram/webhook",
+        post(crate::telegram_webhook::telegram_webhook_handler).with_state(telegram_state),
+    );
+
+    // PagerDuty signs webhook deliveries with the subscription's secret
+    let pagerduty_secret = std::env::var("PAGERDUTY_WEBHOOK_SECRET").ok();
+    let pagerduty_state = Arc::new(crate::webhook::WebhookState::new(
+        crate::webhook::WebhookConfig::new(pagerduty_secret),
+        state.db.clone(),
+    ));
+
+    router = router.route(
+        "/api/pagerduty/webhook",
+        post(crate::pagerduty_webhooks::pagerduty_webhook_handler).with_state(pagerduty_state),
+    );
+
+    // Linear signs webhook deliveries with the webhook's signing secret
+    let linear_secret = std::env::var("LINEAR_WEBHOOK_SECRET").ok();
+    let linear_state = Arc::new(crate::webhook::WebhookState::new(
+        crate::webhook::WebhookConfig::new(linear_secret),
+        state.db.clone(),
+    ));
+
+    router = router.route(
+        "/api/linear/webhook",
+        post(crate::linear_webhooks::linear_webhook_handler).with_state(linear_state),
+    );
+
+    // Prometheus scrapes without an API key
+    router = router.route("/metrics", get(metrics_handler).with_state(state.clone()));
+
+    // Kubernetes probes without an API key
+    router = router
+        .route("/healthz", get(crate::probes::liveness).with_state(state.clone()))
+        .route("/readyz", get(crate::probes::readiness).with_state(state.clone()));
+
+    let router = router
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            http_metrics_middleware,
+        ))
+        .layer(middleware::from_fn(security_headers_middleware));
+
+    // Outermost, so preflight requests are answered without credentials
+    match &state.cors {
+        Some(cors) => router.layer(cors.layer()),
+        None => router,
+    }
 }
 
 // ==================== Handlers ====================
 
+/// GET /metrics - Prometheus metrics, in OpenMetrics format with exemplars when accepted
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "monitoring",
+    summary = "Prometheus metrics",
+    responses(
+        (status = 200, description = "Prometheus metrics", content(
+            (String = "text/plain"),
+            (String = "application/openmetrics-text")
+        ))
+    )
+)]
+async fn metrics_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let openmetrics = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("application/openmetrics-text"));
+
+    let (body, content_type) = if openmetrics {
+        (
+            state.metrics.gather_openmetrics(&state.db).await,
+            OPENMETRICS_CONTENT_TYPE,
+        )
+    } else {
+        (
+            state.metrics.gather(&state.db).await,
+            PROMETHEUS_CONTENT_TYPE,
+        )
+    };
+    let body = body.map_err(|e| ApiError::internal(format!("Failed to gather metrics: {}", e)))?;
+
+    Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
+}
+
 async fn health_check() -> Json<HealthResponse> {
     Json(HealthResponse {
         status: "ok".to_string(),
@@ -285,18 +682,35 @@ async fn health_check() -> Json<HealthResponse> {
     })
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/agents",
+    tag = "agents",
+    summary = "List agents",
+    params(PageParams),
+    responses((status = 200, body = Page<AgentResponse>))
+)]
 async fn list_agents(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<AgentResponse>>, ApiError> {
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<AgentResponse>>, ApiError> {
     let agents = state
         .db
-        .list_agents()
+        .list_agents_page(&page.query()?)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(agents.into_iter().map(Into::into).collect()))
+    Ok(Json(agents.map(Into::into).into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/agents/{id}",
+    tag = "agents",
+    summary = "Get an agent",
+    params(("id" = String, Path)),
+    responses((status = 200, body = AgentResponse))
+)]
 async fn get_agent(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -313,29 +727,76 @@ async fn get_agent(
     Ok(Json(agent.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/agents",
+    tag = "agents",
+    summary = "Create an agent",
+    request_body = CreateAgentRequest,
+    responses((status = 200, body = AgentResponse))
+)]
 async fn create_agent(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateAgentRequest>,
 ) -> Result<Json<AgentResponse>, ApiError> {
+    let agent = spawn_agent(&state, req).await?;
+    Ok(Json(agent.into()))
+}
+
+/// Validate a create request and record the new agent
+pub(crate) async fn spawn_agent(
+    state: &AppState,
+    req: CreateAgentRequest,
+) -> Result<Agent, ApiError> {
     // Validate request
     req.validate()?;
 
-    let mut agent = Agent::new(req.agent_type, req.task);
+    let mut agent = match req.custom_type {
+        Some(ref name) => {
+            let def = state
+                .db
+                .get_agent_type_definition(name)
+                .await
+                .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+                .ok_or_else(|| ApiError::bad_request(format!("Unknown custom agent type: {}", name)))?;
+            Agent::new(def.base_type, req.task).with_custom_type(def.name)
+        }
+        None => Agent::new(req.agent_type, req.task),
+    };
 
     // Set worktree if provided
     if let Some(worktree_id) = req.worktree_id {
         agent = agent.with_worktree(worktree_id);
     }
 
+    if let Some(prompt) = req.system_prompt {
+        agent = agent.with_system_prompt(prompt);
+    }
+    agent.labels = req.labels;
+
     state
         .db
         .insert_agent(&agent)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(agent.into()))
+    state
+        .db
+        .record_agent_prompt_override(&agent)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
+
+    Ok(agent)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/pause",
+    tag = "agents",
+    summary = "Pause an agent",
+    params(("id" = String, Path)),
+    responses((status = 200, body = AgentResponse))
+)]
 async fn pause_agent(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -369,6 +830,14 @@ async fn pause_agent(
     Ok(Json(agent.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/resume",
+    tag = "agents",
+    summary = "Resume an agent",
+    params(("id" = String, Path)),
+    responses((status = 200, body = AgentResponse))
+)]
 async fn resume_agent(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -401,19 +870,33 @@ async fn resume_agent(
     Ok(Json(agent.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/terminate",
+    tag = "agents",
+    summary = "Terminate an agent",
+    params(("id" = String, Path)),
+    responses((status = 200, body = AgentResponse))
+)]
 async fn terminate_agent(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
 ) -> Result<Json<AgentResponse>, ApiError> {
     let uuid = Uuid::parse_str(&id).map_err(|_| ApiError::bad_request("Invalid UUID format"))?;
 
-    let mut agent = state
+    let agent = state
         .db
         .get_agent(uuid)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
         .ok_or_else(|| ApiError::not_found("Agent"))?;
 
+    let agent = terminate(&state, agent).await?;
+    Ok(Json(agent.into()))
+}
+
+/// Terminate a loaded agent unless another request modified it since
+pub(crate) async fn terminate(state: &AppState, mut agent: Agent) -> Result<Agent, ApiError> {
     let original_updated_at = agent.updated_at.to_rfc3339();
 
     agent.transition_to(AgentState::Terminated).map_err(|_| {
@@ -430,13 +913,25 @@ async fn terminate_agent(
         return Err(ApiError::conflict("Agent was modified by another request"));
     }
 
-    Ok(Json(agent.into()))
+    Ok(agent)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/agents/{id}/messages",
+    tag = "agents",
+    summary = "List an agent's messages",
+    params(
+        ("id" = String, Path),
+        PageParams
+    ),
+    responses((status = 200, body = Page<MessageResponse>))
+)]
 async fn get_messages(
     State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
     Path(id): Path<String>,
-) -> Result<Json<Vec<MessageResponse>>, ApiError> {
+) -> Result<Json<Page<MessageResponse>>, ApiError> {
     let uuid = Uuid::parse_str(&id).map_err(|_| ApiError::bad_request("Invalid UUID format"))?;
 
     // Verify agent exists
@@ -449,13 +944,64 @@ async fn get_messages(
 
     let messages = state
         .db
-        .get_messages(uuid)
+        .get_messages_page(uuid, &page.query()?)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
+
+    Ok(Json(messages.map(Into::into).into()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/agents/{id}/events",
+    tag = "agents",
+    summary = "List an agent's events",
+    params(
+        ("id" = String, Path),
+        PageParams,
+        AgentEventsParams
+    ),
+    responses((status = 200, body = Page<AgentEventResponse>))
+)]
+async fn get_agent_events(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
+    Path(id): Path<String>,
+    Query(params): Query<AgentEventsParams>,
+) -> Result<Json<Page<AgentEventResponse>>, ApiError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| ApiError::bad_request("Invalid UUID format"))?;
+
+    let event_type = params
+        .event_type
+        .as_deref()
+        .map(|s| s.parse::<orchestrate_core::AgentEventType>())
+        .transpose()
+        .map_err(|e| ApiError::bad_request(format!("Invalid event_type: {}", e)))?;
+
+    // Verify agent exists
+    let _ = state
+        .db
+        .get_agent(uuid)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Agent"))?;
+
+    let events = state
+        .db
+        .get_agent_events_page(&uuid.to_string(), event_type, &page.query()?)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(messages.into_iter().map(Into::into).collect()))
+    Ok(Json(events.map(Into::into).into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/status",
+    tag = "system",
+    summary = "System status",
+    responses((status = 200, body = SystemStatus))
+)]
 async fn system_status(State(state): State<Arc<AppState>>) -> Result<Json<SystemStatus>, ApiError> {
     let agents = state
         .db
@@ -486,10 +1032,22 @@ async fn system_status(State(state): State<Arc<AppState>>) -> Result<Json<System
 
 // ==================== Instruction Handlers ====================
 
+#[utoipa::path(
+    get,
+    path = "/api/instructions",
+    tag = "instructions",
+    summary = "List instructions",
+    params(
+        PageParams,
+        ListInstructionsParams
+    ),
+    responses((status = 200, body = Page<InstructionResponse>))
+)]
 async fn list_instructions(
     State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
     Query(params): Query<ListInstructionsParams>,
-) -> Result<Json<Vec<InstructionResponse>>, ApiError> {
+) -> Result<Json<Page<InstructionResponse>>, ApiError> {
     let scope = params
         .scope
         .as_deref()
@@ -506,13 +1064,26 @@ async fn list_instructions(
 
     let instructions = state
         .db
-        .list_instructions(params.enabled_only.unwrap_or(false), scope, source)
+        .list_instructions_page(
+            params.enabled_only.unwrap_or(false),
+            scope,
+            source,
+            &page.query()?,
+        )
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(instructions.into_iter().map(Into::into).collect()))
+    Ok(Json(instructions.map(Into::into).into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/instructions/{id}",
+    tag = "instructions",
+    summary = "Get an instruction",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = InstructionResponse))
+)]
 async fn get_instruction(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -527,6 +1098,14 @@ async fn get_instruction(
     Ok(Json(instruction.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/instructions",
+    tag = "instructions",
+    summary = "Create an instruction",
+    request_body = CreateInstructionRequest,
+    responses((status = 200, body = InstructionResponse))
+)]
 async fn create_instruction(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateInstructionRequest>,
@@ -565,6 +1144,15 @@ async fn create_instruction(
     Ok(Json(instruction.into()))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/instructions/{id}",
+    tag = "instructions",
+    summary = "Update an instruction",
+    params(("id" = i64, Path)),
+    request_body = UpdateInstructionRequest,
+    responses((status = 200, body = InstructionResponse))
+)]
 async fn update_instruction(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -602,6 +1190,14 @@ async fn update_instruction(
     Ok(Json(instruction.into()))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/instructions/{id}",
+    tag = "instructions",
+    summary = "Delete an instruction",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
 async fn delete_instruction(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -623,6 +1219,14 @@ async fn delete_instruction(
     Ok(Json(serde_json::json!({"deleted": true})))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/instructions/{id}/enable",
+    tag = "instructions",
+    summary = "Enable an instruction",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = InstructionResponse))
+)]
 async fn enable_instruction(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -644,6 +1248,14 @@ async fn enable_instruction(
     Ok(Json(instruction.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/instructions/{id}/disable",
+    tag = "instructions",
+    summary = "Disable an instruction",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = InstructionResponse))
+)]
 async fn disable_instruction(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -665,6 +1277,14 @@ async fn disable_instruction(
     Ok(Json(instruction.into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/instructions/{id}/effectiveness",
+    tag = "instructions",
+    summary = "Get an instruction's effectiveness",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = EffectivenessResponse))
+)]
 async fn get_instruction_effectiveness(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -689,10 +1309,22 @@ async fn get_instruction_effectiveness(
 
 // ==================== Pattern Handlers ====================
 
+#[utoipa::path(
+    get,
+    path = "/api/patterns",
+    tag = "learning",
+    summary = "List learning patterns",
+    params(
+        PageParams,
+        ListPatternsParams
+    ),
+    responses((status = 200, body = Page<PatternResponse>))
+)]
 async fn list_patterns(
     State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
     Query(params): Query<ListPatternsParams>,
-) -> Result<Json<Vec<PatternResponse>>, ApiError> {
+) -> Result<Json<Page<PatternResponse>>, ApiError> {
     let status = params
         .status
         .as_deref()
@@ -702,13 +1334,21 @@ async fn list_patterns(
 
     let patterns = state
         .db
-        .list_patterns(status)
+        .list_patterns_page(status, &page.query()?)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(patterns.into_iter().map(Into::into).collect()))
+    Ok(Json(patterns.map(Into::into).into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/patterns/{id}",
+    tag = "learning",
+    summary = "Get a learning pattern",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = PatternResponse))
+)]
 async fn get_pattern(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -723,6 +1363,14 @@ async fn get_pattern(
     Ok(Json(pattern.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/patterns/{id}/approve",
+    tag = "learning",
+    summary = "Approve a learning pattern",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = PatternResponse))
+)]
 async fn approve_pattern(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -763,6 +1411,14 @@ async fn approve_pattern(
     Ok(Json(updated_pattern.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/patterns/{id}/reject",
+    tag = "learning",
+    summary = "Reject a learning pattern",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = PatternResponse))
+)]
 async fn reject_pattern(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -790,6 +1446,13 @@ async fn reject_pattern(
     Ok(Json(updated_pattern.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/learning/process",
+    tag = "learning",
+    summary = "Process learning patterns",
+    responses((status = 200, body = ProcessPatternsResponse))
+)]
 async fn process_patterns(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<ProcessPatternsResponse>, ApiError> {
@@ -806,6 +1469,13 @@ async fn process_patterns(
     }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/learning/cleanup",
+    tag = "learning",
+    summary = "Clean up ineffective instructions",
+    responses((status = 200, body = CleanupResponse))
+)]
 async fn cleanup_instructions(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<CleanupResponse>, ApiError> {
@@ -824,18 +1494,35 @@ async fn cleanup_instructions(
 
 // ==================== Pipeline Handlers ====================
 
+#[utoipa::path(
+    get,
+    path = "/api/pipelines",
+    tag = "pipelines",
+    summary = "List pipelines",
+    params(PageParams),
+    responses((status = 200, body = Page<PipelineResponse>))
+)]
 async fn list_pipelines(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<PipelineResponse>>, ApiError> {
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<PipelineResponse>>, ApiError> {
     let pipelines = state
         .db
-        .list_pipelines()
+        .list_pipelines_page(&page.query()?)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(pipelines.into_iter().map(Into::into).collect()))
+    Ok(Json(pipelines.map(Into::into).into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/pipelines/{name}",
+    tag = "pipelines",
+    summary = "Get a pipeline",
+    params(("name" = String, Path)),
+    responses((status = 200, body = PipelineResponse))
+)]
 async fn get_pipeline(
     State(state): State<Arc<AppState>>,
     Path(name): Path<String>,
@@ -850,13 +1537,56 @@ async fn get_pipeline(
     Ok(Json(pipeline.into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/pipelines/{name}/graph",
+    tag = "pipelines",
+    summary = "Get a pipeline's stage graph",
+    params(("name" = String, Path)),
+    responses((status = 200, body = Object))
+)]
+async fn get_pipeline_graph(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<PipelineGraph>, ApiError> {
+    let pipeline = state
+        .db
+        .get_pipeline_by_name(&name)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Pipeline"))?;
+
+    let definition = PipelineDefinition::from_yaml_str(&pipeline.definition)
+        .map_err(|e| ApiError::bad_request(format!("Invalid pipeline definition: {}", e)))?;
+
+    Ok(Json(PipelineGraph::from_definition(&definition)))
+}
+
+/// Expand the `extends:` and `include:` templates of a pipeline definition
+async fn expand_pipeline_templates(db: &Database, definition: &str) -> Result<String, ApiError> {
+    TemplateLibrary::load(db)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .expand(definition)
+        .map_err(|e| ApiError::validation(e.to_string()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/pipelines",
+    tag = "pipelines",
+    summary = "Create a pipeline",
+    request_body = CreatePipelineRequest,
+    responses((status = 200, body = PipelineResponse))
+)]
 async fn create_pipeline(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreatePipelineRequest>,
 ) -> Result<Json<PipelineResponse>, ApiError> {
     req.validate()?;
 
-    let mut pipeline = Pipeline::new(req.name, req.definition);
+    let definition = expand_pipeline_templates(&state.db, &req.definition).await?;
+    let mut pipeline = Pipeline::new(req.name, definition);
     if let Some(enabled) = req.enabled {
         pipeline.enabled = enabled;
     }
@@ -871,6 +1601,15 @@ async fn create_pipeline(
     Ok(Json(pipeline.into()))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/pipelines/{name}",
+    tag = "pipelines",
+    summary = "Update a pipeline",
+    params(("name" = String, Path)),
+    request_body = UpdatePipelineRequest,
+    responses((status = 200, body = PipelineResponse))
+)]
 async fn update_pipeline(
     State(state): State<Arc<AppState>>,
     Path(name): Path<String>,
@@ -884,7 +1623,7 @@ async fn update_pipeline(
         .ok_or_else(|| ApiError::not_found("Pipeline"))?;
 
     if let Some(definition) = req.definition {
-        pipeline.definition = definition;
+        pipeline.definition = expand_pipeline_templates(&state.db, &definition).await?;
     }
     if let Some(enabled) = req.enabled {
         pipeline.enabled = enabled;
@@ -899,6 +1638,14 @@ async fn update_pipeline(
     Ok(Json(pipeline.into()))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/pipelines/{name}",
+    tag = "pipelines",
+    summary = "Delete a pipeline",
+    params(("name" = String, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
 async fn delete_pipeline(
     State(state): State<Arc<AppState>>,
     Path(name): Path<String>,
@@ -923,6 +1670,15 @@ async fn delete_pipeline(
     Ok(Json(serde_json::json!({"deleted": true})))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/pipelines/{name}/run",
+    tag = "pipelines",
+    summary = "Trigger a pipeline run",
+    params(("name" = String, Path)),
+    request_body = TriggerRunRequest,
+    responses((status = 200, body = PipelineRunResponse))
+)]
 async fn trigger_pipeline_run(
     State(state): State<Arc<AppState>>,
     Path(name): Path<String>,
@@ -939,21 +1695,54 @@ async fn trigger_pipeline_run(
         .id
         .ok_or_else(|| ApiError::internal("Pipeline missing ID"))?;
 
+    let concurrency = PipelineDefinition::from_yaml_str(&pipeline.definition)
+        .ok()
+        .and_then(|definition| definition.concurrency);
+
     let mut run = PipelineRun::new(pipeline_id, req.trigger_event);
-    let run_id = state
+    if let Some(commit_sha) = req.commit_sha {
+        run = run.with_commit_sha(commit_sha);
+    }
+    if req.no_cache {
+        run = run.without_cache();
+    }
+    let admission = state
         .db
-        .insert_pipeline_run(&run)
+        .admit_pipeline_run(&run, concurrency.as_ref())
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    run.id = Some(run_id);
+    if let RunAdmission::Rejected {
+        run_id,
+        in_progress,
+    } = admission
+    {
+        return Err(ApiError::conflict(format!(
+            "Pipeline '{}' is at its concurrency limit with {} run(s) in progress; run {} was rejected",
+            name, in_progress, run_id
+        )));
+    }
+
+    run.id = Some(admission.run_id());
     Ok(Json(run.into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/pipelines/{name}/runs",
+    tag = "pipelines",
+    summary = "List a pipeline's runs",
+    params(
+        ("name" = String, Path),
+        PageParams
+    ),
+    responses((status = 200, body = Page<PipelineRunResponse>))
+)]
 async fn list_pipeline_runs(
     State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
     Path(name): Path<String>,
-) -> Result<Json<Vec<PipelineRunResponse>>, ApiError> {
+) -> Result<Json<Page<PipelineRunResponse>>, ApiError> {
     let pipeline = state
         .db
         .get_pipeline_by_name(&name)
@@ -967,13 +1756,21 @@ async fn list_pipeline_runs(
 
     let runs = state
         .db
-        .list_pipeline_runs(pipeline_id)
+        .list_pipeline_runs_page(pipeline_id, &page.query()?)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(runs.into_iter().map(Into::into).collect()))
+    Ok(Json(runs.map(Into::into).into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/pipeline-runs/{id}",
+    tag = "pipelines",
+    summary = "Get a pipeline run",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = PipelineRunResponse))
+)]
 async fn get_pipeline_run(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -988,6 +1785,14 @@ async fn get_pipeline_run(
     Ok(Json(run.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/pipeline-runs/{id}/cancel",
+    tag = "pipelines",
+    summary = "Cancel a pipeline run",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = PipelineRunResponse))
+)]
 async fn cancel_pipeline_run(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -1019,99 +1824,210 @@ async fn cancel_pipeline_run(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/pipeline-runs/{id}/stages",
+    tag = "pipelines",
+    summary = "List a pipeline run's stages",
+    params(
+        ("id" = i64, Path),
+        PageParams
+    ),
+    responses((status = 200, body = Page<PipelineStageResponse>))
+)]
 async fn list_pipeline_stages(
     State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
     Path(id): Path<i64>,
-) -> Result<Json<Vec<PipelineStageResponse>>, ApiError> {
+) -> Result<Json<Page<PipelineStageResponse>>, ApiError> {
     let stages = state
         .db
-        .list_pipeline_stages(id)
+        .list_pipeline_stages_page(id, &page.query()?)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(stages.into_iter().map(|s| s.into()).collect()))
+    Ok(Json(stages.map(Into::into).into()))
 }
 
-// ==================== Approval Handlers ====================
-
-async fn list_pending_approvals(
+#[utoipa::path(
+    get,
+    path = "/api/pipeline-runs/{id}/saga",
+    tag = "pipelines",
+    summary = "Get a pipeline run's saga",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = SagaResponse))
+)]
+async fn get_pipeline_run_saga(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<ApprovalResponse>>, ApiError> {
-    let approvals = state
+    Path(id): Path<i64>,
+) -> Result<Json<SagaResponse>, ApiError> {
+    let saga = state
+        .db
+        .get_saga_for_workflow(SagaWorkflowType::PipelineRun, &id.to_string())
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Saga"))?;
+
+    let compensations = state
         .db
-        .list_pending_approvals()
+        .list_saga_compensations(saga.id)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(approvals.into_iter().map(Into::into).collect()))
+    Ok(Json(SagaResponse::new(saga, compensations)))
 }
 
+// ==================== Approval Handlers ====================
+
+/// Map an approval service error to the matching API error
+pub(crate) fn approval_error(e: orchestrate_core::Error) -> ApiError {
+    match e {
+        orchestrate_core::Error::Other(msg) if msg.contains("not found") => {
+            ApiError::not_found("Approval")
+        }
+        orchestrate_core::Error::Other(msg)
+            if msg.contains("not authorized") || msg.contains("not an authorized") =>
+        {
+            ApiError::bad_request(msg)
+        }
+        orchestrate_core::Error::Other(msg)
+            if msg.contains("already resolved") || msg.contains("already submitted") =>
+        {
+            ApiError::conflict(msg)
+        }
+        _ => ApiError::internal(format!("Approval error: {}", e)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/approvals/{id}/approve",
+    tag = "approvals",
+    summary = "Approve a request",
+    params(("id" = i64, Path)),
+    request_body = ApprovalDecisionRequest,
+    responses((status = 200, body = ApprovalResponse))
+)]
 async fn approve_approval(
     State(state): State<Arc<AppState>>,
+    identity: Option<Identity>,
     Path(id): Path<i64>,
     Json(req): Json<ApprovalDecisionRequest>,
 ) -> Result<Json<ApprovalResponse>, ApiError> {
-    req.validate()?;
+    let approver = req.approver_for(identity.as_ref())?;
 
     let approval_service = ApprovalService::new(state.db.clone());
 
     let approval = approval_service
-        .approve(id, req.approver.clone(), req.comment.clone())
+        .approve(id, approver.clone(), req.comment.clone())
         .await
-        .map_err(|e| match e {
-            orchestrate_core::Error::Other(msg) if msg.contains("not found") => {
-                ApiError::not_found("Approval")
-            }
-            orchestrate_core::Error::Other(msg)
-                if msg.contains("not authorized") || msg.contains("not an authorized") => {
-                ApiError::bad_request(msg)
-            }
-            orchestrate_core::Error::Other(msg) if msg.contains("already resolved") || msg.contains("already submitted") => {
-                ApiError::conflict(msg)
-            }
-            _ => ApiError::internal(format!("Approval error: {}", e)),
-        })?;
+        .map_err(approval_error)?;
+    record_approval_decision(
+        &state.db,
+        id,
+        &approver,
+        AuditAction::ApprovalGranted,
+        &approval,
+        req.comment.as_deref(),
+    )
+    .await;
+    spawn_resume_after_approval(&state.db, &approval);
 
     Ok(Json(approval.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/approvals/{id}/reject",
+    tag = "approvals",
+    summary = "Reject a request",
+    params(("id" = i64, Path)),
+    request_body = ApprovalDecisionRequest,
+    responses((status = 200, body = ApprovalResponse))
+)]
 async fn reject_approval(
     State(state): State<Arc<AppState>>,
+    identity: Option<Identity>,
     Path(id): Path<i64>,
     Json(req): Json<ApprovalDecisionRequest>,
 ) -> Result<Json<ApprovalResponse>, ApiError> {
-    req.validate()?;
+    let approver = req.approver_for(identity.as_ref())?;
 
     let approval_service = ApprovalService::new(state.db.clone());
 
     let approval = approval_service
-        .reject(id, req.approver.clone(), req.comment.clone())
+        .reject(id, approver.clone(), req.comment.clone())
         .await
-        .map_err(|e| match e {
-            orchestrate_core::Error::Other(msg) if msg.contains("not found") => {
-                ApiError::not_found("Approval")
-            }
-            orchestrate_core::Error::Other(msg)
-                if msg.contains("not authorized") || msg.contains("not an authorized") => {
-                ApiError::bad_request(msg)
-            }
-            orchestrate_core::Error::Other(msg) if msg.contains("already resolved") || msg.contains("already submitted") => {
-                ApiError::conflict(msg)
-            }
-            _ => ApiError::internal(format!("Approval error: {}", e)),
-        })?;
+        .map_err(approval_error)?;
+    record_approval_decision(
+        &state.db,
+        id,
+        &approver,
+        AuditAction::ApprovalDenied,
+        &approval,
+        req.comment.as_deref(),
+    )
+    .await;
+    spawn_resume_after_approval(&state.db, &approval);
 
     Ok(Json(approval.into()))
 }
 
+/// Record who decided an approval in the audit log
+async fn record_approval_decision(
+    db: &Database,
+    id: i64,
+    approver: &str,
+    action: AuditAction,
+    approval: &ApprovalRequest,
+    comment: Option<&str>,
+) {
+    let mut entry = AuditEntry::new(approver, action, "approval", id.to_string())
+        .with_detail("run_id", serde_json::json!(approval.run_id))
+        .with_detail("status", serde_json::json!(approval.status.as_str()));
+    if let Some(comment) = comment {
+        entry = entry.with_detail("comment", serde_json::json!(comment));
+    }
+    let _ = db.insert_audit_entry(&entry).await;
+}
+
+/// Resume the pipeline run behind a decided approval in the background
+pub(crate) fn spawn_resume_after_approval(db: &Database, approval: &ApprovalRequest) {
+    if !approval.status.is_terminal() {
+        return;
+    }
+
+    let executor = PipelineExecutor::new(Arc::new(db.clone())).with_notifications_from_env();
+    let approval = approval.clone();
+    tokio::spawn(async move {
+        if let Err(e) = executor.resume_after_approval(&approval).await {
+            tracing::warn!(
+                run_id = approval.run_id,
+                error = %e,
+                "Pipeline run did not succeed after approval decision"
+            );
+        }
+    });
+}
+
 // ==================== Request/Response Types ====================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct CreateAgentRequest {
+    #[schema(value_type = String)]
     pub agent_type: AgentType,
     pub task: String,
     #[serde(default)]
     pub worktree_id: Option<String>,
+    /// Spawn as a user-defined agent type (its base type replaces `agent_type`)
+    #[serde(default)]
+    pub custom_type: Option<String>,
+    /// System prompt overriding the agent type's template for this run
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Labels for selecting the agent later, e.g. to terminate a group
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
 }
 
 impl CreateAgentRequest {
@@ -1129,16 +2045,36 @@ impl CreateAgentRequest {
             )));
         }
 
+        if let Some(ref prompt) = self.system_prompt {
+            if prompt.trim().is_empty() {
+                return Err(ApiError::validation("System prompt cannot be empty"));
+            }
+            if prompt.len() > MAX_SYSTEM_PROMPT_LENGTH {
+                return Err(ApiError::validation(format!(
+                    "System prompt exceeds maximum length of {} characters",
+                    MAX_SYSTEM_PROMPT_LENGTH
+                )));
+            }
+        }
+
+        validate_labels(&self.labels).map_err(|e| ApiError::validation(e.to_string()))?;
+
         Ok(())
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AgentResponse {
     pub id: String,
+    #[schema(value_type = String)]
     pub agent_type: AgentType,
+    #[serde(default)]
+    pub custom_type: Option<String>,
+    #[schema(value_type = String)]
     pub state: AgentState,
     pub task: String,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -1148,15 +2084,17 @@ impl From<Agent> for AgentResponse {
         Self {
             id: agent.id.to_string(),
             agent_type: agent.agent_type,
+            custom_type: agent.custom_type,
             state: agent.state,
             task: agent.task,
+            labels: agent.labels,
             created_at: agent.created_at.to_rfc3339(),
             updated_at: agent.updated_at.to_rfc3339(),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct MessageResponse {
     pub id: i64,
     pub role: String,
@@ -1175,7 +2113,35 @@ impl From<orchestrate_core::Message> for MessageResponse {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AgentEventsParams {
+    pub event_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AgentEventResponse {
+    pub id: i64,
+    pub event_type: String,
+    pub summary: String,
+    pub session_id: Option<String>,
+    pub data: serde_json::Value,
+    pub created_at: String,
+}
+
+impl From<orchestrate_core::AgentEvent> for AgentEventResponse {
+    fn from(event: orchestrate_core::AgentEvent) -> Self {
+        Self {
+            id: event.id,
+            event_type: event.event_type.as_str().to_string(),
+            summary: event.summary,
+            session_id: event.session_id,
+            data: event.data,
+            created_at: event.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SystemStatus {
     pub total_agents: usize,
     pub running_agents: usize,
@@ -1191,14 +2157,14 @@ pub struct HealthResponse {
 
 // ==================== Instruction Request/Response Types ====================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct ListInstructionsParams {
     pub enabled_only: Option<bool>,
     pub scope: Option<String>,
     pub source: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateInstructionRequest {
     pub name: String,
     pub content: String,
@@ -1231,7 +2197,7 @@ impl CreateInstructionRequest {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateInstructionRequest {
     pub name: Option<String>,
     pub content: Option<String>,
@@ -1240,7 +2206,7 @@ pub struct UpdateInstructionRequest {
     pub tags: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct InstructionResponse {
     pub id: i64,
     pub name: String,
@@ -1277,7 +2243,7 @@ impl From<CustomInstruction> for InstructionResponse {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct EffectivenessResponse {
     pub instruction_id: i64,
     pub usage_count: i64,
@@ -1312,12 +2278,12 @@ impl From<InstructionEffectiveness> for EffectivenessResponse {
 
 // ==================== Pattern Request/Response Types ====================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct ListPatternsParams {
     pub status: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PatternResponse {
     pub id: i64,
     pub pattern_type: String,
@@ -1348,13 +2314,13 @@ impl From<LearningPattern> for PatternResponse {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProcessPatternsResponse {
     pub created_count: usize,
     pub instruction_ids: Vec<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CleanupResponse {
     pub disabled_count: usize,
     pub deleted_names: Vec<String>,
@@ -1362,7 +2328,7 @@ pub struct CleanupResponse {
 
 // ==================== Pipeline Request/Response Types ====================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreatePipelineRequest {
     pub name: String,
     pub definition: String,
@@ -1386,13 +2352,13 @@ impl CreatePipelineRequest {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdatePipelineRequest {
     pub definition: Option<String>,
     pub enabled: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PipelineResponse {
     pub id: i64,
     pub name: String,
@@ -1413,17 +2379,27 @@ impl From<Pipeline> for PipelineResponse {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct TriggerRunRequest {
     pub trigger_event: Option<String>,
+    /// Commit the run builds; stages with unchanged inputs reuse cached results
+    #[serde(default)]
+    pub commit_sha: Option<String>,
+    /// Execute every stage, ignoring cached stage results
+    #[serde(default)]
+    pub no_cache: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PipelineRunResponse {
     pub id: i64,
     pub pipeline_id: i64,
     pub status: String,
     pub trigger_event: Option<String>,
+    #[serde(default)]
+    pub variables: std::collections::HashMap<String, String>,
+    #[serde(default)]
+    pub commit_sha: Option<String>,
     pub started_at: Option<String>,
     pub completed_at: Option<String>,
     pub created_at: String,
@@ -1436,6 +2412,8 @@ impl From<PipelineRun> for PipelineRunResponse {
             pipeline_id: run.pipeline_id,
             status: run.status.as_str().to_string(),
             trigger_event: run.trigger_event,
+            variables: run.variables,
+            commit_sha: run.commit_sha,
             started_at: run.started_at.map(|dt| dt.to_rfc3339()),
             completed_at: run.completed_at.map(|dt| dt.to_rfc3339()),
             created_at: run.created_at.to_rfc3339(),
@@ -1443,13 +2421,15 @@ impl From<PipelineRun> for PipelineRunResponse {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PipelineStageResponse {
     pub id: i64,
     pub run_id: i64,
     pub stage_name: String,
     pub status: String,
     pub agent_id: Option<String>,
+    pub attempts: i32,
+    pub failed_after_retries: bool,
     pub started_at: Option<String>,
     pub completed_at: Option<String>,
     pub created_at: String,
@@ -1460,9 +2440,11 @@ impl From<PipelineStage> for PipelineStageResponse {
         Self {
             id: stage.id.unwrap_or(0),
             run_id: stage.run_id,
+            failed_after_retries: stage.failed_after_retries(),
             stage_name: stage.stage_name,
             status: stage.status.as_str().to_string(),
             agent_id: stage.agent_id,
+            attempts: stage.attempts,
             started_at: stage.started_at.map(|dt| dt.to_rfc3339()),
             completed_at: stage.completed_at.map(|dt| dt.to_rfc3339()),
             created_at: stage.created_at.to_rfc3339(),
@@ -1470,10 +2452,68 @@ impl From<PipelineStage> for PipelineStageResponse {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SagaResponse {
+    pub id: i64,
+    pub workflow_type: String,
+    pub workflow_id: String,
+    pub status: String,
+    pub failed_step: Option<String>,
+    pub error_message: Option<String>,
+    pub compensations: Vec<SagaCompensationResponse>,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+}
+
+impl SagaResponse {
+    fn new(saga: Saga, compensations: Vec<SagaCompensation>) -> Self {
+        Self {
+            id: saga.id,
+            workflow_type: saga.workflow_type.as_str().to_string(),
+            workflow_id: saga.workflow_id,
+            status: saga.status.as_str().to_string(),
+            failed_step: saga.failed_step,
+            error_message: saga.error_message,
+            compensations: compensations.into_iter().map(Into::into).collect(),
+            created_at: saga.created_at.to_rfc3339(),
+            completed_at: saga.completed_at.map(|dt| dt.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SagaCompensationResponse {
+    pub id: i64,
+    pub step_name: String,
+    pub sequence: i64,
+    pub action: String,
+    pub agent: String,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub executed_at: Option<String>,
+}
+
+impl From<SagaCompensation> for SagaCompensationResponse {
+    fn from(compensation: SagaCompensation) -> Self {
+        Self {
+            id: compensation.id,
+            step_name: compensation.step_name,
+            sequence: compensation.sequence,
+            action: compensation.action.as_str().to_string(),
+            agent: compensation.agent,
+            status: compensation.status.as_str().to_string(),
+            error_message: compensation.error_message,
+            executed_at: compensation.executed_at.map(|dt| dt.to_rfc3339()),
+        }
+    }
+}
+
 // ==================== Approval Request/Response Types ====================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ApprovalDecisionRequest {
+    /// Ignored for signed-in users, who decide as themselves
+    #[serde(default)]
     pub approver: String,
     pub comment: Option<String>,
 }
@@ -1485,9 +2525,17 @@ impl ApprovalDecisionRequest {
         }
         Ok(())
     }
+
+    /// The signed-in user, or the approver named in the request
+    fn approver_for(&self, identity: Option<&Identity>) -> Result<String, ApiError> {
+        match identity {
+            Some(identity) => Ok(identity.actor()),
+            None => self.validate().map(|_| self.approver.clone()),
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ApprovalResponse {
     pub id: i64,
     pub stage_id: i64,
@@ -1524,20 +2572,216 @@ impl From<ApprovalRequest> for ApprovalResponse {
     }
 }
 
+// ==================== Network Skill Handlers ====================
+
+#[utoipa::path(
+    get,
+    path = "/api/network/skills",
+    tag = "network",
+    summary = "List agent skills",
+    params(PageParams),
+    responses((status = 200, body = Page<SkillResponse>))
+)]
+async fn list_skills(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<SkillResponse>>, ApiError> {
+    let registry = state.network.skill_registry().await;
+    let skills: Vec<SkillResponse> = registry
+        .all_skills()
+        .filter_map(|name| registry.get(name))
+        .map(SkillResponse::from)
+        .collect();
+
+    Ok(Json(page.paginate(skills, |s| s.name.clone())?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/network/skills/{name}",
+    tag = "network",
+    summary = "Get an agent skill",
+    params(("name" = String, Path)),
+    responses((status = 200, body = SkillResponse))
+)]
+async fn get_skill(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<SkillResponse>, ApiError> {
+    let registry = state.network.skill_registry().await;
+    let skill = registry.get(&name).ok_or_else(|| ApiError::not_found("Skill"))?;
+
+    Ok(Json(skill.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/network/skills",
+    tag = "network",
+    summary = "Register an agent skill",
+    request_body = RegisterSkillRequest,
+    responses((status = 201, body = SkillResponse))
+)]
+async fn register_skill(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<RegisterSkillRequest>,
+) -> Result<(StatusCode, Json<SkillResponse>), ApiError> {
+    let definition = req.into_definition();
+    let response = SkillResponse::from(&definition);
+
+    state
+        .network
+        .register_skill(definition)
+        .await
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/network/skills/{name}",
+    tag = "network",
+    summary = "Unregister an agent skill",
+    params(("name" = String, Path)),
+    responses((status = 204, description = "Skill unregistered"))
+)]
+async fn unregister_skill(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .network
+        .unregister_skill(&name)
+        .await
+        .map_err(|_| ApiError::not_found("Skill"))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ==================== Network Skill Request/Response Types ====================
+
+/// Dependency requirement of a skill: an agent of `agent_type` in `state`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SkillDependency {
+    #[schema(value_type = String)]
+    pub agent_type: AgentType,
+    #[schema(value_type = String)]
+    pub state: AgentState,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterSkillRequest {
+    pub name: String,
+    #[schema(value_type = Vec<String>)]
+    pub agent_types: Vec<AgentType>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub required_state: Option<AgentState>,
+    #[serde(default)]
+    pub dependencies: Vec<SkillDependency>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub produces_state: Option<AgentState>,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub cancellable: Option<bool>,
+    #[serde(default)]
+    pub priority: u32,
+}
+
+impl RegisterSkillRequest {
+    fn into_definition(self) -> SkillDefinition {
+        let mut definition = SkillDefinition::new(self.name, self.agent_types)
+            .with_priority(self.priority);
+        if let Some(state) = self.required_state {
+            definition = definition.requires_state(state);
+        }
+        for dep in self.dependencies {
+            definition = definition.requires_dependency(dep.agent_type, dep.state);
+        }
+        if let Some(state) = self.produces_state {
+            definition = definition.produces(state);
+        }
+        if let Some(secs) = self.timeout_secs {
+            definition = definition.with_timeout(secs);
+        }
+        if let Some(cancellable) = self.cancellable {
+            definition = definition.cancellable(cancellable);
+        }
+        definition
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SkillResponse {
+    pub name: String,
+    #[schema(value_type = Vec<String>)]
+    pub agent_types: Vec<AgentType>,
+    #[schema(value_type = String)]
+    pub required_state: AgentState,
+    pub dependencies: Vec<SkillDependency>,
+    #[schema(value_type = Option<String>)]
+    pub produces_state: Option<AgentState>,
+    pub timeout_secs: Option<u64>,
+    pub cancellable: bool,
+    pub priority: u32,
+}
+
+impl From<&SkillDefinition> for SkillResponse {
+    fn from(skill: &SkillDefinition) -> Self {
+        Self {
+            name: skill.name.clone(),
+            agent_types: skill.agent_types.clone(),
+            required_state: skill.required_state,
+            dependencies: skill
+                .dependency_states
+                .iter()
+                .map(|(agent_type, state)| SkillDependency {
+                    agent_type: *agent_type,
+                    state: *state,
+                })
+                .collect(),
+            produces_state: skill.produces_state,
+            timeout_secs: skill.timeout_secs,
+            cancellable: skill.cancellable,
+            priority: skill.priority,
+        }
+    }
+}
+
 // ==================== Schedule Handlers ====================
 
+#[utoipa::path(
+    get,
+    path = "/api/schedules",
+    tag = "schedules",
+    summary = "List schedules",
+    params(PageParams),
+    responses((status = 200, body = Page<ScheduleResponse>))
+)]
 async fn list_schedules(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<ScheduleResponse>>, ApiError> {
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<ScheduleResponse>>, ApiError> {
     let schedules = state
         .db
-        .list_schedules(false)
+        .list_schedules_page(&page.query()?)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(schedules.into_iter().map(Into::into).collect()))
+    Ok(Json(schedules.map(Into::into).into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/schedules/{id}",
+    tag = "schedules",
+    summary = "Get a schedule",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = ScheduleResponse))
+)]
 async fn get_schedule(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -1552,6 +2796,14 @@ async fn get_schedule(
     Ok(Json(schedule.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/schedules",
+    tag = "schedules",
+    summary = "Create a schedule",
+    request_body = CreateScheduleRequest,
+    responses((status = 200, body = ScheduleResponse))
+)]
 async fn create_schedule(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateScheduleRequest>,
@@ -1565,9 +2817,46 @@ async fn create_schedule(
         req.task,
     );
 
+    if let Some(timezone) = req.timezone {
+        schedule = schedule.with_timezone(timezone);
+    }
+    if let Some(run_at) = req.run_at {
+        schedule.run_at = Some(
+            parse_run_at(&run_at, &schedule.timezone)
+                .map_err(|e| ApiError::validation(e.to_string()))?,
+        );
+    }
+    if let Some(jitter_secs) = req.jitter_secs {
+        schedule = schedule.with_jitter(jitter_secs);
+    }
+    if let Some(overlap_policy) = req.overlap_policy {
+        schedule = schedule.with_overlap_policy(
+            overlap_policy
+                .parse::<ScheduleOverlapPolicy>()
+                .map_err(|e| ApiError::validation(e.to_string()))?,
+        );
+    }
+    if let Some(depends_on) = req.depends_on {
+        state
+            .db
+            .validate_schedule_dependency(&schedule.name, &depends_on)
+            .await
+            .map_err(|e| ApiError::validation(e.to_string()))?;
+        schedule = schedule.with_depends_on(depends_on);
+    }
+    schedule.max_tokens = req.max_tokens;
+    schedule.max_cost_usd = req.max_cost_usd;
+    schedule.max_consecutive_misses = req.max_consecutive_misses;
+    if let Some(period) = req.spend_cap_period {
+        schedule = schedule.with_spend_cap_period(period.parse().map_err(ApiError::validation)?);
+    }
+
     schedule
         .validate_cron()
         .map_err(|e| ApiError::validation(format!("Invalid cron expression: {}", e)))?;
+    schedule
+        .validate_timezone()
+        .map_err(|e| ApiError::validation(e.to_string()))?;
 
     schedule
         .update_next_run()
@@ -1587,6 +2876,15 @@ async fn create_schedule(
     Ok(Json(schedule.into()))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/schedules/{id}",
+    tag = "schedules",
+    summary = "Update a schedule",
+    params(("id" = i64, Path)),
+    request_body = UpdateScheduleRequest,
+    responses((status = 200, body = ScheduleResponse))
+)]
 async fn update_schedule(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -1602,11 +2900,58 @@ async fn update_schedule(
     if let Some(name) = req.name {
         schedule.name = name;
     }
+    let reschedule =
+        req.cron_expression.is_some() || req.timezone.is_some() || req.jitter_secs.is_some();
     if let Some(cron_expression) = req.cron_expression {
+        // A cron expression turns a one-shot schedule into a recurring one
         schedule.cron_expression = cron_expression;
+        schedule.run_at = None;
         schedule
             .validate_cron()
             .map_err(|e| ApiError::validation(format!("Invalid cron expression: {}", e)))?;
+    }
+    if let Some(timezone) = req.timezone {
+        schedule.timezone = timezone;
+        schedule
+            .validate_timezone()
+            .map_err(|e| ApiError::validation(e.to_string()))?;
+    }
+    if let Some(jitter_secs) = req.jitter_secs {
+        schedule.jitter_secs = jitter_secs;
+    }
+    if let Some(overlap_policy) = req.overlap_policy {
+        schedule.overlap_policy = overlap_policy
+            .parse::<ScheduleOverlapPolicy>()
+            .map_err(|e| ApiError::validation(e.to_string()))?;
+    }
+    // An empty dependency name removes the dependency
+    if let Some(depends_on) = req.depends_on {
+        if depends_on.is_empty() {
+            schedule.depends_on = None;
+        } else {
+            state
+                .db
+                .validate_schedule_dependency(&schedule.name, &depends_on)
+                .await
+                .map_err(|e| ApiError::validation(e.to_string()))?;
+            schedule.depends_on = Some(depends_on);
+        }
+    }
+    // A zero cap removes the cap
+    if let Some(max_tokens) = req.max_tokens {
+        schedule.max_tokens = Some(max_tokens).filter(|max| *max > 0);
+    }
+    if let Some(max_cost_usd) = req.max_cost_usd {
+        schedule.max_cost_usd = Some(max_cost_usd).filter(|max| *max > 0.0);
+    }
+    if let Some(period) = req.spend_cap_period {
+        schedule.spend_cap_period = period.parse().map_err(ApiError::validation)?;
+    }
+    // Zero consecutive misses removes the alert
+    if let Some(max_misses) = req.max_consecutive_misses {
+        schedule.max_consecutive_misses = Some(max_misses).filter(|max| *max > 0);
+    }
+    if reschedule {
         schedule
             .update_next_run()
             .map_err(|e| ApiError::internal(format!("Failed to calculate next run: {}", e)))?;
@@ -1630,6 +2975,14 @@ async fn update_schedule(
     Ok(Json(schedule.into()))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/schedules/{id}",
+    tag = "schedules",
+    summary = "Delete a schedule",
+    params(("id" = i64, Path)),
+    responses((status = 204, description = "Schedule deleted"))
+)]
 async fn delete_schedule(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -1643,6 +2996,14 @@ async fn delete_schedule(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/schedules/{id}/pause",
+    tag = "schedules",
+    summary = "Pause a schedule",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = ScheduleResponse))
+)]
 async fn pause_schedule(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -1664,6 +3025,14 @@ async fn pause_schedule(
     Ok(Json(schedule.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/schedules/{id}/resume",
+    tag = "schedules",
+    summary = "Resume a schedule",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = ScheduleResponse))
+)]
 async fn resume_schedule(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -1685,6 +3054,14 @@ async fn resume_schedule(
     Ok(Json(schedule.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/schedules/{id}/run",
+    tag = "schedules",
+    summary = "Run a schedule now",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = ScheduleRunResponse))
+)]
 async fn run_schedule(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -1718,25 +3095,81 @@ async fn run_schedule(
     Ok(Json(run.into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/schedules/{id}/runs",
+    tag = "schedules",
+    summary = "List a schedule's runs",
+    params(
+        ("id" = i64, Path),
+        PageParams
+    ),
+    responses((status = 200, body = Page<ScheduleRunResponse>))
+)]
 async fn get_schedule_runs(
     State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
     Path(id): Path<i64>,
-) -> Result<Json<Vec<ScheduleRunResponse>>, ApiError> {
+) -> Result<Json<Page<ScheduleRunResponse>>, ApiError> {
     let runs = state
         .db
-        .get_schedule_runs(id, 50)
+        .get_schedule_runs_page(id, &page.query()?)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(runs.into_iter().map(Into::into).collect()))
+    Ok(Json(runs.map(Into::into).into()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/schedules/{id}/stats",
+    tag = "schedules",
+    summary = "Get a schedule's statistics",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = ScheduleStatsResponse))
+)]
+async fn get_schedule_stats(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<ScheduleStatsResponse>, ApiError> {
+    let schedule = state
+        .db
+        .get_schedule(id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Schedule"))?;
+    let stats = state
+        .db
+        .get_schedule_stats(id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Schedule"))?;
+
+    Ok(Json(ScheduleStatsResponse {
+        schedule_id: id,
+        success_rate: stats.success_rate(),
+        max_consecutive_misses: schedule.max_consecutive_misses,
+        stats,
+    }))
 }
 
 // ==================== Schedule Request/Response Types ====================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct CreateScheduleRequest {
     name: String,
+    #[serde(default)]
     cron_expression: String,
+    /// Run time of a one-shot schedule, used instead of `cron_expression`
+    run_at: Option<String>,
+    timezone: Option<String>,
+    jitter_secs: Option<u32>,
+    overlap_policy: Option<String>,
+    depends_on: Option<String>,
+    max_tokens: Option<u64>,
+    max_cost_usd: Option<f64>,
+    spend_cap_period: Option<String>,
+    max_consecutive_misses: Option<u32>,
     agent_type: String,
     task: String,
     enabled: Option<bool>,
@@ -1747,8 +3180,18 @@ impl CreateScheduleRequest {
         if self.name.is_empty() || self.name.len() > 255 {
             return Err(ApiError::validation("Name must be 1-255 characters"));
         }
-        if self.cron_expression.is_empty() {
-            return Err(ApiError::validation("Cron expression is required"));
+        match (self.cron_expression.is_empty(), self.run_at.is_some()) {
+            (true, false) => {
+                return Err(ApiError::validation(
+                    "Cron expression or run_at is required",
+                ));
+            }
+            (false, true) => {
+                return Err(ApiError::validation(
+                    "Specify either cron_expression or run_at, not both",
+                ));
+            }
+            _ => {}
         }
         if self.agent_type.is_empty() {
             return Err(ApiError::validation("Agent type is required"));
@@ -1763,20 +3206,39 @@ impl CreateScheduleRequest {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct UpdateScheduleRequest {
     name: Option<String>,
     cron_expression: Option<String>,
+    timezone: Option<String>,
+    jitter_secs: Option<u32>,
+    overlap_policy: Option<String>,
+    depends_on: Option<String>,
+    max_tokens: Option<u64>,
+    max_cost_usd: Option<f64>,
+    spend_cap_period: Option<String>,
+    max_consecutive_misses: Option<u32>,
     agent_type: Option<String>,
     task: Option<String>,
     enabled: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct ScheduleResponse {
     id: i64,
     name: String,
     cron_expression: String,
+    run_at: Option<String>,
+    timezone: String,
+    jitter_secs: u32,
+    overlap_policy: String,
+    depends_on: Option<String>,
+    max_tokens: Option<u64>,
+    max_cost_usd: Option<f64>,
+    spend_cap_period: String,
+    max_consecutive_misses: Option<u32>,
+    missed_runs: u32,
+    consecutive_missed_runs: u32,
     agent_type: String,
     task: String,
     enabled: bool,
@@ -1791,6 +3253,17 @@ impl From<Schedule> for ScheduleResponse {
             id: schedule.id,
             name: schedule.name,
             cron_expression: schedule.cron_expression,
+            run_at: schedule.run_at.map(|dt| dt.to_rfc3339()),
+            timezone: schedule.timezone,
+            jitter_secs: schedule.jitter_secs,
+            overlap_policy: schedule.overlap_policy.as_str().to_string(),
+            depends_on: schedule.depends_on,
+            max_tokens: schedule.max_tokens,
+            max_cost_usd: schedule.max_cost_usd,
+            spend_cap_period: schedule.spend_cap_period.to_string(),
+            max_consecutive_misses: schedule.max_consecutive_misses,
+            missed_runs: schedule.missed_runs,
+            consecutive_missed_runs: schedule.consecutive_missed_runs,
             agent_type: schedule.agent_type,
             task: schedule.task,
             enabled: schedule.enabled,
@@ -1801,7 +3274,17 @@ impl From<Schedule> for ScheduleResponse {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
+struct ScheduleStatsResponse {
+    schedule_id: i64,
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    stats: ScheduleStats,
+    success_rate: Option<f64>,
+    max_consecutive_misses: Option<u32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 struct ScheduleRunResponse {
     id: i64,
     schedule_id: i64,
@@ -1828,7 +3311,7 @@ impl From<ScheduleRun> for ScheduleRunResponse {
 
 // ==================== Feedback Handlers ====================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct CreateFeedbackRequest {
     agent_id: String,
     rating: String,
@@ -1838,7 +3321,7 @@ struct CreateFeedbackRequest {
     message_id: Option<i64>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct FeedbackResponse {
     id: i64,
     agent_id: String,
@@ -1865,7 +3348,7 @@ impl From<Feedback> for FeedbackResponse {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct FeedbackStatsResponse {
     total: i64,
     positive: i64,
@@ -1888,7 +3371,7 @@ impl From<FeedbackStats> for FeedbackStatsResponse {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 struct FeedbackListQuery {
     #[serde(default)]
     agent_id: Option<String>,
@@ -1896,14 +3379,16 @@ struct FeedbackListQuery {
     rating: Option<String>,
     #[serde(default)]
     source: Option<String>,
-    #[serde(default = "default_feedback_limit")]
-    limit: i64,
-}
-
-fn default_feedback_limit() -> i64 {
-    50
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/feedback",
+    tag = "feedback",
+    summary = "Create feedback",
+    request_body = CreateFeedbackRequest,
+    responses((status = 200, body = FeedbackResponse))
+)]
 async fn create_feedback(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateFeedbackRequest>,
@@ -1950,36 +3435,58 @@ async fn create_feedback(
     Ok(Json(created.into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/feedback",
+    tag = "feedback",
+    summary = "List feedback",
+    params(
+        PageParams,
+        FeedbackListQuery
+    ),
+    responses((status = 200, body = Page<FeedbackResponse>))
+)]
 async fn list_feedback(
     State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
     Query(query): Query<FeedbackListQuery>,
-) -> Result<Json<Vec<FeedbackResponse>>, ApiError> {
+) -> Result<Json<Page<FeedbackResponse>>, ApiError> {
     use std::str::FromStr;
 
-    let feedbacks = if let Some(agent_id) = query.agent_id {
-        let agent_uuid = Uuid::parse_str(&agent_id)
-            .map_err(|e| ApiError::validation(format!("Invalid agent ID: {}", e)))?;
-        state.db.list_feedback_for_agent(agent_uuid, query.limit).await
-    } else {
-        let rating_filter = query.rating
-            .as_ref()
-            .map(|r| FeedbackRating::from_str(r))
-            .transpose()
-            .map_err(|_| ApiError::validation("Invalid rating filter"))?;
-        let source_filter = query.source
-            .as_ref()
-            .map(|s| FeedbackSource::from_str(s))
-            .transpose()
-            .map_err(|_| ApiError::validation("Invalid source filter"))?;
-        state.db.list_feedback(rating_filter, source_filter, query.limit).await
-    };
+    let agent_filter = query
+        .agent_id
+        .as_deref()
+        .map(Uuid::parse_str)
+        .transpose()
+        .map_err(|e| ApiError::validation(format!("Invalid agent ID: {}", e)))?;
+    let rating_filter = query.rating
+        .as_ref()
+        .map(|r| FeedbackRating::from_str(r))
+        .transpose()
+        .map_err(|_| ApiError::validation("Invalid rating filter"))?;
+    let source_filter = query.source
+        .as_ref()
+        .map(|s| FeedbackSource::from_str(s))
+        .transpose()
+        .map_err(|_| ApiError::validation("Invalid source filter"))?;
 
-    let feedbacks = feedbacks
+    let feedbacks = state
+        .db
+        .list_feedback_page(agent_filter, rating_filter, source_filter, &page.query()?)
+        .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(feedbacks.into_iter().map(Into::into).collect()))
+    Ok(Json(feedbacks.map(Into::into).into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/feedback/{id}",
+    tag = "feedback",
+    summary = "Get feedback",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = FeedbackResponse))
+)]
 async fn get_feedback(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -1991,6 +3498,14 @@ async fn get_feedback(
     Ok(Json(feedback.into()))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/feedback/{id}",
+    tag = "feedback",
+    summary = "Delete feedback",
+    params(("id" = i64, Path)),
+    responses((status = 204, description = "Feedback deleted"))
+)]
 async fn delete_feedback(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -2005,6 +3520,14 @@ async fn delete_feedback(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/feedback/stats",
+    tag = "feedback",
+    summary = "Get feedback statistics",
+    params(FeedbackStatsQuery),
+    responses((status = 200, body = FeedbackStatsResponse))
+)]
 async fn get_feedback_stats(
     State(state): State<Arc<AppState>>,
     Query(query): Query<FeedbackStatsQuery>,
@@ -2023,7 +3546,7 @@ async fn get_feedback_stats(
     Ok(Json(stats.into()))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 struct FeedbackStatsQuery {
     #[serde(default)]
     agent_id: Option<String>,
@@ -2031,13 +3554,13 @@ struct FeedbackStatsQuery {
 
 // ==================== Learning Analytics Handlers ====================
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct LearningEffectivenessResponse {
     instructions: Vec<InstructionEffectivenessItem>,
     summary: EffectivenessSummaryItem,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct InstructionEffectivenessItem {
     instruction_id: i64,
     name: String,
@@ -2049,7 +3572,7 @@ struct InstructionEffectivenessItem {
     level: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct EffectivenessSummaryItem {
     total_instructions: i64,
     enabled_count: i64,
@@ -2060,6 +3583,14 @@ struct EffectivenessSummaryItem {
     ineffective_count: i64,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/learning/effectiveness",
+    tag = "learning",
+    summary = "Get learning effectiveness",
+    params(EffectivenessQuery),
+    responses((status = 200, body = LearningEffectivenessResponse))
+)]
 async fn get_learning_effectiveness(
     State(state): State<Arc<AppState>>,
     Query(query): Query<EffectivenessQuery>,
@@ -2104,7 +3635,7 @@ async fn get_learning_effectiveness(
     }))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 struct EffectivenessQuery {
     #[serde(default)]
     min_usage: Option<i64>,
@@ -2112,13 +3643,13 @@ struct EffectivenessQuery {
     include_disabled: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct SuggestionResponse {
     suggestions: Vec<SuggestionItem>,
     total: usize,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct SuggestionItem {
     instruction_id: i64,
     name: String,
@@ -2127,6 +3658,14 @@ struct SuggestionItem {
     suggestion: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/learning/suggestions",
+    tag = "learning",
+    summary = "Get learning suggestions",
+    params(SuggestionsQuery),
+    responses((status = 200, body = SuggestionResponse))
+)]
 async fn get_learning_suggestions(
     State(state): State<Arc<AppState>>,
     Query(query): Query<SuggestionsQuery>,
@@ -2154,18 +3693,25 @@ async fn get_learning_suggestions(
     Ok(Json(SuggestionResponse { suggestions, total }))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 struct SuggestionsQuery {
     #[serde(default)]
     limit: Option<usize>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct AnalysisResponse {
     patterns_processed: usize,
     instructions_created: Vec<String>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/learning/analyze",
+    tag = "learning",
+    summary = "Run learning analysis",
+    responses((status = 200, body = AnalysisResponse))
+)]
 async fn trigger_learning_analysis(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<AnalysisResponse>, ApiError> {
@@ -2185,7 +3731,7 @@ async fn trigger_learning_analysis(
 
 use orchestrate_core::{Experiment, ExperimentStatus, ExperimentType, ExperimentMetric};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct ExperimentResponse {
     id: i64,
     name: String,
@@ -2196,6 +3742,9 @@ struct ExperimentResponse {
     min_samples: i64,
     confidence_level: f64,
     created_at: String,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+    winner_variant_id: Option<i64>,
 }
 
 impl From<Experiment> for ExperimentResponse {
@@ -2210,14 +3759,29 @@ impl From<Experiment> for ExperimentResponse {
             min_samples: exp.min_samples,
             confidence_level: exp.confidence_level,
             created_at: exp.created_at.to_rfc3339(),
+            started_at: exp.started_at.map(|t| t.to_rfc3339()),
+            completed_at: exp.completed_at.map(|t| t.to_rfc3339()),
+            winner_variant_id: exp.winner_variant_id,
         }
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/experiments",
+    tag = "experiments",
+    summary = "List experiments",
+    params(
+        PageParams,
+        ExperimentListQuery
+    ),
+    responses((status = 200, body = Page<ExperimentResponse>))
+)]
 async fn list_experiments(
     State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
     Query(query): Query<ExperimentListQuery>,
-) -> Result<Json<Vec<ExperimentResponse>>, ApiError> {
+) -> Result<Json<Page<ExperimentResponse>>, ApiError> {
     let status_filter = query.status.as_ref().and_then(|s| {
         use std::str::FromStr;
         ExperimentStatus::from_str(s).ok()
@@ -2225,22 +3789,20 @@ async fn list_experiments(
 
     let experiments = state
         .db
-        .list_experiments(status_filter, query.limit.unwrap_or(100))
+        .list_experiments_page(status_filter, &page.query()?)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    Ok(Json(experiments.into_iter().map(Into::into).collect()))
+    Ok(Json(experiments.map(Into::into).into()))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 struct ExperimentListQuery {
     #[serde(default)]
     status: Option<String>,
-    #[serde(default)]
-    limit: Option<i64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct CreateExperimentRequest {
     name: String,
     description: Option<String>,
@@ -2250,6 +3812,14 @@ struct CreateExperimentRequest {
     confidence_level: Option<f64>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/experiments",
+    tag = "experiments",
+    summary = "Create an experiment",
+    request_body = CreateExperimentRequest,
+    responses((status = 200, body = ExperimentResponse))
+)]
 async fn create_experiment(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateExperimentRequest>,
@@ -2295,6 +3865,14 @@ async fn create_experiment(
     Ok(Json(created.into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/experiments/{id}",
+    tag = "experiments",
+    summary = "Get an experiment",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = ExperimentResponse))
+)]
 async fn get_experiment(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -2309,17 +3887,19 @@ async fn get_experiment(
     Ok(Json(experiment.into()))
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct ExperimentResultsResponse {
     experiment_id: i64,
     variants: Vec<VariantResultItem>,
+    total_samples: i64,
+    min_samples: i64,
     is_significant: bool,
     p_value: Option<f64>,
     improvement: Option<f64>,
     winning_variant: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct VariantResultItem {
     variant_id: i64,
     name: String,
@@ -2328,8 +3908,19 @@ struct VariantResultItem {
     mean: f64,
     std_dev: f64,
     success_rate: f64,
-}
-
+    /// 95% confidence interval of the mean
+    ci_lower: f64,
+    ci_upper: f64,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/experiments/{id}/results",
+    tag = "experiments",
+    summary = "Get an experiment's results",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = ExperimentResultsResponse))
+)]
 async fn get_experiment_results(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -2349,14 +3940,19 @@ async fn get_experiment_results(
 
     let variants: Vec<VariantResultItem> = results
         .iter()
-        .map(|v| VariantResultItem {
-            variant_id: v.variant_id,
-            name: v.variant_name.clone(),
-            is_control: v.is_control,
-            sample_count: v.sample_count,
-            mean: v.mean,
-            std_dev: v.std_dev,
-            success_rate: v.success_rate.unwrap_or(0.0),
+        .map(|v| {
+            let (ci_lower, ci_upper) = v.confidence_interval();
+            VariantResultItem {
+                variant_id: v.variant_id,
+                name: v.variant_name.clone(),
+                is_control: v.is_control,
+                sample_count: v.sample_count,
+                mean: v.mean,
+                std_dev: v.std_dev,
+                success_rate: v.success_rate.unwrap_or(0.0),
+                ci_lower,
+                ci_upper,
+            }
         })
         .collect();
 
@@ -2383,6 +3979,8 @@ async fn get_experiment_results(
 
     Ok(Json(ExperimentResultsResponse {
         experiment_id: id,
+        total_samples: variants.iter().map(|v| v.sample_count).sum(),
+        min_samples: experiment.min_samples,
         variants,
         is_significant,
         p_value,
@@ -2391,11 +3989,144 @@ async fn get_experiment_results(
     }))
 }
 
-#[derive(Debug, Deserialize)]
+#[utoipa::path(
+    get,
+    path = "/api/experiments/{id}/daily",
+    tag = "experiments",
+    summary = "Get an experiment's results per day",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = [Object]))
+)]
+async fn get_experiment_daily_results(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<orchestrate_core::VariantDailyResults>>, ApiError> {
+    state
+        .db
+        .get_experiment(id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Experiment"))?;
+
+    let daily = state
+        .db
+        .get_experiment_daily_results(id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
+
+    Ok(Json(daily))
+}
+
+/// Start a draft experiment, or resume a paused one
+#[utoipa::path(
+    post,
+    path = "/api/experiments/{id}/start",
+    tag = "experiments",
+    summary = "Start or resume an experiment",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = ExperimentResponse))
+)]
+async fn start_experiment(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<ExperimentResponse>, ApiError> {
+    let experiment = state
+        .db
+        .get_experiment(id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Experiment"))?;
+
+    match experiment.status {
+        ExperimentStatus::Paused => {}
+        ExperimentStatus::Draft => {
+            let variants = state
+                .db
+                .get_experiment_variants(id)
+                .await
+                .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
+            if variants.len() < 2 {
+                return Err(ApiError::validation(
+                    "Experiment needs at least 2 variants before starting",
+                ));
+            }
+            if !variants.iter().any(|v| v.is_control) {
+                return Err(ApiError::validation(
+                    "Experiment needs at least one control variant",
+                ));
+            }
+        }
+        status => {
+            return Err(ApiError::conflict(format!(
+                "Experiment is {} and cannot be started",
+                status
+            )))
+        }
+    }
+
+    set_experiment_status(&state, id, ExperimentStatus::Running).await
+}
+
+/// Pause a running experiment
+#[utoipa::path(
+    post,
+    path = "/api/experiments/{id}/pause",
+    tag = "experiments",
+    summary = "Pause an experiment",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = ExperimentResponse))
+)]
+async fn pause_experiment(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<ExperimentResponse>, ApiError> {
+    let experiment = state
+        .db
+        .get_experiment(id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Experiment"))?;
+    if !experiment.is_running() {
+        return Err(ApiError::conflict("Experiment is not running"));
+    }
+
+    set_experiment_status(&state, id, ExperimentStatus::Paused).await
+}
+
+async fn set_experiment_status(
+    state: &AppState,
+    id: i64,
+    status: ExperimentStatus,
+) -> Result<Json<ExperimentResponse>, ApiError> {
+    state
+        .db
+        .update_experiment_status(id, status)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
+    let experiment = state
+        .db
+        .get_experiment(id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Experiment"))?;
+
+    Ok(Json(experiment.into()))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 struct PromoteExperimentRequest {
     winner_variant_id: i64,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/experiments/{id}/promote",
+    tag = "experiments",
+    summary = "Promote an experiment's winner",
+    params(("id" = i64, Path)),
+    request_body = PromoteExperimentRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
 async fn promote_experiment(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -2408,10 +4139,22 @@ async fn promote_experiment(
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
         .ok_or_else(|| ApiError::not_found("Experiment"))?;
 
+    let variants = state
+        .db
+        .get_experiment_variants(id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
+    if !variants.iter().any(|v| v.id == req.winner_variant_id) {
+        return Err(ApiError::validation(format!(
+            "Variant {} is not part of experiment {}",
+            req.winner_variant_id, id
+        )));
+    }
+
     // Complete the experiment with the winner
     state
         .db
-        .update_experiment_status(id, ExperimentStatus::Completed)
+        .set_experiment_winner(id, req.winner_variant_id)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
@@ -2424,13 +4167,13 @@ async fn promote_experiment(
 
 // ==================== Prediction Handlers ====================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct PredictionRequest {
     task: String,
     agent_type: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct PredictionResponse {
     task_description: String,
     success_probability: f64,
@@ -2442,21 +4185,21 @@ struct PredictionResponse {
     recommendations: Vec<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct TokenEstimateItem {
     min: i64,
     max: i64,
     expected: i64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct DurationEstimateItem {
     min_minutes: f64,
     max_minutes: f64,
     expected_minutes: f64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct RiskFactorItem {
     name: String,
     description: String,
@@ -2464,6 +4207,14 @@ struct RiskFactorItem {
     impact_on_success: f64,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/predictions",
+    tag = "experiments",
+    summary = "Predict a task's outcome",
+    request_body = PredictionRequest,
+    responses((status = 200, body = PredictionResponse))
+)]
 async fn get_prediction(
     State(state): State<Arc<AppState>>,
     Json(req): Json<PredictionRequest>,
@@ -2533,13 +4284,13 @@ async fn get_prediction(
 
 // ==================== Documentation Handlers ====================
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct DocGenerateRequest {
     doc_type: String,
     format: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct DocGenerateResponse {
     doc_type: String,
     format: String,
@@ -2547,6 +4298,14 @@ struct DocGenerateResponse {
     generated_at: String,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/docs/generate",
+    tag = "docs",
+    summary = "Generate documentation",
+    request_body = DocGenerateRequest,
+    responses((status = 200, body = DocGenerateResponse))
+)]
 async fn generate_documentation(
     State(_state): State<Arc<AppState>>,
     Json(req): Json<DocGenerateRequest>,
@@ -2638,13 +4397,13 @@ async fn generate_documentation(
     }))
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct DocValidateRequest {
     path: Option<String>,
     coverage_threshold: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct DocValidateResponse {
     total_items: usize,
     documented_items: usize,
@@ -2654,6 +4413,14 @@ struct DocValidateResponse {
     summary: String,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/docs/validate",
+    tag = "docs",
+    summary = "Validate documentation coverage",
+    request_body = DocValidateRequest,
+    responses((status = 200, body = DocValidateResponse))
+)]
 async fn validate_documentation(
     State(_state): State<Arc<AppState>>,
     Json(req): Json<DocValidateRequest>,
@@ -2679,7 +4446,7 @@ async fn validate_documentation(
     }))
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct AdrListItem {
     number: u32,
     title: String,
@@ -2687,15 +4454,18 @@ struct AdrListItem {
     file_path: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct AdrListResponse {
-    adrs: Vec<AdrListItem>,
-    total: usize,
-}
-
+#[utoipa::path(
+    get,
+    path = "/api/docs/adrs",
+    tag = "docs",
+    summary = "List ADRs",
+    params(PageParams),
+    responses((status = 200, body = Page<AdrListItem>))
+)]
 async fn list_adrs(
     State(_state): State<Arc<AppState>>,
-) -> Result<Json<AdrListResponse>, ApiError> {
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<AdrListItem>>, ApiError> {
     let adr_dir = std::path::Path::new("docs/adrs");
     let mut adrs = vec![];
 
@@ -2744,13 +4514,10 @@ async fn list_adrs(
         }
     }
 
-    adrs.sort_by_key(|a| a.number);
-    let total = adrs.len();
-
-    Ok(Json(AdrListResponse { adrs, total }))
+    Ok(Json(page.paginate(adrs, |a| a.number)?))
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct AdrCreateRequest {
     title: String,
     status: Option<String>,
@@ -2758,7 +4525,7 @@ struct AdrCreateRequest {
     decision: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct AdrCreateResponse {
     number: u32,
     title: String,
@@ -2766,6 +4533,14 @@ struct AdrCreateResponse {
     file_path: String,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/docs/adrs",
+    tag = "docs",
+    summary = "Create an ADR",
+    request_body = AdrCreateRequest,
+    responses((status = 200, body = AdrCreateResponse))
+)]
 async fn create_adr(
     State(_state): State<Arc<AppState>>,
     Json(req): Json<AdrCreateRequest>,
@@ -2828,6 +4603,16 @@ async fn create_adr(
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/docs/adrs/{number}",
+    tag = "docs",
+    summary = "Get an ADR",
+    params(("number" = u32, Path)),
+    responses(
+        (status = 200, description = "The ADR", body = String, content_type = "text/markdown")
+    )
+)]
 async fn get_adr(
     State(_state): State<Arc<AppState>>,
     Path(number): Path<u32>,
@@ -2840,12 +4625,21 @@ async fn get_adr(
         .map_err(|e| ApiError::internal(format!("Failed to read ADR: {}", e)))
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct AdrUpdateRequest {
     status: Option<String>,
     superseded_by: Option<u32>,
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/docs/adrs/{number}",
+    tag = "docs",
+    summary = "Update an ADR",
+    params(("number" = u32, Path)),
+    request_body = AdrUpdateRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
 async fn update_adr(
     State(_state): State<Arc<AppState>>,
     Path(number): Path<u32>,
@@ -2898,13 +4692,13 @@ async fn update_adr(
     })))
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct ChangelogRequest {
     from: Option<String>,
     to: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct ChangelogResponse {
     version: String,
     date: String,
@@ -2912,7 +4706,7 @@ struct ChangelogResponse {
     markdown: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct ChangelogEntryItem {
     change_type: String,
     description: String,
@@ -2921,6 +4715,14 @@ struct ChangelogEntryItem {
     breaking: bool,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/docs/changelog",
+    tag = "docs",
+    summary = "Generate a changelog",
+    request_body = ChangelogRequest,
+    responses((status = 200, body = ChangelogResponse))
+)]
 async fn generate_changelog(
     State(_state): State<Arc<AppState>>,
     Json(req): Json<ChangelogRequest>,
@@ -3014,6 +4816,7 @@ async fn generate_changelog(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rbac::Role;
     use axum::{
         body::Body,
         http::{Method, Request, StatusCode},
@@ -3179,14 +4982,318 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
-    // ==================== Agent CRUD Tests ====================
+    fn session_token(state: &AppState, email: &str) -> String {
+        let identity = Identity {
+            subject: "1".to_string(),
+            provider: crate::auth::OidcProviderKind::Google,
+            email: Some(email.to_string()),
+            name: None,
+            username: None,
+        };
+        state.auth.as_ref().unwrap().issue_token(&identity).unwrap().0
+    }
 
     #[tokio::test]
-    async fn test_list_agents_empty() {
-        let test_app = setup_app().await;
+    async fn test_auth_session_token_works() {
+        let db = Database::in_memory().await.unwrap();
+        let state = Arc::new(
+            AppState::new(db, Some("secret-key".to_string()))
+                .with_auth(AuthConfig::new("jwt-secret")),
+        );
+        let router = create_api_router(state.clone());
+        let token = session_token(&state, "dev@example.com");
+
+        for (credential, expected) in [
+            (token.as_str(), StatusCode::OK),
+            ("secret-key", StatusCode::OK),
+            ("forged.session.token", StatusCode::UNAUTHORIZED),
+        ] {
+            let response = router
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri("/api/agents")
+                        .header("authorization", format!("Bearer {}", credential))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), expected);
+        }
+    }
 
-        let response = test_app
-            .router
+    #[tokio::test]
+    async fn test_auth_login_only_requires_session() {
+        let db = Database::in_memory().await.unwrap();
+        let state = Arc::new(AppState::new(db, None).with_auth(AuthConfig::new("jwt-secret")));
+        let router = create_api_router(state);
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/agents")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_rbac_forbids_missing_permission() {
+        let db = Database::in_memory().await.unwrap();
+        let state = Arc::new(
+            AppState::new(db, Some("secret-key".to_string()))
+                .with_auth(AuthConfig::new("jwt-secret"))
+                .with_rbac(RbacPolicy::default().assign("ops@example.com", Role::Operator)),
+        );
+        let router = create_api_router(state.clone());
+        let spawn = |credential: String| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/api/agents")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", credential))
+                .body(Body::from(r#"{"agent_type":"story_developer","task":"Do it"}"#))
+                .unwrap()
+        };
+
+        // Viewers can read but not spawn agents
+        let viewer = session_token(&state, "viewer@example.com");
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/agents")
+                    .header("authorization", format!("Bearer {}", viewer))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = router.clone().oneshot(spawn(viewer)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = body_to_string(response.into_body()).await;
+        assert!(body.contains("agents:spawn"), "{}", body);
+
+        let operator = session_token(&state, "ops@example.com");
+        let response = router.clone().oneshot(spawn(operator)).await.unwrap();
+        assert_ne!(response.status(), StatusCode::FORBIDDEN);
+
+        // The API key isn't tied to a user and keeps full access
+        let response = router.oneshot(spawn("secret-key".to_string())).await.unwrap();
+        assert_ne!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_autonomous_routes_require_permission() {
+        let db = Database::in_memory().await.unwrap();
+        let state = Arc::new(
+            AppState::new(db, Some("secret-key".to_string()))
+                .with_auth(AuthConfig::new("jwt-secret")),
+        );
+        let router = create_api_router(state.clone());
+        let viewer = session_token(&state, "viewer@example.com");
+        let post = |uri: &str, credential: Option<&str>| {
+            let mut request = Request::builder()
+                .method(Method::POST)
+                .uri(uri)
+                .header("content-type", "application/json");
+            if let Some(credential) = credential {
+                request = request.header("authorization", format!("Bearer {}", credential));
+            }
+            request.body(Body::from("{}")).unwrap()
+        };
+
+        let response = router
+            .clone()
+            .oneshot(post("/api/epic/auto-process", None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        for uri in [
+            "/api/epic/auto-process",
+            "/api/epic/auto-stop",
+            "/api/epic/epic-1/unblock",
+            "/api/epic/edge-cases/1/resolve",
+        ] {
+            let response = router
+                .clone()
+                .oneshot(post(uri, Some(&viewer)))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN, "{}", uri);
+        }
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/api/epic/auto-status")
+                    .header("authorization", format!("Bearer {}", viewer))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // ==================== Rate Limit Tests ====================
+
+    #[tokio::test]
+    async fn test_rate_limit_returns_429_with_retry_after() {
+        let db = Database::in_memory().await.unwrap();
+        let state = Arc::new(AppState::new(db, None).with_rate_limit(
+            crate::rate_limit::RateLimitConfig {
+                per_key: Some(crate::rate_limit::RateLimit::new(60, 1)),
+                per_ip: Some(crate::rate_limit::RateLimit::new(60, 1)),
+                trusted_proxies: Vec::new(),
+            },
+        ));
+        let router = create_router(state);
+        let get = |uri: &str| {
+            Request::builder()
+                .method(Method::GET)
+                .uri(uri)
+                .header("x-forwarded-for", "203.0.113.7")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = router.clone().oneshot(get("/api/agents")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-ratelimit-remaining"], "0");
+
+        let response = router.clone().oneshot(get("/api/agents")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[axum::http::header::RETRY_AFTER], "1");
+        let body = body_to_string(response.into_body()).await;
+        assert!(body.contains("rate_limited"));
+
+        // Neither forwarded addresses from untrusted peers nor unverified
+        // tokens get a bucket of their own
+        let mut request = get("/api/agents");
+        request
+            .headers_mut()
+            .insert("x-forwarded-for", "198.51.100.1".parse().unwrap());
+        request
+            .headers_mut()
+            .insert("x-api-key", "made-up".parse().unwrap());
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // Webhook deliveries aren't limited
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/webhooks/github")
+                    .header("x-forwarded-for", "203.0.113.7")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_ne!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_buckets_only_verified_tokens() {
+        let db = Database::in_memory().await.unwrap();
+        let state = Arc::new(
+            AppState::new(db, Some("secret-key".to_string())).with_rate_limit(
+                crate::rate_limit::RateLimitConfig {
+                    per_key: Some(crate::rate_limit::RateLimit::new(60, 1)),
+                    per_ip: Some(crate::rate_limit::RateLimit::new(60, 1)),
+                    trusted_proxies: Vec::new(),
+                },
+            ),
+        );
+        let router = create_router(state);
+        let get = |key: &str| {
+            Request::builder()
+                .method(Method::GET)
+                .uri("/api/agents")
+                .header("x-api-key", key)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // Wrong keys share the anonymous bucket
+        let response = router.clone().oneshot(get("guess-1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = router.clone().oneshot(get("guess-2")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let response = router.clone().oneshot(get("secret-key")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // ==================== CORS and Security Header Tests ====================
+
+    #[tokio::test]
+    async fn test_cors_preflight_and_security_headers() {
+        let db = Database::in_memory().await.unwrap();
+        let state = Arc::new(
+            AppState::new(db, Some("secret-key".to_string()))
+                .with_cors(CorsConfig::new(&["https://ui.example.com"])),
+        );
+        let router = create_router(state);
+
+        // Preflights are answered without the API key
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::OPTIONS)
+                    .uri("/api/agents")
+                    .header("origin", "https://ui.example.com")
+                    .header("access-control-request-method", "GET")
+                    .header("access-control-request-headers", "x-api-key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["access-control-allow-origin"],
+            "https://ui.example.com"
+        );
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/agents")
+                    .header("origin", "https://evil.example.com")
+                    .header("x-api-key", "secret-key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key("access-control-allow-origin"));
+        assert_eq!(response.headers()["x-content-type-options"], "nosniff");
+        assert_eq!(response.headers()["x-frame-options"], "DENY");
+    }
+
+    // ==================== Agent CRUD Tests ====================
+
+    #[tokio::test]
+    async fn test_list_agents_empty() {
+        let test_app = setup_app().await;
+
+        let response = test_app
+            .router
             .oneshot(
                 Request::builder()
                     .method(Method::GET)
@@ -3200,7 +5307,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = body_to_string(response.into_body()).await;
-        let agents: Vec<AgentResponse> = serde_json::from_str(&body).unwrap();
+        let agents = serde_json::from_str::<Page<AgentResponse>>(&body).unwrap().items;
         assert!(agents.is_empty());
     }
 
@@ -3571,7 +5678,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = body_to_string(response.into_body()).await;
-        let messages: Vec<MessageResponse> = serde_json::from_str(&body).unwrap();
+        let messages = serde_json::from_str::<Page<MessageResponse>>(&body).unwrap().items;
         assert!(messages.is_empty());
     }
 
@@ -3594,6 +5701,53 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn test_get_agent_events_filtered() {
+        let test_app = setup_app().await;
+
+        let agent = Agent::new(AgentType::StoryDeveloper, "Test task");
+        let agent_id = agent.id.to_string();
+        test_app.state.db.insert_agent(&agent).await.unwrap();
+        test_app
+            .state
+            .db
+            .insert_agent_event(&orchestrate_core::AgentEvent::state_transition(
+                &agent_id,
+                AgentState::Created,
+                AgentState::Initializing,
+            ))
+            .await
+            .unwrap();
+        test_app
+            .state
+            .db
+            .insert_agent_event(&orchestrate_core::AgentEvent::tool_call(
+                &agent_id, "Bash", false, 10,
+            ))
+            .await
+            .unwrap();
+
+        let response = test_app
+            .router
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!("/api/agents/{}/events?event_type=tool_call", agent_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = body_to_string(response.into_body()).await;
+        let events = serde_json::from_str::<Page<AgentEventResponse>>(&body).unwrap().items;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "tool_call");
+        assert_eq!(events[0].summary, "Bash");
+    }
+
     // ==================== System Status Tests ====================
 
     #[tokio::test]
@@ -3714,6 +5868,9 @@ mod tests {
             agent_type: AgentType::StoryDeveloper,
             task: "Valid task".to_string(),
             worktree_id: None,
+            custom_type: None,
+            system_prompt: None,
+            labels: BTreeMap::new(),
         };
         assert!(valid.validate().is_ok());
 
@@ -3722,6 +5879,9 @@ mod tests {
             agent_type: AgentType::StoryDeveloper,
             task: "".to_string(),
             worktree_id: None,
+            custom_type: None,
+            system_prompt: None,
+            labels: BTreeMap::new(),
         };
         assert!(empty_task.validate().is_err());
 
@@ -3730,6 +5890,9 @@ mod tests {
             agent_type: AgentType::StoryDeveloper,
             task: "   \t\n".to_string(),
             worktree_id: None,
+            custom_type: None,
+            system_prompt: None,
+            labels: BTreeMap::new(),
         };
         assert!(whitespace_task.validate().is_err());
 
@@ -3738,6 +5901,9 @@ mod tests {
             agent_type: AgentType::StoryDeveloper,
             task: "x".repeat(MAX_TASK_LENGTH),
             worktree_id: None,
+            custom_type: None,
+            system_prompt: None,
+            labels: BTreeMap::new(),
         };
         assert!(max_task.validate().is_ok());
 
@@ -3746,8 +5912,40 @@ mod tests {
             agent_type: AgentType::StoryDeveloper,
             task: "x".repeat(MAX_TASK_LENGTH + 1),
             worktree_id: None,
+            custom_type: None,
+            system_prompt: None,
+            labels: BTreeMap::new(),
         };
         assert!(over_max_task.validate().is_err());
+
+        // Empty system prompt override
+        let empty_prompt = CreateAgentRequest {
+            agent_type: AgentType::StoryDeveloper,
+            task: "Valid task".to_string(),
+            worktree_id: None,
+            custom_type: None,
+            system_prompt: Some("  ".to_string()),
+            labels: BTreeMap::new(),
+        };
+        assert!(empty_prompt.validate().is_err());
+
+        // System prompt override over max length
+        let long_prompt = CreateAgentRequest {
+            agent_type: AgentType::StoryDeveloper,
+            task: "Valid task".to_string(),
+            worktree_id: None,
+            custom_type: None,
+            system_prompt: Some("x".repeat(MAX_SYSTEM_PROMPT_LENGTH + 1)),
+            labels: BTreeMap::new(),
+        };
+        assert!(long_prompt.validate().is_err());
+
+        // Invalid label key
+        let bad_label = CreateAgentRequest {
+            labels: BTreeMap::from([("team name".to_string(), "web".to_string())]),
+            ..valid
+        };
+        assert!(bad_label.validate().is_err());
     }
 
     // ==================== Response Conversion Tests ====================
@@ -3796,7 +5994,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = body_to_string(response.into_body()).await;
-        let pipelines: Vec<PipelineResponse> = serde_json::from_str(&body).unwrap();
+        let pipelines = serde_json::from_str::<Page<PipelineResponse>>(&body).unwrap().items;
         assert!(pipelines.is_empty());
     }
 
@@ -3920,6 +6118,72 @@ mod tests {
         assert_eq!(resp.definition, "name: test");
     }
 
+    #[tokio::test]
+    async fn test_get_pipeline_graph() {
+        let test_app = setup_app().await;
+
+        let definition = r#"
+name: graph-pipeline
+description: Graph test
+triggers:
+  - event: pull_request.merged
+stages:
+  - name: build
+    agent: regression-tester
+    task: Build
+  - name: deploy
+    agent: deployer
+    task: Deploy
+    depends_on: [build]
+"#;
+        let pipeline = Pipeline::new("graph-pipeline".to_string(), definition.to_string());
+        test_app.state.db.insert_pipeline(&pipeline).await.unwrap();
+
+        let response = test_app
+            .router
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/pipelines/graph-pipeline/graph")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = body_to_string(response.into_body()).await;
+        let graph: PipelineGraph = serde_json::from_str(&body).unwrap();
+        assert_eq!(graph.name, "graph-pipeline");
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(graph.edges[1].from, "stage:build");
+        assert_eq!(graph.edges[1].to, "stage:deploy");
+    }
+
+    #[tokio::test]
+    async fn test_get_pipeline_graph_invalid_definition() {
+        let test_app = setup_app().await;
+
+        let pipeline = Pipeline::new("broken".to_string(), "name: test".to_string());
+        test_app.state.db.insert_pipeline(&pipeline).await.unwrap();
+
+        let response = test_app
+            .router
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/pipelines/broken/graph")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn test_get_pipeline_not_found() {
         let test_app = setup_app().await;
@@ -4053,6 +6317,45 @@ mod tests {
         assert!(run.id > 0);
     }
 
+    #[tokio::test]
+    async fn test_trigger_pipeline_run_with_commit() {
+        let test_app = setup_app().await;
+
+        let pipeline = Pipeline::new("test-pipeline".to_string(), "definition".to_string());
+        test_app.state.db.insert_pipeline(&pipeline).await.unwrap();
+
+        let response = test_app
+            .router
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/pipelines/test-pipeline/run")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"trigger_event":"manual","commit_sha":"abc123","no_cache":true}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = body_to_string(response.into_body()).await;
+        let response: PipelineRunResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(response.commit_sha.as_deref(), Some("abc123"));
+
+        let run = test_app
+            .state
+            .db
+            .get_pipeline_run(response.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(run.commit_sha.as_deref(), Some("abc123"));
+        assert!(!run.use_cache);
+    }
+
     #[tokio::test]
     async fn test_trigger_pipeline_run_nonexistent_pipeline() {
         let test_app = setup_app().await;
@@ -4102,10 +6405,56 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = body_to_string(response.into_body()).await;
-        let runs: Vec<PipelineRunResponse> = serde_json::from_str(&body).unwrap();
+        let runs = serde_json::from_str::<Page<PipelineRunResponse>>(&body).unwrap().items;
         assert_eq!(runs.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_list_pipeline_runs_paginated() {
+        let test_app = setup_app().await;
+
+        let pipeline = Pipeline::new("test-pipeline".to_string(), "definition".to_string());
+        let pipeline_id = test_app.state.db.insert_pipeline(&pipeline).await.unwrap();
+        let mut run_ids = Vec::new();
+        for event in ["event1", "event2", "event3"] {
+            let run = PipelineRun::new(pipeline_id, Some(event.to_string()));
+            run_ids.push(test_app.state.db.insert_pipeline_run(&run).await.unwrap());
+        }
+
+        let get_page = |uri: String| {
+            let router = test_app.router.clone();
+            async move {
+                let response = router
+                    .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = body_to_string(response.into_body()).await;
+                serde_json::from_str::<Page<PipelineRunResponse>>(&body).unwrap()
+            }
+        };
+
+        // Newest first
+        let first = get_page(
+            "/api/pipelines/test-pipeline/runs?limit=2&include_total=true".to_string(),
+        )
+        .await;
+        let ids: Vec<i64> = first.items.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![run_ids[2], run_ids[1]]);
+        assert_eq!(first.total, Some(3));
+
+        let cursor = first.next_cursor.unwrap();
+        let second = get_page(format!(
+            "/api/pipelines/test-pipeline/runs?limit=2&cursor={}",
+            cursor
+        ))
+        .await;
+        let ids: Vec<i64> = second.items.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![run_ids[0]]);
+        assert_eq!(second.next_cursor, None);
+        assert_eq!(second.total, None);
+    }
+
     #[tokio::test]
     async fn test_get_pipeline_run_success() {
         let test_app = setup_app().await;
@@ -4276,20 +6625,83 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = body_to_string(response.into_body()).await;
-        let stages: Vec<PipelineStageResponse> = serde_json::from_str(&body).unwrap();
+        let stages = serde_json::from_str::<Page<PipelineStageResponse>>(&body).unwrap().items;
         assert_eq!(stages.len(), 2);
         assert_eq!(stages[0].stage_name, "build");
         assert_eq!(stages[1].stage_name, "test");
     }
 
-    // ==================== Approval Tests ====================
-
     #[tokio::test]
-    async fn test_list_pending_approvals_empty() {
+    async fn test_get_pipeline_run_saga() {
+        use orchestrate_core::{CompensationActionType, CompensationDefinition};
+
         let test_app = setup_app().await;
 
-        let response = test_app
-            .router
+        let pipeline = Pipeline::new("test-pipeline".to_string(), "definition".to_string());
+        let pipeline_id = test_app.state.db.insert_pipeline(&pipeline).await.unwrap();
+        let run = PipelineRun::new(pipeline_id, None);
+        let run_id = test_app.state.db.insert_pipeline_run(&run).await.unwrap();
+
+        let mut saga = Saga::new(SagaWorkflowType::PipelineRun, run_id.to_string());
+        saga.id = test_app.state.db.insert_saga(&saga).await.unwrap();
+        let definition = CompensationDefinition::new(CompensationActionType::DeleteBranch);
+        let compensation = SagaCompensation::from_definition(saga.id, "push", 1, &definition);
+        test_app
+            .state
+            .db
+            .insert_saga_compensation(&compensation)
+            .await
+            .unwrap();
+
+        let response = test_app
+            .router
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!("/api/pipeline-runs/{}/saga", run_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = body_to_string(response.into_body()).await;
+        let saga: SagaResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(saga.status, "running");
+        assert_eq!(saga.compensations.len(), 1);
+        assert_eq!(saga.compensations[0].action, "delete_branch");
+        assert_eq!(saga.compensations[0].status, "pending");
+    }
+
+    #[tokio::test]
+    async fn test_get_pipeline_run_saga_not_found() {
+        let test_app = setup_app().await;
+
+        let response = test_app
+            .router
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/pipeline-runs/999/saga")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    // ==================== Approval Tests ====================
+
+    #[tokio::test]
+    async fn test_list_pending_approvals_empty() {
+        let test_app = setup_app().await;
+
+        let response = test_app
+            .router
             .oneshot(
                 Request::builder()
                     .method(Method::GET)
@@ -4303,7 +6715,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = body_to_string(response.into_body()).await;
-        let approvals: Vec<ApprovalResponse> = serde_json::from_str(&body).unwrap();
+        let approvals = serde_json::from_str::<Page<ApprovalResponse>>(&body).unwrap().items;
         assert!(approvals.is_empty());
     }
 
@@ -4350,9 +6762,14 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = body_to_string(response.into_body()).await;
-        let approvals: Vec<ApprovalResponse> = serde_json::from_str(&body).unwrap();
+        let approvals = serde_json::from_str::<Page<ApprovalResponse>>(&body).unwrap().items;
         assert_eq!(approvals.len(), 1);
         assert_eq!(approvals[0].status, "pending");
+
+        let items = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        let context = &items["items"][0]["context"];
+        assert_eq!(context["pipeline_name"], "test-pipeline");
+        assert_eq!(context["stage_name"], "deploy");
     }
 
     #[tokio::test]
@@ -4407,6 +6824,64 @@ mod tests {
         assert_eq!(resp.approval_count, 1);
     }
 
+    #[tokio::test]
+    async fn test_approve_approval_as_signed_in_user() {
+        let db = Database::in_memory().await.unwrap();
+        let state = Arc::new(
+            AppState::new(db, None)
+                .with_auth(AuthConfig::new("jwt-secret"))
+                .with_rbac(RbacPolicy::default().assign("user1@example.com", Role::Approver)),
+        );
+        let router = create_api_router(state.clone());
+
+        let pipeline = Pipeline::new("test-pipeline".to_string(), "definition".to_string());
+        let pipeline_id = state.db.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = state
+            .db
+            .insert_pipeline_run(&PipelineRun::new(pipeline_id, None))
+            .await
+            .unwrap();
+        let stage_id = state
+            .db
+            .insert_pipeline_stage(&PipelineStage::new(run_id, "deploy".to_string()))
+            .await
+            .unwrap();
+        let approval = ApprovalRequest::new(
+            stage_id,
+            run_id,
+            "user1@example.com".to_string(),
+            1,
+            None,
+            None,
+        );
+        let approval_id = state
+            .db
+            .create_approval_request(approval)
+            .await
+            .unwrap()
+            .id
+            .unwrap();
+
+        // The session's identity decides, not the approver in the body
+        let token = session_token(&state, "user1@example.com");
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!("/api/approvals/{}/approve", approval_id))
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token))
+                    .body(Body::from(r#"{"approver":"someone-else","comment":"LGTM"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let decisions = state.db.get_approval_decisions(approval_id).await.unwrap();
+        assert_eq!(decisions[0].approver, "user1@example.com");
+    }
+
     #[tokio::test]
     async fn test_approve_approval_empty_approver_fails() {
         let test_app = setup_app().await;
@@ -4550,6 +7025,92 @@ mod tests {
         assert_eq!(resp.rejection_count, 1);
     }
 
+    #[tokio::test]
+    async fn test_delegate_approval() {
+        let test_app = setup_app().await;
+
+        let pipeline = Pipeline::new("test-pipeline".to_string(), "definition".to_string());
+        let pipeline_id = test_app.state.db.insert_pipeline(&pipeline).await.unwrap();
+        let run = PipelineRun::new(pipeline_id, None);
+        let run_id = test_app.state.db.insert_pipeline_run(&run).await.unwrap();
+        let stage = PipelineStage::new(run_id, "deploy".to_string());
+        let stage_id = test_app.state.db.insert_pipeline_stage(&stage).await.unwrap();
+
+        let approval = ApprovalRequest::new(
+            stage_id,
+            run_id,
+            "user1@example.com,user2@example.com".to_string(),
+            1,
+            None,
+            None,
+        );
+        let created = test_app
+            .state
+            .db
+            .create_approval_request(approval)
+            .await
+            .unwrap();
+        let approval_id = created.id.unwrap();
+
+        let delegate = |body: &'static str| {
+            Request::builder()
+                .method(Method::POST)
+                .uri(format!("/api/approvals/{}/delegate", approval_id))
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        // Only required approvers can delegate
+        let response = test_app
+            .router
+            .clone()
+            .oneshot(delegate(
+                r#"{"approver":"intruder@example.com","to":"user3@example.com"}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = test_app
+            .router
+            .clone()
+            .oneshot(delegate(
+                r#"{"approver":"user1@example.com","to":"user3@example.com","comment":"On leave"}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_to_string(response.into_body()).await;
+        let resp: ApprovalResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(resp.status, "delegated");
+        assert_eq!(
+            resp.required_approvers,
+            "user3@example.com,user2@example.com"
+        );
+
+        // Delegated approvals stay in the inbox and can be decided by the delegate
+        let approvals = test_app.state.db.list_pending_approvals().await.unwrap();
+        assert_eq!(approvals.len(), 1);
+
+        let response = test_app
+            .router
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!("/api/approvals/{}/approve", approval_id))
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"approver":"user3@example.com"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_to_string(response.into_body()).await;
+        let resp: ApprovalResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(resp.status, "approved");
+    }
+
     #[tokio::test]
     async fn test_approve_approval_not_found() {
         let test_app = setup_app().await;
@@ -4590,6 +7151,153 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    // ==================== Experiment Tests ====================
+
+    #[tokio::test]
+    async fn test_experiment_lifecycle_and_results() {
+        use orchestrate_core::ExperimentVariant;
+
+        let test_app = setup_app().await;
+        let db = &test_app.state.db;
+        let experiment = Experiment::new(
+            "prompt-v2".to_string(),
+            ExperimentType::Prompt,
+            ExperimentMetric::SuccessRate,
+        );
+        let id = db.create_experiment(&experiment).await.unwrap();
+        let post = |uri: String| {
+            Request::builder()
+                .method(Method::POST)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // Experiments without a control and a treatment can't start
+        let response = test_app
+            .router
+            .clone()
+            .oneshot(post(format!("/api/experiments/{}/start", id)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let control = ExperimentVariant::new(id, "control".to_string(), true);
+        db.create_experiment_variant(&control).await.unwrap();
+        let treatment = ExperimentVariant::new(id, "treatment".to_string(), false);
+        let treatment_id = db.create_experiment_variant(&treatment).await.unwrap();
+
+        let response = test_app
+            .router
+            .clone()
+            .oneshot(post(format!("/api/experiments/{}/start", id)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_to_string(response.into_body()).await;
+        let started: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(started["status"], "running");
+        assert!(started["started_at"].is_string());
+
+        // Variants without observations yet still have results
+        let response = test_app
+            .router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/experiments/{}/results", id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_to_string(response.into_body()).await;
+        let results: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(results["total_samples"], 0);
+
+        for success in [1.0, 1.0, 0.0, 1.0] {
+            let agent_id = uuid::Uuid::new_v4();
+            db.assign_agent_to_experiment(id, agent_id).await.unwrap();
+            db.record_experiment_observation(id, agent_id, "success_rate", success)
+                .await
+                .unwrap();
+        }
+
+        let response = test_app
+            .router
+            .clone()
+            .oneshot(post(format!("/api/experiments/{}/pause", id)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = test_app
+            .router
+            .clone()
+            .oneshot(post(format!("/api/experiments/{}/pause", id)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = test_app
+            .router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/experiments/{}/results", id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = body_to_string(response.into_body()).await;
+        let results: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(results["total_samples"], 4);
+        assert_eq!(results["min_samples"], 100);
+        let variant = &results["variants"][0];
+        assert!(variant["ci_lower"].as_f64().unwrap() <= variant["mean"].as_f64().unwrap());
+        assert!(variant["ci_upper"].as_f64().unwrap() >= variant["mean"].as_f64().unwrap());
+
+        let response = test_app
+            .router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/experiments/{}/daily", id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = body_to_string(response.into_body()).await;
+        let daily: Vec<serde_json::Value> = serde_json::from_str(&body).unwrap();
+        let samples: i64 = daily
+            .iter()
+            .map(|d| d["sample_count"].as_i64().unwrap())
+            .sum();
+        assert_eq!(samples, 4);
+
+        let response = test_app
+            .router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!("/api/experiments/{}/promote", id))
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::json!({ "winner_variant_id": treatment_id }).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let completed = db.get_experiment(id).await.unwrap().unwrap();
+        assert_eq!(completed.status, ExperimentStatus::Completed);
+        assert_eq!(completed.winner_variant_id, Some(treatment_id));
+    }
+
     // ==================== Request Validation Tests ====================
 
     #[test]
@@ -4704,11 +7412,198 @@ mod tests {
         assert_eq!(response.required_count, 1);
         assert_eq!(response.timeout_seconds, Some(3600));
     }
+
+    // ==================== Network Skill Tests ====================
+
+    #[tokio::test]
+    async fn test_register_and_unregister_skill() {
+        let app = setup_app().await;
+
+        let body = serde_json::json!({
+            "name": "audit",
+            "agent_types": ["explorer"],
+            "timeout_secs": 120
+        });
+        let response = app
+            .router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/network/skills")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response = app
+            .router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/network/skills/audit")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let skill: SkillResponse =
+            serde_json::from_str(&body_to_string(response.into_body()).await).unwrap();
+        assert_eq!(skill.agent_types, vec![AgentType::Explorer]);
+        assert_eq!(skill.timeout_secs, Some(120));
+
+        let response = app
+            .router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/api/network/skills/audit")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(app.state.network.skill_registry().await.get("audit").is_none());
+
+        let response = app
+            .router
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/api/network/skills/audit")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_register_skill_rejects_dependency_cycle() {
+        let app = setup_app().await;
+
+        // The default "review" skill already makes code reviewers depend on developers
+        let body = serde_json::json!({
+            "name": "wait_for_review",
+            "agent_types": ["story_developer"],
+            "dependencies": [{"agent_type": "code_reviewer", "state": "completed"}]
+        });
+        let response = app
+            .router
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/network/skills")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(app
+            .state
+            .network
+            .skill_registry()
+            .await
+            .get("wait_for_review")
+            .is_none());
+    }
+
+    // ==================== Artifact Tests ====================
+
+    #[tokio::test]
+    async fn test_artifacts_served_by_signed_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::in_memory().await.unwrap();
+        let store = Arc::new(orchestrate_core::LocalObjectStore::new(
+            dir.path(),
+            "http://localhost:8080",
+            "signing-key",
+        ));
+        let artifacts = ArtifactStore::new(store, db.clone());
+        let state = Arc::new(AppState::new(db, None).with_artifacts(artifacts.clone()));
+        let router = create_api_router(state);
+
+        let agent_id = Uuid::new_v4();
+        let artifact = artifacts
+            .put_agent_artifact(agent_id, "notes.txt", "text/plain", b"all done".to_vec())
+            .await
+            .unwrap();
+
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/api/artifacts?kind=agent_artifact&owner_id={}",
+                        agent_id
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let listed: Page<serde_json::Value> =
+            serde_json::from_str(&body_to_string(response.into_body()).await).unwrap();
+        let listed = listed.items;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0]["key"], artifact.key.as_str());
+        let url = listed[0]["url"].as_str().unwrap();
+        let path = url.strip_prefix("http://localhost:8080").unwrap();
+
+        let response = router
+            .clone()
+            .oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+        assert_eq!(body_to_string(response.into_body()).await, "all done");
+
+        // A tampered signature is refused
+        let tampered = path.replace("signature=", "signature=00");
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri(tampered)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_artifacts_require_storage() {
+        let app = setup_app().await;
+
+        let response = app
+            .router
+            .oneshot(
+                Request::builder()
+                    .uri("/api/artifacts")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 }
 
 // ==================== Security Handlers ====================
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct TriggerScanRequest {
     scan_types: Vec<String>,
     triggered_by: Option<String>,
@@ -4716,7 +7611,7 @@ struct TriggerScanRequest {
     branch: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct SecurityScanResponse {
     id: String,
     scan_types: Vec<String>,
@@ -4726,7 +7621,7 @@ struct SecurityScanResponse {
     summary: ScanSummaryResponse,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct ScanSummaryResponse {
     total_vulnerabilities: usize,
     critical_count: usize,
@@ -4737,13 +7632,13 @@ struct ScanSummaryResponse {
     secrets_count: usize,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct SecurityFixRequest {
     vulnerability_ids: Vec<String>,
     fix_type: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 struct SecurityGateEvalRequest {
     scan_id: String,
     override_reason: Option<String>,
@@ -4751,6 +7646,14 @@ struct SecurityGateEvalRequest {
 }
 
 /// Trigger a security scan
+#[utoipa::path(
+    post,
+    path = "/api/security/scan",
+    tag = "security",
+    summary = "Trigger a security scan",
+    request_body = TriggerScanRequest,
+    responses((status = 200, body = SecurityScanResponse))
+)]
 async fn trigger_security_scan(
     State(state): State<Arc<AppState>>,
     Json(req): Json<TriggerScanRequest>,
@@ -4808,17 +7711,32 @@ async fn trigger_security_scan(
 }
 
 /// List security scans
+#[utoipa::path(
+    get,
+    path = "/api/security/scans",
+    tag = "security",
+    summary = "List security scans",
+    responses((status = 200, body = Page<SecurityScanResponse>))
+)]
 async fn list_security_scans(
     State(_state): State<Arc<AppState>>,
-) -> Result<Json<Vec<SecurityScanResponse>>, ApiError> {
+) -> Result<Json<Page<SecurityScanResponse>>, ApiError> {
     // TODO: Load scans from database
     // let scans = state.db.list_security_scans().await?;
 
     // Return empty list for now
-    Ok(Json(vec![]))
+    Ok(Json(Page::default()))
 }
 
 /// Get a specific security scan
+#[utoipa::path(
+    get,
+    path = "/api/security/scans/{id}",
+    tag = "security",
+    summary = "Get a security scan",
+    params(("id" = String, Path)),
+    responses((status = 200, body = SecurityScanResponse))
+)]
 async fn get_security_scan(
     State(_state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -4831,18 +7749,37 @@ async fn get_security_scan(
 }
 
 /// List vulnerabilities across all scans
+#[utoipa::path(
+    get,
+    path = "/api/security/vulnerabilities",
+    tag = "security",
+    summary = "List vulnerabilities",
+    params(
+        ("severity" = Option<String>, Query),
+        ("auto_fixable" = Option<bool>, Query)
+    ),
+    responses((status = 200, body = Page<serde_json::Value>))
+)]
 async fn list_vulnerabilities(
     State(_state): State<Arc<AppState>>,
     Query(params): Query<std::collections::HashMap<String, String>>,
-) -> Result<Json<Vec<serde_json::Value>>, ApiError> {
+) -> Result<Json<Page<serde_json::Value>>, ApiError> {
     // TODO: Load vulnerabilities from database with filters
     // let severity = params.get("severity");
     // let auto_fixable = params.get("auto_fixable").and_then(|s| s.parse().ok());
 
-    Ok(Json(vec![]))
+    Ok(Json(Page::default()))
 }
 
 /// Apply security fix
+#[utoipa::path(
+    post,
+    path = "/api/security/fix",
+    tag = "security",
+    summary = "Apply a security fix",
+    request_body = SecurityFixRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
 async fn apply_security_fix(
     State(_state): State<Arc<AppState>>,
     Json(req): Json<SecurityFixRequest>,
@@ -4869,8 +7806,24 @@ async fn apply_security_fix(
 }
 
 /// Download security report
+#[utoipa::path(
+    get,
+    path = "/api/security/report",
+    tag = "security",
+    summary = "Download a security report",
+    params(
+        ("format" = Option<String>, Query, description = "`json` (default), `sarif`, or `html`")
+    ),
+    responses(
+        (status = 200, description = "The report", content(
+            (Object = "application/json"),
+            (String = "text/html")
+        )),
+        (status = 303, description = "Redirect to the report in artifact storage")
+    )
+)]
 async fn download_security_report(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Query(params): Query<std::collections::HashMap<String, String>>,
 ) -> Result<Response, ApiError> {
     use orchestrate_core::{ReportFormat, SecurityScan, ScanType};
@@ -4885,6 +7838,18 @@ async fn download_security_report(
     // For now, create a sample scan
     let scan = SecurityScan::new(vec![ScanType::Full], "api");
 
+    // Keep the report in object storage and send the client there
+    if let Some(artifacts) = &state.artifacts {
+        let artifact = artifacts
+            .put_security_report(&scan, format)
+            .await
+            .map_err(|e| ApiError::internal(format!("Failed to store report: {}", e)))?;
+        let url = artifacts
+            .signed_url(&artifact)
+            .map_err(|e| ApiError::internal(format!("Failed to sign URL: {}", e)))?;
+        return Ok(axum::response::Redirect::to(&url).into_response());
+    }
+
     let content = orchestrate_core::security_report::generate_report(&scan, format)
         .map_err(|e| ApiError::internal(e))?;
 
@@ -4902,6 +7867,13 @@ async fn download_security_report(
 }
 
 /// Get security policy
+#[utoipa::path(
+    get,
+    path = "/api/security/policy",
+    tag = "security",
+    summary = "Get the security policy",
+    responses((status = 200, body = serde_json::Value))
+)]
 async fn get_security_policy(
     State(_state): State<Arc<AppState>>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
@@ -4923,6 +7895,14 @@ async fn get_security_policy(
 }
 
 /// Evaluate security gate
+#[utoipa::path(
+    post,
+    path = "/api/security/gate/evaluate",
+    tag = "security",
+    summary = "Evaluate the security gate",
+    request_body = SecurityGateEvalRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
 async fn evaluate_security_gate(
     State(_state): State<Arc<AppState>>,
     Json(req): Json<SecurityGateEvalRequest>,
@@ -4967,3 +7947,179 @@ async fn evaluate_security_gate(
         }
     })))
 }
+
+// ==================== Artifact Handlers ====================
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ArtifactQuery {
+    pub kind: Option<String>,
+    pub owner_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ArtifactResponse {
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub artifact: Artifact,
+    /// Signed download URL
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SignedUrlQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+pub(crate) fn artifact_store(state: &AppState) -> Result<&ArtifactStore, ApiError> {
+    state
+        .artifacts
+        .as_ref()
+        .ok_or_else(|| ApiError::bad_request("Artifact storage is not configured"))
+}
+
+pub(crate) fn artifact_response(
+    artifacts: &ArtifactStore,
+    artifact: Artifact,
+) -> Result<ArtifactResponse, ApiError> {
+    let url = artifacts
+        .signed_url(&artifact)
+        .map_err(|e| ApiError::internal(format!("Failed to sign URL: {}", e)))?;
+    Ok(ArtifactResponse { artifact, url })
+}
+
+/// List stored reports and artifacts with signed download URLs
+#[utoipa::path(
+    get,
+    path = "/api/artifacts",
+    tag = "artifacts",
+    summary = "List artifacts",
+    params(
+        PageParams,
+        ArtifactQuery
+    ),
+    responses((status = 200, body = Page<ArtifactResponse>))
+)]
+async fn list_artifacts(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
+    Query(query): Query<ArtifactQuery>,
+) -> Result<Json<Page<ArtifactResponse>>, ApiError> {
+    use std::str::FromStr;
+
+    let artifacts = artifact_store(&state)?;
+    let kind = query
+        .kind
+        .as_deref()
+        .map(ArtifactKind::from_str)
+        .transpose()
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    let stored = state
+        .db
+        .list_artifacts_page(kind, query.owner_id.as_deref(), &page.query()?)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
+
+    let responses = stored.try_map(|artifact| artifact_response(artifacts, artifact))?;
+    Ok(Json(responses.into()))
+}
+
+/// Get an artifact with a fresh signed download URL
+#[utoipa::path(
+    get,
+    path = "/api/artifacts/{id}",
+    tag = "artifacts",
+    summary = "Get an artifact",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = ArtifactResponse))
+)]
+async fn get_artifact(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<ArtifactResponse>, ApiError> {
+    let artifacts = artifact_store(&state)?;
+    let artifact = state
+        .db
+        .get_artifact(id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Artifact"))?;
+
+    Ok(Json(artifact_response(artifacts, artifact)?))
+}
+
+/// Delete an artifact from object storage
+#[utoipa::path(
+    delete,
+    path = "/api/artifacts/{id}",
+    tag = "artifacts",
+    summary = "Delete an artifact",
+    params(("id" = i64, Path)),
+    responses((status = 204, description = "Artifact deleted"))
+)]
+async fn delete_artifact(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    let artifacts = artifact_store(&state)?;
+    let artifact = state
+        .db
+        .get_artifact(id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Artifact"))?;
+
+    artifacts
+        .delete(&artifact)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to delete artifact: {}", e)))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Serve a locally stored artifact through its signed URL
+#[utoipa::path(
+    get,
+    path = "/artifacts/{key}",
+    tag = "artifacts",
+    summary = "Download an artifact with a signed URL",
+    params(
+        ("key" = String, Path),
+        SignedUrlQuery
+    ),
+    responses((status = 200, description = "The artifact's content"))
+)]
+async fn serve_artifact(
+    State(state): State<Arc<AppState>>,
+    Path(key): Path<String>,
+    Query(query): Query<SignedUrlQuery>,
+) -> Result<Response, ApiError> {
+    let artifacts = artifact_store(&state)?;
+    artifacts
+        .object_store()
+        .verify_signed_url(&key, query.expires, &query.signature)
+        .map_err(|e| ApiError::forbidden(e.to_string()))?;
+
+    let artifact = state
+        .db
+        .get_artifact_by_key(&key)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Artifact"))?;
+    let body = artifacts
+        .get(&artifact)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to read artifact: {}", e)))?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, artifact.content_type.clone()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{}\"", artifact.name),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
@@ -0,0 +1,242 @@
+//! Approval inbox endpoints backing the approvals page
+//!
+//! - GET /api/approvals - Approvals awaiting a decision, with their context
+//! - POST /api/approvals/:id/delegate - Hand an approval to another approver
+//!
+//! Approving and rejecting live with the other approval handlers in `api`.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use orchestrate_core::{ApprovalRequest, ApprovalService, AuditAction, AuditEntry, RowPage};
+use serde::{Deserialize, Serialize};
+use std::path::Path as FsPath;
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::api::{approval_error, ApiError, AppState, ApprovalResponse};
+use crate::auth::Identity;
+use crate::pagination::{Page, PageParams};
+
+/// What an approval is about, as far as it is known
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ApprovalContext {
+    pub pipeline_name: Option<String>,
+    pub stage_name: Option<String>,
+    pub trigger_event: Option<String>,
+    /// Agent whose work awaits approval
+    pub agent_id: Option<String>,
+    pub agent_task: Option<String>,
+    pub branch: Option<String>,
+    pub pr_number: Option<i32>,
+    /// `git diff --shortstat` of the agent's worktree against its base
+    pub diff_summary: Option<String>,
+}
+
+/// An approval of the inbox
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ApprovalInboxItem {
+    #[serde(flatten)]
+    pub approval: ApprovalResponse,
+    pub context: ApprovalContext,
+}
+
+/// Request body to delegate an approval
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DelegateApprovalRequest {
+    /// Approver handing the approval over, ignored for signed-in users
+    #[serde(default)]
+    pub approver: String,
+    /// Approver taking the approval over
+    pub to: String,
+    pub comment: Option<String>,
+}
+
+/// Summarize the changes of a worktree since it branched off the default
+/// branch, including uncommitted ones
+async fn diff_summary(path: &str) -> Option<String> {
+    if !FsPath::new(path).is_dir() {
+        return None;
+    }
+
+    let git = |args: &[&str]| {
+        tokio::process::Command::new("git")
+            .arg("-C")
+            .arg(path)
+            .args(args)
+            .output()
+    };
+    let base = match git(&["merge-base", "HEAD", "origin/HEAD"]).await {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        _ => "HEAD".to_string(),
+    };
+    let output = git(&["diff", "--shortstat", &base]).await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let summary = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Some(if summary.is_empty() {
+        "No changes".to_string()
+    } else {
+        summary
+    })
+}
+
+/// Look up the pipeline, stage, and agent behind an approval
+///
+/// The agent is the one of the approval's stage, or else the one of the
+/// latest stage of the run that had an agent.
+async fn approval_context(
+    state: &AppState,
+    approval: &ApprovalRequest,
+) -> Result<ApprovalContext, ApiError> {
+    let db_error =
+        |e: orchestrate_core::Error| ApiError::internal(format!("Database error: {}", e));
+    let mut context = ApprovalContext::default();
+
+    if let Some(run) = state
+        .db
+        .get_pipeline_run(approval.run_id)
+        .await
+        .map_err(db_error)?
+    {
+        context.trigger_event = run.trigger_event;
+        context.pipeline_name = state
+            .db
+            .get_pipeline(run.pipeline_id)
+            .await
+            .map_err(db_error)?
+            .map(|pipeline| pipeline.name);
+    }
+
+    let stages = state
+        .db
+        .list_pipeline_stages(approval.run_id)
+        .await
+        .map_err(db_error)?;
+    let stage = stages.iter().find(|s| s.id == Some(approval.stage_id));
+    context.stage_name = stage.map(|s| s.stage_name.clone());
+    context.agent_id = stage
+        .and_then(|s| s.agent_id.clone())
+        .or_else(|| stages.iter().rev().find_map(|s| s.agent_id.clone()));
+
+    let agent_id = context
+        .agent_id
+        .as_deref()
+        .and_then(|id| Uuid::parse_str(id).ok());
+    if let Some(agent) = match agent_id {
+        Some(id) => state.db.get_agent(id).await.map_err(db_error)?,
+        None => None,
+    } {
+        let path = match &agent.worktree_id {
+            Some(worktree_id) => state
+                .db
+                .get_worktree_path(worktree_id)
+                .await
+                .map_err(db_error)?,
+            None => None,
+        }
+        .or_else(|| agent.context.working_directory.clone());
+        if let Some(path) = path {
+            context.diff_summary = diff_summary(&path).await;
+        }
+        context.agent_task = Some(agent.task);
+        context.branch = agent.context.branch_name;
+        context.pr_number = agent.context.pr_number;
+    }
+
+    Ok(context)
+}
+
+/// GET /api/approvals - Approvals awaiting a decision, oldest first
+#[utoipa::path(
+    get,
+    path = "/api/approvals",
+    tag = "approvals",
+    summary = "List pending approvals with context",
+    params(PageParams),
+    responses((status = 200, body = Page<ApprovalInboxItem>))
+)]
+pub async fn list_approval_inbox(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<ApprovalInboxItem>>, ApiError> {
+    let approvals = state
+        .db
+        .list_pending_approvals_page(&page.query()?)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
+
+    let mut items = Vec::with_capacity(approvals.items.len());
+    for approval in approvals.items {
+        let context = approval_context(&state, &approval).await?;
+        items.push(ApprovalInboxItem {
+            approval: approval.into(),
+            context,
+        });
+    }
+    Ok(Json(
+        RowPage {
+            items,
+            next: approvals.next,
+            total: approvals.total,
+        }
+        .into(),
+    ))
+}
+
+/// POST /api/approvals/:id/delegate - Replace the approver with another one
+#[utoipa::path(
+    post,
+    path = "/api/approvals/{id}/delegate",
+    tag = "approvals",
+    summary = "Delegate a request to another approver",
+    params(("id" = i64, Path)),
+    request_body = DelegateApprovalRequest,
+    responses((status = 200, body = ApprovalResponse))
+)]
+pub async fn delegate_approval(
+    State(state): State<Arc<AppState>>,
+    identity: Option<Identity>,
+    Path(id): Path<i64>,
+    Json(req): Json<DelegateApprovalRequest>,
+) -> Result<Json<ApprovalResponse>, ApiError> {
+    let from = match identity {
+        Some(identity) => identity.actor(),
+        None if req.approver.trim().is_empty() => {
+            return Err(ApiError::validation("Approver cannot be empty"))
+        }
+        None => req.approver.clone(),
+    };
+    let to = req.to.trim();
+    if to.is_empty() || to.contains(',') {
+        return Err(ApiError::validation("Delegate must be a single approver"));
+    }
+    if to == from {
+        return Err(ApiError::validation("Cannot delegate to yourself"));
+    }
+
+    let approval = ApprovalService::new(state.db.clone())
+        .delegate(id, from.clone(), to.to_string())
+        .await
+        .map_err(approval_error)?;
+
+    let mut entry = AuditEntry::new(
+        from,
+        AuditAction::Custom("approval.delegated".to_string()),
+        "approval",
+        id.to_string(),
+    )
+    .with_detail("run_id", serde_json::json!(approval.run_id))
+    .with_detail("to", serde_json::json!(to));
+    if let Some(comment) = &req.comment {
+        entry = entry.with_detail("comment", serde_json::json!(comment));
+    }
+    let _ = state.db.insert_audit_entry(&entry).await;
+
+    Ok(Json(approval.into()))
+}
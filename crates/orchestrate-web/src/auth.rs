@@ -0,0 +1,858 @@
+//! OIDC login and JWT sessions
+//!
+//! Users sign in with Google, GitHub, or Okta. When the provider redirects
+//! back with an authorization code, the server exchanges it for the user's
+//! profile and issues a signed JWT, sent as `Authorization: Bearer <token>`
+//! on later requests. The identity in the token is the actor recorded in
+//! audit logs and the approver of approval decisions.
+//!
+//! The login state sent through the provider carries a nonce that is also
+//! set as a cookie in the browser starting the login, so a callback with
+//! someone else's code and state can't sign a victim's browser in.
+//!
+//! The shared `ORCHESTRATE_API_KEY` is still accepted for automation, but
+//! carries no user identity.
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Path, Query, State},
+    http::{header, request::Parts, HeaderMap},
+    response::Redirect,
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use orchestrate_core::{AuditAction, AuditEntry};
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::api::{ApiError, AppState};
+
+/// How long a session token is valid by default
+pub const DEFAULT_SESSION_TTL_HOURS: i64 = 12;
+
+/// How long a login may take between redirect and callback
+const STATE_TTL_SECS: i64 = 600;
+
+/// Cookie holding the nonce of the login in progress
+const NONCE_COOKIE: &str = "orchestrate_login_nonce";
+
+const SESSION_AUDIENCE: &str = "orchestrate-session";
+const STATE_AUDIENCE: &str = "orchestrate-oidc-state";
+
+/// Identity provider users can sign in with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OidcProviderKind {
+    Google,
+    #[serde(rename = "github")]
+    GitHub,
+    Okta,
+}
+
+impl OidcProviderKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Google => "google",
+            Self::GitHub => "github",
+            Self::Okta => "okta",
+        }
+    }
+}
+
+impl std::str::FromStr for OidcProviderKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "google" => Ok(Self::Google),
+            "github" => Ok(Self::GitHub),
+            "okta" => Ok(Self::Okta),
+            _ => Err(format!("Unknown identity provider: {}", s)),
+        }
+    }
+}
+
+/// OAuth2 client registration with an identity provider
+#[derive(Debug, Clone)]
+pub struct OidcProvider {
+    pub kind: OidcProviderKind,
+    pub client_id: String,
+    client_secret: SecretString,
+    authorize_url: String,
+    token_url: String,
+    userinfo_url: String,
+    scopes: &'static str,
+}
+
+impl OidcProvider {
+    pub fn google(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            kind: OidcProviderKind::Google,
+            client_id: client_id.into(),
+            client_secret: SecretString::new(client_secret.into()),
+            authorize_url: "https://accounts.google.com/o/oauth2/v2/auth".to_string(),
+            token_url: "https://oauth2.googleapis.com/token".to_string(),
+            userinfo_url: "https://openidconnect.googleapis.com/v1/userinfo".to_string(),
+            scopes: "openid email profile",
+        }
+    }
+
+    /// GitHub OAuth app; GitHub isn't an OIDC provider, so the profile comes
+    /// from the REST API
+    pub fn github(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            kind: OidcProviderKind::GitHub,
+            client_id: client_id.into(),
+            client_secret: SecretString::new(client_secret.into()),
+            authorize_url: "https://github.com/login/oauth/authorize".to_string(),
+            token_url: "https://github.com/login/oauth/access_token".to_string(),
+            userinfo_url: "https://api.github.com/user".to_string(),
+            scopes: "read:user user:email",
+        }
+    }
+
+    /// Okta authorization server, e.g. `https://example.okta.com/oauth2/default`
+    pub fn okta(
+        issuer: &str,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Self {
+        let issuer = issuer.trim_end_matches('/');
+        Self {
+            kind: OidcProviderKind::Okta,
+            client_id: client_id.into(),
+            client_secret: SecretString::new(client_secret.into()),
+            authorize_url: format!("{}/v1/authorize", issuer),
+            token_url: format!("{}/v1/token", issuer),
+            userinfo_url: format!("{}/v1/userinfo", issuer),
+            scopes: "openid email profile",
+        }
+    }
+
+    /// URL to send the browser to for signing in
+    fn login_url(&self, redirect_uri: &str, state: &str) -> String {
+        let query = serde_urlencoded::to_string([
+            ("response_type", "code"),
+            ("client_id", self.client_id.as_str()),
+            ("redirect_uri", redirect_uri),
+            ("scope", self.scopes),
+            ("state", state),
+        ])
+        .unwrap_or_default();
+        format!("{}?{}", self.authorize_url, query)
+    }
+
+    /// Exchange an authorization code for an access token
+    async fn exchange_code(
+        &self,
+        http: &reqwest::Client,
+        code: &str,
+        redirect_uri: &str,
+    ) -> anyhow::Result<String> {
+        #[derive(Deserialize)]
+        struct TokenResponse {
+            access_token: Option<String>,
+            error: Option<String>,
+        }
+
+        let response = http
+            .post(&self.token_url)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.expose_secret().as_str()),
+            ])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Token exchange failed with HTTP {}", response.status());
+        }
+
+        // GitHub reports errors with HTTP 200 and an `error` field
+        let token: TokenResponse = response.json().await?;
+        match (token.access_token, token.error) {
+            (Some(access_token), _) => Ok(access_token),
+            (None, error) => anyhow::bail!(
+                "Token exchange failed: {}",
+                error.unwrap_or_else(|| "no access token".to_string())
+            ),
+        }
+    }
+
+    /// Profile of the user an access token belongs to
+    async fn fetch_identity(
+        &self,
+        http: &reqwest::Client,
+        access_token: &str,
+    ) -> anyhow::Result<Identity> {
+        let response = http
+            .get(&self.userinfo_url)
+            .bearer_auth(access_token)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .header(reqwest::header::USER_AGENT, "orchestrate")
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Userinfo request failed with HTTP {}", response.status());
+        }
+        let profile: serde_json::Value = response.json().await?;
+        Identity::from_profile(self.kind, &profile)
+            .ok_or_else(|| anyhow::anyhow!("Userinfo response has no subject"))
+    }
+}
+
+/// A signed-in user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Identity {
+    /// The provider's stable user id
+    pub subject: String,
+    pub provider: OidcProviderKind,
+    /// Verified email address
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub username: Option<String>,
+}
+
+impl Identity {
+    /// Read an identity from an OIDC userinfo response or GitHub user
+    ///
+    /// OIDC emails are kept only when `email_verified` is true, as access
+    /// is granted by email domain. GitHub only shows verified emails on
+    /// profiles.
+    fn from_profile(provider: OidcProviderKind, profile: &serde_json::Value) -> Option<Self> {
+        let field = |key: &str| profile[key].as_str().map(str::to_string);
+        let subject = field("sub").or_else(|| profile["id"].as_i64().map(|id| id.to_string()))?;
+        let email_verified = match provider {
+            OidcProviderKind::GitHub => true,
+            OidcProviderKind::Google | OidcProviderKind::Okta => {
+                profile["email_verified"].as_bool() == Some(true)
+            }
+        };
+        Some(Self {
+            subject,
+            provider,
+            email: field("email").filter(|_| email_verified),
+            name: field("name"),
+            username: field("preferred_username").or_else(|| field("login")),
+        })
+    }
+
+    /// Name recorded in audit logs and approval decisions
+    pub fn actor(&self) -> String {
+        self.email
+            .clone()
+            .or_else(|| self.username.clone())
+            .unwrap_or_else(|| format!("{}:{}", self.provider.as_str(), self.subject))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionClaims {
+    sub: String,
+    aud: String,
+    iat: i64,
+    exp: i64,
+    provider: OidcProviderKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+}
+
+/// Login state carried through the provider redirect, signed so the
+/// callback can't be forged
+#[derive(Debug, Serialize, Deserialize)]
+struct StateClaims {
+    aud: String,
+    exp: i64,
+    provider: OidcProviderKind,
+    /// Same as the nonce cookie of the browser that started the login
+    nonce: String,
+}
+
+/// Identity providers and session signing
+pub struct AuthConfig {
+    providers: Vec<OidcProvider>,
+    jwt_secret: SecretString,
+    session_ttl: Duration,
+    /// Base URL the server is reached at, for provider redirects
+    public_url: String,
+    /// Email domains allowed to sign in; empty allows any account
+    allowed_domains: Vec<String>,
+    http: reqwest::Client,
+}
+
+impl AuthConfig {
+    pub fn new(jwt_secret: impl Into<String>) -> Self {
+        Self {
+            providers: Vec::new(),
+            jwt_secret: SecretString::new(jwt_secret.into()),
+            session_ttl: Duration::hours(DEFAULT_SESSION_TTL_HOURS),
+            public_url: "http://localhost:8080".to_string(),
+            allowed_domains: Vec::new(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_provider(mut self, provider: OidcProvider) -> Self {
+        self.providers.retain(|p| p.kind != provider.kind);
+        self.providers.push(provider);
+        self
+    }
+
+    pub fn with_public_url(mut self, url: impl Into<String>) -> Self {
+        self.public_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_session_ttl(mut self, ttl: Duration) -> Self {
+        self.session_ttl = ttl;
+        self
+    }
+
+    pub fn with_allowed_domains(mut self, domains: Vec<String>) -> Self {
+        self.allowed_domains = domains
+            .into_iter()
+            .map(|d| d.trim().trim_start_matches('@').to_lowercase())
+            .filter(|d| !d.is_empty())
+            .collect();
+        self
+    }
+
+    /// Configure from the environment, or `None` without `ORCHESTRATE_JWT_SECRET`
+    ///
+    /// Providers are enabled by `ORCHESTRATE_OIDC_{GOOGLE,GITHUB,OKTA}_CLIENT_ID`
+    /// and `_CLIENT_SECRET`, plus `ORCHESTRATE_OIDC_OKTA_ISSUER` for Okta.
+    pub fn from_env() -> Option<Self> {
+        let env = |key: &str| std::env::var(key).ok().filter(|v| !v.is_empty());
+        let client = |name: &str| {
+            Some((
+                env(&format!("ORCHESTRATE_OIDC_{}_CLIENT_ID", name))?,
+                env(&format!("ORCHESTRATE_OIDC_{}_CLIENT_SECRET", name))?,
+            ))
+        };
+
+        let mut config = Self::new(env("ORCHESTRATE_JWT_SECRET")?);
+        if let Some((id, secret)) = client("GOOGLE") {
+            config = config.with_provider(OidcProvider::google(id, secret));
+        }
+        if let Some((id, secret)) = client("GITHUB") {
+            config = config.with_provider(OidcProvider::github(id, secret));
+        }
+        if let (Some((id, secret)), Some(issuer)) =
+            (client("OKTA"), env("ORCHESTRATE_OIDC_OKTA_ISSUER"))
+        {
+            config = config.with_provider(OidcProvider::okta(&issuer, id, secret));
+        }
+        if let Some(url) = env("ORCHESTRATE_PUBLIC_URL") {
+            config = config.with_public_url(url);
+        }
+        if let Some(hours) = env("ORCHESTRATE_SESSION_TTL_HOURS").and_then(|h| h.parse().ok()) {
+            config = config.with_session_ttl(Duration::hours(hours));
+        }
+        if let Some(domains) = env("ORCHESTRATE_OIDC_ALLOWED_DOMAINS") {
+            config = config.with_allowed_domains(domains.split(',').map(str::to_string).collect());
+        }
+        Some(config)
+    }
+
+    pub fn providers(&self) -> impl Iterator<Item = OidcProviderKind> + '_ {
+        self.providers.iter().map(|p| p.kind)
+    }
+
+    fn provider(&self, kind: OidcProviderKind) -> Option<&OidcProvider> {
+        self.providers.iter().find(|p| p.kind == kind)
+    }
+
+    fn redirect_uri(&self, kind: OidcProviderKind) -> String {
+        format!("{}/auth/callback/{}", self.public_url, kind.as_str())
+    }
+
+    fn encoding_key(&self) -> EncodingKey {
+        EncodingKey::from_secret(self.jwt_secret.expose_secret().as_bytes())
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, token: &str, audience: &str) -> Option<T> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_audience(&[audience]);
+        jsonwebtoken::decode::<T>(
+            token,
+            &DecodingKey::from_secret(self.jwt_secret.expose_secret().as_bytes()),
+            &validation,
+        )
+        .ok()
+        .map(|data| data.claims)
+    }
+
+    /// Whether an identity may sign in
+    pub fn is_allowed(&self, identity: &Identity) -> bool {
+        if self.allowed_domains.is_empty() {
+            return true;
+        }
+        identity
+            .email
+            .as_deref()
+            .and_then(|email| email.rsplit_once('@'))
+            .is_some_and(|(_, domain)| self.allowed_domains.contains(&domain.to_lowercase()))
+    }
+
+    /// Issue a session token, returning it with its expiry
+    pub fn issue_token(&self, identity: &Identity) -> anyhow::Result<(String, DateTime<Utc>)> {
+        let now = Utc::now();
+        let expires_at = now + self.session_ttl;
+        let claims = SessionClaims {
+            sub: identity.subject.clone(),
+            aud: SESSION_AUDIENCE.to_string(),
+            iat: now.timestamp(),
+            exp: expires_at.timestamp(),
+            provider: identity.provider,
+            email: identity.email.clone(),
+            name: identity.name.clone(),
+            username: identity.username.clone(),
+        };
+        let token = jsonwebtoken::encode(&Header::default(), &claims, &self.encoding_key())?;
+        Ok((token, expires_at))
+    }
+
+    /// Identity of a valid, unexpired session token
+    pub fn verify_token(&self, token: &str) -> Option<Identity> {
+        let claims: SessionClaims = self.decode(token, SESSION_AUDIENCE)?;
+        Some(Identity {
+            subject: claims.sub,
+            provider: claims.provider,
+            email: claims.email,
+            name: claims.name,
+            username: claims.username,
+        })
+    }
+
+    fn issue_state(&self, provider: OidcProviderKind, nonce: &str) -> anyhow::Result<String> {
+        let claims = StateClaims {
+            aud: STATE_AUDIENCE.to_string(),
+            exp: (Utc::now() + Duration::seconds(STATE_TTL_SECS)).timestamp(),
+            provider,
+            nonce: nonce.to_string(),
+        };
+        Ok(jsonwebtoken::encode(
+            &Header::default(),
+            &claims,
+            &self.encoding_key(),
+        )?)
+    }
+
+    /// Whether a login state is valid for `provider` and the browser's
+    /// nonce cookie
+    fn verify_state(&self, state: &str, provider: OidcProviderKind, nonce: Option<&str>) -> bool {
+        self.decode::<StateClaims>(state, STATE_AUDIENCE)
+            .is_some_and(|claims| {
+                claims.provider == provider && Some(claims.nonce.as_str()) == nonce
+            })
+    }
+
+    /// `Set-Cookie` value of a login nonce, or of its removal when empty
+    fn nonce_cookie(&self, nonce: &str) -> String {
+        let max_age = if nonce.is_empty() { 0 } else { STATE_TTL_SECS };
+        let secure = if self.public_url.starts_with("https://") {
+            "; Secure"
+        } else {
+            ""
+        };
+        // Lax, as the provider's redirect back is a cross-site navigation
+        format!(
+            "{}={}; Path=/auth/callback; Max-Age={}; HttpOnly; SameSite=Lax{}",
+            NONCE_COOKIE, nonce, max_age, secure
+        )
+    }
+}
+
+/// Bearer token or API key sent with a request
+pub(crate) fn request_token(headers: &axum::http::HeaderMap) -> Option<&str> {
+    headers
+        .get("x-api-key")
+        .or_else(|| headers.get(header::AUTHORIZATION))
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.strip_prefix("Bearer ").unwrap_or(s))
+}
+
+/// Extracts the signed-in user, from the auth middleware or the request's
+/// session token; use `Option<Identity>` where sign-in is optional
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for Identity {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(identity) = parts.extensions.get::<Identity>() {
+            return Ok(identity.clone());
+        }
+        state
+            .auth
+            .as_ref()
+            .zip(request_token(&parts.headers))
+            .and_then(|(auth, token)| auth.verify_token(token))
+            .ok_or_else(ApiError::unauthorized)
+    }
+}
+
+/// Value of a request cookie
+fn cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// Create the login router
+pub fn create_auth_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/auth/providers", get(list_providers))
+        .route("/auth/login/:provider", get(login))
+        .route("/auth/callback/:provider", get(callback))
+        .route("/auth/me", get(current_user))
+}
+
+fn auth_config(state: &AppState) -> Result<&AuthConfig, ApiError> {
+    state
+        .auth
+        .as_deref()
+        .ok_or_else(|| ApiError::bad_request("Login is not configured"))
+}
+
+fn configured_provider<'a>(
+    auth: &'a AuthConfig,
+    provider: &str,
+) -> Result<&'a OidcProvider, ApiError> {
+    provider
+        .parse()
+        .ok()
+        .and_then(|kind| auth.provider(kind))
+        .ok_or_else(|| ApiError::not_found("Identity provider"))
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProvidersResponse {
+    pub providers: Vec<OidcProviderKind>,
+}
+
+/// GET /auth/providers - Identity providers users can sign in with
+#[utoipa::path(
+    get,
+    path = "/auth/providers",
+    tag = "auth",
+    summary = "List identity providers",
+    responses((status = 200, body = ProvidersResponse))
+)]
+async fn list_providers(State(state): State<Arc<AppState>>) -> Json<ProvidersResponse> {
+    Json(ProvidersResponse {
+        providers: state
+            .auth
+            .as_ref()
+            .map(|auth| auth.providers().collect())
+            .unwrap_or_default(),
+    })
+}
+
+/// GET /auth/login/:provider - Redirect to the provider's sign-in page
+#[utoipa::path(
+    get,
+    path = "/auth/login/{provider}",
+    tag = "auth",
+    summary = "Redirect to an identity provider's sign-in page",
+    params(("provider" = String, Path)),
+    responses((status = 303, description = "Redirect to the provider's sign-in page"))
+)]
+async fn login(
+    State(state): State<Arc<AppState>>,
+    Path(provider): Path<String>,
+) -> Result<([(header::HeaderName, String); 1], Redirect), ApiError> {
+    let auth = auth_config(&state)?;
+    let provider = configured_provider(auth, &provider)?;
+    let nonce = uuid::Uuid::new_v4().to_string();
+    let login_state = auth
+        .issue_state(provider.kind, &nonce)
+        .map_err(|e| ApiError::internal(format!("Failed to start login: {}", e)))?;
+
+    Ok((
+        [(header::SET_COOKIE, auth.nonce_cookie(&nonce))],
+        Redirect::temporary(&provider.login_url(&auth.redirect_uri(provider.kind), &login_state)),
+    ))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LoginResponse {
+    pub token: String,
+    pub token_type: String,
+    pub expires_at: DateTime<Utc>,
+    pub user: Identity,
+}
+
+/// GET /auth/callback/:provider - Complete sign-in and issue a session token
+#[utoipa::path(
+    get,
+    path = "/auth/callback/{provider}",
+    tag = "auth",
+    summary = "Complete sign-in and issue a session token",
+    params(
+        ("provider" = String, Path),
+        CallbackQuery
+    ),
+    responses((status = 200, body = LoginResponse))
+)]
+async fn callback(
+    State(state): State<Arc<AppState>>,
+    Path(provider): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<CallbackQuery>,
+) -> Result<([(header::HeaderName, String); 1], Json<LoginResponse>), ApiError> {
+    let auth = auth_config(&state)?;
+    let provider = configured_provider(auth, &provider)?;
+
+    if let Some(error) = query.error {
+        return Err(ApiError::forbidden(format!("Sign-in failed: {}", error)));
+    }
+    let (Some(code), Some(login_state)) = (query.code, query.state) else {
+        return Err(ApiError::bad_request("Missing code or state"));
+    };
+    if !auth.verify_state(&login_state, provider.kind, cookie(&headers, NONCE_COOKIE)) {
+        return Err(ApiError::bad_request("Invalid or expired login state"));
+    }
+
+    let redirect_uri = auth.redirect_uri(provider.kind);
+    let access_token = provider
+        .exchange_code(&auth.http, &code, &redirect_uri)
+        .await
+        .map_err(|e| ApiError::forbidden(e.to_string()))?;
+    let identity = provider
+        .fetch_identity(&auth.http, &access_token)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to read user profile: {}", e)))?;
+
+    if !auth.is_allowed(&identity) {
+        let entry = AuditEntry::new(
+            identity.actor(),
+            AuditAction::UserLogin,
+            "user",
+            &identity.subject,
+        )
+        .with_detail("provider", serde_json::json!(provider.kind))
+        .as_failed("Account not allowed to sign in");
+        let _ = state.db.insert_audit_entry(&entry).await;
+        return Err(ApiError::forbidden("Account not allowed to sign in"));
+    }
+
+    let (token, expires_at) = auth
+        .issue_token(&identity)
+        .map_err(|e| ApiError::internal(format!("Failed to issue session: {}", e)))?;
+
+    let entry = AuditEntry::new(
+        identity.actor(),
+        AuditAction::UserLogin,
+        "user",
+        &identity.subject,
+    )
+    .with_detail("provider", serde_json::json!(provider.kind));
+    let _ = state.db.insert_audit_entry(&entry).await;
+
+    Ok((
+        [(header::SET_COOKIE, auth.nonce_cookie(""))],
+        Json(LoginResponse {
+            token,
+            token_type: "Bearer".to_string(),
+            expires_at,
+            user: identity,
+        }),
+    ))
+}
+
+/// GET /auth/me - The signed-in user
+#[utoipa::path(
+    get,
+    path = "/auth/me",
+    tag = "auth",
+    summary = "Get the signed-in user",
+    responses((status = 200, body = Identity))
+)]
+async fn current_user(identity: Identity) -> Json<Identity> {
+    Json(identity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn identity() -> Identity {
+        Identity {
+            subject: "42".to_string(),
+            provider: OidcProviderKind::Google,
+            email: Some("dev@example.com".to_string()),
+            name: Some("Dev".to_string()),
+            username: None,
+        }
+    }
+
+    #[test]
+    fn test_issue_and_verify_token() {
+        let auth = AuthConfig::new("secret");
+        let (token, expires_at) = auth.issue_token(&identity()).unwrap();
+        assert!(expires_at > Utc::now());
+        assert_eq!(auth.verify_token(&token), Some(identity()));
+
+        // Wrong key, expired sessions, and login state aren't sessions
+        assert!(AuthConfig::new("other").verify_token(&token).is_none());
+        let expired = AuthConfig::new("secret").with_session_ttl(Duration::hours(-1));
+        let (token, _) = expired.issue_token(&identity()).unwrap();
+        assert!(auth.verify_token(&token).is_none());
+        let login_state = auth.issue_state(OidcProviderKind::Google, "n").unwrap();
+        assert!(auth.verify_token(&login_state).is_none());
+    }
+
+    #[test]
+    fn test_login_state_is_bound_to_provider_and_browser() {
+        let auth = AuthConfig::new("secret");
+        let login_state = auth
+            .issue_state(OidcProviderKind::GitHub, "nonce-1")
+            .unwrap();
+        assert!(auth.verify_state(&login_state, OidcProviderKind::GitHub, Some("nonce-1")));
+        assert!(!auth.verify_state(&login_state, OidcProviderKind::Okta, Some("nonce-1")));
+        assert!(!auth.verify_state(&login_state, OidcProviderKind::GitHub, Some("nonce-2")));
+        assert!(!auth.verify_state(&login_state, OidcProviderKind::GitHub, None));
+
+        let provider = OidcProvider::okta("https://example.okta.com/oauth2/default/", "id", "s");
+        let url = provider.login_url("http://localhost:8080/auth/callback/okta", &login_state);
+        assert!(url.starts_with("https://example.okta.com/oauth2/default/v1/authorize?"));
+        assert!(url.contains("redirect_uri=http%3A%2F%2Flocalhost%3A8080%2Fauth%2Fcallback%2Fokta"));
+        assert!(url.contains("scope=openid+email+profile"));
+    }
+
+    #[test]
+    fn test_identity_from_profile() {
+        let google = Identity::from_profile(
+            OidcProviderKind::Google,
+            &json!({"sub": "1", "email": "dev@example.com", "email_verified": true, "name": "Dev"}),
+        )
+        .unwrap();
+        assert_eq!(google.actor(), "dev@example.com");
+
+        // Unverified emails could claim any domain
+        for verified in [json!(false), json!("true"), json!(null)] {
+            let okta = Identity::from_profile(
+                OidcProviderKind::Okta,
+                &json!({"sub": "2", "email": "ceo@example.com", "email_verified": verified}),
+            )
+            .unwrap();
+            assert!(okta.email.is_none());
+        }
+
+        let github = Identity::from_profile(
+            OidcProviderKind::GitHub,
+            &json!({"id": 7, "login": "octocat", "email": null}),
+        )
+        .unwrap();
+        assert_eq!(github.subject, "7");
+        assert_eq!(github.actor(), "octocat");
+
+        assert!(Identity::from_profile(OidcProviderKind::Okta, &json!({})).is_none());
+    }
+
+    #[test]
+    fn test_nonce_cookie() {
+        let auth = AuthConfig::new("secret").with_public_url("https://orchestrate.example.com");
+        let set_cookie = auth.nonce_cookie("abc");
+        assert!(set_cookie.starts_with("orchestrate_login_nonce=abc;"));
+        assert!(set_cookie.contains("HttpOnly"));
+        assert!(set_cookie.contains("SameSite=Lax"));
+        assert!(set_cookie.contains("Secure"));
+        assert!(auth.nonce_cookie("").contains("Max-Age=0"));
+        assert!(!AuthConfig::new("secret")
+            .nonce_cookie("abc")
+            .contains("Secure"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            "theme=dark; orchestrate_login_nonce=abc".parse().unwrap(),
+        );
+        assert_eq!(cookie(&headers, NONCE_COOKIE), Some("abc"));
+        headers.clear();
+        assert_eq!(cookie(&headers, NONCE_COOKIE), None);
+    }
+
+    #[tokio::test]
+    async fn test_callback_requires_nonce_cookie() {
+        use axum::body::Body;
+        use axum::http::{Request, StatusCode};
+        use tower::ServiceExt;
+
+        let db = orchestrate_core::Database::in_memory().await.unwrap();
+        let auth = AuthConfig::new("secret").with_provider(OidcProvider::github("id", "s"));
+        let router = crate::create_router(Arc::new(AppState::new(db, None).with_auth(auth)));
+
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/auth/login/github")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+        let set_cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+        assert!(set_cookie.starts_with("orchestrate_login_nonce="));
+        let location = response.headers()[header::LOCATION].to_str().unwrap();
+        let (_, query) = location.split_once('?').unwrap();
+        let params: std::collections::HashMap<String, String> =
+            serde_urlencoded::from_str(query).unwrap();
+
+        // A state sent to another browser is rejected before the code is used
+        let uri = format!("/auth/callback/github?code=c&state={}", params["state"]);
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri(&uri)
+                    .header(header::COOKIE, "orchestrate_login_nonce=other")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_allowed_domains() {
+        let auth = AuthConfig::new("secret").with_allowed_domains(vec!["@Example.com".into()]);
+        assert!(auth.is_allowed(&identity()));
+
+        let mut other = identity();
+        other.email = Some("dev@evil.com".to_string());
+        assert!(!auth.is_allowed(&other));
+        other.email = None;
+        assert!(!auth.is_allowed(&other));
+        assert!(AuthConfig::new("secret").is_allowed(&other));
+    }
+}
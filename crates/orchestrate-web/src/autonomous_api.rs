@@ -24,8 +24,10 @@ use orchestrate_core::{
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::api::{ApiError, AppState};
+use crate::pagination::{Page, PageParams};
 
 // Input validation constants
 const MAX_EPIC_PATTERN_LENGTH: usize = 256;
@@ -56,7 +58,7 @@ pub fn create_autonomous_router() -> Router<Arc<AppState>> {
 // ==================== Request/Response Types ====================
 
 /// Request to start autonomous processing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StartAutoProcessRequest {
     /// Epic ID or pattern to process
     pub epic_pattern: Option<String>,
@@ -65,7 +67,7 @@ pub struct StartAutoProcessRequest {
 }
 
 /// Configuration for autonomous processing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AutoProcessConfig {
     /// Maximum number of concurrent agents
     pub max_agents: Option<u32>,
@@ -80,7 +82,7 @@ pub struct AutoProcessConfig {
 }
 
 /// Response for auto process start
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StartAutoProcessResponse {
     pub session_id: String,
     pub status: String,
@@ -88,7 +90,7 @@ pub struct StartAutoProcessResponse {
 }
 
 /// Current autonomous processing status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AutoProcessStatus {
     /// Session ID if active
     pub session_id: Option<String>,
@@ -115,7 +117,7 @@ pub struct AutoProcessStatus {
 }
 
 /// Generic action response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ActionResponse {
     pub success: bool,
     pub message: String,
@@ -123,7 +125,7 @@ pub struct ActionResponse {
 }
 
 /// Stuck agent response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StuckAgentResponse {
     pub id: i64,
     pub agent_id: String,
@@ -137,7 +139,7 @@ pub struct StuckAgentResponse {
 }
 
 /// Edge case response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct EdgeCaseResponse {
     pub id: i64,
     pub session_id: Option<String>,
@@ -153,7 +155,7 @@ pub struct EdgeCaseResponse {
 }
 
 /// Session response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SessionResponse {
     pub id: String,
     pub state: String,
@@ -166,10 +168,13 @@ pub struct SessionResponse {
     pub stories_completed: u32,
     pub stories_failed: u32,
     pub tokens_used: u64,
+    pub goal: Option<String>,
+    pub plan_revision: Option<u32>,
+    pub plan_progress: Option<f64>,
 }
 
 /// Session metrics response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SessionMetricsResponse {
     pub session_id: String,
     pub stories_completed: u32,
@@ -186,23 +191,21 @@ pub struct SessionMetricsResponse {
 }
 
 /// Query parameters for listing
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
 pub struct ListQuery {
-    pub limit: Option<u32>,
-    pub offset: Option<u32>,
     pub status: Option<String>,
     pub session_id: Option<String>,
 }
 
 /// Resolve edge case request
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct ResolveEdgeCaseRequest {
     pub resolution: String, // auto_resolved, manual_resolved, bypassed
     pub notes: Option<String>,
 }
 
 /// Unblock epic request
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct UnblockRequest {
     pub action: String, // retry, skip, escalate
     pub notes: Option<String>,
@@ -268,6 +271,14 @@ fn validate_auto_process_config(config: &Option<AutoProcessConfig>) -> Result<()
 // ==================== Handlers ====================
 
 /// Start autonomous processing for an epic
+#[utoipa::path(
+    post,
+    path = "/api/epic/auto-process",
+    tag = "autonomous",
+    summary = "Start autonomous processing",
+    request_body = StartAutoProcessRequest,
+    responses((status = 200, body = StartAutoProcessResponse))
+)]
 async fn start_auto_process(
     State(state): State<Arc<AppState>>,
     Json(req): Json<StartAutoProcessRequest>,
@@ -327,6 +338,13 @@ async fn start_auto_process(
 }
 
 /// Get current autonomous processing status
+#[utoipa::path(
+    get,
+    path = "/api/epic/auto-status",
+    tag = "autonomous",
+    summary = "Get autonomous processing status",
+    responses((status = 200, body = AutoProcessStatus))
+)]
 async fn get_auto_status(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<AutoProcessStatus>, ApiError> {
@@ -376,6 +394,13 @@ async fn get_auto_status(
 }
 
 /// Pause autonomous processing
+#[utoipa::path(
+    post,
+    path = "/api/epic/auto-pause",
+    tag = "autonomous",
+    summary = "Pause autonomous processing",
+    responses((status = 200, body = ActionResponse))
+)]
 async fn pause_auto_process(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<ActionResponse>, ApiError> {
@@ -412,6 +437,13 @@ async fn pause_auto_process(
 }
 
 /// Resume autonomous processing
+#[utoipa::path(
+    post,
+    path = "/api/epic/auto-resume",
+    tag = "autonomous",
+    summary = "Resume autonomous processing",
+    responses((status = 200, body = ActionResponse))
+)]
 async fn resume_auto_process(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<ActionResponse>, ApiError> {
@@ -449,6 +481,13 @@ async fn resume_auto_process(
 }
 
 /// Stop autonomous processing
+#[utoipa::path(
+    post,
+    path = "/api/epic/auto-stop",
+    tag = "autonomous",
+    summary = "Stop autonomous processing",
+    responses((status = 200, body = ActionResponse))
+)]
 async fn stop_auto_process(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<ActionResponse>, ApiError> {
@@ -487,43 +526,53 @@ async fn stop_auto_process(
 }
 
 /// List stuck agents
+#[utoipa::path(
+    get,
+    path = "/api/epic/stuck-agents",
+    tag = "autonomous",
+    summary = "List stuck agents",
+    params(
+        ListQuery,
+        PageParams
+    ),
+    responses((status = 200, body = Page<StuckAgentResponse>))
+)]
 async fn list_stuck_agents(
     State(state): State<Arc<AppState>>,
     Query(query): Query<ListQuery>,
-) -> Result<Json<Vec<StuckAgentResponse>>, ApiError> {
-    let detections = if let Some(session_id) = &query.session_id {
-        state
-            .db
-            .get_stuck_detections_for_session(session_id)
-            .await
-            .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
-    } else {
-        state
-            .db
-            .get_all_unresolved_stuck_detections()
-            .await
-            .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
-    };
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<StuckAgentResponse>>, ApiError> {
+    let detections = state
+        .db
+        .list_stuck_detections_page(query.session_id.as_deref(), &page.query()?)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    let responses: Vec<StuckAgentResponse> = detections
-        .into_iter()
-        .map(|d| StuckAgentResponse {
-            id: d.id,
-            agent_id: d.agent_id.clone(),
-            session_id: d.session_id.clone(),
-            stuck_type: d.detection_type.as_str().to_string(),
-            severity: d.severity.as_str().to_string(),
-            details: d.details.clone(),
-            detected_at: d.detected_at.to_rfc3339(),
-            resolved: d.resolved,
-            suggested_action: get_suggested_action(&d.detection_type, &d.severity),
-        })
-        .collect();
-
-    Ok(Json(responses))
+    let responses = detections.map(|d| StuckAgentResponse {
+        id: d.id,
+        agent_id: d.agent_id.clone(),
+        session_id: d.session_id.clone(),
+        stuck_type: d.detection_type.as_str().to_string(),
+        severity: d.severity.as_str().to_string(),
+        details: d.details.clone(),
+        detected_at: d.detected_at.to_rfc3339(),
+        resolved: d.resolved,
+        suggested_action: get_suggested_action(&d.detection_type, &d.severity),
+    });
+
+    Ok(Json(responses.into()))
 }
 
 /// Unblock an epic/session
+#[utoipa::path(
+    post,
+    path = "/api/epic/{id}/unblock",
+    tag = "autonomous",
+    summary = "Unblock an epic",
+    params(("id" = String, Path)),
+    request_body = UnblockRequest,
+    responses((status = 200, body = ActionResponse))
+)]
 async fn unblock_epic(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -591,52 +640,56 @@ async fn unblock_epic(
 }
 
 /// List edge cases
+#[utoipa::path(
+    get,
+    path = "/api/epic/edge-cases",
+    tag = "autonomous",
+    summary = "List edge cases",
+    params(
+        ListQuery,
+        PageParams
+    ),
+    responses((status = 200, body = Page<EdgeCaseResponse>))
+)]
 async fn list_edge_cases(
     State(state): State<Arc<AppState>>,
     Query(query): Query<ListQuery>,
-) -> Result<Json<Vec<EdgeCaseResponse>>, ApiError> {
-    let events = if let Some(session_id) = &query.session_id {
-        state
-            .db
-            .get_edge_case_events_for_session(session_id)
-            .await
-            .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
-    } else if query.status.as_deref() == Some("unresolved") {
-        state
-            .db
-            .get_unresolved_edge_case_events()
-            .await
-            .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
-    } else {
-        // Return all recent edge cases (default to unresolved)
-        state
-            .db
-            .get_unresolved_edge_case_events()
-            .await
-            .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?
-    };
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<EdgeCaseResponse>>, ApiError> {
+    // Without a session, list the unresolved edge cases
+    let events = state
+        .db
+        .list_edge_case_events_page(query.session_id.as_deref(), &page.query()?)
+        .await
+        .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    let responses: Vec<EdgeCaseResponse> = events
-        .into_iter()
-        .map(|e| EdgeCaseResponse {
-            id: e.id,
-            session_id: e.session_id,
-            agent_id: e.agent_id,
-            story_id: e.story_id,
-            edge_case_type: e.edge_case_type.as_str().to_string(),
-            resolution: e.resolution.as_str().to_string(),
-            action_taken: e.action_taken,
-            retry_count: e.retry_count,
-            error_message: e.error_message,
-            detected_at: e.detected_at.to_rfc3339(),
-            resolved_at: e.resolved_at.map(|dt| dt.to_rfc3339()),
-        })
-        .collect();
-
-    Ok(Json(responses))
+    let responses = events.map(|e| EdgeCaseResponse {
+        id: e.id,
+        session_id: e.session_id,
+        agent_id: e.agent_id,
+        story_id: e.story_id,
+        edge_case_type: e.edge_case_type.as_str().to_string(),
+        resolution: e.resolution.as_str().to_string(),
+        action_taken: e.action_taken,
+        retry_count: e.retry_count,
+        error_message: e.error_message,
+        detected_at: e.detected_at.to_rfc3339(),
+        resolved_at: e.resolved_at.map(|dt| dt.to_rfc3339()),
+    });
+
+    Ok(Json(responses.into()))
 }
 
 /// Resolve an edge case
+#[utoipa::path(
+    post,
+    path = "/api/epic/edge-cases/{id}/resolve",
+    tag = "autonomous",
+    summary = "Resolve an edge case",
+    params(("id" = i64, Path)),
+    request_body = ResolveEdgeCaseRequest,
+    responses((status = 200, body = ActionResponse))
+)]
 async fn resolve_edge_case(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
@@ -676,39 +729,57 @@ async fn resolve_edge_case(
 }
 
 /// List autonomous sessions
+#[utoipa::path(
+    get,
+    path = "/api/epic/sessions",
+    tag = "autonomous",
+    summary = "List autonomous sessions",
+    params(
+        ListQuery,
+        PageParams
+    ),
+    responses((status = 200, body = Page<SessionResponse>))
+)]
 async fn list_sessions(
     State(state): State<Arc<AppState>>,
     Query(query): Query<ListQuery>,
-) -> Result<Json<Vec<SessionResponse>>, ApiError> {
-    let limit = query.limit.unwrap_or(50) as i64;
-
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<SessionResponse>>, ApiError> {
     let sessions = state
         .db
-        .list_autonomous_sessions(query.status.as_deref(), Some(limit))
+        .list_autonomous_sessions_page(query.status.as_deref(), &page.query()?)
         .await
         .map_err(|e| ApiError::internal(format!("Database error: {}", e)))?;
 
-    let responses: Vec<SessionResponse> = sessions
-        .into_iter()
-        .map(|s| SessionResponse {
-            id: s.id,
-            state: s.state.as_str().to_string(),
-            current_epic_id: s.current_epic_id,
-            current_story_id: s.current_story_id,
-            started_at: s.started_at.to_rfc3339(),
-            completed_at: s.completed_at.map(|dt| dt.to_rfc3339()),
-            completed_count: s.completed_items.len() as u32,
-            failed_count: s.completed_items.iter().filter(|i| !i.success).count() as u32,
-            stories_completed: s.metrics.stories_completed,
-            stories_failed: s.metrics.stories_failed,
-            tokens_used: s.metrics.tokens_used,
-        })
-        .collect();
-
-    Ok(Json(responses))
+    let responses = sessions.map(|s| SessionResponse {
+        id: s.id,
+        state: s.state.as_str().to_string(),
+        current_epic_id: s.current_epic_id,
+        current_story_id: s.current_story_id,
+        started_at: s.started_at.to_rfc3339(),
+        completed_at: s.completed_at.map(|dt| dt.to_rfc3339()),
+        completed_count: s.completed_items.len() as u32,
+        failed_count: s.completed_items.iter().filter(|i| !i.success).count() as u32,
+        stories_completed: s.metrics.stories_completed,
+        stories_failed: s.metrics.stories_failed,
+        tokens_used: s.metrics.tokens_used,
+        goal: s.plan.as_ref().map(|p| p.goal.clone()),
+        plan_revision: s.plan.as_ref().map(|p| p.revision),
+        plan_progress: s.plan.as_ref().map(|p| p.progress()),
+    });
+
+    Ok(Json(responses.into()))
 }
 
 /// Get session details
+#[utoipa::path(
+    get,
+    path = "/api/epic/sessions/{id}",
+    tag = "autonomous",
+    summary = "Get an autonomous session",
+    params(("id" = String, Path)),
+    responses((status = 200, body = SessionResponse))
+)]
 async fn get_session(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -736,10 +807,21 @@ async fn get_session(
         stories_completed: session.metrics.stories_completed,
         stories_failed: session.metrics.stories_failed,
         tokens_used: session.metrics.tokens_used,
+        goal: session.plan.as_ref().map(|p| p.goal.clone()),
+        plan_revision: session.plan.as_ref().map(|p| p.revision),
+        plan_progress: session.plan.as_ref().map(|p| p.progress()),
     }))
 }
 
 /// Get session metrics
+#[utoipa::path(
+    get,
+    path = "/api/epic/sessions/{id}/metrics",
+    tag = "autonomous",
+    summary = "Get a session's metrics",
+    params(("id" = String, Path)),
+    responses((status = 200, body = SessionMetricsResponse))
+)]
 async fn get_session_metrics(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -796,6 +878,8 @@ fn get_suggested_action(stuck_type: &StuckType, severity: &StuckSeverity) -> Str
         (StuckType::RateLimit, _) => "Wait for rate limit reset".to_string(),
         (StuckType::ContextLimit, _) => "Summarize context and retry".to_string(),
         (StuckType::ErrorLoop, _) => "Fresh retry with different approach".to_string(),
+        (StuckType::NoFileChanges, _) => "Retry with a nudge to make concrete changes".to_string(),
+        (StuckType::TokenBurn, _) => "Summarize context and retry with a narrower task".to_string(),
     }
 }
 
@@ -0,0 +1,518 @@
+//! Epic and story board endpoints backing the kanban board
+//!
+//! - GET /api/board - Epics and their stories, with cost and CI status
+//! - POST /api/epics/:id/transition - Move an epic to another status
+//! - POST /api/stories/:id/transition - Move a story to another status
+//! - POST /api/stories/:id/assign - Assign an agent to a story
+//!
+//! Moving an epic or story into progress spawns the agent of its BMAD phase,
+//! unless an active agent already works on it.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use orchestrate_core::{
+    Agent, AgentContext, AgentType, BmadPhase, CiStatus, Epic, EpicStatus, Story, StoryStatus,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::api::{ApiError, AppState};
+
+/// Query parameters of the board
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BoardQuery {
+    pub epic_id: Option<String>,
+}
+
+/// An epic on the board
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct EpicCard {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    #[schema(value_type = Option<String>)]
+    pub current_phase: Option<BmadPhase>,
+    pub agent_id: Option<String>,
+    pub story_count: usize,
+    pub completed_stories: usize,
+}
+
+/// A story on the board
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StoryCard {
+    pub id: String,
+    pub epic_id: String,
+    pub title: String,
+    pub status: String,
+    pub agent_id: Option<String>,
+    pub agent_state: Option<String>,
+    pub cost_usd: f64,
+    /// Worst status of the latest CI checks, none without checks
+    pub ci_status: Option<String>,
+}
+
+/// Epics and stories of the board
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct Board {
+    pub epics: Vec<EpicCard>,
+    pub stories: Vec<StoryCard>,
+}
+
+/// Request body to move an epic or story
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TransitionRequest {
+    pub status: String,
+}
+
+/// Request body to assign an agent to a story
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssignStoryRequest {
+    /// Agent to assign, none to unassign
+    pub agent_id: Option<String>,
+}
+
+/// Result of moving an epic or story
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransitionResponse {
+    pub id: String,
+    pub status: String,
+    pub agent_id: Option<String>,
+    /// Agent spawned for the new BMAD phase
+    pub spawned_agent_id: Option<String>,
+}
+
+fn db_error(e: orchestrate_core::Error) -> ApiError {
+    ApiError::internal(format!("Database error: {}", e))
+}
+
+/// The most urgent status of a set of CI checks
+fn worst_ci_status(statuses: &[CiStatus]) -> Option<CiStatus> {
+    const URGENCY: [CiStatus; 6] = [
+        CiStatus::Failed,
+        CiStatus::Timeout,
+        CiStatus::Cancelled,
+        CiStatus::Running,
+        CiStatus::Pending,
+        CiStatus::Passed,
+    ];
+    URGENCY.into_iter().find(|status| statuses.contains(status))
+}
+
+/// Whether the agent is still working, so no other one needs spawning
+async fn has_active_agent(state: &AppState, agent_id: Option<Uuid>) -> Result<bool, ApiError> {
+    let Some(id) = agent_id else {
+        return Ok(false);
+    };
+    Ok(state
+        .db
+        .get_agent(id)
+        .await
+        .map_err(db_error)?
+        .is_some_and(|agent| !agent.state.is_terminal()))
+}
+
+async fn spawn_agent(
+    state: &AppState,
+    agent_type: AgentType,
+    task: String,
+    context: AgentContext,
+) -> Result<Agent, ApiError> {
+    let agent = Agent::new(agent_type, task).with_context(context);
+    state.db.insert_agent(&agent).await.map_err(db_error)?;
+    Ok(agent)
+}
+
+/// GET /api/board - Epics and stories, optionally of one epic
+#[utoipa::path(
+    get,
+    path = "/api/board",
+    tag = "board",
+    summary = "Get epics and stories by status",
+    params(BoardQuery),
+    responses((status = 200, body = Board))
+)]
+pub async fn get_board(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<BoardQuery>,
+) -> Result<Json<Board>, ApiError> {
+    let epics: Vec<Epic> = match &query.epic_id {
+        Some(id) => state
+            .db
+            .get_epic(id)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .collect(),
+        None => state.db.list_epics().await.map_err(db_error)?,
+    };
+    let costs: HashMap<String, f64> = state
+        .db
+        .list_story_costs(query.epic_id.as_deref(), None)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|cost| (cost.entity_id, cost.estimated_cost_usd))
+        .collect();
+
+    let mut board = Board {
+        epics: Vec::with_capacity(epics.len()),
+        stories: Vec::new(),
+    };
+    for epic in epics {
+        let stories = state
+            .db
+            .get_stories_for_epic(&epic.id)
+            .await
+            .map_err(db_error)?;
+        board.epics.push(EpicCard {
+            story_count: stories.len(),
+            completed_stories: stories
+                .iter()
+                .filter(|s| s.status == StoryStatus::Completed)
+                .count(),
+            id: epic.id,
+            title: epic.title,
+            status: epic.status.as_str().to_string(),
+            current_phase: epic.current_phase,
+            agent_id: epic.agent_id.map(|id| id.to_string()),
+        });
+
+        for story in stories {
+            let agent_state = match story.agent_id {
+                Some(id) => state
+                    .db
+                    .get_agent(id)
+                    .await
+                    .map_err(db_error)?
+                    .map(|agent| agent.state.as_str().to_string()),
+                None => None,
+            };
+            let checks: Vec<CiStatus> = state
+                .db
+                .get_latest_ci_check_results(&story.id)
+                .await
+                .map_err(db_error)?
+                .into_iter()
+                .map(|check| check.status)
+                .collect();
+            board.stories.push(StoryCard {
+                cost_usd: costs.get(&story.id).copied().unwrap_or(0.0),
+                ci_status: worst_ci_status(&checks).map(|s| s.as_str().to_string()),
+                id: story.id,
+                epic_id: story.epic_id,
+                title: story.title,
+                status: story.status.as_str().to_string(),
+                agent_id: story.agent_id.map(|id| id.to_string()),
+                agent_state,
+            });
+        }
+    }
+
+    Ok(Json(board))
+}
+
+/// POST /api/epics/:id/transition - Move an epic, spawning a BMAD
+/// orchestrator when it starts
+#[utoipa::path(
+    post,
+    path = "/api/epics/{id}/transition",
+    tag = "board",
+    summary = "Move an epic",
+    params(("id" = String, Path)),
+    request_body = TransitionRequest,
+    responses((status = 200, body = TransitionResponse))
+)]
+pub async fn transition_epic(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<TransitionRequest>,
+) -> Result<Json<TransitionResponse>, ApiError> {
+    let status = EpicStatus::from_str(&req.status)
+        .map_err(|_| ApiError::validation(format!("Unknown epic status '{}'", req.status)))?;
+    let mut epic = state
+        .db
+        .get_epic(&id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| ApiError::not_found("Epic"))?;
+
+    let mut spawned = None;
+    match status {
+        EpicStatus::InProgress if !has_active_agent(&state, epic.agent_id).await? => {
+            let agent = spawn_agent(
+                &state,
+                AgentType::BmadOrchestrator,
+                format!("Run the BMAD workflow for epic {}: {}", epic.id, epic.title),
+                AgentContext {
+                    epic_id: Some(epic.id.clone()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            epic.start(agent.id);
+            spawned = Some(agent.id);
+        }
+        EpicStatus::Completed => epic.complete(),
+        EpicStatus::Blocked => epic.block("Blocked from the board"),
+        _ => {
+            epic.status = status;
+            if status == EpicStatus::Pending {
+                epic.current_phase = None;
+            }
+            epic.updated_at = chrono::Utc::now();
+        }
+    }
+    state.db.upsert_epic(&epic).await.map_err(db_error)?;
+
+    Ok(Json(TransitionResponse {
+        id: epic.id,
+        status: epic.status.as_str().to_string(),
+        agent_id: epic.agent_id.map(|id| id.to_string()),
+        spawned_agent_id: spawned.map(|id| id.to_string()),
+    }))
+}
+
+async fn find_story(state: &AppState, id: &str) -> Result<Story, ApiError> {
+    state
+        .db
+        .get_story(id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| ApiError::not_found("Story"))
+}
+
+/// POST /api/stories/:id/transition - Move a story, spawning a story
+/// developer when it starts
+#[utoipa::path(
+    post,
+    path = "/api/stories/{id}/transition",
+    tag = "board",
+    summary = "Move a story",
+    params(("id" = String, Path)),
+    request_body = TransitionRequest,
+    responses((status = 200, body = TransitionResponse))
+)]
+pub async fn transition_story(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<TransitionRequest>,
+) -> Result<Json<TransitionResponse>, ApiError> {
+    let status = StoryStatus::from_str(&req.status)
+        .map_err(|_| ApiError::validation(format!("Unknown story status '{}'", req.status)))?;
+    let story = find_story(&state, &id).await?;
+
+    let mut agent_id = story.agent_id;
+    let mut spawned = None;
+    if status == StoryStatus::InProgress && !has_active_agent(&state, agent_id).await? {
+        let agent = spawn_agent(
+            &state,
+            AgentType::StoryDeveloper,
+            format!(
+                "Implement story {}: {}\n\n{}",
+                story.id,
+                story.title,
+                story
+                    .description
+                    .as_deref()
+                    .unwrap_or("No description provided.")
+            ),
+            AgentContext {
+                epic_id: Some(story.epic_id.clone()),
+                story_id: Some(story.id.clone()),
+                ..Default::default()
+            },
+        )
+        .await?;
+        agent_id = Some(agent.id);
+        spawned = Some(agent.id);
+
+        // Stories in development move their epic into the development phase
+        if let Some(mut epic) = state.db.get_epic(&story.epic_id).await.map_err(db_error)? {
+            if epic.status == EpicStatus::Pending {
+                epic.status = EpicStatus::InProgress;
+                epic.set_phase(BmadPhase::DevelopStories);
+                state.db.upsert_epic(&epic).await.map_err(db_error)?;
+            }
+        }
+    }
+
+    state
+        .db
+        .update_story_status(&story.id, status, agent_id)
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(TransitionResponse {
+        id: story.id,
+        status: status.as_str().to_string(),
+        agent_id: agent_id.map(|id| id.to_string()),
+        spawned_agent_id: spawned.map(|id| id.to_string()),
+    }))
+}
+
+/// POST /api/stories/:id/assign - Assign an existing agent to a story
+#[utoipa::path(
+    post,
+    path = "/api/stories/{id}/assign",
+    tag = "board",
+    summary = "Assign an agent to a story",
+    params(("id" = String, Path)),
+    request_body = AssignStoryRequest,
+    responses((status = 200, body = TransitionResponse))
+)]
+pub async fn assign_story(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<AssignStoryRequest>,
+) -> Result<Json<TransitionResponse>, ApiError> {
+    let story = find_story(&state, &id).await?;
+    let agent_id = match req.agent_id.as_deref().filter(|id| !id.trim().is_empty()) {
+        Some(agent_id) => {
+            let uuid = Uuid::parse_str(agent_id.trim())
+                .map_err(|_| ApiError::bad_request("Invalid UUID format"))?;
+            state
+                .db
+                .get_agent(uuid)
+                .await
+                .map_err(db_error)?
+                .ok_or_else(|| ApiError::not_found("Agent"))?;
+            Some(uuid)
+        }
+        None => None,
+    };
+
+    state
+        .db
+        .update_story_status(&story.id, story.status, agent_id)
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(TransitionResponse {
+        id: story.id,
+        status: story.status.as_str().to_string(),
+        agent_id: agent_id.map(|id| id.to_string()),
+        spawned_agent_id: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orchestrate_core::Database;
+
+    async fn setup() -> Arc<AppState> {
+        let db = Database::in_memory().await.unwrap();
+        db.upsert_epic(&Epic::new("epic-1", "Checkout"))
+            .await
+            .unwrap();
+        db.upsert_story(&Story::new("epic-1.1", "epic-1", "Cart page"))
+            .await
+            .unwrap();
+        db.upsert_story(&Story::new("epic-1.2", "epic-1", "Payment"))
+            .await
+            .unwrap();
+        Arc::new(AppState::new(db, None))
+    }
+
+    fn transition(status: &str) -> Json<TransitionRequest> {
+        Json(TransitionRequest {
+            status: status.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_worst_ci_status() {
+        assert_eq!(worst_ci_status(&[]), None);
+        assert_eq!(
+            worst_ci_status(&[CiStatus::Passed, CiStatus::Running]),
+            Some(CiStatus::Running)
+        );
+        assert_eq!(
+            worst_ci_status(&[CiStatus::Failed, CiStatus::Passed]),
+            Some(CiStatus::Failed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_story_transition_spawns_developer() {
+        let state = setup().await;
+
+        let Json(moved) = transition_story(
+            State(state.clone()),
+            Path("epic-1.1".to_string()),
+            transition("in_progress"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(moved.status, "in_progress");
+        let agent_id = moved.spawned_agent_id.unwrap();
+        let agent = state
+            .db
+            .get_agent(Uuid::parse_str(&agent_id).unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(agent.agent_type, AgentType::StoryDeveloper);
+        assert_eq!(agent.context.story_id.as_deref(), Some("epic-1.1"));
+
+        // The agent is still active, so moving again spawns no other one
+        let Json(moved) = transition_story(
+            State(state.clone()),
+            Path("epic-1.1".to_string()),
+            transition("in_progress"),
+        )
+        .await
+        .unwrap();
+        assert!(moved.spawned_agent_id.is_none());
+        assert_eq!(moved.agent_id, Some(agent_id));
+
+        let Json(board) = get_board(State(state.clone()), Query(BoardQuery { epic_id: None }))
+            .await
+            .unwrap();
+        assert_eq!(board.epics[0].status, "in_progress");
+        assert_eq!(board.epics[0].story_count, 2);
+        assert_eq!(board.stories[0].agent_state.as_deref(), Some("created"));
+
+        let result = transition_story(
+            State(state),
+            Path("epic-1.2".to_string()),
+            transition("done"),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_assign_story_and_transition_epic() {
+        let state = setup().await;
+        let agent = Agent::new(AgentType::StoryDeveloper, "Payment");
+        state.db.insert_agent(&agent).await.unwrap();
+
+        let Json(assigned) = assign_story(
+            State(state.clone()),
+            Path("epic-1.2".to_string()),
+            Json(AssignStoryRequest {
+                agent_id: Some(agent.id.to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(assigned.agent_id, Some(agent.id.to_string()));
+
+        let Json(moved) = transition_epic(
+            State(state.clone()),
+            Path("epic-1".to_string()),
+            transition("in_progress"),
+        )
+        .await
+        .unwrap();
+        assert!(moved.spawned_agent_id.is_some());
+        let epic = state.db.get_epic("epic-1").await.unwrap().unwrap();
+        assert_eq!(epic.current_phase, Some(BmadPhase::CreateBranch));
+    }
+}
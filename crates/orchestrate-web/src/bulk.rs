@@ -0,0 +1,474 @@
+//! Batch operations over agents and pipeline runs
+//!
+//! - POST /api/agents/bulk/spawn - Spawn agents from a list of templates
+//! - POST /api/agents/bulk/terminate - Terminate agents matching a label selector
+//! - POST /api/pipeline-runs/bulk/retry - Retry pipeline runs that failed since a time
+//!
+//! Items are processed independently: one failing doesn't stop the others.
+//! The response lists what succeeded and why each failure failed, so a
+//! request that partially fails still answers `200`.
+
+use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
+use orchestrate_core::{
+    LabelSelector, Pipeline, PipelineDefinition, PipelineRun, PipelineRunStatus, RunAdmission,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::api::{
+    spawn_agent, terminate, AgentResponse, ApiError, AppState, CreateAgentRequest,
+    PipelineRunResponse,
+};
+
+/// Most agents one request may spawn
+pub const MAX_BULK_SPAWN: usize = 100;
+
+fn db_error(e: orchestrate_core::Error) -> ApiError {
+    ApiError::internal(format!("Database error: {}", e))
+}
+
+/// Items that succeeded and items that failed
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkResult<T> {
+    pub succeeded: Vec<T>,
+    pub failed: Vec<BulkFailure>,
+}
+
+impl<T> Default for BulkResult<T> {
+    fn default() -> Self {
+        Self {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl<T> BulkResult<T> {
+    fn fail(&mut self, item: impl Into<String>, error: ApiError) {
+        self.failed.push(BulkFailure {
+            item: item.into(),
+            error,
+        });
+    }
+}
+
+/// An item that failed, with the error its single-item endpoint would return
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkFailure {
+    /// Template index (`templates[2]`), agent ID, or pipeline run ID
+    pub item: String,
+    #[serde(flatten)]
+    pub error: ApiError,
+}
+
+/// Agents to spawn from one template
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SpawnTemplate {
+    /// Fields of `POST /api/agents`
+    #[serde(flatten)]
+    pub agent: CreateAgentRequest,
+    /// Number of agents to spawn from the template
+    #[serde(default = "default_count")]
+    pub count: usize,
+}
+
+fn default_count() -> usize {
+    1
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkSpawnRequest {
+    pub templates: Vec<SpawnTemplate>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkTerminateRequest {
+    /// Label selector, e.g. `team=web,env!=prod`
+    pub selector: String,
+    /// List the agents that would be terminated without terminating them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkRetryRequest {
+    /// Retry runs that failed at or after this time
+    pub since: DateTime<Utc>,
+    /// Only retry runs of this pipeline
+    #[serde(default)]
+    pub pipeline: Option<String>,
+    /// List the runs that would be retried without retrying them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A failed run and the run retrying it
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RetriedRun {
+    pub failed_run: PipelineRunResponse,
+    /// New run, `None` in a dry run
+    pub retry: Option<PipelineRunResponse>,
+}
+
+/// Spawn agents from a list of templates
+#[utoipa::path(
+    post,
+    path = "/api/agents/bulk/spawn",
+    tag = "agents",
+    summary = "Spawn agents from a list of templates",
+    request_body = BulkSpawnRequest,
+    responses((status = 200, body = BulkResult<AgentResponse>))
+)]
+pub async fn bulk_spawn(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<BulkSpawnRequest>,
+) -> Result<Json<BulkResult<AgentResponse>>, ApiError> {
+    if req.templates.is_empty() {
+        return Err(ApiError::validation("At least one template is required"));
+    }
+    if req.templates.iter().any(|t| t.count == 0) {
+        return Err(ApiError::validation("Template count must be at least 1"));
+    }
+    let total: usize = req.templates.iter().map(|t| t.count).sum();
+    if total > MAX_BULK_SPAWN {
+        return Err(ApiError::validation(format!(
+            "Cannot spawn {} agents in one request, the maximum is {}",
+            total, MAX_BULK_SPAWN
+        )));
+    }
+
+    let mut result = BulkResult::default();
+    for (index, template) in req.templates.into_iter().enumerate() {
+        let item = format!("templates[{}]", index);
+        for _ in 0..template.count {
+            match spawn_agent(&state, template.agent.clone()).await {
+                Ok(agent) => result.succeeded.push(agent.into()),
+                Err(e) => result.fail(&item, e),
+            }
+        }
+    }
+    Ok(Json(result))
+}
+
+/// Terminate the running agents matching a label selector
+#[utoipa::path(
+    post,
+    path = "/api/agents/bulk/terminate",
+    tag = "agents",
+    summary = "Terminate agents matching a label selector",
+    request_body = BulkTerminateRequest,
+    responses((status = 200, body = BulkResult<AgentResponse>))
+)]
+pub async fn bulk_terminate(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<BulkTerminateRequest>,
+) -> Result<Json<BulkResult<AgentResponse>>, ApiError> {
+    let selector: LabelSelector = req
+        .selector
+        .parse()
+        .map_err(|e: orchestrate_core::Error| ApiError::validation(e.to_string()))?;
+    // Terminating every agent takes an explicit selector per label
+    if selector.is_empty() {
+        return Err(ApiError::validation("Selector cannot be empty"));
+    }
+
+    let agents = state.db.list_agents().await.map_err(db_error)?;
+    let mut result = BulkResult::default();
+    for agent in agents {
+        if agent.state.is_terminal() || !selector.matches(&agent.labels) {
+            continue;
+        }
+        if req.dry_run {
+            result.succeeded.push(agent.into());
+            continue;
+        }
+        let id = agent.id.to_string();
+        match terminate(&state, agent).await {
+            Ok(agent) => result.succeeded.push(agent.into()),
+            Err(e) => result.fail(id, e),
+        }
+    }
+    Ok(Json(result))
+}
+
+/// Start a new run of each pipeline run that failed since a time, oldest first
+#[utoipa::path(
+    post,
+    path = "/api/pipeline-runs/bulk/retry",
+    tag = "pipelines",
+    summary = "Retry pipeline runs that failed since a time",
+    request_body = BulkRetryRequest,
+    responses((status = 200, body = BulkResult<RetriedRun>))
+)]
+pub async fn bulk_retry_runs(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<BulkRetryRequest>,
+) -> Result<Json<BulkResult<RetriedRun>>, ApiError> {
+    let only_pipeline = match &req.pipeline {
+        Some(name) => Some(
+            state
+                .db
+                .get_pipeline_by_name(name)
+                .await
+                .map_err(db_error)?
+                .ok_or_else(|| ApiError::not_found("Pipeline"))?,
+        ),
+        None => None,
+    };
+
+    let mut failed_runs: Vec<PipelineRun> = state
+        .db
+        .list_pipeline_runs_by_status(PipelineRunStatus::Failed)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .filter(|run| run.completed_at.unwrap_or(run.created_at) >= req.since)
+        .filter(|run| {
+            only_pipeline
+                .as_ref()
+                .is_none_or(|pipeline| pipeline.id == Some(run.pipeline_id))
+        })
+        .collect();
+    failed_runs.sort_by_key(|run| (run.created_at, run.id));
+
+    let mut pipelines: HashMap<i64, Option<Pipeline>> = HashMap::new();
+    let mut result = BulkResult::default();
+    for failed_run in failed_runs {
+        let item = failed_run.id.unwrap_or_default().to_string();
+        if req.dry_run {
+            result.succeeded.push(RetriedRun {
+                failed_run: failed_run.into(),
+                retry: None,
+            });
+            continue;
+        }
+
+        let pipeline = match pipelines.get(&failed_run.pipeline_id) {
+            Some(pipeline) => pipeline.clone(),
+            None => {
+                let pipeline = state
+                    .db
+                    .get_pipeline(failed_run.pipeline_id)
+                    .await
+                    .map_err(db_error)?;
+                pipelines.insert(failed_run.pipeline_id, pipeline.clone());
+                pipeline
+            }
+        };
+        let Some(pipeline) = pipeline else {
+            result.fail(item, ApiError::not_found("Pipeline"));
+            continue;
+        };
+
+        match retry_run(&state, &pipeline, &failed_run).await {
+            Ok(retry) => result.succeeded.push(RetriedRun {
+                failed_run: failed_run.into(),
+                retry: Some(retry.into()),
+            }),
+            Err(e) => result.fail(item, e),
+        }
+    }
+    Ok(Json(result))
+}
+
+/// Admit a new run of a failed run under its pipeline's concurrency limit
+async fn retry_run(
+    state: &AppState,
+    pipeline: &Pipeline,
+    failed_run: &PipelineRun,
+) -> Result<PipelineRun, ApiError> {
+    let concurrency = PipelineDefinition::from_yaml_str(&pipeline.definition)
+        .ok()
+        .and_then(|definition| definition.concurrency);
+
+    let mut run = failed_run.retry();
+    let admission = state
+        .db
+        .admit_pipeline_run(&run, concurrency.as_ref())
+        .await
+        .map_err(db_error)?;
+
+    if let RunAdmission::Rejected {
+        run_id,
+        in_progress,
+    } = admission
+    {
+        return Err(ApiError::conflict(format!(
+            "Pipeline '{}' is at its concurrency limit with {} run(s) in progress; run {} was rejected",
+            pipeline.name, in_progress, run_id
+        )));
+    }
+
+    run.id = Some(admission.run_id());
+    Ok(run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use orchestrate_core::{Agent, AgentState, AgentType, Database};
+    use tower::ServiceExt;
+
+    async fn setup() -> (Database, Router) {
+        let db = Database::in_memory().await.unwrap();
+        let router = crate::create_router(Arc::new(AppState::new(db.clone(), None)));
+        (db, router)
+    }
+
+    async fn post<T: serde::de::DeserializeOwned>(
+        router: &Router,
+        uri: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, Option<T>) {
+        let request = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).ok())
+    }
+
+    #[tokio::test]
+    async fn test_bulk_spawn_reports_failed_templates() {
+        let (db, router) = setup().await;
+
+        let (status, result) = post::<BulkResult<AgentResponse>>(
+            &router,
+            "/api/v1/agents/bulk/spawn",
+            serde_json::json!({
+                "templates": [
+                    {"agent_type": "story_developer", "task": "Fix login", "count": 2,
+                     "labels": {"team": "web"}},
+                    {"agent_type": "story_developer", "task": "  "},
+                    {"agent_type": "code_reviewer", "task": "Review login"}
+                ]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let result = result.unwrap();
+        assert_eq!(result.succeeded.len(), 3);
+        assert_eq!(result.succeeded[0].labels["team"], "web");
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].item, "templates[1]");
+        assert_eq!(result.failed[0].error.code, "validation_error");
+        assert_eq!(db.count_agents().await.unwrap(), 3);
+
+        let (status, _) = post::<serde_json::Value>(
+            &router,
+            "/api/agents/bulk/spawn",
+            serde_json::json!({
+                "templates": [{"agent_type": "story_developer", "task": "Fix", "count": 101}]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_terminate_by_selector() {
+        let (db, router) = setup().await;
+        let mut running = Vec::new();
+        for team in ["web", "web", "api"] {
+            let mut agent =
+                Agent::new(AgentType::StoryDeveloper, "Fix login").with_label("team", team);
+            agent.transition_to(AgentState::Initializing).unwrap();
+            agent.transition_to(AgentState::Running).unwrap();
+            db.insert_agent(&agent).await.unwrap();
+            running.push(agent);
+        }
+        let mut finished = Agent::new(AgentType::StoryDeveloper, "Done").with_label("team", "web");
+        finished.transition_to(AgentState::Terminated).unwrap();
+        db.insert_agent(&finished).await.unwrap();
+
+        let request = serde_json::json!({"selector": "team=web", "dry_run": true});
+        let (_, result) =
+            post::<BulkResult<AgentResponse>>(&router, "/api/agents/bulk/terminate", request).await;
+        assert_eq!(result.unwrap().succeeded.len(), 2);
+        let agent = db.get_agent(running[0].id).await.unwrap().unwrap();
+        assert_eq!(agent.state, AgentState::Running);
+
+        let request = serde_json::json!({"selector": "team=web"});
+        let (status, result) =
+            post::<BulkResult<AgentResponse>>(&router, "/api/agents/bulk/terminate", request).await;
+        assert_eq!(status, StatusCode::OK);
+        let result = result.unwrap();
+        assert_eq!(result.succeeded.len(), 2);
+        assert!(result.failed.is_empty());
+        for agent in &running {
+            let agent = db.get_agent(agent.id).await.unwrap().unwrap();
+            let expected = if agent.labels["team"] == "web" {
+                AgentState::Terminated
+            } else {
+                AgentState::Running
+            };
+            assert_eq!(agent.state, expected);
+        }
+
+        let request = serde_json::json!({"selector": " "});
+        let (status, _) =
+            post::<serde_json::Value>(&router, "/api/agents/bulk/terminate", request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_retry_failed_runs_since() {
+        let (db, router) = setup().await;
+        let definition = "name: deploy\nstages:\n  - name: build\n    agent: story_developer\n";
+        let pipeline_id = db
+            .insert_pipeline(&Pipeline::new("deploy".to_string(), definition.to_string()))
+            .await
+            .unwrap();
+
+        let since = Utc::now() - chrono::Duration::hours(1);
+        let mut old = PipelineRun::new(pipeline_id, Some("push".to_string()));
+        old.mark_failed();
+        old.completed_at = Some(since - chrono::Duration::hours(1));
+        let mut recent =
+            PipelineRun::new(pipeline_id, Some("push".to_string())).with_commit_sha("abc123");
+        recent.mark_failed();
+        let mut succeeded = PipelineRun::new(pipeline_id, None);
+        succeeded.mark_succeeded();
+        let mut recent_id = 0;
+        for run in [&old, &recent, &succeeded] {
+            let id = db.insert_pipeline_run(run).await.unwrap();
+            if run.commit_sha.is_some() {
+                recent_id = id;
+            }
+        }
+
+        let request = serde_json::json!({"since": since, "pipeline": "deploy"});
+        let (status, result) =
+            post::<BulkResult<RetriedRun>>(&router, "/api/pipeline-runs/bulk/retry", request).await;
+        assert_eq!(status, StatusCode::OK);
+        let result = result.unwrap();
+        assert!(result.failed.is_empty());
+        assert_eq!(result.succeeded.len(), 1);
+        assert_eq!(result.succeeded[0].failed_run.id, recent_id);
+        let retry = result.succeeded[0].retry.as_ref().unwrap();
+        assert_ne!(retry.id, recent_id);
+        assert_eq!(retry.status, "pending");
+
+        let retry = db.get_pipeline_run(retry.id).await.unwrap().unwrap();
+        assert_eq!(retry.commit_sha.as_deref(), Some("abc123"));
+
+        let request = serde_json::json!({"since": since, "pipeline": "missing"});
+        let (status, _) =
+            post::<serde_json::Value>(&router, "/api/pipeline-runs/bulk/retry", request).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
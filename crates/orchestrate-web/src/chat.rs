@@ -0,0 +1,349 @@
+//! Chat endpoints backing the web chat with agents
+//!
+//! - GET /api/agent-types - Custom agent types to spawn chats from
+//! - POST /api/agents/:id/message - Send a follow-up instruction
+//! - POST /api/agents/:id/story - Turn the conversation into a story
+//! - POST /api/agents/:id/pr - Queue a pull request of the agent's branch
+//!
+//! Conversations start with POST /api/agents and stream over the WebSocket.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use orchestrate_core::{
+    Agent, AgentState, AgentTypeDefinition, Message, MessageRole, PullRequest, Story,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::api::{ApiError, AppState, MessageResponse};
+
+/// Request body to send an agent a message
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SendMessageRequest {
+    pub content: String,
+}
+
+/// Request body to turn a conversation into a story
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateStoryRequest {
+    pub epic_id: String,
+    /// Defaults to the agent's task
+    pub title: Option<String>,
+}
+
+/// Request body to queue a pull request of an agent's work
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePrRequest {
+    /// Defaults to the agent's task
+    pub title: Option<String>,
+}
+
+/// A story created from a conversation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StoryResponse {
+    pub id: String,
+    pub epic_id: String,
+    pub title: String,
+    pub status: String,
+}
+
+/// A pull request queued from a conversation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QueuedPrResponse {
+    pub id: i64,
+    pub branch_name: String,
+    pub title: Option<String>,
+    pub status: String,
+}
+
+fn db_error(e: orchestrate_core::Error) -> ApiError {
+    ApiError::internal(format!("Database error: {}", e))
+}
+
+async fn find_agent(state: &AppState, id: &str) -> Result<Agent, ApiError> {
+    let uuid = Uuid::parse_str(id).map_err(|_| ApiError::bad_request("Invalid UUID format"))?;
+    state
+        .db
+        .get_agent(uuid)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| ApiError::not_found("Agent"))
+}
+
+/// The agent's last answer, which becomes the story or PR description
+async fn last_answer(state: &AppState, agent: &Agent) -> Result<Option<String>, ApiError> {
+    let messages = state.db.get_messages(agent.id).await.map_err(db_error)?;
+    Ok(messages
+        .into_iter()
+        .rev()
+        .find(|m| m.role == MessageRole::Assistant && !m.content.trim().is_empty())
+        .map(|m| m.content))
+}
+
+/// First line of the task, short enough for a title
+fn title_from_task(task: &str) -> String {
+    let line = task.lines().next().unwrap_or_default().trim();
+    match line.char_indices().nth(100) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+/// GET /api/agent-types - Custom agent types, by name
+#[utoipa::path(
+    get,
+    path = "/api/agent-types",
+    tag = "agents",
+    summary = "List custom agent types",
+    responses((status = 200, body = [Object]))
+)]
+pub async fn list_agent_types(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<AgentTypeDefinition>>, ApiError> {
+    let types = state
+        .db
+        .list_agent_type_definitions()
+        .await
+        .map_err(db_error)?;
+    Ok(Json(types))
+}
+
+/// POST /api/agents/:id/message - Send the agent a user message
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/message",
+    tag = "agents",
+    summary = "Send an agent a message",
+    params(("id" = String, Path)),
+    request_body = SendMessageRequest,
+    responses((status = 200, body = MessageResponse))
+)]
+pub async fn send_message(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<SendMessageRequest>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    let content = req.content.trim();
+    if content.is_empty() {
+        return Err(ApiError::validation("Message cannot be empty"));
+    }
+
+    let agent = find_agent(&state, &id).await?;
+    if !matches!(
+        agent.state,
+        AgentState::WaitingForInput | AgentState::Running | AgentState::Paused
+    ) {
+        return Err(ApiError::conflict(format!(
+            "Agent is in state {:?} and cannot receive messages",
+            agent.state
+        )));
+    }
+
+    let mut message = Message::user(agent.id, content);
+    message.id = state.db.insert_message(&message).await.map_err(db_error)?;
+    Ok(Json(message.into()))
+}
+
+/// POST /api/agents/:id/story - Create a story of the agent's task, described
+/// by its last answer
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/story",
+    tag = "agents",
+    summary = "Create a story from an agent's conversation",
+    params(("id" = String, Path)),
+    request_body = CreateStoryRequest,
+    responses((status = 200, body = StoryResponse))
+)]
+pub async fn create_story(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<CreateStoryRequest>,
+) -> Result<Json<StoryResponse>, ApiError> {
+    let agent = find_agent(&state, &id).await?;
+    state
+        .db
+        .get_epic(&req.epic_id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| ApiError::not_found("Epic"))?;
+
+    let title = req
+        .title
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| title_from_task(&agent.task));
+    let story_id = format!(
+        "{}-{}",
+        req.epic_id,
+        &Uuid::new_v4().simple().to_string()[..8]
+    );
+    let mut story = Story::new(story_id, &req.epic_id, title);
+    story.description = Some(last_answer(&state, &agent).await?.unwrap_or(agent.task));
+    story.agent_id = Some(agent.id);
+    state.db.upsert_story(&story).await.map_err(db_error)?;
+
+    Ok(Json(StoryResponse {
+        id: story.id,
+        epic_id: story.epic_id,
+        title: story.title,
+        status: story.status.as_str().to_string(),
+    }))
+}
+
+/// POST /api/agents/:id/pr - Queue a pull request of the agent's branch,
+/// described by its last answer
+#[utoipa::path(
+    post,
+    path = "/api/agents/{id}/pr",
+    tag = "agents",
+    summary = "Queue a pull request of an agent's branch",
+    params(("id" = String, Path)),
+    request_body = CreatePrRequest,
+    responses((status = 200, body = QueuedPrResponse))
+)]
+pub async fn create_pr(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<CreatePrRequest>,
+) -> Result<Json<QueuedPrResponse>, ApiError> {
+    let agent = find_agent(&state, &id).await?;
+    let branch =
+        agent.context.branch_name.clone().ok_or_else(|| {
+            ApiError::bad_request("Agent has no branch to open a pull request from")
+        })?;
+
+    let title = req
+        .title
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| title_from_task(&agent.task));
+    let mut pr = PullRequest::new(branch).with_title(title);
+    if let Some(worktree_id) = &agent.worktree_id {
+        pr = pr.with_worktree(worktree_id);
+    }
+    if let Some(epic_id) = &agent.context.epic_id {
+        pr = pr.with_epic(epic_id);
+    }
+    pr.body = last_answer(&state, &agent).await?;
+    pr.agent_id = Some(agent.id);
+    pr.id = state.db.insert_pr(&pr).await.map_err(db_error)?;
+
+    Ok(Json(QueuedPrResponse {
+        id: pr.id,
+        branch_name: pr.branch_name,
+        title: pr.title,
+        status: pr.status.as_str().to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orchestrate_core::{AgentType, Database, Epic};
+
+    async fn setup() -> (Arc<AppState>, Agent) {
+        let db = Database::in_memory().await.unwrap();
+        let mut agent = Agent::new(AgentType::Explorer, "Find slow queries\nin the API");
+        agent.context.branch_name = Some("explore/slow-queries".to_string());
+        db.insert_agent(&agent).await.unwrap();
+        db.insert_message(&Message::assistant(
+            agent.id,
+            "The audit log lacks an index",
+        ))
+        .await
+        .unwrap();
+        (Arc::new(AppState::new(db, None)), agent)
+    }
+
+    #[test]
+    fn test_title_from_task() {
+        assert_eq!(title_from_task("Fix login\nDetails"), "Fix login");
+        assert_eq!(title_from_task(&"a".repeat(120)).len(), 103);
+    }
+
+    #[tokio::test]
+    async fn test_send_message() {
+        let (state, agent) = setup().await;
+
+        // Created agents don't take input yet
+        let result = send_message(
+            State(state.clone()),
+            Path(agent.id.to_string()),
+            Json(SendMessageRequest {
+                content: "Also check the schedules".to_string(),
+            }),
+        )
+        .await;
+        assert!(result.is_err());
+
+        let mut running = agent.clone();
+        running.transition_to(AgentState::Initializing).unwrap();
+        running.transition_to(AgentState::Running).unwrap();
+        state.db.update_agent(&running).await.unwrap();
+
+        let Json(message) = send_message(
+            State(state.clone()),
+            Path(agent.id.to_string()),
+            Json(SendMessageRequest {
+                content: "Also check the schedules".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(message.role, "user");
+        assert_eq!(state.db.get_messages(agent.id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_create_story_and_pr() {
+        let (state, agent) = setup().await;
+
+        let result = create_story(
+            State(state.clone()),
+            Path(agent.id.to_string()),
+            Json(CreateStoryRequest {
+                epic_id: "epic-1".to_string(),
+                title: None,
+            }),
+        )
+        .await;
+        assert!(result.is_err());
+
+        state
+            .db
+            .upsert_epic(&Epic::new("epic-1", "Performance"))
+            .await
+            .unwrap();
+        let Json(story) = create_story(
+            State(state.clone()),
+            Path(agent.id.to_string()),
+            Json(CreateStoryRequest {
+                epic_id: "epic-1".to_string(),
+                title: None,
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(story.title, "Find slow queries");
+        let stored = state.db.get_story(&story.id).await.unwrap().unwrap();
+        assert_eq!(
+            stored.description.as_deref(),
+            Some("The audit log lacks an index")
+        );
+
+        let Json(pr) = create_pr(
+            State(state.clone()),
+            Path(agent.id.to_string()),
+            Json(CreatePrRequest {
+                title: Some("Index the audit log".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(pr.branch_name, "explore/slow-queries");
+        assert_eq!(pr.status, "queued");
+    }
+}
@@ -0,0 +1,401 @@
+//! Cost analytics endpoints backing the cost dashboard
+//!
+//! - GET /api/costs/daily - Daily spend and cache hit rate
+//! - GET /api/costs/breakdown - Spend by model, agent type, or epic
+//! - GET /api/costs/budget - Burn-down of the current budget
+//! - PUT /api/costs/budget - Set the budget
+//!
+//! The daily and breakdown endpoints answer with CSV for `format=csv`.
+
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Utc;
+use orchestrate_core::{
+    BudgetBurnDown, BudgetPeriod, CostAnalytics, CostBudget, EntityCostSummary,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::api::{ApiError, AppState};
+
+/// Query parameters of the daily and breakdown endpoints
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CostsQuery {
+    #[serde(default = "default_days")]
+    pub days: i32,
+    /// `model`, `agent_type`, or `epic`
+    pub by: Option<String>,
+    /// `json` or `csv`
+    pub format: Option<String>,
+}
+
+fn default_days() -> i32 {
+    30
+}
+
+impl CostsQuery {
+    fn days(&self) -> Result<i32, ApiError> {
+        if (1..=366).contains(&self.days) {
+            Ok(self.days)
+        } else {
+            Err(ApiError::validation("days must be between 1 and 366"))
+        }
+    }
+
+    fn csv(&self) -> Result<bool, ApiError> {
+        match self.format.as_deref() {
+            None | Some("json") => Ok(false),
+            Some("csv") => Ok(true),
+            Some(other) => Err(ApiError::validation(format!("Unknown format '{}'", other))),
+        }
+    }
+}
+
+/// Spend of one day across models
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct DailySpend {
+    pub date: String,
+    pub cost_usd: f64,
+    pub requests: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_write_tokens: i64,
+    /// Share of input tokens read from the prompt cache
+    pub cache_hit_rate: f64,
+}
+
+/// Request body to set the budget
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetBudgetRequest {
+    #[schema(value_type = String)]
+    pub period: BudgetPeriod,
+    pub amount_usd: f64,
+    pub alert_threshold_percent: Option<i32>,
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_response(filename: &str, body: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+async fn daily_spend(state: &AppState, days: i32) -> Result<Vec<DailySpend>, ApiError> {
+    let usage = state
+        .db
+        .get_daily_token_usage(days)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to get token usage: {}", e)))?;
+
+    let mut by_date: BTreeMap<String, DailySpend> = BTreeMap::new();
+    for row in usage {
+        let day = by_date
+            .entry(row.date.clone())
+            .or_insert_with(|| DailySpend {
+                date: row.date.clone(),
+                ..DailySpend::default()
+            });
+        day.cost_usd += row.estimated_cost_usd.unwrap_or(0.0);
+        day.requests += row.request_count;
+        day.input_tokens += row.total_input_tokens;
+        day.output_tokens += row.total_output_tokens;
+        day.cache_read_tokens += row.total_cache_read_tokens;
+        day.cache_write_tokens += row.total_cache_write_tokens;
+    }
+
+    Ok(by_date
+        .into_values()
+        .map(|mut day| {
+            if day.input_tokens > 0 {
+                day.cache_hit_rate = day.cache_read_tokens as f64 / day.input_tokens as f64;
+            }
+            day
+        })
+        .collect())
+}
+
+/// GET /api/costs/daily - Daily spend, oldest first
+#[utoipa::path(
+    get,
+    path = "/api/costs/daily",
+    tag = "costs",
+    summary = "Get daily spend",
+    params(CostsQuery),
+    responses(
+        (status = 200, description = "Daily spend", content(
+            (Vec<DailySpend> = "application/json"),
+            (String = "text/csv")
+        ))
+    )
+)]
+pub async fn get_daily_costs(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CostsQuery>,
+) -> Result<Response, ApiError> {
+    let days = daily_spend(&state, query.days()?).await?;
+    if !query.csv()? {
+        return Ok(Json(days).into_response());
+    }
+
+    let mut csv = String::from(
+        "date,cost_usd,requests,input_tokens,output_tokens,cache_read_tokens,cache_write_tokens,cache_hit_rate\n",
+    );
+    for day in days {
+        csv.push_str(&format!(
+            "{},{:.4},{},{},{},{},{},{:.4}\n",
+            day.date,
+            day.cost_usd,
+            day.requests,
+            day.input_tokens,
+            day.output_tokens,
+            day.cache_read_tokens,
+            day.cache_write_tokens,
+            day.cache_hit_rate
+        ));
+    }
+    Ok(csv_response("daily-costs.csv", csv))
+}
+
+/// GET /api/costs/breakdown - Spend by model, agent type, or epic, most
+/// expensive first
+#[utoipa::path(
+    get,
+    path = "/api/costs/breakdown",
+    tag = "costs",
+    summary = "Get spend by model, agent type, or epic",
+    params(CostsQuery),
+    responses(
+        (status = 200, description = "Spend per entity", content(
+            ([Object] = "application/json"),
+            (String = "text/csv")
+        ))
+    )
+)]
+pub async fn get_cost_breakdown(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CostsQuery>,
+) -> Result<Response, ApiError> {
+    let days = Some(query.days()?);
+    let by = query.by.as_deref().unwrap_or("model");
+    let costs: Vec<EntityCostSummary> = match by {
+        "model" => state.db.list_model_costs(days).await,
+        "agent_type" => state.db.list_agent_type_costs(days).await,
+        "epic" => state.db.list_epic_costs(days).await,
+        other => {
+            return Err(ApiError::validation(format!(
+                "Unknown breakdown '{}', expected model, agent_type, or epic",
+                other
+            )))
+        }
+    }
+    .map_err(|e| ApiError::internal(format!("Failed to get costs: {}", e)))?;
+
+    if !query.csv()? {
+        return Ok(Json(costs).into_response());
+    }
+
+    let mut csv = format!("{},cost_usd,requests,input_tokens,output_tokens\n", by);
+    for cost in costs {
+        csv.push_str(&format!(
+            "{},{:.4},{},{},{}\n",
+            csv_field(&cost.entity_id),
+            cost.estimated_cost_usd,
+            cost.request_count,
+            cost.total_input_tokens,
+            cost.total_output_tokens
+        ));
+    }
+    Ok(csv_response(&format!("costs-by-{}.csv", by), csv))
+}
+
+async fn burn_down(state: &AppState, budget: &CostBudget) -> Result<BudgetBurnDown, ApiError> {
+    // A month is at most 31 days
+    let spend: HashMap<String, f64> = daily_spend(state, 31)
+        .await?
+        .into_iter()
+        .map(|day| (day.date, day.cost_usd))
+        .collect();
+    Ok(CostAnalytics::burn_down(
+        budget,
+        &spend,
+        Utc::now().date_naive(),
+    ))
+}
+
+/// GET /api/costs/budget - Burn-down of the current budget, null without one
+#[utoipa::path(
+    get,
+    path = "/api/costs/budget",
+    tag = "costs",
+    summary = "Get the budget burn-down",
+    responses((status = 200, body = Object))
+)]
+pub async fn get_budget(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Option<BudgetBurnDown>>, ApiError> {
+    let budget = state
+        .db
+        .get_current_cost_budget()
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to get budget: {}", e)))?;
+
+    Ok(Json(match budget {
+        Some(budget) => Some(burn_down(&state, &budget).await?),
+        None => None,
+    }))
+}
+
+/// PUT /api/costs/budget - Set the budget, effective today
+#[utoipa::path(
+    put,
+    path = "/api/costs/budget",
+    tag = "costs",
+    summary = "Set the budget",
+    request_body = SetBudgetRequest,
+    responses((status = 200, body = Object))
+)]
+pub async fn set_budget(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<SetBudgetRequest>,
+) -> Result<Json<BudgetBurnDown>, ApiError> {
+    if !req.amount_usd.is_finite() || req.amount_usd < 0.0 {
+        return Err(ApiError::validation(
+            "amount_usd must be a non-negative number",
+        ));
+    }
+    let mut budget = CostBudget::new(req.period, req.amount_usd);
+    if let Some(threshold) = req.alert_threshold_percent {
+        if !(1..=100).contains(&threshold) {
+            return Err(ApiError::validation(
+                "alert_threshold_percent must be between 1 and 100",
+            ));
+        }
+        budget = budget.with_alert_threshold(threshold);
+    }
+
+    budget.id = Some(
+        state
+            .db
+            .insert_cost_budget(&budget)
+            .await
+            .map_err(|e| ApiError::internal(format!("Failed to save budget: {}", e)))?,
+    );
+
+    Ok(Json(burn_down(&state, &budget).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orchestrate_core::Database;
+
+    async fn setup_state() -> Arc<AppState> {
+        let db = Database::in_memory().await.unwrap();
+        db.update_daily_token_usage("claude-sonnet-4", 1_000_000, 100_000, 400_000, 0)
+            .await
+            .unwrap();
+        db.update_daily_token_usage("claude-opus-4", 1_000_000, 0, 0, 0)
+            .await
+            .unwrap();
+        Arc::new(AppState::new(db, None))
+    }
+
+    fn query(by: Option<&str>, format: Option<&str>) -> Query<CostsQuery> {
+        Query(CostsQuery {
+            days: 30,
+            by: by.map(String::from),
+            format: format.map(String::from),
+        })
+    }
+
+    async fn body(response: Response) -> String {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_daily_costs_and_csv() {
+        let state = setup_state().await;
+
+        let response = get_daily_costs(State(state.clone()), query(None, None))
+            .await
+            .unwrap();
+        let days: Vec<DailySpend> = serde_json::from_str(&body(response).await).unwrap();
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].requests, 2);
+        assert_eq!(days[0].cache_hit_rate, 0.2);
+
+        let response = get_daily_costs(State(state), query(None, Some("csv")))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv");
+        let csv = body(response).await;
+        assert!(csv.starts_with("date,cost_usd,"));
+        assert_eq!(csv.lines().count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cost_breakdown() {
+        let state = setup_state().await;
+
+        let response = get_cost_breakdown(State(state.clone()), query(Some("model"), None))
+            .await
+            .unwrap();
+        let costs: Vec<EntityCostSummary> = serde_json::from_str(&body(response).await).unwrap();
+        assert_eq!(costs[0].entity_id, "claude-opus-4");
+        assert_eq!(costs[1].entity_id, "claude-sonnet-4");
+
+        let result = get_cost_breakdown(State(state), query(Some("team"), None)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_and_get_budget() {
+        let state = setup_state().await;
+
+        let Json(none) = get_budget(State(state.clone())).await.unwrap();
+        assert!(none.is_none());
+
+        let Json(burn_down) = set_budget(
+            State(state.clone()),
+            Json(SetBudgetRequest {
+                period: BudgetPeriod::Monthly,
+                amount_usd: 500.0,
+                alert_threshold_percent: Some(90),
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(burn_down.spent > 0.0);
+        assert_eq!(burn_down.remaining, 500.0 - burn_down.spent);
+
+        let Json(current) = get_budget(State(state)).await.unwrap();
+        let current = current.unwrap();
+        assert_eq!(current.budget.amount_usd, 500.0);
+        assert_eq!(current.budget.alert_threshold_percent, 90);
+    }
+}
@@ -0,0 +1,333 @@
+//! Custom webhook receiver
+//!
+//! Accepts payloads from arbitrary tools at `/webhooks/custom/<name>` and
+//! queues them as webhook events using the stored definition's field mapping
+//! (see [`orchestrate_core::CustomWebhookDefinition`]).
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use orchestrate_core::{CustomWebhookDefinition, WebhookEvent};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+
+use crate::webhook::{respond, verify_source, verify_token, SourceVerification, WebhookState};
+
+/// Custom webhook handler
+///
+/// Looks up the enabled custom webhook named in the path, verifies the
+/// request with the `custom:<name>` source's signature scheme or else the
+/// definition's token, and queues the mapped event.
+#[utoipa::path(
+    post,
+    path = "/webhooks/custom/{name}",
+    tag = "webhooks",
+    summary = "Receive a custom webhook",
+    params(("name" = String, Path)),
+    request_body(content = String, content_type = "application/json"),
+    responses(
+        (status = 200, description = "Event accepted"),
+        (status = 400, description = "Malformed payload"),
+        (status = 401, description = "Invalid signature")
+    )
+)]
+pub async fn custom_webhook_handler(
+    State(state): State<Arc<WebhookState>>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let webhook = match state.database.get_custom_webhook_by_name(&name).await {
+        Ok(Some(webhook)) if webhook.enabled => webhook,
+        Ok(_) => {
+            debug!(name = %name, "Unknown or disabled custom webhook");
+            return respond(
+                StatusCode::NOT_FOUND,
+                "error",
+                &format!("Unknown custom webhook: {}", name),
+            );
+        }
+        Err(e) => {
+            error!(error = %e, name = %name, "Failed to load custom webhook");
+            return respond(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "error",
+                "Failed to load custom webhook",
+            );
+        }
+    };
+
+    let definition = match CustomWebhookDefinition::from_yaml_str(&webhook.definition) {
+        Ok(definition) => definition,
+        Err(e) => {
+            error!(error = %e, name = %name, "Invalid custom webhook definition");
+            return respond(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "error",
+                "Invalid custom webhook definition",
+            );
+        }
+    };
+
+    // A signature scheme stored for `custom:<name>` replaces the definition's token
+    let source = format!("custom:{}", name);
+    let source_verified = match verify_source(&state.database, &source, &headers, &body).await {
+        SourceVerification::Rejected(response) => return response,
+        SourceVerification::Verified => true,
+        SourceVerification::NotConfigured => false,
+    };
+
+    if let Some(token) = definition.token.as_ref().filter(|_| !source_verified) {
+        match headers.get("x-webhook-token").and_then(|v| v.to_str().ok()) {
+            Some(received) if verify_token(token, received) => {}
+            Some(_) => {
+                error!(name = %name, "Invalid custom webhook token");
+                return respond(StatusCode::UNAUTHORIZED, "error", "Invalid token");
+            }
+            None => {
+                warn!(name = %name, "Missing X-Webhook-Token header");
+                return respond(StatusCode::UNAUTHORIZED, "error", "Missing token");
+            }
+        }
+    }
+
+    let payload: Value = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(e) => {
+            warn!(error = %e, name = %name, "Failed to parse custom webhook payload");
+            return respond(
+                StatusCode::BAD_REQUEST,
+                "error",
+                &format!("Invalid JSON payload: {}", e),
+            );
+        }
+    };
+
+    let (event_type, mapped) = match definition.map_payload(&payload) {
+        Ok(mapped) => mapped,
+        Err(e) => {
+            warn!(error = %e, name = %name, "Custom webhook payload did not map");
+            return respond(StatusCode::UNPROCESSABLE_ENTITY, "error", &e.to_string());
+        }
+    };
+
+    let delivery_id = headers
+        .get("x-webhook-delivery")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| format!("{}-{}", name, s))
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let webhook_event =
+        WebhookEvent::new(delivery_id.clone(), event_type.clone(), mapped.to_string())
+            .with_source(source);
+
+    match state.database.insert_webhook_event(&webhook_event).await {
+        Ok(id) => {
+            info!(
+                event_id = id,
+                delivery_id = %delivery_id,
+                name = %name,
+                event_type = %event_type,
+                "Custom webhook event queued"
+            );
+        }
+        Err(e) => {
+            error!(error = %e, name = %name, "Failed to queue custom webhook event");
+        }
+    }
+
+    respond(StatusCode::OK, "ok", "Webhook received")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhook::WebhookConfig;
+    use axum::{
+        body::Body,
+        http::{Method, Request},
+        routing::post,
+        Router,
+    };
+    use orchestrate_core::{CustomWebhook, Database};
+    use tower::ServiceExt;
+
+    const GRAFANA_YAML: &str = r#"
+name: grafana
+token: grafana-token
+event: grafana_alert
+action: $.status
+fields:
+  rule: $.alerts[0].labels.alertname
+"#;
+
+    async fn create_test_router() -> (Router, Database) {
+        let database = Database::in_memory().await.unwrap();
+        let webhook = CustomWebhook::new("grafana".to_string(), GRAFANA_YAML.to_string());
+        database.insert_custom_webhook(&webhook).await.unwrap();
+
+        let state = Arc::new(WebhookState::new(
+            WebhookConfig::new(None),
+            database.clone(),
+        ));
+        let router = Router::new()
+            .route("/webhooks/custom/:name", post(custom_webhook_handler))
+            .with_state(state);
+        (router, database)
+    }
+
+    fn request(name: &str, token: Option<&str>, payload: &str) -> Request<Body> {
+        let mut builder = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/webhooks/custom/{}", name))
+            .header("content-type", "application/json")
+            .header("x-webhook-delivery", "delivery-1");
+        if let Some(token) = token {
+            builder = builder.header("x-webhook-token", token);
+        }
+        builder.body(Body::from(payload.to_string())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_custom_webhook_queues_mapped_event() {
+        let (router, database) = create_test_router().await;
+
+        let payload = r#"{"status":"firing","alerts":[{"labels":{"alertname":"HighCPU"}}]}"#;
+        let response = router
+            .oneshot(request("grafana", Some("grafana-token"), payload))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let event = database
+            .get_webhook_event_by_delivery_id("grafana-delivery-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.event_type, "grafana_alert");
+
+        let mapped: Value = serde_json::from_str(&event.payload).unwrap();
+        assert_eq!(mapped["action"], "firing");
+        assert_eq!(mapped["rule"], "HighCPU");
+        assert_eq!(mapped["payload"]["status"], "firing");
+    }
+
+    #[tokio::test]
+    async fn test_custom_webhook_event_triggers_pipeline() {
+        let (router, database) = create_test_router().await;
+
+        let definition = r#"
+name: cpu-triage
+description: Triage firing CPU alerts
+triggers:
+  - event: grafana_alert.firing
+    variables:
+      rule: rule
+stages:
+  - name: triage
+    agent: investigator
+    task: Investigate ${rule}
+"#;
+        let pipeline =
+            orchestrate_core::Pipeline::new("cpu-triage".to_string(), definition.to_string());
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        let payload = r#"{"status":"firing","alerts":[{"labels":{"alertname":"HighCPU"}}]}"#;
+        router
+            .oneshot(request("grafana", Some("grafana-token"), payload))
+            .await
+            .unwrap();
+
+        let processor = crate::WebhookProcessor::new(
+            Arc::new(database.clone()),
+            crate::WebhookProcessorConfig::default(),
+        );
+        processor.process_batch().await.unwrap();
+
+        let runs = database.list_pipeline_runs(pipeline_id).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(
+            runs[0].trigger_event.as_deref(),
+            Some("grafana_alert.firing")
+        );
+        assert_eq!(runs[0].variables["rule"], "HighCPU");
+    }
+
+    #[tokio::test]
+    async fn test_custom_webhook_rejects_bad_token() {
+        let (router, _) = create_test_router().await;
+
+        let response = router
+            .clone()
+            .oneshot(request("grafana", Some("wrong"), "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = router
+            .oneshot(request("grafana", None, "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_custom_webhook_unknown_or_disabled() {
+        let (router, database) = create_test_router().await;
+
+        let response = router
+            .clone()
+            .oneshot(request("sentry", None, "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let mut webhook = database
+            .get_custom_webhook_by_name("grafana")
+            .await
+            .unwrap()
+            .unwrap();
+        webhook.enabled = false;
+        database.update_custom_webhook(&webhook).await.unwrap();
+
+        let response = router
+            .oneshot(request("grafana", Some("grafana-token"), "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_custom_webhook_source_signature_replaces_token() {
+        use orchestrate_core::{sign_payload, SignatureScheme, WebhookSource};
+
+        let (router, database) = create_test_router().await;
+        let source = WebhookSource::new("custom:grafana", SignatureScheme::HmacSha256, "hmac-key")
+            .with_header("X-Grafana-Signature");
+        database.insert_webhook_source(&source).await.unwrap();
+
+        let payload = r#"{"status":"firing","alerts":[]}"#;
+        let response = router
+            .clone()
+            .oneshot(request("grafana", Some("grafana-token"), payload))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let signed = Request::builder()
+            .method(Method::POST)
+            .uri("/webhooks/custom/grafana")
+            .header(
+                "x-grafana-signature",
+                sign_payload("hmac-key", payload.as_bytes()),
+            )
+            .body(Body::from(payload))
+            .unwrap();
+        let response = router.oneshot(signed).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
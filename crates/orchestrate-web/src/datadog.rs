@@ -0,0 +1,452 @@
+//! Datadog metrics export
+//!
+//! Periodically pushes the metrics held by [`MetricsCollector`] to the
+//! Datadog series API, so existing Datadog dashboards and monitors can cover
+//! the orchestrator alongside the Prometheus endpoint.
+//!
+//! Metric names keep their Prometheus names with an `orchestrate.` namespace
+//! (`orchestrate_agents_total` becomes `orchestrate.agents_total`) and labels
+//! become tags, so agent counts are tagged `agent_type:` and `state:`, and
+//! token usage `model:` and `direction:`. Gauges are sent as gauges; counters
+//! and histogram counts and sums are sent as counts of the increase since the
+//! previous export, starting from the second export.
+
+use crate::metrics::MetricsCollector;
+use orchestrate_core::{Database, Error, Result};
+use prometheus::proto::{Metric, MetricFamily, MetricType};
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{debug, error, info};
+
+/// Datadog site used when `DD_SITE` is not set
+pub const DEFAULT_DATADOG_SITE: &str = "datadoghq.com";
+
+/// Default interval between exports
+pub const DEFAULT_EXPORT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Datadog exporter configuration
+#[derive(Debug, Clone)]
+pub struct DatadogConfig {
+    /// API key sent in the `DD-API-KEY` header
+    pub api_key: String,
+    /// Series endpoint, derived from the Datadog site
+    pub api_url: String,
+    /// Tags added to every series, e.g. `env:prod`
+    pub tags: Vec<String>,
+    /// Interval between exports
+    pub interval: Duration,
+}
+
+impl DatadogConfig {
+    /// Create a configuration for the default Datadog site
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            api_url: series_url(DEFAULT_DATADOG_SITE),
+            tags: Vec::new(),
+            interval: DEFAULT_EXPORT_INTERVAL,
+        }
+    }
+
+    /// Load configuration from `DD_API_KEY`, `DD_SITE`, and `DD_TAGS`
+    ///
+    /// Returns `None` when `DD_API_KEY` is not set.
+    pub fn from_env() -> Option<Self> {
+        let api_key = std::env::var("DD_API_KEY").ok().filter(|k| !k.is_empty())?;
+        let mut config = Self::new(api_key);
+        if let Ok(site) = std::env::var("DD_SITE") {
+            config = config.with_site(&site);
+        }
+        if let Ok(tags) = std::env::var("DD_TAGS") {
+            for tag in tags.split([',', ' ']).filter(|t| !t.is_empty()) {
+                config = config.with_tag(tag);
+            }
+        }
+        Some(config)
+    }
+
+    /// Send metrics to another Datadog site, e.g. `datadoghq.eu`
+    pub fn with_site(mut self, site: &str) -> Self {
+        self.api_url = series_url(site);
+        self
+    }
+
+    /// Send metrics to a custom series endpoint
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
+    }
+
+    /// Add a tag to every series
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Set the interval between exports
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+fn series_url(site: &str) -> String {
+    format!("https://api.{}/api/v2/series", site)
+}
+
+/// Datadog metric type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatadogMetricType {
+    Count,
+    Gauge,
+}
+
+impl DatadogMetricType {
+    /// Numeric type used by the series API
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::Count => 1,
+            Self::Gauge => 3,
+        }
+    }
+}
+
+impl Serialize for DatadogMetricType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.code())
+    }
+}
+
+/// A single point of a series
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatadogPoint {
+    pub timestamp: i64,
+    pub value: f64,
+}
+
+/// A series submitted to Datadog
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatadogSeries {
+    pub metric: String,
+    #[serde(rename = "type")]
+    pub metric_type: DatadogMetricType,
+    pub points: Vec<DatadogPoint>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct SeriesPayload<'a> {
+    series: &'a [DatadogSeries],
+}
+
+/// Exports [`MetricsCollector`] metrics to Datadog
+pub struct DatadogExporter {
+    collector: Arc<MetricsCollector>,
+    database: Arc<Database>,
+    config: DatadogConfig,
+    http_client: reqwest::Client,
+    /// Cumulative counter values at the previous export, keyed by series
+    previous: Mutex<HashMap<String, f64>>,
+}
+
+impl DatadogExporter {
+    /// Create an exporter for a collector
+    pub fn new(
+        collector: Arc<MetricsCollector>,
+        database: Arc<Database>,
+        config: DatadogConfig,
+    ) -> Self {
+        Self {
+            collector,
+            database,
+            config,
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            previous: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create an exporter with its own collector when `DD_API_KEY` is set
+    pub fn from_env(database: Arc<Database>) -> Option<Self> {
+        let config = DatadogConfig::from_env()?;
+        Some(Self::new(
+            Arc::new(MetricsCollector::default()),
+            database,
+            config,
+        ))
+    }
+
+    /// Run the export loop (blocking)
+    pub async fn run(&self) {
+        info!(
+            api_url = %self.config.api_url,
+            interval_secs = self.config.interval.as_secs(),
+            "Starting Datadog metrics export"
+        );
+
+        loop {
+            match self.export().await {
+                Ok(count) => debug!(series = count, "Exported metrics to Datadog"),
+                Err(e) => error!(error = %e, "Failed to export metrics to Datadog"),
+            }
+
+            sleep(self.config.interval).await;
+        }
+    }
+
+    /// Refresh the collector and submit its metrics, returning the number of series sent
+    pub async fn export(&self) -> Result<usize> {
+        self.collector
+            .refresh(&self.database)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to refresh metrics: {}", e)))?;
+
+        let timestamp = chrono::Utc::now().timestamp();
+        let mut previous = self.previous.lock().unwrap().clone();
+        let series = self.series(&self.collector.metric_families(), timestamp, &mut previous);
+
+        if !series.is_empty() {
+            let response = self
+                .http_client
+                .post(&self.config.api_url)
+                .header("DD-API-KEY", &self.config.api_key)
+                .json(&SeriesPayload { series: &series })
+                .send()
+                .await
+                .map_err(|e| Error::Other(format!("Datadog request failed: {}", e)))?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                return Err(Error::Other(format!(
+                    "Datadog API error {}: {}",
+                    status, body
+                )));
+            }
+        }
+
+        // Only advance the counter baselines once the increases were delivered
+        *self.previous.lock().unwrap() = previous;
+        Ok(series.len())
+    }
+
+    /// Convert metric families to series, updating counter baselines in `previous`
+    fn series(
+        &self,
+        families: &[MetricFamily],
+        timestamp: i64,
+        previous: &mut HashMap<String, f64>,
+    ) -> Vec<DatadogSeries> {
+        let mut series = Vec::new();
+
+        for family in families {
+            let name = metric_name(family.get_name());
+            for metric in family.get_metric() {
+                let tags = self.tags(metric);
+                let mut push = |metric: String, metric_type, value| {
+                    series.push(DatadogSeries {
+                        metric,
+                        metric_type,
+                        points: vec![DatadogPoint { timestamp, value }],
+                        tags: tags.clone(),
+                    })
+                };
+
+                match family.get_field_type() {
+                    MetricType::GAUGE => push(
+                        name.clone(),
+                        DatadogMetricType::Gauge,
+                        metric.get_gauge().get_value(),
+                    ),
+                    MetricType::COUNTER => {
+                        let total = metric.get_counter().get_value();
+                        if let Some(increase) = increase(previous, &name, &tags, total) {
+                            push(name.clone(), DatadogMetricType::Count, increase);
+                        }
+                    }
+                    MetricType::HISTOGRAM => {
+                        let histogram = metric.get_histogram();
+                        for (suffix, total) in [
+                            ("count", histogram.get_sample_count() as f64),
+                            ("sum", histogram.get_sample_sum()),
+                        ] {
+                            let name = format!("{}.{}", name, suffix);
+                            if let Some(increase) = increase(previous, &name, &tags, total) {
+                                push(name, DatadogMetricType::Count, increase);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        series
+    }
+
+    fn tags(&self, metric: &Metric) -> Vec<String> {
+        let mut tags: Vec<String> = metric
+            .get_label()
+            .iter()
+            .map(|label| format!("{}:{}", tag_name(label.get_name()), label.get_value()))
+            .collect();
+        tags.extend(self.config.tags.iter().cloned());
+        tags
+    }
+}
+
+/// Datadog name for a Prometheus metric, namespaced under `orchestrate.`
+fn metric_name(name: &str) -> String {
+    match name.strip_prefix("orchestrate_") {
+        Some(rest) => format!("orchestrate.{}", rest),
+        None => name.to_string(),
+    }
+}
+
+/// Datadog tag name for a Prometheus label
+fn tag_name(label: &str) -> &str {
+    match label {
+        // Agent metrics label the agent type as `type`, a reserved-looking tag
+        "type" => "agent_type",
+        other => other,
+    }
+}
+
+/// Increase of a cumulative value since the previous export
+///
+/// Returns `None` the first time a series is seen, since its earlier history
+/// is unknown. A value lower than the baseline means the counter restarted.
+fn increase(
+    previous: &mut HashMap<String, f64>,
+    name: &str,
+    tags: &[String],
+    total: f64,
+) -> Option<f64> {
+    let key = format!("{}|{}", name, tags.join(","));
+    let baseline = previous.insert(key, total)?;
+    Some(if total >= baseline {
+        total - baseline
+    } else {
+        total
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, http::HeaderMap, routing::post, Json, Router};
+    use serde_json::Value;
+
+    type Requests = Arc<Mutex<Vec<(String, Value)>>>;
+
+    /// Fake Datadog series API recording the API key and body of each request
+    async fn datadog_api() -> (String, Requests) {
+        async fn series(
+            State(requests): State<Requests>,
+            headers: HeaderMap,
+            Json(body): Json<Value>,
+        ) -> axum::http::StatusCode {
+            let api_key = headers
+                .get("dd-api-key")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            requests.lock().unwrap().push((api_key, body));
+            axum::http::StatusCode::ACCEPTED
+        }
+
+        let requests: Requests = Arc::default();
+        let router = Router::new()
+            .route("/api/v2/series", post(series))
+            .with_state(requests.clone());
+        let url = crate::test_support::serve(router).await;
+        (format!("{}/api/v2/series", url), requests)
+    }
+
+    async fn exporter(config: DatadogConfig) -> DatadogExporter {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        DatadogExporter::new(Arc::new(MetricsCollector::new().unwrap()), database, config)
+    }
+
+    fn find<'a>(series: &'a [DatadogSeries], metric: &str) -> &'a DatadogSeries {
+        series.iter().find(|s| s.metric == metric).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_series_tags_and_counter_increases() {
+        let exporter = exporter(DatadogConfig::new("key").with_tag("env:test")).await;
+        let collector = &exporter.collector;
+        let mut previous = HashMap::new();
+
+        collector.set_mttr("critical", 120.0);
+        collector.record_agent_execution("story_developer", 12.0);
+        let series = exporter.series(&collector.metric_families(), 100, &mut previous);
+
+        let mttr = find(&series, "orchestrate.mttr_seconds");
+        assert_eq!(mttr.metric_type, DatadogMetricType::Gauge);
+        assert_eq!(mttr.tags, vec!["severity:critical", "env:test"]);
+        assert_eq!(
+            mttr.points,
+            vec![DatadogPoint {
+                timestamp: 100,
+                value: 120.0
+            }]
+        );
+        // Counters only report increases once a baseline is known
+        assert!(series
+            .iter()
+            .all(|s| s.metric_type == DatadogMetricType::Gauge));
+
+        collector.record_agent_execution("story_developer", 30.0);
+        let series = exporter.series(&collector.metric_families(), 160, &mut previous);
+
+        let count = find(&series, "orchestrate.agent_execution_seconds.count");
+        assert_eq!(count.metric_type, DatadogMetricType::Count);
+        assert_eq!(count.tags, vec!["agent_type:story_developer", "env:test"]);
+        assert_eq!(count.points[0].value, 1.0);
+        assert_eq!(
+            find(&series, "orchestrate.agent_execution_seconds.sum").points[0].value,
+            30.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_posts_series() {
+        let (url, requests) = datadog_api().await;
+        let exporter = exporter(DatadogConfig::new("dd-key").with_api_url(url)).await;
+
+        exporter.collector.record_error("timeout");
+        assert!(exporter.export().await.unwrap() > 0);
+        exporter.collector.record_error("timeout");
+        exporter.export().await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].0, "dd-key");
+
+        let series = requests[0].1["series"].as_array().unwrap();
+        let queue = series
+            .iter()
+            .find(|s| s["metric"] == "orchestrate.queue_depth")
+            .unwrap();
+        assert_eq!(queue["type"], 3);
+        assert_eq!(queue["tags"][0], "queue:webhook_events");
+        assert!(series
+            .iter()
+            .all(|s| s["metric"] != "orchestrate.errors_total"));
+
+        let errors = requests[1].1["series"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|s| s["metric"] == "orchestrate.errors_total")
+            .unwrap();
+        assert_eq!(errors["type"], 1);
+        assert_eq!(errors["points"][0]["value"], 1.0);
+    }
+}
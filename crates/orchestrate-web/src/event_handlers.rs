@@ -3,7 +3,10 @@
 //! This module processes specific webhook events and spawns appropriate agents.
 
 use orchestrate_core::{
-    create_pr_worktree, Agent, AgentContext, AgentType, Database, Result, WebhookEvent,
+    create_pr_worktree, Agent, AgentContext, AgentState, AgentType, CiCheckResult, CommentCommand,
+    ConflictResolutionStrategy, Database, DequeueReason, IssueTriage, IssueTriageConfig,
+    IssueTriageRecord, IssueTriager, PrStatus, PrWorkflowManager, RepoRole, Result, TriageIssue,
+    WebhookEvent,
 };
 use orchestrate_github::GitHubClient;
 use serde_json::Value;
@@ -164,9 +167,6 @@ pub async fn handle_pr_opened(
 ///
 /// This is a best-effort operation. Failures are logged but not fatal.
 async fn try_post_pr_comment(pr_number: i32) -> Result<()> {
-    let client = GitHubClient::new()
-        .map_err(|e| orchestrate_core::Error::Other(format!("Failed to create GitHub client: {}", e)))?;
-
     let comment_body = format!(
         "🤖 **Orchestrate is now watching this PR**\n\n\
         I'll automatically:\n\
@@ -177,8 +177,16 @@ async fn try_post_pr_comment(pr_number: i32) -> Result<()> {
         pr_number
     );
 
-    client.post_comment(pr_number, &comment_body)
-        .map_err(|e| orchestrate_core::Error::Other(format!("Failed to post comment: {}", e)))?;
+    tokio::task::spawn_blocking(move || {
+        let client = GitHubClient::new().map_err(|e| {
+            orchestrate_core::Error::Other(format!("Failed to create GitHub client: {}", e))
+        })?;
+        client
+            .post_comment(pr_number, &comment_body)
+            .map_err(|e| orchestrate_core::Error::Other(format!("Failed to post comment: {}", e)))
+    })
+    .await
+    .map_err(|e| orchestrate_core::Error::Other(format!("Comment task failed: {}", e)))??;
 
     info!(pr_number = pr_number, "Posted orchestrate watching comment");
 
@@ -270,15 +278,44 @@ pub async fn handle_pr_review_submitted(
     );
 
     // Look for existing pr-shepherd agent for this PR
-    let shepherd_agent_id = database
+    let shepherd = database
         .list_agents()
         .await?
         .into_iter()
         .find(|a| {
             a.agent_type == AgentType::PrShepherd
                 && a.context.pr_number == Some(pr_number as i32)
-        })
-        .map(|a| a.id);
+        });
+    let shepherd_agent_id = shepherd.as_ref().map(|a| a.id);
+
+    // Commit the reviewer's suggested changes in the shepherd's worktree first;
+    // if that addresses every open thread there is nothing left to fix
+    let worktree_path = match shepherd.as_ref().and_then(|a| a.worktree_id.as_deref()) {
+        Some(worktree_id) => database.get_worktree_path(worktree_id).await?,
+        None => None,
+    };
+    let mut applied_suggestions = None;
+    if let Some(path) = worktree_path {
+        match try_apply_suggestions(&repo_full_name, pr_number as i32, path).await {
+            Ok(Some((sha, remaining_threads))) => {
+                if remaining_threads == 0 && review_body.trim().is_empty() {
+                    info!(
+                        pr_number = pr_number,
+                        commit = %sha,
+                        "Applied all suggested changes, no issue-fixer needed"
+                    );
+                    return Ok(());
+                }
+                applied_suggestions = Some(sha);
+            }
+            Ok(None) => {}
+            Err(e) => warn!(
+                pr_number = pr_number,
+                error = %e,
+                "Failed to apply suggested changes, continuing anyway"
+            ),
+        }
+    }
 
     // Build custom context with review information
     let mut custom = serde_json::json!({
@@ -286,6 +323,9 @@ pub async fn handle_pr_review_submitted(
         "event_delivery_id": event.delivery_id,
         "review_body": review_body,
     });
+    if let Some(sha) = applied_suggestions {
+        custom["applied_suggestions_commit"] = serde_json::json!(sha);
+    }
 
     // Link to shepherd if found
     if let Some(shepherd_id) = shepherd_agent_id {
@@ -330,6 +370,33 @@ pub async fn handle_pr_review_submitted(
     Ok(())
 }
 
+/// Apply a PR's suggested changes in its worktree
+///
+/// Returns the commit SHA and the number of review threads still open, or
+/// `None` when there was nothing to apply.
+async fn try_apply_suggestions(
+    repo_full_name: &str,
+    pr_number: i32,
+    worktree_path: String,
+) -> Result<Option<(String, usize)>> {
+    let client = GitHubClient::for_repo(repo_full_name)
+        .map_err(|e| orchestrate_core::Error::Other(format!("Failed to create GitHub client: {}", e)))?;
+
+    tokio::task::spawn_blocking(move || {
+        let applied = client.apply_pr_suggestions(pr_number, std::path::Path::new(&worktree_path))?;
+        let Some(sha) = applied.commit_sha else {
+            return Ok(None);
+        };
+        let remaining = client.get_unresolved_threads(pr_number)?.len();
+        Ok(Some((sha, remaining)))
+    })
+    .await
+    .map_err(|e| orchestrate_core::Error::Other(format!("Suggestion task failed: {}", e)))?
+    .map_err(|e: anyhow::Error| {
+        orchestrate_core::Error::Other(format!("Failed to apply suggestions: {}", e))
+    })
+}
+
 /// Handle a check_run.completed or check_suite.completed event
 ///
 /// Spawns an issue-fixer agent when CI fails.
@@ -348,6 +415,8 @@ pub async fn handle_ci_status(
     // Parse payload
     let payload: Value = serde_json::from_str(&event.payload)?;
 
+    update_ci_status_cache(&event.event_type, &payload);
+
     // Handle both check_run and check_suite events
     match event.event_type.as_str() {
         "check_run" => handle_check_run_completed(database, event, payload).await,
@@ -359,6 +428,58 @@ pub async fn handle_ci_status(
     }
 }
 
+/// Keep the CI status cache current from check webhooks
+///
+/// A check run updates its check on the cached commit; a requested or
+/// rerequested check suite invalidates the commit so it's fetched again.
+fn update_ci_status_cache(event_type: &str, payload: &Value) {
+    let cache = orchestrate_core::ci_status_cache();
+    let action = payload.get("action").and_then(|v| v.as_str()).unwrap_or_default();
+
+    match event_type {
+        "check_run" => {
+            let Some(check_run) = payload.get("check_run") else {
+                return;
+            };
+            let (Some(sha), Some(name)) = (
+                check_run.get("head_sha").and_then(|v| v.as_str()),
+                check_run.get("name").and_then(|v| v.as_str()),
+            ) else {
+                return;
+            };
+            let status = orchestrate_core::check_status(
+                check_run.get("status").and_then(|v| v.as_str()).unwrap_or_default(),
+                check_run.get("conclusion").and_then(|v| v.as_str()),
+            );
+            let mut check = CiCheckResult::new(name, status);
+            if let Some(url) = check_run
+                .get("details_url")
+                .or_else(|| check_run.get("html_url"))
+                .and_then(|v| v.as_str())
+            {
+                check = check.with_url(url);
+            }
+            if let Some(transition) = cache.record_check(sha, check) {
+                info!(
+                    sha = %transition.sha,
+                    to = ?transition.to,
+                    "CI status changed"
+                );
+            }
+        }
+        "check_suite" if action == "requested" || action == "rerequested" => {
+            if let Some(sha) = payload
+                .get("check_suite")
+                .and_then(|s| s.get("head_sha"))
+                .and_then(|v| v.as_str())
+            {
+                cache.invalidate(sha);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Handle check_run.completed event
 async fn handle_check_run_completed(
     database: Arc<Database>,
@@ -922,9 +1043,14 @@ pub async fn handle_push_to_main(
     Ok(())
 }
 
+/// Previously triaged issues compared against when looking for duplicates
+const TRIAGE_HISTORY_LIMIT: i64 = 500;
+
 /// Handle an issues.opened event
 ///
-/// Spawns an issue-triager agent to analyze and triage the issue.
+/// Triages the issue (labels, likely duplicates, complexity, and optionally
+/// a story), records and comments the result, and spawns an issue-triager
+/// agent to review it.
 ///
 /// Returns Ok(()) if event was handled successfully, Err if processing should be retried.
 pub async fn handle_issue_opened(
@@ -1002,12 +1128,40 @@ pub async fn handle_issue_opened(
         })
         .unwrap_or_default();
 
+    // Triage against the issues triaged earlier in the repository
+    let triage_issue = TriageIssue {
+        number: issue_number,
+        title: issue_title.clone(),
+        body: issue_body.clone(),
+        labels: labels.clone(),
+    };
+    let triager = IssueTriager::new(IssueTriageConfig::from_env());
+    let existing = database
+        .list_issue_triages(Some(&repo_full_name), TRIAGE_HISTORY_LIMIT)
+        .await?;
+    let mut triage = triager.triage(&triage_issue, &existing);
+
+    if let Some(story) = triager.story(&repo_full_name, &triage_issue, &triage) {
+        if database.get_epic(&story.epic_id).await?.is_some() {
+            database.upsert_story(&story).await?;
+            info!(story_id = %story.id, issue_number = issue_number, "Created story from issue");
+            triage.story_id = Some(story.id);
+        } else {
+            warn!(
+                epic_id = %story.epic_id,
+                "Triage epic not found, skipping story creation"
+            );
+        }
+    }
+
     info!(
         issue_number = issue_number,
         issue_title = %issue_title,
         repository = %repo_full_name,
         labels_count = labels.len(),
         assignees_count = assignees.len(),
+        complexity = triage.complexity.as_str(),
+        duplicate_of = ?triage.duplicate_of(),
         "Spawning issue-triager agent for new issue"
     );
 
@@ -1028,6 +1182,8 @@ pub async fn handle_issue_opened(
         custom["issue_assignees"] = serde_json::json!(assignees);
     }
 
+    custom["triage"] = serde_json::to_value(&triage)?;
+
     // Create agent context
     let context = AgentContext {
         pr_number: None,
@@ -1063,15 +1219,520 @@ pub async fn handle_issue_opened(
         "issue-triager agent created for new issue"
     );
 
+    let mut record = IssueTriageRecord::new(&repo_full_name, &triage_issue, &triage);
+    record.agent_id = Some(agent.id);
+    database.upsert_issue_triage(&record).await?;
+
+    // Label and comment on the issue
+    // This is done asynchronously and errors are logged but not fatal
+    if let Err(e) = try_post_triage(&repo_full_name, &triage).await {
+        error!(
+            issue_number = issue_number,
+            error = %e,
+            "Failed to post issue triage, continuing anyway"
+        );
+    }
+
     // TODO: Actually spawn the agent (call orchestrate CLI or spawn process)
 
     Ok(())
 }
 
+/// Try to label an issue and comment its triage
+///
+/// This is a best-effort operation. Failures are logged but not fatal.
+async fn try_post_triage(repo_full_name: &str, triage: &IssueTriage) -> Result<()> {
+    let client = GitHubClient::for_repo(repo_full_name).map_err(|e| {
+        orchestrate_core::Error::Other(format!("Failed to create GitHub client: {}", e))
+    })?;
+
+    let issue_number = triage.issue_number;
+    let labels = triage.labels.clone();
+    let comment = triage.comment();
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = client.add_issue_labels(issue_number, &labels) {
+            // Labels missing from the repository are left for the agent to create
+            warn!(issue_number = issue_number, error = %e, "Failed to label issue");
+        }
+
+        client
+            .post_issue_comment(issue_number, &comment)
+            .map_err(|e| orchestrate_core::Error::Other(format!("Failed to post comment: {}", e)))
+    })
+    .await
+    .map_err(|e| orchestrate_core::Error::Other(format!("Triage task failed: {}", e)))??;
+
+    info!(
+        issue_number = triage.issue_number,
+        "Posted issue triage comment"
+    );
+
+    Ok(())
+}
+
+/// Handle pull_request.enqueued and pull_request.dequeued events
+///
+/// Keeps the queued PR's status in sync with the GitHub merge queue. A PR
+/// dequeued because its checks failed or it conflicts goes back to fixing;
+/// one removed for any other reason goes back to open.
+pub async fn handle_merge_queue_event(
+    database: Arc<Database>,
+    event: &WebhookEvent,
+) -> Result<()> {
+    let payload: Value = serde_json::from_str(&event.payload)?;
+
+    let action = payload
+        .get("action")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| orchestrate_core::Error::Other("Missing action field".to_string()))?;
+
+    let pr_number = payload
+        .get("pull_request")
+        .and_then(|pr| pr.get("number"))
+        .and_then(|v| v.as_i64())
+        .ok_or_else(|| orchestrate_core::Error::Other("Missing PR number".to_string()))?;
+
+    let Some(pr) = database.get_pr_by_number(pr_number as i32).await? else {
+        debug!(pr_number = pr_number, "Merge queue event for untracked PR, skipping");
+        return Ok(());
+    };
+
+    match action {
+        "enqueued" => {
+            info!(pr_number = pr_number, "PR entered the merge queue");
+            database
+                .update_pr_merge_queue(pr.id, PrStatus::Enqueued, None, None)
+                .await?;
+        }
+        "dequeued" => {
+            let reason = DequeueReason::from_github(
+                payload
+                    .get("reason")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default(),
+            );
+            info!(
+                pr_number = pr_number,
+                reason = reason.as_str(),
+                "PR left the merge queue"
+            );
+
+            let error = format!("Removed from merge queue: {}", reason.as_str());
+            let (status, error) = match reason {
+                DequeueReason::Merged => (PrStatus::Merged, None),
+                DequeueReason::ChecksFailed | DequeueReason::MergeConflict => {
+                    (PrStatus::Fixing, Some(error.as_str()))
+                }
+                DequeueReason::Manual | DequeueReason::Other(_) => {
+                    (PrStatus::Open, Some(error.as_str()))
+                }
+            };
+            database
+                .update_pr_merge_queue(pr.id, status, None, error)
+                .await?;
+
+            if reason == DequeueReason::MergeConflict {
+                let pull_request = &payload["pull_request"];
+                let branch = |side: &str| {
+                    pull_request[side]["ref"]
+                        .as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| {
+                            orchestrate_core::Error::Other(format!("Missing {} branch", side))
+                        })
+                };
+                let repo_full_name = payload["repository"]["full_name"]
+                    .as_str()
+                    .ok_or_else(|| {
+                        orchestrate_core::Error::Other("Missing repository name".to_string())
+                    })?;
+                spawn_conflict_resolver(
+                    database,
+                    repo_full_name,
+                    pr_number as i32,
+                    &branch("head")?,
+                    &branch("base")?,
+                )
+                .await?;
+            }
+        }
+        _ => debug!(action = %action, "Skipping non merge queue action"),
+    }
+
+    Ok(())
+}
+
+/// Outcome of [`spawn_conflict_resolver`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictResolverOutcome {
+    /// A conflict-resolver agent was created for the given attempt
+    Spawned { agent_id: uuid::Uuid, attempt: u32 },
+    /// A conflict-resolver agent is already working on the PR
+    AlreadyRunning(uuid::Uuid),
+    /// Resolution failed too often; the PR was handed to a human
+    Escalated { attempts: u32 },
+}
+
+/// Spawn a conflict-resolver agent to rebase a conflicting PR
+///
+/// The agent works in the PR shepherd's worktree: it rebases the head
+/// branch onto the base branch, resolves the conflicts, runs the tests,
+/// and force-pushes. Each agent is one recorded attempt; once
+/// `max_conflict_resolution_attempts` attempts have failed, the PR is
+/// marked failed and a comment asks for a human to resolve it.
+pub async fn spawn_conflict_resolver(
+    database: Arc<Database>,
+    repo_full_name: &str,
+    pr_number: i32,
+    head_branch: &str,
+    base_branch: &str,
+) -> Result<ConflictResolverOutcome> {
+    let agents = database.list_agents().await?;
+    let previous: Vec<&Agent> = agents
+        .iter()
+        .filter(|a| {
+            a.agent_type == AgentType::ConflictResolver && a.context.pr_number == Some(pr_number)
+        })
+        .collect();
+
+    if let Some(running) = previous.iter().find(|a| !a.state.is_terminal()) {
+        debug!(
+            pr_number = pr_number,
+            agent_id = %running.id,
+            "Conflict resolver already running, skipping"
+        );
+        return Ok(ConflictResolverOutcome::AlreadyRunning(running.id));
+    }
+
+    let failed_attempts = previous
+        .iter()
+        .filter(|a| a.state == AgentState::Failed)
+        .count() as u32;
+    if PrWorkflowManager::new().conflict_attempts_exhausted(failed_attempts) {
+        warn!(
+            pr_number = pr_number,
+            attempts = failed_attempts,
+            "Conflict resolution attempts exhausted, escalating"
+        );
+        if let Some(pr) = database.get_pr_by_number(pr_number).await? {
+            database.update_pr_status(pr.id, PrStatus::Failed).await?;
+        }
+        if let Err(e) =
+            try_post_conflict_escalation(repo_full_name, pr_number, failed_attempts).await
+        {
+            error!(
+                pr_number = pr_number,
+                error = %e,
+                "Failed to post conflict escalation comment"
+            );
+        }
+        return Ok(ConflictResolverOutcome::Escalated {
+            attempts: failed_attempts,
+        });
+    }
+    let attempt = failed_attempts + 1;
+
+    // Work in the shepherd's worktree, which already has the PR branch
+    let shepherd = agents
+        .iter()
+        .find(|a| a.agent_type == AgentType::PrShepherd && a.context.pr_number == Some(pr_number));
+    let worktree_id = shepherd.and_then(|a| a.worktree_id.clone());
+    let working_directory = match &worktree_id {
+        Some(worktree_id) => database.get_worktree_path(worktree_id).await?,
+        None => None,
+    };
+
+    let mut custom = serde_json::json!({
+        "repository": repo_full_name,
+        "base_branch": base_branch,
+        "strategy": ConflictResolutionStrategy::Rebase.as_str(),
+        "conflict_attempt": attempt,
+    });
+    if let Some(shepherd) = shepherd {
+        custom["shepherd_agent_id"] = serde_json::json!(shepherd.id.to_string());
+    }
+
+    let context = AgentContext {
+        pr_number: Some(pr_number),
+        branch_name: Some(head_branch.to_string()),
+        working_directory,
+        custom,
+        ..Default::default()
+    };
+
+    let mut agent = Agent::new(
+        AgentType::ConflictResolver,
+        format!(
+            "Rebase PR #{} ({}) onto {} and resolve merge conflicts (attempt {})",
+            pr_number, head_branch, base_branch, attempt
+        ),
+    )
+    .with_context(context);
+    if let Some(worktree_id) = worktree_id {
+        agent = agent.with_worktree(worktree_id);
+    }
+
+    database.insert_agent(&agent).await?;
+
+    info!(
+        agent_id = %agent.id,
+        pr_number = pr_number,
+        attempt = attempt,
+        "conflict-resolver agent created"
+    );
+
+    Ok(ConflictResolverOutcome::Spawned {
+        agent_id: agent.id,
+        attempt,
+    })
+}
+
+/// Ask for a human to resolve conflicts the agents couldn't
+async fn try_post_conflict_escalation(
+    repo_full_name: &str,
+    pr_number: i32,
+    attempts: u32,
+) -> Result<()> {
+    let client = GitHubClient::for_repo(repo_full_name)
+        .map_err(|e| orchestrate_core::Error::Other(format!("Failed to create GitHub client: {}", e)))?;
+
+    let comment_body = format!(
+        "⚠️ **Merge conflicts need a human**\n\n\
+        Orchestrate tried to rebase this PR onto its base branch {} times without \
+        success. Please resolve the conflicts manually.",
+        attempts
+    );
+
+    tokio::task::spawn_blocking(move || client.post_comment(pr_number, &comment_body))
+        .await
+        .map_err(|e| orchestrate_core::Error::Other(format!("Comment task failed: {}", e)))?
+        .map_err(|e| orchestrate_core::Error::Other(format!("Failed to post comment: {}", e)))?;
+
+    Ok(())
+}
+
+/// Handle an issue_comment.created event
+///
+/// Runs `/orchestrate <command>` comments on PRs, after checking that the
+/// commenter's repository role allows the command. Every command is
+/// answered with a comment saying what happened.
+pub async fn handle_issue_comment(database: Arc<Database>, event: &WebhookEvent) -> Result<()> {
+    let payload: Value = serde_json::from_str(&event.payload)?;
+
+    if payload.get("action").and_then(|v| v.as_str()) != Some("created") {
+        debug!("Skipping issue comment action other than created");
+        return Ok(());
+    }
+
+    let comment = payload
+        .get("comment")
+        .ok_or_else(|| orchestrate_core::Error::Other("Missing comment field".to_string()))?;
+
+    // Our own replies mention commands; never act on bot comments
+    if comment["user"]["type"].as_str() == Some("Bot") {
+        return Ok(());
+    }
+
+    let Some(command) = CommentCommand::from_comment(comment["body"].as_str().unwrap_or_default())
+    else {
+        return Ok(());
+    };
+
+    let login = comment["user"]["login"]
+        .as_str()
+        .ok_or_else(|| orchestrate_core::Error::Other("Missing comment author".to_string()))?;
+    let number = payload["issue"]["number"]
+        .as_i64()
+        .ok_or_else(|| orchestrate_core::Error::Other("Missing issue number".to_string()))?;
+    let repo_full_name = payload["repository"]["full_name"]
+        .as_str()
+        .ok_or_else(|| orchestrate_core::Error::Other("Missing repository name".to_string()))?;
+
+    info!(
+        number = number,
+        command = command.as_str(),
+        author = %login,
+        "Processing comment command"
+    );
+
+    if payload["issue"].get("pull_request").is_none() {
+        try_reply(
+            repo_full_name,
+            number,
+            "Orchestrate commands only work on pull requests.",
+        )
+        .await;
+        return Ok(());
+    }
+
+    let association = comment["author_association"].as_str().unwrap_or_default();
+    let role = commenter_role(repo_full_name, login, association).await;
+    if !role.allows(&command) {
+        warn!(
+            number = number,
+            author = %login,
+            role = role.as_str(),
+            command = command.as_str(),
+            "Comment command denied"
+        );
+        try_reply(
+            repo_full_name,
+            number,
+            &format!(
+                "@{} `/orchestrate {}` needs {} access to this repository.",
+                login,
+                command.as_str(),
+                command.required_role().as_str()
+            ),
+        )
+        .await;
+        return Ok(());
+    }
+
+    let pr_number = number as i32;
+    let agents = database.list_agents().await?;
+    let shepherd = agents
+        .iter()
+        .find(|a| a.agent_type == AgentType::PrShepherd && a.context.pr_number == Some(pr_number));
+    let head_branch = match shepherd.and_then(|a| a.context.branch_name.clone()) {
+        Some(branch) => Some(branch),
+        None => database
+            .get_pr_by_number(pr_number)
+            .await?
+            .map(|pr| pr.branch_name),
+    };
+
+    let mut custom = serde_json::json!({
+        "repository": repo_full_name,
+        "event_delivery_id": event.delivery_id,
+        "command": command.as_str(),
+        "requested_by": login,
+    });
+    if let Some(shepherd) = shepherd {
+        custom["shepherd_agent_id"] = serde_json::json!(shepherd.id.to_string());
+    }
+    let context = AgentContext {
+        pr_number: Some(pr_number),
+        branch_name: head_branch.clone(),
+        working_directory: None, // Will use existing worktree if shepherd exists
+        custom,
+        ..Default::default()
+    };
+
+    let reply = match &command {
+        CommentCommand::Unknown(name) if name.is_empty() => {
+            format!("Available commands:\n\n{}", CommentCommand::help())
+        }
+        CommentCommand::Unknown(name) => format!(
+            "Unknown command `{}`. Available commands:\n\n{}",
+            name,
+            CommentCommand::help()
+        ),
+        CommentCommand::FixCi => {
+            let agent = Agent::new(
+                AgentType::IssueFixer,
+                format!("Fix CI failures for PR #{} (requested by @{})", pr_number, login),
+            )
+            .with_context(context);
+            database.insert_agent(&agent).await?;
+            "Started an issue-fixer for the failing CI checks.".to_string()
+        }
+        CommentCommand::RerunReview => {
+            let agent = Agent::new(
+                AgentType::CodeReviewer,
+                format!("Review PR #{} again (requested by @{})", pr_number, login),
+            )
+            .with_context(context);
+            database.insert_agent(&agent).await?;
+            "Started a new code review.".to_string()
+        }
+        CommentCommand::Rebase => {
+            let Some(head_branch) = head_branch else {
+                try_reply(
+                    repo_full_name,
+                    number,
+                    "Orchestrate isn't tracking this PR, so it can't rebase it.",
+                )
+                .await;
+                return Ok(());
+            };
+            let base_branch = payload["repository"]["default_branch"]
+                .as_str()
+                .unwrap_or("main");
+            match spawn_conflict_resolver(
+                database,
+                repo_full_name,
+                pr_number,
+                &head_branch,
+                base_branch,
+            )
+            .await?
+            {
+                ConflictResolverOutcome::Spawned { attempt, .. } => format!(
+                    "Rebasing onto `{}` (attempt {}).",
+                    base_branch, attempt
+                ),
+                ConflictResolverOutcome::AlreadyRunning(_) => {
+                    "A rebase is already in progress.".to_string()
+                }
+                // The escalation comment explains it
+                ConflictResolverOutcome::Escalated { .. } => return Ok(()),
+            }
+        }
+    };
+
+    try_reply(repo_full_name, number, &reply).await;
+    Ok(())
+}
+
+/// The commenter's repository role
+///
+/// Asks GitHub for the collaborator permission, falling back to the
+/// comment's author association when that fails.
+async fn commenter_role(repo_full_name: &str, login: &str, association: &str) -> RepoRole {
+    let fallback = RepoRole::from_author_association(association);
+    if fallback == RepoRole::Admin {
+        return fallback;
+    }
+
+    let Ok(client) = GitHubClient::for_repo(repo_full_name) else {
+        return fallback;
+    };
+    let login = login.to_string();
+    match tokio::task::spawn_blocking(move || client.get_repo_role(&login)).await {
+        Ok(Ok(role)) => role,
+        Ok(Err(e)) => {
+            warn!(error = %e, "Failed to look up commenter permission, using author association");
+            fallback
+        }
+        Err(e) => {
+            warn!(error = %e, "Permission lookup task failed, using author association");
+            fallback
+        }
+    }
+}
+
+/// Reply to a comment command
+///
+/// This is a best-effort operation. Failures are logged but not fatal.
+async fn try_reply(repo_full_name: &str, number: i64, body: &str) {
+    let result = match GitHubClient::for_repo(repo_full_name) {
+        Ok(client) => {
+            let body = body.to_string();
+            tokio::task::spawn_blocking(move || client.post_issue_comment(number, &body))
+                .await
+                .unwrap_or_else(|e| Err(anyhow::anyhow!("Reply task failed: {}", e)))
+        }
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        error!(number = number, error = %e, "Failed to reply to comment command");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use orchestrate_core::AgentState;
 
     fn create_pr_opened_payload(pr_number: i64, branch: &str, is_fork: bool) -> String {
         serde_json::json!({
@@ -1721,6 +2382,48 @@ mod tests {
         assert_eq!(agents.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_handle_ci_check_run_updates_status_cache() {
+        use orchestrate_core::{ci_status_cache, CiStatus};
+
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let sha = "cache-update-sha";
+        let cache = ci_status_cache();
+        cache.store(
+            sha,
+            vec![
+                CiCheckResult::new("lint", CiStatus::Running),
+                CiCheckResult::new("test", CiStatus::Passed),
+            ],
+        );
+
+        let payload =
+            create_check_run_completed_payload(22222, "lint", "success", None, sha, None);
+        let event = WebhookEvent::new(
+            "delivery-check-run-cache".to_string(),
+            "check_run".to_string(),
+            payload,
+        );
+        handle_ci_status(database.clone(), &event).await.unwrap();
+
+        let status = cache.get(sha).unwrap();
+        assert_eq!(status.overall, CiStatus::Passed);
+        assert_eq!(status.total, 2);
+
+        let payload = serde_json::json!({
+            "action": "rerequested",
+            "check_suite": { "id": 1, "head_sha": sha },
+            "repository": { "full_name": "owner/repo" }
+        });
+        let event = WebhookEvent::new(
+            "delivery-check-suite-rerun".to_string(),
+            "check_suite".to_string(),
+            payload.to_string(),
+        );
+        handle_ci_status(database, &event).await.unwrap();
+        assert!(cache.get(sha).is_none());
+    }
+
     #[tokio::test]
     async fn test_handle_ci_check_run_without_pr() {
         let database = Arc::new(Database::in_memory().await.unwrap());
@@ -2539,4 +3242,266 @@ mod tests {
             "delivery-issue-repo"
         );
     }
+
+    #[tokio::test]
+    async fn test_handle_issue_opened_records_triage() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        for (number, delivery) in [(107, "delivery-triage-1"), (108, "delivery-triage-2")] {
+            let payload = create_issue_opened_payload(
+                number,
+                "App crashes when saving settings",
+                "Saving the settings page crashes the app",
+            );
+            let event = WebhookEvent::new(delivery.to_string(), "issues".to_string(), payload);
+            handle_issue_opened(database.clone(), &event).await.unwrap();
+        }
+
+        let first = database
+            .get_issue_triage("owner/repo", 107)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(first.labels.contains(&"bug".to_string()));
+        assert!(first.duplicate_of.is_none());
+
+        let second = database
+            .get_issue_triage("owner/repo", 108)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.duplicate_of, Some(107));
+        assert!(second.labels.contains(&"duplicate".to_string()));
+
+        let agents = database.list_agents().await.unwrap();
+        let agent = agents
+            .iter()
+            .find(|agent| Some(agent.id) == second.agent_id)
+            .unwrap();
+        let triage = agent.context.custom.get("triage").unwrap();
+        assert_eq!(triage["duplicates"][0]["issue_number"], 107);
+    }
+
+    async fn insert_open_pr(database: &Database, pr_number: i32) -> i64 {
+        let mut pr = orchestrate_core::PullRequest::new("feature/queued");
+        pr.pr_number = Some(pr_number);
+        pr.status = PrStatus::Open;
+        database.insert_pr(&pr).await.unwrap()
+    }
+
+    fn merge_queue_event(action: &str, pr_number: i64, reason: Option<&str>) -> WebhookEvent {
+        let mut payload = serde_json::json!({
+            "action": action,
+            "pull_request": {
+                "number": pr_number,
+                "head": { "ref": "feature/queued" },
+                "base": { "ref": "main" }
+            },
+            "repository": { "full_name": "owner/repo" }
+        });
+        if let Some(reason) = reason {
+            payload["reason"] = reason.into();
+        }
+        WebhookEvent::new(
+            "delivery-mq".to_string(),
+            "pull_request".to_string(),
+            payload.to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_handle_merge_queue_enqueued() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        insert_open_pr(&database, 42).await;
+
+        handle_merge_queue_event(database.clone(), &merge_queue_event("enqueued", 42, None))
+            .await
+            .unwrap();
+
+        let pr = database.get_pr_by_number(42).await.unwrap().unwrap();
+        assert_eq!(pr.status, PrStatus::Enqueued);
+    }
+
+    #[tokio::test]
+    async fn test_handle_merge_queue_dequeued_ci_failure() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let id = insert_open_pr(&database, 42).await;
+        database
+            .update_pr_merge_queue(id, PrStatus::Enqueued, Some(3), None)
+            .await
+            .unwrap();
+
+        let event = merge_queue_event("dequeued", 42, Some("CI_FAILURE"));
+        handle_merge_queue_event(database.clone(), &event)
+            .await
+            .unwrap();
+
+        let pr = database.get_pr_by_number(42).await.unwrap().unwrap();
+        assert_eq!(pr.status, PrStatus::Fixing);
+        assert_eq!(pr.merge_queue_position, None);
+        assert!(pr.error_message.unwrap().contains("checks_failed"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_merge_queue_dequeued_merged() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        insert_open_pr(&database, 42).await;
+
+        let event = merge_queue_event("dequeued", 42, Some("MERGE"));
+        handle_merge_queue_event(database.clone(), &event)
+            .await
+            .unwrap();
+
+        let pr = database.get_pr_by_number(42).await.unwrap().unwrap();
+        assert_eq!(pr.status, PrStatus::Merged);
+        assert_eq!(pr.error_message, None);
+    }
+
+    #[tokio::test]
+    async fn test_handle_merge_queue_dequeued_conflict_spawns_resolver() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        insert_open_pr(&database, 42).await;
+
+        let event = merge_queue_event("dequeued", 42, Some("MERGE_CONFLICT"));
+        handle_merge_queue_event(database.clone(), &event)
+            .await
+            .unwrap();
+
+        let agents = database.list_agents().await.unwrap();
+        let resolver = agents
+            .iter()
+            .find(|a| a.agent_type == AgentType::ConflictResolver)
+            .unwrap();
+        assert_eq!(resolver.context.pr_number, Some(42));
+        assert_eq!(resolver.context.branch_name.as_deref(), Some("feature/queued"));
+        assert_eq!(resolver.context.custom["base_branch"], "main");
+        assert_eq!(resolver.context.custom["strategy"], "rebase");
+        assert_eq!(resolver.context.custom["conflict_attempt"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_conflict_resolver_skips_running_attempt() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let first = spawn_conflict_resolver(database.clone(), "owner/repo", 42, "feature/x", "main")
+            .await
+            .unwrap();
+        let ConflictResolverOutcome::Spawned { agent_id, attempt } = first else {
+            panic!("expected a spawned resolver, got {:?}", first);
+        };
+        assert_eq!(attempt, 1);
+
+        let second = spawn_conflict_resolver(database.clone(), "owner/repo", 42, "feature/x", "main")
+            .await
+            .unwrap();
+        assert_eq!(second, ConflictResolverOutcome::AlreadyRunning(agent_id));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_conflict_resolver_escalates_after_failed_attempts() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        insert_open_pr(&database, 42).await;
+
+        for _ in 0..3 {
+            let mut agent = Agent::new(AgentType::ConflictResolver, "Rebase PR #42".to_string())
+                .with_context(AgentContext {
+                    pr_number: Some(42),
+                    ..Default::default()
+                });
+            agent.state = AgentState::Failed;
+            database.insert_agent(&agent).await.unwrap();
+        }
+
+        let outcome = spawn_conflict_resolver(database.clone(), "owner/repo", 42, "feature/x", "main")
+            .await
+            .unwrap();
+        assert_eq!(outcome, ConflictResolverOutcome::Escalated { attempts: 3 });
+
+        let pr = database.get_pr_by_number(42).await.unwrap().unwrap();
+        assert_eq!(pr.status, PrStatus::Failed);
+    }
+
+    fn comment_event(body: &str, association: &str, on_pr: bool) -> WebhookEvent {
+        let mut payload = serde_json::json!({
+            "action": "created",
+            "issue": { "number": 42 },
+            "comment": {
+                "body": body,
+                "author_association": association,
+                "user": { "login": "maintainer", "type": "User" }
+            },
+            "repository": { "full_name": "owner/repo", "default_branch": "main" }
+        });
+        if on_pr {
+            payload["issue"]["pull_request"] = serde_json::json!({});
+        }
+        WebhookEvent::new(
+            "delivery-comment".to_string(),
+            "issue_comment".to_string(),
+            payload.to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_handle_issue_comment_rebase() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        insert_open_pr(&database, 42).await;
+
+        let event = comment_event("/orchestrate rebase", "OWNER", true);
+        handle_issue_comment(database.clone(), &event).await.unwrap();
+
+        let agents = database.list_agents().await.unwrap();
+        let resolver = agents
+            .iter()
+            .find(|a| a.agent_type == AgentType::ConflictResolver)
+            .unwrap();
+        assert_eq!(resolver.context.branch_name.as_deref(), Some("feature/queued"));
+        assert_eq!(resolver.context.custom["base_branch"], "main");
+    }
+
+    #[tokio::test]
+    async fn test_handle_issue_comment_rerun_review() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let event = comment_event("Flaky run, again please\n/orchestrate rerun-review", "OWNER", true);
+        handle_issue_comment(database.clone(), &event).await.unwrap();
+
+        let agents = database.list_agents().await.unwrap();
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].agent_type, AgentType::CodeReviewer);
+        assert_eq!(agents[0].context.custom["requested_by"], "maintainer");
+        assert_eq!(agents[0].context.custom["command"], "rerun-review");
+    }
+
+    #[tokio::test]
+    async fn test_handle_issue_comment_denies_without_role() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let event = comment_event("/orchestrate fix-ci", "NONE", true);
+        handle_issue_comment(database.clone(), &event).await.unwrap();
+
+        assert!(database.list_agents().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_handle_issue_comment_ignores_issues_and_plain_comments() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let on_issue = comment_event("/orchestrate fix-ci", "OWNER", false);
+        handle_issue_comment(database.clone(), &on_issue).await.unwrap();
+        let plain = comment_event("Thanks!", "OWNER", true);
+        handle_issue_comment(database.clone(), &plain).await.unwrap();
+
+        assert!(database.list_agents().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_handle_merge_queue_skips_untracked_pr() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let result =
+            handle_merge_queue_event(database.clone(), &merge_queue_event("enqueued", 7, None))
+                .await;
+        assert!(result.is_ok());
+    }
 }
@@ -0,0 +1,174 @@
+//! Server-sent events
+//!
+//! `GET /api/events` streams the structured event feed: agent lifecycle and
+//! network events, pipeline run and stage progress, processed webhooks, and
+//! CI status changes. It's a one-way alternative to the WebSocket for
+//! dashboards and `orchestrate status --watch`.
+//!
+//! Each SSE message is named after the event's kind, e.g.
+//! `pipeline.run_updated`, and carries the [`BusEvent`] as JSON. Filters:
+//! - `kind`: comma-separated kind prefixes, e.g. `agent,pipeline.run_updated`
+//! - `agent_id`: events about one agent
+//! - `run_id`: events about one pipeline run
+
+use axum::{
+    extract::Query,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::stream::{self, Stream};
+use orchestrate_core::BusEvent;
+use serde::Deserialize;
+use std::convert::Infallible;
+use tokio::sync::broadcast::{self, error::RecvError};
+use utoipa::IntoParams;
+
+/// Filters of the event stream
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct EventFilter {
+    /// Comma-separated kind prefixes
+    pub kind: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<i64>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &BusEvent) -> bool {
+        if let Some(kinds) = &self.kind {
+            let matches_kind = kinds
+                .split(',')
+                .map(str::trim)
+                .filter(|k| !k.is_empty())
+                .any(|k| event.kind == k || event.kind.starts_with(&format!("{}.", k)));
+            if !matches_kind {
+                return false;
+            }
+        }
+        if let Some(agent_id) = &self.agent_id {
+            if event.agent_id.as_deref() != Some(agent_id.as_str()) {
+                return false;
+            }
+        }
+        if let Some(run_id) = self.run_id {
+            if event.data["run_id"].as_i64() != Some(run_id) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn sse_event(event: &BusEvent) -> Event {
+    Event::default()
+        .event(event.kind.as_str())
+        .id(event.id.as_str())
+        .json_data(event)
+        .unwrap_or_else(|_| Event::default().event(event.kind.as_str()))
+}
+
+/// Events from a feed subscription that pass a filter
+///
+/// A subscriber that falls behind gets a `lagged` event with the number of
+/// events it missed.
+pub fn event_stream(
+    events: broadcast::Receiver<BusEvent>,
+    filter: EventFilter,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    stream::unfold((events, filter), |(mut events, filter)| async move {
+        loop {
+            match events.recv().await {
+                Ok(event) if filter.matches(&event) => {
+                    return Some((Ok(sse_event(&event)), (events, filter)));
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    let lagged = Event::default()
+                        .event("lagged")
+                        .data(format!("{{\"skipped\":{}}}", skipped));
+                    return Some((Ok(lagged), (events, filter)));
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+/// GET /api/events - Stream the event feed
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "system",
+    summary = "Stream agent, pipeline, and webhook events (server-sent events)",
+    params(EventFilter),
+    responses(
+        (status = 200, description = "Server-sent events", content_type = "text/event-stream")
+    )
+)]
+pub(crate) async fn stream_events(
+    Query(filter): Query<EventFilter>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = orchestrate_core::event_bus::subscribe();
+    Sse::new(event_stream(events, filter)).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use serde_json::json;
+
+    fn run_event(run_id: i64) -> BusEvent {
+        BusEvent::new("pipeline.run_updated", json!({"run_id": run_id})).with_agent("a1")
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let event = run_event(7);
+        assert!(EventFilter::default().matches(&event));
+
+        let kinds = |kind: &str| EventFilter {
+            kind: Some(kind.to_string()),
+            ..EventFilter::default()
+        };
+        assert!(kinds("pipeline").matches(&event));
+        assert!(kinds("agent, pipeline.run_updated").matches(&event));
+        assert!(!kinds("pipe").matches(&event));
+        assert!(!kinds("webhook").matches(&event));
+
+        let run = EventFilter {
+            run_id: Some(8),
+            ..EventFilter::default()
+        };
+        assert!(!run.matches(&event));
+        let agent = EventFilter {
+            agent_id: Some("a1".to_string()),
+            ..EventFilter::default()
+        };
+        assert!(agent.matches(&event));
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_filters_and_reports_lag() {
+        let (tx, rx) = broadcast::channel(2);
+        let filter = EventFilter {
+            run_id: Some(1),
+            ..EventFilter::default()
+        };
+        let mut stream = Box::pin(event_stream(rx, filter));
+
+        tx.send(run_event(2)).unwrap();
+        tx.send(run_event(1)).unwrap();
+        let event = stream.next().await.unwrap().unwrap();
+        assert!(format!("{:?}", event).contains("pipeline.run_updated"));
+
+        // Overflow the channel so the subscriber lags
+        for _ in 0..3 {
+            tx.send(run_event(1)).unwrap();
+        }
+        let event = stream.next().await.unwrap().unwrap();
+        assert!(format!("{:?}", event).contains("lagged"));
+
+        drop(tx);
+        let remaining: Vec<_> = stream.collect().await;
+        assert_eq!(remaining.len(), 2);
+    }
+}
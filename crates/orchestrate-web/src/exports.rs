@@ -0,0 +1,395 @@
+//! Data exports of token usage, costs, the audit log, and agent history
+//!
+//! - GET /api/exports/:dataset - Download an export of up to 10,000 rows
+//! - POST /api/export-jobs - Export in the background into artifact storage
+//! - GET /api/export-jobs - Recent export jobs
+//! - GET /api/export-jobs/:id - Export job, with a download URL once completed
+//!
+//! Datasets are `token_usage`, `costs`, `audit_log`, and `agent_history`.
+//! `format` is `csv` (default) or `parquet`, and `from` and `to` (RFC 3339)
+//! keep rows with `from <= time < to`. Exports too large to download directly
+//! run as jobs, whose files are linked by signed URLs like other artifacts.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use orchestrate_core::{DataExportFormat, ExportDataset, ExportJob, ExportRequest, RowPage};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::api::{artifact_response, artifact_store, ApiError, AppState};
+use crate::auth::Identity;
+use crate::pagination::{Page, PageParams};
+
+/// Most rows a direct download may hold
+pub const MAX_DIRECT_EXPORT_ROWS: usize = 10_000;
+
+fn db_error(e: orchestrate_core::Error) -> ApiError {
+    ApiError::internal(format!("Database error: {}", e))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExportQuery {
+    /// `csv` (default) or `parquet`
+    pub format: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// An export job, with a download URL once completed
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ExportJobResponse {
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub job: ExportJob,
+    /// Signed URL of the exported file
+    pub download_url: Option<String>,
+}
+
+/// GET /api/exports/:dataset - Download an export of up to
+/// [`MAX_DIRECT_EXPORT_ROWS`] rows
+#[utoipa::path(
+    get,
+    path = "/api/exports/{dataset}",
+    tag = "exports",
+    summary = "Download a CSV or Parquet export",
+    params(
+        ("dataset" = String, Path),
+        ExportQuery
+    ),
+    responses(
+        (status = 200, description = "The exported rows", content(
+            (String = "text/csv"),
+            (Vec<u8> = "application/vnd.apache.parquet")
+        ))
+    )
+)]
+pub async fn download_export(
+    State(state): State<Arc<AppState>>,
+    Path(dataset): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let dataset =
+        ExportDataset::from_str(&dataset).map_err(|e| ApiError::validation(e.to_string()))?;
+    let format = query
+        .format
+        .as_deref()
+        .map(DataExportFormat::from_str)
+        .transpose()
+        .map_err(|e| ApiError::validation(e.to_string()))?
+        .unwrap_or_default();
+    let request = ExportRequest::new(dataset, format).between(query.from, query.to);
+
+    export_file(&state, &request, MAX_DIRECT_EXPORT_ROWS).await
+}
+
+async fn export_file(
+    state: &AppState,
+    request: &ExportRequest,
+    max_rows: usize,
+) -> Result<Response, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+
+    // One row past the limit tells a full export from a truncated one
+    let table = state
+        .db
+        .export_rows(request, Some(max_rows as i64 + 1))
+        .await
+        .map_err(db_error)?;
+    if table.rows.len() > max_rows {
+        return Err(ApiError::validation(format!(
+            "Export has more than {} rows; narrow the date range or create an export job with POST /api/v1/export-jobs",
+            max_rows
+        )));
+    }
+
+    let body = table
+        .encode(request.format)
+        .map_err(|e| ApiError::internal(format!("Failed to encode export: {}", e)))?;
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                request.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", request.file_name()),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// POST /api/export-jobs - Export in the background into artifact storage
+#[utoipa::path(
+    post,
+    path = "/api/export-jobs",
+    tag = "exports",
+    summary = "Start an export job",
+    request_body = Object,
+    responses((status = 202, body = ExportJobResponse))
+)]
+pub async fn create_export_job(
+    State(state): State<Arc<AppState>>,
+    identity: Option<Identity>,
+    Json(request): Json<ExportRequest>,
+) -> Result<(StatusCode, Json<ExportJobResponse>), ApiError> {
+    let artifacts = artifact_store(&state)?.clone();
+    request
+        .validate()
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+
+    let mut job = ExportJob::new(request);
+    if let Some(identity) = &identity {
+        job = job.with_requested_by(identity.actor());
+    }
+    state.db.insert_export_job(&job).await.map_err(db_error)?;
+
+    let db = state.db.clone();
+    let mut running = job.clone();
+    tokio::spawn(async move {
+        if let Err(e) = running.run(&db, &artifacts).await {
+            tracing::warn!("Failed to record export job {}: {}", running.id, e);
+        }
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ExportJobResponse {
+            job,
+            download_url: None,
+        }),
+    ))
+}
+
+/// GET /api/export-jobs - Recent export jobs
+#[utoipa::path(
+    get,
+    path = "/api/export-jobs",
+    tag = "exports",
+    summary = "List export jobs",
+    params(PageParams),
+    responses((status = 200, body = Page<ExportJobResponse>))
+)]
+pub async fn list_export_jobs(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<ExportJobResponse>>, ApiError> {
+    let jobs = state
+        .db
+        .list_export_jobs_page(&page.query()?)
+        .await
+        .map_err(db_error)?;
+
+    let mut items = Vec::with_capacity(jobs.items.len());
+    for job in jobs.items {
+        items.push(job_response(&state, job).await?);
+    }
+    Ok(Json(
+        RowPage {
+            items,
+            next: jobs.next,
+            total: jobs.total,
+        }
+        .into(),
+    ))
+}
+
+/// GET /api/export-jobs/:id - Export job, with a download URL once completed
+#[utoipa::path(
+    get,
+    path = "/api/export-jobs/{id}",
+    tag = "exports",
+    summary = "Get an export job",
+    params(("id" = String, Path)),
+    responses((status = 200, body = ExportJobResponse))
+)]
+pub async fn get_export_job(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<ExportJobResponse>, ApiError> {
+    let job = state
+        .db
+        .get_export_job(&id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| ApiError::not_found("Export job"))?;
+    Ok(Json(job_response(&state, job).await?))
+}
+
+async fn job_response(state: &AppState, job: ExportJob) -> Result<ExportJobResponse, ApiError> {
+    let download_url = match (job.artifact_id, state.artifacts.as_ref()) {
+        (Some(artifact_id), Some(artifacts)) => {
+            match state.db.get_artifact(artifact_id).await.map_err(db_error)? {
+                Some(artifact) => Some(artifact_response(artifacts, artifact)?.url),
+                // Deleted through /api/artifacts
+                None => None,
+            }
+        }
+        _ => None,
+    };
+    Ok(ExportJobResponse { job, download_url })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, Router};
+    use orchestrate_core::monitoring::{AuditAction, AuditEntry};
+    use orchestrate_core::{ArtifactStore, Database, ExportJobStatus, LocalObjectStore};
+    use tower::ServiceExt;
+
+    async fn send(router: &Router, request: Request<Body>) -> (StatusCode, Vec<u8>) {
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, body.to_vec())
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    async fn audit(db: &Database, actor: &str) {
+        db.insert_audit_entry(&AuditEntry::new(
+            actor,
+            AuditAction::AgentSpawned,
+            "agent",
+            "a1",
+        ))
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_download_export_as_csv() {
+        let db = Database::in_memory().await.unwrap();
+        audit(&db, "alice").await;
+        let router = crate::create_router(Arc::new(AppState::new(db, None)));
+
+        let response = router
+            .clone()
+            .oneshot(get("/api/v1/exports/audit_log?from=2020-01-01T00:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv");
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"audit_log-2020-01-01.csv\""
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let csv = String::from_utf8(body.to_vec()).unwrap();
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.lines().nth(1).unwrap().contains("alice"));
+
+        let (status, _) = send(&router, get("/api/v1/exports/invoices")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = send(&router, get("/api/v1/exports/costs?format=xlsx")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = send(
+            &router,
+            get("/api/v1/exports/costs?from=2026-10-18T00:00:00Z&to=2026-10-01T00:00:00Z"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_direct_export_row_limit() {
+        let db = Database::in_memory().await.unwrap();
+        audit(&db, "alice").await;
+        audit(&db, "bob").await;
+        let state = AppState::new(db, None);
+        let request = ExportRequest::new(ExportDataset::AuditLog, DataExportFormat::Parquet);
+
+        assert!(export_file(&state, &request, 2).await.is_ok());
+        let error = export_file(&state, &request, 1).await.unwrap_err();
+        assert!(error.error.contains("export job"));
+    }
+
+    #[tokio::test]
+    async fn test_export_job_links_download() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::in_memory().await.unwrap();
+        audit(&db, "alice").await;
+        let store = Arc::new(LocalObjectStore::new(
+            dir.path(),
+            "http://localhost:8080",
+            "signing-key",
+        ));
+        let artifacts = ArtifactStore::new(store, db.clone());
+        let router =
+            crate::create_router(Arc::new(AppState::new(db, None).with_artifacts(artifacts)));
+
+        let (status, body) = send(
+            &router,
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/export-jobs")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({"dataset": "audit_log", "format": "parquet"}).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let created: ExportJobResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(created.job.status, ExportJobStatus::Running);
+
+        let uri = format!("/api/v1/export-jobs/{}", created.job.id);
+        let mut job = created;
+        for _ in 0..50 {
+            let (status, body) = send(&router, get(&uri)).await;
+            assert_eq!(status, StatusCode::OK);
+            job = serde_json::from_slice(&body).unwrap();
+            if job.job.status != ExportJobStatus::Running {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(job.job.status, ExportJobStatus::Completed);
+        assert_eq!(job.job.row_count, Some(1));
+        let url = job.download_url.unwrap();
+        assert!(url.starts_with("http://localhost:8080/artifacts/exports/"));
+
+        let (status, body) = send(&router, get("/api/v1/export-jobs")).await;
+        assert_eq!(status, StatusCode::OK);
+        let page: Page<ExportJobResponse> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].download_url.as_deref(), Some(url.as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_export_jobs_require_storage() {
+        let db = Database::in_memory().await.unwrap();
+        let router = crate::create_router(Arc::new(AppState::new(db, None)));
+
+        let (status, _) = send(
+            &router,
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/export-jobs")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"dataset": "costs"}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
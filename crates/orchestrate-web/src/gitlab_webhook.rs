@@ -0,0 +1,487 @@
+//! GitLab webhook receiver
+//!
+//! Handles incoming GitLab webhook events with secret token verification.
+//! Merge request, pipeline, and push events are mapped onto the payloads of
+//! their GitHub counterparts (`pull_request`, `check_suite`, and `push`)
+//! before they are queued, so the same event handlers, filters, and pipeline
+//! triggers serve GitLab-hosted repositories.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use orchestrate_core::WebhookEvent;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+
+use crate::webhook::{respond, verify_source, verify_token, SourceVerification, WebhookState};
+
+/// GitLab webhook handler
+///
+/// Receives GitLab webhook events, verifies the secret token, and queues the
+/// events it can map for asynchronous processing. Other events are
+/// acknowledged and ignored.
+#[utoipa::path(
+    post,
+    path = "/webhooks/gitlab",
+    tag = "webhooks",
+    summary = "Receive a GitLab webhook",
+    request_body(content = String, content_type = "application/json"),
+    responses(
+        (status = 200, description = "Event accepted"),
+        (status = 400, description = "Malformed payload"),
+        (status = 401, description = "Invalid signature")
+    )
+)]
+pub async fn gitlab_webhook_handler(
+    State(state): State<Arc<WebhookState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let event_name = match headers.get("x-gitlab-event").map(|value| value.to_str()) {
+        Some(Ok(value)) => value.to_string(),
+        Some(Err(_)) => {
+            warn!("Invalid X-Gitlab-Event header");
+            return respond(
+                StatusCode::BAD_REQUEST,
+                "error",
+                "Invalid X-Gitlab-Event header",
+            );
+        }
+        None => {
+            warn!("Missing X-Gitlab-Event header");
+            return respond(
+                StatusCode::BAD_REQUEST,
+                "error",
+                "Missing X-Gitlab-Event header",
+            );
+        }
+    };
+
+    // A signature scheme stored for the source replaces the secret token
+    let source_verified = match verify_source(&state.database, "gitlab", &headers, &body).await {
+        SourceVerification::Rejected(response) => return response,
+        SourceVerification::Verified => true,
+        SourceVerification::NotConfigured => false,
+    };
+
+    // GitLab sends the configured secret token verbatim
+    if let Some(secret) = state.config.secret.as_ref().filter(|_| !source_verified) {
+        match headers.get("x-gitlab-token").and_then(|v| v.to_str().ok()) {
+            Some(token) if verify_token(secret, token) => {}
+            Some(_) => {
+                error!("Invalid GitLab webhook token");
+                return respond(StatusCode::UNAUTHORIZED, "error", "Invalid token");
+            }
+            None => {
+                warn!("Missing X-Gitlab-Token header");
+                return respond(StatusCode::UNAUTHORIZED, "error", "Missing token");
+            }
+        }
+    }
+
+    let payload: Value = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(e) => {
+            warn!(error = %e, "Failed to parse GitLab webhook payload");
+            return respond(
+                StatusCode::BAD_REQUEST,
+                "error",
+                &format!("Invalid JSON payload: {}", e),
+            );
+        }
+    };
+
+    let Some((event_type, mapped)) = map_gitlab_event(&event_name, &payload) else {
+        debug!(event_name = %event_name, "No mapping for GitLab event");
+        return respond(
+            StatusCode::OK,
+            "ignored",
+            &format!("Unsupported GitLab event: {}", event_name),
+        );
+    };
+
+    let delivery_id = headers
+        .get("x-gitlab-event-uuid")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let webhook_event =
+        WebhookEvent::new(delivery_id.clone(), event_type.clone(), mapped.to_string())
+            .with_source("gitlab");
+
+    match state.database.insert_webhook_event(&webhook_event).await {
+        Ok(id) => {
+            info!(
+                event_id = id,
+                delivery_id = %delivery_id,
+                event_name = %event_name,
+                event_type = %event_type,
+                "GitLab webhook event queued"
+            );
+        }
+        Err(e) => {
+            // The webhook was received; GitLab would only retry the delivery
+            error!(error = %e, "Failed to queue GitLab webhook event");
+        }
+    }
+
+    respond(StatusCode::OK, "ok", "Webhook received")
+}
+
+/// Map a GitLab event onto the equivalent GitHub event type and payload
+///
+/// Returns `None` for events without a GitHub counterpart, such as merge
+/// request approvals or comments.
+pub fn map_gitlab_event(event_name: &str, payload: &Value) -> Option<(String, Value)> {
+    match event_name {
+        "Merge Request Hook" => {
+            map_merge_request(payload).map(|mapped| ("pull_request".to_string(), mapped))
+        }
+        "Pipeline Hook" => map_pipeline(payload).map(|mapped| ("check_suite".to_string(), mapped)),
+        "Push Hook" | "Tag Push Hook" => Some(("push".to_string(), map_push(payload))),
+        _ => None,
+    }
+}
+
+fn map_merge_request(payload: &Value) -> Option<Value> {
+    let attrs = payload.get("object_attributes")?;
+    let action = match attrs["action"].as_str()? {
+        "open" => "opened",
+        "reopen" => "reopened",
+        "close" | "merge" => "closed",
+        // Updates that push commits carry the previous head revision
+        "update" if attrs.get("oldrev").is_some() => "synchronize",
+        "update" => "edited",
+        _ => return None,
+    };
+
+    let pull_request = json!({
+        "number": attrs["iid"],
+        "title": attrs["title"],
+        "body": attrs["description"],
+        "state": if attrs["state"] == "opened" { "open" } else { "closed" },
+        "merged": attrs["state"] == "merged",
+        "draft": attrs["draft"],
+        "html_url": attrs["url"],
+        "user": { "login": payload["user"]["username"] },
+        "head": {
+            "ref": attrs["source_branch"],
+            "sha": attrs["last_commit"]["id"],
+            "repo": {
+                "full_name": attrs["source"]["path_with_namespace"],
+                "fork": attrs["source_project_id"] != attrs["target_project_id"],
+            },
+        },
+        "base": {
+            "ref": attrs["target_branch"],
+            "repo": { "full_name": attrs["target"]["path_with_namespace"] },
+        },
+    });
+
+    Some(json!({
+        "action": action,
+        "number": attrs["iid"],
+        "pull_request": pull_request,
+        "repository": repository(payload),
+        "sender": { "login": payload["user"]["username"] },
+    }))
+}
+
+fn map_pipeline(payload: &Value) -> Option<Value> {
+    let attrs = payload.get("object_attributes")?;
+    let status = attrs["status"].as_str()?;
+    let conclusion = match status {
+        "success" => Some("success"),
+        "failed" => Some("failure"),
+        "canceled" => Some("cancelled"),
+        "skipped" => Some("skipped"),
+        _ => None,
+    };
+    let (action, suite_status) = match (conclusion, status) {
+        (Some(_), _) => ("completed", "completed"),
+        (None, "running") => ("requested", "in_progress"),
+        (None, _) => ("requested", "queued"),
+    };
+    let pull_requests: Vec<Value> = payload
+        .get("merge_request")
+        .filter(|merge_request| !merge_request.is_null())
+        .map(|merge_request| json!({ "number": merge_request["iid"] }))
+        .into_iter()
+        .collect();
+
+    Some(json!({
+        "action": action,
+        "check_suite": {
+            "id": attrs["id"],
+            "head_sha": attrs["sha"],
+            "head_branch": attrs["ref"],
+            "status": suite_status,
+            "conclusion": conclusion,
+            "url": attrs["url"],
+            "pull_requests": pull_requests,
+        },
+        "repository": repository(payload),
+        "sender": { "login": payload["user"]["username"] },
+    }))
+}
+
+fn map_push(payload: &Value) -> Value {
+    // GitLab commits already list added, modified, and removed files
+    let commits = payload
+        .get("commits")
+        .filter(|commits| commits.is_array())
+        .cloned()
+        .unwrap_or_else(|| json!([]));
+
+    json!({
+        "ref": payload["ref"],
+        "before": payload["before"],
+        "after": payload["after"],
+        "commits": commits,
+        "repository": repository(payload),
+        "pusher": { "name": payload["user_username"] },
+        "sender": { "login": payload["user_username"] },
+    })
+}
+
+fn repository(payload: &Value) -> Value {
+    let project = &payload["project"];
+    json!({
+        "full_name": project["path_with_namespace"],
+        "html_url": project["web_url"],
+        "default_branch": project["default_branch"],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhook::WebhookConfig;
+    use axum::{
+        body::Body,
+        http::{Method, Request},
+        routing::post,
+        Router,
+    };
+    use orchestrate_core::Database;
+    use tower::ServiceExt;
+
+    const MERGE_REQUEST_PAYLOAD: &str = r#"{
+        "object_kind": "merge_request",
+        "user": { "username": "alice" },
+        "project": {
+            "path_with_namespace": "group/app",
+            "web_url": "https://gitlab.example.com/group/app",
+            "default_branch": "main"
+        },
+        "object_attributes": {
+            "iid": 7,
+            "title": "Add login",
+            "description": "Adds the login form",
+            "state": "opened",
+            "action": "open",
+            "source_branch": "feature/login",
+            "target_branch": "main",
+            "source_project_id": 1,
+            "target_project_id": 1,
+            "source": { "path_with_namespace": "group/app" },
+            "target": { "path_with_namespace": "group/app" },
+            "last_commit": { "id": "abc123" },
+            "url": "https://gitlab.example.com/group/app/-/merge_requests/7"
+        }
+    }"#;
+
+    async fn create_test_router(token: Option<&str>) -> (Router, Database) {
+        let database = Database::in_memory().await.unwrap();
+        let config = WebhookConfig::new(token.map(|t| t.to_string()));
+        let state = Arc::new(WebhookState::new(config, database.clone()));
+        let router = Router::new()
+            .route("/webhooks/gitlab", post(gitlab_webhook_handler))
+            .with_state(state);
+        (router, database)
+    }
+
+    fn request(event: &str, token: Option<&str>, payload: &str) -> Request<Body> {
+        let mut builder = Request::builder()
+            .method(Method::POST)
+            .uri("/webhooks/gitlab")
+            .header("content-type", "application/json")
+            .header("x-gitlab-event", event)
+            .header("x-gitlab-event-uuid", "gitlab-delivery-1");
+        if let Some(token) = token {
+            builder = builder.header("x-gitlab-token", token);
+        }
+        builder.body(Body::from(payload.to_string())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_webhook_queues_mapped_merge_request() {
+        let (router, database) = create_test_router(Some("gl-token")).await;
+
+        let response = router
+            .oneshot(request(
+                "Merge Request Hook",
+                Some("gl-token"),
+                MERGE_REQUEST_PAYLOAD,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let event = database
+            .get_webhook_event_by_delivery_id("gitlab-delivery-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.event_type, "pull_request");
+
+        let payload: Value = serde_json::from_str(&event.payload).unwrap();
+        assert_eq!(payload["action"], "opened");
+        assert_eq!(payload["pull_request"]["number"], 7);
+        assert_eq!(payload["pull_request"]["head"]["ref"], "feature/login");
+        assert_eq!(payload["pull_request"]["head"]["repo"]["fork"], false);
+        assert_eq!(payload["pull_request"]["base"]["ref"], "main");
+        assert_eq!(payload["repository"]["full_name"], "group/app");
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_webhook_rejects_bad_token() {
+        let (router, database) = create_test_router(Some("gl-token")).await;
+
+        let response = router
+            .clone()
+            .oneshot(request(
+                "Merge Request Hook",
+                Some("wrong"),
+                MERGE_REQUEST_PAYLOAD,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = router
+            .oneshot(request("Merge Request Hook", None, MERGE_REQUEST_PAYLOAD))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        assert!(database
+            .get_webhook_event_by_delivery_id("gitlab-delivery-1")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_webhook_ignores_unsupported_event() {
+        let (router, database) = create_test_router(None).await;
+
+        let response = router
+            .oneshot(request("Note Hook", None, r#"{"object_kind":"note"}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(database
+            .get_webhook_event_by_delivery_id("gitlab-delivery-1")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_webhook_missing_event_header() {
+        let (router, _) = create_test_router(None).await;
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/webhooks/gitlab")
+                    .body(Body::from("{}"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_map_merge_request_actions() {
+        let mut payload: Value = serde_json::from_str(MERGE_REQUEST_PAYLOAD).unwrap();
+        let action = |payload: &Value| {
+            map_gitlab_event("Merge Request Hook", payload)
+                .map(|(_, mapped)| mapped["action"].clone())
+        };
+
+        payload["object_attributes"]["action"] = json!("update");
+        assert_eq!(action(&payload), Some(json!("edited")));
+        payload["object_attributes"]["oldrev"] = json!("def456");
+        assert_eq!(action(&payload), Some(json!("synchronize")));
+
+        payload["object_attributes"]["action"] = json!("merge");
+        payload["object_attributes"]["state"] = json!("merged");
+        let (_, mapped) = map_gitlab_event("Merge Request Hook", &payload).unwrap();
+        assert_eq!(mapped["action"], "closed");
+        assert_eq!(mapped["pull_request"]["merged"], true);
+
+        payload["object_attributes"]["action"] = json!("approved");
+        assert_eq!(action(&payload), None);
+    }
+
+    #[test]
+    fn test_map_pipeline_to_check_suite() {
+        let payload = json!({
+            "object_kind": "pipeline",
+            "user": { "username": "alice" },
+            "project": { "path_with_namespace": "group/app" },
+            "object_attributes": {
+                "id": 31,
+                "ref": "feature/login",
+                "sha": "abc123",
+                "status": "failed"
+            },
+            "merge_request": { "iid": 7 }
+        });
+
+        let (event_type, mapped) = map_gitlab_event("Pipeline Hook", &payload).unwrap();
+        assert_eq!(event_type, "check_suite");
+        assert_eq!(mapped["action"], "completed");
+        assert_eq!(mapped["check_suite"]["id"], 31);
+        assert_eq!(mapped["check_suite"]["conclusion"], "failure");
+        assert_eq!(mapped["check_suite"]["head_sha"], "abc123");
+        assert_eq!(mapped["check_suite"]["pull_requests"][0]["number"], 7);
+
+        let running = json!({ "object_attributes": { "id": 32, "status": "running" } });
+        let (_, mapped) = map_gitlab_event("Pipeline Hook", &running).unwrap();
+        assert_eq!(mapped["action"], "requested");
+        assert_eq!(mapped["check_suite"]["status"], "in_progress");
+        assert!(mapped["check_suite"]["conclusion"].is_null());
+    }
+
+    #[test]
+    fn test_map_push() {
+        let payload = json!({
+            "object_kind": "push",
+            "ref": "refs/heads/main",
+            "before": "000111",
+            "after": "222333",
+            "user_username": "alice",
+            "project": { "path_with_namespace": "group/app", "default_branch": "main" },
+            "commits": [
+                { "id": "222333", "message": "Fix", "added": [], "modified": ["src/lib.rs"], "removed": [] }
+            ]
+        });
+
+        let (event_type, mapped) = map_gitlab_event("Push Hook", &payload).unwrap();
+        assert_eq!(event_type, "push");
+        assert_eq!(mapped["ref"], "refs/heads/main");
+        assert_eq!(mapped["after"], "222333");
+        assert_eq!(mapped["repository"]["full_name"], "group/app");
+        assert_eq!(mapped["commits"][0]["modified"][0], "src/lib.rs");
+    }
+}
@@ -0,0 +1,515 @@
+//! GraphQL API
+//!
+//! Read-only GraphQL over the core data model (agents and their messages,
+//! pipelines with their runs and stages, and costs) at `POST /api/graphql`,
+//! so clients can fetch exactly the fields they need in one round trip.
+//! `GET /api/graphql` serves GraphiQL for exploring the schema.
+//!
+//! Built with the `graphql` feature.
+
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, Object, SimpleObject, ID,
+};
+use axum::{
+    extract::State,
+    response::{Html, IntoResponse},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use orchestrate_core::Database;
+use std::sync::{Arc, OnceLock};
+use uuid::Uuid;
+
+use crate::api::AppState;
+
+/// The GraphQL schema
+pub type ApiSchema = async_graphql::Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Deepest query nesting accepted, e.g. run -> stages -> agent -> messages
+const MAX_DEPTH: usize = 10;
+
+pub fn schema() -> &'static ApiSchema {
+    static SCHEMA: OnceLock<ApiSchema> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        async_graphql::Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .limit_depth(MAX_DEPTH)
+            .finish()
+    })
+}
+
+fn database<'a>(ctx: &Context<'a>) -> async_graphql::Result<&'a Database> {
+    ctx.data::<Database>()
+}
+
+fn take<T>(items: Vec<T>, limit: Option<usize>) -> Vec<T> {
+    match limit {
+        Some(limit) => items.into_iter().take(limit).collect(),
+        None => items,
+    }
+}
+
+/// An agent
+pub struct Agent(orchestrate_core::Agent);
+
+#[Object]
+impl Agent {
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    async fn agent_type(&self) -> &str {
+        self.0.agent_type.as_str()
+    }
+
+    async fn custom_type(&self) -> Option<&str> {
+        self.0.custom_type.as_deref()
+    }
+
+    async fn state(&self) -> &str {
+        self.0.state.as_str()
+    }
+
+    async fn task(&self) -> &str {
+        &self.0.task
+    }
+
+    async fn error_message(&self) -> Option<&str> {
+        self.0.error_message.as_deref()
+    }
+
+    async fn parent_agent_id(&self) -> Option<ID> {
+        self.0.parent_agent_id.map(|id| ID(id.to_string()))
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn completed_at(&self) -> Option<DateTime<Utc>> {
+        self.0.completed_at
+    }
+
+    /// Conversation messages, oldest first
+    async fn messages(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<usize>,
+    ) -> async_graphql::Result<Vec<Message>> {
+        let messages = database(ctx)?.get_messages(self.0.id).await?;
+        Ok(take(messages, limit).into_iter().map(Message).collect())
+    }
+}
+
+/// A message of an agent's conversation
+pub struct Message(orchestrate_core::Message);
+
+#[Object]
+impl Message {
+    async fn id(&self) -> i64 {
+        self.0.id
+    }
+
+    async fn role(&self) -> &str {
+        self.0.role.as_str()
+    }
+
+    async fn content(&self) -> &str {
+        &self.0.content
+    }
+
+    async fn input_tokens(&self) -> i32 {
+        self.0.input_tokens
+    }
+
+    async fn output_tokens(&self) -> i32 {
+        self.0.output_tokens
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+}
+
+/// A pipeline definition
+pub struct Pipeline(orchestrate_core::Pipeline);
+
+#[Object]
+impl Pipeline {
+    async fn id(&self) -> Option<i64> {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// Definition in YAML
+    async fn definition(&self) -> &str {
+        &self.0.definition
+    }
+
+    async fn enabled(&self) -> bool {
+        self.0.enabled
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    /// Runs, newest first
+    async fn runs(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<usize>,
+    ) -> async_graphql::Result<Vec<PipelineRun>> {
+        let Some(id) = self.0.id else {
+            return Ok(Vec::new());
+        };
+        let runs = database(ctx)?.list_pipeline_runs(id).await?;
+        Ok(take(runs, limit).into_iter().map(PipelineRun).collect())
+    }
+}
+
+/// A run of a pipeline
+pub struct PipelineRun(orchestrate_core::PipelineRun);
+
+#[Object]
+impl PipelineRun {
+    async fn id(&self) -> Option<i64> {
+        self.0.id
+    }
+
+    async fn pipeline_id(&self) -> i64 {
+        self.0.pipeline_id
+    }
+
+    async fn status(&self) -> &str {
+        self.0.status.as_str()
+    }
+
+    async fn trigger_event(&self) -> Option<&str> {
+        self.0.trigger_event.as_deref()
+    }
+
+    async fn commit_sha(&self) -> Option<&str> {
+        self.0.commit_sha.as_deref()
+    }
+
+    async fn started_at(&self) -> Option<DateTime<Utc>> {
+        self.0.started_at
+    }
+
+    async fn completed_at(&self) -> Option<DateTime<Utc>> {
+        self.0.completed_at
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn pipeline(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Pipeline>> {
+        let pipeline = database(ctx)?.get_pipeline(self.0.pipeline_id).await?;
+        Ok(pipeline.map(Pipeline))
+    }
+
+    async fn stages(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<PipelineStage>> {
+        let Some(id) = self.0.id else {
+            return Ok(Vec::new());
+        };
+        let stages = database(ctx)?.list_pipeline_stages(id).await?;
+        Ok(stages.into_iter().map(PipelineStage).collect())
+    }
+}
+
+/// A stage of a pipeline run
+pub struct PipelineStage(orchestrate_core::PipelineStage);
+
+#[Object]
+impl PipelineStage {
+    async fn id(&self) -> Option<i64> {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.stage_name
+    }
+
+    async fn status(&self) -> &str {
+        self.0.status.as_str()
+    }
+
+    async fn attempts(&self) -> i32 {
+        self.0.attempts
+    }
+
+    async fn cached_from_run_id(&self) -> Option<i64> {
+        self.0.cached_from_run_id
+    }
+
+    async fn started_at(&self) -> Option<DateTime<Utc>> {
+        self.0.started_at
+    }
+
+    async fn completed_at(&self) -> Option<DateTime<Utc>> {
+        self.0.completed_at
+    }
+
+    /// Agent that executed the stage
+    async fn agent(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Agent>> {
+        let Some(id) = self
+            .0
+            .agent_id
+            .as_deref()
+            .and_then(|id| Uuid::parse_str(id).ok())
+        else {
+            return Ok(None);
+        };
+        let agent = database(ctx)?.get_agent(id).await?;
+        Ok(agent.map(Agent))
+    }
+}
+
+/// Token usage and cost of one model on one day
+#[derive(SimpleObject)]
+pub struct DailyCost {
+    pub date: String,
+    pub model: String,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub request_count: i64,
+    pub agent_count: i64,
+    pub estimated_cost_usd: Option<f64>,
+}
+
+/// Total token usage and cost of an epic or story
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct EntityCost {
+    pub id: String,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub request_count: i64,
+    pub estimated_cost_usd: f64,
+}
+
+#[ComplexObject]
+impl EntityCost {
+    async fn total_tokens(&self) -> i64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+impl From<orchestrate_core::EntityCostSummary> for EntityCost {
+    fn from(summary: orchestrate_core::EntityCostSummary) -> Self {
+        Self {
+            id: summary.entity_id,
+            input_tokens: summary.total_input_tokens,
+            output_tokens: summary.total_output_tokens,
+            request_count: summary.request_count,
+            estimated_cost_usd: summary.estimated_cost_usd,
+        }
+    }
+}
+
+/// Root of all queries
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Agents, newest first, optionally in one state
+    async fn agents(
+        &self,
+        ctx: &Context<'_>,
+        state: Option<String>,
+        limit: Option<usize>,
+    ) -> async_graphql::Result<Vec<Agent>> {
+        let agents = database(ctx)?
+            .list_agents()
+            .await?
+            .into_iter()
+            .filter(|a| {
+                state
+                    .as_deref()
+                    .is_none_or(|s| a.state.as_str().eq_ignore_ascii_case(s))
+            })
+            .collect();
+        Ok(take(agents, limit).into_iter().map(Agent).collect())
+    }
+
+    async fn agent(&self, ctx: &Context<'_>, id: ID) -> async_graphql::Result<Option<Agent>> {
+        let id = Uuid::parse_str(&id)?;
+        Ok(database(ctx)?.get_agent(id).await?.map(Agent))
+    }
+
+    async fn pipelines(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Pipeline>> {
+        let pipelines = database(ctx)?.list_pipelines().await?;
+        Ok(pipelines.into_iter().map(Pipeline).collect())
+    }
+
+    async fn pipeline(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> async_graphql::Result<Option<Pipeline>> {
+        Ok(database(ctx)?
+            .get_pipeline_by_name(&name)
+            .await?
+            .map(Pipeline))
+    }
+
+    async fn pipeline_run(
+        &self,
+        ctx: &Context<'_>,
+        id: i64,
+    ) -> async_graphql::Result<Option<PipelineRun>> {
+        Ok(database(ctx)?.get_pipeline_run(id).await?.map(PipelineRun))
+    }
+
+    /// Token usage and cost per model and day over the last `days` days
+    async fn daily_costs(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 30)] days: i32,
+    ) -> async_graphql::Result<Vec<DailyCost>> {
+        let usage = database(ctx)?.get_daily_token_usage(days).await?;
+        Ok(usage
+            .into_iter()
+            .map(|u| DailyCost {
+                date: u.date,
+                model: u.model,
+                input_tokens: u.total_input_tokens,
+                output_tokens: u.total_output_tokens,
+                request_count: u.request_count,
+                agent_count: u.agent_count,
+                estimated_cost_usd: u.estimated_cost_usd,
+            })
+            .collect())
+    }
+
+    /// Cost per epic, most expensive first (all time unless `days` is given)
+    async fn epic_costs(
+        &self,
+        ctx: &Context<'_>,
+        days: Option<i32>,
+    ) -> async_graphql::Result<Vec<EntityCost>> {
+        let costs = database(ctx)?.list_epic_costs(days).await?;
+        Ok(costs.into_iter().map(Into::into).collect())
+    }
+
+    /// Cost per story, most expensive first, optionally of one epic
+    async fn story_costs(
+        &self,
+        ctx: &Context<'_>,
+        epic_id: Option<String>,
+        days: Option<i32>,
+    ) -> async_graphql::Result<Vec<EntityCost>> {
+        let costs = database(ctx)?
+            .list_story_costs(epic_id.as_deref(), days)
+            .await?;
+        Ok(costs.into_iter().map(Into::into).collect())
+    }
+}
+
+/// POST /api/graphql - Execute a GraphQL query
+#[utoipa::path(
+    post,
+    path = "/api/graphql",
+    tag = "graphql",
+    summary = "Run a GraphQL query or mutation",
+    request_body = Object,
+    responses((status = 200, body = Object))
+)]
+async fn graphql_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema().execute(request.data(state.db.clone())).await)
+}
+
+/// GET /api/graphql - GraphiQL explorer
+#[utoipa::path(
+    get,
+    path = "/api/graphql",
+    tag = "graphql",
+    summary = "GraphiQL query explorer",
+    responses((status = 200, description = "GraphiQL IDE", content_type = "text/html"))
+)]
+async fn graphiql() -> impl IntoResponse {
+    Html(GraphiQLSource::build().endpoint("/api/graphql").finish())
+}
+
+/// Create the GraphQL router
+pub fn create_graphql_router() -> Router<Arc<AppState>> {
+    Router::new().route("/api/graphql", get(graphiql).post(graphql_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orchestrate_core::{AgentType, Pipeline as CorePipeline, PipelineRun as CoreRun};
+    use serde_json::json;
+
+    async fn execute(db: &Database, query: &str) -> serde_json::Value {
+        let response = schema()
+            .execute(async_graphql::Request::new(query).data(db.clone()))
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_query_agents_with_messages() {
+        let db = Database::in_memory().await.unwrap();
+        let agent = orchestrate_core::Agent::new(AgentType::StoryDeveloper, "Build it");
+        db.insert_agent(&agent).await.unwrap();
+        db.insert_message(&orchestrate_core::Message::user(agent.id, "hello"))
+            .await
+            .unwrap();
+
+        let data = execute(&db, "{ agents { task state messages { role content } } }").await;
+        assert_eq!(
+            data,
+            json!({"agents": [{
+                "task": "Build it",
+                "state": agent.state.as_str(),
+                "messages": [{"role": "user", "content": "hello"}],
+            }]})
+        );
+
+        let data = execute(&db, "{ agents(state: \"completed\") { id } }").await;
+        assert_eq!(data, json!({"agents": []}));
+    }
+
+    #[tokio::test]
+    async fn test_query_pipeline_runs() {
+        let db = Database::in_memory().await.unwrap();
+        let pipeline_id = db
+            .insert_pipeline(&CorePipeline::new("ci".to_string(), "name: ci".to_string()))
+            .await
+            .unwrap();
+        let run_id = db
+            .insert_pipeline_run(&CoreRun::new(pipeline_id, Some("push".to_string())))
+            .await
+            .unwrap();
+
+        let query = format!(
+            "{{ pipelineRun(id: {}) {{ triggerEvent pipeline {{ name runs {{ id }} }} stages {{ name }} }} }}",
+            run_id
+        );
+        let data = execute(&db, &query).await;
+        assert_eq!(
+            data,
+            json!({"pipelineRun": {
+                "triggerEvent": "push",
+                "pipeline": {"name": "ci", "runs": [{"id": run_id}]},
+                "stages": [],
+            }})
+        );
+    }
+}
@@ -0,0 +1,394 @@
+//! Incident endpoints backing the incident timeline
+//!
+//! - GET /api/incidents - Incidents, newest first
+//! - GET /api/incidents/:id - Incident with its timeline, root cause analysis,
+//!   playbook executions, and post-mortem
+//! - POST /api/incidents/:id/timeline - Comment on the timeline
+//! - POST /api/incidents/:id/postmortem - Generate the post-mortem
+//!
+//! Generated post-mortems are kept in object storage too, when configured.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::Utc;
+use orchestrate_core::{
+    Incident, IncidentStatus, PlaybookExecution, PostMortem, RootCauseAnalysis, TimelineEvent,
+    TimelineEventType,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::api::{ApiError, AppState};
+use crate::auth::Identity;
+
+/// Query parameters of the incident list
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct IncidentQuery {
+    pub status: Option<String>,
+    pub severity: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// A playbook execution, with the name of its playbook
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PlaybookExecutionView {
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub execution: PlaybookExecution,
+    pub playbook_name: Option<String>,
+}
+
+/// An incident with everything known about it
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IncidentDetail {
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub incident: Incident,
+    /// Evidence and hypotheses of the investigation
+    #[schema(value_type = Option<Object>)]
+    pub root_cause: Option<RootCauseAnalysis>,
+    pub playbook_executions: Vec<PlaybookExecutionView>,
+    #[schema(value_type = Option<Object>)]
+    pub post_mortem: Option<PostMortem>,
+}
+
+/// Request body to comment on an incident's timeline
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TimelineCommentRequest {
+    pub description: String,
+    /// Author of the comment, ignored for signed-in users
+    pub actor: Option<String>,
+}
+
+/// A generated post-mortem
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PostMortemResponse {
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub post_mortem: PostMortem,
+    pub markdown: String,
+    /// Signed URL of the stored Markdown, when object storage is configured
+    pub artifact_url: Option<String>,
+}
+
+fn db_error(e: orchestrate_core::Error) -> ApiError {
+    ApiError::internal(format!("Database error: {}", e))
+}
+
+async fn find_incident(state: &AppState, id: &str) -> Result<Incident, ApiError> {
+    state
+        .db
+        .get_incident(id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| ApiError::not_found("Incident"))
+}
+
+/// GET /api/incidents - Incidents, optionally by status and severity
+#[utoipa::path(
+    get,
+    path = "/api/incidents",
+    tag = "incidents",
+    summary = "List incidents",
+    params(IncidentQuery),
+    responses((status = 200, body = [Object]))
+)]
+pub async fn list_incidents(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<IncidentQuery>,
+) -> Result<Json<Vec<Incident>>, ApiError> {
+    let incidents = state
+        .db
+        .list_incidents(
+            query.status.as_deref(),
+            query.severity.as_deref(),
+            Some(query.limit.unwrap_or(100).clamp(1, 1000)),
+        )
+        .await
+        .map_err(db_error)?;
+    Ok(Json(incidents))
+}
+
+/// GET /api/incidents/:id - Incident detail
+#[utoipa::path(
+    get,
+    path = "/api/incidents/{id}",
+    tag = "incidents",
+    summary = "Get an incident with its timeline",
+    params(("id" = String, Path)),
+    responses((status = 200, body = IncidentDetail))
+)]
+pub async fn get_incident(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<IncidentDetail>, ApiError> {
+    let incident = find_incident(&state, &id).await?;
+    let root_cause = state
+        .db
+        .get_root_cause_analysis(&id)
+        .await
+        .map_err(db_error)?;
+    let post_mortem = state.db.get_post_mortem(&id).await.map_err(db_error)?;
+
+    let executions = state
+        .db
+        .list_incident_playbook_executions(&id)
+        .await
+        .map_err(db_error)?;
+    let mut playbook_names: HashMap<String, Option<String>> = HashMap::new();
+    let mut playbook_executions = Vec::with_capacity(executions.len());
+    for execution in executions {
+        if !playbook_names.contains_key(&execution.playbook_id) {
+            let name = state
+                .db
+                .get_playbook(&execution.playbook_id)
+                .await
+                .map_err(db_error)?
+                .map(|playbook| playbook.name);
+            playbook_names.insert(execution.playbook_id.clone(), name);
+        }
+        playbook_executions.push(PlaybookExecutionView {
+            playbook_name: playbook_names[&execution.playbook_id].clone(),
+            execution,
+        });
+    }
+
+    Ok(Json(IncidentDetail {
+        incident,
+        root_cause,
+        playbook_executions,
+        post_mortem,
+    }))
+}
+
+/// POST /api/incidents/:id/timeline - Add a comment to the timeline
+#[utoipa::path(
+    post,
+    path = "/api/incidents/{id}/timeline",
+    tag = "incidents",
+    summary = "Comment on an incident",
+    params(("id" = String, Path)),
+    request_body = TimelineCommentRequest,
+    responses((status = 200, body = Object))
+)]
+pub async fn add_timeline_comment(
+    State(state): State<Arc<AppState>>,
+    identity: Option<Identity>,
+    Path(id): Path<String>,
+    Json(req): Json<TimelineCommentRequest>,
+) -> Result<Json<TimelineEvent>, ApiError> {
+    let description = req.description.trim();
+    if description.is_empty() {
+        return Err(ApiError::validation("Comment cannot be empty"));
+    }
+    find_incident(&state, &id).await?;
+
+    let event = TimelineEvent {
+        timestamp: Utc::now(),
+        event_type: TimelineEventType::Comment,
+        description: description.to_string(),
+        actor: identity
+            .map(|identity| identity.actor())
+            .or(req.actor.filter(|a| !a.trim().is_empty())),
+        metadata: HashMap::new(),
+    };
+    state
+        .db
+        .add_timeline_event(&id, &event)
+        .await
+        .map_err(db_error)?;
+    Ok(Json(event))
+}
+
+/// POST /api/incidents/:id/postmortem - Generate the post-mortem of a
+/// resolved incident, replacing an earlier one
+#[utoipa::path(
+    post,
+    path = "/api/incidents/{id}/postmortem",
+    tag = "incidents",
+    summary = "Generate a post-mortem",
+    params(("id" = String, Path)),
+    responses((status = 200, body = PostMortemResponse))
+)]
+pub async fn generate_post_mortem(
+    State(state): State<Arc<AppState>>,
+    identity: Option<Identity>,
+    Path(id): Path<String>,
+) -> Result<Json<PostMortemResponse>, ApiError> {
+    let mut incident = find_incident(&state, &id).await?;
+    if incident.status.is_active() {
+        return Err(ApiError::conflict(
+            "Incident must be resolved before its post-mortem",
+        ));
+    }
+    let root_cause = state
+        .db
+        .get_root_cause_analysis(&id)
+        .await
+        .map_err(db_error)?;
+
+    let mut post_mortem = PostMortem::generate(&incident, root_cause.as_ref());
+    let actor = identity.map(|identity| identity.actor());
+    post_mortem.authors.extend(actor.clone());
+    state
+        .db
+        .save_post_mortem(&post_mortem)
+        .await
+        .map_err(db_error)?;
+
+    let event = TimelineEvent {
+        timestamp: Utc::now(),
+        event_type: TimelineEventType::PostMortemCreated,
+        description: "Post-mortem generated".to_string(),
+        actor,
+        metadata: HashMap::new(),
+    };
+    state
+        .db
+        .add_timeline_event(&id, &event)
+        .await
+        .map_err(db_error)?;
+    if incident.status != IncidentStatus::PostMortem {
+        incident.status = IncidentStatus::PostMortem;
+        state
+            .db
+            .update_incident(&incident)
+            .await
+            .map_err(db_error)?;
+    }
+
+    let artifact_url = match &state.artifacts {
+        Some(artifacts) => {
+            let artifact = artifacts
+                .put_postmortem(&post_mortem)
+                .await
+                .map_err(|e| ApiError::internal(format!("Failed to store post-mortem: {}", e)))?;
+            Some(
+                artifacts
+                    .signed_url(&artifact)
+                    .map_err(|e| ApiError::internal(format!("Failed to sign URL: {}", e)))?,
+            )
+        }
+        None => None,
+    };
+
+    Ok(Json(PostMortemResponse {
+        markdown: post_mortem.to_markdown(),
+        post_mortem,
+        artifact_url,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orchestrate_core::{
+        Database, EvidenceType, IncidentSeverity, Playbook, PlaybookExecutionStatus,
+    };
+
+    async fn setup() -> Arc<AppState> {
+        let db = Database::in_memory().await.unwrap();
+        let incident = Incident::new("INC-1", "API down", IncidentSeverity::Critical);
+        db.create_incident(&incident).await.unwrap();
+
+        let mut rca = RootCauseAnalysis::new("INC-1");
+        rca.set_primary_cause("Connection pool exhaustion");
+        rca.add_evidence(EvidenceType::MetricSpike, "Pool at 100%", "metrics");
+        rca.add_hypothesis("Leaked connections", 0.7);
+        db.save_root_cause_analysis(&rca).await.unwrap();
+
+        db.create_playbook(&Playbook::new("pb-1", "restart-api"))
+            .await
+            .unwrap();
+        db.create_playbook_execution(&PlaybookExecution {
+            id: "exec-1".to_string(),
+            playbook_id: "pb-1".to_string(),
+            incident_id: Some("INC-1".to_string()),
+            status: PlaybookExecutionStatus::Completed,
+            started_at: Utc::now(),
+            completed_at: Some(Utc::now()),
+            action_results: vec![],
+            triggered_by: None,
+        })
+        .await
+        .unwrap();
+        Arc::new(AppState::new(db, None))
+    }
+
+    #[tokio::test]
+    async fn test_get_incident_detail() {
+        let state = setup().await;
+
+        let Json(comment) = add_timeline_comment(
+            State(state.clone()),
+            None,
+            Path("INC-1".to_string()),
+            Json(TimelineCommentRequest {
+                description: "Restarting the API".to_string(),
+                actor: Some("oncall".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(comment.event_type, TimelineEventType::Comment);
+
+        let Json(detail) = get_incident(State(state.clone()), Path("INC-1".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(detail.incident.timeline.len(), 2);
+        assert_eq!(detail.incident.timeline[1].actor.as_deref(), Some("oncall"));
+        let rca = detail.root_cause.unwrap();
+        assert_eq!(rca.evidence.len(), 1);
+        assert_eq!(rca.hypotheses.len(), 1);
+        assert_eq!(
+            detail.playbook_executions[0].playbook_name.as_deref(),
+            Some("restart-api")
+        );
+        assert!(detail.post_mortem.is_none());
+
+        let result = get_incident(State(state), Path("INC-404".to_string())).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_post_mortem() {
+        let state = setup().await;
+
+        // Active incidents have no post-mortem yet
+        let result =
+            generate_post_mortem(State(state.clone()), None, Path("INC-1".to_string())).await;
+        assert!(result.is_err());
+
+        let mut incident = state.db.get_incident("INC-1").await.unwrap().unwrap();
+        incident.resolve("Raised the pool size", None);
+        state.db.update_incident(&incident).await.unwrap();
+        let event = incident.timeline.last().unwrap();
+        state.db.add_timeline_event("INC-1", event).await.unwrap();
+
+        let Json(generated) =
+            generate_post_mortem(State(state.clone()), None, Path("INC-1".to_string()))
+                .await
+                .unwrap();
+        assert_eq!(
+            generated.post_mortem.root_cause,
+            "Connection pool exhaustion"
+        );
+        assert_eq!(generated.post_mortem.resolution, "Raised the pool size");
+        assert!(generated.markdown.contains("# Post-Mortem: API down"));
+        assert!(generated.artifact_url.is_none());
+
+        let Json(detail) = get_incident(State(state), Path("INC-1".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(detail.incident.status, IncidentStatus::PostMortem);
+        assert!(detail.post_mortem.is_some());
+        assert_eq!(
+            detail.incident.timeline.last().unwrap().event_type,
+            TimelineEventType::PostMortemCreated
+        );
+    }
+}
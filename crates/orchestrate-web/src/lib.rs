@@ -1,28 +1,97 @@
 //! Orchestrate Web - Web interface
 //!
 //! This crate provides the web interface:
-//! - REST API
+//! - REST API under /api/v1, with deprecated unversioned /api paths, an
+//!   OpenAPI spec and Swagger UI at /docs, and cursor pagination of lists
+//! - Batch spawning and termination of agents and retries of failed
+//!   pipeline runs, with per-item failure reporting
+//! - OIDC login (Google, GitHub, Okta) issuing JWT session tokens
+//! - Role-based access control of API routes
+//! - Per-client API rate limits
+//! - Configurable CORS and security headers
 //! - WebSocket for real-time updates
-//! - HTML UI for agent management
-//! - Chat interface
-//! - GitHub webhook receiver
+//! - Server-sent events streaming the structured event feed
+//! - GraphQL API over agents, pipelines, and costs (`graphql` feature)
+//! - Cost analytics for the cost dashboard, with CSV export
+//! - CSV and Parquet exports of token usage, costs, the audit log, and
+//!   agent history, with background jobs for large exports
+//! - Approval inbox with approval context and delegation
+//! - Epic and story board, spawning BMAD phase agents on transitions
+//! - Incident timelines with investigation results and post-mortems
+//! - React UI embedded in the binary, with SPA fallback routing
+//! - Chat interface, with follow-up instructions and conversion of results
+//!   into stories and pull requests
+//! - GitHub, GitLab, and custom webhook receivers, with administration of
+//!   received events
+//! - Slack slash commands and interactive approvals
+//! - Telegram bot approvals through inline buttons
+//! - PagerDuty webhooks syncing incident acknowledgements and resolutions
+//! - Linear webhooks syncing story statuses and spawning agents from issues
+//! - Datadog export of collected metrics
+//! - Kubernetes liveness and readiness probes of the database, Claude and
+//!   GitHub APIs, and webhook queue
 //! - Autonomous processing API (Epic 016)
 
 pub mod api;
+pub mod approvals;
+pub mod auth;
 pub mod autonomous_api;
+pub mod board;
+pub mod bulk;
+pub mod chat;
+pub mod costs;
+pub mod custom_webhook;
+pub mod datadog;
+pub mod exports;
+pub mod incidents;
+pub mod linear_webhooks;
 pub mod metrics;
 pub mod monitoring;
+pub mod openapi;
+pub mod pagination;
+pub mod pagerduty_webhooks;
+pub mod probes;
+pub mod rate_limit;
+pub mod rbac;
 pub mod schedule_executor;
+pub mod security;
+pub mod slack_commands;
+pub mod slack_interactions;
+pub mod telegram_webhook;
+#[cfg(test)]
+mod test_support;
 pub mod event_handlers;
+pub mod events;
+pub mod gitlab_webhook;
+#[cfg(feature = "graphql")]
+pub mod graphql;
 pub mod ui;
+pub mod versioning;
 pub mod webhook;
+pub mod webhook_events;
 pub mod webhook_processor;
 pub mod websocket;
 
 pub use api::{create_router, create_router_with_webhook};
+pub use auth::{create_auth_router, AuthConfig, Identity, OidcProvider, OidcProviderKind};
 pub use autonomous_api::create_autonomous_router;
+pub use custom_webhook::custom_webhook_handler;
+pub use datadog::{DatadogConfig, DatadogExporter};
+pub use gitlab_webhook::{gitlab_webhook_handler, map_gitlab_event};
+pub use linear_webhooks::linear_webhook_handler;
 pub use metrics::MetricsCollector;
+pub use openapi::{create_openapi_router, openapi_spec};
+pub use pagination::{Page, PageParams};
+pub use pagerduty_webhooks::pagerduty_webhook_handler;
+pub use probes::{ProbeConfig, Probes};
+pub use rate_limit::{RateLimit, RateLimitConfig, RateLimiter};
+pub use rbac::{Permission, RbacPolicy, Role};
+pub use security::CorsConfig;
 pub use schedule_executor::{MissedSchedulePolicy, ScheduleExecutor, ScheduleExecutorConfig};
+pub use slack_commands::slack_command_handler;
+pub use slack_interactions::slack_interaction_handler;
+pub use telegram_webhook::telegram_webhook_handler;
 pub use ui::create_ui_router;
+pub use versioning::{Deprecation, API_VERSION};
 pub use webhook::{WebhookConfig, WebhookState, github_webhook_handler};
 pub use webhook_processor::{WebhookProcessor, WebhookProcessorConfig};
@@ -0,0 +1,231 @@
+//! Linear webhook endpoint
+//!
+//! Receives Linear issue webhooks so work tracked in Linear flows back into
+//! orchestrate: moving an issue linked to a story to another workflow state
+//! updates the story's status, and an issue carrying a configured team's
+//! trigger label spawns an agent working on it. Other deliveries are
+//! acknowledged and ignored.
+//!
+//! Deliveries are verified with the webhook's signing secret: Linear sends
+//! the hex HMAC-SHA256 of the body in `Linear-Signature`.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use orchestrate_core::{
+    linear::{verify_webhook_signature, LINEAR_SIGNATURE_HEADER},
+    LinearSyncService, LinearWebhook, LinearWebhookOutcome,
+};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use crate::webhook::{respond, WebhookState};
+
+/// Linear webhook handler
+///
+/// Verifies the signature and applies issue deliveries to linked stories,
+/// or spawns an agent for newly labelled issues.
+#[utoipa::path(
+    post,
+    path = "/api/linear/webhook",
+    tag = "webhooks",
+    summary = "Receive a Linear webhook",
+    request_body(content = String, content_type = "application/json"),
+    responses(
+        (status = 200, description = "Event accepted"),
+        (status = 400, description = "Malformed payload"),
+        (status = 401, description = "Invalid signature")
+    )
+)]
+pub async fn linear_webhook_handler(
+    State(state): State<Arc<WebhookState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let Some(secret) = state.config.secret.as_ref() else {
+        warn!("Linear webhook received but LINEAR_WEBHOOK_SECRET is not set");
+        return respond(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "error",
+            "Linear webhook secret not configured",
+        )
+        .into_response();
+    };
+
+    let signature = headers
+        .get(LINEAR_SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !verify_webhook_signature(secret, &body, signature) {
+        warn!("Rejected Linear webhook with invalid signature");
+        return respond(StatusCode::UNAUTHORIZED, "error", "Invalid signature").into_response();
+    }
+
+    let webhook: LinearWebhook = match serde_json::from_slice(&body) {
+        Ok(webhook) => webhook,
+        Err(e) => {
+            warn!(error = %e, "Failed to parse Linear webhook");
+            return respond(
+                StatusCode::BAD_REQUEST,
+                "error",
+                &format!("Invalid Linear webhook payload: {}", e),
+            )
+            .into_response();
+        }
+    };
+
+    let service = LinearSyncService::new(state.database.clone());
+    match service.apply_webhook(&webhook).await {
+        Ok(LinearWebhookOutcome::StoryUpdated { story_id, status }) => {
+            info!(story_id = %story_id, status = %status.as_str(), "Applied Linear webhook");
+            respond(
+                StatusCode::OK,
+                "ok",
+                &format!("Story {} is now {}", story_id, status.as_str()),
+            )
+            .into_response()
+        }
+        Ok(LinearWebhookOutcome::AgentSpawned {
+            identifier,
+            agent_id,
+        }) => {
+            info!(issue = %identifier, agent_id = %agent_id, "Spawned agent from Linear webhook");
+            respond(
+                StatusCode::OK,
+                "ok",
+                &format!("Spawned agent {} for {}", agent_id, identifier),
+            )
+            .into_response()
+        }
+        Ok(LinearWebhookOutcome::Ignored) => respond(
+            StatusCode::OK,
+            "ignored",
+            &format!("Ignored {} {}", webhook.entity_type, webhook.action),
+        )
+        .into_response(),
+        Err(e) => {
+            error!(error = %e, "Failed to apply Linear webhook");
+            respond(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "error",
+                "Failed to apply Linear webhook",
+            )
+            .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhook::WebhookConfig;
+    use axum::{
+        body::Body,
+        http::{Method, Request},
+        routing::post,
+        Router,
+    };
+    use hmac::{Hmac, Mac};
+    use orchestrate_core::{Database, LinearTeam};
+    use serde_json::Value;
+    use sha2::Sha256;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "linear-webhook-secret";
+
+    async fn create_test_router(secret: Option<&str>) -> (Router, Database) {
+        let database = Database::in_memory().await.unwrap();
+        let state = Arc::new(WebhookState::new(
+            WebhookConfig::new(secret.map(str::to_string)),
+            database.clone(),
+        ));
+        let router = Router::new()
+            .route("/api/linear/webhook", post(linear_webhook_handler))
+            .with_state(state);
+        (router, database)
+    }
+
+    fn sign(body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(body.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn issue_created(labels: &[&str]) -> String {
+        serde_json::json!({
+            "action": "create",
+            "type": "Issue",
+            "url": "https://linear.app/acme/issue/ENG-9",
+            "data": {
+                "id": "issue-uuid",
+                "identifier": "ENG-9",
+                "title": "Fix flaky login test",
+                "teamId": "team-uuid",
+                "labels": labels.iter().map(|name| serde_json::json!({"name": name})).collect::<Vec<_>>()
+            }
+        })
+        .to_string()
+    }
+
+    fn request(body: String, signature: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/api/linear/webhook")
+            .header("content-type", "application/json")
+            .header("linear-signature", signature)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn send(router: Router, body: String) -> (StatusCode, Value) {
+        let signature = sign(&body);
+        let response = router.oneshot(request(body, &signature)).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_signature() {
+        let (router, _) = create_test_router(Some(SECRET)).await;
+        let body = issue_created(&["orchestrate"]);
+        let response = router
+            .oneshot(request(body.clone(), "deadbeef"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let (router, _) = create_test_router(None).await;
+        let response = router
+            .oneshot(request(body.clone(), &sign(&body)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_labelled_issue_spawns_agent() {
+        let (router, database) = create_test_router(Some(SECRET)).await;
+        database
+            .insert_linear_team(&LinearTeam::new("eng", "team-uuid"))
+            .await
+            .unwrap();
+
+        let (status, reply) = send(router.clone(), issue_created(&[])).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(reply["status"], "ignored");
+
+        let (status, reply) = send(router, issue_created(&["orchestrate"])).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(reply["status"], "ok");
+
+        let agents = database.list_agents().await.unwrap();
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].task, "ENG-9: Fix flaky login test");
+    }
+}
@@ -1,19 +1,85 @@
 //! Prometheus metrics collection and export
 //!
 //! This module provides metrics collection in Prometheus format including:
-//! - Agent metrics (count by state and type)
+//! - Agent metrics (count by state and type, loop turns)
 //! - Token usage metrics
-//! - API latency histograms
+//! - API latency histograms and in-flight requests by route, with p50/p95/p99
+//!   over recent requests for the monitoring summary
 //! - Queue depth metrics
+//! - Webhook event lag and outcomes by event type
+//! - Schedule lag metrics
+//! - Pipeline duration metrics
 //! - Error rate metrics
+//! - Remaining GitHub API rate limit quota
+//! - API requests rejected by rate limits
 //! - Business metrics (PR cycle time, story completion rate, etc.)
+//!
+//! Metrics are exposed in the Prometheus text format, or in the OpenMetrics
+//! format with exemplars linking samples to the agent that produced them.
 
 use orchestrate_core::Database;
+use orchestrate_github::RateLimit;
 use prometheus::{
-    Counter, CounterVec, Encoder, Gauge, GaugeVec, Histogram, HistogramOpts, HistogramVec, Opts, Registry, TextEncoder,
+    core::Collector,
+    proto::{MetricFamily, MetricType},
+    Counter, CounterVec, Encoder, Gauge, GaugeVec, Histogram, HistogramOpts, HistogramVec,
+    IntGaugeVec, Opts, Registry, TextEncoder,
 };
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
 use std::sync::{Arc, Mutex};
+use utoipa::ToSchema;
+
+/// Content type of the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Content type of the OpenMetrics text exposition format
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Histogram buckets for agent execution time in seconds
+const AGENT_EXECUTION_BUCKETS: [f64; 8] = [1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0];
+
+/// Requests per route kept for latency percentiles
+const LATENCY_WINDOW: usize = 1024;
+
+/// Recent request durations in seconds by method and route
+type LatencySamples = HashMap<(String, String), VecDeque<f64>>;
+
+/// Histogram buckets for how long webhook events wait before processing, in seconds
+const WEBHOOK_WAIT_BUCKETS: [f64; 9] = [0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0, 900.0];
+
+/// Example sample linking a metric to the agent that produced it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exemplar {
+    pub agent_id: String,
+    pub value: f64,
+}
+
+/// Latency percentiles of one route over its recent requests
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct EndpointLatency {
+    pub method: String,
+    pub path: String,
+    /// Requests the percentiles cover
+    pub requests: usize,
+    pub in_flight: i64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+/// Counts a request as in flight until dropped
+pub struct InFlightGuard {
+    gauge: prometheus::IntGauge,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.gauge.dec();
+    }
+}
 
 /// Metrics collector for the orchestrate system
 pub struct MetricsCollector {
@@ -22,6 +88,7 @@ pub struct MetricsCollector {
     // Agent metrics
     agents_total: GaugeVec,
     agent_execution_seconds: HistogramVec,
+    agent_turns_total: CounterVec,
 
     // Token metrics
     tokens_total: CounterVec,
@@ -29,13 +96,31 @@ pub struct MetricsCollector {
     // API metrics
     http_requests_total: CounterVec,
     http_request_duration_seconds: HistogramVec,
+    http_requests_in_flight: IntGaugeVec,
 
     // Queue metrics
     queue_depth: GaugeVec,
 
+    // Webhook metrics
+    webhook_queue_lag_seconds: GaugeVec,
+    webhook_wait_seconds: HistogramVec,
+    webhook_events_total: CounterVec,
+
+    // Schedule metrics
+    schedule_lag_seconds: GaugeVec,
+
+    // Pipeline metrics
+    pipeline_duration_seconds: GaugeVec,
+
     // Error metrics
     errors_total: CounterVec,
 
+    // GitHub API metrics
+    github_rate_limit_remaining: GaugeVec,
+
+    // API rate limiting metrics
+    rate_limited_requests_total: CounterVec,
+
     // Business metrics
     pr_cycle_time_seconds: HistogramVec,
     story_completion_rate: GaugeVec,
@@ -48,6 +133,11 @@ pub struct MetricsCollector {
     custom_counters: Arc<Mutex<HashMap<String, Counter>>>,
     custom_gauges: Arc<Mutex<HashMap<String, Gauge>>>,
     custom_histograms: Arc<Mutex<HashMap<String, Histogram>>>,
+
+    // Exemplars by sample, see `sample_key`
+    exemplars: Arc<Mutex<HashMap<String, Exemplar>>>,
+
+    latency_samples: Arc<Mutex<LatencySamples>>,
 }
 
 impl MetricsCollector {
@@ -66,10 +156,15 @@ impl MetricsCollector {
                 "orchestrate_agent_execution_seconds",
                 "Agent execution duration in seconds",
             )
-            .buckets(vec![1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0]),
+            .buckets(AGENT_EXECUTION_BUCKETS.to_vec()),
             &["type"],
         )?;
 
+        let agent_turns_total = CounterVec::new(
+            Opts::new("orchestrate_agent_turns_total", "Total agent loop turns by agent type"),
+            &["agent_type"],
+        )?;
+
         // Token metrics
         let tokens_total = CounterVec::new(
             Opts::new("orchestrate_tokens_total", "Total tokens used by model and direction"),
@@ -88,6 +183,14 @@ impl MetricsCollector {
                 "HTTP request duration in seconds",
             )
             .buckets(vec![0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0]),
+            &["method", "path", "status"],
+        )?;
+
+        let http_requests_in_flight = IntGaugeVec::new(
+            Opts::new(
+                "orchestrate_http_requests_in_flight",
+                "HTTP requests being served by method and path",
+            ),
             &["method", "path"],
         )?;
 
@@ -97,12 +200,74 @@ impl MetricsCollector {
             &["queue"],
         )?;
 
+        // Webhook metrics
+        let webhook_queue_lag_seconds = GaugeVec::new(
+            Opts::new(
+                "orchestrate_webhook_queue_lag_seconds",
+                "Age of the oldest pending webhook event by event type",
+            ),
+            &["event_type"],
+        )?;
+
+        let webhook_wait_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "orchestrate_webhook_wait_seconds",
+                "Seconds from receiving a webhook event to processing it by event type",
+            )
+            .buckets(WEBHOOK_WAIT_BUCKETS.to_vec()),
+            &["event_type"],
+        )?;
+
+        let webhook_events_total = CounterVec::new(
+            Opts::new(
+                "orchestrate_webhook_events_total",
+                "Processed webhook events by event type and outcome",
+            ),
+            &["event_type", "outcome"],
+        )?;
+
+        // Schedule metrics
+        let schedule_lag_seconds = GaugeVec::new(
+            Opts::new(
+                "orchestrate_schedule_lag_seconds",
+                "Seconds an enabled schedule is overdue by schedule name",
+            ),
+            &["schedule"],
+        )?;
+
+        // Pipeline metrics
+        let pipeline_duration_seconds = GaugeVec::new(
+            Opts::new(
+                "orchestrate_pipeline_duration_seconds",
+                "Average pipeline run duration in seconds by pipeline and status",
+            ),
+            &["pipeline", "status"],
+        )?;
+
         // Error metrics
         let errors_total = CounterVec::new(
             Opts::new("orchestrate_errors_total", "Total errors by type"),
             &["error_type"],
         )?;
 
+        // GitHub API metrics
+        let github_rate_limit_remaining = GaugeVec::new(
+            Opts::new(
+                "orchestrate_github_rate_limit_remaining",
+                "Remaining GitHub API rate limit quota by resource",
+            ),
+            &["resource"],
+        )?;
+
+        // API rate limiting metrics
+        let rate_limited_requests_total = CounterVec::new(
+            Opts::new(
+                "orchestrate_rate_limited_requests_total",
+                "API requests rejected by rate limits by client kind and route",
+            ),
+            &["client", "path"],
+        )?;
+
         // Business metrics - PR cycle time (open to merge)
         let pr_cycle_time_seconds = HistogramVec::new(
             HistogramOpts::new(
@@ -150,11 +315,20 @@ impl MetricsCollector {
         // Register all metrics
         registry.register(Box::new(agents_total.clone()))?;
         registry.register(Box::new(agent_execution_seconds.clone()))?;
+        registry.register(Box::new(agent_turns_total.clone()))?;
         registry.register(Box::new(tokens_total.clone()))?;
         registry.register(Box::new(http_requests_total.clone()))?;
         registry.register(Box::new(http_request_duration_seconds.clone()))?;
+        registry.register(Box::new(http_requests_in_flight.clone()))?;
         registry.register(Box::new(queue_depth.clone()))?;
+        registry.register(Box::new(webhook_queue_lag_seconds.clone()))?;
+        registry.register(Box::new(webhook_wait_seconds.clone()))?;
+        registry.register(Box::new(webhook_events_total.clone()))?;
+        registry.register(Box::new(schedule_lag_seconds.clone()))?;
+        registry.register(Box::new(pipeline_duration_seconds.clone()))?;
         registry.register(Box::new(errors_total.clone()))?;
+        registry.register(Box::new(github_rate_limit_remaining.clone()))?;
+        registry.register(Box::new(rate_limited_requests_total.clone()))?;
         registry.register(Box::new(pr_cycle_time_seconds.clone()))?;
         registry.register(Box::new(story_completion_rate.clone()))?;
         registry.register(Box::new(agent_success_rate.clone()))?;
@@ -166,11 +340,20 @@ impl MetricsCollector {
             registry,
             agents_total,
             agent_execution_seconds,
+            agent_turns_total,
             tokens_total,
             http_requests_total,
             http_request_duration_seconds,
+            http_requests_in_flight,
             queue_depth,
+            webhook_queue_lag_seconds,
+            webhook_wait_seconds,
+            webhook_events_total,
+            schedule_lag_seconds,
+            pipeline_duration_seconds,
             errors_total,
+            github_rate_limit_remaining,
+            rate_limited_requests_total,
             pr_cycle_time_seconds,
             story_completion_rate,
             agent_success_rate,
@@ -180,6 +363,8 @@ impl MetricsCollector {
             custom_counters: Arc::new(Mutex::new(HashMap::new())),
             custom_gauges: Arc::new(Mutex::new(HashMap::new())),
             custom_histograms: Arc::new(Mutex::new(HashMap::new())),
+            exemplars: Arc::new(Mutex::new(HashMap::new())),
+            latency_samples: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 
@@ -219,15 +404,40 @@ impl MetricsCollector {
         Ok(())
     }
 
+    /// Update agent turn metrics from database
+    ///
+    /// Each agent type's counter carries the agent that ran the latest turn as
+    /// its exemplar.
+    pub async fn update_agent_turn_metrics(&self, db: &Database) -> Result<(), Box<dyn std::error::Error>> {
+        let turn_counts = db.get_agent_turn_counts().await?;
+
+        self.agent_turns_total.reset();
+        for count in turn_counts {
+            self.agent_turns_total
+                .with_label_values(&[&count.agent_type])
+                .inc_by(count.turns as f64);
+            self.set_exemplar(
+                "orchestrate_agent_turns_total",
+                &[("agent_type", &count.agent_type)],
+                Exemplar {
+                    agent_id: count.latest_agent_id,
+                    value: 1.0,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
     /// Update token usage metrics from database
     pub async fn update_token_metrics(&self, db: &Database) -> Result<(), Box<dyn std::error::Error>> {
         // For now, we get token stats from messages table
         // In the future, this could use daily_token_usage or session_token_stats tables
         let token_stats = db.get_token_usage_by_model().await?;
 
+        // Reset and set to current values so repeated updates don't double count
+        self.tokens_total.reset();
         for stats in token_stats {
-            // Reset and set to current values
-            // Note: These are gauges effectively showing current totals
             self.tokens_total
                 .with_label_values(&[&stats.model, "input"])
                 .inc_by(stats.input_tokens as f64);
@@ -248,9 +458,54 @@ impl MetricsCollector {
             .with_label_values(&["webhook_events"])
             .set(webhook_events as f64);
 
+        // Reset so event types with an empty queue drop out
+        self.webhook_queue_lag_seconds.reset();
+        for lag in db.get_webhook_queue_lags().await? {
+            self.webhook_queue_lag_seconds
+                .with_label_values(&[&lag.event_type])
+                .set(lag.lag_seconds);
+        }
+
         Ok(())
     }
 
+    /// Update schedule lag metrics from database
+    pub async fn update_schedule_metrics(&self, db: &Database) -> Result<(), Box<dyn std::error::Error>> {
+        let lags = db.get_schedule_lags().await?;
+
+        // Reset so deleted and disabled schedules drop out
+        self.schedule_lag_seconds.reset();
+        for lag in lags {
+            self.schedule_lag_seconds
+                .with_label_values(&[&lag.schedule])
+                .set(lag.lag_seconds);
+        }
+
+        Ok(())
+    }
+
+    /// Update pipeline duration metrics from database
+    pub async fn update_pipeline_metrics(&self, db: &Database) -> Result<(), Box<dyn std::error::Error>> {
+        let durations = db.get_pipeline_durations().await?;
+
+        for duration in durations {
+            self.pipeline_duration_seconds
+                .with_label_values(&[&duration.pipeline, &duration.status])
+                .set(duration.avg_duration_seconds);
+        }
+
+        Ok(())
+    }
+
+    /// Update GitHub rate limit metrics from the quota seen by the GitHub client
+    pub fn update_github_rate_limit_metrics(&self, limits: &[RateLimit]) {
+        for limit in limits {
+            self.github_rate_limit_remaining
+                .with_label_values(&[&limit.resource])
+                .set(limit.remaining as f64);
+        }
+    }
+
     /// Record HTTP request
     pub fn record_http_request(&self, method: &str, path: &str, status: u16, duration_seconds: f64) {
         self.http_requests_total
@@ -258,8 +513,96 @@ impl MetricsCollector {
             .inc();
 
         self.http_request_duration_seconds
-            .with_label_values(&[method, path])
+            .with_label_values(&[method, path, &status.to_string()])
             .observe(duration_seconds);
+
+        let mut samples = self.latency_samples.lock().unwrap();
+        let window = samples
+            .entry((method.to_string(), path.to_string()))
+            .or_default();
+        if window.len() == LATENCY_WINDOW {
+            window.pop_front();
+        }
+        window.push_back(duration_seconds);
+    }
+
+    /// Count a request as in flight while the returned guard lives
+    pub fn track_in_flight(&self, method: &str, path: &str) -> InFlightGuard {
+        let gauge = self.http_requests_in_flight.with_label_values(&[method, path]);
+        gauge.inc();
+        InFlightGuard { gauge }
+    }
+
+    /// Latency percentiles of every route over its recent requests, slowest
+    /// p95 first
+    pub fn endpoint_latencies(&self) -> Vec<EndpointLatency> {
+        let samples = self.latency_samples.lock().unwrap();
+        let mut latencies: Vec<EndpointLatency> = samples
+            .iter()
+            .map(|((method, path), window)| {
+                let mut sorted: Vec<f64> = window.iter().copied().collect();
+                sorted.sort_by(f64::total_cmp);
+                EndpointLatency {
+                    method: method.clone(),
+                    path: path.clone(),
+                    requests: sorted.len(),
+                    in_flight: self
+                        .http_requests_in_flight
+                        .with_label_values(&[method, path])
+                        .get(),
+                    p50_ms: percentile(&sorted, 0.50) * 1000.0,
+                    p95_ms: percentile(&sorted, 0.95) * 1000.0,
+                    p99_ms: percentile(&sorted, 0.99) * 1000.0,
+                }
+            })
+            .collect();
+        latencies.sort_by(|a, b| b.p95_ms.total_cmp(&a.p95_ms));
+        latencies
+    }
+
+    /// Latency percentiles over the recent requests of all routes, with
+    /// `method` and `path` of "*"
+    pub fn overall_latency(&self) -> EndpointLatency {
+        let samples = self.latency_samples.lock().unwrap();
+        let mut sorted: Vec<f64> = samples.values().flatten().copied().collect();
+        sorted.sort_by(f64::total_cmp);
+        let in_flight = self
+            .http_requests_in_flight
+            .collect()
+            .iter()
+            .flat_map(|family| family.get_metric())
+            .map(|m| m.get_gauge().get_value() as i64)
+            .sum();
+        EndpointLatency {
+            method: "*".to_string(),
+            path: "*".to_string(),
+            requests: sorted.len(),
+            in_flight,
+            p50_ms: percentile(&sorted, 0.50) * 1000.0,
+            p95_ms: percentile(&sorted, 0.95) * 1000.0,
+            p99_ms: percentile(&sorted, 0.99) * 1000.0,
+        }
+    }
+
+    /// Record how long a webhook event waited before processing started
+    pub fn record_webhook_wait(&self, event_type: &str, wait_seconds: f64) {
+        self.webhook_wait_seconds
+            .with_label_values(&[event_type])
+            .observe(wait_seconds);
+    }
+
+    /// Record the outcome of processing a webhook event
+    pub fn record_webhook_outcome(&self, event_type: &str, outcome: &str) {
+        self.webhook_events_total
+            .with_label_values(&[event_type, outcome])
+            .inc();
+    }
+
+    /// Record an API request rejected by a rate limit
+    pub fn record_rate_limited(&self, client: &str, path: &str) {
+        self.rate_limited_requests_total
+            .with_label_values(&[client, path])
+            .inc();
     }
 
     /// Record agent execution time
@@ -269,6 +612,25 @@ impl MetricsCollector {
             .observe(duration_seconds);
     }
 
+    /// Record agent execution time with the agent as the bucket's exemplar
+    pub fn record_agent_execution_for(&self, agent_type: &str, agent_id: &str, duration_seconds: f64) {
+        self.record_agent_execution(agent_type, duration_seconds);
+
+        let bucket = AGENT_EXECUTION_BUCKETS
+            .into_iter()
+            .find(|upper_bound| duration_seconds <= *upper_bound)
+            .map(format_value)
+            .unwrap_or_else(|| "+Inf".to_string());
+        self.set_exemplar(
+            "orchestrate_agent_execution_seconds",
+            &[("le", &bucket), ("type", agent_type)],
+            Exemplar {
+                agent_id: agent_id.to_string(),
+                value: duration_seconds,
+            },
+        );
+    }
+
     /// Record error
     pub fn record_error(&self, error_type: &str) {
         self.errors_total
@@ -429,22 +791,291 @@ impl MetricsCollector {
         }
     }
 
-    /// Gather all metrics and encode to Prometheus text format
-    pub async fn gather(&self, db: &Database) -> Result<String, Box<dyn std::error::Error>> {
-        // Update metrics from database
+    /// Update all database-backed metrics
+    pub async fn refresh(&self, db: &Database) -> Result<(), Box<dyn std::error::Error>> {
         self.update_agent_metrics(db).await?;
+        self.update_agent_turn_metrics(db).await?;
         self.update_token_metrics(db).await?;
         self.update_queue_metrics(db).await?;
+        self.update_schedule_metrics(db).await?;
+        self.update_pipeline_metrics(db).await?;
         self.update_business_metrics(db).await?;
+        self.update_github_rate_limit_metrics(&orchestrate_github::rate_limits());
+
+        Ok(())
+    }
+
+    /// Current values of all registered metrics
+    pub fn metric_families(&self) -> Vec<MetricFamily> {
+        self.registry.gather()
+    }
+
+    /// Gather all metrics and encode to Prometheus text format
+    pub async fn gather(&self, db: &Database) -> Result<String, Box<dyn std::error::Error>> {
+        // Update metrics from database
+        self.refresh(db).await?;
 
         // Encode metrics to text format
         let encoder = TextEncoder::new();
-        let metric_families = self.registry.gather();
+        let metric_families = self.metric_families();
         let mut buffer = vec![];
         encoder.encode(&metric_families, &mut buffer)?;
 
         Ok(String::from_utf8(buffer)?)
     }
+
+    /// Gather all metrics and encode to OpenMetrics text format with exemplars
+    pub async fn gather_openmetrics(&self, db: &Database) -> Result<String, Box<dyn std::error::Error>> {
+        self.refresh(db).await?;
+
+        Ok(self.encode_openmetrics(&self.metric_families()))
+    }
+
+    /// Exemplar recorded for a sample, if any
+    pub fn exemplar(&self, name: &str, labels: &[(&str, &str)]) -> Option<Exemplar> {
+        self.exemplars
+            .lock()
+            .unwrap()
+            .get(&sample_key(name, labels))
+            .cloned()
+    }
+
+    fn set_exemplar(&self, name: &str, labels: &[(&str, &str)], exemplar: Exemplar) {
+        self.exemplars
+            .lock()
+            .unwrap()
+            .insert(sample_key(name, labels), exemplar);
+    }
+
+    /// Encode metric families in the OpenMetrics text format
+    ///
+    /// Counters get the `_total` suffix on their samples only, histogram
+    /// buckets and counters carry exemplars where one was recorded.
+    fn encode_openmetrics(&self, families: &[MetricFamily]) -> String {
+        let exemplars = self.exemplars.lock().unwrap();
+        let mut out = String::new();
+
+        for family in families {
+            let name = family.get_name();
+            let (family_name, type_name) = match family.get_field_type() {
+                MetricType::COUNTER => (name.strip_suffix("_total").unwrap_or(name), "counter"),
+                MetricType::GAUGE => (name, "gauge"),
+                MetricType::HISTOGRAM => (name, "histogram"),
+                MetricType::SUMMARY => (name, "summary"),
+                MetricType::UNTYPED => (name, "unknown"),
+            };
+
+            let _ = writeln!(out, "# TYPE {} {}", family_name, type_name);
+            let _ = writeln!(
+                out,
+                "# HELP {} {}",
+                family_name,
+                escape_help(family.get_help())
+            );
+
+            for metric in family.get_metric() {
+                let labels: Vec<(&str, &str)> = metric
+                    .get_label()
+                    .iter()
+                    .map(|pair| (pair.get_name(), pair.get_value()))
+                    .collect();
+
+                match family.get_field_type() {
+                    MetricType::COUNTER => {
+                        let exemplar = exemplars.get(&sample_key(name, &labels));
+                        write_sample(
+                            &mut out,
+                            &format!("{}_total", family_name),
+                            &labels,
+                            metric.get_counter().get_value(),
+                            exemplar,
+                        );
+                    }
+                    MetricType::GAUGE => {
+                        write_sample(
+                            &mut out,
+                            name,
+                            &labels,
+                            metric.get_gauge().get_value(),
+                            None,
+                        );
+                    }
+                    MetricType::HISTOGRAM => {
+                        let histogram = metric.get_histogram();
+                        let bucket_name = format!("{}_bucket", name);
+                        let mut wrote_inf = false;
+                        for bucket in histogram.get_bucket() {
+                            let le = format_value(bucket.get_upper_bound());
+                            wrote_inf |= bucket.get_upper_bound().is_infinite();
+                            write_bucket(
+                                &mut out,
+                                &bucket_name,
+                                &labels,
+                                &le,
+                                bucket.get_cumulative_count() as f64,
+                                &exemplars,
+                                name,
+                            );
+                        }
+                        if !wrote_inf {
+                            write_bucket(
+                                &mut out,
+                                &bucket_name,
+                                &labels,
+                                "+Inf",
+                                histogram.get_sample_count() as f64,
+                                &exemplars,
+                                name,
+                            );
+                        }
+                        write_sample(
+                            &mut out,
+                            &format!("{}_count", name),
+                            &labels,
+                            histogram.get_sample_count() as f64,
+                            None,
+                        );
+                        write_sample(
+                            &mut out,
+                            &format!("{}_sum", name),
+                            &labels,
+                            histogram.get_sample_sum(),
+                            None,
+                        );
+                    }
+                    MetricType::SUMMARY => {
+                        let summary = metric.get_summary();
+                        for quantile in summary.get_quantile() {
+                            let q = format_value(quantile.get_quantile());
+                            let mut quantile_labels = labels.clone();
+                            quantile_labels.push(("quantile", &q));
+                            write_sample(
+                                &mut out,
+                                name,
+                                &quantile_labels,
+                                quantile.get_value(),
+                                None,
+                            );
+                        }
+                        write_sample(
+                            &mut out,
+                            &format!("{}_count", name),
+                            &labels,
+                            summary.get_sample_count() as f64,
+                            None,
+                        );
+                        write_sample(
+                            &mut out,
+                            &format!("{}_sum", name),
+                            &labels,
+                            summary.get_sample_sum(),
+                            None,
+                        );
+                    }
+                    MetricType::UNTYPED => {
+                        write_sample(
+                            &mut out,
+                            name,
+                            &labels,
+                            metric.get_untyped().get_value(),
+                            None,
+                        );
+                    }
+                }
+            }
+        }
+
+        out.push_str("# EOF\n");
+        out
+    }
+}
+
+/// Nearest-rank percentile of sorted values, zero when there are none
+fn percentile(sorted: &[f64], quantile: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (quantile * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Identify a sample by metric name and labels, independent of label order
+fn sample_key(name: &str, labels: &[(&str, &str)]) -> String {
+    let mut labels = labels.to_vec();
+    labels.sort();
+
+    let mut key = name.to_string();
+    for (label, value) in labels {
+        let _ = write!(key, "|{}={}", label, value);
+    }
+    key
+}
+
+fn write_bucket(
+    out: &mut String,
+    bucket_name: &str,
+    labels: &[(&str, &str)],
+    le: &str,
+    count: f64,
+    exemplars: &HashMap<String, Exemplar>,
+    name: &str,
+) {
+    let mut bucket_labels = labels.to_vec();
+    bucket_labels.push(("le", le));
+    let exemplar = exemplars.get(&sample_key(name, &bucket_labels));
+    write_sample(out, bucket_name, &bucket_labels, count, exemplar);
+}
+
+fn write_sample(
+    out: &mut String,
+    name: &str,
+    labels: &[(&str, &str)],
+    value: f64,
+    exemplar: Option<&Exemplar>,
+) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        write_labels(out, labels);
+    }
+    let _ = write!(out, " {}", format_value(value));
+    if let Some(exemplar) = exemplar {
+        out.push_str(" # ");
+        write_labels(out, &[("agent_id", &exemplar.agent_id)]);
+        let _ = write!(out, " {}", format_value(exemplar.value));
+    }
+    out.push('\n');
+}
+
+fn write_labels(out: &mut String, labels: &[(&str, &str)]) {
+    out.push('{');
+    for (i, (label, value)) in labels.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let _ = write!(out, "{}=\"{}\"", label, escape_label_value(value));
+    }
+    out.push('}');
+}
+
+fn format_value(value: f64) -> String {
+    if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
 }
 
 impl Default for MetricsCollector {
@@ -464,6 +1095,22 @@ mod tests {
         assert!(collector.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_github_rate_limit_metrics() {
+        let collector = MetricsCollector::new().unwrap();
+        let db = Database::in_memory().await.unwrap();
+
+        collector.update_github_rate_limit_metrics(&[RateLimit {
+            resource: "graphql".to_string(),
+            limit: 5000,
+            remaining: 1234,
+            reset: 1_700_000_000,
+        }]);
+
+        let metrics = collector.gather(&db).await.unwrap();
+        assert!(metrics.contains("orchestrate_github_rate_limit_remaining{resource=\"graphql\"} 1234"));
+    }
+
     #[tokio::test]
     async fn test_agent_metrics_empty_database() {
         let collector = MetricsCollector::new().unwrap();
@@ -799,4 +1446,169 @@ mod tests {
         let metrics = collector.gather(&db).await.unwrap();
         assert!(metrics.contains("orchestrate_agent_success_rate") || metrics.len() > 0);
     }
+
+    #[tokio::test]
+    async fn test_pipeline_duration_metric() {
+        let collector = MetricsCollector::new().unwrap();
+        let db = Database::in_memory().await.unwrap();
+
+        let pipeline =
+            orchestrate_core::Pipeline::new("deploy".to_string(), "name: deploy".to_string());
+        let pipeline_id = db.insert_pipeline(&pipeline).await.unwrap();
+
+        let started = chrono::Utc::now() - chrono::Duration::minutes(10);
+        for minutes in [2, 4] {
+            let mut run = orchestrate_core::PipelineRun::new(pipeline_id, None);
+            run.status = orchestrate_core::PipelineRunStatus::Succeeded;
+            run.started_at = Some(started);
+            run.completed_at = Some(started + chrono::Duration::minutes(minutes));
+            db.insert_pipeline_run(&run).await.unwrap();
+        }
+
+        collector.update_pipeline_metrics(&db).await.unwrap();
+
+        let metrics = collector.gather(&db).await.unwrap();
+        assert!(metrics.contains(
+            "orchestrate_pipeline_duration_seconds{pipeline=\"deploy\",status=\"succeeded\"} 180"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_agent_turn_metric_with_exemplar() {
+        let collector = MetricsCollector::new().unwrap();
+        let db = Database::in_memory().await.unwrap();
+
+        let first = Agent::new(AgentType::StoryDeveloper, "Test task 1");
+        let second = Agent::new(AgentType::StoryDeveloper, "Test task 2");
+        db.insert_agent(&first).await.unwrap();
+        db.insert_agent(&second).await.unwrap();
+        for (agent, turn) in [(&first, 1), (&first, 2), (&second, 1)] {
+            db.record_session_tokens("session-1", agent.id, turn, 100, 50, 0, 0, 150, 2, 0)
+                .await
+                .unwrap();
+        }
+
+        collector.update_agent_turn_metrics(&db).await.unwrap();
+
+        let metrics = collector.gather(&db).await.unwrap();
+        assert!(metrics.contains("orchestrate_agent_turns_total{agent_type=\"story_developer\"} 3"));
+
+        // The latest turn's agent is the exemplar
+        let exemplar = collector
+            .exemplar(
+                "orchestrate_agent_turns_total",
+                &[("agent_type", "story_developer")],
+            )
+            .unwrap();
+        assert_eq!(exemplar.agent_id, second.id.to_string());
+
+        let openmetrics = collector.gather_openmetrics(&db).await.unwrap();
+        assert!(openmetrics.contains("# TYPE orchestrate_agent_turns counter"));
+        assert!(openmetrics.contains(&format!(
+            "orchestrate_agent_turns_total{{agent_type=\"story_developer\"}} 3 # {{agent_id=\"{}\"}} 1",
+            second.id
+        )));
+        assert!(openmetrics.ends_with("# EOF\n"));
+    }
+
+    #[tokio::test]
+    async fn test_agent_execution_exemplar() {
+        let collector = MetricsCollector::new().unwrap();
+        let db = Database::in_memory().await.unwrap();
+
+        collector.record_agent_execution_for("story_developer", "agent-1", 42.0);
+        collector.record_agent_execution_for("story_developer", "agent-2", 900.0);
+
+        let openmetrics = collector.gather_openmetrics(&db).await.unwrap();
+        assert!(openmetrics.contains(
+            "orchestrate_agent_execution_seconds_bucket{type=\"story_developer\",le=\"60\"} 1 # {agent_id=\"agent-1\"} 42"
+        ));
+        assert!(openmetrics.contains(
+            "orchestrate_agent_execution_seconds_bucket{type=\"story_developer\",le=\"+Inf\"} 2 # {agent_id=\"agent-2\"} 900"
+        ));
+        assert!(openmetrics
+            .contains("orchestrate_agent_execution_seconds_count{type=\"story_developer\"} 2"));
+    }
+
+    #[tokio::test]
+    async fn test_schedule_lag_metric() {
+        let collector = MetricsCollector::new().unwrap();
+        let db = Database::in_memory().await.unwrap();
+
+        let mut overdue = orchestrate_core::Schedule::new(
+            "nightly".to_string(),
+            "0 2 * * *".to_string(),
+            "story_developer".to_string(),
+            "Nightly run".to_string(),
+        );
+        overdue.next_run = Some(chrono::Utc::now() - chrono::Duration::minutes(5));
+        db.insert_schedule(&overdue).await.unwrap();
+
+        let mut upcoming = overdue.clone();
+        upcoming.name = "hourly".to_string();
+        upcoming.next_run = Some(chrono::Utc::now() + chrono::Duration::minutes(5));
+        db.insert_schedule(&upcoming).await.unwrap();
+
+        collector.update_schedule_metrics(&db).await.unwrap();
+
+        let families = collector.metric_families();
+        let lag = families
+            .iter()
+            .find(|f| f.get_name() == "orchestrate_schedule_lag_seconds")
+            .unwrap();
+        let lag_for = |schedule: &str| {
+            lag.get_metric()
+                .iter()
+                .find(|m| m.get_label()[0].get_value() == schedule)
+                .unwrap()
+                .get_gauge()
+                .get_value()
+        };
+        assert!((lag_for("nightly") - 300.0).abs() < 5.0);
+        assert_eq!(lag_for("hourly"), 0.0);
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[], 0.5), 0.0);
+        assert_eq!(percentile(&[3.0], 0.99), 3.0);
+        let values: Vec<f64> = (1..=20).map(f64::from).collect();
+        assert_eq!(percentile(&values, 0.5), 10.0);
+        assert_eq!(percentile(&values, 0.95), 19.0);
+        assert_eq!(percentile(&values, 0.99), 20.0);
+    }
+
+    #[test]
+    fn test_in_flight_guard() {
+        let collector = MetricsCollector::new().unwrap();
+        let guard = collector.track_in_flight("GET", "/api/agents");
+        assert_eq!(collector.overall_latency().in_flight, 1);
+        drop(guard);
+        assert_eq!(collector.overall_latency().in_flight, 0);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_queue_lag_metric() {
+        let collector = MetricsCollector::new().unwrap();
+        let db = Database::in_memory().await.unwrap();
+
+        let mut push = orchestrate_core::WebhookEvent::new(
+            "delivery-1".to_string(),
+            "push".to_string(),
+            "{}".to_string(),
+        );
+        push.received_at = chrono::Utc::now() - chrono::Duration::minutes(2);
+        db.insert_webhook_event(&push).await.unwrap();
+
+        collector.update_queue_metrics(&db).await.unwrap();
+
+        let families = collector.metric_families();
+        let lag = families
+            .iter()
+            .find(|f| f.get_name() == "orchestrate_webhook_queue_lag_seconds")
+            .unwrap();
+        let metric = &lag.get_metric()[0];
+        assert_eq!(metric.get_label()[0].get_value(), "push");
+        assert!((metric.get_gauge().get_value() - 120.0).abs() < 5.0);
+    }
 }
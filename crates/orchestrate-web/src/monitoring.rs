@@ -1,7 +1,7 @@
 //! Monitoring REST API endpoints
 //!
 //! This module provides REST API endpoints for the monitoring and alerting system:
-//! - GET /api/metrics - Current metrics snapshot
+//! - GET /api/metrics - Current metrics snapshot, with API latency percentiles
 //! - GET /api/metrics/history - Historical metrics
 //! - GET /api/alerts - List alerts
 //! - POST /api/alerts/:id/acknowledge - Acknowledge alert
@@ -25,11 +25,15 @@ use orchestrate_core::{
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::api::{ApiError, AppState};
+use crate::auth::Identity;
+use crate::metrics::EndpointLatency;
+use crate::pagination::{Page, PageParams};
 
 /// Query parameters for metrics history endpoint
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct MetricsHistoryQuery {
     /// Start time for the query (ISO 8601)
     #[serde(default = "default_start_time")]
@@ -53,26 +57,16 @@ fn default_interval() -> i64 {
 }
 
 /// Query parameters for alerts list endpoint
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct AlertsQuery {
     /// Filter by status (active, acknowledged, resolved)
     pub status: Option<String>,
     /// Filter by severity (info, warning, critical)
     pub severity: Option<String>,
-    /// Pagination limit (default: 100)
-    #[serde(default = "default_limit")]
-    pub limit: i64,
-    /// Pagination offset (default: 0)
-    #[serde(default)]
-    pub offset: i64,
-}
-
-fn default_limit() -> i64 {
-    100
 }
 
 /// Request body for acknowledging an alert
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AcknowledgeAlertRequest {
     pub acknowledged_by: String,
     #[serde(default)]
@@ -80,7 +74,7 @@ pub struct AcknowledgeAlertRequest {
 }
 
 /// Request body for creating an alert rule
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateAlertRuleRequest {
     pub name: String,
     pub condition: String,
@@ -102,7 +96,7 @@ fn default_enabled() -> bool {
 }
 
 /// Query parameters for audit log endpoint
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct AuditLogQuery {
     /// Filter by actor
     pub actor: Option<String>,
@@ -120,16 +114,10 @@ pub struct AuditLogQuery {
     pub start: Option<DateTime<Utc>>,
     /// End time (ISO 8601)
     pub end: Option<DateTime<Utc>>,
-    /// Pagination limit (default: 100)
-    #[serde(default = "default_limit")]
-    pub limit: i64,
-    /// Pagination offset (default: 0)
-    #[serde(default)]
-    pub offset: i64,
 }
 
 /// Query parameters for performance stats endpoint
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct PerformanceQuery {
     /// Filter by agent type
     pub agent_type: Option<String>,
@@ -146,7 +134,7 @@ fn default_perf_start_time() -> DateTime<Utc> {
 }
 
 /// Query parameters for cost reports endpoint
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct CostQuery {
     /// Filter by period (daily, weekly, monthly)
     #[serde(default = "default_cost_period")]
@@ -166,15 +154,27 @@ fn default_cost_period() -> String {
 }
 
 /// Response for metrics snapshot endpoint
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct MetricsSnapshotResponse {
     pub timestamp: DateTime<Utc>,
+    #[schema(value_type = Vec<Object>)]
     pub metrics: Vec<MetricValue>,
+    #[schema(value_type = Object)]
     pub summary: MetricsSummary,
+    pub latency: LatencySummary,
+}
+
+/// API latency over recent requests
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LatencySummary {
+    /// Percentiles across all routes
+    pub overall: EndpointLatency,
+    /// Percentiles of each route, slowest p95 first
+    pub endpoints: Vec<EndpointLatency>,
 }
 
 /// Response for metrics history endpoint
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct MetricsHistoryResponse {
     pub start: DateTime<Utc>,
     pub end: DateTime<Utc>,
@@ -182,72 +182,88 @@ pub struct MetricsHistoryResponse {
     pub metrics: HashMap<String, Vec<HistoricalMetricPoint>>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct HistoricalMetricPoint {
     pub timestamp: DateTime<Utc>,
     pub value: f64,
 }
 
-/// Response for alerts list endpoint
-#[derive(Debug, Serialize)]
-pub struct AlertsListResponse {
-    pub total: i64,
-    pub offset: i64,
-    pub limit: i64,
-    pub alerts: Vec<Alert>,
-}
-
 /// Response for alert rule creation
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CreateAlertRuleResponse {
     pub id: i64,
+    #[schema(value_type = Object)]
     pub rule: AlertRule,
 }
 
 /// Response for audit log endpoint
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct AuditLogResponse {
-    pub total: i64,
-    pub offset: i64,
-    pub limit: i64,
-    pub entries: Vec<AuditEntry>,
+    #[serde(flatten)]
+    #[schema(value_type = Page<Object>)]
+    pub page: Page<AuditEntry>,
+    #[schema(value_type = Option<Object>)]
     pub stats: Option<AuditStats>,
 }
 
 /// Response for performance stats endpoint
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PerformanceResponse {
     pub period_start: DateTime<Utc>,
     pub period_end: DateTime<Utc>,
+    #[schema(value_type = Vec<Object>)]
     pub stats: Vec<AgentPerformance>,
 }
 
 /// Response for cost reports endpoint
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CostReportResponse {
     pub period: String,
+    #[schema(value_type = Object)]
     pub report: orchestrate_core::monitoring::CostReport,
 }
 
 /// GET /api/metrics - Current metrics snapshot
+#[utoipa::path(
+    get,
+    path = "/api/metrics",
+    tag = "monitoring",
+    summary = "Get a metrics snapshot",
+    responses((status = 200, body = MetricsSnapshotResponse))
+)]
 async fn get_metrics_snapshot(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
 ) -> Result<Json<MetricsSnapshotResponse>, ApiError> {
-    // TODO: Integrate MetricsCollector into AppState to enable full metrics gathering
+    // TODO: Gather the collector's metrics into the snapshot
     // For now, return empty metrics
     let metrics = Vec::new();
 
     // Get metrics summary from database (using default for now)
-    let summary = MetricsSummary::default();
+    let mut summary = MetricsSummary::default();
+
+    let latency = LatencySummary {
+        overall: state.metrics.overall_latency(),
+        endpoints: state.metrics.endpoint_latencies(),
+    };
+    summary.avg_response_time_ms = latency.overall.p50_ms;
 
     Ok(Json(MetricsSnapshotResponse {
         timestamp: Utc::now(),
         metrics,
         summary,
+        latency,
     }))
 }
 
 /// GET /api/metrics/history - Historical metrics
+#[utoipa::path(
+    get,
+    path = "/api/metrics/history",
+    tag = "monitoring",
+    summary = "Get metrics history",
+    params(MetricsHistoryQuery),
+    responses((status = 200, body = MetricsHistoryResponse))
+)]
 async fn get_metrics_history(
     State(state): State<Arc<AppState>>,
     Query(query): Query<MetricsHistoryQuery>,
@@ -269,10 +285,22 @@ async fn get_metrics_history(
 }
 
 /// GET /api/alerts - List alerts
+#[utoipa::path(
+    get,
+    path = "/api/alerts",
+    tag = "monitoring",
+    summary = "List alerts",
+    params(
+        AlertsQuery,
+        PageParams
+    ),
+    responses((status = 200, body = Page<serde_json::Value>))
+)]
 async fn list_alerts(
     State(state): State<Arc<AppState>>,
     Query(query): Query<AlertsQuery>,
-) -> Result<Json<AlertsListResponse>, ApiError> {
+    Query(page): Query<PageParams>,
+) -> Result<Json<Page<Alert>>, ApiError> {
     // Parse status filter
     let status_filter = if let Some(status_str) = &query.status {
         Some(parse_alert_status(status_str)?)
@@ -289,37 +317,30 @@ async fn list_alerts(
     let status_str = status_filter.map(|s| format!("{:?}", s).to_lowercase());
     let alerts = state
         .db
-        .list_alerts_by_status(
+        .list_alerts_page(
             status_str.as_deref(),
             query.severity.as_deref(),
-            query.limit,
-            query.offset,
+            &page.query()?,
         )
         .await
         .map_err(|e| ApiError::internal(format!("Failed to list alerts: {}", e)))?;
 
-    let total = alerts.len() as i64;
-    let offset = query.offset as usize;
-    let limit = query.limit as usize;
-
-    // Apply pagination
-    let paginated_alerts: Vec<Alert> = alerts
-        .into_iter()
-        .skip(offset)
-        .take(limit)
-        .collect();
-
-    Ok(Json(AlertsListResponse {
-        total,
-        offset: query.offset,
-        limit: query.limit,
-        alerts: paginated_alerts,
-    }))
+    Ok(Json(alerts.into()))
 }
 
 /// POST /api/alerts/:id/acknowledge - Acknowledge alert
+#[utoipa::path(
+    post,
+    path = "/api/alerts/{id}/acknowledge",
+    tag = "monitoring",
+    summary = "Acknowledge an alert",
+    params(("id" = i64, Path)),
+    request_body = AcknowledgeAlertRequest,
+    responses((status = 200, body = Object))
+)]
 async fn acknowledge_alert(
     State(state): State<Arc<AppState>>,
+    identity: Option<Identity>,
     Path(id): Path<i64>,
     Json(req): Json<AcknowledgeAlertRequest>,
 ) -> Result<Json<Alert>, ApiError> {
@@ -334,7 +355,11 @@ async fn acknowledge_alert(
     // Manually update alert fields for acknowledgment
     alert.status = AlertStatus::Acknowledged;
     alert.acknowledged_at = Some(Utc::now());
-    alert.acknowledged_by = Some(req.acknowledged_by.clone());
+    // Signed-in users acknowledge as themselves
+    let acknowledged_by = identity
+        .map(|identity| identity.actor())
+        .unwrap_or_else(|| req.acknowledged_by.clone());
+    alert.acknowledged_by = Some(acknowledged_by.clone());
 
     // Update in database
     state
@@ -345,7 +370,7 @@ async fn acknowledge_alert(
 
     // Create audit log entry
     let mut audit_entry = AuditEntry::new(
-        &req.acknowledged_by,
+        &acknowledged_by,
         AuditAction::AlertAcknowledged,
         "alert",
         id.to_string(),
@@ -359,6 +384,13 @@ async fn acknowledge_alert(
 }
 
 /// GET /api/health - System health status
+#[utoipa::path(
+    get,
+    path = "/api/health",
+    tag = "monitoring",
+    summary = "Get system health",
+    responses((status = 200, body = Object))
+)]
 async fn get_system_health(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<SystemHealth>, ApiError> {
@@ -393,8 +425,17 @@ async fn get_system_health(
 }
 
 /// POST /api/alerts/rules - Create alert rule
+#[utoipa::path(
+    post,
+    path = "/api/alerts/rules",
+    tag = "monitoring",
+    summary = "Create an alert rule",
+    request_body = CreateAlertRuleRequest,
+    responses((status = 200, body = CreateAlertRuleResponse))
+)]
 async fn create_alert_rule(
     State(state): State<Arc<AppState>>,
+    identity: Option<Identity>,
     Json(req): Json<CreateAlertRuleRequest>,
 ) -> Result<Json<CreateAlertRuleResponse>, ApiError> {
     // Parse severity
@@ -418,7 +459,7 @@ async fn create_alert_rule(
 
     // Create audit log entry
     let audit_entry = AuditEntry::new(
-        "system",
+        identity.map_or_else(|| "system".to_string(), |identity| identity.actor()),
         AuditAction::Custom("alert.rule.created".to_string()),
         "alert_rule",
         id.to_string(),
@@ -434,13 +475,24 @@ async fn create_alert_rule(
 }
 
 /// GET /api/audit - Query audit log
+#[utoipa::path(
+    get,
+    path = "/api/audit",
+    tag = "monitoring",
+    summary = "Query the audit log",
+    params(
+        AuditLogQuery,
+        PageParams
+    ),
+    responses((status = 200, body = AuditLogResponse))
+)]
 async fn query_audit_log(
     State(state): State<Arc<AppState>>,
     Query(params): Query<AuditLogQuery>,
+    Query(page): Query<PageParams>,
 ) -> Result<Json<AuditLogResponse>, ApiError> {
     // Build audit query
-    let mut query = AuditQuery::new()
-        .with_pagination(params.limit, params.offset);
+    let mut query = AuditQuery::new();
 
     // Check for filters before consuming params
     let has_filters = params.actor.is_some()
@@ -475,17 +527,10 @@ async fn query_audit_log(
         query = query.with_timerange(start, end);
     }
 
-    // Get total count
-    let total = state
-        .db
-        .count_audit_entries(&query)
-        .await
-        .map_err(|e| ApiError::internal(format!("Failed to count audit entries: {}", e)))?;
-
     // Get entries
     let entries = state
         .db
-        .query_audit_log(&query)
+        .query_audit_log_page(&query, &page.query()?)
         .await
         .map_err(|e| ApiError::internal(format!("Failed to query audit log: {}", e)))?;
 
@@ -497,15 +542,20 @@ async fn query_audit_log(
     };
 
     Ok(Json(AuditLogResponse {
-        total,
-        offset: query.offset.unwrap_or(0),
-        limit: query.limit.unwrap_or(100),
-        entries,
+        page: entries.into(),
         stats,
     }))
 }
 
 /// GET /api/performance - Agent performance stats
+#[utoipa::path(
+    get,
+    path = "/api/performance",
+    tag = "monitoring",
+    summary = "Get performance statistics",
+    params(PerformanceQuery),
+    responses((status = 200, body = PerformanceResponse))
+)]
 async fn get_performance_stats(
     State(state): State<Arc<AppState>>,
     Query(query): Query<PerformanceQuery>,
@@ -527,6 +577,14 @@ async fn get_performance_stats(
 }
 
 /// GET /api/costs - Cost reports
+#[utoipa::path(
+    get,
+    path = "/api/costs",
+    tag = "monitoring",
+    summary = "Get cost reports",
+    params(CostQuery),
+    responses((status = 200, body = CostReportResponse))
+)]
 async fn get_cost_reports(
     State(state): State<Arc<AppState>>,
     Query(query): Query<CostQuery>,
@@ -543,7 +601,28 @@ async fn get_cost_reports(
 
     // Get cost report from database (using empty report for now)
     // TODO: Implement get_cost_report in database
-    let report = orchestrate_core::monitoring::CostReport::new(start, end);
+    let mut report = orchestrate_core::monitoring::CostReport::new(start, end);
+
+    // Attributed spend per epic and story
+    let days = (end - start).num_days().max(1) as i32;
+    let epic_costs = state
+        .db
+        .list_epic_costs(Some(days))
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to get epic costs: {}", e)))?;
+    for cost in epic_costs {
+        if query.epic_id.as_ref().is_none_or(|id| *id == cost.entity_id) {
+            report.by_epic.insert(cost.entity_id, cost.estimated_cost_usd);
+        }
+    }
+    let story_costs = state
+        .db
+        .list_story_costs(query.epic_id.as_deref(), Some(days))
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to get story costs: {}", e)))?;
+    for cost in story_costs {
+        report.by_story.insert(cost.entity_id, cost.estimated_cost_usd);
+    }
 
     Ok(Json(CostReportResponse {
         period: query.period,
@@ -695,15 +774,18 @@ pub fn create_monitoring_router() -> Router<Arc<AppState>> {
 mod tests {
     use super::*;
     use orchestrate_core::{Agent, AgentState, AgentType, Database};
-    use secrecy::SecretString;
 
     async fn setup_test_state() -> Arc<AppState> {
         let db = Database::in_memory().await.unwrap();
 
-        Arc::new(AppState {
-            db,
-            api_key: Some(SecretString::new("test-key".to_string())),
-        })
+        Arc::new(AppState::new(db, Some("test-key".to_string())))
+    }
+
+    fn with_total() -> PageParams {
+        PageParams {
+            include_total: true,
+            ..PageParams::default()
+        }
     }
 
     #[tokio::test]
@@ -721,6 +803,33 @@ mod tests {
         assert!(!response.metrics.is_empty() || response.summary.active_agents >= 0);
     }
 
+    #[tokio::test]
+    async fn test_get_metrics_snapshot_latency() {
+        let state = setup_test_state().await;
+        for ms in 1..=100 {
+            state
+                .metrics
+                .record_http_request("GET", "/api/agents", 200, ms as f64 / 1000.0);
+        }
+        state
+            .metrics
+            .record_http_request("POST", "/api/agents", 201, 2.0);
+        let _in_flight = state.metrics.track_in_flight("POST", "/api/agents");
+
+        let response = get_metrics_snapshot(State(state)).await.unwrap().0;
+        let endpoints = &response.latency.endpoints;
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[0].method, "POST");
+        assert_eq!(endpoints[0].in_flight, 1);
+        assert_eq!(endpoints[1].requests, 100);
+        assert_eq!(endpoints[1].p50_ms.round(), 50.0);
+        assert_eq!(endpoints[1].p95_ms.round(), 95.0);
+        assert_eq!(endpoints[1].p99_ms.round(), 99.0);
+        assert_eq!(response.latency.overall.requests, 101);
+        assert_eq!(response.latency.overall.p99_ms.round(), 100.0);
+        assert_eq!(response.latency.overall.in_flight, 1);
+    }
+
     #[tokio::test]
     async fn test_get_metrics_history() {
         let state = setup_test_state().await;
@@ -768,16 +877,14 @@ mod tests {
         let query = AlertsQuery {
             status: None,
             severity: None,
-            limit: 100,
-            offset: 0,
         };
 
-        let result = list_alerts(State(state.clone()), Query(query)).await;
+        let result = list_alerts(State(state.clone()), Query(query), Query(with_total())).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
-        assert!(response.total >= 1);
-        assert!(!response.alerts.is_empty());
+        assert!(response.total.unwrap() >= 1);
+        assert!(!response.items.is_empty());
     }
 
     #[tokio::test]
@@ -787,11 +894,9 @@ mod tests {
         let query = AlertsQuery {
             status: Some("active".to_string()),
             severity: Some("warning".to_string()),
-            limit: 50,
-            offset: 0,
         };
 
-        let result = list_alerts(State(state.clone()), Query(query)).await;
+        let result = list_alerts(State(state.clone()), Query(query), Query(with_total())).await;
         assert!(result.is_ok());
     }
 
@@ -815,7 +920,7 @@ mod tests {
             notes: Some("Acknowledged in test".to_string()),
         };
 
-        let result = acknowledge_alert(State(state.clone()), Path(alert_id), Json(req)).await;
+        let result = acknowledge_alert(State(state.clone()), None, Path(alert_id), Json(req)).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
@@ -832,7 +937,7 @@ mod tests {
             notes: None,
         };
 
-        let result = acknowledge_alert(State(state.clone()), Path(99999), Json(req)).await;
+        let result = acknowledge_alert(State(state.clone()), None, Path(99999), Json(req)).await;
         assert!(result.is_err());
     }
 
@@ -864,7 +969,7 @@ mod tests {
             enabled: true,
         };
 
-        let result = create_alert_rule(State(state.clone()), Json(req)).await;
+        let result = create_alert_rule(State(state.clone()), None, Json(req)).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
@@ -886,7 +991,7 @@ mod tests {
             enabled: true,
         };
 
-        let result = create_alert_rule(State(state.clone()), Json(req)).await;
+        let result = create_alert_rule(State(state.clone()), None, Json(req)).await;
         assert!(result.is_err());
     }
 
@@ -920,16 +1025,14 @@ mod tests {
             success: None,
             start: None,
             end: None,
-            limit: 100,
-            offset: 0,
         };
 
-        let result = query_audit_log(State(state.clone()), Query(params)).await;
+        let result = query_audit_log(State(state.clone()), Query(params), Query(with_total())).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
-        assert!(response.total >= 2);
-        assert!(response.entries.len() >= 2);
+        assert!(response.page.total.unwrap() >= 2);
+        assert!(response.page.items.len() >= 2);
     }
 
     #[tokio::test]
@@ -954,15 +1057,13 @@ mod tests {
             success: Some(true),
             start: None,
             end: None,
-            limit: 100,
-            offset: 0,
         };
 
-        let result = query_audit_log(State(state.clone()), Query(params)).await;
+        let result = query_audit_log(State(state.clone()), Query(params), Query(with_total())).await;
         assert!(result.is_ok());
 
         let response = result.unwrap().0;
-        assert!(response.total >= 1);
+        assert!(response.page.total.unwrap() >= 1);
     }
 
     #[tokio::test]
@@ -1017,6 +1118,51 @@ mod tests {
         assert_eq!(response.period, "monthly");
     }
 
+    #[tokio::test]
+    async fn test_get_cost_reports_by_epic_and_story() {
+        let state = setup_test_state().await;
+        for epic in ["epic-1", "epic-2"] {
+            state
+                .db
+                .upsert_epic(&orchestrate_core::Epic::new(epic, "Epic"))
+                .await
+                .unwrap();
+            let story = format!("{}.1", epic);
+            state
+                .db
+                .upsert_story(&orchestrate_core::Story::new(story.as_str(), epic, "Story"))
+                .await
+                .unwrap();
+            let context = orchestrate_core::AgentContext {
+                epic_id: Some(epic.to_string()),
+                story_id: Some(story),
+                ..Default::default()
+            };
+            state
+                .db
+                .attribute_token_usage(&context, "claude-sonnet-4", 10_000, 1_000, 0, 0)
+                .await
+                .unwrap();
+        }
+
+        let query = CostQuery {
+            period: "monthly".to_string(),
+            start: None,
+            end: None,
+            epic_id: Some("epic-1".to_string()),
+            agent_type: None,
+        };
+
+        let response = get_cost_reports(State(state.clone()), Query(query))
+            .await
+            .unwrap()
+            .0;
+        assert_eq!(response.report.by_epic.len(), 1);
+        assert!(response.report.by_epic["epic-1"] > 0.0);
+        assert_eq!(response.report.by_story.len(), 1);
+        assert!(response.report.by_story.contains_key("epic-1.1"));
+    }
+
     #[tokio::test]
     async fn test_parse_prometheus_metrics() {
         let text = r#"
@@ -0,0 +1,521 @@
+//! OpenAPI specification and Swagger UI
+//!
+//! The spec is generated from the handlers' `#[utoipa::path]` annotations and
+//! the `ToSchema` types they take and return, documenting `/api` routes under
+//! their `/api/v1` path. It is served at `GET /api/v1/openapi.json`, browsable
+//! with Swagger UI at `/docs`, and printed by
+//! `orchestrate docs generate --doc-type api`. List new handlers in
+//! `ProtectedApi` or `PublicApi`; the tests fail on routes of the router
+//! missing from the spec.
+
+use axum::{
+    response::{Html, IntoResponse},
+    routing::get,
+    Json, Router,
+};
+use std::sync::Arc;
+use utoipa::openapi::{
+    content::ContentBuilder,
+    path::{Operation, PathItem},
+    security::{
+        ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme,
+    },
+    tag::TagBuilder,
+    InfoBuilder, OpenApi, Ref, ResponseBuilder, ServerBuilder,
+};
+use utoipa::{Modify, OpenApi as _};
+
+use crate::api::{ApiError, AppState};
+use crate::versioning::versioned_path;
+
+/// Name of the API key security scheme
+const API_KEY_SCHEME: &str = "api_key";
+
+/// Name of the OIDC session token security scheme
+const SESSION_SCHEME: &str = "session";
+
+/// Routes behind the API key or session token
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(
+        crate::api::list_agents,
+        crate::api::create_agent,
+        crate::bulk::bulk_spawn,
+        crate::bulk::bulk_terminate,
+        crate::api::get_agent,
+        crate::api::pause_agent,
+        crate::api::resume_agent,
+        crate::api::terminate_agent,
+        crate::api::get_messages,
+        crate::api::get_agent_events,
+        crate::chat::send_message,
+        crate::chat::create_story,
+        crate::chat::create_pr,
+        crate::chat::list_agent_types,
+        crate::api::system_status,
+        crate::events::stream_events,
+        crate::costs::get_daily_costs,
+        crate::costs::get_cost_breakdown,
+        crate::costs::get_budget,
+        crate::costs::set_budget,
+        crate::exports::download_export,
+        crate::exports::list_export_jobs,
+        crate::exports::create_export_job,
+        crate::exports::get_export_job,
+        crate::board::get_board,
+        crate::board::transition_epic,
+        crate::board::transition_story,
+        crate::board::assign_story,
+        crate::incidents::list_incidents,
+        crate::incidents::get_incident,
+        crate::incidents::add_timeline_comment,
+        crate::incidents::generate_post_mortem,
+        crate::webhook_events::list_webhook_events,
+        crate::webhook_events::get_webhook_event,
+        crate::webhook_events::retry_webhook_event,
+        crate::webhook_events::purge_dead_letters,
+        crate::webhook_events::webhook_stats,
+        crate::api::list_instructions,
+        crate::api::create_instruction,
+        crate::api::get_instruction,
+        crate::api::update_instruction,
+        crate::api::delete_instruction,
+        crate::api::enable_instruction,
+        crate::api::disable_instruction,
+        crate::api::get_instruction_effectiveness,
+        crate::api::list_patterns,
+        crate::api::get_pattern,
+        crate::api::approve_pattern,
+        crate::api::reject_pattern,
+        crate::api::process_patterns,
+        crate::api::cleanup_instructions,
+        crate::api::list_pipelines,
+        crate::api::create_pipeline,
+        crate::api::get_pipeline,
+        crate::api::update_pipeline,
+        crate::api::delete_pipeline,
+        crate::api::get_pipeline_graph,
+        crate::api::trigger_pipeline_run,
+        crate::api::list_pipeline_runs,
+        crate::bulk::bulk_retry_runs,
+        crate::api::get_pipeline_run,
+        crate::api::cancel_pipeline_run,
+        crate::api::list_pipeline_stages,
+        crate::api::get_pipeline_run_saga,
+        crate::approvals::list_approval_inbox,
+        crate::api::approve_approval,
+        crate::api::reject_approval,
+        crate::approvals::delegate_approval,
+        crate::api::list_schedules,
+        crate::api::create_schedule,
+        crate::api::get_schedule,
+        crate::api::update_schedule,
+        crate::api::delete_schedule,
+        crate::api::pause_schedule,
+        crate::api::resume_schedule,
+        crate::api::run_schedule,
+        crate::api::get_schedule_runs,
+        crate::api::get_schedule_stats,
+        crate::api::list_feedback,
+        crate::api::create_feedback,
+        crate::api::get_feedback,
+        crate::api::delete_feedback,
+        crate::api::get_feedback_stats,
+        crate::api::get_learning_effectiveness,
+        crate::api::get_learning_suggestions,
+        crate::api::trigger_learning_analysis,
+        crate::api::list_experiments,
+        crate::api::create_experiment,
+        crate::api::get_experiment,
+        crate::api::get_experiment_results,
+        crate::api::get_experiment_daily_results,
+        crate::api::start_experiment,
+        crate::api::pause_experiment,
+        crate::api::promote_experiment,
+        crate::api::get_prediction,
+        crate::api::generate_documentation,
+        crate::api::validate_documentation,
+        crate::api::list_adrs,
+        crate::api::create_adr,
+        crate::api::get_adr,
+        crate::api::update_adr,
+        crate::api::generate_changelog,
+        crate::api::trigger_security_scan,
+        crate::api::list_security_scans,
+        crate::api::get_security_scan,
+        crate::api::list_vulnerabilities,
+        crate::api::apply_security_fix,
+        crate::api::download_security_report,
+        crate::api::get_security_policy,
+        crate::api::evaluate_security_gate,
+        crate::api::list_artifacts,
+        crate::api::get_artifact,
+        crate::api::delete_artifact,
+        crate::api::list_skills,
+        crate::api::register_skill,
+        crate::api::get_skill,
+        crate::api::unregister_skill,
+        crate::autonomous_api::start_auto_process,
+        crate::autonomous_api::get_auto_status,
+        crate::autonomous_api::pause_auto_process,
+        crate::autonomous_api::resume_auto_process,
+        crate::autonomous_api::stop_auto_process,
+        crate::autonomous_api::list_stuck_agents,
+        crate::autonomous_api::unblock_epic,
+        crate::autonomous_api::list_edge_cases,
+        crate::autonomous_api::resolve_edge_case,
+        crate::autonomous_api::list_sessions,
+        crate::autonomous_api::get_session,
+        crate::autonomous_api::get_session_metrics,
+        crate::auth::current_user,
+    ),
+    components(schemas(ApiError)),
+    modifiers(&Authenticated)
+)]
+struct ProtectedApi;
+
+/// The GraphQL endpoint, behind the API key or session token
+#[cfg(feature = "graphql")]
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(crate::graphql::graphiql, crate::graphql::graphql_handler),
+    modifiers(&Authenticated)
+)]
+struct GraphqlApi;
+
+/// Routes anyone can call: sign-in, monitoring and probes, the WebSocket,
+/// this spec, and webhook receivers, which check their own signatures
+#[derive(utoipa::OpenApi)]
+#[openapi(paths(
+    crate::api::serve_artifact,
+    crate::websocket::ws_handler,
+    crate::webhook::github_webhook_handler,
+    crate::gitlab_webhook::gitlab_webhook_handler,
+    crate::custom_webhook::custom_webhook_handler,
+    crate::slack_commands::slack_command_handler,
+    crate::slack_interactions::slack_interaction_handler,
+    crate::telegram_webhook::telegram_webhook_handler,
+    crate::pagerduty_webhooks::pagerduty_webhook_handler,
+    crate::linear_webhooks::linear_webhook_handler,
+    crate::api::metrics_handler,
+    crate::probes::liveness,
+    crate::probes::readiness,
+    crate::monitoring::get_metrics_snapshot,
+    crate::monitoring::get_metrics_history,
+    crate::monitoring::list_alerts,
+    crate::monitoring::acknowledge_alert,
+    crate::monitoring::create_alert_rule,
+    crate::monitoring::get_system_health,
+    crate::monitoring::query_audit_log,
+    crate::monitoring::get_performance_stats,
+    crate::monitoring::get_cost_reports,
+    crate::auth::list_providers,
+    crate::auth::login,
+    crate::auth::callback,
+    openapi_json,
+    swagger_ui,
+))]
+struct PublicApi;
+
+/// Requires the session token or API key on every operation
+struct Authenticated;
+
+impl Modify for Authenticated {
+    fn modify(&self, openapi: &mut OpenApi) {
+        let error = || {
+            ContentBuilder::new()
+                .schema(Some(Ref::from_schema_name("ApiError")))
+                .build()
+        };
+        for item in openapi.paths.paths.values_mut() {
+            for operation in operations_mut(item) {
+                let responses = &mut operation.responses.responses;
+                responses.insert(
+                    "401".to_string(),
+                    ResponseBuilder::new()
+                        .description("Missing or invalid API key or session token")
+                        .content("application/json", error())
+                        .into(),
+                );
+                responses.insert(
+                    "403".to_string(),
+                    ResponseBuilder::new()
+                        .description("Missing permission for the route")
+                        .content("application/json", error())
+                        .into(),
+                );
+                operation.security = Some(vec![
+                    SecurityRequirement::new(SESSION_SCHEME, Vec::<String>::new()),
+                    SecurityRequirement::new(API_KEY_SCHEME, Vec::<String>::new()),
+                ]);
+            }
+        }
+    }
+}
+
+fn operations_mut(item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [
+        &mut item.get,
+        &mut item.put,
+        &mut item.post,
+        &mut item.delete,
+        &mut item.patch,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// OpenAPI 3.1 specification of the web API
+pub fn openapi_spec() -> OpenApi {
+    let mut spec = ProtectedApi::openapi().merge_from(PublicApi::openapi());
+    #[cfg(feature = "graphql")]
+    spec.merge(GraphqlApi::openapi());
+
+    spec.paths.paths = std::mem::take(&mut spec.paths.paths)
+        .into_iter()
+        .map(|(path, item)| (versioned_path(&path), item))
+        .collect();
+
+    let mut tags: Vec<String> = spec
+        .paths
+        .paths
+        .values_mut()
+        .flat_map(operations_mut)
+        .flat_map(|operation| operation.tags.clone().unwrap_or_default())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    spec.tags = Some(
+        tags.into_iter()
+            .map(|tag| TagBuilder::new().name(tag).build())
+            .collect(),
+    );
+
+    spec.info = InfoBuilder::new()
+        .title("Orchestrate API")
+        .version(env!("CARGO_PKG_VERSION"))
+        .description(Some("Agent orchestration and automation API"))
+        .build();
+    spec.servers = Some(vec![ServerBuilder::new()
+        .url("http://localhost:8080")
+        .description(Some("Development server"))
+        .build()]);
+
+    let components = spec.components.get_or_insert_with(Default::default);
+    components.add_security_scheme(
+        API_KEY_SCHEME,
+        SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+            "x-api-key",
+            "API key, also accepted as an `Authorization: Bearer` token",
+        ))),
+    );
+    components.add_security_scheme(
+        SESSION_SCHEME,
+        SecurityScheme::Http(
+            HttpBuilder::new()
+                .scheme(HttpAuthScheme::Bearer)
+                .bearer_format("JWT")
+                .description(Some("Session token from `/auth/callback/{provider}`"))
+                .build(),
+        ),
+    );
+    spec
+}
+
+/// Create the router serving the spec and Swagger UI
+pub fn create_openapi_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/api/openapi.json", get(openapi_json))
+        .route("/docs", get(swagger_ui))
+}
+
+/// GET /api/openapi.json - OpenAPI specification
+#[utoipa::path(
+    get,
+    path = "/api/openapi.json",
+    tag = "reference",
+    summary = "Get the OpenAPI specification",
+    responses((status = 200, description = "This specification", body = Object))
+)]
+async fn openapi_json() -> impl IntoResponse {
+    Json(openapi_spec())
+}
+
+/// GET /docs - Swagger UI for the OpenAPI specification
+#[utoipa::path(
+    get,
+    path = "/docs",
+    tag = "reference",
+    summary = "Browse the API with Swagger UI",
+    responses((status = 200, description = "Swagger UI", content_type = "text/html"))
+)]
+async fn swagger_ui() -> Html<&'static str> {
+    Html(SWAGGER_UI_HTML)
+}
+
+const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>Orchestrate API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    window.onload = () => {
+      window.ui = SwaggerUIBundle({ url: "/api/v1/openapi.json", dom_id: "#swagger-ui" });
+    };
+  </script>
+</body>
+</html>
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// OpenAPI path template of an axum route path
+    fn openapi_path(path: &str) -> String {
+        path.split('/')
+            .map(|segment| match segment.strip_prefix([':', '*']) {
+                Some(name) => format!("{{{}}}", name),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    #[test]
+    fn test_spec_documents_schemas_and_security() {
+        let spec = serde_json::to_value(openapi_spec()).unwrap();
+        assert_eq!(spec["openapi"], "3.1.0");
+
+        let paths = &spec["paths"];
+        // API routes are documented under their current version
+        assert!(paths["/api/agents"].is_null());
+        let agent = &paths["/api/v1/agents/{id}"]["get"];
+        assert_eq!(agent["parameters"][0]["name"], "id");
+        assert_eq!(agent["parameters"][0]["in"], "path");
+        assert_eq!(agent["security"][0]["session"], json!([]));
+        assert_eq!(agent["security"][1]["api_key"], json!([]));
+        assert_eq!(
+            agent["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/AgentResponse"
+        );
+        assert_eq!(
+            agent["responses"]["401"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ApiError"
+        );
+        assert!(paths["/api/v1/metrics"]["get"]["security"].is_null());
+        assert!(paths["/webhooks/github"]["post"]["requestBody"].is_object());
+
+        // Request bodies and query parameters come from the handlers' types
+        let create = &paths["/api/v1/agents"]["post"];
+        assert_eq!(
+            create["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/CreateAgentRequest"
+        );
+        let list = &paths["/api/v1/agents"]["get"];
+        assert!(list["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|p| p["name"] == "limit" && p["in"] == "query"));
+
+        let schemas = &spec["components"]["schemas"];
+        for schema in ["AgentResponse", "CreateAgentRequest", "ApiError"] {
+            assert!(schemas[schema]["properties"].is_object(), "{}", schema);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spec_covers_router() {
+        let spec = serde_json::to_value(openapi_spec()).unwrap();
+        let db = orchestrate_core::Database::in_memory().await.unwrap();
+        let router = crate::api::create_unversioned_router(Arc::new(AppState::new(db, None)), None);
+
+        // The spec documents exactly the routes the router registers
+        let registered: Vec<(String, String)> = crate::test_support::registered_routes(router)
+            .await
+            .into_iter()
+            .map(|(method, path)| (method, versioned_path(&openapi_path(&path))))
+            .collect();
+        let documented: Vec<(String, String)> = spec["paths"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .flat_map(|(path, item)| {
+                item.as_object()
+                    .unwrap()
+                    .keys()
+                    .map(move |method| (method.to_uppercase(), path.clone()))
+            })
+            .collect();
+        assert!(registered.len() > 100);
+        let undocumented: Vec<_> = registered
+            .iter()
+            .filter(|route| !documented.contains(route))
+            .collect();
+        assert!(
+            undocumented.is_empty(),
+            "Routes missing from the spec: {:?}",
+            undocumented
+        );
+        let unrouted: Vec<_> = documented
+            .iter()
+            .filter(|route| !registered.contains(route))
+            .collect();
+        assert!(
+            unrouted.is_empty(),
+            "Documented routes the router doesn't serve: {:?}",
+            unrouted
+        );
+
+        // Operation ids are unique across routes
+        let mut ids: Vec<&str> = spec["paths"]
+            .as_object()
+            .unwrap()
+            .values()
+            .flat_map(|item| item.as_object().unwrap().values())
+            .map(|operation| operation["operationId"].as_str().unwrap())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), documented.len());
+    }
+
+    #[tokio::test]
+    async fn test_serves_spec_and_swagger_ui() {
+        use axum::{body::Body, http::Request};
+        use http_body_util::BodyExt;
+        use tower::util::ServiceExt;
+
+        let db = orchestrate_core::Database::in_memory().await.unwrap();
+        let router = create_openapi_router().with_state(Arc::new(AppState::new(db, None)));
+
+        let response = router
+            .clone()
+            .oneshot(
+                Request::get("/api/openapi.json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let spec: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(spec["paths"]["/api/v1/pipelines/{name}/run"]["post"].is_object());
+
+        let response = router
+            .oneshot(Request::get("/docs").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(String::from_utf8_lossy(&body).contains("/api/v1/openapi.json"));
+    }
+}
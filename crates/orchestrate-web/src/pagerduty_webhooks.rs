@@ -0,0 +1,251 @@
+//! PagerDuty webhook endpoint
+//!
+//! Receives PagerDuty v3 webhook deliveries so acknowledgements and
+//! resolutions made in PagerDuty are synced back to the incidents and
+//! alerts orchestrate paged. Events are matched by the deduplication key
+//! orchestrate sent with the page; events about other PagerDuty incidents
+//! are acknowledged and ignored. Opsgenie alerts created for the same
+//! incident or alert are acknowledged or closed along with it.
+//!
+//! Deliveries are verified with the webhook subscription's signing secret:
+//! PagerDuty sends `v1=<hex HMAC-SHA256 of the body>` signatures in
+//! `X-PagerDuty-Signature`.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use orchestrate_core::{
+    pagerduty::{verify_webhook_signature, PAGERDUTY_SIGNATURE_HEADER},
+    IncidentEscalationService, PagerDutyWebhook,
+};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use crate::webhook::{respond, WebhookState};
+
+/// PagerDuty webhook handler
+///
+/// Verifies the signature and applies `incident.acknowledged` and
+/// `incident.resolved` events to the paged incident or alert.
+#[utoipa::path(
+    post,
+    path = "/api/pagerduty/webhook",
+    tag = "webhooks",
+    summary = "Receive a PagerDuty webhook",
+    request_body(content = String, content_type = "application/json"),
+    responses(
+        (status = 200, description = "Event accepted"),
+        (status = 400, description = "Malformed payload"),
+        (status = 401, description = "Invalid signature")
+    )
+)]
+pub async fn pagerduty_webhook_handler(
+    State(state): State<Arc<WebhookState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let Some(secret) = state.config.secret.as_ref() else {
+        warn!("PagerDuty webhook received but PAGERDUTY_WEBHOOK_SECRET is not set");
+        return respond(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "error",
+            "PagerDuty webhook secret not configured",
+        )
+        .into_response();
+    };
+
+    let signature = headers
+        .get(PAGERDUTY_SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !verify_webhook_signature(secret, &body, signature) {
+        warn!("Rejected PagerDuty webhook with invalid signature");
+        return respond(StatusCode::UNAUTHORIZED, "error", "Invalid signature").into_response();
+    }
+
+    let webhook: PagerDutyWebhook = match serde_json::from_slice(&body) {
+        Ok(webhook) => webhook,
+        Err(e) => {
+            warn!(error = %e, "Failed to parse PagerDuty webhook");
+            return respond(
+                StatusCode::BAD_REQUEST,
+                "error",
+                &format!("Invalid PagerDuty webhook payload: {}", e),
+            )
+            .into_response();
+        }
+    };
+
+    let event = &webhook.event;
+    // Other providers paged for the resource follow, when configured
+    let service = IncidentEscalationService::from_env(state.database.clone()).unwrap_or_else(|e| {
+        warn!(error = %e, "Failed to configure on-call providers from the environment");
+        IncidentEscalationService::new(state.database.clone())
+    });
+    match service.apply_webhook(event).await {
+        Ok(true) => {
+            info!(event_id = %event.id, event_type = %event.event_type, "Applied PagerDuty webhook");
+            respond(
+                StatusCode::OK,
+                "ok",
+                &format!("Applied {}", event.event_type),
+            )
+            .into_response()
+        }
+        Ok(false) => respond(
+            StatusCode::OK,
+            "ignored",
+            &format!("Ignored {}", event.event_type),
+        )
+        .into_response(),
+        Err(e) => {
+            error!(event_id = %event.id, error = %e, "Failed to apply PagerDuty webhook");
+            respond(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "error",
+                "Failed to apply PagerDuty webhook",
+            )
+            .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhook::WebhookConfig;
+    use axum::{
+        body::Body,
+        http::{Method, Request},
+        routing::post,
+        Router,
+    };
+    use hmac::{Hmac, Mac};
+    use orchestrate_core::{
+        Database, Incident, IncidentSeverity, IncidentStatus, OncallPage, OncallPageStatus,
+        OncallProvider, PagedResource,
+    };
+    use serde_json::Value;
+    use sha2::Sha256;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "pagerduty-webhook-secret";
+
+    async fn create_test_router(secret: Option<&str>) -> (Router, Database) {
+        let database = Database::in_memory().await.unwrap();
+        let state = Arc::new(WebhookState::new(
+            WebhookConfig::new(secret.map(str::to_string)),
+            database.clone(),
+        ));
+        let router = Router::new()
+            .route("/api/pagerduty/webhook", post(pagerduty_webhook_handler))
+            .with_state(state);
+        (router, database)
+    }
+
+    fn sign(body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(body.as_bytes());
+        format!("v1={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    fn event(event_type: &str, incident_key: &str) -> String {
+        serde_json::json!({
+            "event": {
+                "id": "01DEN4HPBQAAAG05V5QQYBRZMF",
+                "event_type": event_type,
+                "resource_type": "incident",
+                "occurred_at": "2024-01-01T12:00:00.000Z",
+                "agent": {"id": "PLH1HKV", "summary": "Jane Doe", "type": "user_reference"},
+                "data": {
+                    "id": "PGR0VU2",
+                    "type": "incident",
+                    "status": "resolved",
+                    "incident_key": incident_key
+                }
+            }
+        })
+        .to_string()
+    }
+
+    fn request(body: String, signature: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/api/pagerduty/webhook")
+            .header("content-type", "application/json")
+            .header("x-pagerduty-signature", signature)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn send(router: Router, body: String) -> (StatusCode, Value) {
+        let signature = sign(&body);
+        let response = router.oneshot(request(body, &signature)).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_signature() {
+        let (router, _) = create_test_router(Some(SECRET)).await;
+        let body = event("incident.resolved", "orchestrate:incident:INC-1");
+        let response = router
+            .oneshot(request(body.clone(), "v1=deadbeef"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let (router, _) = create_test_router(None).await;
+        let response = router
+            .oneshot(request(body.clone(), &sign(&body)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_resolution_syncs_incident() {
+        let (router, database) = create_test_router(Some(SECRET)).await;
+
+        let incident = Incident::new("INC-1", "API down", IncidentSeverity::Critical);
+        database.create_incident(&incident).await.unwrap();
+        let resource = PagedResource::Incident("INC-1".to_string());
+        database
+            .upsert_oncall_page(&OncallPage::new(
+                OncallProvider::PagerDuty,
+                "R0UTING",
+                resource.clone(),
+            ))
+            .await
+            .unwrap();
+
+        let (status, reply) = send(
+            router.clone(),
+            event("incident.resolved", "orchestrate:incident:INC-1"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(reply["status"], "ok");
+
+        let stored = database.get_incident("INC-1").await.unwrap().unwrap();
+        assert_eq!(stored.status, IncidentStatus::Resolved);
+        assert_eq!(
+            stored.timeline.last().unwrap().actor.as_deref(),
+            Some("Jane Doe")
+        );
+        assert_eq!(
+            database.list_oncall_pages(&resource).await.unwrap()[0].status,
+            OncallPageStatus::Resolved
+        );
+
+        let (status, reply) = send(router, event("incident.resolved", "srv01/HTTP")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(reply["status"], "ignored");
+    }
+}
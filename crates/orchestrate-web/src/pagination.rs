@@ -0,0 +1,191 @@
+//! Cursor pagination of list endpoints
+//!
+//! Every list endpoint takes `limit` (default 50, at most 500), `cursor`, and
+//! `include_total` query parameters and responds with a [`Page`]:
+//!
+//! ```json
+//! {"items": [...], "next_cursor": "7b22...", "total": 120}
+//! ```
+//!
+//! Items are sorted by a stable key ending in a unique id, and a cursor
+//! encodes the key of the last item returned, so pages don't shift when items
+//! are added or removed between requests. `next_cursor` is null on the last
+//! page and `total` is only counted when `include_total=true`.
+//!
+//! Lists stored in the database are paged in SQL through [`PageParams::query`]
+//! (see [`orchestrate_core::pagination`]); [`PageParams::paginate`] pages the
+//! few lists held elsewhere, like the skill registry.
+
+use orchestrate_core::{KeyValue, PageQuery, RowPage};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::api::ApiError;
+
+/// Page size when none is requested
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+/// Largest page size accepted
+pub const MAX_PAGE_SIZE: usize = 500;
+
+/// Query parameters of list endpoints
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageParams {
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+    /// Count all items matching the filters
+    #[serde(default)]
+    pub include_total: bool,
+}
+
+/// One page of a list
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor of the next page, if there is one
+    pub next_cursor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<usize>,
+}
+
+impl<T> Default for Page<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            next_cursor: None,
+            total: None,
+        }
+    }
+}
+
+impl<T> From<RowPage<T>> for Page<T> {
+    fn from(rows: RowPage<T>) -> Self {
+        Self {
+            items: rows.items,
+            next_cursor: rows.next.as_ref().map(encode_cursor),
+            total: rows.total.map(|total| total as usize),
+        }
+    }
+}
+
+impl PageParams {
+    pub fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+
+    /// The requested page of a database list
+    pub fn query(&self) -> Result<PageQuery, ApiError> {
+        Ok(PageQuery {
+            after: self
+                .cursor
+                .as_deref()
+                .map(decode_cursor::<Vec<KeyValue>>)
+                .transpose()?,
+            limit: self.limit(),
+            include_total: self.include_total,
+        })
+    }
+
+    /// Take the requested page of `items` held in memory, ordered by `key`
+    ///
+    /// Wrap the key in [`std::cmp::Reverse`] to list newest first.
+    pub fn paginate<T, K>(
+        &self,
+        mut items: Vec<T>,
+        key: impl Fn(&T) -> K,
+    ) -> Result<Page<T>, ApiError>
+    where
+        K: Ord + Serialize + DeserializeOwned,
+    {
+        let after = self.cursor.as_deref().map(decode_cursor::<K>).transpose()?;
+        let total = self.include_total.then_some(items.len());
+
+        items.sort_by_key(|item| key(item));
+        let start = after.map_or(0, |after| items.partition_point(|item| key(item) <= after));
+        let limit = self.limit();
+
+        let mut page: Vec<T> = items.into_iter().skip(start).take(limit + 1).collect();
+        let next_cursor = if page.len() > limit {
+            page.truncate(limit);
+            page.last().map(|item| encode_cursor(&key(item)))
+        } else {
+            None
+        };
+
+        Ok(Page {
+            items: page,
+            next_cursor,
+            total,
+        })
+    }
+}
+
+fn encode_cursor<K: Serialize>(key: &K) -> String {
+    hex::encode(serde_json::to_vec(key).unwrap_or_default())
+}
+
+fn decode_cursor<K: DeserializeOwned>(cursor: &str) -> Result<K, ApiError> {
+    hex::decode(cursor)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or_else(|| ApiError::bad_request("Invalid cursor"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Reverse;
+
+    fn params(cursor: Option<String>, limit: usize) -> PageParams {
+        PageParams {
+            cursor,
+            limit: Some(limit),
+            include_total: true,
+        }
+    }
+
+    #[test]
+    fn test_paginate_walks_all_pages_in_order() {
+        let items: Vec<i64> = vec![3, 1, 5, 2, 4];
+
+        let first = params(None, 2)
+            .paginate(items.clone(), |i| Reverse(*i))
+            .unwrap();
+        assert_eq!(first.items, vec![5, 4]);
+        assert_eq!(first.total, Some(5));
+
+        let second = params(first.next_cursor, 2)
+            .paginate(items.clone(), |i| Reverse(*i))
+            .unwrap();
+        assert_eq!(second.items, vec![3, 2]);
+
+        // Removing an item already seen doesn't shift the next page
+        let remaining: Vec<i64> = items.into_iter().filter(|i| *i != 3).collect();
+        let third = params(second.next_cursor, 2)
+            .paginate(remaining, |i| Reverse(*i))
+            .unwrap();
+        assert_eq!(third.items, vec![1]);
+        assert_eq!(third.next_cursor, None);
+    }
+
+    #[test]
+    fn test_paginate_limits_and_cursors() {
+        let items: Vec<i64> = (0..1000).collect();
+        let page = PageParams::default()
+            .paginate(items.clone(), |i| *i)
+            .unwrap();
+        assert_eq!(page.items.len(), DEFAULT_PAGE_SIZE);
+        assert_eq!(page.total, None);
+
+        let page = params(None, 10_000)
+            .paginate(items.clone(), |i| *i)
+            .unwrap();
+        assert_eq!(page.items.len(), MAX_PAGE_SIZE);
+
+        let page = params(Some("zz".to_string()), 10).paginate(items, |i| *i);
+        assert!(page.is_err());
+    }
+}
@@ -0,0 +1,395 @@
+//! Kubernetes liveness and readiness probes
+//!
+//! - GET /healthz - Liveness: the server answers and its database responds
+//! - GET /readyz - Readiness: database, Claude API, GitHub API, and webhook
+//!   queue lag, each reported as a component
+//!
+//! Both answer 503 when a component is unhealthy. Claude and GitHub outages
+//! and webhook backlogs only degrade readiness: taking this instance out of
+//! service wouldn't fix them.
+
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::{DateTime, Utc};
+use orchestrate_core::{ComponentHealth, Database, HealthStatus};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
+
+use crate::api::AppState;
+
+const DEFAULT_CLAUDE_API_URL: &str = "https://api.anthropic.com";
+const DEFAULT_GITHUB_API_URL: &str = "https://api.github.com";
+const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+const DEFAULT_MAX_WEBHOOK_LAG: Duration = Duration::from_secs(300);
+
+/// What readiness probes check
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeConfig {
+    /// Claude API base URL, `None` to skip the check
+    pub claude_api_url: Option<String>,
+    /// GitHub API base URL, `None` to skip the check
+    pub github_api_url: Option<String>,
+    /// Time each check may take before it fails
+    pub timeout: Duration,
+    /// Age of the oldest pending webhook event before the queue is degraded
+    pub max_webhook_lag: Duration,
+}
+
+impl Default for ProbeConfig {
+    fn default() -> Self {
+        Self {
+            claude_api_url: Some(DEFAULT_CLAUDE_API_URL.to_string()),
+            github_api_url: Some(DEFAULT_GITHUB_API_URL.to_string()),
+            timeout: DEFAULT_PROBE_TIMEOUT,
+            max_webhook_lag: DEFAULT_MAX_WEBHOOK_LAG,
+        }
+    }
+}
+
+impl ProbeConfig {
+    /// Load configuration from `ORCHESTRATE_PROBE_CLAUDE_URL`,
+    /// `ORCHESTRATE_PROBE_GITHUB_URL`, `ORCHESTRATE_PROBE_TIMEOUT_SECS`, and
+    /// `ORCHESTRATE_PROBE_MAX_WEBHOOK_LAG_SECS`
+    ///
+    /// An empty URL skips that check, e.g. on hosts without internet access.
+    pub fn from_env() -> Self {
+        let url = |var: &str, default: Option<String>| match std::env::var(var) {
+            Ok(url) if url.trim().is_empty() => None,
+            Ok(url) => Some(url.trim().trim_end_matches('/').to_string()),
+            Err(_) => default,
+        };
+        let secs = |var: &str, default: Duration| {
+            let Ok(value) = std::env::var(var) else {
+                return default;
+            };
+            match value.trim().parse::<u64>() {
+                Ok(secs) => Duration::from_secs(secs),
+                Err(_) => {
+                    tracing::warn!("Ignoring invalid {}: {}", var, value);
+                    default
+                }
+            }
+        };
+
+        let default = Self::default();
+        Self {
+            claude_api_url: url("ORCHESTRATE_PROBE_CLAUDE_URL", default.claude_api_url),
+            github_api_url: url("ORCHESTRATE_PROBE_GITHUB_URL", default.github_api_url),
+            timeout: secs("ORCHESTRATE_PROBE_TIMEOUT_SECS", default.timeout),
+            max_webhook_lag: secs(
+                "ORCHESTRATE_PROBE_MAX_WEBHOOK_LAG_SECS",
+                default.max_webhook_lag,
+            ),
+        }
+    }
+
+    /// Skip the Claude and GitHub API checks
+    pub fn without_external_apis(mut self) -> Self {
+        self.claude_api_url = None;
+        self.github_api_url = None;
+        self
+    }
+}
+
+/// Runs the checks of a [`ProbeConfig`]
+#[derive(Debug, Clone)]
+pub struct Probes {
+    config: ProbeConfig,
+    client: reqwest::Client,
+}
+
+impl Probes {
+    pub fn new(config: ProbeConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .unwrap_or_default();
+        Self { config, client }
+    }
+
+    pub fn config(&self) -> &ProbeConfig {
+        &self.config
+    }
+
+    async fn check_database(&self, db: &Database) -> ComponentHealth {
+        let started = Instant::now();
+        let health = match tokio::time::timeout(self.config.timeout, db.ping()).await {
+            Ok(Ok(())) => ComponentHealth::healthy("database"),
+            Ok(Err(e)) => ComponentHealth::unhealthy("database", format!("Query failed: {}", e)),
+            Err(_) => ComponentHealth::unhealthy(
+                "database",
+                format!("No response within {}s", self.config.timeout.as_secs()),
+            ),
+        };
+        health.with_latency(started.elapsed())
+    }
+
+    /// Any response short of a server error means the API is reachable
+    async fn check_api(&self, name: &str, url: &str) -> ComponentHealth {
+        let started = Instant::now();
+        let health = match self.client.get(url).send().await {
+            Ok(response) if response.status().is_server_error() => {
+                ComponentHealth::degraded(name, format!("{} answered {}", url, response.status()))
+            }
+            Ok(_) => ComponentHealth::healthy(name),
+            Err(e) if e.is_timeout() => ComponentHealth::degraded(
+                name,
+                format!(
+                    "No response from {} within {}s",
+                    url,
+                    self.config.timeout.as_secs()
+                ),
+            ),
+            Err(e) => ComponentHealth::degraded(name, format!("{} unreachable: {}", url, e)),
+        };
+        health.with_latency(started.elapsed())
+    }
+
+    async fn check_webhook_queue(&self, db: &Database) -> ComponentHealth {
+        let lags = match db.get_webhook_queue_lags().await {
+            Ok(lags) => lags,
+            Err(e) => {
+                return ComponentHealth::degraded(
+                    "webhook_queue",
+                    format!("Failed to read queue: {}", e),
+                )
+            }
+        };
+
+        let pending: i64 = lags.iter().map(|lag| lag.pending).sum();
+        let max_lag = self.config.max_webhook_lag.as_secs_f64();
+        let mut lagging: Vec<String> = lags
+            .iter()
+            .filter(|lag| lag.lag_seconds > max_lag)
+            .map(|lag| format!("{} ({:.0}s)", lag.event_type, lag.lag_seconds))
+            .collect();
+        lagging.sort();
+
+        if lagging.is_empty() {
+            let mut health = ComponentHealth::healthy("webhook_queue");
+            health.message = Some(format!("{} pending", pending));
+            health
+        } else {
+            ComponentHealth::degraded(
+                "webhook_queue",
+                format!(
+                    "{} pending, waiting over {}s: {}",
+                    pending,
+                    self.config.max_webhook_lag.as_secs(),
+                    lagging.join(", ")
+                ),
+            )
+        }
+    }
+}
+
+/// Outcome of a probe with its components
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProbeResponse {
+    #[schema(value_type = String)]
+    pub status: HealthStatus,
+    pub version: String,
+    pub checked_at: DateTime<Utc>,
+    #[schema(value_type = Vec<Object>)]
+    pub components: Vec<ComponentHealth>,
+}
+
+impl ProbeResponse {
+    fn new(components: Vec<ComponentHealth>) -> (StatusCode, Json<Self>) {
+        let status = if components
+            .iter()
+            .any(|c| c.status == HealthStatus::Unhealthy)
+        {
+            HealthStatus::Unhealthy
+        } else if components
+            .iter()
+            .any(|c| c.status == HealthStatus::Degraded)
+        {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        };
+        let code = if status == HealthStatus::Unhealthy {
+            StatusCode::SERVICE_UNAVAILABLE
+        } else {
+            StatusCode::OK
+        };
+
+        let response = Self {
+            status,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            checked_at: Utc::now(),
+            components,
+        };
+        (code, Json(response))
+    }
+}
+
+/// GET /healthz - Liveness, never depending on external APIs so their
+/// outages don't restart the server
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "monitoring",
+    summary = "Liveness probe",
+    responses(
+        (status = 200, body = ProbeResponse),
+        (status = 503, body = ProbeResponse)
+    )
+)]
+pub async fn liveness(State(state): State<Arc<AppState>>) -> (StatusCode, Json<ProbeResponse>) {
+    let database = state.probes.check_database(&state.db).await;
+    ProbeResponse::new(vec![database])
+}
+
+/// GET /readyz - Readiness, checking every component concurrently
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "monitoring",
+    summary = "Readiness probe of each component",
+    responses(
+        (status = 200, body = ProbeResponse),
+        (status = 503, body = ProbeResponse)
+    )
+)]
+pub async fn readiness(State(state): State<Arc<AppState>>) -> (StatusCode, Json<ProbeResponse>) {
+    let probes = &state.probes;
+    let config = probes.config();
+    let api = |name: &'static str, url: &Option<String>| {
+        let url = url.clone();
+        async move {
+            match url {
+                Some(url) => Some(probes.check_api(name, &url).await),
+                None => None,
+            }
+        }
+    };
+
+    let (database, claude, github, webhook_queue) = tokio::join!(
+        probes.check_database(&state.db),
+        api("claude_api", &config.claude_api_url),
+        api("github_api", &config.github_api_url),
+        probes.check_webhook_queue(&state.db),
+    );
+
+    let mut components = vec![database];
+    components.extend(claude);
+    components.extend(github);
+    components.push(webhook_queue);
+    ProbeResponse::new(components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{closed_port, serve};
+    use axum::{
+        body::Body,
+        http::{Method, Request},
+        Router,
+    };
+    use http_body_util::BodyExt;
+    use orchestrate_core::WebhookEvent;
+    use tower::ServiceExt;
+
+    /// URL of a local server answering `status` to every request
+    async fn serve_status(status: StatusCode) -> String {
+        serve(Router::new().fallback(move || async move { status })).await
+    }
+
+    async fn probe(state: Arc<AppState>, uri: &str) -> (StatusCode, ProbeResponse) {
+        let response = crate::create_router(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_probes_need_no_api_key() {
+        let db = Database::in_memory().await.unwrap();
+        let state = AppState::new(db, Some("secret".to_string()))
+            .with_probes(ProbeConfig::default().without_external_apis());
+        let state = Arc::new(state);
+
+        let (status, live) = probe(state.clone(), "/healthz").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(live.status, HealthStatus::Healthy);
+        assert_eq!(live.components.len(), 1);
+        assert!(live.components[0].latency_ms.is_some());
+
+        let (status, ready) = probe(state, "/readyz").await;
+        assert_eq!(status, StatusCode::OK);
+        let names: Vec<&str> = ready.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["database", "webhook_queue"]);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_reports_each_component() {
+        let db = Database::in_memory().await.unwrap();
+        let mut event = WebhookEvent::new(
+            "delivery-1".to_string(),
+            "push".to_string(),
+            "{}".to_string(),
+        );
+        event.received_at = Utc::now() - chrono::Duration::minutes(10);
+        db.insert_webhook_event(&event).await.unwrap();
+
+        let config = ProbeConfig {
+            claude_api_url: Some(serve_status(StatusCode::UNAUTHORIZED).await),
+            github_api_url: Some(closed_port().await),
+            ..Default::default()
+        };
+        let state = Arc::new(AppState::new(db, None).with_probes(config));
+
+        let (status, ready) = probe(state, "/readyz").await;
+        // Degraded components don't take the instance out of service
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(ready.status, HealthStatus::Degraded);
+
+        let component = |name: &str| {
+            ready
+                .components
+                .iter()
+                .find(|c| c.name == name)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(component("database").status, HealthStatus::Healthy);
+        assert_eq!(component("claude_api").status, HealthStatus::Healthy);
+        assert_eq!(component("github_api").status, HealthStatus::Degraded);
+        let queue = component("webhook_queue");
+        assert_eq!(queue.status, HealthStatus::Degraded);
+        assert!(queue.message.unwrap().contains("push"));
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_degrade_api() {
+        let probes = Probes::new(ProbeConfig::default());
+        let url = serve_status(StatusCode::BAD_GATEWAY).await;
+
+        let health = probes.check_api("claude_api", &url).await;
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert!(health.message.unwrap().contains("502"));
+    }
+
+    #[test]
+    fn test_unhealthy_component_fails_probe() {
+        let (status, Json(response)) = ProbeResponse::new(vec![
+            ComponentHealth::unhealthy("database", "Query failed"),
+            ComponentHealth::degraded("github_api", "unreachable"),
+        ]);
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.status, HealthStatus::Unhealthy);
+    }
+}
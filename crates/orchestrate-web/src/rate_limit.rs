@@ -0,0 +1,429 @@
+//! API rate limiting
+//!
+//! Token buckets per client, so a runaway dashboard or script can't starve
+//! the server. Requests carrying the API key or a valid session token are
+//! limited per token, others per IP address. Tokens are verified before they
+//! get a bucket of their own, so made-up ones can't dodge the per-IP limit.
+//! `X-Forwarded-For` is only read from configured trusted proxies, taking the
+//! last address a trusted proxy added. A bucket holds `burst` requests and
+//! refills at the per-minute rate; a client with an empty bucket gets 429
+//! with `Retry-After`.
+//!
+//! Webhook receivers and the WebSocket are outside the limit, so deliveries
+//! keep being processed while API clients are throttled.
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, MatchedPath, State},
+    http::{header, HeaderMap, HeaderValue, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use secrecy::ExposeSecret;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::api::{ApiError, AppState};
+use crate::auth::request_token;
+
+/// Tracked clients above which idle buckets are dropped
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Sustained rate and burst allowance of one client
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub requests_per_minute: u32,
+    /// Requests allowed at once after a quiet period
+    pub burst: u32,
+}
+
+impl RateLimit {
+    pub fn new(requests_per_minute: u32, burst: u32) -> Self {
+        Self {
+            requests_per_minute: requests_per_minute.max(1),
+            burst: burst.max(1),
+        }
+    }
+
+    /// Parse `<per-minute>[:<burst>]`; the burst defaults to a tenth of the
+    /// per-minute rate
+    pub fn parse(s: &str) -> Option<Self> {
+        let (rate, burst) = match s.trim().split_once(':') {
+            Some((rate, burst)) => (rate.parse().ok()?, Some(burst.parse().ok()?)),
+            None => (s.trim().parse().ok()?, None),
+        };
+        Some(Self::new(rate, burst.unwrap_or(rate.div_ceil(10))))
+    }
+
+    fn tokens_per_second(&self) -> f64 {
+        f64::from(self.requests_per_minute) / 60.0
+    }
+}
+
+/// Limits of clients with a token and of anonymous clients
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitConfig {
+    /// Per API key or session token
+    pub per_key: Option<RateLimit>,
+    /// Per IP address, for requests without a valid token
+    pub per_ip: Option<RateLimit>,
+    /// Proxies whose `X-Forwarded-For` header is trusted
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl RateLimitConfig {
+    /// Configure from `ORCHESTRATE_RATE_LIMIT_KEY` and
+    /// `ORCHESTRATE_RATE_LIMIT_IP`, each `<per-minute>[:<burst>]`, and
+    /// `ORCHESTRATE_TRUSTED_PROXIES`, a comma-separated list of IP addresses
+    ///
+    /// Returns `None` when neither limit is set.
+    pub fn from_env() -> Option<Self> {
+        let limit = |var: &str| {
+            let value = std::env::var(var).ok()?;
+            let limit = RateLimit::parse(&value);
+            if limit.is_none() {
+                tracing::warn!("Ignoring invalid {}: {}", var, value);
+            }
+            limit
+        };
+        let config = Self {
+            per_key: limit("ORCHESTRATE_RATE_LIMIT_KEY"),
+            per_ip: limit("ORCHESTRATE_RATE_LIMIT_IP"),
+            trusted_proxies: std::env::var("ORCHESTRATE_TRUSTED_PROXIES")
+                .map(|value| parse_proxies(&value))
+                .unwrap_or_default(),
+        };
+        (config.per_key.is_some() || config.per_ip.is_some()).then_some(config)
+    }
+}
+
+fn parse_proxies(value: &str) -> Vec<IpAddr> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|proxy| !proxy.is_empty())
+        .filter_map(|proxy| {
+            let ip = proxy.parse().ok();
+            if ip.is_none() {
+                tracing::warn!("Ignoring invalid trusted proxy: {}", proxy);
+            }
+            ip
+        })
+        .collect()
+}
+
+/// Who a request is counted against
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Client {
+    /// Hash of the API key or session token
+    Key(String),
+    Ip(String),
+}
+
+impl Client {
+    /// Client of a verified API key or session token
+    pub fn key(token: &str) -> Self {
+        Self::Key(hex::encode(&Sha256::digest(token.as_bytes())[..8]))
+    }
+
+    /// Client of an anonymous request: the peer address, or behind trusted
+    /// proxies the address the first of them was connected from
+    pub fn ip(headers: &HeaderMap, peer: Option<SocketAddr>, trusted_proxies: &[IpAddr]) -> Self {
+        let Some(mut ip) = peer.map(|addr| addr.ip()) else {
+            return Self::Ip("unknown".to_string());
+        };
+
+        // Each proxy appends the address it was connected from, so walk back
+        // from the last entry while the connection came from a trusted proxy
+        let forwarded: Vec<&str> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .collect();
+        for entry in forwarded.iter().rev() {
+            if !trusted_proxies.contains(&ip) {
+                break;
+            }
+            match entry.parse() {
+                Ok(from) => ip = from,
+                Err(_) => break,
+            }
+        }
+        Self::Ip(ip.to_string())
+    }
+
+    /// Metrics label
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Key(_) => "key",
+            Self::Ip(_) => "ip",
+        }
+    }
+}
+
+/// Outcome of counting a request
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decision {
+    Allowed { limit: u32, remaining: u32 },
+    Limited { retry_after: Duration },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token buckets of all clients
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<HashMap<Client, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn limit_of(&self, client: &Client) -> Option<RateLimit> {
+        match client {
+            Client::Key(_) => self.config.per_key,
+            Client::Ip(_) => self.config.per_ip,
+        }
+    }
+
+    /// Count a request, or `None` if the client's kind isn't limited
+    pub fn check(&self, client: &Client) -> Option<Decision> {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: &Client, now: Instant) -> Option<Decision> {
+        let limit = self.limit_of(client)?;
+        let capacity = f64::from(limit.burst);
+        let rate = limit.tokens_per_second();
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(client) {
+            // Buckets that have refilled since are the same as new ones
+            buckets.retain(|client, bucket| {
+                self.limit_of(client).is_some_and(|limit| {
+                    let refill = f64::from(limit.burst) / limit.tokens_per_second();
+                    now.duration_since(bucket.updated).as_secs_f64() < refill
+                })
+            });
+        }
+
+        let bucket = buckets.entry(client.clone()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Some(Decision::Allowed {
+                limit: limit.burst,
+                remaining: bucket.tokens as u32,
+            })
+        } else {
+            let wait = (1.0 - bucket.tokens) / rate;
+            Some(Decision::Limited {
+                retry_after: Duration::from_secs_f64(wait.ceil().max(1.0)),
+            })
+        }
+    }
+}
+
+/// Token of a request if it's the API key or a valid session token
+///
+/// The middleware runs before authentication, so tokens are checked here
+/// rather than trusted to pick a bucket.
+fn verified_token<'a>(state: &AppState, headers: &'a HeaderMap) -> Option<&'a str> {
+    let token = request_token(headers)?;
+    let is_api_key = state
+        .api_key
+        .as_ref()
+        .is_some_and(|key| token == key.expose_secret());
+    let is_session = state
+        .auth
+        .as_ref()
+        .is_some_and(|auth| auth.verify_token(token).is_some());
+    (is_api_key || is_session).then_some(token)
+}
+
+/// Rate limiting middleware of API routes
+pub(crate) async fn rate_limit_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(limiter) = state.rate_limiter.as_ref() else {
+        return next.run(request).await;
+    };
+
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|info| info.0);
+    let client = match verified_token(&state, request.headers()) {
+        Some(token) => Client::key(token),
+        None => Client::ip(request.headers(), peer, &limiter.config.trusted_proxies),
+    };
+
+    match limiter.check(&client) {
+        None => next.run(request).await,
+        Some(Decision::Allowed { limit, remaining }) => {
+            let mut response = next.run(request).await;
+            let headers = response.headers_mut();
+            headers.insert("x-ratelimit-limit", HeaderValue::from(limit));
+            headers.insert("x-ratelimit-remaining", HeaderValue::from(remaining));
+            response
+        }
+        Some(Decision::Limited { retry_after }) => {
+            let path = request
+                .extensions()
+                .get::<MatchedPath>()
+                .map(|p| p.as_str())
+                .unwrap_or("unmatched");
+            state.metrics.record_rate_limited(client.kind(), path);
+
+            let seconds = retry_after.as_secs();
+            let mut response = ApiError::rate_limited(format!(
+                "Rate limit exceeded, retry in {} seconds",
+                seconds
+            ))
+            .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rate_limit() {
+        assert_eq!(RateLimit::parse("600:50"), Some(RateLimit::new(600, 50)));
+        assert_eq!(RateLimit::parse("120"), Some(RateLimit::new(120, 12)));
+        assert_eq!(RateLimit::parse("5"), Some(RateLimit::new(5, 1)));
+        assert_eq!(RateLimit::parse("fast"), None);
+        assert_eq!(RateLimit::parse("60:x"), None);
+    }
+
+    #[test]
+    fn test_client_identification() {
+        let mut headers = HeaderMap::new();
+        let peer: SocketAddr = "10.0.0.1:5000".parse().unwrap();
+        let proxies: Vec<IpAddr> = vec!["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()];
+        assert_eq!(
+            Client::ip(&headers, Some(peer), &proxies),
+            Client::Ip("10.0.0.1".to_string())
+        );
+        assert_eq!(
+            Client::ip(&headers, None, &proxies),
+            Client::Ip("unknown".to_string())
+        );
+
+        // Entries before the last one added by a trusted proxy are the
+        // client's to make up
+        headers.insert(
+            "x-forwarded-for",
+            "198.51.100.1, 203.0.113.7, 10.0.0.2".parse().unwrap(),
+        );
+        assert_eq!(
+            Client::ip(&headers, Some(peer), &proxies),
+            Client::Ip("203.0.113.7".to_string())
+        );
+
+        // Only trusted proxies can forward addresses
+        assert_eq!(
+            Client::ip(&headers, Some(peer), &[]),
+            Client::Ip("10.0.0.1".to_string())
+        );
+        let direct: SocketAddr = "192.0.2.9:5000".parse().unwrap();
+        assert_eq!(
+            Client::ip(&headers, Some(direct), &proxies),
+            Client::Ip("192.0.2.9".to_string())
+        );
+
+        headers.insert("x-forwarded-for", "junk, 10.0.0.2".parse().unwrap());
+        assert_eq!(
+            Client::ip(&headers, Some(peer), &proxies),
+            Client::Ip("10.0.0.2".to_string())
+        );
+
+        let client = Client::key("secret");
+        assert_eq!(client.kind(), "key");
+        assert!(!format!("{:?}", client).contains("secret"));
+    }
+
+    #[test]
+    fn test_parse_trusted_proxies() {
+        assert_eq!(
+            parse_proxies(" 10.0.0.1, ::1,,proxy "),
+            vec![
+                "10.0.0.1".parse::<IpAddr>().unwrap(),
+                "::1".parse::<IpAddr>().unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bucket_allows_burst_then_refills() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_key: None,
+            per_ip: Some(RateLimit::new(60, 2)),
+            trusted_proxies: Vec::new(),
+        });
+        let client = Client::Ip("10.0.0.1".to_string());
+        let start = Instant::now();
+
+        assert_eq!(
+            limiter.check_at(&client, start),
+            Some(Decision::Allowed {
+                limit: 2,
+                remaining: 1
+            })
+        );
+        assert!(matches!(
+            limiter.check_at(&client, start),
+            Some(Decision::Allowed { remaining: 0, .. })
+        ));
+        assert_eq!(
+            limiter.check_at(&client, start),
+            Some(Decision::Limited {
+                retry_after: Duration::from_secs(1)
+            })
+        );
+
+        // Other clients have their own bucket, and keys aren't limited
+        let other = Client::Ip("10.0.0.2".to_string());
+        assert!(matches!(
+            limiter.check_at(&other, start),
+            Some(Decision::Allowed { .. })
+        ));
+        assert_eq!(limiter.check_at(&Client::Key("k".to_string()), start), None);
+
+        // One request per second refills
+        let later = start + Duration::from_secs(1);
+        assert!(matches!(
+            limiter.check_at(&client, later),
+            Some(Decision::Allowed { remaining: 0, .. })
+        ));
+    }
+}
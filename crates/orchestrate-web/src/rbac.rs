@@ -0,0 +1,456 @@
+//! Role-based access control
+//!
+//! Each API route requires a permission: reads need `read`, and every other
+//! route is listed with its permission in `ROUTE_PERMISSIONS`. Viewers can
+//! read, approvers can also decide approvals, operators can spawn agents and
+//! change configuration, and admins can do everything. Roles are assigned to
+//! signed-in users by verified email, `@domain`, or `provider:username`
+//! (e.g. `github:octocat`), as usernames are only unique per provider.
+//!
+//! Requests authenticated with the shared API key aren't tied to a user and
+//! keep full access.
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{Method, Request},
+    middleware::Next,
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::api::{ApiError, AppState};
+use crate::auth::Identity;
+
+/// An action a route performs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Permission {
+    /// Read any resource
+    #[serde(rename = "read")]
+    Read,
+    /// Spawn and control agents
+    #[serde(rename = "agents:spawn")]
+    SpawnAgents,
+    /// Approve or reject approval requests
+    #[serde(rename = "approvals:decide")]
+    DecideApprovals,
+    /// Create, change, or delete other resources
+    #[serde(rename = "write")]
+    Write,
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::SpawnAgents => "agents:spawn",
+            Self::DecideApprovals => "approvals:decide",
+            Self::Write => "write",
+        }
+    }
+
+    /// Permission required for a request to a route pattern
+    pub fn required_for(method: &Method, route: &str) -> Self {
+        if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            return Self::Read;
+        }
+        ROUTE_PERMISSIONS
+            .iter()
+            .find(|(m, r, _)| *m == method.as_str() && *r == route)
+            .map(|(_, _, permission)| *permission)
+            // Unlisted routes need the broadest permission short of admin
+            .unwrap_or(Self::Write)
+    }
+}
+
+use Permission::{DecideApprovals, Read, SpawnAgents, Write};
+
+/// Permission of each API route that isn't a GET, by method and route pattern
+const ROUTE_PERMISSIONS: &[(&str, &str, Permission)] = &[
+    ("POST", "/api/agents", SpawnAgents),
+    ("POST", "/api/agents/bulk/spawn", SpawnAgents),
+    ("POST", "/api/agents/bulk/terminate", SpawnAgents),
+    ("POST", "/api/agents/:id/pause", SpawnAgents),
+    ("POST", "/api/agents/:id/resume", SpawnAgents),
+    ("POST", "/api/agents/:id/terminate", SpawnAgents),
+    ("POST", "/api/agents/:id/message", SpawnAgents),
+    ("POST", "/api/agents/:id/story", SpawnAgents),
+    ("POST", "/api/agents/:id/pr", SpawnAgents),
+    ("POST", "/api/epic/auto-process", SpawnAgents),
+    ("POST", "/api/epic/auto-pause", SpawnAgents),
+    ("POST", "/api/epic/auto-resume", SpawnAgents),
+    ("POST", "/api/epic/auto-stop", SpawnAgents),
+    ("POST", "/api/epic/:id/unblock", Write),
+    ("POST", "/api/epic/edge-cases/:id/resolve", Write),
+    ("PUT", "/api/costs/budget", Write),
+    ("POST", "/api/export-jobs", Write),
+    ("POST", "/api/epics/:id/transition", Write),
+    ("POST", "/api/stories/:id/transition", Write),
+    ("POST", "/api/stories/:id/assign", Write),
+    ("POST", "/api/incidents/:id/timeline", Write),
+    ("POST", "/api/incidents/:id/postmortem", Write),
+    ("POST", "/api/webhooks/events/:id/retry", Write),
+    ("DELETE", "/api/webhooks/dead-letters", Write),
+    ("POST", "/api/instructions", Write),
+    ("PUT", "/api/instructions/:id", Write),
+    ("DELETE", "/api/instructions/:id", Write),
+    ("POST", "/api/instructions/:id/enable", Write),
+    ("POST", "/api/instructions/:id/disable", Write),
+    ("POST", "/api/patterns/:id/approve", Write),
+    ("POST", "/api/patterns/:id/reject", Write),
+    ("POST", "/api/learning/process", Write),
+    ("POST", "/api/learning/cleanup", Write),
+    ("POST", "/api/pipelines", Write),
+    ("PUT", "/api/pipelines/:name", Write),
+    ("DELETE", "/api/pipelines/:name", Write),
+    ("POST", "/api/pipelines/:name/run", Write),
+    ("POST", "/api/pipeline-runs/bulk/retry", Write),
+    ("POST", "/api/pipeline-runs/:id/cancel", Write),
+    ("POST", "/api/approvals/:id/approve", DecideApprovals),
+    ("POST", "/api/approvals/:id/reject", DecideApprovals),
+    ("POST", "/api/approvals/:id/delegate", DecideApprovals),
+    ("POST", "/api/schedules", Write),
+    ("PUT", "/api/schedules/:id", Write),
+    ("DELETE", "/api/schedules/:id", Write),
+    ("POST", "/api/schedules/:id/pause", Write),
+    ("POST", "/api/schedules/:id/resume", Write),
+    ("POST", "/api/schedules/:id/run", Write),
+    ("POST", "/api/feedback", Write),
+    ("DELETE", "/api/feedback/:id", Write),
+    ("POST", "/api/learning/analyze", Write),
+    ("POST", "/api/experiments", Write),
+    ("POST", "/api/experiments/:id/start", Write),
+    ("POST", "/api/experiments/:id/pause", Write),
+    ("POST", "/api/experiments/:id/promote", Write),
+    ("POST", "/api/predictions", Write),
+    ("POST", "/api/docs/generate", Write),
+    ("POST", "/api/docs/validate", Write),
+    ("POST", "/api/docs/adrs", Write),
+    ("PUT", "/api/docs/adrs/:number", Write),
+    ("POST", "/api/docs/changelog", Write),
+    ("POST", "/api/security/scan", Write),
+    ("POST", "/api/security/fix", Write),
+    ("POST", "/api/security/gate/evaluate", Write),
+    ("DELETE", "/api/artifacts/:id", Write),
+    ("POST", "/api/network/skills", Write),
+    ("DELETE", "/api/network/skills/:name", Write),
+    // GraphQL has no mutations, so its POSTs only read
+    ("POST", "/api/graphql", Read),
+];
+
+/// A set of permissions assigned to users
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Viewer,
+    Approver,
+    Operator,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Viewer => "viewer",
+            Self::Approver => "approver",
+            Self::Operator => "operator",
+            Self::Admin => "admin",
+        }
+    }
+
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Self::Viewer => &[Permission::Read],
+            Self::Approver => &[Permission::Read, Permission::DecideApprovals],
+            Self::Operator => &[Permission::Read, Permission::SpawnAgents, Permission::Write],
+            Self::Admin => &[
+                Permission::Read,
+                Permission::SpawnAgents,
+                Permission::DecideApprovals,
+                Permission::Write,
+            ],
+        }
+    }
+
+    pub fn allows(&self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "viewer" => Ok(Self::Viewer),
+            "approver" => Ok(Self::Approver),
+            "operator" => Ok(Self::Operator),
+            "admin" => Ok(Self::Admin),
+            _ => Err(format!("Unknown role: {}", s)),
+        }
+    }
+}
+
+/// Role assignments of signed-in users
+#[derive(Debug, Clone)]
+pub struct RbacPolicy {
+    /// Roles by lowercase email, `@domain`, or `provider:username`
+    assignments: HashMap<String, Vec<Role>>,
+    /// Roles of users without an assignment
+    default_roles: Vec<Role>,
+}
+
+impl Default for RbacPolicy {
+    fn default() -> Self {
+        Self {
+            assignments: HashMap::new(),
+            default_roles: vec![Role::Viewer],
+        }
+    }
+}
+
+impl RbacPolicy {
+    /// Assign a role to an email, `@domain`, or `provider:username`
+    pub fn assign(mut self, user: &str, role: Role) -> Self {
+        let roles = self
+            .assignments
+            .entry(user.trim().to_lowercase())
+            .or_default();
+        if !roles.contains(&role) {
+            roles.push(role);
+        }
+        self
+    }
+
+    /// Roles of users without an assignment; none denies them everything
+    pub fn with_default_roles(mut self, roles: Vec<Role>) -> Self {
+        self.default_roles = roles;
+        self
+    }
+
+    /// Configure from `ORCHESTRATE_RBAC_{ADMINS,OPERATORS,APPROVERS,VIEWERS}`,
+    /// comma-separated users, and `ORCHESTRATE_RBAC_DEFAULT_ROLE`
+    /// (`viewer` by default, `none` to deny unassigned users)
+    pub fn from_env() -> Self {
+        let mut policy = Self::default();
+        for (var, role) in [
+            ("ORCHESTRATE_RBAC_ADMINS", Role::Admin),
+            ("ORCHESTRATE_RBAC_OPERATORS", Role::Operator),
+            ("ORCHESTRATE_RBAC_APPROVERS", Role::Approver),
+            ("ORCHESTRATE_RBAC_VIEWERS", Role::Viewer),
+        ] {
+            if let Ok(users) = std::env::var(var) {
+                for user in users.split(',').filter(|u| !u.trim().is_empty()) {
+                    if !user.contains(['@', ':']) {
+                        tracing::warn!(
+                            "{}: bare username '{}' matches no one; use <provider>:{}",
+                            var,
+                            user.trim(),
+                            user.trim()
+                        );
+                    }
+                    policy = policy.assign(user, role);
+                }
+            }
+        }
+        if let Ok(default_role) = std::env::var("ORCHESTRATE_RBAC_DEFAULT_ROLE") {
+            let roles = match default_role.parse::<Role>() {
+                Ok(role) => vec![role],
+                Err(_) if default_role.eq_ignore_ascii_case("none") => Vec::new(),
+                Err(e) => {
+                    tracing::warn!("{}; unassigned users get no role", e);
+                    Vec::new()
+                }
+            };
+            policy = policy.with_default_roles(roles);
+        }
+        policy
+    }
+
+    /// Roles of a signed-in user
+    pub fn roles(&self, identity: &Identity) -> Vec<Role> {
+        let mut keys: Vec<String> = Vec::new();
+        if let Some(email) = &identity.email {
+            keys.push(email.to_lowercase());
+            if let Some((_, domain)) = email.rsplit_once('@') {
+                keys.push(format!("@{}", domain.to_lowercase()));
+            }
+        }
+        if let Some(username) = &identity.username {
+            keys.push(format!(
+                "{}:{}",
+                identity.provider.as_str(),
+                username.to_lowercase()
+            ));
+        }
+
+        let mut roles: Vec<Role> = keys
+            .iter()
+            .filter_map(|key| self.assignments.get(key))
+            .flatten()
+            .copied()
+            .collect();
+        if roles.is_empty() {
+            return self.default_roles.clone();
+        }
+        roles.sort();
+        roles.dedup();
+        roles
+    }
+
+    /// Whether a signed-in user holds a permission
+    pub fn allows(&self, identity: &Identity, permission: Permission) -> bool {
+        self.roles(identity)
+            .iter()
+            .any(|role| role.allows(permission))
+    }
+}
+
+/// Authorization middleware, run after authentication
+pub(crate) async fn rbac_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if let Some(identity) = request.extensions().get::<Identity>() {
+        let route = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(|p| p.as_str())
+            .unwrap_or_else(|| request.uri().path());
+        let permission = Permission::required_for(request.method(), route);
+
+        if !state.rbac.allows(identity, permission) {
+            return Err(ApiError::forbidden(format!(
+                "Missing permission '{}'",
+                permission.as_str()
+            )));
+        }
+    }
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::OidcProviderKind;
+
+    fn user(email: &str, username: Option<&str>) -> Identity {
+        Identity {
+            subject: "1".to_string(),
+            provider: OidcProviderKind::GitHub,
+            email: Some(email.to_string()),
+            name: None,
+            username: username.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_required_permission() {
+        assert_eq!(
+            Permission::required_for(&Method::GET, "/api/agents"),
+            Permission::Read
+        );
+        assert_eq!(
+            Permission::required_for(&Method::POST, "/api/agents"),
+            Permission::SpawnAgents
+        );
+        assert_eq!(
+            Permission::required_for(&Method::POST, "/api/agents/:id/pause"),
+            Permission::SpawnAgents
+        );
+        assert_eq!(
+            Permission::required_for(&Method::POST, "/api/approvals/:id/approve"),
+            Permission::DecideApprovals
+        );
+        assert_eq!(
+            Permission::required_for(&Method::DELETE, "/api/schedules/:id"),
+            Permission::Write
+        );
+        assert_eq!(
+            Permission::required_for(&Method::POST, "/api/graphql"),
+            Permission::Read
+        );
+
+        // Routes that merely share a prefix don't share a permission
+        assert_eq!(
+            Permission::required_for(&Method::POST, "/api/agents-config"),
+            Permission::Write
+        );
+        assert_eq!(
+            Permission::required_for(&Method::POST, "/api/approvals/rules"),
+            Permission::Write
+        );
+    }
+
+    #[tokio::test]
+    async fn test_route_permissions_cover_api_routes() {
+        let db = orchestrate_core::Database::in_memory().await.unwrap();
+        let router = crate::api::create_api_router(Arc::new(AppState::new(db, None)));
+        let routes: Vec<(String, String)> = crate::test_support::registered_routes(router)
+            .await
+            .into_iter()
+            .filter(|(method, _)| method != "GET")
+            .collect();
+        assert!(routes.len() > 50);
+
+        for (method, path) in &routes {
+            assert!(
+                ROUTE_PERMISSIONS
+                    .iter()
+                    .any(|(m, r, _)| m == method && r == path),
+                "{} {} has no entry in ROUTE_PERMISSIONS",
+                method,
+                path
+            );
+        }
+        for (method, path, _) in ROUTE_PERMISSIONS {
+            // The GraphQL endpoint is only routed with the `graphql` feature
+            if *path == "/api/graphql" && !cfg!(feature = "graphql") {
+                continue;
+            }
+            assert!(
+                routes.iter().any(|(m, r)| m == method && r == path),
+                "{} {} in ROUTE_PERMISSIONS isn't a registered route",
+                method,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_roles_by_email_domain_and_provider_username() {
+        let policy = RbacPolicy::default()
+            .assign("Lead@Example.com", Role::Approver)
+            .assign("@example.com", Role::Operator)
+            .assign("github:octocat", Role::Admin);
+
+        let lead = user("lead@example.com", None);
+        assert_eq!(policy.roles(&lead), vec![Role::Approver, Role::Operator]);
+        assert!(policy.allows(&lead, Permission::DecideApprovals));
+        assert!(policy.allows(&lead, Permission::SpawnAgents));
+
+        let octocat = user("cat@github.com", Some("octocat"));
+        assert_eq!(policy.roles(&octocat), vec![Role::Admin]);
+
+        // The same username at another provider is someone else
+        let mut impostor = user("cat@okta-tenant.com", Some("octocat"));
+        impostor.provider = OidcProviderKind::Okta;
+        assert_eq!(policy.roles(&impostor), vec![Role::Viewer]);
+        assert_eq!(
+            RbacPolicy::default()
+                .assign("octocat", Role::Admin)
+                .roles(&octocat),
+            vec![Role::Viewer]
+        );
+
+        let stranger = user("someone@else.com", None);
+        assert_eq!(policy.roles(&stranger), vec![Role::Viewer]);
+        assert!(!policy.allows(&stranger, Permission::Write));
+
+        let locked = policy.with_default_roles(Vec::new());
+        assert!(!locked.allows(&stranger, Permission::Read));
+    }
+}
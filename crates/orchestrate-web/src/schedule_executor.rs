@@ -10,6 +10,20 @@
 //! - Records execution history in the schedule_runs table
 //! - Updates schedule metadata (last_run, next_run)
 //! - Prevents concurrent execution of the same schedule using database locks
+//! - Skips runs that fall inside a blackout window (holidays, change freezes)
+//! - Pauses schedules whose runs reached their token or cost cap for the period
+//! - Applies each schedule's overlap policy while its previous run is in progress
+//! - Skips schedules whose dependency's latest run has not succeeded
+//! - Starts pipeline runs for pipelines whose cron triggers are due
+//!
+//! ## Pipeline Cron Triggers
+//!
+//! Pipelines declare cron triggers in their definition (`event: schedule`),
+//! so they have no row in the schedules table. A trigger is due when its next
+//! occurrence after the latest run it started (or after the pipeline was
+//! created) has passed; missed occurrences start a single run. When a
+//! `repository` is configured, runs build the commit at its HEAD, so their
+//! stages can reuse cached results.
 //!
 //! ## Concurrency
 //!
@@ -17,10 +31,40 @@
 //! executed concurrently by multiple executor instances. Locks expire after 5 minutes
 //! to prevent deadlocks in case of crashes.
 //!
+//! A schedule's previous run is in progress until the agent it spawned reaches a
+//! terminal state. Its overlap policy then decides whether a due run is skipped,
+//! held until the previous run finishes (`queue`), or started anyway
+//! (`run_parallel`, the default). Jitter is applied when `next_run` is
+//! calculated, so schedules sharing a cron expression come due at different
+//! times.
+//!
+//! One-shot schedules (created with a `run_at` time) run once and then have no
+//! next run; a missed one-shot run counts as a single missed run.
+//!
+//! Blackout windows are checked first: a due run inside one (global or attached
+//! to the schedule) is recorded as skipped with the window as its reason.
+//!
+//! A schedule with a spend cap (`max_tokens`, `max_cost_usd`) is checked next
+//! against the tokens its runs used in the current period (day, week, or month
+//! in the schedule's timezone). Once a cap is reached the schedule is disabled,
+//! the occurrence is recorded as skipped, and an alert is raised: an error is
+//! logged and a system `config.changed` entry is written to the audit log. The
+//! schedule stays paused until it is re-enabled.
+//!
+//! Occurrences that pass without a run (the executor was down, or the missed
+//! policy skipped them) are counted on the schedule. Starting a run ends the
+//! streak; a schedule with `max_consecutive_misses` raises an alert the same
+//! way once a streak reaches it.
+//!
+//! A schedule with `depends_on` runs only when the named schedule's latest run
+//! spawned an agent that completed successfully. Otherwise the occurrence is
+//! recorded as a skipped run whose error message gives the reason.
+//!
 //! ## Configuration
 //!
 //! The executor can be configured with:
 //! - `poll_interval_secs`: How often to check for due schedules (default: 60s)
+//! - `repository`: Checkout whose HEAD commit scheduled pipeline runs build
 //!
 //! ## Example
 //!
@@ -39,7 +83,13 @@
 //! # }
 //! ```
 
-use orchestrate_core::{Agent, AgentType, Database, Schedule, ScheduleRun};
+use orchestrate_core::{
+    ActorType, Agent, AgentState, AgentType, AuditAction, AuditEntry, CronSchedule, Database,
+    PipelineDefinition, PipelineRun, Schedule, ScheduleOverlapPolicy, ScheduleRun,
+    ScheduleRunStatus, ScheduleSkipReason,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::time::{sleep, Duration};
 use tracing::{debug, error, info, warn};
@@ -70,6 +120,8 @@ pub struct ScheduleExecutorConfig {
     pub missed_policy: MissedSchedulePolicy,
     /// Maximum number of catch-up runs
     pub catch_up_limit: usize,
+    /// Checkout whose HEAD commit scheduled pipeline runs build
+    pub repository: Option<PathBuf>,
 }
 
 impl Default for ScheduleExecutorConfig {
@@ -78,6 +130,7 @@ impl Default for ScheduleExecutorConfig {
             poll_interval_secs: 60,
             missed_policy: MissedSchedulePolicy::RunImmediately,
             catch_up_limit: 3,
+            repository: None,
         }
     }
 }
@@ -112,6 +165,10 @@ impl ScheduleExecutor {
 
     /// Check for due schedules and execute them
     pub async fn check_and_execute(&self) -> orchestrate_core::Result<()> {
+        if let Err(e) = self.trigger_scheduled_pipelines().await {
+            error!(error = %e, "Error starting scheduled pipeline runs");
+        }
+
         let due_schedules = self.database.get_due_schedules().await?;
 
         if due_schedules.is_empty() {
@@ -149,8 +206,81 @@ impl ScheduleExecutor {
             return Ok(());
         }
 
-        // Check if this is a missed schedule
         let now = chrono::Utc::now();
+
+        if let Some(blackout) = self
+            .database
+            .get_active_schedule_blackout(schedule_id, now)
+            .await?
+        {
+            info!(
+                schedule_id = schedule_id,
+                schedule_name = %schedule_name,
+                blackout = %blackout.name,
+                "Schedule is in a blackout window, skipping"
+            );
+
+            self.skip_run(&mut schedule, blackout.skip_reason(), now)
+                .await?;
+            return Ok(());
+        }
+
+        if schedule.has_spend_cap() {
+            let spend = self
+                .database
+                .get_schedule_spend(schedule_id, schedule.spend_period_start(&now))
+                .await?;
+            if let Some(reason) = schedule.spend_cap_exceeded(&spend) {
+                self.pause_schedule(&mut schedule, reason, now).await?;
+                return Ok(());
+            }
+        }
+
+        if schedule.overlap_policy != ScheduleOverlapPolicy::RunParallel
+            && self.previous_run_in_progress(&schedule).await?
+        {
+            if schedule.overlap_policy == ScheduleOverlapPolicy::Skip {
+                info!(
+                    schedule_id = schedule_id,
+                    schedule_name = %schedule_name,
+                    "Previous run still in progress, skipping schedule per overlap policy"
+                );
+
+                // Just update next_run without executing
+                schedule.last_run = Some(now);
+                schedule.update_next_run()?;
+                self.database.update_schedule(&schedule).await?;
+            } else {
+                // Left due, so the run starts on a poll after the previous one finishes
+                debug!(
+                    schedule_id = schedule_id,
+                    schedule_name = %schedule_name,
+                    "Previous run still in progress, queueing schedule per overlap policy"
+                );
+            }
+
+            self.database.unlock_schedule(schedule_id).await?;
+            return Ok(());
+        }
+
+        if let Some(reason) = self.unmet_dependency(&schedule).await? {
+            info!(
+                schedule_id = schedule_id,
+                schedule_name = %schedule_name,
+                reason = %reason,
+                "Schedule dependency not met, skipping"
+            );
+
+            self.skip_run(
+                &mut schedule,
+                ScheduleSkipReason::UnmetDependency(reason),
+                now,
+            )
+            .await?;
+            return Ok(());
+        }
+
+        // Check if this is a missed schedule
         let next_run = schedule.next_run.unwrap_or(now);
         let is_missed = next_run < now;
 
@@ -164,6 +294,9 @@ impl ScheduleExecutor {
                 "Schedule missed its run time"
             );
 
+            // Occurrences after next_run that have also passed
+            let missed_count = self.calculate_missed_runs(&schedule, now).await?;
+
             // Handle based on policy
             match self.config.missed_policy {
                 MissedSchedulePolicy::Skip => {
@@ -172,6 +305,8 @@ impl ScheduleExecutor {
                         schedule_name = %schedule_name,
                         "Skipping missed schedule per policy"
                     );
+                    self.record_missed_runs(&mut schedule, missed_count + 1)
+                        .await?;
 
                     // Just update next_run without executing
                     schedule.last_run = Some(now);
@@ -187,6 +322,7 @@ impl ScheduleExecutor {
                         schedule_name = %schedule_name,
                         "Running missed schedule immediately"
                     );
+                    self.record_missed_runs(&mut schedule, missed_count).await?;
 
                     // Run once and update
                     self.execute_schedule_once(&mut schedule).await?;
@@ -198,9 +334,9 @@ impl ScheduleExecutor {
                         "Catching up missed schedule runs"
                     );
 
-                    // Calculate how many runs were missed
-                    let missed_count = self.calculate_missed_runs(&schedule, now).await?;
                     let runs_to_execute = missed_count.min(self.config.catch_up_limit);
+                    self.record_missed_runs(&mut schedule, missed_count - runs_to_execute)
+                        .await?;
 
                     info!(
                         schedule_id = schedule_id,
@@ -245,6 +381,97 @@ impl ScheduleExecutor {
         Ok(())
     }
 
+    /// Record a skipped run, move the schedule to its next run, and release its lock
+    async fn skip_run(
+        &self,
+        schedule: &mut Schedule,
+        reason: ScheduleSkipReason,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> orchestrate_core::Result<()> {
+        let mut run = ScheduleRun::new(schedule.id);
+        run.mark_skipped(reason);
+        self.database.insert_schedule_run(&run).await?;
+
+        schedule.last_run = Some(now);
+        schedule.update_next_run()?;
+        self.database.update_schedule(schedule).await?;
+        self.database.unlock_schedule(schedule.id).await
+    }
+
+    /// Disable a schedule, record the skipped run, and raise an alert
+    async fn pause_schedule(
+        &self,
+        schedule: &mut Schedule,
+        reason: ScheduleSkipReason,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> orchestrate_core::Result<()> {
+        self.raise_alert(
+            schedule,
+            AuditAction::ConfigurationChanged,
+            "schedule_paused",
+            &reason.to_string(),
+        )
+        .await?;
+
+        schedule.enabled = false;
+        self.skip_run(schedule, reason, now).await
+    }
+
+    /// Count occurrences that passed without a run, alerting once the
+    /// schedule misses `max_consecutive_misses` runs in a row
+    async fn record_missed_runs(
+        &self,
+        schedule: &mut Schedule,
+        missed: usize,
+    ) -> orchestrate_core::Result<()> {
+        if missed == 0 {
+            return Ok(());
+        }
+        if schedule.record_missed_runs(missed as u32) {
+            let message = format!(
+                "Schedule '{}' missed {} consecutive runs",
+                schedule.name, schedule.consecutive_missed_runs
+            );
+            self.raise_alert(
+                schedule,
+                AuditAction::Custom("schedule.missed_runs".to_string()),
+                "missed_runs",
+                &message,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Log an alert about a schedule and record it in the audit log
+    async fn raise_alert(
+        &self,
+        schedule: &Schedule,
+        action: AuditAction,
+        alert: &str,
+        message: &str,
+    ) -> orchestrate_core::Result<()> {
+        error!(
+            schedule_id = schedule.id,
+            schedule_name = %schedule.name,
+            alert = alert,
+            "ALERT: {}",
+            message
+        );
+
+        let mut entry = AuditEntry::new(
+            "schedule-executor",
+            action,
+            "schedule",
+            schedule.name.clone(),
+        )
+        .with_detail("alert", serde_json::json!(alert))
+        .with_detail("reason", serde_json::json!(message));
+        entry.actor_type = ActorType::System;
+        self.database.insert_audit_entry(&entry).await?;
+        Ok(())
+    }
+
     /// Execute a schedule once
     async fn execute_schedule_once(&self, schedule: &mut Schedule) -> orchestrate_core::Result<()> {
         let schedule_id = schedule.id;
@@ -280,8 +507,10 @@ impl ScheduleExecutor {
             }
         }
 
-        // Update schedule: set last_run and calculate next_run
+        // Update schedule: set last_run and calculate next_run, ending any
+        // streak of missed runs
         schedule.last_run = Some(chrono::Utc::now());
+        schedule.consecutive_missed_runs = 0;
         schedule.update_next_run()?;
 
         self.database.update_schedule(schedule).await?;
@@ -289,20 +518,120 @@ impl ScheduleExecutor {
         Ok(())
     }
 
+    /// Start a run of every enabled pipeline whose cron trigger is due
+    ///
+    /// The run records the trigger as its trigger event, which is also how
+    /// the next occurrence is found on later polls. A run rejected by the
+    /// pipeline's concurrency limit is still recorded, so the occurrence is
+    /// skipped rather than retried.
+    async fn trigger_scheduled_pipelines(&self) -> orchestrate_core::Result<()> {
+        let now = chrono::Utc::now();
+
+        for pipeline in self.database.list_enabled_pipelines().await? {
+            let Some(pipeline_id) = pipeline.id else {
+                continue;
+            };
+            let definition = match PipelineDefinition::from_yaml_str(&pipeline.definition) {
+                Ok(definition) => definition,
+                Err(e) => {
+                    warn!(
+                        pipeline = %pipeline.name,
+                        error = %e,
+                        "Skipping pipeline with invalid definition"
+                    );
+                    continue;
+                }
+            };
+
+            for trigger in &definition.triggers {
+                let (Some(cron), Some(trigger_event)) = (&trigger.cron, trigger.scheduled_event())
+                else {
+                    continue;
+                };
+
+                let since = self
+                    .database
+                    .get_latest_pipeline_run_by_trigger(pipeline_id, &trigger_event)
+                    .await?
+                    .map(|run| run.created_at)
+                    .unwrap_or(pipeline.created_at);
+                let due_at = CronSchedule::new(cron)?.next_after(&since)?;
+                if due_at > now {
+                    continue;
+                }
+
+                let mut run = PipelineRun::new(pipeline_id, Some(trigger_event)).with_variables(
+                    HashMap::from([("scheduled_at".to_string(), due_at.to_rfc3339())]),
+                );
+                if let Some(commit_sha) = self.head_commit().await {
+                    run = run.with_commit_sha(commit_sha);
+                }
+                let admission = self
+                    .database
+                    .admit_pipeline_run(&run, definition.concurrency.as_ref())
+                    .await?;
+
+                info!(
+                    pipeline = %pipeline.name,
+                    run_id = admission.run_id(),
+                    admission = ?admission,
+                    cron = %cron,
+                    scheduled_at = %due_at,
+                    "Started scheduled pipeline run"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Commit at the HEAD of the configured repository
+    async fn head_commit(&self) -> Option<String> {
+        let repository = self.config.repository.as_ref()?;
+        let output = tokio::process::Command::new("git")
+            .arg("-C")
+            .arg(repository)
+            .args(["rev-parse", "HEAD"])
+            .output()
+            .await;
+        match output {
+            Ok(output) if output.status.success() => {
+                Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+            }
+            Ok(output) => {
+                warn!(
+                    repository = %repository.display(),
+                    error = %String::from_utf8_lossy(&output.stderr).trim(),
+                    "Failed to resolve commit for scheduled pipeline run"
+                );
+                None
+            }
+            Err(e) => {
+                warn!(
+                    repository = %repository.display(),
+                    error = %e,
+                    "Failed to resolve commit for scheduled pipeline run"
+                );
+                None
+            }
+        }
+    }
+
     /// Calculate how many runs were missed
     async fn calculate_missed_runs(
         &self,
         schedule: &Schedule,
         now: chrono::DateTime<chrono::Utc>,
     ) -> orchestrate_core::Result<usize> {
-        use orchestrate_core::CronSchedule;
-
         let next_run = schedule.next_run.unwrap_or(now);
         if next_run >= now {
             return Ok(0);
         }
+        if schedule.is_one_shot() {
+            // Its single run is the only one that can have been missed
+            return Ok(1);
+        }
 
-        let cron = CronSchedule::new(&schedule.cron_expression)?;
         let mut count = 0;
         let mut current = next_run;
 
@@ -310,7 +639,7 @@ impl ScheduleExecutor {
         // We iterate from the last known next_run and count all occurrences
         // that should have happened but are now in the past
         loop {
-            let next_occurrence = cron.next_after(&current)?;
+            let next_occurrence = schedule.next_occurrence_after(&current)?;
 
             if next_occurrence >= now {
                 // We've caught up to the present
@@ -330,6 +659,107 @@ impl ScheduleExecutor {
         Ok(count)
     }
 
+    /// Whether the agent spawned by the schedule's latest run is still active
+    async fn previous_run_in_progress(
+        &self,
+        schedule: &Schedule,
+    ) -> orchestrate_core::Result<bool> {
+        // Skipped runs never spawn an agent, so look past them
+        let Some(run) = self
+            .database
+            .get_schedule_runs(schedule.id, 20)
+            .await?
+            .into_iter()
+            .find(|run| run.status != ScheduleRunStatus::Skipped)
+        else {
+            return Ok(false);
+        };
+        let Some(agent_id) = run.agent_id.and_then(|id| uuid::Uuid::parse_str(&id).ok()) else {
+            return Ok(false);
+        };
+
+        Ok(self
+            .database
+            .get_agent(agent_id)
+            .await?
+            .is_some_and(|agent| !agent.state.is_terminal()))
+    }
+
+    /// Why the schedule's dependency is not met, or `None` if it may run
+    ///
+    /// The dependency is met when its latest run spawned an agent that
+    /// completed successfully.
+    async fn unmet_dependency(
+        &self,
+        schedule: &Schedule,
+    ) -> orchestrate_core::Result<Option<String>> {
+        let Some(depends_on) = &schedule.depends_on else {
+            return Ok(None);
+        };
+        let Some(dependency) = self.database.get_schedule_by_name(depends_on).await? else {
+            return Ok(Some(format!(
+                "Dependency schedule '{}' not found",
+                depends_on
+            )));
+        };
+        let Some(run) = self
+            .database
+            .get_schedule_runs(dependency.id, 1)
+            .await?
+            .pop()
+        else {
+            return Ok(Some(format!("Dependency '{}' has not run yet", depends_on)));
+        };
+
+        match run.status {
+            ScheduleRunStatus::Running => {
+                return Ok(Some(format!(
+                    "Dependency '{}' latest run is still starting",
+                    depends_on
+                )));
+            }
+            ScheduleRunStatus::Failed => {
+                return Ok(Some(format!(
+                    "Dependency '{}' latest run failed: {}",
+                    depends_on,
+                    run.error_message.as_deref().unwrap_or("unknown error")
+                )));
+            }
+            ScheduleRunStatus::Skipped => {
+                return Ok(Some(format!(
+                    "Dependency '{}' latest run was skipped",
+                    depends_on
+                )));
+            }
+            ScheduleRunStatus::Completed => {}
+        }
+
+        let Some(agent_id) = run.agent_id else {
+            return Ok(None);
+        };
+        let agent = match uuid::Uuid::parse_str(&agent_id) {
+            Ok(id) => self.database.get_agent(id).await?,
+            Err(_) => None,
+        };
+        Ok(match agent {
+            Some(agent) if agent.state == AgentState::Completed => None,
+            Some(agent) if agent.state.is_terminal() => Some(format!(
+                "Dependency '{}' latest run ended as {}",
+                depends_on,
+                agent.state.as_str()
+            )),
+            Some(agent) => Some(format!(
+                "Dependency '{}' latest run is still {}",
+                depends_on,
+                agent.state.as_str()
+            )),
+            None => Some(format!(
+                "Dependency '{}' latest run agent {} not found",
+                depends_on, agent_id
+            )),
+        })
+    }
+
     /// Spawn an agent for the given schedule
     async fn spawn_agent(&self, schedule: &Schedule) -> orchestrate_core::Result<uuid::Uuid> {
         // Parse agent type from string
@@ -350,7 +780,7 @@ impl ScheduleExecutor {
 mod tests {
     use super::*;
     use chrono::{TimeZone, Utc};
-    use orchestrate_core::ScheduleRunStatus;
+    use orchestrate_core::ScheduleBlackout;
 
     #[tokio::test]
     async fn test_executor_executes_due_schedule() {
@@ -501,6 +931,124 @@ mod tests {
         assert!(runs[0].error_message.is_some());
     }
 
+    #[tokio::test]
+    async fn test_executor_starts_scheduled_pipeline_runs() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let definition = r#"
+name: nightly
+description: Nightly build
+triggers:
+  - event: schedule
+    cron: "@hourly"
+  - event: push
+stages:
+  - name: build
+    agent: builder
+    task: Build
+"#;
+        let mut pipeline =
+            orchestrate_core::Pipeline::new("nightly".to_string(), definition.to_string());
+        pipeline.created_at = Utc::now() - chrono::Duration::hours(3);
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        let executor = ScheduleExecutor::new(database.clone(), ScheduleExecutorConfig::default());
+        executor.check_and_execute().await.unwrap();
+
+        // Missed occurrences start a single run
+        let runs = database.list_pipeline_runs(pipeline_id).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].trigger_event.as_deref(), Some("schedule:@hourly"));
+        assert!(runs[0].variables.contains_key("scheduled_at"));
+
+        // Not due again until the next occurrence
+        executor.check_and_execute().await.unwrap();
+        let runs = database.list_pipeline_runs(pipeline_id).await.unwrap();
+        assert_eq!(runs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_executor_builds_repository_head_in_scheduled_pipeline_runs() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let repository = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .arg("-C")
+                .arg(repository.path())
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        git(&["init", "-q"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "initial"]);
+        let head = git(&["rev-parse", "HEAD"]);
+
+        let definition = r#"
+name: nightly
+description: Nightly build
+triggers:
+  - event: schedule
+    cron: "@hourly"
+stages:
+  - name: build
+    agent: builder
+    task: Build
+"#;
+        let mut pipeline =
+            orchestrate_core::Pipeline::new("nightly".to_string(), definition.to_string());
+        pipeline.created_at = Utc::now() - chrono::Duration::hours(3);
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        let config = ScheduleExecutorConfig {
+            repository: Some(repository.path().to_path_buf()),
+            ..Default::default()
+        };
+        let executor = ScheduleExecutor::new(database.clone(), config);
+        executor.check_and_execute().await.unwrap();
+
+        let runs = database.list_pipeline_runs(pipeline_id).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].commit_sha.as_deref(), Some(head.as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_executor_skips_pipeline_schedule_not_yet_due() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let definition = r#"
+name: weekly
+description: Weekly report
+triggers:
+  - event: schedule
+    cron: "@weekly"
+stages:
+  - name: report
+    agent: reporter
+    task: Report
+"#;
+        let mut pipeline =
+            orchestrate_core::Pipeline::new("weekly".to_string(), definition.to_string());
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        // A disabled pipeline with a due schedule does not run either
+        pipeline.name = "weekly-disabled".to_string();
+        pipeline.enabled = false;
+        pipeline.created_at = Utc::now() - chrono::Duration::days(30);
+        let disabled_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        let executor = ScheduleExecutor::new(database.clone(), ScheduleExecutorConfig::default());
+        executor.check_and_execute().await.unwrap();
+
+        for id in [pipeline_id, disabled_id] {
+            let runs = database.list_pipeline_runs(id).await.unwrap();
+            assert!(runs.is_empty());
+        }
+    }
+
     #[tokio::test]
     async fn test_executor_handles_empty_schedules() {
         let database = Arc::new(Database::in_memory().await.unwrap());
@@ -714,4 +1262,375 @@ mod tests {
         let agents = database.list_agents().await.unwrap();
         assert_eq!(agents.len(), 1);
     }
+
+    async fn overlapping_schedule(
+        database: &Database,
+        policy: ScheduleOverlapPolicy,
+    ) -> (ScheduleExecutor, i64) {
+        let mut schedule = Schedule::new(
+            "overlap-schedule".to_string(),
+            "@hourly".to_string(),
+            "background_controller".to_string(),
+            "Long running task".to_string(),
+        )
+        .with_overlap_policy(policy);
+        schedule.next_run = Some(Utc::now() - chrono::Duration::minutes(1));
+        let schedule_id = database.insert_schedule(&schedule).await.unwrap();
+
+        let executor = ScheduleExecutor::new(
+            Arc::new(database.clone()),
+            ScheduleExecutorConfig::default(),
+        );
+        executor.check_and_execute().await.unwrap();
+
+        // Make the schedule due again while its first agent is still active
+        let mut schedule = database.get_schedule(schedule_id).await.unwrap().unwrap();
+        schedule.next_run = Some(Utc::now() - chrono::Duration::minutes(1));
+        database.update_schedule(&schedule).await.unwrap();
+
+        (executor, schedule_id)
+    }
+
+    #[tokio::test]
+    async fn test_overlap_skip_policy() {
+        let database = Database::in_memory().await.unwrap();
+        let (executor, schedule_id) =
+            overlapping_schedule(&database, ScheduleOverlapPolicy::Skip).await;
+
+        executor.check_and_execute().await.unwrap();
+
+        // The overlapping run is skipped and the schedule moves on
+        assert_eq!(database.list_agents().await.unwrap().len(), 1);
+        let updated_schedule = database.get_schedule(schedule_id).await.unwrap().unwrap();
+        assert!(updated_schedule.next_run.unwrap() > Utc::now());
+    }
+
+    #[tokio::test]
+    async fn test_overlap_queue_policy() {
+        let database = Database::in_memory().await.unwrap();
+        let (executor, schedule_id) =
+            overlapping_schedule(&database, ScheduleOverlapPolicy::Queue).await;
+
+        executor.check_and_execute().await.unwrap();
+
+        // The run waits, leaving the schedule due
+        assert_eq!(database.list_agents().await.unwrap().len(), 1);
+        let updated_schedule = database.get_schedule(schedule_id).await.unwrap().unwrap();
+        assert!(updated_schedule.next_run.unwrap() <= Utc::now());
+
+        // Once the previous agent finishes, the queued run starts
+        let mut agent = database.list_agents().await.unwrap().remove(0);
+        agent.state = AgentState::Completed;
+        database.update_agent(&agent).await.unwrap();
+
+        executor.check_and_execute().await.unwrap();
+        assert_eq!(database.list_agents().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_overlap_run_parallel_policy() {
+        let database = Database::in_memory().await.unwrap();
+        let (executor, _) =
+            overlapping_schedule(&database, ScheduleOverlapPolicy::RunParallel).await;
+
+        executor.check_and_execute().await.unwrap();
+
+        assert_eq!(database.list_agents().await.unwrap().len(), 2);
+    }
+
+    /// Insert a due "dependency-check" schedule that depends on "security-scan",
+    /// whose latest run spawned an agent in `dependency_state` (if any)
+    async fn dependent_schedule(database: &Database, dependency_state: Option<AgentState>) -> i64 {
+        let dependency = Schedule::new(
+            "security-scan".to_string(),
+            "@daily".to_string(),
+            "background_controller".to_string(),
+            "Scan".to_string(),
+        );
+        let dependency_id = database.insert_schedule(&dependency).await.unwrap();
+
+        if let Some(state) = dependency_state {
+            let mut agent = Agent::new(AgentType::BackgroundController, "Scan");
+            agent.state = state;
+            database.insert_agent(&agent).await.unwrap();
+
+            let mut run = ScheduleRun::new(dependency_id);
+            run.mark_completed(agent.id.to_string());
+            database.insert_schedule_run(&run).await.unwrap();
+        }
+
+        let mut schedule = Schedule::new(
+            "dependency-check".to_string(),
+            "@hourly".to_string(),
+            "background_controller".to_string(),
+            "Check dependencies".to_string(),
+        )
+        .with_depends_on("security-scan");
+        schedule.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+        database.insert_schedule(&schedule).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dependency_not_run_skips_schedule() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let schedule_id = dependent_schedule(&database, None).await;
+
+        let executor = ScheduleExecutor::new(database.clone(), ScheduleExecutorConfig::default());
+        executor.check_and_execute().await.unwrap();
+
+        assert!(database.list_agents().await.unwrap().is_empty());
+        let runs = database.get_schedule_runs(schedule_id, 10).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, ScheduleRunStatus::Skipped);
+        assert_eq!(
+            runs[0].error_message.as_deref(),
+            Some("Dependency 'security-scan' has not run yet")
+        );
+
+        let updated_schedule = database.get_schedule(schedule_id).await.unwrap().unwrap();
+        assert!(updated_schedule.next_run.unwrap() > Utc::now());
+    }
+
+    #[tokio::test]
+    async fn test_dependency_failed_skips_schedule() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let schedule_id = dependent_schedule(&database, Some(AgentState::Failed)).await;
+
+        let executor = ScheduleExecutor::new(database.clone(), ScheduleExecutorConfig::default());
+        executor.check_and_execute().await.unwrap();
+
+        let runs = database.get_schedule_runs(schedule_id, 10).await.unwrap();
+        assert_eq!(runs[0].status, ScheduleRunStatus::Skipped);
+        assert_eq!(
+            runs[0].error_message.as_deref(),
+            Some("Dependency 'security-scan' latest run ended as failed")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dependency_succeeded_runs_schedule() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let schedule_id = dependent_schedule(&database, Some(AgentState::Completed)).await;
+
+        let executor = ScheduleExecutor::new(database.clone(), ScheduleExecutorConfig::default());
+        executor.check_and_execute().await.unwrap();
+
+        let runs = database.get_schedule_runs(schedule_id, 10).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, ScheduleRunStatus::Completed);
+        assert_eq!(database.list_agents().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_one_shot_schedule_runs_once() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let mut schedule = Schedule::once(
+            "deploy".to_string(),
+            Utc::now() - chrono::Duration::hours(2),
+            "background_controller".to_string(),
+            "Deploy release".to_string(),
+        );
+        schedule.update_next_run().unwrap();
+        let schedule_id = database.insert_schedule(&schedule).await.unwrap();
+
+        let config = ScheduleExecutorConfig {
+            missed_policy: MissedSchedulePolicy::CatchUp,
+            ..Default::default()
+        };
+        let executor = ScheduleExecutor::new(database.clone(), config);
+        executor.check_and_execute().await.unwrap();
+        executor.check_and_execute().await.unwrap();
+
+        assert_eq!(database.list_agents().await.unwrap().len(), 1);
+        let runs = database.get_schedule_runs(schedule_id, 10).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, ScheduleRunStatus::Completed);
+
+        let updated_schedule = database.get_schedule(schedule_id).await.unwrap().unwrap();
+        assert!(updated_schedule.last_run.is_some());
+        assert!(updated_schedule.next_run.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_blackout_window_skips_schedule() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let mut schedule = Schedule::new(
+            "nightly-deploy".to_string(),
+            "@hourly".to_string(),
+            "background_controller".to_string(),
+            "Deploy".to_string(),
+        );
+        schedule.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+        let schedule_id = database.insert_schedule(&schedule).await.unwrap();
+
+        let blackout = ScheduleBlackout::new(
+            "change-freeze".to_string(),
+            Utc::now() - chrono::Duration::hours(1),
+            Utc::now() + chrono::Duration::days(1),
+        )
+        .with_reason("Release freeze");
+        database.insert_schedule_blackout(&blackout).await.unwrap();
+
+        let executor = ScheduleExecutor::new(database.clone(), ScheduleExecutorConfig::default());
+        executor.check_and_execute().await.unwrap();
+
+        assert!(database.list_agents().await.unwrap().is_empty());
+        let runs = database.get_schedule_runs(schedule_id, 10).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, ScheduleRunStatus::Skipped);
+        assert!(runs[0]
+            .error_message
+            .as_deref()
+            .unwrap()
+            .starts_with("Blackout window 'change-freeze' (Release freeze) is active until"));
+
+        let updated_schedule = database.get_schedule(schedule_id).await.unwrap().unwrap();
+        assert!(updated_schedule.next_run.unwrap() > Utc::now());
+    }
+
+    #[tokio::test]
+    async fn test_blackout_window_for_other_schedule_is_ignored() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let other = Schedule::new(
+            "other".to_string(),
+            "@daily".to_string(),
+            "background_controller".to_string(),
+            "Other".to_string(),
+        );
+        let other_id = database.insert_schedule(&other).await.unwrap();
+
+        let mut schedule = Schedule::new(
+            "nightly-deploy".to_string(),
+            "@hourly".to_string(),
+            "background_controller".to_string(),
+            "Deploy".to_string(),
+        );
+        schedule.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+        database.insert_schedule(&schedule).await.unwrap();
+
+        let blackout = ScheduleBlackout::new(
+            "other-freeze".to_string(),
+            Utc::now() - chrono::Duration::hours(1),
+            Utc::now() + chrono::Duration::days(1),
+        )
+        .for_schedule(other_id);
+        database.insert_schedule_blackout(&blackout).await.unwrap();
+
+        let executor = ScheduleExecutor::new(database.clone(), ScheduleExecutorConfig::default());
+        executor.check_and_execute().await.unwrap();
+
+        assert_eq!(database.list_agents().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_spend_cap_pauses_schedule() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let mut schedule = Schedule::new(
+            "nightly-refactor".to_string(),
+            "@hourly".to_string(),
+            "background_controller".to_string(),
+            "Refactor".to_string(),
+        )
+        .with_max_tokens(10_000)
+        .with_spend_cap_period(orchestrate_core::BudgetPeriod::Daily);
+        schedule.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+        let schedule_id = database.insert_schedule(&schedule).await.unwrap();
+
+        let executor = ScheduleExecutor::new(database.clone(), ScheduleExecutorConfig::default());
+        executor.check_and_execute().await.unwrap();
+
+        // The first run stays under the cap
+        let runs = database.get_schedule_runs(schedule_id, 10).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, ScheduleRunStatus::Completed);
+
+        let agent_id = uuid::Uuid::parse_str(runs[0].agent_id.as_deref().unwrap()).unwrap();
+        database
+            .record_session_tokens("session-1", agent_id, 1, 9_000, 3_000, 0, 0, 9_000, 2, 0)
+            .await
+            .unwrap();
+
+        let mut schedule = database.get_schedule(schedule_id).await.unwrap().unwrap();
+        schedule.next_run = Some(Utc::now() - chrono::Duration::seconds(1));
+        database.update_schedule(&schedule).await.unwrap();
+        executor.check_and_execute().await.unwrap();
+
+        assert_eq!(database.list_agents().await.unwrap().len(), 1);
+        let runs = database.get_schedule_runs(schedule_id, 10).await.unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].status, ScheduleRunStatus::Skipped);
+        assert_eq!(
+            runs[0].error_message.as_deref(),
+            Some("Spend cap reached: 12000 tokens of 10000 tokens used today; schedule paused")
+        );
+
+        let updated_schedule = database.get_schedule(schedule_id).await.unwrap().unwrap();
+        assert!(!updated_schedule.enabled);
+    }
+
+    /// An every-minute schedule whose next run was three minutes ago, so
+    /// three later occurrences have also passed
+    async fn late_schedule(database: &Database) -> i64 {
+        use chrono::Timelike;
+
+        let minute = Utc::now()
+            .with_second(0)
+            .unwrap()
+            .with_nanosecond(0)
+            .unwrap();
+        let mut schedule = Schedule::new(
+            "minutely-sync".to_string(),
+            "* * * * *".to_string(),
+            "background_controller".to_string(),
+            "Sync".to_string(),
+        )
+        .with_max_consecutive_misses(4);
+        schedule.next_run = Some(minute - chrono::Duration::minutes(3));
+        database.insert_schedule(&schedule).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_missed_runs_counted_when_run_immediately() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let schedule_id = late_schedule(&database).await;
+
+        let executor = ScheduleExecutor::new(database.clone(), ScheduleExecutorConfig::default());
+        executor.check_and_execute().await.unwrap();
+
+        assert_eq!(database.list_agents().await.unwrap().len(), 1);
+        let updated_schedule = database.get_schedule(schedule_id).await.unwrap().unwrap();
+        assert_eq!(updated_schedule.missed_runs, 3);
+        // The run that started ends the streak
+        assert_eq!(updated_schedule.consecutive_missed_runs, 0);
+    }
+
+    #[tokio::test]
+    async fn test_missed_runs_streak_with_skip_policy() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let schedule_id = late_schedule(&database).await;
+
+        let config = ScheduleExecutorConfig {
+            missed_policy: MissedSchedulePolicy::Skip,
+            ..Default::default()
+        };
+        let executor = ScheduleExecutor::new(database.clone(), config);
+        executor.check_and_execute().await.unwrap();
+
+        assert!(database.list_agents().await.unwrap().is_empty());
+        let updated_schedule = database.get_schedule(schedule_id).await.unwrap().unwrap();
+        assert_eq!(updated_schedule.missed_runs, 4);
+        assert_eq!(updated_schedule.consecutive_missed_runs, 4);
+
+        let stats = database
+            .get_schedule_stats(schedule_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stats.total_runs, 0);
+        assert_eq!(stats.consecutive_missed_runs, 4);
+    }
 }
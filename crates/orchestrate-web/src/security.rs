@@ -0,0 +1,165 @@
+//! CORS and security headers
+//!
+//! Cross-origin requests are refused unless origins are allowed, e.g. when
+//! the web UI is hosted on a different origin than the API:
+//!
+//! ```text
+//! ORCHESTRATE_CORS_ORIGINS=https://ui.example.com,https://admin.example.com
+//! ORCHESTRATE_CORS_METHODS=GET,POST
+//! ```
+//!
+//! `*` allows any origin. Every response also carries standard security
+//! headers, unless a handler set them itself.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderName, HeaderValue, Method, Request},
+    middleware::Next,
+    response::Response,
+};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+/// Methods allowed when none are configured
+const DEFAULT_METHODS: [Method; 5] = [
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+];
+
+/// Origins and methods allowed to call the API from a browser
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorsConfig {
+    /// Allowed origins, empty for any origin
+    pub allowed_origins: Vec<HeaderValue>,
+    pub allowed_methods: Vec<Method>,
+}
+
+impl CorsConfig {
+    /// Allow `origins`, each like `https://ui.example.com`, or any for `*`
+    pub fn new(origins: &[&str]) -> Self {
+        let any = origins.iter().any(|origin| origin.trim() == "*");
+        Self {
+            allowed_origins: if any {
+                Vec::new()
+            } else {
+                origins
+                    .iter()
+                    .filter_map(|origin| {
+                        let origin = origin.trim().trim_end_matches('/');
+                        match HeaderValue::from_str(origin) {
+                            Ok(value) if !origin.is_empty() => Some(value),
+                            _ => {
+                                tracing::warn!("Ignoring invalid CORS origin: {}", origin);
+                                None
+                            }
+                        }
+                    })
+                    .collect()
+            },
+            allowed_methods: DEFAULT_METHODS.to_vec(),
+        }
+    }
+
+    pub fn with_methods(mut self, methods: &[&str]) -> Self {
+        let methods: Vec<Method> = methods
+            .iter()
+            .filter_map(|method| method.trim().to_uppercase().parse().ok())
+            .collect();
+        if !methods.is_empty() {
+            self.allowed_methods = methods;
+        }
+        self
+    }
+
+    /// Configure from `ORCHESTRATE_CORS_ORIGINS` and
+    /// `ORCHESTRATE_CORS_METHODS`, both comma-separated
+    ///
+    /// Returns `None` when no origins are set, refusing cross-origin requests.
+    pub fn from_env() -> Option<Self> {
+        let origins = std::env::var("ORCHESTRATE_CORS_ORIGINS").ok()?;
+        let origins: Vec<&str> = origins
+            .split(',')
+            .filter(|o| !o.trim().is_empty())
+            .collect();
+        if origins.is_empty() {
+            return None;
+        }
+        let config = Self::new(&origins);
+        Some(match std::env::var("ORCHESTRATE_CORS_METHODS") {
+            Ok(methods) => config.with_methods(&methods.split(',').collect::<Vec<_>>()),
+            Err(_) => config,
+        })
+    }
+
+    pub fn allows_any_origin(&self) -> bool {
+        self.allowed_origins.is_empty()
+    }
+
+    /// Layer answering preflight requests and adding CORS headers
+    pub fn layer(&self) -> CorsLayer {
+        let origin = if self.allows_any_origin() {
+            AllowOrigin::any()
+        } else {
+            AllowOrigin::list(self.allowed_origins.clone())
+        };
+        CorsLayer::new()
+            .allow_origin(origin)
+            .allow_methods(self.allowed_methods.clone())
+            .allow_headers([
+                header::AUTHORIZATION,
+                header::CONTENT_TYPE,
+                HeaderName::from_static("x-api-key"),
+            ])
+            .expose_headers([
+                header::RETRY_AFTER,
+                HeaderName::from_static("x-ratelimit-limit"),
+                HeaderName::from_static("x-ratelimit-remaining"),
+            ])
+    }
+}
+
+/// Security headers of every response
+const SECURITY_HEADERS: [(&str, &str); 4] = [
+    ("x-content-type-options", "nosniff"),
+    ("x-frame-options", "DENY"),
+    ("referrer-policy", "strict-origin-when-cross-origin"),
+    // Only framing is restricted; Swagger UI and GraphiQL load CDN scripts
+    ("content-security-policy", "frame-ancestors 'none'"),
+];
+
+/// Add security headers to responses
+pub(crate) async fn security_headers_middleware(request: Request<Body>, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    for (name, value) in SECURITY_HEADERS {
+        headers
+            .entry(HeaderName::from_static(name))
+            .or_insert(HeaderValue::from_static(value));
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cors_config() {
+        let config = CorsConfig::new(&["https://ui.example.com/", " https://admin.example.com"]);
+        assert_eq!(
+            config.allowed_origins,
+            vec![
+                HeaderValue::from_static("https://ui.example.com"),
+                HeaderValue::from_static("https://admin.example.com"),
+            ]
+        );
+        assert_eq!(config.allowed_methods, DEFAULT_METHODS.to_vec());
+
+        let config =
+            CorsConfig::new(&["https://ui.example.com", "*"]).with_methods(&["get", " post"]);
+        assert!(config.allows_any_origin());
+        assert_eq!(config.allowed_methods, vec![Method::GET, Method::POST]);
+    }
+}
@@ -0,0 +1,570 @@
+//! Slack slash command endpoint
+//!
+//! Serves the `/orchestrate` slash command so operators can drive the daemon
+//! from Slack:
+//! - `status`: agent counts by state and pending approvals
+//! - `agents`: active agents with their tasks
+//! - `approve <id> [comment]`: approve a pending approval request
+//! - `spawn <type> <task>`: spawn an agent of a built-in or custom type
+//!
+//! Requests are verified with the Slack app's signing secret: Slack signs
+//! `v0:<timestamp>:<body>` with HMAC-SHA256 and sends the result in
+//! `X-Slack-Signature`. Requests older than five minutes are rejected so a
+//! captured request cannot be replayed.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use orchestrate_core::{
+    Agent, AgentState, AgentType, ApprovalService, Database, SlackBlock, SlackService, SlackText,
+    SlackUserService, SlashCommand, SlashCommandResponse,
+};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use crate::api::{spawn_resume_after_approval, MAX_TASK_LENGTH};
+use crate::webhook::{respond, WebhookState};
+
+/// Maximum age of a signed request before it is treated as a replay
+const MAX_REQUEST_AGE_SECS: i64 = 300;
+
+/// Maximum number of agents listed by `agents`
+const MAX_LISTED_AGENTS: usize = 20;
+
+/// Slash command handler
+///
+/// Verifies the Slack signature, runs the command, and answers with a
+/// [`SlashCommandResponse`]. Command failures are reported back to the
+/// invoking user as ephemeral messages.
+#[utoipa::path(
+    post,
+    path = "/api/slack/commands",
+    tag = "webhooks",
+    summary = "Receive a Slack slash command",
+    request_body(content = String, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Event accepted"),
+        (status = 400, description = "Malformed payload"),
+        (status = 401, description = "Invalid signature")
+    )
+)]
+pub async fn slack_command_handler(
+    State(state): State<Arc<WebhookState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let Some(secret) = state.config.secret.as_ref() else {
+        warn!("Slack command received but SLACK_SIGNING_SECRET is not set");
+        return respond(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "error",
+            "Slack signing secret not configured",
+        )
+        .into_response();
+    };
+
+    if let Err(message) = verify_request(secret, &headers, &body, chrono::Utc::now().timestamp()) {
+        warn!(reason = message, "Rejected Slack command");
+        return respond(StatusCode::UNAUTHORIZED, "error", message).into_response();
+    }
+
+    let command: SlashCommand = match serde_urlencoded::from_bytes(&body) {
+        Ok(command) => command,
+        Err(e) => {
+            warn!(error = %e, "Failed to parse Slack command");
+            return respond(
+                StatusCode::BAD_REQUEST,
+                "error",
+                &format!("Invalid slash command payload: {}", e),
+            )
+            .into_response();
+        }
+    };
+
+    info!(
+        user = %command.user_name,
+        channel = %command.channel_name,
+        text = %command.text,
+        "Slack command received"
+    );
+
+    let response = match run_command(&state.database, &command).await {
+        Ok(response) => response,
+        Err(e) => {
+            error!(error = %e, text = %command.text, "Slack command failed");
+            SlashCommandResponse::ephemeral(format!("Command failed: {}", e))
+        }
+    };
+
+    Json(response).into_response()
+}
+
+/// Check the signing timestamp and `X-Slack-Signature` of a request
+pub(crate) fn verify_request(
+    secret: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+    now: i64,
+) -> Result<(), &'static str> {
+    let timestamp = headers
+        .get("x-slack-request-timestamp")
+        .and_then(|v| v.to_str().ok())
+        .ok_or("Missing request timestamp")?;
+    let signature = headers
+        .get("x-slack-signature")
+        .and_then(|v| v.to_str().ok())
+        .ok_or("Missing signature")?;
+
+    let signed_at: i64 = timestamp.parse().map_err(|_| "Invalid request timestamp")?;
+    if (now - signed_at).abs() > MAX_REQUEST_AGE_SECS {
+        return Err("Stale request");
+    }
+
+    if verify_signature(secret, timestamp, body, signature) {
+        Ok(())
+    } else {
+        Err("Invalid signature")
+    }
+}
+
+/// Verify a `v0=<hex>` signature over `v0:<timestamp>:<body>`
+fn verify_signature(secret: &str, timestamp: &str, body: &[u8], signature: &str) -> bool {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    let Some(expected) = signature
+        .strip_prefix("v0=")
+        .and_then(|sig| hex::decode(sig).ok())
+    else {
+        return false;
+    };
+
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(format!("v0:{}:", timestamp).as_bytes());
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// Run a slash command and build the reply
+async fn run_command(
+    db: &Database,
+    command: &SlashCommand,
+) -> orchestrate_core::Result<SlashCommandResponse> {
+    let args = command.parse_args();
+
+    match args.first().map(String::as_str) {
+        None | Some("help") => Ok(help_response()),
+        Some("status") => status_command(db).await,
+        Some("agents") => agents_command(db).await,
+        Some("approve") => approve_command(db, command, &args[1..]).await,
+        Some("spawn") => spawn_command(db, command, &args[1..]).await,
+        Some(other) => Ok(SlashCommandResponse::ephemeral(format!(
+            "Unknown command: {}. Type `/orchestrate help` for available commands.",
+            other
+        ))),
+    }
+}
+
+/// `status`: agent counts by state and pending approvals
+async fn status_command(db: &Database) -> orchestrate_core::Result<SlashCommandResponse> {
+    let agents = db.list_agents().await?;
+    let pending_approvals = db.list_pending_approvals().await?.len();
+    let count = |states: &[AgentState]| {
+        agents
+            .iter()
+            .filter(|agent| states.contains(&agent.state))
+            .count()
+    };
+
+    let fields = vec![
+        SlackText::mrkdwn(format!(
+            "*Running:* {}",
+            count(&[AgentState::Initializing, AgentState::Running])
+        )),
+        SlackText::mrkdwn(format!(
+            "*Waiting:* {}",
+            count(&[AgentState::WaitingForInput, AgentState::WaitingForExternal])
+        )),
+        SlackText::mrkdwn(format!("*Queued:* {}", count(&[AgentState::Created]))),
+        SlackText::mrkdwn(format!("*Paused:* {}", count(&[AgentState::Paused]))),
+        SlackText::mrkdwn(format!("*Completed:* {}", count(&[AgentState::Completed]))),
+        SlackText::mrkdwn(format!(
+            "*Failed:* {}",
+            count(&[AgentState::Failed, AgentState::Terminated])
+        )),
+        SlackText::mrkdwn(format!("*Pending approvals:* {}", pending_approvals)),
+    ];
+
+    Ok(
+        SlashCommandResponse::ephemeral(format!("{} agents in total", agents.len())).with_blocks(
+            vec![SlackBlock::Section {
+                text: SlackText::mrkdwn(format!(
+                    "*Orchestrate status*: {} agents in total",
+                    agents.len()
+                )),
+                accessory: None,
+                fields: Some(fields),
+            }],
+        ),
+    )
+}
+
+/// `agents`: active agents, most recently created first
+async fn agents_command(db: &Database) -> orchestrate_core::Result<SlashCommandResponse> {
+    let mut agents: Vec<Agent> = db
+        .list_agents()
+        .await?
+        .into_iter()
+        .filter(|agent| !agent.state.is_terminal())
+        .collect();
+    if agents.is_empty() {
+        return Ok(SlashCommandResponse::ephemeral("No active agents"));
+    }
+    agents.sort_by_key(|agent| std::cmp::Reverse(agent.created_at));
+
+    let mut lines: Vec<String> = agents
+        .iter()
+        .take(MAX_LISTED_AGENTS)
+        .map(|agent| {
+            format!(
+                "• `{}` {} ({}): {}",
+                &agent.id.to_string()[..8],
+                agent
+                    .custom_type
+                    .as_deref()
+                    .unwrap_or(agent.agent_type.as_str()),
+                agent.state.as_str(),
+                truncate(&agent.task, 80)
+            )
+        })
+        .collect();
+    if agents.len() > MAX_LISTED_AGENTS {
+        lines.push(format!("…and {} more", agents.len() - MAX_LISTED_AGENTS));
+    }
+
+    Ok(
+        SlashCommandResponse::ephemeral(format!("{} active agents", agents.len())).with_blocks(
+            vec![SlackBlock::Section {
+                text: SlackText::mrkdwn(lines.join("\n")),
+                accessory: None,
+                fields: None,
+            }],
+        ),
+    )
+}
+
+/// `approve <id> [comment]`: approve as the invoking user
+///
+/// The approver is the GitHub username mapped to the Slack user (see
+/// `orchestrate slack map-user`), falling back to the Slack username.
+async fn approve_command(
+    db: &Database,
+    command: &SlashCommand,
+    args: &[String],
+) -> orchestrate_core::Result<SlashCommandResponse> {
+    let Some(approval_id) = args.first().and_then(|id| id.parse::<i64>().ok()) else {
+        return Ok(SlashCommandResponse::ephemeral(
+            "Usage: /orchestrate approve <approval-id> [comment]",
+        ));
+    };
+    let comment = (args.len() > 1).then(|| args[1..].join(" "));
+
+    let user_service = SlackUserService::new(db.clone(), SlackService::new(db.clone()));
+    let approver = user_service
+        .get_github_username(&command.user_id)
+        .await?
+        .unwrap_or_else(|| command.user_name.clone());
+
+    let approval = match ApprovalService::new(db.clone())
+        .approve(approval_id, approver.clone(), comment)
+        .await
+    {
+        Ok(approval) => approval,
+        // Not found, not an authorized approver, already decided
+        Err(orchestrate_core::Error::Other(message)) => {
+            return Ok(SlashCommandResponse::ephemeral(format!(
+                "Cannot approve {}: {}",
+                approval_id, message
+            )))
+        }
+        Err(e) => return Err(e),
+    };
+    spawn_resume_after_approval(db, &approval);
+
+    Ok(SlashCommandResponse::in_channel(format!(
+        "<@{}> approved approval {} as {} ({}/{} approvals, {})",
+        command.user_id,
+        approval_id,
+        approver,
+        approval.approval_count,
+        approval.required_count,
+        approval.status.as_str()
+    )))
+}
+
+/// `spawn <type> <task>`: spawn an agent
+///
+/// The type is a built-in agent type (`story-developer` or
+/// `story_developer`) or the name of a custom agent type.
+async fn spawn_command(
+    db: &Database,
+    command: &SlashCommand,
+    args: &[String],
+) -> orchestrate_core::Result<SlashCommandResponse> {
+    if args.len() < 2 {
+        return Ok(SlashCommandResponse::ephemeral(
+            "Usage: /orchestrate spawn <agent-type> <task>",
+        ));
+    }
+    let task = args[1..].join(" ");
+    if task.len() > MAX_TASK_LENGTH {
+        return Ok(SlashCommandResponse::ephemeral(format!(
+            "Task exceeds maximum length of {} characters",
+            MAX_TASK_LENGTH
+        )));
+    }
+
+    let agent = match AgentType::from_str(&args[0].to_lowercase().replace('-', "_")) {
+        Ok(agent_type) => Agent::new(agent_type, task),
+        Err(_) => match db.get_agent_type_definition(&args[0]).await? {
+            Some(def) => Agent::new(def.base_type, task).with_custom_type(def.name),
+            None => {
+                return Ok(SlashCommandResponse::ephemeral(format!(
+                    "Unknown agent type: {}",
+                    args[0]
+                )))
+            }
+        },
+    };
+    db.insert_agent(&agent).await?;
+
+    info!(agent_id = %agent.id, user = %command.user_name, "Agent spawned from Slack");
+
+    Ok(SlashCommandResponse::in_channel(format!(
+        "<@{}> spawned {} agent `{}`: {}",
+        command.user_id,
+        agent
+            .custom_type
+            .as_deref()
+            .unwrap_or(agent.agent_type.as_str()),
+        agent.id,
+        truncate(&agent.task, 200)
+    )))
+}
+
+fn help_response() -> SlashCommandResponse {
+    SlashCommandResponse::ephemeral("Orchestrate commands").with_blocks(vec![SlackBlock::Section {
+        text: SlackText::mrkdwn(
+            "*Available commands*\n\
+                 • `/orchestrate status` - Show agent and approval counts\n\
+                 • `/orchestrate agents` - List active agents\n\
+                 • `/orchestrate approve <id> [comment]` - Approve a pending request\n\
+                 • `/orchestrate spawn <type> <task>` - Spawn an agent\n\
+                 • `/orchestrate help` - Show this help",
+        ),
+        accessory: None,
+        fields: None,
+    }])
+}
+
+/// Shorten text to at most `max` characters
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhook::WebhookConfig;
+    use axum::{
+        body::Body,
+        http::{Method, Request},
+        routing::post,
+        Router,
+    };
+    use hmac::{Hmac, Mac};
+    use orchestrate_core::{Pipeline, PipelineRun, PipelineStage};
+    use serde_json::Value;
+    use sha2::Sha256;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "slack-signing-secret";
+
+    async fn create_test_router(secret: Option<&str>) -> (Router, Database) {
+        let database = Database::in_memory().await.unwrap();
+        let state = Arc::new(WebhookState::new(
+            WebhookConfig::new(secret.map(str::to_string)),
+            database.clone(),
+        ));
+        let router = Router::new()
+            .route("/api/slack/commands", post(slack_command_handler))
+            .with_state(state);
+        (router, database)
+    }
+
+    fn sign(timestamp: i64, body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+        format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    fn form(text: &str) -> String {
+        serde_urlencoded::to_string([
+            ("token", "deprecated"),
+            ("team_id", "T123"),
+            ("channel_id", "C123"),
+            ("channel_name", "ops"),
+            ("user_id", "U123"),
+            ("user_name", "jane"),
+            ("command", "/orchestrate"),
+            ("text", text),
+            ("response_url", "https://hooks.slack.com/commands/1"),
+            ("trigger_id", "trigger-1"),
+        ])
+        .unwrap()
+    }
+
+    fn request(body: String, timestamp: i64, signature: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/api/slack/commands")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .header("x-slack-request-timestamp", timestamp.to_string())
+            .header("x-slack-signature", signature)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn run(router: Router, text: &str) -> Value {
+        let body = form(text);
+        let timestamp = chrono::Utc::now().timestamp();
+        let signature = sign(timestamp, &body);
+        let response = router
+            .oneshot(request(body, timestamp, &signature))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_and_stale_signatures() {
+        let (router, _) = create_test_router(Some(SECRET)).await;
+        let now = chrono::Utc::now().timestamp();
+
+        let response = router
+            .clone()
+            .oneshot(request(form("status"), now, "v0=deadbeef"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let stale = now - MAX_REQUEST_AGE_SECS - 60;
+        let body = form("status");
+        let signature = sign(stale, &body);
+        let response = router
+            .oneshot(request(body, stale, &signature))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let (router, _) = create_test_router(None).await;
+        let body = form("status");
+        let response = router
+            .oneshot(request(body.clone(), now, &sign(now, &body)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_and_list_agents() {
+        let (router, database) = create_test_router(Some(SECRET)).await;
+
+        let reply = run(router.clone(), "spawn story-developer Fix the login flow").await;
+        assert_eq!(reply["response_type"], "in_channel");
+        assert!(reply["text"].as_str().unwrap().contains("story_developer"));
+
+        let agents = database.list_agents().await.unwrap();
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].agent_type, AgentType::StoryDeveloper);
+        assert_eq!(agents[0].task, "Fix the login flow");
+
+        let reply = run(router.clone(), "agents").await;
+        assert_eq!(reply["response_type"], "ephemeral");
+        assert!(reply["blocks"][0]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("Fix the login flow"));
+
+        let reply = run(router.clone(), "status").await;
+        assert_eq!(reply["text"], "1 agents in total");
+
+        let reply = run(router, "spawn nonsense Do things").await;
+        assert!(reply["text"]
+            .as_str()
+            .unwrap()
+            .contains("Unknown agent type"));
+    }
+
+    #[tokio::test]
+    async fn test_approve_uses_mapped_github_user() {
+        let (router, database) = create_test_router(Some(SECRET)).await;
+
+        let pipeline = Pipeline::new("deploy".to_string(), "name: deploy".to_string());
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+        let run_id = database
+            .insert_pipeline_run(&PipelineRun::new(pipeline_id, None))
+            .await
+            .unwrap();
+        let stage_id = database
+            .insert_pipeline_stage(&PipelineStage::new(run_id, "production".to_string()))
+            .await
+            .unwrap();
+        let approvals = ApprovalService::new(database.clone());
+        let approval = approvals
+            .create_approval(stage_id, run_id, vec!["octocat".to_string()], 2, None, None)
+            .await
+            .unwrap();
+        let approval_id = approval.id.unwrap();
+
+        // Unmapped users approve under their Slack username
+        let reply = run(router.clone(), &format!("approve {}", approval_id)).await;
+        assert!(reply["text"]
+            .as_str()
+            .unwrap()
+            .contains("'jane' is not an authorized approver"));
+
+        SlackUserService::new(database.clone(), SlackService::new(database.clone()))
+            .map_user("octocat", "U123", "jane")
+            .await
+            .unwrap();
+
+        let reply = run(router, &format!("approve {} ship it", approval_id)).await;
+        assert_eq!(reply["response_type"], "in_channel");
+        assert!(reply["text"].as_str().unwrap().contains("as octocat (1/2"));
+
+        let decisions = approvals.get_decisions(approval_id).await.unwrap();
+        assert_eq!(decisions[0].approver, "octocat");
+        assert_eq!(decisions[0].comment.as_deref(), Some("ship it"));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("ünïcödé text", 7), "ünïcödé…");
+    }
+}
@@ -0,0 +1,206 @@
+//! Slack interactivity endpoint
+//!
+//! Receives clicks on the approve/reject buttons and delegate picker of
+//! approval messages. A decision that resolves an approval resumes its
+//! pipeline run. Requests are signed like slash commands, and the
+//! interaction arrives as JSON in the `payload` form field.
+//!
+//! Slack ignores the response body of block actions, so errors are reported
+//! to the user who clicked through the interaction's `response_url`.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use orchestrate_core::{InteractionPayload, SlackApprovalService, SlackService};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::api::spawn_resume_after_approval;
+use crate::slack_commands::verify_request;
+use crate::webhook::{respond, WebhookState};
+
+/// Form body of an interaction request
+#[derive(Debug, Deserialize)]
+struct InteractionForm {
+    payload: String,
+}
+
+/// Interaction handler
+///
+/// Verifies the Slack signature and applies the clicked action to its
+/// approval request. Actions on other messages are acknowledged and ignored.
+#[utoipa::path(
+    post,
+    path = "/api/slack/interactions",
+    tag = "webhooks",
+    summary = "Receive a Slack interaction",
+    request_body(content = String, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Event accepted"),
+        (status = 400, description = "Malformed payload"),
+        (status = 401, description = "Invalid signature")
+    )
+)]
+pub async fn slack_interaction_handler(
+    State(state): State<Arc<WebhookState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let Some(secret) = state.config.secret.as_ref() else {
+        warn!("Slack interaction received but SLACK_SIGNING_SECRET is not set");
+        return respond(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "error",
+            "Slack signing secret not configured",
+        )
+        .into_response();
+    };
+
+    if let Err(message) = verify_request(secret, &headers, &body, chrono::Utc::now().timestamp()) {
+        warn!(reason = message, "Rejected Slack interaction");
+        return respond(StatusCode::UNAUTHORIZED, "error", message).into_response();
+    }
+
+    let payload = match parse_payload(&body) {
+        Ok(payload) => payload,
+        Err(message) => {
+            warn!(error = %message, "Failed to parse Slack interaction");
+            return respond(
+                StatusCode::BAD_REQUEST,
+                "error",
+                &format!("Invalid interaction payload: {}", message),
+            )
+            .into_response();
+        }
+    };
+
+    let database = &state.database;
+    let service = SlackApprovalService::new(database.clone(), SlackService::new(database.clone()));
+    match service.handle_interaction(&payload).await {
+        Ok(Some(approval)) => {
+            info!(
+                approval_id = ?approval.id,
+                user = %payload.user.id,
+                status = approval.status.as_str(),
+                "Slack approval action applied"
+            );
+            spawn_resume_after_approval(database, &approval);
+        }
+        Ok(None) => {}
+        Err(e) => {
+            warn!(error = %e, user = %payload.user.id, "Slack approval action failed");
+            if let Err(e) = service.report_error(&payload, &e).await {
+                warn!(error = %e, "Failed to report Slack approval error");
+            }
+        }
+    }
+
+    StatusCode::OK.into_response()
+}
+
+fn parse_payload(body: &[u8]) -> Result<InteractionPayload, String> {
+    let form: InteractionForm = serde_urlencoded::from_bytes(body).map_err(|e| e.to_string())?;
+    serde_json::from_str(&form.payload).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhook::WebhookConfig;
+    use axum::{
+        body::Body,
+        http::{Method, Request},
+        routing::post,
+        Router,
+    };
+    use hmac::{Hmac, Mac};
+    use orchestrate_core::Database;
+    use serde_json::json;
+    use sha2::Sha256;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "slack-signing-secret";
+
+    async fn create_test_router() -> Router {
+        let database = Database::in_memory().await.unwrap();
+        let state = Arc::new(WebhookState::new(
+            WebhookConfig::new(Some(SECRET.to_string())),
+            database,
+        ));
+        Router::new()
+            .route("/api/slack/interactions", post(slack_interaction_handler))
+            .with_state(state)
+    }
+
+    fn form(action_id: &str) -> String {
+        let payload = json!({
+            "type": "block_actions",
+            "trigger_id": "trigger-1",
+            "user": { "id": "U123", "name": "jane", "username": "jane", "team_id": "T123" },
+            "channel": { "id": "C123", "name": "approvals" },
+            "message": { "ts": "1700000000.000100", "text": "Approval required" },
+            "actions": [{
+                "action_id": action_id,
+                "block_id": "b1",
+                "type": "button",
+                "action_ts": "1700000001.000200"
+            }],
+            "response_url": "https://hooks.slack.com/actions/1"
+        });
+        serde_urlencoded::to_string([("payload", payload.to_string())]).unwrap()
+    }
+
+    fn request(body: String, signature: Option<&str>) -> Request<Body> {
+        let timestamp = chrono::Utc::now().timestamp();
+        let signature = signature.map(str::to_string).unwrap_or_else(|| {
+            let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+            mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+            format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+        });
+        Request::builder()
+            .method(Method::POST)
+            .uri("/api/slack/interactions")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .header("x-slack-request-timestamp", timestamp.to_string())
+            .header("x-slack-signature", signature)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_signature() {
+        let router = create_test_router().await;
+
+        let response = router
+            .oneshot(request(form("approve_1"), Some("v0=deadbeef")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_malformed_payload() {
+        let router = create_test_router().await;
+
+        let response = router
+            .oneshot(request("payload=not-json".to_string(), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_acknowledges_other_actions() {
+        let router = create_test_router().await;
+
+        let response = router
+            .oneshot(request(form("view_agent"), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
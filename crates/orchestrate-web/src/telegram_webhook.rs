@@ -0,0 +1,198 @@
+//! Telegram webhook endpoint
+//!
+//! Receives updates for the notification bot, registered with Telegram's
+//! `setWebhook` and a secret token. Presses of the Approve / Reject buttons
+//! under approval messages arrive as callback queries and are recorded as
+//! decisions; a decision that resolves an approval resumes its pipeline
+//! run. Other updates are acknowledged and ignored.
+//!
+//! Telegram retries deliveries that are not answered with a 2xx status, so
+//! errors are reported to the user who pressed the button instead.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use orchestrate_core::{telegram::TELEGRAM_SECRET_HEADER, TelegramService, TelegramUpdate};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::api::spawn_resume_after_approval;
+use crate::webhook::{respond, verify_token, WebhookState};
+
+/// Telegram webhook handler
+///
+/// Verifies the secret token and applies button presses to their approval
+/// requests.
+#[utoipa::path(
+    post,
+    path = "/api/telegram/webhook",
+    tag = "webhooks",
+    summary = "Receive a Telegram update",
+    request_body(content = String, content_type = "application/json"),
+    responses(
+        (status = 200, description = "Event accepted"),
+        (status = 400, description = "Malformed payload"),
+        (status = 401, description = "Invalid signature")
+    )
+)]
+pub async fn telegram_webhook_handler(
+    State(state): State<Arc<WebhookState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let Some(secret) = state.config.secret.as_ref() else {
+        warn!("Telegram update received but TELEGRAM_WEBHOOK_SECRET is not set");
+        return respond(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "error",
+            "Telegram webhook secret not configured",
+        )
+        .into_response();
+    };
+
+    let token = headers
+        .get(TELEGRAM_SECRET_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !verify_token(secret, token) {
+        warn!("Rejected Telegram update with invalid secret token");
+        return respond(StatusCode::UNAUTHORIZED, "error", "Invalid token").into_response();
+    }
+
+    let update: TelegramUpdate = match serde_json::from_slice(&body) {
+        Ok(update) => update,
+        Err(e) => {
+            warn!(error = %e, "Failed to parse Telegram update");
+            return respond(
+                StatusCode::BAD_REQUEST,
+                "error",
+                &format!("Invalid Telegram update: {}", e),
+            )
+            .into_response();
+        }
+    };
+    let Some(query) = update.callback_query else {
+        return respond(StatusCode::OK, "ignored", "Not a callback query").into_response();
+    };
+
+    let database = &state.database;
+    let service = match TelegramService::from_env(database.clone()) {
+        Ok(Some(service)) => service,
+        Ok(None) => {
+            warn!("Telegram update received but TELEGRAM_BOT_TOKEN is not set");
+            return respond(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "error",
+                "Telegram bot not configured",
+            )
+            .into_response();
+        }
+        Err(e) => {
+            warn!(error = %e, "Invalid Telegram configuration");
+            return respond(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "error",
+                "Telegram bot not configured",
+            )
+            .into_response();
+        }
+    };
+
+    let outcome = service.handle_callback(&query).await;
+    match &outcome {
+        Ok(Some(approval)) => {
+            info!(
+                approval_id = ?approval.id,
+                user = query.from.id,
+                status = approval.status.as_str(),
+                "Telegram approval action applied"
+            );
+            spawn_resume_after_approval(database, approval);
+        }
+        Ok(None) => {}
+        Err(e) => warn!(error = %e, user = query.from.id, "Telegram approval action failed"),
+    }
+    if let Err(e) = service.confirm_callback(&query, &outcome).await {
+        warn!(error = %e, "Failed to answer Telegram callback query");
+    }
+
+    StatusCode::OK.into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhook::WebhookConfig;
+    use axum::{
+        body::Body,
+        http::{Method, Request},
+        routing::post,
+        Router,
+    };
+    use orchestrate_core::Database;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "telegram-secret";
+
+    async fn create_test_router() -> Router {
+        let database = Database::in_memory().await.unwrap();
+        let state = Arc::new(WebhookState::new(
+            WebhookConfig::new(Some(SECRET.to_string())),
+            database,
+        ));
+        Router::new()
+            .route("/api/telegram/webhook", post(telegram_webhook_handler))
+            .with_state(state)
+    }
+
+    fn request(body: String, token: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/api/telegram/webhook")
+            .header("content-type", "application/json")
+            .header(TELEGRAM_SECRET_HEADER, token)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_token() {
+        let router = create_test_router().await;
+
+        let response = router
+            .oneshot(request(json!({ "update_id": 1 }).to_string(), "wrong"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_malformed_update() {
+        let router = create_test_router().await;
+
+        let response = router
+            .oneshot(request("not-json".to_string(), SECRET))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_ignores_messages() {
+        let router = create_test_router().await;
+
+        let update = json!({
+            "update_id": 1,
+            "message": { "message_id": 3, "chat": { "id": 42 }, "text": "hi" }
+        });
+        let response = router
+            .oneshot(request(update.to_string(), SECRET))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
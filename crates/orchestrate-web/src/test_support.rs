@@ -0,0 +1,103 @@
+//! Helpers shared by the crate's tests
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use tower::util::ServiceExt;
+
+/// Serve `router` on a local port, returning its URL
+pub async fn serve(router: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    url
+}
+
+/// URL of a local port nothing listens on
+pub async fn closed_port() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+/// Routes registered on `router`, as `(method, path)` pairs
+///
+/// Paths come from the router's `Debug` output, leaving out its fallback.
+/// Methods come from the `Allow`
+/// header of the 405 answering a `TRACE` of each path, which authentication,
+/// applied with `route_layer`, doesn't see.
+pub async fn registered_routes(router: Router) -> Vec<(String, String)> {
+    let debug = format!("{:?}", router);
+    let (routed, _fallback) = debug.split_once("fallback_router:").unwrap();
+    let mut paths: Vec<&str> = routed
+        .split("RouteId(")
+        .skip(1)
+        .filter_map(|entry| entry.split_once("): \"")?.1.split('"').next())
+        .collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut routes = Vec::new();
+    for path in paths {
+        let uri: Vec<&str> = path
+            .split('/')
+            .map(|segment| {
+                if segment.starts_with([':', '*']) {
+                    "x"
+                } else {
+                    segment
+                }
+            })
+            .collect();
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::TRACE)
+                    .uri(uri.join("/"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::METHOD_NOT_ALLOWED,
+            "TRACE {}",
+            path
+        );
+        let allow = response.headers()[header::ALLOW].to_str().unwrap();
+        for method in allow.split(',').map(str::trim) {
+            if method != "HEAD" {
+                routes.push((method.to_string(), path.to_string()));
+            }
+        }
+    }
+    routes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+
+    #[tokio::test]
+    async fn test_registered_routes() {
+        let router = Router::new()
+            .route("/api/items", get(|| async {}).post(|| async {}))
+            .route("/api/items/:id", get(|| async {}).delete(|| async {}))
+            .route("/files/*key", get(|| async {}))
+            .fallback(|| async {});
+        assert_eq!(
+            registered_routes(router).await,
+            vec![
+                ("GET".to_string(), "/api/items".to_string()),
+                ("POST".to_string(), "/api/items".to_string()),
+                ("GET".to_string(), "/api/items/:id".to_string()),
+                ("DELETE".to_string(), "/api/items/:id".to_string()),
+                ("GET".to_string(), "/files/*key".to_string()),
+            ]
+        );
+    }
+}
@@ -1,25 +1,147 @@
 //! React SPA UI routes for the web interface
+//!
+//! The built UI in `static/` (see `frontend/vite.config.ts`) is embedded in
+//! the binary, so `orchestrate web` needs no asset directory. Debug builds
+//! read the files from disk instead, picking up frontend rebuilds.
 
-use axum::Router;
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    Router,
+};
+use rust_embed::{EmbeddedFile, RustEmbed};
 use std::sync::Arc;
-use tower_http::services::{ServeDir, ServeFile};
 
-use crate::api::AppState;
+use crate::api::{ApiError, AppState};
+
+#[derive(RustEmbed)]
+#[folder = "static/"]
+struct Assets;
+
+/// Assets under `assets/` have content hashes in their names
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+/// `index.html` and unhashed files are revalidated with their ETag
+const REVALIDATE: &str = "no-cache";
 
 /// Create the UI router that serves the React SPA
 pub fn create_ui_router() -> Router<Arc<AppState>> {
-    let static_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("static");
-    let assets_path = static_path.join("assets");
-    let index_path = static_path.join("index.html");
-
-    Router::new()
-        // Serve static assets (JS, CSS, images) from /assets
-        .nest_service("/assets", ServeDir::new(assets_path))
-        // Serve favicon
-        .nest_service(
-            "/favicon.svg",
-            ServeFile::new(static_path.join("favicon.svg")),
-        )
-        // SPA fallback - all other routes serve index.html for client-side routing
-        .fallback_service(ServeFile::new(index_path).precompressed_gzip())
+    Router::new().fallback(serve_ui)
+}
+
+/// Serve an embedded file, or `index.html` for client-side routes
+async fn serve_ui(method: Method, uri: Uri, headers: HeaderMap) -> Response {
+    if method != Method::GET && method != Method::HEAD {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    let path = uri.path().trim_start_matches('/');
+    if let Some(file) = Assets::get(path).filter(|_| !path.is_empty()) {
+        let cache_control = if path.starts_with("assets/") {
+            IMMUTABLE
+        } else {
+            REVALIDATE
+        };
+        return serve_file(file, cache_control, &headers);
+    }
+
+    // Unknown API routes and missing assets aren't client-side routes
+    if path == "api" || path.starts_with("api/") {
+        return ApiError::not_found("API route").into_response();
+    }
+    if path.starts_with("assets/") {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    match Assets::get("index.html") {
+        Some(index) => serve_file(index, REVALIDATE, &headers),
+        None => (StatusCode::NOT_FOUND, "UI not built").into_response(),
+    }
+}
+
+fn serve_file(file: EmbeddedFile, cache_control: &'static str, headers: &HeaderMap) -> Response {
+    let etag = format!("\"{}\"", hex::encode(file.metadata.sha256_hash()));
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|tags| tags.split(',').any(|tag| tag.trim() == etag));
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        let mut response = Body::from(file.data).into_response();
+        if let Ok(content_type) = HeaderValue::from_str(file.metadata.mimetype()) {
+            response
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, content_type);
+        }
+        response
+    };
+
+    let response_headers = response.headers_mut();
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(cache_control),
+    );
+    if let Ok(etag) = HeaderValue::from_str(&etag) {
+        response_headers.insert(header::ETAG, etag);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use orchestrate_core::Database;
+    use tower::ServiceExt;
+
+    async fn router() -> Router {
+        let db = Database::in_memory().await.unwrap();
+        create_ui_router().with_state(Arc::new(AppState::new(db, None)))
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_client_routes_serve_index() {
+        let response = router().await.oneshot(get("/agents/42")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html"));
+        assert_eq!(response.headers()[header::CACHE_CONTROL], REVALIDATE);
+
+        for uri in ["/api/nonexistent", "/assets/missing.js"] {
+            let response = router().await.oneshot(get(uri)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND, "{}", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hashed_assets_are_immutable() {
+        let asset = Assets::iter()
+            .find(|path| path.starts_with("assets/"))
+            .expect("UI assets are built into static/");
+
+        let response = router()
+            .await
+            .oneshot(get(&format!("/{}", asset)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], IMMUTABLE);
+        let etag = response.headers()[header::ETAG].clone();
+
+        let revalidate = Request::builder()
+            .uri(format!("/{}", asset))
+            .header(header::IF_NONE_MATCH, etag)
+            .body(Body::empty())
+            .unwrap();
+        let response = router().await.oneshot(revalidate).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
 }
@@ -0,0 +1,288 @@
+//! API versioning
+//!
+//! Every `/api` route is served under `/api/v1` as well. Versioned requests
+//! are routed as their unversioned path, so authentication, RBAC, rate limits,
+//! and metrics see the same route either way.
+//!
+//! The unversioned paths are a compatibility layer for existing dashboards
+//! and scripts: their responses carry `Deprecation` and
+//! `Link: <...>; rel="successor-version"` headers, plus `Sunset` when
+//! `ORCHESTRATE_API_SUNSET` sets the date they go away.
+//!
+//! Handlers changing a response shape can mark the old one deprecated the
+//! same way by returning a [`Deprecation`] with the response:
+//!
+//! ```ignore
+//! Ok((Deprecation::new().with_successor("/api/v1/agents/:id/status"), Json(agent)))
+//! ```
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderName, HeaderValue, Uri},
+    middleware::{self, Next},
+    response::{IntoResponseParts, Response, ResponseParts},
+    Router,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::convert::Infallible;
+use tower::ServiceExt;
+
+/// Current API version
+pub const API_VERSION: &str = "v1";
+/// Prefix of the current API version's routes
+pub const VERSIONED_PREFIX: &str = "/api/v1";
+
+static DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+static SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Deprecation of a route or response shape, sent as the `Deprecation`
+/// (RFC 9745), `Sunset` (RFC 8594), and successor `Link` headers
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Deprecation {
+    /// When it was deprecated, if known
+    pub since: Option<DateTime<Utc>>,
+    /// When it will be removed, if scheduled
+    pub sunset: Option<DateTime<Utc>>,
+    /// Path of the replacement
+    pub successor: Option<String>,
+}
+
+impl Deprecation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    pub fn with_sunset(mut self, sunset: DateTime<Utc>) -> Self {
+        self.sunset = Some(sunset);
+        self
+    }
+
+    pub fn with_successor(mut self, successor: impl Into<String>) -> Self {
+        self.successor = Some(successor.into());
+        self
+    }
+
+    /// Deprecation of the unversioned paths, with the sunset date from
+    /// `ORCHESTRATE_API_SUNSET` (YYYY-MM-DD)
+    pub fn unversioned_from_env() -> Self {
+        let sunset = std::env::var("ORCHESTRATE_API_SUNSET")
+            .ok()
+            .and_then(|date| {
+                let sunset = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                    .ok()
+                    .and_then(|d| d.and_hms_opt(0, 0, 0))
+                    .map(|d| d.and_utc());
+                if sunset.is_none() {
+                    tracing::warn!("Ignoring invalid ORCHESTRATE_API_SUNSET: {}", date);
+                }
+                sunset
+            });
+        Self {
+            sunset,
+            ..Self::default()
+        }
+    }
+
+    fn apply(&self, headers: &mut axum::http::HeaderMap) {
+        let deprecation = match self.since {
+            Some(since) => format!("@{}", since.timestamp()),
+            None => "true".to_string(),
+        };
+        if let Ok(value) = HeaderValue::from_str(&deprecation) {
+            headers.insert(DEPRECATION.clone(), value);
+        }
+        if let Some(sunset) = self.sunset {
+            let date = sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+            if let Ok(value) = HeaderValue::from_str(&date) {
+                headers.insert(SUNSET.clone(), value);
+            }
+        }
+        if let Some(successor) = &self.successor {
+            let link = format!("<{}>; rel=\"successor-version\"", successor);
+            if let Ok(value) = HeaderValue::from_str(&link) {
+                headers.append(header::LINK, value);
+            }
+        }
+    }
+}
+
+impl IntoResponseParts for Deprecation {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        self.apply(res.headers_mut());
+        Ok(res)
+    }
+}
+
+/// `/api/v1/...` of an unversioned `/api/...` path, other paths unchanged
+pub fn versioned_path(path: &str) -> String {
+    match path.strip_prefix("/api/") {
+        Some(rest) if !is_versioned(path) => format!("{}/{}", VERSIONED_PREFIX, rest),
+        _ => path.to_string(),
+    }
+}
+
+fn is_versioned(path: &str) -> bool {
+    path == VERSIONED_PREFIX || path.starts_with("/api/v1/")
+}
+
+/// Serve the `/api` routes of `router` under `/api/v1` too, marking the
+/// unversioned paths deprecated
+pub fn with_versioned_api(router: Router, deprecation: Deprecation) -> Router {
+    // Rewritten ahead of routing, as nesting would prefix the matched routes
+    let router = router.map_request(|mut request: Request<Body>| {
+        if let Some(uri) = unversioned_uri(request.uri()) {
+            *request.uri_mut() = uri;
+        }
+        request
+    });
+
+    Router::new()
+        .fallback_service(router)
+        .layer(middleware::from_fn(move |request: Request, next: Next| {
+            let deprecation = deprecation.clone();
+            async move { deprecate_unversioned(deprecation, request, next).await }
+        }))
+}
+
+/// `/api/...` of a `/api/v1/...` URI
+fn unversioned_uri(uri: &Uri) -> Option<Uri> {
+    let path_and_query = uri.path_and_query()?.as_str();
+    let rest = path_and_query.strip_prefix(VERSIONED_PREFIX)?;
+    if !(rest.is_empty() || rest.starts_with(['/', '?'])) {
+        return None;
+    }
+    format!("/api{}", rest).parse().ok()
+}
+
+async fn deprecate_unversioned(deprecation: Deprecation, request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    let mut response = next.run(request).await;
+    if path.starts_with("/api/") && !is_versioned(&path) {
+        deprecation
+            .with_successor(versioned_path(&path))
+            .apply(response.headers_mut());
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::AppState;
+    use axum::http::StatusCode;
+    use chrono::TimeZone;
+    use orchestrate_core::Database;
+    use std::sync::Arc;
+
+    async fn app(api_key: Option<&str>) -> Router {
+        let db = Database::in_memory().await.unwrap();
+        crate::create_router(Arc::new(AppState::new(db, api_key.map(str::to_string))))
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::builder()
+            .uri(uri)
+            .header("x-api-key", "secret")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_versioned_path() {
+        assert_eq!(versioned_path("/api/agents"), "/api/v1/agents");
+        assert_eq!(versioned_path("/api/v1/agents"), "/api/v1/agents");
+        assert_eq!(versioned_path("/metrics"), "/metrics");
+
+        let uri = |uri: &str| unversioned_uri(&uri.parse().unwrap()).map(|u| u.to_string());
+        assert_eq!(
+            uri("/api/v1/agents?limit=5").as_deref(),
+            Some("/api/agents?limit=5")
+        );
+        assert_eq!(uri("/api/v10/agents"), None);
+        assert_eq!(uri("/api/agents"), None);
+    }
+
+    #[tokio::test]
+    async fn test_versioned_routes() {
+        let response = app(Some("secret"))
+            .await
+            .oneshot(get("/api/v1/agents?limit=5"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(&DEPRECATION).is_none());
+
+        // Versioned routes are authenticated like the unversioned ones
+        let request = Request::builder()
+            .uri("/api/v1/agents")
+            .body(Body::empty())
+            .unwrap();
+        let response = app(Some("secret")).await.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app(None)
+            .await
+            .oneshot(get("/api/v1/nonexistent"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_versioned_requests_match_unversioned_routes() {
+        let db = Database::in_memory().await.unwrap();
+        let state = Arc::new(AppState::new(db, None));
+        let router = crate::create_router(state.clone());
+
+        let response = router.oneshot(get("/api/v1/agents/42")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let paths: Vec<String> = state
+            .metrics
+            .endpoint_latencies()
+            .into_iter()
+            .map(|e| e.path)
+            .collect();
+        assert_eq!(paths, ["/api/agents/:id"]);
+    }
+
+    #[tokio::test]
+    async fn test_unversioned_routes_are_deprecated() {
+        let response = app(None).await.oneshot(get("/api/agents")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[&DEPRECATION], "true");
+        assert_eq!(
+            response.headers()[header::LINK],
+            "</api/v1/agents>; rel=\"successor-version\""
+        );
+
+        // Routes outside the API aren't versioned
+        let response = app(None).await.oneshot(get("/healthz")).await.unwrap();
+        assert!(response.headers().get(&DEPRECATION).is_none());
+    }
+
+    #[test]
+    fn test_deprecation_headers() {
+        let mut headers = axum::http::HeaderMap::new();
+        Deprecation::new()
+            .since(Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap())
+            .with_sunset(Utc.with_ymd_and_hms(2027, 4, 1, 0, 0, 0).unwrap())
+            .with_successor("/api/v1/agents")
+            .apply(&mut headers);
+
+        assert_eq!(headers[&DEPRECATION], "@1790812800");
+        assert_eq!(headers[&SUNSET], "Thu, 01 Apr 2027 00:00:00 GMT");
+        assert_eq!(
+            headers[header::LINK],
+            "</api/v1/agents>; rel=\"successor-version\""
+        );
+    }
+}
@@ -1,6 +1,9 @@
 //! GitHub webhook receiver
 //!
 //! Handles incoming GitHub webhook events with signature verification.
+//! Sources with a stored signature configuration (see
+//! [`orchestrate_core::WebhookSource`]) are verified with their own scheme,
+//! header, and secrets instead of the global secret.
 
 use axum::{
     body::Bytes,
@@ -17,7 +20,7 @@ use tracing::{debug, error, info, warn};
 /// Webhook configuration
 #[derive(Clone)]
 pub struct WebhookConfig {
-    /// GitHub webhook secret for HMAC verification
+    /// GitHub webhook secret for HMAC verification, or GitLab secret token
     pub secret: Option<String>,
 }
 
@@ -50,6 +53,18 @@ pub struct WebhookResponse {
 /// GitHub webhook handler
 ///
 /// Receives GitHub webhook events, verifies signatures, and processes them asynchronously.
+#[utoipa::path(
+    post,
+    path = "/webhooks/github",
+    tag = "webhooks",
+    summary = "Receive a GitHub webhook",
+    request_body(content = String, content_type = "application/json"),
+    responses(
+        (status = 200, description = "Event accepted"),
+        (status = 400, description = "Malformed payload"),
+        (status = 401, description = "Invalid signature")
+    )
+)]
 pub async fn github_webhook_handler(
     State(state): State<Arc<WebhookState>>,
     headers: HeaderMap,
@@ -94,8 +109,15 @@ pub async fn github_webhook_handler(
         "Received GitHub webhook"
     );
 
+    // A signature scheme stored for the source replaces the global secret
+    let source_verified = match verify_source(&state.database, "github", &headers, &body).await {
+        SourceVerification::Rejected(response) => return response,
+        SourceVerification::Verified => true,
+        SourceVerification::NotConfigured => false,
+    };
+
     // Verify signature if secret is configured
-    if let Some(ref secret) = state.config.secret {
+    if let Some(secret) = state.config.secret.as_ref().filter(|_| !source_verified) {
         let signature = match headers.get("x-hub-signature-256") {
             Some(value) => match value.to_str() {
                 Ok(v) => v,
@@ -208,6 +230,87 @@ pub async fn github_webhook_handler(
     )
 }
 
+/// Build a webhook response with the given status code
+pub(crate) fn respond(
+    status: StatusCode,
+    outcome: &str,
+    message: &str,
+) -> (StatusCode, Json<WebhookResponse>) {
+    (
+        status,
+        Json(WebhookResponse {
+            status: outcome.to_string(),
+            message: message.to_string(),
+        }),
+    )
+}
+
+/// Outcome of verifying a request against its source's stored signature settings
+pub(crate) enum SourceVerification {
+    /// The source has no stored settings, so the handler's own secret applies
+    NotConfigured,
+    /// The signature matched one of the source's active secrets
+    Verified,
+    /// The request is rejected with this response
+    Rejected((StatusCode, Json<WebhookResponse>)),
+}
+
+/// Verify a request with the scheme, header, and secrets stored for the source
+///
+/// Secrets rotated out stay valid until their overlap window ends.
+pub(crate) async fn verify_source(
+    database: &Database,
+    source: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> SourceVerification {
+    let source = match database.get_webhook_source(source).await {
+        Ok(Some(source)) => source,
+        Ok(None) => return SourceVerification::NotConfigured,
+        Err(e) => {
+            error!(error = %e, source = %source, "Failed to load webhook source");
+            return SourceVerification::Rejected(respond(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "error",
+                "Failed to load webhook source",
+            ));
+        }
+    };
+
+    match headers
+        .get(source.header.as_str())
+        .and_then(|v| v.to_str().ok())
+    {
+        Some(signature) if source.verify(body, signature) => SourceVerification::Verified,
+        Some(_) => {
+            error!(source = %source.name, scheme = source.scheme.as_str(), "Invalid webhook signature");
+            SourceVerification::Rejected(respond(
+                StatusCode::UNAUTHORIZED,
+                "error",
+                "Invalid signature",
+            ))
+        }
+        None => {
+            warn!(source = %source.name, header = %source.header, "Missing webhook signature header");
+            SourceVerification::Rejected(respond(
+                StatusCode::UNAUTHORIZED,
+                "error",
+                "Missing signature",
+            ))
+        }
+    }
+}
+
+/// Compare a received token with the configured secret in constant time
+pub(crate) fn verify_token(secret: &str, token: &str) -> bool {
+    secret.len() == token.len()
+        && secret
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
 /// Verify GitHub webhook signature using HMAC-SHA256
 ///
 /// GitHub sends the signature in the format: "sha256=<hex-encoded-hmac>"
@@ -516,6 +619,13 @@ mod tests {
         assert!(!verify_signature(secret, payload, invalid_hex));
     }
 
+    #[test]
+    fn test_verify_token() {
+        assert!(verify_token("secret-token", "secret-token"));
+        assert!(!verify_token("secret-token", "secret-tokem"));
+        assert!(!verify_token("secret-token", "secret-token-2"));
+    }
+
     #[tokio::test]
     async fn test_webhook_queues_event() {
         let database = orchestrate_core::Database::in_memory().await.unwrap();
@@ -553,4 +663,74 @@ mod tests {
         assert_eq!(event.payload, payload);
         assert_eq!(event.status, orchestrate_core::WebhookEventStatus::Pending);
     }
+
+    #[tokio::test]
+    async fn test_webhook_source_hmac_sha512_with_rotation() {
+        use hmac::{Hmac, Mac};
+        use orchestrate_core::{SignatureScheme, WebhookSource};
+
+        fn sign(secret: &str, payload: &str) -> String {
+            let mut mac = Hmac::<sha2::Sha512>::new_from_slice(secret.as_bytes()).unwrap();
+            mac.update(payload.as_bytes());
+            format!("sha512={}", hex::encode(mac.finalize().into_bytes()))
+        }
+
+        let database = orchestrate_core::Database::in_memory().await.unwrap();
+        let mut source = WebhookSource::new("github", SignatureScheme::HmacSha512, "old-secret");
+        source.rotate("new-secret", chrono::Duration::hours(1));
+        database.insert_webhook_source(&source).await.unwrap();
+
+        // The stored source replaces the global secret
+        let state = Arc::new(WebhookState::new(
+            WebhookConfig::new(Some("global-secret".to_string())),
+            database,
+        ));
+        let router = Router::new()
+            .route("/webhooks/github", post(github_webhook_handler))
+            .with_state(state);
+
+        let payload = r#"{"action":"opened","number":1}"#;
+        let request = |signature: String| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/webhooks/github")
+                .header("x-github-event", "pull_request")
+                .header("x-hub-signature-512", signature)
+                .body(Body::from(payload))
+                .unwrap()
+        };
+
+        for (secret, status) in [
+            ("new-secret", StatusCode::OK),
+            ("old-secret", StatusCode::OK),
+            ("global-secret", StatusCode::UNAUTHORIZED),
+        ] {
+            let response = router
+                .clone()
+                .oneshot(request(sign(secret, payload)))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status, "{}", secret);
+        }
+
+        // The global secret's SHA-256 header is not consulted
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/webhooks/github")
+                    .header("x-github-event", "pull_request")
+                    .header(
+                        "x-hub-signature-256",
+                        compute_github_signature("global-secret", payload),
+                    )
+                    .body(Body::from(payload))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body = body_to_string(response.into_body()).await;
+        assert!(body.contains("Missing signature"));
+    }
 }
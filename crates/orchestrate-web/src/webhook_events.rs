@@ -0,0 +1,342 @@
+//! Webhook event administration, the API counterpart of
+//! `orchestrate webhook list-events`
+//!
+//! - GET /api/webhooks/events - Received events, newest first
+//! - GET /api/webhooks/events/:id - Event with its payload
+//! - POST /api/webhooks/events/:id/retry - Queue an event again
+//! - DELETE /api/webhooks/dead-letters - Purge the dead letter queue
+//! - GET /api/webhooks/stats - Event counts by source and status
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use orchestrate_core::{WebhookEvent, WebhookEventStatus, WebhookEventSummary, WebhookSourceStats};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::api::{ApiError, AppState};
+use crate::pagination::{Page, PageParams};
+
+/// Filters of the event list
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct WebhookEventQuery {
+    pub status: Option<String>,
+    pub event_type: Option<String>,
+    /// github, gitlab, or custom:<name>
+    pub source: Option<String>,
+}
+
+/// A received event with its payload
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WebhookEventDetail {
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub event: WebhookEventSummary,
+    /// Parsed payload, or the raw text when it isn't JSON
+    pub payload: serde_json::Value,
+}
+
+/// Result of purging the dead letter queue
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PurgeResponse {
+    pub deleted: u64,
+}
+
+impl From<WebhookEvent> for WebhookEventDetail {
+    fn from(event: WebhookEvent) -> Self {
+        let payload = serde_json::from_str(&event.payload)
+            .unwrap_or_else(|_| serde_json::Value::String(event.payload.clone()));
+        Self {
+            event: WebhookEventSummary::new(&event, &payload),
+            payload,
+        }
+    }
+}
+
+fn db_error(e: orchestrate_core::Error) -> ApiError {
+    ApiError::internal(format!("Database error: {}", e))
+}
+
+async fn find_event(state: &AppState, id: i64) -> Result<WebhookEvent, ApiError> {
+    state
+        .db
+        .get_webhook_event(id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| ApiError::not_found("Webhook event"))
+}
+
+/// GET /api/webhooks/events - Events, optionally by status, event type, and
+/// source
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/events",
+    tag = "webhooks",
+    summary = "List received webhook events",
+    params(
+        PageParams,
+        WebhookEventQuery
+    ),
+    responses((status = 200, body = Page<serde_json::Value>))
+)]
+pub async fn list_webhook_events(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageParams>,
+    Query(query): Query<WebhookEventQuery>,
+) -> Result<Json<Page<WebhookEventSummary>>, ApiError> {
+    let status = query
+        .status
+        .as_deref()
+        .map(WebhookEventStatus::from_str)
+        .transpose()
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+
+    let events = state
+        .db
+        .list_webhook_events_page(
+            status,
+            query.event_type.as_deref(),
+            query.source.as_deref(),
+            &page.query()?,
+        )
+        .await
+        .map_err(db_error)?;
+    Ok(Json(events.into()))
+}
+
+/// GET /api/webhooks/events/:id - Event with its payload
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/events/{id}",
+    tag = "webhooks",
+    summary = "Get a webhook event with its payload",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = WebhookEventDetail))
+)]
+pub async fn get_webhook_event(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<WebhookEventDetail>, ApiError> {
+    let event = find_event(&state, id).await?;
+    Ok(Json(event.into()))
+}
+
+/// POST /api/webhooks/events/:id/retry - Queue a finished event again with a
+/// fresh retry budget
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/events/{id}/retry",
+    tag = "webhooks",
+    summary = "Retry a webhook event",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = WebhookEventDetail))
+)]
+pub async fn retry_webhook_event(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<WebhookEventDetail>, ApiError> {
+    let mut event = find_event(&state, id).await?;
+    if matches!(
+        event.status,
+        WebhookEventStatus::Pending | WebhookEventStatus::Processing
+    ) {
+        return Err(ApiError::conflict(format!(
+            "Webhook event is already {}",
+            event.status.as_str()
+        )));
+    }
+
+    event.requeue();
+    state
+        .db
+        .update_webhook_event(&event)
+        .await
+        .map_err(db_error)?;
+    Ok(Json(event.into()))
+}
+
+/// DELETE /api/webhooks/dead-letters - Delete every dead-lettered event
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/dead-letters",
+    tag = "webhooks",
+    summary = "Purge dead-lettered webhook events",
+    responses((status = 200, body = PurgeResponse))
+)]
+pub async fn purge_dead_letters(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<PurgeResponse>, ApiError> {
+    let deleted = state
+        .db
+        .purge_dead_letter_webhook_events()
+        .await
+        .map_err(db_error)?;
+    Ok(Json(PurgeResponse { deleted }))
+}
+
+/// GET /api/webhooks/stats - Delivery counts of each source
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/stats",
+    tag = "webhooks",
+    summary = "Get webhook delivery stats by source",
+    responses((status = 200, body = [Object]))
+)]
+pub async fn webhook_stats(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<WebhookSourceStats>>, ApiError> {
+    let stats = state
+        .db
+        .get_webhook_source_stats()
+        .await
+        .map_err(db_error)?;
+    Ok(Json(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orchestrate_core::Database;
+
+    async fn setup() -> Arc<AppState> {
+        let db = Database::in_memory().await.unwrap();
+
+        let opened = WebhookEvent::new(
+            "delivery-1".to_string(),
+            "pull_request".to_string(),
+            r#"{"action":"opened"}"#.to_string(),
+        );
+        db.insert_webhook_event(&opened).await.unwrap();
+
+        let mut dead = WebhookEvent::new(
+            "delivery-2".to_string(),
+            "merge_request".to_string(),
+            "not json".to_string(),
+        )
+        .with_source("gitlab");
+        dead.mark_poisoned("Payload is not valid JSON".to_string());
+        db.insert_webhook_event(&dead).await.unwrap();
+
+        Arc::new(AppState::new(db, None))
+    }
+
+    #[tokio::test]
+    async fn test_list_and_inspect_webhook_events() {
+        let state = setup().await;
+
+        let Json(page) = list_webhook_events(
+            State(state.clone()),
+            Query(PageParams::default()),
+            Query(WebhookEventQuery {
+                source: Some("github".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].action.as_deref(), Some("opened"));
+        assert_eq!(page.items[0].payload_bytes, 19);
+
+        // Newest first, one event per page
+        let Json(newest) = list_webhook_events(
+            State(state.clone()),
+            Query(PageParams {
+                limit: Some(1),
+                include_total: true,
+                ..Default::default()
+            }),
+            Query(WebhookEventQuery::default()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(newest.total, Some(2));
+        assert_eq!(newest.items[0].delivery_id, "delivery-2");
+        assert!(newest.items[0].action.is_none());
+        let Json(oldest) = list_webhook_events(
+            State(state.clone()),
+            Query(PageParams {
+                cursor: newest.next_cursor,
+                limit: Some(1),
+                ..Default::default()
+            }),
+            Query(WebhookEventQuery::default()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(oldest.items[0].delivery_id, "delivery-1");
+        assert!(oldest.next_cursor.is_none());
+
+        let result = list_webhook_events(
+            State(state.clone()),
+            Query(PageParams::default()),
+            Query(WebhookEventQuery {
+                status: Some("bogus".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await;
+        assert!(result.is_err());
+
+        let id = page.items[0].id;
+        let Json(detail) = get_webhook_event(State(state.clone()), Path(id))
+            .await
+            .unwrap();
+        assert_eq!(detail.payload["action"], "opened");
+
+        let Json(stats) = webhook_stats(State(state)).await.unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].source, "github");
+        assert_eq!(stats[0].pending, 1);
+        assert_eq!(stats[1].source, "gitlab");
+        assert_eq!(stats[1].dead_letter, 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_and_purge_webhook_events() {
+        let state = setup().await;
+        let pending = state
+            .db
+            .get_webhook_event_by_delivery_id("delivery-1")
+            .await
+            .unwrap()
+            .unwrap();
+        let dead = state
+            .db
+            .get_webhook_event_by_delivery_id("delivery-2")
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Events still queued can't be retried
+        let result = retry_webhook_event(State(state.clone()), Path(pending.id.unwrap())).await;
+        assert!(result.is_err());
+
+        let Json(retried) = retry_webhook_event(State(state.clone()), Path(dead.id.unwrap()))
+            .await
+            .unwrap();
+        assert_eq!(retried.event.status, WebhookEventStatus::Pending);
+        assert!(retried.event.error_message.is_none());
+        assert_eq!(retried.payload, "not json");
+
+        let Json(purged) = purge_dead_letters(State(state.clone())).await.unwrap();
+        assert_eq!(purged.deleted, 0);
+
+        let mut event = state
+            .db
+            .get_webhook_event(dead.id.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        event.mark_poisoned("still broken".to_string());
+        state.db.update_webhook_event(&event).await.unwrap();
+        let Json(purged) = purge_dead_letters(State(state.clone())).await.unwrap();
+        assert_eq!(purged.deleted, 1);
+        assert!(get_webhook_event(State(state), Path(dead.id.unwrap()))
+            .await
+            .is_err());
+    }
+}
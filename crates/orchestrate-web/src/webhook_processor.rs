@@ -1,10 +1,22 @@
 //! Webhook event processor
 //!
-//! Polls the webhook_events queue and processes events asynchronously.
-
-use orchestrate_core::{Database, WebhookConfig, WebhookEvent, WebhookEventStatus};
+//! Polls the webhook_events queue and processes events on a bounded pool of
+//! workers. Event types may be held to fewer workers than the pool, so a burst
+//! of pushes cannot delay pull request reviews and comments. Events that can
+//! never succeed, with a payload that is not JSON or a handler that panics,
+//! skip the retries and go straight to the dead letter queue.
+
+use crate::metrics::MetricsCollector;
+use chrono::Utc;
+use orchestrate_core::{
+    BusEvent, Database, OutboundEvent, OutboundEventType, PipelineDefinition,
+    PipelineRun, WebhookConfig, WebhookEvent, WebhookEventStatus,
+};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::time::{sleep, Duration};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::{sleep, timeout, Duration};
 use tracing::{debug, error, info, warn};
 
 /// Webhook event processor configuration
@@ -16,6 +28,10 @@ pub struct WebhookProcessorConfig {
     pub poll_interval_secs: u64,
     /// Maximum concurrent event processing
     pub max_concurrent: usize,
+    /// Maximum concurrent processing of single event types, e.g. "push"
+    pub event_type_limits: HashMap<String, usize>,
+    /// Seconds the handlers of one event may run before the attempt fails
+    pub handler_timeout_secs: u64,
 }
 
 impl Default for WebhookProcessorConfig {
@@ -24,24 +40,52 @@ impl Default for WebhookProcessorConfig {
             batch_size: 10,
             poll_interval_secs: 5,
             max_concurrent: 5,
+            event_type_limits: HashMap::from([("push".to_string(), 2)]),
+            handler_timeout_secs: 300,
         }
     }
 }
 
+/// How processing an event ended
+enum Outcome {
+    Completed,
+    Failed(String),
+    /// The event can never succeed
+    Poisoned(String),
+}
+
 /// Webhook event processor
+#[derive(Clone)]
 pub struct WebhookProcessor {
     database: Arc<Database>,
     config: WebhookProcessorConfig,
     webhook_config: Option<Arc<WebhookConfig>>,
+    metrics: Option<Arc<MetricsCollector>>,
+    workers: Arc<Semaphore>,
+    event_type_workers: Arc<HashMap<String, Arc<Semaphore>>>,
 }
 
 impl WebhookProcessor {
     /// Create a new webhook processor
     pub fn new(database: Arc<Database>, config: WebhookProcessorConfig) -> Self {
+        let workers = Arc::new(Semaphore::new(config.max_concurrent.max(1)));
+        let event_type_workers = config
+            .event_type_limits
+            .iter()
+            .map(|(event_type, limit)| {
+                (
+                    event_type.clone(),
+                    Arc::new(Semaphore::new((*limit).max(1))),
+                )
+            })
+            .collect();
         Self {
             database,
             config,
             webhook_config: None,
+            metrics: None,
+            workers,
+            event_type_workers: Arc::new(event_type_workers),
         }
     }
 
@@ -51,47 +95,135 @@ impl WebhookProcessor {
         self
     }
 
+    /// Record event lag and outcomes
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     /// Run the processor loop (blocking)
+    ///
+    /// Polls again as soon as a worker frees up, and otherwise every poll
+    /// interval.
     pub async fn run(&self) {
         info!(
             poll_interval_secs = self.config.poll_interval_secs,
             batch_size = self.config.batch_size,
+            max_concurrent = self.config.max_concurrent,
             "Starting webhook event processor"
         );
 
+        match self.database.requeue_processing_webhook_events().await {
+            Ok(0) => {}
+            Ok(count) => warn!(
+                count = count,
+                "Requeued webhook events interrupted by a restart"
+            ),
+            Err(e) => error!(error = %e, "Failed to requeue interrupted webhook events"),
+        }
+
+        let mut tasks = JoinSet::new();
         loop {
-            if let Err(e) = self.process_batch().await {
-                error!(error = %e, "Error processing webhook event batch");
+            while tasks.try_join_next().is_some() {}
+
+            let dispatched = match self.dispatch(&mut tasks).await {
+                Ok(dispatched) => dispatched,
+                Err(e) => {
+                    error!(error = %e, "Error dispatching webhook events");
+                    0
+                }
+            };
+            // A full batch suggests more events are waiting
+            if dispatched > 0 && dispatched as i64 >= self.config.batch_size {
+                continue;
             }
 
-            sleep(Duration::from_secs(self.config.poll_interval_secs)).await;
+            tokio::select! {
+                _ = sleep(Duration::from_secs(self.config.poll_interval_secs)) => {}
+                Some(_) = tasks.join_next(), if !tasks.is_empty() => {}
+            }
         }
     }
 
-    /// Process a batch of events
+    /// Process a batch of events, waiting for all of them to finish
     pub async fn process_batch(&self) -> orchestrate_core::Result<()> {
-        let events = self
-            .database
-            .get_pending_webhook_events(self.config.batch_size)
-            .await?;
+        let mut tasks = JoinSet::new();
+        let count = self.dispatch(&mut tasks).await?;
 
-        if events.is_empty() {
+        if count == 0 {
             debug!("No pending webhook events to process");
             return Ok(());
         }
 
-        info!(count = events.len(), "Processing webhook events");
+        info!(count = count, "Processing webhook events");
+        while tasks.join_next().await.is_some() {}
 
-        for event in events {
-            if let Err(e) = self.process_event(event).await {
-                error!(error = %e, "Failed to process webhook event");
-            }
+        Ok(())
+    }
+
+    /// Claim pending events for the free workers and start processing them
+    ///
+    /// Event types at their concurrency limit are left in the queue.
+    async fn dispatch(&self, tasks: &mut JoinSet<()>) -> orchestrate_core::Result<usize> {
+        let free = self
+            .workers
+            .available_permits()
+            .min(self.config.batch_size.max(0) as usize);
+        if free == 0 {
+            return Ok(0);
         }
 
-        Ok(())
+        let saturated: Vec<String> = self
+            .event_type_workers
+            .iter()
+            .filter(|(_, workers)| workers.available_permits() == 0)
+            .map(|(event_type, _)| event_type.clone())
+            .collect();
+        let events = self
+            .database
+            .claim_pending_webhook_events(free as i64, &saturated)
+            .await?;
+
+        let mut dispatched = 0;
+        for mut event in events {
+            let type_permit = match self.event_type_workers.get(&event.event_type) {
+                Some(workers) => match workers.clone().try_acquire_owned() {
+                    Ok(permit) => Some(permit),
+                    Err(_) => {
+                        // More of this type were claimed than it has workers
+                        event.status = WebhookEventStatus::Pending;
+                        event.updated_at = Utc::now();
+                        self.database.update_webhook_event(&event).await?;
+                        continue;
+                    }
+                },
+                None => None,
+            };
+            let permit = self
+                .workers
+                .clone()
+                .try_acquire_owned()
+                .map_err(|e| orchestrate_core::Error::Other(e.to_string()))?;
+
+            let processor = self.clone();
+            tasks.spawn(async move {
+                let _permits = (permit, type_permit);
+                if let Err(e) = processor.process_event(event).await {
+                    error!(error = %e, "Failed to process webhook event");
+                }
+            });
+            dispatched += 1;
+        }
+
+        Ok(dispatched)
     }
 
-    /// Process a single event
+    /// Process a single claimed event
+    #[tracing::instrument(
+        name = "webhook_event",
+        skip(self, event),
+        fields(delivery_id = %event.delivery_id, event_type = %event.event_type)
+    )]
     async fn process_event(&self, mut event: WebhookEvent) -> orchestrate_core::Result<()> {
         let event_id = event.id.unwrap_or(0);
 
@@ -103,13 +235,26 @@ impl WebhookProcessor {
             "Processing webhook event"
         );
 
+        if let Some(metrics) = &self.metrics {
+            if event.retry_count == 0 {
+                let wait = Utc::now().signed_duration_since(event.received_at);
+                metrics.record_webhook_wait(
+                    &event.event_type,
+                    wait.num_milliseconds().max(0) as f64 / 1000.0,
+                );
+            }
+        }
+
         // Mark as processing
         event.mark_processing();
-        self.database.update_webhook_event(&event).await?;
 
-        // Process the event with the appropriate handler
-        match self.handle_event(&event).await {
-            Ok(()) => {
+        let outcome = match serde_json::from_str::<serde_json::Value>(&event.payload) {
+            Ok(_) => self.run_handlers(&event).await,
+            Err(e) => Outcome::Poisoned(format!("Payload is not valid JSON: {}", e)),
+        };
+
+        let outcome_label = match outcome {
+            Outcome::Completed => {
                 event.mark_completed();
                 self.database.update_webhook_event(&event).await?;
                 info!(
@@ -117,8 +262,9 @@ impl WebhookProcessor {
                     delivery_id = %event.delivery_id,
                     "Webhook event processed successfully"
                 );
+                "completed"
             }
-            Err(e) => {
+            Outcome::Failed(e) => {
                 warn!(
                     event_id = event_id,
                     delivery_id = %event.delivery_id,
@@ -126,7 +272,7 @@ impl WebhookProcessor {
                     retry_count = event.retry_count,
                     "Webhook event processing failed"
                 );
-                event.mark_failed(e.to_string());
+                event.mark_failed(e);
                 self.database.update_webhook_event(&event).await?;
 
                 if event.status == WebhookEventStatus::DeadLetter {
@@ -135,15 +281,71 @@ impl WebhookProcessor {
                         delivery_id = %event.delivery_id,
                         "Webhook event moved to dead letter queue after max retries"
                     );
+                    "dead_letter"
+                } else {
+                    "retried"
                 }
             }
+            Outcome::Poisoned(e) => {
+                error!(
+                    event_id = event_id,
+                    delivery_id = %event.delivery_id,
+                    error = %e,
+                    "Poison webhook event moved to dead letter queue"
+                );
+                event.mark_poisoned(e);
+                self.database.update_webhook_event(&event).await?;
+                "poisoned"
+            }
+        };
+        if let Some(metrics) = &self.metrics {
+            metrics.record_webhook_outcome(&event.event_type, outcome_label);
         }
 
+        orchestrate_core::event_bus::emit(BusEvent::new(
+            "webhook.processed",
+            serde_json::json!({
+                "event_id": event.id,
+                "delivery_id": event.delivery_id,
+                "event_type": event.event_type,
+                "status": event.status.as_str(),
+                "error": event.error_message,
+            }),
+        ));
+
         Ok(())
     }
 
+    /// Run the handlers of an event in their own task, so a panic or a hang
+    /// fails the event instead of the worker
+    async fn run_handlers(&self, event: &WebhookEvent) -> Outcome {
+        let processor = self.clone();
+        let attempt = event.clone();
+        let handlers = tokio::spawn(async move { processor.handle_event(&attempt).await });
+        let abort = handlers.abort_handle();
+
+        let limit = Duration::from_secs(self.config.handler_timeout_secs);
+        match timeout(limit, handlers).await {
+            Ok(Ok(Ok(()))) => Outcome::Completed,
+            Ok(Ok(Err(e))) => Outcome::Failed(e.to_string()),
+            Ok(Err(e)) if e.is_panic() => Outcome::Poisoned(format!("Handler panicked: {}", e)),
+            Ok(Err(e)) => Outcome::Failed(e.to_string()),
+            Err(_) => {
+                abort.abort();
+                Outcome::Failed(format!(
+                    "Handlers timed out after {}s",
+                    self.config.handler_timeout_secs
+                ))
+            }
+        }
+    }
+
     /// Handle event processing
     async fn handle_event(&self, event: &WebhookEvent) -> orchestrate_core::Result<()> {
+        // Pipelines declare their own triggers, independent of the handler config
+        self.trigger_pipelines(event).await?;
+        self.publish_pr_merged(event).await?;
+
         // If config is set, check if event should be handled
         if let Some(config) = &self.webhook_config {
             // Build full event name with action
@@ -172,9 +374,13 @@ impl WebhookProcessor {
         }
 
         match event.event_type.as_str() {
-            "pull_request" => {
-                crate::event_handlers::handle_pr_opened(self.database.clone(), event).await
-            }
+            "pull_request" => match self.get_event_key(event).as_str() {
+                "pull_request.enqueued" | "pull_request.dequeued" => {
+                    crate::event_handlers::handle_merge_queue_event(self.database.clone(), event)
+                        .await
+                }
+                _ => crate::event_handlers::handle_pr_opened(self.database.clone(), event).await,
+            },
             "pull_request_review" => {
                 crate::event_handlers::handle_pr_review_submitted(self.database.clone(), event)
                     .await
@@ -188,6 +394,9 @@ impl WebhookProcessor {
             "issues" => {
                 crate::event_handlers::handle_issue_opened(self.database.clone(), event).await
             }
+            "issue_comment" => {
+                crate::event_handlers::handle_issue_comment(self.database.clone(), event).await
+            }
             _ => {
                 // Unknown event type - not an error, just skip
                 debug!(event_type = %event.event_type, "No handler for event type");
@@ -196,6 +405,101 @@ impl WebhookProcessor {
         }
     }
 
+    /// Start a run of every enabled pipeline with a trigger matching the event
+    ///
+    /// Payload fields named by the matching trigger become run variables, and
+    /// the payload itself is kept on the run for stage condition expressions.
+    /// Runs build the pushed commit or the pull request's head commit, so
+    /// their stages can reuse cached results.
+    /// Runs are admitted under the pipeline's concurrency limit, so a burst of
+    /// events may queue, cancel, or reject runs.
+    async fn trigger_pipelines(&self, event: &WebhookEvent) -> orchestrate_core::Result<()> {
+        let payload: serde_json::Value = match serde_json::from_str(&event.payload) {
+            Ok(payload) => payload,
+            Err(_) => return Ok(()),
+        };
+        let event_key = self.get_event_key(event);
+
+        for pipeline in self.database.list_enabled_pipelines().await? {
+            let definition = match PipelineDefinition::from_yaml_str(&pipeline.definition) {
+                Ok(definition) => definition,
+                Err(e) => {
+                    warn!(
+                        pipeline = %pipeline.name,
+                        error = %e,
+                        "Skipping pipeline with invalid definition"
+                    );
+                    continue;
+                }
+            };
+
+            let trigger = definition
+                .triggers
+                .iter()
+                .find(|trigger| trigger.matches_event(&event_key, &payload));
+            let (Some(pipeline_id), Some(trigger)) = (pipeline.id, trigger) else {
+                continue;
+            };
+
+            let mut run = PipelineRun::new(pipeline_id, Some(event_key.clone()))
+                .with_variables(trigger.payload_variables(&payload))
+                .with_trigger_payload(payload.clone());
+            if let Some(commit_sha) = payload_commit_sha(&payload) {
+                run = run.with_commit_sha(commit_sha);
+            }
+            let admission = self
+                .database
+                .admit_pipeline_run(&run, definition.concurrency.as_ref())
+                .await?;
+
+            info!(
+                pipeline = %pipeline.name,
+                run_id = admission.run_id(),
+                admission = ?admission,
+                event_key = %event_key,
+                delivery_id = %event.delivery_id,
+                "Webhook event triggered pipeline run"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Log outbound webhook deliveries when the event reports a merged pull request
+    async fn publish_pr_merged(&self, event: &WebhookEvent) -> orchestrate_core::Result<()> {
+        if event.event_type != "pull_request" {
+            return Ok(());
+        }
+        let Ok(payload) = serde_json::from_str::<serde_json::Value>(&event.payload) else {
+            return Ok(());
+        };
+        let pr = &payload["pull_request"];
+        if payload["action"] != "closed" || pr["merged"] != true {
+            return Ok(());
+        }
+
+        let merged = OutboundEvent::new(
+            OutboundEventType::PrMerged,
+            serde_json::json!({
+                "number": pr["number"],
+                "title": pr["title"],
+                "url": pr["html_url"],
+                "branch": pr["head"]["ref"],
+                "base_branch": pr["base"]["ref"],
+                "repository": pr["base"]["repo"]["full_name"],
+                "merged_by": pr["merged_by"]["login"],
+            }),
+        );
+        let enqueued = self.database.enqueue_outbound_event(&merged).await?;
+        debug!(
+            delivery_id = %event.delivery_id,
+            enqueued = enqueued,
+            "Merged pull request enqueued for outbound webhooks"
+        );
+
+        Ok(())
+    }
+
     /// Get the event key for configuration lookup (e.g., "pull_request.opened")
     fn get_event_key(&self, event: &WebhookEvent) -> String {
         // Parse payload to get action
@@ -320,6 +624,15 @@ impl WebhookProcessor {
     }
 }
 
+/// Commit a webhook payload refers to: a pull request's head commit, or the
+/// commit a push moved the branch to (none when the push deleted the branch)
+fn payload_commit_sha(payload: &serde_json::Value) -> Option<&str> {
+    payload["pull_request"]["head"]["sha"]
+        .as_str()
+        .or_else(|| payload["after"].as_str())
+        .filter(|sha| !sha.is_empty() && sha.chars().any(|c| c != '0'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -444,6 +757,81 @@ mod tests {
         assert_eq!(agents.len(), 15);
     }
 
+    #[tokio::test]
+    async fn test_processor_triggers_pipeline_on_labeled_pr() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        let definition = r#"
+name: release
+description: Release when labeled
+triggers:
+  - event: pull_request.labeled
+    labels: [release]
+    variables:
+      pr_number: pull_request.number
+      head_branch: pull_request.head.ref
+stages:
+  - name: release
+    agent: deployer
+    task: Release PR ${pr_number}
+"#;
+        let pipeline =
+            orchestrate_core::Pipeline::new("release".to_string(), definition.to_string());
+        let pipeline_id = database.insert_pipeline(&pipeline).await.unwrap();
+
+        for (i, label) in ["docs", "release"].iter().enumerate() {
+            let payload = serde_json::json!({
+                "action": "labeled",
+                "label": { "name": label },
+                "pull_request": {
+                    "number": 7,
+                    "head": {
+                        "ref": "feature/ship-it",
+                        "sha": "0d1e2f3a",
+                        "repo": { "fork": false }
+                    }
+                },
+                "repository": { "full_name": "owner/repo" }
+            })
+            .to_string();
+            let event = WebhookEvent::new(
+                format!("delivery-labeled-{}", i),
+                "pull_request".to_string(),
+                payload,
+            );
+            database.insert_webhook_event(&event).await.unwrap();
+        }
+
+        let processor = WebhookProcessor::new(database.clone(), WebhookProcessorConfig::default());
+        processor.process_batch().await.unwrap();
+
+        let runs = database.list_pipeline_runs(pipeline_id).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(
+            runs[0].trigger_event.as_deref(),
+            Some("pull_request.labeled")
+        );
+        assert_eq!(runs[0].variables["pr_number"], "7");
+        assert_eq!(runs[0].variables["head_branch"], "feature/ship-it");
+        assert_eq!(runs[0].commit_sha.as_deref(), Some("0d1e2f3a"));
+    }
+
+    #[test]
+    fn test_payload_commit_sha() {
+        let push = serde_json::json!({ "ref": "refs/heads/main", "after": "abc123" });
+        assert_eq!(payload_commit_sha(&push), Some("abc123"));
+
+        let deleted = serde_json::json!({ "ref": "refs/heads/old", "after": "0".repeat(40) });
+        assert_eq!(payload_commit_sha(&deleted), None);
+
+        let pull_request = serde_json::json!({
+            "pull_request": { "head": { "sha": "def456" } }
+        });
+        assert_eq!(payload_commit_sha(&pull_request), Some("def456"));
+
+        assert_eq!(payload_commit_sha(&serde_json::json!({})), None);
+    }
+
     #[tokio::test]
     async fn test_processor_handles_empty_queue() {
         let database = Arc::new(Database::in_memory().await.unwrap());
@@ -624,4 +1012,165 @@ mod tests {
         let agents = database.list_agents().await.unwrap();
         assert_eq!(agents.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_processor_enqueues_outbound_pr_merged() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let webhook = orchestrate_core::OutboundWebhook::new(
+            "https://example.com/hook".to_string(),
+            vec!["pr.merged".to_string()],
+        );
+        database.insert_outbound_webhook(&webhook).await.unwrap();
+
+        for (delivery_id, merged) in [("delivery-merged", true), ("delivery-closed", false)] {
+            let payload = serde_json::json!({
+                "action": "closed",
+                "pull_request": {
+                    "number": 42,
+                    "title": "Add retries",
+                    "merged": merged,
+                    "head": { "ref": "feature/retries" },
+                    "base": { "ref": "main", "repo": { "full_name": "owner/repo" } },
+                    "merged_by": { "login": "octocat" }
+                }
+            })
+            .to_string();
+            let event =
+                WebhookEvent::new(delivery_id.to_string(), "pull_request".to_string(), payload);
+            database.insert_webhook_event(&event).await.unwrap();
+        }
+
+        let processor = WebhookProcessor::new(database.clone(), WebhookProcessorConfig::default());
+        processor.process_batch().await.unwrap();
+
+        let deliveries = database.list_outbound_deliveries(None, 10).await.unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].event_type, "pr.merged");
+
+        let payload: serde_json::Value = serde_json::from_str(&deliveries[0].payload).unwrap();
+        assert_eq!(payload["data"]["number"], 42);
+        assert_eq!(payload["data"]["repository"], "owner/repo");
+        assert_eq!(payload["data"]["merged_by"], "octocat");
+    }
+
+    #[tokio::test]
+    async fn test_processor_limits_event_type_concurrency() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+
+        // A burst of pushes arrives before the events queued behind it
+        for i in 1..=3 {
+            let payload = serde_json::json!({ "ref": format!("refs/heads/feature-{}", i) });
+            let event = WebhookEvent::new(
+                format!("delivery-push-{}", i),
+                "push".to_string(),
+                payload.to_string(),
+            );
+            database.insert_webhook_event(&event).await.unwrap();
+        }
+        for i in 1..=2 {
+            let event = WebhookEvent::new(
+                format!("delivery-ping-{}", i),
+                "ping".to_string(),
+                "{}".to_string(),
+            );
+            database.insert_webhook_event(&event).await.unwrap();
+        }
+
+        let config = WebhookProcessorConfig {
+            event_type_limits: HashMap::from([("push".to_string(), 1)]),
+            ..Default::default()
+        };
+        let processor = WebhookProcessor::new(database.clone(), config);
+        processor.process_batch().await.unwrap();
+
+        let completed = database
+            .get_webhook_events_by_status(WebhookEventStatus::Completed, 10)
+            .await
+            .unwrap();
+        assert_eq!(completed.len(), 3);
+        assert_eq!(
+            completed.iter().filter(|e| e.event_type == "push").count(),
+            1
+        );
+        let pending = database
+            .get_webhook_events_by_status(WebhookEventStatus::Pending, 10)
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 2);
+        assert!(pending.iter().all(|e| e.event_type == "push"));
+
+        processor.process_batch().await.unwrap();
+        processor.process_batch().await.unwrap();
+        let completed = database
+            .count_webhook_events_by_status(WebhookEventStatus::Completed)
+            .await
+            .unwrap();
+        assert_eq!(completed, 5);
+    }
+
+    #[tokio::test]
+    async fn test_processor_dead_letters_poison_events() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let event = WebhookEvent::new(
+            "delivery-poison".to_string(),
+            "pull_request".to_string(),
+            "not json".to_string(),
+        );
+        let id = database.insert_webhook_event(&event).await.unwrap();
+
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let processor = WebhookProcessor::new(database.clone(), WebhookProcessorConfig::default())
+            .with_metrics(metrics.clone());
+        processor.process_batch().await.unwrap();
+
+        // No retries for an event that can never succeed
+        let event = database.get_webhook_event(id).await.unwrap().unwrap();
+        assert_eq!(event.status, WebhookEventStatus::DeadLetter);
+        assert_eq!(event.retry_count, 0);
+        assert!(event.error_message.unwrap().contains("not valid JSON"));
+
+        let families = metrics.metric_families();
+        let outcomes = families
+            .iter()
+            .find(|f| f.get_name() == "orchestrate_webhook_events_total")
+            .unwrap();
+        let labels = outcomes.get_metric()[0].get_label();
+        assert_eq!(labels[0].get_value(), "pull_request");
+        assert_eq!(labels[1].get_value(), "poisoned");
+        assert!(families
+            .iter()
+            .any(|f| f.get_name() == "orchestrate_webhook_wait_seconds"));
+    }
+
+    #[tokio::test]
+    async fn test_processor_requeues_interrupted_events() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let event = WebhookEvent::new(
+            "delivery-interrupted".to_string(),
+            "ping".to_string(),
+            "{}".to_string(),
+        );
+        database.insert_webhook_event(&event).await.unwrap();
+
+        // Claimed by a processor that stopped before finishing
+        let claimed = database.claim_pending_webhook_events(10, &[]).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].status, WebhookEventStatus::Processing);
+        assert!(database
+            .claim_pending_webhook_events(10, &[])
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(database.requeue_processing_webhook_events().await.unwrap(), 1);
+        let processor = WebhookProcessor::new(database.clone(), WebhookProcessorConfig::default());
+        processor.process_batch().await.unwrap();
+        assert_eq!(
+            database
+                .count_webhook_events_by_status(WebhookEventStatus::Completed)
+                .await
+                .unwrap(),
+            1
+        );
+    }
 }
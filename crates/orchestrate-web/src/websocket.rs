@@ -1,14 +1,27 @@
 //! WebSocket handling for real-time updates
+//!
+//! Clients choose what they receive by subscribing to topics:
+//! - `agent:<id>` (or a bare agent id): state changes, messages, and events of
+//!   one agent
+//! - `pipeline_run:<id>`: stage and status events of one pipeline run
+//! - `event:<kind>`: feed events whose kind starts with `<kind>`, e.g.
+//!   `event:webhook` or `event:pipeline.run_updated`; `event:*` for all
+//!
+//! `{"type": "subscribe", "channels": [...]}` adds topics and
+//! `{"type": "unsubscribe", "channels": [...]}` removes them; both are answered
+//! with the client's current topics. Agent and feed messages are only sent to
+//! matching subscribers, while system status and replies go to every client.
 
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::extract::State;
 use axum::response::IntoResponse;
 use futures::{SinkExt, StreamExt};
-use orchestrate_core::Database;
+use orchestrate_core::{BusEvent, Database};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::BTreeSet;
+use std::fmt;
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::broadcast::{self, error::RecvError};
 use uuid::Uuid;
 
 /// WebSocket message types
@@ -30,8 +43,14 @@ pub enum WsMessage {
         total_agents: usize,
         running_agents: usize,
     },
+    /// Event from the structured event feed
+    Event { event: BusEvent },
     /// Client subscription request
     Subscribe { channels: Vec<String> },
+    /// Client request to drop subscriptions
+    Unsubscribe { channels: Vec<String> },
+    /// Topics the client is subscribed to
+    Subscribed { channels: Vec<String> },
     /// Client message to agent
     SendMessage { agent_id: String, content: String },
     /// Error response
@@ -40,6 +59,96 @@ pub enum WsMessage {
     Success { message: String },
 }
 
+/// A stream of messages a client can subscribe to
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Topic {
+    Agent(String),
+    PipelineRun(i64),
+    /// Event kind prefix; empty matches all events
+    EventKind(String),
+}
+
+impl Topic {
+    pub fn parse(channel: &str) -> Result<Self, String> {
+        let channel = channel.trim();
+        match channel.split_once(':') {
+            Some(("agent", id)) if !id.is_empty() => Ok(Self::Agent(id.to_string())),
+            Some(("pipeline_run", id)) => id
+                .parse()
+                .map(Self::PipelineRun)
+                .map_err(|_| format!("Invalid pipeline run id in topic '{}'", channel)),
+            Some(("event", "*")) => Ok(Self::EventKind(String::new())),
+            Some(("event", kind)) if !kind.is_empty() => Ok(Self::EventKind(kind.to_string())),
+            // Bare agent ids were the only topics before topic prefixes
+            None if !channel.is_empty() => Ok(Self::Agent(channel.to_string())),
+            _ => Err(format!("Unknown topic '{}'", channel)),
+        }
+    }
+
+    fn matches_event(&self, event: &BusEvent) -> bool {
+        match self {
+            Self::Agent(id) => event.agent_id.as_deref() == Some(id.as_str()),
+            Self::PipelineRun(id) => event.data["run_id"].as_i64() == Some(*id),
+            Self::EventKind(kind) => {
+                kind.is_empty()
+                    || event.kind == *kind
+                    || event.kind.starts_with(&format!("{}.", kind))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Agent(id) => write!(f, "agent:{}", id),
+            Self::PipelineRun(id) => write!(f, "pipeline_run:{}", id),
+            Self::EventKind(kind) if kind.is_empty() => write!(f, "event:*"),
+            Self::EventKind(kind) => write!(f, "event:{}", kind),
+        }
+    }
+}
+
+/// Topics a client is subscribed to
+#[derive(Debug, Clone, Default)]
+pub struct Subscriptions {
+    topics: BTreeSet<Topic>,
+}
+
+impl Subscriptions {
+    /// Add topics, failing without changes if any is invalid
+    pub fn subscribe(&mut self, channels: &[String]) -> Result<(), String> {
+        let topics = channels
+            .iter()
+            .map(|c| Topic::parse(c))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.topics.extend(topics);
+        Ok(())
+    }
+
+    /// Remove topics; unknown ones are ignored
+    pub fn unsubscribe(&mut self, channels: &[String]) {
+        for topic in channels.iter().filter_map(|c| Topic::parse(c).ok()) {
+            self.topics.remove(&topic);
+        }
+    }
+
+    pub fn channels(&self) -> Vec<String> {
+        self.topics.iter().map(Topic::to_string).collect()
+    }
+
+    /// Whether a broadcast message should be sent to this client
+    pub fn matches(&self, message: &WsMessage) -> bool {
+        match message {
+            WsMessage::AgentState { agent_id, .. } | WsMessage::AgentMessage { agent_id, .. } => {
+                self.topics.contains(&Topic::Agent(agent_id.clone()))
+            }
+            WsMessage::Event { event } => self.topics.iter().any(|t| t.matches_event(event)),
+            _ => true,
+        }
+    }
+}
+
 /// WebSocket state for managing connections
 pub struct WsState {
     pub broadcast_tx: broadcast::Sender<WsMessage>,
@@ -59,6 +168,13 @@ impl WsState {
 }
 
 /// WebSocket handler with state
+#[utoipa::path(
+    get,
+    path = "/ws",
+    tag = "live",
+    summary = "Subscribe to events over a WebSocket",
+    responses((status = 101, description = "Switched to the WebSocket protocol"))
+)]
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
     State(state): State<Arc<WsState>>,
@@ -69,76 +185,90 @@ pub async fn ws_handler(
 async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
     let (mut sender, mut receiver) = socket.split();
     let mut rx = state.broadcast_tx.subscribe();
-    let db = state.db.clone();
-
-    // Track which agents this client is subscribed to
-    let subscribed_agents: Arc<tokio::sync::RwLock<HashSet<String>>> =
-        Arc::new(tokio::sync::RwLock::new(HashSet::new()));
-    let subscribed_agents_clone = subscribed_agents.clone();
-
-    // Spawn task to forward broadcast messages to client
-    let send_task = tokio::spawn(async move {
-        while let Ok(msg) = rx.recv().await {
-            // Check if client is subscribed to this agent
-            let should_send = match &msg {
-                WsMessage::AgentState { agent_id, .. }
-                | WsMessage::AgentMessage { agent_id, .. } => {
-                    let subscribed = subscribed_agents_clone.read().await;
-                    subscribed.is_empty() || subscribed.contains(agent_id)
+    let mut events = orchestrate_core::event_bus::subscribe();
+    let mut subscriptions = Subscriptions::default();
+
+    loop {
+        let outgoing = tokio::select! {
+            incoming = receiver.next() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    handle_client_message(&state, &mut subscriptions, text.as_str()).await
                 }
-                // Always send system status and other messages
-                _ => true,
-            };
-
-            if should_send {
-                if let Ok(json) = serde_json::to_string(&msg) {
-                    if sender.send(Message::Text(json.into())).await.is_err() {
-                        break;
-                    }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => None,
+            },
+            msg = rx.recv() => match msg {
+                Ok(msg) => Some(msg).filter(|m| subscriptions.matches(m)),
+                Err(RecvError::Lagged(_)) => None,
+                Err(RecvError::Closed) => break,
+            },
+            event = events.recv() => match event {
+                Ok(event) => Some(WsMessage::Event { event }).filter(|m| subscriptions.matches(m)),
+                Err(RecvError::Lagged(_)) => None,
+                Err(RecvError::Closed) => break,
+            },
+        };
+
+        if let Some(msg) = outgoing {
+            if let Ok(json) = serde_json::to_string(&msg) {
+                if sender.send(Message::Text(json)).await.is_err() {
+                    break;
                 }
             }
         }
-    });
-
-    // Handle incoming messages from client
-    while let Some(msg) = receiver.next().await {
-        if let Ok(Message::Text(text)) = msg {
-            if let Ok(ws_msg) = serde_json::from_str::<WsMessage>(&text.to_string()) {
-                match ws_msg {
-                    WsMessage::Subscribe { channels } => {
-                        // Update subscriptions
-                        let mut subscribed = subscribed_agents.write().await;
-                        subscribed.clear();
-                        for channel in channels {
-                            // Channel format: "agent:<uuid>" or just "<uuid>"
-                            let agent_id = channel.strip_prefix("agent:").unwrap_or(&channel);
-                            subscribed.insert(agent_id.to_string());
-                        }
-                    }
-                    WsMessage::SendMessage { agent_id, content } => {
-                        // Route message to agent via database
-                        match handle_send_message(&db, &agent_id, &content).await {
-                            Ok(_) => {
-                                // Broadcast the new message to all subscribers
-                                let _ = state.broadcast_tx.send(WsMessage::AgentMessage {
-                                    agent_id,
-                                    role: "user".to_string(),
-                                    content,
-                                });
-                            }
-                            Err(e) => {
-                                // Log error but don't disconnect
-                                tracing::warn!("Failed to send message to agent: {}", e);
-                            }
-                        }
-                    }
-                    _ => {}
+    }
+}
+
+/// Handle a message from a client, returning the reply to send back
+async fn handle_client_message(
+    state: &WsState,
+    subscriptions: &mut Subscriptions,
+    text: &str,
+) -> Option<WsMessage> {
+    let ws_msg = match serde_json::from_str::<WsMessage>(text) {
+        Ok(msg) => msg,
+        Err(e) => {
+            return Some(WsMessage::Error {
+                message: format!("Invalid message: {}", e),
+            })
+        }
+    };
+
+    match ws_msg {
+        WsMessage::Subscribe { channels } => Some(match subscriptions.subscribe(&channels) {
+            Ok(()) => WsMessage::Subscribed {
+                channels: subscriptions.channels(),
+            },
+            Err(message) => WsMessage::Error { message },
+        }),
+        WsMessage::Unsubscribe { channels } => {
+            subscriptions.unsubscribe(&channels);
+            Some(WsMessage::Subscribed {
+                channels: subscriptions.channels(),
+            })
+        }
+        WsMessage::SendMessage { agent_id, content } => {
+            // Route message to agent via database
+            match handle_send_message(&state.db, &agent_id, &content).await {
+                Ok(_) => {
+                    // Broadcast the new message to the agent's subscribers
+                    let _ = state.broadcast_tx.send(WsMessage::AgentMessage {
+                        agent_id,
+                        role: "user".to_string(),
+                        content,
+                    });
+                    None
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to send message to agent: {}", e);
+                    Some(WsMessage::Error {
+                        message: e.to_string(),
+                    })
                 }
             }
         }
+        _ => None,
     }
-
-    send_task.abort();
 }
 
 /// Handle sending a message to an agent
@@ -173,3 +303,64 @@ async fn handle_send_message(db: &Database, agent_id: &str, content: &str) -> an
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn channels(topics: &[&str]) -> Vec<String> {
+        topics.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_topic_parse() {
+        assert_eq!(Topic::parse("agent:a1"), Ok(Topic::Agent("a1".to_string())));
+        assert_eq!(Topic::parse("a1"), Ok(Topic::Agent("a1".to_string())));
+        assert_eq!(Topic::parse("pipeline_run:7"), Ok(Topic::PipelineRun(7)));
+        assert_eq!(Topic::parse("event:*"), Ok(Topic::EventKind(String::new())));
+        assert!(Topic::parse("pipeline_run:x").is_err());
+        assert!(Topic::parse("queue:1").is_err());
+        assert_eq!(Topic::parse("event:*").unwrap().to_string(), "event:*");
+    }
+
+    #[test]
+    fn test_subscriptions_filter_messages() {
+        let mut subs = Subscriptions::default();
+        let agent_state = WsMessage::AgentState {
+            agent_id: "a1".to_string(),
+            state: "running".to_string(),
+        };
+        let run_event = WsMessage::Event {
+            event: BusEvent::new("pipeline.stage_updated", json!({"run_id": 7})),
+        };
+        let webhook_event = WsMessage::Event {
+            event: BusEvent::new("webhook.processed", json!({})),
+        };
+        let status = WsMessage::SystemStatus {
+            total_agents: 1,
+            running_agents: 1,
+        };
+
+        assert!(!subs.matches(&agent_state));
+        assert!(!subs.matches(&run_event));
+        assert!(subs.matches(&status));
+
+        subs.subscribe(&channels(&["agent:a1", "pipeline_run:7"]))
+            .unwrap();
+        assert!(subs.matches(&agent_state));
+        assert!(subs.matches(&run_event));
+        assert!(!subs.matches(&webhook_event));
+
+        assert!(subs
+            .subscribe(&channels(&["event:webhook", "bogus:1"]))
+            .is_err());
+        assert!(!subs.matches(&webhook_event));
+
+        subs.subscribe(&channels(&["event:webhook"])).unwrap();
+        subs.unsubscribe(&channels(&["pipeline_run:7"]));
+        assert!(subs.matches(&webhook_event));
+        assert!(!subs.matches(&run_event));
+        assert_eq!(subs.channels(), channels(&["agent:a1", "event:webhook"]));
+    }
+}
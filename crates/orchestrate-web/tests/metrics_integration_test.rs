@@ -160,3 +160,63 @@ async fn test_metrics_content_type() {
     });
     assert!(has_prometheus_format);
 }
+
+#[tokio::test]
+async fn test_metrics_endpoint_openmetrics_format() {
+    // Setup
+    let db = Database::in_memory().await.unwrap();
+    let state = Arc::new(AppState::new(db, None));
+    let app = create_router(state);
+
+    // Request OpenMetrics, as Prometheus does when exemplar storage is enabled
+    let request = Request::builder()
+        .uri("/metrics")
+        .header("accept", "application/openmetrics-text; version=1.0.0")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let content_type = response.headers()["content-type"].to_str().unwrap();
+    assert!(content_type.starts_with("application/openmetrics-text"));
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body_str = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body_str.ends_with("# EOF\n"));
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint_records_api_latency() {
+    // Setup
+    let db = Database::in_memory().await.unwrap();
+    let state = Arc::new(AppState::new(db, None));
+    let app = create_router(state);
+
+    // Make an API request
+    let request = Request::builder()
+        .uri("/api/agents")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Scrape metrics
+    let request = Request::builder()
+        .uri("/metrics")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body_str = String::from_utf8(body.to_vec()).unwrap();
+
+    // Requests are labelled by route pattern and status
+    assert!(body_str.contains(
+        "orchestrate_http_requests_total{method=\"GET\",path=\"/api/agents\",status=\"200\"} 1"
+    ));
+    assert!(body_str.contains(
+        "orchestrate_http_request_duration_seconds_count{method=\"GET\",path=\"/api/agents\",status=\"200\"} 1"
+    ));
+}
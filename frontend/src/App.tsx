@@ -10,6 +10,14 @@ import { PipelineNew } from './pages/PipelineNew';
 import { ScheduleList } from './pages/ScheduleList';
 import { Monitoring } from './pages/Monitoring';
 import { AutonomousProcessing } from './pages/AutonomousProcessing';
+import { Costs } from './pages/Costs';
+import { Approvals } from './pages/Approvals';
+import { Chat } from './pages/Chat';
+import { Board } from './pages/Board';
+import { Incidents } from './pages/Incidents';
+import { IncidentDetail } from './pages/IncidentDetail';
+import { Experiments } from './pages/Experiments';
+import { ExperimentDetail } from './pages/ExperimentDetail';
 
 function App() {
   return (
@@ -21,12 +29,21 @@ function App() {
             <Route path="/" element={<Dashboard />} />
             <Route path="/agents" element={<AgentList />} />
             <Route path="/agents/:id" element={<AgentDetail />} />
+            <Route path="/chat" element={<Chat />} />
+            <Route path="/chat/:id" element={<Chat />} />
             <Route path="/pipelines" element={<PipelineList />} />
             <Route path="/pipelines/new" element={<PipelineNew />} />
             <Route path="/pipelines/:name" element={<PipelineDetail />} />
             <Route path="/pipelines/:name/runs/:runId" element={<PipelineRunDetail />} />
+            <Route path="/approvals" element={<Approvals />} />
+            <Route path="/board" element={<Board />} />
             <Route path="/schedules" element={<ScheduleList />} />
             <Route path="/monitoring" element={<Monitoring />} />
+            <Route path="/incidents" element={<Incidents />} />
+            <Route path="/incidents/:id" element={<IncidentDetail />} />
+            <Route path="/experiments" element={<Experiments />} />
+            <Route path="/experiments/:id" element={<ExperimentDetail />} />
+            <Route path="/costs" element={<Costs />} />
             <Route path="/autonomous" element={<AutonomousProcessing />} />
           </Routes>
         </main>
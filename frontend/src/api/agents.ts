@@ -1,8 +1,17 @@
-import { apiRequest } from './client';
-import type { Agent, CreateAgentRequest, Message, SystemStatus } from './types';
+import { apiList, apiRequest } from './client';
+import type {
+  Agent,
+  AgentTypeDefinition,
+  CreateAgentRequest,
+  CreateStoryRequest,
+  CreatedStory,
+  Message,
+  QueuedPullRequest,
+  SystemStatus,
+} from './types';
 
 export async function listAgents(): Promise<Agent[]> {
-  return apiRequest<Agent[]>('/agents');
+  return apiList<Agent>('/agents');
 }
 
 export async function getAgent(id: string): Promise<Agent> {
@@ -29,19 +38,43 @@ export async function terminateAgent(id: string): Promise<Agent> {
 }
 
 export async function getMessages(id: string): Promise<Message[]> {
-  return apiRequest<Message[]>(`/agents/${id}/messages`);
+  return apiList<Message>(`/agents/${id}/messages`);
 }
 
 export async function sendMessage(
   id: string,
   content: string
-): Promise<void> {
-  return apiRequest(`/agents/${id}/message`, {
+): Promise<Message> {
+  return apiRequest<Message>(`/agents/${id}/message`, {
     method: 'POST',
     body: { content },
   });
 }
 
+export async function listAgentTypes(): Promise<AgentTypeDefinition[]> {
+  return apiRequest<AgentTypeDefinition[]>('/agent-types');
+}
+
+export async function createStoryFromAgent(
+  id: string,
+  data: CreateStoryRequest
+): Promise<CreatedStory> {
+  return apiRequest<CreatedStory>(`/agents/${id}/story`, {
+    method: 'POST',
+    body: data,
+  });
+}
+
+export async function createPrFromAgent(
+  id: string,
+  title?: string
+): Promise<QueuedPullRequest> {
+  return apiRequest<QueuedPullRequest>(`/agents/${id}/pr`, {
+    method: 'POST',
+    body: { title },
+  });
+}
+
 export async function getSystemStatus(): Promise<SystemStatus> {
   return apiRequest<SystemStatus>('/status');
 }
@@ -1,7 +1,8 @@
 // Autonomous Processing API Client
 // Epic 016: Autonomous Epic Processing - Story 16
 
-import { apiRequest } from './client';
+import { apiList, apiRequest } from './client';
+import type { Page } from './types';
 
 // ==================== Types ====================
 
@@ -138,7 +139,7 @@ export async function stopAutoProcess(): Promise<ActionResponse> {
 
 export async function listStuckAgents(sessionId?: string): Promise<StuckAgent[]> {
   const params = sessionId ? `?session_id=${sessionId}` : '';
-  return apiRequest<StuckAgent[]>(`/epic/stuck-agents${params}`);
+  return apiList<StuckAgent>(`/epic/stuck-agents${params}`);
 }
 
 export async function unblockSession(
@@ -159,7 +160,7 @@ export async function listEdgeCases(
   if (sessionId) params.append('session_id', sessionId);
   if (status) params.append('status', status);
   const queryString = params.toString();
-  return apiRequest<EdgeCase[]>(`/epic/edge-cases${queryString ? `?${queryString}` : ''}`);
+  return apiList<EdgeCase>(`/epic/edge-cases${queryString ? `?${queryString}` : ''}`);
 }
 
 export async function resolveEdgeCase(
@@ -180,7 +181,10 @@ export async function listSessions(
   if (limit) params.append('limit', limit.toString());
   if (status) params.append('status', status);
   const queryString = params.toString();
-  return apiRequest<Session[]>(`/epic/sessions${queryString ? `?${queryString}` : ''}`);
+  const page = await apiRequest<Page<Session>>(
+    `/epic/sessions${queryString ? `?${queryString}` : ''}`
+  );
+  return page.items;
 }
 
 export async function getSession(id: string): Promise<Session> {
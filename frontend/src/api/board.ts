@@ -0,0 +1,32 @@
+import { apiRequest } from './client';
+import type { Board, BoardStatus, BoardTransition } from './types';
+
+// GET /api/board - Epics and stories, optionally of one epic
+export async function getBoard(epicId?: string): Promise<Board> {
+  const query = epicId ? `?epic_id=${encodeURIComponent(epicId)}` : '';
+  return apiRequest<Board>(`/board${query}`);
+}
+
+// POST /api/epics/:id/transition - Move an epic, starting its BMAD orchestrator
+export async function transitionEpic(id: string, status: BoardStatus): Promise<BoardTransition> {
+  return apiRequest<BoardTransition>(`/epics/${encodeURIComponent(id)}/transition`, {
+    method: 'POST',
+    body: { status },
+  });
+}
+
+// POST /api/stories/:id/transition - Move a story, starting its developer agent
+export async function transitionStory(id: string, status: BoardStatus): Promise<BoardTransition> {
+  return apiRequest<BoardTransition>(`/stories/${encodeURIComponent(id)}/transition`, {
+    method: 'POST',
+    body: { status },
+  });
+}
+
+// POST /api/stories/:id/assign - Assign an agent to a story, null to unassign
+export async function assignStory(id: string, agentId: string | null): Promise<BoardTransition> {
+  return apiRequest<BoardTransition>(`/stories/${encodeURIComponent(id)}/assign`, {
+    method: 'POST',
+    body: { agent_id: agentId },
+  });
+}
@@ -1,6 +1,9 @@
-import type { ApiError } from './types';
+import type { ApiError, Page } from './types';
 
-const API_BASE = '/api';
+// Origin of the API when the UI is hosted elsewhere, e.g. https://api.example.com;
+// the server must allow the UI's origin in ORCHESTRATE_CORS_ORIGINS
+export const API_ORIGIN: string = import.meta.env.VITE_API_ORIGIN ?? '';
+const API_BASE = `${API_ORIGIN}/api/v1`;
 
 export class ApiClientError extends Error {
   constructor(
@@ -57,3 +60,20 @@ export async function apiRequest<T>(
 
   return JSON.parse(text);
 }
+
+// Fetch every page of a list endpoint
+export async function apiList<T>(endpoint: string): Promise<T[]> {
+  const items: T[] = [];
+  let cursor: string | null = null;
+  do {
+    const separator = endpoint.includes('?') ? '&' : '?';
+    const page: Page<T> = await apiRequest<Page<T>>(
+      cursor
+        ? `${endpoint}${separator}cursor=${encodeURIComponent(cursor)}`
+        : endpoint
+    );
+    items.push(...page.items);
+    cursor = page.next_cursor;
+  } while (cursor);
+  return items;
+}
@@ -0,0 +1,43 @@
+import { API_ORIGIN, apiRequest } from './client';
+import type {
+  BudgetBurnDown,
+  CostBreakdownBy,
+  DailySpend,
+  EntityCost,
+  SetBudgetRequest,
+} from './types';
+
+// GET /api/costs/daily - Daily spend, oldest first
+export async function getDailyCosts(days = 30): Promise<DailySpend[]> {
+  return apiRequest<DailySpend[]>(`/costs/daily?days=${days}`);
+}
+
+// GET /api/costs/breakdown - Spend by model, agent type, or epic
+export async function getCostBreakdown(
+  by: CostBreakdownBy,
+  days = 30
+): Promise<EntityCost[]> {
+  return apiRequest<EntityCost[]>(`/costs/breakdown?by=${by}&days=${days}`);
+}
+
+// GET /api/costs/budget - Burn-down of the current budget, null without one
+export async function getBudget(): Promise<BudgetBurnDown | null> {
+  return apiRequest<BudgetBurnDown | null>('/costs/budget');
+}
+
+// PUT /api/costs/budget - Set the budget, effective today
+export async function setBudget(data: SetBudgetRequest): Promise<BudgetBurnDown> {
+  return apiRequest<BudgetBurnDown>('/costs/budget', {
+    method: 'PUT',
+    body: data,
+  });
+}
+
+// CSV download links of the daily and breakdown endpoints
+export function dailyCostsCsvUrl(days = 30): string {
+  return `${API_ORIGIN}/api/v1/costs/daily?days=${days}&format=csv`;
+}
+
+export function costBreakdownCsvUrl(by: CostBreakdownBy, days = 30): string {
+  return `${API_ORIGIN}/api/v1/costs/breakdown?by=${by}&days=${days}&format=csv`;
+}
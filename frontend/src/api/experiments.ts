@@ -0,0 +1,44 @@
+import { apiList, apiRequest } from './client';
+import type {
+  Experiment,
+  ExperimentResults,
+  ExperimentStatus,
+  VariantDailyResult,
+} from './types';
+
+// GET /api/experiments - Experiments, newest first
+export async function listExperiments(status?: ExperimentStatus): Promise<Experiment[]> {
+  return apiList<Experiment>(`/experiments${status ? `?status=${status}` : ''}`);
+}
+
+export async function getExperiment(id: number): Promise<Experiment> {
+  return apiRequest<Experiment>(`/experiments/${id}`);
+}
+
+// GET /api/experiments/:id/results - Per-variant results with confidence intervals
+export async function getExperimentResults(id: number): Promise<ExperimentResults> {
+  return apiRequest<ExperimentResults>(`/experiments/${id}/results`);
+}
+
+// GET /api/experiments/:id/daily - Per-variant results of each day
+export async function getExperimentDailyResults(id: number): Promise<VariantDailyResult[]> {
+  return apiRequest<VariantDailyResult[]>(`/experiments/${id}/daily`);
+}
+
+// POST /api/experiments/:id/start - Start a draft experiment or resume a paused one
+export async function startExperiment(id: number): Promise<Experiment> {
+  return apiRequest<Experiment>(`/experiments/${id}/start`, { method: 'POST' });
+}
+
+// POST /api/experiments/:id/pause - Pause a running experiment
+export async function pauseExperiment(id: number): Promise<Experiment> {
+  return apiRequest<Experiment>(`/experiments/${id}/pause`, { method: 'POST' });
+}
+
+// POST /api/experiments/:id/promote - Complete an experiment with a winner
+export async function completeExperiment(id: number, winnerVariantId: number): Promise<void> {
+  await apiRequest(`/experiments/${id}/promote`, {
+    method: 'POST',
+    body: { winner_variant_id: winnerVariantId },
+  });
+}
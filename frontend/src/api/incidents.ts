@@ -0,0 +1,45 @@
+import { apiRequest } from './client';
+import type {
+  GeneratedPostMortem,
+  Incident,
+  IncidentDetail,
+  IncidentSeverity,
+  IncidentStatus,
+  TimelineEvent,
+} from './types';
+
+// GET /api/incidents - Incidents, newest first
+export async function listIncidents(filters?: {
+  status?: IncidentStatus;
+  severity?: IncidentSeverity;
+}): Promise<Incident[]> {
+  const params = new URLSearchParams();
+  if (filters?.status) params.set('status', filters.status);
+  if (filters?.severity) params.set('severity', filters.severity);
+  const query = params.toString();
+  return apiRequest<Incident[]>(`/incidents${query ? `?${query}` : ''}`);
+}
+
+// GET /api/incidents/:id - Incident with timeline, investigation, and post-mortem
+export async function getIncident(id: string): Promise<IncidentDetail> {
+  return apiRequest<IncidentDetail>(`/incidents/${encodeURIComponent(id)}`);
+}
+
+// POST /api/incidents/:id/timeline - Comment on the timeline
+export async function addTimelineComment(
+  id: string,
+  description: string,
+  actor?: string
+): Promise<TimelineEvent> {
+  return apiRequest<TimelineEvent>(`/incidents/${encodeURIComponent(id)}/timeline`, {
+    method: 'POST',
+    body: { description, actor },
+  });
+}
+
+// POST /api/incidents/:id/postmortem - Generate the post-mortem of a resolved incident
+export async function generatePostMortem(id: string): Promise<GeneratedPostMortem> {
+  return apiRequest<GeneratedPostMortem>(`/incidents/${encodeURIComponent(id)}/postmortem`, {
+    method: 'POST',
+  });
+}
@@ -8,6 +8,8 @@ import type {
   AcknowledgeAlertRequest,
   MetricValue,
   MetricsSummary,
+  LatencySummary,
+  Page,
 } from './types';
 
 // Response types matching backend
@@ -15,13 +17,7 @@ interface MetricsSnapshotResponse {
   timestamp: string;
   metrics: MetricValue[];
   summary: MetricsSummary;
-}
-
-interface AlertsListResponse {
-  total: number;
-  offset: number;
-  limit: number;
-  alerts: Alert[];
+  latency: LatencySummary;
 }
 
 interface PerformanceResponse {
@@ -50,18 +46,18 @@ export async function listAlerts(params?: {
   status?: AlertStatus;
   severity?: string;
   limit?: number;
-  offset?: number;
-}): Promise<AlertsListResponse> {
+  cursor?: string;
+}): Promise<Page<Alert>> {
   const query = new URLSearchParams();
   if (params?.status) query.set('status', params.status);
   if (params?.severity) query.set('severity', params.severity);
   if (params?.limit) query.set('limit', params.limit.toString());
-  if (params?.offset) query.set('offset', params.offset.toString());
+  if (params?.cursor) query.set('cursor', params.cursor);
 
   const queryString = query.toString();
   const endpoint = queryString ? `/alerts?${queryString}` : '/alerts';
 
-  return apiRequest<AlertsListResponse>(endpoint);
+  return apiRequest<Page<Alert>>(endpoint);
 }
 
 // POST /api/alerts/:id/acknowledge - Acknowledge an alert
@@ -1,18 +1,21 @@
-import { apiRequest } from './client';
+import { apiList, apiRequest } from './client';
 import type {
   Pipeline,
+  PipelineGraph,
   PipelineRun,
   PipelineStage,
   ApprovalRequest,
+  ApprovalInboxItem,
   CreatePipelineRequest,
   UpdatePipelineRequest,
   TriggerRunRequest,
   ApprovalDecisionRequest,
+  DelegateApprovalRequest,
 } from './types';
 
 // Pipeline CRUD
 export async function listPipelines(): Promise<Pipeline[]> {
-  return apiRequest<Pipeline[]>('/pipelines');
+  return apiList<Pipeline>('/pipelines');
 }
 
 export async function getPipeline(name: string): Promise<Pipeline> {
@@ -44,6 +47,12 @@ export async function deletePipeline(name: string): Promise<void> {
   });
 }
 
+export async function getPipelineGraph(name: string): Promise<PipelineGraph> {
+  return apiRequest<PipelineGraph>(
+    `/pipelines/${encodeURIComponent(name)}/graph`
+  );
+}
+
 // Pipeline runs
 export async function triggerPipelineRun(
   name: string,
@@ -59,9 +68,7 @@ export async function triggerPipelineRun(
 }
 
 export async function listPipelineRuns(name: string): Promise<PipelineRun[]> {
-  return apiRequest<PipelineRun[]>(
-    `/pipelines/${encodeURIComponent(name)}/runs`
-  );
+  return apiList<PipelineRun>(`/pipelines/${encodeURIComponent(name)}/runs`);
 }
 
 export async function getPipelineRun(id: number): Promise<PipelineRun> {
@@ -78,12 +85,12 @@ export async function cancelPipelineRun(id: number): Promise<void> {
 export async function getPipelineStages(runId: number): Promise<PipelineStage[]> {
   // This endpoint may need to be added to the backend
   // For now, we'll assume it exists or needs to be created
-  return apiRequest<PipelineStage[]>(`/pipeline-runs/${runId}/stages`);
+  return apiList<PipelineStage>(`/pipeline-runs/${runId}/stages`);
 }
 
 // Approvals
-export async function listPendingApprovals(): Promise<ApprovalRequest[]> {
-  return apiRequest<ApprovalRequest[]>('/approvals');
+export async function listPendingApprovals(): Promise<ApprovalInboxItem[]> {
+  return apiList<ApprovalInboxItem>('/approvals');
 }
 
 export async function approveApproval(
@@ -105,3 +112,13 @@ export async function rejectApproval(
     body: data,
   });
 }
+
+export async function delegateApproval(
+  id: number,
+  data: DelegateApprovalRequest
+): Promise<ApprovalRequest> {
+  return apiRequest<ApprovalRequest>(`/approvals/${id}/delegate`, {
+    method: 'POST',
+    body: data,
+  });
+}
@@ -1,4 +1,4 @@
-import { apiRequest } from './client';
+import { apiList, apiRequest } from './client';
 import type {
   Schedule,
   CreateScheduleRequest,
@@ -8,7 +8,7 @@ import type {
 } from './types';
 
 export async function listSchedules(): Promise<Schedule[]> {
-  return apiRequest<Schedule[]>('/schedules');
+  return apiList<Schedule>('/schedules');
 }
 
 export async function getSchedule(id: number): Promise<Schedule> {
@@ -59,5 +59,5 @@ export async function runSchedule(id: number): Promise<Agent> {
 }
 
 export async function getScheduleRuns(id: number): Promise<ScheduleRun[]> {
-  return apiRequest<ScheduleRun[]>(`/schedules/${id}/runs`);
+  return apiList<ScheduleRun>(`/schedules/${id}/runs`);
 }
@@ -32,6 +32,7 @@ export interface Agent {
   agent_type: AgentType;
   state: AgentState;
   task: string;
+  labels?: Record<string, string>;
   created_at: string;
   updated_at: string;
   error_message?: string;
@@ -41,6 +42,34 @@ export interface CreateAgentRequest {
   agent_type: AgentType;
   task: string;
   worktree_id?: string;
+  /** Spawn as a custom agent type, whose base type replaces agent_type */
+  custom_type?: string;
+  labels?: Record<string, string>;
+}
+
+export interface AgentTypeDefinition {
+  name: string;
+  description: string | null;
+  base_type: AgentType;
+}
+
+export interface CreateStoryRequest {
+  epic_id: string;
+  title?: string;
+}
+
+export interface CreatedStory {
+  id: string;
+  epic_id: string;
+  title: string;
+  status: string;
+}
+
+export interface QueuedPullRequest {
+  id: number;
+  branch_name: string;
+  title: string | null;
+  status: string;
 }
 
 // Message types
@@ -84,8 +113,12 @@ export type WsMessageType =
   | 'agent_state'
   | 'agent_message'
   | 'system_status'
+  | 'event'
   | 'subscribe'
-  | 'send_message';
+  | 'unsubscribe'
+  | 'subscribed'
+  | 'send_message'
+  | 'error';
 
 export interface WsAgentStateMessage {
   type: 'agent_state';
@@ -106,10 +139,41 @@ export interface WsSystemStatusMessage {
   running_agents: number;
 }
 
+export interface WsEventMessage {
+  type: 'event';
+  event: {
+    id: string;
+    kind: string;
+    agent_id?: string | null;
+    occurred_at: string;
+    data: Record<string, unknown>;
+  };
+}
+
+export interface WsSubscribedMessage {
+  type: 'subscribed';
+  channels: string[];
+}
+
+export interface WsErrorMessage {
+  type: 'error';
+  message: string;
+}
+
 export type WsMessage =
   | WsAgentStateMessage
   | WsAgentMessage
-  | WsSystemStatusMessage;
+  | WsSystemStatusMessage
+  | WsEventMessage
+  | WsSubscribedMessage
+  | WsErrorMessage;
+
+// Page of a list endpoint; pass next_cursor as `cursor` for the next page
+export interface Page<T> {
+  items: T[];
+  next_cursor: string | null;
+  total?: number;
+}
 
 // Pipeline types
 export type PipelineRunStatus =
@@ -139,11 +203,39 @@ export interface Pipeline {
   created_at: string;
 }
 
+export type GraphNodeKind = 'trigger' | 'stage';
+
+export type GraphEdgeKind = 'trigger' | 'depends_on' | 'parallel_with' | 'rollback_to';
+
+export interface GraphNode {
+  id: string;
+  kind: GraphNodeKind;
+  label: string;
+  agent?: string;
+  condition?: string;
+  branches?: string[];
+  requires_approval: boolean;
+  environment?: string;
+}
+
+export interface GraphEdge {
+  from: string;
+  to: string;
+  kind: GraphEdgeKind;
+}
+
+export interface PipelineGraph {
+  name: string;
+  nodes: GraphNode[];
+  edges: GraphEdge[];
+}
+
 export interface PipelineRun {
   id: number;
   pipeline_id: number;
   status: PipelineRunStatus;
   trigger_event: string | null;
+  variables: Record<string, string>;
   started_at: string | null;
   completed_at: string | null;
   created_at: string;
@@ -155,6 +247,8 @@ export interface PipelineStage {
   stage_name: string;
   status: PipelineStageStatus;
   agent_id: string | null;
+  attempts: number;
+  failed_after_retries: boolean;
   started_at: string | null;
   completed_at: string | null;
   created_at: string;
@@ -196,6 +290,27 @@ export interface ApprovalDecisionRequest {
   comment?: string;
 }
 
+export interface ApprovalContext {
+  pipeline_name: string | null;
+  stage_name: string | null;
+  trigger_event: string | null;
+  agent_id: string | null;
+  agent_task: string | null;
+  branch: string | null;
+  pr_number: number | null;
+  diff_summary: string | null;
+}
+
+export interface ApprovalInboxItem extends ApprovalRequest {
+  context: ApprovalContext;
+}
+
+export interface DelegateApprovalRequest {
+  approver: string;
+  to: string;
+  comment?: string;
+}
+
 // Pipeline WebSocket message types
 export interface WsPipelineRunMessage {
   type: 'pipeline_run_status';
@@ -295,6 +410,21 @@ export interface MetricValue {
   timestamp: string;
 }
 
+export interface EndpointLatency {
+  method: string;
+  path: string;
+  requests: number;
+  in_flight: number;
+  p50_ms: number;
+  p95_ms: number;
+  p99_ms: number;
+}
+
+export interface LatencySummary {
+  overall: EndpointLatency;
+  endpoints: EndpointLatency[];
+}
+
 export interface MetricsSummary {
   active_agents: number;
   pending_prs?: number;
@@ -426,3 +556,258 @@ export interface CreateReleaseRequest {
   changelog?: string;
   is_prerelease?: boolean;
 }
+
+// Cost dashboard types
+export interface DailySpend {
+  date: string;
+  cost_usd: number;
+  requests: number;
+  input_tokens: number;
+  output_tokens: number;
+  cache_read_tokens: number;
+  cache_write_tokens: number;
+  cache_hit_rate: number;
+}
+
+export type CostBreakdownBy = 'model' | 'agent_type' | 'epic';
+
+export interface EntityCost {
+  entity_id: string;
+  total_input_tokens: number;
+  total_output_tokens: number;
+  request_count: number;
+  estimated_cost_usd: number;
+}
+
+export type BudgetPeriod = 'daily' | 'weekly' | 'monthly';
+
+export interface CostBudget {
+  id: number | null;
+  period_type: BudgetPeriod;
+  amount_usd: number;
+  alert_threshold_percent: number;
+  start_date: string;
+}
+
+export interface BurnDownPoint {
+  date: string;
+  spent: number;
+  remaining: number;
+  ideal_remaining: number;
+}
+
+export interface BudgetBurnDown {
+  budget: CostBudget;
+  period_start: string;
+  period_end: string;
+  spent: number;
+  remaining: number;
+  projected: number;
+  points: BurnDownPoint[];
+}
+
+export interface SetBudgetRequest {
+  period: BudgetPeriod;
+  amount_usd: number;
+  alert_threshold_percent?: number;
+}
+
+export type BoardStatus = 'pending' | 'in_progress' | 'completed' | 'blocked' | 'skipped';
+
+export type CiCheckStatus = 'running' | 'passed' | 'failed' | 'cancelled' | 'timeout' | 'pending';
+
+export interface EpicCard {
+  id: string;
+  title: string;
+  status: BoardStatus;
+  current_phase: string | null;
+  agent_id: string | null;
+  story_count: number;
+  completed_stories: number;
+}
+
+export interface StoryCard {
+  id: string;
+  epic_id: string;
+  title: string;
+  status: BoardStatus;
+  agent_id: string | null;
+  agent_state: AgentState | null;
+  cost_usd: number;
+  ci_status: CiCheckStatus | null;
+}
+
+export interface Board {
+  epics: EpicCard[];
+  stories: StoryCard[];
+}
+
+export interface BoardTransition {
+  id: string;
+  status: BoardStatus;
+  agent_id: string | null;
+  spawned_agent_id: string | null;
+}
+
+export type IncidentSeverity = 'critical' | 'high' | 'medium' | 'low';
+
+export type IncidentStatus = 'detected' | 'investigating' | 'mitigating' | 'resolved' | 'post_mortem';
+
+export type TimelineEventType =
+  | 'detected'
+  | 'acknowledged'
+  | 'investigation_started'
+  | 'root_cause_identified'
+  | 'mitigation_started'
+  | 'playbook_executed'
+  | 'escalated'
+  | 'resolved'
+  | 'post_mortem_created'
+  | 'comment';
+
+export interface TimelineEvent {
+  timestamp: string;
+  event_type: TimelineEventType;
+  description: string;
+  actor: string | null;
+  metadata: Record<string, string>;
+}
+
+export interface Incident {
+  id: string;
+  title: string;
+  description: string;
+  severity: IncidentSeverity;
+  status: IncidentStatus;
+  detected_at: string;
+  acknowledged_at: string | null;
+  resolved_at: string | null;
+  timeline: TimelineEvent[];
+  affected_services: string[];
+  related_incidents: string[];
+  tags: string[];
+  metadata: Record<string, string>;
+}
+
+export interface Evidence {
+  evidence_type: string;
+  description: string;
+  source: string;
+  timestamp: string | null;
+}
+
+export interface Hypothesis {
+  description: string;
+  confidence: number;
+  evidence_for: string[];
+  evidence_against: string[];
+}
+
+export interface RootCauseAnalysis {
+  incident_id: string;
+  primary_cause: string;
+  evidence: Evidence[];
+  contributing_factors: string[];
+  hypotheses: Hypothesis[];
+  analyzed_at: string;
+}
+
+export interface PlaybookActionResult {
+  action_name: string;
+  success: boolean;
+  output: string;
+  error: string | null;
+  started_at: string;
+  completed_at: string;
+}
+
+export interface PlaybookExecution {
+  id: string;
+  playbook_id: string;
+  playbook_name: string | null;
+  status: 'running' | 'waiting_approval' | 'completed' | 'failed' | 'cancelled';
+  started_at: string;
+  completed_at: string | null;
+  action_results: PlaybookActionResult[];
+  triggered_by: string | null;
+}
+
+export interface PostMortemActionItem {
+  description: string;
+  priority: 'high' | 'medium' | 'low';
+  assignee: string | null;
+  completed: boolean;
+}
+
+export interface PostMortem {
+  incident_id: string;
+  title: string;
+  summary: string;
+  root_cause: string;
+  contributing_factors: string[];
+  resolution: string;
+  action_items: PostMortemActionItem[];
+  lessons_learned: string[];
+  created_at: string;
+  authors: string[];
+}
+
+export interface IncidentDetail extends Incident {
+  root_cause: RootCauseAnalysis | null;
+  playbook_executions: PlaybookExecution[];
+  post_mortem: PostMortem | null;
+}
+
+export interface GeneratedPostMortem extends PostMortem {
+  markdown: string;
+  artifact_url: string | null;
+}
+
+export type ExperimentStatus = 'draft' | 'running' | 'paused' | 'completed' | 'cancelled';
+
+export interface Experiment {
+  id: number;
+  name: string;
+  description: string | null;
+  experiment_type: string;
+  metric: string;
+  status: ExperimentStatus;
+  min_samples: number;
+  confidence_level: number;
+  created_at: string;
+  started_at: string | null;
+  completed_at: string | null;
+  winner_variant_id: number | null;
+}
+
+export interface ExperimentVariantResult {
+  variant_id: number;
+  name: string;
+  is_control: boolean;
+  sample_count: number;
+  mean: number;
+  std_dev: number;
+  success_rate: number;
+  ci_lower: number;
+  ci_upper: number;
+}
+
+export interface ExperimentResults {
+  experiment_id: number;
+  variants: ExperimentVariantResult[];
+  total_samples: number;
+  min_samples: number;
+  is_significant: boolean;
+  p_value: number | null;
+  improvement: number | null;
+  winning_variant: string | null;
+}
+
+export interface VariantDailyResult {
+  variant_id: number;
+  variant_name: string;
+  date: string;
+  sample_count: number;
+  mean: number;
+  success_count: number;
+}
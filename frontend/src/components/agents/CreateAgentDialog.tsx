@@ -21,7 +21,7 @@ import {
 } from '@/components/ui/select';
 import { Plus } from 'lucide-react';
 
-const agentTypes: { value: AgentType; label: string; description: string }[] = [
+export const agentTypes: { value: AgentType; label: string; description: string }[] = [
   // Development agents
   { value: 'story_developer', label: 'Story Developer', description: 'Implements user stories and features' },
   { value: 'code_reviewer', label: 'Code Reviewer', description: 'Reviews code for quality and issues' },
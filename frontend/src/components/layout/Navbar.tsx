@@ -7,10 +7,16 @@ export function Navbar() {
   const navLinks = [
     { to: '/', label: 'Dashboard' },
     { to: '/agents', label: 'Agents' },
+    { to: '/chat', label: 'Chat' },
     { to: '/pipelines', label: 'Pipelines' },
+    { to: '/approvals', label: 'Approvals' },
+    { to: '/board', label: 'Board' },
     { to: '/schedules', label: 'Schedules' },
     { to: '/autonomous', label: 'Autonomous' },
     { to: '/monitoring', label: 'Monitoring' },
+    { to: '/incidents', label: 'Incidents' },
+    { to: '/experiments', label: 'Experiments' },
+    { to: '/costs', label: 'Costs' },
   ];
 
   return (
@@ -0,0 +1,77 @@
+import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
+import type { EndpointLatency } from '@/api/types';
+import { cn } from '@/lib/utils';
+
+interface LatencyTableProps {
+  endpoints: EndpointLatency[];
+  // Number of endpoints to show, slowest p95 first
+  limit?: number;
+}
+
+function latencyColor(ms: number) {
+  return ms >= 2000 ? 'text-red-600' : ms >= 1000 ? 'text-yellow-600' : 'text-green-600';
+}
+
+export function LatencyTable({ endpoints, limit = 10 }: LatencyTableProps) {
+  const slowest = (endpoints ?? []).slice(0, limit);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Slowest Endpoints</CardTitle>
+      </CardHeader>
+      <CardContent>
+        {slowest.length === 0 ? (
+          <div className="text-center py-8">
+            <p className="text-sm text-muted-foreground">
+              No requests recorded yet
+            </p>
+          </div>
+        ) : (
+          <div className="overflow-x-auto">
+            <table className="w-full">
+              <thead>
+                <tr className="border-b">
+                  <th className="text-left p-3 text-sm font-medium">Endpoint</th>
+                  <th className="text-right p-3 text-sm font-medium">Requests</th>
+                  <th className="text-right p-3 text-sm font-medium">In Flight</th>
+                  <th className="text-right p-3 text-sm font-medium">p50</th>
+                  <th className="text-right p-3 text-sm font-medium">p95</th>
+                  <th className="text-right p-3 text-sm font-medium">p99</th>
+                </tr>
+              </thead>
+              <tbody>
+                {slowest.map((endpoint) => (
+                  <tr
+                    key={`${endpoint.method} ${endpoint.path}`}
+                    className="border-b hover:bg-muted/50"
+                  >
+                    <td className="p-3">
+                      <span className="text-xs font-semibold text-muted-foreground mr-2">
+                        {endpoint.method}
+                      </span>
+                      <span className="text-sm font-mono">{endpoint.path}</span>
+                    </td>
+                    <td className="p-3 text-right text-sm">
+                      {endpoint.requests.toLocaleString()}
+                    </td>
+                    <td className="p-3 text-right text-sm">{endpoint.in_flight}</td>
+                    <td className="p-3 text-right text-sm">
+                      {endpoint.p50_ms.toFixed(0)}ms
+                    </td>
+                    <td className={cn('p-3 text-right text-sm font-medium', latencyColor(endpoint.p95_ms))}>
+                      {endpoint.p95_ms.toFixed(0)}ms
+                    </td>
+                    <td className={cn('p-3 text-right text-sm', latencyColor(endpoint.p99_ms))}>
+                      {endpoint.p99_ms.toFixed(0)}ms
+                    </td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
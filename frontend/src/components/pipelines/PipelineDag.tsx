@@ -0,0 +1,150 @@
+import { useMemo } from 'react';
+import type { GraphNode, PipelineGraph, PipelineStage } from '@/api/types';
+import { PipelineStageStatusBadge } from '@/components/ui/badge';
+import { cn, formatDuration } from '@/lib/utils';
+
+const NODE_WIDTH = 200;
+const NODE_HEIGHT = 76;
+const COLUMN_GAP = 64;
+const ROW_GAP = 20;
+
+const STATUS_BORDER: Record<string, string> = {
+  Running: 'border-blue-500',
+  WaitingApproval: 'border-yellow-500',
+  Succeeded: 'border-green-600',
+  Failed: 'border-red-600',
+};
+
+interface PositionedNode {
+  node: GraphNode;
+  x: number;
+  y: number;
+}
+
+// Place each node in the column after its deepest dependency
+function layout(graph: PipelineGraph): PositionedNode[] {
+  const parents = new Map<string, string[]>();
+  for (const edge of graph.edges) {
+    if (edge.kind === 'trigger' || edge.kind === 'depends_on') {
+      parents.set(edge.to, [...(parents.get(edge.to) ?? []), edge.from]);
+    }
+  }
+
+  const depths = new Map<string, number>();
+  const depthOf = (id: string, seen: Set<string>): number => {
+    const known = depths.get(id);
+    if (known !== undefined) return known;
+    if (seen.has(id)) return 0;
+    seen.add(id);
+    const depth = Math.max(
+      0,
+      ...(parents.get(id) ?? []).map((parent) => depthOf(parent, seen) + 1)
+    );
+    depths.set(id, depth);
+    return depth;
+  };
+
+  const rows = new Map<number, number>();
+  return graph.nodes.map((node) => {
+    const column = depthOf(node.id, new Set());
+    const row = rows.get(column) ?? 0;
+    rows.set(column, row + 1);
+    return {
+      node,
+      x: column * (NODE_WIDTH + COLUMN_GAP),
+      y: row * (NODE_HEIGHT + ROW_GAP),
+    };
+  });
+}
+
+interface PipelineDagProps {
+  graph: PipelineGraph;
+  stages: PipelineStage[];
+  selectedStage?: string | null;
+  onSelectStage?: (stage: PipelineStage) => void;
+}
+
+/** Pipeline graph with the status and duration of each stage of a run */
+export function PipelineDag({
+  graph,
+  stages,
+  selectedStage,
+  onSelectStage,
+}: PipelineDagProps) {
+  const positioned = useMemo(() => layout(graph), [graph]);
+  const positions = new Map(positioned.map((p) => [p.node.id, p]));
+  const stageByName = new Map(stages.map((stage) => [stage.stage_name, stage]));
+
+  const width = Math.max(...positioned.map((p) => p.x + NODE_WIDTH), NODE_WIDTH);
+  const height = Math.max(...positioned.map((p) => p.y + NODE_HEIGHT), NODE_HEIGHT);
+
+  return (
+    <div className="overflow-x-auto">
+      <div className="relative" style={{ width, height }}>
+        <svg className="absolute inset-0" width={width} height={height}>
+          {graph.edges
+            .filter((edge) => edge.kind === 'trigger' || edge.kind === 'depends_on')
+            .map((edge) => {
+              const from = positions.get(edge.from);
+              const to = positions.get(edge.to);
+              if (!from || !to) return null;
+              const x1 = from.x + NODE_WIDTH;
+              const y1 = from.y + NODE_HEIGHT / 2;
+              const x2 = to.x;
+              const y2 = to.y + NODE_HEIGHT / 2;
+              const mid = (x1 + x2) / 2;
+              return (
+                <path
+                  key={`${edge.from}-${edge.to}`}
+                  d={`M ${x1} ${y1} C ${mid} ${y1}, ${mid} ${y2}, ${x2} ${y2}`}
+                  fill="none"
+                  className="stroke-border"
+                  strokeWidth={2}
+                  strokeDasharray={edge.kind === 'trigger' ? '4 4' : undefined}
+                />
+              );
+            })}
+        </svg>
+
+        {positioned.map(({ node, x, y }) => {
+          const stage = node.kind === 'stage' ? stageByName.get(node.label) : undefined;
+          return (
+            <button
+              key={node.id}
+              type="button"
+              disabled={!stage}
+              onClick={() => stage && onSelectStage?.(stage)}
+              className={cn(
+                'absolute rounded-lg border-2 bg-background p-2 text-left text-sm',
+                node.kind === 'trigger' && 'border-dashed bg-muted',
+                stage && STATUS_BORDER[stage.status],
+                stage?.status === 'Running' && 'animate-pulse',
+                stage && 'hover:bg-accent cursor-pointer',
+                selectedStage === node.label && 'ring-2 ring-primary'
+              )}
+              style={{ left: x, top: y, width: NODE_WIDTH, height: NODE_HEIGHT }}
+            >
+              <div className="flex items-center justify-between gap-2">
+                <span className="truncate font-semibold">{node.label}</span>
+                {stage && <PipelineStageStatusBadge status={stage.status} />}
+              </div>
+              <div className="mt-1 truncate text-xs text-muted-foreground">
+                {node.kind === 'trigger'
+                  ? 'trigger'
+                  : stage
+                    ? formatDuration(stage.started_at, stage.completed_at)
+                    : node.condition ?? 'not started'}
+              </div>
+              {node.agent && (
+                <div className="truncate text-xs text-muted-foreground">
+                  {node.agent}
+                  {node.requires_approval && ' · approval'}
+                </div>
+              )}
+            </button>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
@@ -1,6 +1,7 @@
 import { useEffect, useRef, useCallback } from 'react';
 import { create } from 'zustand';
-import type { AgentState, WsMessage } from '@/api/types';
+import type { AgentState, WsEventMessage, WsMessage } from '@/api/types';
+import { API_ORIGIN } from '@/api/client';
 
 interface WebSocketStore {
   connected: boolean;
@@ -18,6 +19,9 @@ export const useWebSocketStore = create<WebSocketStore>((set) => ({
 
 interface UseWebSocketOptions {
   agentId?: string;
+  /** Topics to subscribe to, e.g. `pipeline_run:7` or `event:pipeline` */
+  channels?: string[];
+  onEvent?: (event: WsEventMessage['event']) => void;
   onAgentStateChange?: (agentId: string, state: AgentState) => void;
   onNewMessage?: (agentId: string, role: string, content: string) => void;
   onSystemStatus?: (total: number, running: number) => void;
@@ -28,6 +32,8 @@ export function useWebSocket(options: UseWebSocketOptions = {}) {
   const reconnectAttempts = useRef(0);
   const maxReconnectAttempts = 5;
   const { setConnected, setReconnecting } = useWebSocketStore();
+  // Reconnect only when the topics change, not on every new array
+  const channelKey = options.channels?.join(',') ?? '';
 
   const handleMessage = useCallback(
     (data: WsMessage) => {
@@ -41,14 +47,18 @@ export function useWebSocket(options: UseWebSocketOptions = {}) {
         case 'system_status':
           options.onSystemStatus?.(data.total_agents, data.running_agents);
           break;
+        case 'event':
+          options.onEvent?.(data.event);
+          break;
       }
     },
     [options]
   );
 
   const connect = useCallback(() => {
-    const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
-    const wsUrl = `${protocol}//${window.location.host}/ws`;
+    const origin = new URL(API_ORIGIN || window.location.origin);
+    const protocol = origin.protocol === 'https:' ? 'wss:' : 'ws:';
+    const wsUrl = `${protocol}//${origin.host}/ws`;
 
     ws.current = new WebSocket(wsUrl);
 
@@ -57,14 +67,13 @@ export function useWebSocket(options: UseWebSocketOptions = {}) {
       setReconnecting(false);
       reconnectAttempts.current = 0;
 
-      // Subscribe to specific agent if provided
-      if (options.agentId && ws.current) {
-        ws.current.send(
-          JSON.stringify({
-            type: 'subscribe',
-            channels: [`agent:${options.agentId}`],
-          })
-        );
+      // Subscribe to specific agent and topics if provided
+      const channels = [
+        ...(options.agentId ? [`agent:${options.agentId}`] : []),
+        ...(channelKey ? channelKey.split(',') : []),
+      ];
+      if (channels.length > 0 && ws.current) {
+        ws.current.send(JSON.stringify({ type: 'subscribe', channels }));
       }
     };
 
@@ -85,7 +94,7 @@ export function useWebSocket(options: UseWebSocketOptions = {}) {
     ws.current.onerror = (error) => {
       console.error('WebSocket error:', error);
     };
-  }, [options.agentId, handleMessage, setConnected, setReconnecting]);
+  }, [options.agentId, channelKey, handleMessage, setConnected, setReconnecting]);
 
   const attemptReconnect = useCallback(() => {
     if (reconnectAttempts.current < maxReconnectAttempts) {
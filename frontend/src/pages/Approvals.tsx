@@ -0,0 +1,241 @@
+import { useState } from 'react';
+import { Link } from 'react-router-dom';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { CheckCircle2, Clock, GitBranch, UserPlus, XCircle } from 'lucide-react';
+import {
+  approveApproval,
+  delegateApproval,
+  listPendingApprovals,
+  rejectApproval,
+} from '@/api/pipelines';
+import type { ApprovalInboxItem } from '@/api/types';
+import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import { Badge } from '@/components/ui/badge';
+import { Input } from '@/components/ui/input';
+import { formatDistanceToNow } from '@/lib/time';
+
+function ContextRow({ label, children }: { label: string; children: React.ReactNode }) {
+  return (
+    <div>
+      <div className="text-muted-foreground">{label}</div>
+      <div className="font-medium truncate">{children}</div>
+    </div>
+  );
+}
+
+function ApprovalCard({ approval, approver }: { approval: ApprovalInboxItem; approver: string }) {
+  const queryClient = useQueryClient();
+  const [comment, setComment] = useState('');
+  const [delegateTo, setDelegateTo] = useState('');
+  const { context } = approval;
+
+  const onDecided = () => {
+    queryClient.invalidateQueries({ queryKey: ['approvals'] });
+    queryClient.invalidateQueries({ queryKey: ['pipeline-run'] });
+    queryClient.invalidateQueries({ queryKey: ['pipeline-stages'] });
+  };
+
+  const decision = { approver, comment: comment.trim() || undefined };
+  const approveMutation = useMutation({
+    mutationFn: () => approveApproval(approval.id, decision),
+    onSuccess: onDecided,
+  });
+  const rejectMutation = useMutation({
+    mutationFn: () => rejectApproval(approval.id, decision),
+    onSuccess: onDecided,
+  });
+  const delegateMutation = useMutation({
+    mutationFn: () => delegateApproval(approval.id, { ...decision, to: delegateTo.trim() }),
+    onSuccess: () => {
+      setDelegateTo('');
+      onDecided();
+    },
+  });
+
+  const isPending =
+    approveMutation.isPending || rejectMutation.isPending || delegateMutation.isPending;
+  const error = approveMutation.error ?? rejectMutation.error ?? delegateMutation.error;
+
+  const requiredApprovers = approval.required_approvers
+    .split(',')
+    .map((a) => a.trim())
+    .filter((a) => a.length > 0);
+
+  return (
+    <Card>
+      <CardHeader>
+        <div className="flex items-center justify-between gap-4">
+          <CardTitle className="text-lg">
+            {context.pipeline_name && context.stage_name
+              ? `${context.pipeline_name} / ${context.stage_name}`
+              : `Approval #${approval.id}`}
+          </CardTitle>
+          <div className="flex items-center gap-2 text-sm text-muted-foreground">
+            <Badge variant="warning">{approval.status}</Badge>
+            <span>
+              {approval.approval_count} / {approval.required_count} approvals
+            </span>
+          </div>
+        </div>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="grid grid-cols-2 md:grid-cols-4 gap-4 text-sm">
+          <ContextRow label="Pipeline run">
+            {context.pipeline_name ? (
+              <Link
+                to={`/pipelines/${encodeURIComponent(context.pipeline_name)}/runs/${approval.run_id}`}
+                className="text-primary hover:underline"
+              >
+                #{approval.run_id}
+              </Link>
+            ) : (
+              `#${approval.run_id}`
+            )}
+            {context.trigger_event && (
+              <span className="ml-2 text-muted-foreground">{context.trigger_event}</span>
+            )}
+          </ContextRow>
+          <ContextRow label="Requesting agent">
+            {context.agent_id ? (
+              <Link to={`/agents/${context.agent_id}`} className="text-primary hover:underline">
+                {context.agent_id.slice(0, 8)}
+              </Link>
+            ) : (
+              '—'
+            )}
+          </ContextRow>
+          <ContextRow label="Branch">
+            {context.branch ? (
+              <span className="inline-flex items-center gap-1">
+                <GitBranch className="h-3 w-3" />
+                {context.branch}
+                {context.pr_number !== null && ` (#${context.pr_number})`}
+              </span>
+            ) : (
+              '—'
+            )}
+          </ContextRow>
+          <ContextRow label="Requested">{formatDistanceToNow(approval.created_at)}</ContextRow>
+        </div>
+
+        {context.agent_task && (
+          <p className="text-sm text-muted-foreground line-clamp-2">{context.agent_task}</p>
+        )}
+        {context.diff_summary && (
+          <div className="rounded-md bg-muted px-3 py-2 font-mono text-xs">
+            {context.diff_summary}
+          </div>
+        )}
+
+        <div className="flex flex-wrap items-center gap-2 text-sm">
+          <span className="text-muted-foreground">Approvers:</span>
+          {requiredApprovers.map((a) => (
+            <Badge key={a} variant="secondary">
+              {a}
+            </Badge>
+          ))}
+          {approval.timeout_at && (
+            <span className="ml-auto inline-flex items-center gap-1 text-muted-foreground">
+              <Clock className="h-3 w-3" />
+              Times out {new Date(approval.timeout_at).toLocaleString()}
+              {approval.timeout_action && ` (${approval.timeout_action})`}
+            </span>
+          )}
+        </div>
+
+        <textarea
+          className="w-full px-3 py-2 border rounded-md text-sm bg-transparent"
+          rows={2}
+          placeholder="Comment (optional)"
+          value={comment}
+          onChange={(e) => setComment(e.target.value)}
+          disabled={isPending}
+        />
+
+        {error && <div className="text-sm text-red-600">{error.message}</div>}
+
+        <div className="flex flex-wrap items-center gap-2">
+          <Button
+            size="sm"
+            onClick={() => approveMutation.mutate()}
+            disabled={isPending || !approver.trim()}
+          >
+            <CheckCircle2 className="mr-2 h-4 w-4" />
+            Approve
+          </Button>
+          <Button
+            size="sm"
+            variant="destructive"
+            onClick={() => rejectMutation.mutate()}
+            disabled={isPending || !approver.trim()}
+          >
+            <XCircle className="mr-2 h-4 w-4" />
+            Reject
+          </Button>
+          <form
+            className="ml-auto flex items-center gap-2"
+            onSubmit={(e) => {
+              e.preventDefault();
+              delegateMutation.mutate();
+            }}
+          >
+            <Input
+              placeholder="Delegate to"
+              value={delegateTo}
+              onChange={(e) => setDelegateTo(e.target.value)}
+              className="h-9 w-56"
+              disabled={isPending}
+            />
+            <Button
+              type="submit"
+              size="sm"
+              variant="outline"
+              disabled={isPending || !approver.trim() || !delegateTo.trim()}
+            >
+              <UserPlus className="mr-2 h-4 w-4" />
+              Delegate
+            </Button>
+          </form>
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
+
+export function Approvals() {
+  const [approver, setApprover] = useState(() => localStorage.getItem('approver') ?? '');
+
+  const { data: approvals = [], isLoading } = useQuery({
+    queryKey: ['approvals'],
+    queryFn: listPendingApprovals,
+    refetchInterval: 10000,
+  });
+
+  return (
+    <div className="space-y-6">
+      <div className="flex items-center justify-between">
+        <h1 className="text-3xl font-bold">Approvals</h1>
+        <Input
+          placeholder="Your name or email"
+          value={approver}
+          onChange={(e) => {
+            setApprover(e.target.value);
+            localStorage.setItem('approver', e.target.value);
+          }}
+          className="w-64"
+        />
+      </div>
+
+      {isLoading ? (
+        <div className="text-center py-8 text-muted-foreground">Loading...</div>
+      ) : approvals.length === 0 ? (
+        <div className="text-center py-8 text-muted-foreground">No approvals waiting</div>
+      ) : (
+        approvals.map((approval) => (
+          <ApprovalCard key={approval.id} approval={approval} approver={approver} />
+        ))
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,242 @@
+import { useState } from 'react';
+import { Link } from 'react-router-dom';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { Bot, UserPlus } from 'lucide-react';
+import { assignStory, getBoard, transitionEpic, transitionStory } from '@/api/board';
+import type { BoardStatus, CiCheckStatus, EpicCard, StoryCard } from '@/api/types';
+import { Badge } from '@/components/ui/badge';
+import { Button } from '@/components/ui/button';
+import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
+import { Input } from '@/components/ui/input';
+import { cn } from '@/lib/utils';
+
+const COLUMNS: { status: BoardStatus; label: string }[] = [
+  { status: 'pending', label: 'Pending' },
+  { status: 'in_progress', label: 'In progress' },
+  { status: 'blocked', label: 'Blocked' },
+  { status: 'completed', label: 'Completed' },
+  { status: 'skipped', label: 'Skipped' },
+];
+
+const ciVariants: Record<CiCheckStatus, 'success' | 'warning' | 'destructive' | 'secondary'> = {
+  passed: 'success',
+  running: 'warning',
+  pending: 'secondary',
+  failed: 'destructive',
+  timeout: 'destructive',
+  cancelled: 'secondary',
+};
+
+// Cards carry their kind so a column knows which transition to request
+type Dragged = { kind: 'epic' | 'story'; id: string; status: BoardStatus };
+
+function StoryAssignment({ story }: { story: StoryCard }) {
+  const queryClient = useQueryClient();
+  const [editing, setEditing] = useState(false);
+  const [agentId, setAgentId] = useState(story.agent_id ?? '');
+
+  const mutation = useMutation({
+    mutationFn: () => assignStory(story.id, agentId.trim() || null),
+    onSuccess: () => {
+      setEditing(false);
+      queryClient.invalidateQueries({ queryKey: ['board'] });
+    },
+  });
+
+  if (!editing) {
+    return (
+      <div className="flex items-center gap-2 text-xs">
+        {story.agent_id ? (
+          <Link
+            to={`/agents/${story.agent_id}`}
+            className="inline-flex items-center gap-1 text-primary hover:underline"
+          >
+            <Bot className="h-3 w-3" />
+            {story.agent_id.slice(0, 8)}
+            {story.agent_state && (
+              <span className="text-muted-foreground">({story.agent_state})</span>
+            )}
+          </Link>
+        ) : (
+          <span className="text-muted-foreground">Unassigned</span>
+        )}
+        <button
+          type="button"
+          className="ml-auto text-muted-foreground hover:text-foreground"
+          onClick={() => setEditing(true)}
+          title="Assign agent"
+        >
+          <UserPlus className="h-3 w-3" />
+        </button>
+      </div>
+    );
+  }
+
+  return (
+    <form
+      className="space-y-1"
+      onSubmit={(e) => {
+        e.preventDefault();
+        mutation.mutate();
+      }}
+    >
+      <div className="flex items-center gap-1">
+        <Input
+          placeholder="Agent ID, empty to unassign"
+          value={agentId}
+          onChange={(e) => setAgentId(e.target.value)}
+          className="h-7 text-xs"
+        />
+        <Button type="submit" size="sm" className="h-7" disabled={mutation.isPending}>
+          Save
+        </Button>
+      </div>
+      {mutation.error && <div className="text-xs text-red-600">{mutation.error.message}</div>}
+    </form>
+  );
+}
+
+function StoryItem({ story, onDragStart }: { story: StoryCard; onDragStart: (d: Dragged) => void }) {
+  return (
+    <div
+      draggable
+      onDragStart={() => onDragStart({ kind: 'story', id: story.id, status: story.status })}
+      className="space-y-2 rounded-md border bg-card p-3 shadow-sm cursor-grab"
+    >
+      <div className="text-xs text-muted-foreground">{story.id}</div>
+      <div className="text-sm font-medium">{story.title}</div>
+      <div className="flex flex-wrap items-center gap-1">
+        {story.cost_usd > 0 && <Badge variant="secondary">${story.cost_usd.toFixed(2)}</Badge>}
+        {story.ci_status && <Badge variant={ciVariants[story.ci_status]}>CI {story.ci_status}</Badge>}
+      </div>
+      <StoryAssignment story={story} />
+    </div>
+  );
+}
+
+function EpicItem({ epic, onDragStart }: { epic: EpicCard; onDragStart: (d: Dragged) => void }) {
+  return (
+    <div
+      draggable
+      onDragStart={() => onDragStart({ kind: 'epic', id: epic.id, status: epic.status })}
+      className="space-y-1 rounded-md border border-primary/40 bg-primary/5 p-3 cursor-grab"
+    >
+      <div className="text-xs text-muted-foreground">Epic {epic.id}</div>
+      <div className="text-sm font-medium">{epic.title}</div>
+      <div className="flex flex-wrap items-center gap-1 text-xs text-muted-foreground">
+        <span>
+          {epic.completed_stories} / {epic.story_count} stories
+        </span>
+        {epic.current_phase && <Badge variant="secondary">{epic.current_phase}</Badge>}
+      </div>
+    </div>
+  );
+}
+
+export function Board() {
+  const queryClient = useQueryClient();
+  const [epicId, setEpicId] = useState('');
+  const [dragged, setDragged] = useState<Dragged | null>(null);
+  const [over, setOver] = useState<BoardStatus | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
+
+  const { data: board, isLoading } = useQuery({
+    queryKey: ['board', epicId],
+    queryFn: () => getBoard(epicId || undefined),
+    refetchInterval: 10000,
+  });
+  // Every epic, for the filter, regardless of the one shown
+  const { data: allEpics = [] } = useQuery({
+    queryKey: ['board', ''],
+    queryFn: () => getBoard(),
+    select: (data) => data.epics,
+  });
+
+  const mutation = useMutation({
+    mutationFn: ({ kind, id, status }: Dragged) =>
+      kind === 'epic' ? transitionEpic(id, status) : transitionStory(id, status),
+    onSuccess: (result) => {
+      setNotice(
+        result.spawned_agent_id
+          ? `Moved ${result.id} to ${result.status} and started agent ${result.spawned_agent_id.slice(0, 8)}`
+          : `Moved ${result.id} to ${result.status}`
+      );
+      queryClient.invalidateQueries({ queryKey: ['board'] });
+      queryClient.invalidateQueries({ queryKey: ['agents'] });
+    },
+  });
+
+  const drop = (status: BoardStatus) => {
+    setOver(null);
+    if (dragged && dragged.status !== status) {
+      mutation.mutate({ ...dragged, status });
+    }
+    setDragged(null);
+  };
+
+  return (
+    <div className="space-y-6">
+      <div className="flex items-center justify-between">
+        <h1 className="text-3xl font-bold">Board</h1>
+        <select
+          value={epicId}
+          onChange={(e) => setEpicId(e.target.value)}
+          className="h-9 w-64 rounded-md border border-input bg-transparent px-2 text-sm"
+        >
+          <option value="">All epics</option>
+          {allEpics.map((epic) => (
+            <option key={epic.id} value={epic.id}>
+              {epic.id}: {epic.title}
+            </option>
+          ))}
+        </select>
+      </div>
+
+      <p className="text-sm text-muted-foreground">
+        Drag epics and stories between columns. Moving one into progress starts its BMAD agent.
+      </p>
+      {notice && <div className="text-sm text-green-600">{notice}</div>}
+      {mutation.error && <div className="text-sm text-red-600">{mutation.error.message}</div>}
+
+      {isLoading || !board ? (
+        <div className="text-center py-8 text-muted-foreground">Loading...</div>
+      ) : (
+        <div className="grid gap-4 md:grid-cols-3 xl:grid-cols-5">
+          {COLUMNS.map((column) => {
+            const epics = board.epics.filter((e) => e.status === column.status);
+            const stories = board.stories.filter((s) => s.status === column.status);
+            return (
+              <Card
+                key={column.status}
+                onDragOver={(e) => {
+                  e.preventDefault();
+                  setOver(column.status);
+                }}
+                onDragLeave={() => setOver(null)}
+                onDrop={() => drop(column.status)}
+                className={cn('min-h-[300px]', over === column.status && 'ring-2 ring-primary')}
+              >
+                <CardHeader className="pb-3">
+                  <CardTitle className="flex items-center justify-between text-base">
+                    {column.label}
+                    <span className="text-sm font-normal text-muted-foreground">
+                      {epics.length + stories.length}
+                    </span>
+                  </CardTitle>
+                </CardHeader>
+                <CardContent className="space-y-2">
+                  {epics.map((epic) => (
+                    <EpicItem key={epic.id} epic={epic} onDragStart={setDragged} />
+                  ))}
+                  {stories.map((story) => (
+                    <StoryItem key={story.id} story={story} onDragStart={setDragged} />
+                  ))}
+                </CardContent>
+              </Card>
+            );
+          })}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,296 @@
+import { useState } from 'react';
+import { Link, useNavigate, useParams } from 'react-router-dom';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { BookOpen, ExternalLink, GitPullRequest, MessageSquarePlus } from 'lucide-react';
+import {
+  createAgent,
+  createPrFromAgent,
+  createStoryFromAgent,
+  getAgent,
+  getMessages,
+  listAgents,
+  listAgentTypes,
+} from '@/api/agents';
+import type { Agent, AgentType } from '@/api/types';
+import { useWebSocket } from '@/hooks/useWebSocket';
+import { agentTypes } from '@/components/agents/CreateAgentDialog';
+import { AgentStateBadge, AgentTypeBadge } from '@/components/ui/badge';
+import { Button } from '@/components/ui/button';
+import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
+import { Input } from '@/components/ui/input';
+import { MessageList } from '@/components/chat/MessageList';
+import { MessageInput } from '@/components/chat/MessageInput';
+import { cn, truncate } from '@/lib/utils';
+
+const RECENT_CHATS = 15;
+
+function NewChat() {
+  const navigate = useNavigate();
+  const queryClient = useQueryClient();
+  // `type:<agent type>` or `custom:<custom type name>`
+  const [kind, setKind] = useState('type:explorer');
+  const [task, setTask] = useState('');
+
+  const { data: customTypes = [] } = useQuery({
+    queryKey: ['agent-types'],
+    queryFn: listAgentTypes,
+  });
+
+  const mutation = useMutation({
+    mutationFn: () => {
+      const separator = kind.indexOf(':');
+      const prefix = kind.slice(0, separator);
+      const name = kind.slice(separator + 1);
+      const custom = prefix === 'custom' ? customTypes.find((t) => t.name === name) : undefined;
+      return createAgent({
+        agent_type: custom ? custom.base_type : (name as AgentType),
+        custom_type: custom?.name,
+        task: task.trim(),
+      });
+    },
+    onSuccess: (agent) => {
+      setTask('');
+      queryClient.invalidateQueries({ queryKey: ['agents'] });
+      navigate(`/chat/${agent.id}`);
+    },
+  });
+
+  return (
+    <form
+      className="space-y-3"
+      onSubmit={(e) => {
+        e.preventDefault();
+        if (task.trim()) mutation.mutate();
+      }}
+    >
+      <select
+        value={kind}
+        onChange={(e) => setKind(e.target.value)}
+        className="h-9 w-full rounded-md border border-input bg-transparent px-2 text-sm"
+      >
+        <optgroup label="Agent types">
+          {agentTypes.map((type) => (
+            <option key={type.value} value={`type:${type.value}`}>
+              {type.label}
+            </option>
+          ))}
+        </optgroup>
+        {customTypes.length > 0 && (
+          <optgroup label="Templates">
+            {customTypes.map((type) => (
+              <option key={type.name} value={`custom:${type.name}`}>
+                {type.name}
+              </option>
+            ))}
+          </optgroup>
+        )}
+      </select>
+      <textarea
+        className="w-full min-h-[100px] rounded-md border border-input bg-background px-3 py-2 text-sm placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring"
+        placeholder="Describe the task..."
+        value={task}
+        onChange={(e) => setTask(e.target.value)}
+      />
+      {mutation.error && <div className="text-sm text-red-600">{mutation.error.message}</div>}
+      <Button type="submit" className="w-full" disabled={mutation.isPending || !task.trim()}>
+        <MessageSquarePlus className="mr-2 h-4 w-4" />
+        {mutation.isPending ? 'Starting...' : 'Start chat'}
+      </Button>
+    </form>
+  );
+}
+
+function RecentChats({ activeId }: { activeId?: string }) {
+  const { data: agents = [] } = useQuery({
+    queryKey: ['agents'],
+    queryFn: listAgents,
+    refetchInterval: 10000,
+  });
+
+  const recent = [...agents]
+    .sort((a, b) => b.created_at.localeCompare(a.created_at))
+    .slice(0, RECENT_CHATS);
+
+  if (recent.length === 0) {
+    return <div className="text-sm text-muted-foreground">No chats yet</div>;
+  }
+
+  return (
+    <div className="space-y-1">
+      {recent.map((agent) => (
+        <Link
+          key={agent.id}
+          to={`/chat/${agent.id}`}
+          className={cn(
+            'block rounded-md px-2 py-1.5 text-sm hover:bg-accent',
+            agent.id === activeId && 'bg-accent'
+          )}
+        >
+          <div className="truncate">{truncate(agent.task, 60)}</div>
+          <div className="text-xs text-muted-foreground">{agent.state}</div>
+        </Link>
+      ))}
+    </div>
+  );
+}
+
+// Turn the agent's result into a story of an epic or a queued pull request
+function ConvertResult({ agent }: { agent: Agent }) {
+  const [epicId, setEpicId] = useState('');
+
+  const storyMutation = useMutation({
+    mutationFn: () => createStoryFromAgent(agent.id, { epic_id: epicId.trim() }),
+  });
+  const prMutation = useMutation({
+    mutationFn: () => createPrFromAgent(agent.id),
+  });
+
+  const error = storyMutation.error ?? prMutation.error;
+
+  return (
+    <div className="space-y-2 border-t p-4">
+      <div className="flex flex-wrap items-center gap-2">
+        <Input
+          placeholder="Epic ID"
+          value={epicId}
+          onChange={(e) => setEpicId(e.target.value)}
+          className="h-9 w-40"
+        />
+        <Button
+          size="sm"
+          variant="outline"
+          onClick={() => storyMutation.mutate()}
+          disabled={!epicId.trim() || storyMutation.isPending}
+        >
+          <BookOpen className="mr-2 h-4 w-4" />
+          Create story
+        </Button>
+        <Button
+          size="sm"
+          variant="outline"
+          onClick={() => prMutation.mutate()}
+          disabled={prMutation.isPending}
+        >
+          <GitPullRequest className="mr-2 h-4 w-4" />
+          Queue PR
+        </Button>
+      </div>
+      {storyMutation.data && (
+        <div className="text-sm text-green-600">
+          Created story {storyMutation.data.id}: {storyMutation.data.title}
+        </div>
+      )}
+      {prMutation.data && (
+        <div className="text-sm text-green-600">
+          Queued PR #{prMutation.data.id} from {prMutation.data.branch_name}
+        </div>
+      )}
+      {error && <div className="text-sm text-red-600">{error.message}</div>}
+    </div>
+  );
+}
+
+function Conversation({ agentId }: { agentId: string }) {
+  const queryClient = useQueryClient();
+
+  const { data: agent } = useQuery({
+    queryKey: ['agent', agentId],
+    queryFn: () => getAgent(agentId),
+  });
+
+  const { data: messages = [], isLoading } = useQuery({
+    queryKey: ['agent', agentId, 'messages'],
+    queryFn: () => getMessages(agentId),
+    // The WebSocket pushes new messages; poll slowly in case it drops
+    refetchInterval: 15000,
+  });
+
+  useWebSocket({
+    agentId,
+    onAgentStateChange: (id, state) => {
+      if (id === agentId) {
+        queryClient.setQueryData(['agent', agentId], (old: Agent | undefined) =>
+          old ? { ...old, state } : old
+        );
+        queryClient.invalidateQueries({ queryKey: ['agents'] });
+      }
+    },
+    onNewMessage: (id) => {
+      if (id === agentId) {
+        queryClient.invalidateQueries({ queryKey: ['agent', agentId, 'messages'] });
+      }
+    },
+  });
+
+  if (!agent) {
+    return <div className="py-12 text-center text-muted-foreground">Loading chat...</div>;
+  }
+
+  const canSendMessage = ['running', 'waiting_for_input', 'paused'].includes(agent.state);
+
+  return (
+    <Card>
+      <CardHeader>
+        <div className="flex items-start justify-between gap-4">
+          <div className="min-w-0">
+            <CardTitle className="truncate">{agent.task.split('\n')[0]}</CardTitle>
+            <div className="mt-2 flex items-center gap-2">
+              <AgentTypeBadge type={agent.agent_type} />
+              <AgentStateBadge state={agent.state} />
+            </div>
+          </div>
+          <Link to={`/agents/${agent.id}`}>
+            <Button variant="ghost" size="sm">
+              <ExternalLink className="mr-2 h-4 w-4" />
+              Details
+            </Button>
+          </Link>
+        </div>
+      </CardHeader>
+      <CardContent className="p-0">
+        {isLoading ? (
+          <div className="p-8 text-center text-muted-foreground">Loading messages...</div>
+        ) : (
+          <MessageList messages={messages} />
+        )}
+        <MessageInput agentId={agent.id} disabled={!canSendMessage} />
+        <ConvertResult agent={agent} />
+      </CardContent>
+    </Card>
+  );
+}
+
+export function Chat() {
+  const { id } = useParams<{ id: string }>();
+
+  return (
+    <div className="grid gap-6 lg:grid-cols-[280px_1fr]">
+      <div className="space-y-6">
+        <Card>
+          <CardHeader>
+            <CardTitle>New chat</CardTitle>
+          </CardHeader>
+          <CardContent>
+            <NewChat />
+          </CardContent>
+        </Card>
+        <Card>
+          <CardHeader>
+            <CardTitle>Recent</CardTitle>
+          </CardHeader>
+          <CardContent>
+            <RecentChats activeId={id} />
+          </CardContent>
+        </Card>
+      </div>
+
+      {id ? (
+        <Conversation key={id} agentId={id} />
+      ) : (
+        <div className="flex items-center justify-center rounded-lg border border-dashed py-24 text-muted-foreground">
+          Start a chat or pick a recent one
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,352 @@
+import { useState } from 'react';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { DollarSign, Download, Percent, Target, TrendingUp } from 'lucide-react';
+import {
+  costBreakdownCsvUrl,
+  dailyCostsCsvUrl,
+  getBudget,
+  getCostBreakdown,
+  getDailyCosts,
+  setBudget,
+} from '@/api/costs';
+import type { BudgetBurnDown, BudgetPeriod, CostBreakdownBy, DailySpend } from '@/api/types';
+import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { MetricCard } from '@/components/monitoring/MetricCard';
+import { cn } from '@/lib/utils';
+
+const RANGES = [7, 30, 90];
+
+const BREAKDOWNS: { by: CostBreakdownBy; label: string }[] = [
+  { by: 'model', label: 'Model' },
+  { by: 'agent_type', label: 'Agent type' },
+  { by: 'epic', label: 'Epic' },
+];
+
+function usd(value: number): string {
+  return `$${value.toFixed(2)}`;
+}
+
+function CsvLink({ href }: { href: string }) {
+  return (
+    <a href={href} download>
+      <Button variant="outline" size="sm">
+        <Download className="mr-2 h-4 w-4" />
+        CSV
+      </Button>
+    </a>
+  );
+}
+
+// Bars of one value per day, with the date and value on hover
+function DailyBars({
+  days,
+  value,
+  format,
+  className,
+}: {
+  days: DailySpend[];
+  value: (day: DailySpend) => number;
+  format: (value: number) => string;
+  className: string;
+}) {
+  const max = Math.max(...days.map(value), 0);
+  if (days.length === 0) {
+    return <div className="text-center py-8 text-muted-foreground">No usage recorded</div>;
+  }
+  return (
+    <div className="flex h-40 items-end gap-1">
+      {days.map((day) => (
+        <div
+          key={day.date}
+          title={`${day.date}: ${format(value(day))}`}
+          className={cn('flex-1 rounded-t', className)}
+          style={{ height: `${max > 0 ? (value(day) / max) * 100 : 0}%`, minHeight: 2 }}
+        />
+      ))}
+    </div>
+  );
+}
+
+// Remaining budget per day against spending evenly over the period
+function BurnDownChart({ burnDown }: { burnDown: BudgetBurnDown }) {
+  const width = 600;
+  const height = 160;
+  const amount = burnDown.budget.amount_usd;
+  const periodDays = Math.max(
+    1,
+    Math.round(
+      (new Date(burnDown.period_end).getTime() - new Date(burnDown.period_start).getTime()) /
+        86_400_000
+    ) + 1
+  );
+  const low = Math.min(0, ...burnDown.points.map((p) => p.remaining));
+  const x = (index: number) => ((index + 1) / periodDays) * width;
+  const y = (remaining: number) =>
+    height - ((remaining - low) / Math.max(amount - low, 1e-9)) * height;
+
+  const actual = [`0,${y(amount)}`, ...burnDown.points.map((p, i) => `${x(i)},${y(p.remaining)}`)];
+
+  return (
+    <svg viewBox={`0 0 ${width} ${height}`} className="h-40 w-full" preserveAspectRatio="none">
+      <line
+        x1={0}
+        y1={y(amount)}
+        x2={width}
+        y2={y(0)}
+        className="stroke-muted-foreground"
+        strokeDasharray="4 4"
+      />
+      <line x1={0} y1={y(0)} x2={width} y2={y(0)} className="stroke-border" />
+      <polyline
+        points={actual.join(' ')}
+        fill="none"
+        strokeWidth={2}
+        className={burnDown.remaining < 0 ? 'stroke-red-500' : 'stroke-blue-500'}
+      />
+    </svg>
+  );
+}
+
+function BudgetForm({ current }: { current: BudgetBurnDown | null | undefined }) {
+  const queryClient = useQueryClient();
+  const [period, setPeriod] = useState<BudgetPeriod>(current?.budget.period_type ?? 'monthly');
+  const [amount, setAmount] = useState(current ? String(current.budget.amount_usd) : '');
+
+  const mutation = useMutation({
+    mutationFn: () => setBudget({ period, amount_usd: Number(amount) }),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: ['cost-budget'] }),
+  });
+
+  return (
+    <form
+      className="flex items-center gap-2"
+      onSubmit={(e) => {
+        e.preventDefault();
+        mutation.mutate();
+      }}
+    >
+      <select
+        value={period}
+        onChange={(e) => setPeriod(e.target.value as BudgetPeriod)}
+        className="h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+      >
+        <option value="daily">Daily</option>
+        <option value="weekly">Weekly</option>
+        <option value="monthly">Monthly</option>
+      </select>
+      <Input
+        type="number"
+        min={0}
+        step="0.01"
+        placeholder="Amount (USD)"
+        value={amount}
+        onChange={(e) => setAmount(e.target.value)}
+        className="w-36"
+      />
+      <Button type="submit" size="sm" disabled={!amount || mutation.isPending}>
+        Set budget
+      </Button>
+    </form>
+  );
+}
+
+export function Costs() {
+  const [days, setDays] = useState(30);
+  const [by, setBy] = useState<CostBreakdownBy>('model');
+
+  const { data: daily = [] } = useQuery({
+    queryKey: ['cost-daily', days],
+    queryFn: () => getDailyCosts(days),
+    refetchInterval: 60000,
+  });
+
+  const { data: breakdown = [] } = useQuery({
+    queryKey: ['cost-breakdown', by, days],
+    queryFn: () => getCostBreakdown(by, days),
+    refetchInterval: 60000,
+  });
+
+  const { data: burnDown } = useQuery({
+    queryKey: ['cost-budget'],
+    queryFn: getBudget,
+    refetchInterval: 60000,
+  });
+
+  const total = daily.reduce((sum, day) => sum + day.cost_usd, 0);
+  const inputTokens = daily.reduce((sum, day) => sum + day.input_tokens, 0);
+  const cacheRead = daily.reduce((sum, day) => sum + day.cache_read_tokens, 0);
+  const cacheHitRate = inputTokens > 0 ? cacheRead / inputTokens : 0;
+  const breakdownTotal = breakdown.reduce((sum, item) => sum + item.estimated_cost_usd, 0);
+
+  return (
+    <div className="space-y-8">
+      <div className="flex items-center justify-between">
+        <h1 className="text-3xl font-bold">Costs</h1>
+        <div className="flex gap-1">
+          {RANGES.map((range) => (
+            <Button
+              key={range}
+              size="sm"
+              variant={range === days ? 'default' : 'outline'}
+              onClick={() => setDays(range)}
+            >
+              {range}d
+            </Button>
+          ))}
+        </div>
+      </div>
+
+      <div className="grid gap-4 md:grid-cols-2 lg:grid-cols-4">
+        <MetricCard
+          label={`Spend (${days}d)`}
+          value={usd(total)}
+          icon={<DollarSign className="h-5 w-5 text-muted-foreground" />}
+        />
+        <MetricCard
+          label="Daily average"
+          value={usd(daily.length > 0 ? total / daily.length : 0)}
+          icon={<TrendingUp className="h-5 w-5 text-muted-foreground" />}
+        />
+        <MetricCard
+          label="Cache hit rate"
+          value={`${(cacheHitRate * 100).toFixed(1)}%`}
+          icon={<Percent className="h-5 w-5 text-muted-foreground" />}
+        />
+        <MetricCard
+          label="Budget left"
+          value={burnDown ? usd(burnDown.remaining) : 'No budget'}
+          icon={<Target className="h-5 w-5 text-muted-foreground" />}
+        />
+      </div>
+
+      <Card>
+        <CardHeader>
+          <div className="flex items-center justify-between">
+            <CardTitle>Daily spend</CardTitle>
+            <CsvLink href={dailyCostsCsvUrl(days)} />
+          </div>
+        </CardHeader>
+        <CardContent>
+          <DailyBars days={daily} value={(d) => d.cost_usd} format={usd} className="bg-blue-500" />
+        </CardContent>
+      </Card>
+
+      <div className="grid gap-4 lg:grid-cols-2">
+        <Card>
+          <CardHeader>
+            <div className="flex items-center justify-between">
+              <CardTitle>Spend by</CardTitle>
+              <div className="flex gap-1">
+                {BREAKDOWNS.map((option) => (
+                  <Button
+                    key={option.by}
+                    size="sm"
+                    variant={option.by === by ? 'default' : 'outline'}
+                    onClick={() => setBy(option.by)}
+                  >
+                    {option.label}
+                  </Button>
+                ))}
+                <CsvLink href={costBreakdownCsvUrl(by, days)} />
+              </div>
+            </div>
+          </CardHeader>
+          <CardContent>
+            {breakdown.length === 0 ? (
+              <div className="text-center py-8 text-muted-foreground">No spend recorded</div>
+            ) : (
+              <div className="space-y-3">
+                {breakdown.map((item) => (
+                  <div key={item.entity_id} className="space-y-1">
+                    <div className="flex justify-between text-sm">
+                      <span className="font-medium truncate">{item.entity_id}</span>
+                      <span>
+                        {usd(item.estimated_cost_usd)}
+                        <span className="ml-2 text-muted-foreground">
+                          {item.request_count} requests
+                        </span>
+                      </span>
+                    </div>
+                    <div className="h-2 rounded-full bg-secondary">
+                      <div
+                        className="h-2 rounded-full bg-green-500"
+                        style={{
+                          width: `${breakdownTotal > 0 ? (item.estimated_cost_usd / breakdownTotal) * 100 : 0}%`,
+                        }}
+                      />
+                    </div>
+                  </div>
+                ))}
+              </div>
+            )}
+          </CardContent>
+        </Card>
+
+        <Card>
+          <CardHeader>
+            <CardTitle>Cache hit rate</CardTitle>
+          </CardHeader>
+          <CardContent>
+            <DailyBars
+              days={daily}
+              value={(d) => d.cache_hit_rate}
+              format={(rate) => `${(rate * 100).toFixed(1)}%`}
+              className="bg-purple-500"
+            />
+          </CardContent>
+        </Card>
+      </div>
+
+      <Card>
+        <CardHeader>
+          <div className="flex items-center justify-between">
+            <CardTitle>Budget burn-down</CardTitle>
+            <BudgetForm key={burnDown?.budget.id ?? 'none'} current={burnDown} />
+          </div>
+        </CardHeader>
+        <CardContent>
+          {burnDown ? (
+            <div className="space-y-4">
+              <div className="grid grid-cols-2 md:grid-cols-4 gap-4 text-sm">
+                <div>
+                  <div className="text-muted-foreground">Budget</div>
+                  <div className="font-medium">
+                    {usd(burnDown.budget.amount_usd)} {burnDown.budget.period_type}
+                  </div>
+                </div>
+                <div>
+                  <div className="text-muted-foreground">Period</div>
+                  <div className="font-medium">
+                    {burnDown.period_start} – {burnDown.period_end}
+                  </div>
+                </div>
+                <div>
+                  <div className="text-muted-foreground">Spent</div>
+                  <div className="font-medium">{usd(burnDown.spent)}</div>
+                </div>
+                <div>
+                  <div className="text-muted-foreground">Projected</div>
+                  <div
+                    className={cn(
+                      'font-medium',
+                      burnDown.projected > burnDown.budget.amount_usd && 'text-red-600'
+                    )}
+                  >
+                    {usd(burnDown.projected)}
+                  </div>
+                </div>
+              </div>
+              <BurnDownChart burnDown={burnDown} />
+            </div>
+          ) : (
+            <div className="text-center py-8 text-muted-foreground">
+              Set a budget to track spend against it
+            </div>
+          )}
+        </CardContent>
+      </Card>
+    </div>
+  );
+}
@@ -0,0 +1,324 @@
+import { useState } from 'react';
+import { Link, useParams } from 'react-router-dom';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { ArrowLeft, Pause, Play, Trophy } from 'lucide-react';
+import {
+  completeExperiment,
+  getExperiment,
+  getExperimentDailyResults,
+  getExperimentResults,
+  pauseExperiment,
+  startExperiment,
+} from '@/api/experiments';
+import type { ExperimentVariantResult, VariantDailyResult } from '@/api/types';
+import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import { Badge } from '@/components/ui/badge';
+import { experimentStatusVariants } from './Experiments';
+
+const VARIANT_COLORS = ['#3b82f6', '#22c55e', '#f97316', '#a855f7', '#ec4899', '#14b8a6'];
+
+// Cumulative success rate per variant, one point per day with results
+function ConversionChart({ daily }: { daily: VariantDailyResult[] }) {
+  const width = 600;
+  const height = 200;
+  const dates = [...new Set(daily.map((d) => d.date))].sort();
+  const variants = [...new Set(daily.map((d) => d.variant_name))];
+  const x = (index: number) => (dates.length > 1 ? (index / (dates.length - 1)) * width : width / 2);
+  const y = (rate: number) => height - rate * height;
+
+  if (dates.length === 0) {
+    return <div className="text-sm text-muted-foreground">No results recorded yet</div>;
+  }
+
+  return (
+    <div className="space-y-2">
+      <svg viewBox={`0 0 ${width} ${height}`} className="h-48 w-full" preserveAspectRatio="none">
+        {[0.25, 0.5, 0.75].map((rate) => (
+          <line
+            key={rate}
+            x1={0}
+            y1={y(rate)}
+            x2={width}
+            y2={y(rate)}
+            className="stroke-border"
+            strokeDasharray="4 4"
+          />
+        ))}
+        {variants.map((name, index) => {
+          let samples = 0;
+          let successes = 0;
+          const points: string[] = [];
+          dates.forEach((date, i) => {
+            const day = daily.find((d) => d.variant_name === name && d.date === date);
+            if (!day) return;
+            samples += day.sample_count;
+            successes += day.success_count;
+            points.push(`${x(i)},${y(successes / samples)}`);
+          });
+          return (
+            <polyline
+              key={name}
+              points={points.join(' ')}
+              fill="none"
+              strokeWidth={2}
+              stroke={VARIANT_COLORS[index % VARIANT_COLORS.length]}
+            />
+          );
+        })}
+      </svg>
+      <div className="flex items-center justify-between text-xs text-muted-foreground">
+        <span>{dates[0]}</span>
+        <div className="flex gap-3">
+          {variants.map((name, index) => (
+            <span key={name} className="inline-flex items-center gap-1">
+              <span
+                className="h-2 w-2 rounded-full"
+                style={{ backgroundColor: VARIANT_COLORS[index % VARIANT_COLORS.length] }}
+              />
+              {name}
+            </span>
+          ))}
+        </div>
+        <span>{dates[dates.length - 1]}</span>
+      </div>
+    </div>
+  );
+}
+
+// Mean of each variant with its 95% confidence interval, on a shared scale
+function ConfidenceIntervals({ variants }: { variants: ExperimentVariantResult[] }) {
+  const low = Math.min(...variants.map((v) => v.ci_lower));
+  const high = Math.max(...variants.map((v) => v.ci_upper));
+  const span = Math.max(high - low, 1e-9);
+  const position = (value: number) => `${((value - low) / span) * 100}%`;
+
+  return (
+    <div className="space-y-3">
+      {variants.map((variant, index) => (
+        <div key={variant.variant_id} className="space-y-1 text-sm">
+          <div className="flex items-center justify-between">
+            <span className="font-medium">
+              {variant.name}
+              {variant.is_control && (
+                <span className="ml-2 text-xs text-muted-foreground">control</span>
+              )}
+            </span>
+            <span className="font-mono text-xs text-muted-foreground">
+              {variant.mean.toFixed(3)} [{variant.ci_lower.toFixed(3)}, {variant.ci_upper.toFixed(3)}]
+            </span>
+          </div>
+          <div className="relative h-3 rounded bg-muted">
+            <div
+              className="absolute top-1 h-1 rounded"
+              style={{
+                left: position(variant.ci_lower),
+                width: `calc(${position(variant.ci_upper)} - ${position(variant.ci_lower)})`,
+                backgroundColor: VARIANT_COLORS[index % VARIANT_COLORS.length],
+              }}
+            />
+            <div
+              className="absolute top-0 h-3 w-0.5 bg-foreground"
+              style={{ left: position(variant.mean) }}
+            />
+          </div>
+        </div>
+      ))}
+    </div>
+  );
+}
+
+export function ExperimentDetail() {
+  const { id = '' } = useParams<{ id: string }>();
+  const experimentId = Number(id);
+  const queryClient = useQueryClient();
+  const [winner, setWinner] = useState('');
+
+  const { data: experiment, isLoading } = useQuery({
+    queryKey: ['experiment', experimentId],
+    queryFn: () => getExperiment(experimentId),
+  });
+  const { data: results } = useQuery({
+    queryKey: ['experiment-results', experimentId],
+    queryFn: () => getExperimentResults(experimentId),
+    refetchInterval: 15000,
+  });
+  const { data: daily = [] } = useQuery({
+    queryKey: ['experiment-daily', experimentId],
+    queryFn: () => getExperimentDailyResults(experimentId),
+    refetchInterval: 60000,
+  });
+
+  const invalidate = () => {
+    queryClient.invalidateQueries({ queryKey: ['experiment', experimentId] });
+    queryClient.invalidateQueries({ queryKey: ['experiments'] });
+  };
+  const startMutation = useMutation({
+    mutationFn: () => startExperiment(experimentId),
+    onSuccess: invalidate,
+  });
+  const pauseMutation = useMutation({
+    mutationFn: () => pauseExperiment(experimentId),
+    onSuccess: invalidate,
+  });
+  const completeMutation = useMutation({
+    mutationFn: () => completeExperiment(experimentId, Number(winner)),
+    onSuccess: invalidate,
+  });
+
+  if (isLoading || !experiment) {
+    return <div className="text-center py-12 text-muted-foreground">Loading...</div>;
+  }
+
+  const variants = results?.variants ?? [];
+  const progress = results
+    ? Math.min(1, results.total_samples / Math.max(results.min_samples * Math.max(variants.length, 1), 1))
+    : 0;
+  const error = startMutation.error ?? pauseMutation.error ?? completeMutation.error;
+  const active = experiment.status === 'running' || experiment.status === 'paused';
+  const winnerName = variants.find((v) => v.variant_id === experiment.winner_variant_id)?.name;
+
+  return (
+    <div className="space-y-6">
+      <Link
+        to="/experiments"
+        className="inline-flex items-center text-sm text-muted-foreground hover:text-foreground"
+      >
+        <ArrowLeft className="mr-1 h-4 w-4" />
+        Experiments
+      </Link>
+
+      <div className="flex items-start justify-between gap-4">
+        <div>
+          <div className="flex items-center gap-2">
+            <h1 className="text-3xl font-bold">{experiment.name}</h1>
+            <Badge variant={experimentStatusVariants[experiment.status]}>{experiment.status}</Badge>
+          </div>
+          <div className="mt-1 text-sm text-muted-foreground">
+            {experiment.experiment_type.replace(/_/g, ' ')} · {experiment.metric.replace(/_/g, ' ')}
+            {experiment.started_at && ` · started ${new Date(experiment.started_at).toLocaleString()}`}
+            {experiment.completed_at &&
+              ` · completed ${new Date(experiment.completed_at).toLocaleString()}`}
+          </div>
+          {experiment.description && <p className="mt-2 text-sm">{experiment.description}</p>}
+          {winnerName && (
+            <div className="mt-2 inline-flex items-center gap-1 text-sm text-green-600">
+              <Trophy className="h-4 w-4" />
+              Winner: {winnerName}
+            </div>
+          )}
+        </div>
+        <div className="flex items-center gap-2">
+          {(experiment.status === 'draft' || experiment.status === 'paused') && (
+            <Button onClick={() => startMutation.mutate()} disabled={startMutation.isPending}>
+              <Play className="mr-2 h-4 w-4" />
+              {experiment.status === 'draft' ? 'Start' : 'Resume'}
+            </Button>
+          )}
+          {experiment.status === 'running' && (
+            <Button
+              variant="outline"
+              onClick={() => pauseMutation.mutate()}
+              disabled={pauseMutation.isPending}
+            >
+              <Pause className="mr-2 h-4 w-4" />
+              Pause
+            </Button>
+          )}
+          {active && (
+            <form
+              className="flex items-center gap-2"
+              onSubmit={(e) => {
+                e.preventDefault();
+                if (winner) completeMutation.mutate();
+              }}
+            >
+              <select
+                value={winner}
+                onChange={(e) => setWinner(e.target.value)}
+                className="h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+              >
+                <option value="">Pick a winner</option>
+                {variants.map((variant) => (
+                  <option key={variant.variant_id} value={variant.variant_id}>
+                    {variant.name}
+                  </option>
+                ))}
+              </select>
+              <Button type="submit" disabled={!winner || completeMutation.isPending}>
+                <Trophy className="mr-2 h-4 w-4" />
+                Complete
+              </Button>
+            </form>
+          )}
+        </div>
+      </div>
+      {error && <div className="text-sm text-red-600">{error.message}</div>}
+
+      {results && (
+        <Card>
+          <CardHeader>
+            <CardTitle>Sample progress</CardTitle>
+          </CardHeader>
+          <CardContent className="space-y-2 text-sm">
+            <div className="flex items-center justify-between">
+              <span>
+                {results.total_samples} samples · {results.min_samples} needed per variant
+              </span>
+              <span className="text-muted-foreground">
+                {results.is_significant
+                  ? `Significant${results.p_value !== null ? ` (p = ${results.p_value.toFixed(4)})` : ''}`
+                  : 'Not significant yet'}
+              </span>
+            </div>
+            <div className="h-2 rounded bg-muted">
+              <div
+                className="h-2 rounded bg-primary"
+                style={{ width: `${Math.round(progress * 100)}%` }}
+              />
+            </div>
+            <div className="grid gap-2 sm:grid-cols-3">
+              {variants.map((variant) => (
+                <div key={variant.variant_id} className="flex items-center justify-between">
+                  <span>{variant.name}</span>
+                  <span className="text-muted-foreground">
+                    {variant.sample_count} / {results.min_samples}
+                  </span>
+                </div>
+              ))}
+            </div>
+            {results.improvement !== null && (
+              <div className="text-muted-foreground">
+                {results.winning_variant ?? 'Best variant'} improves on control by{' '}
+                {results.improvement.toFixed(1)}%
+              </div>
+            )}
+          </CardContent>
+        </Card>
+      )}
+
+      <div className="grid gap-6 lg:grid-cols-2">
+        <Card>
+          <CardHeader>
+            <CardTitle>Conversion over time</CardTitle>
+          </CardHeader>
+          <CardContent>
+            <ConversionChart daily={daily} />
+          </CardContent>
+        </Card>
+        <Card>
+          <CardHeader>
+            <CardTitle>Confidence intervals</CardTitle>
+          </CardHeader>
+          <CardContent>
+            {variants.length === 0 ? (
+              <div className="text-sm text-muted-foreground">No variants</div>
+            ) : (
+              <ConfidenceIntervals variants={variants} />
+            )}
+          </CardContent>
+        </Card>
+      </div>
+    </div>
+  );
+}
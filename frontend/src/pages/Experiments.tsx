@@ -0,0 +1,78 @@
+import { useState } from 'react';
+import { Link } from 'react-router-dom';
+import { useQuery } from '@tanstack/react-query';
+import { listExperiments } from '@/api/experiments';
+import type { ExperimentStatus } from '@/api/types';
+import { Card, CardContent } from '@/components/ui/card';
+import { Badge } from '@/components/ui/badge';
+import { formatDistanceToNow } from '@/lib/time';
+
+export const experimentStatusVariants: Record<
+  ExperimentStatus,
+  'success' | 'warning' | 'secondary' | 'default' | 'destructive'
+> = {
+  draft: 'secondary',
+  running: 'success',
+  paused: 'warning',
+  completed: 'default',
+  cancelled: 'destructive',
+};
+
+export function Experiments() {
+  const [status, setStatus] = useState<ExperimentStatus | ''>('');
+
+  const { data: experiments = [], isLoading } = useQuery({
+    queryKey: ['experiments', status],
+    queryFn: () => listExperiments(status || undefined),
+    refetchInterval: 15000,
+  });
+
+  return (
+    <div className="space-y-6">
+      <div className="flex items-center justify-between">
+        <h1 className="text-3xl font-bold">Experiments</h1>
+        <select
+          value={status}
+          onChange={(e) => setStatus(e.target.value as ExperimentStatus | '')}
+          className="h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+        >
+          <option value="">All statuses</option>
+          {Object.keys(experimentStatusVariants).map((value) => (
+            <option key={value} value={value}>
+              {value}
+            </option>
+          ))}
+        </select>
+      </div>
+
+      {isLoading ? (
+        <div className="text-center py-8 text-muted-foreground">Loading...</div>
+      ) : experiments.length === 0 ? (
+        <div className="text-center py-8 text-muted-foreground">No experiments</div>
+      ) : (
+        <Card>
+          <CardContent className="divide-y p-0">
+            {experiments.map((experiment) => (
+              <Link
+                key={experiment.id}
+                to={`/experiments/${experiment.id}`}
+                className="flex items-center gap-4 px-4 py-3 hover:bg-accent"
+              >
+                <div className="min-w-0 flex-1">
+                  <div className="truncate font-medium">{experiment.name}</div>
+                  <div className="text-xs text-muted-foreground">
+                    {experiment.experiment_type.replace(/_/g, ' ')} · {experiment.metric.replace(/_/g, ' ')}
+                  </div>
+                </div>
+                <Badge variant={experimentStatusVariants[experiment.status]}>{experiment.status}</Badge>
+                <span className="w-28 text-right text-sm text-muted-foreground">
+                  {formatDistanceToNow(experiment.created_at)}
+                </span>
+              </Link>
+            ))}
+          </CardContent>
+        </Card>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,356 @@
+import { useState } from 'react';
+import { Link, useParams } from 'react-router-dom';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { ArrowLeft, CheckCircle2, FileText, MessageSquare, XCircle } from 'lucide-react';
+import { addTimelineComment, generatePostMortem, getIncident } from '@/api/incidents';
+import type {
+  GeneratedPostMortem,
+  PlaybookExecution,
+  RootCauseAnalysis,
+  TimelineEvent,
+  TimelineEventType,
+} from '@/api/types';
+import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import { Badge } from '@/components/ui/badge';
+import { Input } from '@/components/ui/input';
+import { cn } from '@/lib/utils';
+import { incidentStatusLabels, severityVariants } from './Incidents';
+
+const eventColors: Record<TimelineEventType, string> = {
+  detected: 'bg-red-500',
+  acknowledged: 'bg-yellow-500',
+  investigation_started: 'bg-blue-500',
+  root_cause_identified: 'bg-purple-500',
+  mitigation_started: 'bg-blue-500',
+  playbook_executed: 'bg-indigo-500',
+  escalated: 'bg-orange-500',
+  resolved: 'bg-green-500',
+  post_mortem_created: 'bg-gray-500',
+  comment: 'bg-gray-300',
+};
+
+function Timeline({ incidentId, events }: { incidentId: string; events: TimelineEvent[] }) {
+  const queryClient = useQueryClient();
+  const [comment, setComment] = useState('');
+
+  const mutation = useMutation({
+    mutationFn: () =>
+      addTimelineComment(incidentId, comment.trim(), localStorage.getItem('approver') ?? undefined),
+    onSuccess: () => {
+      setComment('');
+      queryClient.invalidateQueries({ queryKey: ['incident', incidentId] });
+    },
+  });
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Timeline</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <ol className="relative space-y-4 border-l pl-6">
+          {events.map((event, index) => (
+            <li key={index} className="relative">
+              <span
+                className={cn(
+                  'absolute -left-[29px] top-1.5 h-2.5 w-2.5 rounded-full',
+                  eventColors[event.event_type]
+                )}
+              />
+              <div className="flex flex-wrap items-baseline gap-2 text-sm">
+                <span className="font-mono text-xs text-muted-foreground">
+                  {new Date(event.timestamp).toLocaleString()}
+                </span>
+                <span className="text-xs uppercase text-muted-foreground">
+                  {event.event_type.replace(/_/g, ' ')}
+                </span>
+                {event.actor && <span className="text-xs text-muted-foreground">by {event.actor}</span>}
+              </div>
+              <div className="text-sm">{event.description}</div>
+            </li>
+          ))}
+        </ol>
+
+        <form
+          className="flex items-center gap-2"
+          onSubmit={(e) => {
+            e.preventDefault();
+            if (comment.trim()) mutation.mutate();
+          }}
+        >
+          <Input
+            placeholder="Add a note to the timeline"
+            value={comment}
+            onChange={(e) => setComment(e.target.value)}
+          />
+          <Button type="submit" variant="outline" disabled={mutation.isPending || !comment.trim()}>
+            <MessageSquare className="mr-2 h-4 w-4" />
+            Comment
+          </Button>
+        </form>
+        {mutation.error && <div className="text-sm text-red-600">{mutation.error.message}</div>}
+      </CardContent>
+    </Card>
+  );
+}
+
+function Investigation({ analysis }: { analysis: RootCauseAnalysis | null }) {
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Investigation</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4 text-sm">
+        {!analysis ? (
+          <div className="text-muted-foreground">No root cause analysis yet</div>
+        ) : (
+          <>
+            <div>
+              <div className="text-muted-foreground">Primary cause</div>
+              <div className="font-medium">{analysis.primary_cause || 'Unknown'}</div>
+            </div>
+
+            <div>
+              <div className="mb-1 text-muted-foreground">Hypotheses</div>
+              {analysis.hypotheses.length === 0 && <div>None</div>}
+              {analysis.hypotheses.map((hypothesis, index) => (
+                <div key={index} className="mb-2">
+                  <div className="flex items-center justify-between">
+                    <span>{hypothesis.description}</span>
+                    <span className="text-xs text-muted-foreground">
+                      {Math.round(hypothesis.confidence * 100)}%
+                    </span>
+                  </div>
+                  <div className="mt-1 h-1.5 rounded bg-muted">
+                    <div
+                      className="h-1.5 rounded bg-primary"
+                      style={{ width: `${Math.round(hypothesis.confidence * 100)}%` }}
+                    />
+                  </div>
+                </div>
+              ))}
+            </div>
+
+            <div>
+              <div className="mb-1 text-muted-foreground">Evidence</div>
+              {analysis.evidence.length === 0 && <div>None</div>}
+              <ul className="space-y-1">
+                {analysis.evidence.map((evidence, index) => (
+                  <li key={index} className="flex items-start gap-2">
+                    <Badge variant="secondary">{evidence.evidence_type.replace(/_/g, ' ')}</Badge>
+                    <span className="flex-1">{evidence.description}</span>
+                    <span className="text-xs text-muted-foreground">{evidence.source}</span>
+                  </li>
+                ))}
+              </ul>
+            </div>
+
+            {analysis.contributing_factors.length > 0 && (
+              <div>
+                <div className="mb-1 text-muted-foreground">Contributing factors</div>
+                <ul className="list-disc pl-5">
+                  {analysis.contributing_factors.map((factor, index) => (
+                    <li key={index}>{factor}</li>
+                  ))}
+                </ul>
+              </div>
+            )}
+          </>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
+
+function PlaybookExecutions({ executions }: { executions: PlaybookExecution[] }) {
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Playbook executions</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4 text-sm">
+        {executions.length === 0 && <div className="text-muted-foreground">No playbooks ran</div>}
+        {executions.map((execution) => (
+          <div key={execution.id} className="space-y-2 rounded-md border p-3">
+            <div className="flex items-center justify-between">
+              <span className="font-medium">{execution.playbook_name ?? execution.playbook_id}</span>
+              <Badge
+                variant={
+                  execution.status === 'completed'
+                    ? 'success'
+                    : execution.status === 'failed'
+                      ? 'destructive'
+                      : 'warning'
+                }
+              >
+                {execution.status.replace(/_/g, ' ')}
+              </Badge>
+            </div>
+            <div className="text-xs text-muted-foreground">
+              Started {new Date(execution.started_at).toLocaleString()}
+              {execution.triggered_by && ` by ${execution.triggered_by}`}
+            </div>
+            {execution.action_results.map((result, index) => (
+              <div key={index} className="flex items-start gap-2">
+                {result.success ? (
+                  <CheckCircle2 className="mt-0.5 h-4 w-4 text-green-600" />
+                ) : (
+                  <XCircle className="mt-0.5 h-4 w-4 text-red-600" />
+                )}
+                <div className="min-w-0">
+                  <div>{result.action_name}</div>
+                  {(result.error || result.output) && (
+                    <pre className="whitespace-pre-wrap text-xs text-muted-foreground">
+                      {result.error ?? result.output}
+                    </pre>
+                  )}
+                </div>
+              </div>
+            ))}
+          </div>
+        ))}
+      </CardContent>
+    </Card>
+  );
+}
+
+export function IncidentDetail() {
+  const { id = '' } = useParams<{ id: string }>();
+  const queryClient = useQueryClient();
+  const [generated, setGenerated] = useState<GeneratedPostMortem | null>(null);
+
+  const { data: incident, isLoading } = useQuery({
+    queryKey: ['incident', id],
+    queryFn: () => getIncident(id),
+    refetchInterval: 15000,
+  });
+
+  const postMortemMutation = useMutation({
+    mutationFn: () => generatePostMortem(id),
+    onSuccess: (postMortem) => {
+      setGenerated(postMortem);
+      queryClient.invalidateQueries({ queryKey: ['incident', id] });
+      queryClient.invalidateQueries({ queryKey: ['incidents'] });
+    },
+  });
+
+  if (isLoading || !incident) {
+    return <div className="text-center py-12 text-muted-foreground">Loading...</div>;
+  }
+
+  const resolved = incident.status === 'resolved' || incident.status === 'post_mortem';
+
+  return (
+    <div className="space-y-6">
+      <Link
+        to="/incidents"
+        className="inline-flex items-center text-sm text-muted-foreground hover:text-foreground"
+      >
+        <ArrowLeft className="mr-1 h-4 w-4" />
+        Incidents
+      </Link>
+
+      <div className="flex items-start justify-between gap-4">
+        <div>
+          <div className="flex items-center gap-2">
+            <h1 className="text-3xl font-bold">{incident.title}</h1>
+            <Badge variant={severityVariants[incident.severity]}>{incident.severity}</Badge>
+            <Badge variant="secondary">{incidentStatusLabels[incident.status]}</Badge>
+          </div>
+          <div className="mt-1 text-sm text-muted-foreground">
+            {incident.id} · detected {new Date(incident.detected_at).toLocaleString()}
+            {incident.resolved_at && ` · resolved ${new Date(incident.resolved_at).toLocaleString()}`}
+          </div>
+          {incident.description && <p className="mt-2 text-sm">{incident.description}</p>}
+          {incident.affected_services.length > 0 && (
+            <div className="mt-2 flex flex-wrap gap-1">
+              {incident.affected_services.map((service) => (
+                <Badge key={service} variant="outline">
+                  {service}
+                </Badge>
+              ))}
+            </div>
+          )}
+        </div>
+        <Button
+          onClick={() => postMortemMutation.mutate()}
+          disabled={!resolved || postMortemMutation.isPending}
+          title={resolved ? undefined : 'Resolve the incident first'}
+        >
+          <FileText className="mr-2 h-4 w-4" />
+          {postMortemMutation.isPending
+            ? 'Generating...'
+            : incident.post_mortem
+              ? 'Regenerate post-mortem'
+              : 'Generate post-mortem'}
+        </Button>
+      </div>
+      {postMortemMutation.error && (
+        <div className="text-sm text-red-600">{postMortemMutation.error.message}</div>
+      )}
+
+      <div className="grid gap-6 lg:grid-cols-2">
+        <Timeline incidentId={incident.id} events={incident.timeline} />
+        <div className="space-y-6">
+          <Investigation analysis={incident.root_cause} />
+          <PlaybookExecutions executions={incident.playbook_executions} />
+        </div>
+      </div>
+
+      {(generated || incident.post_mortem) && (
+        <Card>
+          <CardHeader>
+            <div className="flex items-center justify-between">
+              <CardTitle>Post-mortem</CardTitle>
+              {generated?.artifact_url && (
+                <a
+                  href={generated.artifact_url}
+                  className="text-sm text-primary hover:underline"
+                  target="_blank"
+                  rel="noreferrer"
+                >
+                  Download Markdown
+                </a>
+              )}
+            </div>
+          </CardHeader>
+          <CardContent>
+            {generated ? (
+              <pre className="whitespace-pre-wrap rounded-md bg-muted p-4 text-sm">
+                {generated.markdown}
+              </pre>
+            ) : (
+              incident.post_mortem && (
+                <div className="space-y-3 text-sm">
+                  <p>{incident.post_mortem.summary}</p>
+                  <div>
+                    <div className="text-muted-foreground">Root cause</div>
+                    <div>{incident.post_mortem.root_cause || 'Unknown'}</div>
+                  </div>
+                  <div>
+                    <div className="text-muted-foreground">Resolution</div>
+                    <div>{incident.post_mortem.resolution || '—'}</div>
+                  </div>
+                  {incident.post_mortem.action_items.length > 0 && (
+                    <div>
+                      <div className="text-muted-foreground">Action items</div>
+                      <ul className="list-disc pl-5">
+                        {incident.post_mortem.action_items.map((item, index) => (
+                          <li key={index}>
+                            {item.description}{' '}
+                            <span className="text-xs text-muted-foreground">({item.priority})</span>
+                          </li>
+                        ))}
+                      </ul>
+                    </div>
+                  )}
+                </div>
+              )
+            )}
+          </CardContent>
+        </Card>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,104 @@
+import { useState } from 'react';
+import { Link } from 'react-router-dom';
+import { useQuery } from '@tanstack/react-query';
+import { listIncidents } from '@/api/incidents';
+import type { IncidentSeverity, IncidentStatus } from '@/api/types';
+import { Card, CardContent } from '@/components/ui/card';
+import { Badge } from '@/components/ui/badge';
+import { formatDistanceToNow } from '@/lib/time';
+
+export const severityVariants: Record<
+  IncidentSeverity,
+  'destructive' | 'warning' | 'secondary' | 'default'
+> = {
+  critical: 'destructive',
+  high: 'destructive',
+  medium: 'warning',
+  low: 'secondary',
+};
+
+export const incidentStatusLabels: Record<IncidentStatus, string> = {
+  detected: 'Detected',
+  investigating: 'Investigating',
+  mitigating: 'Mitigating',
+  resolved: 'Resolved',
+  post_mortem: 'Post-mortem',
+};
+
+export function Incidents() {
+  const [status, setStatus] = useState<IncidentStatus | ''>('');
+  const [severity, setSeverity] = useState<IncidentSeverity | ''>('');
+
+  const { data: incidents = [], isLoading } = useQuery({
+    queryKey: ['incidents', status, severity],
+    queryFn: () =>
+      listIncidents({ status: status || undefined, severity: severity || undefined }),
+    refetchInterval: 15000,
+  });
+
+  return (
+    <div className="space-y-6">
+      <div className="flex items-center justify-between">
+        <h1 className="text-3xl font-bold">Incidents</h1>
+        <div className="flex items-center gap-2">
+          <select
+            value={status}
+            onChange={(e) => setStatus(e.target.value as IncidentStatus | '')}
+            className="h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+          >
+            <option value="">All statuses</option>
+            {Object.entries(incidentStatusLabels).map(([value, label]) => (
+              <option key={value} value={value}>
+                {label}
+              </option>
+            ))}
+          </select>
+          <select
+            value={severity}
+            onChange={(e) => setSeverity(e.target.value as IncidentSeverity | '')}
+            className="h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+          >
+            <option value="">All severities</option>
+            {Object.keys(severityVariants).map((value) => (
+              <option key={value} value={value}>
+                {value}
+              </option>
+            ))}
+          </select>
+        </div>
+      </div>
+
+      {isLoading ? (
+        <div className="text-center py-8 text-muted-foreground">Loading...</div>
+      ) : incidents.length === 0 ? (
+        <div className="text-center py-8 text-muted-foreground">No incidents</div>
+      ) : (
+        <Card>
+          <CardContent className="divide-y p-0">
+            {incidents.map((incident) => (
+              <Link
+                key={incident.id}
+                to={`/incidents/${encodeURIComponent(incident.id)}`}
+                className="flex items-center gap-4 px-4 py-3 hover:bg-accent"
+              >
+                <Badge variant={severityVariants[incident.severity]}>{incident.severity}</Badge>
+                <div className="min-w-0 flex-1">
+                  <div className="truncate font-medium">{incident.title}</div>
+                  <div className="text-xs text-muted-foreground">
+                    {incident.id}
+                    {incident.affected_services.length > 0 &&
+                      ` · ${incident.affected_services.join(', ')}`}
+                  </div>
+                </div>
+                <Badge variant="secondary">{incidentStatusLabels[incident.status]}</Badge>
+                <span className="w-28 text-right text-sm text-muted-foreground">
+                  {formatDistanceToNow(incident.detected_at)}
+                </span>
+              </Link>
+            ))}
+          </CardContent>
+        </Card>
+      )}
+    </div>
+  );
+}
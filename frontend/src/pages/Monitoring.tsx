@@ -11,6 +11,7 @@ import { MetricCard } from '@/components/monitoring/MetricCard';
 import { AlertsList } from '@/components/monitoring/AlertsList';
 import { CostChart } from '@/components/monitoring/CostChart';
 import { PerformanceTable } from '@/components/monitoring/PerformanceTable';
+import { LatencyTable } from '@/components/monitoring/LatencyTable';
 import {
   Activity,
   Clock,
@@ -158,7 +159,10 @@ export function Monitoring() {
       </div>
 
       {/* Alerts List */}
-      {alertsData && <AlertsList alerts={alertsData.alerts} />}
+      {alertsData && <AlertsList alerts={alertsData.items} />}
+
+      {/* Per-endpoint latency percentiles */}
+      {metrics?.latency && <LatencyTable endpoints={metrics.latency.endpoints} />}
 
       {/* Performance and Cost Grid */}
       <div className="grid grid-cols-1 lg:grid-cols-3 gap-6">
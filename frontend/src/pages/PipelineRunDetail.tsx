@@ -1,36 +1,80 @@
-import { useState } from 'react';
-import { useParams, Link } from 'react-router-dom';
+import { useCallback, useMemo, useState } from 'react';
+import { useParams, Link, useNavigate } from 'react-router-dom';
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
-import { ArrowLeft, X } from 'lucide-react';
+import { ArrowLeft, RotateCcw, X } from 'lucide-react';
 import {
   getPipelineRun,
   getPipelineStages,
+  getPipelineGraph,
   cancelPipelineRun,
+  triggerPipelineRun,
   listPendingApprovals,
 } from '@/api/pipelines';
+import { getMessages } from '@/api/agents';
+import type { PipelineStage, WsEventMessage } from '@/api/types';
 import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
 import { Button } from '@/components/ui/button';
 import { PipelineRunStatusBadge, PipelineStageStatusBadge } from '@/components/ui/badge';
 import { formatDate, formatDuration } from '@/lib/utils';
 import { ApprovalModal } from '@/components/pipelines/ApprovalModal';
+import { PipelineDag } from '@/components/pipelines/PipelineDag';
+import { useWebSocket } from '@/hooks/useWebSocket';
 
 export function PipelineRunDetail() {
   const { name, runId } = useParams<{ name: string; runId: string }>();
+  const navigate = useNavigate();
   const queryClient = useQueryClient();
   const [selectedApprovalId, setSelectedApprovalId] = useState<number | null>(null);
+  const [selectedStage, setSelectedStage] = useState<PipelineStage | null>(null);
+
+  // Run events refresh the run as it progresses; polling covers runs
+  // executed outside this server
+  const handleEvent = useCallback(
+    (event: WsEventMessage['event']) => {
+      if (!event.kind.startsWith('pipeline.')) return;
+      queryClient.invalidateQueries({ queryKey: ['pipeline-run', runId] });
+      queryClient.invalidateQueries({ queryKey: ['pipeline-stages', runId] });
+    },
+    [queryClient, runId]
+  );
+  const wsOptions = useMemo(
+    () => ({ channels: [`pipeline_run:${runId}`], onEvent: handleEvent }),
+    [runId, handleEvent]
+  );
+  useWebSocket(wsOptions);
 
   const { data: run, isLoading: runLoading } = useQuery({
     queryKey: ['pipeline-run', runId],
     queryFn: () => getPipelineRun(Number(runId)),
     enabled: !!runId,
-    refetchInterval: 3000, // Refresh every 3 seconds for live updates
+    refetchInterval: 10000,
   });
 
   const { data: stages = [], isLoading: stagesLoading } = useQuery({
     queryKey: ['pipeline-stages', runId],
     queryFn: () => getPipelineStages(Number(runId)),
     enabled: !!runId,
-    refetchInterval: 3000,
+    refetchInterval: 10000,
+  });
+
+  // Without a valid definition the stages are listed instead
+  const { data: graph } = useQuery({
+    queryKey: ['pipeline-graph', name],
+    queryFn: () => getPipelineGraph(name!),
+    enabled: !!name,
+    retry: false,
+  });
+
+  // Latest state of the selected stage
+  const stage = selectedStage
+    ? stages.find((s) => s.id === selectedStage.id) ?? selectedStage
+    : null;
+
+  const { data: logs = [] } = useQuery({
+    queryKey: ['agent-messages', stage?.agent_id],
+    queryFn: () => getMessages(stage!.agent_id!),
+    enabled: !!stage?.agent_id,
+    refetchInterval: stage?.status === 'Running' ? 3000 : false,
   });
 
   const { data: approvals = [] } = useQuery({
@@ -47,6 +91,15 @@ export function PipelineRunDetail() {
     },
   });
 
+  const rerunMutation = useMutation({
+    mutationFn: () =>
+      triggerPipelineRun(name!, { trigger_event: run?.trigger_event ?? undefined }),
+    onSuccess: (newRun) => {
+      queryClient.invalidateQueries({ queryKey: ['pipeline-runs', name] });
+      navigate(`/pipelines/${name}/runs/${newRun.id}`);
+    },
+  });
+
   const handleCancel = () => {
     if (window.confirm('Are you sure you want to cancel this pipeline run?')) {
       cancelMutation.mutate();
@@ -74,6 +127,10 @@ export function PipelineRunDetail() {
     run.status === 'Pending' ||
     run.status === 'Running' ||
     run.status === 'WaitingApproval';
+  const canRerun =
+    run.status === 'Succeeded' ||
+    run.status === 'Failed' ||
+    run.status === 'Cancelled';
 
   return (
     <div className="space-y-8">
@@ -89,6 +146,17 @@ export function PipelineRunDetail() {
         </h1>
         <div className="flex items-center gap-2">
           <PipelineRunStatusBadge status={run.status} />
+          {canRerun && (
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => rerunMutation.mutate()}
+              disabled={rerunMutation.isPending}
+            >
+              <RotateCcw className="mr-2 h-4 w-4" />
+              Rerun
+            </Button>
+          )}
           {canCancel && (
             <Button
               variant="destructive"
@@ -163,7 +231,14 @@ export function PipelineRunDetail() {
           <CardTitle>Pipeline Stages</CardTitle>
         </CardHeader>
         <CardContent>
-          {stages.length === 0 ? (
+          {graph && graph.nodes.length > 0 ? (
+            <PipelineDag
+              graph={graph}
+              stages={stages}
+              selectedStage={stage?.stage_name}
+              onSelectStage={setSelectedStage}
+            />
+          ) : stages.length === 0 ? (
             <div className="text-center py-8 text-muted-foreground">
               No stages yet
             </div>
@@ -174,7 +249,10 @@ export function PipelineRunDetail() {
                   {index > 0 && (
                     <div className="absolute left-6 -top-4 w-0.5 h-4 bg-border" />
                   )}
-                  <div className="flex items-start gap-4 p-4 border rounded-lg">
+                  <div
+                    className="flex items-start gap-4 p-4 border rounded-lg cursor-pointer hover:bg-accent"
+                    onClick={() => setSelectedStage(stage)}
+                  >
                     <div className="flex-shrink-0 w-12 h-12 rounded-full border-2 flex items-center justify-center bg-background">
                       <div className="text-sm font-semibold">{index + 1}</div>
                     </div>
@@ -210,6 +288,55 @@ export function PipelineRunDetail() {
         </CardContent>
       </Card>
 
+      {/* Stage Logs */}
+      {stage && (
+        <Card>
+          <CardHeader>
+            <div className="flex items-center justify-between">
+              <CardTitle className="flex items-center gap-3">
+                {stage.stage_name}
+                <PipelineStageStatusBadge status={stage.status} />
+              </CardTitle>
+              <Button variant="ghost" size="sm" onClick={() => setSelectedStage(null)}>
+                <X className="h-4 w-4" />
+              </Button>
+            </div>
+            <div className="text-sm text-muted-foreground">
+              {formatDuration(stage.started_at, stage.completed_at)}
+              {stage.attempts > 1 && ` · ${stage.attempts} attempts`}
+              {stage.agent_id && (
+                <>
+                  {' · '}
+                  <Link to={`/agents/${stage.agent_id}`} className="font-mono underline">
+                    {stage.agent_id}
+                  </Link>
+                </>
+              )}
+            </div>
+          </CardHeader>
+          <CardContent>
+            {!stage.agent_id ? (
+              <div className="text-center py-8 text-muted-foreground">
+                No agent has run this stage yet
+              </div>
+            ) : logs.length === 0 ? (
+              <div className="text-center py-8 text-muted-foreground">No logs yet</div>
+            ) : (
+              <div className="max-h-96 overflow-y-auto space-y-2 font-mono text-xs">
+                {logs.map((message) => (
+                  <div key={message.id} className="whitespace-pre-wrap">
+                    <span className="text-muted-foreground">
+                      [{formatDate(message.created_at)}] {message.role}:
+                    </span>{' '}
+                    {message.content}
+                  </div>
+                ))}
+              </div>
+            )}
+          </CardContent>
+        </Card>
+      )}
+
       {/* Approval Modal */}
       {selectedApprovalId && (
         <ApprovalModal
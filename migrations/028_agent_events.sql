@@ -0,0 +1,18 @@
+-- Agent Events Schema
+-- Structured per-agent event log, kept separate from the message transcript
+
+CREATE TABLE IF NOT EXISTS agent_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    agent_id TEXT NOT NULL,
+    session_id TEXT,
+    event_type TEXT NOT NULL CHECK(event_type IN (
+        'state_transition', 'tool_call', 'recovery', 'evaluation'
+    )),
+    summary TEXT NOT NULL,
+    data TEXT NOT NULL DEFAULT '{}',  -- JSON: event-specific payload
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_agent_events_agent_id ON agent_events(agent_id);
+CREATE INDEX IF NOT EXISTS idx_agent_events_agent_type ON agent_events(agent_id, event_type);
+CREATE INDEX IF NOT EXISTS idx_agent_events_created_at ON agent_events(created_at);
@@ -0,0 +1,18 @@
+-- Custom Agent Types Schema
+-- User-defined agent types layered on top of a built-in base type
+
+CREATE TABLE IF NOT EXISTS custom_agent_types (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    base_type TEXT NOT NULL,
+    system_prompt TEXT,
+    skills TEXT NOT NULL DEFAULT '[]',         -- JSON array of skill names
+    allowed_tools TEXT NOT NULL DEFAULT '[]',  -- JSON array; empty inherits base type tools
+    model TEXT,
+    max_turns INTEGER,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_custom_agent_types_base_type ON custom_agent_types(base_type);
@@ -0,0 +1,4 @@
+-- Agent Custom Type Column
+-- Records which user-defined agent type an agent was spawned as
+
+ALTER TABLE agents ADD COLUMN custom_type TEXT;
@@ -0,0 +1,39 @@
+-- Saga Compensation Schema
+-- Tracks compensation actions for multi-stage workflows (pipeline runs, epics)
+
+CREATE TABLE IF NOT EXISTS sagas (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    workflow_type TEXT NOT NULL CHECK(workflow_type IN ('pipeline_run', 'epic')),
+    workflow_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'running' CHECK(status IN (
+        'running', 'completed', 'compensating', 'compensated', 'compensation_failed'
+    )),
+    failed_step TEXT,
+    error_message TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    completed_at TEXT,
+    UNIQUE(workflow_type, workflow_id)
+);
+
+CREATE TABLE IF NOT EXISTS saga_compensations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    saga_id INTEGER NOT NULL REFERENCES sagas(id) ON DELETE CASCADE,
+    step_name TEXT NOT NULL,
+    sequence INTEGER NOT NULL,
+    action TEXT NOT NULL CHECK(action IN (
+        'revert_pr', 'delete_branch', 'rollback_deploy', 'custom'
+    )),
+    agent TEXT NOT NULL,
+    task TEXT NOT NULL,
+    params TEXT NOT NULL DEFAULT '{}',  -- JSON: action parameters
+    status TEXT NOT NULL DEFAULT 'pending' CHECK(status IN (
+        'pending', 'running', 'succeeded', 'failed', 'skipped'
+    )),
+    error_message TEXT,
+    executed_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_sagas_status ON sagas(status);
+CREATE INDEX IF NOT EXISTS idx_saga_compensations_saga_id ON saga_compensations(saga_id, sequence);
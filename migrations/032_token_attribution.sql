@@ -0,0 +1,8 @@
+-- Token Usage Attribution
+-- Attributes per-turn token usage to the epic/story the agent was working on
+
+ALTER TABLE session_token_stats ADD COLUMN epic_id TEXT;
+ALTER TABLE session_token_stats ADD COLUMN story_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_session_stats_epic ON session_token_stats(epic_id);
+CREATE INDEX IF NOT EXISTS idx_session_stats_story ON session_token_stats(story_id);
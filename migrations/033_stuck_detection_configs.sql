@@ -0,0 +1,10 @@
+-- Stuck Detection Configuration
+-- Per-agent-type stuck detector settings (JSON-encoded StuckDetectionConfig).
+-- The 'default' agent type applies to agent types without their own row.
+
+CREATE TABLE IF NOT EXISTS stuck_detection_configs (
+    agent_type TEXT PRIMARY KEY,
+    config TEXT NOT NULL DEFAULT '{}',
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
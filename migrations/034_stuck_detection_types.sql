@@ -0,0 +1,44 @@
+-- Stuck Detection Types
+-- Rebuilds stuck_agent_detections so the detection_type CHECK constraint
+-- accepts the no_file_changes and token_burn detectors.
+-- Only applied when the existing table lacks the new types.
+
+PRAGMA foreign_keys=OFF;
+
+CREATE TABLE stuck_agent_detections_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    agent_id TEXT NOT NULL,
+    session_id TEXT,
+    detection_type TEXT NOT NULL CHECK(detection_type IN (
+        'turn_limit', 'no_progress', 'ci_timeout', 'review_delay',
+        'merge_conflict', 'rate_limit', 'context_limit', 'error_loop',
+        'no_file_changes', 'token_burn'
+    )),
+    severity TEXT NOT NULL CHECK(severity IN ('low', 'medium', 'high', 'critical')),
+    details TEXT NOT NULL DEFAULT '{}',  -- JSON: detection details
+    resolved BOOLEAN NOT NULL DEFAULT FALSE,
+    resolution_action TEXT,  -- What action was taken
+    detected_at TEXT NOT NULL DEFAULT (datetime('now')),
+    resolved_at TEXT
+);
+
+INSERT INTO stuck_agent_detections_new
+    (id, agent_id, session_id, detection_type, severity, details,
+     resolved, resolution_action, detected_at, resolved_at)
+SELECT id, agent_id, session_id, detection_type, severity, details,
+       resolved, resolution_action, detected_at, resolved_at
+FROM stuck_agent_detections;
+
+DROP TABLE stuck_agent_detections;
+ALTER TABLE stuck_agent_detections_new RENAME TO stuck_agent_detections;
+
+CREATE INDEX IF NOT EXISTS idx_stuck_agent_detections_agent_id ON stuck_agent_detections(agent_id);
+CREATE INDEX IF NOT EXISTS idx_stuck_agent_detections_resolved ON stuck_agent_detections(resolved);
+CREATE INDEX IF NOT EXISTS idx_stuck_agent_detections_severity ON stuck_agent_detections(severity);
+CREATE INDEX IF NOT EXISTS idx_stuck_agent_detections_detected_at ON stuck_agent_detections(detected_at);
+CREATE INDEX IF NOT EXISTS idx_stuck_agent_detections_agent_resolved
+ON stuck_agent_detections(agent_id, resolved, detected_at);
+CREATE INDEX IF NOT EXISTS idx_stuck_agent_detections_session_severity
+ON stuck_agent_detections(session_id, severity, resolved);
+
+PRAGMA foreign_keys=ON;
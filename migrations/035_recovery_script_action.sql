@@ -0,0 +1,47 @@
+-- Script Recovery Action
+-- Rebuilds recovery_attempts so the action_type CHECK constraint accepts the
+-- 'script' action (operator-provided remediation scripts).
+-- Only applied when the existing table lacks the new action type.
+
+PRAGMA foreign_keys=OFF;
+
+CREATE TABLE recovery_attempts_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    agent_id TEXT NOT NULL,
+    session_id TEXT,
+    stuck_detection_id INTEGER,
+    action_type TEXT NOT NULL CHECK(action_type IN (
+        'pause_and_alert', 'model_escalation', 'spawn_fixer', 'fresh_retry',
+        'escalate_to_parent', 'retry', 'wait', 'abort', 'script'
+    )),
+    outcome TEXT NOT NULL CHECK(outcome IN (
+        'success', 'failed', 'in_progress', 'cancelled', 'skipped'
+    )),
+    details TEXT NOT NULL DEFAULT '{}',  -- JSON: action details
+    attempt_number INTEGER NOT NULL DEFAULT 1,
+    started_at TEXT NOT NULL DEFAULT (datetime('now')),
+    completed_at TEXT,
+    error_message TEXT,
+    FOREIGN KEY (stuck_detection_id) REFERENCES stuck_agent_detections(id)
+);
+
+INSERT INTO recovery_attempts_new
+    (id, agent_id, session_id, stuck_detection_id, action_type, outcome, details,
+     attempt_number, started_at, completed_at, error_message)
+SELECT id, agent_id, session_id, stuck_detection_id, action_type, outcome, details,
+       attempt_number, started_at, completed_at, error_message
+FROM recovery_attempts;
+
+DROP TABLE recovery_attempts;
+ALTER TABLE recovery_attempts_new RENAME TO recovery_attempts;
+
+CREATE INDEX IF NOT EXISTS idx_recovery_attempts_agent_id ON recovery_attempts(agent_id);
+CREATE INDEX IF NOT EXISTS idx_recovery_attempts_outcome ON recovery_attempts(outcome);
+CREATE INDEX IF NOT EXISTS idx_recovery_attempts_action_type ON recovery_attempts(action_type);
+CREATE INDEX IF NOT EXISTS idx_recovery_attempts_started_at ON recovery_attempts(started_at);
+CREATE INDEX IF NOT EXISTS idx_recovery_attempts_stuck_detection
+ON recovery_attempts(stuck_detection_id, outcome);
+CREATE INDEX IF NOT EXISTS idx_recovery_attempts_session_outcome
+ON recovery_attempts(session_id, outcome);
+
+PRAGMA foreign_keys=ON;
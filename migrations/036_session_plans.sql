@@ -0,0 +1,4 @@
+-- Session Goal and Plan Tracking
+-- Adds the goal/plan representation updated as autonomous session work completes
+
+ALTER TABLE autonomous_sessions ADD COLUMN plan TEXT;  -- JSON: SessionPlan
@@ -0,0 +1,17 @@
+-- Context Summary Index
+-- Stores agent work summaries with embeddings for related-work search
+
+CREATE TABLE IF NOT EXISTS context_summaries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    agent_id TEXT,
+    session_id TEXT,
+    story_id TEXT,
+    summary TEXT NOT NULL,                 -- JSON: ContextSummary
+    embedding TEXT NOT NULL DEFAULT '[]',  -- JSON: embedding vector
+    embedding_model TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_context_summaries_agent_id ON context_summaries(agent_id);
+CREATE INDEX IF NOT EXISTS idx_context_summaries_story_id ON context_summaries(story_id);
+CREATE INDEX IF NOT EXISTS idx_context_summaries_embedding_model ON context_summaries(embedding_model);
@@ -0,0 +1,4 @@
+-- Agent System Prompt Override
+-- Per-agent system prompt that supersedes the agent type's template for that run
+
+ALTER TABLE agents ADD COLUMN system_prompt TEXT;
@@ -0,0 +1,15 @@
+-- Pipeline Stage Result Cache
+-- Records succeeded stages keyed by their inputs so re-runs can skip them
+
+CREATE TABLE IF NOT EXISTS pipeline_stage_cache (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    pipeline_id INTEGER NOT NULL REFERENCES pipelines(id) ON DELETE CASCADE,
+    stage_name TEXT NOT NULL,
+    cache_key TEXT NOT NULL,  -- SHA-256 of commit SHA and stage definition
+    commit_sha TEXT NOT NULL,
+    run_id INTEGER NOT NULL,  -- Run whose stage produced the cached outcome
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(pipeline_id, stage_name, cache_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pipeline_stage_cache_pipeline_id ON pipeline_stage_cache(pipeline_id);
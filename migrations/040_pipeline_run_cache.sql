@@ -0,0 +1,7 @@
+-- Pipeline Run Cache Inputs
+-- Commit the run is built from, whether cached stage results may be reused,
+-- and the run a cached stage's outcome was taken from
+
+ALTER TABLE pipeline_runs ADD COLUMN commit_sha TEXT;
+ALTER TABLE pipeline_runs ADD COLUMN use_cache INTEGER NOT NULL DEFAULT 1;  -- Boolean: 1=reuse cached stages
+ALTER TABLE pipeline_stages ADD COLUMN cached_from_run_id INTEGER;
@@ -0,0 +1,5 @@
+-- Approval Timeout Escalation
+-- Approvers added when an approval first times out, before the timeout action applies
+
+ALTER TABLE approval_requests ADD COLUMN escalate_to TEXT;  -- Comma-separated list of approver identifiers
+ALTER TABLE approval_requests ADD COLUMN escalated_at TEXT;
@@ -0,0 +1,5 @@
+-- Pipeline Stage Attempts
+-- Number of times a stage's agent was started, so failures after retries can
+-- be told apart from first-attempt failures
+
+ALTER TABLE pipeline_stages ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
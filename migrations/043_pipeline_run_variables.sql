@@ -0,0 +1,5 @@
+-- Pipeline Run Variables
+-- Variables supplied by the trigger (e.g. webhook payload fields), stored as a
+-- JSON object and layered over the pipeline's own variables
+
+ALTER TABLE pipeline_runs ADD COLUMN variables TEXT NOT NULL DEFAULT '{}';
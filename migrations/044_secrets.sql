@@ -0,0 +1,10 @@
+-- Secrets Vault
+-- Named secrets that pipeline stages expose to their agents as environment variables
+
+CREATE TABLE IF NOT EXISTS secrets (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    value TEXT NOT NULL,  -- AES-256-GCM encrypted, base64 encoded
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
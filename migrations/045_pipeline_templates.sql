@@ -0,0 +1,11 @@
+-- Pipeline Templates
+-- Parameterized pipeline definitions that pipelines instantiate with extends: or include:
+
+CREATE TABLE IF NOT EXISTS pipeline_templates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT NOT NULL DEFAULT '',
+    yaml TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
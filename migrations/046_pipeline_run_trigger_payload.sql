@@ -0,0 +1,5 @@
+-- Pipeline Run Trigger Payload
+-- JSON payload of the event that triggered a run, readable from stage
+-- condition expressions as trigger.payload
+
+ALTER TABLE pipeline_runs ADD COLUMN trigger_payload TEXT;
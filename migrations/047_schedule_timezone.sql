@@ -0,0 +1,5 @@
+-- Schedule Timezone
+-- IANA timezone the schedule's cron expression is read in, so that
+-- "0 9 * * 1-5" means 9am local time across daylight saving changes
+
+ALTER TABLE schedules ADD COLUMN timezone TEXT NOT NULL DEFAULT 'UTC';
@@ -0,0 +1,6 @@
+-- Schedule Jitter and Overlap Policy
+-- Random delay added to each run time, and what happens when a schedule
+-- comes due while its previous run is still in progress
+
+ALTER TABLE schedules ADD COLUMN jitter_secs INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE schedules ADD COLUMN overlap_policy TEXT NOT NULL DEFAULT 'run_parallel';
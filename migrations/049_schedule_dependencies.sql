@@ -0,0 +1,5 @@
+-- Schedule Dependencies
+-- Name of a schedule whose latest run must have succeeded before this
+-- schedule runs
+
+ALTER TABLE schedules ADD COLUMN depends_on TEXT;
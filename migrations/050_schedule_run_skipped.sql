@@ -0,0 +1,31 @@
+-- Skipped Schedule Runs
+-- Rebuilds schedule_runs so the status CHECK constraint accepts 'skipped'
+-- (runs not started because a dependency was unmet; the reason is kept in
+-- error_message).
+-- Only applied when the existing table lacks the new status.
+
+PRAGMA foreign_keys=OFF;
+
+CREATE TABLE schedule_runs_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    schedule_id INTEGER NOT NULL REFERENCES schedules(id) ON DELETE CASCADE,
+    agent_id TEXT REFERENCES agents(id),
+    started_at TEXT NOT NULL DEFAULT (datetime('now')),
+    completed_at TEXT,
+    status TEXT NOT NULL DEFAULT 'running' CHECK (status IN ('running', 'completed', 'failed', 'skipped')),
+    error_message TEXT
+);
+
+INSERT INTO schedule_runs_new
+    (id, schedule_id, agent_id, started_at, completed_at, status, error_message)
+SELECT id, schedule_id, agent_id, started_at, completed_at, status, error_message
+FROM schedule_runs;
+
+DROP TABLE schedule_runs;
+ALTER TABLE schedule_runs_new RENAME TO schedule_runs;
+
+CREATE INDEX IF NOT EXISTS idx_schedule_runs_schedule ON schedule_runs(schedule_id);
+CREATE INDEX IF NOT EXISTS idx_schedule_runs_status ON schedule_runs(status);
+CREATE INDEX IF NOT EXISTS idx_schedule_runs_started ON schedule_runs(started_at);
+
+PRAGMA foreign_keys=ON;
@@ -0,0 +1,5 @@
+-- One-Shot Schedules
+-- Time of a one-shot schedule's single run; NULL for recurring schedules,
+-- which keep using cron_expression
+
+ALTER TABLE schedules ADD COLUMN run_at TEXT;
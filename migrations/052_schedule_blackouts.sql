@@ -0,0 +1,16 @@
+-- Schedule Blackout Windows
+-- Periods (holidays, change freezes) during which scheduled runs are skipped.
+-- A window with a NULL schedule_id applies to every schedule.
+
+CREATE TABLE IF NOT EXISTS schedule_blackouts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    schedule_id INTEGER REFERENCES schedules(id) ON DELETE CASCADE,
+    starts_at TEXT NOT NULL,
+    ends_at TEXT NOT NULL,
+    reason TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_schedule_blackouts_schedule ON schedule_blackouts(schedule_id);
+CREATE INDEX IF NOT EXISTS idx_schedule_blackouts_ends ON schedule_blackouts(ends_at);
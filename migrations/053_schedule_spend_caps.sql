@@ -0,0 +1,7 @@
+-- Schedule Spend Caps
+-- Token and estimated-cost limits on a schedule's runs per period (daily,
+-- weekly, monthly); NULL caps are unlimited
+
+ALTER TABLE schedules ADD COLUMN max_tokens INTEGER;
+ALTER TABLE schedules ADD COLUMN max_cost_usd REAL;
+ALTER TABLE schedules ADD COLUMN spend_cap_period TEXT NOT NULL DEFAULT 'monthly';
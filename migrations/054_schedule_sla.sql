@@ -0,0 +1,7 @@
+-- Schedule Run Metrics and SLA
+-- Occurrences that passed without a run, in total and since the last run
+-- started, and how many consecutive misses raise an alert (NULL for none)
+
+ALTER TABLE schedules ADD COLUMN missed_runs INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE schedules ADD COLUMN consecutive_missed_runs INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE schedules ADD COLUMN max_consecutive_misses INTEGER;
@@ -0,0 +1,11 @@
+-- Custom Inbound Webhooks
+-- YAML definitions mapping payloads posted to /webhooks/custom/<name> into
+-- webhook events
+
+CREATE TABLE IF NOT EXISTS custom_webhooks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    definition TEXT NOT NULL,  -- YAML custom webhook definition
+    enabled INTEGER NOT NULL DEFAULT 1,  -- Boolean: 1=enabled, 0=disabled
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
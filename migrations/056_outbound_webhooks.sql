@@ -0,0 +1,33 @@
+-- Outbound Webhooks
+-- Subscribers registered for orchestrate events and the log of signed
+-- payloads delivered to them
+
+CREATE TABLE IF NOT EXISTS outbound_webhooks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    url TEXT NOT NULL,
+    secret TEXT,  -- HMAC-SHA256 signing secret
+    events TEXT NOT NULL,  -- JSON array of event filters (e.g. "agent.completed", "pipeline.*", "*")
+    description TEXT,
+    enabled INTEGER NOT NULL DEFAULT 1,  -- Boolean: 1=enabled, 0=disabled
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS outbound_webhook_deliveries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    webhook_id INTEGER NOT NULL REFERENCES outbound_webhooks(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL,
+    payload TEXT NOT NULL,  -- JSON body posted to the subscriber
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'succeeded', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 5,
+    response_status INTEGER,  -- HTTP status of the last attempt
+    error_message TEXT,
+    next_attempt_at TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    delivered_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbound_webhook_deliveries_due
+    ON outbound_webhook_deliveries(status, next_attempt_at);
+CREATE INDEX IF NOT EXISTS idx_outbound_webhook_deliveries_webhook
+    ON outbound_webhook_deliveries(webhook_id, created_at);
@@ -0,0 +1,13 @@
+-- Webhook Source Signatures
+-- Per-source signature schemes and secrets for inbound webhooks, replacing
+-- the single global secret for the sources configured here
+
+CREATE TABLE IF NOT EXISTS webhook_sources (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,  -- github, gitlab, or custom:<name>
+    scheme TEXT NOT NULL CHECK (scheme IN ('hmac-sha256', 'hmac-sha512', 'ed25519', 'token')),
+    header TEXT NOT NULL,  -- Header the signature or token is read from
+    secrets TEXT NOT NULL,  -- JSON array of {secret, created_at, expires_at}
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,8 @@
+-- Email Notifications
+-- Per-user email address and opt-ins for approval request, incident
+-- escalation, and daily cost summary emails
+
+ALTER TABLE slack_user_mappings ADD COLUMN email TEXT;
+ALTER TABLE slack_user_mappings ADD COLUMN email_on_approval INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE slack_user_mappings ADD COLUMN email_on_incident INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE slack_user_mappings ADD COLUMN email_cost_summary INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,19 @@
+-- On-call Pages
+-- Incidents and alerts paged to an on-call provider, keyed by the
+-- deduplication key sent with every event so acknowledgements and
+-- resolutions can be synced in both directions
+
+CREATE TABLE IF NOT EXISTS oncall_pages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    provider TEXT NOT NULL,  -- pagerduty
+    routing_key TEXT NOT NULL,  -- Integration key of the provider service paged
+    dedup_key TEXT NOT NULL,  -- orchestrate:incident:<id> or orchestrate:alert:<id>
+    resource_type TEXT NOT NULL CHECK (resource_type IN ('incident', 'alert')),
+    resource_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'triggered' CHECK (status IN ('triggered', 'acknowledged', 'resolved')),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (provider, routing_key, dedup_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_oncall_pages_dedup_key ON oncall_pages(dedup_key);
@@ -0,0 +1,44 @@
+-- Jira Issue Synchronization
+-- Per-project Jira configuration, the Jira issues created for epics and
+-- stories, and the Jira comments pulled back as story context
+
+CREATE TABLE IF NOT EXISTS jira_projects (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    base_url TEXT NOT NULL,  -- e.g. https://example.atlassian.net
+    project_key TEXT NOT NULL,
+    epic_prefix TEXT NOT NULL DEFAULT '',  -- Epics whose ID starts with this prefix sync here
+    epic_issue_type TEXT NOT NULL DEFAULT 'Epic',
+    story_issue_type TEXT NOT NULL DEFAULT 'Story',
+    status_mapping TEXT NOT NULL DEFAULT '{}',  -- JSON object of story status -> Jira status
+    field_mapping TEXT NOT NULL DEFAULT '{}',  -- JSON object of story field -> Jira field ID
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS jira_issue_links (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id INTEGER NOT NULL REFERENCES jira_projects(id) ON DELETE CASCADE,
+    resource_type TEXT NOT NULL CHECK (resource_type IN ('epic', 'story')),
+    resource_id TEXT NOT NULL,
+    issue_key TEXT NOT NULL,
+    synced_status TEXT,  -- Story status the issue was last transitioned for
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(resource_type, resource_id)
+);
+
+CREATE TABLE IF NOT EXISTS jira_comments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    story_id TEXT NOT NULL,
+    issue_key TEXT NOT NULL,
+    comment_id TEXT NOT NULL,
+    author TEXT,
+    body TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(issue_key, comment_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_jira_comments_story ON jira_comments(story_id);
@@ -0,0 +1,29 @@
+-- Linear Issue Synchronization
+-- Per-team Linear configuration and the Linear issues linked to stories or
+-- to the agents they spawned
+
+CREATE TABLE IF NOT EXISTS linear_teams (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    linear_team_id TEXT NOT NULL,  -- Linear team UUID
+    epic_prefix TEXT NOT NULL DEFAULT '',  -- Epics whose ID starts with this prefix sync here
+    status_mapping TEXT NOT NULL DEFAULT '{}',  -- JSON object of story status -> workflow state name
+    trigger_label TEXT NOT NULL DEFAULT 'orchestrate',  -- Issues with this label spawn an agent
+    agent_type TEXT NOT NULL DEFAULT 'story_developer',
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS linear_issue_links (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    team_id INTEGER NOT NULL REFERENCES linear_teams(id) ON DELETE CASCADE,
+    issue_id TEXT NOT NULL UNIQUE,  -- Linear issue UUID
+    identifier TEXT NOT NULL,  -- e.g. ENG-123
+    url TEXT,
+    story_id TEXT UNIQUE,  -- Story synced to the issue
+    agent_id TEXT,  -- Agent spawned from the issue
+    synced_status TEXT,  -- Story status the issue was last moved for
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,19 @@
+-- GitHub Issue Triage
+-- Automated triage of opened issues, kept to find duplicates among later
+-- issues of the same repository
+
+CREATE TABLE IF NOT EXISTS issue_triages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    repository TEXT NOT NULL,  -- e.g. owner/repo
+    issue_number INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL DEFAULT '',
+    labels TEXT NOT NULL DEFAULT '[]',  -- JSON array of labels after triage
+    duplicate_of INTEGER,  -- Issue number this one most likely duplicates
+    complexity TEXT NOT NULL CHECK (complexity IN ('small', 'medium', 'large', 'xlarge')),
+    story_id TEXT,  -- Story created for the issue
+    agent_id TEXT,  -- Issue-triager agent spawned for the issue
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(repository, issue_number)
+);
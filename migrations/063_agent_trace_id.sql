@@ -0,0 +1,4 @@
+-- Agent Trace ID
+-- OpenTelemetry trace ID of the agent's latest run, linking it to exported spans
+
+ALTER TABLE agents ADD COLUMN trace_id TEXT;
@@ -0,0 +1,18 @@
+-- Artifacts
+-- Reports and agent outputs kept in object storage, listed by the web UI
+-- with signed download URLs
+
+CREATE TABLE IF NOT EXISTS artifacts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL CHECK (kind IN ('security_report', 'coverage_report', 'postmortem', 'agent_artifact')),
+    object_key TEXT NOT NULL UNIQUE,  -- Key in the object store
+    name TEXT NOT NULL,
+    owner_id TEXT,  -- Scan, project, incident, or agent the artifact belongs to
+    content_type TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL DEFAULT 0,
+    backend TEXT NOT NULL,  -- s3, gcs, or local
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_artifacts_kind ON artifacts(kind, created_at);
+CREATE INDEX IF NOT EXISTS idx_artifacts_owner ON artifacts(owner_id);
@@ -0,0 +1,4 @@
+-- Google Chat Webhooks
+-- Incoming webhook URLs selected per notification type, as a JSON object
+
+ALTER TABLE slack_channel_configs ADD COLUMN google_chat_webhooks TEXT NOT NULL DEFAULT '{}';
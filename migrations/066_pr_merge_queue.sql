@@ -0,0 +1,4 @@
+-- PR Merge Queue
+-- Position of a PR in the GitHub merge queue, while it is enqueued
+
+ALTER TABLE pr_queue ADD COLUMN merge_queue_position INTEGER;
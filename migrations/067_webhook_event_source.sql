@@ -0,0 +1,5 @@
+-- Webhook Event Source
+-- Receiver an event came in through: github, gitlab, or custom:<name>
+
+ALTER TABLE webhook_events ADD COLUMN source TEXT NOT NULL DEFAULT 'github';
+CREATE INDEX IF NOT EXISTS idx_webhook_events_source ON webhook_events(source, status);
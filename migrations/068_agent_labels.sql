@@ -0,0 +1,4 @@
+-- Agent Labels
+-- Key/value labels for selecting groups of agents, stored as a JSON object
+
+ALTER TABLE agents ADD COLUMN labels TEXT NOT NULL DEFAULT '{}';
@@ -0,0 +1,31 @@
+-- Export Artifacts
+-- Rebuilds artifacts so the kind CHECK constraint accepts 'export' (files of
+-- data export jobs).
+-- Only applied when the existing table lacks the new kind.
+
+PRAGMA foreign_keys=OFF;
+
+CREATE TABLE artifacts_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL CHECK (kind IN ('security_report', 'coverage_report', 'postmortem', 'agent_artifact', 'export')),
+    object_key TEXT NOT NULL UNIQUE,  -- Key in the object store
+    name TEXT NOT NULL,
+    owner_id TEXT,  -- Scan, project, incident, agent, or export job the artifact belongs to
+    content_type TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL DEFAULT 0,
+    backend TEXT NOT NULL,  -- s3, gcs, or local
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+INSERT INTO artifacts_new
+    (id, kind, object_key, name, owner_id, content_type, size_bytes, backend, created_at)
+SELECT id, kind, object_key, name, owner_id, content_type, size_bytes, backend, created_at
+FROM artifacts;
+
+DROP TABLE artifacts;
+ALTER TABLE artifacts_new RENAME TO artifacts;
+
+CREATE INDEX IF NOT EXISTS idx_artifacts_kind ON artifacts(kind, created_at);
+CREATE INDEX IF NOT EXISTS idx_artifacts_owner ON artifacts(owner_id);
+
+PRAGMA foreign_keys=ON;
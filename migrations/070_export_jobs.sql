@@ -0,0 +1,19 @@
+-- Export Jobs
+-- Data exports run in the background, whose files are kept as artifacts
+
+CREATE TABLE IF NOT EXISTS export_jobs (
+    id TEXT PRIMARY KEY,
+    dataset TEXT NOT NULL,  -- token_usage, costs, audit_log, or agent_history
+    format TEXT NOT NULL,  -- csv or parquet
+    from_time TEXT,
+    to_time TEXT,
+    status TEXT NOT NULL DEFAULT 'running' CHECK (status IN ('running', 'completed', 'failed')),
+    row_count INTEGER,
+    artifact_id INTEGER REFERENCES artifacts(id) ON DELETE SET NULL,
+    error_message TEXT,
+    requested_by TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    completed_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_export_jobs_created ON export_jobs(created_at);
@@ -0,0 +1,10 @@
+-- Rollback Agent Events Schema
+-- Reverses migration 028_agent_events.sql
+
+-- Drop indexes first
+DROP INDEX IF EXISTS idx_agent_events_created_at;
+DROP INDEX IF EXISTS idx_agent_events_agent_type;
+DROP INDEX IF EXISTS idx_agent_events_agent_id;
+
+-- Drop table
+DROP TABLE IF EXISTS agent_events;
@@ -0,0 +1,8 @@
+-- Rollback Custom Agent Types Schema
+-- Reverses migration 029_custom_agent_types.sql
+
+-- Drop indexes first
+DROP INDEX IF EXISTS idx_custom_agent_types_base_type;
+
+-- Drop table
+DROP TABLE IF EXISTS custom_agent_types;
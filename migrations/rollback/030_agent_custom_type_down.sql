@@ -0,0 +1,4 @@
+-- Rollback Agent Custom Type Column
+-- Reverses migration 030_agent_custom_type.sql (requires SQLite 3.35+)
+
+ALTER TABLE agents DROP COLUMN custom_type;
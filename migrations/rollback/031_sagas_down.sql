@@ -0,0 +1,10 @@
+-- Rollback Saga Compensation Schema
+-- Reverses migration 031_sagas.sql
+
+-- Drop indexes first
+DROP INDEX IF EXISTS idx_saga_compensations_saga_id;
+DROP INDEX IF EXISTS idx_sagas_status;
+
+-- Drop tables
+DROP TABLE IF EXISTS saga_compensations;
+DROP TABLE IF EXISTS sagas;
@@ -0,0 +1,8 @@
+-- Rollback Token Usage Attribution
+-- Reverses migration 032_token_attribution.sql (requires SQLite 3.35+)
+
+DROP INDEX IF EXISTS idx_session_stats_story;
+DROP INDEX IF EXISTS idx_session_stats_epic;
+
+ALTER TABLE session_token_stats DROP COLUMN story_id;
+ALTER TABLE session_token_stats DROP COLUMN epic_id;
@@ -0,0 +1,4 @@
+-- Rollback Stuck Detection Configuration
+-- Reverses migration 033_stuck_detection_configs.sql
+
+DROP TABLE IF EXISTS stuck_detection_configs;
@@ -0,0 +1,6 @@
+-- Rollback Stuck Detection Types
+-- Reverses migration 034_stuck_detection_types.sql by removing detections of
+-- the new types; the wider CHECK constraint is left in place.
+
+DELETE FROM stuck_agent_detections
+WHERE detection_type IN ('no_file_changes', 'token_burn');
@@ -0,0 +1,5 @@
+-- Rollback Script Recovery Action
+-- Reverses migration 035_recovery_script_action.sql by removing script
+-- attempts; the wider CHECK constraint is left in place.
+
+DELETE FROM recovery_attempts WHERE action_type = 'script';
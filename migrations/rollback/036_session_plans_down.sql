@@ -0,0 +1,4 @@
+-- Rollback Session Goal and Plan Tracking
+-- Reverses migration 036_session_plans.sql (requires SQLite 3.35+)
+
+ALTER TABLE autonomous_sessions DROP COLUMN plan;
@@ -0,0 +1,8 @@
+-- Rollback Context Summary Index
+-- Reverses migration 037_context_summaries.sql
+
+DROP INDEX IF EXISTS idx_context_summaries_embedding_model;
+DROP INDEX IF EXISTS idx_context_summaries_story_id;
+DROP INDEX IF EXISTS idx_context_summaries_agent_id;
+
+DROP TABLE IF EXISTS context_summaries;
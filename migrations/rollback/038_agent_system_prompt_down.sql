@@ -0,0 +1,4 @@
+-- Rollback Agent System Prompt Override
+-- Reverses migration 038_agent_system_prompt.sql (requires SQLite 3.35+)
+
+ALTER TABLE agents DROP COLUMN system_prompt;
@@ -0,0 +1,6 @@
+-- Rollback Pipeline Stage Result Cache
+-- Reverses migration 039_pipeline_stage_cache.sql
+
+DROP INDEX IF EXISTS idx_pipeline_stage_cache_pipeline_id;
+
+DROP TABLE IF EXISTS pipeline_stage_cache;
@@ -0,0 +1,6 @@
+-- Rollback Pipeline Run Cache Inputs
+-- Reverses migration 040_pipeline_run_cache.sql (requires SQLite 3.35+)
+
+ALTER TABLE pipeline_stages DROP COLUMN cached_from_run_id;
+ALTER TABLE pipeline_runs DROP COLUMN use_cache;
+ALTER TABLE pipeline_runs DROP COLUMN commit_sha;
@@ -0,0 +1,5 @@
+-- Rollback Approval Timeout Escalation
+-- Reverses migration 041_approval_escalation.sql (requires SQLite 3.35+)
+
+ALTER TABLE approval_requests DROP COLUMN escalated_at;
+ALTER TABLE approval_requests DROP COLUMN escalate_to;
@@ -0,0 +1,4 @@
+-- Rollback Pipeline Stage Attempts
+-- Reverses migration 042_pipeline_stage_attempts.sql (requires SQLite 3.35+)
+
+ALTER TABLE pipeline_stages DROP COLUMN attempts;
@@ -0,0 +1,4 @@
+-- Rollback Pipeline Run Variables
+-- Reverses migration 043_pipeline_run_variables.sql (requires SQLite 3.35+)
+
+ALTER TABLE pipeline_runs DROP COLUMN variables;
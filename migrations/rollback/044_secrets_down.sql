@@ -0,0 +1,4 @@
+-- Rollback Secrets Vault
+-- Reverses migration 044_secrets.sql
+
+DROP TABLE IF EXISTS secrets;
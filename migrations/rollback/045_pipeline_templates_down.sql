@@ -0,0 +1,4 @@
+-- Rollback Pipeline Templates
+-- Reverses migration 045_pipeline_templates.sql
+
+DROP TABLE IF EXISTS pipeline_templates;
@@ -0,0 +1,4 @@
+-- Rollback Pipeline Run Trigger Payload
+-- Reverses migration 046_pipeline_run_trigger_payload.sql (requires SQLite 3.35+)
+
+ALTER TABLE pipeline_runs DROP COLUMN trigger_payload;
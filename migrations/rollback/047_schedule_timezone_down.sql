@@ -0,0 +1,4 @@
+-- Rollback Schedule Timezone
+-- Reverses migration 047_schedule_timezone.sql (requires SQLite 3.35+)
+
+ALTER TABLE schedules DROP COLUMN timezone;
@@ -0,0 +1,5 @@
+-- Rollback Schedule Jitter and Overlap Policy
+-- Reverses migration 048_schedule_overlap.sql (requires SQLite 3.35+)
+
+ALTER TABLE schedules DROP COLUMN overlap_policy;
+ALTER TABLE schedules DROP COLUMN jitter_secs;
@@ -0,0 +1,4 @@
+-- Rollback Schedule Dependencies
+-- Reverses migration 049_schedule_dependencies.sql (requires SQLite 3.35+)
+
+ALTER TABLE schedules DROP COLUMN depends_on;
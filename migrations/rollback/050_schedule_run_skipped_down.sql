@@ -0,0 +1,5 @@
+-- Rollback Skipped Schedule Runs
+-- Reverses migration 050_schedule_run_skipped.sql by removing skipped runs;
+-- the wider CHECK constraint is left in place.
+
+DELETE FROM schedule_runs WHERE status = 'skipped';
@@ -0,0 +1,5 @@
+-- Rollback One-Shot Schedules
+-- Reverses migration 051_schedule_run_at.sql (requires SQLite 3.35+)
+
+DELETE FROM schedules WHERE run_at IS NOT NULL;
+ALTER TABLE schedules DROP COLUMN run_at;
@@ -0,0 +1,6 @@
+-- Rollback Schedule Blackout Windows
+-- Reverses migration 052_schedule_blackouts.sql
+
+DROP INDEX IF EXISTS idx_schedule_blackouts_ends;
+DROP INDEX IF EXISTS idx_schedule_blackouts_schedule;
+DROP TABLE IF EXISTS schedule_blackouts;
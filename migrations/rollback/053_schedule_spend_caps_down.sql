@@ -0,0 +1,6 @@
+-- Rollback Schedule Spend Caps
+-- Reverses migration 053_schedule_spend_caps.sql (requires SQLite 3.35+)
+
+ALTER TABLE schedules DROP COLUMN spend_cap_period;
+ALTER TABLE schedules DROP COLUMN max_cost_usd;
+ALTER TABLE schedules DROP COLUMN max_tokens;
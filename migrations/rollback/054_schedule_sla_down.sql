@@ -0,0 +1,6 @@
+-- Rollback Schedule Run Metrics and SLA
+-- Reverses migration 054_schedule_sla.sql (requires SQLite 3.35+)
+
+ALTER TABLE schedules DROP COLUMN max_consecutive_misses;
+ALTER TABLE schedules DROP COLUMN consecutive_missed_runs;
+ALTER TABLE schedules DROP COLUMN missed_runs;
@@ -0,0 +1,4 @@
+-- Rollback Custom Inbound Webhooks
+-- Reverses migration 055_custom_webhooks.sql
+
+DROP TABLE IF EXISTS custom_webhooks;
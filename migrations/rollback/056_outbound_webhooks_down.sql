@@ -0,0 +1,7 @@
+-- Rollback Outbound Webhooks
+-- Reverses migration 056_outbound_webhooks.sql
+
+DROP INDEX IF EXISTS idx_outbound_webhook_deliveries_webhook;
+DROP INDEX IF EXISTS idx_outbound_webhook_deliveries_due;
+DROP TABLE IF EXISTS outbound_webhook_deliveries;
+DROP TABLE IF EXISTS outbound_webhooks;
@@ -0,0 +1,4 @@
+-- Rollback Webhook Source Signatures
+-- Reverses migration 057_webhook_sources.sql
+
+DROP TABLE IF EXISTS webhook_sources;
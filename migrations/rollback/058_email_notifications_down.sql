@@ -0,0 +1,7 @@
+-- Rollback Email Notifications
+-- Reverses migration 058_email_notifications.sql (requires SQLite 3.35+)
+
+ALTER TABLE slack_user_mappings DROP COLUMN email_cost_summary;
+ALTER TABLE slack_user_mappings DROP COLUMN email_on_incident;
+ALTER TABLE slack_user_mappings DROP COLUMN email_on_approval;
+ALTER TABLE slack_user_mappings DROP COLUMN email;
@@ -0,0 +1,4 @@
+-- Rollback On-call Pages
+-- Reverses migration 059_oncall_pages.sql
+
+DROP TABLE IF EXISTS oncall_pages;
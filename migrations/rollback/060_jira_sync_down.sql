@@ -0,0 +1,6 @@
+-- Rollback Jira Issue Synchronization
+-- Reverses migration 060_jira_sync.sql
+
+DROP TABLE IF EXISTS jira_comments;
+DROP TABLE IF EXISTS jira_issue_links;
+DROP TABLE IF EXISTS jira_projects;
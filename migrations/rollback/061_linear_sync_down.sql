@@ -0,0 +1,5 @@
+-- Rollback Linear Issue Synchronization
+-- Reverses migration 061_linear_sync.sql
+
+DROP TABLE IF EXISTS linear_issue_links;
+DROP TABLE IF EXISTS linear_teams;
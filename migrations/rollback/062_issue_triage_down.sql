@@ -0,0 +1,4 @@
+-- Rollback GitHub Issue Triage
+-- Reverses migration 062_issue_triage.sql
+
+DROP TABLE IF EXISTS issue_triages;
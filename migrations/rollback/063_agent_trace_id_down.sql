@@ -0,0 +1,4 @@
+-- Rollback Agent Trace ID
+-- Reverses migration 063_agent_trace_id.sql (requires SQLite 3.35+)
+
+ALTER TABLE agents DROP COLUMN trace_id;
@@ -0,0 +1,6 @@
+-- Rollback Artifacts
+-- Reverses migration 064_artifacts.sql
+
+DROP INDEX IF EXISTS idx_artifacts_owner;
+DROP INDEX IF EXISTS idx_artifacts_kind;
+DROP TABLE IF EXISTS artifacts;
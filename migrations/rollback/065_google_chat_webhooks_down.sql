@@ -0,0 +1,4 @@
+-- Rollback Google Chat Webhooks
+-- Reverses migration 065_google_chat_webhooks.sql (requires SQLite 3.35+)
+
+ALTER TABLE slack_channel_configs DROP COLUMN google_chat_webhooks;
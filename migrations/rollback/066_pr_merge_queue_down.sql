@@ -0,0 +1,4 @@
+-- Rollback PR Merge Queue
+-- Reverses migration 066_pr_merge_queue.sql (requires SQLite 3.35+)
+
+ALTER TABLE pr_queue DROP COLUMN merge_queue_position;
@@ -0,0 +1,5 @@
+-- Rollback Webhook Event Source
+-- Reverses migration 067_webhook_event_source.sql (requires SQLite 3.35+)
+
+DROP INDEX IF EXISTS idx_webhook_events_source;
+ALTER TABLE webhook_events DROP COLUMN source;
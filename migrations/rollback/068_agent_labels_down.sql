@@ -0,0 +1,4 @@
+-- Rollback Agent Labels
+-- Reverses migration 068_agent_labels.sql (requires SQLite 3.35+)
+
+ALTER TABLE agents DROP COLUMN labels;
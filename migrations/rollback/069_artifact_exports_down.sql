@@ -0,0 +1,6 @@
+-- Rollback Export Artifacts
+-- Reverses migration 069_artifact_exports.sql by removing export artifacts;
+-- the wider CHECK constraint is left in place. Their files stay in the
+-- object store.
+
+DELETE FROM artifacts WHERE kind = 'export';
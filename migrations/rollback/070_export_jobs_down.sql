@@ -0,0 +1,5 @@
+-- Rollback Export Jobs
+-- Reverses migration 070_export_jobs.sql
+
+DROP INDEX IF EXISTS idx_export_jobs_created;
+DROP TABLE IF EXISTS export_jobs;
@@ -519,7 +519,8 @@ mod api {
 
         assert_eq!(response.status(), StatusCode::OK);
         let body = body_to_string(response.into_body()).await;
-        let messages: Vec<serde_json::Value> = serde_json::from_str(&body).unwrap();
+        let page: serde_json::Value = serde_json::from_str(&body).unwrap();
+        let messages = page["items"].as_array().unwrap();
 
         assert_eq!(messages.len(), 4);
         assert_eq!(messages[0]["role"], "user");
@@ -25,6 +25,9 @@ async fn test_complete_ci_cd_pipeline() {
         version: 1,
         triggers: vec![],
         variables,
+        notifications: None,
+        concurrency: None,
+        max_parallel: None,
         stages: vec![
             // Stage 1: Lint (no dependencies)
             StageDefinition {
@@ -32,13 +35,19 @@ async fn test_complete_ci_cd_pipeline() {
                 agent: "linter".to_string(),
                 task: "Run code linter".to_string(),
                 timeout: Some("5m".to_string()),
+                retry: None,
                 on_failure: Some(FailureAction::Halt),
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec![],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             },
             // Stage 2: Test (runs in parallel with lint)
@@ -47,13 +56,19 @@ async fn test_complete_ci_cd_pipeline() {
                 agent: "tester".to_string(),
                 task: "Run unit tests".to_string(),
                 timeout: Some("10m".to_string()),
+                retry: None,
                 on_failure: Some(FailureAction::Halt),
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec![],
                 parallel_with: Some("lint".to_string()),
+                matrix: None,
                 when: None,
             },
             // Stage 3: Build (depends on lint and test)
@@ -62,13 +77,19 @@ async fn test_complete_ci_cd_pipeline() {
                 agent: "builder".to_string(),
                 task: "Build version ${version}".to_string(), // Variable substitution
                 timeout: Some("15m".to_string()),
+                retry: None,
                 on_failure: Some(FailureAction::Halt),
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec!["lint".to_string(), "test".to_string()],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             },
             // Stage 4: Security scan (depends on build)
@@ -77,13 +98,19 @@ async fn test_complete_ci_cd_pipeline() {
                 agent: "security-scanner".to_string(),
                 task: "Run security scan".to_string(),
                 timeout: Some("10m".to_string()),
+                retry: None,
                 on_failure: Some(FailureAction::Halt),
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec!["build".to_string()],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             },
             // Stage 5: Deploy to staging (depends on security scan)
@@ -92,13 +119,19 @@ async fn test_complete_ci_cd_pipeline() {
                 agent: "deployer".to_string(),
                 task: "Deploy to ${environment}".to_string(), // Variable substitution
                 timeout: Some("20m".to_string()),
+                retry: None,
                 on_failure: Some(FailureAction::Halt),
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: Some("staging".to_string()),
+                secrets: Vec::new(),
                 depends_on: vec!["security-scan".to_string()],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             },
             // Stage 6: Smoke tests (depends on deploy)
@@ -107,13 +140,19 @@ async fn test_complete_ci_cd_pipeline() {
                 agent: "smoke-tester".to_string(),
                 task: "Run smoke tests on ${environment}".to_string(),
                 timeout: Some("5m".to_string()),
+                retry: None,
                 on_failure: Some(FailureAction::Halt),
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec!["deploy-staging".to_string()],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             },
         ],
@@ -179,18 +218,27 @@ async fn test_pipeline_with_timeout() {
         version: 1,
         triggers: vec![],
         variables: HashMap::new(),
+        notifications: None,
+        concurrency: None,
+        max_parallel: None,
         stages: vec![StageDefinition {
             name: "quick-task".to_string(),
             agent: "worker".to_string(),
             task: "Quick task".to_string(),
             timeout: Some("1h".to_string()), // 1 hour timeout
+            retry: None,
             on_failure: None,
             rollback_to: None,
+            rollback: None,
+            compensation: Vec::new(),
             requires_approval: false,
             approvers: vec![],
+            approval_timeout: None,
             environment: None,
+            secrets: Vec::new(),
             depends_on: vec![],
             parallel_with: None,
+            matrix: None,
             when: None,
         }],
     };
@@ -233,19 +281,28 @@ async fn test_multiple_parallel_stages() {
         version: 1,
         triggers: vec![],
         variables: HashMap::new(),
+        notifications: None,
+        concurrency: None,
+        max_parallel: None,
         stages: vec![
             StageDefinition {
                 name: "init".to_string(),
                 agent: "initializer".to_string(),
                 task: "Initialize".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec![],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             },
             // Three stages that run in parallel after init
@@ -254,13 +311,19 @@ async fn test_multiple_parallel_stages() {
                 agent: "worker-a".to_string(),
                 task: "Task A".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec!["init".to_string()],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             },
             StageDefinition {
@@ -268,13 +331,19 @@ async fn test_multiple_parallel_stages() {
                 agent: "worker-b".to_string(),
                 task: "Task B".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec!["init".to_string()],
                 parallel_with: Some("parallel-a".to_string()),
+                matrix: None,
                 when: None,
             },
             StageDefinition {
@@ -282,13 +351,19 @@ async fn test_multiple_parallel_stages() {
                 agent: "worker-c".to_string(),
                 task: "Task C".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec!["init".to_string()],
                 parallel_with: Some("parallel-a".to_string()),
+                matrix: None,
                 when: None,
             },
             // Final stage that depends on all parallel stages
@@ -297,17 +372,23 @@ async fn test_multiple_parallel_stages() {
                 agent: "finalizer".to_string(),
                 task: "Finalize".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec![
                     "parallel-a".to_string(),
                     "parallel-b".to_string(),
                     "parallel-c".to_string(),
                 ],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             },
         ],
@@ -350,19 +431,28 @@ async fn test_complex_dependency_graph() {
         version: 1,
         triggers: vec![],
         variables: HashMap::new(),
+        notifications: None,
+        concurrency: None,
+        max_parallel: None,
         stages: vec![
             StageDefinition {
                 name: "start".to_string(),
                 agent: "starter".to_string(),
                 task: "Start".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec![],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             },
             StageDefinition {
@@ -370,13 +460,19 @@ async fn test_complex_dependency_graph() {
                 agent: "left-worker".to_string(),
                 task: "Left path".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec!["start".to_string()],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             },
             StageDefinition {
@@ -384,13 +480,19 @@ async fn test_complex_dependency_graph() {
                 agent: "right-worker".to_string(),
                 task: "Right path".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec!["start".to_string()],
                 parallel_with: Some("left".to_string()),
+                matrix: None,
                 when: None,
             },
             StageDefinition {
@@ -398,13 +500,19 @@ async fn test_complex_dependency_graph() {
                 agent: "ender".to_string(),
                 task: "End".to_string(),
                 timeout: None,
+                retry: None,
                 on_failure: None,
                 rollback_to: None,
+                rollback: None,
+                compensation: Vec::new(),
                 requires_approval: false,
                 approvers: vec![],
+                approval_timeout: None,
                 environment: None,
+                secrets: Vec::new(),
                 depends_on: vec!["left".to_string(), "right".to_string()],
                 parallel_with: None,
+                matrix: None,
                 when: None,
             },
         ],